@@ -0,0 +1,5 @@
+fn main() {
+    // Linker setup for the Node.js addon (only needed when building the `node` feature)
+    #[cfg(feature = "node")]
+    napi_build::setup();
+}
@@ -0,0 +1,24 @@
+//! Prints the venue × capability matrix of every CEX integration as Markdown (or CSV).
+//! With `--health` every venue's health check runs first and its result is added.
+//!
+//! ```text
+//! cargo run --example capability_matrix
+//! cargo run --example capability_matrix -- --health --csv
+//! ```
+
+use aeon_market_scanner_rs::CapabilityReport;
+use std::time::Duration;
+
+#[tokio::main]
+async fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let mut report = CapabilityReport::new();
+    if args.iter().any(|arg| arg == "--health") {
+        report = report.with_health_checks(Duration::from_secs(10)).await;
+    }
+    if args.iter().any(|arg| arg == "--csv") {
+        print!("{}", report.to_csv());
+    } else {
+        print!("{}", report.to_markdown());
+    }
+}
@@ -0,0 +1,127 @@
+//! Service configuration, read once at startup. Thresholds, filters and fees live in the
+//! separate scanner settings file, which is reloaded while the service runs.
+
+use aeon_market_scanner_rs::{AlertTemplate, CexExchange, MarketScannerError};
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+
+/// Message used when a sink has no template.
+const DEFAULT_TEMPLATE: &str = "{{symbol}}: buy {{source_exchange}} @ {{round effective_ask 4}}, \
+     sell {{destination_exchange}} @ {{round effective_bid 4}}, {{round spread_percentage 3}}%";
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ServiceConfig {
+    pub symbols: Vec<String>,
+    /// WebSocket venues to scan
+    pub exchanges: Vec<CexExchange>,
+    /// Scanner settings (`ScannerSettings` JSON), relative to this file; reloaded on change
+    #[serde(default)]
+    pub settings_file: Option<PathBuf>,
+    /// An opportunity alerts again after it was gone for this long
+    #[serde(default = "default_alert_expiry_ms")]
+    pub alert_expiry_ms: u64,
+    /// JSON file keeping alerted opportunities across restarts
+    #[serde(default)]
+    pub alert_store: Option<PathBuf>,
+    /// Opportunities unseen for this long are reported closed
+    #[serde(default = "default_close_after_ms")]
+    pub close_after_ms: u64,
+    #[serde(default)]
+    pub telegram: Option<TelegramConfig>,
+    #[serde(default)]
+    pub webhook: Option<WebhookConfig>,
+    /// Address of the Prometheus `/metrics` endpoint (disabled when unset)
+    #[serde(default)]
+    pub metrics_addr: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct TelegramConfig {
+    /// Bot token; falls back to the `TELEGRAM_BOT_TOKEN` environment variable
+    #[serde(default)]
+    pub bot_token: Option<String>,
+    pub chat_id: String,
+    #[serde(default)]
+    pub template: Option<AlertTemplate>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct WebhookConfig {
+    pub url: String,
+    /// Posts `{"text": <rendered>}` when set, the opportunity JSON otherwise
+    #[serde(default)]
+    pub template: Option<AlertTemplate>,
+}
+
+fn default_alert_expiry_ms() -> u64 {
+    300_000
+}
+
+fn default_close_after_ms() -> u64 {
+    30_000
+}
+
+impl ServiceConfig {
+    /// Reads and checks the configuration at `path`; relative paths inside it are resolved
+    /// against its directory.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, MarketScannerError> {
+        let path = path.as_ref();
+        let json = std::fs::read_to_string(path).map_err(|e| {
+            MarketScannerError::InvalidConfig(format!(
+                "Cannot read service config {}: {}",
+                path.display(),
+                e
+            ))
+        })?;
+        let mut config: Self = serde_json::from_str(&json)?;
+        let dir = path.parent().unwrap_or(Path::new("."));
+        for file in [&mut config.settings_file, &mut config.alert_store]
+            .into_iter()
+            .flatten()
+        {
+            if file.is_relative() {
+                *file = dir.join(&*file);
+            }
+        }
+        config.validate()?;
+        Ok(config)
+    }
+
+    fn validate(&self) -> Result<(), MarketScannerError> {
+        if self.symbols.is_empty() {
+            return Err(MarketScannerError::InvalidConfig(
+                "At least one symbol required".to_string(),
+            ));
+        }
+        if self.exchanges.len() < 2 {
+            return Err(MarketScannerError::InvalidConfig(
+                "At least two exchanges required".to_string(),
+            ));
+        }
+        if let Some(telegram) = &self.telegram {
+            if telegram.bot_token().is_none() {
+                return Err(MarketScannerError::InvalidConfig(
+                    "Telegram needs bot_token or TELEGRAM_BOT_TOKEN".to_string(),
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl TelegramConfig {
+    pub fn bot_token(&self) -> Option<String> {
+        self.bot_token
+            .clone()
+            .filter(|token| !token.is_empty())
+            .or_else(|| std::env::var("TELEGRAM_BOT_TOKEN").ok())
+    }
+
+    pub fn template(&self) -> AlertTemplate {
+        self.template.clone().unwrap_or_else(default_template)
+    }
+}
+
+pub fn default_template() -> AlertTemplate {
+    AlertTemplate::parse(DEFAULT_TEMPLATE).expect("default template parses")
+}
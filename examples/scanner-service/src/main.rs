@@ -0,0 +1,150 @@
+//! Example scanner service: a production-shaped wiring of the crate.
+//!
+//! config file → live WebSocket scan (circuit breaker, reloadable settings) → filters and
+//! alert deduplication → Telegram / webhook alerts → Prometheus metrics on `/metrics`.
+//!
+//! ```text
+//! cargo run -p scanner-service -- examples/scanner-service/service.example.json
+//! ```
+
+mod config;
+mod metrics;
+mod sinks;
+
+use aeon_market_scanner_rs::{
+    AlertDeduplicator, ArbitrageOpportunity, LiveScanOptions, ScannerContext, ScannerEvent,
+    ScannerHandle, ScannerSettings, SettingsHandle,
+};
+use config::ServiceConfig;
+use metrics::Metrics;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// How often the settings file is checked for changes.
+const SETTINGS_POLL: Duration = Duration::from_secs(2);
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let path = std::env::args()
+        .nth(1)
+        .unwrap_or_else(|| "service.json".to_string());
+    let config = ServiceConfig::load(&path)?;
+    let metrics = Arc::new(Metrics::default());
+
+    let settings = match &config.settings_file {
+        Some(file) => {
+            let settings = SettingsHandle::load(file)?;
+            watch_settings(&settings, file.clone(), metrics.clone());
+            settings
+        }
+        None => SettingsHandle::new(ScannerSettings::default()),
+    };
+
+    let mut dedup = AlertDeduplicator::new(config.alert_expiry_ms);
+    if let Some(store) = &config.alert_store {
+        dedup = dedup.with_file(store)?;
+    }
+
+    let handle = ScannerHandle::new();
+    let snapshot_metrics = metrics.clone();
+    let mut context = ScannerContext::new("service")
+        .with_settings(settings)
+        .with_expiry(Duration::from_millis(config.close_after_ms))
+        .with_live_options(LiveScanOptions::default().with_handle(handle.clone()))
+        .with_sink(move |_, opportunities| record_snapshot(&snapshot_metrics, opportunities));
+    if let Some(telegram) = &config.telegram {
+        context = context.with_sink(dedup.sink(sinks::telegram(telegram, metrics.clone())));
+    }
+    if let Some(webhook) = &config.webhook {
+        context = context.with_sink(dedup.sink(sinks::webhook(webhook, metrics.clone())));
+    }
+
+    if let Some(addr) = config.metrics_addr.clone() {
+        let metrics = metrics.clone();
+        tokio::spawn(async move {
+            if let Err(e) = metrics::serve(&addr, metrics).await {
+                eprintln!("metrics endpoint {addr} failed: {e}");
+            }
+        });
+    }
+
+    let stop = handle.clone();
+    tokio::spawn(async move {
+        if tokio::signal::ctrl_c().await.is_ok() {
+            println!("stopping");
+            stop.stop();
+        }
+    });
+
+    let symbols: Vec<&str> = config.symbols.iter().map(String::as_str).collect();
+    println!("scanning {:?} on {:?}", config.symbols, config.exchanges);
+    let mut events = context.scan_live(&symbols, &config.exchanges).await?;
+    while let Some(event) = events.recv().await {
+        match event {
+            ScannerEvent::Opportunities(_) => {}
+            ScannerEvent::OpportunityClosed(closed) => {
+                for close in &closed {
+                    Metrics::inc(&metrics.opportunities_closed);
+                    println!(
+                        "closed {} after {} ms (max {:.3}%)",
+                        close.opportunity.key(),
+                        close.duration_ms,
+                        close.max_spread_percentage
+                    );
+                }
+            }
+            ScannerEvent::CircuitStateChanged {
+                exchange, state, ..
+            } => {
+                println!("{exchange:?} circuit {state:?}");
+                metrics.circuit(&exchange, state);
+            }
+            ScannerEvent::VenueError {
+                exchange, error, ..
+            } => {
+                eprintln!("{exchange:?}: {error}");
+                metrics.venue_error(&exchange);
+            }
+            ScannerEvent::FeedChanged { exchange, feed, .. } => {
+                println!("{exchange:?} feed {feed:?}");
+            }
+            ScannerEvent::StateChanged { state, .. } => println!("scanner {state:?}"),
+        }
+    }
+    dedup.save()?;
+    Ok(())
+}
+
+/// Counts a filtered snapshot and its best spread per symbol.
+fn record_snapshot(metrics: &Metrics, opportunities: &[ArbitrageOpportunity]) {
+    Metrics::inc(&metrics.snapshots);
+    metrics.opportunities.fetch_add(
+        opportunities.len() as u64,
+        std::sync::atomic::Ordering::Relaxed,
+    );
+    let mut best: HashMap<String, f64> = HashMap::new();
+    for opportunity in opportunities {
+        let spread = best.entry(opportunity.symbol.clone()).or_insert(f64::MIN);
+        *spread = spread.max(opportunity.spread_percentage);
+    }
+    metrics.best_spreads(best);
+}
+
+fn watch_settings(settings: &SettingsHandle, file: std::path::PathBuf, metrics: Arc<Metrics>) {
+    let mut reloads = settings.watch_file(file, SETTINGS_POLL);
+    tokio::spawn(async move {
+        while let Some(reload) = reloads.recv().await {
+            match reload.error {
+                None => {
+                    Metrics::inc(&metrics.settings_reloads);
+                    println!("settings v{} applied", reload.version);
+                }
+                Some(error) => {
+                    Metrics::inc(&metrics.settings_reload_errors);
+                    eprintln!("settings rejected: {error}");
+                }
+            }
+        }
+    });
+}
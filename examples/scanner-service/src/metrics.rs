@@ -0,0 +1,196 @@
+//! Prometheus metrics: service counters plus the library's per-venue health, served as
+//! text exposition on `GET /metrics`.
+
+use aeon_market_scanner_rs::{CexExchange, CircuitState, all_venue_health};
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+
+#[derive(Debug, Default)]
+pub struct Metrics {
+    pub snapshots: AtomicU64,
+    pub opportunities: AtomicU64,
+    pub opportunities_closed: AtomicU64,
+    pub alerts_sent: AtomicU64,
+    pub alerts_failed: AtomicU64,
+    pub settings_reloads: AtomicU64,
+    pub settings_reload_errors: AtomicU64,
+    venue_errors: Mutex<HashMap<CexExchange, u64>>,
+    circuits: Mutex<HashMap<CexExchange, CircuitState>>,
+    best_spread: Mutex<HashMap<String, f64>>,
+}
+
+impl Metrics {
+    pub fn inc(counter: &AtomicU64) {
+        counter.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn venue_error(&self, exchange: &CexExchange) {
+        *self
+            .venue_errors
+            .lock()
+            .unwrap()
+            .entry(exchange.clone())
+            .or_default() += 1;
+    }
+
+    pub fn circuit(&self, exchange: &CexExchange, state: CircuitState) {
+        self.circuits
+            .lock()
+            .unwrap()
+            .insert(exchange.clone(), state);
+    }
+
+    /// Best spread percentage per symbol of the latest snapshot.
+    pub fn best_spreads(&self, spreads: HashMap<String, f64>) {
+        *self.best_spread.lock().unwrap() = spreads;
+    }
+
+    pub fn render(&self) -> String {
+        let mut out = String::new();
+        let counters = [
+            (
+                "scanner_snapshots_total",
+                "Opportunity snapshots",
+                &self.snapshots,
+            ),
+            (
+                "scanner_opportunities_total",
+                "Opportunities passing the filters, summed over snapshots",
+                &self.opportunities,
+            ),
+            (
+                "scanner_opportunities_closed_total",
+                "Opportunities that closed",
+                &self.opportunities_closed,
+            ),
+            (
+                "scanner_alerts_sent_total",
+                "Alerts delivered",
+                &self.alerts_sent,
+            ),
+            (
+                "scanner_alerts_failed_total",
+                "Alerts that failed",
+                &self.alerts_failed,
+            ),
+            (
+                "scanner_settings_reloads_total",
+                "Applied settings reloads",
+                &self.settings_reloads,
+            ),
+            (
+                "scanner_settings_reload_errors_total",
+                "Rejected settings files",
+                &self.settings_reload_errors,
+            ),
+        ];
+        for (name, help, counter) in counters {
+            let _ = writeln!(out, "# HELP {name} {help}\n# TYPE {name} counter");
+            let _ = writeln!(out, "{name} {}", counter.load(Ordering::Relaxed));
+        }
+
+        let _ = writeln!(
+            out,
+            "# HELP scanner_best_spread_percent Best net spread of the latest snapshot\n\
+             # TYPE scanner_best_spread_percent gauge"
+        );
+        for (symbol, spread) in self.best_spread.lock().unwrap().iter() {
+            let _ = writeln!(
+                out,
+                "scanner_best_spread_percent{{symbol=\"{symbol}\"}} {spread}"
+            );
+        }
+
+        let _ = writeln!(
+            out,
+            "# HELP scanner_venue_errors_total Venue connection errors\n\
+             # TYPE scanner_venue_errors_total counter"
+        );
+        for (exchange, errors) in self.venue_errors.lock().unwrap().iter() {
+            let _ = writeln!(
+                out,
+                "scanner_venue_errors_total{{venue=\"{exchange:?}\"}} {errors}"
+            );
+        }
+
+        let _ = writeln!(
+            out,
+            "# HELP scanner_circuit_open Whether the venue circuit breaker is open (0.5 half-open)\n\
+             # TYPE scanner_circuit_open gauge"
+        );
+        for (exchange, state) in self.circuits.lock().unwrap().iter() {
+            let value = match state {
+                CircuitState::Closed => 0.0,
+                CircuitState::HalfOpen => 0.5,
+                CircuitState::Open => 1.0,
+            };
+            let _ = writeln!(
+                out,
+                "scanner_circuit_open{{venue=\"{exchange:?}\"}} {value}"
+            );
+        }
+
+        let _ = writeln!(
+            out,
+            "# HELP scanner_ws_connections_total Price stream connections per venue\n\
+             # TYPE scanner_ws_connections_total counter\n\
+             # HELP scanner_ws_decode_failures_total WebSocket frames that failed to decode\n\
+             # TYPE scanner_ws_decode_failures_total counter\n\
+             # HELP scanner_ws_latency_ms Average WebSocket ping round trip\n\
+             # TYPE scanner_ws_latency_ms gauge"
+        );
+        for health in all_venue_health() {
+            let venue = format!("{:?}", health.exchange);
+            let _ = writeln!(
+                out,
+                "scanner_ws_connections_total{{venue=\"{venue}\"}} {}",
+                health.ws_connections
+            );
+            if let Some(decode) = &health.decode {
+                let _ = writeln!(
+                    out,
+                    "scanner_ws_decode_failures_total{{venue=\"{venue}\"}} {}",
+                    decode.failures
+                );
+            }
+            if let Some(latency) = &health.ws_latency {
+                let _ = writeln!(
+                    out,
+                    "scanner_ws_latency_ms{{venue=\"{venue}\"}} {}",
+                    latency.avg_ms
+                );
+            }
+        }
+        out
+    }
+}
+
+/// Serves `metrics` on `addr` until the process exits.
+pub async fn serve(addr: &str, metrics: Arc<Metrics>) -> std::io::Result<()> {
+    let listener = TcpListener::bind(addr).await?;
+    loop {
+        let (mut stream, _) = listener.accept().await?;
+        let metrics = metrics.clone();
+        tokio::spawn(async move {
+            let mut request = [0u8; 1024];
+            let n = stream.read(&mut request).await.unwrap_or(0);
+            let request = String::from_utf8_lossy(&request[..n]);
+            let response = if request.starts_with("GET /metrics") {
+                let body = metrics.render();
+                format!(
+                    "HTTP/1.1 200 OK\r\nContent-Type: text/plain; version=0.0.4\r\n\
+                     Content-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                    body.len()
+                )
+            } else {
+                "HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n"
+                    .to_string()
+            };
+            let _ = stream.write_all(response.as_bytes()).await;
+        });
+    }
+}
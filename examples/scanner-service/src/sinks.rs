@@ -0,0 +1,77 @@
+//! Alert delivery. Context sinks run on the scanner's task, so they only queue messages;
+//! one task per destination posts them.
+
+use crate::config::{TelegramConfig, WebhookConfig};
+use crate::metrics::Metrics;
+use aeon_market_scanner_rs::ArbitrageOpportunity;
+use std::sync::Arc;
+use tokio::sync::mpsc;
+
+/// Alerts queued per destination before new ones are dropped.
+const ALERT_QUEUE: usize = 256;
+
+/// Sink posting one Telegram message per opportunity.
+pub fn telegram(
+    config: &TelegramConfig,
+    metrics: Arc<Metrics>,
+) -> impl Fn(&str, &[ArbitrageOpportunity]) + Send + Sync + 'static {
+    let (tx, mut rx) = mpsc::channel::<String>(ALERT_QUEUE);
+    let url = format!(
+        "https://api.telegram.org/bot{}/sendMessage",
+        config.bot_token().unwrap_or_default()
+    );
+    let chat_id = config.chat_id.clone();
+    let client = reqwest::Client::new();
+    let counters = metrics.clone();
+    tokio::spawn(async move {
+        while let Some(text) = rx.recv().await {
+            let body = serde_json::json!({ "chat_id": chat_id, "text": text });
+            let sent = client.post(&url).json(&body).send().await;
+            record(&counters, sent);
+        }
+    });
+    config.template().sink(move |message| {
+        if tx.try_send(message).is_err() {
+            Metrics::inc(&metrics.alerts_failed);
+        }
+    })
+}
+
+/// Sink posting each opportunity to a webhook, as JSON or as a rendered `{"text": ...}`.
+pub fn webhook(
+    config: &WebhookConfig,
+    metrics: Arc<Metrics>,
+) -> impl Fn(&str, &[ArbitrageOpportunity]) + Send + Sync + 'static {
+    let (tx, mut rx) = mpsc::channel::<serde_json::Value>(ALERT_QUEUE);
+    let url = config.url.clone();
+    let client = reqwest::Client::new();
+    let counters = metrics.clone();
+    tokio::spawn(async move {
+        while let Some(body) = rx.recv().await {
+            let sent = client.post(&url).json(&body).send().await;
+            record(&counters, sent);
+        }
+    });
+    let template = config.template.clone();
+    move |context: &str, opportunities: &[ArbitrageOpportunity]| {
+        for opportunity in opportunities {
+            let body = match &template {
+                Some(template) => serde_json::json!({ "text": template.render(opportunity) }),
+                None => serde_json::json!({ "context": context, "opportunity": opportunity }),
+            };
+            if tx.try_send(body).is_err() {
+                Metrics::inc(&metrics.alerts_failed);
+            }
+        }
+    }
+}
+
+fn record(metrics: &Metrics, sent: reqwest::Result<reqwest::Response>) {
+    match sent.and_then(|response| response.error_for_status()) {
+        Ok(_) => Metrics::inc(&metrics.alerts_sent),
+        Err(e) => {
+            Metrics::inc(&metrics.alerts_failed);
+            eprintln!("alert failed: {e}");
+        }
+    }
+}
@@ -0,0 +1,38 @@
+//! WebSocket soak test: streams every WebSocket venue for N hours and prints a stability
+//! report (reconnects, message gaps, memory growth).
+//!
+//! ```text
+//! cargo run --release --example ws_soak -- <hours> [SYMBOL ...]
+//! cargo run --release --example ws_soak -- 6 BTCUSDT ETHUSDT
+//! ```
+
+use aeon_market_scanner_rs::{ArbitrageScanner, CexExchange, WsSoakOptions};
+use std::time::Duration;
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let mut args = std::env::args().skip(1);
+    let hours: f64 = match args.next() {
+        Some(hours) => hours.parse()?,
+        None => 1.0,
+    };
+    let mut symbols: Vec<String> = args.collect();
+    if symbols.is_empty() {
+        symbols.push("BTCUSDT".to_string());
+    }
+    let symbols: Vec<&str> = symbols.iter().map(String::as_str).collect();
+
+    let options = WsSoakOptions::new(Duration::from_secs_f64(hours * 3600.0))
+        .with_sample_interval(Duration::from_secs(300))
+        .with_progress(|report| println!("{report}\n"));
+    println!("Soaking {:?} for {hours} h...", symbols);
+    let report = ArbitrageScanner::run_ws_soak(&CexExchange::ALL, &symbols, options).await?;
+
+    println!("{report}");
+    std::fs::write(
+        "ws_soak_report.json",
+        serde_json::to_string_pretty(&report)?,
+    )?;
+    println!("Saved ws_soak_report.json");
+    Ok(())
+}
@@ -0,0 +1,168 @@
+//! Apache Arrow / Parquet export, enabled by the `arrow` feature.
+//!
+//! Converts batches of [CexPrice]s and [ArbitrageOpportunity]s to Arrow [RecordBatch]es
+//! with a fixed schema ([price_schema], [opportunity_schema]) and writes them as Parquet,
+//! so recorded data can be loaded directly by DataFusion, Polars or pyarrow.
+//! Timestamps are stored as `Timestamp(Millisecond, "UTC")`, exchanges by display name.
+
+use crate::common::{CexPrice, Timestamp};
+use crate::scanner::{ArbitrageOpportunity, ArbitrageScanner, PriceData};
+use ::arrow::array::{ArrayRef, Float64Array, StringArray, TimestampMillisecondArray, UInt32Array};
+use ::arrow::datatypes::{DataType, Field, Schema, SchemaRef, TimeUnit};
+use ::arrow::error::ArrowError;
+use ::arrow::record_batch::RecordBatch;
+use parquet::arrow::ArrowWriter;
+use parquet::errors::ParquetError;
+use std::io::Write;
+use std::sync::Arc;
+
+fn timestamp_type() -> DataType {
+    DataType::Timestamp(TimeUnit::Millisecond, Some("UTC".into()))
+}
+
+fn timestamp_array(timestamps: impl Iterator<Item = Timestamp>) -> ArrayRef {
+    Arc::new(
+        TimestampMillisecondArray::from_iter_values(timestamps.map(|ts| ts.as_millis() as i64))
+            .with_timezone("UTC"),
+    )
+}
+
+fn f64_array(values: impl Iterator<Item = f64>) -> ArrayRef {
+    Arc::new(Float64Array::from_iter_values(values))
+}
+
+fn string_array<S: AsRef<str>>(values: impl Iterator<Item = S>) -> ArrayRef {
+    Arc::new(StringArray::from_iter_values(values))
+}
+
+/// Schema of [prices_to_record_batch].
+pub fn price_schema() -> SchemaRef {
+    Arc::new(Schema::new(vec![
+        Field::new("timestamp", timestamp_type(), false),
+        Field::new("exchange", DataType::Utf8, false),
+        Field::new("symbol", DataType::Utf8, false),
+        Field::new("bid_price", DataType::Float64, false),
+        Field::new("ask_price", DataType::Float64, false),
+        Field::new("mid_price", DataType::Float64, false),
+        Field::new("bid_qty", DataType::Float64, false),
+        Field::new("ask_qty", DataType::Float64, false),
+        Field::new("trading_status", DataType::Utf8, false),
+    ]))
+}
+
+/// One row per price, in input order.
+pub fn prices_to_record_batch(prices: &[CexPrice]) -> Result<RecordBatch, ArrowError> {
+    RecordBatch::try_new(
+        price_schema(),
+        vec![
+            timestamp_array(prices.iter().map(|p| p.timestamp)),
+            string_array(
+                prices
+                    .iter()
+                    .map(|p| ArbitrageScanner::exchange_name(&p.exchange)),
+            ),
+            string_array(prices.iter().map(|p| p.symbol.as_str())),
+            f64_array(prices.iter().map(|p| p.bid_price)),
+            f64_array(prices.iter().map(|p| p.ask_price)),
+            f64_array(prices.iter().map(|p| p.mid_price)),
+            f64_array(prices.iter().map(|p| p.bid_qty)),
+            f64_array(prices.iter().map(|p| p.ask_qty)),
+            string_array(prices.iter().map(|p| format!("{:?}", p.trading_status))),
+        ],
+    )
+}
+
+/// Schema of [opportunities_to_record_batch]. Leg timestamps are the price timestamps of
+/// the source and destination legs; `total_profit` is `spread × executable_quantity`.
+pub fn opportunity_schema() -> SchemaRef {
+    Arc::new(Schema::new(vec![
+        Field::new("schema_version", DataType::UInt32, false),
+        Field::new("source_exchange", DataType::Utf8, false),
+        Field::new("destination_exchange", DataType::Utf8, false),
+        Field::new("symbol", DataType::Utf8, false),
+        Field::new("source_timestamp", timestamp_type(), false),
+        Field::new("destination_timestamp", timestamp_type(), false),
+        Field::new("effective_ask", DataType::Float64, false),
+        Field::new("effective_bid", DataType::Float64, false),
+        Field::new("spread", DataType::Float64, false),
+        Field::new("spread_percentage", DataType::Float64, false),
+        Field::new("executable_quantity", DataType::Float64, false),
+        Field::new("source_commission_percent", DataType::Float64, false),
+        Field::new("destination_commission_percent", DataType::Float64, false),
+        Field::new("total_commission_quote", DataType::Float64, false),
+        Field::new("total_profit", DataType::Float64, false),
+    ]))
+}
+
+fn leg_timestamp(leg: &PriceData) -> Timestamp {
+    match leg {
+        PriceData::Cex(p) => p.timestamp,
+        PriceData::Dex(p) => p.timestamp,
+    }
+}
+
+/// One row per opportunity, in input order.
+pub fn opportunities_to_record_batch(
+    opportunities: &[ArbitrageOpportunity],
+) -> Result<RecordBatch, ArrowError> {
+    RecordBatch::try_new(
+        opportunity_schema(),
+        vec![
+            Arc::new(UInt32Array::from_iter_values(
+                opportunities.iter().map(|o| o.schema_version),
+            )),
+            string_array(opportunities.iter().map(|o| o.source_exchange.as_str())),
+            string_array(
+                opportunities
+                    .iter()
+                    .map(|o| o.destination_exchange.as_str()),
+            ),
+            string_array(opportunities.iter().map(|o| o.symbol.as_str())),
+            timestamp_array(opportunities.iter().map(|o| leg_timestamp(&o.source_leg))),
+            timestamp_array(
+                opportunities
+                    .iter()
+                    .map(|o| leg_timestamp(&o.destination_leg)),
+            ),
+            f64_array(opportunities.iter().map(|o| o.effective_ask)),
+            f64_array(opportunities.iter().map(|o| o.effective_bid)),
+            f64_array(opportunities.iter().map(|o| o.spread)),
+            f64_array(opportunities.iter().map(|o| o.spread_percentage)),
+            f64_array(opportunities.iter().map(|o| o.executable_quantity)),
+            f64_array(opportunities.iter().map(|o| o.source_commission_percent)),
+            f64_array(
+                opportunities
+                    .iter()
+                    .map(|o| o.destination_commission_percent),
+            ),
+            f64_array(opportunities.iter().map(|o| o.total_commission_quote)),
+            f64_array(opportunities.iter().map(|o| o.total_profit())),
+        ],
+    )
+}
+
+/// Writes `prices` as a Parquet file with [price_schema].
+///
+/// For recordings that grow over time, create a [parquet::arrow::ArrowWriter] with
+/// [price_schema] and append one [prices_to_record_batch] per flush instead.
+pub fn write_prices_parquet<W: Write + Send>(
+    writer: W,
+    prices: &[CexPrice],
+) -> Result<(), ParquetError> {
+    write_parquet(writer, prices_to_record_batch(prices)?)
+}
+
+/// Writes `opportunities` as a Parquet file with [opportunity_schema].
+pub fn write_opportunities_parquet<W: Write + Send>(
+    writer: W,
+    opportunities: &[ArbitrageOpportunity],
+) -> Result<(), ParquetError> {
+    write_parquet(writer, opportunities_to_record_batch(opportunities)?)
+}
+
+fn write_parquet<W: Write + Send>(writer: W, batch: RecordBatch) -> Result<(), ParquetError> {
+    let mut writer = ArrowWriter::try_new(writer, batch.schema(), None)?;
+    writer.write(&batch)?;
+    writer.close()?;
+    Ok(())
+}
@@ -1,17 +1,33 @@
 mod types;
+mod user_data;
+
+pub use user_data::BINANCE_TESTNET_API_BASE;
+
+use crate::common::capabilities::{Capabilities, VenueCapability};
+use crate::common::clock::json_server_time;
+use crate::common::reconnect::{ReconnectPolicy, ws_endpoint};
+use crate::common::symbol_registry;
 use crate::common::{
-    CEXTrait, CexExchange, CexPrice, Exchange, ExchangeTrait, MarketScannerError, find_mid_price,
-    format_symbol_for_exchange, format_symbol_for_exchange_ws, get_timestamp_millis,
-    normalize_symbol, parse_f64, standard_symbol_for_cex_ws_response,
+    CEXTrait, CexExchange, CexPrice, DepthDelta, DepthSnapshot, DepthSyncStatus, Exchange,
+    ExchangeTrait, MarketScannerError, OrderBook, OrderBookLevel, OrderBookSynchronizer,
+    OrderLimits, RequestPriority, Timestamp, TradingStatus, WsSymbolFilter, decode_ws_json,
+    decode_ws_value, find_mid_price, format_symbol_for_exchange, format_symbol_for_exchange_ws,
+    normalize_symbol, parse_f64, record_ws_connect, standard_symbol_for_cex_ws_response,
 };
 use crate::create_exchange;
 use async_trait::async_trait;
 use futures::StreamExt;
 use tokio::sync::mpsc;
-use types::{BinanceBookTickerResponse, BinanceBookTickerWs};
+use types::{
+    BinanceBookTickerResponse, BinanceBookTickerWs, BinanceDepthResponse, BinanceDepthUpdateWs,
+};
 
 const BINANCE_API_BASE: &str = "https://api.binance.com/api/v3";
 const BINANCE_WS_BASE: &str = "wss://stream.binance.com:9443";
+/// Largest `limit` accepted by the depth endpoint
+const BINANCE_MAX_DEPTH_LIMIT: usize = 5000;
+/// Minimum snapshot size for synced books, so deltas rarely reach past the snapshot
+const BINANCE_SNAPSHOT_DEPTH_LIMIT: usize = 1000;
 
 create_exchange!(Binance);
 
@@ -26,18 +42,25 @@ impl ExchangeTrait for Binance {
     }
 
     fn exchange_name(&self) -> &str {
-        "Binance"
+        CexExchange::Binance.rest_venue_name()
     }
 
     async fn health_check(&self) -> Result<(), MarketScannerError> {
         // Binance ping endpoint - test connectivity to the REST API
         let endpoint = "ping";
-        self.get::<serde_json::Value>(endpoint)
+        self.get_with_priority::<serde_json::Value>(endpoint, RequestPriority::Metadata)
             .await
             .map_err(|_| MarketScannerError::HealthCheckFailed)?;
 
         Ok(())
     }
+
+    async fn get_server_time(&self) -> Result<Timestamp, MarketScannerError> {
+        let response: serde_json::Value = self
+            .get_with_priority("time", RequestPriority::Metadata)
+            .await?;
+        json_server_time(&response, "/serverTime", self.exchange_name())
+    }
 }
 
 #[async_trait]
@@ -46,6 +69,17 @@ impl CEXTrait for Binance {
         true
     }
 
+    fn capabilities(&self) -> Capabilities {
+        Capabilities::new(&[
+            VenueCapability::Websocket,
+            VenueCapability::OrderBook,
+            VenueCapability::OrderBookStream,
+            VenueCapability::UserData,
+            VenueCapability::OrderLimits,
+            VenueCapability::ServerTime,
+        ])
+    }
+
     async fn get_price(&self, symbol: &str) -> Result<CexPrice, MarketScannerError> {
         // Validate symbol is not empty
         if symbol.is_empty() {
@@ -58,7 +92,12 @@ impl CEXTrait for Binance {
         let binance_symbol = format_symbol_for_exchange(symbol, &CexExchange::Binance)?;
         let endpoint = format!("ticker/bookTicker?symbol={}", binance_symbol);
 
-        let ticker: BinanceBookTickerResponse = self.get(&endpoint).await?;
+        // Trading status is best effort: an unreachable exchangeInfo must not fail the price
+        let (ticker, status) = tokio::join!(
+            self.get::<BinanceBookTickerResponse>(&endpoint),
+            self.fetch_trading_status(&binance_symbol)
+        );
+        let ticker = ticker?;
 
         let bid = parse_f64(&ticker.bid_price, "bid price")?;
         let ask = parse_f64(&ticker.ask_price, "ask price")?;
@@ -76,11 +115,57 @@ impl CEXTrait for Binance {
             ask_price: ask,
             bid_qty,
             ask_qty,
-            timestamp: get_timestamp_millis(),
+            timestamp: Timestamp::now(),
+            exchange: Exchange::Cex(CexExchange::Binance),
+            trading_status: status.unwrap_or_default(),
+        })
+    }
+
+    async fn get_order_book(
+        &self,
+        symbol: &str,
+        depth: usize,
+    ) -> Result<OrderBook, MarketScannerError> {
+        if symbol.is_empty() {
+            return Err(MarketScannerError::InvalidSymbol(
+                "Symbol cannot be empty".to_string(),
+            ));
+        }
+
+        let binance_symbol = format_symbol_for_exchange(symbol, &CexExchange::Binance)?;
+        let snapshot = self
+            .fetch_depth_snapshot(&binance_symbol, depth.clamp(1, BINANCE_MAX_DEPTH_LIMIT))
+            .await?;
+
+        Ok(OrderBook {
+            symbol: normalize_symbol(symbol),
+            bids: snapshot.bids.into_iter().take(depth).collect(),
+            asks: snapshot.asks.into_iter().take(depth).collect(),
+            timestamp: Timestamp::now(),
             exchange: Exchange::Cex(CexExchange::Binance),
         })
     }
 
+    async fn get_order_limits(&self, symbol: &str) -> Result<OrderLimits, MarketScannerError> {
+        if symbol.is_empty() {
+            return Err(MarketScannerError::InvalidSymbol(
+                "Symbol cannot be empty".to_string(),
+            ));
+        }
+
+        let binance_symbol = format_symbol_for_exchange(symbol, &CexExchange::Binance)?;
+        let endpoint = format!("exchangeInfo?symbol={}", binance_symbol);
+        let response: serde_json::Value = self.get_cached(&endpoint).await?;
+        let filters = response["symbols"][0]["filters"]
+            .as_array()
+            .ok_or_else(|| {
+                MarketScannerError::ApiError(
+                    "Binance exchangeInfo missing symbol filters".to_string(),
+                )
+            })?;
+        Ok(parse_binance_order_limits(filters))
+    }
+
     /// Connection stays open; incoming prices are sent over the returned Receiver.
     /// When the channel closes (Receiver returns None), the connection has closed.
     async fn stream_price_websocket(
@@ -105,14 +190,11 @@ impl CEXTrait for Binance {
             .ok_or_else(|| MarketScannerError::InvalidSymbol("Invalid symbol".to_string()))?;
 
         let is_combined = stream_names.len() > 1;
+        let base = ws_endpoint(&CexExchange::Binance, BINANCE_WS_BASE);
         let url = if stream_names.len() == 1 {
-            format!("{}/ws/{}", BINANCE_WS_BASE, stream_names[0])
+            format!("{}/ws/{}", base, stream_names[0])
         } else {
-            format!(
-                "{}/stream?streams={}",
-                BINANCE_WS_BASE,
-                stream_names.join("/")
-            )
+            format!("{}/stream?streams={}", base, stream_names.join("/"))
         };
 
         let single_symbol = if symbols.len() == 1 {
@@ -124,24 +206,23 @@ impl CEXTrait for Binance {
             None
         };
         let (tx, rx) = mpsc::channel(64);
+        let symbol_filter = WsSymbolFilter::new(&CexExchange::Binance, symbols);
 
-        let delay = std::time::Duration::from_millis(if reconnect_delay_ms == 0 {
-            1000
-        } else {
-            reconnect_delay_ms
-        });
-        tokio::spawn(async move {
+        let policy = ReconnectPolicy::new(reconnect_attempts, reconnect_delay_ms);
+        symbol_registry::spawn(async move {
             let mut attempt = 0u32;
             loop {
                 attempt += 1;
                 let (ws_stream, _) = match tokio_tungstenite::connect_async(&url).await {
-                    Ok(v) => v,
+                    Ok(v) => {
+                        record_ws_connect(&CexExchange::Binance);
+                        v
+                    }
                     Err(_) => {
-                        if tx.is_closed() || reconnect_attempts == 0 || attempt > reconnect_attempts
-                        {
+                        if tx.is_closed() || !policy.should_reconnect(attempt) {
                             break;
                         }
-                        tokio::time::sleep(delay).await;
+                        tokio::time::sleep(policy.delay()).await;
                         continue;
                     }
                 };
@@ -149,14 +230,19 @@ impl CEXTrait for Binance {
                 let (_write, mut read) = ws_stream.split();
 
                 while let Some(Ok(msg)) = read.next().await {
+                    // Control frames carry no payload to decode
+                    if msg.is_ping() || msg.is_pong() || msg.is_close() {
+                        continue;
+                    }
                     let text = match msg.into_text() {
                         Ok(t) => t,
                         Err(_) => continue,
                     };
-                    let value: serde_json::Value = match serde_json::from_str(&text) {
-                        Ok(v) => v,
-                        Err(_) => continue,
-                    };
+                    let value: serde_json::Value =
+                        match decode_ws_json(&CexExchange::Binance, &text) {
+                            Some(v) => v,
+                            None => continue,
+                        };
 
                     // Combined stream: {"stream":"btcusdt@bookTicker","data":{...}}
                     // Single stream: raw payload {b, B, a, A}
@@ -186,10 +272,11 @@ impl CEXTrait for Binance {
                         )
                     };
 
-                    let ticker: BinanceBookTickerWs = match serde_json::from_value(ticker_value) {
-                        Ok(t) => t,
-                        Err(_) => continue,
-                    };
+                    let ticker: BinanceBookTickerWs =
+                        match decode_ws_value(&CexExchange::Binance, ticker_value) {
+                            Some(t) => t,
+                            None => continue,
+                        };
 
                     let (bid, ask, bid_qty, ask_qty) = match (
                         parse_f64(&ticker.b, "bid"),
@@ -207,21 +294,239 @@ impl CEXTrait for Binance {
                         ask_price: ask,
                         bid_qty,
                         ask_qty,
-                        timestamp: get_timestamp_millis(),
+                        timestamp: Timestamp::now(),
                         exchange: Exchange::Cex(CexExchange::Binance),
+                        trading_status: TradingStatus::Unknown,
                     };
+                    if !symbol_filter.allows(&price) {
+                        continue;
+                    }
                     if tx.send(price).await.is_err() {
                         return;
                     }
                 }
 
-                if tx.is_closed() || reconnect_attempts == 0 || attempt > reconnect_attempts {
+                if tx.is_closed() || !policy.should_reconnect(attempt) {
                     break;
                 }
-                tokio::time::sleep(delay).await;
+                tokio::time::sleep(policy.delay()).await;
             }
         });
 
         Ok(rx)
     }
+
+    /// Syncs the book from a `depth` REST snapshot and the `@depth@100ms` diff stream.
+    /// Deltas are buffered while the snapshot loads; on an update id gap a new snapshot is
+    /// fetched, and on reconnect the book is rebuilt from scratch.
+    async fn stream_order_book(
+        &self,
+        symbol: &str,
+        depth: usize,
+        reconnect_attempts: u32,
+        reconnect_delay_ms: u64,
+    ) -> Result<mpsc::Receiver<OrderBook>, MarketScannerError> {
+        if symbol.is_empty() {
+            return Err(MarketScannerError::InvalidSymbol(
+                "Symbol cannot be empty".to_string(),
+            ));
+        }
+
+        let binance_symbol = format_symbol_for_exchange(symbol, &CexExchange::Binance)?;
+        let url = format!(
+            "{}/ws/{}@depth@100ms",
+            ws_endpoint(&CexExchange::Binance, BINANCE_WS_BASE),
+            binance_symbol.to_lowercase()
+        );
+        let standard_symbol = normalize_symbol(symbol);
+        let snapshot_limit = depth.clamp(BINANCE_SNAPSHOT_DEPTH_LIMIT, BINANCE_MAX_DEPTH_LIMIT);
+        let exchange = Binance {
+            client: self.client.clone(),
+        };
+        let (tx, rx) = mpsc::channel(64);
+
+        let policy = ReconnectPolicy::new(reconnect_attempts, reconnect_delay_ms);
+        symbol_registry::spawn(async move {
+            let exchange = std::sync::Arc::new(exchange);
+            let mut attempt = 0u32;
+            loop {
+                attempt += 1;
+                let (ws_stream, _) = match tokio_tungstenite::connect_async(&url).await {
+                    Ok(v) => v,
+                    Err(_) => {
+                        if tx.is_closed() || !policy.should_reconnect(attempt) {
+                            break;
+                        }
+                        tokio::time::sleep(policy.delay()).await;
+                        continue;
+                    }
+                };
+
+                let (_write, mut read) = ws_stream.split();
+                let mut sync = OrderBookSynchronizer::new(
+                    &standard_symbol,
+                    Exchange::Cex(CexExchange::Binance),
+                );
+                // One snapshot request in flight at a time
+                let (snapshot_tx, mut snapshot_rx) = mpsc::channel(1);
+                let mut fetching = false;
+
+                loop {
+                    let status = tokio::select! {
+                        msg = read.next() => {
+                            let msg = match msg {
+                                Some(Ok(msg)) => msg,
+                                _ => break,
+                            };
+                            // Control frames carry no payload to decode
+                            if msg.is_ping() || msg.is_pong() || msg.is_close() {
+                                continue;
+                            }
+                            let text = match msg.into_text() {
+                                Ok(t) => t,
+                                Err(_) => continue,
+                            };
+                            let update: BinanceDepthUpdateWs =
+                                match decode_ws_json(&CexExchange::Binance, &text) {
+                                    Some(u) => u,
+                                    None => continue,
+                                };
+                            let delta = match (
+                                parse_depth_levels(&update.b),
+                                parse_depth_levels(&update.a),
+                            ) {
+                                (Ok(bids), Ok(asks)) => DepthDelta {
+                                    first_update_id: update.U,
+                                    final_update_id: update.u,
+                                    bids,
+                                    asks,
+                                },
+                                _ => continue,
+                            };
+                            sync.push_delta(delta)
+                        }
+                        Some(snapshot) = snapshot_rx.recv() => {
+                            fetching = false;
+                            match snapshot {
+                                Ok(snapshot) => sync.apply_snapshot(snapshot),
+                                // Retried with the next buffered delta
+                                Err(_) => continue,
+                            }
+                        }
+                    };
+
+                    match status {
+                        DepthSyncStatus::Applied => {
+                            if let Some(book) = sync.book(depth) {
+                                if tx.send(book).await.is_err() {
+                                    return;
+                                }
+                            }
+                        }
+                        DepthSyncStatus::Buffered | DepthSyncStatus::Gap if !fetching => {
+                            fetching = true;
+                            let exchange = exchange.clone();
+                            let binance_symbol = binance_symbol.clone();
+                            let snapshot_tx = snapshot_tx.clone();
+                            symbol_registry::spawn(async move {
+                                let snapshot = exchange
+                                    .fetch_depth_snapshot(&binance_symbol, snapshot_limit)
+                                    .await;
+                                let _ = snapshot_tx.send(snapshot).await;
+                            });
+                        }
+                        _ => {}
+                    }
+                }
+
+                if tx.is_closed() || !policy.should_reconnect(attempt) {
+                    break;
+                }
+                tokio::time::sleep(policy.delay()).await;
+            }
+        });
+
+        Ok(rx)
+    }
+}
+
+impl Binance {
+    /// REST depth snapshot of `binance_symbol` with up to `limit` levels per side.
+    async fn fetch_depth_snapshot(
+        &self,
+        binance_symbol: &str,
+        limit: usize,
+    ) -> Result<DepthSnapshot, MarketScannerError> {
+        let endpoint = format!("depth?symbol={}&limit={}", binance_symbol, limit);
+        let response: BinanceDepthResponse = self
+            .get_with_priority(&endpoint, RequestPriority::OrderBook)
+            .await?;
+        Ok(DepthSnapshot {
+            last_update_id: response.last_update_id,
+            bids: parse_depth_levels(&response.bids)?,
+            asks: parse_depth_levels(&response.asks)?,
+        })
+    }
+
+    /// Trading status of `binance_symbol` from `exchangeInfo`.
+    async fn fetch_trading_status(
+        &self,
+        binance_symbol: &str,
+    ) -> Result<TradingStatus, MarketScannerError> {
+        let endpoint = format!("exchangeInfo?symbol={}", binance_symbol);
+        let response: serde_json::Value = self.get_cached(&endpoint).await?;
+        let status = response["symbols"][0]["status"].as_str().ok_or_else(|| {
+            MarketScannerError::ApiError("Binance exchangeInfo missing symbol status".to_string())
+        })?;
+        Ok(parse_binance_trading_status(status))
+    }
+}
+
+/// Parses `[price, quantity]` string pairs into order book levels.
+fn parse_depth_levels(levels: &[[String; 2]]) -> Result<Vec<OrderBookLevel>, MarketScannerError> {
+    levels
+        .iter()
+        .map(|[price, quantity]| {
+            Ok(OrderBookLevel {
+                price: parse_f64(price, "depth price")?,
+                quantity: parse_f64(quantity, "depth quantity")?,
+            })
+        })
+        .collect()
+}
+
+/// Minimum quantity (`LOT_SIZE`) and notional (`NOTIONAL`, or the older `MIN_NOTIONAL`)
+/// from the filters of an `exchangeInfo` symbol.
+fn parse_binance_order_limits(filters: &[serde_json::Value]) -> OrderLimits {
+    let value = |filter: &serde_json::Value, field: &str| {
+        filter[field]
+            .as_str()
+            .and_then(|v| v.parse::<f64>().ok())
+            .filter(|v| *v > 0.0)
+    };
+    let mut limits = OrderLimits::default();
+    for filter in filters {
+        match filter["filterType"].as_str() {
+            Some("LOT_SIZE") => {
+                limits.min_quantity = value(filter, "minQty");
+                limits.quantity_step = value(filter, "stepSize");
+            }
+            Some("PRICE_FILTER") => limits.price_tick = value(filter, "tickSize"),
+            Some("NOTIONAL") | Some("MIN_NOTIONAL") => {
+                limits.min_notional = value(filter, "minNotional").or(limits.min_notional)
+            }
+            _ => {}
+        }
+    }
+    limits
+}
+
+/// Maps a Binance symbol status (`TRADING`, `HALT`, `BREAK`, ...) to [TradingStatus].
+fn parse_binance_trading_status(status: &str) -> TradingStatus {
+    match status {
+        "TRADING" => TradingStatus::Trading,
+        "HALT" | "BREAK" | "END_OF_DAY" | "POST_TRADING" => TradingStatus::Halted,
+        "PRE_TRADING" | "AUCTION_MATCH" => TradingStatus::Auction,
+        _ => TradingStatus::Unknown,
+    }
 }
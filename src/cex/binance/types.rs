@@ -23,3 +23,23 @@ pub struct BinanceBookTickerWs {
     pub a: String, // best ask price
     pub A: String, // best ask qty
 }
+
+/// REST depth snapshot. Endpoint: /api/v3/depth?symbol=<symbol>&limit=<n>
+#[derive(Debug, Deserialize)]
+pub struct BinanceDepthResponse {
+    #[serde(rename = "lastUpdateId")]
+    pub last_update_id: u64,
+    pub bids: Vec<[String; 2]>, // [price, quantity]
+    pub asks: Vec<[String; 2]>, // [price, quantity]
+}
+
+/// WebSocket diff depth payload.
+/// Stream: wss://stream.binance.com:9443/ws/<symbol>@depth@100ms
+#[derive(Debug, Deserialize)]
+#[allow(non_snake_case)]
+pub struct BinanceDepthUpdateWs {
+    pub U: u64,              // first update id in event
+    pub u: u64,              // final update id in event
+    pub b: Vec<[String; 2]>, // bids to update
+    pub a: Vec<[String; 2]>, // asks to update
+}
@@ -0,0 +1,183 @@
+//! Binance user data stream (listenKey): execution reports and account positions.
+
+use super::{BINANCE_API_BASE, BINANCE_WS_BASE, Binance};
+use crate::cex::execution::{
+    BalanceUpdate, KeepAlive, OrderStatus, OrderUpdate, PrivateWsSession, SessionFactory,
+    TradingMode, UserDataEvent, UserDataStream, json_f64, json_side, json_str, json_timestamp,
+    spawn_user_data_stream,
+};
+use crate::common::{
+    CexExchange, Credentials, MarketScannerError, standard_symbol_for_cex_ws_response,
+};
+use async_trait::async_trait;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::mpsc;
+
+/// REST base of the Binance spot testnet
+pub const BINANCE_TESTNET_API_BASE: &str = "https://testnet.binance.vision/api/v3";
+const BINANCE_TESTNET_WS_BASE: &str = "wss://stream.testnet.binance.vision";
+/// listenKeys expire after 60 minutes without a keepalive
+const LISTEN_KEY_KEEPALIVE: Duration = Duration::from_secs(30 * 60);
+
+fn api_base(mode: TradingMode) -> &'static str {
+    match mode {
+        TradingMode::Live => BINANCE_API_BASE,
+        TradingMode::Demo => BINANCE_TESTNET_API_BASE,
+    }
+}
+
+/// Requests a new listenKey.
+async fn create_listen_key(
+    client: &reqwest::Client,
+    api_key: &str,
+    mode: TradingMode,
+) -> Result<String, MarketScannerError> {
+    let response = client
+        .post(format!("{}/userDataStream", api_base(mode)))
+        .header("X-MBX-APIKEY", api_key)
+        .send()
+        .await?;
+    let status = response.status();
+    let text = response.text().await?;
+    if !status.is_success() {
+        return Err(MarketScannerError::ApiError(format!(
+            "Binance API error: {} - {}",
+            status, text
+        )));
+    }
+    let value: serde_json::Value = serde_json::from_str(&text)?;
+    value["listenKey"]
+        .as_str()
+        .map(str::to_string)
+        .ok_or_else(|| MarketScannerError::ApiError("Binance response missing listenKey".into()))
+}
+
+async fn listen_key_session(
+    client: reqwest::Client,
+    api_key: String,
+    mode: TradingMode,
+) -> Result<PrivateWsSession, MarketScannerError> {
+    let listen_key = create_listen_key(&client, &api_key, mode).await?;
+    let ws_base = match mode {
+        TradingMode::Live => BINANCE_WS_BASE,
+        TradingMode::Demo => BINANCE_TESTNET_WS_BASE,
+    };
+    let url = format!("{}/ws/{}", ws_base, listen_key);
+    let keepalive_url = format!("{}/userDataStream?listenKey={}", api_base(mode), listen_key);
+    let keepalive: KeepAlive = Arc::new(move || {
+        let client = client.clone();
+        let api_key = api_key.clone();
+        let url = keepalive_url.clone();
+        Box::pin(async move {
+            // An expired key is reported on the stream and renewed there
+            let _ = client
+                .put(&url)
+                .header("X-MBX-APIKEY", api_key)
+                .send()
+                .await;
+        })
+    });
+    Ok(PrivateWsSession {
+        url,
+        on_connect: Vec::new(),
+        after_login: Vec::new(),
+        login_ack: None,
+        expired: Some(|value| value["e"] == "listenKeyExpired"),
+        text_ping: None,
+        ping_interval: Duration::from_secs(30),
+        keepalive: Some((LISTEN_KEY_KEEPALIVE, keepalive)),
+    })
+}
+
+fn order_status(status: &str) -> OrderStatus {
+    match status {
+        "NEW" => OrderStatus::New,
+        "PARTIALLY_FILLED" => OrderStatus::PartiallyFilled,
+        "FILLED" => OrderStatus::Filled,
+        "CANCELED" => OrderStatus::Canceled,
+        "REJECTED" => OrderStatus::Rejected,
+        "EXPIRED" | "EXPIRED_IN_MATCH" => OrderStatus::Expired,
+        _ => OrderStatus::Unknown,
+    }
+}
+
+fn parse_user_data(message: &serde_json::Value) -> Vec<UserDataEvent> {
+    match message["e"].as_str() {
+        Some("executionReport") => {
+            let Some(side) = json_side(&message["S"]) else {
+                return Vec::new();
+            };
+            let filled_quantity = json_f64(&message["z"]).unwrap_or(0.0);
+            let filled_quote = json_f64(&message["Z"]).unwrap_or(0.0);
+            let order_id = match &message["i"] {
+                serde_json::Value::Number(id) => id.to_string(),
+                other => other.as_str().unwrap_or_default().to_string(),
+            };
+            vec![UserDataEvent::Order(OrderUpdate {
+                exchange: CexExchange::Binance,
+                symbol: standard_symbol_for_cex_ws_response(
+                    message["s"].as_str().unwrap_or_default(),
+                    &CexExchange::Binance,
+                ),
+                order_id,
+                client_order_id: json_str(&message["c"]),
+                side,
+                status: order_status(message["X"].as_str().unwrap_or_default()),
+                price: json_f64(&message["p"]).filter(|p| *p > 0.0),
+                quantity: json_f64(&message["q"]).unwrap_or(0.0),
+                filled_quantity,
+                average_fill_price: (filled_quantity > 0.0).then(|| filled_quote / filled_quantity),
+                timestamp: json_timestamp(&message["E"]),
+            })]
+        }
+        Some("outboundAccountPosition") => {
+            let timestamp = json_timestamp(&message["E"]);
+            message["B"]
+                .as_array()
+                .into_iter()
+                .flatten()
+                .filter_map(|balance| {
+                    Some(UserDataEvent::Balance(BalanceUpdate {
+                        exchange: CexExchange::Binance,
+                        asset: json_str(&balance["a"])?,
+                        free: json_f64(&balance["f"])?,
+                        locked: json_f64(&balance["l"]).unwrap_or(0.0),
+                        timestamp,
+                    }))
+                })
+                .collect()
+        }
+        _ => Vec::new(),
+    }
+}
+
+#[async_trait]
+impl UserDataStream for Binance {
+    fn parse_user_data(&self, message: &serde_json::Value) -> Vec<UserDataEvent> {
+        parse_user_data(message)
+    }
+
+    /// Demo mode uses the spot testnet (testnet API keys).
+    async fn stream_user_data(
+        &self,
+        credentials: &Credentials,
+        mode: TradingMode,
+        reconnect_attempts: u32,
+        reconnect_delay_ms: u64,
+    ) -> Result<mpsc::Receiver<UserDataEvent>, MarketScannerError> {
+        let client = self.client.clone();
+        let api_key = credentials.api_key().to_string();
+        let first_session = listen_key_session(client.clone(), api_key.clone(), mode).await?;
+        let new_session: SessionFactory =
+            Arc::new(move || Box::pin(listen_key_session(client.clone(), api_key.clone(), mode)));
+        Ok(spawn_user_data_stream(
+            CexExchange::Binance,
+            first_session,
+            new_session,
+            parse_user_data,
+            reconnect_attempts,
+            reconnect_delay_ms,
+        ))
+    }
+}
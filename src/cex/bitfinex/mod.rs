@@ -1,17 +1,26 @@
 mod types;
 
 use crate::cex::bitfinex::types::BitfinexOrderBookResponse;
+use crate::common::capabilities::{Capabilities, VenueCapability};
+use crate::common::depth_sync::PriceKey;
+use crate::common::market::split_base_quote;
+use crate::common::reconnect::{ReconnectPolicy, ws_endpoint};
+use crate::common::symbol_registry;
 use crate::common::{
-    CEXTrait, CexExchange, CexPrice, Exchange, ExchangeTrait, MarketScannerError, find_mid_price,
-    format_symbol_for_exchange, format_symbol_for_exchange_ws, get_timestamp_millis,
-    normalize_symbol, standard_symbol_for_cex_ws_response,
+    CEXTrait, CexExchange, CexPrice, Exchange, ExchangeTrait, MarketScannerError, RequestPriority,
+    Timestamp, TradingStatus, WsSymbolFilter, canonical_asset, decode_ws_json, find_mid_price,
+    format_symbol_for_exchange, format_symbol_for_exchange_ws, normalize_symbol, record_ws_connect,
+    standard_symbol_for_cex_ws_response,
 };
 use crate::create_exchange;
 use async_trait::async_trait;
 use futures::{SinkExt, StreamExt};
+use std::collections::{BTreeMap, HashMap};
 use tokio::sync::mpsc;
 
 const BITFINEX_API_BASE: &str = "https://api-pub.bitfinex.com/v2";
+/// Price levels per side requested on the WS `book` channel
+const BITFINEX_WS_BOOK_LEN: &str = "25";
 const BITFINEX_WS_URL: &str = "wss://api-pub.bitfinex.com/ws/2";
 
 create_exchange!(Bitfinex);
@@ -27,13 +36,15 @@ impl ExchangeTrait for Bitfinex {
     }
 
     fn exchange_name(&self) -> &str {
-        "Bitfinex"
+        CexExchange::Bitfinex.rest_venue_name()
     }
 
     async fn health_check(&self) -> Result<(), MarketScannerError> {
         // Bitfinex platform status endpoint - test connectivity to the REST API
         let endpoint = "platform/status";
-        let response: types::BitfinexPlatformStatus = self.get(endpoint).await?;
+        let response: types::BitfinexPlatformStatus = self
+            .get_with_priority(endpoint, RequestPriority::Metadata)
+            .await?;
 
         // Bitfinex returns [1] for operational, [0] for maintenance
         if let Some(code) = response.first() {
@@ -44,6 +55,11 @@ impl ExchangeTrait for Bitfinex {
 
         Err(MarketScannerError::HealthCheckFailed)
     }
+
+    async fn get_server_time(&self) -> Result<Timestamp, MarketScannerError> {
+        // No server time endpoint: the HTTP Date header has second resolution
+        self.get_date_header("platform/status").await
+    }
 }
 
 #[async_trait]
@@ -52,6 +68,14 @@ impl CEXTrait for Bitfinex {
         true
     }
 
+    fn capabilities(&self) -> Capabilities {
+        Capabilities::new(&[
+            VenueCapability::Websocket,
+            VenueCapability::InstrumentIds,
+            VenueCapability::ServerTime,
+        ])
+    }
+
     async fn get_price(&self, symbol: &str) -> Result<CexPrice, MarketScannerError> {
         // Validate symbol is not empty
         if symbol.is_empty() {
@@ -173,12 +197,18 @@ impl CEXTrait for Bitfinex {
             ask_price: ask,
             bid_qty,
             ask_qty,
-            timestamp: get_timestamp_millis(),
+            timestamp: Timestamp::now(),
             exchange: Exchange::Cex(CexExchange::Bitfinex),
+            trading_status: TradingStatus::Unknown,
         })
     }
 
     /// Connection stays open; incoming ticker updates are sent over the returned Receiver.
+    async fn get_instrument_ids(&self) -> Result<HashMap<String, String>, MarketScannerError> {
+        let response: serde_json::Value = self.get_cached("conf/pub:list:pair:exchange").await?;
+        Ok(parse_bitfinex_pairs(&response))
+    }
+
     async fn stream_price_websocket(
         &self,
         symbols: &[&str],
@@ -197,35 +227,37 @@ impl CEXTrait for Bitfinex {
             .collect::<Result<Vec<_>, _>>()?;
 
         let (tx, rx) = mpsc::channel(64);
-        let delay = std::time::Duration::from_millis(if reconnect_delay_ms == 0 {
-            1000
-        } else {
-            reconnect_delay_ms
-        });
+        let symbol_filter = WsSymbolFilter::new(&CexExchange::Bitfinex, symbols);
+        let policy = ReconnectPolicy::new(reconnect_attempts, reconnect_delay_ms);
+        let url = ws_endpoint(&CexExchange::Bitfinex, BITFINEX_WS_URL);
 
-        tokio::spawn(async move {
+        symbol_registry::spawn(async move {
             let mut attempt = 0u32;
             loop {
                 attempt += 1;
-                let (mut ws_stream, _) = match tokio_tungstenite::connect_async(BITFINEX_WS_URL)
-                    .await
-                {
-                    Ok(v) => v,
+                let (mut ws_stream, _) = match tokio_tungstenite::connect_async(&url).await {
+                    Ok(v) => {
+                        record_ws_connect(&CexExchange::Bitfinex);
+                        v
+                    }
                     Err(_) => {
-                        if tx.is_closed() || reconnect_attempts == 0 || attempt > reconnect_attempts
-                        {
+                        if tx.is_closed() || !policy.should_reconnect(attempt) {
                             break;
                         }
-                        tokio::time::sleep(delay).await;
+                        tokio::time::sleep(policy.delay()).await;
                         continue;
                     }
                 };
 
                 for bitfinex_symbol in &bitfinex_symbols {
+                    // Raw price levels (P0) so sizes are top-of-book level sizes, as on REST
                     let subscribe_msg = serde_json::json!({
                         "event": "subscribe",
-                        "channel": "ticker",
-                        "symbol": bitfinex_symbol
+                        "channel": "book",
+                        "symbol": bitfinex_symbol,
+                        "prec": "P0",
+                        "freq": "F0",
+                        "len": BITFINEX_WS_BOOK_LEN
                     });
                     if ws_stream
                         .send(tokio_tungstenite::tungstenite::Message::Text(
@@ -239,24 +271,31 @@ impl CEXTrait for Bitfinex {
                 }
 
                 let (_write, mut read) = ws_stream.split();
-                let mut chan_to_symbol: std::collections::HashMap<u64, String> =
-                    std::collections::HashMap::new();
+                let mut chan_to_symbol: HashMap<u64, String> = HashMap::new();
+                // Local book per channel, rebuilt from the snapshot sent on (re)subscribe
+                let mut books: HashMap<u64, (BookMap, BookMap)> = HashMap::new();
 
                 while let Some(Ok(msg)) = read.next().await {
+                    // Control frames carry no payload to decode
+                    if msg.is_ping() || msg.is_pong() || msg.is_close() {
+                        continue;
+                    }
                     let text = match msg.into_text() {
                         Ok(t) => t,
                         Err(_) => continue,
                     };
-                    let value: serde_json::Value = match serde_json::from_str(&text) {
-                        Ok(v) => v,
-                        Err(_) => continue,
-                    };
+                    let value: serde_json::Value =
+                        match decode_ws_json(&CexExchange::Bitfinex, &text) {
+                            Some(v) => v,
+                            None => continue,
+                        };
                     if let (Some(ev), Some(chan_id), Some(sym)) = (
                         value.get("event").and_then(|e| e.as_str()),
                         value.get("chanId").and_then(|c| c.as_u64()),
                         value.get("symbol").and_then(|s| s.as_str()),
                     ) {
                         if ev == "subscribed" {
+                            books.remove(&chan_id);
                             chan_to_symbol.insert(
                                 chan_id,
                                 standard_symbol_for_cex_ws_response(sym, &CexExchange::Bitfinex),
@@ -276,20 +315,28 @@ impl CEXTrait for Bitfinex {
                         Some(s) => s.clone(),
                         None => continue,
                     };
+                    // Heartbeats ("hb") carry no book data
                     let data = match arr[1].as_array() {
-                        Some(d) if d.len() >= 4 => d,
+                        Some(d) if !d.is_empty() => d,
                         _ => continue,
                     };
-                    let bid = match data[0].as_f64() {
-                        Some(b) if b > 0.0 => b,
-                        _ => continue,
-                    };
-                    let bid_qty = data[1].as_f64().unwrap_or(0.0).abs();
-                    let ask = match data[2].as_f64() {
-                        Some(a) if a > 0.0 => a,
-                        _ => continue,
+                    let (bids, asks) = books
+                        .entry(chan_id)
+                        .or_insert_with(|| (BTreeMap::new(), BTreeMap::new()));
+                    if data[0].is_array() {
+                        // Snapshot: [[price, count, amount], ...]
+                        bids.clear();
+                        asks.clear();
+                        for entry in data {
+                            apply_entry(bids, asks, entry);
+                        }
+                    } else {
+                        apply_entry(bids, asks, &arr[1]);
+                    }
+                    let (bid, ask, bid_qty, ask_qty) = match best_bid_ask(bids, asks) {
+                        Some(v) => v,
+                        None => continue,
                     };
-                    let ask_qty = data[3].as_f64().unwrap_or(0.0).abs();
                     let price = CexPrice {
                         symbol: symbol_std,
                         mid_price: find_mid_price(bid, ask),
@@ -297,21 +344,89 @@ impl CEXTrait for Bitfinex {
                         ask_price: ask,
                         bid_qty,
                         ask_qty,
-                        timestamp: get_timestamp_millis(),
+                        timestamp: Timestamp::now(),
                         exchange: Exchange::Cex(CexExchange::Bitfinex),
+                        trading_status: TradingStatus::Unknown,
                     };
+                    if !symbol_filter.allows(&price) {
+                        continue;
+                    }
                     if tx.send(price).await.is_err() {
                         return;
                     }
                 }
 
-                if tx.is_closed() || reconnect_attempts == 0 || attempt > reconnect_attempts {
+                if tx.is_closed() || !policy.should_reconnect(attempt) {
                     break;
                 }
-                tokio::time::sleep(delay).await;
+                tokio::time::sleep(policy.delay()).await;
             }
         });
 
         Ok(rx)
     }
 }
+
+/// One side of the local WS book, price -> size
+type BookMap = BTreeMap<PriceKey, f64>;
+
+/// Applies one `[price, count, amount]` book entry: amount > 0 is a bid, amount < 0 an ask,
+/// count 0 removes the level.
+fn apply_entry(bids: &mut BookMap, asks: &mut BookMap, entry: &serde_json::Value) {
+    let entry = match entry.as_array().filter(|e| e.len() >= 3) {
+        Some(e) => e,
+        None => return,
+    };
+    let (Some(price), Some(count), Some(amount)) =
+        (entry[0].as_f64(), entry[1].as_f64(), entry[2].as_f64())
+    else {
+        return;
+    };
+    let price = PriceKey(price);
+    if count == 0.0 {
+        if amount > 0.0 {
+            bids.remove(&price);
+        } else {
+            asks.remove(&price);
+        }
+        return;
+    }
+    if amount > 0.0 {
+        bids.insert(price, amount);
+    } else if amount < 0.0 {
+        asks.insert(price, -amount);
+    }
+}
+
+/// Best bid and ask with their sizes as (bid, ask, bid_qty, ask_qty).
+fn best_bid_ask(bids: &BookMap, asks: &BookMap) -> Option<(f64, f64, f64, f64)> {
+    let (bid, bid_qty) = bids.iter().next_back()?;
+    let (ask, ask_qty) = asks.iter().next()?;
+    if bid.0 <= 0.0 || ask.0 <= 0.0 {
+        return None;
+    }
+    Some((bid.0, ask.0, *bid_qty, *ask_qty))
+}
+
+/// Builds the standard symbol -> Bitfinex trading pair map from the
+/// `conf/pub:list:pair:exchange` listing (`[["BTCUSD", "BTCUST", "DOGE:USD", ...]]`).
+/// Long tickers are separated by `:`; `UST` is Bitfinex's spelling of USDT.
+fn parse_bitfinex_pairs(response: &serde_json::Value) -> HashMap<String, String> {
+    let mut pairs = HashMap::new();
+    let Some(listing) = response[0].as_array() else {
+        return pairs;
+    };
+    for pair in listing.iter().filter_map(|p| p.as_str()) {
+        let (base, quote) = match pair.split_once(':') {
+            Some((base, quote)) => (base, quote),
+            None => split_base_quote(pair),
+        };
+        if base.is_empty() || quote.is_empty() {
+            continue;
+        }
+        let quote = if quote == "UST" { "USDT" } else { quote };
+        let standard = format!("{}{}", canonical_asset(base), quote);
+        pairs.insert(standard, format!("t{}", pair));
+    }
+    pairs
+}
@@ -1,9 +1,17 @@
+mod trade;
 mod types;
 
+pub use trade::BITGET_PAPER_TRADING_HEADER;
+
 use crate::cex::bitget::types::BitgetOrderBookResponse;
+use crate::common::capabilities::{Capabilities, VenueCapability};
+use crate::common::clock::json_server_time;
+use crate::common::reconnect::{ReconnectPolicy, ws_endpoint};
+use crate::common::symbol_registry;
 use crate::common::{
-    CEXTrait, CexExchange, CexPrice, Exchange, ExchangeTrait, MarketScannerError, find_mid_price,
-    format_symbol_for_exchange, format_symbol_for_exchange_ws, get_timestamp_millis, parse_f64,
+    CEXTrait, CexExchange, CexPrice, Exchange, ExchangeTrait, MarketScannerError, RequestPriority,
+    Timestamp, TradingStatus, WsSymbolFilter, decode_ws_json, find_mid_price,
+    format_symbol_for_exchange, format_symbol_for_exchange_ws, parse_f64, record_ws_connect,
     standard_symbol_for_cex_ws_response,
 };
 use crate::create_exchange;
@@ -27,13 +35,15 @@ impl ExchangeTrait for Bitget {
     }
 
     fn exchange_name(&self) -> &str {
-        "Bitget"
+        CexExchange::Bitget.rest_venue_name()
     }
 
     async fn health_check(&self) -> Result<(), MarketScannerError> {
         // Bitget public server time endpoint - test connectivity to the REST API
         let endpoint = "public/time";
-        let response: serde_json::Value = self.get(endpoint).await?;
+        let response: serde_json::Value = self
+            .get_with_priority(endpoint, RequestPriority::Metadata)
+            .await?;
 
         // Check if API returned success (Bitget uses "00000" for success)
         let code = response["code"].as_str();
@@ -43,6 +53,13 @@ impl ExchangeTrait for Bitget {
             Err(MarketScannerError::HealthCheckFailed)
         }
     }
+
+    async fn get_server_time(&self) -> Result<Timestamp, MarketScannerError> {
+        let response: serde_json::Value = self
+            .get_with_priority("public/time", RequestPriority::Metadata)
+            .await?;
+        json_server_time(&response, "/data/serverTime", self.exchange_name())
+    }
 }
 
 #[async_trait]
@@ -51,6 +68,14 @@ impl CEXTrait for Bitget {
         true
     }
 
+    fn capabilities(&self) -> Capabilities {
+        Capabilities::new(&[
+            VenueCapability::Websocket,
+            VenueCapability::Execution,
+            VenueCapability::ServerTime,
+        ])
+    }
+
     async fn get_price(&self, symbol: &str) -> Result<CexPrice, MarketScannerError> {
         // Validate symbol is not empty
         if symbol.is_empty() {
@@ -130,8 +155,9 @@ impl CEXTrait for Bitget {
             ask_price: ask,
             bid_qty,
             ask_qty,
-            timestamp: get_timestamp_millis(),
+            timestamp: Timestamp::now(),
             exchange: Exchange::Cex(CexExchange::Bitget),
+            trading_status: TradingStatus::Unknown,
         })
     }
 
@@ -165,24 +191,24 @@ impl CEXTrait for Bitget {
             .collect();
 
         let (tx, rx) = mpsc::channel(64);
-        let delay =
-            std::time::Duration::from_millis(if reconnect_delay_ms == 0 { 1000 } else { reconnect_delay_ms });
+        let symbol_filter = WsSymbolFilter::new(&CexExchange::Bitget, symbols);
+        let policy = ReconnectPolicy::new(reconnect_attempts, reconnect_delay_ms);
+        let url = ws_endpoint(&CexExchange::Bitget, BITGET_WS_URL);
 
-        tokio::spawn(async move {
+        symbol_registry::spawn(async move {
             let mut attempt = 0u32;
             loop {
                 attempt += 1;
-                let (mut ws_stream, _) = match tokio_tungstenite::connect_async(BITGET_WS_URL).await
-                {
-                    Ok(v) => v,
+                let (mut ws_stream, _) = match tokio_tungstenite::connect_async(&url).await {
+                    Ok(v) => {
+                        record_ws_connect(&CexExchange::Bitget);
+                        v
+                    }
                     Err(_) => {
-                        if tx.is_closed()
-                            || reconnect_attempts == 0
-                            || attempt > reconnect_attempts
-                        {
+                        if tx.is_closed() || !policy.should_reconnect(attempt) {
                             break;
                         }
-                        tokio::time::sleep(delay).await;
+                        tokio::time::sleep(policy.delay()).await;
                         continue;
                     }
                 };
@@ -198,26 +224,28 @@ impl CEXTrait for Bitget {
                     .await
                     .is_err()
                 {
-                    if tx.is_closed()
-                        || reconnect_attempts == 0
-                        || attempt > reconnect_attempts
-                    {
+                    if tx.is_closed() || !policy.should_reconnect(attempt) {
                         break;
                     }
-                    tokio::time::sleep(delay).await;
+                    tokio::time::sleep(policy.delay()).await;
                     continue;
                 }
 
                 let (_write, mut read) = ws_stream.split();
 
                 while let Some(Ok(msg)) = read.next().await {
+                    // Control frames carry no payload to decode
+                    if msg.is_ping() || msg.is_pong() || msg.is_close() {
+                        continue;
+                    }
                     let text = match msg.into_text() {
                         Ok(t) => t,
                         Err(_) => continue,
                     };
-                    let value: serde_json::Value = match serde_json::from_str(&text) {
-                        Ok(v) => v,
-                        Err(_) => continue,
+                    let value: serde_json::Value = match decode_ws_json(&CexExchange::Bitget, &text)
+                    {
+                        Some(v) => v,
+                        None => continue,
                     };
                     if value.get("event").is_some()
                         || value.get("op").and_then(|o| o.as_str()) == Some("subscribe")
@@ -278,22 +306,23 @@ impl CEXTrait for Bitget {
                             ask_price: a,
                             bid_qty: bq,
                             ask_qty: aq,
-                            timestamp: get_timestamp_millis(),
+                            timestamp: Timestamp::now(),
                             exchange: Exchange::Cex(CexExchange::Bitget),
+                            trading_status: TradingStatus::Unknown,
                         };
+                        if !symbol_filter.allows(&price) {
+                            continue;
+                        }
                         if tx.send(price).await.is_err() {
                             return;
                         }
                     }
                 }
 
-                if tx.is_closed()
-                    || reconnect_attempts == 0
-                    || attempt > reconnect_attempts
-                {
+                if tx.is_closed() || !policy.should_reconnect(attempt) {
                     break;
                 }
-                tokio::time::sleep(delay).await;
+                tokio::time::sleep(policy.delay()).await;
             }
         });
 
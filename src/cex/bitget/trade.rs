@@ -0,0 +1,231 @@
+//! Spot orders on Bitget (`spot/trade/place-order`), live or paper trading.
+
+use super::{BITGET_API_BASE, Bitget};
+use crate::cex::execution::{
+    CexOrder, CexOrderAck, CexOrderExecution, OrderStatus, OrderType, OrderUpdate,
+    SignedOrderRequest, TradingMode, format_amount, hmac_sha256_base64, json_f64, json_side,
+    json_str, json_timestamp, required_passphrase,
+};
+use crate::common::{
+    CexExchange, Credentials, MarketScannerError, Timestamp, format_symbol_for_exchange,
+    standard_symbol_for_cex_ws_response,
+};
+use crate::portfolio::TradeSide;
+use reqwest::Method;
+
+const ORDER_PATH: &str = "/api/v2/spot/trade/place-order";
+const ORDER_INFO_PATH: &str = "/api/v2/spot/trade/orderInfo";
+const CANCEL_PATH: &str = "/api/v2/spot/trade/cancel-order";
+/// Header routing requests to Bitget paper trading
+pub const BITGET_PAPER_TRADING_HEADER: &str = "paptrading";
+
+/// Request to `path` (with query string) signed over `timestamp + method + path + body`.
+fn signed_request(
+    credentials: &Credentials,
+    method: Method,
+    path: &str,
+    body: String,
+    mode: TradingMode,
+    timestamp_ms: u64,
+) -> Result<SignedOrderRequest, MarketScannerError> {
+    let passphrase = required_passphrase(credentials, "Bitget")?;
+    let timestamp = timestamp_ms.to_string();
+    let signature = hmac_sha256_base64(
+        credentials.api_secret(),
+        &format!("{}{}{}{}", timestamp, method, path, body),
+    );
+    let mut headers = vec![
+        ("ACCESS-KEY".to_string(), credentials.api_key().to_string()),
+        ("ACCESS-SIGN".to_string(), signature),
+        ("ACCESS-TIMESTAMP".to_string(), timestamp),
+        ("ACCESS-PASSPHRASE".to_string(), passphrase.to_string()),
+        ("locale".to_string(), "en-US".to_string()),
+    ];
+    if mode == TradingMode::Demo {
+        headers.push((BITGET_PAPER_TRADING_HEADER.to_string(), "1".to_string()));
+    }
+    Ok(SignedOrderRequest {
+        method,
+        // Paths carry the `/api/v2` prefix of the API base
+        url: format!("{}{}", BITGET_API_BASE, path.trim_start_matches("/api/v2")),
+        headers,
+        body,
+    })
+}
+
+/// `data` of a successful response.
+fn response_data<'a>(
+    response: &'a serde_json::Value,
+    action: &str,
+) -> Result<&'a serde_json::Value, MarketScannerError> {
+    let code = response["code"].as_str().unwrap_or_default();
+    if code != "00000" {
+        return Err(MarketScannerError::ApiError(format!(
+            "Bitget {} rejected: {} {}",
+            action,
+            code,
+            response["msg"].as_str().unwrap_or_default()
+        )));
+    }
+    Ok(&response["data"])
+}
+
+fn order_status(status: &str) -> OrderStatus {
+    match status {
+        "live" | "init" | "new" => OrderStatus::New,
+        "partially_filled" => OrderStatus::PartiallyFilled,
+        "filled" => OrderStatus::Filled,
+        "cancelled" => OrderStatus::Canceled,
+        _ => OrderStatus::Unknown,
+    }
+}
+
+impl CexOrderExecution for Bitget {
+    fn cex_exchange(&self) -> CexExchange {
+        CexExchange::Bitget
+    }
+
+    fn order_request(
+        &self,
+        credentials: &Credentials,
+        order: &CexOrder,
+        mode: TradingMode,
+        timestamp_ms: u64,
+    ) -> Result<SignedOrderRequest, MarketScannerError> {
+        let order = &order.rounded()?;
+        let price = order.checked_price()?;
+        // Bitget sizes market buys in quote coin; a base quantity cannot be expressed
+        if order.order_type == OrderType::Market && order.side == TradeSide::Buy {
+            return Err(MarketScannerError::InvalidSymbol(
+                "Bitget market buys are sized in quote coin; use a limit order".to_string(),
+            ));
+        }
+
+        let mut body = serde_json::json!({
+            "symbol": format_symbol_for_exchange(&order.symbol, &CexExchange::Bitget)?,
+            "side": match order.side {
+                TradeSide::Buy => "buy",
+                TradeSide::Sell => "sell",
+            },
+            "orderType": match order.order_type {
+                OrderType::Market => "market",
+                OrderType::Limit => "limit",
+            },
+            "force": "gtc",
+            "size": format_amount(order.quantity, order.quantity_step),
+        });
+        if let Some(price) = price {
+            body["price"] = format_amount(price, order.price_tick).into();
+        }
+        if let Some(id) = &order.client_order_id {
+            body["clientOid"] = id.as_str().into();
+        }
+        signed_request(
+            credentials,
+            Method::POST,
+            ORDER_PATH,
+            body.to_string(),
+            mode,
+            timestamp_ms,
+        )
+    }
+
+    fn parse_order_response(
+        &self,
+        response: &serde_json::Value,
+        order: &CexOrder,
+        mode: TradingMode,
+    ) -> Result<CexOrderAck, MarketScannerError> {
+        // The ack records the amounts that were sent
+        let order = &order.rounded()?;
+        let data = response_data(response, "order")?;
+        let order_id = data["orderId"].as_str().ok_or_else(|| {
+            MarketScannerError::ApiError("Bitget order response missing orderId".to_string())
+        })?;
+        Ok(CexOrderAck {
+            exchange: CexExchange::Bitget,
+            symbol: order.symbol.clone(),
+            order_id: order_id.to_string(),
+            client_order_id: data["clientOid"]
+                .as_str()
+                .filter(|id| !id.is_empty())
+                .map(str::to_string),
+            side: order.side,
+            quantity: order.quantity,
+            price: order.price,
+            mode,
+            timestamp: Timestamp::now(),
+        })
+    }
+
+    fn order_status_request(
+        &self,
+        credentials: &Credentials,
+        ack: &CexOrderAck,
+        timestamp_ms: u64,
+    ) -> Result<SignedOrderRequest, MarketScannerError> {
+        let path = format!("{}?orderId={}", ORDER_INFO_PATH, ack.order_id);
+        signed_request(
+            credentials,
+            Method::GET,
+            &path,
+            String::new(),
+            ack.mode,
+            timestamp_ms,
+        )
+    }
+
+    fn parse_order_status(
+        &self,
+        response: &serde_json::Value,
+        ack: &CexOrderAck,
+    ) -> Result<OrderUpdate, MarketScannerError> {
+        let order = &response_data(response, "order query")?[0];
+        let not_found =
+            || MarketScannerError::ApiError(format!("Bitget order {} not found", ack.order_id));
+        Ok(OrderUpdate {
+            exchange: CexExchange::Bitget,
+            symbol: standard_symbol_for_cex_ws_response(
+                order["symbol"].as_str().ok_or_else(not_found)?,
+                &CexExchange::Bitget,
+            ),
+            order_id: json_str(&order["orderId"]).ok_or_else(not_found)?,
+            client_order_id: json_str(&order["clientOid"]),
+            side: json_side(&order["side"]).unwrap_or(ack.side),
+            status: order_status(order["status"].as_str().unwrap_or_default()),
+            price: json_f64(&order["price"]).filter(|p| *p > 0.0),
+            quantity: json_f64(&order["size"]).unwrap_or(ack.quantity),
+            filled_quantity: json_f64(&order["baseVolume"]).unwrap_or(0.0),
+            average_fill_price: json_f64(&order["priceAvg"]).filter(|p| *p > 0.0),
+            timestamp: json_timestamp(&order["uTime"]),
+        })
+    }
+
+    fn cancel_request(
+        &self,
+        credentials: &Credentials,
+        ack: &CexOrderAck,
+        timestamp_ms: u64,
+    ) -> Result<SignedOrderRequest, MarketScannerError> {
+        let body = serde_json::json!({
+            "symbol": format_symbol_for_exchange(&ack.symbol, &CexExchange::Bitget)?,
+            "orderId": ack.order_id,
+        });
+        signed_request(
+            credentials,
+            Method::POST,
+            CANCEL_PATH,
+            body.to_string(),
+            ack.mode,
+            timestamp_ms,
+        )
+    }
+
+    fn parse_cancel_response(
+        &self,
+        response: &serde_json::Value,
+        _ack: &CexOrderAck,
+    ) -> Result<(), MarketScannerError> {
+        response_data(response, "cancel").map(|_| ())
+    }
+}
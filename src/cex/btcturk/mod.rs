@@ -1,9 +1,11 @@
 mod types;
 
 use crate::cex::btcturk::types::BtcturkOrderBookResponse;
+use crate::common::capabilities::{Capabilities, VenueCapability};
 use crate::common::{
-    CEXTrait, CexExchange, CexPrice, Exchange, ExchangeTrait, MarketScannerError, find_mid_price,
-    format_symbol_for_exchange, get_timestamp_millis, parse_f64,
+    CEXTrait, CexExchange, CexPrice, Exchange, ExchangeTrait, MarketScannerError, OrderBook,
+    OrderBookLevel, RequestPriority, Timestamp, TradingStatus, find_mid_price,
+    format_symbol_for_exchange, parse_f64,
 };
 use crate::create_exchange;
 use async_trait::async_trait;
@@ -23,14 +25,16 @@ impl ExchangeTrait for Btcturk {
     }
 
     fn exchange_name(&self) -> &str {
-        "BTCTurk"
+        CexExchange::Btcturk.rest_venue_name()
     }
 
     async fn health_check(&self) -> Result<(), MarketScannerError> {
         // BTCTurk orderbook endpoint - test connectivity to the REST API
         // Using a common pair like BTCUSDT for health check
         let endpoint = "orderbook?pairSymbol=BTCUSDT&limit=1";
-        let response: serde_json::Value = self.get(endpoint).await?;
+        let response: serde_json::Value = self
+            .get_with_priority(endpoint, RequestPriority::Metadata)
+            .await?;
 
         // BTCTurk returns {"data": {...}, "success": true, ...}
         let success = response["success"].as_bool().unwrap_or(false);
@@ -40,6 +44,12 @@ impl ExchangeTrait for Btcturk {
             Err(MarketScannerError::HealthCheckFailed)
         }
     }
+
+    async fn get_server_time(&self) -> Result<Timestamp, MarketScannerError> {
+        // No server time endpoint: the HTTP Date header has second resolution
+        self.get_date_header("orderbook?pairSymbol=BTCUSDT&limit=1")
+            .await
+    }
 }
 
 #[async_trait]
@@ -49,6 +59,10 @@ impl CEXTrait for Btcturk {
         false
     }
 
+    fn capabilities(&self) -> Capabilities {
+        Capabilities::new(&[VenueCapability::OrderBook, VenueCapability::ServerTime])
+    }
+
     async fn get_price(&self, symbol: &str) -> Result<CexPrice, MarketScannerError> {
         // Validate symbol is not empty
         if symbol.is_empty() {
@@ -120,7 +134,72 @@ impl CEXTrait for Btcturk {
             ask_price: ask,
             bid_qty,
             ask_qty,
-            timestamp: get_timestamp_millis(),
+            timestamp: Timestamp::now(),
+            exchange: Exchange::Cex(CexExchange::Btcturk),
+            trading_status: TradingStatus::Unknown,
+        })
+    }
+
+    async fn get_order_book(
+        &self,
+        symbol: &str,
+        depth: usize,
+    ) -> Result<OrderBook, MarketScannerError> {
+        if symbol.is_empty() {
+            return Err(MarketScannerError::InvalidSymbol(
+                "Symbol cannot be empty".to_string(),
+            ));
+        }
+
+        let btcturk_symbol = format_symbol_for_exchange(symbol, &CexExchange::Btcturk)?;
+        let endpoint = format!(
+            "orderbook?pairSymbol={}&limit={}",
+            btcturk_symbol,
+            depth.max(1)
+        );
+
+        let response: serde_json::Value = self
+            .get_with_priority(&endpoint, RequestPriority::OrderBook)
+            .await?;
+        let success = response["success"].as_bool().unwrap_or(false);
+        if !success {
+            let message = response["message"]
+                .as_str()
+                .unwrap_or("Unknown error")
+                .to_string();
+            return Err(MarketScannerError::ApiError(format!(
+                "BTCTurk API error: {}",
+                message
+            )));
+        }
+
+        let orderbook_response: BtcturkOrderBookResponse = serde_json::from_value(response)
+            .map_err(|e| {
+                MarketScannerError::ApiError(format!(
+                    "BTCTurk API error: failed to parse orderbook response: {}",
+                    e
+                ))
+            })?;
+
+        let to_levels =
+            |entries: &[[String; 2]]| -> Result<Vec<OrderBookLevel>, MarketScannerError> {
+                entries
+                    .iter()
+                    .take(depth)
+                    .map(|e| {
+                        Ok(OrderBookLevel {
+                            price: parse_f64(&e[0], "price")?,
+                            quantity: parse_f64(&e[1], "quantity")?,
+                        })
+                    })
+                    .collect()
+            };
+
+        Ok(OrderBook {
+            symbol: crate::common::normalize_symbol(symbol),
+            bids: to_levels(&orderbook_response.data.bids)?,
+            asks: to_levels(&orderbook_response.data.asks)?,
+            timestamp: Timestamp::now(),
             exchange: Exchange::Cex(CexExchange::Btcturk),
         })
     }
@@ -1,19 +1,33 @@
+mod trade;
 mod types;
+mod user_data;
+
+pub use trade::BYBIT_DEMO_API_BASE;
 
 use crate::cex::bybit::types::{BybitOrderbookWsMessage, BybitTickerData};
+use crate::common::capabilities::{Capabilities, VenueCapability};
+use crate::common::clock::json_server_time;
+use crate::common::reconnect::{ReconnectPolicy, ws_endpoint};
+use crate::common::symbol_registry;
 use crate::common::{
-    CEXTrait, CexExchange, CexPrice, Exchange, ExchangeTrait, MarketScannerError, find_mid_price,
-    format_symbol_for_exchange, format_symbol_for_exchange_ws, get_timestamp_millis,
-    normalize_symbol, parse_f64, standard_symbol_for_cex_ws_response,
+    CEXTrait, CexExchange, CexPrice, DepthDelta, DepthSnapshot, DepthSyncStatus, Exchange,
+    ExchangeTrait, MarketScannerError, OrderBook, OrderBookLevel, OrderBookSynchronizer,
+    RequestPriority, Timestamp, TradingStatus, WsSymbolFilter, decode_ws_json, decode_ws_value,
+    find_mid_price, format_symbol_for_exchange, format_symbol_for_exchange_ws, normalize_symbol,
+    parse_f64, record_ws_connect, standard_symbol_for_cex_ws_response,
 };
 use crate::create_exchange;
 use async_trait::async_trait;
 use futures::{SinkExt, StreamExt};
 use tokio::sync::mpsc;
+use tokio_tungstenite::tungstenite::Message as WsMessage;
 
 const BYBIT_API_BASE: &str = "https://api.bybit.com/v5";
 const BYBIT_WS_SPOT: &str = "wss://stream.bybit.com/v5/public/spot";
 
+/// Depths of the spot `orderbook.{depth}` channels.
+const BYBIT_BOOK_DEPTHS: [usize; 4] = [1, 50, 200, 1000];
+
 create_exchange!(Bybit);
 
 #[async_trait]
@@ -27,18 +41,25 @@ impl ExchangeTrait for Bybit {
     }
 
     fn exchange_name(&self) -> &str {
-        "Bybit"
+        CexExchange::Bybit.rest_venue_name()
     }
 
     async fn health_check(&self) -> Result<(), MarketScannerError> {
         // Bybit market/time endpoint - test connectivity to the REST API
         let endpoint = "market/time";
-        self.get::<serde_json::Value>(endpoint)
+        self.get_with_priority::<serde_json::Value>(endpoint, RequestPriority::Metadata)
             .await
             .map_err(|_| MarketScannerError::HealthCheckFailed)?;
 
         Ok(())
     }
+
+    async fn get_server_time(&self) -> Result<Timestamp, MarketScannerError> {
+        let response: serde_json::Value = self
+            .get_with_priority("market/time", RequestPriority::Metadata)
+            .await?;
+        json_server_time(&response, "/time", self.exchange_name())
+    }
 }
 
 #[async_trait]
@@ -47,6 +68,16 @@ impl CEXTrait for Bybit {
         true
     }
 
+    fn capabilities(&self) -> Capabilities {
+        Capabilities::new(&[
+            VenueCapability::Websocket,
+            VenueCapability::OrderBookStream,
+            VenueCapability::Execution,
+            VenueCapability::UserData,
+            VenueCapability::ServerTime,
+        ])
+    }
+
     async fn get_price(&self, symbol: &str) -> Result<CexPrice, MarketScannerError> {
         // Validate symbol is not empty
         if symbol.is_empty() {
@@ -105,8 +136,9 @@ impl CEXTrait for Bybit {
             ask_price: ask,
             bid_qty,
             ask_qty,
-            timestamp: get_timestamp_millis(),
+            timestamp: Timestamp::now(),
             exchange: Exchange::Cex(CexExchange::Bybit),
+            trading_status: TradingStatus::Unknown,
         })
     }
 
@@ -132,24 +164,24 @@ impl CEXTrait for Bybit {
             .collect::<Result<Vec<_>, MarketScannerError>>()?;
 
         let (tx, rx) = mpsc::channel(64);
-        let delay =
-            std::time::Duration::from_millis(if reconnect_delay_ms == 0 { 1000 } else { reconnect_delay_ms });
+        let symbol_filter = WsSymbolFilter::new(&CexExchange::Bybit, symbols);
+        let policy = ReconnectPolicy::new(reconnect_attempts, reconnect_delay_ms);
+        let url = ws_endpoint(&CexExchange::Bybit, BYBIT_WS_SPOT);
 
-        tokio::spawn(async move {
+        symbol_registry::spawn(async move {
             let mut attempt = 0u32;
             loop {
                 attempt += 1;
-                let (mut ws_stream, _) = match tokio_tungstenite::connect_async(BYBIT_WS_SPOT).await
-                {
-                    Ok(v) => v,
+                let (mut ws_stream, _) = match tokio_tungstenite::connect_async(&url).await {
+                    Ok(v) => {
+                        record_ws_connect(&CexExchange::Bybit);
+                        v
+                    }
                     Err(_) => {
-                        if tx.is_closed()
-                            || reconnect_attempts == 0
-                            || attempt > reconnect_attempts
-                        {
+                        if tx.is_closed() || !policy.should_reconnect(attempt) {
                             break;
                         }
-                        tokio::time::sleep(delay).await;
+                        tokio::time::sleep(policy.delay()).await;
                         continue;
                     }
                 };
@@ -165,30 +197,38 @@ impl CEXTrait for Bybit {
                     .await
                     .is_err()
                 {
-                    if tx.is_closed()
-                        || reconnect_attempts == 0
-                        || attempt > reconnect_attempts
-                    {
+                    if tx.is_closed() || !policy.should_reconnect(attempt) {
                         break;
                     }
-                    tokio::time::sleep(delay).await;
+                    tokio::time::sleep(policy.delay()).await;
                     continue;
                 }
 
                 let (_write, mut read) = ws_stream.split();
 
                 while let Some(Ok(msg)) = read.next().await {
+                    // Control frames carry no payload to decode
+                    if msg.is_ping() || msg.is_pong() || msg.is_close() {
+                        continue;
+                    }
                     let text = match msg.into_text() {
                         Ok(t) => t,
                         Err(_) => continue,
                     };
-                    let parsed: BybitOrderbookWsMessage = match serde_json::from_str(&text) {
-                        Ok(p) => p,
-                        Err(_) => continue,
+                    let value: serde_json::Value = match decode_ws_json(&CexExchange::Bybit, &text)
+                    {
+                        Some(v) => v,
+                        None => continue,
                     };
-                    if parsed.msg_type != "snapshot" {
+                    // Subscribe acks and pongs carry no book
+                    if value.get("type").and_then(|t| t.as_str()) != Some("snapshot") {
                         continue;
                     }
+                    let parsed: BybitOrderbookWsMessage =
+                        match decode_ws_value(&CexExchange::Bybit, value) {
+                            Some(p) => p,
+                            None => continue,
+                        };
                     let data = &parsed.data;
                     let symbol_std =
                         standard_symbol_for_cex_ws_response(&data.symbol, &CexExchange::Bybit);
@@ -224,24 +264,173 @@ impl CEXTrait for Bybit {
                         ask_price,
                         bid_qty,
                         ask_qty,
-                        timestamp: get_timestamp_millis(),
+                        timestamp: Timestamp::now(),
                         exchange: Exchange::Cex(CexExchange::Bybit),
+                        trading_status: TradingStatus::Unknown,
                     };
+                    if !symbol_filter.allows(&price) {
+                        continue;
+                    }
                     if tx.send(price).await.is_err() {
                         return;
                     }
                 }
 
-                if tx.is_closed()
-                    || reconnect_attempts == 0
-                    || attempt > reconnect_attempts
+                if tx.is_closed() || !policy.should_reconnect(attempt) {
+                    break;
+                }
+                tokio::time::sleep(policy.delay()).await;
+            }
+        });
+
+        Ok(rx)
+    }
+
+    /// Streams the shallowest `orderbook.{1,50,200,1000}` channel holding `depth` levels:
+    /// a snapshot, then deltas applied in arrival order. A new snapshot (e.g. after a
+    /// service restart) replaces the book.
+    async fn stream_order_book(
+        &self,
+        symbol: &str,
+        depth: usize,
+        reconnect_attempts: u32,
+        reconnect_delay_ms: u64,
+    ) -> Result<mpsc::Receiver<OrderBook>, MarketScannerError> {
+        if symbol.is_empty() {
+            return Err(MarketScannerError::InvalidSymbol(
+                "Symbol cannot be empty".to_string(),
+            ));
+        }
+
+        let channel_depth = BYBIT_BOOK_DEPTHS
+            .into_iter()
+            .find(|d| *d >= depth)
+            .unwrap_or(BYBIT_BOOK_DEPTHS[BYBIT_BOOK_DEPTHS.len() - 1]);
+        let topic = format!(
+            "orderbook.{}.{}",
+            channel_depth,
+            format_symbol_for_exchange_ws(symbol, &CexExchange::Bybit)?
+        );
+        let subscribe_msg = serde_json::json!({ "op": "subscribe", "args": [topic] });
+        let ping_msg = serde_json::json!({ "op": "ping" });
+        let standard_symbol = normalize_symbol(symbol);
+        let (tx, rx) = mpsc::channel(64);
+
+        let policy = ReconnectPolicy::new(reconnect_attempts, reconnect_delay_ms);
+        let url = ws_endpoint(&CexExchange::Bybit, BYBIT_WS_SPOT);
+        symbol_registry::spawn(async move {
+            let mut attempt = 0u32;
+            loop {
+                attempt += 1;
+                let (ws_stream, _) = match tokio_tungstenite::connect_async(&url).await {
+                    Ok(v) => v,
+                    Err(_) => {
+                        if tx.is_closed() || !policy.should_reconnect(attempt) {
+                            break;
+                        }
+                        tokio::time::sleep(policy.delay()).await;
+                        continue;
+                    }
+                };
+
+                let (mut write, mut read) = ws_stream.split();
+                if write
+                    .send(WsMessage::Text(subscribe_msg.to_string()))
+                    .await
+                    .is_err()
                 {
+                    if tx.is_closed() || !policy.should_reconnect(attempt) {
+                        break;
+                    }
+                    tokio::time::sleep(policy.delay()).await;
+                    continue;
+                }
+
+                let mut sync =
+                    OrderBookSynchronizer::new(&standard_symbol, Exchange::Cex(CexExchange::Bybit));
+                // Bybit drops connections without an application-level ping
+                let mut ping_interval = tokio::time::interval(std::time::Duration::from_secs(20));
+                ping_interval.tick().await;
+
+                loop {
+                    let text = tokio::select! {
+                        _ = ping_interval.tick() => {
+                            if write.send(WsMessage::Text(ping_msg.to_string())).await.is_err() {
+                                break;
+                            }
+                            continue;
+                        }
+                        msg = read.next() => match msg {
+                            Some(Ok(WsMessage::Text(t))) => t,
+                            Some(Ok(WsMessage::Close(_))) | Some(Err(_)) | None => break,
+                            Some(Ok(_)) => continue,
+                        },
+                    };
+                    let value: serde_json::Value = match decode_ws_json(&CexExchange::Bybit, &text)
+                    {
+                        Some(v) => v,
+                        None => continue,
+                    };
+                    // Subscribe acks and pongs carry no book
+                    if value.get("topic").is_none() {
+                        continue;
+                    }
+                    let message: BybitOrderbookWsMessage =
+                        match decode_ws_value(&CexExchange::Bybit, value) {
+                            Some(m) => m,
+                            None => continue,
+                        };
+                    let (bids, asks) = match (
+                        parse_bybit_levels(&message.data.bids),
+                        parse_bybit_levels(&message.data.asks),
+                    ) {
+                        (Some(bids), Some(asks)) => (bids, asks),
+                        _ => continue,
+                    };
+                    let status = if message.msg_type == "snapshot" {
+                        sync.apply_snapshot(DepthSnapshot {
+                            last_update_id: message.data.update_id,
+                            bids,
+                            asks,
+                        })
+                    } else {
+                        // Deltas continue whatever the book holds
+                        let next = sync.last_update_id().map_or(1, |last| last + 1);
+                        sync.push_delta(DepthDelta {
+                            first_update_id: next,
+                            final_update_id: next,
+                            bids,
+                            asks,
+                        })
+                    };
+                    if status == DepthSyncStatus::Applied {
+                        if let Some(book) = sync.book(depth) {
+                            if tx.send(book).await.is_err() {
+                                return;
+                            }
+                        }
+                    }
+                }
+
+                if tx.is_closed() || !policy.should_reconnect(attempt) {
                     break;
                 }
-                tokio::time::sleep(delay).await;
+                tokio::time::sleep(policy.delay()).await;
             }
         });
 
         Ok(rx)
     }
 }
+
+fn parse_bybit_levels(levels: &[[String; 2]]) -> Option<Vec<OrderBookLevel>> {
+    levels
+        .iter()
+        .map(|[price, quantity]| {
+            Some(OrderBookLevel {
+                price: parse_f64(price, "depth price").ok()?,
+                quantity: parse_f64(quantity, "depth quantity").ok()?,
+            })
+        })
+        .collect()
+}
@@ -0,0 +1,217 @@
+//! Spot orders on Bybit (`order/create`), live or on the demo trading host.
+
+use super::user_data::order_update;
+use super::{BYBIT_API_BASE, Bybit};
+use crate::cex::execution::{
+    CexOrder, CexOrderAck, CexOrderExecution, OrderType, OrderUpdate, SignedOrderRequest,
+    TradingMode, format_amount, hmac_sha256_hex,
+};
+use crate::common::{
+    CexExchange, Credentials, MarketScannerError, Timestamp, format_symbol_for_exchange,
+};
+use crate::portfolio::TradeSide;
+use reqwest::Method;
+
+/// REST base of Bybit demo trading
+pub const BYBIT_DEMO_API_BASE: &str = "https://api-demo.bybit.com/v5";
+/// Milliseconds a signed request stays valid
+const RECV_WINDOW: &str = "5000";
+
+/// Request to `path` (relative to the v5 base) signed over
+/// `timestamp + key + recv window + payload`, the payload being the JSON body of POST
+/// requests or the query string of GET requests.
+fn signed_request(
+    credentials: &Credentials,
+    method: Method,
+    path: &str,
+    payload: String,
+    mode: TradingMode,
+    timestamp_ms: u64,
+) -> SignedOrderRequest {
+    let timestamp = timestamp_ms.to_string();
+    let signature = hmac_sha256_hex(
+        credentials.api_secret(),
+        &format!(
+            "{}{}{}{}",
+            timestamp,
+            credentials.api_key(),
+            RECV_WINDOW,
+            payload
+        ),
+    );
+    let api_base = match mode {
+        TradingMode::Live => BYBIT_API_BASE,
+        TradingMode::Demo => BYBIT_DEMO_API_BASE,
+    };
+    let (url, body) = if method == Method::GET {
+        (format!("{}{}?{}", api_base, path, payload), String::new())
+    } else {
+        (format!("{}{}", api_base, path), payload)
+    };
+    SignedOrderRequest {
+        method,
+        url,
+        headers: vec![
+            (
+                "X-BAPI-API-KEY".to_string(),
+                credentials.api_key().to_string(),
+            ),
+            ("X-BAPI-TIMESTAMP".to_string(), timestamp),
+            ("X-BAPI-RECV-WINDOW".to_string(), RECV_WINDOW.to_string()),
+            ("X-BAPI-SIGN".to_string(), signature),
+        ],
+        body,
+    }
+}
+
+/// `result` of a successful response.
+fn response_result<'a>(
+    response: &'a serde_json::Value,
+    action: &str,
+) -> Result<&'a serde_json::Value, MarketScannerError> {
+    if response["retCode"].as_i64() != Some(0) {
+        return Err(MarketScannerError::ApiError(format!(
+            "Bybit {} rejected: {} {}",
+            action,
+            response["retCode"],
+            response["retMsg"].as_str().unwrap_or_default()
+        )));
+    }
+    Ok(&response["result"])
+}
+
+impl CexOrderExecution for Bybit {
+    fn cex_exchange(&self) -> CexExchange {
+        CexExchange::Bybit
+    }
+
+    fn order_request(
+        &self,
+        credentials: &Credentials,
+        order: &CexOrder,
+        mode: TradingMode,
+        timestamp_ms: u64,
+    ) -> Result<SignedOrderRequest, MarketScannerError> {
+        let order = &order.rounded()?;
+        let price = order.checked_price()?;
+
+        let mut body = serde_json::json!({
+            "category": "spot",
+            "symbol": format_symbol_for_exchange(&order.symbol, &CexExchange::Bybit)?,
+            "side": match order.side {
+                TradeSide::Buy => "Buy",
+                TradeSide::Sell => "Sell",
+            },
+            "orderType": match order.order_type {
+                OrderType::Market => "Market",
+                OrderType::Limit => "Limit",
+            },
+            "qty": format_amount(order.quantity, order.quantity_step),
+        });
+        match price {
+            Some(price) => body["price"] = format_amount(price, order.price_tick).into(),
+            // Market buys are sized in quote coin unless set to base
+            None => body["marketUnit"] = "baseCoin".into(),
+        }
+        if let Some(id) = &order.client_order_id {
+            body["orderLinkId"] = id.as_str().into();
+        }
+        Ok(signed_request(
+            credentials,
+            Method::POST,
+            "/order/create",
+            body.to_string(),
+            mode,
+            timestamp_ms,
+        ))
+    }
+
+    fn parse_order_response(
+        &self,
+        response: &serde_json::Value,
+        order: &CexOrder,
+        mode: TradingMode,
+    ) -> Result<CexOrderAck, MarketScannerError> {
+        // The ack records the amounts that were sent
+        let order = &order.rounded()?;
+        let result = response_result(response, "order")?;
+        let order_id = result["orderId"].as_str().ok_or_else(|| {
+            MarketScannerError::ApiError("Bybit order response missing orderId".to_string())
+        })?;
+        Ok(CexOrderAck {
+            exchange: CexExchange::Bybit,
+            symbol: order.symbol.clone(),
+            order_id: order_id.to_string(),
+            client_order_id: result["orderLinkId"]
+                .as_str()
+                .filter(|id| !id.is_empty())
+                .map(str::to_string),
+            side: order.side,
+            quantity: order.quantity,
+            price: order.price,
+            mode,
+            timestamp: Timestamp::now(),
+        })
+    }
+
+    fn order_status_request(
+        &self,
+        credentials: &Credentials,
+        ack: &CexOrderAck,
+        timestamp_ms: u64,
+    ) -> Result<SignedOrderRequest, MarketScannerError> {
+        let query = format!(
+            "category=spot&symbol={}&orderId={}",
+            format_symbol_for_exchange(&ack.symbol, &CexExchange::Bybit)?,
+            ack.order_id
+        );
+        Ok(signed_request(
+            credentials,
+            Method::GET,
+            "/order/realtime",
+            query,
+            ack.mode,
+            timestamp_ms,
+        ))
+    }
+
+    fn parse_order_status(
+        &self,
+        response: &serde_json::Value,
+        ack: &CexOrderAck,
+    ) -> Result<OrderUpdate, MarketScannerError> {
+        let result = response_result(response, "order query")?;
+        order_update(&result["list"][0]).ok_or_else(|| {
+            MarketScannerError::ApiError(format!("Bybit order {} not found", ack.order_id))
+        })
+    }
+
+    fn cancel_request(
+        &self,
+        credentials: &Credentials,
+        ack: &CexOrderAck,
+        timestamp_ms: u64,
+    ) -> Result<SignedOrderRequest, MarketScannerError> {
+        let body = serde_json::json!({
+            "category": "spot",
+            "symbol": format_symbol_for_exchange(&ack.symbol, &CexExchange::Bybit)?,
+            "orderId": ack.order_id,
+        });
+        Ok(signed_request(
+            credentials,
+            Method::POST,
+            "/order/cancel",
+            body.to_string(),
+            ack.mode,
+            timestamp_ms,
+        ))
+    }
+
+    fn parse_cancel_response(
+        &self,
+        response: &serde_json::Value,
+        _ack: &CexOrderAck,
+    ) -> Result<(), MarketScannerError> {
+        response_result(response, "cancel").map(|_| ())
+    }
+}
@@ -13,7 +13,7 @@ pub struct BybitTickerData {
     pub ask1_size: String,
 }
 
-/// WebSocket orderbook snapshot (orderbook.1) for spot.
+/// WebSocket orderbook snapshot or delta (orderbook.{depth}) for spot.
 #[derive(Debug, Deserialize)]
 pub struct BybitOrderbookSnapshot {
     #[serde(rename = "s")]
@@ -25,12 +25,16 @@ pub struct BybitOrderbookSnapshot {
     /// Asks: [[price, size], ...], ascending by price.
     #[serde(rename = "a")]
     pub asks: Vec<[String; 2]>,
+    /// Update id; 1 marks a snapshot sent after a service restart
+    #[serde(rename = "u", default)]
+    pub update_id: u64,
 }
 
 #[derive(Debug, Deserialize)]
 pub struct BybitOrderbookWsMessage {
     #[allow(dead_code)]
     pub topic: String,
+    /// "snapshot" or "delta"
     #[serde(rename = "type")]
     pub msg_type: String,
     pub data: BybitOrderbookSnapshot,
@@ -0,0 +1,139 @@
+//! Bybit private WebSocket: `order` and `wallet` topics, live or demo trading.
+
+use super::Bybit;
+use crate::cex::execution::{
+    BalanceUpdate, OrderStatus, OrderUpdate, PrivateWsSession, SessionFactory, TradingMode,
+    UserDataEvent, UserDataStream, hmac_sha256_hex, json_f64, json_side, json_str, json_timestamp,
+    spawn_user_data_stream,
+};
+use crate::common::{
+    CexExchange, Credentials, MarketScannerError, get_timestamp_millis,
+    standard_symbol_for_cex_ws_response,
+};
+use async_trait::async_trait;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::mpsc;
+
+const BYBIT_WS_PRIVATE: &str = "wss://stream.bybit.com/v5/private";
+const BYBIT_DEMO_WS_PRIVATE: &str = "wss://stream-demo.bybit.com/v5/private";
+/// Milliseconds the auth signature stays valid
+const AUTH_VALIDITY_MS: u64 = 10_000;
+
+/// Session with a freshly signed auth message.
+fn private_session(
+    credentials: &Credentials,
+    mode: TradingMode,
+) -> Result<PrivateWsSession, MarketScannerError> {
+    let expires = get_timestamp_millis() + AUTH_VALIDITY_MS;
+    let signature = hmac_sha256_hex(
+        credentials.api_secret(),
+        &format!("GET/realtime{}", expires),
+    );
+    let auth = serde_json::json!({
+        "op": "auth",
+        "args": [credentials.api_key(), expires, signature],
+    });
+    Ok(PrivateWsSession {
+        url: match mode {
+            TradingMode::Live => BYBIT_WS_PRIVATE,
+            TradingMode::Demo => BYBIT_DEMO_WS_PRIVATE,
+        }
+        .to_string(),
+        on_connect: vec![auth.to_string()],
+        after_login: vec![
+            serde_json::json!({"op": "subscribe", "args": ["order", "wallet"]}).to_string(),
+        ],
+        login_ack: Some(|value| value["op"] == "auth" && value["success"] == true),
+        expired: None,
+        text_ping: Some(serde_json::json!({"op": "ping"}).to_string()),
+        ping_interval: Duration::from_secs(20),
+        keepalive: None,
+    })
+}
+
+fn order_status(status: &str) -> OrderStatus {
+    match status {
+        "New" | "Untriggered" => OrderStatus::New,
+        "PartiallyFilled" => OrderStatus::PartiallyFilled,
+        "Filled" => OrderStatus::Filled,
+        "Cancelled" | "PartiallyFilledCanceled" | "Deactivated" => OrderStatus::Canceled,
+        "Rejected" => OrderStatus::Rejected,
+        _ => OrderStatus::Unknown,
+    }
+}
+
+/// Order state from an `order` topic entry or an `order/realtime` query result.
+pub(super) fn order_update(order: &serde_json::Value) -> Option<OrderUpdate> {
+    Some(OrderUpdate {
+        exchange: CexExchange::Bybit,
+        symbol: standard_symbol_for_cex_ws_response(order["symbol"].as_str()?, &CexExchange::Bybit),
+        order_id: json_str(&order["orderId"])?,
+        client_order_id: json_str(&order["orderLinkId"]),
+        side: json_side(&order["side"])?,
+        status: order_status(order["orderStatus"].as_str().unwrap_or_default()),
+        price: json_f64(&order["price"]).filter(|p| *p > 0.0),
+        quantity: json_f64(&order["qty"]).unwrap_or(0.0),
+        filled_quantity: json_f64(&order["cumExecQty"]).unwrap_or(0.0),
+        average_fill_price: json_f64(&order["avgPrice"]).filter(|p| *p > 0.0),
+        timestamp: json_timestamp(&order["updatedTime"]),
+    })
+}
+
+fn parse_user_data(message: &serde_json::Value) -> Vec<UserDataEvent> {
+    let data = message["data"].as_array().into_iter().flatten();
+    match message["topic"].as_str() {
+        Some("order") => data
+            .filter_map(|order| order_update(order).map(UserDataEvent::Order))
+            .collect(),
+        Some("wallet") => {
+            let timestamp = json_timestamp(&message["creationTime"]);
+            data.flat_map(|account| account["coin"].as_array().into_iter().flatten())
+                .filter_map(|coin| {
+                    let balance = json_f64(&coin["walletBalance"])?;
+                    let locked = json_f64(&coin["locked"]).unwrap_or(0.0);
+                    Some(UserDataEvent::Balance(BalanceUpdate {
+                        exchange: CexExchange::Bybit,
+                        asset: json_str(&coin["coin"])?,
+                        free: balance - locked,
+                        locked,
+                        timestamp,
+                    }))
+                })
+                .collect()
+        }
+        _ => Vec::new(),
+    }
+}
+
+#[async_trait]
+impl UserDataStream for Bybit {
+    fn parse_user_data(&self, message: &serde_json::Value) -> Vec<UserDataEvent> {
+        parse_user_data(message)
+    }
+
+    /// Demo mode uses the demo trading stream (demo account API keys).
+    async fn stream_user_data(
+        &self,
+        credentials: &Credentials,
+        mode: TradingMode,
+        reconnect_attempts: u32,
+        reconnect_delay_ms: u64,
+    ) -> Result<mpsc::Receiver<UserDataEvent>, MarketScannerError> {
+        let first_session = private_session(credentials, mode)?;
+        // Each reconnect signs a new auth message
+        let credentials = credentials.clone();
+        let new_session: SessionFactory = Arc::new(move || {
+            let session = private_session(&credentials, mode);
+            Box::pin(async move { session })
+        });
+        Ok(spawn_user_data_stream(
+            CexExchange::Bybit,
+            first_session,
+            new_session,
+            parse_user_data,
+            reconnect_attempts,
+            reconnect_delay_ms,
+        ))
+    }
+}
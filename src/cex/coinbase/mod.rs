@@ -1,10 +1,15 @@
 mod types;
 
 use crate::cex::coinbase::types::{CoinbaseOrderBookResponse, CoinbaseTickerWs};
+use crate::common::capabilities::{Capabilities, VenueCapability};
+use crate::common::clock::json_server_time;
+use crate::common::reconnect::{ReconnectPolicy, ws_endpoint};
+use crate::common::symbol_registry;
 use crate::common::{
-    CEXTrait, CexExchange, CexPrice, Exchange, ExchangeTrait, MarketScannerError, find_mid_price,
-    format_symbol_for_exchange, format_symbol_for_exchange_ws, get_timestamp_millis, parse_f64,
-    standard_symbol_for_cex_ws_response,
+    CEXTrait, CexExchange, CexPrice, Exchange, ExchangeTrait, HttpClientConfig, MarketScannerError,
+    RequestPriority, Timestamp, TradingStatus, WsSymbolFilter, decode_ws_json, decode_ws_value,
+    find_mid_price, format_symbol_for_exchange, format_symbol_for_exchange_ws, parse_f64,
+    record_ws_connect, standard_symbol_for_cex_ws_response,
 };
 use crate::create_exchange;
 use async_trait::async_trait;
@@ -14,7 +19,11 @@ use tokio::sync::mpsc;
 const COINBASE_API_BASE: &str = "https://api.exchange.coinbase.com";
 const COINBASE_WS_FEED: &str = "wss://ws-feed.exchange.coinbase.com";
 
-create_exchange!(Coinbase);
+// Coinbase rejects requests without a User-Agent
+create_exchange!(
+    Coinbase,
+    HttpClientConfig::new().with_user_agent("aeon-market-scanner-rs")
+);
 
 #[async_trait]
 impl ExchangeTrait for Coinbase {
@@ -27,21 +36,16 @@ impl ExchangeTrait for Coinbase {
     }
 
     fn exchange_name(&self) -> &str {
-        "Coinbase"
+        CexExchange::Coinbase.rest_venue_name()
     }
 
-    // Override get method to add User-Agent header
+    // Override get method to include the endpoint in 404 errors
     async fn get<T: for<'de> serde::Deserialize<'de>>(
         &self,
         endpoint: &str,
     ) -> Result<T, MarketScannerError> {
         let url = format!("{}/{}", self.api_base(), endpoint);
-        let response = self
-            .client()
-            .get(&url)
-            .header("User-Agent", "aeon-market-scanner-rs")
-            .send()
-            .await?;
+        let response = self.client().get(&url).send().await?;
 
         let status = response.status();
 
@@ -71,7 +75,9 @@ impl ExchangeTrait for Coinbase {
     async fn health_check(&self) -> Result<(), MarketScannerError> {
         // Coinbase time endpoint - test connectivity to the REST API
         let endpoint = "time";
-        let response: serde_json::Value = self.get(endpoint).await?;
+        let response: serde_json::Value = self
+            .get_with_priority(endpoint, RequestPriority::Metadata)
+            .await?;
 
         // Coinbase returns {"iso": "...", "epoch": ...}
         if response["iso"].is_string() {
@@ -80,6 +86,13 @@ impl ExchangeTrait for Coinbase {
             Err(MarketScannerError::HealthCheckFailed)
         }
     }
+
+    async fn get_server_time(&self) -> Result<Timestamp, MarketScannerError> {
+        let response: serde_json::Value = self
+            .get_with_priority("time", RequestPriority::Metadata)
+            .await?;
+        json_server_time(&response, "/epoch", self.exchange_name())
+    }
 }
 
 #[async_trait]
@@ -88,6 +101,10 @@ impl CEXTrait for Coinbase {
         true
     }
 
+    fn capabilities(&self) -> Capabilities {
+        Capabilities::new(&[VenueCapability::Websocket, VenueCapability::ServerTime])
+    }
+
     async fn get_price(&self, symbol: &str) -> Result<CexPrice, MarketScannerError> {
         // Validate symbol is not empty
         if symbol.is_empty() {
@@ -102,8 +119,13 @@ impl CEXTrait for Coinbase {
         // Using orderbook endpoint with level=1 for best bid/ask only
         let endpoint = format!("products/{}/book?level=1", coinbase_symbol);
 
-        // First get as JSON value to handle errors gracefully
-        let response: serde_json::Value = self.get(&endpoint).await?;
+        // First get as JSON value to handle errors gracefully; the trading status is best
+        // effort and must not fail the price
+        let (response, status) = tokio::join!(
+            self.get::<serde_json::Value>(&endpoint),
+            self.fetch_trading_status(&coinbase_symbol)
+        );
+        let response = response?;
 
         // Check if response has error
         if let Some(message) = response.get("message") {
@@ -187,8 +209,9 @@ impl CEXTrait for Coinbase {
             ask_price: ask,
             bid_qty,
             ask_qty,
-            timestamp: get_timestamp_millis(),
+            timestamp: Timestamp::now(),
             exchange: Exchange::Cex(CexExchange::Coinbase),
+            trading_status: status.unwrap_or_default(),
         })
     }
 
@@ -210,26 +233,24 @@ impl CEXTrait for Coinbase {
             .collect::<Result<Vec<_>, _>>()?;
 
         let (tx, rx) = mpsc::channel(64);
-        let delay = std::time::Duration::from_millis(if reconnect_delay_ms == 0 {
-            1000
-        } else {
-            reconnect_delay_ms
-        });
+        let symbol_filter = WsSymbolFilter::new(&CexExchange::Coinbase, symbols);
+        let policy = ReconnectPolicy::new(reconnect_attempts, reconnect_delay_ms);
+        let url = ws_endpoint(&CexExchange::Coinbase, COINBASE_WS_FEED);
 
-        tokio::spawn(async move {
+        symbol_registry::spawn(async move {
             let mut attempt = 0u32;
             loop {
                 attempt += 1;
-                let (mut ws_stream, _) = match tokio_tungstenite::connect_async(COINBASE_WS_FEED)
-                    .await
-                {
-                    Ok(v) => v,
+                let (mut ws_stream, _) = match tokio_tungstenite::connect_async(&url).await {
+                    Ok(v) => {
+                        record_ws_connect(&CexExchange::Coinbase);
+                        v
+                    }
                     Err(_) => {
-                        if tx.is_closed() || reconnect_attempts == 0 || attempt > reconnect_attempts
-                        {
+                        if tx.is_closed() || !policy.should_reconnect(attempt) {
                             break;
                         }
-                        tokio::time::sleep(delay).await;
+                        tokio::time::sleep(policy.delay()).await;
                         continue;
                     }
                 };
@@ -246,27 +267,38 @@ impl CEXTrait for Coinbase {
                     .await
                     .is_err()
                 {
-                    if tx.is_closed() || reconnect_attempts == 0 || attempt > reconnect_attempts {
+                    if tx.is_closed() || !policy.should_reconnect(attempt) {
                         break;
                     }
-                    tokio::time::sleep(delay).await;
+                    tokio::time::sleep(policy.delay()).await;
                     continue;
                 }
 
                 let (_write, mut read) = ws_stream.split();
 
                 while let Some(Ok(msg)) = read.next().await {
+                    // Control frames carry no payload to decode
+                    if msg.is_ping() || msg.is_pong() || msg.is_close() {
+                        continue;
+                    }
                     let text = match msg.into_text() {
                         Ok(t) => t,
                         Err(_) => continue,
                     };
-                    let ticker: CoinbaseTickerWs = match serde_json::from_str(&text) {
-                        Ok(t) => t,
-                        Err(_) => continue,
-                    };
-                    if ticker.msg_type != "ticker" {
+                    let value: serde_json::Value =
+                        match decode_ws_json(&CexExchange::Coinbase, &text) {
+                            Some(v) => v,
+                            None => continue,
+                        };
+                    // Subscriptions and heartbeats carry no ticker
+                    if value.get("type").and_then(|t| t.as_str()) != Some("ticker") {
                         continue;
                     }
+                    let ticker: CoinbaseTickerWs =
+                        match decode_ws_value(&CexExchange::Coinbase, value) {
+                            Some(t) => t,
+                            None => continue,
+                        };
                     let bid = match parse_f64(&ticker.best_bid, "bid") {
                         Ok(v) => v,
                         Err(_) => continue,
@@ -291,21 +323,59 @@ impl CEXTrait for Coinbase {
                         ask_price: ask,
                         bid_qty,
                         ask_qty,
-                        timestamp: get_timestamp_millis(),
+                        timestamp: Timestamp::now(),
                         exchange: Exchange::Cex(CexExchange::Coinbase),
+                        trading_status: TradingStatus::Unknown,
                     };
+                    if !symbol_filter.allows(&price) {
+                        continue;
+                    }
                     if tx.send(price).await.is_err() {
                         return;
                     }
                 }
 
-                if tx.is_closed() || reconnect_attempts == 0 || attempt > reconnect_attempts {
+                if tx.is_closed() || !policy.should_reconnect(attempt) {
                     break;
                 }
-                tokio::time::sleep(delay).await;
+                tokio::time::sleep(policy.delay()).await;
             }
         });
 
         Ok(rx)
     }
 }
+
+impl Coinbase {
+    /// Trading status of `coinbase_symbol` from the product details.
+    async fn fetch_trading_status(
+        &self,
+        coinbase_symbol: &str,
+    ) -> Result<TradingStatus, MarketScannerError> {
+        let endpoint = format!("products/{}", coinbase_symbol);
+        let product: serde_json::Value = self.get_cached(&endpoint).await?;
+        Ok(parse_coinbase_trading_status(&product))
+    }
+}
+
+/// Maps Coinbase product flags (`status`, `trading_disabled`, `auction_mode`, `cancel_only`,
+/// `post_only`) to [TradingStatus].
+fn parse_coinbase_trading_status(product: &serde_json::Value) -> TradingStatus {
+    let flag = |name: &str| product[name].as_bool().unwrap_or(false);
+    match product["status"].as_str() {
+        Some("online") => {}
+        Some(_) => return TradingStatus::Halted,
+        None => return TradingStatus::Unknown,
+    }
+    if flag("trading_disabled") {
+        TradingStatus::Halted
+    } else if flag("auction_mode") {
+        TradingStatus::Auction
+    } else if flag("cancel_only") {
+        TradingStatus::CancelOnly
+    } else if flag("post_only") {
+        TradingStatus::PostOnly
+    } else {
+        TradingStatus::Trading
+    }
+}
@@ -8,6 +8,7 @@ pub struct CoinbaseOrderBookResponse {
 
 #[derive(Debug, Deserialize)]
 pub struct CoinbaseTickerWs {
+    #[allow(dead_code)]
     #[serde(rename = "type")]
     pub msg_type: String,
     #[serde(rename = "product_id", default)]
@@ -1,19 +1,26 @@
 mod types;
 
 use crate::cex::cryptocom::types::CryptocomOrderBookResponse;
+use crate::common::capabilities::{Capabilities, VenueCapability};
+use crate::common::reconnect::{ReconnectPolicy, ws_endpoint};
+use crate::common::symbol_registry;
 use crate::common::{
-    CEXTrait, CexExchange, CexPrice, Exchange, ExchangeTrait, MarketScannerError, find_mid_price,
-    format_symbol_for_exchange, format_symbol_for_exchange_ws, get_timestamp_millis,
-    normalize_symbol, parse_f64, standard_symbol_for_cex_ws_response,
+    CEXTrait, CexExchange, CexPrice, Exchange, ExchangeTrait, MarketScannerError, RequestPriority,
+    Timestamp, TradingStatus, WsSymbolFilter, decode_ws_json, find_mid_price,
+    format_symbol_for_exchange, format_symbol_for_exchange_ws, normalize_symbol, parse_f64,
+    record_ws_connect, standard_symbol_for_cex_ws_response,
 };
 use crate::create_exchange;
 use async_trait::async_trait;
 use futures::{SinkExt, StreamExt};
-use std::collections::{BTreeMap, HashMap};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use tokio::sync::mpsc;
 
 const CRYPTOCOM_API_BASE: &str = "https://api.crypto.com/v2/public";
 const CRYPTOCOM_WS_MARKET: &str = "wss://stream.crypto.com/v2/market";
+/// Book depths offered by the `book.{instrument}.{depth}` channel
+const CRYPTOCOM_BOOK_DEPTHS: [u32; 2] = [10, 50];
+const CRYPTOCOM_DEFAULT_BOOK_DEPTH: u32 = 10;
 
 create_exchange!(Cryptocom);
 
@@ -28,7 +35,7 @@ impl ExchangeTrait for Cryptocom {
     }
 
     fn exchange_name(&self) -> &str {
-        "Crypto.com"
+        CexExchange::Cryptocom.rest_venue_name()
     }
 
     async fn health_check(&self) -> Result<(), MarketScannerError> {
@@ -36,7 +43,9 @@ impl ExchangeTrait for Cryptocom {
         // Time endpoint returns BAD_REQUEST, so we use get-book instead
         // Note: api_base already includes /public, so we don't need to prefix with "public/"
         let endpoint = "get-book?instrument_name=BTC_USDT&depth=1";
-        let response: serde_json::Value = self.get(endpoint).await?;
+        let response: serde_json::Value = self
+            .get_with_priority(endpoint, RequestPriority::Metadata)
+            .await?;
 
         // Check if response indicates successful connection
         // Crypto.com returns {"code": 0, "result": {...}}
@@ -48,6 +57,12 @@ impl ExchangeTrait for Cryptocom {
 
         Err(MarketScannerError::HealthCheckFailed)
     }
+
+    async fn get_server_time(&self) -> Result<Timestamp, MarketScannerError> {
+        // No server time endpoint: the HTTP Date header has second resolution
+        self.get_date_header("get-book?instrument_name=BTC_USDT&depth=1")
+            .await
+    }
 }
 
 #[async_trait]
@@ -56,6 +71,10 @@ impl CEXTrait for Cryptocom {
         true
     }
 
+    fn capabilities(&self) -> Capabilities {
+        Capabilities::new(&[VenueCapability::Websocket, VenueCapability::ServerTime])
+    }
+
     async fn get_price(&self, symbol: &str) -> Result<CexPrice, MarketScannerError> {
         if symbol.is_empty() {
             return Err(MarketScannerError::InvalidSymbol(
@@ -131,8 +150,9 @@ impl CEXTrait for Cryptocom {
             ask_price: ask,
             bid_qty,
             ask_qty,
-            timestamp: get_timestamp_millis(),
+            timestamp: Timestamp::now(),
             exchange: Exchange::Cex(CexExchange::Cryptocom),
+            trading_status: TradingStatus::Unknown,
         })
     }
 
@@ -141,6 +161,30 @@ impl CEXTrait for Cryptocom {
         symbols: &[&str],
         reconnect_attempts: u32,
         reconnect_delay_ms: u64,
+    ) -> Result<mpsc::Receiver<CexPrice>, MarketScannerError> {
+        self.stream_price_websocket_with_depth(
+            symbols,
+            CRYPTOCOM_DEFAULT_BOOK_DEPTH,
+            reconnect_attempts,
+            reconnect_delay_ms,
+        )
+        .await
+    }
+}
+
+impl Cryptocom {
+    /// Same as [CEXTrait::stream_price_websocket], with the depth of the maintained book.
+    ///
+    /// Crypto.com offers book depths of 10 and 50; other values are rounded up to the next
+    /// supported depth. Book deltas are checked for sequence gaps (`pu` must equal the
+    /// previous `u`); on a gap the book is dropped and the channel is resubscribed to get a
+    /// fresh snapshot, so the maintained book cannot drift.
+    pub async fn stream_price_websocket_with_depth(
+        &self,
+        symbols: &[&str],
+        depth: u32,
+        reconnect_attempts: u32,
+        reconnect_delay_ms: u64,
     ) -> Result<mpsc::Receiver<CexPrice>, MarketScannerError> {
         if symbols.is_empty() {
             return Err(MarketScannerError::InvalidSymbol(
@@ -148,13 +192,24 @@ impl CEXTrait for Cryptocom {
             ));
         }
 
-        let channels: Vec<String> = symbols
+        let depth = CRYPTOCOM_BOOK_DEPTHS
+            .iter()
+            .copied()
+            .find(|d| *d >= depth)
+            .unwrap_or(CRYPTOCOM_BOOK_DEPTHS[CRYPTOCOM_BOOK_DEPTHS.len() - 1]);
+
+        // Standard symbol -> channel, used to resubscribe a single book after a gap
+        let channel_by_symbol: HashMap<String, String> = symbols
             .iter()
             .map(|s| {
                 let sym = format_symbol_for_exchange_ws(s, &CexExchange::Cryptocom)?;
-                Ok(format!("book.{}.10", sym))
+                Ok((
+                    standard_symbol_for_cex_ws_response(&sym, &CexExchange::Cryptocom),
+                    format!("book.{}.{}", sym, depth),
+                ))
             })
-            .collect::<Result<Vec<_>, MarketScannerError>>()?;
+            .collect::<Result<HashMap<_, _>, MarketScannerError>>()?;
+        let channels: Vec<String> = channel_by_symbol.values().cloned().collect();
 
         let subscribe_msg = serde_json::json!({
             "id": 1,
@@ -166,13 +221,11 @@ impl CEXTrait for Cryptocom {
             }
         });
         let (tx, rx) = mpsc::channel(64);
-        let delay = std::time::Duration::from_millis(if reconnect_delay_ms == 0 {
-            1000
-        } else {
-            reconnect_delay_ms
-        });
+        let symbol_filter = WsSymbolFilter::new(&CexExchange::Cryptocom, symbols);
+        let policy = ReconnectPolicy::new(reconnect_attempts, reconnect_delay_ms);
+        let url = ws_endpoint(&CexExchange::Cryptocom, CRYPTOCOM_WS_MARKET);
 
-        tokio::spawn(async move {
+        symbol_registry::spawn(async move {
             type BookMap = BTreeMap<rust_decimal::Decimal, rust_decimal::Decimal>;
             let mut attempt = 0u32;
 
@@ -219,16 +272,16 @@ impl CEXTrait for Cryptocom {
 
             loop {
                 attempt += 1;
-                let (mut ws_stream, _) = match tokio_tungstenite::connect_async(CRYPTOCOM_WS_MARKET)
-                    .await
-                {
-                    Ok(v) => v,
+                let (mut ws_stream, _) = match tokio_tungstenite::connect_async(&url).await {
+                    Ok(v) => {
+                        record_ws_connect(&CexExchange::Cryptocom);
+                        v
+                    }
                     Err(_) => {
-                        if tx.is_closed() || reconnect_attempts == 0 || attempt > reconnect_attempts
-                        {
+                        if tx.is_closed() || !policy.should_reconnect(attempt) {
                             break;
                         }
-                        tokio::time::sleep(delay).await;
+                        tokio::time::sleep(policy.delay()).await;
                         continue;
                     }
                 };
@@ -240,25 +293,35 @@ impl CEXTrait for Cryptocom {
                     .await
                     .is_err()
                 {
-                    if tx.is_closed() || reconnect_attempts == 0 || attempt > reconnect_attempts {
+                    if tx.is_closed() || !policy.should_reconnect(attempt) {
                         break;
                     }
-                    tokio::time::sleep(delay).await;
+                    tokio::time::sleep(policy.delay()).await;
                     continue;
                 }
 
-                let (_write, mut read) = ws_stream.split();
+                let (mut write, mut read) = ws_stream.split();
                 let mut books: HashMap<String, (BookMap, BookMap)> = HashMap::new();
+                // Last applied sequence number (`u`) per symbol
+                let mut last_seq: HashMap<String, u64> = HashMap::new();
+                // Symbols waiting for a fresh snapshot after a gap; deltas are ignored
+                let mut resyncing: HashSet<String> = HashSet::new();
+                let mut request_id = 1u64;
 
                 while let Some(Ok(msg)) = read.next().await {
+                    // Control frames carry no payload to decode
+                    if msg.is_ping() || msg.is_pong() || msg.is_close() {
+                        continue;
+                    }
                     let text = match msg.into_text() {
                         Ok(t) => t,
                         Err(_) => continue,
                     };
-                    let value: serde_json::Value = match serde_json::from_str(&text) {
-                        Ok(v) => v,
-                        Err(_) => continue,
-                    };
+                    let value: serde_json::Value =
+                        match decode_ws_json(&CexExchange::Cryptocom, &text) {
+                            Some(v) => v,
+                            None => continue,
+                        };
                     // Skip subscribe ack (has method=subscribe but no book data)
                     if value.get("method").and_then(|m| m.as_str()) == Some("subscribe") {
                         let has_data = value.get("params").and_then(|p| p.get("data")).is_some()
@@ -333,10 +396,55 @@ impl CEXTrait for Cryptocom {
                         (item.get("bids"), item.get("asks"))
                     };
 
+                    let is_update = channel == Some("book.update");
+                    let seq = item.get("u").and_then(|v| v.as_u64());
+                    if is_update {
+                        if resyncing.contains(&symbol_std) {
+                            continue;
+                        }
+                        let prev = item.get("pu").and_then(|v| v.as_u64());
+                        let gap = match (prev, last_seq.get(&symbol_std)) {
+                            (Some(pu), Some(last)) => pu != *last,
+                            // Delta before any snapshot: the book cannot be trusted
+                            _ => !books.contains_key(&symbol_std),
+                        };
+                        if gap {
+                            books.remove(&symbol_std);
+                            last_seq.remove(&symbol_std);
+                            let Some(channel) = channel_by_symbol.get(&symbol_std) else {
+                                continue;
+                            };
+                            resyncing.insert(symbol_std.clone());
+                            for method in ["unsubscribe", "subscribe"] {
+                                request_id += 1;
+                                let msg = serde_json::json!({
+                                    "id": request_id,
+                                    "method": method,
+                                    "params": {
+                                        "channels": [channel],
+                                        "book_subscription_type": "SNAPSHOT_AND_UPDATE",
+                                        "book_update_frequency": 100
+                                    }
+                                });
+                                let _ = write
+                                    .send(tokio_tungstenite::tungstenite::Message::Text(
+                                        msg.to_string(),
+                                    ))
+                                    .await;
+                            }
+                            continue;
+                        }
+                    } else {
+                        resyncing.remove(&symbol_std);
+                    }
+                    if let Some(u) = seq {
+                        last_seq.insert(symbol_std.clone(), u);
+                    }
+
                     let (bids, asks) = books
                         .entry(symbol_std.clone())
                         .or_insert_with(|| (BTreeMap::new(), BTreeMap::new()));
-                    if channel == Some("book.update") {
+                    if is_update {
                         apply_levels(bids, data_bids);
                         apply_levels(asks, data_asks);
                     } else {
@@ -357,18 +465,22 @@ impl CEXTrait for Cryptocom {
                         ask_price: ask,
                         bid_qty,
                         ask_qty,
-                        timestamp: get_timestamp_millis(),
+                        timestamp: Timestamp::now(),
                         exchange: Exchange::Cex(CexExchange::Cryptocom),
+                        trading_status: TradingStatus::Unknown,
                     };
+                    if !symbol_filter.allows(&price) {
+                        continue;
+                    }
                     if tx.send(price).await.is_err() {
                         return;
                     }
                 }
 
-                if tx.is_closed() || reconnect_attempts == 0 || attempt > reconnect_attempts {
+                if tx.is_closed() || !policy.should_reconnect(attempt) {
                     break;
                 }
-                tokio::time::sleep(delay).await;
+                tokio::time::sleep(policy.delay()).await;
             }
         });
 
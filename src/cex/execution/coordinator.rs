@@ -0,0 +1,690 @@
+//! Two-leg execution of CEX-CEX opportunities.
+//!
+//! [TwoLegExecutor] places the buy and sell legs at the same time, polls both until they
+//! are final (cancelling what is still open after the fill timeout) and, if the filled
+//! quantities differ, corrects the imbalance according to a [PartialFillPolicy]. With a
+//! [ChaseConfig], a limit leg that missed because the price moved is cancelled and re-placed
+//! at the current price a bounded number of times first. The result is an
+//! [ExecutionOutcome] with the state of every order that was sent.
+
+use super::{
+    CexOrder, CexOrderAck, CexOrderExecution, OrderStatus, OrderUpdate, TradingMode, check_quantity,
+};
+use crate::common::{CexExchange, CexPrice, Credentials, Exchange, MarketScannerError, Timestamp};
+use crate::portfolio::{Fill, TradeSide};
+use crate::risk::{Exposure, RiskManager};
+use crate::scanner::{ArbitrageOpportunity, PriceData};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::time::Instant;
+
+/// Account orders are executed on: place, query and cancel.
+#[async_trait]
+pub trait ExecutionVenue: Send + Sync {
+    fn exchange(&self) -> CexExchange;
+
+    async fn place_order(&self, order: &CexOrder) -> Result<CexOrderAck, MarketScannerError>;
+
+    async fn order_status(&self, ack: &CexOrderAck) -> Result<OrderUpdate, MarketScannerError>;
+
+    async fn cancel_order(&self, ack: &CexOrderAck) -> Result<(), MarketScannerError>;
+
+    /// Current top of book, used to re-price chased legs. Venues without quotes cannot
+    /// chase.
+    async fn quote(&self, symbol: &str) -> Result<CexPrice, MarketScannerError> {
+        Err(MarketScannerError::ApiError(format!(
+            "{:?} does not provide quotes for {}",
+            self.exchange(),
+            symbol
+        )))
+    }
+}
+
+/// A [CexOrderExecution] venue with the credentials and mode to trade with.
+pub struct CexAccount<E> {
+    venue: E,
+    credentials: Credentials,
+    mode: TradingMode,
+}
+
+impl<E: CexOrderExecution> CexAccount<E> {
+    pub fn new(venue: E, credentials: Credentials, mode: TradingMode) -> Self {
+        Self {
+            venue,
+            credentials,
+            mode,
+        }
+    }
+}
+
+#[async_trait]
+impl<E: CexOrderExecution> ExecutionVenue for CexAccount<E> {
+    fn exchange(&self) -> CexExchange {
+        self.venue.cex_exchange()
+    }
+
+    async fn place_order(&self, order: &CexOrder) -> Result<CexOrderAck, MarketScannerError> {
+        self.venue
+            .place_order(&self.credentials, order, self.mode)
+            .await
+    }
+
+    async fn order_status(&self, ack: &CexOrderAck) -> Result<OrderUpdate, MarketScannerError> {
+        self.venue.order_status(&self.credentials, ack).await
+    }
+
+    async fn cancel_order(&self, ack: &CexOrderAck) -> Result<(), MarketScannerError> {
+        self.venue.cancel_order(&self.credentials, ack).await
+    }
+
+    async fn quote(&self, symbol: &str) -> Result<CexPrice, MarketScannerError> {
+        self.venue.get_price(symbol).await
+    }
+}
+
+/// What to do when one leg filled more than the other.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PartialFillPolicy {
+    /// Reverse the excess with a market order on the venue where it filled
+    #[default]
+    Unwind,
+    /// Complete the lagging leg with a market order on its venue
+    Hedge,
+    /// Leave the imbalance open; it is reported on the outcome
+    Hold,
+}
+
+/// Cancel-replace settings for limit legs that did not fill in time.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ChaseConfig {
+    /// Replacement orders per leg
+    pub max_attempts: u32,
+    /// Furthest a replacement may be priced from the original limit, in basis points
+    /// (above it for buys, below it for sells)
+    pub max_slippage_bps: f64,
+    /// Time each replacement order rests before it is cancelled (milliseconds)
+    pub attempt_timeout_ms: u64,
+}
+
+impl ChaseConfig {
+    pub fn new(max_attempts: u32, max_slippage_bps: f64) -> Self {
+        Self {
+            max_attempts,
+            max_slippage_bps,
+            attempt_timeout_ms: 2_000,
+        }
+    }
+
+    pub fn with_attempt_timeout_ms(mut self, attempt_timeout_ms: u64) -> Self {
+        self.attempt_timeout_ms = attempt_timeout_ms;
+        self
+    }
+
+    /// Worst replacement price allowed for an order first priced at `limit`.
+    pub fn price_bound(&self, side: TradeSide, limit: f64) -> f64 {
+        let slippage = limit * self.max_slippage_bps / 10_000.0;
+        match side {
+            TradeSide::Buy => limit + slippage,
+            TradeSide::Sell => limit - slippage,
+        }
+    }
+}
+
+/// Two-leg execution settings.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExecutionConfig {
+    pub policy: PartialFillPolicy,
+    /// Orders still open this long after placement are cancelled (milliseconds)
+    pub fill_timeout_ms: u64,
+    /// Delay between order status queries (milliseconds)
+    pub poll_interval_ms: u64,
+    /// Base quantity difference between the legs treated as balanced
+    pub quantity_tolerance: f64,
+    /// Re-place missed limit legs at the current price; `None` disables chasing
+    pub chase: Option<ChaseConfig>,
+}
+
+impl Default for ExecutionConfig {
+    fn default() -> Self {
+        Self {
+            policy: PartialFillPolicy::default(),
+            fill_timeout_ms: 10_000,
+            poll_interval_ms: 250,
+            quantity_tolerance: 1e-9,
+            chase: None,
+        }
+    }
+}
+
+impl ExecutionConfig {
+    pub fn with_policy(mut self, policy: PartialFillPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    pub fn with_fill_timeout_ms(mut self, fill_timeout_ms: u64) -> Self {
+        self.fill_timeout_ms = fill_timeout_ms;
+        self
+    }
+
+    pub fn with_poll_interval_ms(mut self, poll_interval_ms: u64) -> Self {
+        self.poll_interval_ms = poll_interval_ms;
+        self
+    }
+
+    pub fn with_quantity_tolerance(mut self, quantity_tolerance: f64) -> Self {
+        self.quantity_tolerance = quantity_tolerance;
+        self
+    }
+
+    pub fn with_chase(mut self, chase: ChaseConfig) -> Self {
+        self.chase = Some(chase);
+        self
+    }
+}
+
+/// One order sent (or attempted) by the executor, with the replacements sent for it when
+/// chasing.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LegOutcome {
+    pub exchange: CexExchange,
+    pub order: CexOrder,
+    /// `None` if the venue did not accept the order
+    pub ack: Option<CexOrderAck>,
+    /// Last known order state
+    pub last_update: Option<OrderUpdate>,
+    /// Placement, query, cancel and chase errors
+    pub errors: Vec<String>,
+    /// Orders re-placed for the unfilled quantity (see [ChaseConfig]), oldest first
+    #[serde(default)]
+    pub replacements: Vec<LegOutcome>,
+}
+
+impl LegOutcome {
+    fn new(exchange: CexExchange, order: CexOrder) -> Self {
+        Self {
+            exchange,
+            order,
+            ack: None,
+            last_update: None,
+            errors: Vec::new(),
+            replacements: Vec::new(),
+        }
+    }
+
+    /// The original order followed by its replacements.
+    pub fn attempts(&self) -> impl Iterator<Item = &LegOutcome> {
+        std::iter::once(self).chain(&self.replacements)
+    }
+
+    /// Filled base quantity over all attempts.
+    pub fn filled_quantity(&self) -> f64 {
+        self.attempts().map(LegOutcome::attempt_fill).sum()
+    }
+
+    /// Fill-weighted average price over all attempts.
+    pub fn average_fill_price(&self) -> Option<f64> {
+        let (quantity, notional) = self
+            .attempts()
+            .filter_map(|attempt| {
+                let update = attempt.last_update.as_ref()?;
+                Some((
+                    update.filled_quantity,
+                    update.filled_quantity * update.average_fill_price?,
+                ))
+            })
+            .fold((0.0, 0.0), |(q, n), (dq, dn)| (q + dq, n + dn));
+        (quantity > 0.0).then(|| notional / quantity)
+    }
+
+    fn attempt_fill(&self) -> f64 {
+        self.last_update
+            .as_ref()
+            .map(|update| update.filled_quantity)
+            .unwrap_or(0.0)
+    }
+
+    /// Filled base quantity, negative for sells.
+    fn signed_fill(&self) -> f64 {
+        match self.order.side {
+            TradeSide::Buy => self.filled_quantity(),
+            TradeSide::Sell => -self.filled_quantity(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ExecutionStatus {
+    /// Both legs filled the same quantity
+    Completed,
+    /// Neither leg filled
+    NotFilled,
+    /// The excess of one leg was reversed ([PartialFillPolicy::Unwind])
+    Unwound,
+    /// The lagging leg was completed ([PartialFillPolicy::Hedge])
+    Hedged,
+    /// The legs are still imbalanced (see [ExecutionOutcome::residual_quantity])
+    Unbalanced,
+}
+
+/// Result of a two-leg execution.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExecutionOutcome {
+    pub status: ExecutionStatus,
+    pub buy: LegOutcome,
+    pub sell: LegOutcome,
+    /// Market order correcting an imbalance, if one was needed
+    pub correction: Option<LegOutcome>,
+    /// Net base quantity left after all orders (positive = long, negative = short)
+    pub residual_quantity: f64,
+    pub started_at: Timestamp,
+    pub finished_at: Timestamp,
+}
+
+impl ExecutionOutcome {
+    /// Quantity bought on one venue and sold on the other.
+    pub fn matched_quantity(&self) -> f64 {
+        self.buy.filled_quantity().min(self.sell.filled_quantity())
+    }
+
+    /// Fills of every leg and the correction, at their average fill prices, with fees at
+    /// the opportunity's commission rates. Feed them to a [crate::portfolio::Portfolio].
+    pub fn fills(&self, opportunity: &ArbitrageOpportunity) -> Vec<Fill> {
+        [Some(&self.buy), Some(&self.sell), self.correction.as_ref()]
+            .into_iter()
+            .flatten()
+            .filter_map(|leg| {
+                let quantity = leg.filled_quantity();
+                let price = leg.average_fill_price()?;
+                let commission_percent = if leg.exchange == self.buy.exchange {
+                    opportunity.source_commission_percent
+                } else {
+                    opportunity.destination_commission_percent
+                };
+                (quantity > 0.0).then(|| Fill {
+                    exchange: Exchange::Cex(leg.exchange.clone()),
+                    symbol: opportunity.symbol.clone(),
+                    side: leg.order.side,
+                    quantity,
+                    price,
+                    fee_quote: price * quantity * commission_percent / 100.0,
+                    timestamp: self.finished_at,
+                })
+            })
+            .collect()
+    }
+}
+
+/// Executes both legs of an opportunity and keeps them balanced.
+#[derive(Clone)]
+pub struct TwoLegExecutor {
+    buy_venue: Arc<dyn ExecutionVenue>,
+    sell_venue: Arc<dyn ExecutionVenue>,
+    config: ExecutionConfig,
+    risk: Option<RiskManager>,
+}
+
+impl TwoLegExecutor {
+    pub fn new(
+        buy_venue: impl ExecutionVenue + 'static,
+        sell_venue: impl ExecutionVenue + 'static,
+        config: ExecutionConfig,
+    ) -> Self {
+        Self {
+            buy_venue: Arc::new(buy_venue),
+            sell_venue: Arc::new(sell_venue),
+            config,
+            risk: None,
+        }
+    }
+
+    /// Reserves every execution with `risk` before sending, correction orders included; the
+    /// unfilled part of each leg is released afterwards.
+    pub fn with_risk(mut self, risk: RiskManager) -> Self {
+        self.risk = Some(risk);
+        self
+    }
+
+    pub fn config(&self) -> &ExecutionConfig {
+        &self.config
+    }
+
+    /// Executes `opportunity` for `quantity` (capped at its executable quantity) with
+    /// [CexOrder::from_opportunity] orders. The opportunity's source and destination must
+    /// be the buy and sell venues of this executor.
+    pub async fn execute_opportunity(
+        &self,
+        opportunity: &ArbitrageOpportunity,
+        quantity: f64,
+    ) -> Result<ExecutionOutcome, MarketScannerError> {
+        for (leg, venue) in [
+            (&opportunity.source_leg, &self.buy_venue),
+            (&opportunity.destination_leg, &self.sell_venue),
+        ] {
+            let PriceData::Cex(price) = leg else {
+                continue;
+            };
+            if price.exchange != Exchange::Cex(venue.exchange()) {
+                return Err(MarketScannerError::InvalidConfig(format!(
+                    "Opportunity leg on {:?} does not match executor venue {:?}",
+                    price.exchange,
+                    venue.exchange()
+                )));
+            }
+        }
+        check_quantity(quantity)?;
+        check_quantity(quantity.min(opportunity.executable_quantity))?;
+        let (buy, sell) = CexOrder::from_opportunity(opportunity, quantity)?;
+        let reserved = match &self.risk {
+            Some(risk) => risk.reserve_opportunity(opportunity, quantity)?.to_vec(),
+            None => Vec::new(),
+        };
+        Ok(self.run(buy, sell, reserved).await)
+    }
+
+    /// Executes a buy order on the buy venue and a sell order on the sell venue.
+    /// With a risk manager, both orders need limit prices.
+    pub async fn execute(
+        &self,
+        buy: CexOrder,
+        sell: CexOrder,
+    ) -> Result<ExecutionOutcome, MarketScannerError> {
+        if buy.side != TradeSide::Buy || sell.side != TradeSide::Sell {
+            return Err(MarketScannerError::InvalidSymbol(
+                "Two-leg execution needs a buy and a sell order".to_string(),
+            ));
+        }
+        if buy.symbol != sell.symbol {
+            return Err(MarketScannerError::InvalidSymbol(format!(
+                "Legs trade different symbols: {} / {}",
+                buy.symbol, sell.symbol
+            )));
+        }
+        check_quantity(buy.quantity)?;
+        check_quantity(sell.quantity)?;
+        let mut reserved = Vec::new();
+        if let Some(risk) = &self.risk {
+            reserved.push(risk.reserve_order(&self.buy_venue.exchange(), &buy, None)?);
+            match risk.reserve_order(&self.sell_venue.exchange(), &sell, None) {
+                Ok(exposure) => reserved.push(exposure),
+                Err(e) => {
+                    risk.release(&reserved[0]);
+                    return Err(e);
+                }
+            }
+        }
+        Ok(self.run(buy, sell, reserved).await)
+    }
+
+    async fn run(
+        &self,
+        buy: CexOrder,
+        sell: CexOrder,
+        reserved: Vec<Exposure>,
+    ) -> ExecutionOutcome {
+        let started_at = Timestamp::now();
+        let (buy, sell) = tokio::join!(
+            self.place_leg(&self.buy_venue, buy),
+            self.place_leg(&self.sell_venue, sell)
+        );
+        // One leg rejected: take the other off the book before it fills further
+        let one_sided = buy.ack.is_none() != sell.ack.is_none();
+        let (buy, sell) = tokio::join!(
+            self.monitor_leg(&self.buy_venue, buy, one_sided, self.config.fill_timeout_ms),
+            self.monitor_leg(
+                &self.sell_venue,
+                sell,
+                one_sided,
+                self.config.fill_timeout_ms
+            )
+        );
+        let (buy, sell) = if one_sided {
+            (buy, sell)
+        } else {
+            tokio::join!(
+                self.chase_leg(&self.buy_venue, buy),
+                self.chase_leg(&self.sell_venue, sell)
+            )
+        };
+
+        if let Some(risk) = &self.risk {
+            for (exposure, leg) in reserved.iter().zip([&buy, &sell]) {
+                release_unfilled(risk, exposure, leg);
+            }
+        }
+
+        let imbalance = buy.signed_fill() + sell.signed_fill();
+        let tolerance = self.config.quantity_tolerance;
+        let mut correction = None;
+        let status = if imbalance.abs() <= tolerance {
+            if buy.filled_quantity() <= tolerance {
+                ExecutionStatus::NotFilled
+            } else {
+                ExecutionStatus::Completed
+            }
+        } else {
+            // Long after the legs: sell the excess; short: buy it back
+            let (side, quantity) = if imbalance > 0.0 {
+                (TradeSide::Sell, imbalance)
+            } else {
+                (TradeSide::Buy, -imbalance)
+            };
+            let venue = match (self.config.policy, side) {
+                (PartialFillPolicy::Hold, _) => None,
+                (PartialFillPolicy::Unwind, TradeSide::Sell)
+                | (PartialFillPolicy::Hedge, TradeSide::Buy) => Some((&self.buy_venue, &buy)),
+                (PartialFillPolicy::Unwind, TradeSide::Buy)
+                | (PartialFillPolicy::Hedge, TradeSide::Sell) => Some((&self.sell_venue, &sell)),
+            };
+            // The correction trades in the quantity steps of the leg it trades against; an
+            // imbalance below one step cannot be sent
+            let planned = venue.map(|(venue, venue_leg)| {
+                let order = CexOrder {
+                    quantity_step: venue_leg.order.quantity_step,
+                    ..CexOrder::market(&buy.order.symbol, side, quantity)
+                };
+                (venue, venue_leg, order)
+            });
+            match planned.filter(|(_, _, order)| order.rounded().is_ok()) {
+                Some((venue, venue_leg, order)) => {
+                    // The market order is reserved at the price of the leg it trades against
+                    let reference_price = venue_leg.average_fill_price().or(venue_leg.order.price);
+                    let reserved = match &self.risk {
+                        Some(risk) => risk
+                            .reserve_order(&venue.exchange(), &order, reference_price)
+                            .map(Some),
+                        None => Ok(None),
+                    };
+                    let leg = match reserved {
+                        Ok(reserved) => {
+                            let leg = self.place_leg(venue, order).await;
+                            let leg = self
+                                .monitor_leg(venue, leg, false, self.config.fill_timeout_ms)
+                                .await;
+                            if let (Some(risk), Some(exposure)) = (&self.risk, &reserved) {
+                                release_unfilled(risk, exposure, &leg);
+                            }
+                            leg
+                        }
+                        Err(e) => {
+                            let mut leg = LegOutcome::new(venue.exchange(), order);
+                            leg.errors.push(e.to_string());
+                            leg
+                        }
+                    };
+                    let residual = imbalance + leg.signed_fill();
+                    correction = Some(leg);
+                    if residual.abs() > tolerance {
+                        ExecutionStatus::Unbalanced
+                    } else if self.config.policy == PartialFillPolicy::Unwind {
+                        ExecutionStatus::Unwound
+                    } else {
+                        ExecutionStatus::Hedged
+                    }
+                }
+                None => ExecutionStatus::Unbalanced,
+            }
+        };
+
+        let residual_quantity = buy.signed_fill()
+            + sell.signed_fill()
+            + correction
+                .as_ref()
+                .map(LegOutcome::signed_fill)
+                .unwrap_or(0.0);
+        ExecutionOutcome {
+            status,
+            buy,
+            sell,
+            correction,
+            residual_quantity,
+            started_at,
+            finished_at: Timestamp::now(),
+        }
+    }
+
+    async fn place_leg(&self, venue: &Arc<dyn ExecutionVenue>, order: CexOrder) -> LegOutcome {
+        let mut leg = LegOutcome::new(venue.exchange(), order);
+        match venue.place_order(&leg.order).await {
+            Ok(ack) => leg.ack = Some(ack),
+            Err(e) => leg.errors.push(e.to_string()),
+        }
+        leg
+    }
+
+    /// Re-places the unfilled part of a limit leg at the venue's current price while
+    /// attempts are left, the price stays within the chase bound and the risk manager (if
+    /// any) accepts the replacement.
+    async fn chase_leg(&self, venue: &Arc<dyn ExecutionVenue>, mut leg: LegOutcome) -> LegOutcome {
+        let Some(chase) = &self.config.chase else {
+            return leg;
+        };
+        let Some(limit) = leg.order.price else {
+            return leg;
+        };
+        let side = leg.order.side;
+        let bound = chase.price_bound(side, limit);
+        while (leg.replacements.len() as u32) < chase.max_attempts {
+            // Only re-place once the previous order is known to be off the book
+            let last = leg.replacements.last().unwrap_or(&leg);
+            let missed = last.last_update.as_ref().is_some_and(|update| {
+                update.status.is_final() && update.status != OrderStatus::Rejected
+            });
+            let remaining = leg.order.quantity - leg.filled_quantity();
+            if !missed || remaining <= self.config.quantity_tolerance {
+                break;
+            }
+            let price = match venue.quote(&leg.order.symbol).await {
+                Ok(quote) => match side {
+                    TradeSide::Buy => quote.ask_price,
+                    TradeSide::Sell => quote.bid_price,
+                },
+                Err(e) => {
+                    leg.errors.push(e.to_string());
+                    break;
+                }
+            };
+            let within_bound = match side {
+                TradeSide::Buy => price <= bound,
+                TradeSide::Sell => price >= bound,
+            };
+            if !(price > 0.0 && within_bound) {
+                leg.errors.push(format!(
+                    "Chase stopped: price {} is beyond the {} bps bound {}",
+                    price, chase.max_slippage_bps, bound
+                ));
+                break;
+            }
+            // Same increments and client order id as the original; a remainder below one
+            // quantity step cannot be sent
+            let order = CexOrder {
+                quantity: remaining,
+                price: Some(price),
+                ..leg.order.clone()
+            };
+            if order.rounded().is_err() {
+                break;
+            }
+            let reserved = match &self.risk {
+                Some(risk) => match risk.reserve_order(&venue.exchange(), &order, None) {
+                    Ok(exposure) => Some(exposure),
+                    Err(e) => {
+                        leg.errors.push(format!("Chase stopped: {}", e));
+                        break;
+                    }
+                },
+                None => None,
+            };
+            let replacement = self.place_leg(venue, order).await;
+            let replacement = self
+                .monitor_leg(venue, replacement, false, chase.attempt_timeout_ms)
+                .await;
+            if let (Some(risk), Some(exposure)) = (&self.risk, &reserved) {
+                release_unfilled(risk, exposure, &replacement);
+            }
+            leg.replacements.push(replacement);
+        }
+        leg
+    }
+
+    /// Polls the leg until its order is final. Open orders are cancelled after `timeout_ms`,
+    /// or right away with `cancel_now`, and queried once more.
+    async fn monitor_leg(
+        &self,
+        venue: &Arc<dyn ExecutionVenue>,
+        mut leg: LegOutcome,
+        cancel_now: bool,
+        timeout_ms: u64,
+    ) -> LegOutcome {
+        let Some(ack) = leg.ack.clone() else {
+            return leg;
+        };
+        let deadline = if cancel_now {
+            Instant::now()
+        } else {
+            Instant::now() + Duration::from_millis(timeout_ms)
+        };
+        let poll_interval = Duration::from_millis(self.config.poll_interval_ms.max(1));
+
+        loop {
+            if !cancel_now {
+                match venue.order_status(&ack).await {
+                    Ok(update) => {
+                        let is_final = update.status.is_final();
+                        leg.last_update = Some(update);
+                        if is_final {
+                            return leg;
+                        }
+                    }
+                    Err(e) => leg.errors.push(e.to_string()),
+                }
+            }
+            if Instant::now() >= deadline {
+                break;
+            }
+            tokio::time::sleep(poll_interval.min(deadline - Instant::now())).await;
+        }
+
+        // Cancelling can race a fill; the final query reports what happened
+        if let Err(e) = venue.cancel_order(&ack).await {
+            leg.errors.push(e.to_string());
+        }
+        match venue.order_status(&ack).await {
+            Ok(update) => leg.last_update = Some(update),
+            Err(e) => leg.errors.push(e.to_string()),
+        }
+        leg
+    }
+}
+
+/// Releases the part of `exposure` that the order of `leg` left unfilled. Replacements are
+/// reserved and released on their own.
+fn release_unfilled(risk: &RiskManager, exposure: &Exposure, leg: &LegOutcome) {
+    let unfilled = (leg.order.quantity - leg.attempt_fill()).max(0.0);
+    risk.release(&Exposure {
+        notional: exposure.notional * unfilled / leg.order.quantity,
+        ..exposure.clone()
+    });
+}
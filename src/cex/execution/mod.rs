@@ -0,0 +1,508 @@
+//! Order placement on CEX venues, with paper / demo trading.
+//!
+//! [CexOrderExecution] is implemented by venues that accept authenticated orders (OKX,
+//! Bybit, Bitget). Each venue builds a signed [SignedOrderRequest] and parses the response;
+//! [CexOrderExecution::place_order] sends it. With [TradingMode::Demo] orders go to the
+//! venue's simulated environment (OKX `x-simulated-trading` header, Bybit demo host,
+//! Bitget `paptrading` header), so the full scan → execute loop can be validated without
+//! risking funds. Demo trading needs API keys created for the demo account. In dry-run
+//! mode ([crate::common::set_dry_run]) orders and cancels are signed and logged but not sent.
+//! [UserDataStream] follows placed orders and balances over the venues' private streams;
+//! [TwoLegExecutor] places both legs of an opportunity and corrects partial fills.
+
+mod coordinator;
+mod signing;
+mod user_data;
+
+pub use coordinator::{
+    CexAccount, ChaseConfig, ExecutionConfig, ExecutionOutcome, ExecutionStatus, ExecutionVenue,
+    LegOutcome, PartialFillPolicy, TwoLegExecutor,
+};
+pub use signing::{hmac_sha256_base64, hmac_sha256_hex};
+pub use user_data::{BalanceUpdate, OrderStatus, OrderUpdate, UserDataEvent, UserDataStream};
+pub(crate) use user_data::{
+    KeepAlive, PrivateWsSession, SessionFactory, json_f64, json_side, json_str, json_timestamp,
+    spawn_user_data_stream,
+};
+
+use crate::common::dry_run::{DryRunAction, dry_run_enabled, is_dry_run_id, record_dry_run};
+use crate::common::{
+    CEXTrait, CexExchange, Credentials, MarketScannerError, OrderLimits, RequestPriority,
+    Timestamp, acquire_request_slot, get_timestamp_millis,
+};
+use crate::portfolio::TradeSide;
+use crate::risk::RiskManager;
+use crate::scanner::{ArbitrageOpportunity, PriceData};
+use async_trait::async_trait;
+use reqwest::Method;
+use reqwest::header::CONTENT_TYPE;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Where orders are sent.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TradingMode {
+    /// Real account, real funds
+    #[default]
+    Live,
+    /// The venue's paper / demo trading environment
+    Demo,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum OrderType {
+    Market,
+    Limit,
+}
+
+/// Spot order to place.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CexOrder {
+    /// Standard symbol (e.g. "BTCUSDT"); formatted per venue when sent
+    pub symbol: String,
+    pub side: TradeSide,
+    pub order_type: OrderType,
+    /// Base quantity
+    pub quantity: f64,
+    /// Limit price; required for [OrderType::Limit]
+    pub price: Option<f64>,
+    pub client_order_id: Option<String>,
+    /// Venue quantity increment; the quantity is rounded to it when sent
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub quantity_step: Option<f64>,
+    /// Venue price increment; the limit price is rounded to it when sent
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub price_tick: Option<f64>,
+}
+
+impl CexOrder {
+    pub fn market(symbol: &str, side: TradeSide, quantity: f64) -> Self {
+        Self {
+            symbol: symbol.to_string(),
+            side,
+            order_type: OrderType::Market,
+            quantity,
+            price: None,
+            client_order_id: None,
+            quantity_step: None,
+            price_tick: None,
+        }
+    }
+
+    pub fn limit(symbol: &str, side: TradeSide, quantity: f64, price: f64) -> Self {
+        Self {
+            symbol: symbol.to_string(),
+            side,
+            order_type: OrderType::Limit,
+            quantity,
+            price: Some(price),
+            client_order_id: None,
+            quantity_step: None,
+            price_tick: None,
+        }
+    }
+
+    pub fn with_client_order_id(mut self, client_order_id: &str) -> Self {
+        self.client_order_id = Some(client_order_id.to_string());
+        self
+    }
+
+    /// Rounds the quantity and price to the increments of `limits` when the order is sent.
+    pub fn with_limits(mut self, limits: &OrderLimits) -> Self {
+        self.quantity_step = limits.quantity_step;
+        self.price_tick = limits.price_tick;
+        self
+    }
+
+    /// Limit buy on the source leg (at its ask) and limit sell on the destination leg (at
+    /// its bid) for `quantity` (capped at the opportunity's executable quantity).
+    /// Fails if a leg is a DEX.
+    pub fn from_opportunity(
+        opportunity: &ArbitrageOpportunity,
+        quantity: f64,
+    ) -> Result<(CexOrder, CexOrder), MarketScannerError> {
+        let quantity = quantity.min(opportunity.executable_quantity);
+        let (PriceData::Cex(source), PriceData::Cex(destination)) =
+            (&opportunity.source_leg, &opportunity.destination_leg)
+        else {
+            return Err(MarketScannerError::InvalidSymbol(
+                "Only CEX legs can be placed as CEX orders".to_string(),
+            ));
+        };
+        Ok((
+            CexOrder::limit(
+                &opportunity.symbol,
+                TradeSide::Buy,
+                quantity,
+                source.ask_price,
+            ),
+            CexOrder::limit(
+                &opportunity.symbol,
+                TradeSide::Sell,
+                quantity,
+                destination.bid_price,
+            ),
+        ))
+    }
+
+    /// The order as a venue receives it: the quantity rounded down to `quantity_step` and the
+    /// limit price to `price_tick` on the passive side (buys down, sells up), so neither
+    /// exceeds what was planned. Fails if the quantity rounds to zero.
+    pub fn rounded(&self) -> Result<CexOrder, MarketScannerError> {
+        check_quantity(self.quantity)?;
+        let mut order = self.clone();
+        if let Some(step) = valid_increment(self.quantity_step) {
+            order.quantity = round_to_increment(self.quantity, step, false);
+            if order.quantity <= 0.0 {
+                return Err(MarketScannerError::InvalidConfig(format!(
+                    "Order quantity {} is below the venue step {}",
+                    self.quantity, step
+                )));
+            }
+        }
+        if let (Some(price), Some(tick)) = (self.price, valid_increment(self.price_tick)) {
+            order.price = Some(round_to_increment(
+                price,
+                tick,
+                self.side == TradeSide::Sell,
+            ));
+        }
+        Ok(order)
+    }
+
+    /// Limit price, checked against the order type.
+    pub(crate) fn checked_price(&self) -> Result<Option<f64>, MarketScannerError> {
+        check_quantity(self.quantity)?;
+        match (self.order_type, self.price) {
+            (OrderType::Limit, Some(price)) if price.is_finite() && price > 0.0 => Ok(Some(price)),
+            (OrderType::Limit, _) => Err(MarketScannerError::InvalidConfig(
+                "Limit orders need a finite, positive price".to_string(),
+            )),
+            (OrderType::Market, _) => Ok(None),
+        }
+    }
+}
+
+/// Fails unless `quantity` is finite and positive.
+pub(crate) fn check_quantity(quantity: f64) -> Result<(), MarketScannerError> {
+    if quantity.is_finite() && quantity > 0.0 {
+        Ok(())
+    } else {
+        Err(MarketScannerError::InvalidConfig(format!(
+            "Order quantity must be positive, got {}",
+            quantity
+        )))
+    }
+}
+
+/// Venue acknowledgement of a placed order.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CexOrderAck {
+    pub exchange: CexExchange,
+    pub symbol: String,
+    pub order_id: String,
+    pub client_order_id: Option<String>,
+    pub side: TradeSide,
+    pub quantity: f64,
+    pub price: Option<f64>,
+    pub mode: TradingMode,
+    pub timestamp: Timestamp,
+}
+
+/// Signed HTTP request of an order action (place, query, cancel).
+#[derive(Clone)]
+pub struct SignedOrderRequest {
+    pub method: Method,
+    /// Full URL, including the query string of GET requests
+    pub url: String,
+    /// Authentication and mode headers (besides `Content-Type: application/json`)
+    pub headers: Vec<(String, String)>,
+    /// JSON body; empty for GET requests
+    pub body: String,
+}
+
+impl SignedOrderRequest {
+    /// Value of header `name`, if set.
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(n, _)| n.eq_ignore_ascii_case(name))
+            .map(|(_, v)| v.as_str())
+    }
+}
+
+impl fmt::Debug for SignedOrderRequest {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Header values carry keys, passphrases and signatures
+        let header_names: Vec<&str> = self.headers.iter().map(|(n, _)| n.as_str()).collect();
+        f.debug_struct("SignedOrderRequest")
+            .field("method", &self.method)
+            .field("url", &self.url)
+            .field("headers", &header_names)
+            .field("body", &self.body)
+            .finish()
+    }
+}
+
+/// Authenticated spot order placement.
+#[async_trait]
+pub trait CexOrderExecution: CEXTrait {
+    /// Venue orders are placed on.
+    fn cex_exchange(&self) -> CexExchange;
+
+    /// Signed request placing `order` in `mode`, stamped with `timestamp_ms`.
+    fn order_request(
+        &self,
+        credentials: &Credentials,
+        order: &CexOrder,
+        mode: TradingMode,
+        timestamp_ms: u64,
+    ) -> Result<SignedOrderRequest, MarketScannerError>;
+
+    /// Acknowledgement from the venue's JSON response; venue error codes become errors.
+    fn parse_order_response(
+        &self,
+        response: &serde_json::Value,
+        order: &CexOrder,
+        mode: TradingMode,
+    ) -> Result<CexOrderAck, MarketScannerError>;
+
+    /// Signed request for the current state of the order behind `ack`.
+    fn order_status_request(
+        &self,
+        credentials: &Credentials,
+        ack: &CexOrderAck,
+        timestamp_ms: u64,
+    ) -> Result<SignedOrderRequest, MarketScannerError>;
+
+    /// Order state from the venue's JSON response to [CexOrderExecution::order_status_request].
+    fn parse_order_status(
+        &self,
+        response: &serde_json::Value,
+        ack: &CexOrderAck,
+    ) -> Result<OrderUpdate, MarketScannerError>;
+
+    /// Signed request cancelling the order behind `ack`.
+    fn cancel_request(
+        &self,
+        credentials: &Credentials,
+        ack: &CexOrderAck,
+        timestamp_ms: u64,
+    ) -> Result<SignedOrderRequest, MarketScannerError>;
+
+    /// Checks the venue's JSON response to [CexOrderExecution::cancel_request].
+    fn parse_cancel_response(
+        &self,
+        response: &serde_json::Value,
+        ack: &CexOrderAck,
+    ) -> Result<(), MarketScannerError>;
+
+    /// Signs and sends `order`. Orders share the venue's rate limit (see
+    /// [crate::common::set_rate_limit]) at [RequestPriority::Price].
+    async fn place_order(
+        &self,
+        credentials: &Credentials,
+        order: &CexOrder,
+        mode: TradingMode,
+    ) -> Result<CexOrderAck, MarketScannerError> {
+        acquire_request_slot(self.exchange_name(), RequestPriority::Price).await;
+        // Signed after the rate-limit wait so the timestamp is fresh
+        let request = self.order_request(credentials, order, mode, get_timestamp_millis())?;
+        if dry_run_enabled() {
+            let order = order.rounded()?;
+            let order_id = record_dry_run(
+                self.exchange_name(),
+                DryRunAction::PlaceOrder,
+                dry_run_request(&request),
+                None,
+            );
+            return Ok(CexOrderAck {
+                exchange: self.cex_exchange(),
+                symbol: order.symbol.clone(),
+                order_id,
+                client_order_id: order.client_order_id.clone(),
+                side: order.side,
+                quantity: order.quantity,
+                price: order.price,
+                mode,
+                timestamp: Timestamp::now(),
+            });
+        }
+        let value = send_signed_request(self.client(), self.exchange_name(), &request).await?;
+        self.parse_order_response(&value, order, mode)
+    }
+
+    /// Current state of the order behind `ack` (in the mode it was placed in). Orders
+    /// placed in dry-run mode stay [OrderStatus::New] and are not queried.
+    async fn order_status(
+        &self,
+        credentials: &Credentials,
+        ack: &CexOrderAck,
+    ) -> Result<OrderUpdate, MarketScannerError> {
+        if is_dry_run_id(&ack.order_id) {
+            return Ok(OrderUpdate {
+                exchange: ack.exchange.clone(),
+                symbol: ack.symbol.clone(),
+                order_id: ack.order_id.clone(),
+                client_order_id: ack.client_order_id.clone(),
+                side: ack.side,
+                status: OrderStatus::New,
+                price: ack.price,
+                quantity: ack.quantity,
+                filled_quantity: 0.0,
+                average_fill_price: None,
+                timestamp: Timestamp::now(),
+            });
+        }
+        acquire_request_slot(self.exchange_name(), RequestPriority::Price).await;
+        let request = self.order_status_request(credentials, ack, get_timestamp_millis())?;
+        let value = send_signed_request(self.client(), self.exchange_name(), &request).await?;
+        self.parse_order_status(&value, ack)
+    }
+
+    /// Cancels the order behind `ack`. Fails if the venue refuses (e.g. already filled).
+    async fn cancel_order(
+        &self,
+        credentials: &Credentials,
+        ack: &CexOrderAck,
+    ) -> Result<(), MarketScannerError> {
+        acquire_request_slot(self.exchange_name(), RequestPriority::Price).await;
+        let request = self.cancel_request(credentials, ack, get_timestamp_millis())?;
+        if dry_run_enabled() {
+            record_dry_run(
+                self.exchange_name(),
+                DryRunAction::CancelOrder,
+                dry_run_request(&request),
+                None,
+            );
+            return Ok(());
+        }
+        let value = send_signed_request(self.client(), self.exchange_name(), &request).await?;
+        self.parse_cancel_response(&value, ack)
+    }
+
+    /// [CexOrderExecution::place_order] after reserving the order with `risk` (see
+    /// [RiskManager::reserve_order]); nothing is sent if a limit would be broken. The
+    /// reservation is released again if the order fails.
+    async fn place_order_with_risk(
+        &self,
+        risk: &RiskManager,
+        credentials: &Credentials,
+        order: &CexOrder,
+        mode: TradingMode,
+        reference_price: Option<f64>,
+    ) -> Result<CexOrderAck, MarketScannerError> {
+        let exposure = risk.reserve_order(&self.cex_exchange(), order, reference_price)?;
+        let ack = self.place_order(credentials, order, mode).await;
+        if ack.is_err() {
+            risk.release(&exposure);
+        }
+        ack
+    }
+}
+
+/// Sends `request` and decodes the JSON response; HTTP errors become [MarketScannerError::ApiError].
+async fn send_signed_request(
+    client: &reqwest::Client,
+    exchange: &str,
+    request: &SignedOrderRequest,
+) -> Result<serde_json::Value, MarketScannerError> {
+    let mut builder = client.request(request.method.clone(), &request.url);
+    if !request.body.is_empty() {
+        builder = builder
+            .header(CONTENT_TYPE, "application/json")
+            .body(request.body.clone());
+    }
+    for (name, value) in &request.headers {
+        builder = builder.header(name, value);
+    }
+    let response = builder.send().await?;
+
+    let status = response.status();
+    let text = response.text().await?;
+    if !status.is_success() {
+        return Err(MarketScannerError::ApiError(format!(
+            "{} API error: {} - {}",
+            exchange, status, text
+        )));
+    }
+    Ok(serde_json::from_str(&text)?)
+}
+
+/// Method, URL and body of `request` for the dry-run log; headers carry secrets.
+fn dry_run_request(request: &SignedOrderRequest) -> String {
+    if request.body.is_empty() {
+        format!("{} {}", request.method, request.url)
+    } else {
+        format!("{} {} {}", request.method, request.url, request.body)
+    }
+}
+
+/// Significant digits kept when the venue increment is unknown: all an f64 holds reliably,
+/// so float noise such as `0.30000000000000004` is dropped.
+const AMOUNT_SIGNIFICANT_DIGITS: i32 = 15;
+
+/// Decimal string of an order amount (no exponent notation) with the decimals of
+/// `increment` (a venue step or tick size) when one is known. Amounts are rounded to the
+/// increment beforehand (see [CexOrder::rounded]).
+pub(crate) fn format_amount(value: f64, increment: Option<f64>) -> String {
+    let decimals = match valid_increment(increment) {
+        Some(increment) => increment_decimals(increment),
+        None => {
+            let integer_digits = if value.abs() >= 1.0 {
+                value.abs().log10().floor() as i32 + 1
+            } else {
+                0
+            };
+            (AMOUNT_SIGNIFICANT_DIGITS - integer_digits).max(0) as usize
+        }
+    };
+    let formatted = format!("{:.*}", decimals, value);
+    if formatted.contains('.') {
+        formatted
+            .trim_end_matches('0')
+            .trim_end_matches('.')
+            .to_string()
+    } else {
+        formatted
+    }
+}
+
+fn valid_increment(increment: Option<f64>) -> Option<f64> {
+    increment.filter(|i| i.is_finite() && *i > 0.0)
+}
+
+/// `value` as a multiple of `increment`, rounded up or down; values within float noise of a
+/// multiple (`0.003 / 0.001` is `2.9999999999999996`) stay on it.
+fn round_to_increment(value: f64, increment: f64, up: bool) -> f64 {
+    let steps = value / increment;
+    let nearest = steps.round();
+    let steps = if (steps - nearest).abs() < 1e-9 {
+        nearest
+    } else if up {
+        steps.ceil()
+    } else {
+        steps.floor()
+    };
+    steps * increment
+}
+
+/// Decimal places of a step or tick size (`0.001` → 3, `5` → 0).
+fn increment_decimals(increment: f64) -> usize {
+    (0..AMOUNT_SIGNIFICANT_DIGITS)
+        .find(|decimals| {
+            let scaled = increment * 10f64.powi(*decimals);
+            (scaled - scaled.round()).abs() < 1e-9 * scaled.max(1.0)
+        })
+        .unwrap_or(AMOUNT_SIGNIFICANT_DIGITS) as usize
+}
+
+/// Passphrase of venues that require one (OKX, Bitget).
+pub(crate) fn required_passphrase<'a>(
+    credentials: &'a Credentials,
+    exchange: &str,
+) -> Result<&'a str, MarketScannerError> {
+    credentials.passphrase().ok_or_else(|| {
+        MarketScannerError::InvalidConfig(format!("{} credentials need a passphrase", exchange))
+    })
+}
@@ -0,0 +1,23 @@
+//! HMAC-SHA256 request signatures used by venue REST APIs.
+
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+fn hmac_sha256(secret: &str, message: &str) -> Vec<u8> {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any length");
+    mac.update(message.as_bytes());
+    mac.finalize().into_bytes().to_vec()
+}
+
+/// Base64 HMAC-SHA256 of `message` (OKX, Bitget).
+pub fn hmac_sha256_base64(secret: &str, message: &str) -> String {
+    STANDARD.encode(hmac_sha256(secret, message))
+}
+
+/// Lower-case hex HMAC-SHA256 of `message` (Bybit, Binance).
+pub fn hmac_sha256_hex(secret: &str, message: &str) -> String {
+    hex::encode(hmac_sha256(secret, message))
+}
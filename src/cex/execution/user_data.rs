@@ -0,0 +1,280 @@
+//! Authenticated user-data streams: order and balance updates.
+//!
+//! [UserDataStream::stream_user_data] logs into a venue's private WebSocket (Binance
+//! listenKey stream, Bybit private topics, OKX private channels) and emits unified
+//! [UserDataEvent]s, so placed orders can be followed to their final state.
+//! [UserDataStream::parse_user_data] exposes the message mapping without a connection.
+
+use super::TradingMode;
+use crate::common::health::decode_private_ws_json;
+use crate::common::reconnect::ReconnectPolicy;
+use crate::common::{CEXTrait, CexExchange, Credentials, MarketScannerError, Timestamp};
+use crate::portfolio::TradeSide;
+use async_trait::async_trait;
+use futures::future::BoxFuture;
+use futures::{SinkExt, StreamExt};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tokio_tungstenite::tungstenite::Message as WsMessage;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum OrderStatus {
+    New,
+    PartiallyFilled,
+    Filled,
+    Canceled,
+    Rejected,
+    Expired,
+    /// Venue state without a unified equivalent (e.g. pending cancel, untriggered)
+    Unknown,
+}
+
+impl OrderStatus {
+    /// Whether the order can no longer change.
+    pub fn is_final(self) -> bool {
+        matches!(
+            self,
+            OrderStatus::Filled
+                | OrderStatus::Canceled
+                | OrderStatus::Rejected
+                | OrderStatus::Expired
+        )
+    }
+}
+
+/// State of one order after a venue update.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OrderUpdate {
+    pub exchange: CexExchange,
+    /// Standard symbol (e.g. "BTCUSDT")
+    pub symbol: String,
+    pub order_id: String,
+    pub client_order_id: Option<String>,
+    pub side: TradeSide,
+    pub status: OrderStatus,
+    /// Limit price; `None` for market orders
+    pub price: Option<f64>,
+    /// Ordered base quantity
+    pub quantity: f64,
+    /// Cumulative filled base quantity
+    pub filled_quantity: f64,
+    pub average_fill_price: Option<f64>,
+    pub timestamp: Timestamp,
+}
+
+/// Balance of one asset after a venue update.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BalanceUpdate {
+    pub exchange: CexExchange,
+    pub asset: String,
+    pub free: f64,
+    /// Held by open orders
+    pub locked: f64,
+    pub timestamp: Timestamp,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum UserDataEvent {
+    Order(OrderUpdate),
+    Balance(BalanceUpdate),
+}
+
+/// Private WebSocket streams of orders and balances.
+#[async_trait]
+pub trait UserDataStream: CEXTrait {
+    /// Order and balance events in one decoded venue message (none for acks, pongs, ...).
+    fn parse_user_data(&self, message: &serde_json::Value) -> Vec<UserDataEvent>;
+
+    /// Logs in with `credentials` and streams order and balance updates of the account
+    /// (`mode` selects the demo environment). Login problems found before connecting
+    /// (e.g. a rejected listenKey request) are returned; later failures reconnect as in
+    /// [CEXTrait::stream_price_websocket].
+    async fn stream_user_data(
+        &self,
+        credentials: &Credentials,
+        mode: TradingMode,
+        reconnect_attempts: u32,
+        reconnect_delay_ms: u64,
+    ) -> Result<mpsc::Receiver<UserDataEvent>, MarketScannerError>;
+}
+
+/// Periodic request keeping a session alive (e.g. Binance listenKey keepalive).
+pub(crate) type KeepAlive = Arc<dyn Fn() -> BoxFuture<'static, ()> + Send + Sync>;
+
+/// Creates the session of each (re)connection.
+pub(crate) type SessionFactory =
+    Arc<dyn Fn() -> BoxFuture<'static, Result<PrivateWsSession, MarketScannerError>> + Send + Sync>;
+
+/// Connection parameters of one private WebSocket session.
+pub(crate) struct PrivateWsSession {
+    pub url: String,
+    /// Sent right after connecting (login, subscriptions)
+    pub on_connect: Vec<String>,
+    /// Sent once `login_ack` accepts a message (venues that reject early subscriptions)
+    pub after_login: Vec<String>,
+    pub login_ack: Option<fn(&serde_json::Value) -> bool>,
+    /// Messages after which the session must be renewed (e.g. expired listenKey)
+    pub expired: Option<fn(&serde_json::Value) -> bool>,
+    /// Text ping; a WebSocket ping frame when `None`
+    pub text_ping: Option<String>,
+    pub ping_interval: Duration,
+    pub keepalive: Option<(Duration, KeepAlive)>,
+}
+
+/// Runs sessions from `new_session` until the receiver is dropped or reconnects run out.
+/// `first_session` is used for the first connection.
+pub(crate) fn spawn_user_data_stream(
+    exchange: CexExchange,
+    first_session: PrivateWsSession,
+    new_session: SessionFactory,
+    parse: fn(&serde_json::Value) -> Vec<UserDataEvent>,
+    reconnect_attempts: u32,
+    reconnect_delay_ms: u64,
+) -> mpsc::Receiver<UserDataEvent> {
+    let (tx, rx) = mpsc::channel(64);
+    let policy = ReconnectPolicy::new(reconnect_attempts, reconnect_delay_ms);
+
+    tokio::spawn(async move {
+        let mut attempt = 0u32;
+        let mut next_session = Some(first_session);
+        loop {
+            attempt += 1;
+            let session = match next_session.take() {
+                Some(session) => Ok(session),
+                None => new_session().await,
+            };
+            if let Ok(session) = session {
+                run_session(&exchange, session, parse, &tx).await;
+            }
+            if tx.is_closed() || !policy.should_reconnect(attempt) {
+                break;
+            }
+            tokio::time::sleep(policy.delay()).await;
+        }
+    });
+    rx
+}
+
+/// One connection; returns when it closes, fails or expires.
+async fn run_session(
+    exchange: &CexExchange,
+    session: PrivateWsSession,
+    parse: fn(&serde_json::Value) -> Vec<UserDataEvent>,
+    tx: &mpsc::Sender<UserDataEvent>,
+) {
+    let Ok((ws_stream, _)) = tokio_tungstenite::connect_async(&session.url).await else {
+        return;
+    };
+    let (mut write, mut read) = ws_stream.split();
+    for message in &session.on_connect {
+        if write.send(WsMessage::Text(message.clone())).await.is_err() {
+            return;
+        }
+    }
+
+    let mut logged_in = session.login_ack.is_none();
+    let mut ping_interval = tokio::time::interval(session.ping_interval);
+    ping_interval.tick().await;
+    let mut keepalive = session.keepalive.map(|(every, keepalive)| {
+        let mut interval = tokio::time::interval(every);
+        interval.reset();
+        (interval, keepalive)
+    });
+
+    loop {
+        tokio::select! {
+            _ = ping_interval.tick() => {
+                let ping = match &session.text_ping {
+                    Some(text) => WsMessage::Text(text.clone()),
+                    None => WsMessage::Ping(Vec::new()),
+                };
+                if write.send(ping).await.is_err() {
+                    return;
+                }
+            }
+            _ = async {
+                match keepalive.as_mut() {
+                    Some((interval, _)) => {
+                        interval.tick().await;
+                    }
+                    None => std::future::pending::<()>().await,
+                }
+            } => {
+                if let Some((_, keepalive)) = &keepalive {
+                    tokio::spawn(keepalive());
+                }
+            }
+            msg = read.next() => {
+                let text = match msg {
+                    Some(Ok(WsMessage::Text(text))) => text,
+                    Some(Ok(WsMessage::Ping(payload))) => {
+                        let _ = write.send(WsMessage::Pong(payload)).await;
+                        continue;
+                    }
+                    Some(Ok(WsMessage::Close(_))) | Some(Err(_)) | None => return,
+                    Some(Ok(_)) => continue,
+                };
+                if text == "pong" {
+                    continue;
+                }
+                let Some(value) = decode_private_ws_json(exchange, &text) else {
+                    continue;
+                };
+
+                if !logged_in {
+                    if session.login_ack.is_some_and(|ack| ack(&value)) {
+                        logged_in = true;
+                        for message in &session.after_login {
+                            if write.send(WsMessage::Text(message.clone())).await.is_err() {
+                                return;
+                            }
+                        }
+                    }
+                    continue;
+                }
+                if session.expired.is_some_and(|expired| expired(&value)) {
+                    return;
+                }
+                for event in parse(&value) {
+                    if tx.send(event).await.is_err() {
+                        return;
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// `f64` from a JSON string or number field.
+pub(crate) fn json_f64(value: &serde_json::Value) -> Option<f64> {
+    match value {
+        serde_json::Value::String(s) => s.parse().ok(),
+        _ => value.as_f64(),
+    }
+}
+
+/// Non-empty string field.
+pub(crate) fn json_str(value: &serde_json::Value) -> Option<String> {
+    value.as_str().filter(|s| !s.is_empty()).map(str::to_string)
+}
+
+/// Millisecond timestamp from a JSON string or number field; now when missing.
+pub(crate) fn json_timestamp(value: &serde_json::Value) -> Timestamp {
+    match value {
+        serde_json::Value::String(s) => s.parse().ok(),
+        _ => value.as_u64(),
+    }
+    .map(Timestamp::from_millis)
+    .unwrap_or_else(Timestamp::now)
+}
+
+/// Buy / sell in any capitalisation.
+pub(crate) fn json_side(value: &serde_json::Value) -> Option<TradeSide> {
+    match value.as_str()?.to_ascii_lowercase().as_str() {
+        "buy" => Some(TradeSide::Buy),
+        "sell" => Some(TradeSide::Sell),
+        _ => None,
+    }
+}
@@ -1,19 +1,25 @@
 mod types;
 
 use crate::cex::gateio::types::GateioOrderBookResponse;
+use crate::common::capabilities::{Capabilities, VenueCapability};
+use crate::common::clock::json_server_time;
+use crate::common::reconnect::{ReconnectPolicy, ws_endpoint};
+use crate::common::symbol_registry;
 use crate::common::{
-    CEXTrait, CexExchange, CexPrice, Exchange, ExchangeTrait, MarketScannerError, find_mid_price,
+    CEXTrait, CexExchange, CexPrice, Exchange, ExchangeTrait, MarketScannerError, RequestPriority,
+    Timestamp, TradingStatus, WsSymbolFilter, decode_ws_json, find_mid_price,
     format_symbol_for_exchange, format_symbol_for_exchange_ws, get_timestamp_millis, parse_f64,
-    standard_symbol_for_cex_ws_response,
+    record_ws_connect, standard_symbol_for_cex_ws_response,
 };
 use crate::create_exchange;
 use async_trait::async_trait;
 use futures::{SinkExt, StreamExt};
 use tokio::sync::mpsc;
+use tokio_tungstenite::tungstenite::Message as WsMessage;
 
 const GATEIO_API_BASE: &str = "https://api.gateio.ws/api/v4";
-// WebSocket v3: wss://ws.gate.io/v3/ - method/params format (depth.subscribe)
-const GATEIO_WS_URL: &str = "wss://ws.gate.io/v3/";
+// WebSocket v4: channel/event format (spot.book_ticker, spot.ping)
+const GATEIO_WS_URL: &str = "wss://api.gateio.ws/ws/v4/";
 
 create_exchange!(Gateio);
 
@@ -28,18 +34,25 @@ impl ExchangeTrait for Gateio {
     }
 
     fn exchange_name(&self) -> &str {
-        "Gate.io"
+        CexExchange::Gateio.rest_venue_name()
     }
 
     async fn health_check(&self) -> Result<(), MarketScannerError> {
         // Gate.io time endpoint - test connectivity to the REST API
         let endpoint = "spot/time";
-        self.get::<serde_json::Value>(endpoint)
+        self.get_with_priority::<serde_json::Value>(endpoint, RequestPriority::Metadata)
             .await
             .map_err(|_| MarketScannerError::HealthCheckFailed)?;
 
         Ok(())
     }
+
+    async fn get_server_time(&self) -> Result<Timestamp, MarketScannerError> {
+        let response: serde_json::Value = self
+            .get_with_priority("spot/time", RequestPriority::Metadata)
+            .await?;
+        json_server_time(&response, "/server_time", self.exchange_name())
+    }
 }
 
 #[async_trait]
@@ -48,6 +61,10 @@ impl CEXTrait for Gateio {
         true
     }
 
+    fn capabilities(&self) -> Capabilities {
+        Capabilities::new(&[VenueCapability::Websocket, VenueCapability::ServerTime])
+    }
+
     async fn get_price(&self, symbol: &str) -> Result<CexPrice, MarketScannerError> {
         // Validate symbol is not empty
         if symbol.is_empty() {
@@ -90,8 +107,9 @@ impl CEXTrait for Gateio {
             ask_price: ask,
             bid_qty,
             ask_qty,
-            timestamp: get_timestamp_millis(),
+            timestamp: Timestamp::now(),
             exchange: Exchange::Cex(CexExchange::Gateio),
+            trading_status: TradingStatus::Unknown,
         })
     }
 
@@ -112,152 +130,144 @@ impl CEXTrait for Gateio {
             .map(|s| format_symbol_for_exchange_ws(s, &CexExchange::Gateio))
             .collect::<Result<Vec<_>, _>>()?;
 
-        // depth.subscribe: multi-market [[market, limit, interval], ...]
-        let params: Vec<serde_json::Value> = gateio_symbols
-            .iter()
-            .map(|s| serde_json::json!([s, 10, "0.01"]))
-            .collect();
+        // spot.book_ticker: best bid/ask per pair, payload lists all pairs
         let subscribe_msg = serde_json::json!({
-            "id": 1,
-            "method": "depth.subscribe",
-            "params": params
+            "time": get_timestamp_millis() / 1000,
+            "channel": "spot.book_ticker",
+            "event": "subscribe",
+            "payload": gateio_symbols
         });
 
         let (tx, rx) = mpsc::channel(64);
-        let delay =
-            std::time::Duration::from_millis(if reconnect_delay_ms == 0 { 1000 } else { reconnect_delay_ms });
+        let symbol_filter = WsSymbolFilter::new(&CexExchange::Gateio, symbols);
+        let policy = ReconnectPolicy::new(reconnect_attempts, reconnect_delay_ms);
+        let url = ws_endpoint(&CexExchange::Gateio, GATEIO_WS_URL);
 
-        tokio::spawn(async move {
+        symbol_registry::spawn(async move {
             let mut attempt = 0u32;
             loop {
                 attempt += 1;
-                let (mut ws_stream, _) = match tokio_tungstenite::connect_async(GATEIO_WS_URL).await
-                {
-                    Ok(v) => v,
+                let (mut ws_stream, _) = match tokio_tungstenite::connect_async(&url).await {
+                    Ok(v) => {
+                        record_ws_connect(&CexExchange::Gateio);
+                        v
+                    }
                     Err(_) => {
-                        if tx.is_closed()
-                            || reconnect_attempts == 0
-                            || attempt > reconnect_attempts
-                        {
+                        if tx.is_closed() || !policy.should_reconnect(attempt) {
                             break;
                         }
-                        tokio::time::sleep(delay).await;
+                        tokio::time::sleep(policy.delay()).await;
                         continue;
                     }
                 };
 
                 if ws_stream
-                    .send(tokio_tungstenite::tungstenite::Message::Text(
-                        subscribe_msg.to_string(),
-                    ))
+                    .send(WsMessage::Text(subscribe_msg.to_string()))
                     .await
                     .is_err()
                 {
-                    if tx.is_closed()
-                        || reconnect_attempts == 0
-                        || attempt > reconnect_attempts
-                    {
+                    if tx.is_closed() || !policy.should_reconnect(attempt) {
                         break;
                     }
-                    tokio::time::sleep(delay).await;
+                    tokio::time::sleep(policy.delay()).await;
                     continue;
                 }
 
-                let (_write, mut read) = ws_stream.split();
-
-                while let Some(Ok(msg)) = read.next().await {
-                    let text = match msg.into_text() {
-                        Ok(t) => t,
-                        Err(_) => continue,
-                    };
-                    let value: serde_json::Value = match serde_json::from_str(&text) {
-                        Ok(v) => v,
-                        Err(_) => continue,
-                    };
-                    // Skip subscribe ack: {"error":null,"result":{"status":"success"},"id":1}
-                    if value.get("id").is_some() && value.get("id").unwrap().is_number() {
-                        let result = value.get("result");
-                        if result
-                            .and_then(|r| r.get("status"))
-                            .and_then(|s| s.as_str())
-                            == Some("success")
-                        {
-                            continue;
-                        }
-                        if value.get("error").is_some() {
-                            continue;
+                let (mut write, mut read) = ws_stream.split();
+
+                // Application-level ping keeps the connection alive and detects dead links
+                let mut ping_interval = tokio::time::interval(std::time::Duration::from_secs(15));
+                ping_interval.tick().await;
+
+                loop {
+                    tokio::select! {
+                        _ = ping_interval.tick() => {
+                            let ping = serde_json::json!({
+                                "time": get_timestamp_millis() / 1000,
+                                "channel": "spot.ping"
+                            });
+                            if write.send(WsMessage::Text(ping.to_string())).await.is_err() {
+                                break;
+                            }
                         }
-                    }
-                    // depth.update: params = [clean, depth, market]; depth has bids/asks
-                    if value.get("method").and_then(|m| m.as_str()) != Some("depth.update") {
-                        continue;
-                    }
-                    let params = match value.get("params").and_then(|p| p.as_array()) {
-                        Some(p) if p.len() >= 3 => p,
-                        _ => continue,
-                    };
-                    let market = params[2].as_str().unwrap_or("");
-                    let symbol_std =
-                        standard_symbol_for_cex_ws_response(market, &CexExchange::Gateio);
-                    let depth = match params[1].as_object() {
-                        Some(d) => d,
-                        None => continue,
-                    };
-                    let bids = depth.get("bids").and_then(|v| v.as_array());
-                    let asks = depth.get("asks").and_then(|v| v.as_array());
-                    let (bid_entry, ask_entry) = match (bids, asks) {
-                        (Some(b), Some(a)) => {
-                            let be = b.first().and_then(|x| x.as_array());
-                            let ae = a.first().and_then(|x| x.as_array());
-                            match (be, ae) {
-                                (Some(be), Some(ae)) if be.len() >= 2 && ae.len() >= 2 => (be, ae),
-                                _ => continue,
+                        msg = read.next() => {
+                            let msg = match msg {
+                                Some(Ok(m)) => m,
+                                _ => break,
+                            };
+                            match msg {
+                                WsMessage::Text(t) => {
+                                    let value: serde_json::Value =
+                                        match decode_ws_json(&CexExchange::Gateio, &t) {
+                                            Some(v) => v,
+                                            None => continue,
+                                        };
+                                    // spot.pong and subscribe acks carry no prices
+                                    if let Some(price) = parse_gateio_book_ticker(&value) {
+                                        if !symbol_filter.allows(&price) {
+                                            continue;
+                                        }
+                                        if tx.send(price).await.is_err() {
+                                            return;
+                                        }
+                                    }
+                                }
+                                WsMessage::Ping(payload) => {
+                                    let _ = write.send(WsMessage::Pong(payload)).await;
+                                }
+                                WsMessage::Close(_) => break,
+                                _ => {}
                             }
                         }
-                        _ => continue,
-                    };
-                    let bid_str = bid_entry[0].as_str().unwrap_or("");
-                    let bid_qty_str = bid_entry[1].as_str().unwrap_or("0");
-                    let ask_str = ask_entry[0].as_str().unwrap_or("");
-                    let ask_qty_str = ask_entry[1].as_str().unwrap_or("0");
-                    let bid = match parse_f64(bid_str, "bid") {
-                        Ok(v) => v,
-                        Err(_) => continue,
-                    };
-                    let ask = match parse_f64(ask_str, "ask") {
-                        Ok(v) => v,
-                        Err(_) => continue,
-                    };
-                    let bid_qty = parse_f64(bid_qty_str, "bid_qty").unwrap_or(0.0);
-                    let ask_qty = parse_f64(ask_qty_str, "ask_qty").unwrap_or(0.0);
-                    if bid <= 0.0 || ask <= 0.0 {
-                        continue;
-                    }
-                    let price = CexPrice {
-                        symbol: symbol_std,
-                        mid_price: find_mid_price(bid, ask),
-                        bid_price: bid,
-                        ask_price: ask,
-                        bid_qty,
-                        ask_qty,
-                        timestamp: get_timestamp_millis(),
-                        exchange: Exchange::Cex(CexExchange::Gateio),
-                    };
-                    if tx.send(price).await.is_err() {
-                        return;
                     }
                 }
 
-                if tx.is_closed()
-                    || reconnect_attempts == 0
-                    || attempt > reconnect_attempts
-                {
+                if tx.is_closed() || !policy.should_reconnect(attempt) {
                     break;
                 }
-                tokio::time::sleep(delay).await;
+                tokio::time::sleep(policy.delay()).await;
             }
         });
 
         Ok(rx)
     }
 }
+
+/// Parses a `spot.book_ticker` update:
+/// {"channel":"spot.book_ticker","event":"update","result":{"s":"BTC_USDT","b":"..","B":"..","a":"..","A":".."}}
+fn parse_gateio_book_ticker(value: &serde_json::Value) -> Option<CexPrice> {
+    if value.get("channel").and_then(|c| c.as_str()) != Some("spot.book_ticker")
+        || value.get("event").and_then(|e| e.as_str()) != Some("update")
+    {
+        return None;
+    }
+    let result = value.get("result")?;
+    let market = result.get("s").and_then(|s| s.as_str())?;
+    let bid = parse_f64(result.get("b")?.as_str()?, "bid").ok()?;
+    let ask = parse_f64(result.get("a")?.as_str()?, "ask").ok()?;
+    if bid <= 0.0 || ask <= 0.0 {
+        return None;
+    }
+    let bid_qty = result
+        .get("B")
+        .and_then(|v| v.as_str())
+        .and_then(|v| parse_f64(v, "bid_qty").ok())
+        .unwrap_or(0.0);
+    let ask_qty = result
+        .get("A")
+        .and_then(|v| v.as_str())
+        .and_then(|v| parse_f64(v, "ask_qty").ok())
+        .unwrap_or(0.0);
+
+    Some(CexPrice {
+        symbol: standard_symbol_for_cex_ws_response(market, &CexExchange::Gateio),
+        mid_price: find_mid_price(bid, ask),
+        bid_price: bid,
+        ask_price: ask,
+        bid_qty,
+        ask_qty,
+        timestamp: Timestamp::now(),
+        exchange: Exchange::Cex(CexExchange::Gateio),
+        trading_status: TradingStatus::Unknown,
+    })
+}
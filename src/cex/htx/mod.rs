@@ -1,14 +1,19 @@
 mod types;
 
 use crate::cex::htx::types::HtxOrderBookResponse;
+use crate::common::capabilities::{Capabilities, VenueCapability};
+use crate::common::clock::json_server_time;
 use crate::common::{
-    CEXTrait, CexExchange, CexPrice, Exchange, ExchangeTrait, MarketScannerError, find_mid_price,
-    format_symbol_for_exchange, get_timestamp_millis,
+    CEXTrait, CexExchange, CexPrice, Exchange, ExchangeTrait, MarketScannerError, OrderBook,
+    OrderBookLevel, RequestPriority, Timestamp, TradingStatus, find_mid_price,
+    format_symbol_for_exchange,
 };
 use crate::create_exchange;
 use async_trait::async_trait;
 
 const HTX_API_BASE: &str = "https://api.htx.com";
+/// Depth values accepted by `market/depth` with `type=step0`
+const HTX_DEPTH_LEVELS: [usize; 3] = [5, 10, 20];
 
 create_exchange!(Htx);
 
@@ -23,14 +28,16 @@ impl ExchangeTrait for Htx {
     }
 
     fn exchange_name(&self) -> &str {
-        "HTX"
+        CexExchange::Htx.rest_venue_name()
     }
 
     async fn health_check(&self) -> Result<(), MarketScannerError> {
         // HTX orderbook endpoint - test connectivity to the REST API
         // Using a common pair like BTCUSDT for health check
         let endpoint = "market/depth?symbol=btcusdt&type=step0";
-        let response: serde_json::Value = self.get(endpoint).await?;
+        let response: serde_json::Value = self
+            .get_with_priority(endpoint, RequestPriority::Metadata)
+            .await?;
 
         // HTX returns {"status": "ok", ...}
         let status = response["status"].as_str().unwrap_or("");
@@ -40,6 +47,13 @@ impl ExchangeTrait for Htx {
             Err(MarketScannerError::HealthCheckFailed)
         }
     }
+
+    async fn get_server_time(&self) -> Result<Timestamp, MarketScannerError> {
+        let response: serde_json::Value = self
+            .get_with_priority("v1/common/timestamp", RequestPriority::Metadata)
+            .await?;
+        json_server_time(&response, "/data", self.exchange_name())
+    }
 }
 
 #[async_trait]
@@ -48,6 +62,10 @@ impl CEXTrait for Htx {
         false
     }
 
+    fn capabilities(&self) -> Capabilities {
+        Capabilities::new(&[VenueCapability::OrderBook, VenueCapability::ServerTime])
+    }
+
     async fn get_price(&self, symbol: &str) -> Result<CexPrice, MarketScannerError> {
         // Validate symbol is not empty
         if symbol.is_empty() {
@@ -119,7 +137,69 @@ impl CEXTrait for Htx {
             ask_price: ask,
             bid_qty,
             ask_qty,
-            timestamp: get_timestamp_millis(),
+            timestamp: Timestamp::now(),
+            exchange: Exchange::Cex(CexExchange::Htx),
+            trading_status: TradingStatus::Unknown,
+        })
+    }
+
+    async fn get_order_book(
+        &self,
+        symbol: &str,
+        depth: usize,
+    ) -> Result<OrderBook, MarketScannerError> {
+        if symbol.is_empty() {
+            return Err(MarketScannerError::InvalidSymbol(
+                "Symbol cannot be empty".to_string(),
+            ));
+        }
+
+        let htx_symbol = format_symbol_for_exchange(symbol, &CexExchange::Htx)?;
+        // step0 accepts depth 5, 10 or 20; without it the full 150 levels are returned
+        let endpoint = match HTX_DEPTH_LEVELS.iter().find(|d| **d >= depth) {
+            Some(d) => format!("market/depth?symbol={}&type=step0&depth={}", htx_symbol, d),
+            None => format!("market/depth?symbol={}&type=step0", htx_symbol),
+        };
+
+        let response: serde_json::Value = self
+            .get_with_priority(&endpoint, RequestPriority::OrderBook)
+            .await?;
+        let status = response["status"].as_str().unwrap_or("");
+        if status != "ok" {
+            let err_msg = response["err-msg"]
+                .as_str()
+                .unwrap_or("Unknown error")
+                .to_string();
+            return Err(MarketScannerError::ApiError(format!(
+                "HTX API error: {}",
+                err_msg
+            )));
+        }
+
+        let orderbook_response: HtxOrderBookResponse =
+            serde_json::from_value(response).map_err(|e| {
+                MarketScannerError::ApiError(format!(
+                    "HTX API error: failed to parse orderbook response: {}",
+                    e
+                ))
+            })?;
+
+        let to_levels = |entries: &[[f64; 2]]| -> Vec<OrderBookLevel> {
+            entries
+                .iter()
+                .take(depth)
+                .map(|e| OrderBookLevel {
+                    price: e[0],
+                    quantity: e[1],
+                })
+                .collect()
+        };
+
+        Ok(OrderBook {
+            symbol: crate::common::normalize_symbol(symbol),
+            bids: to_levels(&orderbook_response.tick.bids),
+            asks: to_levels(&orderbook_response.tick.asks),
+            timestamp: Timestamp::now(),
             exchange: Exchange::Cex(CexExchange::Htx),
         })
     }
@@ -1,10 +1,17 @@
 mod types;
 
 use crate::cex::kraken::types::KrakenDepthResponse;
+use crate::common::capabilities::{Capabilities, VenueCapability};
+use crate::common::clock::json_server_time;
+use crate::common::reconnect::{ReconnectPolicy, ws_endpoint};
+use crate::common::symbol_overrides::symbol_override;
+use crate::common::symbol_registry;
 use crate::common::{
-    CEXTrait, CexExchange, CexPrice, Exchange, ExchangeTrait, MarketScannerError, find_mid_price,
-    format_symbol_for_exchange, format_symbol_for_exchange_ws, get_timestamp_millis, parse_f64,
-    standard_symbol_for_cex_ws_response,
+    CEXTrait, CexExchange, CexPrice, Exchange, ExchangeTrait, MarketScannerError, RequestPriority,
+    Timestamp, TradingStatus, WsSymbolFilter, canonical_asset, decode_ws_json, find_mid_price,
+    format_symbol_for_exchange, format_symbol_for_exchange_ws, get_timestamp_millis, instrument_id,
+    instrument_ids_refreshed_at, normalize_symbol, parse_f64, record_instrument_ids,
+    record_ws_connect, standard_symbol_for_cex_ws_response,
 };
 use crate::create_exchange;
 use async_trait::async_trait;
@@ -14,6 +21,8 @@ use tokio::sync::mpsc;
 
 const KRAKEN_API_BASE: &str = "https://api.kraken.com/0/public";
 const KRAKEN_WS_URL: &str = "wss://ws.kraken.com/v2";
+/// How long the `AssetPairs` listing is reused before unknown symbols trigger a refresh
+const KRAKEN_PAIR_CACHE_TTL_MS: u64 = 60 * 60 * 1000;
 
 create_exchange!(Kraken);
 
@@ -28,13 +37,15 @@ impl ExchangeTrait for Kraken {
     }
 
     fn exchange_name(&self) -> &str {
-        "Kraken"
+        CexExchange::Kraken.rest_venue_name()
     }
 
     async fn health_check(&self) -> Result<(), MarketScannerError> {
         // Kraken time endpoint - test connectivity to the REST API
         let endpoint = "Time";
-        let response: serde_json::Value = self.get(endpoint).await?;
+        let response: serde_json::Value = self
+            .get_with_priority(endpoint, RequestPriority::Metadata)
+            .await?;
 
         // Kraken returns {"error": [], "result": {"unixtime": ..., "rfc1123": ...}}
         let error = response["error"].as_array();
@@ -48,6 +59,13 @@ impl ExchangeTrait for Kraken {
             Err(MarketScannerError::HealthCheckFailed)
         }
     }
+
+    async fn get_server_time(&self) -> Result<Timestamp, MarketScannerError> {
+        let response: serde_json::Value = self
+            .get_with_priority("Time", RequestPriority::Metadata)
+            .await?;
+        json_server_time(&response, "/result/unixtime", self.exchange_name())
+    }
 }
 
 #[async_trait]
@@ -56,6 +74,14 @@ impl CEXTrait for Kraken {
         true
     }
 
+    fn capabilities(&self) -> Capabilities {
+        Capabilities::new(&[
+            VenueCapability::Websocket,
+            VenueCapability::InstrumentIds,
+            VenueCapability::ServerTime,
+        ])
+    }
+
     async fn get_price(&self, symbol: &str) -> Result<CexPrice, MarketScannerError> {
         // Validate symbol is not empty
         if symbol.is_empty() {
@@ -64,99 +90,15 @@ impl CEXTrait for Kraken {
             ));
         }
 
-        // Format symbol for Kraken (BTC -> XBT conversion)
-        let kraken_symbol = format_symbol_for_exchange(symbol, &CexExchange::Kraken)?;
-
-        // Using Depth endpoint with count=1 for best bid/ask only
-        let endpoint = format!("Depth?pair={}&count=1", kraken_symbol);
-
-        // First get as JSON value to handle errors gracefully
-        let response: serde_json::Value = self.get(&endpoint).await?;
-
-        // Check if API returned errors
-        let errors = response["error"].as_array().ok_or_else(|| {
-            MarketScannerError::ApiError("Kraken API response missing error field".to_string())
-        })?;
-
-        if !errors.is_empty() {
-            let error_msg = errors
-                .iter()
-                .filter_map(|e| e.as_str())
-                .collect::<Vec<_>>()
-                .join(", ");
-            return Err(MarketScannerError::ApiError(format!(
-                "Kraken API error: {}",
-                error_msg
-            )));
-        }
-
-        // Deserialize response to KrakenDepthResponse
-        let depth_response: KrakenDepthResponse =
-            serde_json::from_value(response).map_err(|e| {
-                MarketScannerError::ApiError(format!(
-                    "Kraken API error: failed to parse depth response: {}",
-                    e
-                ))
-            })?;
-
-        // Get the first (and only) pair data from result
-        let pair_data = depth_response.result.values().next().ok_or_else(|| {
-            MarketScannerError::ApiError(format!(
-                "Kraken API error: no data found for symbol: {}",
-                symbol
-            ))
-        })?;
-
-        // Get best bid (first element in bids array: [price, quantity, timestamp])
-        let bid_entry = pair_data.bids.first().ok_or_else(|| {
-            MarketScannerError::ApiError(format!(
-                "Kraken API error: no bid found for symbol: {}",
-                symbol
-            ))
-        })?;
-
-        // Get best ask (first element in asks array: [price, quantity, timestamp])
-        let ask_entry = pair_data.asks.first().ok_or_else(|| {
-            MarketScannerError::ApiError(format!(
-                "Kraken API error: no ask found for symbol: {}",
-                symbol
-            ))
-        })?;
+        // Resolve the Kraken pair (e.g. DOGEUSDT -> XDGUSDT, BTCUSDT -> XBTUSDT)
+        let kraken_pair = self.resolve_pair(symbol).await?;
 
-        // Parse bid entry: [price, quantity, timestamp]
-        let bid_price_str = bid_entry[0].as_str().ok_or_else(|| {
-            MarketScannerError::ApiError(format!(
-                "Kraken API error: invalid bid price format for symbol: {}",
-                symbol
-            ))
-        })?;
-
-        let bid_qty_str = bid_entry[1].as_str().ok_or_else(|| {
-            MarketScannerError::ApiError(format!(
-                "Kraken API error: invalid bid quantity format for symbol: {}",
-                symbol
-            ))
-        })?;
-
-        // Parse ask entry: [price, quantity, timestamp]
-        let ask_price_str = ask_entry[0].as_str().ok_or_else(|| {
-            MarketScannerError::ApiError(format!(
-                "Kraken API error: invalid ask price format for symbol: {}",
-                symbol
-            ))
-        })?;
-
-        let ask_qty_str = ask_entry[1].as_str().ok_or_else(|| {
-            MarketScannerError::ApiError(format!(
-                "Kraken API error: invalid ask quantity format for symbol: {}",
-                symbol
-            ))
-        })?;
-
-        let bid = parse_f64(bid_price_str, "bid price")?;
-        let ask = parse_f64(ask_price_str, "ask price")?;
-        let bid_qty = parse_f64(bid_qty_str, "bid quantity")?;
-        let ask_qty = parse_f64(ask_qty_str, "ask quantity")?;
+        // The trading status is best effort and must not fail the price
+        let (top, status) = tokio::join!(
+            self.fetch_top_of_book(&kraken_pair, symbol),
+            self.fetch_trading_status(&kraken_pair)
+        );
+        let (bid, ask, bid_qty, ask_qty) = top?;
 
         let mid_price = find_mid_price(bid, ask);
 
@@ -170,11 +112,17 @@ impl CEXTrait for Kraken {
             ask_price: ask,
             bid_qty,
             ask_qty,
-            timestamp: get_timestamp_millis(),
+            timestamp: Timestamp::now(),
             exchange: Exchange::Cex(CexExchange::Kraken),
+            trading_status: status.unwrap_or_default(),
         })
     }
 
+    async fn get_instrument_ids(&self) -> Result<HashMap<String, String>, MarketScannerError> {
+        let response = check_kraken_errors(self.get_cached("AssetPairs").await?)?;
+        Ok(parse_kraken_asset_pairs(&response))
+    }
+
     async fn stream_price_websocket(
         &self,
         symbols: &[&str],
@@ -201,13 +149,11 @@ impl CEXTrait for Kraken {
             }
         });
         let (tx, rx) = mpsc::channel(64);
-        let delay = std::time::Duration::from_millis(if reconnect_delay_ms == 0 {
-            1000
-        } else {
-            reconnect_delay_ms
-        });
+        let symbol_filter = WsSymbolFilter::new(&CexExchange::Kraken, symbols);
+        let policy = ReconnectPolicy::new(reconnect_attempts, reconnect_delay_ms);
+        let url = ws_endpoint(&CexExchange::Kraken, KRAKEN_WS_URL);
 
-        tokio::spawn(async move {
+        symbol_registry::spawn(async move {
             type BookMap = BTreeMap<rust_decimal::Decimal, rust_decimal::Decimal>;
             let mut attempt = 0u32;
 
@@ -256,15 +202,16 @@ impl CEXTrait for Kraken {
 
             loop {
                 attempt += 1;
-                let (mut ws_stream, _) = match tokio_tungstenite::connect_async(KRAKEN_WS_URL).await
-                {
-                    Ok(v) => v,
+                let (mut ws_stream, _) = match tokio_tungstenite::connect_async(&url).await {
+                    Ok(v) => {
+                        record_ws_connect(&CexExchange::Kraken);
+                        v
+                    }
                     Err(_) => {
-                        if tx.is_closed() || reconnect_attempts == 0 || attempt > reconnect_attempts
-                        {
+                        if tx.is_closed() || !policy.should_reconnect(attempt) {
                             break;
                         }
-                        tokio::time::sleep(delay).await;
+                        tokio::time::sleep(policy.delay()).await;
                         continue;
                     }
                 };
@@ -276,10 +223,10 @@ impl CEXTrait for Kraken {
                     .await
                     .is_err()
                 {
-                    if tx.is_closed() || reconnect_attempts == 0 || attempt > reconnect_attempts {
+                    if tx.is_closed() || !policy.should_reconnect(attempt) {
                         break;
                     }
-                    tokio::time::sleep(delay).await;
+                    tokio::time::sleep(policy.delay()).await;
                     continue;
                 }
 
@@ -287,13 +234,18 @@ impl CEXTrait for Kraken {
                 let mut books: HashMap<String, (BookMap, BookMap)> = HashMap::new();
 
                 while let Some(Ok(msg)) = read.next().await {
+                    // Control frames carry no payload to decode
+                    if msg.is_ping() || msg.is_pong() || msg.is_close() {
+                        continue;
+                    }
                     let text = match msg.into_text() {
                         Ok(t) => t,
                         Err(_) => continue,
                     };
-                    let value: serde_json::Value = match serde_json::from_str(&text) {
-                        Ok(v) => v,
-                        Err(_) => continue,
+                    let value: serde_json::Value = match decode_ws_json(&CexExchange::Kraken, &text)
+                    {
+                        Some(v) => v,
+                        None => continue,
                     };
 
                     // Server ping: respond with pong to keep connection alive
@@ -362,22 +314,270 @@ impl CEXTrait for Kraken {
                             ask_price: ask,
                             bid_qty,
                             ask_qty,
-                            timestamp: get_timestamp_millis(),
+                            timestamp: Timestamp::now(),
                             exchange: Exchange::Cex(CexExchange::Kraken),
+                            trading_status: TradingStatus::Unknown,
                         };
+                        if !symbol_filter.allows(&price) {
+                            continue;
+                        }
                         if tx.send(price).await.is_err() {
                             return;
                         }
                     }
                 }
 
-                if tx.is_closed() || reconnect_attempts == 0 || attempt > reconnect_attempts {
+                if tx.is_closed() || !policy.should_reconnect(attempt) {
                     break;
                 }
-                tokio::time::sleep(delay).await;
+                tokio::time::sleep(policy.delay()).await;
             }
         });
 
         Ok(rx)
     }
 }
+
+impl Kraken {
+    /// Kraken pair for a standard symbol. Symbol overrides win; otherwise the pair is
+    /// looked up in the instrument id cache (filled from `AssetPairs`), falling back to the
+    /// built-in format.
+    async fn resolve_pair(&self, symbol: &str) -> Result<String, MarketScannerError> {
+        let normalized = normalize_symbol(symbol);
+        if normalized.is_empty() {
+            return Err(MarketScannerError::InvalidSymbol(
+                "Symbol cannot be empty".to_string(),
+            ));
+        }
+        if let Some(overridden) = symbol_override(&normalized, &CexExchange::Kraken) {
+            return Ok(overridden);
+        }
+
+        if let Some(pair) = instrument_id(&CexExchange::Kraken, &normalized) {
+            return Ok(pair);
+        }
+
+        let stale = instrument_ids_refreshed_at(&CexExchange::Kraken)
+            .is_none_or(|at| get_timestamp_millis().saturating_sub(at) > KRAKEN_PAIR_CACHE_TTL_MS);
+        if stale {
+            // Unreachable AssetPairs falls back to the built-in format below
+            if let Ok(pairs) = self.get_instrument_ids().await {
+                record_instrument_ids(&CexExchange::Kraken, pairs);
+            }
+        }
+        format_symbol_for_exchange(symbol, &CexExchange::Kraken)
+    }
+
+    /// GET returning the JSON body, with Kraken's `error` array turned into an error.
+    async fn get_checked(&self, endpoint: &str) -> Result<serde_json::Value, MarketScannerError> {
+        check_kraken_errors(self.get(endpoint).await?)
+    }
+
+    /// Best bid/ask and their sizes from `Depth`, falling back to `Ticker` for pairs
+    /// whose depth is unavailable. Returns the `Depth` error if both fail.
+    async fn fetch_top_of_book(
+        &self,
+        kraken_pair: &str,
+        symbol: &str,
+    ) -> Result<(f64, f64, f64, f64), MarketScannerError> {
+        match self.fetch_depth_top(kraken_pair, symbol).await {
+            Ok(top) => Ok(top),
+            Err(depth_err) => self
+                .fetch_ticker_top(kraken_pair, symbol)
+                .await
+                .map_err(|_| depth_err),
+        }
+    }
+
+    async fn fetch_depth_top(
+        &self,
+        kraken_pair: &str,
+        symbol: &str,
+    ) -> Result<(f64, f64, f64, f64), MarketScannerError> {
+        // Using Depth endpoint with count=1 for best bid/ask only
+        let endpoint = format!("Depth?pair={}&count=1", kraken_pair);
+
+        // First get as JSON value to handle errors gracefully
+        let response = self.get_checked(&endpoint).await?;
+
+        // Deserialize response to KrakenDepthResponse
+        let depth_response: KrakenDepthResponse =
+            serde_json::from_value(response).map_err(|e| {
+                MarketScannerError::ApiError(format!(
+                    "Kraken API error: failed to parse depth response: {}",
+                    e
+                ))
+            })?;
+
+        // Get the first (and only) pair data from result
+        let pair_data = depth_response.result.values().next().ok_or_else(|| {
+            MarketScannerError::ApiError(format!(
+                "Kraken API error: no data found for symbol: {}",
+                symbol
+            ))
+        })?;
+
+        // Get best bid (first element in bids array: [price, quantity, timestamp])
+        let bid_entry = pair_data.bids.first().ok_or_else(|| {
+            MarketScannerError::ApiError(format!(
+                "Kraken API error: no bid found for symbol: {}",
+                symbol
+            ))
+        })?;
+
+        // Get best ask (first element in asks array: [price, quantity, timestamp])
+        let ask_entry = pair_data.asks.first().ok_or_else(|| {
+            MarketScannerError::ApiError(format!(
+                "Kraken API error: no ask found for symbol: {}",
+                symbol
+            ))
+        })?;
+
+        // Parse bid entry: [price, quantity, timestamp]
+        let bid_price_str = bid_entry[0].as_str().ok_or_else(|| {
+            MarketScannerError::ApiError(format!(
+                "Kraken API error: invalid bid price format for symbol: {}",
+                symbol
+            ))
+        })?;
+
+        let bid_qty_str = bid_entry[1].as_str().ok_or_else(|| {
+            MarketScannerError::ApiError(format!(
+                "Kraken API error: invalid bid quantity format for symbol: {}",
+                symbol
+            ))
+        })?;
+
+        // Parse ask entry: [price, quantity, timestamp]
+        let ask_price_str = ask_entry[0].as_str().ok_or_else(|| {
+            MarketScannerError::ApiError(format!(
+                "Kraken API error: invalid ask price format for symbol: {}",
+                symbol
+            ))
+        })?;
+
+        let ask_qty_str = ask_entry[1].as_str().ok_or_else(|| {
+            MarketScannerError::ApiError(format!(
+                "Kraken API error: invalid ask quantity format for symbol: {}",
+                symbol
+            ))
+        })?;
+
+        let bid = parse_f64(bid_price_str, "bid price")?;
+        let ask = parse_f64(ask_price_str, "ask price")?;
+        let bid_qty = parse_f64(bid_qty_str, "bid quantity")?;
+        let ask_qty = parse_f64(ask_qty_str, "ask quantity")?;
+
+        Ok((bid, ask, bid_qty, ask_qty))
+    }
+
+    /// Top of book from `Ticker`: `b` / `a` are `[price, whole lot volume, lot volume]`.
+    async fn fetch_ticker_top(
+        &self,
+        kraken_pair: &str,
+        symbol: &str,
+    ) -> Result<(f64, f64, f64, f64), MarketScannerError> {
+        let endpoint = format!("Ticker?pair={}", kraken_pair);
+        let response = self.get_checked(&endpoint).await?;
+        let ticker = response["result"]
+            .as_object()
+            .and_then(|pairs| pairs.values().next())
+            .ok_or_else(|| {
+                MarketScannerError::ApiError(format!(
+                    "Kraken API error: no ticker found for symbol: {}",
+                    symbol
+                ))
+            })?;
+        let level = |side: &str, idx: usize, field: &str| {
+            ticker[side][idx]
+                .as_str()
+                .ok_or_else(|| {
+                    MarketScannerError::ApiError(format!(
+                        "Kraken API error: invalid {} format for symbol: {}",
+                        field, symbol
+                    ))
+                })
+                .and_then(|v| parse_f64(v, field))
+        };
+        Ok((
+            level("b", 0, "bid price")?,
+            level("a", 0, "ask price")?,
+            level("b", 2, "bid quantity")?,
+            level("a", 2, "ask quantity")?,
+        ))
+    }
+
+    /// Trading status of `kraken_symbol` from `AssetPairs`.
+    async fn fetch_trading_status(
+        &self,
+        kraken_symbol: &str,
+    ) -> Result<TradingStatus, MarketScannerError> {
+        let endpoint = format!("AssetPairs?pair={}", kraken_symbol);
+        let response: serde_json::Value = self.get_cached(&endpoint).await?;
+        let status = response["result"]
+            .as_object()
+            .and_then(|pairs| pairs.values().next())
+            .and_then(|pair| pair["status"].as_str())
+            .ok_or_else(|| {
+                MarketScannerError::ApiError("Kraken AssetPairs missing pair status".to_string())
+            })?;
+        Ok(parse_kraken_trading_status(status))
+    }
+}
+
+/// Turns Kraken's `error` array into an error, returning the response otherwise.
+fn check_kraken_errors(
+    response: serde_json::Value,
+) -> Result<serde_json::Value, MarketScannerError> {
+    // Check if API returned errors
+    let errors = response["error"].as_array().ok_or_else(|| {
+        MarketScannerError::ApiError("Kraken API response missing error field".to_string())
+    })?;
+
+    if !errors.is_empty() {
+        let error_msg = errors
+            .iter()
+            .filter_map(|e| e.as_str())
+            .collect::<Vec<_>>()
+            .join(", ");
+        return Err(MarketScannerError::ApiError(format!(
+            "Kraken API error: {}",
+            error_msg
+        )));
+    }
+    Ok(response)
+}
+
+/// Builds the standard symbol -> Kraken pair map from an `AssetPairs` response.
+/// Standard symbols come from `wsname` with asset aliases resolved (`XDG/USDT` -> `DOGEUSDT`,
+/// `XBT/USD` -> `BTCUSD`); Kraken's own altnames (`XDGUSDT`) map to themselves.
+fn parse_kraken_asset_pairs(response: &serde_json::Value) -> HashMap<String, String> {
+    let mut pairs = HashMap::new();
+    let Some(result) = response["result"].as_object() else {
+        return pairs;
+    };
+    for pair in result.values() {
+        let (Some(altname), Some(wsname)) = (pair["altname"].as_str(), pair["wsname"].as_str())
+        else {
+            continue;
+        };
+        pairs.insert(normalize_symbol(altname), altname.to_string());
+        if let Some((base, quote)) = wsname.split_once('/') {
+            let standard = format!("{}{}", canonical_asset(base), canonical_asset(quote));
+            // Keep Kraken's own spelling if it collides with another pair's altname
+            pairs.entry(standard).or_insert_with(|| altname.to_string());
+        }
+    }
+    pairs
+}
+
+/// Maps a Kraken pair status (`online`, `cancel_only`, `post_only`, ...) to [TradingStatus].
+fn parse_kraken_trading_status(status: &str) -> TradingStatus {
+    match status {
+        "online" | "limit_only" => TradingStatus::Trading,
+        "post_only" => TradingStatus::PostOnly,
+        "cancel_only" => TradingStatus::CancelOnly,
+        "reduce_only" | "delisted" => TradingStatus::Halted,
+        _ => TradingStatus::Unknown,
+    }
+}
@@ -1,8 +1,13 @@
 mod types;
+use crate::common::capabilities::{Capabilities, VenueCapability};
+use crate::common::clock::json_server_time;
+use crate::common::reconnect::{ReconnectPolicy, ws_endpoint_override};
+use crate::common::symbol_registry;
 use crate::common::{
-    CEXTrait, CexExchange, CexPrice, Exchange, ExchangeTrait, MarketScannerError, find_mid_price,
+    CEXTrait, CexExchange, CexPrice, Exchange, ExchangeTrait, MarketScannerError, RequestPriority,
+    Timestamp, TradingStatus, WsSymbolFilter, decode_ws_json, find_mid_price,
     format_symbol_for_exchange, format_symbol_for_exchange_ws, get_timestamp_millis, parse_f64,
-    standard_symbol_for_cex_ws_response,
+    record_ws_connect, record_ws_latency, standard_symbol_for_cex_ws_response,
 };
 use crate::create_exchange;
 use async_trait::async_trait;
@@ -12,6 +17,8 @@ use tokio::sync::mpsc;
 use tokio_tungstenite::tungstenite::Message as WsMessage;
 
 const KUCOIN_API_BASE: &str = "https://api.kucoin.com/api/v1";
+/// Heartbeat interval of overridden endpoints, which skip the `bullet-public` request
+const KUCOIN_DEFAULT_PING_INTERVAL_MS: u64 = 18_000;
 
 create_exchange!(Kucoin);
 
@@ -26,18 +33,25 @@ impl ExchangeTrait for Kucoin {
     }
 
     fn exchange_name(&self) -> &str {
-        "KuCoin"
+        CexExchange::Kucoin.rest_venue_name()
     }
 
     async fn health_check(&self) -> Result<(), MarketScannerError> {
         // KuCoin timestamp endpoint - test connectivity to the REST API
         let endpoint = "timestamp";
-        self.get::<serde_json::Value>(endpoint)
+        self.get_with_priority::<serde_json::Value>(endpoint, RequestPriority::Metadata)
             .await
             .map_err(|_| MarketScannerError::HealthCheckFailed)?;
 
         Ok(())
     }
+
+    async fn get_server_time(&self) -> Result<Timestamp, MarketScannerError> {
+        let response: serde_json::Value = self
+            .get_with_priority("timestamp", RequestPriority::Metadata)
+            .await?;
+        json_server_time(&response, "/data", self.exchange_name())
+    }
 }
 
 #[async_trait]
@@ -46,6 +60,10 @@ impl CEXTrait for Kucoin {
         true
     }
 
+    fn capabilities(&self) -> Capabilities {
+        Capabilities::new(&[VenueCapability::Websocket, VenueCapability::ServerTime])
+    }
+
     async fn get_price(&self, symbol: &str) -> Result<CexPrice, MarketScannerError> {
         // Validate symbol is not empty
         if symbol.is_empty() {
@@ -109,8 +127,9 @@ impl CEXTrait for Kucoin {
             ask_price: ask,
             bid_qty,
             ask_qty,
-            timestamp: get_timestamp_millis(),
+            timestamp: Timestamp::now(),
             exchange: Exchange::Cex(CexExchange::Kucoin),
+            trading_status: TradingStatus::Unknown,
         })
     }
 
@@ -134,84 +153,79 @@ impl CEXTrait for Kucoin {
 
         let client = self.client.clone();
         let (tx, rx) = mpsc::channel(64);
-        let delay =
-            std::time::Duration::from_millis(if reconnect_delay_ms == 0 { 1000 } else { reconnect_delay_ms });
+        let symbol_filter = WsSymbolFilter::new(&CexExchange::Kucoin, symbols);
+        let policy = ReconnectPolicy::new(reconnect_attempts, reconnect_delay_ms);
+        let endpoint_override = ws_endpoint_override(&CexExchange::Kucoin);
 
-        tokio::spawn(async move {
+        symbol_registry::spawn(async move {
             let mut attempt = 0u32;
             loop {
                 attempt += 1;
-                // 1) Get WS endpoint via bullet-public (POST)
-                let bullet_url = format!("{}/bullet-public", KUCOIN_API_BASE);
-                let bullet_resp = client.post(&bullet_url).send().await;
-                let bullet = match bullet_resp {
-                    Ok(r) => match r.json::<KucoinBulletPublicResponse>().await {
-                        Ok(b) => b,
-                        Err(_) => {
-                            if tx.is_closed()
-                                || reconnect_attempts == 0
-                                || attempt > reconnect_attempts
-                            {
+                let connect_id = get_timestamp_millis();
+                // 1) Get WS endpoint via bullet-public (POST), unless overridden
+                let (ws_url, ping_interval_ms) = match &endpoint_override {
+                    Some(url) => (url.clone(), KUCOIN_DEFAULT_PING_INTERVAL_MS),
+                    None => {
+                        let bullet_url = format!("{}/bullet-public", KUCOIN_API_BASE);
+                        let bullet_resp = client.post(&bullet_url).send().await;
+                        let bullet = match bullet_resp {
+                            Ok(r) => match r.json::<KucoinBulletPublicResponse>().await {
+                                Ok(b) => b,
+                                Err(_) => {
+                                    if tx.is_closed() || !policy.should_reconnect(attempt) {
+                                        break;
+                                    }
+                                    tokio::time::sleep(policy.delay()).await;
+                                    continue;
+                                }
+                            },
+                            Err(_) => {
+                                if tx.is_closed() || !policy.should_reconnect(attempt) {
+                                    break;
+                                }
+                                tokio::time::sleep(policy.delay()).await;
+                                continue;
+                            }
+                        };
+
+                        if bullet.code != "200000" {
+                            if tx.is_closed() || !policy.should_reconnect(attempt) {
                                 break;
                             }
-                            tokio::time::sleep(delay).await;
+                            tokio::time::sleep(policy.delay()).await;
                             continue;
                         }
-                    },
-                    Err(_) => {
-                        if tx.is_closed()
-                            || reconnect_attempts == 0
-                            || attempt > reconnect_attempts
-                        {
-                            break;
-                        }
-                        tokio::time::sleep(delay).await;
-                        continue;
-                    }
-                };
 
-                if bullet.code != "200000" {
-                    if tx.is_closed()
-                        || reconnect_attempts == 0
-                        || attempt > reconnect_attempts
-                    {
-                        break;
-                    }
-                    tokio::time::sleep(delay).await;
-                    continue;
-                }
+                        let server = match bullet.data.instance_servers.first() {
+                            Some(s) => s,
+                            None => {
+                                if tx.is_closed() || !policy.should_reconnect(attempt) {
+                                    break;
+                                }
+                                tokio::time::sleep(policy.delay()).await;
+                                continue;
+                            }
+                        };
 
-                let server = match bullet.data.instance_servers.first() {
-                    Some(s) => s,
-                    None => {
-                        if tx.is_closed()
-                            || reconnect_attempts == 0
-                            || attempt > reconnect_attempts
-                        {
-                            break;
-                        }
-                        tokio::time::sleep(delay).await;
-                        continue;
+                        let url = format!(
+                            "{}?token={}&connectId={}",
+                            server.endpoint, bullet.data.token, connect_id
+                        );
+                        (url, server.ping_interval)
                     }
                 };
 
-                let connect_id = get_timestamp_millis();
-                let ws_url = format!(
-                    "{}?token={}&connectId={}",
-                    server.endpoint, bullet.data.token, connect_id
-                );
-
                 // 2) Connect
                 let (ws_stream, _) = match tokio_tungstenite::connect_async(&ws_url).await {
-                    Ok(v) => v,
+                    Ok(v) => {
+                        record_ws_connect(&CexExchange::Kucoin);
+                        v
+                    }
                     Err(_) => {
-                        if tx.is_closed()
-                            || reconnect_attempts == 0
-                            || attempt > reconnect_attempts
-                        {
+                        if tx.is_closed() || !policy.should_reconnect(attempt) {
                             break;
                         }
-                        tokio::time::sleep(delay).await;
+                        tokio::time::sleep(policy.delay()).await;
                         continue;
                     }
                 };
@@ -237,9 +251,10 @@ impl CEXTrait for Kucoin {
                 }
 
                 // 4) Read loop + heartbeat
-                let ping_every = std::time::Duration::from_millis(server.ping_interval.max(5000));
+                let ping_every = std::time::Duration::from_millis(ping_interval_ms.max(5000));
                 let mut ping_interval = tokio::time::interval(ping_every);
                 ping_interval.tick().await;
+                let mut ping_sent_at: Option<std::time::Instant> = None;
 
                 loop {
                     tokio::select! {
@@ -247,6 +262,7 @@ impl CEXTrait for Kucoin {
                             if write.send(WsMessage::Ping(Vec::new())).await.is_err() {
                                 break;
                             }
+                            ping_sent_at = Some(std::time::Instant::now());
                         }
                         msg = read.next() => {
                             let msg = match msg {
@@ -258,12 +274,17 @@ impl CEXTrait for Kucoin {
                                 WsMessage::Ping(payload) => {
                                     let _ = write.send(WsMessage::Pong(payload)).await;
                                 }
-                                WsMessage::Pong(_) => {}
+                                WsMessage::Pong(_) => {
+                                    if let Some(sent) = ping_sent_at.take() {
+                                        record_ws_latency(&CexExchange::Kucoin, sent.elapsed());
+                                    }
+                                }
                                 WsMessage::Text(t) => {
-                                    let v: serde_json::Value = match serde_json::from_str(&t) {
-                                        Ok(v) => v,
-                                        Err(_) => continue,
-                                    };
+                                    let v: serde_json::Value =
+                                        match decode_ws_json(&CexExchange::Kucoin, &t) {
+                                            Some(v) => v,
+                                            None => continue,
+                                        };
 
                                     // Server ping in JSON form: {"id":"...","type":"ping"}
                                     if v.get("type").and_then(|x| x.as_str()) == Some("ping") {
@@ -284,6 +305,9 @@ impl CEXTrait for Kucoin {
                                     }
 
                                     if let Some(price) = parse_kucoin_level1(&v) {
+                                        if !symbol_filter.allows(&price) {
+                                            continue;
+                                        }
                                         if tx.send(price).await.is_err() {
                                             return;
                                         }
@@ -296,13 +320,10 @@ impl CEXTrait for Kucoin {
                     }
                 }
 
-                if tx.is_closed()
-                    || reconnect_attempts == 0
-                    || attempt > reconnect_attempts
-                {
+                if tx.is_closed() || !policy.should_reconnect(attempt) {
                     break;
                 }
-                tokio::time::sleep(delay).await;
+                tokio::time::sleep(policy.delay()).await;
             }
         });
 
@@ -367,7 +388,8 @@ fn parse_kucoin_level1(v: &serde_json::Value) -> Option<CexPrice> {
         ask_price: ask,
         bid_qty,
         ask_qty,
-        timestamp: get_timestamp_millis(),
+        timestamp: Timestamp::now(),
         exchange: Exchange::Cex(CexExchange::Kucoin),
+        trading_status: TradingStatus::Unknown,
     })
 }
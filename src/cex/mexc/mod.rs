@@ -1,9 +1,16 @@
 mod types;
 
+use crate::common::capabilities::{Capabilities, VenueCapability};
+use crate::common::clock::json_server_time;
+use crate::common::reconnect::{ReconnectPolicy, ws_endpoint};
+use crate::common::symbol_registry;
+use crate::common::ws_capture::capture_ws_binary;
 use crate::common::{
-    CEXTrait, CexExchange, CexPrice, Exchange, ExchangeTrait, MarketScannerError, find_mid_price,
-    format_symbol_for_exchange, format_symbol_for_exchange_ws, get_timestamp_millis,
-    normalize_symbol, parse_f64, standard_symbol_for_cex_ws_response,
+    CEXTrait, CexExchange, CexPrice, Exchange, ExchangeTrait, MarketScannerError, RequestPriority,
+    Timestamp, TradingStatus, WsSymbolFilter, decode_ws_json, find_mid_price,
+    format_symbol_for_exchange, format_symbol_for_exchange_ws, mark_ws_frame_received,
+    normalize_symbol, parse_f64, record_ws_connect, record_ws_decode, record_ws_latency,
+    standard_symbol_for_cex_ws_response,
 };
 use crate::create_exchange;
 use async_trait::async_trait;
@@ -29,18 +36,25 @@ impl ExchangeTrait for Mexc {
     }
 
     fn exchange_name(&self) -> &str {
-        "Mexc"
+        CexExchange::MEXC.rest_venue_name()
     }
 
     async fn health_check(&self) -> Result<(), MarketScannerError> {
         // MEXC ping endpoint - test connectivity to the REST API
         let endpoint = "ping";
-        self.get::<serde_json::Value>(endpoint)
+        self.get_with_priority::<serde_json::Value>(endpoint, RequestPriority::Metadata)
             .await
             .map_err(|_| MarketScannerError::HealthCheckFailed)?;
 
         Ok(())
     }
+
+    async fn get_server_time(&self) -> Result<Timestamp, MarketScannerError> {
+        let response: serde_json::Value = self
+            .get_with_priority("time", RequestPriority::Metadata)
+            .await?;
+        json_server_time(&response, "/serverTime", self.exchange_name())
+    }
 }
 
 #[async_trait]
@@ -49,6 +63,10 @@ impl CEXTrait for Mexc {
         true
     }
 
+    fn capabilities(&self) -> Capabilities {
+        Capabilities::new(&[VenueCapability::Websocket, VenueCapability::ServerTime])
+    }
+
     async fn get_price(&self, symbol: &str) -> Result<CexPrice, MarketScannerError> {
         // Validate symbol is not empty
         if symbol.is_empty() {
@@ -79,8 +97,9 @@ impl CEXTrait for Mexc {
             ask_price: ask,
             bid_qty,
             ask_qty,
-            timestamp: get_timestamp_millis(),
+            timestamp: Timestamp::now(),
             exchange: Exchange::Cex(CexExchange::MEXC),
+            trading_status: TradingStatus::Unknown,
         })
     }
 
@@ -111,24 +130,24 @@ impl CEXTrait for Mexc {
             "params": params
         });
         let (tx, rx) = mpsc::channel(64);
-        let delay = std::time::Duration::from_millis(if reconnect_delay_ms == 0 {
-            1000
-        } else {
-            reconnect_delay_ms
-        });
+        let symbol_filter = WsSymbolFilter::new(&CexExchange::MEXC, symbols);
+        let policy = ReconnectPolicy::new(reconnect_attempts, reconnect_delay_ms);
+        let url = ws_endpoint(&CexExchange::MEXC, MEXC_WS_URL);
 
-        tokio::spawn(async move {
+        symbol_registry::spawn(async move {
             let mut attempt = 0u32;
             loop {
                 attempt += 1;
-                let (mut ws_stream, _) = match tokio_tungstenite::connect_async(MEXC_WS_URL).await {
-                    Ok(v) => v,
+                let (mut ws_stream, _) = match tokio_tungstenite::connect_async(&url).await {
+                    Ok(v) => {
+                        record_ws_connect(&CexExchange::MEXC);
+                        v
+                    }
                     Err(_) => {
-                        if tx.is_closed() || reconnect_attempts == 0 || attempt > reconnect_attempts
-                        {
+                        if tx.is_closed() || !policy.should_reconnect(attempt) {
                             break;
                         }
-                        tokio::time::sleep(delay).await;
+                        tokio::time::sleep(policy.delay()).await;
                         continue;
                     }
                 };
@@ -138,10 +157,10 @@ impl CEXTrait for Mexc {
                     .await
                     .is_err()
                 {
-                    if tx.is_closed() || reconnect_attempts == 0 || attempt > reconnect_attempts {
+                    if tx.is_closed() || !policy.should_reconnect(attempt) {
                         break;
                     }
-                    tokio::time::sleep(delay).await;
+                    tokio::time::sleep(policy.delay()).await;
                     continue;
                 }
 
@@ -149,6 +168,7 @@ impl CEXTrait for Mexc {
 
                 let mut ping_interval = tokio::time::interval(std::time::Duration::from_secs(15));
                 ping_interval.tick().await;
+                let mut ping_sent_at: Option<std::time::Instant> = None;
 
                 loop {
                     tokio::select! {
@@ -157,6 +177,7 @@ impl CEXTrait for Mexc {
                             if write.send(WsMessage::Text(ping.to_string())).await.is_err() {
                                 break;
                             }
+                            ping_sent_at = Some(std::time::Instant::now());
                         }
                         msg = read.next() => {
                             let msg = match msg {
@@ -166,8 +187,13 @@ impl CEXTrait for Mexc {
                             match msg {
                                 WsMessage::Text(t) => {
                                     // JSON: subscribe ack, PONG, error
-                                    if let Ok(v) = serde_json::from_str::<serde_json::Value>(&t) {
+                                    if let Some(v) =
+                                        decode_ws_json::<serde_json::Value>(&CexExchange::MEXC, &t)
+                                    {
                                         if v.get("msg").and_then(|m| m.as_str()) == Some("PONG") {
+                                            if let Some(sent) = ping_sent_at.take() {
+                                                record_ws_latency(&CexExchange::MEXC, sent.elapsed());
+                                            }
                                             continue;
                                         }
                                         if v.get("code").is_some() || v.get("msg").is_some() {
@@ -177,6 +203,9 @@ impl CEXTrait for Mexc {
                                 }
                                 WsMessage::Binary(b) => {
                                     if let Some(price) = parse_mexc_protobuf(&b) {
+                                        if !symbol_filter.allows(&price) {
+                                            continue;
+                                        }
                                         if tx.send(price).await.is_err() {
                                             return;
                                         }
@@ -188,10 +217,10 @@ impl CEXTrait for Mexc {
                     }
                 }
 
-                if tx.is_closed() || reconnect_attempts == 0 || attempt > reconnect_attempts {
+                if tx.is_closed() || !policy.should_reconnect(attempt) {
                     break;
                 }
-                tokio::time::sleep(delay).await;
+                tokio::time::sleep(policy.delay()).await;
             }
         });
 
@@ -200,7 +229,11 @@ impl CEXTrait for Mexc {
 }
 
 fn parse_mexc_protobuf(bytes: &[u8]) -> Option<CexPrice> {
-    let wrapper = MexcPushDataWrapper::decode(prost::bytes::Bytes::copy_from_slice(bytes)).ok()?;
+    capture_ws_binary(&CexExchange::MEXC, bytes);
+    mark_ws_frame_received();
+    let wrapper = MexcPushDataWrapper::decode(prost::bytes::Bytes::copy_from_slice(bytes));
+    record_ws_decode(&CexExchange::MEXC, wrapper.is_ok());
+    let wrapper = wrapper.ok()?;
     let body = wrapper.body?;
     let ticker = match body {
         MexcPushBody::PublicAggreBookTicker(t) => t,
@@ -231,7 +264,8 @@ fn parse_mexc_protobuf(bytes: &[u8]) -> Option<CexPrice> {
         ask_price: ask,
         bid_qty: parse_f64(&ticker.bid_quantity, "bid_qty").unwrap_or(0.0),
         ask_qty: parse_f64(&ticker.ask_quantity, "ask_qty").unwrap_or(0.0),
-        timestamp: get_timestamp_millis(),
+        timestamp: Timestamp::now(),
         exchange: Exchange::Cex(CexExchange::MEXC),
+        trading_status: TradingStatus::Unknown,
     })
 }
@@ -5,6 +5,7 @@ pub mod btcturk;
 pub mod bybit;
 pub mod coinbase;
 pub mod cryptocom;
+pub mod execution;
 pub mod gateio;
 pub mod htx;
 pub mod kraken;
@@ -1,10 +1,20 @@
+mod trade;
 mod types;
+mod user_data;
 
-use crate::cex::okx::types::OkxTickerResponse;
+pub use trade::OKX_SIMULATED_TRADING_HEADER;
+
+use crate::cex::okx::types::{OkxBookData, OkxBookWsMessage, OkxTickerResponse};
+use crate::common::capabilities::{Capabilities, VenueCapability};
+use crate::common::clock::json_server_time;
+use crate::common::reconnect::{ReconnectPolicy, ws_endpoint};
+use crate::common::symbol_registry;
 use crate::common::{
-    CEXTrait, CexExchange, CexPrice, Exchange, ExchangeTrait, MarketScannerError, find_mid_price,
-    format_symbol_for_exchange, format_symbol_for_exchange_ws, get_timestamp_millis, parse_f64,
-    standard_symbol_for_cex_ws_response,
+    CEXTrait, CexExchange, CexPrice, DepthDelta, DepthSnapshot, DepthSyncStatus, Exchange,
+    ExchangeTrait, MarketScannerError, OrderBook, OrderBookLevel, OrderBookSynchronizer,
+    RequestPriority, Timestamp, TradingStatus, WsSymbolFilter, decode_ws_json, decode_ws_value,
+    find_mid_price, format_symbol_for_exchange, format_symbol_for_exchange_ws, normalize_symbol,
+    parse_f64, record_ws_connect, record_ws_latency, standard_symbol_for_cex_ws_response,
 };
 use crate::create_exchange;
 use async_trait::async_trait;
@@ -15,6 +25,9 @@ use tokio_tungstenite::tungstenite::Message as WsMessage;
 const OKX_API_BASE: &str = "https://www.okx.com/api/v5";
 const OKX_WS_URL: &str = "wss://ws.okx.com:8443/ws/v5/public";
 
+/// Levels of the `books5` channel; deeper books come from `books` (400 levels).
+const OKX_BOOKS5_DEPTH: usize = 5;
+
 create_exchange!(OKX);
 
 #[async_trait]
@@ -28,13 +41,15 @@ impl ExchangeTrait for OKX {
     }
 
     fn exchange_name(&self) -> &str {
-        "OKX"
+        CexExchange::OKX.rest_venue_name()
     }
 
     async fn health_check(&self) -> Result<(), MarketScannerError> {
         // OKX public/time endpoint - returns server time
         let endpoint = "public/time";
-        let response: serde_json::Value = self.get(endpoint).await?;
+        let response: serde_json::Value = self
+            .get_with_priority(endpoint, RequestPriority::Metadata)
+            .await?;
 
         // OKX returns {"code":"0", "data":[...], "msg":""} for success
         if let Some(code) = response["code"].as_str() {
@@ -51,6 +66,13 @@ impl ExchangeTrait for OKX {
             Err(MarketScannerError::HealthCheckFailed)
         }
     }
+
+    async fn get_server_time(&self) -> Result<Timestamp, MarketScannerError> {
+        let response: serde_json::Value = self
+            .get_with_priority("public/time", RequestPriority::Metadata)
+            .await?;
+        json_server_time(&response, "/data/0/ts", self.exchange_name())
+    }
 }
 
 #[async_trait]
@@ -59,6 +81,16 @@ impl CEXTrait for OKX {
         true
     }
 
+    fn capabilities(&self) -> Capabilities {
+        Capabilities::new(&[
+            VenueCapability::Websocket,
+            VenueCapability::OrderBookStream,
+            VenueCapability::Execution,
+            VenueCapability::UserData,
+            VenueCapability::ServerTime,
+        ])
+    }
+
     async fn get_price(&self, symbol: &str) -> Result<CexPrice, MarketScannerError> {
         // Validate symbol is not empty
         if symbol.is_empty() {
@@ -101,8 +133,9 @@ impl CEXTrait for OKX {
             ask_price: ask,
             bid_qty,
             ask_qty,
-            timestamp: get_timestamp_millis(),
+            timestamp: Timestamp::now(),
             exchange: Exchange::Cex(CexExchange::OKX),
+            trading_status: TradingStatus::Unknown,
         })
     }
 
@@ -132,24 +165,24 @@ impl CEXTrait for OKX {
         let subscribe_msg = serde_json::json!({ "op": "subscribe", "args": args });
 
         let (tx, rx) = mpsc::channel(64);
-        let delay = std::time::Duration::from_millis(if reconnect_delay_ms == 0 {
-            1000
-        } else {
-            reconnect_delay_ms
-        });
+        let symbol_filter = WsSymbolFilter::new(&CexExchange::OKX, symbols);
+        let policy = ReconnectPolicy::new(reconnect_attempts, reconnect_delay_ms);
+        let url = ws_endpoint(&CexExchange::OKX, OKX_WS_URL);
 
-        tokio::spawn(async move {
+        symbol_registry::spawn(async move {
             let mut attempt = 0u32;
             loop {
                 attempt += 1;
-                let (ws_stream, _) = match tokio_tungstenite::connect_async(OKX_WS_URL).await {
-                    Ok(v) => v,
+                let (ws_stream, _) = match tokio_tungstenite::connect_async(&url).await {
+                    Ok(v) => {
+                        record_ws_connect(&CexExchange::OKX);
+                        v
+                    }
                     Err(_) => {
-                        if tx.is_closed() || reconnect_attempts == 0 || attempt > reconnect_attempts
-                        {
+                        if tx.is_closed() || !policy.should_reconnect(attempt) {
                             break;
                         }
-                        tokio::time::sleep(delay).await;
+                        tokio::time::sleep(policy.delay()).await;
                         continue;
                     }
                 };
@@ -161,15 +194,16 @@ impl CEXTrait for OKX {
                     .await
                     .is_err()
                 {
-                    if tx.is_closed() || reconnect_attempts == 0 || attempt > reconnect_attempts {
+                    if tx.is_closed() || !policy.should_reconnect(attempt) {
                         break;
                     }
-                    tokio::time::sleep(delay).await;
+                    tokio::time::sleep(policy.delay()).await;
                     continue;
                 }
 
                 let mut ping_interval = tokio::time::interval(std::time::Duration::from_secs(20));
                 ping_interval.tick().await;
+                let mut ping_sent_at: Option<std::time::Instant> = None;
 
                 loop {
                     tokio::select! {
@@ -178,6 +212,7 @@ impl CEXTrait for OKX {
                             if write.send(WsMessage::Ping(Vec::new())).await.is_err() {
                                 break;
                             }
+                            ping_sent_at = Some(std::time::Instant::now());
                         }
                         msg = read.next() => {
                             let msg = match msg {
@@ -189,7 +224,11 @@ impl CEXTrait for OKX {
                                 WsMessage::Ping(payload) => {
                                     let _ = write.send(WsMessage::Pong(payload)).await;
                                 }
-                                WsMessage::Pong(_) => {}
+                                WsMessage::Pong(_) => {
+                                    if let Some(sent) = ping_sent_at.take() {
+                                        record_ws_latency(&CexExchange::OKX, sent.elapsed());
+                                    }
+                                }
                                 WsMessage::Text(t) => {
                                     // OKX may also send raw "pong"
                                     if t == "pong" || t == "ping" {
@@ -199,10 +238,11 @@ impl CEXTrait for OKX {
                                         continue;
                                     }
 
-                                    let v: serde_json::Value = match serde_json::from_str(&t) {
-                                        Ok(v) => v,
-                                        Err(_) => continue,
-                                    };
+                                    let v: serde_json::Value =
+                                        match decode_ws_json(&CexExchange::OKX, &t) {
+                                            Some(v) => v,
+                                            None => continue,
+                                        };
 
                                     // events: {"event":"subscribe",...} / {"event":"error",...}
                                     if v.get("event").and_then(|e| e.as_str()).is_some() {
@@ -221,6 +261,9 @@ impl CEXTrait for OKX {
 
                                     for item in data {
                                         if let Some(price) = parse_okx_books5(item, arg_inst) {
+                                            if !symbol_filter.allows(&price) {
+                                                continue;
+                                            }
                                             if tx.send(price).await.is_err() {
                                                 return;
                                             }
@@ -235,15 +278,173 @@ impl CEXTrait for OKX {
                     }
                 }
 
-                if tx.is_closed() || reconnect_attempts == 0 || attempt > reconnect_attempts {
+                if tx.is_closed() || !policy.should_reconnect(attempt) {
                     break;
                 }
-                tokio::time::sleep(delay).await;
+                tokio::time::sleep(policy.delay()).await;
             }
         });
 
         Ok(rx)
     }
+
+    /// Streams `books5` (a 5-level snapshot per push) when `depth` is at most 5, else
+    /// `books`: a 400-level snapshot followed by incremental updates chained by
+    /// `prevSeqId`. A sequence gap reconnects for a fresh snapshot.
+    async fn stream_order_book(
+        &self,
+        symbol: &str,
+        depth: usize,
+        reconnect_attempts: u32,
+        reconnect_delay_ms: u64,
+    ) -> Result<mpsc::Receiver<OrderBook>, MarketScannerError> {
+        if symbol.is_empty() {
+            return Err(MarketScannerError::InvalidSymbol(
+                "Symbol cannot be empty".to_string(),
+            ));
+        }
+
+        let inst_id = format_symbol_for_exchange_ws(symbol, &CexExchange::OKX)?;
+        let channel = if depth <= OKX_BOOKS5_DEPTH {
+            "books5"
+        } else {
+            "books"
+        };
+        let subscribe_msg = serde_json::json!({
+            "op": "subscribe",
+            "args": [{"channel": channel, "instId": inst_id}]
+        });
+        let standard_symbol = normalize_symbol(symbol);
+        let (tx, rx) = mpsc::channel(64);
+
+        let policy = ReconnectPolicy::new(reconnect_attempts, reconnect_delay_ms);
+        let url = ws_endpoint(&CexExchange::OKX, OKX_WS_URL);
+        symbol_registry::spawn(async move {
+            let mut attempt = 0u32;
+            loop {
+                attempt += 1;
+                let (ws_stream, _) = match tokio_tungstenite::connect_async(&url).await {
+                    Ok(v) => v,
+                    Err(_) => {
+                        if tx.is_closed() || !policy.should_reconnect(attempt) {
+                            break;
+                        }
+                        tokio::time::sleep(policy.delay()).await;
+                        continue;
+                    }
+                };
+
+                let (mut write, mut read) = ws_stream.split();
+                if write
+                    .send(WsMessage::Text(subscribe_msg.to_string()))
+                    .await
+                    .is_err()
+                {
+                    if tx.is_closed() || !policy.should_reconnect(attempt) {
+                        break;
+                    }
+                    tokio::time::sleep(policy.delay()).await;
+                    continue;
+                }
+
+                let mut sync =
+                    OrderBookSynchronizer::new(&standard_symbol, Exchange::Cex(CexExchange::OKX));
+                let mut ping_interval = tokio::time::interval(std::time::Duration::from_secs(20));
+                ping_interval.tick().await;
+
+                'connection: loop {
+                    let text = tokio::select! {
+                        _ = ping_interval.tick() => {
+                            if write.send(WsMessage::Ping(Vec::new())).await.is_err() {
+                                break;
+                            }
+                            continue;
+                        }
+                        msg = read.next() => match msg {
+                            Some(Ok(WsMessage::Text(t))) => t,
+                            Some(Ok(WsMessage::Ping(payload))) => {
+                                let _ = write.send(WsMessage::Pong(payload)).await;
+                                continue;
+                            }
+                            Some(Ok(WsMessage::Close(_))) | Some(Err(_)) | None => break,
+                            Some(Ok(_)) => continue,
+                        },
+                    };
+                    if text == "pong" {
+                        continue;
+                    }
+                    let value: serde_json::Value = match decode_ws_json(&CexExchange::OKX, &text) {
+                        Some(v) => v,
+                        None => continue,
+                    };
+                    // Subscribe acks and errors carry no book
+                    if value.get("event").is_some() {
+                        continue;
+                    }
+                    let message: OkxBookWsMessage = match decode_ws_value(&CexExchange::OKX, value)
+                    {
+                        Some(m) => m,
+                        None => continue,
+                    };
+
+                    for item in &message.data {
+                        let Some((bids, asks)) = parse_okx_book_levels(item) else {
+                            continue;
+                        };
+                        let seq_id = item.seq_id.unwrap_or(0).max(0) as u64;
+                        let status = if message.action.as_deref() == Some("update") {
+                            let prev_seq_id = item.prev_seq_id.unwrap_or(0).max(0) as u64;
+                            sync.push_delta(DepthDelta {
+                                first_update_id: prev_seq_id + 1,
+                                final_update_id: seq_id,
+                                bids,
+                                asks,
+                            })
+                        } else {
+                            sync.apply_snapshot(DepthSnapshot {
+                                last_update_id: seq_id,
+                                bids,
+                                asks,
+                            })
+                        };
+                        match status {
+                            DepthSyncStatus::Applied => {
+                                if let Some(book) = sync.book(depth) {
+                                    if tx.send(book).await.is_err() {
+                                        return;
+                                    }
+                                }
+                            }
+                            DepthSyncStatus::Gap => break 'connection,
+                            DepthSyncStatus::Buffered | DepthSyncStatus::Stale => {}
+                        }
+                    }
+                }
+
+                if tx.is_closed() || !policy.should_reconnect(attempt) {
+                    break;
+                }
+                tokio::time::sleep(policy.delay()).await;
+            }
+        });
+
+        Ok(rx)
+    }
+}
+
+/// Bids and asks of an OKX book push; `None` if a level does not parse.
+fn parse_okx_book_levels(item: &OkxBookData) -> Option<(Vec<OrderBookLevel>, Vec<OrderBookLevel>)> {
+    let levels = |side: &[Vec<String>]| -> Option<Vec<OrderBookLevel>> {
+        side.iter()
+            .map(|level| {
+                Some(OrderBookLevel {
+                    price: parse_f64(level.first()?, "depth price").ok()?,
+                    quantity: parse_f64(level.get(1)?, "depth quantity").ok()?,
+                })
+            })
+            .collect()
+    };
+    Some((levels(&item.bids)?, levels(&item.asks)?))
 }
 
 fn json_to_f64(v: &serde_json::Value) -> Option<f64> {
@@ -288,7 +489,8 @@ fn parse_okx_books5(item: &serde_json::Value, arg_inst: Option<&str>) -> Option<
         ask_price: ask,
         bid_qty,
         ask_qty,
-        timestamp: get_timestamp_millis(),
+        timestamp: Timestamp::now(),
         exchange: Exchange::Cex(CexExchange::OKX),
+        trading_status: TradingStatus::Unknown,
     })
 }
@@ -0,0 +1,217 @@
+//! Spot orders on OKX (`trade/order`), live or simulated trading.
+
+use super::user_data::order_update;
+use super::{OKX, OKX_API_BASE};
+use crate::cex::execution::{
+    CexOrder, CexOrderAck, CexOrderExecution, OrderType, OrderUpdate, SignedOrderRequest,
+    TradingMode, format_amount, hmac_sha256_base64, required_passphrase,
+};
+use crate::common::{
+    CexExchange, Credentials, MarketScannerError, Timestamp, format_symbol_for_exchange,
+};
+use crate::portfolio::TradeSide;
+use chrono::{TimeZone, Utc};
+use reqwest::Method;
+
+const ORDER_PATH: &str = "/api/v5/trade/order";
+const CANCEL_PATH: &str = "/api/v5/trade/cancel-order";
+/// Header routing requests to OKX simulated trading
+pub const OKX_SIMULATED_TRADING_HEADER: &str = "x-simulated-trading";
+
+/// Request to `path` (with query string) signed over `timestamp + method + path + body`.
+fn signed_request(
+    credentials: &Credentials,
+    method: Method,
+    path: &str,
+    body: String,
+    mode: TradingMode,
+    timestamp_ms: u64,
+) -> Result<SignedOrderRequest, MarketScannerError> {
+    let passphrase = required_passphrase(credentials, "OKX")?;
+    let timestamp = Utc
+        .timestamp_millis_opt(timestamp_ms as i64)
+        .single()
+        .unwrap_or_default()
+        .format("%Y-%m-%dT%H:%M:%S%.3fZ")
+        .to_string();
+    let signature = hmac_sha256_base64(
+        credentials.api_secret(),
+        &format!("{}{}{}{}", timestamp, method, path, body),
+    );
+
+    let mut headers = vec![
+        (
+            "OK-ACCESS-KEY".to_string(),
+            credentials.api_key().to_string(),
+        ),
+        ("OK-ACCESS-SIGN".to_string(), signature),
+        ("OK-ACCESS-TIMESTAMP".to_string(), timestamp),
+        ("OK-ACCESS-PASSPHRASE".to_string(), passphrase.to_string()),
+    ];
+    if mode == TradingMode::Demo {
+        headers.push((OKX_SIMULATED_TRADING_HEADER.to_string(), "1".to_string()));
+    }
+    Ok(SignedOrderRequest {
+        method,
+        // Paths carry the `/api/v5` prefix of the API base
+        url: format!("{}{}", OKX_API_BASE, path.trim_start_matches("/api/v5")),
+        headers,
+        body,
+    })
+}
+
+/// First `data` entry of a successful response.
+fn response_data<'a>(
+    response: &'a serde_json::Value,
+    action: &str,
+) -> Result<&'a serde_json::Value, MarketScannerError> {
+    let data = &response["data"][0];
+    let code = response["code"].as_str().unwrap_or_default();
+    let item_code = data["sCode"].as_str().unwrap_or("0");
+    if code != "0" || item_code != "0" {
+        return Err(MarketScannerError::ApiError(format!(
+            "OKX {} rejected: {} {} {}",
+            action,
+            code,
+            response["msg"].as_str().unwrap_or_default(),
+            data["sMsg"].as_str().unwrap_or_default()
+        )));
+    }
+    Ok(data)
+}
+
+impl CexOrderExecution for OKX {
+    fn cex_exchange(&self) -> CexExchange {
+        CexExchange::OKX
+    }
+
+    fn order_request(
+        &self,
+        credentials: &Credentials,
+        order: &CexOrder,
+        mode: TradingMode,
+        timestamp_ms: u64,
+    ) -> Result<SignedOrderRequest, MarketScannerError> {
+        let order = &order.rounded()?;
+        let price = order.checked_price()?;
+
+        let mut body = serde_json::json!({
+            "instId": format_symbol_for_exchange(&order.symbol, &CexExchange::OKX)?,
+            "tdMode": "cash",
+            "side": match order.side {
+                TradeSide::Buy => "buy",
+                TradeSide::Sell => "sell",
+            },
+            "ordType": match order.order_type {
+                OrderType::Market => "market",
+                OrderType::Limit => "limit",
+            },
+            "sz": format_amount(order.quantity, order.quantity_step),
+        });
+        match price {
+            Some(price) => body["px"] = format_amount(price, order.price_tick).into(),
+            // Market order sizes are in quote currency for buys unless set to base
+            None => body["tgtCcy"] = "base_ccy".into(),
+        }
+        if let Some(id) = &order.client_order_id {
+            body["clOrdId"] = id.as_str().into();
+        }
+        signed_request(
+            credentials,
+            Method::POST,
+            ORDER_PATH,
+            body.to_string(),
+            mode,
+            timestamp_ms,
+        )
+    }
+
+    fn parse_order_response(
+        &self,
+        response: &serde_json::Value,
+        order: &CexOrder,
+        mode: TradingMode,
+    ) -> Result<CexOrderAck, MarketScannerError> {
+        // The ack records the amounts that were sent
+        let order = &order.rounded()?;
+        let data = response_data(response, "order")?;
+        let order_id = data["ordId"].as_str().ok_or_else(|| {
+            MarketScannerError::ApiError("OKX order response missing ordId".to_string())
+        })?;
+        Ok(CexOrderAck {
+            exchange: CexExchange::OKX,
+            symbol: order.symbol.clone(),
+            order_id: order_id.to_string(),
+            client_order_id: data["clOrdId"]
+                .as_str()
+                .filter(|id| !id.is_empty())
+                .map(str::to_string),
+            side: order.side,
+            quantity: order.quantity,
+            price: order.price,
+            mode,
+            timestamp: Timestamp::now(),
+        })
+    }
+
+    fn order_status_request(
+        &self,
+        credentials: &Credentials,
+        ack: &CexOrderAck,
+        timestamp_ms: u64,
+    ) -> Result<SignedOrderRequest, MarketScannerError> {
+        let path = format!(
+            "{}?instId={}&ordId={}",
+            ORDER_PATH,
+            format_symbol_for_exchange(&ack.symbol, &CexExchange::OKX)?,
+            ack.order_id
+        );
+        signed_request(
+            credentials,
+            Method::GET,
+            &path,
+            String::new(),
+            ack.mode,
+            timestamp_ms,
+        )
+    }
+
+    fn parse_order_status(
+        &self,
+        response: &serde_json::Value,
+        ack: &CexOrderAck,
+    ) -> Result<OrderUpdate, MarketScannerError> {
+        let data = response_data(response, "order query")?;
+        order_update(data).ok_or_else(|| {
+            MarketScannerError::ApiError(format!("OKX order {} not found", ack.order_id))
+        })
+    }
+
+    fn cancel_request(
+        &self,
+        credentials: &Credentials,
+        ack: &CexOrderAck,
+        timestamp_ms: u64,
+    ) -> Result<SignedOrderRequest, MarketScannerError> {
+        let body = serde_json::json!({
+            "instId": format_symbol_for_exchange(&ack.symbol, &CexExchange::OKX)?,
+            "ordId": ack.order_id,
+        });
+        signed_request(
+            credentials,
+            Method::POST,
+            CANCEL_PATH,
+            body.to_string(),
+            ack.mode,
+            timestamp_ms,
+        )
+    }
+
+    fn parse_cancel_response(
+        &self,
+        response: &serde_json::Value,
+        _ack: &CexOrderAck,
+    ) -> Result<(), MarketScannerError> {
+        response_data(response, "cancel").map(|_| ())
+    }
+}
@@ -20,3 +20,24 @@ pub struct OkxTickerData {
     #[serde(rename = "bidSz")]
     pub bid_sz: String,
 }
+
+/// Order book push of the `books5` and `books` WebSocket channels.
+#[derive(Debug, Deserialize)]
+pub struct OkxBookWsMessage {
+    /// "snapshot" or "update" on `books`; absent on `books5`, whose pushes are all snapshots
+    #[serde(default)]
+    pub action: Option<String>,
+    pub data: Vec<OkxBookData>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct OkxBookData {
+    /// Levels as [price, size, deprecated, order count]
+    pub asks: Vec<Vec<String>>,
+    pub bids: Vec<Vec<String>>,
+    #[serde(rename = "seqId", default)]
+    pub seq_id: Option<i64>,
+    /// Sequence id of the previous push of the channel (-1 on snapshots)
+    #[serde(rename = "prevSeqId", default)]
+    pub prev_seq_id: Option<i64>,
+}
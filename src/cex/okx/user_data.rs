@@ -0,0 +1,142 @@
+//! OKX private WebSocket: `orders` and `account` channels, live or simulated trading.
+
+use super::OKX;
+use crate::cex::execution::{
+    BalanceUpdate, OrderStatus, OrderUpdate, PrivateWsSession, SessionFactory, TradingMode,
+    UserDataEvent, UserDataStream, hmac_sha256_base64, json_f64, json_side, json_str,
+    json_timestamp, required_passphrase, spawn_user_data_stream,
+};
+use crate::common::{
+    CexExchange, Credentials, MarketScannerError, get_timestamp_millis,
+    standard_symbol_for_cex_ws_response,
+};
+use async_trait::async_trait;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::mpsc;
+
+const OKX_WS_PRIVATE: &str = "wss://ws.okx.com:8443/ws/v5/private";
+const OKX_DEMO_WS_PRIVATE: &str = "wss://wspap.okx.com:8443/ws/v5/private";
+
+/// Session with a freshly signed login message.
+fn private_session(
+    credentials: &Credentials,
+    mode: TradingMode,
+) -> Result<PrivateWsSession, MarketScannerError> {
+    let passphrase = required_passphrase(credentials, "OKX")?;
+    let timestamp = (get_timestamp_millis() / 1000).to_string();
+    let signature = hmac_sha256_base64(
+        credentials.api_secret(),
+        &format!("{}GET/users/self/verify", timestamp),
+    );
+    let login = serde_json::json!({
+        "op": "login",
+        "args": [{
+            "apiKey": credentials.api_key(),
+            "passphrase": passphrase,
+            "timestamp": timestamp,
+            "sign": signature,
+        }],
+    });
+    let subscribe = serde_json::json!({
+        "op": "subscribe",
+        "args": [{"channel": "orders", "instType": "SPOT"}, {"channel": "account"}],
+    });
+    Ok(PrivateWsSession {
+        url: match mode {
+            TradingMode::Live => OKX_WS_PRIVATE,
+            TradingMode::Demo => OKX_DEMO_WS_PRIVATE,
+        }
+        .to_string(),
+        on_connect: vec![login.to_string()],
+        // OKX rejects private subscriptions sent before the login is confirmed
+        after_login: vec![subscribe.to_string()],
+        login_ack: Some(|value| value["event"] == "login" && value["code"] == "0"),
+        expired: None,
+        text_ping: Some("ping".to_string()),
+        ping_interval: Duration::from_secs(25),
+        keepalive: None,
+    })
+}
+
+fn order_status(state: &str) -> OrderStatus {
+    match state {
+        "live" => OrderStatus::New,
+        "partially_filled" => OrderStatus::PartiallyFilled,
+        "filled" => OrderStatus::Filled,
+        "canceled" | "mmp_canceled" => OrderStatus::Canceled,
+        _ => OrderStatus::Unknown,
+    }
+}
+
+/// Order state from an `orders` channel entry or a `trade/order` query result.
+pub(super) fn order_update(order: &serde_json::Value) -> Option<OrderUpdate> {
+    Some(OrderUpdate {
+        exchange: CexExchange::OKX,
+        symbol: standard_symbol_for_cex_ws_response(order["instId"].as_str()?, &CexExchange::OKX),
+        order_id: json_str(&order["ordId"])?,
+        client_order_id: json_str(&order["clOrdId"]),
+        side: json_side(&order["side"])?,
+        status: order_status(order["state"].as_str().unwrap_or_default()),
+        // Empty for market orders
+        price: json_f64(&order["px"]).filter(|p| *p > 0.0),
+        quantity: json_f64(&order["sz"]).unwrap_or(0.0),
+        filled_quantity: json_f64(&order["accFillSz"]).unwrap_or(0.0),
+        average_fill_price: json_f64(&order["avgPx"]).filter(|p| *p > 0.0),
+        timestamp: json_timestamp(&order["uTime"]),
+    })
+}
+
+fn parse_user_data(message: &serde_json::Value) -> Vec<UserDataEvent> {
+    let data = message["data"].as_array().into_iter().flatten();
+    match message["arg"]["channel"].as_str() {
+        Some("orders") => data
+            .filter_map(|order| order_update(order).map(UserDataEvent::Order))
+            .collect(),
+        Some("account") => data
+            .flat_map(|account| account["details"].as_array().into_iter().flatten())
+            .filter_map(|detail| {
+                Some(UserDataEvent::Balance(BalanceUpdate {
+                    exchange: CexExchange::OKX,
+                    asset: json_str(&detail["ccy"])?,
+                    free: json_f64(&detail["availBal"])?,
+                    locked: json_f64(&detail["frozenBal"]).unwrap_or(0.0),
+                    timestamp: json_timestamp(&detail["uTime"]),
+                }))
+            })
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+#[async_trait]
+impl UserDataStream for OKX {
+    fn parse_user_data(&self, message: &serde_json::Value) -> Vec<UserDataEvent> {
+        parse_user_data(message)
+    }
+
+    /// Needs a passphrase. Demo mode uses the simulated trading stream (demo API keys).
+    async fn stream_user_data(
+        &self,
+        credentials: &Credentials,
+        mode: TradingMode,
+        reconnect_attempts: u32,
+        reconnect_delay_ms: u64,
+    ) -> Result<mpsc::Receiver<UserDataEvent>, MarketScannerError> {
+        let first_session = private_session(credentials, mode)?;
+        // Each reconnect signs a new login message
+        let credentials = credentials.clone();
+        let new_session: SessionFactory = Arc::new(move || {
+            let session = private_session(&credentials, mode);
+            Box::pin(async move { session })
+        });
+        Ok(spawn_user_data_stream(
+            CexExchange::OKX,
+            first_session,
+            new_session,
+            parse_user_data,
+            reconnect_attempts,
+            reconnect_delay_ms,
+        ))
+    }
+}
@@ -1,14 +1,19 @@
 mod types;
 
 use crate::cex::upbit::types::UpbitOrderBookResponse;
+use crate::common::capabilities::{Capabilities, VenueCapability};
+use crate::common::reconnect::{ReconnectPolicy, ws_endpoint};
+use crate::common::symbol_registry;
 use crate::common::{
-    CEXTrait, CexExchange, CexPrice, Exchange, ExchangeTrait, MarketScannerError, find_mid_price,
-    format_symbol_for_exchange, format_symbol_for_exchange_ws, get_timestamp_millis,
-    normalize_symbol, standard_symbol_for_cex_ws_response,
+    CEXTrait, CexExchange, CexPrice, Exchange, ExchangeTrait, MarketScannerError, OrderBook,
+    OrderBookLevel, RequestPriority, Timestamp, TradingStatus, WsSymbolFilter, decode_ws_json,
+    find_mid_price, format_symbol_for_exchange, format_symbol_for_exchange_ws, normalize_symbol,
+    record_ws_connect, standard_symbol_for_cex_ws_response,
 };
 use crate::create_exchange;
 use async_trait::async_trait;
 use futures::{SinkExt, StreamExt};
+use std::collections::HashMap;
 use tokio::sync::mpsc;
 use tokio_tungstenite::tungstenite::Message as WsMessage;
 
@@ -28,13 +33,15 @@ impl ExchangeTrait for Upbit {
     }
 
     fn exchange_name(&self) -> &str {
-        "Upbit"
+        CexExchange::Upbit.rest_venue_name()
     }
 
     async fn health_check(&self) -> Result<(), MarketScannerError> {
         // Upbit market all endpoint - test connectivity to the REST API
         let endpoint = "market/all?isDetails=false";
-        let response: serde_json::Value = self.get(endpoint).await?;
+        let response: serde_json::Value = self
+            .get_with_priority(endpoint, RequestPriority::Metadata)
+            .await?;
 
         // Upbit returns array of market objects for success
         if let Some(array) = response.as_array() {
@@ -45,6 +52,11 @@ impl ExchangeTrait for Upbit {
 
         Err(MarketScannerError::HealthCheckFailed)
     }
+
+    async fn get_server_time(&self) -> Result<Timestamp, MarketScannerError> {
+        // No server time endpoint: the HTTP Date header has second resolution
+        self.get_date_header("ticker?markets=KRW-BTC").await
+    }
 }
 
 #[async_trait]
@@ -53,6 +65,15 @@ impl CEXTrait for Upbit {
         true
     }
 
+    fn capabilities(&self) -> Capabilities {
+        Capabilities::new(&[
+            VenueCapability::Websocket,
+            VenueCapability::OrderBook,
+            VenueCapability::InstrumentIds,
+            VenueCapability::ServerTime,
+        ])
+    }
+
     async fn get_price(&self, symbol: &str) -> Result<CexPrice, MarketScannerError> {
         // Validate symbol is not empty
         if symbol.is_empty() {
@@ -137,11 +158,85 @@ impl CEXTrait for Upbit {
             ask_price: ask,
             bid_qty,
             ask_qty,
-            timestamp: get_timestamp_millis(),
+            timestamp: Timestamp::now(),
             exchange: Exchange::Cex(CexExchange::Upbit),
+            trading_status: TradingStatus::Unknown,
         })
     }
 
+    async fn get_order_book(
+        &self,
+        symbol: &str,
+        depth: usize,
+    ) -> Result<OrderBook, MarketScannerError> {
+        if symbol.is_empty() {
+            return Err(MarketScannerError::InvalidSymbol(
+                "Symbol cannot be empty".to_string(),
+            ));
+        }
+
+        let upbit_symbol = format_symbol_for_exchange(symbol, &CexExchange::Upbit)?;
+        // Upbit returns up to 15 units per request; each unit holds one bid and one ask level
+        let endpoint = format!("orderbook?markets={}", upbit_symbol);
+
+        let response: serde_json::Value = self
+            .get_with_priority(&endpoint, RequestPriority::OrderBook)
+            .await?;
+        if let Some(error) = response.get("error") {
+            let error_msg = error.as_str().unwrap_or("Unknown error");
+            return Err(MarketScannerError::ApiError(format!(
+                "Upbit API error: {}",
+                error_msg
+            )));
+        }
+
+        let first = response
+            .as_array()
+            .and_then(|a| a.first())
+            .cloned()
+            .ok_or_else(|| {
+                MarketScannerError::ApiError(format!(
+                    "Upbit API error: empty orderbook response for symbol: {}",
+                    symbol
+                ))
+            })?;
+        let orderbook_response: UpbitOrderBookResponse =
+            serde_json::from_value(first).map_err(|e| {
+                MarketScannerError::ApiError(format!(
+                    "Upbit API error: failed to parse orderbook response: {}",
+                    e
+                ))
+            })?;
+
+        let units = orderbook_response.orderbook_units.iter().take(depth);
+        let bids = units
+            .clone()
+            .map(|u| OrderBookLevel {
+                price: u.bid_price,
+                quantity: u.bid_size,
+            })
+            .collect();
+        let asks = units
+            .map(|u| OrderBookLevel {
+                price: u.ask_price,
+                quantity: u.ask_size,
+            })
+            .collect();
+
+        Ok(OrderBook {
+            symbol: normalize_symbol(symbol),
+            bids,
+            asks,
+            timestamp: Timestamp::now(),
+            exchange: Exchange::Cex(CexExchange::Upbit),
+        })
+    }
+
+    async fn get_instrument_ids(&self) -> Result<HashMap<String, String>, MarketScannerError> {
+        let response: serde_json::Value = self.get_cached("market/all").await?;
+        Ok(parse_upbit_markets(&response))
+    }
+
     async fn stream_price_websocket(
         &self,
         symbols: &[&str],
@@ -167,24 +262,24 @@ impl CEXTrait for Upbit {
         ]);
 
         let (tx, rx) = mpsc::channel(64);
-        let delay =
-            std::time::Duration::from_millis(if reconnect_delay_ms == 0 { 1000 } else { reconnect_delay_ms });
+        let symbol_filter = WsSymbolFilter::new(&CexExchange::Upbit, symbols);
+        let policy = ReconnectPolicy::new(reconnect_attempts, reconnect_delay_ms);
+        let url = ws_endpoint(&CexExchange::Upbit, UPBIT_WS_URL);
 
-        tokio::spawn(async move {
+        symbol_registry::spawn(async move {
             let mut attempt = 0u32;
             loop {
                 attempt += 1;
-                let (mut ws_stream, _) = match tokio_tungstenite::connect_async(UPBIT_WS_URL).await
-                {
-                    Ok(v) => v,
+                let (mut ws_stream, _) = match tokio_tungstenite::connect_async(&url).await {
+                    Ok(v) => {
+                        record_ws_connect(&CexExchange::Upbit);
+                        v
+                    }
                     Err(_) => {
-                        if tx.is_closed()
-                            || reconnect_attempts == 0
-                            || attempt > reconnect_attempts
-                        {
+                        if tx.is_closed() || !policy.should_reconnect(attempt) {
                             break;
                         }
-                        tokio::time::sleep(delay).await;
+                        tokio::time::sleep(policy.delay()).await;
                         continue;
                     }
                 };
@@ -194,44 +289,46 @@ impl CEXTrait for Upbit {
                     .await
                     .is_err()
                 {
-                    if tx.is_closed()
-                        || reconnect_attempts == 0
-                        || attempt > reconnect_attempts
-                    {
+                    if tx.is_closed() || !policy.should_reconnect(attempt) {
                         break;
                     }
-                    tokio::time::sleep(delay).await;
+                    tokio::time::sleep(policy.delay()).await;
                     continue;
                 }
 
                 let (_write, mut read) = ws_stream.split();
 
                 while let Some(Ok(msg)) = read.next().await {
+                    // Control frames carry no payload to decode
+                    if msg.is_ping() || msg.is_pong() || msg.is_close() {
+                        continue;
+                    }
                     let text = match msg.into_text() {
                         Ok(t) => t,
                         Err(_) => continue,
                     };
-                    let value: serde_json::Value = match serde_json::from_str(&text) {
-                        Ok(v) => v,
-                        Err(_) => continue,
+                    let value: serde_json::Value = match decode_ws_json(&CexExchange::Upbit, &text)
+                    {
+                        Some(v) => v,
+                        None => continue,
                     };
                     if value.get("type").and_then(|t| t.as_str()) != Some("orderbook") {
                         continue;
                     }
                     if let Some(price) = parse_upbit_orderbook(&value) {
+                        if !symbol_filter.allows(&price) {
+                            continue;
+                        }
                         if tx.send(price).await.is_err() {
                             return;
                         }
                     }
                 }
 
-                if tx.is_closed()
-                    || reconnect_attempts == 0
-                    || attempt > reconnect_attempts
-                {
+                if tx.is_closed() || !policy.should_reconnect(attempt) {
                     break;
                 }
-                tokio::time::sleep(delay).await;
+                tokio::time::sleep(policy.delay()).await;
             }
         });
 
@@ -262,7 +359,23 @@ fn parse_upbit_orderbook(value: &serde_json::Value) -> Option<CexPrice> {
         ask_price: ask_price,
         bid_qty: bid_size,
         ask_qty: ask_size,
-        timestamp: get_timestamp_millis(),
+        timestamp: Timestamp::now(),
         exchange: Exchange::Cex(CexExchange::Upbit),
+        trading_status: TradingStatus::Unknown,
     })
 }
+
+/// Builds the standard symbol -> Upbit market code map from the `market/all` listing
+/// (`KRW-BTC` -> `BTCKRW`, `USDT-ETH` -> `ETHUSDT`).
+fn parse_upbit_markets(response: &serde_json::Value) -> HashMap<String, String> {
+    let mut markets = HashMap::new();
+    let Some(listing) = response.as_array() else {
+        return markets;
+    };
+    for market in listing.iter().filter_map(|m| m["market"].as_str()) {
+        if let Some((quote, base)) = market.split_once('-') {
+            markets.insert(format!("{}{}", base, quote), market.to_string());
+        }
+    }
+    markets
+}
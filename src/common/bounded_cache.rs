@@ -0,0 +1,219 @@
+//! Size- and age-bounded caches.
+//!
+//! Latest-value caches (one price per venue and symbol) grow with the watchlist. A
+//! [BoundedCache] caps them with [CacheLimits]: at most `max_entries` entries, evicting the
+//! least recently updated one first, and entries not updated within `ttl` expire. Recency
+//! is by write, not read: for price caches a symbol that stopped updating is the one to
+//! drop. [CacheStats] counts evictions per cause so long-running processes can tell whether
+//! their limits bite.
+
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::hash::Hash;
+use std::time::{Duration, Instant};
+
+/// Bounds of a [BoundedCache]; unbounded by default.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CacheLimits {
+    pub max_entries: Option<usize>,
+    pub ttl: Option<Duration>,
+}
+
+impl CacheLimits {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Keeps at most `max_entries` entries, evicting the least recently updated.
+    pub fn with_max_entries(mut self, max_entries: usize) -> Self {
+        self.max_entries = Some(max_entries);
+        self
+    }
+
+    /// Expires entries not updated within `ttl`.
+    pub fn with_ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = Some(ttl);
+        self
+    }
+}
+
+/// Size and eviction counters of a [BoundedCache].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CacheStats {
+    pub entries: usize,
+    pub max_entries: Option<usize>,
+    /// Entries evicted to stay within `max_entries`
+    pub capacity_evictions: u64,
+    /// Entries expired by the TTL
+    pub ttl_evictions: u64,
+}
+
+impl CacheStats {
+    pub fn evictions(&self) -> u64 {
+        self.capacity_evictions + self.ttl_evictions
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Slot<V> {
+    value: V,
+    updated_at: Instant,
+    seq: u64,
+}
+
+/// Map bounded by [CacheLimits].
+#[derive(Debug, Clone)]
+pub struct BoundedCache<K, V> {
+    limits: CacheLimits,
+    entries: HashMap<K, Slot<V>>,
+    /// Keys by update sequence, least recently updated first
+    order: BTreeMap<u64, K>,
+    next_seq: u64,
+    stats: CacheStats,
+}
+
+impl<K, V> Default for BoundedCache<K, V> {
+    fn default() -> Self {
+        Self {
+            limits: CacheLimits::default(),
+            entries: HashMap::new(),
+            order: BTreeMap::new(),
+            next_seq: 0,
+            stats: CacheStats::default(),
+        }
+    }
+}
+
+impl<K: Clone + Eq + Hash, V> BoundedCache<K, V> {
+    pub fn new(limits: CacheLimits) -> Self {
+        Self {
+            limits,
+            stats: CacheStats {
+                max_entries: limits.max_entries,
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+    }
+
+    pub fn limits(&self) -> CacheLimits {
+        self.limits
+    }
+
+    /// Changes the limits, evicting what no longer fits.
+    pub fn set_limits(&mut self, limits: CacheLimits) {
+        self.limits = limits;
+        self.stats.max_entries = limits.max_entries;
+        self.purge_expired();
+        self.enforce_capacity();
+    }
+
+    /// Inserts or updates `key`, then evicts expired and excess entries.
+    pub fn insert(&mut self, key: K, value: V) {
+        let seq = self.next_seq;
+        self.next_seq += 1;
+        if let Some(old) = self.entries.insert(
+            key.clone(),
+            Slot {
+                value,
+                updated_at: Instant::now(),
+                seq,
+            },
+        ) {
+            self.order.remove(&old.seq);
+        }
+        self.order.insert(seq, key);
+        self.purge_expired();
+        self.enforce_capacity();
+    }
+
+    /// Value of `key`, unless it expired.
+    pub fn get(&self, key: &K) -> Option<&V> {
+        self.entries
+            .get(key)
+            .filter(|slot| !self.is_expired(slot))
+            .map(|slot| &slot.value)
+    }
+
+    pub fn remove(&mut self, key: &K) -> Option<V> {
+        let slot = self.entries.remove(key)?;
+        self.order.remove(&slot.seq);
+        Some(slot.value)
+    }
+
+    /// Keeps the entries for which `keep` returns true (not counted as evictions).
+    pub fn retain<F: FnMut(&K, &V) -> bool>(&mut self, mut keep: F) {
+        let order = &mut self.order;
+        self.entries.retain(|key, slot| {
+            let kept = keep(key, &slot.value);
+            if !kept {
+                order.remove(&slot.seq);
+            }
+            kept
+        });
+        self.stats.entries = self.entries.len();
+    }
+
+    /// Unexpired values, in no particular order.
+    pub fn values(&self) -> impl Iterator<Item = &V> {
+        self.entries
+            .values()
+            .filter(|slot| !self.is_expired(slot))
+            .map(|slot| &slot.value)
+    }
+
+    /// Number of entries, expired ones not purged yet included.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Drops entries older than the TTL.
+    pub fn purge_expired(&mut self) {
+        let Some(ttl) = self.limits.ttl else {
+            return;
+        };
+        let now = Instant::now();
+        // Least recently updated first, so expired entries form a prefix
+        while let Some((&seq, key)) = self.order.first_key_value() {
+            let expired = self
+                .entries
+                .get(key)
+                .is_none_or(|slot| now.duration_since(slot.updated_at) > ttl);
+            if !expired {
+                break;
+            }
+            let key = self.order.remove(&seq).expect("first key exists");
+            if self.entries.remove(&key).is_some() {
+                self.stats.ttl_evictions += 1;
+            }
+        }
+        self.stats.entries = self.entries.len();
+    }
+
+    pub fn stats(&self) -> CacheStats {
+        self.stats
+    }
+
+    fn enforce_capacity(&mut self) {
+        if let Some(max_entries) = self.limits.max_entries {
+            while self.entries.len() > max_entries {
+                let Some((_, key)) = self.order.pop_first() else {
+                    break;
+                };
+                self.entries.remove(&key);
+                self.stats.capacity_evictions += 1;
+            }
+        }
+        self.stats.entries = self.entries.len();
+    }
+
+    fn is_expired(&self, slot: &Slot<V>) -> bool {
+        self.limits
+            .ttl
+            .is_some_and(|ttl| slot.updated_at.elapsed() > ttl)
+    }
+}
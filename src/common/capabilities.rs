@@ -0,0 +1,103 @@
+//! What each CEX integration supports.
+//!
+//! [CEXTrait::capabilities] lists the features a venue implements, so callers can pick
+//! venues without probing for "does not support" errors. The venue × capability matrix of
+//! every integration (optionally with a live health check) is built by
+//! [crate::scanner::CapabilityReport].
+//!
+//! [CEXTrait::capabilities]: crate::common::CEXTrait::capabilities
+
+use serde::{Deserialize, Serialize};
+
+/// A feature a venue integration may implement.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum VenueCapability {
+    /// Streaming top-of-book prices ([crate::common::CEXTrait::stream_price_websocket])
+    Websocket,
+    /// REST order book depth ([crate::common::CEXTrait::get_order_book])
+    OrderBook,
+    /// Synced order book stream ([crate::common::CEXTrait::stream_order_book])
+    OrderBookStream,
+    /// Public trade feed
+    Trades,
+    /// Futures / perpetual markets
+    Futures,
+    /// Authenticated order placement ([crate::cex::execution::CexOrderExecution])
+    Execution,
+    /// Private order and balance stream ([crate::cex::execution::UserDataStream])
+    UserData,
+    /// Minimum order sizes ([crate::common::CEXTrait::get_order_limits])
+    OrderLimits,
+    /// Instrument id listing ([crate::common::CEXTrait::get_instrument_ids])
+    InstrumentIds,
+    /// Venue clock ([crate::common::ExchangeTrait::get_server_time])
+    ServerTime,
+}
+
+impl VenueCapability {
+    /// Every capability, in report column order.
+    pub const ALL: [VenueCapability; 10] = [
+        VenueCapability::Websocket,
+        VenueCapability::OrderBook,
+        VenueCapability::OrderBookStream,
+        VenueCapability::Trades,
+        VenueCapability::Futures,
+        VenueCapability::Execution,
+        VenueCapability::UserData,
+        VenueCapability::OrderLimits,
+        VenueCapability::InstrumentIds,
+        VenueCapability::ServerTime,
+    ];
+
+    /// Short column name (e.g. "order_book_stream").
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            VenueCapability::Websocket => "websocket",
+            VenueCapability::OrderBook => "order_book",
+            VenueCapability::OrderBookStream => "order_book_stream",
+            VenueCapability::Trades => "trades",
+            VenueCapability::Futures => "futures",
+            VenueCapability::Execution => "execution",
+            VenueCapability::UserData => "user_data",
+            VenueCapability::OrderLimits => "order_limits",
+            VenueCapability::InstrumentIds => "instrument_ids",
+            VenueCapability::ServerTime => "server_time",
+        }
+    }
+}
+
+/// Set of [VenueCapability]s of one venue.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Capabilities {
+    supported: Vec<VenueCapability>,
+}
+
+impl Capabilities {
+    pub fn new(supported: &[VenueCapability]) -> Self {
+        Self::default().with_all(supported)
+    }
+
+    /// Adds `capability`.
+    pub fn with(mut self, capability: VenueCapability) -> Self {
+        if !self.supported.contains(&capability) {
+            self.supported.push(capability);
+            self.supported.sort();
+        }
+        self
+    }
+
+    pub fn with_all(self, capabilities: &[VenueCapability]) -> Self {
+        capabilities
+            .iter()
+            .fold(self, |caps, capability| caps.with(*capability))
+    }
+
+    pub fn supports(&self, capability: VenueCapability) -> bool {
+        self.supported.contains(&capability)
+    }
+
+    /// Supported capabilities, in [VenueCapability::ALL] order.
+    pub fn supported(&self) -> &[VenueCapability] {
+        &self.supported
+    }
+}
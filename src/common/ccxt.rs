@@ -0,0 +1,106 @@
+//! Translation between ccxt identifiers and this crate's types.
+//!
+//! ccxt names venues by lowercase exchange id (`binance`, `okx`, `gateio`) and markets by
+//! unified symbol (`BTC/USDT`). [cex_from_ccxt_id] / [ccxt_id] map venue ids in both
+//! directions, [symbol_from_ccxt] / [symbol_to_ccxt] map symbols, so configurations and
+//! data from a ccxt stack can be used here (and vice versa) without hand-written tables.
+
+use crate::common::errors::MarketScannerError;
+use crate::common::exchange::CexExchange;
+use crate::common::market::split_base_quote;
+use crate::common::utils::normalize_symbol;
+
+/// ccxt exchange ids per venue; the first id of each venue is the canonical one.
+const CCXT_IDS: &[(&str, CexExchange)] = &[
+    ("binance", CexExchange::Binance),
+    ("bybit", CexExchange::Bybit),
+    ("mexc", CexExchange::MEXC),
+    ("okx", CexExchange::OKX),
+    ("gateio", CexExchange::Gateio),
+    ("gate", CexExchange::Gateio),
+    ("kucoin", CexExchange::Kucoin),
+    ("bitget", CexExchange::Bitget),
+    ("btcturk", CexExchange::Btcturk),
+    ("htx", CexExchange::Htx),
+    ("huobi", CexExchange::Htx),
+    ("coinbase", CexExchange::Coinbase),
+    ("coinbaseexchange", CexExchange::Coinbase),
+    ("kraken", CexExchange::Kraken),
+    ("bitfinex", CexExchange::Bitfinex),
+    ("bitfinex2", CexExchange::Bitfinex),
+    ("upbit", CexExchange::Upbit),
+    ("cryptocom", CexExchange::Cryptocom),
+];
+
+/// Venue tickers that ccxt reports under their common currency code.
+const CCXT_COMMON_CURRENCIES: &[(&str, &str)] = &[("XBT", "BTC"), ("XDG", "DOGE"), ("UST", "USDT")];
+
+/// Venue for a ccxt exchange id (case-insensitive), e.g. `okx` or `huobi`.
+pub fn cex_from_ccxt_id(id: &str) -> Option<CexExchange> {
+    let id = id.to_lowercase();
+    CCXT_IDS
+        .iter()
+        .find(|(ccxt, _)| *ccxt == id)
+        .map(|(_, exchange)| exchange.clone())
+}
+
+/// Canonical ccxt exchange id of `exchange`.
+pub fn ccxt_id(exchange: &CexExchange) -> &'static str {
+    CCXT_IDS
+        .iter()
+        .find(|(_, ex)| ex == exchange)
+        .map(|(id, _)| *id)
+        .expect("every CexExchange has a ccxt id")
+}
+
+/// Standard symbol for a ccxt unified spot symbol: `BTC/USDT` -> `BTCUSDT`.
+/// Derivative symbols (`BTC/USDT:USDT`) are rejected.
+pub fn symbol_from_ccxt(unified: &str) -> Result<String, MarketScannerError> {
+    if unified.contains(':') {
+        return Err(MarketScannerError::InvalidSymbol(format!(
+            "ccxt derivative symbol not supported: {}",
+            unified
+        )));
+    }
+    match unified.split_once('/') {
+        Some((base, quote))
+            if !base.trim().is_empty() && !quote.trim().is_empty() && !quote.contains('/') =>
+        {
+            Ok(normalize_symbol(&format!(
+                "{}{}",
+                base.trim(),
+                quote.trim()
+            )))
+        }
+        _ => Err(MarketScannerError::InvalidSymbol(format!(
+            "Not a ccxt unified symbol (BASE/QUOTE): {}",
+            unified
+        ))),
+    }
+}
+
+/// ccxt unified symbol for a standard symbol: `BTCUSDT` -> `BTC/USDT`. Venue tickers
+/// ccxt renames (`XBT`, `XDG`, `UST`) are mapped to their common codes.
+pub fn symbol_to_ccxt(symbol: &str) -> Result<String, MarketScannerError> {
+    let normalized = normalize_symbol(symbol);
+    let (base, quote) = split_base_quote(&normalized);
+    if base.is_empty() || quote.is_empty() {
+        return Err(MarketScannerError::InvalidSymbol(format!(
+            "Cannot split symbol into base and quote: {}",
+            symbol
+        )));
+    }
+    Ok(format!(
+        "{}/{}",
+        common_currency(base),
+        common_currency(quote)
+    ))
+}
+
+fn common_currency(ticker: &str) -> &str {
+    CCXT_COMMON_CURRENCIES
+        .iter()
+        .find(|(venue, _)| *venue == ticker)
+        .map(|(_, common)| *common)
+        .unwrap_or(ticker)
+}
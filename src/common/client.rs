@@ -1,8 +1,515 @@
+//! HTTP client construction and a shared cache for semi-static REST endpoints.
+//!
+//! Exchange info, symbol listings and similar responses rarely change, yet discovery
+//! features fetch them at startup and on every refresh. [cached_get] keeps the last body
+//! per URL for a TTL and then revalidates it with `If-None-Match` / `If-Modified-Since`,
+//! so an unchanged resource costs a `304 Not Modified` instead of a full download. TTLs
+//! default to [DEFAULT_HTTP_CACHE_TTL] and can be set per endpoint prefix with
+//! [set_http_cache_ttl].
+//!
+//! Venues that need specific headers (User-Agent, API-key or client-id headers) get them
+//! from an [HttpClientConfig] at construction: every exchange type has
+//! `default_http_config()` with its built-in headers and `with_http_config(config)`, which
+//! layers caller headers on top of those defaults.
+//!
+//! Listing endpoints that split results across requests are walked with a [Paginator]
+//! (page-number or cursor style, see [crate::common::ExchangeTrait::get_all_pages]), so
+//! discovery gets the complete listing instead of its first page.
+
+use crate::common::errors::MarketScannerError;
+use crate::common::rate_limit::{RequestPriority, acquire_request_slot};
+use crate::common::rest_diagnostics::send_recorded;
+use crate::common::time::Timestamp;
+use reqwest::StatusCode;
+use reqwest::header::{
+    ETAG, HeaderMap, HeaderName, HeaderValue, IF_MODIFIED_SINCE, IF_NONE_MATCH, LAST_MODIFIED,
+    USER_AGENT,
+};
+use std::collections::HashMap;
+use std::sync::{LazyLock, RwLock};
+use std::time::Duration;
+
 const DEFAULT_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(5);
 
+/// TTL of cached responses whose endpoint has no configured TTL.
+pub const DEFAULT_HTTP_CACHE_TTL: Duration = Duration::from_secs(60);
+
 pub fn create_http_client() -> reqwest::Client {
     reqwest::Client::builder()
         .timeout(DEFAULT_TIMEOUT)
         .build()
         .expect("Failed to create HTTP client")
 }
+
+/// Default headers and timeout of an exchange's HTTP client.
+///
+/// Header names compare case-insensitively; setting a header again replaces it. Values of
+/// headers added with [HttpClientConfig::with_api_key] are marked sensitive and redacted in
+/// `Debug` output.
+#[derive(Clone, Default)]
+pub struct HttpClientConfig {
+    /// (name, value, sensitive)
+    headers: Vec<(String, String, bool)>,
+    timeout: Option<Duration>,
+}
+
+impl HttpClientConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the `User-Agent` header.
+    pub fn with_user_agent(self, user_agent: &str) -> Self {
+        self.with_header(USER_AGENT.as_str(), user_agent)
+    }
+
+    /// Sets a default header sent with every request.
+    pub fn with_header(self, name: &str, value: &str) -> Self {
+        self.set(name, value, false)
+    }
+
+    /// Sets an API-key header (e.g. `X-MBX-APIKEY`); its value is marked sensitive.
+    pub fn with_api_key(self, header_name: &str, key: &str) -> Self {
+        self.set(header_name, key, true)
+    }
+
+    /// Request timeout; [create_http_client]'s 5 seconds when unset.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    fn set(mut self, name: &str, value: &str, sensitive: bool) -> Self {
+        self.headers
+            .retain(|(n, _, _)| !n.eq_ignore_ascii_case(name));
+        self.headers
+            .push((name.to_string(), value.to_string(), sensitive));
+        self
+    }
+
+    /// Value of header `name`, if set.
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(n, _, _)| n.eq_ignore_ascii_case(name))
+            .map(|(_, v, _)| v.as_str())
+    }
+
+    pub fn user_agent(&self) -> Option<&str> {
+        self.header(USER_AGENT.as_str())
+    }
+
+    pub fn timeout(&self) -> Duration {
+        self.timeout.unwrap_or(DEFAULT_TIMEOUT)
+    }
+
+    /// `self` with every header and the timeout of `overrides` applied on top.
+    pub fn merge(self, overrides: HttpClientConfig) -> Self {
+        let mut merged = overrides
+            .headers
+            .into_iter()
+            .fold(self, |config, (name, value, sensitive)| {
+                config.set(&name, &value, sensitive)
+            });
+        merged.timeout = overrides.timeout.or(merged.timeout);
+        merged
+    }
+
+    fn header_map(&self) -> Result<HeaderMap, MarketScannerError> {
+        let mut headers = HeaderMap::new();
+        for (name, value, sensitive) in &self.headers {
+            let header_name = HeaderName::from_bytes(name.as_bytes()).map_err(|e| {
+                MarketScannerError::InvalidConfig(format!("Invalid header name '{}': {}", name, e))
+            })?;
+            let mut header_value = HeaderValue::from_str(value).map_err(|e| {
+                MarketScannerError::InvalidConfig(format!(
+                    "Invalid value of header '{}': {}",
+                    name, e
+                ))
+            })?;
+            header_value.set_sensitive(*sensitive);
+            headers.insert(header_name, header_value);
+        }
+        Ok(headers)
+    }
+}
+
+impl std::fmt::Debug for HttpClientConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let headers: Vec<(&str, &str)> = self
+            .headers
+            .iter()
+            .map(|(name, value, sensitive)| match sensitive {
+                true => (name.as_str(), "<redacted>"),
+                false => (name.as_str(), value.as_str()),
+            })
+            .collect();
+        f.debug_struct("HttpClientConfig")
+            .field("headers", &headers)
+            .field("timeout", &self.timeout)
+            .finish()
+    }
+}
+
+/// HTTP client sending the default headers of `config`.
+pub fn create_http_client_with_config(
+    config: &HttpClientConfig,
+) -> Result<reqwest::Client, MarketScannerError> {
+    Ok(reqwest::Client::builder()
+        .timeout(config.timeout())
+        .default_headers(config.header_map()?)
+        .build()?)
+}
+
+/// Retries of REST requests that failed with a retryable error (see
+/// [MarketScannerError::is_retryable] and [set_rest_retry_policy]). The delay doubles with
+/// every retry, from `initial_delay` up to `max_delay`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RestRetryPolicy {
+    pub max_retries: u32,
+    pub initial_delay: Duration,
+    pub max_delay: Duration,
+}
+
+impl Default for RestRetryPolicy {
+    /// No retries.
+    fn default() -> Self {
+        Self {
+            max_retries: 0,
+            initial_delay: Duration::from_millis(200),
+            max_delay: Duration::from_secs(5),
+        }
+    }
+}
+
+impl RestRetryPolicy {
+    pub fn new(max_retries: u32) -> Self {
+        Self {
+            max_retries,
+            ..Self::default()
+        }
+    }
+
+    pub fn with_initial_delay(mut self, initial_delay: Duration) -> Self {
+        self.initial_delay = initial_delay;
+        self
+    }
+
+    pub fn with_max_delay(mut self, max_delay: Duration) -> Self {
+        self.max_delay = max_delay;
+        self
+    }
+
+    /// Wait before retry number `retry` (1-based).
+    pub fn delay(&self, retry: u32) -> Duration {
+        let factor = 1u32 << retry.saturating_sub(1).min(16);
+        self.initial_delay
+            .saturating_mul(factor)
+            .min(self.max_delay)
+    }
+
+    /// Whether a request that failed with `error` after `retries` retries is retried.
+    pub fn should_retry(&self, error: &MarketScannerError, retries: u32) -> bool {
+        retries < self.max_retries && error.is_retryable()
+    }
+}
+
+static REST_RETRY_POLICY: LazyLock<RwLock<RestRetryPolicy>> =
+    LazyLock::new(|| RwLock::new(RestRetryPolicy::default()));
+
+/// Retry policy of every venue's REST requests ([crate::common::ExchangeTrait::get] and
+/// [crate::common::ExchangeTrait::get_with_priority]); no retries by default.
+pub fn set_rest_retry_policy(policy: RestRetryPolicy) {
+    *REST_RETRY_POLICY.write().unwrap_or_else(|e| e.into_inner()) = policy;
+}
+
+pub fn rest_retry_policy() -> RestRetryPolicy {
+    *REST_RETRY_POLICY.read().unwrap_or_else(|e| e.into_inner())
+}
+
+#[derive(Debug, Clone)]
+struct CachedResponse {
+    body: String,
+    etag: Option<String>,
+    last_modified: Option<String>,
+    /// Last time the body was fetched or revalidated
+    validated_at: Timestamp,
+}
+
+/// Cached bodies per full URL
+static HTTP_CACHE: LazyLock<RwLock<HashMap<String, CachedResponse>>> =
+    LazyLock::new(|| RwLock::new(HashMap::new()));
+
+/// TTL per endpoint prefix (e.g. `exchangeInfo`, `AssetPairs`)
+static HTTP_CACHE_TTLS: LazyLock<RwLock<HashMap<String, Duration>>> =
+    LazyLock::new(|| RwLock::new(HashMap::new()));
+
+/// Caches endpoints starting with `endpoint_prefix` (relative to the venue's API base, e.g.
+/// `exchangeInfo` or `products/`) for `ttl`. A zero TTL revalidates on every request.
+pub fn set_http_cache_ttl(endpoint_prefix: &str, ttl: Duration) {
+    let mut ttls = HTTP_CACHE_TTLS.write().unwrap_or_else(|e| e.into_inner());
+    ttls.insert(endpoint_prefix.to_string(), ttl);
+}
+
+/// Removes the TTL of `endpoint_prefix`, returning it if one was set.
+pub fn remove_http_cache_ttl(endpoint_prefix: &str) -> Option<Duration> {
+    let mut ttls = HTTP_CACHE_TTLS.write().unwrap_or_else(|e| e.into_inner());
+    ttls.remove(endpoint_prefix)
+}
+
+/// TTL for `endpoint`: the longest matching configured prefix, else [DEFAULT_HTTP_CACHE_TTL].
+pub fn http_cache_ttl(endpoint: &str) -> Duration {
+    let ttls = HTTP_CACHE_TTLS.read().unwrap_or_else(|e| e.into_inner());
+    ttls.iter()
+        .filter(|(prefix, _)| endpoint.starts_with(prefix.as_str()))
+        .max_by_key(|(prefix, _)| prefix.len())
+        .map(|(_, ttl)| *ttl)
+        .unwrap_or(DEFAULT_HTTP_CACHE_TTL)
+}
+
+/// Drops every cached response.
+pub fn clear_http_cache() {
+    HTTP_CACHE
+        .write()
+        .unwrap_or_else(|e| e.into_inner())
+        .clear();
+}
+
+/// GET `url` through the shared cache and return the body. Within `ttl` the cached body is
+/// returned without a request; afterwards it is revalidated with the stored `ETag` /
+/// `Last-Modified`. Only successful responses are cached. `venue` labels error messages and
+/// selects the rate limit; requests are [RequestPriority::Metadata] requests.
+pub async fn cached_get(
+    client: &reqwest::Client,
+    url: &str,
+    ttl: Duration,
+    venue: &str,
+) -> Result<String, MarketScannerError> {
+    let cached = HTTP_CACHE
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .get(url)
+        .cloned();
+    if let Some(entry) = &cached {
+        if !entry.validated_at.is_older_than(ttl) {
+            return Ok(entry.body.clone());
+        }
+    }
+
+    let mut request = client.get(url);
+    if let Some(entry) = &cached {
+        if let Some(etag) = &entry.etag {
+            request = request.header(IF_NONE_MATCH, etag);
+        }
+        if let Some(last_modified) = &entry.last_modified {
+            request = request.header(IF_MODIFIED_SINCE, last_modified);
+        }
+    }
+    let queued_at = std::time::Instant::now();
+    acquire_request_slot(venue, RequestPriority::Metadata).await;
+    let response = send_recorded(venue, request, queued_at.elapsed()).await?;
+    let status = response.status();
+
+    if status == StatusCode::NOT_MODIFIED {
+        if let Some(mut entry) = cached {
+            entry.validated_at = Timestamp::now();
+            let body = entry.body.clone();
+            HTTP_CACHE
+                .write()
+                .unwrap_or_else(|e| e.into_inner())
+                .insert(url.to_string(), entry);
+            return Ok(body);
+        }
+    }
+    if !status.is_success() {
+        let error_text = response.text().await.unwrap_or_default();
+        return Err(MarketScannerError::ApiError(format!(
+            "{} API error: {} - {}",
+            venue, status, error_text
+        )));
+    }
+
+    let header = |name| {
+        response
+            .headers()
+            .get(name)
+            .and_then(|v| v.to_str().ok())
+            .map(str::to_string)
+    };
+    let etag = header(ETAG);
+    let last_modified = header(LAST_MODIFIED);
+    let body = response.text().await?;
+    HTTP_CACHE
+        .write()
+        .unwrap_or_else(|e| e.into_inner())
+        .insert(
+            url.to_string(),
+            CachedResponse {
+                body: body.clone(),
+                etag,
+                last_modified,
+                validated_at: Timestamp::now(),
+            },
+        );
+    Ok(body)
+}
+
+/// How a listing endpoint splits its results across requests.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PageStyle {
+    /// `param=N` from `first` upwards (Upbit-style `page=1, 2, ...`)
+    PageNumber { param: String, first: u64 },
+    /// `param=<cursor>` where the cursor of the next page is read from each response at
+    /// the JSON pointer `next` (Coinbase-style `after` cursors)
+    Cursor { param: String, next: String },
+}
+
+/// Walks every page of a paginated listing endpoint (see [Paginator::fetch_all]).
+///
+/// Pagination stops at an empty page, a page shorter than the page size (when one is
+/// set), a missing or repeated cursor, or a page identical to the previous one (venues
+/// that ignore the page parameter return the full listing every time).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Paginator {
+    style: PageStyle,
+    items: String,
+    page_size: Option<(String, usize)>,
+    max_pages: usize,
+}
+
+impl Paginator {
+    /// Default limit of [Paginator::with_max_pages].
+    pub const DEFAULT_MAX_PAGES: usize = 500;
+
+    /// Page-number pagination over `param`, starting at page `first`.
+    pub fn page_number(param: &str, first: u64) -> Self {
+        Self::new(PageStyle::PageNumber {
+            param: param.to_string(),
+            first,
+        })
+    }
+
+    /// Cursor pagination: the cursor at JSON pointer `next` of a response is sent as
+    /// `param` to fetch the following page.
+    pub fn cursor(param: &str, next: &str) -> Self {
+        Self::new(PageStyle::Cursor {
+            param: param.to_string(),
+            next: next.to_string(),
+        })
+    }
+
+    fn new(style: PageStyle) -> Self {
+        Self {
+            style,
+            items: String::new(),
+            page_size: None,
+            max_pages: Self::DEFAULT_MAX_PAGES,
+        }
+    }
+
+    /// JSON pointer of the item array in a response (default: the response itself).
+    pub fn with_items_at(mut self, pointer: &str) -> Self {
+        self.items = pointer.to_string();
+        self
+    }
+
+    /// Requests `size` items per page with `param`; a shorter page is the last one.
+    pub fn with_page_size(mut self, param: &str, size: usize) -> Self {
+        self.page_size = Some((param.to_string(), size));
+        self
+    }
+
+    /// Fails instead of fetching more than `max_pages` pages.
+    pub fn with_max_pages(mut self, max_pages: usize) -> Self {
+        self.max_pages = max_pages;
+        self
+    }
+
+    pub fn style(&self) -> &PageStyle {
+        &self.style
+    }
+
+    /// `endpoint` with the page size and the page (`None`: first cursor page) appended.
+    pub fn page_endpoint(&self, endpoint: &str, page: Option<&str>) -> String {
+        let mut params: Vec<String> = Vec::new();
+        if let Some((param, size)) = &self.page_size {
+            params.push(format!("{}={}", param, size));
+        }
+        match (&self.style, page) {
+            (PageStyle::PageNumber { param, first }, page) => {
+                params.push(format!("{}={}", param, page.unwrap_or(&first.to_string())));
+            }
+            (PageStyle::Cursor { param, .. }, Some(cursor)) => {
+                params.push(format!("{}={}", param, cursor));
+            }
+            (PageStyle::Cursor { .. }, None) => {}
+        }
+        if params.is_empty() {
+            return endpoint.to_string();
+        }
+        let separator = if endpoint.contains('?') { '&' } else { '?' };
+        format!("{}{}{}", endpoint, separator, params.join("&"))
+    }
+
+    /// Items of every page of `endpoint`, in page order. `fetch` GETs one page endpoint
+    /// (built by [Paginator::page_endpoint]) and returns its JSON body.
+    pub async fn fetch_all<F, Fut>(
+        &self,
+        endpoint: &str,
+        mut fetch: F,
+    ) -> Result<Vec<serde_json::Value>, MarketScannerError>
+    where
+        F: FnMut(String) -> Fut,
+        Fut: std::future::Future<Output = Result<serde_json::Value, MarketScannerError>>,
+    {
+        let mut items = Vec::new();
+        let mut page: Option<String> = None;
+        let mut previous: Option<Vec<serde_json::Value>> = None;
+        for fetched in 0..self.max_pages {
+            let body = fetch(self.page_endpoint(endpoint, page.as_deref())).await?;
+            let page_items = match body.pointer(&self.items) {
+                Some(serde_json::Value::Array(page_items)) => page_items.clone(),
+                Some(serde_json::Value::Null) | None => Vec::new(),
+                Some(_) => {
+                    return Err(MarketScannerError::ApiError(format!(
+                        "{} page has no item array at '{}'",
+                        endpoint, self.items
+                    )));
+                }
+            };
+            if page_items.is_empty() || previous.as_ref() == Some(&page_items) {
+                return Ok(items);
+            }
+            let short = self
+                .page_size
+                .as_ref()
+                .is_some_and(|(_, size)| page_items.len() < *size);
+            items.extend(page_items.iter().cloned());
+            previous = Some(page_items);
+            if short {
+                return Ok(items);
+            }
+
+            page = match &self.style {
+                PageStyle::PageNumber { first, .. } => {
+                    Some((first + fetched as u64 + 1).to_string())
+                }
+                PageStyle::Cursor { next, .. } => {
+                    let cursor = match body.pointer(next) {
+                        Some(serde_json::Value::String(cursor)) => cursor.clone(),
+                        Some(serde_json::Value::Number(cursor)) => cursor.to_string(),
+                        _ => String::new(),
+                    };
+                    if cursor.is_empty() || page.as_deref() == Some(cursor.as_str()) {
+                        return Ok(items);
+                    }
+                    Some(cursor)
+                }
+            };
+        }
+        Err(MarketScannerError::ApiError(format!(
+            "{} has more than {} pages",
+            endpoint, self.max_pages
+        )))
+    }
+}
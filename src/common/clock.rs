@@ -0,0 +1,108 @@
+//! Venue clock offsets.
+//!
+//! [ExchangeTrait::get_server_time] reads a venue's clock. [measure_clock_offset] samples
+//! it several times and keeps the sample with the shortest round trip, assuming the venue
+//! read its clock halfway through the request (as NTP does). The resulting [ClockOffset]
+//! converts venue timestamps to local time and back, and bounds the error by half the
+//! round trip.
+
+use crate::common::{ExchangeTrait, MarketScannerError, Timestamp};
+use serde::{Deserialize, Serialize};
+
+/// Offset of a venue clock from the local clock.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ClockOffset {
+    /// Venue time − local time (milliseconds); positive when the venue clock is ahead
+    pub offset_ms: i64,
+    /// Round trip of the sample the offset comes from; the offset is accurate to half of it
+    /// (plus the venue clock resolution)
+    pub round_trip_ms: u64,
+    /// Local time of the measurement
+    pub measured_at: Timestamp,
+}
+
+impl ClockOffset {
+    /// Offset of one sample: request sent at `sent_at`, `server_time` read from the
+    /// response received at `received_at` (all but `server_time` local).
+    pub fn from_sample(sent_at: Timestamp, server_time: Timestamp, received_at: Timestamp) -> Self {
+        let round_trip_ms = received_at.as_millis().saturating_sub(sent_at.as_millis());
+        let midpoint = sent_at.as_millis() + round_trip_ms / 2;
+        Self {
+            offset_ms: server_time.as_millis() as i64 - midpoint as i64,
+            round_trip_ms,
+            measured_at: received_at,
+        }
+    }
+
+    /// Local time of a venue timestamp.
+    pub fn to_local(&self, venue_time: Timestamp) -> Timestamp {
+        shift(venue_time, -self.offset_ms)
+    }
+
+    /// Venue time of a local timestamp.
+    pub fn to_venue(&self, local_time: Timestamp) -> Timestamp {
+        shift(local_time, self.offset_ms)
+    }
+}
+
+fn shift(timestamp: Timestamp, offset_ms: i64) -> Timestamp {
+    Timestamp::from_millis(timestamp.as_millis().saturating_add_signed(offset_ms))
+}
+
+/// Measures the clock offset of `venue` from `samples` server time requests (at least
+/// one), keeping the one with the shortest round trip. Fails only if every request fails.
+pub async fn measure_clock_offset<E: ExchangeTrait + ?Sized>(
+    venue: &E,
+    samples: usize,
+) -> Result<ClockOffset, MarketScannerError> {
+    let mut best: Option<ClockOffset> = None;
+    let mut last_error = None;
+    for _ in 0..samples.max(1) {
+        let sent_at = Timestamp::now();
+        match venue.get_server_time().await {
+            Ok(server_time) => {
+                let sample = ClockOffset::from_sample(sent_at, server_time, Timestamp::now());
+                if best.is_none_or(|best| sample.round_trip_ms < best.round_trip_ms) {
+                    best = Some(sample);
+                }
+            }
+            Err(e) => last_error = Some(e),
+        }
+    }
+    best.ok_or_else(|| last_error.expect("at least one sample"))
+}
+
+/// Server time at JSON `pointer` of `body`: epoch seconds (possibly fractional) or
+/// milliseconds, as a number or numeric string.
+pub(crate) fn json_server_time(
+    body: &serde_json::Value,
+    pointer: &str,
+    exchange_name: &str,
+) -> Result<Timestamp, MarketScannerError> {
+    let value = body
+        .pointer(pointer)
+        .and_then(|v| v.as_f64().or_else(|| v.as_str()?.parse().ok()))
+        .filter(|value| *value > 0.0);
+    value
+        .map(|value| {
+            if value < 10_000_000_000.0 {
+                Timestamp::from_millis((value * 1000.0).round() as u64)
+            } else {
+                Timestamp::from_epoch_guess(value as u64)
+            }
+        })
+        .ok_or_else(|| {
+            MarketScannerError::ApiError(format!(
+                "{} server time missing at {}: {}",
+                exchange_name, pointer, body
+            ))
+        })
+}
+
+/// Time of an HTTP `Date` header (RFC 2822, second resolution).
+pub(crate) fn parse_http_date(value: &str) -> Option<Timestamp> {
+    let date = chrono::DateTime::parse_from_rfc2822(value).ok()?;
+    u64::try_from(date.timestamp_millis())
+        .ok()
+        .map(Timestamp::from_millis)
+}
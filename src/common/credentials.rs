@@ -0,0 +1,300 @@
+//! API keys and secrets for authenticated venue endpoints.
+//!
+//! [Credentials] holds one venue's key, secret and optional passphrase; the strings are
+//! zeroed when the value is dropped and never printed by `Debug`. A [CredentialProvider]
+//! looks credentials up per [CexExchange]: [EnvCredentials] (`BINANCE_API_KEY`, ...),
+//! [FileCredentials] (a JSON file), [MemoryCredentials], or any custom implementation
+//! (vault, KMS). [CredentialStore] chains providers and returns the first match.
+
+use crate::common::{CexExchange, MarketScannerError};
+use async_trait::async_trait;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fmt;
+use std::path::Path;
+use std::sync::{Arc, RwLock};
+use zeroize::{Zeroize, Zeroizing};
+
+/// API key, secret and optional passphrase (OKX, Bitget, Kucoin) of one venue.
+#[derive(Clone, Deserialize)]
+pub struct Credentials {
+    api_key: String,
+    api_secret: String,
+    #[serde(default)]
+    passphrase: Option<String>,
+}
+
+impl Credentials {
+    pub fn new(api_key: impl Into<String>, api_secret: impl Into<String>) -> Self {
+        Self {
+            api_key: api_key.into(),
+            api_secret: api_secret.into(),
+            passphrase: None,
+        }
+    }
+
+    pub fn with_passphrase(mut self, passphrase: impl Into<String>) -> Self {
+        self.passphrase = Some(passphrase.into());
+        self
+    }
+
+    pub fn api_key(&self) -> &str {
+        &self.api_key
+    }
+
+    pub fn api_secret(&self) -> &str {
+        &self.api_secret
+    }
+
+    pub fn passphrase(&self) -> Option<&str> {
+        self.passphrase.as_deref()
+    }
+}
+
+impl Drop for Credentials {
+    fn drop(&mut self) {
+        self.api_key.zeroize();
+        self.api_secret.zeroize();
+        self.passphrase.zeroize();
+    }
+}
+
+impl fmt::Debug for Credentials {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Credentials")
+            .field("api_key", &"<redacted>")
+            .field("api_secret", &"<redacted>")
+            .field(
+                "passphrase",
+                &self.passphrase.as_ref().map(|_| "<redacted>"),
+            )
+            .finish()
+    }
+}
+
+/// Source of per-venue credentials.
+#[async_trait]
+pub trait CredentialProvider: Send + Sync {
+    /// Credentials of `exchange`; `Ok(None)` when this provider has none.
+    async fn credentials(
+        &self,
+        exchange: &CexExchange,
+    ) -> Result<Option<Credentials>, MarketScannerError>;
+}
+
+/// Upper-case venue name used in environment variables (`BINANCE`, `GATEIO`, `CRYPTOCOM`).
+fn env_name(exchange: &CexExchange) -> String {
+    format!("{:?}", exchange).to_uppercase()
+}
+
+/// Credentials from `{PREFIX}{EXCHANGE}_API_KEY`, `_API_SECRET` and `_API_PASSPHRASE`
+/// (e.g. `BINANCE_API_KEY`). Loads `.env` first. A venue with only one of key and secret
+/// set is an error.
+#[derive(Debug, Clone, Default)]
+pub struct EnvCredentials {
+    prefix: String,
+}
+
+impl EnvCredentials {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Prefix of every variable, e.g. `SCANNER_` for `SCANNER_BINANCE_API_KEY`.
+    pub fn with_prefix(prefix: &str) -> Self {
+        Self {
+            prefix: prefix.to_string(),
+        }
+    }
+
+    /// Name of the variable holding `field` (`API_KEY`, `API_SECRET`, `API_PASSPHRASE`).
+    pub fn var_name(&self, exchange: &CexExchange, field: &str) -> String {
+        format!("{}{}_{}", self.prefix, env_name(exchange), field)
+    }
+
+    fn var(&self, exchange: &CexExchange, field: &str) -> Option<String> {
+        std::env::var(self.var_name(exchange, field))
+            .ok()
+            .filter(|value| !value.is_empty())
+    }
+}
+
+#[async_trait]
+impl CredentialProvider for EnvCredentials {
+    async fn credentials(
+        &self,
+        exchange: &CexExchange,
+    ) -> Result<Option<Credentials>, MarketScannerError> {
+        crate::dex::load_dotenv();
+        // Built first so a half-set pair is zeroed on drop as well
+        let credentials = Credentials {
+            api_key: self.var(exchange, "API_KEY").unwrap_or_default(),
+            api_secret: self.var(exchange, "API_SECRET").unwrap_or_default(),
+            passphrase: self.var(exchange, "API_PASSPHRASE"),
+        };
+        match (
+            credentials.api_key.is_empty(),
+            credentials.api_secret.is_empty(),
+        ) {
+            (false, false) => Ok(Some(credentials)),
+            (true, true) => Ok(None),
+            _ => Err(MarketScannerError::InvalidConfig(format!(
+                "{} and {} must both be set",
+                self.var_name(exchange, "API_KEY"),
+                self.var_name(exchange, "API_SECRET")
+            ))),
+        }
+    }
+}
+
+/// Credentials read from a JSON file keyed by venue name (case-insensitive):
+///
+/// ```json
+/// { "binance": { "api_key": "...", "api_secret": "..." },
+///   "okx": { "api_key": "...", "api_secret": "...", "passphrase": "..." } }
+/// ```
+#[derive(Debug)]
+pub struct FileCredentials {
+    /// Lowercased venue name -> credentials
+    entries: HashMap<String, Credentials>,
+}
+
+impl FileCredentials {
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, MarketScannerError> {
+        let path = path.as_ref();
+        let contents = Zeroizing::new(std::fs::read_to_string(path).map_err(|e| {
+            MarketScannerError::InvalidConfig(format!(
+                "Cannot read credentials file {}: {}",
+                path.display(),
+                e
+            ))
+        })?);
+        Self::from_json(&contents)
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, MarketScannerError> {
+        let entries: HashMap<String, Credentials> = serde_json::from_str(json).map_err(|e| {
+            // Only the position: serde messages can quote the offending (secret) value
+            MarketScannerError::InvalidConfig(format!(
+                "Invalid credentials file at line {} column {}",
+                e.line(),
+                e.column()
+            ))
+        })?;
+        Ok(Self {
+            entries: entries
+                .into_iter()
+                .map(|(venue, credentials)| (venue.to_lowercase(), credentials))
+                .collect(),
+        })
+    }
+}
+
+#[async_trait]
+impl CredentialProvider for FileCredentials {
+    async fn credentials(
+        &self,
+        exchange: &CexExchange,
+    ) -> Result<Option<Credentials>, MarketScannerError> {
+        let venue = format!("{:?}", exchange).to_lowercase();
+        Ok(self.entries.get(&venue).cloned())
+    }
+}
+
+/// Credentials set in code (tests, secrets fetched at startup).
+#[derive(Debug, Default)]
+pub struct MemoryCredentials {
+    entries: RwLock<HashMap<CexExchange, Credentials>>,
+}
+
+impl MemoryCredentials {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the credentials of `exchange`, replacing (and zeroing) previous ones.
+    pub fn insert(&self, exchange: CexExchange, credentials: Credentials) {
+        self.entries
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(exchange, credentials);
+    }
+
+    /// Removes the credentials of `exchange`; returns whether there were any.
+    pub fn remove(&self, exchange: &CexExchange) -> bool {
+        self.entries
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .remove(exchange)
+            .is_some()
+    }
+}
+
+#[async_trait]
+impl CredentialProvider for MemoryCredentials {
+    async fn credentials(
+        &self,
+        exchange: &CexExchange,
+    ) -> Result<Option<Credentials>, MarketScannerError> {
+        Ok(self
+            .entries
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(exchange)
+            .cloned())
+    }
+}
+
+/// Ordered chain of providers; the first one with credentials for a venue wins.
+#[derive(Clone, Default)]
+pub struct CredentialStore {
+    providers: Vec<Arc<dyn CredentialProvider>>,
+}
+
+impl CredentialStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Store reading only the environment ([EnvCredentials] without prefix).
+    pub fn from_env() -> Self {
+        Self::new().with_provider(EnvCredentials::new())
+    }
+
+    /// Appends `provider`; it is asked after the providers added before it.
+    pub fn with_provider(mut self, provider: impl CredentialProvider + 'static) -> Self {
+        self.providers.push(Arc::new(provider));
+        self
+    }
+
+    /// Credentials of `exchange` from the first provider that has them.
+    pub async fn lookup(
+        &self,
+        exchange: &CexExchange,
+    ) -> Result<Option<Credentials>, MarketScannerError> {
+        for provider in &self.providers {
+            if let Some(credentials) = provider.credentials(exchange).await? {
+                return Ok(Some(credentials));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Like [CredentialStore::lookup], but missing credentials are an error.
+    pub async fn get(&self, exchange: &CexExchange) -> Result<Credentials, MarketScannerError> {
+        self.lookup(exchange).await?.ok_or_else(|| {
+            MarketScannerError::InvalidConfig(format!(
+                "No credentials configured for {:?}",
+                exchange
+            ))
+        })
+    }
+}
+
+impl fmt::Debug for CredentialStore {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CredentialStore")
+            .field("providers", &self.providers.len())
+            .finish()
+    }
+}
@@ -0,0 +1,216 @@
+//! Order book synchronization from a REST snapshot and WebSocket deltas.
+//!
+//! Venues such as Binance publish depth as a REST snapshot tagged with an update id plus a
+//! WebSocket stream of deltas, each covering a range of update ids. [OrderBookSynchronizer]
+//! buffers deltas until a snapshot arrives, drops the ones the snapshot already contains,
+//! then applies the rest in sequence. A gap in the update ids resets the book and asks for
+//! a new snapshot, so the exposed book is always consistent.
+
+use crate::common::{Exchange, OrderBook, OrderBookLevel, Timestamp};
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, VecDeque};
+
+/// Deltas kept while waiting for a snapshot; older ones are dropped (the next snapshot
+/// then reports a gap and is fetched again).
+pub const MAX_BUFFERED_DEPTH_DELTAS: usize = 1000;
+
+/// REST order book snapshot with the id of the last update it contains.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DepthSnapshot {
+    pub last_update_id: u64,
+    pub bids: Vec<OrderBookLevel>,
+    pub asks: Vec<OrderBookLevel>,
+}
+
+/// WebSocket depth delta covering update ids `first_update_id..=final_update_id`.
+/// A level with zero quantity removes that price.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DepthDelta {
+    pub first_update_id: u64,
+    pub final_update_id: u64,
+    pub bids: Vec<OrderBookLevel>,
+    pub asks: Vec<OrderBookLevel>,
+}
+
+/// Outcome of feeding a snapshot or delta to an [OrderBookSynchronizer].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DepthSyncStatus {
+    /// The book is in sync and reflects the update
+    Applied,
+    /// No snapshot yet; the delta was buffered
+    Buffered,
+    /// The delta is older than the book and was ignored
+    Stale,
+    /// Update ids do not line up; the book was reset and a new snapshot is needed
+    Gap,
+}
+
+/// Price key with a total order, so levels can live in a [BTreeMap].
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct PriceKey(pub(crate) f64);
+
+impl PartialEq for PriceKey {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for PriceKey {}
+
+impl PartialOrd for PriceKey {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for PriceKey {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.total_cmp(&other.0)
+    }
+}
+
+/// Maintains one order book from a REST snapshot and WebSocket deltas.
+#[derive(Debug, Clone)]
+pub struct OrderBookSynchronizer {
+    symbol: String,
+    exchange: Exchange,
+    bids: BTreeMap<PriceKey, f64>,
+    asks: BTreeMap<PriceKey, f64>,
+    /// Last applied update id; `None` until a snapshot lines up with the deltas
+    last_update_id: Option<u64>,
+    buffer: VecDeque<DepthDelta>,
+}
+
+impl OrderBookSynchronizer {
+    pub fn new(symbol: &str, exchange: Exchange) -> Self {
+        Self {
+            symbol: symbol.to_string(),
+            exchange,
+            bids: BTreeMap::new(),
+            asks: BTreeMap::new(),
+            last_update_id: None,
+            buffer: VecDeque::new(),
+        }
+    }
+
+    pub fn is_synced(&self) -> bool {
+        self.last_update_id.is_some()
+    }
+
+    pub fn last_update_id(&self) -> Option<u64> {
+        self.last_update_id
+    }
+
+    pub fn buffered_deltas(&self) -> usize {
+        self.buffer.len()
+    }
+
+    /// Feeds a WebSocket delta. Before the first snapshot, and after a gap, deltas are
+    /// buffered for the next [OrderBookSynchronizer::apply_snapshot].
+    pub fn push_delta(&mut self, delta: DepthDelta) -> DepthSyncStatus {
+        let Some(last) = self.last_update_id else {
+            if self.buffer.len() == MAX_BUFFERED_DEPTH_DELTAS {
+                self.buffer.pop_front();
+            }
+            self.buffer.push_back(delta);
+            return DepthSyncStatus::Buffered;
+        };
+        if delta.final_update_id <= last {
+            return DepthSyncStatus::Stale;
+        }
+        if delta.first_update_id != last + 1 {
+            self.reset();
+            self.buffer.push_back(delta);
+            return DepthSyncStatus::Gap;
+        }
+        self.apply(&delta);
+        DepthSyncStatus::Applied
+    }
+
+    /// Replaces the book with `snapshot` and replays the buffered deltas it does not
+    /// contain. Returns [DepthSyncStatus::Gap] (keeping the buffer) when the first newer
+    /// delta does not continue the snapshot, i.e. another snapshot is needed.
+    pub fn apply_snapshot(&mut self, snapshot: DepthSnapshot) -> DepthSyncStatus {
+        let last = snapshot.last_update_id;
+        self.buffer.retain(|delta| delta.final_update_id > last);
+        if let Some(first) = self.buffer.front() {
+            if first.first_update_id > last + 1 {
+                self.clear_book();
+                return DepthSyncStatus::Gap;
+            }
+        }
+
+        self.clear_book();
+        for level in snapshot.bids {
+            set_level(&mut self.bids, level);
+        }
+        for level in snapshot.asks {
+            set_level(&mut self.asks, level);
+        }
+        self.last_update_id = Some(last);
+
+        let buffered: Vec<DepthDelta> = self.buffer.drain(..).collect();
+        let mut deltas = buffered.into_iter();
+        if let Some(first) = deltas.next() {
+            // The first delta straddles the snapshot; later ones must be contiguous
+            self.apply(&first);
+        }
+        while let Some(delta) = deltas.next() {
+            if self.push_delta(delta) == DepthSyncStatus::Gap {
+                // The gap reset the buffer to the delta that broke the sequence; keep the
+                // ones after it for the next snapshot
+                self.buffer.extend(deltas);
+                return DepthSyncStatus::Gap;
+            }
+        }
+        DepthSyncStatus::Applied
+    }
+
+    /// Up to `depth` levels per side, or `None` while the book is not in sync.
+    pub fn book(&self, depth: usize) -> Option<OrderBook> {
+        if !self.is_synced() {
+            return None;
+        }
+        let level = |(price, quantity): (&PriceKey, &f64)| OrderBookLevel {
+            price: price.0,
+            quantity: *quantity,
+        };
+        Some(OrderBook {
+            symbol: self.symbol.clone(),
+            bids: self.bids.iter().rev().take(depth).map(level).collect(),
+            asks: self.asks.iter().take(depth).map(level).collect(),
+            timestamp: Timestamp::now(),
+            exchange: self.exchange.clone(),
+        })
+    }
+
+    /// Drops the book and all buffered deltas.
+    pub fn reset(&mut self) {
+        self.clear_book();
+        self.buffer.clear();
+    }
+
+    fn clear_book(&mut self) {
+        self.bids.clear();
+        self.asks.clear();
+        self.last_update_id = None;
+    }
+
+    fn apply(&mut self, delta: &DepthDelta) {
+        for level in &delta.bids {
+            set_level(&mut self.bids, *level);
+        }
+        for level in &delta.asks {
+            set_level(&mut self.asks, *level);
+        }
+        self.last_update_id = Some(delta.final_update_id);
+    }
+}
+
+fn set_level(side: &mut BTreeMap<PriceKey, f64>, level: OrderBookLevel) {
+    if level.quantity == 0.0 {
+        side.remove(&PriceKey(level.price));
+    } else {
+        side.insert(PriceKey(level.price), level.quantity);
+    }
+}
@@ -0,0 +1,110 @@
+//! Global dry-run mode for order and transaction sending.
+//!
+//! With [set_dry_run] enabled, CEX order actions
+//! ([crate::cex::execution::CexOrderExecution]) and DEX transaction submission
+//! ([crate::dex::execution::ExecutionWallet], [crate::dex::execution::PrivateRelay]) build
+//! and sign every request as usual but transmit nothing. Each would-be request is recorded
+//! in [dry_run_log] (and traced at info level with the `tracing` feature) and a synthetic
+//! id is returned: `dryrun-<n>` for CEX orders, the hash the signed transaction or bundle
+//! would have had for DEX submissions. Header values (keys, signatures) are never recorded.
+//! Read-only calls (balances, nonces, gas estimates, order status of real orders) still
+//! reach the venue or node.
+
+use crate::common::time::Timestamp;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{LazyLock, RwLock};
+
+/// Prefix of synthetic CEX order ids.
+pub const DRY_RUN_ID_PREFIX: &str = "dryrun-";
+
+/// Records kept by [dry_run_log]; the oldest are dropped first.
+pub const MAX_DRY_RUN_RECORDS: usize = 1000;
+
+static DRY_RUN_ENABLED: AtomicBool = AtomicBool::new(false);
+
+static NEXT_DRY_RUN_ID: AtomicU64 = AtomicU64::new(1);
+
+static DRY_RUN_LOG: LazyLock<RwLock<VecDeque<DryRunRecord>>> =
+    LazyLock::new(|| RwLock::new(VecDeque::new()));
+
+/// Action a dry-run request stands for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DryRunAction {
+    PlaceOrder,
+    CancelOrder,
+    SendTransaction,
+    SendBundle,
+}
+
+/// A request that dry-run mode kept from being sent.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DryRunRecord {
+    /// Synthetic id returned to the caller
+    pub id: String,
+    /// Venue (e.g. "OKX") or chain / relay the request targeted
+    pub target: String,
+    pub action: DryRunAction,
+    /// Method and URL plus body of CEX requests; hex of the signed transaction or bundle
+    /// body of DEX submissions
+    pub request: String,
+    pub timestamp: Timestamp,
+}
+
+/// Enables or disables dry-run mode (disabled by default).
+pub fn set_dry_run(enabled: bool) {
+    DRY_RUN_ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+pub fn dry_run_enabled() -> bool {
+    DRY_RUN_ENABLED.load(Ordering::Relaxed)
+}
+
+/// Whether `order_id` was returned by a dry-run order.
+pub fn is_dry_run_id(order_id: &str) -> bool {
+    order_id.starts_with(DRY_RUN_ID_PREFIX)
+}
+
+/// Requests recorded in dry-run mode, oldest first.
+pub fn dry_run_log() -> Vec<DryRunRecord> {
+    let log = DRY_RUN_LOG.read().unwrap_or_else(|e| e.into_inner());
+    log.iter().cloned().collect()
+}
+
+pub fn clear_dry_run_log() {
+    DRY_RUN_LOG
+        .write()
+        .unwrap_or_else(|e| e.into_inner())
+        .clear();
+}
+
+/// Records a request that was not sent and returns its id (`id`, or a new `dryrun-<n>`).
+pub(crate) fn record_dry_run(
+    target: &str,
+    action: DryRunAction,
+    request: String,
+    id: Option<String>,
+) -> String {
+    let id = id.unwrap_or_else(|| {
+        format!(
+            "{}{}",
+            DRY_RUN_ID_PREFIX,
+            NEXT_DRY_RUN_ID.fetch_add(1, Ordering::Relaxed)
+        )
+    });
+    #[cfg(feature = "tracing")]
+    tracing::info!(id = %id, to = %target, action = ?action, request = %request, "dry run");
+    let mut log = DRY_RUN_LOG.write().unwrap_or_else(|e| e.into_inner());
+    if log.len() >= MAX_DRY_RUN_RECORDS {
+        log.pop_front();
+    }
+    log.push_back(DryRunRecord {
+        id: id.clone(),
+        target: target.to_string(),
+        action,
+        request,
+        timestamp: Timestamp::now(),
+    });
+    id
+}
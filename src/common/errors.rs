@@ -17,4 +17,60 @@ pub enum MarketScannerError {
 
     #[error("WebSocket / RPC error: {0}")]
     WsRpcError(String),
+
+    #[error("Unsupported chain: {0}")]
+    UnsupportedChain(String),
+
+    #[error("Invalid configuration: {0}")]
+    InvalidConfig(String),
+
+    #[error("Risk limit exceeded: {0}")]
+    RiskLimitExceeded(#[from] crate::risk::RiskViolation),
+}
+
+impl MarketScannerError {
+    /// HTTP status of a failed request, read from the transport error or from a venue
+    /// error message (`"<venue> API error: <status> - <body>"`).
+    pub fn http_status(&self) -> Option<u16> {
+        match self {
+            MarketScannerError::HttpError(e) => e.status().map(|status| status.as_u16()),
+            MarketScannerError::ApiError(message) => {
+                let (_, rest) = message.split_once("API error: ")?;
+                let code = rest.get(..3)?;
+                let followed_by_digit = rest[3..].starts_with(|c: char| c.is_ascii_digit());
+                if followed_by_digit {
+                    return None;
+                }
+                code.parse().ok().filter(|code| (100..600).contains(code))
+            }
+            _ => None,
+        }
+    }
+
+    /// Whether the same request may succeed later: rate limits (429), request timeouts
+    /// (408), server errors (5xx), timeouts and dropped connections. Other 4xx responses,
+    /// unknown symbols, parse errors and invalid configuration are permanent, as are venue
+    /// errors without a status that do not mention a timeout or rate limit.
+    pub fn is_retryable(&self) -> bool {
+        if let Some(status) = self.http_status() {
+            return matches!(status, 408 | 429 | 500..=599);
+        }
+        match self {
+            MarketScannerError::HealthCheckFailed | MarketScannerError::WsRpcError(_) => true,
+            MarketScannerError::HttpError(e) => {
+                e.is_timeout() || e.is_connect() || e.is_request() || e.is_body()
+            }
+            MarketScannerError::ApiError(message) => {
+                let message = message.to_lowercase();
+                ["timeout", "timed out", "rate limit", "too many requests"]
+                    .iter()
+                    .any(|hint| message.contains(hint))
+            }
+            MarketScannerError::ParseError(_)
+            | MarketScannerError::InvalidSymbol(_)
+            | MarketScannerError::UnsupportedChain(_)
+            | MarketScannerError::InvalidConfig(_)
+            | MarketScannerError::RiskLimitExceeded(_) => false,
+        }
+    }
 }
@@ -1,5 +1,15 @@
-use crate::common::{CexPrice, DexPrice, MarketScannerError};
+use crate::common::capabilities::{Capabilities, VenueCapability};
+use crate::common::client::rest_retry_policy;
+use crate::common::clock::parse_http_date;
+use crate::common::ladder::{DexLadderPoint, DexPriceLadder};
+use crate::common::rate_limit::{RequestPriority, acquire_request_slot};
+use crate::common::rest_diagnostics::send_recorded;
+use crate::common::{
+    AmountSpec, CexPrice, DexPrice, DexSourceFilter, MarketScannerError, OrderBook, OrderLimits,
+    Paginator, Timestamp,
+};
 use async_trait::async_trait;
+use futures::future::join_all;
 use serde::{Deserialize, Serialize};
 
 // Common exchange enum definition
@@ -28,6 +38,47 @@ pub enum CexExchange {
     Cryptocom,
 }
 
+impl CexExchange {
+    /// Every supported centralized exchange.
+    pub const ALL: [CexExchange; 14] = [
+        CexExchange::Binance,
+        CexExchange::Bybit,
+        CexExchange::MEXC,
+        CexExchange::OKX,
+        CexExchange::Gateio,
+        CexExchange::Kucoin,
+        CexExchange::Bitget,
+        CexExchange::Btcturk,
+        CexExchange::Htx,
+        CexExchange::Coinbase,
+        CexExchange::Kraken,
+        CexExchange::Bitfinex,
+        CexExchange::Upbit,
+        CexExchange::Cryptocom,
+    ];
+
+    /// Venue name of the REST clients ([ExchangeTrait::exchange_name]), which their requests
+    /// are recorded under (see [crate::common::rest_connection_stats]).
+    pub fn rest_venue_name(&self) -> &'static str {
+        match self {
+            CexExchange::Binance => "Binance",
+            CexExchange::Bybit => "Bybit",
+            CexExchange::MEXC => "Mexc",
+            CexExchange::OKX => "OKX",
+            CexExchange::Gateio => "Gate.io",
+            CexExchange::Kucoin => "KuCoin",
+            CexExchange::Bitget => "Bitget",
+            CexExchange::Btcturk => "BTCTurk",
+            CexExchange::Htx => "HTX",
+            CexExchange::Coinbase => "Coinbase",
+            CexExchange::Kraken => "Kraken",
+            CexExchange::Bitfinex => "Bitfinex",
+            CexExchange::Upbit => "Upbit",
+            CexExchange::Cryptocom => "Crypto.com",
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
 pub enum DexAggregator {
     KyberSwap,
@@ -42,26 +93,105 @@ pub trait ExchangeTrait: Send + Sync {
     fn exchange_name(&self) -> &str;
 
     // Default implementations
+    /// GET `endpoint` as a [RequestPriority::Price] request.
     async fn get<T: for<'de> serde::Deserialize<'de>>(
         &self,
         endpoint: &str,
+    ) -> Result<T, MarketScannerError> {
+        self.get_with_priority(endpoint, RequestPriority::Price)
+            .await
+    }
+
+    /// GET `endpoint` once the venue's rate limit (see [crate::common::set_rate_limit])
+    /// grants a slot to a request of `priority`. Retryable failures are retried under the
+    /// [crate::common::rest_retry_policy], each retry waiting for a new slot.
+    async fn get_with_priority<T: for<'de> serde::Deserialize<'de>>(
+        &self,
+        endpoint: &str,
+        priority: RequestPriority,
+    ) -> Result<T, MarketScannerError> {
+        let url = format!("{}/{}", self.api_base(), endpoint);
+        let policy = rest_retry_policy();
+        let mut retries = 0;
+        loop {
+            let error = match get_json(self, &url, priority).await {
+                Ok(value) => return Ok(value),
+                Err(error) => error,
+            };
+            if !policy.should_retry(&error, retries) {
+                return Err(error);
+            }
+            retries += 1;
+            tokio::time::sleep(policy.delay(retries)).await;
+        }
+    }
+
+    /// GET through the shared HTTP cache (see [crate::common::cached_get]), for
+    /// semi-static endpoints such as exchange info or symbol listings. The TTL comes from
+    /// [crate::common::http_cache_ttl]. Requests that reach the venue are
+    /// [RequestPriority::Metadata] requests.
+    async fn get_cached<T: for<'de> serde::Deserialize<'de>>(
+        &self,
+        endpoint: &str,
     ) -> Result<T, MarketScannerError> {
         let url = format!("{}/{}", self.api_base(), endpoint);
-        let response = self.client().get(&url).send().await?;
+        let body = crate::common::client::cached_get(
+            self.client(),
+            &url,
+            crate::common::client::http_cache_ttl(endpoint),
+            self.exchange_name(),
+        )
+        .await?;
+        serde_json::from_str(&body).map_err(MarketScannerError::from)
+    }
 
-        let status = response.status();
+    /// Items of every page of a paginated listing `endpoint` (see [Paginator]), fetched as
+    /// [RequestPriority::Metadata] requests.
+    async fn get_all_pages(
+        &self,
+        endpoint: &str,
+        paginator: &Paginator,
+    ) -> Result<Vec<serde_json::Value>, MarketScannerError> {
+        paginator
+            .fetch_all(endpoint, |page| async move {
+                self.get_with_priority(&page, RequestPriority::Metadata)
+                    .await
+            })
+            .await
+    }
 
-        if !status.is_success() {
-            let error_text = response.text().await.unwrap_or_default();
-            return Err(MarketScannerError::ApiError(format!(
-                "{} API error: {} - {}",
-                self.exchange_name(),
-                status,
-                error_text
-            )));
-        }
+    /// Time of the HTTP `Date` header of a GET of `endpoint` (second resolution), for
+    /// venues without a server time endpoint.
+    async fn get_date_header(&self, endpoint: &str) -> Result<Timestamp, MarketScannerError> {
+        let url = format!("{}/{}", self.api_base(), endpoint);
+        let queued_at = std::time::Instant::now();
+        acquire_request_slot(self.exchange_name(), RequestPriority::Metadata).await;
+        let response = send_recorded(
+            self.exchange_name(),
+            self.client().get(&url),
+            queued_at.elapsed(),
+        )
+        .await?;
+        response
+            .headers()
+            .get(reqwest::header::DATE)
+            .and_then(|value| value.to_str().ok())
+            .and_then(parse_http_date)
+            .ok_or_else(|| {
+                MarketScannerError::ApiError(format!(
+                    "{} response has no valid Date header",
+                    self.exchange_name()
+                ))
+            })
+    }
 
-        Ok(response.json().await?)
+    /// Current time of the venue's clock (see [crate::common::measure_clock_offset]).
+    /// Default: returns error if this venue does not expose its time.
+    async fn get_server_time(&self) -> Result<Timestamp, MarketScannerError> {
+        Err(MarketScannerError::ApiError(format!(
+            "{} does not expose server time",
+            self.exchange_name()
+        )))
     }
 
     // Trait methods
@@ -74,8 +204,56 @@ pub trait CEXTrait: ExchangeTrait {
     /// Whether this CEX supports fetching price via WebSocket (same format as [get_price]).
     fn supports_websocket(&self) -> bool;
 
+    /// Features this venue implements (see [crate::common::capabilities]).
+    /// Default: WebSocket prices if [CEXTrait::supports_websocket], nothing else.
+    fn capabilities(&self) -> Capabilities {
+        let capabilities = Capabilities::default();
+        if self.supports_websocket() {
+            capabilities.with(VenueCapability::Websocket)
+        } else {
+            capabilities
+        }
+    }
+
     async fn get_price(&self, symbol: &str) -> Result<CexPrice, MarketScannerError>;
 
+    /// Order book snapshot with up to `depth` levels per side (REST).
+    /// Default: returns error if this exchange does not expose order book depth.
+    async fn get_order_book(
+        &self,
+        symbol: &str,
+        depth: usize,
+    ) -> Result<OrderBook, MarketScannerError> {
+        let _ = symbol;
+        let _ = depth;
+        Err(MarketScannerError::ApiError(format!(
+            "{} does not support order book depth",
+            self.exchange_name()
+        )))
+    }
+
+    /// Minimum order quantity and notional of `symbol` (the venue's exchange filters).
+    /// Default: returns error if this exchange does not expose order limits.
+    async fn get_order_limits(&self, symbol: &str) -> Result<OrderLimits, MarketScannerError> {
+        let _ = symbol;
+        Err(MarketScannerError::ApiError(format!(
+            "{} does not expose order limits",
+            self.exchange_name()
+        )))
+    }
+
+    /// Standard symbol → venue instrument id of every market listed by the venue's exchange
+    /// info (see [crate::common::record_instrument_ids]).
+    /// Default: returns error if this exchange does not list its instruments.
+    async fn get_instrument_ids(
+        &self,
+    ) -> Result<std::collections::HashMap<String, String>, MarketScannerError> {
+        Err(MarketScannerError::ApiError(format!(
+            "{} does not list instrument ids",
+            self.exchange_name()
+        )))
+    }
+
     /// Continuous price feed: connection stays open, CexPrice is sent over the channel.
     /// Subscribes to all given symbols; each update includes the symbol in CexPrice.
     /// When the receiver returns None, the connection has closed.
@@ -96,6 +274,28 @@ pub trait CEXTrait: ExchangeTrait {
             self.exchange_name()
         )))
     }
+
+    /// Continuous order book of `symbol` with up to `depth` levels per side, kept in sync
+    /// from a REST snapshot and WebSocket deltas (see [crate::common::OrderBookSynchronizer]).
+    /// A book is sent after every applied delta. Reconnect and delay work as in
+    /// [CEXTrait::stream_price_websocket].
+    /// Default: returns error if this exchange does not support order book streaming.
+    async fn stream_order_book(
+        &self,
+        symbol: &str,
+        depth: usize,
+        reconnect_attempts: u32,
+        reconnect_delay_ms: u64,
+    ) -> Result<tokio::sync::mpsc::Receiver<OrderBook>, MarketScannerError> {
+        let _ = symbol;
+        let _ = depth;
+        let _ = reconnect_attempts;
+        let _ = reconnect_delay_ms;
+        Err(MarketScannerError::ApiError(format!(
+            "{} does not support order book streaming",
+            self.exchange_name()
+        )))
+    }
 }
 
 #[async_trait]
@@ -106,6 +306,82 @@ pub trait DEXTrait: ExchangeTrait {
         quote_token: &crate::dex::chains::Token,
         quote_amount: f64,
     ) -> Result<DexPrice, MarketScannerError>;
+
+    /// Quotes an exact base or quote amount. [AmountSpec::Quote] is the same as
+    /// [DEXTrait::get_price]; aggregators that cannot size by base amount return an error
+    /// for [AmountSpec::Base].
+    async fn get_price_for_amount(
+        &self,
+        base_token: &crate::dex::chains::Token,
+        quote_token: &crate::dex::chains::Token,
+        amount: AmountSpec,
+    ) -> Result<DexPrice, MarketScannerError> {
+        match amount {
+            AmountSpec::Quote(quote_amount) => {
+                self.get_price(base_token, quote_token, quote_amount).await
+            }
+            AmountSpec::Base(_) => Err(MarketScannerError::ApiError(format!(
+                "{} does not support quoting by base amount",
+                self.exchange_name()
+            ))),
+        }
+    }
+
+    /// [DEXTrait::get_price_for_amount] routed only through the sources `sources` allows.
+    /// Aggregators without source selection accept only the default filter and return an
+    /// error for any other.
+    async fn get_price_with_sources(
+        &self,
+        base_token: &crate::dex::chains::Token,
+        quote_token: &crate::dex::chains::Token,
+        amount: AmountSpec,
+        sources: &DexSourceFilter,
+    ) -> Result<DexPrice, MarketScannerError> {
+        if *sources != DexSourceFilter::default() {
+            return Err(MarketScannerError::InvalidConfig(format!(
+                "{} does not support liquidity source filters",
+                self.exchange_name()
+            )));
+        }
+        self.get_price_for_amount(base_token, quote_token, amount)
+            .await
+    }
+
+    /// Quotes every size of `quote_amounts` concurrently (see
+    /// [crate::common::DEFAULT_LADDER_SIZES]). Sizes that fail to quote are left out of the
+    /// ladder; the first error is returned only if no size quotes.
+    async fn get_price_ladder(
+        &self,
+        base_token: &crate::dex::chains::Token,
+        quote_token: &crate::dex::chains::Token,
+        quote_amounts: &[f64],
+    ) -> Result<DexPriceLadder, MarketScannerError> {
+        let results = join_all(quote_amounts.iter().map(|&quote_amount| async move {
+            self.get_price(base_token, quote_token, quote_amount)
+                .await
+                .map(|price| DexLadderPoint {
+                    quote_amount,
+                    price,
+                })
+        }))
+        .await;
+
+        let mut points = Vec::new();
+        let mut first_error = None;
+        for result in results {
+            match result {
+                Ok(point) => points.push(point),
+                Err(e) => {
+                    first_error.get_or_insert(e);
+                }
+            }
+        }
+        DexPriceLadder::new(points).ok_or_else(|| {
+            first_error.unwrap_or_else(|| {
+                MarketScannerError::ApiError("No ladder sizes given".to_string())
+            })
+        })
+    }
 }
 
 // CEX MACRO EXPORTS
@@ -113,6 +389,11 @@ pub trait DEXTrait: ExchangeTrait {
 macro_rules! create_exchange {
     (
         $struct_name:ident
+    ) => {
+        $crate::create_exchange!($struct_name, $crate::common::HttpClientConfig::new());
+    };
+    (
+        $struct_name:ident, $default_http_config:expr
     ) => {
         pub struct $struct_name {
             client: reqwest::Client,
@@ -120,10 +401,55 @@ macro_rules! create_exchange {
 
         impl $struct_name {
             pub fn new() -> Self {
-                Self {
-                    client: $crate::common::create_http_client(),
-                }
+                Self::with_http_config($crate::common::HttpClientConfig::new())
+                    .expect("Failed to create HTTP client")
+            }
+
+            /// Headers and timeout this venue sends by default.
+            pub fn default_http_config() -> $crate::common::HttpClientConfig {
+                $default_http_config
+            }
+
+            /// Client with `config` applied on top of [Self::default_http_config], e.g. a
+            /// custom User-Agent or an API-key header.
+            pub fn with_http_config(
+                config: $crate::common::HttpClientConfig,
+            ) -> Result<Self, $crate::common::MarketScannerError> {
+                let config = Self::default_http_config().merge(config);
+                Ok(Self {
+                    client: $crate::common::create_http_client_with_config(&config)?,
+                })
             }
         }
     };
 }
+
+/// One GET of `url` through the venue's rate limit, decoding the JSON body.
+async fn get_json<E: ExchangeTrait + ?Sized, T: for<'de> serde::Deserialize<'de>>(
+    exchange: &E,
+    url: &str,
+    priority: RequestPriority,
+) -> Result<T, MarketScannerError> {
+    let queued_at = std::time::Instant::now();
+    acquire_request_slot(exchange.exchange_name(), priority).await;
+    let response = send_recorded(
+        exchange.exchange_name(),
+        exchange.client().get(url),
+        queued_at.elapsed(),
+    )
+    .await?;
+
+    let status = response.status();
+
+    if !status.is_success() {
+        let error_text = response.text().await.unwrap_or_default();
+        return Err(MarketScannerError::ApiError(format!(
+            "{} API error: {} - {}",
+            exchange.exchange_name(),
+            status,
+            error_text
+        )));
+    }
+
+    Ok(response.json().await?)
+}
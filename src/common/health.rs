@@ -0,0 +1,286 @@
+//! Per-venue health telemetry collected by the WebSocket clients.
+//!
+//! Venues that answer pings (OKX, Kucoin, MEXC) record the measured round-trip time of
+//! each ping/pong exchange here; [venue_health] exposes the resulting latency gauge so
+//! operators can compare links or flag degraded ones.
+//!
+//! Every stream also counts the messages it failed to decode (JSON or protobuf). A venue
+//! changing its protocol shows up as a rising decode error rate; crossing the warning
+//! threshold emits a [DecodeWarning] to [subscribe_decode_warnings] subscribers.
+//!
+//! Price streams also count their successful WebSocket connections, so reconnects of a
+//! long-running stream show up as [VenueHealth::ws_reconnects].
+
+use crate::common::exchange::CexExchange;
+use crate::common::latency::mark_ws_frame_received;
+use crate::common::time::Timestamp;
+use crate::common::ws_capture::capture_ws_text;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::{LazyLock, RwLock};
+use std::time::Duration;
+use tokio::sync::broadcast;
+
+/// Weight of the newest sample in the smoothed latency.
+const LATENCY_EWMA_ALPHA: f64 = 0.2;
+
+/// Weight of the newest message in the smoothed decode error rate.
+const DECODE_ERROR_EWMA_ALPHA: f64 = 0.05;
+
+/// Messages a venue must have seen before its decode error rate can raise a warning.
+const DECODE_WARNING_MIN_MESSAGES: u64 = 20;
+
+/// Decode error rate (0..=1) above which a [DecodeWarning] is emitted by default.
+pub const DEFAULT_DECODE_WARNING_THRESHOLD: f64 = 0.2;
+
+static VENUE_HEALTH: LazyLock<RwLock<HashMap<CexExchange, VenueHealth>>> =
+    LazyLock::new(|| RwLock::new(HashMap::new()));
+
+static DECODE_WARNING_THRESHOLD: RwLock<f64> = RwLock::new(DEFAULT_DECODE_WARNING_THRESHOLD);
+
+static DECODE_WARNINGS: LazyLock<broadcast::Sender<DecodeWarning>> =
+    LazyLock::new(|| broadcast::channel(64).0);
+
+/// WebSocket round-trip latency gauge of a venue (milliseconds).
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct LatencyGauge {
+    /// Most recent ping round-trip time
+    pub last_ms: f64,
+    /// Exponentially weighted moving average of the round-trip time
+    pub avg_ms: f64,
+    /// Lowest round-trip time observed
+    pub min_ms: f64,
+    /// Highest round-trip time observed
+    pub max_ms: f64,
+    /// Number of samples recorded
+    pub samples: u64,
+}
+
+impl LatencyGauge {
+    fn record(&mut self, rtt_ms: f64) {
+        if self.samples == 0 {
+            self.avg_ms = rtt_ms;
+            self.min_ms = rtt_ms;
+            self.max_ms = rtt_ms;
+        } else {
+            self.avg_ms += LATENCY_EWMA_ALPHA * (rtt_ms - self.avg_ms);
+            self.min_ms = self.min_ms.min(rtt_ms);
+            self.max_ms = self.max_ms.max(rtt_ms);
+        }
+        self.last_ms = rtt_ms;
+        self.samples += 1;
+    }
+}
+
+/// WebSocket message decode counters of a venue.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct DecodeStats {
+    /// Messages decoded successfully
+    pub decoded: u64,
+    /// Messages that failed to decode
+    pub failures: u64,
+    /// Exponentially weighted share of failed messages (0..=1)
+    pub error_rate: f64,
+    /// Whether the error rate is currently above the warning threshold
+    pub warning: bool,
+}
+
+impl DecodeStats {
+    pub fn messages(&self) -> u64 {
+        self.decoded + self.failures
+    }
+
+    fn record(&mut self, ok: bool) {
+        let sample = if ok { 0.0 } else { 1.0 };
+        if self.messages() == 0 {
+            self.error_rate = sample;
+        } else {
+            self.error_rate += DECODE_ERROR_EWMA_ALPHA * (sample - self.error_rate);
+        }
+        if ok {
+            self.decoded += 1;
+        } else {
+            self.failures += 1;
+        }
+    }
+}
+
+/// Emitted when a venue's decode error rate rises above the warning threshold.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DecodeWarning {
+    pub exchange: CexExchange,
+    /// Smoothed error rate at the time of the warning (0..=1)
+    pub error_rate: f64,
+    pub failures: u64,
+    pub messages: u64,
+    pub timestamp: Timestamp,
+}
+
+/// Health snapshot of a venue.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct VenueHealth {
+    pub exchange: CexExchange,
+    /// WebSocket ping latency; `None` until the venue answered a ping
+    pub ws_latency: Option<LatencyGauge>,
+    /// WebSocket decode counters; `None` until the venue received a message
+    #[serde(default)]
+    pub decode: Option<DecodeStats>,
+    /// Successful price stream connections (the first one of each stream included)
+    #[serde(default)]
+    pub ws_connections: u64,
+    /// Last time this snapshot was updated
+    pub updated_at: Timestamp,
+}
+
+impl VenueHealth {
+    fn new(exchange: CexExchange) -> Self {
+        Self {
+            exchange,
+            ws_latency: None,
+            decode: None,
+            ws_connections: 0,
+            updated_at: Timestamp::default(),
+        }
+    }
+
+    /// Price stream connections beyond the first (assumes one stream per venue).
+    pub fn ws_reconnects(&self) -> u64 {
+        self.ws_connections.saturating_sub(1)
+    }
+}
+
+/// Records one WebSocket ping round-trip for `exchange`.
+pub fn record_ws_latency(exchange: &CexExchange, rtt: Duration) {
+    let mut health = VENUE_HEALTH.write().unwrap_or_else(|e| e.into_inner());
+    let entry = health
+        .entry(exchange.clone())
+        .or_insert_with(|| VenueHealth::new(exchange.clone()));
+    entry
+        .ws_latency
+        .get_or_insert_with(LatencyGauge::default)
+        .record(rtt.as_secs_f64() * 1000.0);
+    entry.updated_at = Timestamp::now();
+}
+
+/// Records one successful price stream connection of `exchange`.
+pub fn record_ws_connect(exchange: &CexExchange) {
+    let mut health = VENUE_HEALTH.write().unwrap_or_else(|e| e.into_inner());
+    let entry = health
+        .entry(exchange.clone())
+        .or_insert_with(|| VenueHealth::new(exchange.clone()));
+    entry.ws_connections += 1;
+    entry.updated_at = Timestamp::now();
+}
+
+/// Records one WebSocket message of `exchange` that was (`ok`) or was not decoded.
+/// Emits a [DecodeWarning] when the error rate rises above the threshold; the warning
+/// re-arms once the rate falls back below it.
+pub fn record_ws_decode(exchange: &CexExchange, ok: bool) {
+    let threshold = decode_warning_threshold();
+    let warning = {
+        let mut health = VENUE_HEALTH.write().unwrap_or_else(|e| e.into_inner());
+        let entry = health
+            .entry(exchange.clone())
+            .or_insert_with(|| VenueHealth::new(exchange.clone()));
+        entry.updated_at = Timestamp::now();
+        let stats = entry.decode.get_or_insert_with(DecodeStats::default);
+        stats.record(ok);
+
+        let above = stats.error_rate > threshold;
+        if above && !stats.warning && stats.messages() >= DECODE_WARNING_MIN_MESSAGES {
+            stats.warning = true;
+            Some(DecodeWarning {
+                exchange: exchange.clone(),
+                error_rate: stats.error_rate,
+                failures: stats.failures,
+                messages: stats.messages(),
+                timestamp: entry.updated_at,
+            })
+        } else {
+            if !above {
+                stats.warning = false;
+            }
+            None
+        }
+    };
+
+    if let Some(warning) = warning {
+        eprintln!(
+            "Warning: {:?} WebSocket decode error rate at {:.1}% ({} of {} messages failed)",
+            warning.exchange,
+            warning.error_rate * 100.0,
+            warning.failures,
+            warning.messages
+        );
+        // No subscribers is fine
+        let _ = DECODE_WARNINGS.send(warning);
+    }
+}
+
+/// Decodes a WebSocket text payload of `exchange`, recording the outcome with
+/// [record_ws_decode]. The raw payload goes to the WebSocket capture hook first, if one is
+/// set (see [crate::common::ws_capture]).
+pub fn decode_ws_json<T: DeserializeOwned>(exchange: &CexExchange, text: &str) -> Option<T> {
+    capture_ws_text(exchange, text);
+    decode_private_ws_json(exchange, text)
+}
+
+/// [decode_ws_json] without raw capture, for private (user data) streams.
+pub(crate) fn decode_private_ws_json<T: DeserializeOwned>(
+    exchange: &CexExchange,
+    text: &str,
+) -> Option<T> {
+    mark_ws_frame_received();
+    let decoded = serde_json::from_str(text).ok();
+    record_ws_decode(exchange, decoded.is_some());
+    decoded
+}
+
+/// Same as [decode_ws_json] for an already parsed JSON value (e.g. a typed data payload).
+pub fn decode_ws_value<T: DeserializeOwned>(
+    exchange: &CexExchange,
+    value: serde_json::Value,
+) -> Option<T> {
+    let decoded = serde_json::from_value(value).ok();
+    record_ws_decode(exchange, decoded.is_some());
+    decoded
+}
+
+/// Sets the decode error rate (0..=1) above which a [DecodeWarning] is emitted.
+pub fn set_decode_warning_threshold(rate: f64) {
+    *DECODE_WARNING_THRESHOLD
+        .write()
+        .unwrap_or_else(|e| e.into_inner()) = rate;
+}
+
+pub fn decode_warning_threshold() -> f64 {
+    *DECODE_WARNING_THRESHOLD
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+}
+
+/// Receiver of [DecodeWarning]s from every venue.
+pub fn subscribe_decode_warnings() -> broadcast::Receiver<DecodeWarning> {
+    DECODE_WARNINGS.subscribe()
+}
+
+/// Current health of `exchange` (empty snapshot if nothing was recorded yet).
+pub fn venue_health(exchange: &CexExchange) -> VenueHealth {
+    VENUE_HEALTH
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .get(exchange)
+        .cloned()
+        .unwrap_or_else(|| VenueHealth::new(exchange.clone()))
+}
+
+/// Health of every venue that has recorded telemetry.
+pub fn all_venue_health() -> Vec<VenueHealth> {
+    VENUE_HEALTH
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .values()
+        .cloned()
+        .collect()
+}
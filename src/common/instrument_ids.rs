@@ -0,0 +1,124 @@
+//! Venue-native instrument ids resolved from exchange info.
+//!
+//! The heuristics of [format_symbol_for_exchange](crate::common::format_symbol_for_exchange)
+//! guess venue symbols from the standard one, which goes wrong for irregular listings
+//! (Kraken `XBT` / `XDG`, Bitfinex `UST` and `BASE:QUOTE` pairs, Upbit market codes). This
+//! cache holds the standard symbol → venue instrument id map each venue publishes
+//! ([crate::common::CEXTrait::get_instrument_ids]). The formatters consult it after the
+//! user overrides of [register_symbol_override](crate::common::register_symbol_override)
+//! and before the heuristics. The cache can be saved to and loaded from a JSON file so a
+//! restart does not wait for the venues; see
+//! [ArbitrageScanner::refresh_instrument_ids_every](crate::scanner::ArbitrageScanner::refresh_instrument_ids_every)
+//! for background refresh.
+
+use crate::common::MarketScannerError;
+use crate::common::exchange::CexExchange;
+use crate::common::utils::{get_timestamp_millis, normalize_symbol};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::{LazyLock, RwLock};
+
+/// Instrument ids of one venue.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct VenueInstrumentIds {
+    /// Milliseconds since epoch of the exchange info the ids were read from
+    pub refreshed_at: u64,
+    /// Standard symbol (e.g. `DOGEUSDT`) → venue instrument id (e.g. `XDGUSDT`)
+    pub ids: HashMap<String, String>,
+}
+
+static INSTRUMENT_IDS: LazyLock<RwLock<HashMap<CexExchange, VenueInstrumentIds>>> =
+    LazyLock::new(|| RwLock::new(HashMap::new()));
+
+/// Replaces the instrument ids of `exchange` with `ids` (standard symbol → venue id),
+/// refreshed now. Standard symbols are normalized.
+pub fn record_instrument_ids(exchange: &CexExchange, ids: HashMap<String, String>) {
+    let ids = ids
+        .into_iter()
+        .map(|(standard, id)| (normalize_symbol(&standard), id))
+        .collect();
+    let mut cache = INSTRUMENT_IDS.write().unwrap_or_else(|e| e.into_inner());
+    cache.insert(
+        exchange.clone(),
+        VenueInstrumentIds {
+            refreshed_at: get_timestamp_millis(),
+            ids,
+        },
+    );
+}
+
+/// Cached instrument id of `symbol` (any accepted spelling) on `exchange`.
+pub fn instrument_id(exchange: &CexExchange, symbol: &str) -> Option<String> {
+    let cache = INSTRUMENT_IDS.read().unwrap_or_else(|e| e.into_inner());
+    cache
+        .get(exchange)?
+        .ids
+        .get(&normalize_symbol(symbol))
+        .cloned()
+}
+
+/// When the instrument ids of `exchange` were last refreshed (`None` if never).
+pub fn instrument_ids_refreshed_at(exchange: &CexExchange) -> Option<u64> {
+    let cache = INSTRUMENT_IDS.read().unwrap_or_else(|e| e.into_inner());
+    cache.get(exchange).map(|venue| venue.refreshed_at)
+}
+
+/// Drops the instrument ids of `exchange`; its symbols fall back to the heuristics.
+pub fn clear_instrument_ids(exchange: &CexExchange) {
+    let mut cache = INSTRUMENT_IDS.write().unwrap_or_else(|e| e.into_inner());
+    cache.remove(exchange);
+}
+
+/// Writes the whole cache to `path` as JSON.
+pub fn save_instrument_ids(path: impl AsRef<Path>) -> Result<(), MarketScannerError> {
+    let path = path.as_ref();
+    let json = {
+        let cache = INSTRUMENT_IDS.read().unwrap_or_else(|e| e.into_inner());
+        serde_json::to_string_pretty(&*cache)?
+    };
+    std::fs::write(path, json).map_err(|e| {
+        MarketScannerError::InvalidConfig(format!(
+            "Cannot write instrument ids {}: {}",
+            path.display(),
+            e
+        ))
+    })
+}
+
+/// Loads venues from a file written by [save_instrument_ids], replacing their cached ids
+/// (venues not in the file are kept). Returns the number of venues loaded.
+pub fn load_instrument_ids(path: impl AsRef<Path>) -> Result<usize, MarketScannerError> {
+    let path = path.as_ref();
+    let json = std::fs::read_to_string(path).map_err(|e| {
+        MarketScannerError::InvalidConfig(format!(
+            "Cannot read instrument ids {}: {}",
+            path.display(),
+            e
+        ))
+    })?;
+    let venues: HashMap<CexExchange, VenueInstrumentIds> = serde_json::from_str(&json)?;
+    let count = venues.len();
+    let mut cache = INSTRUMENT_IDS.write().unwrap_or_else(|e| e.into_inner());
+    cache.extend(venues);
+    Ok(count)
+}
+
+/// Standard symbol for a cached venue instrument id.
+pub(crate) fn standard_symbol_from_instrument_id(
+    exchange_symbol: &str,
+    exchange: &CexExchange,
+) -> Option<String> {
+    let cache = INSTRUMENT_IDS.read().unwrap_or_else(|e| e.into_inner());
+    let wanted = normalize_symbol(exchange_symbol);
+    let venue = cache.get(exchange)?;
+    // Prefer the entry whose standard symbol differs from the id (e.g. DOGEUSDT over
+    // Kraken's own XDGUSDT spelling)
+    venue
+        .ids
+        .iter()
+        .filter(|(_, id)| normalize_symbol(id) == wanted)
+        .map(|(standard, _)| standard)
+        .min_by_key(|standard| (**standard == wanted, standard.as_str()))
+        .cloned()
+}
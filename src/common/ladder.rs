@@ -0,0 +1,134 @@
+//! DEX quotes at a ladder of trade sizes.
+//!
+//! A single DEX quote hides price impact. [crate::common::DEXTrait::get_price_ladder]
+//! quotes several sizes concurrently and returns a [DexPriceLadder]: the average execution
+//! price per size. [DexPriceLadder::to_order_book] turns the curve into marginal depth
+//! levels, so DEX legs can be matched against CEX books with
+//! [crate::ArbitrageScanner::multi_leg_opportunities_from_books].
+
+use crate::common::{DexPrice, Exchange, OrderBook, OrderBookLevel, Timestamp};
+use serde::{Deserialize, Serialize};
+
+/// Default ladder of quote amounts (in quote token units).
+pub const DEFAULT_LADDER_SIZES: [f64; 4] = [500.0, 2_500.0, 10_000.0, 50_000.0];
+
+/// Quote of one ladder size.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DexLadderPoint {
+    /// Quote amount of the request (quote token units)
+    pub quote_amount: f64,
+    pub price: DexPrice,
+}
+
+/// DEX quotes of one pair at increasing sizes, smallest first.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DexPriceLadder {
+    pub symbol: String,
+    pub exchange: Exchange,
+    pub points: Vec<DexLadderPoint>,
+    pub timestamp: Timestamp,
+}
+
+impl DexPriceLadder {
+    /// Builds a ladder from per-size quotes (in any order). Returns `None` without quotes.
+    pub fn new(mut points: Vec<DexLadderPoint>) -> Option<Self> {
+        points.sort_by(|a, b| {
+            a.quote_amount
+                .partial_cmp(&b.quote_amount)
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+        let first = &points.first()?.price;
+        Some(Self {
+            symbol: first.symbol.clone(),
+            exchange: first.exchange.clone(),
+            timestamp: Timestamp::now(),
+            points,
+        })
+    }
+
+    /// Quote of the smallest size.
+    pub fn best(&self) -> Option<&DexPrice> {
+        self.points.first().map(|p| &p.price)
+    }
+
+    /// Average ask price per size: `(quote_amount, ask_price)`.
+    pub fn ask_curve(&self) -> Vec<(f64, f64)> {
+        self.points
+            .iter()
+            .map(|p| (p.quote_amount, p.price.ask_price))
+            .collect()
+    }
+
+    /// Average bid price per size: `(quote_amount, bid_price)`.
+    pub fn bid_curve(&self) -> Vec<(f64, f64)> {
+        self.points
+            .iter()
+            .map(|p| (p.quote_amount, p.price.bid_price))
+            .collect()
+    }
+
+    /// Price impact of the largest size against the smallest on the ask side, in percent.
+    pub fn ask_slippage_percent(&self) -> f64 {
+        match (self.points.first(), self.points.last()) {
+            (Some(first), Some(last)) if first.price.ask_price > 0.0 => {
+                (last.price.ask_price - first.price.ask_price) / first.price.ask_price * 100.0
+            }
+            _ => 0.0,
+        }
+    }
+
+    /// Synthetic order book with one level per ladder step. Each level holds the extra
+    /// quantity a step adds and its marginal price, so walking the book reproduces the
+    /// average price of every size. Steps that add no quantity are skipped.
+    pub fn to_order_book(&self) -> OrderBook {
+        let mut asks = marginal_levels(
+            self.points
+                .iter()
+                .map(|p| (p.price.ask_qty, p.price.ask_price)),
+        );
+        let mut bids = marginal_levels(
+            self.points
+                .iter()
+                .map(|p| (p.price.bid_qty, p.price.bid_price)),
+        );
+        asks.sort_by(|a, b| {
+            a.price
+                .partial_cmp(&b.price)
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+        bids.sort_by(|a, b| {
+            b.price
+                .partial_cmp(&a.price)
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+        OrderBook {
+            symbol: self.symbol.clone(),
+            bids,
+            asks,
+            timestamp: self.timestamp,
+            exchange: self.exchange.clone(),
+        }
+    }
+}
+
+/// Marginal levels from cumulative `(quantity, average price)` pairs.
+fn marginal_levels(cumulative: impl Iterator<Item = (f64, f64)>) -> Vec<OrderBookLevel> {
+    let mut levels = Vec::new();
+    let (mut prev_qty, mut prev_notional) = (0.0, 0.0);
+    for (qty, avg_price) in cumulative {
+        if qty <= 0.0 || avg_price <= 0.0 {
+            continue;
+        }
+        let notional = qty * avg_price;
+        let step_qty = qty - prev_qty;
+        if step_qty > 0.0 {
+            levels.push(OrderBookLevel {
+                price: (notional - prev_notional) / step_qty,
+                quantity: step_qty,
+            });
+            prev_qty = qty;
+            prev_notional = notional;
+        }
+    }
+    levels
+}
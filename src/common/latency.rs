@@ -0,0 +1,138 @@
+//! Frame timing stamps for end-to-end latency traces.
+//!
+//! While latency tracing is enabled ([set_latency_tracing_enabled]), venue streams note
+//! when a WebSocket frame arrived ([mark_ws_frame_received], called by [decode_ws_json])
+//! and stamp every price parsed from it with a correlation id when it passes the
+//! [WsSymbolFilter]. The scanner takes the stamp of a price when the price reaches its
+//! cache ([take_frame_stamp]) and carries the timings on to the opportunities it emits.
+//! Tracing is off by default and costs one atomic load per frame while disabled.
+//!
+//! [decode_ws_json]: crate::common::decode_ws_json
+//! [WsSymbolFilter]: crate::common::WsSymbolFilter
+
+use crate::common::exchange::Exchange;
+use crate::common::price::CexPrice;
+use crate::common::time::Timestamp;
+use std::cell::Cell;
+use std::collections::{HashMap, VecDeque};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{LazyLock, Mutex};
+use std::time::Instant;
+
+/// Stamps kept per (venue, symbol) until the scanner takes them.
+const MAX_PENDING_STAMPS: usize = 64;
+
+static LATENCY_TRACING_ENABLED: AtomicBool = AtomicBool::new(false);
+
+static NEXT_CORRELATION_ID: AtomicU64 = AtomicU64::new(1);
+
+type PendingStamps = HashMap<(Exchange, String), VecDeque<PendingStamp>>;
+
+static PENDING_STAMPS: LazyLock<Mutex<PendingStamps>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+thread_local! {
+    // Parsing a frame never awaits, so the frame being parsed on this thread is the last one marked
+    static FRAME_RECEIVED_AT: Cell<Option<Instant>> = const { Cell::new(None) };
+}
+
+/// Enables or disables latency tracing (disabled by default).
+pub fn set_latency_tracing_enabled(enabled: bool) {
+    LATENCY_TRACING_ENABLED.store(enabled, Ordering::Relaxed);
+    if !enabled {
+        PENDING_STAMPS
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clear();
+    }
+}
+
+pub fn latency_tracing_enabled() -> bool {
+    LATENCY_TRACING_ENABLED.load(Ordering::Relaxed)
+}
+
+/// Timing of the WebSocket frame a price was parsed from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FrameStamp {
+    /// Id shared by every trace event of this price
+    pub correlation_id: u64,
+    /// When the frame was received
+    pub received_at: Instant,
+    /// When the price was parsed from it
+    pub parsed_at: Instant,
+}
+
+#[derive(Debug, Clone)]
+struct PendingStamp {
+    timestamp: Timestamp,
+    bid_price: f64,
+    ask_price: f64,
+    stamp: FrameStamp,
+}
+
+impl PendingStamp {
+    fn matches(&self, price: &CexPrice) -> bool {
+        self.timestamp == price.timestamp
+            && self.bid_price == price.bid_price
+            && self.ask_price == price.ask_price
+    }
+}
+
+/// Notes that a WebSocket frame was just received on this thread. Called by the decode
+/// helpers; venues with custom decoding (e.g. protobuf) call it before parsing.
+pub fn mark_ws_frame_received() {
+    if latency_tracing_enabled() {
+        FRAME_RECEIVED_AT.with(|cell| cell.set(Some(Instant::now())));
+    }
+}
+
+/// Stamps `price` with the frame marked on this thread and a new correlation id.
+pub(crate) fn stamp_parsed_price(price: &CexPrice) {
+    if !latency_tracing_enabled() {
+        return;
+    }
+    let Some(received_at) = FRAME_RECEIVED_AT.with(Cell::get) else {
+        return;
+    };
+    let stamp = FrameStamp {
+        correlation_id: NEXT_CORRELATION_ID.fetch_add(1, Ordering::Relaxed),
+        received_at,
+        parsed_at: Instant::now(),
+    };
+    #[cfg(feature = "tracing")]
+    tracing::trace!(
+        correlation_id = stamp.correlation_id,
+        exchange = ?price.exchange,
+        symbol = %price.symbol,
+        parse_us = stamp.parsed_at.duration_since(received_at).as_micros() as u64,
+        "ws price parsed"
+    );
+
+    let mut pending = PENDING_STAMPS.lock().unwrap_or_else(|e| e.into_inner());
+    let queue = pending
+        .entry((price.exchange.clone(), price.symbol.clone()))
+        .or_default();
+    if queue.len() >= MAX_PENDING_STAMPS {
+        queue.pop_front();
+    }
+    queue.push_back(PendingStamp {
+        timestamp: price.timestamp,
+        bid_price: price.bid_price,
+        ask_price: price.ask_price,
+        stamp,
+    });
+}
+
+/// Takes the frame stamp of `price`, if it came from a traced venue stream. Older
+/// stamps of the same venue and symbol (prices that were never taken) are discarded.
+pub fn take_frame_stamp(price: &CexPrice) -> Option<FrameStamp> {
+    if !latency_tracing_enabled() {
+        return None;
+    }
+    let mut pending = PENDING_STAMPS.lock().unwrap_or_else(|e| e.into_inner());
+    let queue = pending.get_mut(&(price.exchange.clone(), price.symbol.clone()))?;
+    let index = queue.iter().position(|p| p.matches(price))?;
+    let stamp = queue[index].stamp;
+    queue.drain(..=index);
+    Some(stamp)
+}
@@ -0,0 +1,131 @@
+//! Logical market identity used to decide which prices are comparable.
+//!
+//! Venues spell the same market differently (Bitfinex `BTCUST`, Kraken `XBTUSDT`,
+//! Upbit `KRW-BTC` requested as `BTCUSD`). The scanner only pairs legs whose
+//! [MarketKey] is equal, so a USDT leg is never matched against a KRW or USD leg.
+//! Renamed tickers (e.g. `MATIC` → `POL`) are resolved through a configurable alias map,
+//! see [register_asset_alias]; the aliases of a [SymbolRegistry](crate::common::SymbolRegistry)
+//! in scope come first.
+
+use crate::common::exchange::{CexExchange, Exchange};
+use crate::common::symbol_registry::scoped_asset_alias;
+use crate::common::utils::normalize_symbol;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::{LazyLock, RwLock};
+
+/// Quote assets recognised when splitting a symbol, longest first so that
+/// `FDUSD` / `USDT` win over `USD`.
+const KNOWN_QUOTES: &[&str] = &[
+    "FDUSD", "USDT", "USDC", "BUSD", "TUSD", "USDE", "USD", "UST", "DAI", "EUR", "GBP", "TRY",
+    "KRW", "BRL", "JPY", "AUD", "BTC", "ETH", "BNB",
+];
+
+/// Default base asset aliases: venue-specific or wrapped tickers, plus tickers renamed
+/// by their project (venues migrate at different times).
+const DEFAULT_BASE_ALIASES: &[(&str, &str)] = &[
+    ("XBT", "BTC"),
+    ("XDG", "DOGE"),
+    ("WETH", "ETH"),
+    ("WBNB", "BNB"),
+    ("MATIC", "POL"),
+    ("FTM", "S"),
+];
+
+/// Configurable base asset alias map, seeded with [DEFAULT_BASE_ALIASES].
+static BASE_ALIASES: LazyLock<RwLock<HashMap<String, String>>> = LazyLock::new(|| {
+    RwLock::new(
+        DEFAULT_BASE_ALIASES
+            .iter()
+            .map(|(alias, canonical)| (alias.to_string(), canonical.to_string()))
+            .collect(),
+    )
+});
+
+/// Quote tickers that denote the same asset under another name.
+const QUOTE_ALIASES: &[(&str, &str)] = &[("UST", "USDT")];
+
+/// Base asset + normalized quote group of a trading pair (e.g. `BTC` / `USDT`).
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct MarketKey {
+    pub base: String,
+    pub quote: String,
+}
+
+impl MarketKey {
+    /// Venue-agnostic key for a symbol in common format (e.g. `BTCUSDT`, `btc-usdt`).
+    pub fn from_symbol(symbol: &str) -> Self {
+        let normalized = normalize_symbol(symbol);
+        let (base, quote) = split_base_quote(&normalized);
+        Self {
+            base: canonical_asset(base),
+            quote: resolve_alias(quote, QUOTE_ALIASES).to_string(),
+        }
+    }
+}
+
+impl fmt::Display for MarketKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", self.base, self.quote)
+    }
+}
+
+/// Logical market a price from `exchange` labelled `symbol` actually trades.
+///
+/// Besides ticker aliases this accounts for venue quote mappings, e.g. Upbit serves
+/// `BTCUSD` requests from its KRW market.
+pub fn logical_market_key(symbol: &str, exchange: &Exchange) -> MarketKey {
+    let mut key = MarketKey::from_symbol(symbol);
+    if *exchange == Exchange::Cex(CexExchange::Upbit) && key.quote == "USD" {
+        key.quote = "KRW".to_string();
+    }
+    key
+}
+
+/// Registers `alias` as another ticker for `canonical` (e.g. `MATIC` → `POL` during a
+/// rename), so prices quoted under either ticker are treated as the same market.
+/// Replaces any existing mapping for `alias`.
+pub fn register_asset_alias(alias: &str, canonical: &str) {
+    let mut aliases = BASE_ALIASES.write().unwrap_or_else(|e| e.into_inner());
+    aliases.insert(alias.to_uppercase(), canonical.to_uppercase());
+}
+
+/// Removes the mapping for `alias`, returning its canonical ticker if one was set.
+pub fn remove_asset_alias(alias: &str) -> Option<String> {
+    let mut aliases = BASE_ALIASES.write().unwrap_or_else(|e| e.into_inner());
+    aliases.remove(&alias.to_uppercase())
+}
+
+/// Canonical ticker for an asset after applying the alias map (e.g. `XBT` → `BTC`).
+pub fn canonical_asset(ticker: &str) -> String {
+    let ticker = ticker.to_uppercase();
+    if let Some(canonical) = scoped_asset_alias(&ticker) {
+        return canonical;
+    }
+    let aliases = BASE_ALIASES.read().unwrap_or_else(|e| e.into_inner());
+    aliases.get(&ticker).cloned().unwrap_or(ticker)
+}
+
+/// Split a normalized symbol at the longest known quote suffix.
+/// Unknown quotes fall back to treating the last 3 characters as quote.
+pub(crate) fn split_base_quote(normalized: &str) -> (&str, &str) {
+    for quote in KNOWN_QUOTES {
+        if normalized.len() > quote.len() && normalized.ends_with(quote) {
+            return normalized.split_at(normalized.len() - quote.len());
+        }
+    }
+    if normalized.len() > 3 {
+        normalized.split_at(normalized.len() - 3)
+    } else {
+        (normalized, "")
+    }
+}
+
+fn resolve_alias<'a>(ticker: &'a str, aliases: &[(&'a str, &'a str)]) -> &'a str {
+    aliases
+        .iter()
+        .find(|(alias, _)| *alias == ticker)
+        .map(|(_, canonical)| *canonical)
+        .unwrap_or(ticker)
+}
@@ -1,20 +1,111 @@
+pub mod bounded_cache;
+pub mod capabilities;
+pub mod ccxt;
 pub mod client;
+pub mod clock;
 pub mod commission;
+pub mod credentials;
+pub mod depth_sync;
+pub mod dry_run;
 pub mod errors;
 pub mod exchange;
+pub mod health;
+pub mod instrument_ids;
+pub mod ladder;
+pub mod latency;
+pub mod market;
 pub mod price;
+pub mod rate_limit;
+pub mod reconnect;
+pub mod rest_diagnostics;
+pub mod rng;
+pub mod smoothing;
+pub mod stream;
+pub mod symbol_overrides;
+pub mod symbol_registry;
+pub mod time;
+pub mod units;
 pub mod utils;
+pub mod ws_capture;
 
 // Re-export
-pub use client::create_http_client;
+pub use bounded_cache::{BoundedCache, CacheLimits, CacheStats};
+pub use capabilities::{Capabilities, VenueCapability};
+pub use client::{
+    DEFAULT_HTTP_CACHE_TTL, HttpClientConfig, PageStyle, Paginator, RestRetryPolicy, cached_get,
+    clear_http_cache, create_http_client, create_http_client_with_config, http_cache_ttl,
+    remove_http_cache_ttl, rest_retry_policy, set_http_cache_ttl, set_rest_retry_policy,
+};
+pub use clock::{ClockOffset, measure_clock_offset};
 pub use commission::{
     AmountSide, FeeOverrides, effective_price, effective_price_with_overrides, fee_rate,
     fee_rate_with_overrides, taker_fee_rate, taker_fee_rate_with_overrides,
 };
+pub use credentials::{
+    CredentialProvider, CredentialStore, Credentials, EnvCredentials, FileCredentials,
+    MemoryCredentials,
+};
+pub use depth_sync::{
+    DepthDelta, DepthSnapshot, DepthSyncStatus, MAX_BUFFERED_DEPTH_DELTAS, OrderBookSynchronizer,
+};
+pub use dry_run::{
+    DRY_RUN_ID_PREFIX, DryRunAction, DryRunRecord, MAX_DRY_RUN_RECORDS, clear_dry_run_log,
+    dry_run_enabled, dry_run_log, is_dry_run_id, set_dry_run,
+};
 pub use errors::MarketScannerError;
 pub use exchange::{CEXTrait, CexExchange, DEXTrait, DexAggregator, Exchange, ExchangeTrait};
-pub use price::{CexPrice, DexPrice, DexRouteSummary};
+pub use health::{
+    DEFAULT_DECODE_WARNING_THRESHOLD, DecodeStats, DecodeWarning, LatencyGauge, VenueHealth,
+    all_venue_health, decode_warning_threshold, decode_ws_json, decode_ws_value, record_ws_connect,
+    record_ws_decode, record_ws_latency, set_decode_warning_threshold, subscribe_decode_warnings,
+    venue_health,
+};
+pub use instrument_ids::{
+    VenueInstrumentIds, clear_instrument_ids, instrument_id, instrument_ids_refreshed_at,
+    load_instrument_ids, record_instrument_ids, save_instrument_ids,
+};
+pub use ladder::{DEFAULT_LADDER_SIZES, DexLadderPoint, DexPriceLadder};
+pub use latency::{
+    FrameStamp, latency_tracing_enabled, mark_ws_frame_received, set_latency_tracing_enabled,
+    take_frame_stamp,
+};
+pub use market::{
+    MarketKey, canonical_asset, logical_market_key, register_asset_alias, remove_asset_alias,
+};
+pub use price::{
+    AmountSpec, CexPrice, DEFAULT_EXCLUDED_DEX_SOURCES, DexPrice, DexRouteSummary, DexSourceFilter,
+    OrderBook, OrderBookLevel, OrderLimits, TradingStatus,
+};
+pub use rate_limit::{
+    RequestPriority, acquire_request_slot, queued_requests, remove_rate_limit, set_rate_limit,
+};
+pub use reconnect::{
+    DEFAULT_RECONNECT_DELAY_MS, ReconnectPolicy, clear_ws_endpoint_override,
+    set_ws_endpoint_override, ws_endpoint_override,
+};
+pub use rest_diagnostics::{
+    NEW_CONNECTION_LATENCY_FACTOR, RestConnectionStats, RestRequestHook, RestRequestSample,
+    all_rest_connection_stats, clear_rest_request_hook, record_rest_request,
+    reset_rest_connection_stats, rest_connection_stats, set_rest_request_hook,
+};
+pub use rng::SeededRng;
+pub use smoothing::{MidPriceSmoother, SmoothedPrice, smooth_price_stream};
+pub use stream::{
+    LabeledPrice, WsSymbolFilter, merge_labeled_price_streams, merge_price_streams,
+    set_ws_symbol_filter_enabled, ws_symbol_filter_enabled,
+};
+pub use symbol_overrides::{
+    SymbolFormatter, clear_symbol_formatter, register_symbol_override, remove_symbol_override,
+    set_symbol_formatter,
+};
+pub use symbol_registry::SymbolRegistry;
+pub use time::Timestamp;
+pub use units::{BaseQty, QuoteQty};
 pub use utils::{
     find_mid_price, format_symbol_for_exchange, format_symbol_for_exchange_ws,
     get_timestamp_millis, normalize_symbol, parse_f64, standard_symbol_for_cex_ws_response,
 };
+pub use ws_capture::{
+    RawWsFrame, RawWsPayload, WsCaptureFile, WsCaptureHook, clear_ws_capture_hook, read_ws_capture,
+    set_ws_capture_hook, ws_capture_enabled,
+};
@@ -1,4 +1,6 @@
 use crate::common::exchange::Exchange;
+use crate::common::time::Timestamp;
+use crate::common::units::{BaseQty, QuoteQty};
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -7,10 +9,169 @@ pub struct CexPrice {
     pub mid_price: f64,
     pub bid_price: f64,
     pub ask_price: f64,
+    /// Size at the best bid, in base units
     pub bid_qty: f64,
+    /// Size at the best ask, in base units
     pub ask_qty: f64,
-    pub timestamp: u64,
+    pub timestamp: Timestamp,
     pub exchange: Exchange,
+    /// Trading state of the market on the venue (`Unknown` if the venue does not report it)
+    #[serde(default)]
+    pub trading_status: TradingStatus,
+}
+
+/// Trading state of a market as reported by the venue.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TradingStatus {
+    /// Open for regular trading
+    Trading,
+    /// Trading is halted or suspended
+    Halted,
+    /// Market is in an auction / pre-open phase
+    Auction,
+    /// Only maker (post-only) orders are accepted
+    PostOnly,
+    /// Only cancellations are accepted
+    CancelOnly,
+    /// Venue does not report a status; treated as trading
+    #[default]
+    Unknown,
+}
+
+impl CexPrice {
+    /// [CexPrice::bid_qty] as a base quantity.
+    pub fn bid_size(&self) -> BaseQty {
+        BaseQty(self.bid_qty)
+    }
+
+    /// [CexPrice::ask_qty] as a base quantity.
+    pub fn ask_size(&self) -> BaseQty {
+        BaseQty(self.ask_qty)
+    }
+
+    /// Quote value of the best bid level.
+    pub fn bid_notional(&self) -> QuoteQty {
+        self.bid_size().to_quote(self.bid_price)
+    }
+
+    /// Quote cost of the best ask level.
+    pub fn ask_notional(&self) -> QuoteQty {
+        self.ask_size().to_quote(self.ask_price)
+    }
+}
+
+impl TradingStatus {
+    /// Whether taker orders can be placed, i.e. the venue can take part in an opportunity.
+    pub fn is_trading(&self) -> bool {
+        matches!(self, TradingStatus::Trading | TradingStatus::Unknown)
+    }
+}
+
+/// Size of a DEX quote: an exact amount of the base token (e.g. 2 ETH) or of the quote
+/// token (e.g. 5000 USDT).
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum AmountSpec {
+    Base(f64),
+    Quote(f64),
+}
+
+impl AmountSpec {
+    /// The amount, in units of its token.
+    pub fn amount(&self) -> f64 {
+        match self {
+            AmountSpec::Base(amount) | AmountSpec::Quote(amount) => *amount,
+        }
+    }
+
+    /// The amount in base units, converting a quote amount at `price`.
+    pub fn to_base(&self, price: f64) -> BaseQty {
+        match self {
+            AmountSpec::Base(amount) => BaseQty(*amount),
+            AmountSpec::Quote(amount) => QuoteQty(*amount).to_base(price),
+        }
+    }
+
+    /// The amount in quote units, converting a base amount at `price`.
+    pub fn to_quote(&self, price: f64) -> QuoteQty {
+        match self {
+            AmountSpec::Base(amount) => BaseQty(*amount).to_quote(price),
+            AmountSpec::Quote(amount) => QuoteQty(*amount),
+        }
+    }
+}
+
+/// Liquidity sources a DEX aggregator may route through (e.g. KyberSwap `includedSources` /
+/// `excludedSources`). Source ids are the aggregator's own (`uniswap-v3`, `bebop`, ...).
+/// The default excludes RFQ sources whose quotes are often unfillable (bebop, smardex,
+/// dodo); [DexSourceFilter::unfiltered] routes through every source.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DexSourceFilter {
+    /// Only these sources (all when empty)
+    #[serde(default)]
+    pub included: Vec<String>,
+    /// Never these sources
+    #[serde(default)]
+    pub excluded: Vec<String>,
+}
+
+/// Sources excluded by [DexSourceFilter::default].
+pub const DEFAULT_EXCLUDED_DEX_SOURCES: &[&str] = &["bebop", "smardex", "dodo"];
+
+impl Default for DexSourceFilter {
+    fn default() -> Self {
+        Self {
+            included: Vec::new(),
+            excluded: DEFAULT_EXCLUDED_DEX_SOURCES
+                .iter()
+                .map(|source| source.to_string())
+                .collect(),
+        }
+    }
+}
+
+impl DexSourceFilter {
+    /// No restriction: every source the aggregator knows.
+    pub fn unfiltered() -> Self {
+        Self {
+            included: Vec::new(),
+            excluded: Vec::new(),
+        }
+    }
+
+    /// Replaces the included sources.
+    pub fn with_included_sources<I, S>(mut self, sources: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.included = sources.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Replaces the excluded sources (including the defaults).
+    pub fn with_excluded_sources<I, S>(mut self, sources: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.excluded = sources.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Adds `source` to the excluded sources.
+    pub fn exclude(mut self, source: impl Into<String>) -> Self {
+        let source = source.into();
+        if !self.excluded.contains(&source) {
+            self.excluded.push(source);
+        }
+        self
+    }
+
+    /// Whether a route through `source` is allowed.
+    pub fn allows(&self, source: &str) -> bool {
+        (self.included.is_empty() || self.included.iter().any(|s| s == source))
+            && !self.excluded.iter().any(|s| s == source)
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -19,9 +180,11 @@ pub struct DexPrice {
     pub mid_price: f64,
     pub bid_price: f64,
     pub ask_price: f64,
+    /// Base amount of the sell quote (what the bid route sells)
     pub bid_qty: f64,
+    /// Base amount of the buy quote (what the ask route buys)
     pub ask_qty: f64,
-    pub timestamp: u64,
+    pub timestamp: Timestamp,
     pub exchange: Exchange,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub bid_route_summary: Option<DexRouteSummary>,
@@ -31,6 +194,38 @@ pub struct DexPrice {
     pub bid_route_data: Option<serde_json::Value>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub ask_route_data: Option<serde_json::Value>,
+    /// Transfer tax in basis points of the most taxed token of the pair, when one is a
+    /// known fee-on-transfer token (see [crate::dex::chains::simulate_transfer_tax])
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub transfer_tax_bps: Option<f64>,
+}
+
+impl DexPrice {
+    /// Whether a token of the pair takes a cut of every transfer, so swaps deliver less
+    /// than quoted.
+    pub fn has_transfer_tax(&self) -> bool {
+        self.transfer_tax_bps.is_some_and(|bps| bps > 0.0)
+    }
+
+    /// [DexPrice::bid_qty] as a base quantity.
+    pub fn bid_size(&self) -> BaseQty {
+        BaseQty(self.bid_qty)
+    }
+
+    /// [DexPrice::ask_qty] as a base quantity.
+    pub fn ask_size(&self) -> BaseQty {
+        BaseQty(self.ask_qty)
+    }
+
+    /// Quote value of the sell quote.
+    pub fn bid_notional(&self) -> QuoteQty {
+        self.bid_size().to_quote(self.bid_price)
+    }
+
+    /// Quote cost of the buy quote.
+    pub fn ask_notional(&self) -> QuoteQty {
+        self.ask_size().to_quote(self.ask_price)
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -51,3 +246,47 @@ pub struct DexRouteSummary {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub gas_usd: Option<f64>,
 }
+
+/// One price level of an order book.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct OrderBookLevel {
+    pub price: f64,
+    pub quantity: f64,
+}
+
+/// Order book snapshot: bids sorted highest first, asks sorted lowest first.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OrderBook {
+    pub symbol: String,
+    pub bids: Vec<OrderBookLevel>,
+    pub asks: Vec<OrderBookLevel>,
+    pub timestamp: Timestamp,
+    pub exchange: Exchange,
+}
+
+/// Smallest order a venue accepts on a market and the increments orders must use (its
+/// exchange filters, e.g. Binance `LOT_SIZE` / `NOTIONAL` / `PRICE_FILTER`).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct OrderLimits {
+    /// Minimum order quantity in base units
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_quantity: Option<f64>,
+    /// Minimum order value in quote currency
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_notional: Option<f64>,
+    /// Order quantity increment in base units
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub quantity_step: Option<f64>,
+    /// Limit price increment in quote currency
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub price_tick: Option<f64>,
+}
+
+impl OrderLimits {
+    /// Smallest order value (quote currency) at `price`: the larger of the minimum notional
+    /// and the minimum quantity at that price.
+    pub fn min_notional_at(&self, price: f64) -> f64 {
+        let by_quantity = self.min_quantity.map_or(0.0, |qty| qty * price);
+        self.min_notional.unwrap_or(0.0).max(by_quantity)
+    }
+}
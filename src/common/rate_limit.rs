@@ -0,0 +1,191 @@
+//! Per-venue REST rate limiting with request priorities.
+//!
+//! Venues have no limit by default. Once a limit is set with [set_rate_limit], every REST
+//! request of the venue waits for a slot. When requests queue up, slots go to the highest
+//! [RequestPriority] first (price > order book > metadata) and to the oldest request within
+//! a priority, so health checks and symbol discovery cannot starve price fetches.
+//! Venues are identified by their [crate::common::ExchangeTrait::exchange_name].
+
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap};
+use std::sync::{Arc, LazyLock, Mutex};
+use std::time::Duration;
+use tokio::sync::Notify;
+use tokio::time::Instant;
+
+/// Priority of a REST request in its venue's rate limit queue (highest first).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum RequestPriority {
+    /// Health checks, exchange info, symbol listings
+    Metadata,
+    /// Order book snapshots
+    OrderBook,
+    /// Ticker / best bid and ask
+    Price,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Ticket {
+    priority: RequestPriority,
+    seq: u64,
+}
+
+impl Ord for Ticket {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // Max-heap: higher priority first, then the older ticket
+        self.priority
+            .cmp(&other.priority)
+            .then_with(|| other.seq.cmp(&self.seq))
+    }
+}
+
+impl PartialOrd for Ticket {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+#[derive(Debug)]
+struct VenueLimiter {
+    /// Minimum spacing between two requests
+    interval: Duration,
+    /// Earliest time the next request may start
+    next_slot: Instant,
+    waiting: BinaryHeap<Ticket>,
+    next_seq: u64,
+    notify: Arc<Notify>,
+}
+
+static RATE_LIMITS: LazyLock<Mutex<HashMap<String, VenueLimiter>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// Limits `venue` to `max_requests` REST requests per `per` (spread evenly over the
+/// window). Updating an existing limit keeps its queued requests.
+pub fn set_rate_limit(venue: &str, max_requests: u32, per: Duration) {
+    let interval = per / max_requests.max(1);
+    let mut limits = RATE_LIMITS.lock().unwrap_or_else(|e| e.into_inner());
+    match limits.get_mut(venue) {
+        Some(limiter) => {
+            limiter.interval = interval;
+            limiter.notify.notify_waiters();
+        }
+        None => {
+            limits.insert(
+                venue.to_string(),
+                VenueLimiter {
+                    interval,
+                    next_slot: Instant::now(),
+                    waiting: BinaryHeap::new(),
+                    next_seq: 0,
+                    notify: Arc::new(Notify::new()),
+                },
+            );
+        }
+    }
+}
+
+/// Removes the limit of `venue`, releasing its queued requests. Returns the previous
+/// spacing between requests if a limit was set.
+pub fn remove_rate_limit(venue: &str) -> Option<Duration> {
+    let removed = RATE_LIMITS
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .remove(venue)?;
+    removed.notify.notify_waiters();
+    Some(removed.interval)
+}
+
+/// Number of requests of `venue` waiting for a slot.
+pub fn queued_requests(venue: &str) -> usize {
+    RATE_LIMITS
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .get(venue)
+        .map_or(0, |limiter| limiter.waiting.len())
+}
+
+/// Removes a queued ticket when its request is dropped before getting a slot.
+struct QueuedTicket<'a> {
+    venue: &'a str,
+    ticket: Ticket,
+    granted: bool,
+}
+
+impl Drop for QueuedTicket<'_> {
+    fn drop(&mut self) {
+        if self.granted {
+            return;
+        }
+        let mut limits = RATE_LIMITS.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(limiter) = limits.get_mut(self.venue) {
+            limiter.waiting.retain(|t| *t != self.ticket);
+            limiter.notify.notify_waiters();
+        }
+    }
+}
+
+/// Waits for a request slot of `venue`. Returns immediately if the venue has no limit.
+pub async fn acquire_request_slot(venue: &str, priority: RequestPriority) {
+    let (ticket, notify) = {
+        let mut limits = RATE_LIMITS.lock().unwrap_or_else(|e| e.into_inner());
+        let Some(limiter) = limits.get_mut(venue) else {
+            return;
+        };
+        let now = Instant::now();
+        if limiter.waiting.is_empty() && now >= limiter.next_slot {
+            limiter.next_slot = now + limiter.interval;
+            return;
+        }
+        let ticket = Ticket {
+            priority,
+            seq: limiter.next_seq,
+        };
+        limiter.next_seq += 1;
+        limiter.waiting.push(ticket);
+        (ticket, limiter.notify.clone())
+    };
+    let mut queued = QueuedTicket {
+        venue,
+        ticket,
+        granted: false,
+    };
+
+    loop {
+        let notified = notify.notified();
+        tokio::pin!(notified);
+        notified.as_mut().enable();
+
+        let wait = {
+            let mut limits = RATE_LIMITS.lock().unwrap_or_else(|e| e.into_inner());
+            let Some(limiter) = limits.get_mut(venue) else {
+                // Limit removed while waiting
+                queued.granted = true;
+                return;
+            };
+            if limiter.waiting.peek() == Some(&ticket) {
+                let now = Instant::now();
+                if now >= limiter.next_slot {
+                    limiter.waiting.pop();
+                    limiter.next_slot = now + limiter.interval;
+                    limiter.notify.notify_waiters();
+                    queued.granted = true;
+                    return;
+                }
+                Some(limiter.next_slot - now)
+            } else {
+                None
+            }
+        };
+
+        match wait {
+            Some(delay) => {
+                tokio::select! {
+                    _ = tokio::time::sleep(delay) => {}
+                    _ = &mut notified => {}
+                }
+            }
+            None => notified.await,
+        }
+    }
+}
@@ -0,0 +1,92 @@
+//! Reconnect policy shared by every CEX WebSocket stream, and stream endpoint overrides.
+//!
+//! All venues take the same `reconnect_attempts` / `reconnect_delay_ms` pair and interpret
+//! it through [ReconnectPolicy]: `0` attempts = no reconnect, `n` = up to `n` reconnects
+//! after the initial connection (`n + 1` runs in total), a fixed delay before each
+//! reconnect (0 ms is treated as 1000 ms). Every run counts as an attempt, whether the
+//! connection failed, the subscription failed or an established connection dropped.
+//!
+//! [set_ws_endpoint_override] points a venue's public streams (prices, order books) at
+//! another URL (a proxy, or a local server forcing disconnects in tests).
+
+use crate::common::exchange::CexExchange;
+use std::collections::HashMap;
+use std::sync::{LazyLock, RwLock};
+use std::time::Duration;
+
+/// Delay used when `reconnect_delay_ms` is 0.
+pub const DEFAULT_RECONNECT_DELAY_MS: u64 = 1000;
+
+/// How a stream reconnects after its connection ends.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReconnectPolicy {
+    reconnect_attempts: u32,
+    delay: Duration,
+}
+
+impl ReconnectPolicy {
+    pub fn new(reconnect_attempts: u32, reconnect_delay_ms: u64) -> Self {
+        let delay_ms = if reconnect_delay_ms == 0 {
+            DEFAULT_RECONNECT_DELAY_MS
+        } else {
+            reconnect_delay_ms
+        };
+        Self {
+            reconnect_attempts,
+            delay: Duration::from_millis(delay_ms),
+        }
+    }
+
+    pub fn reconnect_attempts(&self) -> u32 {
+        self.reconnect_attempts
+    }
+
+    /// Wait before each reconnect.
+    pub fn delay(&self) -> Duration {
+        self.delay
+    }
+
+    /// Whether to reconnect after run `attempt` (1 = the initial connection) ended.
+    pub fn should_reconnect(&self, attempt: u32) -> bool {
+        self.reconnect_attempts > 0 && attempt <= self.reconnect_attempts
+    }
+
+    /// Connections made in total when every run ends (initial one plus reconnects).
+    pub fn max_connections(&self) -> u32 {
+        self.reconnect_attempts.saturating_add(1)
+    }
+}
+
+static WS_ENDPOINT_OVERRIDES: LazyLock<RwLock<HashMap<CexExchange, String>>> =
+    LazyLock::new(|| RwLock::new(HashMap::new()));
+
+/// Connects the public streams of `exchange` to `url` instead of the venue's. For
+/// Binance `url` replaces the base (`wss://stream.binance.com:9443`) and stream paths are
+/// appended; for KuCoin it replaces the `bullet-public` token request and instance server,
+/// and is connected as is. Applies to streams started afterwards.
+pub fn set_ws_endpoint_override(exchange: &CexExchange, url: &str) {
+    let mut overrides = WS_ENDPOINT_OVERRIDES
+        .write()
+        .unwrap_or_else(|e| e.into_inner());
+    overrides.insert(exchange.clone(), url.to_string());
+}
+
+/// Removes the endpoint override of `exchange`, returning it if one was set.
+pub fn clear_ws_endpoint_override(exchange: &CexExchange) -> Option<String> {
+    let mut overrides = WS_ENDPOINT_OVERRIDES
+        .write()
+        .unwrap_or_else(|e| e.into_inner());
+    overrides.remove(exchange)
+}
+
+pub fn ws_endpoint_override(exchange: &CexExchange) -> Option<String> {
+    let overrides = WS_ENDPOINT_OVERRIDES
+        .read()
+        .unwrap_or_else(|e| e.into_inner());
+    overrides.get(exchange).cloned()
+}
+
+/// The override of `exchange`, or `default`.
+pub(crate) fn ws_endpoint(exchange: &CexExchange, default: &str) -> String {
+    ws_endpoint_override(exchange).unwrap_or_else(|| default.to_string())
+}
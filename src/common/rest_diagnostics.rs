@@ -0,0 +1,232 @@
+//! REST connection reuse diagnostics per venue.
+//!
+//! A reused keep-alive connection skips the TCP and TLS handshakes, which cost one to three
+//! round trips per request otherwise. reqwest does not expose its connection pool, so reuse
+//! is inferred from timing: every REST request made through [ExchangeTrait] helpers and
+//! [cached_get] records its rate-limit wait and its time to response headers. A request
+//! counts as a likely new connection when it is the venue's first, follows an idle gap
+//! longer than the pool's idle timeout, or takes more than
+//! [NEW_CONNECTION_LATENCY_FACTOR] × the fastest time to headers seen for the venue.
+//! [rest_connection_stats] summarizes the samples; [set_rest_request_hook] receives each one.
+//!
+//! [ExchangeTrait]: crate::common::ExchangeTrait
+//! [cached_get]: crate::common::cached_get
+
+use crate::common::time::Timestamp;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::{Arc, LazyLock, RwLock};
+use std::time::{Duration, Instant};
+
+/// Time to headers, relative to the venue's fastest, above which a request is counted as a
+/// new connection.
+pub const NEW_CONNECTION_LATENCY_FACTOR: f64 = 2.0;
+
+/// reqwest's default idle timeout of pooled connections.
+const POOL_IDLE_TIMEOUT: Duration = Duration::from_secs(90);
+
+/// Receiver of every [RestRequestSample].
+pub type RestRequestHook = Arc<dyn Fn(&RestRequestSample) + Send + Sync>;
+
+static REST_STATS: LazyLock<RwLock<HashMap<String, VenueRestState>>> =
+    LazyLock::new(|| RwLock::new(HashMap::new()));
+
+static REST_REQUEST_HOOK: RwLock<Option<RestRequestHook>> = RwLock::new(None);
+
+/// Timing of one REST request.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RestRequestSample {
+    pub venue: String,
+    pub at: Timestamp,
+    /// Time spent waiting for a rate-limit slot
+    pub queue_wait_ms: f64,
+    /// Time from sending the request to the response headers
+    pub time_to_headers_ms: f64,
+    pub status: u16,
+    /// Whether the timing suggests a fresh connection (handshakes) rather than a pooled one
+    pub likely_new_connection: bool,
+}
+
+/// Connection reuse summary of a venue's REST requests.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RestConnectionStats {
+    pub venue: String,
+    pub requests: u64,
+    pub likely_new_connections: u64,
+    /// Fastest time to headers observed (the reused-connection baseline)
+    pub min_time_to_headers_ms: f64,
+    /// Mean time to headers of likely new connections
+    pub avg_new_connection_ms: f64,
+    /// Mean time to headers of likely reused connections
+    pub avg_reused_connection_ms: f64,
+    /// Mean rate-limit wait
+    pub avg_queue_wait_ms: f64,
+    pub updated_at: Timestamp,
+}
+
+impl RestConnectionStats {
+    /// Share of requests that likely reused a pooled connection.
+    pub fn reuse_rate(&self) -> f64 {
+        if self.requests == 0 {
+            0.0
+        } else {
+            1.0 - self.likely_new_connections as f64 / self.requests as f64
+        }
+    }
+
+    /// Mean time a request takes to answer: rate-limit wait plus time to headers.
+    pub fn avg_response_ms(&self) -> f64 {
+        if self.requests == 0 {
+            return 0.0;
+        }
+        let reused = self.requests - self.likely_new_connections;
+        let headers_ms = (self.avg_new_connection_ms * self.likely_new_connections as f64
+            + self.avg_reused_connection_ms * reused as f64)
+            / self.requests as f64;
+        self.avg_queue_wait_ms + headers_ms
+    }
+
+    /// Extra time a new connection costs (mostly the TCP and TLS handshakes); `None`
+    /// until both kinds of requests were seen.
+    pub fn estimated_handshake_ms(&self) -> Option<f64> {
+        let reused = self.requests - self.likely_new_connections;
+        (self.likely_new_connections > 0 && reused > 0)
+            .then(|| (self.avg_new_connection_ms - self.avg_reused_connection_ms).max(0.0))
+    }
+}
+
+#[derive(Debug, Default)]
+struct VenueRestState {
+    stats: RestConnectionStats,
+    new_total_ms: f64,
+    reused_total_ms: f64,
+    queue_total_ms: f64,
+    last_request: Option<Instant>,
+}
+
+/// Calls `hook` with every recorded REST request, replacing any previous hook.
+pub fn set_rest_request_hook<F>(hook: F)
+where
+    F: Fn(&RestRequestSample) + Send + Sync + 'static,
+{
+    *REST_REQUEST_HOOK.write().unwrap_or_else(|e| e.into_inner()) = Some(Arc::new(hook));
+}
+
+/// Removes the hook set with [set_rest_request_hook].
+pub fn clear_rest_request_hook() {
+    *REST_REQUEST_HOOK.write().unwrap_or_else(|e| e.into_inner()) = None;
+}
+
+/// Records one REST request of `venue` and returns its sample.
+pub fn record_rest_request(
+    venue: &str,
+    queue_wait: Duration,
+    time_to_headers: Duration,
+    status: u16,
+) -> RestRequestSample {
+    let time_to_headers_ms = time_to_headers.as_secs_f64() * 1000.0;
+    let queue_wait_ms = queue_wait.as_secs_f64() * 1000.0;
+    let sample = {
+        let mut venues = REST_STATS.write().unwrap_or_else(|e| e.into_inner());
+        let state = venues
+            .entry(venue.to_string())
+            .or_insert_with(|| VenueRestState {
+                stats: RestConnectionStats {
+                    venue: venue.to_string(),
+                    ..Default::default()
+                },
+                ..Default::default()
+            });
+        let now = Instant::now();
+        let idle = state
+            .last_request
+            .is_none_or(|last| now.duration_since(last) > POOL_IDLE_TIMEOUT);
+        let stats = &mut state.stats;
+        let slow = stats.requests > 0
+            && time_to_headers_ms > stats.min_time_to_headers_ms * NEW_CONNECTION_LATENCY_FACTOR;
+        let likely_new_connection = idle || slow;
+
+        stats.min_time_to_headers_ms = if stats.requests == 0 {
+            time_to_headers_ms
+        } else {
+            stats.min_time_to_headers_ms.min(time_to_headers_ms)
+        };
+        stats.requests += 1;
+        state.queue_total_ms += queue_wait_ms;
+        stats.avg_queue_wait_ms = state.queue_total_ms / stats.requests as f64;
+        if likely_new_connection {
+            stats.likely_new_connections += 1;
+            state.new_total_ms += time_to_headers_ms;
+            stats.avg_new_connection_ms = state.new_total_ms / stats.likely_new_connections as f64;
+        } else {
+            state.reused_total_ms += time_to_headers_ms;
+            stats.avg_reused_connection_ms =
+                state.reused_total_ms / (stats.requests - stats.likely_new_connections) as f64;
+        }
+        stats.updated_at = Timestamp::now();
+        state.last_request = Some(now);
+
+        RestRequestSample {
+            venue: venue.to_string(),
+            at: stats.updated_at,
+            queue_wait_ms,
+            time_to_headers_ms,
+            status,
+            likely_new_connection,
+        }
+    };
+    let hook = REST_REQUEST_HOOK
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .clone();
+    if let Some(hook) = hook {
+        hook(&sample);
+    }
+    sample
+}
+
+/// Sends `request` for `venue` and records its time to headers (`queue_wait` is the
+/// rate-limit wait that preceded it).
+pub(crate) async fn send_recorded(
+    venue: &str,
+    request: reqwest::RequestBuilder,
+    queue_wait: Duration,
+) -> Result<reqwest::Response, reqwest::Error> {
+    let started = Instant::now();
+    let response = request.send().await?;
+    record_rest_request(
+        venue,
+        queue_wait,
+        started.elapsed(),
+        response.status().as_u16(),
+    );
+    Ok(response)
+}
+
+/// Connection reuse summary of `venue` (exchange name, e.g. "Binance"), if it made REST
+/// requests.
+pub fn rest_connection_stats(venue: &str) -> Option<RestConnectionStats> {
+    REST_STATS
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .get(venue)
+        .map(|state| state.stats.clone())
+}
+
+/// Connection reuse summaries of every venue that made REST requests.
+pub fn all_rest_connection_stats() -> Vec<RestConnectionStats> {
+    REST_STATS
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .values()
+        .map(|state| state.stats.clone())
+        .collect()
+}
+
+/// Forgets every recorded request, e.g. after changing client settings.
+pub fn reset_rest_connection_stats() {
+    REST_STATS
+        .write()
+        .unwrap_or_else(|e| e.into_inner())
+        .clear();
+}
@@ -0,0 +1,56 @@
+//! Seedable random numbers.
+//!
+//! Every randomized component (paper-trading slippage and quote loss, scan schedule jitter)
+//! draws from a [SeededRng]. Built with [SeededRng::new] the draws are the same on every
+//! run, so tests and backtests are reproducible; [SeededRng::from_entropy] seeds from the
+//! clock for live use.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Small seeded generator (SplitMix64); quality is ample for simulation and jitter.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SeededRng {
+    state: u64,
+}
+
+impl SeededRng {
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    /// Generator seeded from the clock; two calls never share a seed.
+    pub fn from_entropy() -> Self {
+        static COUNTER: AtomicU64 = AtomicU64::new(0);
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_nanos() as u64);
+        let count = COUNTER.fetch_add(1, Ordering::Relaxed);
+        let mut seeder = Self::new(nanos ^ count.rotate_left(32));
+        Self::new(seeder.next_u64())
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform in [0, 1).
+    pub fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    /// `base` scaled by a uniform factor in [1 − `fraction`, 1 + `fraction`]; `fraction` is
+    /// clamped to [0, 1].
+    pub fn jitter(&mut self, base: Duration, fraction: f64) -> Duration {
+        let fraction = fraction.clamp(0.0, 1.0);
+        if fraction == 0.0 {
+            return base;
+        }
+        let factor = 1.0 - fraction + 2.0 * fraction * self.next_f64();
+        base.mul_f64(factor)
+    }
+}
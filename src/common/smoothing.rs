@@ -0,0 +1,107 @@
+//! Exponential moving average of the mid price per (exchange, symbol).
+//!
+//! A single tick can move the mid far from where the market trades a moment later. A
+//! [MidPriceSmoother] keeps a time-weighted EMA of the mid with a configurable half-life: an
+//! update `dt` after the previous one moves the average `1 − 0.5^(dt / half_life)` of the
+//! way to the new mid. Strategies can trigger off sustained spreads of smoothed mids, or
+//! use them as a reference to spot outlier ticks. [smooth_price_stream] wraps a price
+//! stream and attaches the smoothed mid to every update.
+
+use crate::common::exchange::Exchange;
+use crate::common::price::CexPrice;
+use crate::common::time::Timestamp;
+use std::collections::HashMap;
+use std::time::Duration;
+use tokio::sync::mpsc;
+
+const SMOOTHED_CHANNEL_CAPACITY: usize = 256;
+
+/// A price update with the smoothed mid of its (exchange, symbol) after the update.
+#[derive(Debug, Clone)]
+pub struct SmoothedPrice {
+    pub price: CexPrice,
+    pub smoothed_mid: f64,
+}
+
+/// Time-weighted EMA of the mid price per (exchange, symbol).
+#[derive(Debug, Clone)]
+pub struct MidPriceSmoother {
+    half_life: Duration,
+    averages: HashMap<(Exchange, String), (f64, Timestamp)>,
+}
+
+impl MidPriceSmoother {
+    /// Smoother with the given half-life; a zero half-life follows the mid exactly.
+    pub fn new(half_life: Duration) -> Self {
+        Self {
+            half_life,
+            averages: HashMap::new(),
+        }
+    }
+
+    pub fn half_life(&self) -> Duration {
+        self.half_life
+    }
+
+    /// Folds the mid of `price` into its average and returns the new average. The first
+    /// update of a symbol starts the average at its mid; updates not newer than the
+    /// previous one leave it unchanged.
+    pub fn update(&mut self, price: &CexPrice) -> f64 {
+        let key = (price.exchange.clone(), price.symbol.clone());
+        let Some((average, updated_at)) = self.averages.get_mut(&key) else {
+            self.averages
+                .insert(key, (price.mid_price, price.timestamp));
+            return price.mid_price;
+        };
+        if price.timestamp <= *updated_at {
+            return *average;
+        }
+        let weight = if self.half_life.is_zero() {
+            1.0
+        } else {
+            let elapsed = price.timestamp.duration_since(*updated_at).as_secs_f64();
+            1.0 - 0.5f64.powf(elapsed / self.half_life.as_secs_f64())
+        };
+        *average += weight * (price.mid_price - *average);
+        *updated_at = price.timestamp;
+        *average
+    }
+
+    /// Current average of `symbol` on `exchange`, if it had an update.
+    pub fn smoothed_mid(&self, exchange: &Exchange, symbol: &str) -> Option<f64> {
+        self.averages
+            .get(&(exchange.clone(), symbol.to_string()))
+            .map(|(average, _)| *average)
+    }
+
+    /// Forgets every average.
+    pub fn reset(&mut self) {
+        self.averages.clear();
+    }
+}
+
+/// Attaches the smoothed mid (EMA with `half_life`) to every update of `receiver`. The
+/// returned receiver ends when `receiver` does.
+pub fn smooth_price_stream(
+    mut receiver: mpsc::Receiver<CexPrice>,
+    half_life: Duration,
+) -> mpsc::Receiver<SmoothedPrice> {
+    let (tx, rx) = mpsc::channel(SMOOTHED_CHANNEL_CAPACITY);
+    tokio::spawn(async move {
+        let mut smoother = MidPriceSmoother::new(half_life);
+        while let Some(price) = receiver.recv().await {
+            let smoothed_mid = smoother.update(&price);
+            if tx
+                .send(SmoothedPrice {
+                    price,
+                    smoothed_mid,
+                })
+                .await
+                .is_err()
+            {
+                return;
+            }
+        }
+    });
+    rx
+}
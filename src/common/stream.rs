@@ -0,0 +1,151 @@
+//! Helpers for composing [CexPrice] streams (e.g. several `stream_price_websocket` receivers),
+//! and the [WsSymbolFilter] venue streams apply before emitting a price.
+
+use crate::common::exchange::{CexExchange, Exchange};
+use crate::common::latency::stamp_parsed_price;
+use crate::common::market::{MarketKey, logical_market_key};
+use crate::common::price::CexPrice;
+use crate::common::symbol_registry;
+use std::collections::{HashMap, HashSet};
+use std::sync::atomic::{AtomicBool, Ordering};
+use tokio::sync::mpsc;
+
+const MERGED_CHANNEL_CAPACITY: usize = 256;
+
+static WS_SYMBOL_FILTER_ENABLED: AtomicBool = AtomicBool::new(true);
+
+/// Enables or disables the [WsSymbolFilter] of every venue stream (enabled by default).
+pub fn set_ws_symbol_filter_enabled(enabled: bool) {
+    WS_SYMBOL_FILTER_ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+pub fn ws_symbol_filter_enabled() -> bool {
+    WS_SYMBOL_FILTER_ENABLED.load(Ordering::Relaxed)
+}
+
+/// Allow-list of the symbols a venue stream subscribed to, checked right before a parsed
+/// price is sent. Some venues push instruments beyond the subscription (e.g. after a
+/// resubscribe); those never reach consumers. Symbols are compared by [MarketKey], so
+/// venue spellings such as Bitfinex `BTCUST` match a `BTCUSDT` subscription.
+#[derive(Debug, Clone)]
+pub struct WsSymbolFilter {
+    exchange: Exchange,
+    markets: HashSet<MarketKey>,
+}
+
+impl WsSymbolFilter {
+    pub fn new(exchange: &CexExchange, symbols: &[&str]) -> Self {
+        let exchange = Exchange::Cex(exchange.clone());
+        let markets = symbols
+            .iter()
+            .map(|symbol| logical_market_key(symbol, &exchange))
+            .collect();
+        Self { exchange, markets }
+    }
+
+    /// Whether `price` belongs to a subscribed symbol (always true while the filter is
+    /// disabled with [set_ws_symbol_filter_enabled]). While latency tracing is enabled,
+    /// allowed prices are also stamped with the timing of their frame.
+    pub fn allows(&self, price: &CexPrice) -> bool {
+        let allowed = !ws_symbol_filter_enabled()
+            || self
+                .markets
+                .contains(&logical_market_key(&price.symbol, &self.exchange));
+        if allowed {
+            stamp_parsed_price(price);
+        }
+        allowed
+    }
+}
+
+/// A price update tagged with the label of the stream it came from.
+#[derive(Debug, Clone)]
+pub struct LabeledPrice<L> {
+    pub source: L,
+    pub price: CexPrice,
+}
+
+/// Merges several price receivers into one. The merged receiver ends once every
+/// source has closed (or the merged receiver is dropped).
+pub fn merge_price_streams(receivers: Vec<mpsc::Receiver<CexPrice>>) -> mpsc::Receiver<CexPrice> {
+    let (tx, rx) = mpsc::channel(MERGED_CHANNEL_CAPACITY);
+    for mut source in receivers {
+        let tx = tx.clone();
+        symbol_registry::spawn(async move {
+            while let Some(price) = source.recv().await {
+                if tx.send(price).await.is_err() {
+                    return;
+                }
+            }
+        });
+    }
+    rx
+}
+
+/// Merges labeled price receivers into one stream of [LabeledPrice].
+///
+/// With `coalesce` enabled, updates that pile up while the consumer is busy are
+/// collapsed so that only the latest price per (label, exchange, symbol) is delivered;
+/// a slow consumer then sees fresh prices instead of working through a backlog.
+pub fn merge_labeled_price_streams<L>(
+    receivers: Vec<(L, mpsc::Receiver<CexPrice>)>,
+    coalesce: bool,
+) -> mpsc::Receiver<LabeledPrice<L>>
+where
+    L: Clone + Eq + std::hash::Hash + Send + Sync + 'static,
+{
+    let (tx_in, mut rx_in) = mpsc::channel(MERGED_CHANNEL_CAPACITY);
+    for (label, mut source) in receivers {
+        let tx_in = tx_in.clone();
+        symbol_registry::spawn(async move {
+            while let Some(price) = source.recv().await {
+                let labeled = LabeledPrice {
+                    source: label.clone(),
+                    price,
+                };
+                if tx_in.send(labeled).await.is_err() {
+                    return;
+                }
+            }
+        });
+    }
+    drop(tx_in);
+
+    if !coalesce {
+        return rx_in;
+    }
+
+    let (tx, rx) = mpsc::channel(MERGED_CHANNEL_CAPACITY);
+    symbol_registry::spawn(async move {
+        while let Some(first) = rx_in.recv().await {
+            // Collapse everything already buffered, keeping first-seen order per key
+            let mut order: Vec<(L, Exchange, String)> = Vec::new();
+            let mut latest: HashMap<(L, Exchange, String), LabeledPrice<L>> = HashMap::new();
+            let mut pending = Some(first);
+            let mut drained = 0;
+            // Bounded so fast producers cannot starve the output side
+            while drained < MERGED_CHANNEL_CAPACITY {
+                let Some(update) = pending.take().or_else(|| rx_in.try_recv().ok()) else {
+                    break;
+                };
+                drained += 1;
+                let key = (
+                    update.source.clone(),
+                    update.price.exchange.clone(),
+                    update.price.symbol.clone(),
+                );
+                if latest.insert(key.clone(), update).is_none() {
+                    order.push(key);
+                }
+            }
+            for key in order {
+                if let Some(update) = latest.remove(&key) {
+                    if tx.send(update).await.is_err() {
+                        return;
+                    }
+                }
+            }
+        }
+    });
+    rx
+}
@@ -0,0 +1,110 @@
+//! User-registered symbol formats per exchange.
+//!
+//! [format_symbol_for_exchange](crate::common::format_symbol_for_exchange) derives venue
+//! symbols from a few quote-splitting heuristics, which get exotic pairs wrong. Overrides
+//! registered here are consulted first: an exact mapping ([register_symbol_override]) wins
+//! over a per-exchange formatter closure ([set_symbol_formatter]), which wins over the
+//! built-in formatting. Kraken WebSocket pairs (`BASE/QUOTE`) are built from the standard
+//! symbol and are not affected. Overrides of a [SymbolRegistry](crate::common::SymbolRegistry)
+//! in scope win over the ones registered here.
+
+use crate::common::exchange::CexExchange;
+use crate::common::symbol_registry::{scoped_standard_symbol, scoped_symbol_override};
+use crate::common::utils::normalize_symbol;
+use std::collections::HashMap;
+use std::sync::{Arc, LazyLock, RwLock};
+
+/// Formatter closure: receives the normalized symbol (e.g. `BTCUSDT`) and returns the
+/// exchange symbol, or `None` to fall back to the built-in formatting.
+pub type SymbolFormatter = Arc<dyn Fn(&str) -> Option<String> + Send + Sync>;
+
+#[derive(Clone, Default)]
+pub(crate) struct ExchangeOverrides {
+    /// Normalized symbol -> exchange symbol
+    pub(crate) symbols: HashMap<String, String>,
+    pub(crate) formatter: Option<SymbolFormatter>,
+}
+
+static SYMBOL_OVERRIDES: LazyLock<RwLock<HashMap<CexExchange, ExchangeOverrides>>> =
+    LazyLock::new(|| RwLock::new(HashMap::new()));
+
+/// Maps `symbol` (any accepted spelling, e.g. `BTC-USDT`) to `exchange_symbol` on `exchange`.
+/// Replaces any existing mapping for the symbol.
+pub fn register_symbol_override(exchange: &CexExchange, symbol: &str, exchange_symbol: &str) {
+    let mut overrides = SYMBOL_OVERRIDES.write().unwrap_or_else(|e| e.into_inner());
+    overrides
+        .entry(exchange.clone())
+        .or_default()
+        .symbols
+        .insert(normalize_symbol(symbol), exchange_symbol.to_string());
+}
+
+/// Removes the mapping for `symbol` on `exchange`, returning the exchange symbol if one was set.
+pub fn remove_symbol_override(exchange: &CexExchange, symbol: &str) -> Option<String> {
+    let mut overrides = SYMBOL_OVERRIDES.write().unwrap_or_else(|e| e.into_inner());
+    overrides
+        .get_mut(exchange)
+        .and_then(|o| o.symbols.remove(&normalize_symbol(symbol)))
+}
+
+/// Installs a formatter closure for `exchange`, replacing any previous one.
+pub fn set_symbol_formatter<F>(exchange: &CexExchange, formatter: F)
+where
+    F: Fn(&str) -> Option<String> + Send + Sync + 'static,
+{
+    let mut overrides = SYMBOL_OVERRIDES.write().unwrap_or_else(|e| e.into_inner());
+    overrides.entry(exchange.clone()).or_default().formatter = Some(Arc::new(formatter));
+}
+
+/// Removes the formatter closure of `exchange`.
+pub fn clear_symbol_formatter(exchange: &CexExchange) {
+    let mut overrides = SYMBOL_OVERRIDES.write().unwrap_or_else(|e| e.into_inner());
+    if let Some(o) = overrides.get_mut(exchange) {
+        o.formatter = None;
+    }
+}
+
+/// Registered exchange symbol for a normalized symbol, if any.
+pub(crate) fn symbol_override(normalized: &str, exchange: &CexExchange) -> Option<String> {
+    if let Some(symbol) = scoped_symbol_override(normalized, exchange) {
+        return Some(symbol);
+    }
+    let formatter = {
+        let overrides = SYMBOL_OVERRIDES.read().unwrap_or_else(|e| e.into_inner());
+        let o = overrides.get(exchange)?;
+        if let Some(symbol) = o.symbols.get(normalized) {
+            return Some(symbol.clone());
+        }
+        o.formatter.clone()?
+    };
+    // Called without holding the lock so the closure may itself register overrides
+    formatter(normalized)
+}
+
+/// Standard symbol for an exchange symbol registered via [register_symbol_override].
+pub(crate) fn standard_symbol_from_override(
+    exchange_symbol: &str,
+    exchange: &CexExchange,
+) -> Option<String> {
+    if let Some(standard) = scoped_standard_symbol(exchange_symbol, exchange) {
+        return Some(standard);
+    }
+    let overrides = SYMBOL_OVERRIDES.read().unwrap_or_else(|e| e.into_inner());
+    standard_symbol_in(&overrides, exchange_symbol, exchange)
+}
+
+/// Standard symbol mapped to `exchange_symbol` on `exchange` in `overrides`.
+pub(crate) fn standard_symbol_in(
+    overrides: &HashMap<CexExchange, ExchangeOverrides>,
+    exchange_symbol: &str,
+    exchange: &CexExchange,
+) -> Option<String> {
+    let wanted = normalize_symbol(exchange_symbol);
+    overrides
+        .get(exchange)?
+        .symbols
+        .iter()
+        .find_map(|(standard, symbol)| {
+            (normalize_symbol(symbol) == wanted).then(|| standard.clone())
+        })
+}
@@ -0,0 +1,156 @@
+//! Symbol overrides and asset aliases of one tenant.
+//!
+//! [register_symbol_override](crate::common::register_symbol_override),
+//! [set_symbol_formatter](crate::common::set_symbol_formatter) and
+//! [register_asset_alias](crate::common::register_asset_alias) configure the whole process. A
+//! [SymbolRegistry] holds the same entries for one tenant (see
+//! [ScannerContext::with_symbol_registry](crate::ScannerContext::with_symbol_registry)): while
+//! code runs in [SymbolRegistry::scope], its entries are consulted before the process-wide
+//! ones, including in the stream tasks that code starts.
+
+use crate::common::exchange::CexExchange;
+use crate::common::symbol_overrides::{ExchangeOverrides, standard_symbol_in};
+use crate::common::utils::normalize_symbol;
+use std::collections::HashMap;
+use std::fmt;
+use std::future::Future;
+use std::sync::Arc;
+use tokio::task::JoinHandle;
+
+tokio::task_local! {
+    static CURRENT: SymbolRegistry;
+}
+
+#[derive(Clone, Default)]
+struct Entries {
+    /// Alias -> canonical ticker, both uppercase
+    asset_aliases: HashMap<String, String>,
+    symbol_overrides: HashMap<CexExchange, ExchangeOverrides>,
+}
+
+/// Asset aliases and symbol overrides layered over the process-wide ones. Clones share
+/// their entries until one of them is changed.
+#[derive(Clone, Default)]
+pub struct SymbolRegistry {
+    entries: Arc<Entries>,
+}
+
+impl fmt::Debug for SymbolRegistry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let overrides: HashMap<_, _> = self
+            .entries
+            .symbol_overrides
+            .iter()
+            .map(|(exchange, o)| (exchange, (&o.symbols, o.formatter.is_some())))
+            .collect();
+        f.debug_struct("SymbolRegistry")
+            .field("asset_aliases", &self.entries.asset_aliases)
+            .field("symbol_overrides", &overrides)
+            .finish()
+    }
+}
+
+impl SymbolRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Treats `alias` as another ticker for `canonical`, like
+    /// [register_asset_alias](crate::common::register_asset_alias).
+    pub fn with_asset_alias(mut self, alias: &str, canonical: &str) -> Self {
+        Arc::make_mut(&mut self.entries)
+            .asset_aliases
+            .insert(alias.to_uppercase(), canonical.to_uppercase());
+        self
+    }
+
+    /// Maps `symbol` to `exchange_symbol` on `exchange`, like
+    /// [register_symbol_override](crate::common::register_symbol_override).
+    pub fn with_symbol_override(
+        mut self,
+        exchange: &CexExchange,
+        symbol: &str,
+        exchange_symbol: &str,
+    ) -> Self {
+        Arc::make_mut(&mut self.entries)
+            .symbol_overrides
+            .entry(exchange.clone())
+            .or_default()
+            .symbols
+            .insert(normalize_symbol(symbol), exchange_symbol.to_string());
+        self
+    }
+
+    /// Formats the symbols of `exchange` with `formatter`, like
+    /// [set_symbol_formatter](crate::common::set_symbol_formatter).
+    pub fn with_symbol_formatter<F>(mut self, exchange: &CexExchange, formatter: F) -> Self
+    where
+        F: Fn(&str) -> Option<String> + Send + Sync + 'static,
+    {
+        Arc::make_mut(&mut self.entries)
+            .symbol_overrides
+            .entry(exchange.clone())
+            .or_default()
+            .formatter = Some(Arc::new(formatter));
+        self
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.asset_aliases.is_empty() && self.entries.symbol_overrides.is_empty()
+    }
+
+    /// Runs `future` with this registry in effect. Scopes nest: the innermost registry is
+    /// consulted, then the process-wide entries.
+    pub async fn scope<F: Future>(&self, future: F) -> F::Output {
+        CURRENT.scope(self.clone(), future).await
+    }
+
+    /// Runs `f` with this registry in effect (see [Self::scope]).
+    pub fn scope_sync<R>(&self, f: impl FnOnce() -> R) -> R {
+        CURRENT.sync_scope(self.clone(), f)
+    }
+}
+
+/// Registry in effect on the current task, if any.
+fn current() -> Option<SymbolRegistry> {
+    CURRENT.try_with(SymbolRegistry::clone).ok()
+}
+
+/// Canonical ticker of an uppercase `ticker` in the registry in effect.
+pub(crate) fn scoped_asset_alias(ticker: &str) -> Option<String> {
+    current()?.entries.asset_aliases.get(ticker).cloned()
+}
+
+/// Exchange symbol of a normalized symbol in the registry in effect.
+pub(crate) fn scoped_symbol_override(normalized: &str, exchange: &CexExchange) -> Option<String> {
+    let registry = current()?;
+    let overrides = registry.entries.symbol_overrides.get(exchange)?;
+    if let Some(symbol) = overrides.symbols.get(normalized) {
+        return Some(symbol.clone());
+    }
+    overrides.formatter.as_ref()?(normalized)
+}
+
+/// Standard symbol of an exchange symbol overridden in the registry in effect.
+pub(crate) fn scoped_standard_symbol(
+    exchange_symbol: &str,
+    exchange: &CexExchange,
+) -> Option<String> {
+    standard_symbol_in(
+        &current()?.entries.symbol_overrides,
+        exchange_symbol,
+        exchange,
+    )
+}
+
+/// [tokio::spawn] that keeps the registry of the current task in effect in the new task.
+pub(crate) fn spawn<F>(future: F) -> JoinHandle<F::Output>
+where
+    F: Future + Send + 'static,
+    F::Output: Send + 'static,
+{
+    match current() {
+        Some(registry) => tokio::spawn(CURRENT.scope(registry, future)),
+        None => tokio::spawn(future),
+    }
+}
@@ -0,0 +1,119 @@
+//! Millisecond timestamps.
+//!
+//! Venues report time in seconds, milliseconds, microseconds or nanoseconds. Every
+//! timestamp exposed by this crate is a [Timestamp] (milliseconds since the Unix epoch),
+//! so values from different sources cannot be mixed up by unit. It serializes as a plain
+//! integer, so the JSON layout of prices and events is unchanged.
+
+use crate::common::utils::get_timestamp_millis;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::time::Duration;
+
+/// Milliseconds since the Unix epoch.
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+#[serde(transparent)]
+pub struct Timestamp(u64);
+
+impl Timestamp {
+    /// Current wall-clock time.
+    pub fn now() -> Self {
+        Self(get_timestamp_millis())
+    }
+
+    pub const fn from_millis(millis: u64) -> Self {
+        Self(millis)
+    }
+
+    pub const fn from_secs(secs: u64) -> Self {
+        Self(secs.saturating_mul(1000))
+    }
+
+    pub const fn from_micros(micros: u64) -> Self {
+        Self(micros / 1000)
+    }
+
+    pub const fn from_nanos(nanos: u64) -> Self {
+        Self(nanos / 1_000_000)
+    }
+
+    /// Venue timestamps of unknown unit, guessed by magnitude (seconds, milliseconds,
+    /// microseconds or nanoseconds for dates between 2001 and 2286).
+    pub const fn from_epoch_guess(value: u64) -> Self {
+        if value < 10_000_000_000 {
+            Self::from_secs(value)
+        } else if value < 10_000_000_000_000 {
+            Self::from_millis(value)
+        } else if value < 10_000_000_000_000_000 {
+            Self::from_micros(value)
+        } else {
+            Self::from_nanos(value)
+        }
+    }
+
+    pub const fn as_millis(self) -> u64 {
+        self.0
+    }
+
+    pub const fn as_secs(self) -> u64 {
+        self.0 / 1000
+    }
+
+    /// Time elapsed since `earlier` (zero if `earlier` is later).
+    pub const fn duration_since(self, earlier: Timestamp) -> Duration {
+        Duration::from_millis(self.0.saturating_sub(earlier.0))
+    }
+
+    /// Time elapsed since this timestamp, measured against the current time.
+    pub fn age(self) -> Duration {
+        Self::now().duration_since(self)
+    }
+
+    /// Whether this timestamp is older than `max_age`.
+    pub fn is_older_than(self, max_age: Duration) -> bool {
+        self.age() > max_age
+    }
+
+    pub fn saturating_add(self, duration: Duration) -> Self {
+        Self(self.0.saturating_add(duration.as_millis() as u64))
+    }
+
+    pub fn saturating_sub(self, duration: Duration) -> Self {
+        Self(self.0.saturating_sub(duration.as_millis() as u64))
+    }
+}
+
+impl From<u64> for Timestamp {
+    /// Interprets the value as milliseconds.
+    fn from(millis: u64) -> Self {
+        Self(millis)
+    }
+}
+
+impl From<Timestamp> for u64 {
+    fn from(timestamp: Timestamp) -> Self {
+        timestamp.0
+    }
+}
+
+/// Compares against a raw millisecond value.
+impl PartialEq<u64> for Timestamp {
+    fn eq(&self, millis: &u64) -> bool {
+        self.0 == *millis
+    }
+}
+
+/// Compares against a raw millisecond value.
+impl PartialOrd<u64> for Timestamp {
+    fn partial_cmp(&self, millis: &u64) -> Option<std::cmp::Ordering> {
+        self.0.partial_cmp(millis)
+    }
+}
+
+impl fmt::Display for Timestamp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
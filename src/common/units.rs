@@ -0,0 +1,122 @@
+//! Typed quantities: base asset vs quote currency.
+//!
+//! Sizes in this crate come in two units. Order book and DEX quantities
+//! (`bid_qty` / `ask_qty` of [crate::common::CexPrice] and [crate::common::DexPrice],
+//! [crate::ArbitrageOpportunity::executable_quantity]) count the base asset (ETH of
+//! ETHUSDT); notionals, fees and profits
+//! ([crate::ArbitrageOpportunity::total_commission_quote],
+//! [crate::common::DexLadderPoint::quote_amount]) count the quote currency (USDT). The
+//! `f64` fields stay for compatibility; typed accessors return [BaseQty] and [QuoteQty],
+//! so a base amount cannot be passed where a quote amount is expected and converting
+//! between them always takes a price.
+
+use crate::common::price::AmountSpec;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::iter::Sum;
+use std::ops::{Add, AddAssign, Sub};
+
+/// Amount of the base asset (e.g. 0.5 BTC of BTCUSDT).
+#[derive(Debug, Clone, Copy, Default, PartialEq, PartialOrd, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct BaseQty(pub f64);
+
+/// Amount of the quote currency (e.g. 30 000 USDT of BTCUSDT).
+#[derive(Debug, Clone, Copy, Default, PartialEq, PartialOrd, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct QuoteQty(pub f64);
+
+impl BaseQty {
+    pub fn value(self) -> f64 {
+        self.0
+    }
+
+    /// Value at `price` (quote per base).
+    pub fn to_quote(self, price: f64) -> QuoteQty {
+        QuoteQty(self.0 * price)
+    }
+
+    pub fn min(self, other: Self) -> Self {
+        Self(self.0.min(other.0))
+    }
+
+    pub fn max(self, other: Self) -> Self {
+        Self(self.0.max(other.0))
+    }
+}
+
+impl QuoteQty {
+    pub fn value(self) -> f64 {
+        self.0
+    }
+
+    /// Base amount it buys at `price` (quote per base); zero for a non-positive price.
+    pub fn to_base(self, price: f64) -> BaseQty {
+        if price > 0.0 {
+            BaseQty(self.0 / price)
+        } else {
+            BaseQty(0.0)
+        }
+    }
+
+    pub fn min(self, other: Self) -> Self {
+        Self(self.0.min(other.0))
+    }
+
+    pub fn max(self, other: Self) -> Self {
+        Self(self.0.max(other.0))
+    }
+}
+
+macro_rules! qty_ops {
+    ($qty:ident) => {
+        impl Add for $qty {
+            type Output = Self;
+
+            fn add(self, rhs: Self) -> Self {
+                Self(self.0 + rhs.0)
+            }
+        }
+
+        impl AddAssign for $qty {
+            fn add_assign(&mut self, rhs: Self) {
+                self.0 += rhs.0;
+            }
+        }
+
+        impl Sub for $qty {
+            type Output = Self;
+
+            fn sub(self, rhs: Self) -> Self {
+                Self(self.0 - rhs.0)
+            }
+        }
+
+        impl Sum for $qty {
+            fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+                Self(iter.map(|q| q.0).sum())
+            }
+        }
+
+        impl fmt::Display for $qty {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                fmt::Display::fmt(&self.0, f)
+            }
+        }
+    };
+}
+
+qty_ops!(BaseQty);
+qty_ops!(QuoteQty);
+
+impl From<BaseQty> for AmountSpec {
+    fn from(qty: BaseQty) -> Self {
+        AmountSpec::Base(qty.0)
+    }
+}
+
+impl From<QuoteQty> for AmountSpec {
+    fn from(qty: QuoteQty) -> Self {
+        AmountSpec::Quote(qty.0)
+    }
+}
@@ -1,4 +1,6 @@
 // src/common/utils.rs
+use crate::common::instrument_ids::{instrument_id, standard_symbol_from_instrument_id};
+use crate::common::symbol_overrides::{standard_symbol_from_override, symbol_override};
 use crate::common::{CexExchange, MarketScannerError};
 
 // Parse a string to a f64, return a MarketScannerError if the parsing fails
@@ -29,6 +31,8 @@ pub fn normalize_symbol(symbol: &str) -> String {
 
 /// Convert common symbol format (e.g., BTCUSDT) to exchange-specific format
 /// Common format: BTCUSDT (uppercase, no separators)
+/// Overrides registered via [crate::common::register_symbol_override] or
+/// [crate::common::set_symbol_formatter] take precedence.
 pub fn format_symbol_for_exchange(
     symbol: &str,
     exchange: &CexExchange,
@@ -43,6 +47,16 @@ pub fn format_symbol_for_exchange(
         ));
     }
 
+    // User overrides for pairs the heuristics below get wrong
+    if let Some(overridden) = symbol_override(&normalized, exchange) {
+        return Ok(overridden);
+    }
+
+    // Instrument ids published by the venue, for pairs the heuristics get wrong
+    if let Some(id) = instrument_id(exchange, &normalized) {
+        return Ok(id);
+    }
+
     // Convert to exchange-specific format
     let formatted = match exchange {
         // Exchanges using standard format: BTCUSDT (uppercase, no separators)
@@ -295,6 +309,12 @@ pub fn format_symbol_for_exchange_ws(
 /// Standard symbol string for [CexPrice] when returning from WebSocket (same format as REST).
 /// E.g. Bitfinex uses UST instead of USDT in the pair name.
 pub fn standard_symbol_for_cex_ws_response(symbol: &str, exchange: &CexExchange) -> String {
+    if let Some(standard) = standard_symbol_from_override(symbol, exchange) {
+        return standard;
+    }
+    if let Some(standard) = standard_symbol_from_instrument_id(symbol, exchange) {
+        return standard;
+    }
     let normalized = normalize_symbol(symbol);
     match exchange {
         CexExchange::Bitfinex if normalized.ends_with("USDT") => normalized.replace("USDT", "UST"),
@@ -0,0 +1,223 @@
+//! Raw WebSocket frame capture.
+//!
+//! Off by default. With a hook set ([set_ws_capture_hook]), every market data frame the
+//! WebSocket price clients receive is handed to it as a [RawWsFrame] (venue, arrival time
+//! and the undecoded payload) before decoding. [WsCaptureFile] writes the frames to a JSON
+//! Lines file, e.g. to build fixtures for replay tests or to inspect a venue's protocol
+//! change in production; [read_ws_capture] reads such a file back. Private (user data)
+//! streams are never captured.
+
+use crate::common::MarketScannerError;
+use crate::common::exchange::CexExchange;
+use crate::common::time::Timestamp;
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::collections::HashSet;
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, LineWriter, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, RwLock};
+
+/// Receiver of captured frames; called on the client's task, so it should return quickly.
+pub type WsCaptureHook = Arc<dyn Fn(&RawWsFrame) + Send + Sync>;
+
+static WS_CAPTURE_HOOK: RwLock<Option<WsCaptureHook>> = RwLock::new(None);
+
+/// Skips the lock on every frame while no hook is set.
+static WS_CAPTURE_ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// Undecoded payload of a frame.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RawWsPayload {
+    Text(String),
+    /// Serialized as base64
+    Binary(
+        #[serde(
+            serialize_with = "serialize_base64",
+            deserialize_with = "deserialize_base64"
+        )]
+        Vec<u8>,
+    ),
+}
+
+/// A frame as received from a venue.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RawWsFrame {
+    pub exchange: CexExchange,
+    pub received_at: Timestamp,
+    pub payload: RawWsPayload,
+}
+
+impl RawWsFrame {
+    /// Text payload (`None` for binary frames).
+    pub fn text(&self) -> Option<&str> {
+        match &self.payload {
+            RawWsPayload::Text(text) => Some(text),
+            RawWsPayload::Binary(_) => None,
+        }
+    }
+}
+
+/// Calls `hook` with every received market data frame, replacing any previous hook.
+pub fn set_ws_capture_hook<F>(hook: F)
+where
+    F: Fn(&RawWsFrame) + Send + Sync + 'static,
+{
+    *WS_CAPTURE_HOOK.write().unwrap_or_else(|e| e.into_inner()) = Some(Arc::new(hook));
+    WS_CAPTURE_ENABLED.store(true, Ordering::Release);
+}
+
+/// Removes the hook set with [set_ws_capture_hook] (or [WsCaptureFile::install]).
+pub fn clear_ws_capture_hook() {
+    WS_CAPTURE_ENABLED.store(false, Ordering::Release);
+    *WS_CAPTURE_HOOK.write().unwrap_or_else(|e| e.into_inner()) = None;
+}
+
+pub fn ws_capture_enabled() -> bool {
+    WS_CAPTURE_ENABLED.load(Ordering::Acquire)
+}
+
+/// Hands a text frame of `exchange` to the capture hook, if one is set.
+pub(crate) fn capture_ws_text(exchange: &CexExchange, text: &str) {
+    if ws_capture_enabled() {
+        capture(exchange, || RawWsPayload::Text(text.to_string()));
+    }
+}
+
+/// Hands a binary frame of `exchange` to the capture hook, if one is set.
+pub(crate) fn capture_ws_binary(exchange: &CexExchange, bytes: &[u8]) {
+    if ws_capture_enabled() {
+        capture(exchange, || RawWsPayload::Binary(bytes.to_vec()));
+    }
+}
+
+fn capture(exchange: &CexExchange, payload: impl FnOnce() -> RawWsPayload) {
+    let hook = WS_CAPTURE_HOOK
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .clone();
+    if let Some(hook) = hook {
+        hook(&RawWsFrame {
+            exchange: exchange.clone(),
+            received_at: Timestamp::now(),
+            payload: payload(),
+        });
+    }
+}
+
+/// JSON Lines file sink of captured frames, one [RawWsFrame] per line.
+#[derive(Debug, Clone)]
+pub struct WsCaptureFile {
+    path: PathBuf,
+    writer: Arc<Mutex<LineWriter<File>>>,
+    venues: Option<HashSet<CexExchange>>,
+}
+
+impl WsCaptureFile {
+    /// Creates (or truncates) `path`.
+    pub fn create(path: impl AsRef<Path>) -> Result<Self, MarketScannerError> {
+        Self::open(path.as_ref(), false)
+    }
+
+    /// Appends to `path`, creating it if missing.
+    pub fn append(path: impl AsRef<Path>) -> Result<Self, MarketScannerError> {
+        Self::open(path.as_ref(), true)
+    }
+
+    fn open(path: &Path, append: bool) -> Result<Self, MarketScannerError> {
+        let file = OpenOptions::new()
+            .create(true)
+            .write(true)
+            .append(append)
+            .truncate(!append)
+            .open(path)
+            .map_err(|e| {
+                MarketScannerError::InvalidConfig(format!(
+                    "Cannot open WebSocket capture {}: {}",
+                    path.display(),
+                    e
+                ))
+            })?;
+        Ok(Self {
+            path: path.to_path_buf(),
+            writer: Arc::new(Mutex::new(LineWriter::new(file))),
+            venues: None,
+        })
+    }
+
+    /// Only writes frames of `venues` (all venues by default).
+    pub fn with_venues(mut self, venues: &[CexExchange]) -> Self {
+        self.venues = Some(venues.iter().cloned().collect());
+        self
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Writes `frame` unless its venue is filtered out.
+    pub fn write(&self, frame: &RawWsFrame) -> Result<(), MarketScannerError> {
+        if self
+            .venues
+            .as_ref()
+            .is_some_and(|venues| !venues.contains(&frame.exchange))
+        {
+            return Ok(());
+        }
+        let line = serde_json::to_string(frame)?;
+        let mut writer = self.writer.lock().unwrap_or_else(|e| e.into_inner());
+        writeln!(writer, "{}", line).map_err(|e| {
+            MarketScannerError::InvalidConfig(format!(
+                "Cannot write WebSocket capture {}: {}",
+                self.path.display(),
+                e
+            ))
+        })
+    }
+
+    /// Sets this file as the capture hook. Write errors are ignored so a full disk never
+    /// stalls a price stream.
+    pub fn install(self) {
+        set_ws_capture_hook(move |frame| {
+            let _ = self.write(frame);
+        });
+    }
+}
+
+/// Frames of a file written by [WsCaptureFile], in file order.
+pub fn read_ws_capture(path: impl AsRef<Path>) -> Result<Vec<RawWsFrame>, MarketScannerError> {
+    let path = path.as_ref();
+    let file = File::open(path).map_err(|e| {
+        MarketScannerError::InvalidConfig(format!(
+            "Cannot read WebSocket capture {}: {}",
+            path.display(),
+            e
+        ))
+    })?;
+    let mut frames = Vec::new();
+    for line in BufReader::new(file).lines() {
+        let line = line.map_err(|e| {
+            MarketScannerError::InvalidConfig(format!(
+                "Cannot read WebSocket capture {}: {}",
+                path.display(),
+                e
+            ))
+        })?;
+        if !line.trim().is_empty() {
+            frames.push(serde_json::from_str(&line)?);
+        }
+    }
+    Ok(frames)
+}
+
+fn serialize_base64<S: Serializer>(bytes: &[u8], serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(&BASE64.encode(bytes))
+}
+
+fn deserialize_base64<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<u8>, D::Error> {
+    let encoded = String::deserialize(deserializer)?;
+    BASE64.decode(encoded).map_err(serde::de::Error::custom)
+}
@@ -0,0 +1,125 @@
+//! Raw token amounts as 256-bit integers.
+//!
+//! Aggregators and pools report amounts in base units (wei for an 18-decimal token).
+//! Those exceed `u128` and the 96-bit mantissa of `Decimal` for large-supply tokens, and
+//! lose digits as `f64`. DEX math keeps them as [U256] and converts at the boundaries:
+//! [parse_units] / [f64_to_raw] turn caller amounts into raw ones, [format_units] renders
+//! them exactly, and [raw_to_f64] / [raw_ratio] produce the `f64` quantities and prices
+//! of [crate::common::DexPrice].
+
+use crate::common::MarketScannerError;
+use ethers::core::types::U256;
+
+/// Parses a raw (base unit) decimal amount such as an aggregator's `amountOut`.
+pub fn parse_raw_amount(raw: &str) -> Result<U256, MarketScannerError> {
+    U256::from_dec_str(raw.trim())
+        .map_err(|e| MarketScannerError::ApiError(format!("invalid amount {}: {}", raw, e)))
+}
+
+/// Raw amount of a decimal token amount (e.g. "1.5" with 18 decimals). Fails on digits
+/// below the token's precision, signs, exponents and overflow.
+pub fn parse_units(amount: &str, decimals: u8) -> Result<U256, MarketScannerError> {
+    let invalid = |reason: &str| {
+        MarketScannerError::ApiError(format!("invalid amount {}: {}", amount, reason))
+    };
+    let amount = amount.trim();
+    let (whole, fraction) = amount.split_once('.').unwrap_or((amount, ""));
+    if whole.is_empty() && fraction.is_empty() {
+        return Err(invalid("empty"));
+    }
+    if !whole
+        .chars()
+        .chain(fraction.chars())
+        .all(|c| c.is_ascii_digit())
+    {
+        return Err(invalid("not a decimal number"));
+    }
+    let fraction = fraction.trim_end_matches('0');
+    if fraction.len() > decimals as usize {
+        return Err(invalid(&format!("more than {} decimals", decimals)));
+    }
+    let digits = format!(
+        "{}{}{}",
+        whole,
+        fraction,
+        "0".repeat(decimals as usize - fraction.len())
+    );
+    let digits = digits.trim_start_matches('0');
+    if digits.is_empty() {
+        return Ok(U256::zero());
+    }
+    U256::from_dec_str(digits).map_err(|_| invalid("does not fit 256 bits"))
+}
+
+/// Exact decimal form of a raw amount (e.g. 1500000000000000000 with 18 decimals →
+/// "1.5"), without trailing zeros.
+pub fn format_units(raw: U256, decimals: u8) -> String {
+    let digits = raw.to_string();
+    let decimals = decimals as usize;
+    let (whole, fraction) = if digits.len() > decimals {
+        digits.split_at(digits.len() - decimals)
+    } else {
+        ("", digits.as_str())
+    };
+    let fraction = format!("{:0>width$}", fraction, width = decimals);
+    let fraction = fraction.trim_end_matches('0');
+    let whole = if whole.is_empty() { "0" } else { whole };
+    if fraction.is_empty() {
+        whole.to_string()
+    } else {
+        format!("{}.{}", whole, fraction)
+    }
+}
+
+/// Raw amount of an `f64` token amount, from its shortest decimal form so no binary
+/// noise ends up in the raw digits. Digits below the token's precision are truncated;
+/// negative, non-finite and overflowing amounts fail.
+pub fn f64_to_raw(amount: f64, decimals: u8) -> Result<U256, MarketScannerError> {
+    if !amount.is_finite() || amount < 0.0 {
+        return Err(MarketScannerError::ApiError(format!(
+            "invalid amount {}: must be finite and non-negative",
+            amount
+        )));
+    }
+    let formatted = amount.to_string();
+    let (whole, fraction) = formatted
+        .split_once('.')
+        .unwrap_or((formatted.as_str(), ""));
+    let fraction = &fraction[..fraction.len().min(decimals as usize)];
+    parse_units(&format!("{}.{}", whole, fraction), decimals)
+}
+
+/// Nearest `f64` of a raw integer.
+pub fn u256_to_f64(value: U256) -> f64 {
+    value.0.iter().rev().fold(0.0, |acc, word| {
+        acc * 18_446_744_073_709_551_616.0 + *word as f64
+    })
+}
+
+/// Token amount of a raw amount as `f64`; whole and fractional parts are split in
+/// integer arithmetic first, so amounts beyond 2^128 convert without overflow.
+pub fn raw_to_f64(raw: U256, decimals: u8) -> f64 {
+    match U256::from(10u8).checked_pow(U256::from(decimals)) {
+        Some(unit) => {
+            let (whole, fraction) = raw.div_mod(unit);
+            u256_to_f64(whole) + u256_to_f64(fraction) / u256_to_f64(unit)
+        }
+        None => u256_to_f64(raw) / 10f64.powi(decimals as i32),
+    }
+}
+
+/// Price of one whole denominator token in numerator tokens, from raw amounts (e.g. a
+/// route's raw `amountIn` over its raw `amountOut`). Fails on a zero denominator.
+pub fn raw_ratio(
+    numerator: U256,
+    numerator_decimals: u8,
+    denominator: U256,
+    denominator_decimals: u8,
+) -> Result<f64, MarketScannerError> {
+    if denominator.is_zero() {
+        return Err(MarketScannerError::ApiError(
+            "Division by zero: divisor cannot be zero".to_string(),
+        ));
+    }
+    Ok(raw_to_f64(numerator, numerator_decimals) / raw_to_f64(denominator, denominator_decimals))
+}
@@ -1,4 +1,4 @@
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum ChainId {
     ETHEREUM = 0x1,
     BSC = 0x38,
@@ -17,6 +17,24 @@ pub enum ChainId {
 }
 
 impl ChainId {
+    /// Every supported chain.
+    pub const ALL: [ChainId; 14] = [
+        ChainId::ETHEREUM,
+        ChainId::BSC,
+        ChainId::POLYGON,
+        ChainId::AVALANCHE,
+        ChainId::ARBITRUM,
+        ChainId::OPTIMISM,
+        ChainId::BASE,
+        ChainId::PLASMA,
+        ChainId::UNICHAIN,
+        ChainId::SONIC,
+        ChainId::RONIN,
+        ChainId::HyperEVM,
+        ChainId::LINEA,
+        ChainId::MANTLE,
+    ];
+
     pub fn name(&self) -> &'static str {
         match self {
             ChainId::ETHEREUM => "ethereum",
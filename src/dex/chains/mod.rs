@@ -3,4 +3,7 @@ pub mod tokens;
 
 // Re-export
 pub use chain::ChainId;
-pub use tokens::Token;
+pub use tokens::{
+    Token, TransferTax, clear_transfer_taxes, pair_transfer_tax_bps, record_transfer_tax,
+    simulate_transfer_tax, transfer_tax,
+};
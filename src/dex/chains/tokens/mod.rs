@@ -1,4 +1,9 @@
+pub mod tax;
 pub mod token;
 
 // Re-export
+pub use tax::{
+    TransferTax, clear_transfer_taxes, pair_transfer_tax_bps, record_transfer_tax,
+    simulate_transfer_tax, transfer_tax,
+};
 pub use token::Token;
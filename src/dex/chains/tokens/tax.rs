@@ -0,0 +1,157 @@
+//! Fee-on-transfer (tax) token detection.
+//!
+//! Some long-tail tokens take a cut of every transfer, so a swap delivers less than the
+//! aggregator quoted and the arbitrage math silently breaks. [simulate_transfer_tax] runs a
+//! transfer from a holder to a fresh address with `eth_call` and compares what arrives with
+//! what was sent. Results are kept process-wide per token; DEX quotes of a pair with a
+//! known tax token carry it in [crate::common::DexPrice::transfer_tax_bps].
+
+use crate::common::{MarketScannerError, Timestamp};
+use crate::dex::chains::{ChainId, Token};
+use ethers::core::types::transaction::eip2718::TypedTransaction;
+use ethers::core::types::{Address, Bytes, Eip1559TransactionRequest, U256};
+use ethers::providers::{JsonRpcClient, Provider, RawCall, spoof};
+use std::collections::HashMap;
+use std::sync::{LazyLock, RwLock};
+
+/// Helper placed at the holder's address via a state override. Called with
+/// `(token, recipient, amount)` it reads `balanceOf(recipient)`, calls
+/// `transfer(recipient, amount)` as the holder, reads the balance again and returns the
+/// difference; it reverts if any of these calls reverts.
+const TRANSFER_PROBE_CODE: &str = "0x6370a0823160e01b60005260203560045260206080602460006000355afa156100805763a9059cbb60e01b600052602035600452604035602452600060a06044600060006000355af115610080576370a0823160e01b600052602035600452602060c0602460006000355afa156100805760805160c0510360005260206000f35b60006000fd";
+
+/// Recipient of probe transfers: an address no token exempts from its tax.
+const PROBE_RECIPIENT: u64 = 0x5ca1_ab1e_7a8e;
+
+/// Outcome of a simulated transfer of a token.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TransferTax {
+    pub chain: ChainId,
+    /// Token address as in [Token::address]
+    pub token: String,
+    /// Amount sent (base units)
+    pub sent: U256,
+    /// Amount that arrived at the recipient (base units)
+    pub received: U256,
+    pub checked_at: Timestamp,
+}
+
+impl TransferTax {
+    /// Share of the transfer kept by the token, in basis points (0 when all arrived).
+    pub fn tax_bps(&self) -> f64 {
+        if self.sent.is_zero() || self.received >= self.sent {
+            return 0.0;
+        }
+        u256_to_f64(self.sent - self.received) / u256_to_f64(self.sent) * 10_000.0
+    }
+
+    /// Whether less arrived than was sent.
+    pub fn is_taxed(&self) -> bool {
+        self.received < self.sent
+    }
+}
+
+static TRANSFER_TAXES: LazyLock<RwLock<HashMap<(ChainId, String), TransferTax>>> =
+    LazyLock::new(|| RwLock::new(HashMap::new()));
+
+fn registry_key(chain: &ChainId, address: &str) -> (ChainId, String) {
+    (chain.clone(), address.to_lowercase())
+}
+
+/// Records the outcome of a transfer check (or a tax known from elsewhere).
+pub fn record_transfer_tax(tax: TransferTax) {
+    TRANSFER_TAXES
+        .write()
+        .unwrap_or_else(|e| e.into_inner())
+        .insert(registry_key(&tax.chain, &tax.token), tax);
+}
+
+/// Last transfer check of `token`, if it was ever checked.
+pub fn transfer_tax(token: &Token) -> Option<TransferTax> {
+    TRANSFER_TAXES
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .get(&registry_key(&token.chain_id, &token.address))
+        .cloned()
+}
+
+/// Forgets every transfer check.
+pub fn clear_transfer_taxes() {
+    TRANSFER_TAXES
+        .write()
+        .unwrap_or_else(|e| e.into_inner())
+        .clear();
+}
+
+/// Highest known transfer tax of `base_token` and `quote_token` in basis points; `None`
+/// unless one of them was found to be taxed.
+pub fn pair_transfer_tax_bps(base_token: &Token, quote_token: &Token) -> Option<f64> {
+    [base_token, quote_token]
+        .into_iter()
+        .filter_map(transfer_tax)
+        .filter(TransferTax::is_taxed)
+        .map(|tax| tax.tax_bps())
+        .reduce(f64::max)
+}
+
+/// Simulates a transfer of `amount` (base units) of `token` from `holder` (e.g. one of the
+/// token's pools, see [crate::dex::find_pools]) with `eth_call`, and records the result.
+/// `holder` must hold at least `amount`. Needs an RPC that supports state overrides
+/// (geth, erigon, reth, most hosted providers); fails if the transfer reverts.
+pub async fn simulate_transfer_tax<P: JsonRpcClient>(
+    provider: &Provider<P>,
+    token: &Token,
+    holder: Address,
+    amount: U256,
+) -> Result<TransferTax, MarketScannerError> {
+    let token_address: Address = token
+        .address
+        .parse()
+        .map_err(|e| MarketScannerError::ApiError(format!("invalid token address: {}", e)))?;
+    let code: Bytes = TRANSFER_PROBE_CODE
+        .parse()
+        .map_err(|e| MarketScannerError::ApiError(format!("invalid probe code: {}", e)))?;
+
+    let mut data = abi_word(token_address.as_bytes()).to_vec();
+    data.extend_from_slice(&abi_word(
+        Address::from_low_u64_be(PROBE_RECIPIENT).as_bytes(),
+    ));
+    let mut word = [0u8; 32];
+    amount.to_big_endian(&mut word);
+    data.extend_from_slice(&word);
+
+    let tx: TypedTransaction = Eip1559TransactionRequest::new()
+        .to(holder)
+        .data(Bytes::from(data))
+        .into();
+    let state = spoof::code(holder, code);
+    let res = provider.call_raw(&tx).state(&state).await.map_err(|e| {
+        MarketScannerError::WsRpcError(format!("transfer simulation reverted: {}", e))
+    })?;
+    if res.len() < 32 {
+        return Err(MarketScannerError::WsRpcError(
+            "transfer simulation returned no amount".into(),
+        ));
+    }
+
+    let tax = TransferTax {
+        chain: token.chain_id.clone(),
+        token: token.address.clone(),
+        sent: amount,
+        received: U256::from_big_endian(&res[..32]),
+        checked_at: Timestamp::now(),
+    };
+    record_transfer_tax(tax.clone());
+    Ok(tax)
+}
+
+/// Left-pads `bytes` to a 32-byte ABI word.
+fn abi_word(bytes: &[u8]) -> [u8; 32] {
+    let mut word = [0u8; 32];
+    word[32 - bytes.len()..].copy_from_slice(bytes);
+    word
+}
+
+fn u256_to_f64(value: U256) -> f64 {
+    value.to_string().parse().unwrap_or(f64::MAX)
+}
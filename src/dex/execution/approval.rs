@@ -0,0 +1,193 @@
+//! ERC-20 allowance checks and router approval transactions.
+//!
+//! Before a DEX leg can be executed, the wallet must let the aggregator router spend its
+//! input token. [ApprovalCache] remembers allowances per (wallet, token, router) so the
+//! scanner loop does not query the chain for every opportunity; [build_approval_tx] and
+//! [simulate_approval] prepare the `approve` transaction when the allowance is short.
+
+use crate::common::{MarketScannerError, Timestamp};
+use ethers::core::types::transaction::eip2718::TypedTransaction;
+use ethers::core::types::{Address, Bytes, Eip1559TransactionRequest, U256};
+use ethers::providers::Middleware;
+use std::collections::HashMap;
+use std::time::Duration;
+
+/// KyberSwap MetaAggregationRouterV2 (same address on every supported EVM chain).
+pub const KYBERSWAP_ROUTER_ADDRESS: &str = "0x6131B5fae19EA4f9D964eAc0408E4408b66337b5";
+
+// allowance(address,address)
+const SELECTOR_ALLOWANCE: [u8; 4] = [0xdd, 0x62, 0xed, 0x3e];
+// approve(address,uint256)
+const SELECTOR_APPROVE: [u8; 4] = [0x09, 0x5e, 0xa7, 0xb3];
+
+/// Default time a cached allowance is trusted (5 minutes).
+const DEFAULT_ALLOWANCE_TTL: Duration = Duration::from_secs(300);
+
+/// `allowance(owner, spender)` of `token`.
+pub async fn fetch_allowance<M: Middleware>(
+    provider: &M,
+    token: Address,
+    owner: Address,
+    spender: Address,
+) -> Result<U256, MarketScannerError> {
+    let mut data = SELECTOR_ALLOWANCE.to_vec();
+    data.extend_from_slice(&abi_word(owner.as_bytes()));
+    data.extend_from_slice(&abi_word(spender.as_bytes()));
+    let tx: TypedTransaction = Eip1559TransactionRequest::new().to(token).data(data).into();
+    let res = provider
+        .call(&tx, None)
+        .await
+        .map_err(|e| MarketScannerError::WsRpcError(e.to_string()))?;
+    if res.len() < 32 {
+        return Err(MarketScannerError::WsRpcError(
+            "allowance response too short".into(),
+        ));
+    }
+    Ok(U256::from_big_endian(&res[..32]))
+}
+
+/// Calldata of `approve(spender, amount)`.
+pub fn approval_calldata(spender: Address, amount: U256) -> Bytes {
+    let mut data = SELECTOR_APPROVE.to_vec();
+    data.extend_from_slice(&abi_word(spender.as_bytes()));
+    let mut word = [0u8; 32];
+    amount.to_big_endian(&mut word);
+    data.extend_from_slice(&word);
+    Bytes::from(data)
+}
+
+/// Unsigned EIP-1559 `approve` transaction from `owner`. Gas and fee fields are left for
+/// the signer middleware (or [simulate_approval]) to fill in. Some tokens (e.g. USDT) only
+/// accept a new non-zero allowance after it was reset to zero.
+pub fn build_approval_tx(
+    chain_id: u64,
+    token: Address,
+    owner: Address,
+    spender: Address,
+    amount: U256,
+) -> TypedTransaction {
+    Eip1559TransactionRequest::new()
+        .chain_id(chain_id)
+        .from(owner)
+        .to(token)
+        .value(U256::zero())
+        .data(approval_calldata(spender, amount))
+        .into()
+}
+
+/// Simulates `tx` with `eth_call` and returns its gas estimate. Fails if the call reverts
+/// or the token returns `false`.
+pub async fn simulate_approval<M: Middleware>(
+    provider: &M,
+    tx: &TypedTransaction,
+) -> Result<U256, MarketScannerError> {
+    let res = provider
+        .call(tx, None)
+        .await
+        .map_err(|e| MarketScannerError::WsRpcError(format!("approve reverted: {}", e)))?;
+    // Tokens like USDT return nothing; others return a bool
+    if res.len() >= 32 && U256::from_big_endian(&res[..32]).is_zero() {
+        return Err(MarketScannerError::ApiError(
+            "approve returned false".to_string(),
+        ));
+    }
+    provider
+        .estimate_gas(tx, None)
+        .await
+        .map_err(|e| MarketScannerError::WsRpcError(e.to_string()))
+}
+
+#[derive(Debug, Clone, Copy)]
+struct CachedAllowance {
+    allowance: U256,
+    fetched_at: Timestamp,
+}
+
+/// Allowances per (wallet, token, router), refreshed from chain once older than the TTL.
+#[derive(Debug, Clone)]
+pub struct ApprovalCache {
+    entries: HashMap<(Address, Address, Address), CachedAllowance>,
+    ttl: Duration,
+}
+
+impl Default for ApprovalCache {
+    fn default() -> Self {
+        Self {
+            entries: HashMap::new(),
+            ttl: DEFAULT_ALLOWANCE_TTL,
+        }
+    }
+}
+
+impl ApprovalCache {
+    pub fn with_ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = ttl;
+        self
+    }
+
+    /// Cached allowance if it is younger than the TTL.
+    pub fn cached(&self, wallet: Address, token: Address, router: Address) -> Option<U256> {
+        self.entries
+            .get(&(wallet, token, router))
+            .filter(|entry| !entry.fetched_at.is_older_than(self.ttl))
+            .map(|entry| entry.allowance)
+    }
+
+    /// Allowance of `router` over `wallet`'s `token`, from cache or chain.
+    pub async fn allowance<M: Middleware>(
+        &mut self,
+        provider: &M,
+        wallet: Address,
+        token: Address,
+        router: Address,
+    ) -> Result<U256, MarketScannerError> {
+        if let Some(allowance) = self.cached(wallet, token, router) {
+            return Ok(allowance);
+        }
+        let allowance = fetch_allowance(provider, token, wallet, router).await?;
+        self.record(wallet, token, router, allowance);
+        Ok(allowance)
+    }
+
+    /// Whether `router` needs a new approval before spending `amount` of `token`.
+    pub async fn needs_approval<M: Middleware>(
+        &mut self,
+        provider: &M,
+        wallet: Address,
+        token: Address,
+        router: Address,
+        amount: U256,
+    ) -> Result<bool, MarketScannerError> {
+        Ok(self.allowance(provider, wallet, token, router).await? < amount)
+    }
+
+    /// Stores a known allowance, e.g. after an approval was mined.
+    pub fn record(&mut self, wallet: Address, token: Address, router: Address, allowance: U256) {
+        self.entries.insert(
+            (wallet, token, router),
+            CachedAllowance {
+                allowance,
+                fetched_at: Timestamp::now(),
+            },
+        );
+    }
+
+    /// Lowers the cached allowance after a swap spent `amount` (the router consumes it).
+    pub fn record_spend(&mut self, wallet: Address, token: Address, router: Address, amount: U256) {
+        if let Some(entry) = self.entries.get_mut(&(wallet, token, router)) {
+            entry.allowance = entry.allowance.saturating_sub(amount);
+        }
+    }
+
+    /// Forgets the cached allowance so the next lookup queries the chain.
+    pub fn invalidate(&mut self, wallet: Address, token: Address, router: Address) {
+        self.entries.remove(&(wallet, token, router));
+    }
+}
+
+/// Left-pads `bytes` to a 32-byte ABI word.
+fn abi_word(bytes: &[u8]) -> [u8; 32] {
+    let mut word = [0u8; 32];
+    word[32 - bytes.len()..].copy_from_slice(bytes);
+    word
+}
@@ -0,0 +1,29 @@
+//! Helpers for executing DEX legs on-chain.
+//!
+//! The scanner itself never sends transactions; these helpers are for users who execute
+//! the DEX side of an opportunity with their own signer.
+
+pub mod approval;
+pub mod relay;
+pub mod simulation;
+pub mod swap;
+pub mod wallet;
+
+// re-exports
+pub use approval::{
+    ApprovalCache, KYBERSWAP_ROUTER_ADDRESS, approval_calldata, build_approval_tx, fetch_allowance,
+    simulate_approval,
+};
+pub use relay::{
+    FLASHBOTS_PROTECT_RPC_URL, FLASHBOTS_RELAY_URL, PrivateRelay, PrivateRelayConfig, RelayHint,
+    bundle_params,
+};
+pub use simulation::{SwapSimulation, simulate_route};
+pub use swap::{
+    GasFees, SWAP_GAS_LIMIT_BUFFER_PCT, build_leg_route, build_swap_tx, fetch_gas_fees,
+    prepare_swap_tx,
+};
+pub use wallet::{
+    CallbackSigner, DEFAULT_PRIVATE_KEY_ENV, ExecutionWallet, SignCallback, TransactionSigner,
+    local_signer_from_env,
+};
@@ -0,0 +1,280 @@
+//! Private transaction submission through Flashbots Protect / MEV-Share.
+//!
+//! Signed transactions sent to a public mempool can be sandwiched before they land.
+//! [PrivateRelay::send_transaction] forwards a signed transaction to a private RPC
+//! (Flashbots Protect by default) with optional MEV-Share hints; [PrivateRelay::send_bundle]
+//! submits a `mev_sendBundle` request, authenticated with the `X-Flashbots-Signature`
+//! header of a separate reputation key.
+
+use crate::common::MarketScannerError;
+use crate::common::dry_run::{DryRunAction, dry_run_enabled, record_dry_run};
+use ethers::core::types::{Bytes, H256};
+use ethers::core::utils::{hex, keccak256};
+use ethers::providers::{Http, Middleware, Provider};
+use ethers::signers::{LocalWallet, Signer};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+/// Flashbots Protect RPC (`eth_sendRawTransaction`).
+pub const FLASHBOTS_PROTECT_RPC_URL: &str = "https://rpc.flashbots.net";
+/// Flashbots relay (`mev_sendBundle`).
+pub const FLASHBOTS_RELAY_URL: &str = "https://relay.flashbots.net";
+
+/// Data shared with searchers through MEV-Share. Less data means less backrun rebate but
+/// also less information for searchers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RelayHint {
+    Calldata,
+    ContractAddress,
+    FunctionSelector,
+    Logs,
+    Hash,
+    DefaultLogs,
+}
+
+impl RelayHint {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RelayHint::Calldata => "calldata",
+            RelayHint::ContractAddress => "contract_address",
+            RelayHint::FunctionSelector => "function_selector",
+            RelayHint::Logs => "logs",
+            RelayHint::Hash => "hash",
+            RelayHint::DefaultLogs => "default_logs",
+        }
+    }
+}
+
+/// Private relay endpoints and MEV-Share preferences.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PrivateRelayConfig {
+    /// Private RPC for single transactions (`eth_sendRawTransaction`)
+    pub rpc_url: String,
+    /// Relay for bundles (`mev_sendBundle`)
+    pub relay_url: String,
+    /// MEV-Share hints; empty keeps the relay's defaults
+    pub hints: Vec<RelayHint>,
+    /// Bundles stay valid for this many blocks after the target block
+    pub max_block_offset: u64,
+}
+
+impl Default for PrivateRelayConfig {
+    fn default() -> Self {
+        Self {
+            rpc_url: FLASHBOTS_PROTECT_RPC_URL.to_string(),
+            relay_url: FLASHBOTS_RELAY_URL.to_string(),
+            hints: Vec::new(),
+            max_block_offset: 0,
+        }
+    }
+}
+
+impl PrivateRelayConfig {
+    pub fn with_rpc_url(mut self, rpc_url: &str) -> Self {
+        self.rpc_url = rpc_url.to_string();
+        self
+    }
+
+    pub fn with_relay_url(mut self, relay_url: &str) -> Self {
+        self.relay_url = relay_url.to_string();
+        self
+    }
+
+    pub fn with_hints(mut self, hints: Vec<RelayHint>) -> Self {
+        self.hints = hints;
+        self
+    }
+
+    pub fn with_max_block_offset(mut self, max_block_offset: u64) -> Self {
+        self.max_block_offset = max_block_offset;
+        self
+    }
+
+    /// Private RPC URL with the hints appended as `hint` query parameters.
+    pub fn rpc_url_with_hints(&self) -> String {
+        if self.hints.is_empty() {
+            return self.rpc_url.clone();
+        }
+        let query = self
+            .hints
+            .iter()
+            .map(|h| format!("hint={}", h.as_str()))
+            .collect::<Vec<_>>()
+            .join("&");
+        let separator = if self.rpc_url.contains('?') { '&' } else { '?' };
+        format!("{}{}{}", self.rpc_url, separator, query)
+    }
+}
+
+/// Submits signed transactions to a private relay instead of the public mempool.
+#[derive(Debug, Clone)]
+pub struct PrivateRelay {
+    config: PrivateRelayConfig,
+    provider: Provider<Http>,
+    client: reqwest::Client,
+    auth_signer: Option<LocalWallet>,
+}
+
+impl PrivateRelay {
+    pub fn new(config: PrivateRelayConfig) -> Result<Self, MarketScannerError> {
+        let provider = Provider::<Http>::try_from(config.rpc_url_with_hints().as_str())
+            .map_err(|e| MarketScannerError::ApiError(format!("invalid relay RPC URL: {}", e)))?;
+        Ok(Self {
+            config,
+            provider,
+            client: reqwest::Client::new(),
+            auth_signer: None,
+        })
+    }
+
+    /// Key used to sign bundle requests (`X-Flashbots-Signature`). It identifies the sender
+    /// for relay reputation and should not be the key that holds funds.
+    pub fn with_auth_signer(mut self, auth_signer: LocalWallet) -> Self {
+        self.auth_signer = Some(auth_signer);
+        self
+    }
+
+    pub fn config(&self) -> &PrivateRelayConfig {
+        &self.config
+    }
+
+    /// Sends one signed (RLP-encoded) transaction through the private RPC and returns its hash.
+    /// In dry-run mode nothing is sent and the transaction's hash is returned.
+    pub async fn send_transaction(&self, signed_tx: &Bytes) -> Result<H256, MarketScannerError> {
+        if dry_run_enabled() {
+            let hash = H256::from(keccak256(signed_tx));
+            record_dry_run(
+                &self.config.rpc_url,
+                DryRunAction::SendTransaction,
+                format!("0x{}", hex::encode(signed_tx)),
+                Some(format!("{:?}", hash)),
+            );
+            return Ok(hash);
+        }
+        let hash: H256 = self
+            .provider
+            .request("eth_sendRawTransaction", [signed_tx])
+            .await
+            .map_err(|e| MarketScannerError::WsRpcError(e.to_string()))?;
+        Ok(hash)
+    }
+
+    /// Same as [Self::send_transaction], first checking the target chain of the private RPC.
+    pub async fn send_transaction_on_chain(
+        &self,
+        signed_tx: &Bytes,
+        chain_id: u64,
+    ) -> Result<H256, MarketScannerError> {
+        let relay_chain = self
+            .provider
+            .get_chainid()
+            .await
+            .map_err(|e| MarketScannerError::WsRpcError(e.to_string()))?;
+        if relay_chain.as_u64() != chain_id {
+            return Err(MarketScannerError::ApiError(format!(
+                "relay serves chain {}, transaction targets chain {}",
+                relay_chain, chain_id
+            )));
+        }
+        self.send_transaction(signed_tx).await
+    }
+
+    /// Submits signed transactions as an MEV-Share bundle targeting `block_number` (up to
+    /// `max_block_offset` blocks later). Returns the bundle hash; in dry-run mode the signed
+    /// request is not sent and the keccak256 hash of its body is returned.
+    pub async fn send_bundle(
+        &self,
+        signed_txs: &[Bytes],
+        block_number: u64,
+    ) -> Result<H256, MarketScannerError> {
+        let auth_signer = self.auth_signer.as_ref().ok_or_else(|| {
+            MarketScannerError::ApiError("bundle submission requires an auth signer".to_string())
+        })?;
+        if signed_txs.is_empty() {
+            return Err(MarketScannerError::ApiError("empty bundle".to_string()));
+        }
+
+        let body = json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "mev_sendBundle",
+            "params": [bundle_params(signed_txs, block_number, &self.config)],
+        })
+        .to_string();
+        let signature = flashbots_signature(auth_signer, &body).await?;
+        if dry_run_enabled() {
+            let hash = H256::from(keccak256(body.as_bytes()));
+            record_dry_run(
+                &self.config.relay_url,
+                DryRunAction::SendBundle,
+                body,
+                Some(format!("{:?}", hash)),
+            );
+            return Ok(hash);
+        }
+
+        let response: serde_json::Value = self
+            .client
+            .post(&self.config.relay_url)
+            .header("Content-Type", "application/json")
+            .header("X-Flashbots-Signature", signature)
+            .body(body)
+            .send()
+            .await?
+            .json()
+            .await?;
+
+        if let Some(error) = response.get("error") {
+            return Err(MarketScannerError::ApiError(format!(
+                "relay error: {}",
+                error
+            )));
+        }
+        let bundle_hash = response["result"]["bundleHash"]
+            .as_str()
+            .ok_or_else(|| MarketScannerError::ApiError("missing bundleHash".to_string()))?;
+        bundle_hash
+            .parse()
+            .map_err(|e| MarketScannerError::ApiError(format!("invalid bundleHash: {}", e)))
+    }
+}
+
+/// `mev_sendBundle` parameters (MEV-Share bundle v0.1).
+pub fn bundle_params(
+    signed_txs: &[Bytes],
+    block_number: u64,
+    config: &PrivateRelayConfig,
+) -> serde_json::Value {
+    let body: Vec<_> = signed_txs
+        .iter()
+        .map(|tx| json!({ "tx": tx, "canRevert": false }))
+        .collect();
+    let mut params = json!({
+        "version": "v0.1",
+        "inclusion": {
+            "block": format!("0x{:x}", block_number),
+            "maxBlock": format!("0x{:x}", block_number + config.max_block_offset),
+        },
+        "body": body,
+    });
+    if !config.hints.is_empty() {
+        let hints: Vec<_> = config.hints.iter().map(|h| h.as_str()).collect();
+        params["privacy"] = json!({ "hints": hints });
+    }
+    params
+}
+
+/// `address:signature` where the signature is an EIP-191 signature of the hex-encoded
+/// keccak256 hash of the request body.
+async fn flashbots_signature(
+    signer: &LocalWallet,
+    body: &str,
+) -> Result<String, MarketScannerError> {
+    let digest = format!("0x{}", hex::encode(keccak256(body.as_bytes())));
+    let signature = signer
+        .sign_message(digest)
+        .await
+        .map_err(|e| MarketScannerError::ApiError(format!("signing failed: {}", e)))?;
+    Ok(format!("{:?}:0x{}", signer.address(), signature))
+}
@@ -0,0 +1,97 @@
+//! Pre-flight simulation of built swaps.
+//!
+//! Quotes can go stale between the scan and execution. [simulate_route] runs the built
+//! router call with `eth_call` against the latest state, so the real output can be compared
+//! with the aggregator's expected amount before a transaction is signed. Routes for a DEX
+//! leg come from [crate::dex::execution::build_leg_route].
+
+use crate::common::MarketScannerError;
+use crate::dex::amount::{parse_raw_amount, u256_to_f64};
+use crate::dex::kyberswap::KyberSwapBuiltRoute;
+use crate::scanner::ArbitrageOpportunity;
+use ethers::core::types::transaction::eip2718::TypedTransaction;
+use ethers::core::types::{Address, Bytes, Eip1559TransactionRequest, U256};
+use ethers::providers::Middleware;
+use serde::{Deserialize, Serialize};
+
+/// Result of simulating a built route.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SwapSimulation {
+    /// Output amount quoted by the aggregator (base units of the output token)
+    pub expected_amount_out: U256,
+    /// Output amount returned by the simulated router call
+    pub simulated_amount_out: U256,
+    /// Gas the router reports it used, when returned
+    pub gas_used: Option<U256>,
+}
+
+impl SwapSimulation {
+    /// How far the simulated output falls short of the expected output, in basis points
+    /// (0 when it matches or exceeds it).
+    pub fn shortfall_bps(&self) -> f64 {
+        if self.expected_amount_out.is_zero()
+            || self.simulated_amount_out >= self.expected_amount_out
+        {
+            return 0.0;
+        }
+        let shortfall = self.expected_amount_out - self.simulated_amount_out;
+        u256_to_f64(shortfall) / u256_to_f64(self.expected_amount_out) * 10_000.0
+    }
+
+    /// Whether the simulated output is at most `tolerance_bps` below the expected output.
+    pub fn within_tolerance(&self, tolerance_bps: u32) -> bool {
+        self.shortfall_bps() <= tolerance_bps as f64
+    }
+
+    /// Records the simulated output on `opportunity`
+    /// ([ArbitrageOpportunity::simulated_amount_out]).
+    pub fn annotate(&self, opportunity: &mut ArbitrageOpportunity) {
+        opportunity.simulated_amount_out = Some(self.simulated_amount_out.to_string());
+    }
+}
+
+/// Runs the router call of `route` from `from` with `eth_call` at the latest block. The
+/// sender must already hold the input token and have approved the router, otherwise the
+/// call reverts. Fails if the call reverts or returns no output amount.
+pub async fn simulate_route<M: Middleware>(
+    provider: &M,
+    route: &KyberSwapBuiltRoute,
+    from: Address,
+) -> Result<SwapSimulation, MarketScannerError> {
+    let router: Address = route
+        .router_address
+        .parse()
+        .map_err(|e| MarketScannerError::ApiError(format!("invalid router address: {}", e)))?;
+    let data: Bytes = route
+        .data
+        .parse()
+        .map_err(|e| MarketScannerError::ApiError(format!("invalid route calldata: {}", e)))?;
+    let value = match route.transaction_value.as_deref() {
+        Some(value) if !value.is_empty() => parse_raw_amount(value)?,
+        _ => U256::zero(),
+    };
+    let expected_amount_out = parse_raw_amount(&route.amount_out)?;
+
+    let tx: TypedTransaction = Eip1559TransactionRequest::new()
+        .from(from)
+        .to(router)
+        .data(data)
+        .value(value)
+        .into();
+    let res = provider
+        .call(&tx, None)
+        .await
+        .map_err(|e| MarketScannerError::WsRpcError(format!("swap simulation reverted: {}", e)))?;
+    // Router swaps return (uint256 returnAmount, uint256 gasUsed)
+    if res.len() < 32 {
+        return Err(MarketScannerError::WsRpcError(
+            "swap simulation returned no output amount".into(),
+        ));
+    }
+    let gas_used = (res.len() >= 64).then(|| U256::from_big_endian(&res[32..64]));
+    Ok(SwapSimulation {
+        expected_amount_out,
+        simulated_amount_out: U256::from_big_endian(&res[..32]),
+        gas_used,
+    })
+}
@@ -0,0 +1,115 @@
+//! EIP-1559 swap transactions for aggregator routes.
+//!
+//! [prepare_swap_tx] turns a DEX leg from scanner output into a ready-to-sign
+//! [TypedTransaction]: it encodes the leg's KyberSwap route through the route build
+//! endpoint and prices it with the node's EIP-1559 fee estimate.
+
+use crate::common::{AmountSide, DexPrice, MarketScannerError};
+use crate::dex::amount::parse_raw_amount;
+use crate::dex::chains::ChainId;
+use crate::dex::kyberswap::{KyberSwap, KyberSwapBuiltRoute};
+use ethers::core::types::transaction::eip2718::TypedTransaction;
+use ethers::core::types::{Address, Bytes, Eip1559TransactionRequest, U256};
+use ethers::providers::Middleware;
+use serde::{Deserialize, Serialize};
+
+/// Extra gas on top of the aggregator's estimate, in percent.
+pub const SWAP_GAS_LIMIT_BUFFER_PCT: u64 = 20;
+
+/// EIP-1559 fee parameters (wei).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GasFees {
+    pub max_fee_per_gas: U256,
+    pub max_priority_fee_per_gas: U256,
+}
+
+/// Current EIP-1559 fee estimate of the node behind `provider`.
+pub async fn fetch_gas_fees<M: Middleware>(provider: &M) -> Result<GasFees, MarketScannerError> {
+    let (max_fee_per_gas, max_priority_fee_per_gas) = provider
+        .estimate_eip1559_fees(None)
+        .await
+        .map_err(|e| MarketScannerError::WsRpcError(e.to_string()))?;
+    Ok(GasFees {
+        max_fee_per_gas,
+        max_priority_fee_per_gas,
+    })
+}
+
+/// Unsigned EIP-1559 transaction executing `route` from `sender`. The gas limit is the
+/// route's estimate plus [SWAP_GAS_LIMIT_BUFFER_PCT] (left unset if the route has none).
+pub fn build_swap_tx(
+    chain_id: u64,
+    sender: Address,
+    route: &KyberSwapBuiltRoute,
+    fees: &GasFees,
+) -> Result<TypedTransaction, MarketScannerError> {
+    let router: Address = route
+        .router_address
+        .parse()
+        .map_err(|e| MarketScannerError::ApiError(format!("invalid router address: {}", e)))?;
+    let data: Bytes = route
+        .data
+        .parse()
+        .map_err(|e| MarketScannerError::ApiError(format!("invalid route calldata: {}", e)))?;
+    let value = match route.transaction_value.as_deref() {
+        Some(value) if !value.is_empty() => parse_raw_amount(value)?,
+        _ => U256::zero(),
+    };
+
+    let mut tx = Eip1559TransactionRequest::new()
+        .chain_id(chain_id)
+        .from(sender)
+        .to(router)
+        .data(data)
+        .value(value)
+        .max_fee_per_gas(fees.max_fee_per_gas)
+        .max_priority_fee_per_gas(fees.max_priority_fee_per_gas);
+    if let Some(gas) = route.gas.as_deref() {
+        let gas = parse_raw_amount(gas)?;
+        tx = tx.gas(gas + gas * SWAP_GAS_LIMIT_BUFFER_PCT / 100);
+    }
+    Ok(tx.into())
+}
+
+/// Encodes one side of a KyberSwap [DexPrice] through the route build endpoint:
+/// [AmountSide::Buy] spends the quote token for the base token (bid route),
+/// [AmountSide::Sell] sells the base token (ask route). Proceeds go to `sender`.
+pub async fn build_leg_route(
+    kyberswap: &KyberSwap,
+    chain: &ChainId,
+    price: &DexPrice,
+    side: AmountSide,
+    sender: Address,
+    slippage_bps: u32,
+) -> Result<KyberSwapBuiltRoute, MarketScannerError> {
+    let route_data = match side {
+        AmountSide::Buy => price.bid_route_data.as_ref(),
+        AmountSide::Sell => price.ask_route_data.as_ref(),
+    };
+    let route_summary = route_data
+        .and_then(|data| data.get("routeSummary"))
+        .ok_or_else(|| {
+            MarketScannerError::ApiError(format!("no route data for {} leg", price.symbol))
+        })?;
+
+    let sender_hex = format!("{:?}", sender);
+    kyberswap
+        .build_route(chain, route_summary, &sender_hex, &sender_hex, slippage_bps)
+        .await
+}
+
+/// Builds the swap for one side of a KyberSwap [DexPrice] (see [build_leg_route]) and
+/// prices it with the node's fee estimate; only signing and sending remain.
+pub async fn prepare_swap_tx<M: Middleware>(
+    provider: &M,
+    kyberswap: &KyberSwap,
+    chain: &ChainId,
+    price: &DexPrice,
+    side: AmountSide,
+    sender: Address,
+    slippage_bps: u32,
+) -> Result<TypedTransaction, MarketScannerError> {
+    let route = build_leg_route(kyberswap, chain, price, side, sender, slippage_bps).await?;
+    let fees = fetch_gas_fees(provider).await?;
+    build_swap_tx(chain.clone() as u64, sender, &route, &fees)
+}
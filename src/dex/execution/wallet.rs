@@ -0,0 +1,257 @@
+//! Wallet used to execute DEX legs: signer, balances and nonces.
+//!
+//! [TransactionSigner] abstracts over where keys live. Every ethers signer implements it
+//! (a [LocalWallet] loaded with [local_signer_from_env], or a Ledger when the application
+//! enables ethers' `ledger` feature), and [CallbackSigner] forwards signing to any external
+//! service (KMS, remote signer, custody API). [ExecutionWallet] pairs a signer with a
+//! provider, hands out nonces locally so several swaps can be sent back to back, and signs
+//! and sends the transactions built by [crate::dex::execution::prepare_swap_tx].
+
+use crate::common::MarketScannerError;
+use crate::common::dry_run::{DryRunAction, dry_run_enabled, record_dry_run};
+use crate::dex::execution::relay::PrivateRelay;
+use async_trait::async_trait;
+use ethers::core::types::transaction::eip2718::TypedTransaction;
+use ethers::core::types::{Address, BlockNumber, Bytes, H256, Signature, TransactionRequest, U256};
+use ethers::core::utils::{hex, keccak256};
+use ethers::providers::Middleware;
+use ethers::signers::{LocalWallet, Signer};
+use futures::future::BoxFuture;
+use std::fmt;
+use std::sync::{Arc, Mutex};
+
+/// Environment variable read by [local_signer_from_env] when no name is given.
+pub const DEFAULT_PRIVATE_KEY_ENV: &str = "WALLET_PRIVATE_KEY";
+
+// balanceOf(address)
+const SELECTOR_BALANCE_OF: [u8; 4] = [0x70, 0xa0, 0x82, 0x31];
+
+/// Signs transactions for [ExecutionWallet].
+#[async_trait]
+pub trait TransactionSigner: Send + Sync {
+    /// Address the signatures recover to.
+    fn signer_address(&self) -> Address;
+
+    /// Signs a fully populated transaction (chain ID, nonce, gas and fees set).
+    async fn sign_tx(&self, tx: &TypedTransaction) -> Result<Signature, MarketScannerError>;
+}
+
+#[async_trait]
+impl<S: Signer> TransactionSigner for S {
+    fn signer_address(&self) -> Address {
+        self.address()
+    }
+
+    async fn sign_tx(&self, tx: &TypedTransaction) -> Result<Signature, MarketScannerError> {
+        self.sign_transaction(tx)
+            .await
+            .map_err(|e| MarketScannerError::ApiError(format!("signing failed: {}", e)))
+    }
+}
+
+/// Signing callback of a [CallbackSigner].
+pub type SignCallback = Arc<
+    dyn Fn(TypedTransaction) -> BoxFuture<'static, Result<Signature, MarketScannerError>>
+        + Send
+        + Sync,
+>;
+
+/// Signer that delegates to an external signing service.
+#[derive(Clone)]
+pub struct CallbackSigner {
+    address: Address,
+    sign: SignCallback,
+}
+
+impl CallbackSigner {
+    pub fn new<F>(address: Address, sign: F) -> Self
+    where
+        F: Fn(TypedTransaction) -> BoxFuture<'static, Result<Signature, MarketScannerError>>
+            + Send
+            + Sync
+            + 'static,
+    {
+        Self {
+            address,
+            sign: Arc::new(sign),
+        }
+    }
+}
+
+impl fmt::Debug for CallbackSigner {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CallbackSigner")
+            .field("address", &self.address)
+            .finish_non_exhaustive()
+    }
+}
+
+#[async_trait]
+impl TransactionSigner for CallbackSigner {
+    fn signer_address(&self) -> Address {
+        self.address
+    }
+
+    async fn sign_tx(&self, tx: &TypedTransaction) -> Result<Signature, MarketScannerError> {
+        (self.sign)(tx.clone()).await
+    }
+}
+
+/// Local signer from a hex private key in the environment variable `var`
+/// ([DEFAULT_PRIVATE_KEY_ENV] if `None`). Loads `.env` first.
+pub fn local_signer_from_env(var: Option<&str>) -> Result<LocalWallet, MarketScannerError> {
+    crate::dex::load_dotenv();
+    let var = var.unwrap_or(DEFAULT_PRIVATE_KEY_ENV);
+    let key = std::env::var(var)
+        .map_err(|_| MarketScannerError::ApiError(format!("{} is not set", var)))?;
+    key.trim()
+        .trim_start_matches("0x")
+        .parse()
+        .map_err(|e| MarketScannerError::ApiError(format!("invalid private key in {}: {}", var, e)))
+}
+
+/// Signer plus provider: balances, local nonce tracking, signing and sending.
+pub struct ExecutionWallet<M, S> {
+    provider: M,
+    signer: S,
+    chain_id: u64,
+    /// Next nonce to hand out; `None` until fetched from the node
+    next_nonce: Mutex<Option<U256>>,
+}
+
+impl<M: Middleware, S: TransactionSigner> ExecutionWallet<M, S> {
+    pub fn new(provider: M, signer: S, chain_id: u64) -> Self {
+        Self {
+            provider,
+            signer,
+            chain_id,
+            next_nonce: Mutex::new(None),
+        }
+    }
+
+    pub fn address(&self) -> Address {
+        self.signer.signer_address()
+    }
+
+    pub fn chain_id(&self) -> u64 {
+        self.chain_id
+    }
+
+    pub fn provider(&self) -> &M {
+        &self.provider
+    }
+
+    /// Native token balance (wei).
+    pub async fn native_balance(&self) -> Result<U256, MarketScannerError> {
+        self.provider
+            .get_balance(self.address(), None)
+            .await
+            .map_err(|e| MarketScannerError::WsRpcError(e.to_string()))
+    }
+
+    /// ERC-20 `balanceOf` of the wallet (token units).
+    pub async fn token_balance(&self, token: Address) -> Result<U256, MarketScannerError> {
+        let mut data = SELECTOR_BALANCE_OF.to_vec();
+        data.extend_from_slice(&[0u8; 12]);
+        data.extend_from_slice(self.address().as_bytes());
+        let tx: TypedTransaction = TransactionRequest::new().to(token).data(data).into();
+        let res = self
+            .provider
+            .call(&tx, None)
+            .await
+            .map_err(|e| MarketScannerError::WsRpcError(e.to_string()))?;
+        if res.len() < 32 {
+            return Err(MarketScannerError::WsRpcError(
+                "balanceOf response too short".into(),
+            ));
+        }
+        Ok(U256::from_big_endian(&res[..32]))
+    }
+
+    /// Reserves the next nonce. The pending transaction count is fetched once; later calls
+    /// count up locally so back-to-back transactions do not reuse a nonce.
+    pub async fn next_nonce(&self) -> Result<U256, MarketScannerError> {
+        if let Some(nonce) = self.take_local_nonce() {
+            return Ok(nonce);
+        }
+        let fetched = self
+            .provider
+            .get_transaction_count(self.address(), Some(BlockNumber::Pending.into()))
+            .await
+            .map_err(|e| MarketScannerError::WsRpcError(e.to_string()))?;
+        let mut next = self.next_nonce.lock().unwrap_or_else(|e| e.into_inner());
+        // Another caller may have fetched meanwhile; never go backwards
+        let nonce = next.map_or(fetched, |local| local.max(fetched));
+        *next = Some(nonce + 1);
+        Ok(nonce)
+    }
+
+    /// Drops the local nonce so the next transaction refetches it (e.g. after a failed send
+    /// or a transaction sent from elsewhere).
+    pub fn reset_nonce(&self) {
+        *self.next_nonce.lock().unwrap_or_else(|e| e.into_inner()) = None;
+    }
+
+    fn take_local_nonce(&self) -> Option<U256> {
+        let mut next = self.next_nonce.lock().unwrap_or_else(|e| e.into_inner());
+        let nonce = (*next)?;
+        *next = Some(nonce + 1);
+        Some(nonce)
+    }
+
+    /// Fills in sender, chain ID, nonce and (if missing) gas, signs `tx` and returns the
+    /// raw signed transaction.
+    pub async fn sign(&self, mut tx: TypedTransaction) -> Result<Bytes, MarketScannerError> {
+        tx.set_from(self.address());
+        tx.set_chain_id(self.chain_id);
+        if tx.nonce().is_none() {
+            tx.set_nonce(self.next_nonce().await?);
+        }
+        if tx.gas().is_none() {
+            let gas = self
+                .provider
+                .estimate_gas(&tx, None)
+                .await
+                .map_err(|e| MarketScannerError::WsRpcError(e.to_string()))?;
+            tx.set_gas(gas);
+        }
+        let signature = self.signer.sign_tx(&tx).await?;
+        Ok(tx.rlp_signed(&signature))
+    }
+
+    /// Signs and broadcasts `tx` through the provider. Returns the transaction hash. In
+    /// dry-run mode ([crate::common::set_dry_run]) the signed transaction is logged instead
+    /// of broadcast; its nonce stays used, as if it had been sent.
+    pub async fn send(&self, tx: TypedTransaction) -> Result<H256, MarketScannerError> {
+        let signed = self.sign(tx).await?;
+        if dry_run_enabled() {
+            let hash = H256::from(keccak256(&signed));
+            record_dry_run(
+                &format!("chain {}", self.chain_id),
+                DryRunAction::SendTransaction,
+                format!("0x{}", hex::encode(&signed)),
+                Some(format!("{:?}", hash)),
+            );
+            return Ok(hash);
+        }
+        match self.provider.send_raw_transaction(signed).await {
+            Ok(pending) => Ok(pending.tx_hash()),
+            Err(e) => {
+                self.reset_nonce();
+                Err(MarketScannerError::WsRpcError(e.to_string()))
+            }
+        }
+    }
+
+    /// Signs `tx` and submits it through a private relay instead of the public mempool.
+    pub async fn send_private(
+        &self,
+        tx: TypedTransaction,
+        relay: &PrivateRelay,
+    ) -> Result<H256, MarketScannerError> {
+        let signed = self.sign(tx).await?;
+        relay.send_transaction(&signed).await.inspect_err(|_| {
+            self.reset_nonce();
+        })
+    }
+}
@@ -0,0 +1,146 @@
+//! Per-chain routability of the KyberSwap aggregator.
+//!
+//! [KyberSwap::supported_chains] probes every [ChainId] with a tiny native → wrapped native
+//! route. Results are kept process-wide; quotes on a chain whose last probe failed (within
+//! [CHAIN_PROBE_TTL]) fail fast with [MarketScannerError::UnsupportedChain], and the
+//! scanner skips such chains instead of reporting them mid-scan.
+
+use super::KYBERSWAP_API_BASE;
+use super::KyberSwap;
+use super::types::KyberSwapRoutesResponse;
+use crate::common::{MarketScannerError, Timestamp};
+use crate::dex::chains::ChainId;
+use futures::future::join_all;
+use std::collections::HashMap;
+use std::sync::{LazyLock, RwLock};
+use std::time::Duration;
+
+/// How long a failed probe keeps a chain out of quotes before it is tried again.
+pub const CHAIN_PROBE_TTL: Duration = Duration::from_secs(300);
+
+/// Native token placeholder of the KyberSwap API
+const NATIVE_TOKEN: &str = "0xEeeeeEeeeEeEeeEeEeEeeEEEeeeeEeeeeeeeEEeE";
+/// 0.001 native token (18 decimals on every supported chain)
+const PROBE_AMOUNT_IN: &str = "1000000000000000";
+
+/// Result of the last routability probe of a chain.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChainProbe {
+    pub chain: ChainId,
+    pub routable: bool,
+    pub checked_at: Timestamp,
+    /// Why the probe failed
+    pub error: Option<String>,
+}
+
+static CHAIN_PROBES: LazyLock<RwLock<HashMap<ChainId, ChainProbe>>> =
+    LazyLock::new(|| RwLock::new(HashMap::new()));
+
+/// Wrapped native token used as probe route target.
+fn wrapped_native(chain: &ChainId) -> &'static str {
+    match chain {
+        ChainId::ETHEREUM => "0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2",
+        ChainId::BSC => "0xbb4CdB9CBd36B01bD1cBaEBF2De08d9173bc095c",
+        ChainId::POLYGON => "0x0d500B1d8E8eF31E21C99d1Db9A6444d3ADf1270",
+        ChainId::AVALANCHE => "0xB31f66AA3C1e785363F0875A1B74E27b85FD66c7",
+        ChainId::ARBITRUM => "0x82aF49447D8a07e3bd95BD0d56f35241523fBab1",
+        ChainId::OPTIMISM | ChainId::BASE | ChainId::UNICHAIN => {
+            "0x4200000000000000000000000000000000000006"
+        }
+        ChainId::PLASMA => "0x6100E367285b01F48D07953803A2d8dCA5D19873",
+        ChainId::SONIC => "0x039e2fB66102314Ce7b64Ce5Ce3E5183bc94aD38",
+        ChainId::RONIN => "0xe514d9DEB7966c8BE0ca922de8a064264eA6bcd4",
+        ChainId::HyperEVM => "0x5555555555555555555555555555555555555555",
+        ChainId::LINEA => "0xe5D7C2a44FfDDf6b295A15c148167daaAf5Cf34f",
+        ChainId::MANTLE => "0x78c1b0C915c4FAA5FffA6CAbf0219DA63d7f4cb8",
+    }
+}
+
+/// Records the outcome of a probe (or of a quote that proved a chain routable).
+pub fn record_chain_probe(probe: ChainProbe) {
+    CHAIN_PROBES
+        .write()
+        .unwrap_or_else(|e| e.into_inner())
+        .insert(probe.chain.clone(), probe);
+}
+
+/// Last probe of `chain`, if it was ever probed.
+pub fn chain_probe(chain: &ChainId) -> Option<ChainProbe> {
+    CHAIN_PROBES
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .get(chain)
+        .cloned()
+}
+
+/// Forgets every probe result, so all chains are quoted again.
+pub fn clear_chain_probes() {
+    CHAIN_PROBES
+        .write()
+        .unwrap_or_else(|e| e.into_inner())
+        .clear();
+}
+
+/// Whether quotes on `chain` are skipped: its last probe failed less than
+/// [CHAIN_PROBE_TTL] ago. Unprobed chains are never skipped.
+pub fn is_chain_skipped(chain: &ChainId) -> bool {
+    chain_probe(chain)
+        .is_some_and(|probe| !probe.routable && !probe.checked_at.is_older_than(CHAIN_PROBE_TTL))
+}
+
+impl KyberSwap {
+    /// Probes every [ChainId] concurrently and returns the chains that currently route.
+    /// Each result is recorded (see [chain_probe]).
+    pub async fn supported_chains(&self) -> Vec<ChainId> {
+        let probes = join_all(ChainId::ALL.iter().map(|chain| self.probe_chain(chain))).await;
+        probes
+            .into_iter()
+            .filter(|probe| probe.routable)
+            .map(|probe| probe.chain)
+            .collect()
+    }
+
+    /// Requests a small native → wrapped native route on `chain` and records the result.
+    pub async fn probe_chain(&self, chain: &ChainId) -> ChainProbe {
+        let error = self.request_probe_route(chain).await.err();
+        let probe = ChainProbe {
+            chain: chain.clone(),
+            routable: error.is_none(),
+            checked_at: Timestamp::now(),
+            error: error.map(|e| e.to_string()),
+        };
+        record_chain_probe(probe.clone());
+        probe
+    }
+
+    async fn request_probe_route(&self, chain: &ChainId) -> Result<(), MarketScannerError> {
+        let url = format!(
+            "{}/{}/api/v1/routes?tokenIn={}&tokenOut={}&amountIn={}",
+            KYBERSWAP_API_BASE,
+            chain.name(),
+            NATIVE_TOKEN,
+            wrapped_native(chain),
+            PROBE_AMOUNT_IN
+        );
+        let response = self.client.get(&url).send().await?;
+
+        let status = response.status();
+        if !status.is_success() {
+            return Err(MarketScannerError::ApiError(format!(
+                "KyberSwap API error: status {} on {}",
+                status,
+                chain.name()
+            )));
+        }
+        let routes: KyberSwapRoutesResponse = response.json().await.map_err(|e| {
+            MarketScannerError::ApiError(format!("Failed to parse KyberSwap response: {}", e))
+        })?;
+        if routes.code != 0 || routes.data.is_none() {
+            return Err(MarketScannerError::ApiError(format!(
+                "KyberSwap API error: {}",
+                routes.message.unwrap_or_default()
+            )));
+        }
+        Ok(())
+    }
+}
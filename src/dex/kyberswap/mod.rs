@@ -1,18 +1,33 @@
+mod chains;
+mod requote;
 mod types;
 mod utils;
 
+pub use chains::{
+    CHAIN_PROBE_TTL, ChainProbe, chain_probe, clear_chain_probes, is_chain_skipped,
+    record_chain_probe,
+};
+pub use requote::{
+    RouteRequote, cache_route, cached_route, clear_route_cache, diff_routes, route_hops,
+};
+pub use types::KyberSwapBuiltRoute;
+
 use crate::common::{
-    DEXTrait, DexAggregator, DexPrice, DexRouteSummary, Exchange, ExchangeTrait,
-    MarketScannerError, find_mid_price, get_timestamp_millis,
+    AmountSpec, DEXTrait, DexAggregator, DexPrice, DexRouteSummary, DexSourceFilter, Exchange,
+    ExchangeTrait, MarketScannerError, RequestPriority, Timestamp, acquire_request_slot,
+    find_mid_price,
 };
 use crate::create_exchange;
+use crate::dex::amount::{f64_to_raw, parse_raw_amount, raw_ratio, raw_to_f64};
+use crate::dex::chains::ChainId;
+use crate::dex::chains::tokens::pair_transfer_tax_bps;
 use async_trait::async_trait;
-use types::KyberSwapRoutesResponse;
-use utils::{calculate_amount_for_value, create_http_client_with_browser_headers, wei_to_eth};
+use types::{KyberSwapBuildRouteResponse, KyberSwapRoutesData, KyberSwapRoutesResponse};
+use utils::browser_http_config;
 
 const KYBERSWAP_API_BASE: &str = "https://aggregator-api.kyberswap.com";
 
-create_exchange!(KyberSwap);
+create_exchange!(KyberSwap, browser_http_config());
 
 #[async_trait]
 impl ExchangeTrait for KyberSwap {
@@ -40,8 +55,8 @@ impl ExchangeTrait for KyberSwap {
             api_base
         );
 
-        // Build client with custom headers to bypass Cloudflare protection
-        let client = create_http_client_with_browser_headers()?;
+        // Default headers (see browser_http_config) pass Cloudflare protection
+        let client = self.client();
 
         let response = client
             .get(&url)
@@ -55,6 +70,11 @@ impl ExchangeTrait for KyberSwap {
             Err(MarketScannerError::HealthCheckFailed)
         }
     }
+
+    async fn get_server_time(&self) -> Result<Timestamp, MarketScannerError> {
+        // No server time endpoint: the HTTP Date header has second resolution
+        self.get_date_header("ethereum/api/v1/routes").await
+    }
 }
 
 //TODO: add qutoto amount in params
@@ -68,6 +88,27 @@ impl DEXTrait for KyberSwap {
         base_token: &crate::dex::chains::Token,
         quote_token: &crate::dex::chains::Token,
         quote_amount: f64,
+    ) -> Result<DexPrice, MarketScannerError> {
+        self.get_price_for_amount(base_token, quote_token, AmountSpec::Quote(quote_amount))
+            .await
+    }
+
+    async fn get_price_for_amount(
+        &self,
+        base_token: &crate::dex::chains::Token,
+        quote_token: &crate::dex::chains::Token,
+        amount: AmountSpec,
+    ) -> Result<DexPrice, MarketScannerError> {
+        self.get_price_with_sources(base_token, quote_token, amount, &DexSourceFilter::default())
+            .await
+    }
+
+    async fn get_price_with_sources(
+        &self,
+        base_token: &crate::dex::chains::Token,
+        quote_token: &crate::dex::chains::Token,
+        amount: AmountSpec,
+        sources: &DexSourceFilter,
     ) -> Result<DexPrice, MarketScannerError> {
         // Validate that both tokens are on the same chain
         if base_token.chain_id != quote_token.chain_id {
@@ -77,7 +118,13 @@ impl DEXTrait for KyberSwap {
             )));
         }
 
-        let quote_amount_str = calculate_amount_for_value(quote_amount, quote_token.decimal);
+        // Chains that failed their last probe are skipped until it expires
+        if is_chain_skipped(&base_token.chain_id) {
+            return Err(MarketScannerError::UnsupportedChain(format!(
+                "KyberSwap cannot route on {}",
+                base_token.chain_id.name()
+            )));
+        }
 
         // Get chain-specific API base URL from token's chain_id
         let chain_name = base_token.chain_id.name();
@@ -86,58 +133,77 @@ impl DEXTrait for KyberSwap {
         // Create symbol from token symbols (for DexPrice)
         let normalized = format!("{}{}", base_token.symbol, quote_token.symbol);
 
-        // Build client with custom headers to bypass Cloudflare protection
-        let client = create_http_client_with_browser_headers()?;
-
-        // First Calculate Bid price (quote token -> base token)
-        let bid_endpoint = format!(
-            "{}/routes?tokenIn={}&tokenOut={}&amountIn={}&gasInclude=true&saveGas=0&excludedSources=bebop,smardex,dodo",
-            api_base, quote_token.address, base_token.address, quote_amount_str
-        );
-
-        let bid_response_raw = client
-            .get(&bid_endpoint)
-            .send()
-            .await
-            .map_err(|e| MarketScannerError::HttpError(e))?;
-
-        let status = bid_response_raw.status();
-        if !status.is_success() {
-            let error_text = bid_response_raw.text().await.unwrap_or_default();
-            return Err(MarketScannerError::ApiError(format!(
-                "KyberSwap API error: status {} - {}",
-                status, error_text
-            )));
-        }
-
-        let bid_response: KyberSwapRoutesResponse = bid_response_raw.json().await.map_err(|e| {
-            MarketScannerError::ApiError(format!("Failed to parse KyberSwap response: {}", e))
-        })?;
-
-        if bid_response.code != 0 {
-            return Err(MarketScannerError::ApiError(format!(
-                "KyberSwap API error: {}",
-                bid_response.message.unwrap_or_default()
-            )));
-        }
-
-        let bid_data = bid_response.data.ok_or_else(|| {
-            MarketScannerError::ApiError("KyberSwap API returned no data".to_string())
-        })?;
+        // The exact amount is swapped first; the other side reuses its raw amount_out so both
+        // routes cover the same size
+        let (bid_data, ask_data) = match amount {
+            AmountSpec::Quote(quote_amount) => {
+                // Bid route: quote token -> base token
+                let quote_amount_str = f64_to_raw(quote_amount, quote_token.decimal)?.to_string();
+                let bid_data = self
+                    .fetch_route(
+                        &api_base,
+                        quote_token,
+                        base_token,
+                        &quote_amount_str,
+                        sources,
+                    )
+                    .await?;
+                // Ask route: selling the base token bought above (base -> quote)
+                let ask_data = self
+                    .fetch_route(
+                        &api_base,
+                        base_token,
+                        quote_token,
+                        &bid_data.route_summary.amount_out,
+                        sources,
+                    )
+                    .await?;
+                (bid_data, ask_data)
+            }
+            AmountSpec::Base(base_amount) => {
+                // Ask route: base token -> quote token
+                let base_amount_str = f64_to_raw(base_amount, base_token.decimal)?.to_string();
+                let ask_data = self
+                    .fetch_route(
+                        &api_base,
+                        base_token,
+                        quote_token,
+                        &base_amount_str,
+                        sources,
+                    )
+                    .await?;
+                // Bid route: buying back with the quote token received above (quote -> base)
+                let bid_data = self
+                    .fetch_route(
+                        &api_base,
+                        quote_token,
+                        base_token,
+                        &ask_data.route_summary.amount_out,
+                        sources,
+                    )
+                    .await?;
+                (bid_data, ask_data)
+            }
+        };
 
-        // Parse amounts using safe conversion with Decimal for precision
-        let bid_amount_in_decimal =
-            wei_to_eth(&bid_data.route_summary.amount_in, quote_token.decimal)?;
-        let bid_amount_out_decimal =
-            wei_to_eth(&bid_data.route_summary.amount_out, base_token.decimal)?;
+        // Raw amounts stay 256-bit; f64 only for the reported quantities and prices
+        let bid_amount_in_raw = parse_raw_amount(&bid_data.route_summary.amount_in)?;
+        let bid_amount_out_raw = parse_raw_amount(&bid_data.route_summary.amount_out)?;
+        let bid_amount_in_decimal = raw_to_f64(bid_amount_in_raw, quote_token.decimal);
+        let bid_amount_out_decimal = raw_to_f64(bid_amount_out_raw, base_token.decimal);
         // Price per 1 base token in  (quote token)
-        let bid_price = utils::safe_divide(bid_amount_in_decimal, bid_amount_out_decimal)?;
+        let bid_price = raw_ratio(
+            bid_amount_in_raw,
+            quote_token.decimal,
+            bid_amount_out_raw,
+            base_token.decimal,
+        )?;
 
         let bid_route_summary = DexRouteSummary {
             token_in: bid_data.route_summary.token_in.clone(),
             token_out: bid_data.route_summary.token_out.clone(),
-            amount_in: wei_to_eth(&bid_data.route_summary.amount_in, quote_token.decimal)?,
-            amount_out: wei_to_eth(&bid_data.route_summary.amount_out, base_token.decimal)?,
+            amount_in: bid_amount_in_decimal,
+            amount_out: bid_amount_out_decimal,
             amount_in_wei: bid_data.route_summary.amount_in.clone(),
             amount_out_wei: bid_data.route_summary.amount_out.clone(),
             gas: bid_data.route_summary.gas.clone(),
@@ -150,50 +216,20 @@ impl DEXTrait for KyberSwap {
         };
 
         let bid_route_data = serde_json::to_value(&bid_data).ok();
-
-        // Query for ASK price: selling base token for quote token (base -> quote)
-        // Use the raw amount_out from bid response (already in raw format with decimals)
-        let ask_endpoint = format!(
-            "{}/routes?tokenIn={}&tokenOut={}&amountIn={}&gasInclude=true&saveGas=0&excludedSources=bebop,smardex,dodo",
-            api_base, base_token.address, quote_token.address, bid_data.route_summary.amount_out
-        );
-
-        let ask_response_raw = client
-            .get(&ask_endpoint)
-            .send()
-            .await
-            .map_err(|e| MarketScannerError::HttpError(e))?;
-
-        let status = ask_response_raw.status();
-        if !status.is_success() {
-            let error_text = ask_response_raw.text().await.unwrap_or_default();
-            return Err(MarketScannerError::ApiError(format!(
-                "KyberSwap API error: status {} - {}",
-                status, error_text
-            )));
+        if let Some(route_data) = &bid_route_data {
+            cache_route(&base_token.chain_id, route_data);
         }
 
-        let ask_response: KyberSwapRoutesResponse = ask_response_raw.json().await.map_err(|e| {
-            MarketScannerError::ApiError(format!("Failed to parse KyberSwap response: {}", e))
-        })?;
-
-        if ask_response.code != 0 {
-            return Err(MarketScannerError::ApiError(format!(
-                "KyberSwap API error: {}",
-                ask_response.message.unwrap_or_default()
-            )));
-        }
-
-        let ask_data = ask_response.data.ok_or_else(|| {
-            MarketScannerError::ApiError("KyberSwap API returned no data".to_string())
-        })?;
-
-        // Parse amounts using safe conversion with Decimal for precision
-        let ask_amount_in_decimal =
-            wei_to_eth(&ask_data.route_summary.amount_in, base_token.decimal)?;
-        let ask_amount_out_decimal =
-            wei_to_eth(&ask_data.route_summary.amount_out, quote_token.decimal)?;
-        let ask_price = utils::safe_divide(ask_amount_out_decimal, ask_amount_in_decimal)?;
+        let ask_amount_in_raw = parse_raw_amount(&ask_data.route_summary.amount_in)?;
+        let ask_amount_out_raw = parse_raw_amount(&ask_data.route_summary.amount_out)?;
+        let ask_amount_in_decimal = raw_to_f64(ask_amount_in_raw, base_token.decimal);
+        let ask_amount_out_decimal = raw_to_f64(ask_amount_out_raw, quote_token.decimal);
+        let ask_price = raw_ratio(
+            ask_amount_out_raw,
+            quote_token.decimal,
+            ask_amount_in_raw,
+            base_token.decimal,
+        )?;
 
         // Store route summary for ask
         let ask_route_summary = DexRouteSummary {
@@ -201,8 +237,8 @@ impl DEXTrait for KyberSwap {
             token_out: ask_data.route_summary.token_out.clone(),
             amount_in_wei: ask_data.route_summary.amount_in.clone(),
             amount_out_wei: ask_data.route_summary.amount_out.clone(),
-            amount_in: wei_to_eth(&ask_data.route_summary.amount_in, base_token.decimal)?,
-            amount_out: wei_to_eth(&ask_data.route_summary.amount_out, quote_token.decimal)?,
+            amount_in: ask_amount_in_decimal,
+            amount_out: ask_amount_out_decimal,
             gas: ask_data.route_summary.gas.clone(),
             gas_price: ask_data.route_summary.gas_price.clone(),
             gas_usd: ask_data
@@ -214,26 +250,139 @@ impl DEXTrait for KyberSwap {
 
         // Store full route data as JSON
         let ask_route_data = serde_json::to_value(&ask_data).ok();
+        if let Some(route_data) = &ask_route_data {
+            cache_route(&base_token.chain_id, route_data);
+        }
 
         let mid_price = find_mid_price(bid_price, ask_price);
 
-        // Calculate quantities using safe conversion
-        let bid_qty = wei_to_eth(&bid_data.route_summary.amount_out, base_token.decimal)?;
-        let ask_qty = wei_to_eth(&ask_data.route_summary.amount_in, base_token.decimal)?;
-
         Ok(DexPrice {
             symbol: normalized,
             mid_price,
             bid_price: bid_price,
             ask_price: ask_price,
-            bid_qty,
-            ask_qty,
-            timestamp: get_timestamp_millis(),
+            bid_qty: bid_amount_out_decimal,
+            ask_qty: ask_amount_in_decimal,
+            timestamp: Timestamp::now(),
             exchange: Exchange::Dex(DexAggregator::KyberSwap),
             bid_route_summary: Some(bid_route_summary),
             ask_route_summary: Some(ask_route_summary),
             bid_route_data: bid_route_data,
             ask_route_data: ask_route_data,
+            transfer_tax_bps: pair_transfer_tax_bps(base_token, quote_token),
+        })
+    }
+}
+
+impl KyberSwap {
+    /// Fetches the route swapping `amount_in` (raw, in `token_in` decimals) of `token_in`
+    /// for `token_out` through the sources `sources` allows.
+    async fn fetch_route(
+        &self,
+        api_base: &str,
+        token_in: &crate::dex::chains::Token,
+        token_out: &crate::dex::chains::Token,
+        amount_in: &str,
+        sources: &DexSourceFilter,
+    ) -> Result<KyberSwapRoutesData, MarketScannerError> {
+        let endpoint = format!(
+            "{}/routes?tokenIn={}&tokenOut={}&amountIn={}&gasInclude=true&saveGas=0{}",
+            api_base,
+            token_in.address,
+            token_out.address,
+            amount_in,
+            source_params(sources)
+        );
+
+        // Shared by every chain: one budget per aggregator (see set_rate_limit)
+        acquire_request_slot(self.exchange_name(), RequestPriority::Price).await;
+        // Default headers (see browser_http_config) pass Cloudflare protection
+        let response_raw = self
+            .client()
+            .get(&endpoint)
+            .send()
+            .await
+            .map_err(MarketScannerError::HttpError)?;
+
+        let status = response_raw.status();
+        if !status.is_success() {
+            let error_text = response_raw.text().await.unwrap_or_default();
+            return Err(MarketScannerError::ApiError(format!(
+                "KyberSwap API error: status {} - {}",
+                status, error_text
+            )));
+        }
+
+        let response: KyberSwapRoutesResponse = response_raw.json().await.map_err(|e| {
+            MarketScannerError::ApiError(format!("Failed to parse KyberSwap response: {}", e))
+        })?;
+
+        if response.code != 0 {
+            return Err(MarketScannerError::ApiError(format!(
+                "KyberSwap API error: {}",
+                response.message.unwrap_or_default()
+            )));
+        }
+
+        response.data.ok_or_else(|| {
+            MarketScannerError::ApiError("KyberSwap API returned no data".to_string())
         })
     }
+
+    /// Encodes a swap for `route_summary` (the `routeSummary` object of a routes response,
+    /// e.g. from [DexPrice::bid_route_data]) via the route build endpoint.
+    /// `slippage_bps` is the tolerated slippage in basis points (10 = 0.1%).
+    pub async fn build_route(
+        &self,
+        chain: &ChainId,
+        route_summary: &serde_json::Value,
+        sender: &str,
+        recipient: &str,
+        slippage_bps: u32,
+    ) -> Result<KyberSwapBuiltRoute, MarketScannerError> {
+        let url = format!("{}/{}/api/v1/route/build", KYBERSWAP_API_BASE, chain.name());
+        let body = serde_json::json!({
+            "routeSummary": route_summary,
+            "sender": sender,
+            "recipient": recipient,
+            "slippageTolerance": slippage_bps,
+        });
+
+        let client = self.client();
+        let response = client.post(&url).json(&body).send().await?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let error_text = response.text().await.unwrap_or_default();
+            return Err(MarketScannerError::ApiError(format!(
+                "KyberSwap API error: status {} - {}",
+                status, error_text
+            )));
+        }
+
+        let built: KyberSwapBuildRouteResponse = response.json().await.map_err(|e| {
+            MarketScannerError::ApiError(format!("Failed to parse KyberSwap response: {}", e))
+        })?;
+        if built.code != 0 {
+            return Err(MarketScannerError::ApiError(format!(
+                "KyberSwap API error: {}",
+                built.message.unwrap_or_default()
+            )));
+        }
+        built.data.ok_or_else(|| {
+            MarketScannerError::ApiError("KyberSwap API returned no data".to_string())
+        })
+    }
+}
+
+/// `includedSources` / `excludedSources` query parameters of a routes request.
+pub fn source_params(sources: &DexSourceFilter) -> String {
+    let mut params = String::new();
+    if !sources.included.is_empty() {
+        params.push_str(&format!("&includedSources={}", sources.included.join(",")));
+    }
+    if !sources.excluded.is_empty() {
+        params.push_str(&format!("&excludedSources={}", sources.excluded.join(",")));
+    }
+    params
 }
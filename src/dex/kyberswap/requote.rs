@@ -0,0 +1,233 @@
+//! Route caching and re-quoting of KyberSwap legs.
+//!
+//! Full route discovery on every scan cycle is slow and can flip between near-equal paths.
+//! [KyberSwap::get_price](crate::DEXTrait::get_price) caches the last route per chain and
+//! token pair; [KyberSwap::requote] re-prices a route restricted to the liquidity sources it
+//! already uses (`includedSources`) and returns a [RouteRequote] with the amount-out change
+//! and whether the pool path stayed the same.
+
+use super::KYBERSWAP_API_BASE;
+use super::KyberSwap;
+use super::types::KyberSwapRoutesResponse;
+use crate::common::{MarketScannerError, Timestamp};
+use crate::dex::amount::{parse_raw_amount, u256_to_f64};
+use crate::dex::chains::ChainId;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::{LazyLock, RwLock};
+
+/// Result of re-quoting a cached route.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RouteRequote {
+    /// Fresh route data (same shape as [crate::DexPrice::bid_route_data])
+    pub route_data: serde_json::Value,
+    pub amount_in_wei: String,
+    pub previous_amount_out_wei: String,
+    pub amount_out_wei: String,
+    /// `amount_out_wei - previous_amount_out_wei`, signed
+    pub amount_out_change_wei: String,
+    /// Amount-out change relative to the previous route, in percent
+    pub amount_out_change_percent: f64,
+    /// Whether the fresh route swaps through the same pools in the same order
+    pub same_path: bool,
+    pub timestamp: Timestamp,
+}
+
+/// (chain, token in, token out) with lowercased token addresses
+type RouteKey = (ChainId, String, String);
+
+/// Last route per pair key
+static ROUTE_CACHE: LazyLock<RwLock<HashMap<RouteKey, serde_json::Value>>> =
+    LazyLock::new(|| RwLock::new(HashMap::new()));
+
+fn route_key(chain: &ChainId, token_in: &str, token_out: &str) -> RouteKey {
+    (
+        chain.clone(),
+        token_in.to_lowercase(),
+        token_out.to_lowercase(),
+    )
+}
+
+/// The `routeSummary` of route data; a bare summary is accepted as well.
+fn route_summary(route_data: &serde_json::Value) -> &serde_json::Value {
+    route_data.get("routeSummary").unwrap_or(route_data)
+}
+
+fn summary_field<'a>(
+    route_data: &'a serde_json::Value,
+    field: &str,
+) -> Result<&'a str, MarketScannerError> {
+    route_summary(route_data)[field].as_str().ok_or_else(|| {
+        MarketScannerError::ApiError(format!("KyberSwap route data missing {}", field))
+    })
+}
+
+/// Swaps of a route, flattened in execution order.
+fn swaps(route_data: &serde_json::Value) -> impl Iterator<Item = &serde_json::Value> {
+    route_summary(route_data)["route"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|path| path.as_array())
+        .flatten()
+}
+
+/// Number of swaps (hops over all split paths) of a route; `None` if the route data has
+/// no route.
+pub fn route_hops(route_data: &serde_json::Value) -> Option<usize> {
+    route_summary(route_data)["route"]
+        .as_array()
+        .map(|_| swaps(route_data).count())
+}
+
+fn pool_path(route_data: &serde_json::Value) -> Vec<String> {
+    swaps(route_data)
+        .filter_map(|swap| swap["pool"].as_str())
+        .map(str::to_lowercase)
+        .collect()
+}
+
+/// Liquidity sources (`exchange` of each swap) used by a route, deduplicated in order.
+fn sources(route_data: &serde_json::Value) -> Vec<String> {
+    let mut sources: Vec<String> = Vec::new();
+    for source in swaps(route_data).filter_map(|swap| swap["exchange"].as_str()) {
+        if !sources.iter().any(|s| s == source) {
+            sources.push(source.to_string());
+        }
+    }
+    sources
+}
+
+/// Stores `route_data` as the last route of its chain and token pair.
+pub fn cache_route(chain: &ChainId, route_data: &serde_json::Value) {
+    let (Ok(token_in), Ok(token_out)) = (
+        summary_field(route_data, "tokenIn"),
+        summary_field(route_data, "tokenOut"),
+    ) else {
+        return;
+    };
+    ROUTE_CACHE
+        .write()
+        .unwrap_or_else(|e| e.into_inner())
+        .insert(route_key(chain, token_in, token_out), route_data.clone());
+}
+
+/// Last route from `token_in` to `token_out` on `chain`, if one was quoted.
+pub fn cached_route(chain: &ChainId, token_in: &str, token_out: &str) -> Option<serde_json::Value> {
+    ROUTE_CACHE
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .get(&route_key(chain, token_in, token_out))
+        .cloned()
+}
+
+/// Drops every cached route.
+pub fn clear_route_cache() {
+    ROUTE_CACHE
+        .write()
+        .unwrap_or_else(|e| e.into_inner())
+        .clear();
+}
+
+/// Compares two quotes of the same token pair and amount.
+pub fn diff_routes(
+    previous: &serde_json::Value,
+    current: &serde_json::Value,
+) -> Result<RouteRequote, MarketScannerError> {
+    let previous_out = summary_field(previous, "amountOut")?;
+    let current_out = summary_field(current, "amountOut")?;
+    let previous_wei = parse_raw_amount(previous_out)?;
+    let current_wei = parse_raw_amount(current_out)?;
+    // Raw amounts can exceed every signed integer type: keep the sign apart
+    let (change, sign) = if current_wei >= previous_wei {
+        (current_wei - previous_wei, "")
+    } else {
+        (previous_wei - current_wei, "-")
+    };
+    let change_percent = if previous_wei.is_zero() {
+        0.0
+    } else {
+        let percent = u256_to_f64(change) / u256_to_f64(previous_wei) * 100.0;
+        if sign.is_empty() { percent } else { -percent }
+    };
+
+    Ok(RouteRequote {
+        route_data: current.clone(),
+        amount_in_wei: summary_field(current, "amountIn")?.to_string(),
+        previous_amount_out_wei: previous_out.to_string(),
+        amount_out_wei: current_out.to_string(),
+        amount_out_change_wei: format!("{}{}", sign, change),
+        amount_out_change_percent: change_percent,
+        same_path: pool_path(previous) == pool_path(current),
+        timestamp: Timestamp::now(),
+    })
+}
+
+impl KyberSwap {
+    /// Re-prices `route_data` (e.g. [crate::DexPrice::bid_route_data]) for the same tokens
+    /// and amount, restricted to the liquidity sources the route already uses. The fresh
+    /// route replaces the cached one.
+    pub async fn requote(
+        &self,
+        chain: &ChainId,
+        route_data: &serde_json::Value,
+    ) -> Result<RouteRequote, MarketScannerError> {
+        let mut url = format!(
+            "{}/{}/api/v1/routes?tokenIn={}&tokenOut={}&amountIn={}&gasInclude=true",
+            KYBERSWAP_API_BASE,
+            chain.name(),
+            summary_field(route_data, "tokenIn")?,
+            summary_field(route_data, "tokenOut")?,
+            summary_field(route_data, "amountIn")?
+        );
+        let sources = sources(route_data);
+        if !sources.is_empty() {
+            url.push_str(&format!("&includedSources={}", sources.join(",")));
+        }
+
+        let response = self.client.get(&url).send().await?;
+        let status = response.status();
+        if !status.is_success() {
+            let error_text = response.text().await.unwrap_or_default();
+            return Err(MarketScannerError::ApiError(format!(
+                "KyberSwap API error: status {} - {}",
+                status, error_text
+            )));
+        }
+        let routes: KyberSwapRoutesResponse = response.json().await.map_err(|e| {
+            MarketScannerError::ApiError(format!("Failed to parse KyberSwap response: {}", e))
+        })?;
+        if routes.code != 0 {
+            return Err(MarketScannerError::ApiError(format!(
+                "KyberSwap API error: {}",
+                routes.message.unwrap_or_default()
+            )));
+        }
+        let data = routes.data.ok_or_else(|| {
+            MarketScannerError::ApiError("KyberSwap API returned no data".to_string())
+        })?;
+
+        let current = serde_json::to_value(&data)?;
+        let requote = diff_routes(route_data, &current)?;
+        cache_route(chain, &current);
+        Ok(requote)
+    }
+
+    /// [KyberSwap::requote] of the cached route from `token_in` to `token_out` on `chain`.
+    pub async fn requote_cached(
+        &self,
+        chain: &ChainId,
+        token_in: &str,
+        token_out: &str,
+    ) -> Result<RouteRequote, MarketScannerError> {
+        let route_data = cached_route(chain, token_in, token_out).ok_or_else(|| {
+            MarketScannerError::ApiError(format!(
+                "No cached KyberSwap route from {} to {} on {}",
+                token_in,
+                token_out,
+                chain.name()
+            ))
+        })?;
+        self.requote(chain, &route_data).await
+    }
+}
@@ -11,6 +11,12 @@ pub struct KyberSwapRoutesResponse {
 pub struct KyberSwapRoutesData {
     #[serde(rename = "routeSummary")]
     pub route_summary: RouteSummary,
+    #[serde(
+        rename = "routerAddress",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub router_address: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -32,4 +38,37 @@ pub struct RouteSummary {
     /// Gas cost in USD
     #[serde(rename = "gasUsd", default)]
     pub gas_usd: Option<String>,
+    /// Remaining fields (route, extra fee, ...), kept so the summary can be sent back to
+    /// the route build endpoint unchanged
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KyberSwapBuildRouteResponse {
+    pub code: i32,
+    pub message: Option<String>,
+    pub data: Option<KyberSwapBuiltRoute>,
+}
+
+/// Encoded swap returned by the KyberSwap route build endpoint.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KyberSwapBuiltRoute {
+    /// Input amount (wei)
+    #[serde(rename = "amountIn")]
+    pub amount_in: String,
+    /// Expected output amount (wei)
+    #[serde(rename = "amountOut")]
+    pub amount_out: String,
+    /// Estimated gas limit
+    #[serde(default)]
+    pub gas: Option<String>,
+    /// Router calldata (hex)
+    pub data: String,
+    /// Router to call
+    #[serde(rename = "routerAddress")]
+    pub router_address: String,
+    /// Native value to send with the transaction (wei), non-zero when swapping the native token
+    #[serde(rename = "transactionValue", default)]
+    pub transaction_value: Option<String>,
 }
@@ -1,11 +1,27 @@
 // imports
+pub mod amount;
 pub mod chains;
+pub mod execution;
 pub mod kyberswap;
+pub mod oracles;
+pub mod pool_discovery;
 pub mod pool_listener;
 
 // re-exports
-pub use kyberswap::KyberSwap;
+pub use amount::{
+    f64_to_raw, format_units, parse_raw_amount, parse_units, raw_ratio, raw_to_f64, u256_to_f64,
+};
+pub use kyberswap::{ChainProbe, KyberSwap, KyberSwapBuiltRoute, RouteRequote};
+pub use oracles::{
+    ChainlinkOracle, OraclePrice, OracleProvider, OracleSource, PythOracle, RedstoneOracle,
+    reference_price,
+};
+pub use pool_discovery::{
+    DiscoveredPool, PoolFactories, UNISWAP_V3_FEE_TIERS, find_pools, find_pools_with_factories,
+    uniswap_v2_factory, uniswap_v3_factory,
+};
 pub use pool_listener::{
-    ListenMode, PoolKind, PoolListenerConfig, PoolPriceUpdate, PriceDirection, load_dotenv,
-    stream_pool_prices,
+    ChainPreset, ListenMode, PoolEvent, PoolKind, PoolListenerConfig, PoolPriceUpdate,
+    PriceDirection, get_twap, load_dotenv, resolve_direction, stream_pool_events,
+    stream_pool_prices, twap_from_tick_cumulatives,
 };
@@ -0,0 +1,224 @@
+//! Chainlink aggregator feeds (`latestRoundData`).
+//!
+//! [ChainlinkOracle] maps symbols to aggregator (proxy) addresses and reads them over any
+//! ethers provider, e.g. the WebSocket RPC already used by the pool listener.
+
+use crate::common::{MarketScannerError, Timestamp, normalize_symbol};
+use crate::dex::chains::ChainId;
+use crate::dex::oracles::{OraclePrice, OracleProvider, OracleSource};
+use crate::dex::pool_listener::parse_address;
+use async_trait::async_trait;
+use ethers::core::types::transaction::eip2718::TypedTransaction;
+use ethers::core::types::{Address, Eip1559TransactionRequest, U256};
+use ethers::providers::{Middleware, Provider, Ws};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::RwLock;
+use std::time::Duration;
+
+// latestRoundData()
+const SELECTOR_LATEST_ROUND_DATA: [u8; 4] = [0xfe, 0xaf, 0x96, 0x8c];
+// decimals()
+const SELECTOR_DECIMALS: [u8; 4] = [0x31, 0x3c, 0xe5, 0x67];
+
+/// Default maximum age of a round before [ChainlinkOracle::get_price] rejects it. Most
+/// USD feeds have a 1 hour heartbeat; stablecoin feeds may update only once a day.
+const DEFAULT_MAX_STALENESS: Duration = Duration::from_secs(3600 + 600);
+
+/// Chainlink `<base>/USD` proxy address on `chain`, for a few widely used feeds.
+pub fn chainlink_usd_feed(chain: &ChainId, base: &str) -> Option<&'static str> {
+    let base = base.to_uppercase();
+    match (chain, base.as_str()) {
+        (ChainId::ETHEREUM, "ETH" | "WETH") => Some("0x5f4eC3Df9cbd43714FE2740f5E3616155c5b8419"),
+        (ChainId::ETHEREUM, "BTC" | "WBTC") => Some("0xF4030086522a5bEEa4988F8cA5B36dbC97BeE88c"),
+        (ChainId::ETHEREUM, "USDC") => Some("0x8fFfFfd4AfB6115b954Bd326cbe7B4BA576818f6"),
+        (ChainId::ETHEREUM, "LINK") => Some("0x2c1d072e956AFFC0D435Cb7AC38EF18d24d9127c"),
+        (ChainId::ARBITRUM, "ETH" | "WETH") => Some("0x639Fe6ab55C921f74e7fac1ee960C0B6293ba612"),
+        (ChainId::BASE, "ETH" | "WETH") => Some("0x71041dddad3595F9CEd3DcCFBe3D1F4b0a16Bb70"),
+        _ => None,
+    }
+}
+
+/// Raw `latestRoundData()` result.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChainlinkRound {
+    pub round_id: u128,
+    /// Price scaled by the feed's `decimals()`
+    pub answer: i128,
+    /// Round start (unix seconds)
+    pub started_at: u64,
+    /// Last update (unix seconds)
+    pub updated_at: u64,
+    pub answered_in_round: u128,
+}
+
+/// Decodes the five words returned by `latestRoundData()`.
+pub fn decode_latest_round_data(data: &[u8]) -> Result<ChainlinkRound, MarketScannerError> {
+    if data.len() < 5 * 32 {
+        return Err(MarketScannerError::WsRpcError(
+            "latestRoundData response too short".into(),
+        ));
+    }
+    let word = |i: usize| U256::from_big_endian(&data[i * 32..(i + 1) * 32]);
+    let answer = word(1);
+    // int256: negative values have the top bit set
+    let answer = if answer.bit(255) {
+        -(((!answer) + 1).low_u128() as i128)
+    } else {
+        answer.low_u128() as i128
+    };
+    Ok(ChainlinkRound {
+        round_id: word(0).low_u128(),
+        answer,
+        started_at: word(2).low_u64(),
+        updated_at: word(3).low_u64(),
+        answered_in_round: word(4).low_u128(),
+    })
+}
+
+/// `latestRoundData()` of the aggregator at `feed`.
+pub async fn fetch_latest_round<M: Middleware>(
+    provider: &M,
+    feed: Address,
+) -> Result<ChainlinkRound, MarketScannerError> {
+    let res = call(provider, feed, SELECTOR_LATEST_ROUND_DATA).await?;
+    decode_latest_round_data(&res)
+}
+
+/// Chainlink feeds per symbol, read through `provider`.
+pub struct ChainlinkOracle<M> {
+    provider: M,
+    /// Aggregator address per normalized symbol
+    feeds: HashMap<String, String>,
+    max_staleness: Duration,
+    /// `decimals()` per aggregator; fixed for the lifetime of a feed
+    decimals: RwLock<HashMap<Address, u8>>,
+}
+
+impl ChainlinkOracle<Provider<Ws>> {
+    /// Oracle reading over a WebSocket RPC endpoint.
+    pub async fn connect(rpc_ws_url: &str) -> Result<Self, MarketScannerError> {
+        let provider = Provider::<Ws>::connect(rpc_ws_url)
+            .await
+            .map_err(|e| MarketScannerError::WsRpcError(e.to_string()))?;
+        Ok(Self::new(provider))
+    }
+}
+
+impl<M: Middleware> ChainlinkOracle<M> {
+    pub fn new(provider: M) -> Self {
+        Self {
+            provider,
+            feeds: HashMap::new(),
+            max_staleness: DEFAULT_MAX_STALENESS,
+            decimals: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Reads `symbol` (e.g. "ETHUSD") from the aggregator at `feed_address`.
+    pub fn with_feed(mut self, symbol: &str, feed_address: &str) -> Self {
+        self.feeds
+            .insert(normalize_symbol(symbol), feed_address.to_string());
+        self
+    }
+
+    /// Rounds older than this are rejected as stale.
+    pub fn with_max_staleness(mut self, max_staleness: Duration) -> Self {
+        self.max_staleness = max_staleness;
+        self
+    }
+
+    /// Configured symbols.
+    pub fn symbols(&self) -> Vec<String> {
+        self.feeds.keys().cloned().collect()
+    }
+
+    /// Latest price of `symbol`. Fails for unconfigured symbols, non-positive answers and
+    /// rounds older than the staleness limit.
+    pub async fn get_price(&self, symbol: &str) -> Result<OraclePrice, MarketScannerError> {
+        let symbol = normalize_symbol(symbol);
+        let feed_address = self.feeds.get(&symbol).ok_or_else(|| {
+            MarketScannerError::InvalidSymbol(format!("no Chainlink feed for {}", symbol))
+        })?;
+        let feed = parse_address(feed_address)?;
+
+        let decimals = self.feed_decimals(feed).await?;
+        let round = fetch_latest_round(&self.provider, feed).await?;
+        if round.answer <= 0 {
+            return Err(MarketScannerError::ApiError(format!(
+                "Chainlink feed {} returned non-positive answer {}",
+                feed_address, round.answer
+            )));
+        }
+        let updated_at = Timestamp::from_secs(round.updated_at);
+        if updated_at.is_older_than(self.max_staleness) {
+            return Err(MarketScannerError::ApiError(format!(
+                "Chainlink feed {} is stale (updated at {})",
+                feed_address, updated_at
+            )));
+        }
+
+        Ok(OraclePrice {
+            symbol,
+            price: round.answer as f64 / 10f64.powi(decimals as i32),
+            source: OracleSource::Chainlink,
+            feed: feed_address.clone(),
+            updated_at,
+            confidence: None,
+        })
+    }
+
+    async fn feed_decimals(&self, feed: Address) -> Result<u8, MarketScannerError> {
+        if let Some(decimals) = self
+            .decimals
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(&feed)
+        {
+            return Ok(*decimals);
+        }
+        let res = call(&self.provider, feed, SELECTOR_DECIMALS).await?;
+        if res.len() < 32 {
+            return Err(MarketScannerError::WsRpcError(
+                "decimals response too short".into(),
+            ));
+        }
+        let decimals = res[31];
+        self.decimals
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(feed, decimals);
+        Ok(decimals)
+    }
+}
+
+#[async_trait]
+impl<M: Middleware> OracleProvider for ChainlinkOracle<M> {
+    fn source(&self) -> OracleSource {
+        OracleSource::Chainlink
+    }
+
+    fn supports(&self, symbol: &str) -> bool {
+        self.feeds.contains_key(&normalize_symbol(symbol))
+    }
+
+    async fn get_price(&self, symbol: &str) -> Result<OraclePrice, MarketScannerError> {
+        ChainlinkOracle::get_price(self, symbol).await
+    }
+}
+
+async fn call<M: Middleware>(
+    provider: &M,
+    to: Address,
+    selector: [u8; 4],
+) -> Result<Vec<u8>, MarketScannerError> {
+    let tx: TypedTransaction = Eip1559TransactionRequest::new()
+        .to(to)
+        .data(selector.to_vec())
+        .into();
+    let res = provider
+        .call(&tx, None)
+        .await
+        .map_err(|e| MarketScannerError::WsRpcError(e.to_string()))?;
+    Ok(res.to_vec())
+}
@@ -0,0 +1,116 @@
+//! On-chain oracle prices used as a reference for venue quotes.
+//!
+//! Oracle prices are not tradable; they are a sanity check for CEX/DEX prices (e.g. to
+//! drop a quote that is far off the oracle before it becomes an opportunity). Chainlink
+//! is read on-chain; Pyth (Hermes) and Redstone are read from their HTTP APIs and cover
+//! chains and pairs without a Chainlink feed. All of them implement [OracleProvider].
+
+// imports
+pub mod chainlink;
+pub mod pyth;
+pub mod redstone;
+
+use crate::common::{MarketScannerError, Timestamp, normalize_symbol};
+use crate::scanner::PriceData;
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+// re-exports
+pub use chainlink::{
+    ChainlinkOracle, ChainlinkRound, chainlink_usd_feed, decode_latest_round_data,
+    fetch_latest_round,
+};
+pub use pyth::{HERMES_API_BASE, PythOracle, PythPrice, PythPriceFeed, pyth_usd_feed_id};
+pub use redstone::{REDSTONE_API_BASE, RedstoneOracle, RedstonePrice};
+
+/// Oracle network a price comes from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum OracleSource {
+    Chainlink,
+    Pyth,
+    Redstone,
+}
+
+/// Reference price read from an oracle feed.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OraclePrice {
+    /// Normalized symbol the feed is configured for (e.g. "ETHUSD")
+    pub symbol: String,
+    /// Price in quote units per base unit
+    pub price: f64,
+    pub source: OracleSource,
+    /// Feed identifier (aggregator address for Chainlink, price feed ID for Pyth, data
+    /// feed symbol for Redstone)
+    pub feed: String,
+    /// When the oracle last updated the price
+    pub updated_at: Timestamp,
+    /// Confidence interval (± price units) when the oracle publishes one (Pyth)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub confidence: Option<f64>,
+}
+
+impl OraclePrice {
+    /// Whether the oracle update is older than `max_age`.
+    pub fn is_stale(&self, max_age: Duration) -> bool {
+        self.updated_at.is_older_than(max_age)
+    }
+
+    /// Relative deviation of `price` from the oracle price (`(price - oracle) / oracle`).
+    pub fn deviation(&self, price: f64) -> f64 {
+        if self.price == 0.0 {
+            return f64::INFINITY;
+        }
+        (price - self.price) / self.price
+    }
+
+    /// Relative deviation of a venue's mid price from the oracle price.
+    pub fn deviation_from(&self, price: &PriceData) -> f64 {
+        let mid = match price {
+            PriceData::Cex(p) => p.mid_price,
+            PriceData::Dex(p) => p.mid_price,
+        };
+        self.deviation(mid)
+    }
+
+    /// Whether `price` is for the same symbol and its mid is within `max_deviation_pct`
+    /// percent of the oracle price.
+    pub fn confirms(&self, price: &PriceData, max_deviation_pct: f64) -> bool {
+        let symbol = match price {
+            PriceData::Cex(p) => &p.symbol,
+            PriceData::Dex(p) => &p.symbol,
+        };
+        normalize_symbol(symbol) == self.symbol
+            && self.deviation_from(price).abs() * 100.0 <= max_deviation_pct
+    }
+}
+
+/// Source of reference prices.
+#[async_trait]
+pub trait OracleProvider: Send + Sync {
+    fn source(&self) -> OracleSource;
+
+    /// Whether a feed is configured for `symbol`.
+    fn supports(&self, symbol: &str) -> bool;
+
+    /// Latest price of `symbol`. Fails for unconfigured symbols and stale updates.
+    async fn get_price(&self, symbol: &str) -> Result<OraclePrice, MarketScannerError>;
+}
+
+/// Price of `symbol` from the first provider that has a feed for it and answers
+/// successfully. Returns the last error if none does.
+pub async fn reference_price(
+    providers: &[&dyn OracleProvider],
+    symbol: &str,
+) -> Result<OraclePrice, MarketScannerError> {
+    let mut last_error = None;
+    for provider in providers.iter().filter(|p| p.supports(symbol)) {
+        match provider.get_price(symbol).await {
+            Ok(price) => return Ok(price),
+            Err(e) => last_error = Some(e),
+        }
+    }
+    Err(last_error.unwrap_or_else(|| {
+        MarketScannerError::InvalidSymbol(format!("no oracle feed for {}", symbol))
+    }))
+}
@@ -0,0 +1,210 @@
+//! Pyth price feeds through the Hermes API.
+
+use crate::common::{ExchangeTrait, MarketScannerError, Timestamp, normalize_symbol};
+use crate::dex::oracles::{OraclePrice, OracleProvider, OracleSource};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::time::Duration;
+
+/// Public Hermes endpoint.
+pub const HERMES_API_BASE: &str = "https://hermes.pyth.network";
+
+/// Default maximum age of a Pyth update; Hermes publishes several times per second.
+const DEFAULT_MAX_STALENESS: Duration = Duration::from_secs(60);
+
+/// Pyth `<base>/USD` price feed ID, for a few widely used feeds.
+pub fn pyth_usd_feed_id(base: &str) -> Option<&'static str> {
+    match base.to_uppercase().as_str() {
+        "ETH" | "WETH" => {
+            Some("0xff61491a931112ddf1bd8147cd1b641375f79f5825126d665480874634fd0ace")
+        }
+        "BTC" | "WBTC" => {
+            Some("0xe62df6c8b4a85fe1a67db44dc12de5db330f7ac66b72dc658afedf0f4a415b43")
+        }
+        "SOL" => Some("0xef0d8b6fda2ceba41da15d4095d1da392a0d2f8ed0c6c7bc0f4cfac8c280b56d"),
+        "USDC" => Some("0xeaa020c61cc479712813461ce153894a96a6c00b21ed0cfc2798d1f9a9e9c94a"),
+        _ => None,
+    }
+}
+
+/// Price, confidence and exponent as published by Pyth.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PythPrice {
+    /// Integer price (scaled by `10^expo`)
+    pub price: String,
+    /// Integer confidence interval (scaled by `10^expo`)
+    pub conf: String,
+    pub expo: i32,
+    /// Unix seconds
+    pub publish_time: u64,
+}
+
+impl PythPrice {
+    /// Price in quote units.
+    pub fn value(&self) -> Option<f64> {
+        Some(self.price.parse::<f64>().ok()? * 10f64.powi(self.expo))
+    }
+
+    /// Confidence interval in quote units.
+    pub fn confidence(&self) -> Option<f64> {
+        Some(self.conf.parse::<f64>().ok()? * 10f64.powi(self.expo))
+    }
+}
+
+/// One entry of the Hermes `parsed` array.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PythPriceFeed {
+    /// Feed ID (hex, without `0x`)
+    pub id: String,
+    pub price: PythPrice,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ema_price: Option<PythPrice>,
+}
+
+impl PythPriceFeed {
+    /// [OraclePrice] for `symbol`. Fails for non-positive or unparsable prices.
+    pub fn to_oracle_price(&self, symbol: &str) -> Result<OraclePrice, MarketScannerError> {
+        let price = self.price.value().filter(|p| *p > 0.0).ok_or_else(|| {
+            MarketScannerError::ApiError(format!(
+                "invalid Pyth price {} for feed {}",
+                self.price.price, self.id
+            ))
+        })?;
+        Ok(OraclePrice {
+            symbol: normalize_symbol(symbol),
+            price,
+            source: OracleSource::Pyth,
+            feed: format!("0x{}", self.id.trim_start_matches("0x")),
+            updated_at: Timestamp::from_secs(self.price.publish_time),
+            confidence: self.price.confidence(),
+        })
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct HermesLatestResponse {
+    parsed: Vec<PythPriceFeed>,
+}
+
+/// Pyth feeds per symbol, read from Hermes.
+#[derive(Debug, Clone)]
+pub struct PythOracle {
+    client: reqwest::Client,
+    api_base: String,
+    /// Feed ID per normalized symbol
+    feeds: HashMap<String, String>,
+    max_staleness: Duration,
+}
+
+impl Default for PythOracle {
+    fn default() -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            api_base: HERMES_API_BASE.to_string(),
+            feeds: HashMap::new(),
+            max_staleness: DEFAULT_MAX_STALENESS,
+        }
+    }
+}
+
+impl PythOracle {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Hermes instance to query (e.g. a private endpoint).
+    pub fn with_api_base(mut self, api_base: &str) -> Self {
+        self.api_base = api_base.trim_end_matches('/').to_string();
+        self
+    }
+
+    /// Reads `symbol` (e.g. "ETHUSD") from the Pyth feed `feed_id`.
+    pub fn with_feed(mut self, symbol: &str, feed_id: &str) -> Self {
+        self.feeds
+            .insert(normalize_symbol(symbol), feed_id.to_string());
+        self
+    }
+
+    /// Updates older than this are rejected as stale.
+    pub fn with_max_staleness(mut self, max_staleness: Duration) -> Self {
+        self.max_staleness = max_staleness;
+        self
+    }
+
+    /// Latest price of `symbol`.
+    pub async fn get_price(&self, symbol: &str) -> Result<OraclePrice, MarketScannerError> {
+        let symbol = normalize_symbol(symbol);
+        let feed_id = self.feeds.get(&symbol).ok_or_else(|| {
+            MarketScannerError::InvalidSymbol(format!("no Pyth feed for {}", symbol))
+        })?;
+        let response: HermesLatestResponse = self
+            .get(&format!(
+                "v2/updates/price/latest?ids[]={}&parsed=true",
+                feed_id
+            ))
+            .await?;
+        let wanted = feed_id.trim_start_matches("0x").to_lowercase();
+        let feed = response
+            .parsed
+            .iter()
+            .find(|f| f.id.trim_start_matches("0x").to_lowercase() == wanted)
+            .ok_or_else(|| {
+                MarketScannerError::ApiError(format!("Pyth returned no price for {}", feed_id))
+            })?;
+
+        let price = feed.to_oracle_price(&symbol)?;
+        if price.is_stale(self.max_staleness) {
+            return Err(MarketScannerError::ApiError(format!(
+                "Pyth feed {} is stale (published at {})",
+                feed_id, price.updated_at
+            )));
+        }
+        Ok(price)
+    }
+}
+
+#[async_trait]
+impl ExchangeTrait for PythOracle {
+    fn api_base(&self) -> &str {
+        &self.api_base
+    }
+
+    fn client(&self) -> &reqwest::Client {
+        &self.client
+    }
+
+    fn exchange_name(&self) -> &str {
+        "Pyth"
+    }
+
+    async fn health_check(&self) -> Result<(), MarketScannerError> {
+        let url = format!("{}/live", self.api_base);
+        let response = self
+            .client
+            .get(&url)
+            .send()
+            .await
+            .map_err(|_| MarketScannerError::HealthCheckFailed)?;
+        if response.status().is_success() {
+            Ok(())
+        } else {
+            Err(MarketScannerError::HealthCheckFailed)
+        }
+    }
+}
+
+#[async_trait]
+impl OracleProvider for PythOracle {
+    fn source(&self) -> OracleSource {
+        OracleSource::Pyth
+    }
+
+    fn supports(&self, symbol: &str) -> bool {
+        self.feeds.contains_key(&normalize_symbol(symbol))
+    }
+
+    async fn get_price(&self, symbol: &str) -> Result<OraclePrice, MarketScannerError> {
+        PythOracle::get_price(self, symbol).await
+    }
+}
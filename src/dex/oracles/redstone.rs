@@ -0,0 +1,178 @@
+//! Redstone price feeds through the Redstone HTTP API.
+
+use crate::common::{ExchangeTrait, MarketScannerError, Timestamp, normalize_symbol};
+use crate::dex::oracles::{OraclePrice, OracleProvider, OracleSource};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::time::Duration;
+
+/// Public Redstone price API.
+pub const REDSTONE_API_BASE: &str = "https://api.redstone.finance";
+
+/// Data service queried by default.
+const DEFAULT_DATA_SERVICE: &str = "redstone";
+
+/// Default maximum age of a Redstone price; feeds are refreshed every few seconds to minutes.
+const DEFAULT_MAX_STALENESS: Duration = Duration::from_secs(300);
+
+/// One price returned by `GET /prices`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RedstonePrice {
+    /// Redstone data feed symbol (e.g. "ETH")
+    pub symbol: String,
+    /// Price in USD
+    pub value: f64,
+    /// Unix milliseconds
+    pub timestamp: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub provider: Option<String>,
+}
+
+impl RedstonePrice {
+    /// [OraclePrice] for `symbol`. Fails for non-positive prices.
+    pub fn to_oracle_price(&self, symbol: &str) -> Result<OraclePrice, MarketScannerError> {
+        if self.value.is_nan() || self.value <= 0.0 {
+            return Err(MarketScannerError::ApiError(format!(
+                "invalid Redstone price {} for {}",
+                self.value, self.symbol
+            )));
+        }
+        Ok(OraclePrice {
+            symbol: normalize_symbol(symbol),
+            price: self.value,
+            source: OracleSource::Redstone,
+            feed: self.symbol.clone(),
+            updated_at: Timestamp::from_millis(self.timestamp),
+            confidence: None,
+        })
+    }
+}
+
+/// Redstone feeds per symbol.
+#[derive(Debug, Clone)]
+pub struct RedstoneOracle {
+    client: reqwest::Client,
+    api_base: String,
+    data_service: String,
+    /// Redstone data feed symbol per normalized symbol
+    feeds: HashMap<String, String>,
+    max_staleness: Duration,
+}
+
+impl Default for RedstoneOracle {
+    fn default() -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            api_base: REDSTONE_API_BASE.to_string(),
+            data_service: DEFAULT_DATA_SERVICE.to_string(),
+            feeds: HashMap::new(),
+            max_staleness: DEFAULT_MAX_STALENESS,
+        }
+    }
+}
+
+impl RedstoneOracle {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_api_base(mut self, api_base: &str) -> Self {
+        self.api_base = api_base.trim_end_matches('/').to_string();
+        self
+    }
+
+    /// Redstone data service (`provider` query parameter).
+    pub fn with_data_service(mut self, data_service: &str) -> Self {
+        self.data_service = data_service.to_string();
+        self
+    }
+
+    /// Reads `symbol` (e.g. "ETHUSD") from the Redstone data feed `feed_symbol` (e.g. "ETH").
+    /// Redstone prices are quoted in USD.
+    pub fn with_feed(mut self, symbol: &str, feed_symbol: &str) -> Self {
+        self.feeds
+            .insert(normalize_symbol(symbol), feed_symbol.to_string());
+        self
+    }
+
+    /// Prices older than this are rejected as stale.
+    pub fn with_max_staleness(mut self, max_staleness: Duration) -> Self {
+        self.max_staleness = max_staleness;
+        self
+    }
+
+    /// Latest price of `symbol`.
+    pub async fn get_price(&self, symbol: &str) -> Result<OraclePrice, MarketScannerError> {
+        let symbol = normalize_symbol(symbol);
+        let feed_symbol = self.feeds.get(&symbol).ok_or_else(|| {
+            MarketScannerError::InvalidSymbol(format!("no Redstone feed for {}", symbol))
+        })?;
+        let prices: Vec<RedstonePrice> = self
+            .get(&format!(
+                "prices?symbol={}&provider={}&limit=1",
+                feed_symbol, self.data_service
+            ))
+            .await?;
+        let latest = prices.first().ok_or_else(|| {
+            MarketScannerError::ApiError(format!("Redstone returned no price for {}", feed_symbol))
+        })?;
+
+        let price = latest.to_oracle_price(&symbol)?;
+        if price.is_stale(self.max_staleness) {
+            return Err(MarketScannerError::ApiError(format!(
+                "Redstone feed {} is stale (updated at {})",
+                feed_symbol, price.updated_at
+            )));
+        }
+        Ok(price)
+    }
+}
+
+#[async_trait]
+impl ExchangeTrait for RedstoneOracle {
+    fn api_base(&self) -> &str {
+        &self.api_base
+    }
+
+    fn client(&self) -> &reqwest::Client {
+        &self.client
+    }
+
+    fn exchange_name(&self) -> &str {
+        "Redstone"
+    }
+
+    async fn health_check(&self) -> Result<(), MarketScannerError> {
+        let url = format!(
+            "{}/prices?symbol=ETH&provider={}&limit=1",
+            self.api_base, self.data_service
+        );
+        let response = self
+            .client
+            .get(&url)
+            .send()
+            .await
+            .map_err(|_| MarketScannerError::HealthCheckFailed)?;
+        if response.status().is_success() {
+            Ok(())
+        } else {
+            Err(MarketScannerError::HealthCheckFailed)
+        }
+    }
+}
+
+#[async_trait]
+impl OracleProvider for RedstoneOracle {
+    fn source(&self) -> OracleSource {
+        OracleSource::Redstone
+    }
+
+    fn supports(&self, symbol: &str) -> bool {
+        self.feeds.contains_key(&normalize_symbol(symbol))
+    }
+
+    async fn get_price(&self, symbol: &str) -> Result<OraclePrice, MarketScannerError> {
+        RedstoneOracle::get_price(self, symbol).await
+    }
+}
@@ -0,0 +1,209 @@
+//! DEX pool discovery by token pair.
+//!
+//! Queries Uniswap V2 (`getPair`) and V3 (`getPool`, one call per fee tier) factories over
+//! WebSocket RPC and returns the deployed pools, ready to be turned into a
+//! [PoolListenerConfig].
+
+use crate::common::MarketScannerError;
+use crate::dex::chains::ChainId;
+use crate::dex::pool_listener::{
+    ListenMode, PoolKind, PoolListenerConfig, PriceDirection, bytes_to_address, eth_call,
+    parse_address,
+};
+use ethers::core::types::Address;
+use ethers::providers::{Provider, Ws};
+use serde::{Deserialize, Serialize};
+
+/// Fee tiers (hundredths of a bip) of Uniswap V3 factories.
+pub const UNISWAP_V3_FEE_TIERS: [u32; 4] = [100, 500, 3000, 10000];
+
+// getPair(address,address)
+const SELECTOR_GET_PAIR: [u8; 4] = [0xe6, 0xa4, 0x39, 0x05];
+// getPool(address,address,uint24)
+const SELECTOR_GET_POOL: [u8; 4] = [0x16, 0x98, 0xee, 0x82];
+
+/// Factories queried by [find_pools_with_factories].
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PoolFactories {
+    /// Uniswap V2 style factory (`getPair`)
+    pub v2_factory: Option<String>,
+    /// Uniswap V3 style factory (`getPool`)
+    pub v3_factory: Option<String>,
+    /// Fee tiers to query on the V3 factory
+    pub v3_fee_tiers: Vec<u32>,
+}
+
+impl PoolFactories {
+    /// Official Uniswap V2/V3 factories of `chain` (either may be missing).
+    pub fn uniswap(chain: &ChainId) -> Self {
+        Self {
+            v2_factory: uniswap_v2_factory(chain).map(str::to_string),
+            v3_factory: uniswap_v3_factory(chain).map(str::to_string),
+            v3_fee_tiers: UNISWAP_V3_FEE_TIERS.to_vec(),
+        }
+    }
+
+    pub fn with_v2_factory(mut self, v2_factory: &str) -> Self {
+        self.v2_factory = Some(v2_factory.to_string());
+        self
+    }
+
+    pub fn with_v3_factory(mut self, v3_factory: &str) -> Self {
+        self.v3_factory = Some(v3_factory.to_string());
+        self
+    }
+
+    pub fn with_v3_fee_tiers(mut self, v3_fee_tiers: Vec<u32>) -> Self {
+        self.v3_fee_tiers = v3_fee_tiers;
+        self
+    }
+}
+
+/// Uniswap V2 factory address on `chain`.
+pub fn uniswap_v2_factory(chain: &ChainId) -> Option<&'static str> {
+    match chain {
+        ChainId::ETHEREUM => Some("0x5C69bEe701ef814a2B6a3EDD4B1652CB9cc5aA6f"),
+        ChainId::ARBITRUM => Some("0xf1D7CC64Fb4452F05c498126312eBE29f30Fbcf9"),
+        ChainId::OPTIMISM => Some("0x0c3c1c532F1e39EdF36BE9Fe0bE1410313E074Bf"),
+        ChainId::POLYGON | ChainId::AVALANCHE => Some("0x9e5A52f57b3038F1B8EeE45F28b3C1967e22799C"),
+        ChainId::BASE | ChainId::BSC => Some("0x8909Dc15e40173Ff4699343b6eB8132c65e18eC6"),
+        _ => None,
+    }
+}
+
+/// Uniswap V3 factory address on `chain`.
+pub fn uniswap_v3_factory(chain: &ChainId) -> Option<&'static str> {
+    match chain {
+        ChainId::ETHEREUM | ChainId::ARBITRUM | ChainId::OPTIMISM | ChainId::POLYGON => {
+            Some("0x1F98431c8aD98523631AE4a59f267346ea31F984")
+        }
+        ChainId::BASE => Some("0x33128a8fC17869897dcE68Ed026d694621f6FDfD"),
+        ChainId::BSC => Some("0xdB1d10011AD0Ff90774D0C6Bb92e5C5c8b4461F7"),
+        ChainId::AVALANCHE => Some("0x740b1c1de25031C31FF4fC9A62f554A55cdC1baD"),
+        _ => None,
+    }
+}
+
+/// Pool found by [find_pools].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DiscoveredPool {
+    pub chain_id: u64,
+    /// V2 pair or V3 pool address
+    pub pool_address: String,
+    pub pool_kind: PoolKind,
+    /// V3 fee tier (hundredths of a bip). V2: None.
+    pub fee: Option<u32>,
+    /// First token of the query (base)
+    pub token_a: String,
+    /// Second token of the query (quote)
+    pub token_b: String,
+}
+
+impl DiscoveredPool {
+    /// Listener config for this pool, quoting `token_b` per `token_a` on every block.
+    pub fn listener_config(&self, rpc_ws_url: &str) -> PoolListenerConfig {
+        PoolListenerConfig {
+            rpc_ws_url: rpc_ws_url.to_string(),
+            rpc_ws_fallback_urls: Vec::new(),
+            chain_id: self.chain_id,
+            pool_address: self.pool_address.clone(),
+            pool_kind: self.pool_kind,
+            listen_mode: ListenMode::EveryBlock,
+            price_direction: PriceDirection::Token1PerToken0,
+            base_token: Some(self.token_a.clone()),
+            quote_token: Some(self.token_b.clone()),
+            twap_window_secs: None,
+            symbol: None,
+            reconnect_attempts: 0,
+            reconnect_delay_ms: 0,
+        }
+    }
+}
+
+/// Finds the Uniswap V2 pair and V3 pools (all fee tiers) of `token_a` / `token_b` on `chain`.
+pub async fn find_pools(
+    rpc_ws_url: &str,
+    chain: &ChainId,
+    token_a: &str,
+    token_b: &str,
+) -> Result<Vec<DiscoveredPool>, MarketScannerError> {
+    find_pools_with_factories(
+        rpc_ws_url,
+        chain.clone() as u64,
+        &PoolFactories::uniswap(chain),
+        token_a,
+        token_b,
+    )
+    .await
+}
+
+/// Same as [find_pools] against custom factories (e.g. Uniswap forks).
+pub async fn find_pools_with_factories(
+    rpc_ws_url: &str,
+    chain_id: u64,
+    factories: &PoolFactories,
+    token_a: &str,
+    token_b: &str,
+) -> Result<Vec<DiscoveredPool>, MarketScannerError> {
+    if factories.v2_factory.is_none() && factories.v3_factory.is_none() {
+        return Err(MarketScannerError::ApiError(format!(
+            "no pool factories known for chain {}",
+            chain_id
+        )));
+    }
+    let addr_a = parse_address(token_a)?;
+    let addr_b = parse_address(token_b)?;
+    if addr_a == addr_b {
+        return Err(MarketScannerError::InvalidSymbol(
+            "token_a and token_b must differ".to_string(),
+        ));
+    }
+
+    let provider = Provider::<Ws>::connect(rpc_ws_url)
+        .await
+        .map_err(|e| MarketScannerError::WsRpcError(e.to_string()))?;
+
+    let discovered = |pool: Address, pool_kind: PoolKind, fee: Option<u32>| DiscoveredPool {
+        chain_id,
+        pool_address: format!("{:?}", pool),
+        pool_kind,
+        fee,
+        token_a: token_a.to_string(),
+        token_b: token_b.to_string(),
+    };
+
+    let mut pools = Vec::new();
+    if let Some(factory) = &factories.v2_factory {
+        let factory = parse_address(factory)?;
+        let data = encode_call(SELECTOR_GET_PAIR, &[addr_a, addr_b], None);
+        let pair = bytes_to_address(&eth_call(&provider, factory, &data).await?)?;
+        if !pair.is_zero() {
+            pools.push(discovered(pair, PoolKind::V2, None));
+        }
+    }
+    if let Some(factory) = &factories.v3_factory {
+        let factory = parse_address(factory)?;
+        for &fee in &factories.v3_fee_tiers {
+            let data = encode_call(SELECTOR_GET_POOL, &[addr_a, addr_b], Some(fee));
+            let pool = bytes_to_address(&eth_call(&provider, factory, &data).await?)?;
+            if !pool.is_zero() {
+                pools.push(discovered(pool, PoolKind::V3, Some(fee)));
+            }
+        }
+    }
+    Ok(pools)
+}
+
+/// ABI-encodes `selector(address...[, uint24])`.
+fn encode_call(selector: [u8; 4], addresses: &[Address], fee: Option<u32>) -> Vec<u8> {
+    let mut data = selector.to_vec();
+    for address in addresses {
+        data.extend_from_slice(&[0u8; 12]);
+        data.extend_from_slice(address.as_bytes());
+    }
+    if let Some(fee) = fee {
+        data.extend_from_slice(&[0u8; 28]);
+        data.extend_from_slice(&fee.to_be_bytes());
+    }
+    data
+}
@@ -1,17 +1,26 @@
 //! DEX pool price listener over WebSocket RPC (ethers-rs).
 //!
 //! Connects to an Ethereum node via WebSocket, subscribes to new blocks or Swap events,
-//! and emits price updates for Uniswap V2 or V3 style pools.
+//! and emits price updates for Uniswap V2 or V3 style pools. A config may list fallback
+//! RPC URLs: the listener fails over to the next one when its provider drops or cannot be
+//! reached, and [ChainPreset] fills them in from per-chain environment variables.
 
-use crate::common::{MarketScannerError, get_timestamp_millis};
-use ethers::core::types::{Address, Bytes, Filter, H256, TransactionRequest, U256};
+use crate::common::{MarketScannerError, Timestamp};
+use crate::dex::amount::{raw_ratio, raw_to_f64, u256_to_f64};
+use ethers::core::types::{Address, BlockId, Bytes, Filter, H256, TransactionRequest, U256};
 use ethers::providers::{Middleware, Provider, Ws};
 use futures::StreamExt;
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::collections::BTreeMap;
 use std::str::FromStr;
+use std::sync::atomic::{AtomicBool, Ordering};
 use tokio::sync::mpsc;
 use tokio::time::Duration;
 
+pub mod presets;
+
+pub use presets::ChainPreset;
+
 /// Uniswap V2 or V3 pool type.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum PoolKind {
@@ -31,8 +40,9 @@ pub enum PriceDirection {
 /// When to emit a price update.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ListenMode {
-    /// Emit on each new block event from the RPC (re-query reserves/slot0).
+    /// Emit on each new block event from the RPC (re-query reserves/slot0 at that block).
     /// Block numbers may not be consecutive: the RPC may skip blocks (throttling, reorgs, or provider behaviour).
+    /// Duplicate notifications are dropped; reorgs are reported as [PoolEvent::Reorged].
     EveryBlock,
     /// Emit only when a Swap event is logged for the pool.
     OnSwapEvent,
@@ -43,6 +53,9 @@ pub enum ListenMode {
 pub struct PoolListenerConfig {
     /// WebSocket RPC URL (e.g. `wss://eth-mainnet.g.alchemy.com/v2/...` or `wss://mainnet.infura.io/ws/v3/...`).
     pub rpc_ws_url: String,
+    /// Further WebSocket RPC URLs of the same chain, tried in order after `rpc_ws_url`
+    /// (see [PoolListenerConfig::rpc_ws_urls]).
+    pub rpc_ws_fallback_urls: Vec<String>,
     /// Chain ID (e.g. 1 for Ethereum mainnet).
     pub chain_id: u64,
     /// Pool contract address (V2 pair or V3 pool).
@@ -52,7 +65,16 @@ pub struct PoolListenerConfig {
     /// When to emit updates.
     pub listen_mode: ListenMode,
     /// How to quote price: token1/token0 or token0/token1.
+    /// Ignored when `base_token` and `quote_token` are both set.
     pub price_direction: PriceDirection,
+    /// Optional base token address. Together with `quote_token`, the direction is resolved
+    /// from the pool's on-chain `token0()` / `token1()` so the price is always quote per base.
+    pub base_token: Option<String>,
+    /// Optional quote token address (see `base_token`).
+    pub quote_token: Option<String>,
+    /// V3 only: also compute the TWAP over this many seconds (via `observe()`) for each
+    /// update. Ignored for V2 pools.
+    pub twap_window_secs: Option<u32>,
     /// Optional symbol for the pair (e.g. "ETHUSDT") for the emitted price.
     pub symbol: Option<String>,
     /// On WS disconnect/error: 0 = no reconnect; n = up to n reconnects (1 initial run + n retries).
@@ -61,6 +83,28 @@ pub struct PoolListenerConfig {
     pub reconnect_delay_ms: u64,
 }
 
+impl PoolListenerConfig {
+    /// Adds fallback RPC URLs (see [PoolListenerConfig::rpc_ws_fallback_urls]).
+    pub fn with_fallback_rpc_ws_urls(mut self, urls: &[&str]) -> Self {
+        self.rpc_ws_fallback_urls
+            .extend(urls.iter().map(|url| url.to_string()));
+        self
+    }
+
+    /// RPC pool of the listener: `rpc_ws_url`, then the fallbacks, without empty or
+    /// duplicate entries.
+    pub fn rpc_ws_urls(&self) -> Vec<String> {
+        let mut urls: Vec<String> = Vec::new();
+        for url in std::iter::once(&self.rpc_ws_url).chain(&self.rpc_ws_fallback_urls) {
+            let url = url.trim();
+            if !url.is_empty() && !urls.iter().any(|known| known == url) {
+                urls.push(url.to_string());
+            }
+        }
+        urls
+    }
+}
+
 /// A single price update from the pool.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PoolPriceUpdate {
@@ -77,21 +121,78 @@ pub struct PoolPriceUpdate {
     /// V2: reserve of token1 (human-readable). V3: None.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub reserve1: Option<f64>,
-    /// V3: sqrtPriceX96 from slot0. V2: None.
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub sqrt_price_x96: Option<u128>,
+    /// V3: sqrtPriceX96 from slot0. V2: None. A uint160, serialized as a JSON number like
+    /// the `u128` it used to be; values above `u128::MAX` (token1/token0 ratios above 2^64)
+    /// are left out of serialized updates.
+    #[serde(
+        default,
+        skip_serializing_if = "sqrt_price_not_serializable",
+        serialize_with = "serialize_sqrt_price",
+        deserialize_with = "deserialize_sqrt_price"
+    )]
+    pub sqrt_price_x96: Option<U256>,
     pub block_number: u64,
-    pub timestamp: u64,
+    pub timestamp: Timestamp,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub symbol: Option<String>,
+    /// V3 with `twap_window_secs`: TWAP over the window, in the same direction as `price`.
+    /// None if not requested or the pool has too few observations.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub twap_price: Option<f64>,
+}
+
+impl PoolPriceUpdate {
+    /// Relative deviation of the spot price from the TWAP (`(price - twap) / twap`).
+    pub fn twap_deviation(&self) -> Option<f64> {
+        match self.twap_price {
+            Some(twap) if twap > 0.0 => Some((self.price - twap) / twap),
+            _ => None,
+        }
+    }
+}
+
+fn sqrt_price_not_serializable(sqrt_price: &Option<U256>) -> bool {
+    sqrt_price.is_none_or(|value| value > U256::from(u128::MAX))
+}
+
+fn serialize_sqrt_price<S: Serializer>(
+    sqrt_price: &Option<U256>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    match sqrt_price {
+        Some(value) if *value <= U256::from(u128::MAX) => {
+            serializer.serialize_u128(value.as_u128())
+        }
+        _ => serializer.serialize_none(),
+    }
 }
 
+fn deserialize_sqrt_price<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<U256>, D::Error> {
+    Ok(Option::<u128>::deserialize(deserializer)?.map(U256::from))
+}
+
+/// Event emitted by [stream_pool_events].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum PoolEvent {
+    /// Price of the pool at a block.
+    Price(PoolPriceUpdate),
+    /// Blocks from `from_block` on were replaced by a reorg; prices emitted for them are
+    /// invalid. The price of the new head follows as a regular [PoolEvent::Price].
+    Reorged { from_block: u64 },
+}
+
+/// Number of recent block hashes kept for reorg detection.
+const REORG_TRACK_DEPTH: u64 = 64;
+
 // Selectors (first 4 bytes of keccak256)
 const SELECTOR_GET_RESERVES: &[u8] = &[0x09, 0x02, 0xf1, 0xac];
 const SELECTOR_SLOT0: &[u8] = &[0x38, 0x50, 0xc7, 0xbd];
 const SELECTOR_TOKEN0: &[u8] = &[0x0d, 0xfe, 0x16, 0x81];
 const SELECTOR_TOKEN1: &[u8] = &[0xd2, 0x12, 0x20, 0xa7];
 const SELECTOR_DECIMALS: &[u8] = &[0x31, 0x3c, 0xe5, 0x67];
+const SELECTOR_OBSERVE: &[u8] = &[0x88, 0x3b, 0xdb, 0xfd];
 
 /// Uniswap V2 Swap(address,uint256,uint256,uint256,uint256,address)
 const TOPIC_V2_SWAP: &str = "0xd78ad95fa46c994b6551d0da85fc275fe613ce37657fb8d5e3d130840159d822";
@@ -112,49 +213,109 @@ pub fn load_dotenv() {
 
 /// Subscribe to pool price updates over WebSocket RPC (ethers-rs).
 /// Returns a receiver of [PoolPriceUpdate]; the stream runs until the connection closes or an error occurs.
+/// Reorg markers are dropped; use [stream_pool_events] to receive them.
 pub async fn stream_pool_prices(
     config: PoolListenerConfig,
 ) -> Result<mpsc::Receiver<PoolPriceUpdate>, MarketScannerError> {
+    let mut events = stream_pool_events(config).await?;
+    let (tx, rx) = mpsc::channel(64);
+    tokio::spawn(async move {
+        while let Some(event) = events.recv().await {
+            if let PoolEvent::Price(update) = event {
+                if tx.send(update).await.is_err() {
+                    return;
+                }
+            }
+        }
+    });
+    Ok(rx)
+}
+
+/// Same as [stream_pool_prices], but also emits [PoolEvent::Reorged] when blocks that
+/// already produced prices are replaced (in [ListenMode::EveryBlock]).
+pub async fn stream_pool_events(
+    config: PoolListenerConfig,
+) -> Result<mpsc::Receiver<PoolEvent>, MarketScannerError> {
+    let expected_tokens = match (&config.base_token, &config.quote_token) {
+        (Some(base), Some(quote)) => Some((parse_address(base)?, parse_address(quote)?)),
+        (None, None) => None,
+        _ => {
+            return Err(MarketScannerError::InvalidSymbol(
+                "base_token and quote_token must be set together".to_string(),
+            ));
+        }
+    };
+
+    let rpc_ws_urls = config.rpc_ws_urls();
+    if rpc_ws_urls.is_empty() {
+        return Err(MarketScannerError::InvalidConfig(
+            "rpc_ws_url must not be empty".to_string(),
+        ));
+    }
+
     let (tx, rx) = mpsc::channel(64);
-    let pool_address = config.pool_address.clone();
-    let rpc_ws_url = config.rpc_ws_url.clone();
-    let chain_id = config.chain_id;
-    let pool_kind = config.pool_kind;
-    let listen_mode = config.listen_mode;
-    let price_direction = config.price_direction;
-    let symbol = config.symbol.clone();
     let reconnect_attempts = config.reconnect_attempts;
     let reconnect_delay_ms = config.reconnect_delay_ms;
 
     tokio::spawn(async move {
         let mut attempt = 0u32;
+        let mut url_index = 0usize;
+        // URLs that failed to establish a session since the last reconnect
+        let mut failed_urls = 0usize;
         loop {
-            attempt += 1;
+            let established = AtomicBool::new(false);
             match run_listener(
-                rpc_ws_url.clone(),
-                chain_id,
-                pool_address.clone(),
-                pool_kind,
-                listen_mode,
-                price_direction,
-                symbol.clone(),
-                tx.clone(),
+                &config,
+                &rpc_ws_urls[url_index],
+                expected_tokens,
+                &tx,
+                &established,
             )
             .await
             {
                 Ok(()) => {
                     eprintln!("[pool_listener] connection closed (stream ended)");
                 }
+                // Misconfigured pool/tokens will not fix itself on reconnect
+                Err(e @ MarketScannerError::InvalidSymbol(_)) => {
+                    eprintln!("[pool_listener] configuration error: {}", e);
+                    break;
+                }
                 Err(e) => {
                     eprintln!("[pool_listener] run_listener error: {}", e);
                 }
             }
+            // Rotate on every drop, so a provider that keeps dropping sessions is not reused
+            url_index = (url_index + 1) % rpc_ws_urls.len();
+            if established.load(Ordering::Relaxed) {
+                failed_urls = 0;
+            } else {
+                failed_urls += 1;
+                // Fail over without delay until every URL of the pool was tried; URLs are
+                // logged by position only as they often embed API keys
+                if failed_urls < rpc_ws_urls.len() {
+                    eprintln!(
+                        "[pool_listener] failing over to RPC #{} of {}",
+                        url_index + 1,
+                        rpc_ws_urls.len()
+                    );
+                    continue;
+                }
+                failed_urls = 0;
+            }
+            attempt += 1;
             if reconnect_attempts == 0 || attempt > reconnect_attempts {
-                eprintln!("[pool_listener] not reconnecting (runs={}, max_reconnects={})", attempt, reconnect_attempts);
+                eprintln!(
+                    "[pool_listener] not reconnecting (runs={}, max_reconnects={})",
+                    attempt, reconnect_attempts
+                );
                 break;
             }
             let delay = Duration::from_millis(reconnect_delay_ms);
-            eprintln!("[pool_listener] reconnecting in {:?} (run {} done, up to {} reconnects)", delay, attempt, reconnect_attempts);
+            eprintln!(
+                "[pool_listener] reconnecting in {:?} (run {} done, up to {} reconnects)",
+                delay, attempt, reconnect_attempts
+            );
             tokio::time::sleep(delay).await;
         }
     });
@@ -162,64 +323,125 @@ pub async fn stream_pool_prices(
     Ok(rx)
 }
 
+/// One session of the listener on `rpc_ws_url`; sets `established` once subscribed.
 async fn run_listener(
-    rpc_ws_url: String,
-    chain_id: u64,
-    pool_address: String,
-    pool_kind: PoolKind,
-    listen_mode: ListenMode,
-    price_direction: PriceDirection,
-    symbol: Option<String>,
-    tx: mpsc::Sender<PoolPriceUpdate>,
+    config: &PoolListenerConfig,
+    rpc_ws_url: &str,
+    expected_tokens: Option<(Address, Address)>,
+    tx: &mpsc::Sender<PoolEvent>,
+    established: &AtomicBool,
 ) -> Result<(), MarketScannerError> {
-    let provider = Provider::<Ws>::connect(&rpc_ws_url)
+    let chain_id = config.chain_id;
+    let pool_address = &config.pool_address;
+    let pool_kind = config.pool_kind;
+    let symbol = &config.symbol;
+    let twap_window_secs = config
+        .twap_window_secs
+        .filter(|_| pool_kind == PoolKind::V3);
+
+    let provider = Provider::<Ws>::connect(rpc_ws_url)
         .await
         .map_err(|e| MarketScannerError::WsRpcError(e.to_string()))?;
 
     let pool_addr = Address::from_str(pool_address.trim_start_matches("0x"))
         .map_err(|e| MarketScannerError::WsRpcError(e.to_string()))?;
 
-    let (decimals0, decimals1) = fetch_decimals(&provider, &pool_addr).await?;
+    let (token0, token1) = fetch_pool_tokens(&provider, &pool_addr).await?;
+    let (decimals0, decimals1) = fetch_decimals(&provider, token0, token1).await?;
+    let price_direction = match expected_tokens {
+        Some((base, quote)) => resolve_direction(token0, token1, base, quote)?,
+        None => config.price_direction,
+    };
 
-    match listen_mode {
+    match config.listen_mode {
         ListenMode::EveryBlock => {
             let mut block_stream = provider
                 .watch_blocks()
                 .await
                 .map_err(|e| MarketScannerError::WsRpcError(e.to_string()))?;
+            established.store(true, Ordering::Relaxed);
 
-            let mut last_emitted_block: Option<u64> = None;
+            let mut blocks = BlockTracker::default();
 
             while let Some(block_hash) = block_stream.next().await {
-                let _ = block_hash;
-                let block_number = provider
-                    .get_block_number()
-                    .await
-                    .map(|n| n.as_u64())
-                    .unwrap_or(0);
-                // Only emit once per new block (RPC may send duplicate events for same block).
-                if last_emitted_block.map_or(true, |b| block_number > b) {
-                    last_emitted_block = Some(block_number);
-                    if let Ok(data) =
-                        fetch_price(&provider, &pool_addr, pool_kind, decimals0, decimals1).await
+                let block = match provider.get_block(block_hash).await {
+                    Ok(Some(block)) => block,
+                    _ => continue,
+                };
+                let Some(block_number) = block.number.map(|n| n.as_u64()) else {
+                    continue;
+                };
+                // RPC may send duplicate events for the same block
+                if blocks.hash_at(block_number) == Some(block_hash) {
+                    continue;
+                }
+
+                // Walk back while our recorded ancestors are not on the new chain
+                let mut fork = block_number;
+                let mut parent = block.parent_hash;
+                while fork > 0 && block_number - fork < REORG_TRACK_DEPTH {
+                    match blocks.hash_at(fork - 1) {
+                        Some(known) if known != parent => {}
+                        _ => break,
+                    }
+                    fork -= 1;
+                    parent = match provider.get_block(parent).await {
+                        Ok(Some(b)) => b.parent_hash,
+                        _ => break,
+                    };
+                }
+                let replaced_head = blocks.hash_at(block_number).is_some();
+                if fork < block_number || replaced_head {
+                    blocks.truncate_from(fork);
+                    if tx
+                        .send(PoolEvent::Reorged { from_block: fork })
+                        .await
+                        .is_err()
                     {
-                        let price = apply_direction(data.price, price_direction);
-                        let update = PoolPriceUpdate {
-                            chain_id,
-                            pool_address: pool_address.clone(),
-                            pool_kind,
-                            price,
-                            direction: price_direction,
-                            reserve0: data.reserve0,
-                            reserve1: data.reserve1,
-                            sqrt_price_x96: data.sqrt_price_x96,
-                            block_number,
-                            timestamp: get_timestamp_millis(),
-                            symbol: symbol.clone(),
-                        };
-                        if tx.send(update).await.is_err() {
-                            break;
-                        }
+                        break;
+                    }
+                } else if blocks.highest().is_some_and(|h| block_number < h) {
+                    // Late notification for a block we have moved past
+                    continue;
+                }
+                blocks.record(block_number, block_hash);
+
+                if let Ok(data) = fetch_price(
+                    &provider,
+                    &pool_addr,
+                    pool_kind,
+                    decimals0,
+                    decimals1,
+                    Some(BlockId::Hash(block_hash)),
+                )
+                .await
+                {
+                    let price = apply_direction(data.price, price_direction);
+                    let twap_price = fetch_twap_in_direction(
+                        &provider,
+                        &pool_addr,
+                        twap_window_secs,
+                        (decimals0, decimals1),
+                        price_direction,
+                        Some(BlockId::Hash(block_hash)),
+                    )
+                    .await;
+                    let update = PoolPriceUpdate {
+                        chain_id,
+                        pool_address: pool_address.clone(),
+                        pool_kind,
+                        price,
+                        direction: price_direction,
+                        reserve0: data.reserve0,
+                        reserve1: data.reserve1,
+                        sqrt_price_x96: data.sqrt_price_x96,
+                        block_number,
+                        timestamp: Timestamp::now(),
+                        symbol: symbol.clone(),
+                        twap_price,
+                    };
+                    if tx.send(PoolEvent::Price(update)).await.is_err() {
+                        break;
                     }
                 }
             }
@@ -234,13 +456,23 @@ async fn run_listener(
                 .watch(&filter)
                 .await
                 .map_err(|e| MarketScannerError::WsRpcError(e.to_string()))?;
+            established.store(true, Ordering::Relaxed);
 
             while let Some(log) = log_stream.next().await {
                 if let Ok(data) =
-                    fetch_price(&provider, &pool_addr, pool_kind, decimals0, decimals1).await
+                    fetch_price(&provider, &pool_addr, pool_kind, decimals0, decimals1, None).await
                 {
                     let block_number = log.block_number.unwrap_or_default().as_u64();
                     let price = apply_direction(data.price, price_direction);
+                    let twap_price = fetch_twap_in_direction(
+                        &provider,
+                        &pool_addr,
+                        twap_window_secs,
+                        (decimals0, decimals1),
+                        price_direction,
+                        None,
+                    )
+                    .await;
                     let update = PoolPriceUpdate {
                         chain_id,
                         pool_address: pool_address.clone(),
@@ -251,10 +483,11 @@ async fn run_listener(
                         reserve1: data.reserve1,
                         sqrt_price_x96: data.sqrt_price_x96,
                         block_number,
-                        timestamp: get_timestamp_millis(),
+                        timestamp: Timestamp::now(),
                         symbol: symbol.clone(),
+                        twap_price,
                     };
-                    if tx.send(update).await.is_err() {
+                    if tx.send(PoolEvent::Price(update)).await.is_err() {
                         break;
                     }
                 }
@@ -265,6 +498,58 @@ async fn run_listener(
     Ok(())
 }
 
+/// Direction that quotes `quote` per `base` for a pool holding `token0` / `token1`.
+/// Errors if the pool does not contain exactly these two tokens.
+pub fn resolve_direction(
+    token0: Address,
+    token1: Address,
+    base: Address,
+    quote: Address,
+) -> Result<PriceDirection, MarketScannerError> {
+    if base == token0 && quote == token1 {
+        Ok(PriceDirection::Token1PerToken0)
+    } else if base == token1 && quote == token0 {
+        Ok(PriceDirection::Token0PerToken1)
+    } else {
+        Err(MarketScannerError::InvalidSymbol(format!(
+            "pool tokens {:?}/{:?} do not match base {:?} / quote {:?}",
+            token0, token1, base, quote
+        )))
+    }
+}
+
+pub(crate) fn parse_address(address: &str) -> Result<Address, MarketScannerError> {
+    Address::from_str(address.trim_start_matches("0x"))
+        .map_err(|e| MarketScannerError::InvalidSymbol(format!("{}: {}", address, e)))
+}
+
+/// Hashes of the most recent blocks seen by the [ListenMode::EveryBlock] listener.
+#[derive(Default)]
+struct BlockTracker {
+    hashes: BTreeMap<u64, H256>,
+}
+
+impl BlockTracker {
+    fn hash_at(&self, number: u64) -> Option<H256> {
+        self.hashes.get(&number).copied()
+    }
+
+    fn highest(&self) -> Option<u64> {
+        self.hashes.keys().next_back().copied()
+    }
+
+    fn record(&mut self, number: u64, hash: H256) {
+        self.hashes.insert(number, hash);
+        let keep_from = number.saturating_sub(REORG_TRACK_DEPTH);
+        self.hashes = self.hashes.split_off(&keep_from);
+    }
+
+    /// Forgets `from` and every later block.
+    fn truncate_from(&mut self, from: u64) {
+        self.hashes.split_off(&from);
+    }
+}
+
 /// Internal: raw price is always token1/token0; convert to requested direction.
 fn apply_direction(raw_token1_per_token0: f64, direction: PriceDirection) -> f64 {
     match direction {
@@ -279,28 +564,44 @@ fn apply_direction(raw_token1_per_token0: f64, direction: PriceDirection) -> f64
     }
 }
 
-async fn eth_call(
+pub(crate) async fn eth_call(
     provider: &Provider<Ws>,
     to: Address,
     data: &[u8],
+) -> Result<Bytes, MarketScannerError> {
+    eth_call_at(provider, to, data, None).await
+}
+
+/// `eth_call` against the state at `block` (latest if `None`).
+async fn eth_call_at(
+    provider: &Provider<Ws>,
+    to: Address,
+    data: &[u8],
+    block: Option<BlockId>,
 ) -> Result<Bytes, MarketScannerError> {
     let tx = TransactionRequest::new()
         .to(to)
         .data(Bytes::from(data.to_vec()));
     provider
-        .call(&tx.into(), None)
+        .call(&tx.into(), block)
         .await
         .map_err(|e| MarketScannerError::WsRpcError(e.to_string()))
 }
 
-async fn fetch_decimals(
+async fn fetch_pool_tokens(
     provider: &Provider<Ws>,
     pool: &Address,
-) -> Result<(u8, u8), MarketScannerError> {
+) -> Result<(Address, Address), MarketScannerError> {
     let token0 = eth_call(provider, *pool, SELECTOR_TOKEN0).await?;
     let token1 = eth_call(provider, *pool, SELECTOR_TOKEN1).await?;
-    let addr0 = bytes_to_address(&token0)?;
-    let addr1 = bytes_to_address(&token1)?;
+    Ok((bytes_to_address(&token0)?, bytes_to_address(&token1)?))
+}
+
+async fn fetch_decimals(
+    provider: &Provider<Ws>,
+    addr0: Address,
+    addr1: Address,
+) -> Result<(u8, u8), MarketScannerError> {
     let dec0 = eth_call(provider, addr0, SELECTOR_DECIMALS).await?;
     let dec1 = eth_call(provider, addr1, SELECTOR_DECIMALS).await?;
     let d0 =
@@ -310,7 +611,7 @@ async fn fetch_decimals(
     Ok((d0, d1))
 }
 
-fn bytes_to_address(b: &Bytes) -> Result<Address, MarketScannerError> {
+pub(crate) fn bytes_to_address(b: &Bytes) -> Result<Address, MarketScannerError> {
     if b.len() < 32 {
         return Err(MarketScannerError::WsRpcError(
             "token address too short".into(),
@@ -332,7 +633,7 @@ struct PriceAndRaw {
     price: f64,
     reserve0: Option<f64>,
     reserve1: Option<f64>,
-    sqrt_price_x96: Option<u128>,
+    sqrt_price_x96: Option<U256>,
 }
 
 async fn fetch_price(
@@ -341,10 +642,12 @@ async fn fetch_price(
     pool_kind: PoolKind,
     decimals0: u8,
     decimals1: u8,
+    block: Option<BlockId>,
 ) -> Result<PriceAndRaw, MarketScannerError> {
     match pool_kind {
         PoolKind::V2 => {
-            let (price, r0, r1) = fetch_v2_price(provider, pool, decimals0, decimals1).await?;
+            let (price, r0, r1) =
+                fetch_v2_price(provider, pool, decimals0, decimals1, block).await?;
             Ok(PriceAndRaw {
                 price,
                 reserve0: Some(r0),
@@ -353,7 +656,8 @@ async fn fetch_price(
             })
         }
         PoolKind::V3 => {
-            let (price, sqrt_x96) = fetch_v3_price(provider, pool, decimals0, decimals1).await?;
+            let (price, sqrt_x96) =
+                fetch_v3_price(provider, pool, decimals0, decimals1, block).await?;
             Ok(PriceAndRaw {
                 price,
                 reserve0: None,
@@ -369,19 +673,25 @@ async fn fetch_v2_price(
     pool: &Address,
     decimals0: u8,
     decimals1: u8,
+    block: Option<BlockId>,
 ) -> Result<(f64, f64, f64), MarketScannerError> {
-    let res = eth_call(provider, *pool, SELECTOR_GET_RESERVES).await?;
+    let res = eth_call_at(provider, *pool, SELECTOR_GET_RESERVES, block).await?;
     if res.len() < 64 {
         return Err(MarketScannerError::WsRpcError(
             "getReserves response too short".into(),
         ));
     }
-    let r0 = U256::from_big_endian(&res[0..32]).as_u128() as f64 / 10f64.powi(decimals0 as i32);
-    let r1 = U256::from_big_endian(&res[32..64]).as_u128() as f64 / 10f64.powi(decimals1 as i32);
-    if r0 == 0.0 {
+    let raw0 = U256::from_big_endian(&res[0..32]);
+    let raw1 = U256::from_big_endian(&res[32..64]);
+    if raw0.is_zero() {
         return Err(MarketScannerError::WsRpcError("zero reserve0".into()));
     }
-    Ok((r1 / r0, r0, r1))
+    let price = raw_ratio(raw1, decimals1, raw0, decimals0)?;
+    Ok((
+        price,
+        raw_to_f64(raw0, decimals0),
+        raw_to_f64(raw1, decimals1),
+    ))
 }
 
 async fn fetch_v3_price(
@@ -389,17 +699,138 @@ async fn fetch_v3_price(
     pool: &Address,
     decimals0: u8,
     decimals1: u8,
-) -> Result<(f64, u128), MarketScannerError> {
-    let res = eth_call(provider, *pool, SELECTOR_SLOT0).await?;
+    block: Option<BlockId>,
+) -> Result<(f64, U256), MarketScannerError> {
+    let res = eth_call_at(provider, *pool, SELECTOR_SLOT0, block).await?;
     if res.len() < 32 {
         return Err(MarketScannerError::WsRpcError(
             "slot0 response too short".into(),
         ));
     }
-    let sqrt_price_x96 = U256::from_big_endian(&res[0..32]).as_u128();
-    let sqrt_f = sqrt_price_x96 as f64;
+    // uint160: does not fit u128 for pools with a large token1/token0 ratio
+    let sqrt_price_x96 = U256::from_big_endian(&res[0..32]);
+    let sqrt_f = u256_to_f64(sqrt_price_x96);
     let q96 = 2f64.powi(96);
     let price = (sqrt_f / q96).powi(2);
-    let decimals_adj = 10f64.powi((decimals1 as i32) - (decimals0 as i32));
-    Ok((price * decimals_adj, sqrt_price_x96))
+    Ok((
+        price * decimals_adjustment(decimals0, decimals1),
+        sqrt_price_x96,
+    ))
+}
+
+/// Scales a raw token1/token0 ratio to whole-token units.
+fn decimals_adjustment(decimals0: u8, decimals1: u8) -> f64 {
+    10f64.powi((decimals0 as i32) - (decimals1 as i32))
+}
+
+/// Time-weighted average price (token1 per token0) of a Uniswap V3 pool over the last
+/// `window_secs` seconds, from the pool's `observe()` oracle.
+pub async fn get_twap(
+    rpc_ws_url: &str,
+    pool_address: &str,
+    window_secs: u32,
+    price_direction: PriceDirection,
+) -> Result<f64, MarketScannerError> {
+    let provider = Provider::<Ws>::connect(rpc_ws_url)
+        .await
+        .map_err(|e| MarketScannerError::WsRpcError(e.to_string()))?;
+    let pool = parse_address(pool_address)?;
+    let (token0, token1) = fetch_pool_tokens(&provider, &pool).await?;
+    let (decimals0, decimals1) = fetch_decimals(&provider, token0, token1).await?;
+    let twap = fetch_v3_twap(&provider, &pool, window_secs, decimals0, decimals1, None).await?;
+    Ok(apply_direction(twap, price_direction))
+}
+
+/// TWAP for a listener update; errors (e.g. too few observations) yield None.
+async fn fetch_twap_in_direction(
+    provider: &Provider<Ws>,
+    pool: &Address,
+    window_secs: Option<u32>,
+    (decimals0, decimals1): (u8, u8),
+    price_direction: PriceDirection,
+    block: Option<BlockId>,
+) -> Option<f64> {
+    let window_secs = window_secs?;
+    fetch_v3_twap(provider, pool, window_secs, decimals0, decimals1, block)
+        .await
+        .ok()
+        .map(|twap| apply_direction(twap, price_direction))
+}
+
+async fn fetch_v3_twap(
+    provider: &Provider<Ws>,
+    pool: &Address,
+    window_secs: u32,
+    decimals0: u8,
+    decimals1: u8,
+    block: Option<BlockId>,
+) -> Result<f64, MarketScannerError> {
+    if window_secs == 0 {
+        return Err(MarketScannerError::InvalidSymbol(
+            "TWAP window must be at least 1 second".to_string(),
+        ));
+    }
+    let res = eth_call_at(provider, *pool, &encode_observe(&[window_secs, 0]), block).await?;
+    let ticks = decode_tick_cumulatives(&res)
+        .ok_or_else(|| MarketScannerError::WsRpcError("invalid observe response".into()))?;
+    if ticks.len() != 2 {
+        return Err(MarketScannerError::WsRpcError(
+            "observe returned unexpected length".into(),
+        ));
+    }
+    Ok(twap_from_tick_cumulatives(
+        ticks[0],
+        ticks[1],
+        window_secs,
+        decimals0,
+        decimals1,
+    ))
+}
+
+/// Price (token1 per token0, whole-token units) from the tick cumulatives at the start and
+/// end of a window.
+pub fn twap_from_tick_cumulatives(
+    tick_cumulative_start: i64,
+    tick_cumulative_end: i64,
+    window_secs: u32,
+    decimals0: u8,
+    decimals1: u8,
+) -> f64 {
+    let mean_tick = (tick_cumulative_end - tick_cumulative_start) as f64 / window_secs as f64;
+    1.0001f64.powf(mean_tick) * decimals_adjustment(decimals0, decimals1)
+}
+
+/// ABI-encodes `observe(uint32[] secondsAgos)`.
+fn encode_observe(seconds_agos: &[u32]) -> Vec<u8> {
+    let word = |value: u64| {
+        let mut w = [0u8; 32];
+        w[24..].copy_from_slice(&value.to_be_bytes());
+        w
+    };
+    let mut data = SELECTOR_OBSERVE.to_vec();
+    data.extend_from_slice(&word(32));
+    data.extend_from_slice(&word(seconds_agos.len() as u64));
+    for &ago in seconds_agos {
+        data.extend_from_slice(&word(ago as u64));
+    }
+    data
+}
+
+/// First return value of `observe()`: `int56[] tickCumulatives`.
+fn decode_tick_cumulatives(res: &[u8]) -> Option<Vec<i64>> {
+    let word = |i: usize| res.get(i..i + 32);
+    // Offsets/lengths beyond the response are malformed (and could overflow usize)
+    let index = |w: &[u8]| {
+        let value = U256::from_big_endian(w);
+        (value <= U256::from(res.len())).then(|| value.as_usize())
+    };
+    let offset = index(word(0)?)?;
+    let len = index(word(offset)?)?;
+    (0..len)
+        .map(|k| {
+            let w = word(offset + 32 * (k + 1))?;
+            // int56 is sign-extended to 32 bytes; the low 8 bytes hold it as i64
+            Some(i64::from_be_bytes(w[24..32].try_into().ok()?))
+        })
+        .collect()
 }
@@ -0,0 +1,146 @@
+//! Per-chain defaults for the pool listener.
+//!
+//! A [ChainPreset] knows a chain's id, its approximate block time and the environment
+//! variables its WebSocket RPC URLs are commonly kept in. [ChainPreset::rpc_ws_urls]
+//! collects every URL set there (a variable may hold a comma-separated list), and
+//! [ChainPreset::listener_config] builds a [PoolListenerConfig] that fails over between
+//! them.
+
+use crate::common::MarketScannerError;
+use crate::dex::chains::ChainId;
+use crate::dex::pool_listener::{ListenMode, PoolKind, PoolListenerConfig, PriceDirection};
+use std::time::Duration;
+
+/// Reconnects of a [ChainPreset::listener_config] (passes over the whole RPC pool).
+const PRESET_RECONNECT_ATTEMPTS: u32 = 10;
+
+/// Chain id, block time and RPC environment variables of a chain.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChainPreset {
+    pub chain: ChainId,
+    /// EVM chain id (e.g. 1 for Ethereum)
+    pub chain_id: u64,
+    /// Approximate block time (milliseconds)
+    pub block_time_ms: u64,
+    /// Environment variables read by [ChainPreset::rpc_ws_urls], in priority order
+    pub rpc_ws_env_vars: &'static [&'static str],
+}
+
+impl ChainPreset {
+    pub fn for_chain(chain: &ChainId) -> Self {
+        let (block_time_ms, rpc_ws_env_vars): (u64, &'static [&'static str]) = match chain {
+            ChainId::ETHEREUM => (
+                12_000,
+                &[
+                    "ETHEREUM_WS_RPC_URL",
+                    "ETH_WS_RPC_URL",
+                    "ETH_RPC_WS",
+                    "MAINNET_WS_URL",
+                ],
+            ),
+            ChainId::BSC => (750, &["BSC_WS_RPC_URL", "BNB_WS_RPC_URL", "BSC_RPC_WS"]),
+            ChainId::POLYGON => (
+                2_000,
+                &["POLYGON_WS_RPC_URL", "MATIC_WS_RPC_URL", "POLYGON_RPC_WS"],
+            ),
+            ChainId::AVALANCHE => (
+                2_000,
+                &["AVALANCHE_WS_RPC_URL", "AVAX_WS_RPC_URL", "AVAX_RPC_WS"],
+            ),
+            ChainId::ARBITRUM => (
+                250,
+                &["ARBITRUM_WS_RPC_URL", "ARB_WS_RPC_URL", "ARBITRUM_RPC_WS"],
+            ),
+            ChainId::OPTIMISM => (
+                2_000,
+                &["OPTIMISM_WS_RPC_URL", "OP_WS_RPC_URL", "OPTIMISM_RPC_WS"],
+            ),
+            ChainId::BASE => (2_000, &["BASE_WS_RPC_URL", "BASE_RPC_WS"]),
+            ChainId::PLASMA => (1_000, &["PLASMA_WS_RPC_URL", "PLASMA_RPC_WS"]),
+            ChainId::UNICHAIN => (1_000, &["UNICHAIN_WS_RPC_URL", "UNICHAIN_RPC_WS"]),
+            ChainId::SONIC => (1_000, &["SONIC_WS_RPC_URL", "SONIC_RPC_WS"]),
+            ChainId::RONIN => (3_000, &["RONIN_WS_RPC_URL", "RONIN_RPC_WS"]),
+            ChainId::HyperEVM => (1_000, &["HYPEREVM_WS_RPC_URL", "HYPEREVM_RPC_WS"]),
+            ChainId::LINEA => (2_000, &["LINEA_WS_RPC_URL", "LINEA_RPC_WS"]),
+            ChainId::MANTLE => (2_000, &["MANTLE_WS_RPC_URL", "MANTLE_RPC_WS"]),
+        };
+        Self {
+            chain: chain.clone(),
+            chain_id: chain.clone() as u64,
+            block_time_ms,
+            rpc_ws_env_vars,
+        }
+    }
+
+    /// Preset of the chain with EVM id `chain_id`, if supported.
+    pub fn for_chain_id(chain_id: u64) -> Option<Self> {
+        ChainId::ALL
+            .iter()
+            .find(|chain| (*chain).clone() as u64 == chain_id)
+            .map(Self::for_chain)
+    }
+
+    /// Presets of every supported chain.
+    pub fn all() -> Vec<Self> {
+        ChainId::ALL.iter().map(Self::for_chain).collect()
+    }
+
+    pub fn block_time(&self) -> Duration {
+        Duration::from_millis(self.block_time_ms)
+    }
+
+    /// WebSocket RPC URLs from [ChainPreset::rpc_ws_env_vars], in order and without
+    /// duplicates. Each variable may hold one URL or a comma-separated list.
+    pub fn rpc_ws_urls(&self) -> Vec<String> {
+        let mut urls: Vec<String> = Vec::new();
+        for var in self.rpc_ws_env_vars {
+            let Ok(value) = std::env::var(var) else {
+                continue;
+            };
+            for url in value
+                .split(',')
+                .map(str::trim)
+                .filter(|url| !url.is_empty())
+            {
+                if !urls.iter().any(|known| known == url) {
+                    urls.push(url.to_string());
+                }
+            }
+        }
+        urls
+    }
+
+    /// Listener config for `pool_address` on this chain, emitting on every block and
+    /// failing over between every URL of [ChainPreset::rpc_ws_urls]. Fails if none is
+    /// set.
+    pub fn listener_config(
+        &self,
+        pool_address: &str,
+        pool_kind: PoolKind,
+    ) -> Result<PoolListenerConfig, MarketScannerError> {
+        let mut urls = self.rpc_ws_urls().into_iter();
+        let Some(primary) = urls.next() else {
+            return Err(MarketScannerError::InvalidConfig(format!(
+                "No WebSocket RPC URL for {}: set one of {}",
+                self.chain.name(),
+                self.rpc_ws_env_vars.join(", ")
+            )));
+        };
+        Ok(PoolListenerConfig {
+            rpc_ws_url: primary,
+            rpc_ws_fallback_urls: urls.collect(),
+            chain_id: self.chain_id,
+            pool_address: pool_address.to_string(),
+            pool_kind,
+            listen_mode: ListenMode::EveryBlock,
+            price_direction: PriceDirection::Token1PerToken0,
+            base_token: None,
+            quote_token: None,
+            twap_window_secs: None,
+            symbol: None,
+            reconnect_attempts: PRESET_RECONNECT_ATTEMPTS,
+            // A few blocks, at least a second
+            reconnect_delay_ms: (self.block_time_ms * 3).max(1_000),
+        })
+    }
+}
@@ -0,0 +1,310 @@
+//! Cross-venue index price.
+//!
+//! An [IndexCalculator] combines the mid prices of one market across venues into a single
+//! weighted index price, the way reference indices for derivatives are built. Venues are
+//! weighted per [IndexWeighting] (equally, by top-of-book liquidity, by [VenueScorer] score or
+//! by fixed weights). Stale prices and mids too far from the cross-venue median are left out.
+//! The index is a fair-value reference: [IndexPrice::deviation_percent] tells how far a
+//! venue quote or an execution price is from it, and [IndexPrice::is_sane] checks both legs of
+//! an opportunity against it. [IndexCalculator::stream] turns a price stream into a stream of
+//! index updates.
+
+use crate::common::{CexExchange, CexPrice, Exchange, MarketKey, Timestamp, logical_market_key};
+use crate::scanner::{ArbitrageOpportunity, ArbitrageScanner, PriceData, VenueScorer};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::time::Duration;
+use tokio::sync::mpsc;
+
+/// How the venues of an index are weighted.
+#[derive(Debug, Clone, Default)]
+pub enum IndexWeighting {
+    /// Same weight for every venue
+    Equal,
+    /// Top-of-book depth in quote currency (`mid × min(bid_qty, ask_qty)`)
+    #[default]
+    Liquidity,
+    /// Total [VenueScorer] score of the venue for the symbol
+    Scored(VenueScorer),
+    /// Fixed weight per venue; venues without a weight are left out
+    Fixed(HashMap<CexExchange, f64>),
+}
+
+/// Settings of an [IndexCalculator].
+#[derive(Debug, Clone)]
+pub struct IndexConfig {
+    pub weighting: IndexWeighting,
+    /// Mids further than this from the median of all venues (percent) are excluded
+    pub max_deviation_percent: f64,
+    /// Prices older than this are excluded
+    pub max_age: Option<Duration>,
+    /// Fewest venues an index is computed from
+    pub min_venues: usize,
+}
+
+impl Default for IndexConfig {
+    fn default() -> Self {
+        Self {
+            weighting: IndexWeighting::default(),
+            max_deviation_percent: 1.0,
+            max_age: None,
+            min_venues: 2,
+        }
+    }
+}
+
+impl IndexConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_weighting(mut self, weighting: IndexWeighting) -> Self {
+        self.weighting = weighting;
+        self
+    }
+
+    pub fn with_max_deviation_percent(mut self, max_deviation_percent: f64) -> Self {
+        self.max_deviation_percent = max_deviation_percent;
+        self
+    }
+
+    pub fn with_max_age(mut self, max_age: Duration) -> Self {
+        self.max_age = Some(max_age);
+        self
+    }
+
+    pub fn with_min_venues(mut self, min_venues: usize) -> Self {
+        self.min_venues = min_venues.max(1);
+        self
+    }
+}
+
+/// A venue in an [IndexPrice].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct IndexConstituent {
+    pub exchange: String,
+    pub mid_price: f64,
+    /// Normalized weight (the weights of an index sum to 1)
+    pub weight: f64,
+}
+
+/// Why a venue was left out of an [IndexPrice].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ExclusionReason {
+    /// Mid too far from the cross-venue median
+    Outlier,
+    /// Price older than the configured maximum age
+    Stale,
+    /// Zero weight (no liquidity, no score or no fixed weight)
+    NoWeight,
+}
+
+/// A venue left out of an [IndexPrice].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExcludedVenue {
+    pub exchange: String,
+    pub mid_price: f64,
+    pub reason: ExclusionReason,
+}
+
+/// Weighted cross-venue index price of one market.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct IndexPrice {
+    pub symbol: String,
+    pub price: f64,
+    /// Median mid of all fresh venues, the reference for outlier exclusion
+    pub median: f64,
+    pub constituents: Vec<IndexConstituent>,
+    pub excluded: Vec<ExcludedVenue>,
+    pub timestamp: Timestamp,
+}
+
+impl IndexPrice {
+    /// Signed distance of `price` from the index, in percent.
+    pub fn deviation_percent(&self, price: f64) -> f64 {
+        (price - self.price) / self.price * 100.0
+    }
+
+    /// Whether both legs of `opportunity` (source ask, destination bid) are within
+    /// `max_deviation_percent` of the index; a leg far from fair value usually means a bad
+    /// quote rather than a real spread.
+    pub fn is_sane(&self, opportunity: &ArbitrageOpportunity, max_deviation_percent: f64) -> bool {
+        let ask = match &opportunity.source_leg {
+            PriceData::Cex(p) => p.ask_price,
+            PriceData::Dex(p) => p.ask_price,
+        };
+        let bid = match &opportunity.destination_leg {
+            PriceData::Cex(p) => p.bid_price,
+            PriceData::Dex(p) => p.bid_price,
+        };
+        self.deviation_percent(ask).abs() <= max_deviation_percent
+            && self.deviation_percent(bid).abs() <= max_deviation_percent
+    }
+}
+
+/// Computes [IndexPrice]s, keeping the latest price per venue and market.
+#[derive(Debug, Clone, Default)]
+pub struct IndexCalculator {
+    config: IndexConfig,
+    latest: HashMap<(MarketKey, Exchange), CexPrice>,
+}
+
+impl IndexCalculator {
+    pub fn new(config: IndexConfig) -> Self {
+        Self {
+            config,
+            latest: HashMap::new(),
+        }
+    }
+
+    pub fn config(&self) -> &IndexConfig {
+        &self.config
+    }
+
+    /// Index of `symbol` from `prices` (other markets in `prices` are ignored); `None` if
+    /// fewer than `min_venues` venues remain after exclusions.
+    pub fn compute(&self, symbol: &str, prices: &[CexPrice]) -> Option<IndexPrice> {
+        let market = MarketKey::from_symbol(symbol);
+        let prices: Vec<&CexPrice> = prices
+            .iter()
+            .filter(|p| p.mid_price > 0.0 && p.mid_price.is_finite())
+            .filter(|p| logical_market_key(&p.symbol, &p.exchange) == market)
+            .collect();
+
+        let mut excluded = Vec::new();
+        let mut fresh = Vec::new();
+        for price in prices {
+            if self
+                .config
+                .max_age
+                .is_some_and(|max_age| price.timestamp.is_older_than(max_age))
+            {
+                excluded.push(excluded_venue(price, ExclusionReason::Stale));
+            } else {
+                fresh.push(price);
+            }
+        }
+        let median = median(fresh.iter().map(|p| p.mid_price).collect())?;
+
+        let mut weighted = Vec::new();
+        let owned: Vec<CexPrice> = fresh.iter().map(|p| (*p).clone()).collect();
+        let scores = match &self.config.weighting {
+            IndexWeighting::Scored(scorer) => Some(scorer.score(symbol, &owned)),
+            _ => None,
+        };
+        for price in fresh {
+            if (price.mid_price - median).abs() / median * 100.0 > self.config.max_deviation_percent
+            {
+                excluded.push(excluded_venue(price, ExclusionReason::Outlier));
+                continue;
+            }
+            let weight = match &self.config.weighting {
+                IndexWeighting::Equal => 1.0,
+                IndexWeighting::Liquidity => price.mid_price * price.bid_qty.min(price.ask_qty),
+                IndexWeighting::Scored(_) => scores
+                    .iter()
+                    .flatten()
+                    .find(|s| Exchange::Cex(s.exchange.clone()) == price.exchange)
+                    .map_or(0.0, |s| s.total),
+                IndexWeighting::Fixed(weights) => match &price.exchange {
+                    Exchange::Cex(exchange) => weights.get(exchange).copied().unwrap_or(0.0),
+                    Exchange::Dex(_) => 0.0,
+                },
+            };
+            if weight.is_finite() && weight > 0.0 {
+                weighted.push((price, weight));
+            } else {
+                excluded.push(excluded_venue(price, ExclusionReason::NoWeight));
+            }
+        }
+        if weighted.len() < self.config.min_venues.max(1) {
+            return None;
+        }
+
+        let total: f64 = weighted.iter().map(|(_, w)| w).sum();
+        let index = weighted.iter().map(|(p, w)| p.mid_price * w).sum::<f64>() / total;
+        let constituents = weighted
+            .into_iter()
+            .map(|(price, weight)| IndexConstituent {
+                exchange: ArbitrageScanner::exchange_name(&price.exchange),
+                mid_price: price.mid_price,
+                weight: weight / total,
+            })
+            .collect();
+        Some(IndexPrice {
+            symbol: symbol.to_string(),
+            price: index,
+            median,
+            constituents,
+            excluded,
+            timestamp: Timestamp::now(),
+        })
+    }
+
+    /// Records `price` as the latest of its venue and returns the updated index of its
+    /// market (labelled with the price's symbol).
+    pub fn update(&mut self, price: CexPrice) -> Option<IndexPrice> {
+        let market = logical_market_key(&price.symbol, &price.exchange);
+        let symbol = price.symbol.clone();
+        self.latest
+            .insert((market.clone(), price.exchange.clone()), price);
+        let prices: Vec<CexPrice> = self
+            .latest
+            .iter()
+            .filter(|((m, _), _)| *m == market)
+            .map(|(_, p)| p.clone())
+            .collect();
+        self.compute(&symbol, &prices)
+    }
+
+    /// Index updates for `symbols` computed from `prices`: one per incoming price of a
+    /// watched symbol that yields an index. Ends when `prices` does.
+    pub fn stream(
+        mut self,
+        symbols: &[&str],
+        mut prices: mpsc::Receiver<CexPrice>,
+    ) -> mpsc::Receiver<IndexPrice> {
+        let watched: HashMap<MarketKey, String> = symbols
+            .iter()
+            .map(|s| (MarketKey::from_symbol(s), s.to_string()))
+            .collect();
+        let (tx, rx) = mpsc::channel(64);
+        tokio::spawn(async move {
+            while let Some(price) = prices.recv().await {
+                let market = logical_market_key(&price.symbol, &price.exchange);
+                let Some(symbol) = watched.get(&market) else {
+                    continue;
+                };
+                let Some(mut index) = self.update(price) else {
+                    continue;
+                };
+                index.symbol = symbol.clone();
+                if tx.send(index).await.is_err() {
+                    return;
+                }
+            }
+        });
+        rx
+    }
+}
+
+fn excluded_venue(price: &CexPrice, reason: ExclusionReason) -> ExcludedVenue {
+    ExcludedVenue {
+        exchange: ArbitrageScanner::exchange_name(&price.exchange),
+        mid_price: price.mid_price,
+        reason,
+    }
+}
+
+fn median(mut values: Vec<f64>) -> Option<f64> {
+    if values.is_empty() {
+        return None;
+    }
+    values.sort_by(f64::total_cmp);
+    let mid = values.len() / 2;
+    Some(if values.len() % 2 == 0 {
+        (values[mid - 1] + values[mid]) / 2.0
+    } else {
+        values[mid]
+    })
+}
@@ -33,9 +33,16 @@
 //! # }
 //! ```
 
+#[cfg(feature = "arrow")]
+pub mod arrow;
 pub mod cex;
 pub mod common;
 pub mod dex;
+pub mod index;
+#[cfg(feature = "node")]
+pub mod node;
+pub mod portfolio;
+pub mod risk;
 pub mod scanner;
 
 // Re-export common types
@@ -45,13 +52,54 @@ pub use cex::{
 };
 
 pub use common::{
-    AmountSide, CEXTrait, CexExchange, CexPrice, DEXTrait, DexAggregator, DexPrice,
-    DexRouteSummary, Exchange, ExchangeTrait, FeeOverrides, MarketScannerError, effective_price,
-    effective_price_with_overrides, fee_rate, fee_rate_with_overrides, taker_fee_rate,
-    taker_fee_rate_with_overrides,
+    AmountSide, AmountSpec, BaseQty, CEXTrait, CacheLimits, CacheStats, Capabilities, CexExchange,
+    CexPrice, ClockOffset, DEXTrait, DexAggregator, DexLadderPoint, DexPrice, DexPriceLadder,
+    DexRouteSummary, DexSourceFilter, DryRunRecord, Exchange, ExchangeTrait, FeeOverrides,
+    LatencyGauge, MarketKey, MarketScannerError, OrderBook, OrderBookLevel, OrderLimits, QuoteQty,
+    ReconnectPolicy, SeededRng, SymbolFormatter, SymbolRegistry, Timestamp, TradingStatus,
+    VenueCapability, VenueHealth, all_venue_health, canonical_asset, clear_dry_run_log,
+    clear_symbol_formatter, clear_ws_endpoint_override, dry_run_enabled, dry_run_log,
+    effective_price, effective_price_with_overrides, fee_rate, fee_rate_with_overrides,
+    logical_market_key, measure_clock_offset, register_asset_alias, register_symbol_override,
+    remove_asset_alias, remove_symbol_override, set_dry_run, set_symbol_formatter,
+    set_ws_endpoint_override, taker_fee_rate, taker_fee_rate_with_overrides, venue_health,
 };
 pub use dex::{
-    KyberSwap, ListenMode, PoolKind, PriceDirection, PoolListenerConfig, PoolPriceUpdate,
-    load_dotenv, stream_pool_prices,
+    ChainPreset, KyberSwap, ListenMode, PoolKind, PoolListenerConfig, PoolPriceUpdate,
+    PriceDirection, load_dotenv, stream_pool_prices,
+};
+pub use index::{
+    ExcludedVenue, ExclusionReason, IndexCalculator, IndexConfig, IndexConstituent, IndexPrice,
+    IndexWeighting,
+};
+pub use portfolio::{
+    AdverseSelectionModel, AttributionGrouping, Fill, PaperTrader, PnlPoint, Portfolio, Position,
+    ProfitAttribution, RebalancePlan, RebalancePlanner, SlippageModel, TradeAttribution, TradeSide,
+};
+pub use risk::{Exposure, RiskLimits, RiskManager, RiskViolation};
+pub use scanner::{
+    AckOutcome, AggregatorComparison, AggregatorPollingSource, AggregatorQuote,
+    AlertBackfillReport, AlertDeduplicator, AlertTemplate, AnomalyDetector, AnomalyDetectorConfig,
+    AnomalyKind, ArbitrageOpportunity, ArbitrageScanner, AssetTransferStatus, AutoScan,
+    BackpressurePolicy, CapabilityReport, CapitalEfficiency, CapitalEfficiencyModel,
+    CexWebSocketSource, ChainDexQuote, ChainFanOut, CircuitBreakerConfig, CircuitState,
+    CompositeQuote, CompositeQuoteConfig, CompositeQuoteSource, CostLayer, DepthRecorderOptions,
+    DepthSnapshot, DepthSnapshotReader, ExecutionAck, ExecutionReport, ExecutionStats,
+    FeedDiffOptions, FeedDiffReport, FeedUpdateStats, FiatCalendar, FiatRestriction, FieldClass,
+    FundingModel, IncidentRouter, IncidentSink, InstrumentIdRefresh, LatencyTrace, LifecycleConfig,
+    LifecycleEvent, LiquidityCurve, LiquidityPoint, LiquiditySource, LivePriceCache,
+    LiveScanOptions, MaintenanceMonitor, MarketAnomaly, MatchMetrics, MevRisk, MevRiskModel,
+    MinNotionalGuard, MockPriceSource, MultiHopOpportunity, MultiHopOptions, MultiLegOpportunity,
+    NotionalFloor, OPPORTUNITY_SCHEMA_VERSION, OpportunityAggregator, OpportunityClosed,
+    OpportunityExpiry, OpportunityLifecycle, OpportunityRanking, OpportunityReport,
+    OpportunitySeasonality, OpportunitySummary, OpportunityViability, OpsgenieSink,
+    OutputPrecision, PagerDutySink, PoolListenerSource, PriceData, PriceHistoryFrame, PriceReplay,
+    PriceSource, QuoteSourceKind, ReadinessReport, RejectReason, ReplayCheckpoint, ReplayProgress,
+    RestPollingSource, ScanSchedule, ScannerContext, ScannerEvent, ScannerHandle, ScannerRunState,
+    ScannerSettings, ScheduledScan, SellTranche, SettingsHandle, SpreadBreakdown, SpreadCostModel,
+    SpreadHeatmap, StreamTee, TransferCostModel, TransferCosts, TransferStatusMonitor,
+    UpdateRateTracker, VenueFeed, VenueHealthCheck, VenueIncident, VenueReadiness, VenueReport,
+    VenueScore, VenueScoreWeights, VenueScorer, VenueStatus, VenueStatusEvent, VolatilityGuard,
+    VolatilityGuardConfig, WarmUpOptions, WarmUpStep, WsSoakOptions, WsStabilityReport,
+    ZeroSizePolicy, detect_market_anomalies,
 };
-pub use scanner::{ArbitrageOpportunity, ArbitrageScanner, PriceData};
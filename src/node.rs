@@ -0,0 +1,240 @@
+//! Node.js bindings (napi-rs), enabled by the `node` feature.
+//!
+//! Exchanges are passed by ccxt id (`"binance"`, `"okx"`, ...), prices and events are
+//! returned as plain objects with the same fields as their serde representation. Both
+//! entry points return a stream handle whose `next()` resolves to the next item, or
+//! `null` once the stream has ended, so JavaScript can wrap it in an async iterator or an
+//! `EventEmitter`.
+
+use crate::common::ccxt::cex_from_ccxt_id;
+use crate::common::{CexExchange, CexPrice, FeeOverrides, MarketScannerError, merge_price_streams};
+use crate::scanner::{ArbitrageScanner, LiveScanOptions, ScannerEvent, ScannerHandle};
+use napi::bindgen_prelude::*;
+use napi_derive::napi;
+use serde_json::{Value, json};
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::{Mutex, mpsc};
+
+const DEFAULT_RECONNECT_ATTEMPTS: u32 = 10;
+const DEFAULT_RECONNECT_DELAY_MS: u32 = 5000;
+
+/// Options of `streamPrices`.
+#[napi(object)]
+#[derive(Default)]
+pub struct StreamPricesOptions {
+    /// Reconnect attempts per venue (default 10, 0 disables reconnects)
+    pub reconnect_attempts: Option<u32>,
+    /// Delay between reconnects (default 5000 ms)
+    pub reconnect_delay_ms: Option<u32>,
+}
+
+/// Options of `scanArbitrage`.
+#[napi(object)]
+#[derive(Default)]
+pub struct ScanArbitrageOptions {
+    /// Delay between reconnects of a dropped venue (0 → 1000 ms)
+    pub reconnect_delay_ms: Option<u32>,
+    /// Taker fee per ccxt exchange id, e.g. `{ binance: 0.00075 }`
+    pub taker_fees: Option<HashMap<String, f64>>,
+    /// REST polling interval while a venue's circuit breaker is open (default: no fallback)
+    pub rest_fallback_interval_ms: Option<u32>,
+}
+
+/// Merged price stream of several venues.
+#[napi]
+pub struct PriceStream {
+    rx: Arc<Mutex<mpsc::Receiver<CexPrice>>>,
+}
+
+#[napi]
+impl PriceStream {
+    /// Next price update, or `null` once every venue stream has ended.
+    #[napi]
+    pub async fn next(&self) -> Result<Option<Value>> {
+        match self.rx.lock().await.recv().await {
+            Some(price) => serde_json::to_value(price).map(Some).map_err(to_napi_error),
+            None => Ok(None),
+        }
+    }
+
+    /// Stops all venue streams; pending `next()` calls resolve to `null`.
+    #[napi]
+    pub async fn close(&self) {
+        self.rx.lock().await.close();
+    }
+}
+
+/// Live scanner events: `{ type: "opportunities", opportunities }`,
+/// `{ type: "circuitStateChanged", exchange, state, timestamp }` and
+/// `{ type: "feedChanged", exchange, feed, timestamp }` and
+/// `{ type: "stateChanged", state, timestamp }`.
+#[napi]
+pub struct ScannerStream {
+    rx: Arc<Mutex<mpsc::Receiver<ScannerEvent>>>,
+    handle: ScannerHandle,
+}
+
+#[napi]
+impl ScannerStream {
+    /// Next scanner event, or `null` once the scanner has stopped.
+    #[napi]
+    pub async fn next(&self) -> Result<Option<Value>> {
+        match self.rx.lock().await.recv().await {
+            Some(event) => event_to_value(event).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    /// Halts opportunity events; venue connections stay open.
+    #[napi]
+    pub fn pause(&self) {
+        self.handle.pause();
+    }
+
+    /// Resumes opportunity events after `pause()`.
+    #[napi]
+    pub fn resume(&self) {
+        self.handle.resume();
+    }
+
+    /// Stops the scanner and its venue connections.
+    #[napi]
+    pub async fn close(&self) {
+        self.handle.stop();
+        self.rx.lock().await.close();
+    }
+}
+
+/// Streams prices of `symbols` from the WebSocket feeds of `exchanges` (ccxt ids).
+#[napi]
+pub async fn stream_prices(
+    symbols: Vec<String>,
+    exchanges: Vec<String>,
+    options: Option<StreamPricesOptions>,
+) -> Result<PriceStream> {
+    let exchanges = parse_exchanges(&exchanges)?;
+    let options = options.unwrap_or_default();
+    let symbol_refs: Vec<&str> = symbols.iter().map(String::as_str).collect();
+
+    let mut receivers = Vec::with_capacity(exchanges.len());
+    for exchange in &exchanges {
+        let rx = ArbitrageScanner::stream_cex_prices_websocket(
+            exchange,
+            &symbol_refs,
+            options
+                .reconnect_attempts
+                .unwrap_or(DEFAULT_RECONNECT_ATTEMPTS),
+            options
+                .reconnect_delay_ms
+                .unwrap_or(DEFAULT_RECONNECT_DELAY_MS) as u64,
+        )
+        .await
+        .map_err(to_napi_error)?;
+        receivers.push(rx);
+    }
+
+    Ok(PriceStream {
+        rx: Arc::new(Mutex::new(merge_price_streams(receivers))),
+    })
+}
+
+/// Runs the live scanner ([ArbitrageScanner::scan_live]) on `exchanges` (ccxt ids).
+#[napi]
+pub async fn scan_arbitrage(
+    symbols: Vec<String>,
+    exchanges: Vec<String>,
+    options: Option<ScanArbitrageOptions>,
+) -> Result<ScannerStream> {
+    let exchanges = parse_exchanges(&exchanges)?;
+    let options = options.unwrap_or_default();
+    let symbol_refs: Vec<&str> = symbols.iter().map(String::as_str).collect();
+
+    let handle = ScannerHandle::new();
+    let mut live_options = LiveScanOptions::default()
+        .with_handle(handle.clone())
+        .with_reconnect_delay_ms(options.reconnect_delay_ms.unwrap_or(0) as u64)
+        .with_rest_fallback(options.rest_fallback_interval_ms.unwrap_or(0) as u64);
+    if let Some(taker_fees) = options.taker_fees {
+        let mut overrides = FeeOverrides::default();
+        for (id, fee) in taker_fees {
+            overrides = overrides.with_cex_taker_fee(parse_exchange(&id)?, fee);
+        }
+        live_options = live_options.with_fee_overrides(overrides);
+    }
+
+    let rx = ArbitrageScanner::scan_live(&symbol_refs, &exchanges, live_options)
+        .await
+        .map_err(to_napi_error)?;
+    Ok(ScannerStream {
+        rx: Arc::new(Mutex::new(rx)),
+        handle,
+    })
+}
+
+fn event_to_value(event: ScannerEvent) -> Result<Value> {
+    match event {
+        ScannerEvent::Opportunities(opportunities) => Ok(json!({
+            "type": "opportunities",
+            "opportunities": serde_json::to_value(opportunities).map_err(to_napi_error)?,
+        })),
+        ScannerEvent::OpportunityClosed(closed) => Ok(json!({
+            "type": "opportunityClosed",
+            "closed": serde_json::to_value(closed).map_err(to_napi_error)?,
+        })),
+        ScannerEvent::CircuitStateChanged {
+            exchange,
+            state,
+            timestamp,
+        } => Ok(json!({
+            "type": "circuitStateChanged",
+            "exchange": exchange,
+            "state": state,
+            "timestamp": timestamp,
+        })),
+        ScannerEvent::FeedChanged {
+            exchange,
+            feed,
+            timestamp,
+        } => Ok(json!({
+            "type": "feedChanged",
+            "exchange": exchange,
+            "feed": feed,
+            "timestamp": timestamp,
+        })),
+        ScannerEvent::StateChanged { state, timestamp } => Ok(json!({
+            "type": "stateChanged",
+            "state": state,
+            "timestamp": timestamp,
+        })),
+        ScannerEvent::VenueError {
+            exchange,
+            error,
+            retryable,
+            timestamp,
+        } => Ok(json!({
+            "type": "venueError",
+            "exchange": exchange,
+            "error": error,
+            "retryable": retryable,
+            "timestamp": timestamp,
+        })),
+    }
+}
+
+fn parse_exchanges(ids: &[String]) -> Result<Vec<CexExchange>> {
+    ids.iter().map(|id| parse_exchange(id)).collect()
+}
+
+fn parse_exchange(id: &str) -> Result<CexExchange> {
+    cex_from_ccxt_id(id).ok_or_else(|| {
+        to_napi_error(MarketScannerError::ApiError(format!(
+            "Unknown exchange id: {}",
+            id
+        )))
+    })
+}
+
+fn to_napi_error(err: impl std::fmt::Display) -> Error {
+    Error::from_reason(err.to_string())
+}
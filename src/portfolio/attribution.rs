@@ -0,0 +1,273 @@
+//! Where realized profit came from.
+//!
+//! A [TradeAttribution] splits the realized PnL of one executed (or paper-traded)
+//! opportunity into the quoted spread captured on the matched quantity, fees paid,
+//! slippage against the quoted prices (including the cost of unwinding unmatched
+//! quantity) and transfer costs, so that
+//! `realized_pnl = spread_captured − fees − slippage − transfer_costs`.
+//! [ProfitAttribution] collects trades and sums them per venue pair or symbol, exportable
+//! to CSV or JSON (optionally rounded, see [OutputPrecision]).
+
+use super::{Fill, TradeSide};
+use crate::cex::execution::ExecutionOutcome;
+use crate::common::{MarketScannerError, Timestamp};
+use crate::scanner::{ArbitrageOpportunity, FieldClass, OutputPrecision};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// PnL components of one opportunity.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TradeAttribution {
+    pub buy_exchange: String,
+    pub sell_exchange: String,
+    pub symbol: String,
+    /// Quantity bought on the buy venue and sold on the sell venue
+    pub matched_quantity: f64,
+    /// Quoted bid − ask on the matched quantity
+    pub spread_captured: f64,
+    pub fees: f64,
+    /// Execution shortfall against the quoted prices; negative for price improvement
+    pub slippage: f64,
+    pub transfer_costs: f64,
+    pub realized_pnl: f64,
+    pub timestamp: Timestamp,
+}
+
+impl TradeAttribution {
+    /// Attributes the fills of `opportunity`. Quantity left open is valued at the quoted
+    /// price of its leg, so only execution costs are counted.
+    pub fn from_fills(opportunity: &ArbitrageOpportunity, fills: &[Fill]) -> Self {
+        let (buy_exchange, buy_price) = opportunity.source_leg.exchange_and_price(true);
+        let (sell_exchange, sell_price) = opportunity.destination_leg.exchange_and_price(false);
+
+        let mut bought = 0.0;
+        let mut sold = 0.0;
+        let mut open = 0.0;
+        let mut cash = 0.0;
+        let mut fees = 0.0;
+        for fill in fills {
+            match fill.side {
+                TradeSide::Buy => {
+                    open += fill.quantity;
+                    cash -= fill.quantity * fill.price;
+                    if fill.exchange == buy_exchange {
+                        bought += fill.quantity;
+                    }
+                }
+                TradeSide::Sell => {
+                    open -= fill.quantity;
+                    cash += fill.quantity * fill.price;
+                    if fill.exchange == sell_exchange {
+                        sold += fill.quantity;
+                    }
+                }
+            }
+            fees += fill.fee_quote;
+        }
+        let open_value = if open > 0.0 {
+            open * buy_price
+        } else {
+            open * sell_price
+        };
+        let matched_quantity = f64::min(bought, sold);
+        let spread_captured = matched_quantity * (sell_price - buy_price);
+        let realized_pnl = cash + open_value - fees;
+
+        Self {
+            buy_exchange: opportunity.source_exchange.clone(),
+            sell_exchange: opportunity.destination_exchange.clone(),
+            symbol: opportunity.symbol.clone(),
+            matched_quantity,
+            spread_captured,
+            fees,
+            slippage: spread_captured - fees - realized_pnl,
+            transfer_costs: 0.0,
+            realized_pnl,
+            timestamp: fills
+                .iter()
+                .map(|fill| fill.timestamp)
+                .max()
+                .unwrap_or_else(Timestamp::now),
+        }
+    }
+
+    /// Attributes a [crate::cex::execution::TwoLegExecutor] outcome.
+    pub fn from_execution(opportunity: &ArbitrageOpportunity, outcome: &ExecutionOutcome) -> Self {
+        Self::from_fills(opportunity, &outcome.fills(opportunity))
+    }
+
+    /// Adds the cost of moving inventory for this trade (quote currency).
+    pub fn with_transfer_costs(mut self, transfer_costs: f64) -> Self {
+        self.transfer_costs += transfer_costs;
+        self.realized_pnl -= transfer_costs;
+        self
+    }
+
+    /// `buy->sell` venue names.
+    pub fn venue_pair(&self) -> String {
+        format!("{}->{}", self.buy_exchange, self.sell_exchange)
+    }
+}
+
+/// How [ProfitAttribution::summary] groups trades.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AttributionGrouping {
+    VenuePair,
+    Symbol,
+}
+
+/// PnL components summed over a group of trades.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct AttributionSummary {
+    /// Venue pair or symbol; empty for the total
+    pub group: String,
+    pub trades: u64,
+    pub matched_quantity: f64,
+    pub spread_captured: f64,
+    pub fees: f64,
+    pub slippage: f64,
+    pub transfer_costs: f64,
+    pub realized_pnl: f64,
+}
+
+impl AttributionSummary {
+    fn add(&mut self, trade: &TradeAttribution) {
+        self.trades += 1;
+        self.matched_quantity += trade.matched_quantity;
+        self.spread_captured += trade.spread_captured;
+        self.fees += trade.fees;
+        self.slippage += trade.slippage;
+        self.transfer_costs += trade.transfer_costs;
+        self.realized_pnl += trade.realized_pnl;
+    }
+}
+
+/// Attributed trades, in the order they were recorded.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ProfitAttribution {
+    pub trades: Vec<TradeAttribution>,
+}
+
+impl ProfitAttribution {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn record(&mut self, trade: TradeAttribution) {
+        self.trades.push(trade);
+    }
+
+    /// Every trade summed.
+    pub fn total(&self) -> AttributionSummary {
+        let mut total = AttributionSummary::default();
+        for trade in &self.trades {
+            total.add(trade);
+        }
+        total
+    }
+
+    /// One summary per venue pair or symbol, sorted by group.
+    pub fn summary(&self, grouping: AttributionGrouping) -> Vec<AttributionSummary> {
+        let mut groups: BTreeMap<String, AttributionSummary> = BTreeMap::new();
+        for trade in &self.trades {
+            let group = match grouping {
+                AttributionGrouping::VenuePair => trade.venue_pair(),
+                AttributionGrouping::Symbol => trade.symbol.clone(),
+            };
+            groups
+                .entry(group.clone())
+                .or_insert_with(|| AttributionSummary {
+                    group,
+                    ..Default::default()
+                })
+                .add(trade);
+        }
+        groups.into_values().collect()
+    }
+
+    /// One CSV row per trade, with a header.
+    pub fn to_csv(&self) -> String {
+        self.to_csv_with_precision(&OutputPrecision::default())
+    }
+
+    /// [Self::to_csv] with numbers rounded by `precision`.
+    pub fn to_csv_with_precision(&self, precision: &OutputPrecision) -> String {
+        let price = |value| precision.format(value, FieldClass::Price);
+        let mut csv = String::from(
+            "timestamp,buy_exchange,sell_exchange,symbol,matched_quantity,spread_captured,fees,slippage,transfer_costs,realized_pnl\n",
+        );
+        for trade in &self.trades {
+            csv.push_str(&format!(
+                "{},{},{},{},{},{},{},{},{},{}\n",
+                trade.timestamp,
+                csv_field(&trade.buy_exchange),
+                csv_field(&trade.sell_exchange),
+                csv_field(&trade.symbol),
+                precision.format(trade.matched_quantity, FieldClass::Quantity),
+                price(trade.spread_captured),
+                price(trade.fees),
+                price(trade.slippage),
+                price(trade.transfer_costs),
+                price(trade.realized_pnl)
+            ));
+        }
+        csv
+    }
+
+    /// One CSV row per group of [Self::summary], with a header.
+    pub fn summary_csv(&self, grouping: AttributionGrouping) -> String {
+        self.summary_csv_with_precision(grouping, &OutputPrecision::default())
+    }
+
+    /// [Self::summary_csv] with numbers rounded by `precision`.
+    pub fn summary_csv_with_precision(
+        &self,
+        grouping: AttributionGrouping,
+        precision: &OutputPrecision,
+    ) -> String {
+        let price = |value| precision.format(value, FieldClass::Price);
+        let mut csv = String::from(
+            "group,trades,matched_quantity,spread_captured,fees,slippage,transfer_costs,realized_pnl\n",
+        );
+        for summary in self.summary(grouping) {
+            csv.push_str(&format!(
+                "{},{},{},{},{},{},{},{}\n",
+                csv_field(&summary.group),
+                summary.trades,
+                precision.format(summary.matched_quantity, FieldClass::Quantity),
+                price(summary.spread_captured),
+                price(summary.fees),
+                price(summary.slippage),
+                price(summary.transfer_costs),
+                price(summary.realized_pnl)
+            ));
+        }
+        csv
+    }
+
+    /// Trades, total and both groupings as one JSON document.
+    pub fn to_json(&self) -> Result<String, MarketScannerError> {
+        self.to_json_with_precision(&OutputPrecision::default())
+    }
+
+    /// [Self::to_json] with numbers rounded by `precision`.
+    pub fn to_json_with_precision(
+        &self,
+        precision: &OutputPrecision,
+    ) -> Result<String, MarketScannerError> {
+        precision.to_json(&serde_json::json!({
+            "total": self.total(),
+            "by_venue_pair": self.summary(AttributionGrouping::VenuePair),
+            "by_symbol": self.summary(AttributionGrouping::Symbol),
+            "trades": self.trades,
+        }))
+    }
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
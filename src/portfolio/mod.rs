@@ -0,0 +1,198 @@
+//! Lightweight position / exposure tracking for executed arbitrage.
+//!
+//! Feed [Fill]s (execution confirmations or simulated fills) into a [Portfolio] to get
+//! per-exchange asset balances and realized PnL over time without an external ledger.
+//! Realized PnL uses the average-cost method per logical market (see [MarketKey]), so
+//! buying on one venue and selling on another realizes the spread. [PaperTrader] feeds a
+//! portfolio with simulated fills under an [AdverseSelectionModel]; [ProfitAttribution]
+//! breaks realized PnL down per venue pair, symbol and cost component; [RebalancePlanner]
+//! suggests transfers that move balances to where opportunities need them.
+
+use crate::common::{Exchange, MarketKey, Timestamp, canonical_asset};
+use crate::scanner::ArbitrageOpportunity;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+pub mod attribution;
+pub mod rebalance;
+pub mod simulator;
+pub use attribution::{
+    AttributionGrouping, AttributionSummary, ProfitAttribution, TradeAttribution,
+};
+pub use rebalance::{RebalancePlan, RebalancePlanner};
+pub use simulator::{
+    AdverseSelectionModel, PaperTrader, SimulatedExecution, SimulatedOutcome, SimulationStats,
+    SlippageModel,
+};
+
+/// Direction of a fill.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TradeSide {
+    Buy,
+    Sell,
+}
+
+/// An executed (or simulated) trade.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Fill {
+    pub exchange: Exchange,
+    /// Trading pair symbol (e.g. "BTCUSDT")
+    pub symbol: String,
+    pub side: TradeSide,
+    /// Base quantity filled
+    pub quantity: f64,
+    /// Fill price in quote currency
+    pub price: f64,
+    /// Fee paid, in quote currency
+    pub fee_quote: f64,
+    pub timestamp: Timestamp,
+}
+
+/// Net position of one logical market across all venues.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Position {
+    /// Net base quantity (negative = net short)
+    pub quantity: f64,
+    /// Average entry price of the open quantity
+    pub average_price: f64,
+    /// Realized PnL in quote currency, net of fees
+    pub realized_pnl: f64,
+}
+
+/// Cumulative realized PnL after a fill.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct PnlPoint {
+    pub timestamp: Timestamp,
+    pub realized_pnl: f64,
+}
+
+/// Tracks per-exchange asset exposure and realized PnL.
+#[derive(Debug, Clone, Default)]
+pub struct Portfolio {
+    balances: HashMap<(Exchange, String), f64>,
+    positions: HashMap<MarketKey, Position>,
+    realized_pnl: f64,
+    history: Vec<PnlPoint>,
+}
+
+impl Portfolio {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Applies one fill to balances, positions and realized PnL.
+    pub fn apply_fill(&mut self, fill: &Fill) {
+        let market = MarketKey::from_symbol(&fill.symbol);
+        let notional = fill.quantity * fill.price;
+        let (base_delta, quote_delta, signed_qty) = match fill.side {
+            TradeSide::Buy => (fill.quantity, -notional - fill.fee_quote, fill.quantity),
+            TradeSide::Sell => (-fill.quantity, notional - fill.fee_quote, -fill.quantity),
+        };
+        *self
+            .balances
+            .entry((fill.exchange.clone(), market.base.clone()))
+            .or_insert(0.0) += base_delta;
+        *self
+            .balances
+            .entry((fill.exchange.clone(), market.quote.clone()))
+            .or_insert(0.0) += quote_delta;
+
+        let position = self.positions.entry(market).or_default();
+        let realized = apply_to_position(position, signed_qty, fill.price) - fill.fee_quote;
+        position.realized_pnl += realized;
+        self.realized_pnl += realized;
+        self.history.push(PnlPoint {
+            timestamp: fill.timestamp,
+            realized_pnl: self.realized_pnl,
+        });
+    }
+
+    /// Records an executed opportunity as a buy fill on the source leg and a sell fill on
+    /// the destination leg, using leg prices and commission rates of the opportunity.
+    pub fn apply_opportunity(&mut self, opportunity: &ArbitrageOpportunity, quantity: f64) {
+        let (buy_exchange, buy_price) = opportunity.source_leg.exchange_and_price(true);
+        let (sell_exchange, sell_price) = opportunity.destination_leg.exchange_and_price(false);
+        let timestamp = Timestamp::now();
+
+        self.apply_fill(&Fill {
+            exchange: buy_exchange,
+            symbol: opportunity.symbol.clone(),
+            side: TradeSide::Buy,
+            quantity,
+            price: buy_price,
+            fee_quote: buy_price * quantity * opportunity.source_commission_percent / 100.0,
+            timestamp,
+        });
+        self.apply_fill(&Fill {
+            exchange: sell_exchange,
+            symbol: opportunity.symbol.clone(),
+            side: TradeSide::Sell,
+            quantity,
+            price: sell_price,
+            fee_quote: sell_price * quantity * opportunity.destination_commission_percent / 100.0,
+            timestamp,
+        });
+    }
+
+    /// Balance of `asset` on `exchange` (0 if never touched). Aliases such as `XBT`
+    /// resolve to their canonical asset.
+    pub fn balance(&self, exchange: &Exchange, asset: &str) -> f64 {
+        self.balances
+            .get(&(exchange.clone(), canonical_asset(asset)))
+            .copied()
+            .unwrap_or(0.0)
+    }
+
+    /// All non-zero balances as (exchange, asset, amount).
+    pub fn exposures(&self) -> Vec<(Exchange, String, f64)> {
+        self.balances
+            .iter()
+            .filter(|(_, amount)| amount.abs() > f64::EPSILON)
+            .map(|((exchange, asset), amount)| (exchange.clone(), asset.clone(), *amount))
+            .collect()
+    }
+
+    /// Net position of a logical market across venues.
+    pub fn position(&self, market: &MarketKey) -> Option<&Position> {
+        self.positions.get(market)
+    }
+
+    /// Total realized PnL in quote currency, net of fees.
+    pub fn realized_pnl(&self) -> f64 {
+        self.realized_pnl
+    }
+
+    /// Cumulative realized PnL after every fill, in fill order.
+    pub fn pnl_history(&self) -> &[PnlPoint] {
+        &self.history
+    }
+}
+
+/// Applies a signed quantity at `price` to `position` (average-cost method).
+/// Returns the PnL realized by the closing part, before fees.
+fn apply_to_position(position: &mut Position, signed_qty: f64, price: f64) -> f64 {
+    // An empty fill would average a flat position over 0 units
+    if signed_qty == 0.0 {
+        return 0.0;
+    }
+    let current = position.quantity;
+    if current == 0.0 || current.signum() == signed_qty.signum() {
+        let total = current.abs() + signed_qty.abs();
+        position.average_price =
+            (current.abs() * position.average_price + signed_qty.abs() * price) / total;
+        position.quantity += signed_qty;
+        return 0.0;
+    }
+
+    let closed = signed_qty.abs().min(current.abs());
+    let realized = closed * (price - position.average_price) * current.signum();
+    position.quantity += signed_qty;
+    if position.quantity.abs() <= f64::EPSILON {
+        position.quantity = 0.0;
+        position.average_price = 0.0;
+    } else if position.quantity.signum() != current.signum() {
+        // Position flipped: the remainder opens at the fill price
+        position.average_price = price;
+    }
+    realized
+}
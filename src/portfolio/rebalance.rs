@@ -0,0 +1,185 @@
+//! Inventory rebalancing suggestions.
+//!
+//! Opportunities can only be captured where the inventory is: the buying venue needs the
+//! quote asset, the selling venue the base asset. A [RebalancePlanner] reads where recent
+//! opportunities needed each asset from an [OpportunityReport] (weighted by their
+//! cumulative profit), spreads the asset's total balance across venues in the same
+//! proportions and suggests the transfers that get there as [RebalancePlan]s. Call it
+//! periodically, e.g. with every closed [crate::scanner::OpportunityAggregator] window.
+
+use super::Portfolio;
+use crate::common::{CexExchange, Exchange, MarketKey};
+use crate::scanner::{ArbitrageScanner, OpportunityReport, TransferCostModel, TransferNetwork};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+
+/// Suggested transfer of `amount` of `asset` from one venue to another.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RebalancePlan {
+    pub asset: String,
+    pub from_exchange: Exchange,
+    pub to_exchange: Exchange,
+    /// Amount to withdraw (the withdrawal fee is taken from it)
+    pub amount: f64,
+    /// Share (0..=1) of the asset's recent opportunity profit that needed it on
+    /// `to_exchange`
+    pub demand_share: f64,
+    /// Cheapest open network, when planned with a [TransferCostModel]
+    pub network: Option<TransferNetwork>,
+}
+
+/// Suggests transfers that move inventory to where opportunities need it.
+#[derive(Debug, Clone, Default)]
+pub struct RebalancePlanner {
+    min_transfer: HashMap<String, f64>,
+    transfer_costs: Option<TransferCostModel>,
+}
+
+impl RebalancePlanner {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Skips transfers of `asset` smaller than `amount` (in units of the asset).
+    pub fn with_min_transfer(mut self, asset: &str, amount: f64) -> Self {
+        self.min_transfer.insert(asset.to_uppercase(), amount);
+        self
+    }
+
+    /// Only suggests transfers over a network both venues support and that is not
+    /// suspended, and attaches that network. Transfers that would not cover the network
+    /// fee are skipped.
+    pub fn with_transfer_costs(mut self, model: TransferCostModel) -> Self {
+        self.transfer_costs = Some(model);
+        self
+    }
+
+    /// Transfers that bring the tracked balances of `portfolio` closer to the demand of
+    /// `report` (see [RebalancePlanner::plan_balances]).
+    pub fn plan(&self, portfolio: &Portfolio, report: &OpportunityReport) -> Vec<RebalancePlan> {
+        self.plan_balances(&portfolio.exposures(), report)
+    }
+
+    /// Transfers that bring `balances` (venue, asset, amount) closer to the demand of
+    /// `report`, largest first per asset. Assets without demand in the report or without a
+    /// positive total balance are left alone.
+    pub fn plan_balances(
+        &self,
+        balances: &[(Exchange, String, f64)],
+        report: &OpportunityReport,
+    ) -> Vec<RebalancePlan> {
+        let mut venues: Vec<Exchange> = CexExchange::ALL
+            .iter()
+            .cloned()
+            .map(Exchange::Cex)
+            .collect();
+        for (exchange, _, _) in balances {
+            if !venues.contains(exchange) {
+                venues.push(exchange.clone());
+            }
+        }
+        let by_name: HashMap<String, Exchange> = venues
+            .iter()
+            .map(|exchange| (ArbitrageScanner::exchange_name(exchange), exchange.clone()))
+            .collect();
+
+        // Demand per asset and venue: quote on the buying venue, base on the selling one
+        let mut demand: BTreeMap<String, Vec<(Exchange, f64)>> = BTreeMap::new();
+        for entry in &report.entries {
+            let profit = entry.cumulative_profit_quote.max(0.0);
+            let market = MarketKey::from_symbol(&entry.symbol);
+            for (asset, venue) in [
+                (market.quote, &entry.source_exchange),
+                (market.base, &entry.destination_exchange),
+            ] {
+                let Some(exchange) = by_name.get(venue) else {
+                    continue;
+                };
+                let venues = demand.entry(asset).or_default();
+                match venues.iter_mut().find(|(e, _)| e == exchange) {
+                    Some((_, weight)) => *weight += profit,
+                    None => venues.push((exchange.clone(), profit)),
+                }
+            }
+        }
+
+        let mut plans = Vec::new();
+        for (asset, weights) in demand {
+            plans.extend(self.plan_asset(balances, &asset, &weights));
+        }
+        plans
+    }
+
+    fn plan_asset(
+        &self,
+        balances: &[(Exchange, String, f64)],
+        asset: &str,
+        weights: &[(Exchange, f64)],
+    ) -> Vec<RebalancePlan> {
+        let total_weight: f64 = weights.iter().map(|(_, w)| w).sum();
+        let mut holders: Vec<(Exchange, f64)> = balances
+            .iter()
+            .filter(|(_, a, amount)| a.eq_ignore_ascii_case(asset) && *amount > 0.0)
+            .map(|(exchange, _, amount)| (exchange.clone(), *amount))
+            .collect();
+        let total: f64 = holders.iter().map(|(_, amount)| amount).sum();
+        if total_weight <= 0.0 || total <= 0.0 {
+            return Vec::new();
+        }
+        let share = |exchange: &Exchange| {
+            weights
+                .iter()
+                .find(|(e, _)| e == exchange)
+                .map_or(0.0, |(_, w)| w / total_weight)
+        };
+
+        // Surplus (positive) or deficit (negative) against the target per venue
+        for (exchange, _) in weights {
+            if !holders.iter().any(|(e, _)| e == exchange) {
+                holders.push((exchange.clone(), 0.0));
+            }
+        }
+        let mut surpluses: Vec<(Exchange, f64)> = Vec::new();
+        let mut deficits: Vec<(Exchange, f64)> = Vec::new();
+        for (exchange, balance) in holders {
+            let excess = balance - total * share(&exchange);
+            if excess > 0.0 {
+                surpluses.push((exchange, excess));
+            } else if excess < 0.0 {
+                deficits.push((exchange, -excess));
+            }
+        }
+        surpluses.sort_by(|a, b| b.1.total_cmp(&a.1));
+        deficits.sort_by(|a, b| b.1.total_cmp(&a.1));
+
+        let min_transfer = self.min_transfer.get(asset).copied().unwrap_or(0.0);
+        let mut plans = Vec::new();
+        for (to, mut needed) in deficits {
+            for (from, available) in surpluses.iter_mut() {
+                let amount = needed.min(*available);
+                if amount <= 0.0 || amount < min_transfer {
+                    continue;
+                }
+                let network = match &self.transfer_costs {
+                    Some(model) => match model.cheapest_network(asset, from, &to) {
+                        Some(network) if network.fee < amount => Some(network.clone()),
+                        _ => continue,
+                    },
+                    None => None,
+                };
+                *available -= amount;
+                needed -= amount;
+                plans.push(RebalancePlan {
+                    asset: asset.to_string(),
+                    from_exchange: from.clone(),
+                    to_exchange: to.clone(),
+                    amount,
+                    demand_share: share(&to),
+                    network,
+                });
+            }
+        }
+        plans.sort_by(|a, b| b.amount.total_cmp(&a.amount));
+        plans
+    }
+}
@@ -0,0 +1,292 @@
+//! Paper trading of opportunities with an adverse-selection model.
+//!
+//! Quotes are rarely still there when an order arrives. [PaperTrader] fills opportunities
+//! into a [Portfolio] through an [AdverseSelectionModel]: after the configured latency each
+//! leg's quote is gone with a probability that grows with the latency (see
+//! [AdverseSelectionModel::quote_half_life_ms]), and filled legs pay a slippage drawn from
+//! a [SlippageModel]. A leg that misses while the other fills is unwound at a cost, so
+//! backtests do not assume every quoted spread is captured. Draws come from a seeded
+//! [SeededRng], so a run is reproducible.
+
+use super::{Fill, Portfolio, ProfitAttribution, TradeAttribution, TradeSide};
+use crate::common::{Exchange, SeededRng, Timestamp};
+use crate::scanner::ArbitrageOpportunity;
+use serde::{Deserialize, Serialize};
+
+/// Slippage of a filled leg in basis points; positive values are against the trader.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub enum SlippageModel {
+    #[default]
+    None,
+    Fixed {
+        bps: f64,
+    },
+    Uniform {
+        min_bps: f64,
+        max_bps: f64,
+    },
+    Normal {
+        mean_bps: f64,
+        std_dev_bps: f64,
+    },
+}
+
+impl SlippageModel {
+    fn sample(&self, rng: &mut SeededRng) -> f64 {
+        match *self {
+            SlippageModel::None => 0.0,
+            SlippageModel::Fixed { bps } => bps,
+            SlippageModel::Uniform { min_bps, max_bps } => {
+                min_bps + (max_bps - min_bps) * rng.next_f64()
+            }
+            SlippageModel::Normal {
+                mean_bps,
+                std_dev_bps,
+            } => {
+                // Box-Muller
+                let u1 = rng.next_f64().max(f64::MIN_POSITIVE);
+                let u2 = rng.next_f64();
+                let z = (-2.0 * u1.ln()).sqrt() * (2.0 * std::f64::consts::PI * u2).cos();
+                mean_bps + std_dev_bps * z
+            }
+        }
+    }
+}
+
+/// How much of a quoted opportunity survives until the orders arrive.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AdverseSelectionModel {
+    /// Time from the quote to the orders reaching the venues (milliseconds)
+    pub latency_ms: u64,
+    /// Latency after which half of the quotes are gone; the probability that a leg's quote
+    /// is gone is `1 − 0.5^(latency / half_life)`. 0 means quotes never vanish
+    pub quote_half_life_ms: f64,
+    pub slippage: SlippageModel,
+    /// Cost of unwinding a leg whose other leg missed, in basis points of its notional
+    pub unwind_cost_bps: f64,
+    /// Seed of the random draws
+    pub seed: u64,
+}
+
+impl Default for AdverseSelectionModel {
+    /// Perfect fills: no latency, quotes never vanish, no slippage.
+    fn default() -> Self {
+        Self {
+            latency_ms: 0,
+            quote_half_life_ms: 0.0,
+            slippage: SlippageModel::None,
+            unwind_cost_bps: 0.0,
+            seed: 0,
+        }
+    }
+}
+
+impl AdverseSelectionModel {
+    pub fn with_latency_ms(mut self, latency_ms: u64) -> Self {
+        self.latency_ms = latency_ms;
+        self
+    }
+
+    pub fn with_quote_half_life_ms(mut self, quote_half_life_ms: f64) -> Self {
+        self.quote_half_life_ms = quote_half_life_ms;
+        self
+    }
+
+    pub fn with_slippage(mut self, slippage: SlippageModel) -> Self {
+        self.slippage = slippage;
+        self
+    }
+
+    pub fn with_unwind_cost_bps(mut self, unwind_cost_bps: f64) -> Self {
+        self.unwind_cost_bps = unwind_cost_bps;
+        self
+    }
+
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Probability that a quote is gone after the model's latency.
+    pub fn vanish_probability(&self) -> f64 {
+        if self.quote_half_life_ms <= 0.0 {
+            return 0.0;
+        }
+        1.0 - 0.5f64.powf(self.latency_ms as f64 / self.quote_half_life_ms)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SimulatedOutcome {
+    /// Both legs filled
+    Filled,
+    /// Both quotes were gone; nothing traded
+    Missed,
+    /// One leg filled and was unwound
+    Legged,
+}
+
+/// Result of one paper-traded opportunity.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SimulatedExecution {
+    pub outcome: SimulatedOutcome,
+    /// Fills applied to the portfolio: legs, then the unwind
+    pub fills: Vec<Fill>,
+    /// Profit at the quoted prices and fees
+    pub expected_profit: f64,
+    /// Quote currency gained or lost by the fills, net of fees
+    pub realized_profit: f64,
+}
+
+/// Totals over every simulated execution.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SimulationStats {
+    pub attempted: u64,
+    pub filled: u64,
+    pub missed: u64,
+    pub legged: u64,
+    pub expected_profit: f64,
+    pub realized_profit: f64,
+}
+
+impl SimulationStats {
+    /// Realized share of the quoted profit (0 without expected profit).
+    pub fn capture_ratio(&self) -> f64 {
+        if self.expected_profit > 0.0 {
+            self.realized_profit / self.expected_profit
+        } else {
+            0.0
+        }
+    }
+}
+
+/// Paper-trading engine: opportunities in, simulated fills into a [Portfolio].
+#[derive(Debug, Clone)]
+pub struct PaperTrader {
+    model: AdverseSelectionModel,
+    rng: SeededRng,
+    portfolio: Portfolio,
+    stats: SimulationStats,
+    attribution: ProfitAttribution,
+}
+
+impl PaperTrader {
+    pub fn new(model: AdverseSelectionModel) -> Self {
+        Self {
+            rng: SeededRng::new(model.seed),
+            model,
+            portfolio: Portfolio::new(),
+            stats: SimulationStats::default(),
+            attribution: ProfitAttribution::new(),
+        }
+    }
+
+    /// Draws from `rng` instead of a generator seeded with [AdverseSelectionModel::seed],
+    /// e.g. to share one generator across traders.
+    pub fn with_rng(mut self, rng: SeededRng) -> Self {
+        self.rng = rng;
+        self
+    }
+
+    pub fn model(&self) -> &AdverseSelectionModel {
+        &self.model
+    }
+
+    pub fn portfolio(&self) -> &Portfolio {
+        &self.portfolio
+    }
+
+    pub fn stats(&self) -> &SimulationStats {
+        &self.stats
+    }
+
+    /// PnL attribution of every simulated execution.
+    pub fn attribution(&self) -> &ProfitAttribution {
+        &self.attribution
+    }
+
+    /// Paper-trades `quantity` of `opportunity` at the opportunity's leg prices and
+    /// commission rates, through the adverse-selection model.
+    pub fn execute(
+        &mut self,
+        opportunity: &ArbitrageOpportunity,
+        quantity: f64,
+    ) -> SimulatedExecution {
+        let (buy_exchange, buy_price) = opportunity.source_leg.exchange_and_price(true);
+        let (sell_exchange, sell_price) = opportunity.destination_leg.exchange_and_price(false);
+        let buy_fee_rate = opportunity.source_commission_percent / 100.0;
+        let sell_fee_rate = opportunity.destination_commission_percent / 100.0;
+        let expected_profit =
+            quantity * (sell_price * (1.0 - sell_fee_rate) - buy_price * (1.0 + buy_fee_rate));
+
+        let vanish = self.model.vanish_probability();
+        let buy_filled = self.rng.next_f64() >= vanish;
+        let sell_filled = self.rng.next_f64() >= vanish;
+        let timestamp = Timestamp::now();
+        let fill = |exchange: &Exchange, side, price: f64, fee_rate: f64| Fill {
+            exchange: exchange.clone(),
+            symbol: opportunity.symbol.clone(),
+            side,
+            quantity,
+            price,
+            fee_quote: price * quantity * fee_rate,
+            timestamp,
+        };
+
+        let mut fills = Vec::new();
+        if buy_filled {
+            let slippage = self.model.slippage.sample(&mut self.rng);
+            let price = buy_price * (1.0 + slippage / 10_000.0);
+            fills.push(fill(&buy_exchange, TradeSide::Buy, price, buy_fee_rate));
+        }
+        if sell_filled {
+            let slippage = self.model.slippage.sample(&mut self.rng);
+            let price = sell_price * (1.0 - slippage / 10_000.0);
+            fills.push(fill(&sell_exchange, TradeSide::Sell, price, sell_fee_rate));
+        }
+        let outcome = match (buy_filled, sell_filled) {
+            (true, true) => SimulatedOutcome::Filled,
+            (false, false) => SimulatedOutcome::Missed,
+            _ => {
+                // Reverse the lone leg on its venue, paying fees and the unwind cost
+                let leg = &fills[0];
+                let cost = self.model.unwind_cost_bps / 10_000.0;
+                let (side, price, fee_rate) = match leg.side {
+                    TradeSide::Buy => (TradeSide::Sell, leg.price * (1.0 - cost), buy_fee_rate),
+                    TradeSide::Sell => (TradeSide::Buy, leg.price * (1.0 + cost), sell_fee_rate),
+                };
+                let unwind = fill(&leg.exchange.clone(), side, price, fee_rate);
+                fills.push(unwind);
+                SimulatedOutcome::Legged
+            }
+        };
+
+        let realized_profit = fills
+            .iter()
+            .map(|fill| match fill.side {
+                TradeSide::Buy => -fill.quantity * fill.price - fill.fee_quote,
+                TradeSide::Sell => fill.quantity * fill.price - fill.fee_quote,
+            })
+            .sum();
+        for fill in &fills {
+            self.portfolio.apply_fill(fill);
+        }
+
+        self.stats.attempted += 1;
+        match outcome {
+            SimulatedOutcome::Filled => self.stats.filled += 1,
+            SimulatedOutcome::Missed => self.stats.missed += 1,
+            SimulatedOutcome::Legged => self.stats.legged += 1,
+        }
+        self.stats.expected_profit += expected_profit;
+        self.stats.realized_profit += realized_profit;
+        self.attribution
+            .record(TradeAttribution::from_fills(opportunity, &fills));
+        SimulatedExecution {
+            outcome,
+            fills,
+            expected_profit,
+            realized_profit,
+        }
+    }
+}
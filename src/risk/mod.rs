@@ -0,0 +1,423 @@
+//! Pre-trade risk limits for execution.
+//!
+//! A [RiskManager] holds [RiskLimits] (maximum notional per opportunity, maximum open
+//! exposure per asset and per venue, orders per minute) and the exposure opened so far.
+//! The execution layer reserves every order or opportunity with it before sending
+//! anything; a reservation that would break a limit fails with
+//! [MarketScannerError::RiskLimitExceeded] and nothing is recorded. Limits can be replaced
+//! and exposure queried at runtime; clones share the same state.
+//!
+//! Notional and exposure are in quote currency and not converted between quotes, so
+//! limits assume venues quoted in the same (or equivalent) currency.
+
+use crate::cex::execution::{CexOrder, OrderUpdate, check_quantity};
+use crate::common::{CexExchange, Exchange, MarketKey, MarketScannerError, canonical_asset};
+use crate::scanner::ArbitrageOpportunity;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use std::fmt;
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, Instant};
+
+const ORDER_RATE_WINDOW: Duration = Duration::from_secs(60);
+
+/// Limits checked before orders are sent; `None` disables a limit.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RiskLimits {
+    /// Buy-leg notional of one opportunity
+    pub max_notional_per_opportunity: Option<f64>,
+    /// Open exposure of one base asset across venues
+    pub max_exposure_per_asset: Option<f64>,
+    /// Open exposure of one venue across assets
+    pub max_exposure_per_venue: Option<f64>,
+    /// Per-asset limits replacing `max_exposure_per_asset` (keyed by canonical asset)
+    pub asset_exposure_limits: HashMap<String, f64>,
+    /// Per-venue limits replacing `max_exposure_per_venue`
+    pub venue_exposure_limits: HashMap<Exchange, f64>,
+    /// Orders reserved within any 60 second window, across venues
+    pub max_orders_per_minute: Option<u32>,
+}
+
+impl RiskLimits {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_max_notional_per_opportunity(mut self, notional: f64) -> Self {
+        self.max_notional_per_opportunity = Some(notional);
+        self
+    }
+
+    pub fn with_max_exposure_per_asset(mut self, exposure: f64) -> Self {
+        self.max_exposure_per_asset = Some(exposure);
+        self
+    }
+
+    pub fn with_max_exposure_per_venue(mut self, exposure: f64) -> Self {
+        self.max_exposure_per_venue = Some(exposure);
+        self
+    }
+
+    /// Exposure limit of `asset` (e.g. "BTC"; aliases such as "XBT" resolve to it).
+    pub fn with_asset_exposure_limit(mut self, asset: &str, exposure: f64) -> Self {
+        self.asset_exposure_limits
+            .insert(canonical_asset(asset), exposure);
+        self
+    }
+
+    pub fn with_venue_exposure_limit(mut self, exchange: Exchange, exposure: f64) -> Self {
+        self.venue_exposure_limits.insert(exchange, exposure);
+        self
+    }
+
+    pub fn with_max_orders_per_minute(mut self, orders: u32) -> Self {
+        self.max_orders_per_minute = Some(orders);
+        self
+    }
+
+    /// Effective exposure limit of `asset` (aliases resolve to the canonical asset).
+    pub fn asset_limit(&self, asset: &str) -> Option<f64> {
+        self.asset_exposure_limits
+            .get(&canonical_asset(asset))
+            .copied()
+            .or(self.max_exposure_per_asset)
+    }
+
+    /// Effective exposure limit of `exchange`.
+    pub fn venue_limit(&self, exchange: &Exchange) -> Option<f64> {
+        self.venue_exposure_limits
+            .get(exchange)
+            .copied()
+            .or(self.max_exposure_per_venue)
+    }
+}
+
+/// Limit a reservation would have broken.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum RiskViolation {
+    OpportunityNotional {
+        notional: f64,
+        limit: f64,
+    },
+    /// `exposure` is the total the reservation would have reached
+    AssetExposure {
+        asset: String,
+        exposure: f64,
+        limit: f64,
+    },
+    VenueExposure {
+        exchange: Exchange,
+        exposure: f64,
+        limit: f64,
+    },
+    /// `orders` is the count within the window including the rejected ones
+    OrderRate {
+        orders: u32,
+        limit: u32,
+    },
+}
+
+impl fmt::Display for RiskViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RiskViolation::OpportunityNotional { notional, limit } => {
+                write!(f, "opportunity notional {} above limit {}", notional, limit)
+            }
+            RiskViolation::AssetExposure {
+                asset,
+                exposure,
+                limit,
+            } => write!(f, "{} exposure {} above limit {}", asset, exposure, limit),
+            RiskViolation::VenueExposure {
+                exchange,
+                exposure,
+                limit,
+            } => write!(
+                f,
+                "{:?} exposure {} above limit {}",
+                exchange, exposure, limit
+            ),
+            RiskViolation::OrderRate { orders, limit } => {
+                write!(f, "{} orders per minute above limit {}", orders, limit)
+            }
+        }
+    }
+}
+
+impl std::error::Error for RiskViolation {}
+
+/// Open exposure reserved on one venue for one asset.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Exposure {
+    pub exchange: Exchange,
+    pub asset: String,
+    /// Quote notional
+    pub notional: f64,
+}
+
+#[derive(Debug, Default)]
+struct RiskState {
+    limits: RiskLimits,
+    exposure: HashMap<(Exchange, String), f64>,
+    orders: VecDeque<Instant>,
+}
+
+impl RiskState {
+    fn prune_orders(&mut self, now: Instant) {
+        while self
+            .orders
+            .front()
+            .is_some_and(|sent| now.duration_since(*sent) >= ORDER_RATE_WINDOW)
+        {
+            self.orders.pop_front();
+        }
+    }
+
+    /// Checks `legs` (venue, asset, notional) and `order_count` new orders, then records
+    /// them; nothing is recorded on a violation.
+    fn reserve(&mut self, legs: &[Exposure], order_count: u32) -> Result<(), MarketScannerError> {
+        let now = Instant::now();
+        self.prune_orders(now);
+        if let Some(limit) = self.limits.max_orders_per_minute {
+            let orders = self.orders.len() as u32 + order_count;
+            if orders > limit {
+                return Err(RiskViolation::OrderRate { orders, limit }.into());
+            }
+        }
+
+        for leg in legs {
+            if let Some(limit) = self.limits.asset_limit(&leg.asset) {
+                let exposure = self.asset_exposure(&leg.asset)
+                    + legs
+                        .iter()
+                        .filter(|other| other.asset == leg.asset)
+                        .map(|other| other.notional)
+                        .sum::<f64>();
+                if exposure > limit {
+                    return Err(RiskViolation::AssetExposure {
+                        asset: leg.asset.clone(),
+                        exposure,
+                        limit,
+                    }
+                    .into());
+                }
+            }
+            if let Some(limit) = self.limits.venue_limit(&leg.exchange) {
+                let exposure = self.venue_exposure(&leg.exchange)
+                    + legs
+                        .iter()
+                        .filter(|other| other.exchange == leg.exchange)
+                        .map(|other| other.notional)
+                        .sum::<f64>();
+                if exposure > limit {
+                    return Err(RiskViolation::VenueExposure {
+                        exchange: leg.exchange.clone(),
+                        exposure,
+                        limit,
+                    }
+                    .into());
+                }
+            }
+        }
+
+        for leg in legs {
+            *self
+                .exposure
+                .entry((leg.exchange.clone(), leg.asset.clone()))
+                .or_insert(0.0) += leg.notional;
+        }
+        self.orders.extend((0..order_count).map(|_| now));
+        Ok(())
+    }
+
+    fn asset_exposure(&self, asset: &str) -> f64 {
+        let asset = canonical_asset(asset);
+        self.exposure
+            .iter()
+            .filter(|((_, a), _)| *a == asset)
+            .map(|(_, notional)| notional)
+            .sum()
+    }
+
+    fn venue_exposure(&self, exchange: &Exchange) -> f64 {
+        self.exposure
+            .iter()
+            .filter(|((e, _), _)| e == exchange)
+            .map(|(_, notional)| notional)
+            .sum()
+    }
+}
+
+/// Shared risk limits and open exposure.
+#[derive(Debug, Clone, Default)]
+pub struct RiskManager {
+    state: Arc<RwLock<RiskState>>,
+}
+
+impl RiskManager {
+    pub fn new(limits: RiskLimits) -> Self {
+        Self {
+            state: Arc::new(RwLock::new(RiskState {
+                limits,
+                ..RiskState::default()
+            })),
+        }
+    }
+
+    pub fn limits(&self) -> RiskLimits {
+        self.state
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .limits
+            .clone()
+    }
+
+    /// Replaces the limits; open exposure and the order window are kept.
+    pub fn set_limits(&self, limits: RiskLimits) {
+        self.state.write().unwrap_or_else(|e| e.into_inner()).limits = limits;
+    }
+
+    /// Reserves one order on `exchange`, valued at its limit price or (market orders)
+    /// `reference_price`. Returns the reserved [Exposure] to [RiskManager::release] later.
+    pub fn reserve_order(
+        &self,
+        exchange: &CexExchange,
+        order: &CexOrder,
+        reference_price: Option<f64>,
+    ) -> Result<Exposure, MarketScannerError> {
+        check_quantity(order.quantity)?;
+        let price = order.price.or(reference_price).ok_or_else(|| {
+            MarketScannerError::InvalidConfig(
+                "Market orders need a reference price for risk checks".to_string(),
+            )
+        })?;
+        let exposure = Exposure {
+            exchange: Exchange::Cex(exchange.clone()),
+            asset: MarketKey::from_symbol(&order.symbol).base,
+            notional: order.quantity * price,
+        };
+        self.state
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .reserve(std::slice::from_ref(&exposure), 1)?;
+        Ok(exposure)
+    }
+
+    /// Reserves both legs of `opportunity` for `quantity` (capped at its executable
+    /// quantity) as two orders: the buy at the source ask, the sell at the destination bid.
+    pub fn reserve_opportunity(
+        &self,
+        opportunity: &ArbitrageOpportunity,
+        quantity: f64,
+    ) -> Result<[Exposure; 2], MarketScannerError> {
+        check_quantity(quantity)?;
+        let quantity = quantity.min(opportunity.executable_quantity);
+        check_quantity(quantity)?;
+        let asset = MarketKey::from_symbol(&opportunity.symbol).base;
+        let (source, buy_price) = opportunity.source_leg.exchange_and_price(true);
+        let (destination, sell_price) = opportunity.destination_leg.exchange_and_price(false);
+        let legs = [
+            Exposure {
+                exchange: source,
+                asset: asset.clone(),
+                notional: quantity * buy_price,
+            },
+            Exposure {
+                exchange: destination,
+                asset,
+                notional: quantity * sell_price,
+            },
+        ];
+
+        let mut state = self.state.write().unwrap_or_else(|e| e.into_inner());
+        if let Some(limit) = state.limits.max_notional_per_opportunity
+            && legs[0].notional > limit
+        {
+            return Err(RiskViolation::OpportunityNotional {
+                notional: legs[0].notional,
+                limit,
+            }
+            .into());
+        }
+        state.reserve(&legs, 2)?;
+        Ok(legs)
+    }
+
+    /// Releases previously reserved exposure (position closed, order cancelled).
+    pub fn release(&self, exposure: &Exposure) {
+        let mut state = self.state.write().unwrap_or_else(|e| e.into_inner());
+        let key = (exposure.exchange.clone(), canonical_asset(&exposure.asset));
+        if let Some(open) = state.exposure.get_mut(&key) {
+            *open -= exposure.notional;
+            if *open <= f64::EPSILON {
+                state.exposure.remove(&key);
+            }
+        }
+    }
+
+    /// Releases the unfilled part of an order once `update` reports it final (cancelled,
+    /// rejected, expired, or filled below its quantity). Filled quantity stays open.
+    pub fn apply_order_update(&self, update: &OrderUpdate) {
+        let unfilled = update.quantity - update.filled_quantity;
+        if !update.status.is_final() || unfilled <= 0.0 {
+            return;
+        }
+        let Some(price) = update.price.or(update.average_fill_price) else {
+            return;
+        };
+        self.release(&Exposure {
+            exchange: Exchange::Cex(update.exchange.clone()),
+            asset: MarketKey::from_symbol(&update.symbol).base,
+            notional: unfilled * price,
+        });
+    }
+
+    /// Open exposure of `asset` on `exchange` (aliases resolve to the canonical asset).
+    pub fn exposure(&self, exchange: &Exchange, asset: &str) -> f64 {
+        self.state
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .exposure
+            .get(&(exchange.clone(), canonical_asset(asset)))
+            .copied()
+            .unwrap_or(0.0)
+    }
+
+    /// Open exposure of `asset` across venues (aliases resolve to the canonical asset).
+    pub fn asset_exposure(&self, asset: &str) -> f64 {
+        self.state
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .asset_exposure(asset)
+    }
+
+    /// Open exposure of `exchange` across assets.
+    pub fn venue_exposure(&self, exchange: &Exchange) -> f64 {
+        self.state
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .venue_exposure(exchange)
+    }
+
+    /// Every non-zero open exposure.
+    pub fn exposures(&self) -> Vec<Exposure> {
+        self.state
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .exposure
+            .iter()
+            .map(|((exchange, asset), notional)| Exposure {
+                exchange: exchange.clone(),
+                asset: asset.clone(),
+                notional: *notional,
+            })
+            .collect()
+    }
+
+    /// Orders reserved within the last 60 seconds.
+    pub fn orders_last_minute(&self) -> u32 {
+        let mut state = self.state.write().unwrap_or_else(|e| e.into_inner());
+        state.prune_orders(Instant::now());
+        state.orders.len() as u32
+    }
+}
@@ -0,0 +1,243 @@
+//! Execution acknowledgments from external execution engines.
+//!
+//! Opportunities tracked by an [crate::scanner::OpportunityLifecycle] carry an
+//! [ArbitrageOpportunity::id](crate::scanner::ArbitrageOpportunity::id) per episode. An
+//! engine that acts on one reports back with an [ExecutionAck] (the id and an
+//! [AckOutcome]); [crate::scanner::OpportunityLifecycle::ack] links it to the
+//! detected episode and keeps [ExecutionStats], so detection and execution numbers line up.
+//! [serve_ack_webhook] accepts acks as JSON `POST`s for engines in other processes.
+
+use crate::common::{MarketScannerError, Timestamp};
+use crate::scanner::ArbitrageOpportunity;
+use serde::{Deserialize, Serialize};
+use std::net::SocketAddr;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::mpsc;
+
+/// Largest webhook request (headers and body) accepted.
+const MAX_WEBHOOK_REQUEST_BYTES: usize = 64 * 1024;
+
+/// What the execution engine did with an opportunity.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum AckOutcome {
+    /// Both legs filled
+    Filled { realized_profit_quote: f64 },
+    /// Legs filled for part of the quantity
+    PartiallyFilled {
+        filled_quantity: f64,
+        realized_profit_quote: f64,
+    },
+    /// The engine decided not to trade (risk, balance, stale quote)
+    Rejected { reason: String },
+    /// Orders were sent but the opportunity was gone
+    Missed,
+}
+
+impl AckOutcome {
+    /// Realized profit in quote currency (0 unless something filled).
+    pub fn realized_profit_quote(&self) -> f64 {
+        match self {
+            AckOutcome::Filled {
+                realized_profit_quote,
+            }
+            | AckOutcome::PartiallyFilled {
+                realized_profit_quote,
+                ..
+            } => *realized_profit_quote,
+            AckOutcome::Rejected { .. } | AckOutcome::Missed => 0.0,
+        }
+    }
+}
+
+/// Acknowledgment of an opportunity by an execution engine, e.g.
+/// `{"id": "Binance->OKX:BTCUSDT@1700000000000", "outcome": {"status": "filled", "realized_profit_quote": 12.5}}`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExecutionAck {
+    /// [ArbitrageOpportunity::id] of the acknowledged opportunity
+    pub id: String,
+    pub outcome: AckOutcome,
+    /// Time of the acknowledgment (time of receipt when omitted)
+    #[serde(default = "Timestamp::now")]
+    pub at: Timestamp,
+}
+
+impl ExecutionAck {
+    pub fn new(id: impl Into<String>, outcome: AckOutcome) -> Self {
+        Self {
+            id: id.into(),
+            outcome,
+            at: Timestamp::now(),
+        }
+    }
+}
+
+/// A detected episode joined with its acknowledgment.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExecutionReport {
+    pub id: String,
+    /// Last observation of the opportunity before the acknowledgment
+    pub opportunity: ArbitrageOpportunity,
+    pub opened_at: Timestamp,
+    /// `None` while the opportunity is still open
+    pub closed_at: Option<Timestamp>,
+    pub outcome: AckOutcome,
+    pub acked_at: Timestamp,
+    /// Time from opening to the acknowledgment
+    pub ack_delay_ms: u64,
+}
+
+/// Detection and execution counters of an [crate::scanner::OpportunityLifecycle].
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ExecutionStats {
+    /// Opened opportunities
+    pub detected: u64,
+    pub acknowledged: u64,
+    pub filled: u64,
+    pub partially_filled: u64,
+    pub rejected: u64,
+    pub missed: u64,
+    /// Total profit of opened opportunities at the time they opened (quote currency)
+    pub detected_profit_quote: f64,
+    /// Sum of realized profits reported in acknowledgments (quote currency)
+    pub realized_profit_quote: f64,
+    /// Sum of [ExecutionReport::ack_delay_ms]
+    pub total_ack_delay_ms: u64,
+}
+
+impl ExecutionStats {
+    /// Share of detected opportunities that were acknowledged.
+    pub fn ack_rate(&self) -> f64 {
+        ratio(self.acknowledged, self.detected)
+    }
+
+    /// Share of acknowledged opportunities that (at least partially) filled.
+    pub fn fill_rate(&self) -> f64 {
+        ratio(self.filled + self.partially_filled, self.acknowledged)
+    }
+
+    /// Mean time from opening to acknowledgment.
+    pub fn avg_ack_delay_ms(&self) -> f64 {
+        ratio(self.total_ack_delay_ms, self.acknowledged)
+    }
+
+    pub(crate) fn record_ack(&mut self, report: &ExecutionReport) {
+        self.acknowledged += 1;
+        match &report.outcome {
+            AckOutcome::Filled { .. } => self.filled += 1,
+            AckOutcome::PartiallyFilled { .. } => self.partially_filled += 1,
+            AckOutcome::Rejected { .. } => self.rejected += 1,
+            AckOutcome::Missed => self.missed += 1,
+        }
+        self.realized_profit_quote += report.outcome.realized_profit_quote();
+        self.total_ack_delay_ms += report.ack_delay_ms;
+    }
+}
+
+fn ratio(part: u64, total: u64) -> f64 {
+    if total == 0 {
+        0.0
+    } else {
+        part as f64 / total as f64
+    }
+}
+
+/// Listens on `addr` (e.g. "0.0.0.0:8787") for acknowledgments posted as JSON
+/// [ExecutionAck]s to any path. Valid acks are answered with `202 Accepted` and forwarded
+/// to the returned receiver; the listener stops once the receiver is dropped. Returns the
+/// bound address (useful with port 0).
+pub async fn serve_ack_webhook(
+    addr: &str,
+) -> Result<(SocketAddr, mpsc::Receiver<ExecutionAck>), MarketScannerError> {
+    let listener = TcpListener::bind(addr).await.map_err(|e| {
+        MarketScannerError::InvalidConfig(format!("Cannot bind ack webhook to {}: {}", addr, e))
+    })?;
+    let local_addr = listener.local_addr().map_err(|e| {
+        MarketScannerError::InvalidConfig(format!("Ack webhook has no local address: {}", e))
+    })?;
+    let (tx, rx) = mpsc::channel(64);
+    tokio::spawn(async move {
+        loop {
+            let stream = tokio::select! {
+                accepted = listener.accept() => match accepted {
+                    Ok((stream, _)) => stream,
+                    Err(_) => continue,
+                },
+                _ = tx.closed() => return,
+            };
+            let tx = tx.clone();
+            tokio::spawn(async move {
+                let _ = handle_webhook_request(stream, tx).await;
+            });
+        }
+    });
+    Ok((local_addr, rx))
+}
+
+async fn handle_webhook_request(
+    mut stream: TcpStream,
+    tx: mpsc::Sender<ExecutionAck>,
+) -> std::io::Result<()> {
+    let (status, message) = match read_request(&mut stream).await? {
+        Err(status) => (status, String::new()),
+        Ok(body) => match serde_json::from_slice::<ExecutionAck>(&body) {
+            Ok(ack) => match tx.send(ack).await {
+                Ok(()) => ("202 Accepted", String::new()),
+                Err(_) => ("503 Service Unavailable", String::new()),
+            },
+            Err(e) => ("400 Bad Request", e.to_string()),
+        },
+    };
+    let response = format!(
+        "HTTP/1.1 {}\r\nContent-Type: text/plain\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        message.len(),
+        message
+    );
+    stream.write_all(response.as_bytes()).await?;
+    stream.shutdown().await
+}
+
+/// Body of a `POST` request, or the status to answer with.
+async fn read_request(stream: &mut TcpStream) -> std::io::Result<Result<Vec<u8>, &'static str>> {
+    let mut buf = Vec::new();
+    let mut chunk = [0u8; 4096];
+    let header_end = loop {
+        if let Some(pos) = buf.windows(4).position(|w| w == b"\r\n\r\n") {
+            break pos + 4;
+        }
+        if buf.len() > MAX_WEBHOOK_REQUEST_BYTES {
+            return Ok(Err("413 Payload Too Large"));
+        }
+        let n = stream.read(&mut chunk).await?;
+        if n == 0 {
+            return Ok(Err("400 Bad Request"));
+        }
+        buf.extend_from_slice(&chunk[..n]);
+    };
+
+    let head = String::from_utf8_lossy(&buf[..header_end]).to_string();
+    if !head.starts_with("POST ") {
+        return Ok(Err("405 Method Not Allowed"));
+    }
+    let content_length = head
+        .lines()
+        .filter_map(|line| line.split_once(':'))
+        .find(|(name, _)| name.trim().eq_ignore_ascii_case("content-length"))
+        .and_then(|(_, value)| value.trim().parse::<usize>().ok());
+    let Some(content_length) = content_length else {
+        return Ok(Err("411 Length Required"));
+    };
+    if header_end + content_length > MAX_WEBHOOK_REQUEST_BYTES {
+        return Ok(Err("413 Payload Too Large"));
+    }
+    while buf.len() < header_end + content_length {
+        let n = stream.read(&mut chunk).await?;
+        if n == 0 {
+            return Ok(Err("400 Bad Request"));
+        }
+        buf.extend_from_slice(&chunk[..n]);
+    }
+    Ok(Ok(buf[header_end..header_end + content_length].to_vec()))
+}
@@ -0,0 +1,151 @@
+//! Side-by-side comparison of DEX aggregators.
+//!
+//! [ArbitrageScanner::compare_aggregators] quotes the same pair and size on several
+//! aggregators and lines up their prices, gas costs, route hops and effective prices, so
+//! aggregator quality can be monitored and only the best quote fed into the scanner.
+
+use crate::common::{
+    AmountSide, DexAggregator, DexPrice, DexRouteSummary, Exchange, FeeOverrides,
+    MarketScannerError, Timestamp, effective_price_with_overrides,
+};
+use crate::dex::chains::Token;
+use crate::dex::kyberswap::route_hops;
+use crate::scanner::ArbitrageScanner;
+use futures::future::join_all;
+use serde::{Deserialize, Serialize};
+
+/// Quote of one aggregator in an [AggregatorComparison].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AggregatorQuote {
+    pub aggregator: DexAggregator,
+    pub ask_price: f64,
+    pub bid_price: f64,
+    /// Gas cost of the buy (ask) swap in USD, if reported
+    pub ask_gas_usd: Option<f64>,
+    /// Gas cost of the sell (bid) swap in USD, if reported
+    pub bid_gas_usd: Option<f64>,
+    /// Swaps of the buy route over all split paths, if the route is known
+    pub ask_route_hops: Option<usize>,
+    /// Swaps of the sell route over all split paths, if the route is known
+    pub bid_route_hops: Option<usize>,
+    /// Ask after fees plus buy gas per base unit (gas in USD counted 1:1 as quote)
+    pub effective_ask: f64,
+    /// Bid after fees minus sell gas per base unit (gas in USD counted 1:1 as quote)
+    pub effective_bid: f64,
+    pub price: DexPrice,
+}
+
+fn gas_per_unit(summary: Option<&DexRouteSummary>, quantity: f64) -> f64 {
+    match summary.and_then(|s| s.gas_usd) {
+        Some(gas_usd) if quantity > 0.0 => gas_usd / quantity,
+        _ => 0.0,
+    }
+}
+
+fn hops(aggregator: &DexAggregator, route_data: Option<&serde_json::Value>) -> Option<usize> {
+    match aggregator {
+        DexAggregator::KyberSwap => route_data.and_then(route_hops),
+    }
+}
+
+impl AggregatorQuote {
+    /// Quote of `price`; `None` unless it comes from a DEX aggregator.
+    pub fn from_price(price: DexPrice, fee_overrides: Option<&FeeOverrides>) -> Option<Self> {
+        let Exchange::Dex(aggregator) = price.exchange.clone() else {
+            return None;
+        };
+        let effective_ask = effective_price_with_overrides(
+            price.ask_price,
+            &price.exchange,
+            AmountSide::Buy,
+            fee_overrides,
+        ) + gas_per_unit(price.ask_route_summary.as_ref(), price.ask_qty);
+        let effective_bid = effective_price_with_overrides(
+            price.bid_price,
+            &price.exchange,
+            AmountSide::Sell,
+            fee_overrides,
+        ) - gas_per_unit(price.bid_route_summary.as_ref(), price.bid_qty);
+        Some(Self {
+            ask_price: price.ask_price,
+            bid_price: price.bid_price,
+            ask_gas_usd: price.ask_route_summary.as_ref().and_then(|s| s.gas_usd),
+            bid_gas_usd: price.bid_route_summary.as_ref().and_then(|s| s.gas_usd),
+            ask_route_hops: hops(&aggregator, price.ask_route_data.as_ref()),
+            bid_route_hops: hops(&aggregator, price.bid_route_data.as_ref()),
+            effective_ask,
+            effective_bid,
+            aggregator,
+            price,
+        })
+    }
+}
+
+/// Quotes of several aggregators for the same pair and size.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AggregatorComparison {
+    pub quote_amount: f64,
+    /// Successful quotes, best (lowest) effective ask first
+    pub quotes: Vec<AggregatorQuote>,
+    /// Aggregators that failed to quote, with the error
+    pub failures: Vec<(DexAggregator, String)>,
+    pub generated_at: Timestamp,
+}
+
+impl AggregatorComparison {
+    /// Comparison of already fetched quotes.
+    pub fn from_results(
+        quote_amount: f64,
+        results: Vec<(DexAggregator, Result<DexPrice, MarketScannerError>)>,
+        fee_overrides: Option<&FeeOverrides>,
+    ) -> Self {
+        let mut quotes = Vec::new();
+        let mut failures = Vec::new();
+        for (aggregator, result) in results {
+            match result {
+                Ok(price) => quotes.extend(AggregatorQuote::from_price(price, fee_overrides)),
+                Err(e) => failures.push((aggregator, e.to_string())),
+            }
+        }
+        quotes.sort_by(|a, b| a.effective_ask.total_cmp(&b.effective_ask));
+        Self {
+            quote_amount,
+            quotes,
+            failures,
+            generated_at: Timestamp::now(),
+        }
+    }
+
+    /// Quote with the lowest effective ask (best to buy on).
+    pub fn best_ask(&self) -> Option<&AggregatorQuote> {
+        self.quotes.first()
+    }
+
+    /// Quote with the highest effective bid (best to sell on).
+    pub fn best_bid(&self) -> Option<&AggregatorQuote> {
+        self.quotes
+            .iter()
+            .max_by(|a, b| a.effective_bid.total_cmp(&b.effective_bid))
+    }
+}
+
+impl ArbitrageScanner {
+    /// Quotes `quote_amount` of `quote_token` for `base_token` on every aggregator of
+    /// `aggregators` concurrently and compares them. Failed quotes are listed in
+    /// [AggregatorComparison::failures].
+    pub async fn compare_aggregators(
+        base_token: &Token,
+        quote_token: &Token,
+        quote_amount: f64,
+        aggregators: &[DexAggregator],
+        fee_overrides: Option<&FeeOverrides>,
+    ) -> AggregatorComparison {
+        let results = join_all(aggregators.iter().map(|aggregator| async move {
+            let result =
+                Self::get_dex_price(aggregator, base_token, quote_token, quote_amount).await;
+            (aggregator.clone(), result)
+        }))
+        .await;
+        AggregatorComparison::from_results(quote_amount, results, fee_overrides)
+    }
+}
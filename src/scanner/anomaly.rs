@@ -0,0 +1,240 @@
+//! Locked and crossed market detection.
+//!
+//! A book whose best bid reaches its best ask is locked (bid = ask) or crossed (bid > ask).
+//! Within one venue this never lasts on a healthy feed, and across venues a wide raw cross
+//! (before fees) is far more often a stale or broken quote than a tradable edge.
+//! [AnomalyDetector] keeps the latest quote per venue and market and reports a
+//! [MarketAnomaly] when a book or venue pair becomes locked or crossed;
+//! [detect_market_anomalies] runs it on a price stream and emits the anomalies on their own
+//! channel, next to the unchanged prices.
+
+use crate::common::{CexPrice, Exchange, MarketKey, Timestamp, logical_market_key};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use tokio::sync::mpsc;
+
+/// Anomalies buffered by [detect_market_anomalies]; later ones are dropped while it is full.
+const ANOMALY_CHANNEL_CAPACITY: usize = 256;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum AnomalyKind {
+    /// Best bid equals best ask
+    Locked,
+    /// Best bid above best ask
+    Crossed,
+}
+
+/// A locked or crossed market, within one venue or between two.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MarketAnomaly {
+    pub kind: AnomalyKind,
+    pub market: MarketKey,
+    /// Venue quoting the bid
+    pub bid_exchange: Exchange,
+    pub bid_price: f64,
+    /// Venue quoting the ask; the bid venue for a single-venue book
+    pub ask_exchange: Exchange,
+    pub ask_price: f64,
+    /// Timestamp of the quote that triggered the anomaly
+    pub timestamp: Timestamp,
+}
+
+impl MarketAnomaly {
+    /// Whether bid and ask come from different venues.
+    pub fn is_cross_venue(&self) -> bool {
+        self.bid_exchange != self.ask_exchange
+    }
+
+    /// How far the bid is above the ask, in basis points of the ask (0 when locked).
+    pub fn cross_bps(&self) -> f64 {
+        (self.bid_price - self.ask_price) / self.ask_price * 10_000.0
+    }
+}
+
+/// Settings of [AnomalyDetector].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AnomalyDetectorConfig {
+    /// Also compare quotes of different venues (single-venue books are always checked)
+    pub cross_venue: bool,
+    /// Cross-venue crosses narrower than this are ignored (basis points). Raw crosses
+    /// between venues include genuine opportunities; raise this to report only the ones
+    /// too wide to be real. At 0 locked venue pairs are reported too.
+    pub min_cross_venue_bps: f64,
+    /// Quotes of other venues older than this (relative to the new quote) are not compared
+    /// (milliseconds)
+    pub max_quote_age_ms: u64,
+}
+
+impl Default for AnomalyDetectorConfig {
+    fn default() -> Self {
+        Self {
+            cross_venue: true,
+            min_cross_venue_bps: 0.0,
+            max_quote_age_ms: 5_000,
+        }
+    }
+}
+
+impl AnomalyDetectorConfig {
+    pub fn with_cross_venue(mut self, cross_venue: bool) -> Self {
+        self.cross_venue = cross_venue;
+        self
+    }
+
+    pub fn with_min_cross_venue_bps(mut self, min_cross_venue_bps: f64) -> Self {
+        self.min_cross_venue_bps = min_cross_venue_bps;
+        self
+    }
+
+    pub fn with_max_quote_age_ms(mut self, max_quote_age_ms: u64) -> Self {
+        self.max_quote_age_ms = max_quote_age_ms;
+        self
+    }
+}
+
+/// (market, bid venue, ask venue)
+type AnomalyKey = (MarketKey, Exchange, Exchange);
+
+/// Latest quote per venue and market, and the anomalies currently open.
+#[derive(Debug, Clone, Default)]
+pub struct AnomalyDetector {
+    config: AnomalyDetectorConfig,
+    quotes: HashMap<MarketKey, HashMap<Exchange, CexPrice>>,
+    active: HashMap<AnomalyKey, MarketAnomaly>,
+}
+
+impl AnomalyDetector {
+    pub fn new(config: AnomalyDetectorConfig) -> Self {
+        Self {
+            config,
+            quotes: HashMap::new(),
+            active: HashMap::new(),
+        }
+    }
+
+    pub fn config(&self) -> &AnomalyDetectorConfig {
+        &self.config
+    }
+
+    /// Records `price` and returns the anomalies it starts. An anomaly is reported once
+    /// when it appears (or turns from locked to crossed) and again only after it cleared.
+    /// Quotes without a positive bid and ask are ignored.
+    pub fn update(&mut self, price: &CexPrice) -> Vec<MarketAnomaly> {
+        if price.bid_price <= 0.0 || price.ask_price <= 0.0 {
+            return Vec::new();
+        }
+        let market = logical_market_key(&price.symbol, &price.exchange);
+        let venues = self.quotes.entry(market.clone()).or_default();
+        venues.insert(price.exchange.clone(), price.clone());
+
+        // Every book or venue pair involving the updated venue, with its current state
+        let mut checks: Vec<(AnomalyKey, Option<MarketAnomaly>)> = Vec::new();
+        let own = anomaly(&market, price, price, price.timestamp);
+        checks.push((
+            (
+                market.clone(),
+                price.exchange.clone(),
+                price.exchange.clone(),
+            ),
+            own,
+        ));
+        if self.config.cross_venue {
+            for other in venues.values() {
+                if other.exchange == price.exchange {
+                    continue;
+                }
+                let fresh = other
+                    .timestamp
+                    .as_millis()
+                    .abs_diff(price.timestamp.as_millis())
+                    <= self.config.max_quote_age_ms;
+                for (bid, ask) in [(price, other), (other, price)] {
+                    let found = anomaly(&market, bid, ask, price.timestamp)
+                        .filter(|a| fresh && a.cross_bps() >= self.config.min_cross_venue_bps);
+                    checks.push((
+                        (market.clone(), bid.exchange.clone(), ask.exchange.clone()),
+                        found,
+                    ));
+                }
+            }
+        }
+
+        let mut started = Vec::new();
+        for (key, found) in checks {
+            match found {
+                Some(found) => {
+                    let is_new = self.active.get(&key).is_none_or(|open| {
+                        open.kind == AnomalyKind::Locked && found.kind == AnomalyKind::Crossed
+                    });
+                    if is_new {
+                        started.push(found.clone());
+                    }
+                    self.active.insert(key, found);
+                }
+                None => {
+                    self.active.remove(&key);
+                }
+            }
+        }
+        started
+    }
+
+    /// Anomalies that have not cleared yet.
+    pub fn active(&self) -> Vec<MarketAnomaly> {
+        self.active.values().cloned().collect()
+    }
+
+    /// Forgets every quote and open anomaly.
+    pub fn reset(&mut self) {
+        self.quotes.clear();
+        self.active.clear();
+    }
+}
+
+fn anomaly(
+    market: &MarketKey,
+    bid: &CexPrice,
+    ask: &CexPrice,
+    timestamp: Timestamp,
+) -> Option<MarketAnomaly> {
+    let kind = if bid.bid_price > ask.ask_price {
+        AnomalyKind::Crossed
+    } else if bid.bid_price == ask.ask_price {
+        AnomalyKind::Locked
+    } else {
+        return None;
+    };
+    Some(MarketAnomaly {
+        kind,
+        market: market.clone(),
+        bid_exchange: bid.exchange.clone(),
+        bid_price: bid.bid_price,
+        ask_exchange: ask.exchange.clone(),
+        ask_price: ask.ask_price,
+        timestamp,
+    })
+}
+
+/// Runs an [AnomalyDetector] on `receiver`. Returns the prices, forwarded unchanged, and
+/// the anomalies they start. Anomalies never hold prices back: they are dropped while their
+/// channel is full. Both receivers end when `receiver` does, or once both are dropped.
+pub fn detect_market_anomalies(
+    mut receiver: mpsc::Receiver<CexPrice>,
+    config: AnomalyDetectorConfig,
+) -> (mpsc::Receiver<CexPrice>, mpsc::Receiver<MarketAnomaly>) {
+    let (price_tx, price_rx) = mpsc::channel(receiver.max_capacity());
+    let (anomaly_tx, anomaly_rx) = mpsc::channel(ANOMALY_CHANNEL_CAPACITY);
+    tokio::spawn(async move {
+        let mut detector = AnomalyDetector::new(config);
+        while let Some(price) = receiver.recv().await {
+            if price_tx.is_closed() && anomaly_tx.is_closed() {
+                return;
+            }
+            for anomaly in detector.update(&price) {
+                let _ = anomaly_tx.try_send(anomaly);
+            }
+            let _ = price_tx.send(price).await;
+        }
+    });
+    (price_rx, anomaly_rx)
+}
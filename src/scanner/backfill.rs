@@ -0,0 +1,411 @@
+//! Alert backfill over recorded prices.
+//!
+//! Before switching a new filter configuration live, [ScannerContext::backfill_alerts]
+//! replays recorded price snapshots ([PriceHistoryFrame]s) through a context's fees, fiat
+//! calendar, filters and ranking, and reports which alerts would have fired and when. Sinks
+//! are not called. With a dedup expiry the replay suppresses repeats like an
+//! [AlertDeduplicator] would, so the count matches what a deduplicated alert channel
+//! receives. Depth recordings (see [crate::scanner::DepthSnapshotReader]) convert to frames
+//! with [PriceHistoryFrame::from_depth_snapshots].
+//!
+//! For long multi-day recordings, [PriceReplay] replays interactively: frame by frame or up
+//! to a time, with seeking, checkpoints that restore the exact replay state, a progress
+//! callback, and paced playback ([PriceReplay::play]) at a speed multiple of recorded time
+//! that a [ScannerHandle] pauses, resumes or stops.
+
+use crate::common::{CexPrice, DexPrice, MarketScannerError, Timestamp, TradingStatus};
+use crate::scanner::dedup::AlertRecord;
+use crate::scanner::{
+    AlertDeduplicator, ArbitrageOpportunity, DepthSnapshot, ScannerContext, ScannerHandle,
+    ScannerRunState,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Prices of every venue recorded at one point in time.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PriceHistoryFrame {
+    pub at: Timestamp,
+    #[serde(default)]
+    pub cex_prices: Vec<CexPrice>,
+    #[serde(default)]
+    pub dex_prices: Vec<DexPrice>,
+}
+
+impl PriceHistoryFrame {
+    pub fn new(at: Timestamp, cex_prices: Vec<CexPrice>, dex_prices: Vec<DexPrice>) -> Self {
+        Self {
+            at,
+            cex_prices,
+            dex_prices,
+        }
+    }
+
+    /// Top of book of recorded depth snapshots, one frame per `bucket_ms` of capture time
+    /// (a poll's books arrive a few milliseconds apart). A venue and symbol recorded twice
+    /// in a bucket keeps its latest book; books without bids or asks are skipped.
+    pub fn from_depth_snapshots(snapshots: &[DepthSnapshot], bucket_ms: u64) -> Vec<Self> {
+        let bucket_ms = bucket_ms.max(1);
+        let mut buckets: BTreeMap<u64, Vec<&DepthSnapshot>> = BTreeMap::new();
+        for snapshot in snapshots {
+            buckets
+                .entry(snapshot.captured_at.as_millis() / bucket_ms)
+                .or_default()
+                .push(snapshot);
+        }
+        buckets
+            .into_values()
+            .map(|mut snapshots| {
+                snapshots.sort_by_key(|snapshot| snapshot.captured_at);
+                let mut prices: Vec<CexPrice> = Vec::new();
+                for snapshot in &snapshots {
+                    let book = &snapshot.book;
+                    let (Some(bid), Some(ask)) = (book.bids.first(), book.asks.first()) else {
+                        continue;
+                    };
+                    prices.retain(|p| p.exchange != book.exchange || p.symbol != book.symbol);
+                    prices.push(CexPrice {
+                        symbol: book.symbol.clone(),
+                        mid_price: (bid.price + ask.price) / 2.0,
+                        bid_price: bid.price,
+                        ask_price: ask.price,
+                        bid_qty: bid.quantity,
+                        ask_qty: ask.quantity,
+                        timestamp: book.timestamp,
+                        exchange: book.exchange.clone(),
+                        trading_status: TradingStatus::Unknown,
+                    });
+                }
+                let at = snapshots
+                    .last()
+                    .map_or(Timestamp::default(), |s| s.captured_at);
+                Self::new(at, prices, Vec::new())
+            })
+            .collect()
+    }
+}
+
+/// Alert that would have fired during a backfill.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BackfilledAlert {
+    /// Time of the frame that produced it
+    pub at: Timestamp,
+    pub opportunity: ArbitrageOpportunity,
+}
+
+/// Outcome of [ScannerContext::backfill_alerts].
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AlertBackfillReport {
+    /// Number of frames replayed
+    pub frames: usize,
+    pub start: Option<Timestamp>,
+    pub end: Option<Timestamp>,
+    /// Opportunities found before the context's filters
+    pub candidates: u64,
+    /// Opportunities that passed the filters (before deduplication)
+    pub matched: u64,
+    /// Alerts in firing order
+    pub alerts: Vec<BackfilledAlert>,
+}
+
+impl AlertBackfillReport {
+    /// Number of alerts per [ArbitrageOpportunity::key], most alerted first.
+    pub fn alerts_per_key(&self) -> Vec<(String, usize)> {
+        let mut counts: BTreeMap<String, usize> = BTreeMap::new();
+        for alert in &self.alerts {
+            *counts.entry(alert.opportunity.key()).or_default() += 1;
+        }
+        let mut counts: Vec<(String, usize)> = counts.into_iter().collect();
+        counts.sort_by_key(|(_, count)| std::cmp::Reverse(*count));
+        counts
+    }
+}
+
+impl ScannerContext {
+    /// Replays `history` (in time order) through this context's fees, fiat calendar,
+    /// filters and ranking without calling its sinks. With `dedup_expiry_ms`, an
+    /// opportunity alerts once until it was gone for longer than that (see
+    /// [AlertDeduplicator]); without it, every match is an alert.
+    pub fn backfill_alerts(
+        &self,
+        history: &[PriceHistoryFrame],
+        dedup_expiry_ms: Option<u64>,
+    ) -> AlertBackfillReport {
+        let mut replay = PriceReplay::new(self.clone(), history.to_vec());
+        if let Some(expiry_ms) = dedup_expiry_ms {
+            replay = replay.with_dedup_expiry_ms(expiry_ms);
+        }
+        replay.run_to_end();
+        replay.into_report()
+    }
+}
+
+/// Position of a [PriceReplay], passed to its progress callback.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ReplayProgress {
+    /// Frames replayed or skipped so far
+    pub position: usize,
+    pub frames: usize,
+    /// Time of the last replayed frame
+    pub at: Option<Timestamp>,
+    /// Alerts so far
+    pub alerts: usize,
+}
+
+impl ReplayProgress {
+    /// Share of the frames done (0.0–1.0; 1.0 for an empty history).
+    pub fn fraction(&self) -> f64 {
+        if self.frames == 0 {
+            1.0
+        } else {
+            self.position as f64 / self.frames as f64
+        }
+    }
+}
+
+/// Saved state of a [PriceReplay]; restoring it resumes the replay exactly, including
+/// deduplication. Serializable, so a long analysis can continue in another process.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReplayCheckpoint {
+    pub position: usize,
+    /// Time of the next frame to replay (`None` at the end), to detect a different history
+    pub next_frame_at: Option<Timestamp>,
+    pub report: AlertBackfillReport,
+    #[serde(default)]
+    pub dedup_records: HashMap<String, AlertRecord>,
+}
+
+type ProgressCallback = Arc<dyn Fn(&ReplayProgress) + Send + Sync>;
+
+/// Stepwise [ScannerContext::backfill_alerts] over recorded frames.
+pub struct PriceReplay {
+    context: ScannerContext,
+    frames: Vec<PriceHistoryFrame>,
+    position: usize,
+    dedup: Option<AlertDeduplicator>,
+    report: AlertBackfillReport,
+    progress: Option<(usize, ProgressCallback)>,
+}
+
+impl fmt::Debug for PriceReplay {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PriceReplay")
+            .field("context", &self.context.name())
+            .field("frames", &self.frames.len())
+            .field("position", &self.position)
+            .field("dedup", &self.dedup)
+            .field("alerts", &self.report.alerts.len())
+            .finish()
+    }
+}
+
+impl PriceReplay {
+    /// Replay of `history` (sorted by time) through `context`, positioned at the start.
+    pub fn new(context: ScannerContext, mut history: Vec<PriceHistoryFrame>) -> Self {
+        history.sort_by_key(|frame| frame.at);
+        let report = AlertBackfillReport {
+            frames: history.len(),
+            start: history.first().map(|frame| frame.at),
+            end: history.last().map(|frame| frame.at),
+            ..Default::default()
+        };
+        Self {
+            context,
+            frames: history,
+            position: 0,
+            dedup: None,
+            report,
+            progress: None,
+        }
+    }
+
+    /// Deduplicates alerts like an [AlertDeduplicator] with `expiry_ms`.
+    pub fn with_dedup_expiry_ms(mut self, expiry_ms: u64) -> Self {
+        self.dedup = Some(AlertDeduplicator::new(expiry_ms));
+        self
+    }
+
+    /// Calls `callback` after every `every_frames` frames (at least 1) and at the end.
+    pub fn with_progress<F>(mut self, every_frames: usize, callback: F) -> Self
+    where
+        F: Fn(&ReplayProgress) + Send + Sync + 'static,
+    {
+        self.progress = Some((every_frames.max(1), Arc::new(callback)));
+        self
+    }
+
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.position >= self.frames.len()
+    }
+
+    /// Time of the next frame to replay.
+    pub fn next_frame_at(&self) -> Option<Timestamp> {
+        self.frames.get(self.position).map(|frame| frame.at)
+    }
+
+    pub fn progress(&self) -> ReplayProgress {
+        ReplayProgress {
+            position: self.position,
+            frames: self.frames.len(),
+            at: self
+                .position
+                .checked_sub(1)
+                .and_then(|last| self.frames.get(last))
+                .map(|frame| frame.at),
+            alerts: self.report.alerts.len(),
+        }
+    }
+
+    /// Report of the frames replayed so far.
+    pub fn report(&self) -> &AlertBackfillReport {
+        &self.report
+    }
+
+    pub fn into_report(self) -> AlertBackfillReport {
+        self.report
+    }
+
+    /// Replays the next frame and returns its alerts; `None` at the end.
+    pub fn step(&mut self) -> Option<Vec<BackfilledAlert>> {
+        let frame = self.frames.get(self.position)?;
+        let candidates = self
+            .context
+            .raw_opportunities(&frame.cex_prices, &frame.dex_prices);
+        self.report.candidates += candidates.len() as u64;
+        let matched = self.context.select(candidates, frame.at);
+        self.report.matched += matched.len() as u64;
+        let alerts = match &self.dedup {
+            Some(dedup) => dedup.filter_new(&matched, frame.at),
+            None => matched,
+        };
+        let alerts: Vec<BackfilledAlert> = alerts
+            .into_iter()
+            .map(|opportunity| BackfilledAlert {
+                at: frame.at,
+                opportunity,
+            })
+            .collect();
+        self.report.alerts.extend(alerts.iter().cloned());
+        self.position += 1;
+        self.report_progress();
+        Some(alerts)
+    }
+
+    /// Replays every frame up to and including `at`; returns their alerts.
+    pub fn run_until(&mut self, at: Timestamp) -> Vec<BackfilledAlert> {
+        let mut alerts = Vec::new();
+        while self.next_frame_at().is_some_and(|next| next <= at) {
+            alerts.extend(self.step().unwrap_or_default());
+        }
+        alerts
+    }
+
+    /// Replays the remaining frames.
+    pub fn run_to_end(&mut self) -> &AlertBackfillReport {
+        while self.step().is_some() {}
+        &self.report
+    }
+
+    /// Moves to the first frame at or after `at`. Frames jumped over are not replayed; the
+    /// report and deduplication state stay as they are (restore a [ReplayCheckpoint] to go
+    /// back to an earlier state).
+    pub fn seek(&mut self, at: Timestamp) {
+        self.position = self.frames.partition_point(|frame| frame.at < at);
+        self.report_progress();
+    }
+
+    /// Moves to frame `position` (clamped to the end); see [Self::seek].
+    pub fn seek_to_frame(&mut self, position: usize) {
+        self.position = position.min(self.frames.len());
+        self.report_progress();
+    }
+
+    /// Current state, to resume from later with [Self::restore].
+    pub fn checkpoint(&self) -> ReplayCheckpoint {
+        ReplayCheckpoint {
+            position: self.position,
+            next_frame_at: self.next_frame_at(),
+            report: self.report.clone(),
+            dedup_records: self
+                .dedup
+                .as_ref()
+                .map(AlertDeduplicator::records)
+                .unwrap_or_default(),
+        }
+    }
+
+    /// Returns to `checkpoint`. Fails if it was taken on a different history.
+    pub fn restore(&mut self, checkpoint: &ReplayCheckpoint) -> Result<(), MarketScannerError> {
+        if checkpoint.position > self.frames.len()
+            || self.frames.get(checkpoint.position).map(|frame| frame.at)
+                != checkpoint.next_frame_at
+        {
+            return Err(MarketScannerError::InvalidConfig(format!(
+                "Replay checkpoint at frame {} does not match this history of {} frames",
+                checkpoint.position,
+                self.frames.len()
+            )));
+        }
+        self.position = checkpoint.position;
+        self.report = checkpoint.report.clone();
+        if let Some(dedup) = &self.dedup {
+            dedup.replace_records(checkpoint.dedup_records.clone());
+        }
+        Ok(())
+    }
+
+    /// Replays the remaining frames paced at `speed` times recorded time (e.g. 60.0 plays
+    /// a minute per second; a non-positive or infinite speed does not wait). `handle`
+    /// pauses and resumes playback between frames; stopping it returns early, keeping the
+    /// position. Returns the alerts of the frames played.
+    pub async fn play(&mut self, speed: f64, handle: &ScannerHandle) -> Vec<BackfilledAlert> {
+        let mut control = handle.subscribe();
+        let mut alerts = Vec::new();
+        let mut previous = self.progress().at;
+        while let Some(next) = self.next_frame_at() {
+            if speed.is_finite() && speed > 0.0 {
+                if let Some(previous) = previous {
+                    let wait = next.duration_since(previous).as_secs_f64() / speed;
+                    tokio::select! {
+                        _ = tokio::time::sleep(Duration::from_secs_f64(wait)) => {}
+                        _ = handle.stopped() => return alerts,
+                    }
+                }
+            }
+            let state = control
+                .wait_for(|state| *state != ScannerRunState::Paused)
+                .await
+                .map(|state| *state);
+            if !matches!(state, Ok(ScannerRunState::Running)) {
+                return alerts;
+            }
+            alerts.extend(self.step().unwrap_or_default());
+            previous = Some(next);
+        }
+        alerts
+    }
+
+    fn report_progress(&self) {
+        let Some((every, callback)) = &self.progress else {
+            return;
+        };
+        if self.position % every == 0 || self.is_finished() {
+            callback(&self.progress());
+        }
+    }
+}
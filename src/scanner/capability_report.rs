@@ -0,0 +1,215 @@
+//! Venue × capability matrix, with an optional batch health check.
+//!
+//! [CapabilityReport] collects [CEXTrait::capabilities] of a set of venues (every
+//! integration by default) and renders it as Markdown or CSV, so integration coverage can
+//! be checked at a glance or diffed in CI as features land venue by venue.
+//! [CapabilityReport::with_health_checks] also runs every venue's health check
+//! concurrently and adds the result and round trip to each row.
+//!
+//! [CEXTrait::capabilities]: crate::common::CEXTrait::capabilities
+
+use crate::common::{Capabilities, CexExchange, Timestamp, VenueCapability};
+use crate::scanner::ArbitrageScanner;
+use futures::future::join_all;
+use serde::{Deserialize, Serialize};
+use std::fmt::Write;
+use std::time::{Duration, Instant};
+
+/// Outcome of one venue's health check.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct VenueHealthCheck {
+    pub healthy: bool,
+    /// Round trip of the check (milliseconds)
+    pub latency_ms: u64,
+    /// Error of a failed check, or "timed out"
+    pub error: Option<String>,
+}
+
+/// One row of a [CapabilityReport].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct VenueReport {
+    pub exchange: CexExchange,
+    pub capabilities: Capabilities,
+    /// `None` until [CapabilityReport::with_health_checks] ran
+    pub health: Option<VenueHealthCheck>,
+}
+
+/// Capabilities (and optionally health) of a set of venues.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CapabilityReport {
+    pub venues: Vec<VenueReport>,
+    pub generated_at: Timestamp,
+}
+
+impl Default for CapabilityReport {
+    fn default() -> Self {
+        Self::for_venues(&CexExchange::ALL)
+    }
+}
+
+impl CapabilityReport {
+    /// Report of every CEX integration.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Report of `exchanges`, in the given order.
+    pub fn for_venues(exchanges: &[CexExchange]) -> Self {
+        Self {
+            venues: exchanges
+                .iter()
+                .map(|exchange| VenueReport {
+                    exchange: exchange.clone(),
+                    capabilities: ArbitrageScanner::cex_capabilities(exchange),
+                    health: None,
+                })
+                .collect(),
+            generated_at: Timestamp::now(),
+        }
+    }
+
+    /// Runs the health check of every venue concurrently; a check still running after
+    /// `timeout` counts as failed.
+    pub async fn with_health_checks(mut self, timeout: Duration) -> Self {
+        let checks = self.venues.iter().map(|venue| async move {
+            let started = Instant::now();
+            let result =
+                tokio::time::timeout(timeout, ArbitrageScanner::cex_health_check(&venue.exchange))
+                    .await;
+            let latency_ms = started.elapsed().as_millis() as u64;
+            let error = match result {
+                Ok(Ok(())) => None,
+                Ok(Err(e)) => Some(e.to_string()),
+                Err(_) => Some("timed out".to_string()),
+            };
+            VenueHealthCheck {
+                healthy: error.is_none(),
+                latency_ms,
+                error,
+            }
+        });
+        let results = join_all(checks).await;
+        for (venue, health) in self.venues.iter_mut().zip(results) {
+            venue.health = Some(health);
+        }
+        self.generated_at = Timestamp::now();
+        self
+    }
+
+    pub fn venue(&self, exchange: &CexExchange) -> Option<&VenueReport> {
+        self.venues.iter().find(|venue| venue.exchange == *exchange)
+    }
+
+    /// Venues of the report supporting `capability`.
+    pub fn venues_with(&self, capability: VenueCapability) -> Vec<CexExchange> {
+        self.venues
+            .iter()
+            .filter(|venue| venue.capabilities.supports(capability))
+            .map(|venue| venue.exchange.clone())
+            .collect()
+    }
+
+    /// Venues whose health check failed.
+    pub fn unhealthy(&self) -> Vec<CexExchange> {
+        self.venues
+            .iter()
+            .filter(|venue| venue.health.as_ref().is_some_and(|h| !h.healthy))
+            .map(|venue| venue.exchange.clone())
+            .collect()
+    }
+
+    fn has_health(&self) -> bool {
+        self.venues.iter().any(|venue| venue.health.is_some())
+    }
+
+    /// Markdown table: one row per venue, one column per [VenueCapability] (plus health
+    /// when checked), and a last row counting the venues per capability.
+    pub fn to_markdown(&self) -> String {
+        let with_health = self.has_health();
+        let mut out = String::from("| venue |");
+        for capability in VenueCapability::ALL {
+            let _ = write!(out, " {} |", capability.as_str());
+        }
+        if with_health {
+            out.push_str(" health |");
+        }
+        out.push_str("\n|---|");
+        for _ in VenueCapability::ALL {
+            out.push_str(":---:|");
+        }
+        if with_health {
+            out.push_str("---|");
+        }
+        out.push('\n');
+        for venue in &self.venues {
+            let _ = write!(out, "| {:?} |", venue.exchange);
+            for capability in VenueCapability::ALL {
+                let mark = if venue.capabilities.supports(capability) {
+                    "yes"
+                } else {
+                    "-"
+                };
+                let _ = write!(out, " {} |", mark);
+            }
+            if with_health {
+                let _ = write!(out, " {} |", health_cell(venue.health.as_ref()));
+            }
+            out.push('\n');
+        }
+        out.push_str("| **total** |");
+        for capability in VenueCapability::ALL {
+            let _ = write!(out, " {} |", self.venues_with(capability).len());
+        }
+        if with_health {
+            let healthy = self.venues.len() - self.unhealthy().len();
+            let _ = write!(out, " {} ok |", healthy);
+        }
+        out.push('\n');
+        out
+    }
+
+    /// CSV with a header row; capabilities are `true` / `false`, health columns are added
+    /// when checked.
+    pub fn to_csv(&self) -> String {
+        let with_health = self.has_health();
+        let mut out = String::from("venue");
+        for capability in VenueCapability::ALL {
+            let _ = write!(out, ",{}", capability.as_str());
+        }
+        if with_health {
+            out.push_str(",healthy,health_latency_ms");
+        }
+        out.push('\n');
+        for venue in &self.venues {
+            let _ = write!(out, "{:?}", venue.exchange);
+            for capability in VenueCapability::ALL {
+                let _ = write!(out, ",{}", venue.capabilities.supports(capability));
+            }
+            if with_health {
+                match &venue.health {
+                    Some(health) => {
+                        let _ = write!(out, ",{},{}", health.healthy, health.latency_ms);
+                    }
+                    None => out.push_str(",,"),
+                }
+            }
+            out.push('\n');
+        }
+        out
+    }
+}
+
+fn health_cell(health: Option<&VenueHealthCheck>) -> String {
+    match health {
+        None => String::new(),
+        Some(health) if health.healthy => format!("ok ({} ms)", health.latency_ms),
+        Some(health) => format!(
+            "failed: {}",
+            health
+                .error
+                .as_deref()
+                .unwrap_or_default()
+                .replace('|', "/")
+        ),
+    }
+}
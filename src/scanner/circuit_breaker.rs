@@ -0,0 +1,169 @@
+//! Per-venue circuit breaker for the live scanner.
+//!
+//! A venue whose WebSocket keeps dropping is taken out of rotation once it fails
+//! `failure_threshold` times within `failure_window_ms`. After `open_duration_ms` a single
+//! half-open probe connection is allowed; the first price it delivers closes the breaker,
+//! another failure opens it again. A session that keeps delivering prices for
+//! `healthy_reset_ms` clears the failures counted so far.
+
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+
+/// Circuit breaker thresholds.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CircuitBreakerConfig {
+    /// Failures within the window that open the breaker (0 disables the breaker)
+    pub failure_threshold: u32,
+    /// Sliding window for counting failures (milliseconds)
+    pub failure_window_ms: u64,
+    /// How long the breaker stays open before a half-open probe (milliseconds)
+    pub open_duration_ms: u64,
+    /// How long a session has to stay healthy before earlier failures are forgotten
+    /// (milliseconds, 0 keeps them for the whole window)
+    #[serde(default = "default_healthy_reset_ms")]
+    pub healthy_reset_ms: u64,
+}
+
+fn default_healthy_reset_ms() -> u64 {
+    60 * 1000
+}
+
+impl Default for CircuitBreakerConfig {
+    /// 5 failures in 5 minutes open the breaker for 1 minute; a session healthy for
+    /// 1 minute clears the failures.
+    fn default() -> Self {
+        Self {
+            failure_threshold: 5,
+            failure_window_ms: 5 * 60 * 1000,
+            open_duration_ms: 60 * 1000,
+            healthy_reset_ms: default_healthy_reset_ms(),
+        }
+    }
+}
+
+impl CircuitBreakerConfig {
+    pub fn with_failure_threshold(mut self, failure_threshold: u32) -> Self {
+        self.failure_threshold = failure_threshold;
+        self
+    }
+
+    pub fn with_failure_window_ms(mut self, failure_window_ms: u64) -> Self {
+        self.failure_window_ms = failure_window_ms;
+        self
+    }
+
+    pub fn with_open_duration_ms(mut self, open_duration_ms: u64) -> Self {
+        self.open_duration_ms = open_duration_ms;
+        self
+    }
+
+    pub fn with_healthy_reset_ms(mut self, healthy_reset_ms: u64) -> Self {
+        self.healthy_reset_ms = healthy_reset_ms;
+        self
+    }
+}
+
+/// State of a venue's circuit breaker.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CircuitState {
+    /// Venue is connected normally
+    Closed,
+    /// Venue failed too often; no reconnects until the open duration elapses
+    Open,
+    /// One probe connection is allowed to test whether the venue recovered
+    HalfOpen,
+}
+
+/// Circuit breaker state machine. Time is passed in explicitly (milliseconds since epoch)
+/// so the transitions are deterministic. Every method returns the new state if it changed.
+#[derive(Debug, Clone)]
+pub struct CircuitBreaker {
+    config: CircuitBreakerConfig,
+    state: CircuitState,
+    failures: VecDeque<u64>,
+    opened_at: u64,
+    /// First success since the last failure
+    healthy_since: Option<u64>,
+}
+
+impl CircuitBreaker {
+    pub fn new(config: CircuitBreakerConfig) -> Self {
+        Self {
+            config,
+            state: CircuitState::Closed,
+            failures: VecDeque::new(),
+            opened_at: 0,
+            healthy_since: None,
+        }
+    }
+
+    pub fn state(&self) -> CircuitState {
+        self.state
+    }
+
+    /// Moves an open breaker to half-open once the open duration has elapsed.
+    pub fn poll(&mut self, now_ms: u64) -> Option<CircuitState> {
+        if self.state == CircuitState::Open
+            && now_ms >= self.opened_at.saturating_add(self.config.open_duration_ms)
+        {
+            self.state = CircuitState::HalfOpen;
+            return Some(self.state);
+        }
+        None
+    }
+
+    /// Milliseconds until an open breaker allows a probe (0 if not open).
+    pub fn remaining_open_ms(&self, now_ms: u64) -> u64 {
+        if self.state != CircuitState::Open {
+            return 0;
+        }
+        self.opened_at
+            .saturating_add(self.config.open_duration_ms)
+            .saturating_sub(now_ms)
+    }
+
+    /// Records a failed or dropped connection.
+    pub fn record_failure(&mut self, now_ms: u64) -> Option<CircuitState> {
+        if self.config.failure_threshold == 0 {
+            return None;
+        }
+        self.healthy_since = None;
+        self.failures.push_back(now_ms);
+        while let Some(&oldest) = self.failures.front() {
+            if now_ms.saturating_sub(oldest) > self.config.failure_window_ms {
+                self.failures.pop_front();
+            } else {
+                break;
+            }
+        }
+
+        let trip = match self.state {
+            CircuitState::HalfOpen => true,
+            CircuitState::Closed => self.failures.len() >= self.config.failure_threshold as usize,
+            CircuitState::Open => false,
+        };
+        if trip {
+            self.state = CircuitState::Open;
+            self.opened_at = now_ms;
+            return Some(self.state);
+        }
+        None
+    }
+
+    /// Records a healthy connection (a price was received). Closes a half-open breaker, and
+    /// clears the failures once the connection has been healthy for `healthy_reset_ms`.
+    pub fn record_success(&mut self, now_ms: u64) -> Option<CircuitState> {
+        let healthy_since = *self.healthy_since.get_or_insert(now_ms);
+        if self.state == CircuitState::HalfOpen {
+            self.state = CircuitState::Closed;
+            self.failures.clear();
+            return Some(self.state);
+        }
+        if self.config.healthy_reset_ms > 0
+            && now_ms.saturating_sub(healthy_since) >= self.config.healthy_reset_ms
+        {
+            self.failures.clear();
+        }
+        None
+    }
+}
@@ -0,0 +1,222 @@
+//! Composite quotes: several sources per venue with priority and agreement checks.
+//!
+//! A venue's price can come from its WebSocket stream, its REST endpoint, or (as a
+//! reference only) an oracle. [CompositeQuoteSource] keeps the latest quote of every source
+//! per venue and market. [CompositeQuoteSource::quote] emits the price of the highest
+//! priority fresh source and flags it when the other fresh sources diverge by more than the
+//! configured threshold, e.g. a WebSocket feed that silently froze while REST moved on.
+
+use crate::common::{CexExchange, CexPrice, Exchange, MarketKey, Timestamp, logical_market_key};
+use crate::dex::oracles::OraclePrice;
+use crate::scanner::{ArbitrageOpportunity, PriceData};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::time::Duration;
+
+/// Kind of quote source.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum QuoteSourceKind {
+    WebSocket,
+    Rest,
+    /// Reference price; used for agreement checks, never emitted
+    Oracle,
+}
+
+/// Composite quote thresholds.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CompositeQuoteConfig {
+    /// Sources in priority order, primary first
+    pub priority: Vec<QuoteSourceKind>,
+    /// Divergence (percent of the primary mid) above which a quote is flagged
+    pub disagreement_threshold_percent: f64,
+    /// Quotes older than this are ignored (milliseconds, 0 → 10 000)
+    pub max_age_ms: u64,
+}
+
+impl Default for CompositeQuoteConfig {
+    /// WebSocket, then REST, then oracle; flag beyond 0.5%; 10 s freshness.
+    fn default() -> Self {
+        Self {
+            priority: vec![
+                QuoteSourceKind::WebSocket,
+                QuoteSourceKind::Rest,
+                QuoteSourceKind::Oracle,
+            ],
+            disagreement_threshold_percent: 0.5,
+            max_age_ms: 10_000,
+        }
+    }
+}
+
+impl CompositeQuoteConfig {
+    pub fn with_priority(mut self, priority: Vec<QuoteSourceKind>) -> Self {
+        self.priority = priority;
+        self
+    }
+
+    pub fn with_disagreement_threshold_percent(mut self, threshold_percent: f64) -> Self {
+        self.disagreement_threshold_percent = threshold_percent;
+        self
+    }
+
+    pub fn with_max_age_ms(mut self, max_age_ms: u64) -> Self {
+        self.max_age_ms = max_age_ms;
+        self
+    }
+
+    fn max_age(&self) -> Duration {
+        Duration::from_millis(if self.max_age_ms == 0 {
+            10_000
+        } else {
+            self.max_age_ms
+        })
+    }
+}
+
+/// Mid price of one source at a point in time.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct SourceQuote {
+    pub source: QuoteSourceKind,
+    pub mid_price: f64,
+    pub timestamp: Timestamp,
+}
+
+/// Price of a venue taken from its primary source, with the agreement of the others.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CompositeQuote {
+    /// Price from the primary source
+    pub price: CexPrice,
+    /// Highest priority source that had a fresh price
+    pub primary: QuoteSourceKind,
+    /// Fresh quotes of all configured sources, in priority order
+    pub sources: Vec<SourceQuote>,
+    /// Largest deviation of another source from the primary mid (percent)
+    pub max_divergence_percent: f64,
+    /// Whether `max_divergence_percent` exceeds the disagreement threshold
+    pub diverged: bool,
+}
+
+#[derive(Debug, Clone, Default)]
+struct VenueQuotes {
+    websocket: Option<CexPrice>,
+    rest: Option<CexPrice>,
+}
+
+/// Latest quote per source, venue and logical market.
+#[derive(Debug, Clone, Default)]
+pub struct CompositeQuoteSource {
+    config: CompositeQuoteConfig,
+    venues: HashMap<(Exchange, MarketKey), VenueQuotes>,
+    oracles: HashMap<MarketKey, OraclePrice>,
+}
+
+impl CompositeQuoteSource {
+    pub fn new(config: CompositeQuoteConfig) -> Self {
+        Self {
+            config,
+            ..Self::default()
+        }
+    }
+
+    pub fn config(&self) -> &CompositeQuoteConfig {
+        &self.config
+    }
+
+    /// Records a price received from the venue's WebSocket stream.
+    pub fn record_websocket(&mut self, price: &CexPrice) {
+        self.venue_entry(price).websocket = Some(price.clone());
+    }
+
+    /// Records a price fetched from the venue's REST endpoint.
+    pub fn record_rest(&mut self, price: &CexPrice) {
+        self.venue_entry(price).rest = Some(price.clone());
+    }
+
+    /// Records an oracle reference price for `market` (all venues quoting it). Oracle feeds
+    /// are usually USD-denominated, so the market is given explicitly, e.g.
+    /// `MarketKey::from_symbol("ETHUSDT")` for a Chainlink ETH/USD feed.
+    pub fn record_oracle(&mut self, market: &MarketKey, price: &OraclePrice) {
+        self.oracles.insert(market.clone(), price.clone());
+    }
+
+    fn venue_entry(&mut self, price: &CexPrice) -> &mut VenueQuotes {
+        let market = logical_market_key(&price.symbol, &price.exchange);
+        self.venues
+            .entry((price.exchange.clone(), market))
+            .or_default()
+    }
+
+    /// Composite quote of `symbol` on `exchange` at `now`. `None` when no WebSocket or
+    /// REST price of a configured source is fresh.
+    pub fn quote(
+        &self,
+        exchange: &CexExchange,
+        symbol: &str,
+        now: Timestamp,
+    ) -> Option<CompositeQuote> {
+        let exchange = Exchange::Cex(exchange.clone());
+        let market = logical_market_key(symbol, &exchange);
+        let venue = self.venues.get(&(exchange, market.clone()));
+        let max_age = self.config.max_age();
+        let fresh = |ts: Timestamp| now.duration_since(ts) <= max_age;
+
+        let mut primary: Option<(QuoteSourceKind, &CexPrice)> = None;
+        let mut sources = Vec::new();
+        for kind in &self.config.priority {
+            let quote = match kind {
+                QuoteSourceKind::WebSocket => venue.and_then(|v| v.websocket.as_ref()),
+                QuoteSourceKind::Rest => venue.and_then(|v| v.rest.as_ref()),
+                QuoteSourceKind::Oracle => {
+                    if let Some(oracle) = self.oracles.get(&market) {
+                        if fresh(oracle.updated_at) {
+                            sources.push(SourceQuote {
+                                source: *kind,
+                                mid_price: oracle.price,
+                                timestamp: oracle.updated_at,
+                            });
+                        }
+                    }
+                    continue;
+                }
+            };
+            if let Some(price) = quote.filter(|p| fresh(p.timestamp)) {
+                sources.push(SourceQuote {
+                    source: *kind,
+                    mid_price: price.mid_price,
+                    timestamp: price.timestamp,
+                });
+                primary.get_or_insert((*kind, price));
+            }
+        }
+
+        let (primary_kind, price) = primary?;
+        let max_divergence_percent = sources
+            .iter()
+            .filter(|q| q.source != primary_kind && price.mid_price > 0.0)
+            .map(|q| ((q.mid_price - price.mid_price) / price.mid_price).abs() * 100.0)
+            .fold(0.0, f64::max);
+        Some(CompositeQuote {
+            price: price.clone(),
+            primary: primary_kind,
+            sources,
+            max_divergence_percent,
+            diverged: max_divergence_percent > self.config.disagreement_threshold_percent,
+        })
+    }
+
+    /// Whether no CEX leg of `opportunity` has diverging sources at `now`. Legs without a
+    /// composite quote (no recorded sources) count as agreeing.
+    pub fn legs_agree(&self, opportunity: &ArbitrageOpportunity, now: Timestamp) -> bool {
+        [&opportunity.source_leg, &opportunity.destination_leg]
+            .into_iter()
+            .all(|leg| match leg {
+                PriceData::Cex(p) => match &p.exchange {
+                    Exchange::Cex(cex) => {
+                        self.quote(cex, &p.symbol, now).is_none_or(|q| !q.diverged)
+                    }
+                    Exchange::Dex(_) => true,
+                },
+                PriceData::Dex(_) => true,
+            })
+    }
+}
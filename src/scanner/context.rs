@@ -0,0 +1,427 @@
+//! Independent scanner instances.
+//!
+//! [ArbitrageScanner] takes its settings per call. A [ScannerContext] bundles them for one
+//! tenant (a strategy or a customer): fee overrides, opportunity filters, ranking, live scan
+//! options and sinks that receive every result. Contexts share no state with each other, so a
+//! service can run one per tenant in the same process. Symbol overrides and asset aliases
+//! of a tenant go in a [SymbolRegistry] (see [ScannerContext::with_symbol_registry]). The
+//! HTTP cache and venue health describe the venues rather than a tenant and are shared.
+//! Thresholds, filters and fees that change at runtime come from a [SettingsHandle] (see
+//! [ScannerContext::with_settings]). With [ScannerContext::with_expiry], opportunities that
+//! stop being reported are announced as [OpportunityClosed] to the close sinks and live
+//! stream.
+
+use crate::common::{
+    CexExchange, CexPrice, DexPrice, FeeOverrides, MarketScannerError, SymbolRegistry, Timestamp,
+    symbol_registry,
+};
+use crate::scanner::{
+    ArbitrageOpportunity, ArbitrageScanner, FiatCalendar, LiveScanOptions, MinNotionalGuard,
+    OpportunityClosed, OpportunityExpiry, OpportunityRanking, ScanSchedule, ScannerEvent,
+    ScannerSettings, ScheduledScan, SettingsHandle, SpreadCostModel,
+};
+use std::fmt;
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::mpsc;
+
+/// Predicate an opportunity must pass to be kept by a [ScannerContext].
+pub type OpportunityFilter = Arc<dyn Fn(&ArbitrageOpportunity) -> bool + Send + Sync>;
+
+/// Receiver of the filtered opportunities of every scan run by a [ScannerContext].
+pub type OpportunitySink = Arc<dyn Fn(&str, &[ArbitrageOpportunity]) + Send + Sync>;
+
+/// Receiver of the opportunities a [ScannerContext] with an expiry saw close.
+pub type OpportunityCloseSink = Arc<dyn Fn(&str, &[OpportunityClosed]) + Send + Sync>;
+
+/// Scanner settings, filters and sinks of one tenant.
+#[derive(Clone, Default)]
+pub struct ScannerContext {
+    name: String,
+    fee_overrides: Option<FeeOverrides>,
+    min_spread_percent: Option<f64>,
+    filters: Vec<OpportunityFilter>,
+    ranking: Option<OpportunityRanking>,
+    fiat_calendar: Option<FiatCalendar>,
+    min_notional: Option<MinNotionalGuard>,
+    spread_costs: Option<SpreadCostModel>,
+    sinks: Vec<OpportunitySink>,
+    expiry: Option<OpportunityExpiry>,
+    close_sinks: Vec<OpportunityCloseSink>,
+    live_options: LiveScanOptions,
+    settings: Option<SettingsHandle>,
+    symbols: SymbolRegistry,
+}
+
+impl fmt::Debug for ScannerContext {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ScannerContext")
+            .field("name", &self.name)
+            .field("fee_overrides", &self.fee_overrides)
+            .field("min_spread_percent", &self.min_spread_percent)
+            .field("filters", &self.filters.len())
+            .field("ranking", &self.ranking)
+            .field("fiat_calendar", &self.fiat_calendar)
+            .field("min_notional", &self.min_notional)
+            .field("spread_costs", &self.spread_costs)
+            .field("sinks", &self.sinks.len())
+            .field("expiry", &self.expiry)
+            .field("close_sinks", &self.close_sinks.len())
+            .field("live_options", &self.live_options)
+            .field("settings", &self.settings)
+            .field("symbols", &self.symbols)
+            .finish()
+    }
+}
+
+impl ScannerContext {
+    /// New context; `name` is passed to its sinks.
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            ..Self::default()
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn fee_overrides(&self) -> Option<&FeeOverrides> {
+        self.fee_overrides.as_ref()
+    }
+
+    pub fn with_fee_overrides(mut self, fee_overrides: FeeOverrides) -> Self {
+        self.fee_overrides = Some(fee_overrides);
+        self
+    }
+
+    /// Drops opportunities below `min_spread_percent` (net of fees).
+    pub fn with_min_spread_percent(mut self, min_spread_percent: f64) -> Self {
+        self.min_spread_percent = Some(min_spread_percent);
+        self
+    }
+
+    /// Adds a filter; opportunities must pass every filter.
+    pub fn with_filter<F>(mut self, filter: F) -> Self
+    where
+        F: Fn(&ArbitrageOpportunity) -> bool + Send + Sync + 'static,
+    {
+        self.filters.push(Arc::new(filter));
+        self
+    }
+
+    /// Orders the filtered opportunities of every scan by `ranking` before they reach the
+    /// sinks (e.g. [OpportunityRanking::CapitalEfficiency] when capital is constrained).
+    pub fn with_ranking(mut self, ranking: OpportunityRanking) -> Self {
+        self.ranking = Some(ranking);
+        self
+    }
+
+    /// Annotates (and optionally dampens) opportunities whose fiat quote currency is in a
+    /// flagged period, before the filters run.
+    pub fn with_fiat_calendar(mut self, calendar: FiatCalendar) -> Self {
+        self.fiat_calendar = Some(calendar);
+        self
+    }
+
+    /// Sets the notional floor of every opportunity from venue order limits, before the
+    /// filters run (see [ArbitrageOpportunity::is_below_min_notional]).
+    pub fn with_min_notional_guard(mut self, guard: MinNotionalGuard) -> Self {
+        self.min_notional = Some(guard);
+        self
+    }
+
+    /// Completes the [crate::scanner::SpreadBreakdown] of every opportunity with `model`'s
+    /// slippage and transfer costs, before the filters run.
+    pub fn with_spread_cost_model(mut self, model: SpreadCostModel) -> Self {
+        self.spread_costs = Some(model);
+        self
+    }
+
+    /// Adds a sink called with the context name and the filtered opportunities of every
+    /// scan (REST scans, price snapshots and live snapshots).
+    pub fn with_sink<F>(mut self, sink: F) -> Self
+    where
+        F: Fn(&str, &[ArbitrageOpportunity]) + Send + Sync + 'static,
+    {
+        self.sinks.push(Arc::new(sink));
+        self
+    }
+
+    /// Closes opportunities that were not reported (after filters) for `expiry`; each
+    /// close goes to the close sinks and, in [Self::scan_live], is emitted as
+    /// [ScannerEvent::OpportunityClosed]. Clones of the context share the tracking.
+    pub fn with_expiry(mut self, expiry: Duration) -> Self {
+        self.expiry = Some(OpportunityExpiry::new(expiry));
+        self
+    }
+
+    /// Adds a sink called with the context name and the opportunities that closed at a
+    /// scan; needs [Self::with_expiry]. Not called when nothing closed.
+    pub fn with_close_sink<F>(mut self, sink: F) -> Self
+    where
+        F: Fn(&str, &[OpportunityClosed]) + Send + Sync + 'static,
+    {
+        self.close_sinks.push(Arc::new(sink));
+        self
+    }
+
+    /// Options of [ScannerContext::scan_live]. Their fee overrides are replaced by the
+    /// context's when it has some.
+    pub fn with_live_options(mut self, live_options: LiveScanOptions) -> Self {
+        self.live_options = live_options;
+        self
+    }
+
+    /// Applies the thresholds, filters and fees of `settings` on top of this context's own,
+    /// re-read for every scan and snapshot so reloads take effect without restarting
+    /// streams. Fees from the settings replace [Self::with_fee_overrides] while they set any;
+    /// [Self::scan_periodic] keeps the fees it started with.
+    pub fn with_settings(mut self, settings: SettingsHandle) -> Self {
+        self.settings = Some(settings);
+        self
+    }
+
+    pub fn settings(&self) -> Option<&SettingsHandle> {
+        self.settings.as_ref()
+    }
+
+    /// Symbol overrides and asset aliases of this context, consulted before the
+    /// process-wide ones by its scans, their streams and [Self::process].
+    pub fn with_symbol_registry(mut self, symbols: SymbolRegistry) -> Self {
+        self.symbols = symbols;
+        self
+    }
+
+    pub fn symbol_registry(&self) -> &SymbolRegistry {
+        &self.symbols
+    }
+
+    /// Runs `future` with this context's symbol registry in effect.
+    async fn scoped<F: Future>(&self, future: F) -> F::Output {
+        if self.symbols.is_empty() {
+            future.await
+        } else {
+            self.symbols.scope(future).await
+        }
+    }
+
+    /// Runs `f` with this context's symbol registry in effect.
+    fn scoped_sync<R>(&self, f: impl FnOnce() -> R) -> R {
+        if self.symbols.is_empty() {
+            f()
+        } else {
+            self.symbols.scope_sync(f)
+        }
+    }
+
+    /// Whether `opportunity` passes the minimum spread, every filter and the current
+    /// settings of this context.
+    pub fn allows(&self, opportunity: &ArbitrageOpportunity) -> bool {
+        let settings = self.settings.as_ref().map(SettingsHandle::current);
+        self.allows_with(opportunity, settings.as_deref())
+    }
+
+    fn allows_with(
+        &self,
+        opportunity: &ArbitrageOpportunity,
+        settings: Option<&ScannerSettings>,
+    ) -> bool {
+        self.min_spread_percent
+            .is_none_or(|min| opportunity.spread_percentage >= min)
+            && settings.is_none_or(|settings| settings.allows(opportunity))
+            && self.filters.iter().all(|filter| filter(opportunity))
+    }
+
+    /// Fee overrides in effect: those of the current settings, else the context's own.
+    fn current_fee_overrides(&self) -> Option<FeeOverrides> {
+        self.settings
+            .as_ref()
+            .and_then(|settings| settings.current().fee_overrides())
+            .or_else(|| self.fee_overrides.clone())
+    }
+
+    /// Applies the fiat calendar, filters and ranking of this context to `opportunities`
+    /// and hands the result to its sinks.
+    pub fn process(&self, opportunities: Vec<ArbitrageOpportunity>) -> Vec<ArbitrageOpportunity> {
+        self.process_at(opportunities, Timestamp::now()).0
+    }
+
+    /// [Self::process] at `now`, also returning the opportunities that expired (handed to
+    /// the close sinks).
+    pub fn process_at(
+        &self,
+        opportunities: Vec<ArbitrageOpportunity>,
+        now: Timestamp,
+    ) -> (Vec<ArbitrageOpportunity>, Vec<OpportunityClosed>) {
+        let opportunities = self.scoped_sync(|| self.select(opportunities, now));
+        for sink in &self.sinks {
+            sink(&self.name, &opportunities);
+        }
+        let closed = match &self.expiry {
+            Some(expiry) => expiry.observe(&opportunities, now),
+            None => Vec::new(),
+        };
+        if !closed.is_empty() {
+            for sink in &self.close_sinks {
+                sink(&self.name, &closed);
+            }
+        }
+        (opportunities, closed)
+    }
+
+    /// Fiat calendar (at `at`), filters and ranking of this context, without the sinks.
+    pub(crate) fn select(
+        &self,
+        mut opportunities: Vec<ArbitrageOpportunity>,
+        at: Timestamp,
+    ) -> Vec<ArbitrageOpportunity> {
+        if let Some(calendar) = &self.fiat_calendar {
+            calendar.annotate_all(&mut opportunities, at);
+        }
+        if let Some(guard) = &self.min_notional {
+            guard.annotate_all(&mut opportunities);
+        }
+        if let Some(model) = &self.spread_costs {
+            model.annotate_all(&mut opportunities);
+        }
+        // One settings value for the whole batch, even if a reload lands meanwhile
+        let settings = self.settings.as_ref().map(SettingsHandle::current);
+        opportunities.retain(|o| self.allows_with(o, settings.as_deref()));
+        if let Some(ranking) = &self.ranking {
+            ranking.sort(&mut opportunities);
+        }
+        opportunities
+    }
+
+    /// Unfiltered opportunities of `cex_prices` and `dex_prices` with this context's fees,
+    /// most profitable first.
+    pub(crate) fn raw_opportunities(
+        &self,
+        cex_prices: &[CexPrice],
+        dex_prices: &[DexPrice],
+    ) -> Vec<ArbitrageOpportunity> {
+        let mut opportunities = ArbitrageScanner::opportunities_from_prices(
+            cex_prices,
+            dex_prices,
+            self.current_fee_overrides().as_ref(),
+        );
+        opportunities.sort_by(|a, b| {
+            b.spread_percentage
+                .partial_cmp(&a.spread_percentage)
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+        opportunities
+    }
+
+    /// [ArbitrageScanner::opportunities_from_prices] with this context's fees, filters and
+    /// sinks; sorted by profitability.
+    pub fn opportunities_from_prices(
+        &self,
+        cex_prices: &[CexPrice],
+        dex_prices: &[DexPrice],
+    ) -> Vec<ArbitrageOpportunity> {
+        let opportunities = self.scoped_sync(|| self.raw_opportunities(cex_prices, dex_prices));
+        self.process(opportunities)
+    }
+
+    /// REST scan of `symbol` on `cex_exchanges` with this context's fees, filters and
+    /// sinks (see [ArbitrageScanner::scan_arbitrage_opportunities]).
+    pub async fn scan(
+        &self,
+        symbol: &str,
+        cex_exchanges: &[CexExchange],
+    ) -> Result<Vec<ArbitrageOpportunity>, MarketScannerError> {
+        let opportunities = self
+            .scoped(ArbitrageScanner::scan_arbitrage_opportunities(
+                symbol,
+                cex_exchanges,
+                None,
+                None,
+                None,
+                None,
+                self.current_fee_overrides().as_ref(),
+            ))
+            .await?;
+        Ok(self.process(opportunities))
+    }
+
+    /// Live scan ([ArbitrageScanner::scan_live]) with this context's options. Opportunity
+    /// snapshots are filtered and handed to the sinks before they are forwarded; with an
+    /// expiry, each snapshot is followed by a [ScannerEvent::OpportunityClosed] when
+    /// opportunities closed.
+    pub async fn scan_live(
+        &self,
+        symbols: &[&str],
+        cex_exchanges: &[CexExchange],
+    ) -> Result<mpsc::Receiver<ScannerEvent>, MarketScannerError> {
+        let mut options = self.live_options.clone();
+        if let Some(fee_overrides) = &self.fee_overrides {
+            options = options.with_fee_overrides(fee_overrides.clone());
+        }
+        if let Some(settings) = &self.settings {
+            options = options.with_settings(settings.clone());
+        }
+        let mut rx_live = self
+            .scoped(ArbitrageScanner::scan_live(symbols, cex_exchanges, options))
+            .await?;
+
+        let context = self.clone();
+        let (tx, rx) = mpsc::channel(64);
+        symbol_registry::spawn(async move {
+            while let Some(event) = rx_live.recv().await {
+                let events = match event {
+                    ScannerEvent::Opportunities(opportunities) => {
+                        let (opportunities, closed) =
+                            context.process_at(opportunities, Timestamp::now());
+                        let mut events = vec![ScannerEvent::Opportunities(opportunities)];
+                        if !closed.is_empty() {
+                            events.push(ScannerEvent::OpportunityClosed(closed));
+                        }
+                        events
+                    }
+                    other => vec![other],
+                };
+                for event in events {
+                    if tx.send(event).await.is_err() {
+                        return;
+                    }
+                }
+            }
+        });
+        Ok(rx)
+    }
+
+    /// Periodic scan ([ArbitrageScanner::scan_periodic]) with this context's fees, filters
+    /// and sinks; successful scans are filtered and handed to the sinks before they are
+    /// forwarded.
+    pub async fn scan_periodic(
+        &self,
+        schedule: ScanSchedule,
+        cex_exchanges: &[CexExchange],
+    ) -> Result<mpsc::Receiver<ScheduledScan>, MarketScannerError> {
+        let mut rx_scans = self
+            .scoped(ArbitrageScanner::scan_periodic(
+                schedule,
+                cex_exchanges,
+                self.fee_overrides.as_ref(),
+            ))
+            .await?;
+
+        let context = self.clone();
+        let (tx, rx) = mpsc::channel(64);
+        symbol_registry::spawn(async move {
+            while let Some(mut scan) = rx_scans.recv().await {
+                scan.result = scan
+                    .result
+                    .map(|opportunities| context.process(opportunities));
+                if tx.send(scan).await.is_err() {
+                    return;
+                }
+            }
+        });
+        Ok(rx)
+    }
+}
@@ -0,0 +1,228 @@
+//! Operator control of a running live scanner.
+//!
+//! A [ScannerHandle] passed through [LiveScanOptions::with_handle] pauses, resumes or stops
+//! the scanner. Pausing halts opportunity emission while venue connections stay up and
+//! keep the price cache warm, so alerting and execution can be suspended during an
+//! incident and resumed without reconnecting. Stopping tears everything down.
+//!
+//! The handle also keeps the scanner's latest prices and opportunity snapshot, so
+//! request/response consumers (e.g. HTTP handlers) can read the current state with
+//! [ScannerHandle::latest_price] and [ScannerHandle::latest_opportunities] instead of
+//! consuming the event stream. [ScannerHandle::match_metrics] tells how many candidate
+//! pairs the matcher evaluated, rejected and emitted.
+//!
+//! [LiveScanOptions::with_handle]: crate::scanner::LiveScanOptions::with_handle
+
+use crate::common::{CacheLimits, CacheStats, CexExchange, CexPrice, Exchange, MarketKey};
+use crate::scanner::{
+    ArbitrageOpportunity, ArbitrageScanner, FeedUpdateStats, LivePriceCache, MatchMetrics,
+    UpdateRateTracker,
+};
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+use tokio::sync::watch;
+
+/// Run state of a live scanner.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ScannerRunState {
+    /// Emitting opportunity snapshots
+    #[default]
+    Running,
+    /// Connected and caching prices, but not emitting opportunities
+    Paused,
+    /// Shut down; venue connections are closed and the event channel ends
+    Stopped,
+}
+
+/// Kill switch of a live scanner. Cheap to clone; clones control the same scanner(s).
+#[derive(Debug, Clone)]
+pub struct ScannerHandle {
+    state: Arc<watch::Sender<ScannerRunState>>,
+    prices: LivePriceCache,
+    opportunities: Arc<RwLock<Vec<ArbitrageOpportunity>>>,
+    update_rates: UpdateRateTracker,
+    match_metrics: Arc<RwLock<MatchMetrics>>,
+}
+
+impl Default for ScannerHandle {
+    fn default() -> Self {
+        Self {
+            state: Arc::new(watch::Sender::new(ScannerRunState::Running)),
+            prices: LivePriceCache::new(),
+            opportunities: Arc::new(RwLock::new(Vec::new())),
+            update_rates: UpdateRateTracker::new(),
+            match_metrics: Arc::new(RwLock::new(MatchMetrics::new())),
+        }
+    }
+}
+
+impl ScannerHandle {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn state(&self) -> ScannerRunState {
+        *self.state.borrow()
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.state() == ScannerRunState::Paused
+    }
+
+    pub fn is_stopped(&self) -> bool {
+        self.state() == ScannerRunState::Stopped
+    }
+
+    /// Halts opportunity emission; venue streams stay connected. No-op once stopped.
+    pub fn pause(&self) {
+        self.transition(ScannerRunState::Paused);
+    }
+
+    /// Resumes opportunity emission after [ScannerHandle::pause]. No-op once stopped.
+    pub fn resume(&self) {
+        self.transition(ScannerRunState::Running);
+    }
+
+    /// Stops the scanner for good: venue connections are closed and the event channel
+    /// ends after a final [ScannerEvent::StateChanged].
+    ///
+    /// [ScannerEvent::StateChanged]: crate::scanner::ScannerEvent::StateChanged
+    pub fn stop(&self) {
+        self.transition(ScannerRunState::Stopped);
+    }
+
+    /// Resolves once the scanner is stopped.
+    pub async fn stopped(&self) {
+        let mut rx = self.state.subscribe();
+        let _ = rx
+            .wait_for(|state| *state == ScannerRunState::Stopped)
+            .await;
+    }
+
+    /// Latest price of `symbol` (matched through asset aliases) on `exchange`, if the
+    /// scanner has one. Prices of a venue are dropped while its circuit breaker is open.
+    pub fn latest_price(&self, exchange: &CexExchange, symbol: &str) -> Option<CexPrice> {
+        let venue = Exchange::Cex(exchange.clone());
+        self.prices
+            .prices(symbol)
+            .into_iter()
+            .find(|price| price.exchange == venue)
+    }
+
+    /// Opportunities of `symbol` in the latest snapshot, sorted by profitability. While
+    /// paused no snapshots are built, so this is the last one before pausing.
+    pub fn latest_opportunities(&self, symbol: &str) -> Vec<ArbitrageOpportunity> {
+        let market = MarketKey::from_symbol(symbol);
+        self.opportunities
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .iter()
+            .filter(|o| MarketKey::from_symbol(&o.symbol) == market)
+            .cloned()
+            .collect()
+    }
+
+    /// Entry count and evictions of the handle's latest-price cache, which is bounded by
+    /// the scanner's [LiveScanOptions::with_cache_limits].
+    ///
+    /// [LiveScanOptions::with_cache_limits]: crate::scanner::LiveScanOptions::with_cache_limits
+    pub fn cache_stats(&self) -> CacheStats {
+        self.prices.stats()
+    }
+
+    /// Update rate and last-update age of every venue and symbol the scanner subscribed
+    /// to, including feeds that have not delivered a price yet. Counters keep running
+    /// while paused and survive circuit breaker trips.
+    pub fn update_stats(&self) -> Vec<FeedUpdateStats> {
+        self.update_rates.stats()
+    }
+
+    /// Update statistics of `symbol` (matched through asset aliases) on `exchange`.
+    pub fn feed_update_stats(
+        &self,
+        exchange: &CexExchange,
+        symbol: &str,
+    ) -> Option<FeedUpdateStats> {
+        self.update_rates
+            .stats_for(&Exchange::Cex(exchange.clone()), symbol)
+    }
+
+    /// Feeds without a price update for `max_age`.
+    pub fn dormant_feeds(&self, max_age: Duration) -> Vec<FeedUpdateStats> {
+        self.update_rates.dormant(max_age)
+    }
+
+    /// Matching counters accumulated since the scanner started (or the last
+    /// [ScannerHandle::reset_match_metrics]). Nothing is counted while paused.
+    pub fn match_metrics(&self) -> MatchMetrics {
+        self.match_metrics
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+    }
+
+    /// Restarts the matching counters, e.g. after retuning filters.
+    pub fn reset_match_metrics(&self) {
+        *self
+            .match_metrics
+            .write()
+            .unwrap_or_else(|e| e.into_inner()) = MatchMetrics::new();
+    }
+
+    pub(crate) fn set_cache_limits(&self, limits: CacheLimits) {
+        self.prices.set_limits(limits);
+    }
+
+    pub(crate) fn expect_feeds(&self, exchanges: &[CexExchange], symbols: &[String]) {
+        for exchange in exchanges {
+            let venue = Exchange::Cex(exchange.clone());
+            for symbol in symbols {
+                self.update_rates.expect(&venue, symbol);
+            }
+        }
+    }
+
+    pub(crate) fn record_price(&self, price: CexPrice) {
+        self.update_rates.record(&price);
+        self.prices.insert(price);
+    }
+
+    pub(crate) fn record_opportunities(&self, opportunities: &[ArbitrageOpportunity]) {
+        *self
+            .opportunities
+            .write()
+            .unwrap_or_else(|e| e.into_inner()) = opportunities.to_vec();
+    }
+
+    pub(crate) fn record_match_metrics(&self, snapshot: &MatchMetrics) {
+        self.match_metrics
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .merge(snapshot);
+    }
+
+    /// Forgets the prices and opportunities of `exchange`.
+    pub(crate) fn remove_venue(&self, exchange: &Exchange) {
+        self.prices.remove_venue(exchange);
+        let name = ArbitrageScanner::exchange_name(exchange);
+        self.opportunities
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .retain(|o| o.source_exchange != name && o.destination_exchange != name);
+    }
+
+    pub(crate) fn subscribe(&self) -> watch::Receiver<ScannerRunState> {
+        self.state.subscribe()
+    }
+
+    fn transition(&self, next: ScannerRunState) {
+        self.state.send_if_modified(|state| {
+            if *state == next || *state == ScannerRunState::Stopped {
+                return false;
+            }
+            *state = next;
+            true
+        });
+    }
+}
@@ -0,0 +1,132 @@
+//! Depth-aware decomposition of opportunities into multi-venue sell tranches.
+
+use crate::common::{
+    AmountSide, FeeOverrides, OrderBook, effective_price_with_overrides, logical_market_key,
+};
+use crate::scanner::ArbitrageScanner;
+use crate::scanner::opportunity::{MultiLegOpportunity, SellTranche};
+
+impl ArbitrageScanner {
+    /// Sizes opportunities against order book depth.
+    ///
+    /// For every source venue, its asks are walked from the cheapest level while the best
+    /// remaining bid on any other venue (same logical market) still pays more after fees.
+    /// The matched quantity is split into one [SellTranche] per destination venue, so a
+    /// large opportunity can be sold across several venues. Results are sorted by total
+    /// profit (most profitable first).
+    pub fn multi_leg_opportunities_from_books(
+        books: &[OrderBook],
+        fee_overrides: Option<&FeeOverrides>,
+    ) -> Vec<MultiLegOpportunity> {
+        let mut opportunities = Vec::new();
+
+        for source in books {
+            let source_market = logical_market_key(&source.symbol, &source.exchange);
+
+            // Effective ask levels (ask × (1 + fee)), lowest first
+            let mut asks: Vec<(f64, f64)> = source
+                .asks
+                .iter()
+                .filter(|l| l.price > 0.0 && l.quantity > 0.0)
+                .map(|l| {
+                    let effective = effective_price_with_overrides(
+                        l.price,
+                        &source.exchange,
+                        AmountSide::Buy,
+                        fee_overrides,
+                    );
+                    (effective, l.quantity)
+                })
+                .collect();
+            asks.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(std::cmp::Ordering::Equal));
+
+            // Effective bid levels (bid × (1 − fee)) of all other venues, highest first
+            let mut bids: Vec<(f64, f64, usize)> = Vec::new();
+            for (idx, dest) in books.iter().enumerate() {
+                if dest.exchange == source.exchange
+                    || logical_market_key(&dest.symbol, &dest.exchange) != source_market
+                {
+                    continue;
+                }
+                for level in dest
+                    .bids
+                    .iter()
+                    .filter(|l| l.price > 0.0 && l.quantity > 0.0)
+                {
+                    let effective = effective_price_with_overrides(
+                        level.price,
+                        &dest.exchange,
+                        AmountSide::Sell,
+                        fee_overrides,
+                    );
+                    bids.push((effective, level.quantity, idx));
+                }
+            }
+            bids.sort_by(|a, b| b.0.partial_cmp(&a.0).unwrap_or(std::cmp::Ordering::Equal));
+
+            // Greedy match: cheapest ask against richest bid while still profitable
+            let mut sold: Vec<(usize, f64, f64)> = Vec::new(); // (book idx, qty, proceeds)
+            let mut quantity = 0.0;
+            let mut cost_quote = 0.0;
+            let (mut i, mut j) = (0, 0);
+            while i < asks.len() && j < bids.len() && bids[j].0 > asks[i].0 {
+                let qty = asks[i].1.min(bids[j].1);
+                quantity += qty;
+                cost_quote += qty * asks[i].0;
+                match sold.iter_mut().find(|(idx, _, _)| *idx == bids[j].2) {
+                    Some(entry) => {
+                        entry.1 += qty;
+                        entry.2 += qty * bids[j].0;
+                    }
+                    None => sold.push((bids[j].2, qty, qty * bids[j].0)),
+                }
+                asks[i].1 -= qty;
+                bids[j].1 -= qty;
+                if asks[i].1 <= 0.0 {
+                    i += 1;
+                }
+                if bids[j].1 <= 0.0 {
+                    j += 1;
+                }
+            }
+
+            if quantity <= 0.0 {
+                continue;
+            }
+
+            let mut tranches: Vec<SellTranche> = sold
+                .into_iter()
+                .map(|(idx, qty, proceeds)| SellTranche {
+                    destination_exchange: Self::exchange_name(&books[idx].exchange),
+                    quantity: qty,
+                    fraction: qty / quantity,
+                    average_effective_bid: proceeds / qty,
+                    proceeds_quote: proceeds,
+                })
+                .collect();
+            tranches.sort_by(|a, b| {
+                b.quantity
+                    .partial_cmp(&a.quantity)
+                    .unwrap_or(std::cmp::Ordering::Equal)
+            });
+            let proceeds_quote = tranches.iter().map(|t| t.proceeds_quote).sum();
+
+            opportunities.push(MultiLegOpportunity {
+                source_exchange: Self::exchange_name(&source.exchange),
+                symbol: source.symbol.clone(),
+                quantity,
+                average_effective_ask: cost_quote / quantity,
+                cost_quote,
+                tranches,
+                proceeds_quote,
+            });
+        }
+
+        opportunities.sort_by(|a, b| {
+            b.total_profit()
+                .partial_cmp(&a.total_profit())
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+        opportunities
+    }
+}
@@ -0,0 +1,191 @@
+//! Alert deduplication that survives restarts.
+//!
+//! Snapshot scans report a long-lived spread on every update. [AlertDeduplicator] lets an
+//! opportunity (identified by [ArbitrageOpportunity::key]) through once, then suppresses it
+//! for as long as it keeps being seen within the expiry; it alerts again only after the
+//! spread was gone for longer than that. The records are kept in an [AlertStore] (a JSON
+//! file with [JsonFileAlertStore]), so a restarted process does not re-alert on spreads it
+//! already reported.
+
+use crate::common::{MarketScannerError, Timestamp};
+use crate::scanner::ArbitrageOpportunity;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// When an opportunity was alerted and last seen.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AlertRecord {
+    pub alerted_at: Timestamp,
+    pub last_seen_at: Timestamp,
+}
+
+/// Persistence of [AlertDeduplicator] records, keyed by [ArbitrageOpportunity::key].
+/// Implement it to keep records in a database.
+pub trait AlertStore: Send + Sync {
+    fn load(&self) -> Result<HashMap<String, AlertRecord>, MarketScannerError>;
+    fn save(&self, records: &HashMap<String, AlertRecord>) -> Result<(), MarketScannerError>;
+}
+
+/// Records in a JSON file, replaced atomically on every save. A missing file is empty.
+#[derive(Debug, Clone)]
+pub struct JsonFileAlertStore {
+    path: PathBuf,
+}
+
+impl JsonFileAlertStore {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+}
+
+impl AlertStore for JsonFileAlertStore {
+    fn load(&self) -> Result<HashMap<String, AlertRecord>, MarketScannerError> {
+        match std::fs::read_to_string(&self.path) {
+            Ok(contents) => Ok(serde_json::from_str(&contents)?),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(HashMap::new()),
+            Err(e) => Err(MarketScannerError::InvalidConfig(format!(
+                "Cannot read alert store {}: {}",
+                self.path.display(),
+                e
+            ))),
+        }
+    }
+
+    fn save(&self, records: &HashMap<String, AlertRecord>) -> Result<(), MarketScannerError> {
+        let temp = self.path.with_extension("tmp");
+        std::fs::write(&temp, serde_json::to_vec(records)?)
+            .and_then(|_| std::fs::rename(&temp, &self.path))
+            .map_err(|e| {
+                MarketScannerError::InvalidConfig(format!(
+                    "Cannot write alert store {}: {}",
+                    self.path.display(),
+                    e
+                ))
+            })
+    }
+}
+
+/// Suppresses repeated alerts of the same opportunity. Cheap to clone; clones share records.
+#[derive(Clone)]
+pub struct AlertDeduplicator {
+    expiry: Duration,
+    records: Arc<Mutex<HashMap<String, AlertRecord>>>,
+    store: Option<Arc<dyn AlertStore>>,
+}
+
+impl std::fmt::Debug for AlertDeduplicator {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AlertDeduplicator")
+            .field("expiry", &self.expiry)
+            .field(
+                "records",
+                &self.records.lock().map(|r| r.len()).unwrap_or(0),
+            )
+            .field("persistent", &self.store.is_some())
+            .finish()
+    }
+}
+
+impl AlertDeduplicator {
+    /// In-memory deduplicator; an opportunity unseen for `expiry_ms` alerts again.
+    pub fn new(expiry_ms: u64) -> Self {
+        Self {
+            expiry: Duration::from_millis(expiry_ms),
+            records: Arc::new(Mutex::new(HashMap::new())),
+            store: None,
+        }
+    }
+
+    /// Loads the records of `store`; [Self::save] writes them back.
+    pub fn with_store(
+        mut self,
+        store: impl AlertStore + 'static,
+    ) -> Result<Self, MarketScannerError> {
+        let loaded = store.load()?;
+        self.records
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .extend(loaded);
+        self.store = Some(Arc::new(store));
+        Ok(self)
+    }
+
+    /// [Self::with_store] with a [JsonFileAlertStore] at `path`.
+    pub fn with_file(self, path: impl Into<PathBuf>) -> Result<Self, MarketScannerError> {
+        self.with_store(JsonFileAlertStore::new(path))
+    }
+
+    /// Record of `key` (see [ArbitrageOpportunity::key]), if it is still tracked.
+    pub fn record(&self, key: &str) -> Option<AlertRecord> {
+        self.records
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(key)
+            .copied()
+    }
+
+    /// Opportunities of a scan at `now` that were not alerted yet (or expired). All of
+    /// them are marked seen and expired records are dropped; call [Self::save] to persist.
+    pub fn filter_new(
+        &self,
+        opportunities: &[ArbitrageOpportunity],
+        now: Timestamp,
+    ) -> Vec<ArbitrageOpportunity> {
+        let mut records = self.records.lock().unwrap_or_else(|e| e.into_inner());
+        records.retain(|_, record| now.duration_since(record.last_seen_at) <= self.expiry);
+
+        let mut fresh = Vec::new();
+        for opportunity in opportunities {
+            let record = records.entry(opportunity.key()).or_insert_with(|| {
+                fresh.push(opportunity.clone());
+                AlertRecord {
+                    alerted_at: now,
+                    last_seen_at: now,
+                }
+            });
+            record.last_seen_at = record.last_seen_at.max(now);
+        }
+        fresh
+    }
+
+    /// Copy of all records, for replay checkpoints.
+    pub(crate) fn records(&self) -> HashMap<String, AlertRecord> {
+        self.records
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+    }
+
+    /// Replaces all records, when a replay checkpoint is restored.
+    pub(crate) fn replace_records(&self, records: HashMap<String, AlertRecord>) {
+        *self.records.lock().unwrap_or_else(|e| e.into_inner()) = records;
+    }
+
+    /// Writes the records to the store, if there is one.
+    pub fn save(&self) -> Result<(), MarketScannerError> {
+        match &self.store {
+            Some(store) => store.save(&self.records.lock().unwrap_or_else(|e| e.into_inner())),
+            None => Ok(()),
+        }
+    }
+
+    /// Wraps a [crate::scanner::ScannerContext] sink so that it only receives opportunities
+    /// not alerted yet; it is not called when a scan has none. Records are saved after
+    /// every scan; store errors are ignored so alerts are never held back.
+    pub fn sink<F>(&self, sink: F) -> impl Fn(&str, &[ArbitrageOpportunity]) + Send + Sync + 'static
+    where
+        F: Fn(&str, &[ArbitrageOpportunity]) + Send + Sync + 'static,
+    {
+        let dedup = self.clone();
+        move |context, opportunities| {
+            let fresh = dedup.filter_new(opportunities, Timestamp::now());
+            let _ = dedup.save();
+            if !fresh.is_empty() {
+                sink(context, &fresh);
+            }
+        }
+    }
+}
@@ -0,0 +1,311 @@
+//! Depth-of-market recording for research.
+//!
+//! [ArbitrageScanner::record_depth_snapshots] polls the REST order books (up to N levels) of
+//! selected venues and symbols at a fixed interval and appends them as gzip-compressed
+//! JSON lines to files in a directory, starting a new file every
+//! [DepthRecorderOptions::snapshots_per_file] snapshots. [DepthSnapshotReader] reads a file
+//! back and [depth_recording_files] lists the files of a directory in recording order, so
+//! slippage models and backtests can replay full depth instead of top-of-book only.
+//!
+//! Files are flushed after every poll: a file of a recording that was killed yields its
+//! complete snapshots, then an error for the truncated tail.
+
+use crate::common::{CexExchange, MarketScannerError, OrderBook, Timestamp};
+use crate::scanner::{ArbitrageScanner, ScannerHandle};
+use flate2::Compression;
+use flate2::read::MultiGzDecoder;
+use flate2::write::GzEncoder;
+use futures::future::join_all;
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::io::{BufRead, BufReader, BufWriter, Lines, Write};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tokio::time::{Instant, MissedTickBehavior};
+
+/// File name extension of recorded files.
+pub const DEPTH_FILE_EXTENSION: &str = "jsonl.gz";
+
+/// One recorded order book.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DepthSnapshot {
+    /// Local time the response was received (the book carries the venue timestamp)
+    pub captured_at: Timestamp,
+    pub book: OrderBook,
+}
+
+/// What [ArbitrageScanner::record_depth_snapshots] records and for how long.
+#[derive(Debug, Clone)]
+pub struct DepthRecorderOptions {
+    pub venues: Vec<CexExchange>,
+    pub symbols: Vec<String>,
+    /// Levels per side (default: 50)
+    pub depth: usize,
+    /// Poll interval (default: 1000 ms)
+    pub interval_ms: u64,
+    /// Snapshots per file before a new one is started (default: 10_000)
+    pub snapshots_per_file: usize,
+    /// Stops after this long (default: only when the handle is stopped)
+    pub duration_ms: Option<u64>,
+    /// Stops the recording when stopped; polls are skipped while paused
+    pub handle: Option<ScannerHandle>,
+}
+
+impl DepthRecorderOptions {
+    pub fn new(venues: &[CexExchange], symbols: &[&str]) -> Self {
+        Self {
+            venues: venues.to_vec(),
+            symbols: symbols.iter().map(|s| s.to_string()).collect(),
+            depth: 50,
+            interval_ms: 1000,
+            snapshots_per_file: 10_000,
+            duration_ms: None,
+            handle: None,
+        }
+    }
+
+    pub fn with_depth(mut self, depth: usize) -> Self {
+        self.depth = depth;
+        self
+    }
+
+    pub fn with_interval_ms(mut self, interval_ms: u64) -> Self {
+        self.interval_ms = interval_ms;
+        self
+    }
+
+    pub fn with_snapshots_per_file(mut self, snapshots_per_file: usize) -> Self {
+        self.snapshots_per_file = snapshots_per_file;
+        self
+    }
+
+    pub fn with_duration_ms(mut self, duration_ms: u64) -> Self {
+        self.duration_ms = Some(duration_ms);
+        self
+    }
+
+    pub fn with_handle(mut self, handle: ScannerHandle) -> Self {
+        self.handle = Some(handle);
+        self
+    }
+
+    pub fn validate(&self) -> Result<(), MarketScannerError> {
+        if self.venues.is_empty() || self.symbols.is_empty() {
+            return Err(MarketScannerError::InvalidConfig(
+                "Depth recorder needs at least one venue and one symbol".to_string(),
+            ));
+        }
+        if self.depth == 0 || self.snapshots_per_file == 0 {
+            return Err(MarketScannerError::InvalidConfig(format!(
+                "Depth recorder depth ({}) and snapshots per file ({}) must be positive",
+                self.depth, self.snapshots_per_file
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Outcome of a recording.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DepthRecordingSummary {
+    /// Written files, in recording order
+    pub files: Vec<PathBuf>,
+    pub snapshots: usize,
+    /// Order book requests that failed (they are skipped)
+    pub errors: usize,
+}
+
+/// Appends [DepthSnapshot]s to a gzip-compressed JSON lines file.
+pub struct DepthSnapshotWriter {
+    path: PathBuf,
+    encoder: GzEncoder<BufWriter<File>>,
+    written: usize,
+}
+
+impl DepthSnapshotWriter {
+    /// Creates (or truncates) the file at `path`.
+    pub fn create(path: impl Into<PathBuf>) -> Result<Self, MarketScannerError> {
+        let path = path.into();
+        let file = File::create(&path).map_err(|e| io_error("create", &path, e))?;
+        Ok(Self {
+            encoder: GzEncoder::new(BufWriter::new(file), Compression::default()),
+            path,
+            written: 0,
+        })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Snapshots written so far.
+    pub fn len(&self) -> usize {
+        self.written
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.written == 0
+    }
+
+    pub fn write(&mut self, snapshot: &DepthSnapshot) -> Result<(), MarketScannerError> {
+        let mut line = serde_json::to_vec(snapshot)?;
+        line.push(b'\n');
+        self.encoder
+            .write_all(&line)
+            .map_err(|e| io_error("write", &self.path, e))?;
+        self.written += 1;
+        Ok(())
+    }
+
+    /// Makes everything written so far readable, without ending the file.
+    pub fn flush(&mut self) -> Result<(), MarketScannerError> {
+        self.encoder
+            .flush()
+            .map_err(|e| io_error("write", &self.path, e))
+    }
+
+    /// Ends the gzip stream and returns the path of the file.
+    pub fn finish(self) -> Result<PathBuf, MarketScannerError> {
+        self.encoder
+            .finish()
+            .and_then(|mut writer| writer.flush())
+            .map_err(|e| io_error("write", &self.path, e))?;
+        Ok(self.path)
+    }
+}
+
+/// Reads the [DepthSnapshot]s of a recorded file in order.
+pub struct DepthSnapshotReader {
+    path: PathBuf,
+    lines: Lines<BufReader<MultiGzDecoder<File>>>,
+}
+
+impl DepthSnapshotReader {
+    pub fn open(path: impl Into<PathBuf>) -> Result<Self, MarketScannerError> {
+        let path = path.into();
+        let file = File::open(&path).map_err(|e| io_error("open", &path, e))?;
+        Ok(Self {
+            lines: BufReader::new(MultiGzDecoder::new(file)).lines(),
+            path,
+        })
+    }
+
+    /// All snapshots of the file at `path`.
+    pub fn read_all(path: impl Into<PathBuf>) -> Result<Vec<DepthSnapshot>, MarketScannerError> {
+        Self::open(path)?.collect()
+    }
+}
+
+impl Iterator for DepthSnapshotReader {
+    type Item = Result<DepthSnapshot, MarketScannerError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let line = match self.lines.next()? {
+            Ok(line) => line,
+            Err(e) => return Some(Err(io_error("read", &self.path, e))),
+        };
+        Some(serde_json::from_str(&line).map_err(MarketScannerError::from))
+    }
+}
+
+/// Recorded files in `directory`, in recording order.
+pub fn depth_recording_files(
+    directory: impl AsRef<Path>,
+) -> Result<Vec<PathBuf>, MarketScannerError> {
+    let directory = directory.as_ref();
+    let entries = std::fs::read_dir(directory).map_err(|e| io_error("read", directory, e))?;
+    let mut files: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| {
+            path.file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|name| name.ends_with(&format!(".{}", DEPTH_FILE_EXTENSION)))
+        })
+        .collect();
+    files.sort();
+    Ok(files)
+}
+
+fn io_error(action: &str, path: &Path, error: std::io::Error) -> MarketScannerError {
+    MarketScannerError::InvalidConfig(format!(
+        "Cannot {} depth file {}: {}",
+        action,
+        path.display(),
+        error
+    ))
+}
+
+impl ArbitrageScanner {
+    /// Records the order books of [DepthRecorderOptions::venues] × `symbols` into
+    /// `directory` (created if missing) until the duration elapses or the handle is
+    /// stopped. Files are named `depth-<start millis>-<index>.jsonl.gz`. Failed requests
+    /// are counted and skipped; failing to write ends the recording with an error.
+    pub async fn record_depth_snapshots(
+        options: DepthRecorderOptions,
+        directory: impl AsRef<Path>,
+    ) -> Result<DepthRecordingSummary, MarketScannerError> {
+        options.validate()?;
+        let directory = directory.as_ref();
+        std::fs::create_dir_all(directory).map_err(|e| io_error("create", directory, e))?;
+
+        let handle = options.handle.clone().unwrap_or_default();
+        let deadline = options
+            .duration_ms
+            .map(|ms| Instant::now() + Duration::from_millis(ms));
+        let started_at = Timestamp::now().as_millis();
+        let mut poll = tokio::time::interval(Duration::from_millis(options.interval_ms.max(1)));
+        poll.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+        let mut summary = DepthRecordingSummary::default();
+        let mut writer: Option<DepthSnapshotWriter> = None;
+        loop {
+            tokio::select! {
+                _ = handle.stopped() => break,
+                _ = async { tokio::time::sleep_until(deadline.expect("deadline")).await },
+                    if deadline.is_some() => break,
+                _ = poll.tick() => {}
+            }
+            if handle.is_paused() {
+                continue;
+            }
+
+            let requests = options.venues.iter().flat_map(|venue| {
+                options.symbols.iter().map(move |symbol| async move {
+                    let book = Self::get_cex_order_book(venue, symbol, options.depth).await;
+                    (book, Timestamp::now())
+                })
+            });
+            for (book, captured_at) in join_all(requests).await {
+                let Ok(book) = book else {
+                    summary.errors += 1;
+                    continue;
+                };
+                if writer
+                    .as_ref()
+                    .is_some_and(|w| w.len() >= options.snapshots_per_file)
+                {
+                    let full = writer.take().expect("writer");
+                    summary.files.push(full.finish()?);
+                }
+                let current = match writer.as_mut() {
+                    Some(current) => current,
+                    None => writer.insert(DepthSnapshotWriter::create(directory.join(format!(
+                        "depth-{}-{:05}.{}",
+                        started_at,
+                        summary.files.len(),
+                        DEPTH_FILE_EXTENSION
+                    )))?),
+                };
+                current.write(&DepthSnapshot { captured_at, book })?;
+                summary.snapshots += 1;
+            }
+            if let Some(current) = writer.as_mut() {
+                current.flush()?;
+            }
+        }
+        if let Some(current) = writer {
+            summary.files.push(current.finish()?);
+        }
+        Ok(summary)
+    }
+}
@@ -0,0 +1,114 @@
+//! Expiry of reported opportunities.
+//!
+//! Sinks and streams receive opportunities while a spread lasts but nothing when it
+//! collapses. [OpportunityExpiry] follows the opportunities of successive scans (by
+//! [ArbitrageOpportunity::key]) and reports an [OpportunityClosed] once one has gone
+//! unseen for the expiry, so downstream automation can cancel pending actions.
+//! [crate::scanner::ScannerContext::with_expiry] hands the closes to the context's close
+//! sinks and emits them on its live stream as [crate::scanner::ScannerEvent::OpportunityClosed].
+
+use crate::common::Timestamp;
+use crate::scanner::ArbitrageOpportunity;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// Notification that a reported opportunity is gone.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OpportunityClosed {
+    /// Last observation of the opportunity
+    pub opportunity: ArbitrageOpportunity,
+    pub opened_at: Timestamp,
+    pub last_seen_at: Timestamp,
+    pub closed_at: Timestamp,
+    /// How long the opportunity was seen (last seen − opened), in milliseconds
+    pub duration_ms: u64,
+    /// Highest spread percentage observed while open
+    pub max_spread_percentage: f64,
+}
+
+#[derive(Debug, Clone)]
+struct Tracked {
+    latest: ArbitrageOpportunity,
+    opened_at: Timestamp,
+    last_seen_at: Timestamp,
+    max_spread_percentage: f64,
+}
+
+/// Tracks reported opportunities until they expire. Cheap to clone; clones share state.
+#[derive(Debug, Clone)]
+pub struct OpportunityExpiry {
+    expiry: Duration,
+    tracked: Arc<Mutex<HashMap<String, Tracked>>>,
+}
+
+impl OpportunityExpiry {
+    /// Opportunities unseen for `expiry` are closed (with a zero expiry, on the
+    /// first scan without them).
+    pub fn new(expiry: Duration) -> Self {
+        Self {
+            expiry,
+            tracked: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    pub fn expiry(&self) -> Duration {
+        self.expiry
+    }
+
+    /// Records the opportunities of a scan at `now` and returns the ones that expired,
+    /// oldest first.
+    pub fn observe(
+        &self,
+        opportunities: &[ArbitrageOpportunity],
+        now: Timestamp,
+    ) -> Vec<OpportunityClosed> {
+        let mut tracked = self.tracked.lock().unwrap_or_else(|e| e.into_inner());
+        let mut seen = HashSet::with_capacity(opportunities.len());
+        for opportunity in opportunities {
+            seen.insert(opportunity.key());
+            let spread = opportunity.spread_percentage;
+            tracked
+                .entry(opportunity.key())
+                .and_modify(|t| {
+                    t.latest = opportunity.clone();
+                    t.last_seen_at = t.last_seen_at.max(now);
+                    t.max_spread_percentage = t.max_spread_percentage.max(spread);
+                })
+                .or_insert_with(|| Tracked {
+                    latest: opportunity.clone(),
+                    opened_at: now,
+                    last_seen_at: now,
+                    max_spread_percentage: spread,
+                });
+        }
+
+        let expired: Vec<String> = tracked
+            .iter()
+            .filter(|(key, t)| {
+                !seen.contains(*key) && now.duration_since(t.last_seen_at) >= self.expiry
+            })
+            .map(|(key, _)| key.clone())
+            .collect();
+        let mut closed: Vec<OpportunityClosed> = expired
+            .into_iter()
+            .filter_map(|key| tracked.remove(&key))
+            .map(|t| OpportunityClosed {
+                duration_ms: t.last_seen_at.duration_since(t.opened_at).as_millis() as u64,
+                opportunity: t.latest,
+                opened_at: t.opened_at,
+                last_seen_at: t.last_seen_at,
+                closed_at: now,
+                max_spread_percentage: t.max_spread_percentage,
+            })
+            .collect();
+        closed.sort_by_key(|c| c.opened_at);
+        closed
+    }
+
+    /// Number of opportunities currently tracked as open.
+    pub fn open_count(&self) -> usize {
+        self.tracked.lock().unwrap_or_else(|e| e.into_inner()).len()
+    }
+}
@@ -0,0 +1,147 @@
+//! Parallel DEX quoting across chains.
+//!
+//! Quoting many pairs on several chains and aggregators at once trips Cloudflare and the
+//! aggregators' own rate limits. [ChainFanOut] caps how many quotes run at the same time
+//! per chain and sets a request budget per aggregator that is shared by every chain (the
+//! aggregator's venue limit, see [crate::common::set_rate_limit]).
+//! [ArbitrageScanner::fetch_dex_prices_across_chains] fans out with it.
+
+use crate::common::{DexAggregator, DexPrice, Exchange, MarketScannerError, set_rate_limit};
+use crate::dex::chains::{ChainId, Token};
+use crate::scanner::ArbitrageScanner;
+use futures::future::join_all;
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Semaphore;
+
+/// Concurrent quotes per chain unless set with [ChainFanOut::with_chain_concurrency].
+pub const DEFAULT_CHAIN_CONCURRENCY: usize = 4;
+
+/// Concurrency limits per chain and request budgets per aggregator for multi-chain scans.
+#[derive(Debug, Clone)]
+pub struct ChainFanOut {
+    default_concurrency: usize,
+    chain_concurrency: HashMap<ChainId, usize>,
+    aggregator_budgets: HashMap<DexAggregator, (u32, Duration)>,
+}
+
+impl Default for ChainFanOut {
+    fn default() -> Self {
+        Self {
+            default_concurrency: DEFAULT_CHAIN_CONCURRENCY,
+            chain_concurrency: HashMap::new(),
+            aggregator_budgets: HashMap::new(),
+        }
+    }
+}
+
+impl ChainFanOut {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Concurrent quotes on chains without their own limit (at least 1).
+    pub fn with_default_concurrency(mut self, limit: usize) -> Self {
+        self.default_concurrency = limit.max(1);
+        self
+    }
+
+    /// Concurrent quotes on `chain` (at least 1).
+    pub fn with_chain_concurrency(mut self, chain: ChainId, limit: usize) -> Self {
+        self.chain_concurrency.insert(chain, limit.max(1));
+        self
+    }
+
+    /// At most `max_requests` requests to `aggregator` per `per`, over all chains.
+    pub fn with_aggregator_budget(
+        mut self,
+        aggregator: DexAggregator,
+        max_requests: u32,
+        per: Duration,
+    ) -> Self {
+        self.aggregator_budgets
+            .insert(aggregator, (max_requests, per));
+        self
+    }
+
+    /// Concurrent quotes allowed on `chain`.
+    pub fn concurrency(&self, chain: &ChainId) -> usize {
+        self.chain_concurrency
+            .get(chain)
+            .copied()
+            .unwrap_or(self.default_concurrency)
+    }
+
+    /// Applies the aggregator budgets as venue rate limits. Called by [ChainFanOut::run].
+    pub fn apply_aggregator_budgets(&self) {
+        for (aggregator, (max_requests, per)) in &self.aggregator_budgets {
+            let venue = ArbitrageScanner::exchange_name(&Exchange::Dex(aggregator.clone()));
+            set_rate_limit(&venue, *max_requests, *per);
+        }
+    }
+
+    /// Runs every job concurrently, at most [ChainFanOut::concurrency] at a time per chain,
+    /// and returns their outputs in input order.
+    pub async fn run<F>(&self, jobs: Vec<(ChainId, F)>) -> Vec<F::Output>
+    where
+        F: Future,
+    {
+        self.apply_aggregator_budgets();
+        let mut semaphores: HashMap<ChainId, Arc<Semaphore>> = HashMap::new();
+        let jobs = jobs.into_iter().map(|(chain, job)| {
+            let semaphore = semaphores
+                .entry(chain.clone())
+                .or_insert_with(|| Arc::new(Semaphore::new(self.concurrency(&chain))))
+                .clone();
+            async move {
+                // The semaphore is never closed
+                let _permit = semaphore.acquire_owned().await.ok();
+                job.await
+            }
+        });
+        join_all(jobs.collect::<Vec<_>>()).await
+    }
+}
+
+/// Quote of one pair on one aggregator in a multi-chain scan.
+#[derive(Debug)]
+pub struct ChainDexQuote {
+    pub chain: ChainId,
+    pub aggregator: DexAggregator,
+    pub base_token: Token,
+    pub quote_token: Token,
+    pub result: Result<DexPrice, MarketScannerError>,
+}
+
+impl ArbitrageScanner {
+    /// Quotes every `(base, quote)` pair of `pairs` (each on its own chain) on every
+    /// aggregator of `aggregators` for `quote_amount`, limited by `fan_out`. Results follow
+    /// the order of `pairs`, then `aggregators`.
+    pub async fn fetch_dex_prices_across_chains(
+        pairs: &[(Token, Token)],
+        aggregators: &[DexAggregator],
+        quote_amount: f64,
+        fan_out: &ChainFanOut,
+    ) -> Vec<ChainDexQuote> {
+        let jobs = pairs
+            .iter()
+            .flat_map(|(base, quote)| {
+                aggregators.iter().map(move |aggregator| {
+                    (base.chain_id.clone(), async move {
+                        ChainDexQuote {
+                            chain: base.chain_id.clone(),
+                            aggregator: aggregator.clone(),
+                            base_token: base.clone(),
+                            quote_token: quote.clone(),
+                            result: Self::get_dex_price(aggregator, base, quote, quote_amount)
+                                .await,
+                        }
+                    })
+                })
+            })
+            .collect();
+        fan_out.run(jobs).await
+    }
+}
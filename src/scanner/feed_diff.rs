@@ -0,0 +1,310 @@
+//! REST vs WebSocket feed comparison.
+//!
+//! [ArbitrageScanner::record_feed_diff] streams a venue's WebSocket prices for a symbol
+//! while polling its REST price, and compares every REST quote with the latest WebSocket
+//! quote received before it. The resulting [FeedDiffReport] has divergence statistics
+//! (price differences in basis points, WebSocket staleness, exchange timestamp lag, REST
+//! latency) and a [FeedDiffReport::preferred_feed] suggestion, to decide which feed to
+//! trust per venue. [FeedDiffRecorder] does the bookkeeping and can be fed directly.
+
+use crate::common::{CexExchange, CexPrice, MarketScannerError, Timestamp};
+use crate::scanner::{ArbitrageScanner, VenueFeed};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+use tokio::time::{Instant, MissedTickBehavior};
+
+/// Settings of [ArbitrageScanner::record_feed_diff].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FeedDiffOptions {
+    /// How long to record (milliseconds)
+    pub duration_ms: u64,
+    /// Delay between REST polls (milliseconds)
+    pub rest_interval_ms: u64,
+    /// Mid price difference above which a comparison counts as diverged (basis points)
+    pub divergence_threshold_bps: f64,
+    /// Keep every comparison in [FeedDiffReport::samples]
+    pub keep_samples: bool,
+}
+
+impl Default for FeedDiffOptions {
+    fn default() -> Self {
+        Self {
+            duration_ms: 60_000,
+            rest_interval_ms: 1_000,
+            divergence_threshold_bps: 5.0,
+            keep_samples: false,
+        }
+    }
+}
+
+impl FeedDiffOptions {
+    pub fn with_duration_ms(mut self, duration_ms: u64) -> Self {
+        self.duration_ms = duration_ms;
+        self
+    }
+
+    pub fn with_rest_interval_ms(mut self, rest_interval_ms: u64) -> Self {
+        self.rest_interval_ms = rest_interval_ms;
+        self
+    }
+
+    pub fn with_divergence_threshold_bps(mut self, divergence_threshold_bps: f64) -> Self {
+        self.divergence_threshold_bps = divergence_threshold_bps;
+        self
+    }
+
+    pub fn with_samples(mut self, keep_samples: bool) -> Self {
+        self.keep_samples = keep_samples;
+        self
+    }
+}
+
+/// One REST quote compared with the latest WebSocket quote.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FeedDiffSample {
+    pub rest: CexPrice,
+    pub websocket: CexPrice,
+    /// (REST − WebSocket) / WebSocket, in basis points
+    pub mid_diff_bps: f64,
+    pub bid_diff_bps: f64,
+    pub ask_diff_bps: f64,
+    /// Time since the WebSocket quote was received when the REST quote arrived
+    pub websocket_age_ms: u64,
+    /// REST minus WebSocket exchange timestamp; positive when REST carried newer data
+    pub timestamp_lag_ms: i64,
+    /// REST request round trip
+    pub rest_latency_ms: u64,
+}
+
+/// Divergence statistics of a venue's REST and WebSocket feeds for one symbol.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FeedDiffReport {
+    pub exchange: CexExchange,
+    pub symbol: String,
+    pub started_at: Timestamp,
+    pub finished_at: Timestamp,
+    pub websocket_updates: u64,
+    pub rest_samples: u64,
+    pub rest_errors: u64,
+    /// REST quotes that had a WebSocket quote to compare with
+    pub compared: u64,
+    /// Comparisons whose mid price differed by more than the divergence threshold
+    pub diverged: u64,
+    pub mean_abs_mid_diff_bps: f64,
+    pub max_abs_mid_diff_bps: f64,
+    pub mean_abs_bid_diff_bps: f64,
+    pub mean_abs_ask_diff_bps: f64,
+    pub mean_websocket_age_ms: f64,
+    pub max_websocket_age_ms: u64,
+    pub mean_timestamp_lag_ms: f64,
+    pub mean_rest_latency_ms: f64,
+    /// Every comparison, when recorded with [FeedDiffOptions::with_samples]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub samples: Vec<FeedDiffSample>,
+}
+
+impl FeedDiffReport {
+    /// Share of comparisons that diverged (0..=1).
+    pub fn divergence_rate(&self) -> f64 {
+        if self.compared == 0 {
+            0.0
+        } else {
+            self.diverged as f64 / self.compared as f64
+        }
+    }
+
+    /// Feed that delivered fresher data: REST when the WebSocket sent nothing, or when REST
+    /// quotes were on average newer than the latest WebSocket quote by more than a REST
+    /// round trip; the WebSocket otherwise. `None` without REST data.
+    pub fn preferred_feed(&self) -> Option<VenueFeed> {
+        if self.rest_samples == 0 {
+            return (self.websocket_updates > 0).then_some(VenueFeed::WebSocket);
+        }
+        if self.websocket_updates == 0
+            || self.mean_timestamp_lag_ms > self.mean_rest_latency_ms.max(1.0)
+        {
+            Some(VenueFeed::RestPolling)
+        } else {
+            Some(VenueFeed::WebSocket)
+        }
+    }
+}
+
+/// Accumulates REST and WebSocket quotes into a [FeedDiffReport].
+#[derive(Debug, Clone)]
+pub struct FeedDiffRecorder {
+    exchange: CexExchange,
+    symbol: String,
+    options: FeedDiffOptions,
+    started_at: Timestamp,
+    latest_websocket: Option<(CexPrice, Timestamp)>,
+    websocket_updates: u64,
+    rest_samples: u64,
+    rest_errors: u64,
+    diverged: u64,
+    sum_mid: f64,
+    sum_bid: f64,
+    sum_ask: f64,
+    max_mid: f64,
+    sum_age_ms: u64,
+    max_age_ms: u64,
+    sum_lag_ms: i64,
+    sum_latency_ms: u64,
+    compared: u64,
+    samples: Vec<FeedDiffSample>,
+}
+
+fn diff_bps(rest: f64, websocket: f64) -> f64 {
+    if websocket > 0.0 {
+        (rest - websocket) / websocket * 10_000.0
+    } else {
+        0.0
+    }
+}
+
+impl FeedDiffRecorder {
+    pub fn new(exchange: CexExchange, symbol: &str, options: FeedDiffOptions) -> Self {
+        Self {
+            exchange,
+            symbol: symbol.to_string(),
+            options,
+            started_at: Timestamp::now(),
+            latest_websocket: None,
+            websocket_updates: 0,
+            rest_samples: 0,
+            rest_errors: 0,
+            diverged: 0,
+            sum_mid: 0.0,
+            sum_bid: 0.0,
+            sum_ask: 0.0,
+            max_mid: 0.0,
+            sum_age_ms: 0,
+            max_age_ms: 0,
+            sum_lag_ms: 0,
+            sum_latency_ms: 0,
+            compared: 0,
+            samples: Vec::new(),
+        }
+    }
+
+    /// WebSocket quote received at `received_at`.
+    pub fn record_websocket(&mut self, price: CexPrice, received_at: Timestamp) {
+        self.websocket_updates += 1;
+        self.latest_websocket = Some((price, received_at));
+    }
+
+    /// REST quote requested at `requested_at` and received at `received_at`; returns the
+    /// comparison if a WebSocket quote was available.
+    pub fn record_rest(
+        &mut self,
+        price: CexPrice,
+        requested_at: Timestamp,
+        received_at: Timestamp,
+    ) -> Option<FeedDiffSample> {
+        self.rest_samples += 1;
+        let rest_latency_ms = received_at.duration_since(requested_at).as_millis() as u64;
+        self.sum_latency_ms += rest_latency_ms;
+        let (websocket, websocket_received_at) = self.latest_websocket.clone()?;
+
+        let sample = FeedDiffSample {
+            mid_diff_bps: diff_bps(price.mid_price, websocket.mid_price),
+            bid_diff_bps: diff_bps(price.bid_price, websocket.bid_price),
+            ask_diff_bps: diff_bps(price.ask_price, websocket.ask_price),
+            websocket_age_ms: received_at
+                .duration_since(websocket_received_at)
+                .as_millis() as u64,
+            timestamp_lag_ms: price.timestamp.as_millis() as i64
+                - websocket.timestamp.as_millis() as i64,
+            rest_latency_ms,
+            rest: price,
+            websocket,
+        };
+        self.compared += 1;
+        self.sum_mid += sample.mid_diff_bps.abs();
+        self.sum_bid += sample.bid_diff_bps.abs();
+        self.sum_ask += sample.ask_diff_bps.abs();
+        self.max_mid = self.max_mid.max(sample.mid_diff_bps.abs());
+        self.sum_age_ms += sample.websocket_age_ms;
+        self.max_age_ms = self.max_age_ms.max(sample.websocket_age_ms);
+        self.sum_lag_ms += sample.timestamp_lag_ms;
+        if sample.mid_diff_bps.abs() > self.options.divergence_threshold_bps {
+            self.diverged += 1;
+        }
+        if self.options.keep_samples {
+            self.samples.push(sample.clone());
+        }
+        Some(sample)
+    }
+
+    pub fn record_rest_error(&mut self) {
+        self.rest_errors += 1;
+    }
+
+    pub fn report(&self) -> FeedDiffReport {
+        let compared = self.compared.max(1) as f64;
+        FeedDiffReport {
+            exchange: self.exchange.clone(),
+            symbol: self.symbol.clone(),
+            started_at: self.started_at,
+            finished_at: Timestamp::now(),
+            websocket_updates: self.websocket_updates,
+            rest_samples: self.rest_samples,
+            rest_errors: self.rest_errors,
+            compared: self.compared,
+            diverged: self.diverged,
+            mean_abs_mid_diff_bps: self.sum_mid / compared,
+            max_abs_mid_diff_bps: self.max_mid,
+            mean_abs_bid_diff_bps: self.sum_bid / compared,
+            mean_abs_ask_diff_bps: self.sum_ask / compared,
+            mean_websocket_age_ms: self.sum_age_ms as f64 / compared,
+            max_websocket_age_ms: self.max_age_ms,
+            mean_timestamp_lag_ms: self.sum_lag_ms as f64 / compared,
+            mean_rest_latency_ms: self.sum_latency_ms as f64 / self.rest_samples.max(1) as f64,
+            samples: self.samples.clone(),
+        }
+    }
+}
+
+impl ArbitrageScanner {
+    /// Runs the WebSocket stream and REST polling of `exchange` for `symbol` side by side
+    /// for [FeedDiffOptions::duration_ms] and reports how the two feeds diverged.
+    pub async fn record_feed_diff(
+        exchange: &CexExchange,
+        symbol: &str,
+        options: FeedDiffOptions,
+    ) -> Result<FeedDiffReport, MarketScannerError> {
+        if !Self::exchange_supports_websocket(exchange) {
+            return Err(MarketScannerError::ApiError(format!(
+                "{:?} does not support WebSocket streaming",
+                exchange
+            )));
+        }
+        let mut websocket = Self::stream_cex_prices_websocket(exchange, &[symbol], 3, 1000).await?;
+        let mut recorder = FeedDiffRecorder::new(exchange.clone(), symbol, options.clone());
+        let deadline = Instant::now() + Duration::from_millis(options.duration_ms);
+        let mut poll =
+            tokio::time::interval(Duration::from_millis(options.rest_interval_ms.max(1)));
+        poll.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        let mut websocket_open = true;
+
+        loop {
+            tokio::select! {
+                _ = tokio::time::sleep_until(deadline) => break,
+                price = websocket.recv(), if websocket_open => match price {
+                    Some(price) => recorder.record_websocket(price, Timestamp::now()),
+                    None => websocket_open = false,
+                },
+                _ = poll.tick() => {
+                    let requested_at = Timestamp::now();
+                    match Self::get_cex_price(exchange, symbol).await {
+                        Ok(price) => {
+                            recorder.record_rest(price, requested_at, Timestamp::now());
+                        }
+                        Err(_) => recorder.record_rest_error(),
+                    }
+                }
+            }
+        }
+        Ok(recorder.report())
+    }
+}
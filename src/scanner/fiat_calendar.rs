@@ -0,0 +1,239 @@
+//! Market-hours and holiday awareness for fiat-quoted markets.
+//!
+//! Fiat rails close outside banking hours and on banking holidays, and venues throttle fiat
+//! deposits and withdrawals then (BTCTurk TRY, Upbit KRW). Spreads on those markets widen
+//! without being any easier to realize. A [FiatCalendar] flags such periods per quote
+//! currency (optionally per venue), from fixed periods, weekly banking hours or a custom
+//! hook, and annotates opportunities with a CEX leg quoted in a flagged currency with a
+//! [FiatRestriction], optionally dampening their executable quantity.
+
+use crate::common::{CexExchange, Exchange, Timestamp, logical_market_key};
+use crate::scanner::{ArbitrageOpportunity, PriceData};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::sync::Arc;
+
+const MINUTE_MS: i64 = 60_000;
+const DAY_MINUTES: i64 = 24 * 60;
+
+/// Custom calendar: returns the reason if `quote` is restricted on the venue at the time.
+pub type FiatCalendarHook =
+    Arc<dyn Fn(&CexExchange, &str, Timestamp) -> Option<String> + Send + Sync>;
+
+/// Flagged period of a fiat quote currency an opportunity falls into.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FiatRestriction {
+    /// Venue of the restricted leg
+    pub exchange: CexExchange,
+    /// Quote currency (e.g. "TRY")
+    pub quote: String,
+    pub reason: String,
+    /// Factor applied to the executable quantity (1.0 = annotated only)
+    pub dampening: f64,
+}
+
+#[derive(Debug, Clone)]
+struct Holiday {
+    quote: String,
+    exchange: Option<CexExchange>,
+    start: Timestamp,
+    end: Timestamp,
+    reason: String,
+}
+
+#[derive(Debug, Clone)]
+struct BankingHours {
+    quote: String,
+    utc_offset_minutes: i32,
+    open_minute: u32,
+    close_minute: u32,
+}
+
+/// Restricted periods of fiat quote currencies.
+#[derive(Clone)]
+pub struct FiatCalendar {
+    holidays: Vec<Holiday>,
+    banking_hours: Vec<BankingHours>,
+    hooks: Vec<FiatCalendarHook>,
+    dampening: f64,
+}
+
+impl Default for FiatCalendar {
+    fn default() -> Self {
+        Self {
+            holidays: Vec::new(),
+            banking_hours: Vec::new(),
+            hooks: Vec::new(),
+            dampening: 1.0,
+        }
+    }
+}
+
+impl fmt::Debug for FiatCalendar {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FiatCalendar")
+            .field("holidays", &self.holidays)
+            .field("banking_hours", &self.banking_hours)
+            .field("hooks", &self.hooks.len())
+            .field("dampening", &self.dampening)
+            .finish()
+    }
+}
+
+impl FiatCalendar {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Flags `quote` on every venue from `start` (inclusive) to `end` (exclusive).
+    pub fn with_holiday(
+        mut self,
+        quote: &str,
+        start: Timestamp,
+        end: Timestamp,
+        reason: &str,
+    ) -> Self {
+        self.holidays.push(Holiday {
+            quote: quote.to_uppercase(),
+            exchange: None,
+            start,
+            end,
+            reason: reason.to_string(),
+        });
+        self
+    }
+
+    /// Flags `quote` on `exchange` only from `start` (inclusive) to `end` (exclusive).
+    pub fn with_venue_holiday(
+        mut self,
+        exchange: CexExchange,
+        quote: &str,
+        start: Timestamp,
+        end: Timestamp,
+        reason: &str,
+    ) -> Self {
+        self.holidays.push(Holiday {
+            quote: quote.to_uppercase(),
+            exchange: Some(exchange),
+            start,
+            end,
+            reason: reason.to_string(),
+        });
+        self
+    }
+
+    /// Banking hours of `quote`: open Monday to Friday from `open_minute` to `close_minute`
+    /// (minutes after local midnight) at `utc_offset_minutes` (e.g. 180 for Istanbul);
+    /// flagged outside of them.
+    pub fn with_banking_hours(
+        mut self,
+        quote: &str,
+        utc_offset_minutes: i32,
+        open_minute: u32,
+        close_minute: u32,
+    ) -> Self {
+        self.banking_hours.push(BankingHours {
+            quote: quote.to_uppercase(),
+            utc_offset_minutes,
+            open_minute,
+            close_minute,
+        });
+        self
+    }
+
+    /// Custom calendar consulted after the holidays and banking hours, e.g. backed by a
+    /// holiday API. Receives the venue, the uppercase quote currency and the time.
+    pub fn with_hook<F>(mut self, hook: F) -> Self
+    where
+        F: Fn(&CexExchange, &str, Timestamp) -> Option<String> + Send + Sync + 'static,
+    {
+        self.hooks.push(Arc::new(hook));
+        self
+    }
+
+    /// Factor (0.0–1.0) applied to the executable quantity of restricted opportunities;
+    /// 1.0 (the default) only annotates them.
+    pub fn with_dampening(mut self, dampening: f64) -> Self {
+        self.dampening = dampening.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Reason `quote` is restricted on `exchange` at `at`, if it is.
+    pub fn restriction_reason(
+        &self,
+        exchange: &CexExchange,
+        quote: &str,
+        at: Timestamp,
+    ) -> Option<String> {
+        let quote = quote.to_uppercase();
+        let holiday = self.holidays.iter().find(|h| {
+            h.quote == quote
+                && h.exchange.as_ref().is_none_or(|e| e == exchange)
+                && h.start <= at
+                && at < h.end
+        });
+        if let Some(holiday) = holiday {
+            return Some(holiday.reason.clone());
+        }
+        if let Some(hours) = self
+            .banking_hours
+            .iter()
+            .find(|h| h.quote == quote && !h.is_open(at))
+        {
+            return Some(format!("{} banking hours closed", hours.quote));
+        }
+        self.hooks
+            .iter()
+            .find_map(|hook| hook(exchange, &quote, at))
+    }
+
+    /// Restriction of the first CEX leg of `opportunity` whose quote currency is flagged
+    /// at `at`.
+    pub fn restriction(
+        &self,
+        opportunity: &ArbitrageOpportunity,
+        at: Timestamp,
+    ) -> Option<FiatRestriction> {
+        [&opportunity.source_leg, &opportunity.destination_leg]
+            .into_iter()
+            .find_map(|leg| {
+                let PriceData::Cex(price) = leg else {
+                    return None;
+                };
+                let Exchange::Cex(exchange) = &price.exchange else {
+                    return None;
+                };
+                let quote = logical_market_key(&price.symbol, &price.exchange).quote;
+                let reason = self.restriction_reason(exchange, &quote, at)?;
+                Some(FiatRestriction {
+                    exchange: exchange.clone(),
+                    quote,
+                    reason,
+                    dampening: self.dampening,
+                })
+            })
+    }
+
+    /// Sets [ArbitrageOpportunity::fiat_restriction] on every opportunity and scales the
+    /// executable quantity (and commission) of restricted ones by the dampening factor.
+    pub fn annotate_all(&self, opportunities: &mut [ArbitrageOpportunity], at: Timestamp) {
+        for opportunity in opportunities {
+            opportunity.fiat_restriction = self.restriction(opportunity, at);
+            if let Some(restriction) = &opportunity.fiat_restriction {
+                opportunity.executable_quantity *= restriction.dampening;
+                opportunity.total_commission_quote *= restriction.dampening;
+            }
+        }
+    }
+}
+
+impl BankingHours {
+    fn is_open(&self, at: Timestamp) -> bool {
+        let local_minutes = at.as_millis() as i64 / MINUTE_MS + self.utc_offset_minutes as i64;
+        let days = local_minutes.div_euclid(DAY_MINUTES);
+        let minute = local_minutes.rem_euclid(DAY_MINUTES) as u32;
+        // 1970-01-01 was a Thursday: 0 = Monday
+        let weekday = (days + 3).rem_euclid(7);
+        weekday < 5 && self.open_minute <= minute && minute < self.close_minute
+    }
+}
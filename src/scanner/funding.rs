@@ -0,0 +1,199 @@
+//! Funding / transfer time model used to classify whether an opportunity can be executed.
+//!
+//! Given the balances a user declares per venue and a table of typical transfer times per
+//! asset (confirmation + withdrawal processing), [FundingModel::classify] decides whether
+//! both legs are pre-funded ([OpportunityViability::Instant]), whether inventory has to be
+//! moved first ([OpportunityViability::TransferRequired]) or whether it cannot be funded in
+//! time ([OpportunityViability::NotViable]). With a [TransferStatusMonitor], transfers that
+//! need a suspended deposit or withdrawal make it [OpportunityViability::TransfersSuspended].
+
+use crate::common::{Exchange, MarketKey, logical_market_key};
+use crate::scanner::transfer_status::TransferStatusMonitor;
+use crate::scanner::{ArbitrageOpportunity, PriceData};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Typical deposit-to-credit times in seconds (network confirmations + venue processing).
+const DEFAULT_TRANSFER_TIMES_SECS: &[(&str, u64)] = &[
+    ("BTC", 3600),
+    ("ETH", 600),
+    ("USDT", 600),
+    ("USDC", 600),
+    ("BNB", 300),
+    ("SOL", 120),
+    ("XRP", 120),
+    ("TRX", 180),
+    ("LTC", 1800),
+    ("DOGE", 1800),
+];
+
+/// Default longest transfer that still keeps an opportunity viable (15 minutes).
+const DEFAULT_MAX_TRANSFER_SECS: u64 = 900;
+
+/// Whether an opportunity can be executed with the declared balances.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum OpportunityViability {
+    /// Both legs are pre-funded: quote on the source venue, base on the destination venue
+    Instant,
+    /// Inventory must be moved first; `estimated_secs` is the slowest required transfer
+    TransferRequired {
+        assets: Vec<String>,
+        estimated_secs: u64,
+    },
+    /// Missing inventory cannot be transferred in time (or is not held anywhere)
+    NotViable,
+    /// Inventory must be moved, but deposits on the receiving venue or withdrawals on
+    /// every venue holding enough of these assets are suspended
+    TransfersSuspended { assets: Vec<String> },
+}
+
+/// User-declared balances plus per-asset transfer time estimates.
+#[derive(Debug, Clone)]
+pub struct FundingModel {
+    balances: HashMap<(Exchange, String), f64>,
+    transfer_times_secs: HashMap<String, u64>,
+    max_transfer_secs: u64,
+    transfer_status: Option<TransferStatusMonitor>,
+}
+
+impl Default for FundingModel {
+    fn default() -> Self {
+        Self {
+            balances: HashMap::new(),
+            transfer_times_secs: DEFAULT_TRANSFER_TIMES_SECS
+                .iter()
+                .map(|(asset, secs)| (asset.to_string(), *secs))
+                .collect(),
+            max_transfer_secs: DEFAULT_MAX_TRANSFER_SECS,
+            transfer_status: None,
+        }
+    }
+}
+
+impl FundingModel {
+    /// Declares `amount` of `asset` available on `exchange`.
+    pub fn with_balance(mut self, exchange: Exchange, asset: &str, amount: f64) -> Self {
+        self.balances
+            .insert((exchange, asset.to_uppercase()), amount);
+        self
+    }
+
+    /// Overrides the typical transfer time of `asset`.
+    pub fn with_transfer_time_secs(mut self, asset: &str, secs: u64) -> Self {
+        self.transfer_times_secs.insert(asset.to_uppercase(), secs);
+        self
+    }
+
+    /// Longest transfer that still counts as viable.
+    pub fn with_max_transfer_secs(mut self, secs: u64) -> Self {
+        self.max_transfer_secs = secs;
+        self
+    }
+
+    /// Checks required transfers against the deposit / withdrawal status of `monitor`.
+    pub fn with_transfer_status(mut self, monitor: TransferStatusMonitor) -> Self {
+        self.transfer_status = Some(monitor);
+        self
+    }
+
+    pub fn balance(&self, exchange: &Exchange, asset: &str) -> f64 {
+        self.balances
+            .get(&(exchange.clone(), asset.to_uppercase()))
+            .copied()
+            .unwrap_or(0.0)
+    }
+
+    pub fn transfer_time_secs(&self, asset: &str) -> Option<u64> {
+        self.transfer_times_secs.get(&asset.to_uppercase()).copied()
+    }
+
+    /// Classifies `opportunity` for its full executable quantity.
+    pub fn classify(&self, opportunity: &ArbitrageOpportunity) -> OpportunityViability {
+        let (source, source_symbol, ask) = leg_info(&opportunity.source_leg, true);
+        let (destination, _, _) = leg_info(&opportunity.destination_leg, false);
+        let MarketKey { base, quote } = logical_market_key(source_symbol, source);
+        let quantity = opportunity.executable_quantity;
+
+        // Buy leg needs quote on the source venue, sell leg needs base on the destination
+        let needs = [
+            (source, quote, quantity * ask),
+            (destination, base, quantity),
+        ];
+
+        let mut assets = Vec::new();
+        let mut suspended = Vec::new();
+        let mut estimated_secs = 0;
+        for (venue, asset, required) in needs {
+            let shortfall = required - self.balance(venue, &asset);
+            if shortfall <= 0.0 {
+                continue;
+            }
+            let held_elsewhere = |withdrawable_only: bool| -> f64 {
+                self.balances
+                    .iter()
+                    .filter(|((ex, a), _)| ex != venue && *a == asset)
+                    .filter(|((ex, a), _)| !withdrawable_only || self.withdrawals_enabled(ex, a))
+                    .map(|(_, amount)| amount.max(0.0))
+                    .sum()
+            };
+            match self.transfer_time_secs(&asset) {
+                Some(secs)
+                    if held_elsewhere(false) >= shortfall && secs <= self.max_transfer_secs =>
+                {
+                    if !self.deposits_enabled(venue, &asset) || held_elsewhere(true) < shortfall {
+                        suspended.push(asset);
+                        continue;
+                    }
+                    estimated_secs = estimated_secs.max(secs);
+                    assets.push(asset);
+                }
+                _ => return OpportunityViability::NotViable,
+            }
+        }
+
+        if !suspended.is_empty() {
+            OpportunityViability::TransfersSuspended { assets: suspended }
+        } else if assets.is_empty() {
+            OpportunityViability::Instant
+        } else {
+            OpportunityViability::TransferRequired {
+                assets,
+                estimated_secs,
+            }
+        }
+    }
+
+    fn deposits_enabled(&self, exchange: &Exchange, asset: &str) -> bool {
+        self.transfer_status
+            .as_ref()
+            .is_none_or(|monitor| monitor.deposits_enabled(exchange, asset))
+    }
+
+    fn withdrawals_enabled(&self, exchange: &Exchange, asset: &str) -> bool {
+        self.transfer_status
+            .as_ref()
+            .is_none_or(|monitor| monitor.withdrawals_enabled(exchange, asset))
+    }
+
+    /// Sets [ArbitrageOpportunity::viability] on every opportunity.
+    pub fn classify_all(&self, opportunities: &mut [ArbitrageOpportunity]) {
+        for opportunity in opportunities {
+            opportunity.viability = Some(self.classify(opportunity));
+        }
+    }
+}
+
+fn leg_info(leg: &PriceData, buy: bool) -> (&Exchange, &str, f64) {
+    match leg {
+        PriceData::Cex(p) => (
+            &p.exchange,
+            p.symbol.as_str(),
+            if buy { p.ask_price } else { p.bid_price },
+        ),
+        PriceData::Dex(p) => (
+            &p.exchange,
+            p.symbol.as_str(),
+            if buy { p.ask_price } else { p.bid_price },
+        ),
+    }
+}
@@ -0,0 +1,184 @@
+//! Venue-pair spread matrices for dashboards.
+//!
+//! A [SpreadHeatmap] holds the current effective spread (net of taker fees) between every
+//! pair of venues quoting a symbol: rows are the venue bought on, columns the venue sold
+//! on. It serializes to JSON as is, so a dashboard can draw a heatmap without recomputing
+//! it from opportunities. [LivePriceCache] shares the live scanner's latest prices (see
+//! [crate::scanner::LiveScanOptions::with_price_cache]) so heatmaps can be built on demand.
+
+use crate::common::{
+    AmountSide, BoundedCache, CacheLimits, CacheStats, CexPrice, Exchange, FeeOverrides, MarketKey,
+    Timestamp, effective_price_with_overrides, logical_market_key,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+
+/// N×N effective spreads between the venues quoting a symbol.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SpreadHeatmap {
+    pub symbol: String,
+    /// Row and column order, sorted by name
+    pub venues: Vec<Exchange>,
+    /// `spreads[buy][sell]`: spread percentage when buying at the row venue's effective
+    /// ask and selling at the column venue's effective bid; `None` on the diagonal
+    pub spreads: Vec<Vec<Option<f64>>>,
+    /// Oldest price the matrix was built from
+    pub oldest_price: Timestamp,
+    pub generated_at: Timestamp,
+}
+
+impl SpreadHeatmap {
+    /// Matrix from the latest price of each venue; prices of other markets are ignored.
+    /// Negative spreads (no opportunity) are kept so the whole matrix can be shaded.
+    pub fn from_prices(
+        symbol: &str,
+        prices: &[CexPrice],
+        fee_overrides: Option<&FeeOverrides>,
+    ) -> Self {
+        let market = MarketKey::from_symbol(symbol);
+        let mut latest: HashMap<&Exchange, &CexPrice> = HashMap::new();
+        for price in prices
+            .iter()
+            .filter(|p| p.bid_price > 0.0 && p.ask_price > 0.0)
+            .filter(|p| logical_market_key(&p.symbol, &p.exchange) == market)
+        {
+            let entry = latest.entry(&price.exchange).or_insert(price);
+            if price.timestamp > entry.timestamp {
+                *entry = price;
+            }
+        }
+        let mut quotes: Vec<&CexPrice> = latest.into_values().collect();
+        quotes.sort_by_key(|p| format!("{:?}", p.exchange));
+
+        let spreads = quotes
+            .iter()
+            .enumerate()
+            .map(|(row, buy)| {
+                let ask = effective_price_with_overrides(
+                    buy.ask_price,
+                    &buy.exchange,
+                    AmountSide::Buy,
+                    fee_overrides,
+                );
+                quotes
+                    .iter()
+                    .enumerate()
+                    .map(|(column, sell)| {
+                        (row != column).then(|| {
+                            let bid = effective_price_with_overrides(
+                                sell.bid_price,
+                                &sell.exchange,
+                                AmountSide::Sell,
+                                fee_overrides,
+                            );
+                            (bid - ask) / ask * 100.0
+                        })
+                    })
+                    .collect()
+            })
+            .collect();
+
+        Self {
+            symbol: symbol.to_string(),
+            venues: quotes.iter().map(|p| p.exchange.clone()).collect(),
+            spreads,
+            oldest_price: quotes.iter().map(|p| p.timestamp).min().unwrap_or_default(),
+            generated_at: Timestamp::now(),
+        }
+    }
+
+    /// Spread percentage buying on `buy` and selling on `sell`.
+    pub fn spread(&self, buy: &Exchange, sell: &Exchange) -> Option<f64> {
+        let row = self.venues.iter().position(|v| v == buy)?;
+        let column = self.venues.iter().position(|v| v == sell)?;
+        self.spreads[row][column]
+    }
+
+    /// Best pair as `(buy, sell, spread percentage)`.
+    pub fn best(&self) -> Option<(&Exchange, &Exchange, f64)> {
+        self.spreads
+            .iter()
+            .enumerate()
+            .flat_map(|(row, spreads)| {
+                spreads
+                    .iter()
+                    .enumerate()
+                    .filter_map(move |(column, spread)| spread.map(|s| (row, column, s)))
+            })
+            .max_by(|a, b| a.2.total_cmp(&b.2))
+            .map(|(row, column, spread)| (&self.venues[row], &self.venues[column], spread))
+    }
+}
+
+/// Latest price per venue and symbol, shared with a running live scanner. Cheap to clone;
+/// clones share the same prices.
+#[derive(Debug, Clone, Default)]
+pub struct LivePriceCache {
+    prices: Arc<RwLock<BoundedCache<(Exchange, String), CexPrice>>>,
+}
+
+impl LivePriceCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Cache bounded by `limits` (see [BoundedCache]).
+    pub fn with_limits(limits: CacheLimits) -> Self {
+        Self {
+            prices: Arc::new(RwLock::new(BoundedCache::new(limits))),
+        }
+    }
+
+    /// Changes the limits of this cache and its clones, evicting what no longer fits.
+    pub fn set_limits(&self, limits: CacheLimits) {
+        self.prices
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .set_limits(limits);
+    }
+
+    /// Entry count and evictions.
+    pub fn stats(&self) -> CacheStats {
+        self.prices
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .stats()
+    }
+
+    pub fn insert(&self, price: CexPrice) {
+        self.prices
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert((price.exchange.clone(), price.symbol.clone()), price);
+    }
+
+    /// Drops every price of `exchange` (e.g. when its circuit breaker opens).
+    pub fn remove_venue(&self, exchange: &Exchange) {
+        self.prices
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .retain(|(venue, _), _| venue != exchange);
+    }
+
+    /// Latest prices of `symbol` (matched through asset aliases) on every venue.
+    pub fn prices(&self, symbol: &str) -> Vec<CexPrice> {
+        let market = MarketKey::from_symbol(symbol);
+        self.prices
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .values()
+            .filter(|p| logical_market_key(&p.symbol, &p.exchange) == market)
+            .cloned()
+            .collect()
+    }
+
+    /// [SpreadHeatmap] of `symbol` from the cached prices.
+    pub fn spread_heatmap(
+        &self,
+        symbol: &str,
+        fee_overrides: Option<&FeeOverrides>,
+    ) -> SpreadHeatmap {
+        SpreadHeatmap::from_prices(symbol, &self.prices(symbol), fee_overrides)
+    }
+}
@@ -0,0 +1,512 @@
+//! Incident routing for venue outages.
+//!
+//! Venue health changes (circuit breaker opened, WebSocket down and replaced by REST
+//! polling, REST requests failing, maintenance, decode errors) become [VenueIncident]s.
+//! An [IncidentRouter] forwards them to incident-management sinks such as [PagerDutySink]
+//! and [OpsgenieSink], kept apart from opportunity sinks so operational pages and trading
+//! notifications go to different people. Every incident has a dedup key per venue and
+//! [IncidentKind], so the recovery resolves the incident its failure triggered.
+
+use crate::common::{
+    CexExchange, DecodeWarning, MarketScannerError, Timestamp, create_http_client,
+};
+use crate::scanner::{CircuitState, ScannerEvent, VenueFeed, VenueStatus, VenueStatusEvent};
+use async_trait::async_trait;
+use chrono::{TimeZone, Utc};
+use futures::future::join_all;
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+
+/// PagerDuty Events API v2 endpoint.
+pub const PAGERDUTY_EVENTS_URL: &str = "https://events.pagerduty.com/v2/enqueue";
+/// Opsgenie API base (US instance; EU accounts use `https://api.eu.opsgenie.com`).
+pub const OPSGENIE_API_BASE: &str = "https://api.opsgenie.com";
+
+const DEFAULT_SOURCE: &str = "aeon-market-scanner";
+
+/// What is wrong with a venue.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum IncidentKind {
+    /// The live scanner's circuit breaker opened for the venue
+    CircuitOpen,
+    /// The WebSocket feed is down and prices come from REST polling
+    WebSocketDown,
+    /// REST requests to the venue are failing
+    RestFailing,
+    /// The venue reports maintenance
+    Maintenance,
+    /// WebSocket messages fail to decode above the warning threshold
+    DecodeErrors,
+}
+
+impl IncidentKind {
+    pub fn default_severity(self) -> IncidentSeverity {
+        match self {
+            IncidentKind::CircuitOpen => IncidentSeverity::Critical,
+            IncidentKind::WebSocketDown | IncidentKind::RestFailing => IncidentSeverity::Error,
+            IncidentKind::Maintenance | IncidentKind::DecodeErrors => IncidentSeverity::Warning,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum IncidentAction {
+    /// Open (or update) the incident
+    Trigger,
+    /// Close the incident
+    Resolve,
+}
+
+/// Severity levels shared by PagerDuty and Opsgenie (priorities P1 to P4).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum IncidentSeverity {
+    Critical,
+    Error,
+    Warning,
+    Info,
+}
+
+impl IncidentSeverity {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            IncidentSeverity::Critical => "critical",
+            IncidentSeverity::Error => "error",
+            IncidentSeverity::Warning => "warning",
+            IncidentSeverity::Info => "info",
+        }
+    }
+
+    fn opsgenie_priority(self) -> &'static str {
+        match self {
+            IncidentSeverity::Critical => "P1",
+            IncidentSeverity::Error => "P2",
+            IncidentSeverity::Warning => "P3",
+            IncidentSeverity::Info => "P4",
+        }
+    }
+}
+
+/// A venue health state change to open or close an incident for.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct VenueIncident {
+    pub exchange: CexExchange,
+    pub kind: IncidentKind,
+    pub action: IncidentAction,
+    pub severity: IncidentSeverity,
+    pub summary: String,
+    pub timestamp: Timestamp,
+}
+
+impl VenueIncident {
+    /// Opens a `kind` incident with its default severity.
+    pub fn trigger(exchange: CexExchange, kind: IncidentKind, summary: impl Into<String>) -> Self {
+        Self {
+            exchange,
+            kind,
+            action: IncidentAction::Trigger,
+            severity: kind.default_severity(),
+            summary: summary.into(),
+            timestamp: Timestamp::now(),
+        }
+    }
+
+    /// Closes the `kind` incident of `exchange`.
+    pub fn resolve(exchange: CexExchange, kind: IncidentKind) -> Self {
+        Self {
+            summary: format!("{:?}: {:?} recovered", exchange, kind),
+            action: IncidentAction::Resolve,
+            severity: IncidentSeverity::Info,
+            ..Self::trigger(exchange, kind, String::new())
+        }
+    }
+
+    pub fn with_severity(mut self, severity: IncidentSeverity) -> Self {
+        self.severity = severity;
+        self
+    }
+
+    pub fn with_timestamp(mut self, timestamp: Timestamp) -> Self {
+        self.timestamp = timestamp;
+        self
+    }
+
+    /// Key shared by the trigger and resolve of one venue incident.
+    pub fn dedup_key(&self) -> String {
+        format!("{}-{:?}-{:?}", DEFAULT_SOURCE, self.exchange, self.kind)
+    }
+
+    /// Incident for a circuit breaker or feed change of the live scanner. Half-open
+    /// breakers and other events produce none.
+    pub fn from_scanner_event(event: &ScannerEvent) -> Option<Self> {
+        let (exchange, kind, failing, timestamp) = match event {
+            ScannerEvent::CircuitStateChanged {
+                exchange,
+                state,
+                timestamp,
+            } => match state {
+                CircuitState::Open => (exchange, IncidentKind::CircuitOpen, true, timestamp),
+                CircuitState::Closed => (exchange, IncidentKind::CircuitOpen, false, timestamp),
+                CircuitState::HalfOpen => return None,
+            },
+            ScannerEvent::FeedChanged {
+                exchange,
+                feed,
+                timestamp,
+            } => (
+                exchange,
+                IncidentKind::WebSocketDown,
+                *feed == VenueFeed::RestPolling,
+                timestamp,
+            ),
+            _ => return None,
+        };
+        let incident = if failing {
+            let summary = match kind {
+                IncidentKind::CircuitOpen => {
+                    format!("{:?}: circuit breaker open, venue paused", exchange)
+                }
+                _ => format!(
+                    "{:?}: WebSocket down, falling back to REST polling",
+                    exchange
+                ),
+            };
+            Self::trigger(exchange.clone(), kind, summary)
+        } else {
+            Self::resolve(exchange.clone(), kind)
+        };
+        Some(incident.with_timestamp(*timestamp))
+    }
+
+    /// Incident for a maintenance status change; `None` for changes to or from `Unknown`.
+    pub fn from_venue_status(event: &VenueStatusEvent) -> Option<Self> {
+        let incident = match (event.previous, event.status) {
+            (_, VenueStatus::Maintenance) => Self::trigger(
+                event.exchange.clone(),
+                IncidentKind::Maintenance,
+                format!("{:?}: under maintenance", event.exchange),
+            ),
+            (VenueStatus::Maintenance, VenueStatus::Operational) => {
+                Self::resolve(event.exchange.clone(), IncidentKind::Maintenance)
+            }
+            _ => return None,
+        };
+        Some(incident.with_timestamp(event.timestamp))
+    }
+
+    pub fn from_decode_warning(warning: &DecodeWarning) -> Self {
+        Self::trigger(
+            warning.exchange.clone(),
+            IncidentKind::DecodeErrors,
+            format!(
+                "{:?}: {:.0}% of WebSocket messages fail to decode ({} of {})",
+                warning.exchange,
+                warning.error_rate * 100.0,
+                warning.failures,
+                warning.messages
+            ),
+        )
+        .with_timestamp(warning.timestamp)
+    }
+
+    /// [IncidentKind::RestFailing] trigger for a failed request, resolve for a success. The
+    /// summary says whether the failure is transient or permanent (see
+    /// [MarketScannerError::is_retryable]).
+    pub fn from_rest_result<T>(
+        exchange: &CexExchange,
+        result: &Result<T, MarketScannerError>,
+    ) -> Self {
+        match result {
+            Ok(_) => Self::resolve(exchange.clone(), IncidentKind::RestFailing),
+            Err(e) => Self::trigger(
+                exchange.clone(),
+                IncidentKind::RestFailing,
+                format!(
+                    "{:?}: REST requests failing ({}): {}",
+                    exchange,
+                    if e.is_retryable() {
+                        "transient"
+                    } else {
+                        "permanent"
+                    },
+                    e
+                ),
+            ),
+        }
+    }
+
+    fn iso_timestamp(&self) -> String {
+        Utc.timestamp_millis_opt(self.timestamp.as_millis() as i64)
+            .single()
+            .unwrap_or_default()
+            .format("%Y-%m-%dT%H:%M:%S%.3fZ")
+            .to_string()
+    }
+}
+
+/// Destination of venue incidents.
+#[async_trait]
+pub trait IncidentSink: Send + Sync {
+    async fn send(&self, incident: &VenueIncident) -> Result<(), MarketScannerError>;
+}
+
+/// PagerDuty Events API v2 sink.
+#[derive(Debug, Clone)]
+pub struct PagerDutySink {
+    routing_key: String,
+    source: String,
+    url: String,
+    client: reqwest::Client,
+}
+
+impl PagerDutySink {
+    /// Sink for the service integration with `routing_key`.
+    pub fn new(routing_key: impl Into<String>) -> Self {
+        Self {
+            routing_key: routing_key.into(),
+            source: DEFAULT_SOURCE.to_string(),
+            url: PAGERDUTY_EVENTS_URL.to_string(),
+            client: create_http_client(),
+        }
+    }
+
+    /// `source` field of triggered events (default "aeon-market-scanner").
+    pub fn with_source(mut self, source: impl Into<String>) -> Self {
+        self.source = source.into();
+        self
+    }
+
+    pub fn with_url(mut self, url: impl Into<String>) -> Self {
+        self.url = url.into();
+        self
+    }
+
+    /// Events API v2 body for `incident`.
+    pub fn event(&self, incident: &VenueIncident) -> Value {
+        match incident.action {
+            IncidentAction::Trigger => json!({
+                "routing_key": self.routing_key,
+                "event_action": "trigger",
+                "dedup_key": incident.dedup_key(),
+                "payload": {
+                    "summary": incident.summary,
+                    "source": self.source,
+                    "severity": incident.severity.as_str(),
+                    "timestamp": incident.iso_timestamp(),
+                    "component": format!("{:?}", incident.exchange),
+                    "class": format!("{:?}", incident.kind),
+                },
+            }),
+            IncidentAction::Resolve => json!({
+                "routing_key": self.routing_key,
+                "event_action": "resolve",
+                "dedup_key": incident.dedup_key(),
+            }),
+        }
+    }
+}
+
+#[async_trait]
+impl IncidentSink for PagerDutySink {
+    async fn send(&self, incident: &VenueIncident) -> Result<(), MarketScannerError> {
+        post_json(
+            &self.client,
+            &self.url,
+            &[],
+            &self.event(incident),
+            "PagerDuty",
+        )
+        .await
+    }
+}
+
+/// Opsgenie Alert API sink; incidents are opened and closed by alias.
+#[derive(Debug, Clone)]
+pub struct OpsgenieSink {
+    api_key: String,
+    source: String,
+    base_url: String,
+    client: reqwest::Client,
+}
+
+impl OpsgenieSink {
+    /// Sink authenticated with the API integration key `api_key`.
+    pub fn new(api_key: impl Into<String>) -> Self {
+        Self {
+            api_key: api_key.into(),
+            source: DEFAULT_SOURCE.to_string(),
+            base_url: OPSGENIE_API_BASE.to_string(),
+            client: create_http_client(),
+        }
+    }
+
+    pub fn with_source(mut self, source: impl Into<String>) -> Self {
+        self.source = source.into();
+        self
+    }
+
+    /// API base, e.g. `https://api.eu.opsgenie.com` for EU accounts.
+    pub fn with_base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = base_url.into().trim_end_matches('/').to_string();
+        self
+    }
+
+    /// URL and body of the Alert API request for `incident`.
+    pub fn request(&self, incident: &VenueIncident) -> (String, Value) {
+        let alias = incident.dedup_key();
+        match incident.action {
+            IncidentAction::Trigger => (
+                format!("{}/v2/alerts", self.base_url),
+                json!({
+                    // Opsgenie truncates messages after 130 characters
+                    "message": incident.summary.chars().take(130).collect::<String>(),
+                    "alias": alias,
+                    "description": incident.summary,
+                    "priority": incident.severity.opsgenie_priority(),
+                    "source": self.source,
+                    "entity": format!("{:?}", incident.exchange),
+                    "tags": [
+                        DEFAULT_SOURCE,
+                        format!("{:?}", incident.exchange),
+                        format!("{:?}", incident.kind),
+                    ],
+                }),
+            ),
+            IncidentAction::Resolve => (
+                format!(
+                    "{}/v2/alerts/{}/close?identifierType=alias",
+                    self.base_url, alias
+                ),
+                json!({ "source": self.source, "note": incident.summary }),
+            ),
+        }
+    }
+}
+
+#[async_trait]
+impl IncidentSink for OpsgenieSink {
+    async fn send(&self, incident: &VenueIncident) -> Result<(), MarketScannerError> {
+        let (url, body) = self.request(incident);
+        let authorization = format!("GenieKey {}", self.api_key);
+        post_json(
+            &self.client,
+            &url,
+            &[("Authorization", authorization.as_str())],
+            &body,
+            "Opsgenie",
+        )
+        .await
+    }
+}
+
+async fn post_json(
+    client: &reqwest::Client,
+    url: &str,
+    headers: &[(&str, &str)],
+    body: &Value,
+    service: &str,
+) -> Result<(), MarketScannerError> {
+    let mut request = client.post(url).json(body);
+    for (name, value) in headers {
+        request = request.header(*name, *value);
+    }
+    let response = request.send().await?;
+    let status = response.status();
+    if !status.is_success() {
+        return Err(MarketScannerError::ApiError(format!(
+            "{} API error: {} - {}",
+            service,
+            status,
+            response.text().await.unwrap_or_default()
+        )));
+    }
+    Ok(())
+}
+
+/// Forwards venue incidents to its sinks, once per state change. Cheap to clone; clones
+/// share the open incidents.
+#[derive(Clone, Default)]
+pub struct IncidentRouter {
+    sinks: Vec<Arc<dyn IncidentSink>>,
+    open: Arc<RwLock<HashMap<String, VenueIncident>>>,
+}
+
+impl std::fmt::Debug for IncidentRouter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("IncidentRouter")
+            .field("sinks", &self.sinks.len())
+            .field("open", &self.open_incidents().len())
+            .finish()
+    }
+}
+
+impl IncidentRouter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_sink(mut self, sink: impl IncidentSink + 'static) -> Self {
+        self.sinks.push(Arc::new(sink));
+        self
+    }
+
+    /// Sends `incident` to every sink unless it repeats the current state (a trigger for
+    /// an open incident, a resolve for a closed one). Returns whether it was sent; sink
+    /// errors are reported after all sinks were tried.
+    pub async fn report(&self, incident: VenueIncident) -> Result<bool, MarketScannerError> {
+        {
+            let mut open = self.open.write().unwrap_or_else(|e| e.into_inner());
+            let key = incident.dedup_key();
+            match incident.action {
+                IncidentAction::Trigger if open.contains_key(&key) => return Ok(false),
+                IncidentAction::Trigger => {
+                    open.insert(key, incident.clone());
+                }
+                IncidentAction::Resolve => {
+                    if open.remove(&key).is_none() {
+                        return Ok(false);
+                    }
+                }
+            }
+        }
+        let errors: Vec<String> = join_all(self.sinks.iter().map(|sink| sink.send(&incident)))
+            .await
+            .into_iter()
+            .filter_map(|result| result.err().map(|e| e.to_string()))
+            .collect();
+        if errors.is_empty() {
+            Ok(true)
+        } else {
+            Err(MarketScannerError::ApiError(format!(
+                "Incident {} not delivered: {}",
+                incident.dedup_key(),
+                errors.join("; ")
+            )))
+        }
+    }
+
+    /// Reports the incident of a live scanner event, if it has one.
+    pub async fn handle_scanner_event(
+        &self,
+        event: &ScannerEvent,
+    ) -> Result<bool, MarketScannerError> {
+        match VenueIncident::from_scanner_event(event) {
+            Some(incident) => self.report(incident).await,
+            None => Ok(false),
+        }
+    }
+
+    /// Open incidents, oldest first.
+    pub fn open_incidents(&self) -> Vec<VenueIncident> {
+        let mut incidents: Vec<_> = self
+            .open
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .values()
+            .cloned()
+            .collect();
+        incidents.sort_by_key(|incident| incident.timestamp);
+        incidents
+    }
+}
@@ -0,0 +1,93 @@
+//! Refresh of the venue instrument id cache.
+//!
+//! [ArbitrageScanner::refresh_instrument_ids] reads the listings of the given venues into
+//! the cache of [crate::common::instrument_ids];
+//! [ArbitrageScanner::refresh_instrument_ids_every] keeps doing so in the background and
+//! optionally persists the cache after every round, so the next start can
+//! [load](crate::common::load_instrument_ids) it instead of falling back to the symbol
+//! heuristics until the venues answer.
+
+use crate::common::{
+    CexExchange, MarketScannerError, Timestamp, record_instrument_ids, save_instrument_ids,
+};
+use crate::scanner::ArbitrageScanner;
+use futures::future::join_all;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::time::Duration;
+use tokio::sync::mpsc;
+
+/// Outcome of refreshing the instrument ids of one venue.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct InstrumentIdRefresh {
+    pub exchange: CexExchange,
+    pub at: Timestamp,
+    /// Instruments recorded (0 when the refresh failed)
+    pub instruments: usize,
+    /// Why the listing could not be read; the previously cached ids stay in place
+    pub error: Option<String>,
+}
+
+impl ArbitrageScanner {
+    /// Reads the instrument listings of `exchanges` in parallel and records them in the
+    /// instrument id cache. Venues that do not publish a listing report an error.
+    pub async fn refresh_instrument_ids(exchanges: &[CexExchange]) -> Vec<InstrumentIdRefresh> {
+        let refreshes = exchanges.iter().map(|exchange| async move {
+            let result = Self::get_cex_instrument_ids(exchange).await;
+            let (instruments, error) = match result {
+                Ok(ids) => {
+                    let count = ids.len();
+                    record_instrument_ids(exchange, ids);
+                    (count, None)
+                }
+                Err(e) => (0, Some(e.to_string())),
+            };
+            InstrumentIdRefresh {
+                exchange: exchange.clone(),
+                at: Timestamp::now(),
+                instruments,
+                error,
+            }
+        });
+        join_all(refreshes).await
+    }
+
+    /// Refreshes the instrument ids of `exchanges` now and then every `interval`, saving the
+    /// cache to `persist_to` after each round (if given). Each round is reported on the
+    /// returned channel; refreshing stops when the receiver is dropped.
+    pub fn refresh_instrument_ids_every(
+        exchanges: &[CexExchange],
+        interval: Duration,
+        persist_to: Option<PathBuf>,
+    ) -> Result<mpsc::Receiver<Vec<InstrumentIdRefresh>>, MarketScannerError> {
+        if interval.is_zero() {
+            return Err(MarketScannerError::InvalidConfig(
+                "Instrument id refresh interval must be positive".to_string(),
+            ));
+        }
+        let exchanges = exchanges.to_vec();
+        let (tx, rx) = mpsc::channel(16);
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            loop {
+                tokio::select! {
+                    _ = ticker.tick() => {}
+                    _ = tx.closed() => return,
+                }
+                let mut round = Self::refresh_instrument_ids(&exchanges).await;
+                if let Some(path) = &persist_to {
+                    if let Err(e) = save_instrument_ids(path) {
+                        for refresh in &mut round {
+                            refresh.error.get_or_insert_with(|| e.to_string());
+                        }
+                    }
+                }
+                if tx.send(round).await.is_err() {
+                    return;
+                }
+            }
+        });
+        Ok(rx)
+    }
+}
@@ -0,0 +1,89 @@
+//! Per-opportunity latency budget.
+//!
+//! With latency tracing enabled ([crate::common::set_latency_tracing_enabled]), the
+//! WebSocket scanners attach a [LatencyTrace] to every opportunity that involves the price
+//! update which triggered the snapshot. The trace splits the time from frame receipt to
+//! emission into stages, so operators can see where milliseconds are lost. With the
+//! `tracing` feature each trace is also emitted as an `opportunity_latency` span.
+
+use crate::common::{CexPrice, FrameStamp};
+use crate::scanner::{ArbitrageOpportunity, ArbitrageScanner, PriceData};
+use serde::{Deserialize, Serialize};
+use std::time::Instant;
+
+/// Stage timings (microseconds) of the price update that produced an opportunity.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LatencyTrace {
+    /// Id of the triggering price, shared with its trace events
+    pub correlation_id: u64,
+    /// Venue of the triggering price update
+    pub exchange: String,
+    /// Symbol of the triggering price update
+    pub symbol: String,
+    /// WebSocket frame receipt → price parsed
+    pub parse_us: u64,
+    /// Price parsed → inserted into the scanner's price cache
+    pub cache_insert_us: u64,
+    /// Cache insert → opportunities matched
+    pub match_us: u64,
+    /// Matching done → snapshot handed to the output channel (filters, classification)
+    pub emit_us: u64,
+    /// Frame receipt → emission
+    pub total_us: u64,
+}
+
+impl LatencyTrace {
+    /// Builds the trace of `price` from its frame stamp and the scanner's stage instants.
+    pub fn new(
+        price: &CexPrice,
+        stamp: &FrameStamp,
+        inserted_at: Instant,
+        matched_at: Instant,
+        emitted_at: Instant,
+    ) -> Self {
+        let micros =
+            |from: Instant, to: Instant| to.saturating_duration_since(from).as_micros() as u64;
+        Self {
+            correlation_id: stamp.correlation_id,
+            exchange: ArbitrageScanner::exchange_name(&price.exchange),
+            symbol: price.symbol.clone(),
+            parse_us: micros(stamp.received_at, stamp.parsed_at),
+            cache_insert_us: micros(stamp.parsed_at, inserted_at),
+            match_us: micros(inserted_at, matched_at),
+            emit_us: micros(matched_at, emitted_at),
+            total_us: micros(stamp.received_at, emitted_at),
+        }
+    }
+
+    /// Attaches the trace to every opportunity with a leg on the triggering price.
+    pub fn attach(&self, price: &CexPrice, opportunities: &mut [ArbitrageOpportunity]) {
+        #[cfg(feature = "tracing")]
+        self.emit_span();
+
+        let is_trigger = |leg: &PriceData| match leg {
+            PriceData::Cex(p) => p.exchange == price.exchange && p.symbol == price.symbol,
+            PriceData::Dex(_) => false,
+        };
+        for opportunity in opportunities {
+            if is_trigger(&opportunity.source_leg) || is_trigger(&opportunity.destination_leg) {
+                opportunity.latency = Some(self.clone());
+            }
+        }
+    }
+
+    #[cfg(feature = "tracing")]
+    fn emit_span(&self) {
+        let span = tracing::debug_span!(
+            "opportunity_latency",
+            correlation_id = self.correlation_id,
+            exchange = %self.exchange,
+            symbol = %self.symbol,
+        );
+        let _entered = span.enter();
+        tracing::debug!(stage = "parse", elapsed_us = self.parse_us);
+        tracing::debug!(stage = "cache_insert", elapsed_us = self.cache_insert_us);
+        tracing::debug!(stage = "match", elapsed_us = self.match_us);
+        tracing::debug!(stage = "emit", elapsed_us = self.emit_us);
+        tracing::debug!(total_us = self.total_us, "opportunity emitted");
+    }
+}
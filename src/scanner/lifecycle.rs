@@ -0,0 +1,386 @@
+//! Opportunity lifecycle with hysteresis.
+//!
+//! Spreads hovering around a threshold make opportunities flap in and out of every
+//! snapshot. [OpportunityLifecycle] follows each opportunity (by
+//! [ArbitrageOpportunity::key]) across snapshots and only opens it once its spread reaches
+//! the enter threshold, then keeps it open until the spread falls to the lower exit
+//! threshold or it disappears. Comparisons tolerate an epsilon so floating point noise at
+//! the exact threshold does not decide.
+//!
+//! Venues update at different times, so a one-tick spread between a fresh and a stale
+//! quote can reach the enter threshold without being real. With
+//! [LifecycleConfig::with_min_duration_ms] and [LifecycleConfig::with_min_ticks] a
+//! candidate must stay at the enter threshold for that long / that many consecutive
+//! snapshots before it opens; candidates that drop below it or vanish earlier are
+//! discarded without an event.
+//!
+//! Every opening starts an episode with its own [ArbitrageOpportunity::id], stamped on the
+//! opportunities of its events. Execution engines acknowledge episodes by id with
+//! [OpportunityLifecycle::ack], which keeps [ExecutionStats].
+
+use crate::common::{MarketScannerError, Timestamp};
+use crate::scanner::ArbitrageOpportunity;
+use crate::scanner::ack::{ExecutionAck, ExecutionReport, ExecutionStats};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet, VecDeque};
+
+/// Episodes remembered for acknowledgments (oldest are forgotten first).
+const MAX_TRACKED_EPISODES: usize = 10_000;
+
+/// Enter / exit thresholds of an [OpportunityLifecycle] (spread percentages net of fees).
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct LifecycleConfig {
+    /// Opens at spread ≥ this
+    pub enter_spread_percent: f64,
+    /// Closes at spread ≤ this; at most `enter_spread_percent`
+    pub exit_spread_percent: f64,
+    /// Tolerance of both comparisons
+    pub epsilon: f64,
+    /// Time a candidate must stay at the enter threshold before it opens (milliseconds);
+    /// 0 opens immediately
+    #[serde(default)]
+    pub min_duration_ms: u64,
+    /// Consecutive snapshots a candidate must stay at the enter threshold before it
+    /// opens; 0 and 1 open on the first one
+    #[serde(default)]
+    pub min_ticks: u32,
+}
+
+impl LifecycleConfig {
+    /// Thresholds with an epsilon of 1e-9, opening on the first snapshot.
+    pub fn new(enter_spread_percent: f64, exit_spread_percent: f64) -> Self {
+        Self {
+            enter_spread_percent,
+            exit_spread_percent,
+            epsilon: 1e-9,
+            min_duration_ms: 0,
+            min_ticks: 0,
+        }
+    }
+
+    pub fn with_epsilon(mut self, epsilon: f64) -> Self {
+        self.epsilon = epsilon;
+        self
+    }
+
+    /// Opens only after the spread stayed at the enter threshold for `min_duration_ms`.
+    pub fn with_min_duration_ms(mut self, min_duration_ms: u64) -> Self {
+        self.min_duration_ms = min_duration_ms;
+        self
+    }
+
+    /// Opens only after the spread stayed at the enter threshold for `min_ticks`
+    /// consecutive snapshots.
+    pub fn with_min_ticks(mut self, min_ticks: u32) -> Self {
+        self.min_ticks = min_ticks;
+        self
+    }
+
+    pub fn validate(&self) -> Result<(), MarketScannerError> {
+        let thresholds = [
+            self.enter_spread_percent,
+            self.exit_spread_percent,
+            self.epsilon,
+        ];
+        if thresholds.iter().any(|value| value.is_nan())
+            || self.exit_spread_percent > self.enter_spread_percent
+            || self.epsilon < 0.0
+        {
+            return Err(MarketScannerError::InvalidConfig(format!(
+                "Lifecycle exit threshold {}% must not exceed enter threshold {}% (epsilon {})",
+                self.exit_spread_percent, self.enter_spread_percent, self.epsilon
+            )));
+        }
+        Ok(())
+    }
+
+    fn enters(&self, spread_percentage: f64) -> bool {
+        spread_percentage + self.epsilon >= self.enter_spread_percent
+    }
+
+    fn exits(&self, spread_percentage: f64) -> bool {
+        spread_percentage - self.epsilon <= self.exit_spread_percent
+    }
+
+    fn persisted(&self, candidate: &Candidate, now: Timestamp) -> bool {
+        candidate.ticks >= self.min_ticks.max(1)
+            && now.duration_since(candidate.first_seen).as_millis() as u64 >= self.min_duration_ms
+    }
+}
+
+/// Why an opportunity was closed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CloseReason {
+    /// Spread fell to the exit threshold
+    BelowExit,
+    /// No longer in the snapshot
+    Gone,
+}
+
+/// State change of an opportunity.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum LifecycleEvent {
+    Opened {
+        opportunity: ArbitrageOpportunity,
+        at: Timestamp,
+    },
+    Closed {
+        /// Last observation while open
+        opportunity: ArbitrageOpportunity,
+        opened_at: Timestamp,
+        closed_at: Timestamp,
+        peak_spread_percentage: f64,
+        reason: CloseReason,
+    },
+}
+
+impl LifecycleEvent {
+    pub fn key(&self) -> String {
+        match self {
+            LifecycleEvent::Opened { opportunity, .. }
+            | LifecycleEvent::Closed { opportunity, .. } => opportunity.key(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct OpenOpportunity {
+    latest: ArbitrageOpportunity,
+    opened_at: Timestamp,
+    peak_spread_percentage: f64,
+}
+
+/// Opportunity at the enter threshold that has not persisted long enough to open.
+#[derive(Debug, Clone, Copy)]
+struct Candidate {
+    first_seen: Timestamp,
+    ticks: u32,
+    peak_spread_percentage: f64,
+}
+
+/// Episode kept for acknowledgments.
+#[derive(Debug, Clone)]
+struct Episode {
+    latest: ArbitrageOpportunity,
+    opened_at: Timestamp,
+    closed_at: Option<Timestamp>,
+    acked: bool,
+}
+
+/// Tracks which opportunities are open across snapshots. Time is passed in explicitly.
+#[derive(Debug, Clone)]
+pub struct OpportunityLifecycle {
+    config: LifecycleConfig,
+    open: HashMap<String, OpenOpportunity>,
+    candidates: HashMap<String, Candidate>,
+    episodes: HashMap<String, Episode>,
+    episode_order: VecDeque<String>,
+    stats: ExecutionStats,
+}
+
+impl OpportunityLifecycle {
+    pub fn new(config: LifecycleConfig) -> Result<Self, MarketScannerError> {
+        config.validate()?;
+        Ok(Self {
+            config,
+            open: HashMap::new(),
+            candidates: HashMap::new(),
+            episodes: HashMap::new(),
+            episode_order: VecDeque::new(),
+            stats: ExecutionStats::default(),
+        })
+    }
+
+    pub fn config(&self) -> &LifecycleConfig {
+        &self.config
+    }
+
+    /// Applies a full snapshot taken at `now` and returns the opened and closed
+    /// opportunities, closes first.
+    pub fn update(
+        &mut self,
+        opportunities: &[ArbitrageOpportunity],
+        now: Timestamp,
+    ) -> Vec<LifecycleEvent> {
+        let mut closed = Vec::new();
+        let mut opened = Vec::new();
+        let mut seen = HashSet::with_capacity(opportunities.len());
+
+        for opportunity in opportunities {
+            let key = opportunity.key();
+            let spread = opportunity.spread_percentage;
+            match self.open.get_mut(&key) {
+                Some(_) if self.config.exits(spread) => {
+                    let open = self.open.remove(&key).expect("open opportunity");
+                    closed.push(self.closed(open, now, CloseReason::BelowExit));
+                }
+                Some(open) => {
+                    let mut latest = opportunity.clone();
+                    latest.id = open.latest.id.clone();
+                    open.latest = latest;
+                    open.peak_spread_percentage = open.peak_spread_percentage.max(spread);
+                    if let Some(episode) = open
+                        .latest
+                        .id
+                        .as_ref()
+                        .and_then(|id| self.episodes.get_mut(id))
+                    {
+                        episode.latest = open.latest.clone();
+                    }
+                    seen.insert(key);
+                }
+                None if self.config.enters(spread) => {
+                    let candidate = self
+                        .candidates
+                        .entry(key.clone())
+                        .and_modify(|candidate| {
+                            candidate.ticks += 1;
+                            candidate.peak_spread_percentage =
+                                candidate.peak_spread_percentage.max(spread);
+                        })
+                        .or_insert(Candidate {
+                            first_seen: now,
+                            ticks: 1,
+                            peak_spread_percentage: spread,
+                        });
+                    if !self.config.persisted(candidate, now) {
+                        seen.insert(key);
+                        continue;
+                    }
+                    let peak_spread_percentage = candidate.peak_spread_percentage;
+                    self.candidates.remove(&key);
+                    let mut opportunity = opportunity.clone();
+                    let id = format!("{}@{}", key, now.as_millis());
+                    opportunity.id = Some(id.clone());
+                    self.track_episode(id, &opportunity, now);
+                    self.open.insert(
+                        key.clone(),
+                        OpenOpportunity {
+                            latest: opportunity.clone(),
+                            opened_at: now,
+                            peak_spread_percentage,
+                        },
+                    );
+                    opened.push(LifecycleEvent::Opened {
+                        opportunity,
+                        at: now,
+                    });
+                    seen.insert(key);
+                }
+                None => {
+                    self.candidates.remove(&key);
+                }
+            }
+        }
+        self.candidates.retain(|key, _| seen.contains(key));
+
+        let gone: Vec<String> = self
+            .open
+            .keys()
+            .filter(|key| !seen.contains(*key))
+            .cloned()
+            .collect();
+        for key in gone {
+            if let Some(open) = self.open.remove(&key) {
+                closed.push(self.closed(open, now, CloseReason::Gone));
+            }
+        }
+        closed.extend(opened);
+        closed
+    }
+
+    /// Latest observation of every open opportunity.
+    pub fn open_opportunities(&self) -> Vec<&ArbitrageOpportunity> {
+        self.open.values().map(|open| &open.latest).collect()
+    }
+
+    pub fn is_open(&self, key: &str) -> bool {
+        self.open.contains_key(key)
+    }
+
+    /// Whether `key` is at the enter threshold but has not persisted long enough to open.
+    pub fn is_pending(&self, key: &str) -> bool {
+        self.candidates.contains_key(key)
+    }
+
+    /// Id of the current episode of the open opportunity `key`.
+    pub fn open_id(&self, key: &str) -> Option<&str> {
+        self.open
+            .get(key)
+            .and_then(|open| open.latest.id.as_deref())
+    }
+
+    /// Links `ack` to its episode (open or among the last closed ones) and counts it in
+    /// the execution statistics. Fails for unknown or already acknowledged ids.
+    pub fn ack(&mut self, ack: &ExecutionAck) -> Result<ExecutionReport, MarketScannerError> {
+        let episode = self.episodes.get_mut(&ack.id).ok_or_else(|| {
+            MarketScannerError::InvalidConfig(format!("Unknown opportunity id {}", ack.id))
+        })?;
+        if episode.acked {
+            return Err(MarketScannerError::InvalidConfig(format!(
+                "Opportunity {} was already acknowledged",
+                ack.id
+            )));
+        }
+        episode.acked = true;
+        let report = ExecutionReport {
+            id: ack.id.clone(),
+            opportunity: episode.latest.clone(),
+            opened_at: episode.opened_at,
+            closed_at: episode.closed_at,
+            outcome: ack.outcome.clone(),
+            acked_at: ack.at,
+            ack_delay_ms: ack.at.duration_since(episode.opened_at).as_millis() as u64,
+        };
+        self.stats.record_ack(&report);
+        Ok(report)
+    }
+
+    /// Detection and execution counters since creation.
+    pub fn execution_stats(&self) -> &ExecutionStats {
+        &self.stats
+    }
+
+    fn track_episode(&mut self, id: String, opportunity: &ArbitrageOpportunity, now: Timestamp) {
+        self.stats.detected += 1;
+        self.stats.detected_profit_quote += opportunity.total_profit();
+        if self.episode_order.len() >= MAX_TRACKED_EPISODES {
+            if let Some(oldest) = self.episode_order.pop_front() {
+                self.episodes.remove(&oldest);
+            }
+        }
+        self.episode_order.push_back(id.clone());
+        self.episodes.insert(
+            id,
+            Episode {
+                latest: opportunity.clone(),
+                opened_at: now,
+                closed_at: None,
+                acked: false,
+            },
+        );
+    }
+
+    fn closed(
+        &mut self,
+        open: OpenOpportunity,
+        now: Timestamp,
+        reason: CloseReason,
+    ) -> LifecycleEvent {
+        if let Some(episode) = open
+            .latest
+            .id
+            .as_ref()
+            .and_then(|id| self.episodes.get_mut(id))
+        {
+            episode.closed_at = Some(now);
+        }
+        LifecycleEvent::Closed {
+            opportunity: open.latest,
+            opened_at: open.opened_at,
+            closed_at: now,
+            peak_spread_percentage: open.peak_spread_percentage,
+            reason,
+        }
+    }
+}
@@ -0,0 +1,229 @@
+//! Effective liquidity curve of a symbol across a CEX book and a DEX ladder.
+//!
+//! A CEX order book and a DEX size ladder describe depth differently: discrete levels
+//! versus average prices at a few sizes. [LiquidityCurve] converts the ladder to marginal
+//! levels (see [DexPriceLadder::to_order_book]), applies taker fees to both and merges them
+//! per side, best price first, so each point tells how much can be traded up to a price
+//! and where it comes from. The points are meant for plotting; [LiquidityCurve::optimal_quantity]
+//! and [ArbitrageScanner::size_from_liquidity_curve] size CEX↔DEX opportunities from them.
+
+use crate::common::{
+    AmountSide, DexPriceLadder, Exchange, FeeOverrides, OrderBook, OrderBookLevel, Timestamp,
+    effective_price_with_overrides,
+};
+use crate::scanner::{ArbitrageOpportunity, ArbitrageScanner, PriceData};
+use serde::{Deserialize, Serialize};
+
+/// Venue of a liquidity point.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LiquiditySource {
+    Cex,
+    Dex,
+}
+
+/// One level of a [LiquidityCurve] side.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LiquidityPoint {
+    pub source: LiquiditySource,
+    /// Price of this level after taker fees
+    pub price: f64,
+    /// Quantity of this level
+    pub quantity: f64,
+    /// Quantity of this and every better level
+    pub cumulative_quantity: f64,
+    /// Average effective price of the cumulative quantity
+    pub average_price: f64,
+}
+
+/// Merged CEX and DEX depth of a symbol; asks cheapest first, bids highest first.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LiquidityCurve {
+    pub symbol: String,
+    pub cex_exchange: Exchange,
+    pub dex_exchange: Exchange,
+    pub asks: Vec<LiquidityPoint>,
+    pub bids: Vec<LiquidityPoint>,
+    pub timestamp: Timestamp,
+}
+
+impl LiquidityCurve {
+    /// Merges `book` and `ladder` (the same symbol on a CEX and a DEX) with taker fees of
+    /// `fee_overrides` or the defaults.
+    pub fn new(
+        book: &OrderBook,
+        ladder: &DexPriceLadder,
+        fee_overrides: Option<&FeeOverrides>,
+    ) -> Self {
+        let dex_book = ladder.to_order_book();
+        let side = |side: AmountSide| {
+            let (cex_levels, dex_levels) = match side {
+                AmountSide::Buy => (&book.asks, &dex_book.asks),
+                AmountSide::Sell => (&book.bids, &dex_book.bids),
+            };
+            let mut levels: Vec<(LiquiditySource, f64, f64)> =
+                effective_levels(cex_levels, &book.exchange, side, fee_overrides)
+                    .map(|(price, quantity)| (LiquiditySource::Cex, price, quantity))
+                    .chain(
+                        effective_levels(dex_levels, &ladder.exchange, side, fee_overrides)
+                            .map(|(price, quantity)| (LiquiditySource::Dex, price, quantity)),
+                    )
+                    .collect();
+            match side {
+                AmountSide::Buy => levels.sort_by(|a, b| a.1.total_cmp(&b.1)),
+                AmountSide::Sell => levels.sort_by(|a, b| b.1.total_cmp(&a.1)),
+            }
+            let (mut cumulative, mut notional) = (0.0, 0.0);
+            levels
+                .into_iter()
+                .map(|(source, price, quantity)| {
+                    cumulative += quantity;
+                    notional += price * quantity;
+                    LiquidityPoint {
+                        source,
+                        price,
+                        quantity,
+                        cumulative_quantity: cumulative,
+                        average_price: notional / cumulative,
+                    }
+                })
+                .collect()
+        };
+        Self {
+            symbol: book.symbol.clone(),
+            cex_exchange: book.exchange.clone(),
+            dex_exchange: ladder.exchange.clone(),
+            asks: side(AmountSide::Buy),
+            bids: side(AmountSide::Sell),
+            timestamp: book.timestamp.max(ladder.timestamp),
+        }
+    }
+
+    /// Points of `side` (Buy = asks, Sell = bids).
+    pub fn side(&self, side: AmountSide) -> &[LiquidityPoint] {
+        match side {
+            AmountSide::Buy => &self.asks,
+            AmountSide::Sell => &self.bids,
+        }
+    }
+
+    /// Total quantity available on `side`.
+    pub fn depth(&self, side: AmountSide) -> f64 {
+        self.side(side)
+            .last()
+            .map_or(0.0, |point| point.cumulative_quantity)
+    }
+
+    /// Average effective price of trading `quantity` on `side` across both venues; `None`
+    /// if the curve is not that deep.
+    pub fn average_price(&self, side: AmountSide, quantity: f64) -> Option<f64> {
+        if quantity <= 0.0 || quantity > self.depth(side) {
+            return None;
+        }
+        let (mut filled, mut notional) = (0.0, 0.0);
+        for point in self.side(side) {
+            let take = point.quantity.min(quantity - filled);
+            filled += take;
+            notional += take * point.price;
+            if filled >= quantity {
+                break;
+            }
+        }
+        Some(notional / filled)
+    }
+
+    /// Quantity that can be bought on `buy_on` and sold on the other venue while the
+    /// marginal effective bid stays above the marginal effective ask; selling all of it
+    /// maximizes the profit.
+    pub fn optimal_quantity(&self, buy_on: LiquiditySource) -> f64 {
+        let levels = |points: &[LiquidityPoint], source: LiquiditySource| -> Vec<(f64, f64)> {
+            points
+                .iter()
+                .filter(|point| point.source == source)
+                .map(|point| (point.price, point.quantity))
+                .collect()
+        };
+        let sell_on = match buy_on {
+            LiquiditySource::Cex => LiquiditySource::Dex,
+            LiquiditySource::Dex => LiquiditySource::Cex,
+        };
+        let asks = levels(&self.asks, buy_on);
+        let bids = levels(&self.bids, sell_on);
+
+        let (mut ask_idx, mut bid_idx) = (0, 0);
+        let (mut ask_left, mut bid_left) = (
+            asks.first().map_or(0.0, |l| l.1),
+            bids.first().map_or(0.0, |l| l.1),
+        );
+        let mut quantity = 0.0;
+        while ask_idx < asks.len() && bid_idx < bids.len() && bids[bid_idx].0 > asks[ask_idx].0 {
+            let take = ask_left.min(bid_left);
+            quantity += take;
+            ask_left -= take;
+            bid_left -= take;
+            if ask_left <= 0.0 {
+                ask_idx += 1;
+                ask_left = asks.get(ask_idx).map_or(0.0, |l| l.1);
+            }
+            if bid_left <= 0.0 {
+                bid_idx += 1;
+                bid_left = bids.get(bid_idx).map_or(0.0, |l| l.1);
+            }
+        }
+        quantity
+    }
+}
+
+fn effective_levels<'a>(
+    levels: &'a [OrderBookLevel],
+    exchange: &'a Exchange,
+    side: AmountSide,
+    fee_overrides: Option<&'a FeeOverrides>,
+) -> impl Iterator<Item = (f64, f64)> + 'a {
+    levels
+        .iter()
+        .filter(|l| l.price > 0.0 && l.quantity > 0.0)
+        .map(move |l| {
+            (
+                effective_price_with_overrides(l.price, exchange, side, fee_overrides),
+                l.quantity,
+            )
+        })
+}
+
+fn leg_exchange(leg: &PriceData) -> &Exchange {
+    match leg {
+        PriceData::Cex(price) => &price.exchange,
+        PriceData::Dex(price) => &price.exchange,
+    }
+}
+
+impl ArbitrageScanner {
+    /// Sizes a CEX↔DEX `opportunity` at the [LiquidityCurve::optimal_quantity] of `curve`
+    /// (which must cover both of its venues). Prices stay those of the quote; commissions
+    /// are recomputed for the new quantity. `None` for other venues or when no quantity is
+    /// profitable.
+    pub fn size_from_liquidity_curve(
+        mut opportunity: ArbitrageOpportunity,
+        curve: &LiquidityCurve,
+    ) -> Option<ArbitrageOpportunity> {
+        let source = leg_exchange(&opportunity.source_leg);
+        let destination = leg_exchange(&opportunity.destination_leg);
+        let buy_on = if *source == curve.cex_exchange && *destination == curve.dex_exchange {
+            LiquiditySource::Cex
+        } else if *source == curve.dex_exchange && *destination == curve.cex_exchange {
+            LiquiditySource::Dex
+        } else {
+            return None;
+        };
+        let quantity = curve.optimal_quantity(buy_on);
+        if quantity <= 0.0 {
+            return None;
+        }
+        opportunity.executable_quantity = quantity;
+        opportunity.total_commission_quote = quantity
+            * (opportunity.effective_ask * opportunity.source_commission_percent
+                + opportunity.effective_bid * opportunity.destination_commission_percent)
+            / 100.0;
+        Some(opportunity)
+    }
+}
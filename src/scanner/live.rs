@@ -0,0 +1,543 @@
+//! Live WebSocket scanner with scanner-managed reconnects.
+//!
+//! Unlike [ArbitrageScanner::scan_arbitrage_from_websockets], every venue connection is
+//! supervised by the scanner: dropped connections are reconnected through a per-venue
+//! [CircuitBreaker], and the resulting state changes are emitted next to opportunity
+//! snapshots as [ScannerEvent]s. With [LiveScanOptions::with_rest_fallback], a venue whose
+//! breaker is open is polled over REST until its WebSocket recovers. A [ScannerHandle]
+//! pauses, resumes or stops the scanner and answers queries for its latest state.
+
+use crate::common::symbol_registry;
+use crate::common::{
+    BoundedCache, CacheLimits, CexExchange, CexPrice, Exchange, FeeOverrides, MarketScannerError,
+    Timestamp, get_timestamp_millis, take_frame_stamp,
+};
+use crate::scanner::circuit_breaker::{CircuitBreaker, CircuitBreakerConfig, CircuitState};
+use crate::scanner::control::{ScannerHandle, ScannerRunState};
+use crate::scanner::expiry::OpportunityClosed;
+use crate::scanner::funding::FundingModel;
+use crate::scanner::heatmap::LivePriceCache;
+use crate::scanner::latency::LatencyTrace;
+use crate::scanner::maintenance::MaintenanceMonitor;
+use crate::scanner::match_metrics::{MatchMetrics, RejectReason};
+use crate::scanner::reload::SettingsHandle;
+use crate::scanner::sizing::ZeroSizePolicy;
+use crate::scanner::volatility::{VolatilityGuard, VolatilityGuardConfig};
+use crate::scanner::{ArbitrageOpportunity, ArbitrageScanner};
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+use tokio::sync::mpsc;
+
+/// Options for [ArbitrageScanner::scan_live].
+#[derive(Debug, Clone, Default)]
+pub struct LiveScanOptions {
+    /// Custom fee rates used for effective prices
+    pub fee_overrides: Option<FeeOverrides>,
+    /// Delay between reconnects of a dropped venue (0 → 1000 ms)
+    pub reconnect_delay_ms: u64,
+    /// Per-venue circuit breaker thresholds
+    pub circuit_breaker: CircuitBreakerConfig,
+    /// Venues reported under maintenance are left out of snapshots
+    pub maintenance: Option<MaintenanceMonitor>,
+    /// When set, every opportunity is classified by funding viability
+    pub funding_model: Option<FundingModel>,
+    /// When set, volatile markets need a larger spread before opportunities are emitted
+    pub volatility_guard: Option<VolatilityGuardConfig>,
+    /// REST polling interval for venues whose circuit breaker is open (0 disables the fallback)
+    pub rest_fallback_interval_ms: u64,
+    /// Pause/resume/stop control of the scanner
+    pub handle: Option<ScannerHandle>,
+    /// Shared copy of the scanner's latest prices
+    pub price_cache: Option<LivePriceCache>,
+    /// Handling of opportunities with a zero-size leg (default: kept)
+    pub zero_size_policy: ZeroSizePolicy,
+    /// Bounds of the latest-price caches (default: unbounded)
+    pub cache_limits: CacheLimits,
+    /// Reloadable thresholds, filters and fees, read for every snapshot
+    pub settings: Option<SettingsHandle>,
+}
+
+impl LiveScanOptions {
+    pub fn with_fee_overrides(mut self, fee_overrides: FeeOverrides) -> Self {
+        self.fee_overrides = Some(fee_overrides);
+        self
+    }
+
+    pub fn with_reconnect_delay_ms(mut self, reconnect_delay_ms: u64) -> Self {
+        self.reconnect_delay_ms = reconnect_delay_ms;
+        self
+    }
+
+    pub fn with_circuit_breaker(mut self, circuit_breaker: CircuitBreakerConfig) -> Self {
+        self.circuit_breaker = circuit_breaker;
+        self
+    }
+
+    pub fn with_maintenance(mut self, maintenance: MaintenanceMonitor) -> Self {
+        self.maintenance = Some(maintenance);
+        self
+    }
+
+    pub fn with_funding_model(mut self, funding_model: FundingModel) -> Self {
+        self.funding_model = Some(funding_model);
+        self
+    }
+
+    pub fn with_volatility_guard(mut self, volatility_guard: VolatilityGuardConfig) -> Self {
+        self.volatility_guard = Some(volatility_guard);
+        self
+    }
+
+    /// While a venue's circuit breaker is open, polls its REST prices every `interval_ms`
+    /// so the venue keeps contributing (possibly stale) prices until WebSocket recovers.
+    pub fn with_rest_fallback(mut self, interval_ms: u64) -> Self {
+        self.rest_fallback_interval_ms = interval_ms;
+        self
+    }
+
+    /// Controls the scanner through `handle` (keep a clone to call
+    /// [ScannerHandle::pause], [ScannerHandle::resume] or [ScannerHandle::stop]).
+    pub fn with_handle(mut self, handle: ScannerHandle) -> Self {
+        self.handle = Some(handle);
+        self
+    }
+
+    /// Mirrors the scanner's latest prices into `price_cache` (keep a clone to build
+    /// [LivePriceCache::spread_heatmap]s while the scanner runs).
+    pub fn with_price_cache(mut self, price_cache: LivePriceCache) -> Self {
+        self.price_cache = Some(price_cache);
+        self
+    }
+
+    /// Bounds the scanner's latest-price cache, and those of its [ScannerHandle] and
+    /// `price_cache`, so large watchlists have predictable memory. Evicted prices leave the
+    /// snapshots until their venue updates them again; [ScannerHandle::cache_stats] counts
+    /// the evictions.
+    pub fn with_cache_limits(mut self, cache_limits: CacheLimits) -> Self {
+        self.cache_limits = cache_limits;
+        self
+    }
+
+    /// Reads thresholds, filters and fees from `settings` for every snapshot, so
+    /// [SettingsHandle::reload_from] and its watch tasks retune the scanner without
+    /// reconnecting. Fees from the settings replace [Self::with_fee_overrides] while they
+    /// set any.
+    pub fn with_settings(mut self, settings: SettingsHandle) -> Self {
+        self.settings = Some(settings);
+        self
+    }
+
+    /// Drops or re-sizes opportunities whose legs quote no quantity (see [ZeroSizePolicy]).
+    /// [ZeroSizePolicy::DepthFallback] fetches order books before the snapshot is emitted.
+    pub fn with_zero_size_policy(mut self, zero_size_policy: ZeroSizePolicy) -> Self {
+        self.zero_size_policy = zero_size_policy;
+        self
+    }
+}
+
+/// Source of a venue's prices in the live scanner.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum VenueFeed {
+    /// Streaming over WebSocket
+    WebSocket,
+    /// Polling REST while the WebSocket circuit breaker is open
+    RestPolling,
+}
+
+/// Event emitted by the live scanner.
+#[derive(Debug, Clone)]
+pub enum ScannerEvent {
+    /// Opportunity snapshot (sorted by profitability) after a price update
+    Opportunities(Vec<ArbitrageOpportunity>),
+    /// Opportunities that expired; emitted by [crate::scanner::ScannerContext::scan_live]
+    /// when the context has an expiry
+    OpportunityClosed(Vec<OpportunityClosed>),
+    /// A venue's circuit breaker changed state
+    CircuitStateChanged {
+        exchange: CexExchange,
+        state: CircuitState,
+        timestamp: Timestamp,
+    },
+    /// A venue switched between WebSocket and REST fallback prices
+    FeedChanged {
+        exchange: CexExchange,
+        feed: VenueFeed,
+        timestamp: Timestamp,
+    },
+    /// The scanner was paused, resumed or stopped through its [ScannerHandle]
+    StateChanged {
+        state: ScannerRunState,
+        timestamp: Timestamp,
+    },
+    /// A venue connection failed. Retryable errors (see
+    /// [MarketScannerError::is_retryable]) go through the circuit breaker; after a
+    /// permanent one the venue is no longer reconnected
+    VenueError {
+        exchange: CexExchange,
+        error: String,
+        retryable: bool,
+        timestamp: Timestamp,
+    },
+}
+
+/// Input of the aggregation task, produced by the venue supervisors.
+enum LiveInput {
+    Price(CexPrice),
+    Circuit(CexExchange, CircuitState),
+    Feed(CexExchange, VenueFeed),
+    Error(CexExchange, MarketScannerError),
+}
+
+impl ArbitrageScanner {
+    /// Connects to the given CEX WebSocket streams and emits [ScannerEvent]s: opportunity
+    /// snapshots as prices arrive, and circuit breaker state changes per venue.
+    ///
+    /// Venues are reconnected by the scanner for as long as the receiver is alive, but a
+    /// venue that keeps failing is paused by its circuit breaker (see [CircuitBreakerConfig]).
+    /// Prices of a venue are dropped from the snapshot while its breaker is open.
+    ///
+    /// While paused through [LiveScanOptions::with_handle], prices keep updating the cache
+    /// but no snapshots are emitted; the first price update after resuming emits one.
+    /// Stopping closes every venue connection and ends the channel.
+    pub async fn scan_live(
+        symbols: &[&str],
+        cex_exchanges: &[CexExchange],
+        options: LiveScanOptions,
+    ) -> Result<mpsc::Receiver<ScannerEvent>, MarketScannerError> {
+        let ws_exchanges: Vec<_> = cex_exchanges
+            .iter()
+            .filter(|ex| Self::exchange_supports_websocket(ex))
+            .cloned()
+            .collect();
+
+        if ws_exchanges.is_empty() {
+            return Err(MarketScannerError::ApiError(
+                "No WebSocket-supported exchanges in the list".to_string(),
+            ));
+        }
+        if symbols.is_empty() {
+            return Err(MarketScannerError::InvalidSymbol(
+                "At least one symbol required".to_string(),
+            ));
+        }
+
+        let symbols_vec: Vec<String> = symbols.iter().map(|s| (*s).to_string()).collect();
+        let handle = options.handle.clone().unwrap_or_default();
+        if options.cache_limits != CacheLimits::default() {
+            handle.set_cache_limits(options.cache_limits);
+            if let Some(shared) = &options.price_cache {
+                shared.set_limits(options.cache_limits);
+            }
+        }
+        handle.expect_feeds(&ws_exchanges, &symbols_vec);
+        let (tx_input, mut rx_input) = mpsc::channel::<LiveInput>(256);
+        for ex in ws_exchanges {
+            let venue = supervise_venue(ex, symbols_vec.clone(), options.clone(), tx_input.clone());
+            let handle = handle.clone();
+            symbol_registry::spawn(async move {
+                // Dropping the supervisor closes its venue connection
+                tokio::select! {
+                    _ = venue => {}
+                    _ = handle.stopped() => {}
+                }
+            });
+        }
+        drop(tx_input);
+
+        let mut control = handle.subscribe();
+        let mut run_state = *control.borrow_and_update();
+        let (tx, rx) = mpsc::channel(64);
+        symbol_registry::spawn(async move {
+            let mut cache: BoundedCache<(Exchange, String), CexPrice> =
+                BoundedCache::new(options.cache_limits);
+            let mut volatility = options.volatility_guard.clone().map(VolatilityGuard::new);
+
+            loop {
+                let input = tokio::select! {
+                    // Stopping also drops the venue supervisors: report the stop first
+                    biased;
+                    changed = control.changed() => {
+                        if changed.is_err() {
+                            return;
+                        }
+                        let state = *control.borrow_and_update();
+                        if state == run_state {
+                            continue;
+                        }
+                        run_state = state;
+                        let event = ScannerEvent::StateChanged {
+                            state,
+                            timestamp: Timestamp::now(),
+                        };
+                        if tx.send(event).await.is_err() || state == ScannerRunState::Stopped {
+                            return;
+                        }
+                        continue;
+                    }
+                    input = rx_input.recv() => match input {
+                        Some(input) => input,
+                        None => return,
+                    },
+                };
+                let event = match input {
+                    LiveInput::Price(price) => {
+                        // Skip invalid prices so a zero update does not overwrite the last valid one
+                        if price.mid_price <= 0.0
+                            || price.bid_price <= 0.0
+                            || price.ask_price <= 0.0
+                        {
+                            continue;
+                        }
+                        let stamp = take_frame_stamp(&price);
+                        if let Some(guard) = volatility.as_mut() {
+                            guard.record(&price);
+                        }
+                        cache.insert(
+                            (price.exchange.clone(), price.symbol.clone()),
+                            price.clone(),
+                        );
+                        if let Some(shared) = &options.price_cache {
+                            shared.insert(price.clone());
+                        }
+                        handle.record_price(price.clone());
+                        if run_state != ScannerRunState::Running {
+                            continue;
+                        }
+                        let inserted_at = Instant::now();
+                        // One settings value per snapshot, even if a reload lands meanwhile
+                        let settings = options.settings.as_ref().map(SettingsHandle::current);
+                        let fee_overrides = settings
+                            .as_ref()
+                            .and_then(|settings| settings.fee_overrides())
+                            .or_else(|| options.fee_overrides.clone());
+                        let mut metrics = MatchMetrics::new();
+                        let mut opps = Self::snapshot_from_cache(
+                            cache.values(),
+                            &symbols_vec,
+                            fee_overrides.as_ref(),
+                            options.maintenance.as_ref(),
+                            Some(&mut metrics),
+                        );
+                        if let Some(settings) = &settings {
+                            let before = opps.len();
+                            opps.retain(|o| settings.allows(o));
+                            metrics
+                                .reject(RejectReason::SettingsFilter, (before - opps.len()) as u64);
+                        }
+                        let matched_at = Instant::now();
+                        if let Some(guard) = &volatility {
+                            let before = opps.len();
+                            guard.filter(&mut opps);
+                            metrics.reject(
+                                RejectReason::VolatilityGuard,
+                                (before - opps.len()) as u64,
+                            );
+                        }
+                        if options.zero_size_policy != ZeroSizePolicy::Keep {
+                            let before = opps.len();
+                            opps = Self::apply_zero_size_policy(
+                                opps,
+                                options.zero_size_policy,
+                                fee_overrides.as_ref(),
+                            )
+                            .await;
+                            metrics.reject(
+                                RejectReason::ZeroSize,
+                                before.saturating_sub(opps.len()) as u64,
+                            );
+                        }
+                        metrics.opportunities_emitted = opps.len() as u64;
+                        handle.record_match_metrics(&metrics);
+                        if let Some(model) = &options.funding_model {
+                            model.classify_all(&mut opps);
+                        }
+                        if let Some(stamp) = stamp {
+                            LatencyTrace::new(
+                                &price,
+                                &stamp,
+                                inserted_at,
+                                matched_at,
+                                Instant::now(),
+                            )
+                            .attach(&price, &mut opps);
+                        }
+                        handle.record_opportunities(&opps);
+                        ScannerEvent::Opportunities(opps)
+                    }
+                    LiveInput::Circuit(exchange, state) => {
+                        if state == CircuitState::Open {
+                            let venue = Exchange::Cex(exchange.clone());
+                            cache.retain(|(ex, _), _| *ex != venue);
+                            if let Some(shared) = &options.price_cache {
+                                shared.remove_venue(&venue);
+                            }
+                            handle.remove_venue(&venue);
+                        }
+                        ScannerEvent::CircuitStateChanged {
+                            exchange,
+                            state,
+                            timestamp: Timestamp::now(),
+                        }
+                    }
+                    LiveInput::Feed(exchange, feed) => ScannerEvent::FeedChanged {
+                        exchange,
+                        feed,
+                        timestamp: Timestamp::now(),
+                    },
+                    LiveInput::Error(exchange, error) => {
+                        let retryable = error.is_retryable();
+                        if !retryable {
+                            // The venue is not reconnected; its prices only get staler
+                            let venue = Exchange::Cex(exchange.clone());
+                            cache.retain(|(ex, _), _| *ex != venue);
+                            if let Some(shared) = &options.price_cache {
+                                shared.remove_venue(&venue);
+                            }
+                            handle.remove_venue(&venue);
+                        }
+                        ScannerEvent::VenueError {
+                            exchange,
+                            error: error.to_string(),
+                            retryable,
+                            timestamp: Timestamp::now(),
+                        }
+                    }
+                };
+                if tx.send(event).await.is_err() {
+                    return;
+                }
+            }
+        });
+
+        Ok(rx)
+    }
+}
+
+/// Keeps one venue connected, reconnecting through its circuit breaker until the
+/// scanner output is dropped or the venue fails with a permanent error. While the breaker
+/// is open, REST prices are polled instead when the REST fallback is enabled.
+async fn supervise_venue(
+    exchange: CexExchange,
+    symbols: Vec<String>,
+    options: LiveScanOptions,
+    tx: mpsc::Sender<LiveInput>,
+) {
+    let symbol_refs: Vec<&str> = symbols.iter().map(|s| s.as_str()).collect();
+    let delay = Duration::from_millis(if options.reconnect_delay_ms == 0 {
+        1000
+    } else {
+        options.reconnect_delay_ms
+    });
+    let mut breaker = CircuitBreaker::new(options.circuit_breaker.clone());
+    let mut feed = VenueFeed::WebSocket;
+
+    loop {
+        if tx.is_closed() {
+            return;
+        }
+
+        let now = get_timestamp_millis();
+        if let Some(state) = breaker.poll(now) {
+            if tx
+                .send(LiveInput::Circuit(exchange.clone(), state))
+                .await
+                .is_err()
+            {
+                return;
+            }
+        }
+        if breaker.state() == CircuitState::Open {
+            let mut wait_ms = breaker.remaining_open_ms(now).max(1);
+            if options.rest_fallback_interval_ms > 0 {
+                if feed != VenueFeed::RestPolling {
+                    feed = VenueFeed::RestPolling;
+                    if tx
+                        .send(LiveInput::Feed(exchange.clone(), feed))
+                        .await
+                        .is_err()
+                    {
+                        return;
+                    }
+                }
+                if !poll_rest_prices(&exchange, &symbol_refs, &tx).await {
+                    return;
+                }
+                wait_ms = wait_ms.min(options.rest_fallback_interval_ms);
+            }
+            tokio::time::sleep(Duration::from_millis(wait_ms)).await;
+            continue;
+        }
+
+        // Single connection per session; reconnects are driven by the breaker
+        let session =
+            ArbitrageScanner::stream_cex_prices_websocket(&exchange, &symbol_refs, 0, 0).await;
+        match session {
+            Ok(mut rx) => {
+                while let Some(price) = rx.recv().await {
+                    if feed != VenueFeed::WebSocket {
+                        feed = VenueFeed::WebSocket;
+                        if tx
+                            .send(LiveInput::Feed(exchange.clone(), feed))
+                            .await
+                            .is_err()
+                        {
+                            return;
+                        }
+                    }
+                    if let Some(state) = breaker.record_success(get_timestamp_millis()) {
+                        if tx
+                            .send(LiveInput::Circuit(exchange.clone(), state))
+                            .await
+                            .is_err()
+                        {
+                            return;
+                        }
+                    }
+                    if tx.send(LiveInput::Price(price)).await.is_err() {
+                        return;
+                    }
+                }
+            }
+            Err(error) => {
+                let retryable = error.is_retryable();
+                if tx
+                    .send(LiveInput::Error(exchange.clone(), error))
+                    .await
+                    .is_err()
+                    || !retryable
+                {
+                    return;
+                }
+            }
+        }
+
+        if let Some(state) = breaker.record_failure(get_timestamp_millis()) {
+            if tx
+                .send(LiveInput::Circuit(exchange.clone(), state))
+                .await
+                .is_err()
+            {
+                return;
+            }
+            continue;
+        }
+        tokio::time::sleep(delay).await;
+    }
+}
+
+/// Fetches the REST price of every symbol of `exchange` and forwards the successful ones.
+/// Returns false once the scanner output is gone.
+async fn poll_rest_prices(
+    exchange: &CexExchange,
+    symbols: &[&str],
+    tx: &mpsc::Sender<LiveInput>,
+) -> bool {
+    for symbol in symbols {
+        if let Ok(price) = ArbitrageScanner::get_cex_price(exchange, symbol).await {
+            if tx.send(LiveInput::Price(price)).await.is_err() {
+                return false;
+            }
+        }
+    }
+    !tx.is_closed()
+}
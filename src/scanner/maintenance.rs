@@ -0,0 +1,222 @@
+//! Venue maintenance awareness.
+//!
+//! [MaintenanceMonitor] polls the public status endpoints of venues that publish one
+//! (Binance system status, Kraken system status, OKX scheduled maintenance) and keeps
+//! track of which venues are currently under maintenance. The scanner skips those venues,
+//! and every status change is emitted as a [VenueStatusEvent].
+
+use crate::common::{CexExchange, MarketScannerError, Timestamp, create_http_client};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+use tokio::sync::mpsc;
+
+const BINANCE_STATUS_URL: &str = "https://api.binance.com/sapi/v1/system/status";
+const KRAKEN_STATUS_URL: &str = "https://api.kraken.com/0/public/SystemStatus";
+const OKX_STATUS_URL: &str = "https://www.okx.com/api/v5/system/status?state=ongoing";
+/// OKX `serviceType`s of trading-service maintenance: 5 (trading service), 8 (in batches of
+/// accounts) and 9 (in batches of products). WebSocket, block trading, bots, spread and copy
+/// trading windows leave spot trading up.
+const OKX_TRADING_SERVICE_TYPES: [&str; 3] = ["5", "8", "9"];
+
+/// Operational state of a venue as reported by its status endpoint.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum VenueStatus {
+    /// Venue reports normal operation
+    Operational,
+    /// Venue is (fully or partially) under maintenance; excluded from scans
+    Maintenance,
+    /// Venue has no status endpoint or it could not be reached; treated as available
+    Unknown,
+}
+
+/// Emitted whenever the status of a venue changes.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VenueStatusEvent {
+    pub exchange: CexExchange,
+    pub previous: VenueStatus,
+    pub status: VenueStatus,
+    /// Time the change was observed
+    pub timestamp: Timestamp,
+}
+
+/// Tracks venue maintenance state. Cheap to clone; clones share the same state.
+#[derive(Debug, Clone, Default)]
+pub struct MaintenanceMonitor {
+    statuses: Arc<RwLock<HashMap<CexExchange, VenueStatus>>>,
+}
+
+impl MaintenanceMonitor {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Starts polling the status endpoints of `exchanges` every `poll_interval_ms`
+    /// (0 → 60000 ms). Returns a receiver of status-change events; polling stops once
+    /// the receiver is dropped.
+    pub fn start(
+        &self,
+        exchanges: &[CexExchange],
+        poll_interval_ms: u64,
+    ) -> mpsc::Receiver<VenueStatusEvent> {
+        let (tx, rx) = mpsc::channel(64);
+        let monitor = self.clone();
+        let exchanges = exchanges.to_vec();
+        let interval_ms = if poll_interval_ms == 0 {
+            60_000
+        } else {
+            poll_interval_ms
+        };
+
+        tokio::spawn(async move {
+            let client = create_http_client();
+            loop {
+                for exchange in &exchanges {
+                    // Unreachable status endpoints must not take a venue out of the scan
+                    let status = fetch_venue_status(&client, exchange)
+                        .await
+                        .unwrap_or(VenueStatus::Unknown);
+                    if let Some(event) = monitor.set_status(exchange, status) {
+                        if tx.send(event).await.is_err() {
+                            return;
+                        }
+                    }
+                }
+                if tx.is_closed() {
+                    return;
+                }
+                tokio::time::sleep(std::time::Duration::from_millis(interval_ms)).await;
+            }
+        });
+
+        rx
+    }
+
+    /// Records `status` for `exchange`. Returns an event if the status changed.
+    /// Can also be used to feed maintenance windows from an external calendar.
+    pub fn set_status(
+        &self,
+        exchange: &CexExchange,
+        status: VenueStatus,
+    ) -> Option<VenueStatusEvent> {
+        let mut statuses = self.statuses.write().unwrap_or_else(|e| e.into_inner());
+        let previous = statuses
+            .insert(exchange.clone(), status)
+            .unwrap_or(VenueStatus::Unknown);
+        if previous == status {
+            return None;
+        }
+        Some(VenueStatusEvent {
+            exchange: exchange.clone(),
+            previous,
+            status,
+            timestamp: Timestamp::now(),
+        })
+    }
+
+    /// Last known status of `exchange` (`Unknown` if never observed).
+    pub fn status(&self, exchange: &CexExchange) -> VenueStatus {
+        self.statuses
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(exchange)
+            .copied()
+            .unwrap_or(VenueStatus::Unknown)
+    }
+
+    pub fn is_in_maintenance(&self, exchange: &CexExchange) -> bool {
+        self.status(exchange) == VenueStatus::Maintenance
+    }
+
+    /// Returns `exchanges` without the venues currently under maintenance.
+    pub fn available(&self, exchanges: &[CexExchange]) -> Vec<CexExchange> {
+        exchanges
+            .iter()
+            .filter(|ex| !self.is_in_maintenance(ex))
+            .cloned()
+            .collect()
+    }
+}
+
+/// Fetches the current status of `exchange` from its public status endpoint.
+/// Venues without such an endpoint report `Unknown`.
+pub async fn fetch_venue_status(
+    client: &reqwest::Client,
+    exchange: &CexExchange,
+) -> Result<VenueStatus, MarketScannerError> {
+    let url = match exchange {
+        CexExchange::Binance => BINANCE_STATUS_URL,
+        CexExchange::Kraken => KRAKEN_STATUS_URL,
+        CexExchange::OKX => OKX_STATUS_URL,
+        _ => return Ok(VenueStatus::Unknown),
+    };
+    let response: serde_json::Value = client.get(url).send().await?.json().await?;
+
+    match exchange {
+        // {"status": 0, "msg": "normal"} / {"status": 1, "msg": "system maintenance"}
+        CexExchange::Binance => match response["status"].as_i64() {
+            Some(0) => Ok(VenueStatus::Operational),
+            Some(_) => Ok(VenueStatus::Maintenance),
+            None => Err(MarketScannerError::ApiError(
+                "Binance system status missing status field".to_string(),
+            )),
+        },
+        // {"error": [], "result": {"status": "online" | "maintenance" | "cancel_only" | "post_only"}}
+        CexExchange::Kraken => match response["result"]["status"].as_str() {
+            Some("online") => Ok(VenueStatus::Operational),
+            Some(_) => Ok(VenueStatus::Maintenance),
+            None => Err(MarketScannerError::ApiError(
+                "Kraken system status missing result.status".to_string(),
+            )),
+        },
+        // {"code": "0", "data": [...ongoing maintenance windows...]}
+        CexExchange::OKX => {
+            if response["code"].as_str() != Some("0") {
+                return Err(MarketScannerError::ApiError(format!(
+                    "OKX system status error: {}",
+                    response["msg"]
+                )));
+            }
+            let windows = response["data"]
+                .as_array()
+                .map(Vec::as_slice)
+                .unwrap_or(&[]);
+            if windows.iter().any(okx_window_affects_spot) {
+                Ok(VenueStatus::Maintenance)
+            } else {
+                Ok(VenueStatus::Operational)
+            }
+        }
+        _ => Ok(VenueStatus::Unknown),
+    }
+}
+
+/// Whether an ongoing OKX maintenance window takes spot trading or funding down.
+///
+/// Demo-trading windows (`env` "2") are ignored. A window listing product lines only counts
+/// if one of them is spot or funding; a funding window counts whatever its service type.
+pub fn okx_window_affects_spot(window: &serde_json::Value) -> bool {
+    if window["env"].as_str() == Some("2") {
+        return false;
+    }
+    let product_lines: Vec<String> = match &window["productLine"] {
+        serde_json::Value::String(lines) => lines
+            .split(',')
+            .map(|line| line.trim().to_uppercase())
+            .filter(|line| !line.is_empty())
+            .collect(),
+        serde_json::Value::Array(lines) => lines
+            .iter()
+            .filter_map(|line| line.as_str())
+            .map(str::to_uppercase)
+            .collect(),
+        _ => Vec::new(),
+    };
+    if product_lines.iter().any(|line| line == "FUNDING") {
+        return true;
+    }
+    let trading = window["serviceType"]
+        .as_str()
+        .is_some_and(|service| OKX_TRADING_SERVICE_TYPES.contains(&service));
+    trading && (product_lines.is_empty() || product_lines.iter().any(|line| line == "SPOT"))
+}
@@ -0,0 +1,97 @@
+//! Counters of how well the matcher turns prices into opportunities.
+//!
+//! A scanner that emits nothing may be healthy (no spread) or broken (every price left out,
+//! every pair filtered). [MatchMetrics] counts the buy / sell pairs evaluated, the pairs
+//! rejected per [RejectReason], the opportunities emitted and the cached prices per
+//! symbol. The live scanner accumulates them in its [crate::scanner::ScannerHandle] (see
+//! [crate::scanner::ScannerHandle::match_metrics]);
+//! [crate::scanner::ArbitrageScanner::opportunities_from_prices_with_metrics] counts a
+//! single match.
+
+use crate::common::Timestamp;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Why a price or a buy / sell pair did not become an emitted opportunity.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum RejectReason {
+    /// Price left out because its venue is under maintenance (counts prices, not pairs)
+    Maintenance,
+    /// Price left out because its market is halted, in auction or post-only (counts
+    /// prices, not pairs)
+    NotTrading,
+    /// Both legs on the same venue
+    SameVenue,
+    /// Legs quote different logical markets
+    MarketMismatch,
+    /// Effective bid at or below the effective ask
+    NoSpread,
+    /// Spread below the 0.01% matching floor
+    BelowMinSpread,
+    /// Removed by the reloadable [crate::scanner::ScannerSettings] filters
+    SettingsFilter,
+    /// Removed by the volatility guard
+    VolatilityGuard,
+    /// Removed by the zero-size policy
+    ZeroSize,
+}
+
+/// Matching counters, accumulated over snapshots.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct MatchMetrics {
+    /// Snapshots built
+    pub snapshots: u64,
+    /// Buy / sell candidate pairs evaluated
+    pub candidates_evaluated: u64,
+    /// Rejections per reason
+    pub rejected: BTreeMap<RejectReason, u64>,
+    /// Opportunities in the emitted snapshots
+    pub opportunities_emitted: u64,
+    /// Cached venue prices per requested symbol at the latest snapshot
+    pub cache_sizes: BTreeMap<String, usize>,
+    /// When the latest snapshot was counted
+    pub updated_at: Option<Timestamp>,
+}
+
+impl MatchMetrics {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Rejections for `reason`.
+    pub fn rejected(&self, reason: RejectReason) -> u64 {
+        self.rejected.get(&reason).copied().unwrap_or_default()
+    }
+
+    /// Rejections over every reason.
+    pub fn total_rejected(&self) -> u64 {
+        self.rejected.values().sum()
+    }
+
+    /// Share of evaluated pairs that were emitted (0 before any evaluation).
+    pub fn emit_ratio(&self) -> f64 {
+        if self.candidates_evaluated == 0 {
+            0.0
+        } else {
+            self.opportunities_emitted as f64 / self.candidates_evaluated as f64
+        }
+    }
+
+    pub(crate) fn reject(&mut self, reason: RejectReason, count: u64) {
+        if count > 0 {
+            *self.rejected.entry(reason).or_default() += count;
+        }
+    }
+
+    /// Adds the counters of `snapshot` and takes its cache sizes.
+    pub(crate) fn merge(&mut self, snapshot: &MatchMetrics) {
+        self.snapshots += snapshot.snapshots;
+        self.candidates_evaluated += snapshot.candidates_evaluated;
+        for (reason, count) in &snapshot.rejected {
+            self.reject(*reason, *count);
+        }
+        self.opportunities_emitted += snapshot.opportunities_emitted;
+        self.cache_sizes = snapshot.cache_sizes.clone();
+        self.updated_at = snapshot.updated_at.or(self.updated_at);
+    }
+}
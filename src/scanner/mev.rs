@@ -0,0 +1,168 @@
+//! Sandwich / MEV risk heuristics for DEX legs.
+//!
+//! A DEX swap is exposed to sandwiching when it is large relative to the pool, moves the
+//! price noticeably, or is sent while the mempool is congested. [MevRiskModel::classify]
+//! grades each of these factors for every DEX leg of an opportunity and reports the worst
+//! leg, so execution engines can route risky swaps through a private relay or skip them.
+
+use crate::common::{DexPrice, Exchange, normalize_symbol};
+use crate::scanner::{ArbitrageOpportunity, PriceData};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// MEV exposure grade of an opportunity's DEX legs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum MevRisk {
+    Low,
+    Medium,
+    High,
+}
+
+/// Heuristic thresholds plus optional per-pool liquidity and mempool gas price.
+#[derive(Debug, Clone)]
+pub struct MevRiskModel {
+    /// Liquidity (quote currency) per DEX venue and normalized symbol
+    liquidity_quote: HashMap<(Exchange, String), f64>,
+    /// Trade notional / pool liquidity ratios graded Medium and High
+    liquidity_ratio_thresholds: (f64, f64),
+    /// Price impact (percent) graded Medium and High
+    price_impact_thresholds_pct: (f64, f64),
+    /// Gas price (gwei) above which the mempool counts as congested
+    congested_gas_gwei: Option<f64>,
+}
+
+impl Default for MevRiskModel {
+    /// Medium from 0.5% of pool liquidity or 0.3% impact, High from 2% or 1%.
+    fn default() -> Self {
+        Self {
+            liquidity_quote: HashMap::new(),
+            liquidity_ratio_thresholds: (0.005, 0.02),
+            price_impact_thresholds_pct: (0.3, 1.0),
+            congested_gas_gwei: None,
+        }
+    }
+}
+
+impl MevRiskModel {
+    /// Declares the pool liquidity (quote currency) behind `symbol` on a DEX venue.
+    pub fn with_liquidity(
+        mut self,
+        exchange: Exchange,
+        symbol: &str,
+        liquidity_quote: f64,
+    ) -> Self {
+        self.liquidity_quote
+            .insert((exchange, normalize_symbol(symbol)), liquidity_quote);
+        self
+    }
+
+    /// Trade-size-to-liquidity ratios at which a leg is graded Medium / High.
+    pub fn with_liquidity_ratio_thresholds(mut self, medium: f64, high: f64) -> Self {
+        self.liquidity_ratio_thresholds = (medium, high);
+        self
+    }
+
+    /// Price impact percentages at which a leg is graded Medium / High.
+    pub fn with_price_impact_thresholds_pct(mut self, medium: f64, high: f64) -> Self {
+        self.price_impact_thresholds_pct = (medium, high);
+        self
+    }
+
+    /// Route gas price (gwei) from which the mempool is considered congested: at or above
+    /// it a leg is Medium, at twice it High.
+    pub fn with_congested_gas_gwei(mut self, gwei: f64) -> Self {
+        self.congested_gas_gwei = Some(gwei);
+        self
+    }
+
+    /// Worst grade across the DEX legs of `opportunity` (None for CEX-only opportunities).
+    pub fn classify(&self, opportunity: &ArbitrageOpportunity) -> Option<MevRisk> {
+        [
+            (&opportunity.source_leg, true),
+            (&opportunity.destination_leg, false),
+        ]
+        .into_iter()
+        .filter_map(|(leg, buy)| match leg {
+            PriceData::Dex(price) => {
+                Some(self.classify_leg(price, buy, opportunity.executable_quantity))
+            }
+            PriceData::Cex(_) => None,
+        })
+        .max()
+    }
+
+    /// Sets [ArbitrageOpportunity::mev_risk] on every opportunity.
+    pub fn classify_all(&self, opportunities: &mut [ArbitrageOpportunity]) {
+        for opportunity in opportunities {
+            opportunity.mev_risk = self.classify(opportunity);
+        }
+    }
+
+    /// Grade of one DEX leg trading `quantity` (bought when `buy`, sold otherwise).
+    pub fn classify_leg(&self, price: &DexPrice, buy: bool, quantity: f64) -> MevRisk {
+        let mut grades = Vec::with_capacity(3);
+
+        let key = (price.exchange.clone(), normalize_symbol(&price.symbol));
+        if let Some(&liquidity) = self.liquidity_quote.get(&key) {
+            let executed_price = if buy {
+                price.ask_price
+            } else {
+                price.bid_price
+            };
+            let ratio = if liquidity > 0.0 {
+                quantity * executed_price / liquidity
+            } else {
+                f64::INFINITY
+            };
+            grades.push(grade(ratio, self.liquidity_ratio_thresholds));
+        }
+
+        grades.push(grade(
+            price_impact_pct(price),
+            self.price_impact_thresholds_pct,
+        ));
+
+        if let (Some(congested), Some(gwei)) = (self.congested_gas_gwei, gas_price_gwei(price, buy))
+        {
+            grades.push(grade(gwei, (congested, congested * 2.0)));
+        }
+
+        let worst = grades.iter().copied().max().unwrap_or(MevRisk::Low);
+        // Several moderate signals together are as exposed as one strong signal
+        let moderate = grades.iter().filter(|g| **g >= MevRisk::Medium).count();
+        if worst == MevRisk::Medium && moderate >= 2 {
+            MevRisk::High
+        } else {
+            worst
+        }
+    }
+}
+
+fn grade(value: f64, (medium, high): (f64, f64)) -> MevRisk {
+    if value >= high {
+        MevRisk::High
+    } else if value >= medium {
+        MevRisk::Medium
+    } else {
+        MevRisk::Low
+    }
+}
+
+/// Half of the quoted bid/ask spread relative to mid: the price move of one quoted swap.
+fn price_impact_pct(price: &DexPrice) -> f64 {
+    if price.mid_price <= 0.0 || price.ask_price < price.bid_price {
+        return 0.0;
+    }
+    (price.ask_price - price.bid_price) / 2.0 / price.mid_price * 100.0
+}
+
+/// Gas price of the route used by the leg, in gwei.
+fn gas_price_gwei(price: &DexPrice, buy: bool) -> Option<f64> {
+    let route = if buy {
+        price.ask_route_summary.as_ref()
+    } else {
+        price.bid_route_summary.as_ref()
+    };
+    let wei: f64 = route?.gas_price.as_deref()?.parse().ok()?;
+    Some(wei / 1e9)
+}
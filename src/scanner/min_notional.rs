@@ -0,0 +1,148 @@
+//! Minimum order sizes of the legs of an opportunity.
+//!
+//! Venues reject orders below their exchange filters (minimum quantity, minimum notional).
+//! An opportunity whose executable quantity is worth less than the larger of its two legs'
+//! minimums cannot be traded on at least one leg, however wide the spread. A
+//! [MinNotionalGuard] holds the [OrderLimits] per venue and symbol, fetched from the venues
+//! ([MinNotionalGuard::fetch]) or set by hand, and annotates opportunities with a
+//! [NotionalFloor]; [ArbitrageOpportunity::is_below_min_notional] marks the ones that are
+//! not executable. DEX legs have no minimum.
+
+use crate::common::{CexExchange, Exchange, MarketKey, OrderLimits, logical_market_key};
+use crate::scanner::{ArbitrageOpportunity, ArbitrageScanner, PriceData};
+use futures::future::join_all;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Minimum tradable notional of an opportunity, set by a [MinNotionalGuard].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NotionalFloor {
+    /// Larger of the two legs' minimum order values, in quote currency
+    pub min_notional: f64,
+    /// Venue whose minimum sets the floor
+    pub binding_exchange: String,
+    /// Value of the executable quantity at the source ask, in quote currency
+    pub executable_notional: f64,
+}
+
+impl NotionalFloor {
+    /// Whether the executable quantity is large enough to be ordered on both legs.
+    pub fn is_met(&self) -> bool {
+        self.executable_notional >= self.min_notional
+    }
+}
+
+impl ArbitrageOpportunity {
+    /// Whether a [MinNotionalGuard] found the executable quantity below a leg's minimum
+    /// order size, so the opportunity cannot be traded (false when not checked).
+    pub fn is_below_min_notional(&self) -> bool {
+        self.notional_floor
+            .as_ref()
+            .is_some_and(|floor| !floor.is_met())
+    }
+}
+
+/// Order limits per venue and symbol.
+#[derive(Debug, Clone, Default)]
+pub struct MinNotionalGuard {
+    limits: HashMap<(CexExchange, MarketKey), OrderLimits>,
+    venue_defaults: HashMap<CexExchange, OrderLimits>,
+}
+
+impl MinNotionalGuard {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Limits of `symbol` on `exchange`.
+    pub fn with_limits(mut self, exchange: CexExchange, symbol: &str, limits: OrderLimits) -> Self {
+        let market = logical_market_key(symbol, &Exchange::Cex(exchange.clone()));
+        self.limits.insert((exchange, market), limits);
+        self
+    }
+
+    /// Limits of every symbol of `exchange` without limits of its own.
+    pub fn with_venue_default(mut self, exchange: CexExchange, limits: OrderLimits) -> Self {
+        self.venue_defaults.insert(exchange, limits);
+        self
+    }
+
+    /// Guard with the limits of `symbols` on `exchanges`, read from the venues' exchange
+    /// filters ([crate::common::CEXTrait::get_order_limits]). Venues that do not expose
+    /// them, or fail to answer, are left without limits.
+    pub async fn fetch(exchanges: &[CexExchange], symbols: &[&str]) -> Self {
+        let requests = exchanges.iter().flat_map(|exchange| {
+            symbols.iter().map(move |symbol| async move {
+                let limits = ArbitrageScanner::get_cex_order_limits(exchange, symbol).await;
+                (exchange.clone(), *symbol, limits)
+            })
+        });
+        join_all(requests).await.into_iter().fold(
+            Self::new(),
+            |guard, (exchange, symbol, limits)| match limits {
+                Ok(limits) => guard.with_limits(exchange, symbol, limits),
+                Err(_) => guard,
+            },
+        )
+    }
+
+    /// Limits of `symbol` on `exchange`, falling back to the venue default.
+    pub fn limits(&self, exchange: &CexExchange, symbol: &str) -> Option<OrderLimits> {
+        let market = logical_market_key(symbol, &Exchange::Cex(exchange.clone()));
+        self.limits
+            .get(&(exchange.clone(), market))
+            .or_else(|| self.venue_defaults.get(exchange))
+            .copied()
+    }
+
+    /// Notional floor of `opportunity`; `None` if neither leg has limits.
+    pub fn floor(&self, opportunity: &ArbitrageOpportunity) -> Option<NotionalFloor> {
+        let source_ask = leg_price(&opportunity.source_leg, true);
+        let destination_bid = leg_price(&opportunity.destination_leg, false);
+        let legs = [
+            (&opportunity.source_leg, source_ask),
+            (&opportunity.destination_leg, destination_bid),
+        ];
+        let (min_notional, binding_exchange) = legs
+            .into_iter()
+            .filter_map(|(leg, price)| {
+                let PriceData::Cex(cex) = leg else {
+                    return None;
+                };
+                let Exchange::Cex(exchange) = &cex.exchange else {
+                    return None;
+                };
+                let limits = self.limits(exchange, &cex.symbol)?;
+                Some((
+                    limits.min_notional_at(price),
+                    ArbitrageScanner::exchange_name(&cex.exchange),
+                ))
+            })
+            .max_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(std::cmp::Ordering::Equal))?;
+        Some(NotionalFloor {
+            min_notional,
+            binding_exchange,
+            executable_notional: opportunity.executable_quantity * source_ask,
+        })
+    }
+
+    /// Sets [ArbitrageOpportunity::notional_floor] of `opportunity`.
+    pub fn annotate(&self, opportunity: &mut ArbitrageOpportunity) {
+        opportunity.notional_floor = self.floor(opportunity);
+    }
+
+    pub fn annotate_all(&self, opportunities: &mut [ArbitrageOpportunity]) {
+        for opportunity in opportunities {
+            self.annotate(opportunity);
+        }
+    }
+}
+
+fn leg_price(leg: &PriceData, ask: bool) -> f64 {
+    match (leg, ask) {
+        (PriceData::Cex(p), true) => p.ask_price,
+        (PriceData::Cex(p), false) => p.bid_price,
+        (PriceData::Dex(p), true) => p.ask_price,
+        (PriceData::Dex(p), false) => p.bid_price,
+    }
+}
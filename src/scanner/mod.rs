@@ -1,6 +1,9 @@
+use crate::common::symbol_registry;
 use crate::common::{
-    AmountSide, CEXTrait, CexExchange, CexPrice, DEXTrait, DexAggregator, DexPrice, Exchange,
-    FeeOverrides, MarketScannerError, effective_price_with_overrides, fee_rate_with_overrides,
+    AmountSide, AmountSpec, CEXTrait, Capabilities, CexExchange, CexPrice, DEXTrait, DexAggregator,
+    DexPrice, DexSourceFilter, Exchange, ExchangeTrait, FeeOverrides, MarketKey,
+    MarketScannerError, OrderBook, OrderLimits, Timestamp, effective_price_with_overrides,
+    fee_rate_with_overrides, logical_market_key, rest_connection_stats, take_frame_stamp,
 };
 use crate::dex::chains::Token;
 use crate::{
@@ -9,10 +12,131 @@ use crate::{
 };
 use futures::future::join_all;
 use std::collections::HashMap;
+use std::time::Instant;
 use tokio::sync::mpsc;
 
+pub mod ack;
+pub mod aggregators;
+pub mod anomaly;
+pub mod backfill;
+pub mod capability_report;
+pub mod circuit_breaker;
+pub mod composite;
+pub mod context;
+pub mod control;
+mod decompose;
+pub mod dedup;
+pub mod depth_recorder;
+pub mod expiry;
+pub mod fan_out;
+pub mod feed_diff;
+pub mod fiat_calendar;
+pub mod funding;
+pub mod heatmap;
+pub mod incident;
+pub mod instruments;
+pub mod latency;
+pub mod lifecycle;
+pub mod liquidity_curve;
+pub mod live;
+pub mod maintenance;
+pub mod match_metrics;
+pub mod mev;
+pub mod min_notional;
+pub mod multi_hop;
 mod opportunity;
-pub use opportunity::{ArbitrageOpportunity, PriceData};
+pub mod precision;
+pub mod ranking;
+pub mod reload;
+pub mod report;
+pub mod sampling;
+pub mod schedule;
+pub mod sizing;
+pub mod soak;
+pub mod source;
+pub mod spread_breakdown;
+pub mod tee;
+pub mod template;
+pub mod transfer_cost;
+pub mod transfer_status;
+pub mod update_rate;
+pub mod venue_score;
+pub mod volatility;
+pub mod warmup;
+pub use ack::{AckOutcome, ExecutionAck, ExecutionReport, ExecutionStats, serve_ack_webhook};
+pub use aggregators::{AggregatorComparison, AggregatorQuote};
+pub use anomaly::{
+    AnomalyDetector, AnomalyDetectorConfig, AnomalyKind, MarketAnomaly, detect_market_anomalies,
+};
+pub use backfill::{
+    AlertBackfillReport, BackfilledAlert, PriceHistoryFrame, PriceReplay, ReplayCheckpoint,
+    ReplayProgress,
+};
+pub use capability_report::{CapabilityReport, VenueHealthCheck, VenueReport};
+pub use circuit_breaker::{CircuitBreaker, CircuitBreakerConfig, CircuitState};
+pub use composite::{
+    CompositeQuote, CompositeQuoteConfig, CompositeQuoteSource, QuoteSourceKind, SourceQuote,
+};
+pub use context::{OpportunityCloseSink, OpportunityFilter, OpportunitySink, ScannerContext};
+pub use control::{ScannerHandle, ScannerRunState};
+pub use dedup::{AlertDeduplicator, AlertRecord, AlertStore, JsonFileAlertStore};
+pub use depth_recorder::{
+    DepthRecorderOptions, DepthRecordingSummary, DepthSnapshot, DepthSnapshotReader,
+    DepthSnapshotWriter, depth_recording_files,
+};
+pub use expiry::{OpportunityClosed, OpportunityExpiry};
+pub use fan_out::{ChainDexQuote, ChainFanOut, DEFAULT_CHAIN_CONCURRENCY};
+pub use feed_diff::{FeedDiffOptions, FeedDiffRecorder, FeedDiffReport, FeedDiffSample};
+pub use fiat_calendar::{FiatCalendar, FiatCalendarHook, FiatRestriction};
+pub use funding::{FundingModel, OpportunityViability};
+pub use heatmap::{LivePriceCache, SpreadHeatmap};
+pub use incident::{
+    IncidentAction, IncidentKind, IncidentRouter, IncidentSeverity, IncidentSink, OpsgenieSink,
+    PagerDutySink, VenueIncident,
+};
+pub use instruments::InstrumentIdRefresh;
+pub use latency::LatencyTrace;
+pub use lifecycle::{CloseReason, LifecycleConfig, LifecycleEvent, OpportunityLifecycle};
+pub use liquidity_curve::{LiquidityCurve, LiquidityPoint, LiquiditySource};
+pub use live::{LiveScanOptions, ScannerEvent, VenueFeed};
+pub use maintenance::{MaintenanceMonitor, VenueStatus, VenueStatusEvent};
+pub use match_metrics::{MatchMetrics, RejectReason};
+pub use mev::{MevRisk, MevRiskModel};
+pub use min_notional::{MinNotionalGuard, NotionalFloor};
+pub use multi_hop::{DEFAULT_MAX_HOPS, Hop, MultiHopOpportunity, MultiHopOptions};
+pub use opportunity::{
+    ArbitrageOpportunity, MultiLegOpportunity, OPPORTUNITY_SCHEMA_VERSION, PriceData, SellTranche,
+};
+pub use precision::{FieldClass, OutputPrecision};
+pub use ranking::{CapitalEfficiency, CapitalEfficiencyModel, OpportunityRanking};
+pub use reload::{ScannerSettings, SettingsHandle, SettingsReload};
+pub use report::{
+    OpportunityAggregator, OpportunityReport, OpportunitySeasonality, OpportunitySummary,
+    SeasonalityBucket, VenuePairSeasonality, aggregate_opportunity_stream,
+};
+pub use sampling::{SampleKey, sample_stream};
+pub use schedule::{ScanSchedule, ScheduledScan};
+pub use sizing::ZeroSizePolicy;
+pub use soak::{
+    MemorySample, SoakProgress, VenueStability, WsSoakOptions, WsSoakRecorder, WsStabilityReport,
+    resident_memory_bytes,
+};
+pub use source::{
+    AggregatorPollingSource, CexWebSocketSource, MockPriceSource, PoolListenerSource, PriceSource,
+    RestPollingSource,
+};
+pub use spread_breakdown::{CostLayer, SpreadBreakdown, SpreadCostModel};
+pub use tee::{BackpressurePolicy, StreamTee, SubscriberStats};
+pub use template::AlertTemplate;
+pub use transfer_cost::{TransferCostModel, TransferCosts, TransferNetwork, TransferRoute};
+pub use transfer_status::{
+    AssetTransferStatus, NetworkTransferStatus, TransferStatusMonitor, fetch_asset_transfer_status,
+    parse_asset_transfer_status,
+};
+pub use update_rate::{FeedUpdateStats, UPDATE_RATE_WINDOW, UpdateRateTracker};
+pub use venue_score::{AutoScan, VenueScore, VenueScoreWeights, VenueScorer};
+pub use volatility::{VolatilityGuard, VolatilityGuardConfig};
+pub use warmup::{ReadinessReport, VenueReadiness, WarmUpOptions, WarmUpStep};
 
 /// Arbitrage scanner - fetches price data from CEX and DEX exchanges and finds arbitrage opportunities
 pub struct ArbitrageScanner;
@@ -39,11 +163,44 @@ impl ArbitrageScanner {
         quote_token: Option<&Token>,
         quote_amount: Option<f64>,
         fee_overrides: Option<&FeeOverrides>,
+    ) -> Result<Vec<ArbitrageOpportunity>, MarketScannerError> {
+        Self::scan_arbitrage_opportunities_with_dex_sources(
+            symbol,
+            cex_exchanges,
+            dex_exchanges,
+            base_token,
+            quote_token,
+            quote_amount,
+            fee_overrides,
+            &DexSourceFilter::default(),
+        )
+        .await
+    }
+
+    /// [Self::scan_arbitrage_opportunities] with the DEX quotes routed only through the
+    /// liquidity sources `dex_sources` allows (e.g. to keep RFQ sources with unfillable
+    /// quotes out of opportunity detection).
+    #[allow(clippy::too_many_arguments)]
+    pub async fn scan_arbitrage_opportunities_with_dex_sources(
+        symbol: &str,
+        cex_exchanges: &[CexExchange],
+        dex_exchanges: Option<&[DexAggregator]>,
+        base_token: Option<&Token>,
+        quote_token: Option<&Token>,
+        quote_amount: Option<f64>,
+        fee_overrides: Option<&FeeOverrides>,
+        dex_sources: &DexSourceFilter,
     ) -> Result<Vec<ArbitrageOpportunity>, MarketScannerError> {
         // Fetch all prices in parallel
         let (cex_prices, dex_prices) = tokio::try_join!(
             Self::fetch_cex_prices(cex_exchanges, symbol),
-            Self::fetch_dex_prices(dex_exchanges, base_token, quote_token, quote_amount)
+            Self::fetch_dex_prices(
+                dex_exchanges,
+                base_token,
+                quote_token,
+                quote_amount,
+                dex_sources
+            )
         )?;
 
         // Find arbitrage opportunities by matching buy and sell candidates
@@ -71,7 +228,21 @@ impl ArbitrageScanner {
         dex_prices: &[DexPrice],
         fee_overrides: Option<&FeeOverrides>,
     ) -> Vec<ArbitrageOpportunity> {
-        Self::find_opportunities(cex_prices, dex_prices, fee_overrides)
+        Self::find_opportunities(cex_prices, dex_prices, fee_overrides, None)
+    }
+
+    /// Same as [ArbitrageScanner::opportunities_from_prices], also counting the evaluated
+    /// pairs, the rejections and the emitted opportunities into `metrics`.
+    pub fn opportunities_from_prices_with_metrics(
+        cex_prices: &[CexPrice],
+        dex_prices: &[DexPrice],
+        fee_overrides: Option<&FeeOverrides>,
+        metrics: &mut MatchMetrics,
+    ) -> Vec<ArbitrageOpportunity> {
+        let opportunities =
+            Self::find_opportunities(cex_prices, dex_prices, fee_overrides, Some(&mut *metrics));
+        metrics.opportunities_emitted += opportunities.len() as u64;
+        opportunities
     }
 
     /// Connects to the given CEX WebSocket streams and continuously emits arbitrage
@@ -86,6 +257,47 @@ impl ArbitrageScanner {
         fee_overrides: Option<&FeeOverrides>,
         reconnect_attempts: u32,
         reconnect_delay_ms: u64,
+    ) -> Result<mpsc::Receiver<Vec<ArbitrageOpportunity>>, MarketScannerError> {
+        Self::run_websocket_scan(
+            symbols,
+            cex_exchanges,
+            fee_overrides,
+            reconnect_attempts,
+            reconnect_delay_ms,
+            None,
+        )
+        .await
+    }
+
+    /// Same as [ArbitrageScanner::scan_arbitrage_from_websockets], but prices from venues
+    /// that `maintenance` currently reports as under maintenance are left out of every
+    /// snapshot. Venues rejoin automatically once they are operational again.
+    pub async fn scan_arbitrage_from_websockets_with_maintenance(
+        symbols: &[&str],
+        cex_exchanges: &[CexExchange],
+        fee_overrides: Option<&FeeOverrides>,
+        reconnect_attempts: u32,
+        reconnect_delay_ms: u64,
+        maintenance: &MaintenanceMonitor,
+    ) -> Result<mpsc::Receiver<Vec<ArbitrageOpportunity>>, MarketScannerError> {
+        Self::run_websocket_scan(
+            symbols,
+            cex_exchanges,
+            fee_overrides,
+            reconnect_attempts,
+            reconnect_delay_ms,
+            Some(maintenance.clone()),
+        )
+        .await
+    }
+
+    async fn run_websocket_scan(
+        symbols: &[&str],
+        cex_exchanges: &[CexExchange],
+        fee_overrides: Option<&FeeOverrides>,
+        reconnect_attempts: u32,
+        reconnect_delay_ms: u64,
+        maintenance: Option<MaintenanceMonitor>,
     ) -> Result<mpsc::Receiver<Vec<ArbitrageOpportunity>>, MarketScannerError> {
         let ws_exchanges: Vec<_> = cex_exchanges
             .iter()
@@ -99,67 +311,101 @@ impl ArbitrageScanner {
             ));
         }
 
-        let mut receivers: Vec<(CexExchange, mpsc::Receiver<CexPrice>)> = Vec::new();
-        for ex in &ws_exchanges {
-            let rx = Self::stream_cex_prices_websocket(
-                ex,
-                symbols,
-                reconnect_attempts,
-                reconnect_delay_ms,
-            )
-            .await?;
-            receivers.push((ex.clone(), rx));
+        let sources: Vec<Box<dyn PriceSource>> = ws_exchanges
+            .into_iter()
+            .map(|ex| {
+                Box::new(
+                    CexWebSocketSource::new(ex, symbols)
+                        .with_reconnect(reconnect_attempts, reconnect_delay_ms),
+                ) as Box<dyn PriceSource>
+            })
+            .collect();
+        Self::run_source_scan(sources, symbols, fee_overrides, maintenance).await
+    }
+
+    /// Starts every source and continuously emits arbitrage opportunities (sorted by
+    /// profitability) matched across all of them: CEX WebSocket feeds, REST pollers, pool
+    /// listeners, aggregator pollers or any other [PriceSource]. The latest price per
+    /// (exchange, symbol) is kept and a snapshot is emitted on every update.
+    ///
+    /// Fails if `sources` is empty or any source fails to start. The receiver ends once
+    /// every source has ended.
+    pub async fn scan_sources(
+        sources: Vec<Box<dyn PriceSource>>,
+        symbols: &[&str],
+        fee_overrides: Option<&FeeOverrides>,
+    ) -> Result<mpsc::Receiver<Vec<ArbitrageOpportunity>>, MarketScannerError> {
+        Self::run_source_scan(sources, symbols, fee_overrides, None).await
+    }
+
+    async fn run_source_scan(
+        sources: Vec<Box<dyn PriceSource>>,
+        symbols: &[&str],
+        fee_overrides: Option<&FeeOverrides>,
+        maintenance: Option<MaintenanceMonitor>,
+    ) -> Result<mpsc::Receiver<Vec<ArbitrageOpportunity>>, MarketScannerError> {
+        if sources.is_empty() {
+            return Err(MarketScannerError::InvalidConfig(
+                "No price sources to scan".to_string(),
+            ));
+        }
+        let mut receivers = Vec::with_capacity(sources.len());
+        for source in sources {
+            receivers.push(source.start().await?);
         }
 
         let (tx, rx) = mpsc::channel(64);
-        let (tx_prices, mut rx_prices) = mpsc::channel::<CexPrice>(256);
+        let mut rx_prices = source::merge_sources(receivers);
         let symbols_vec: Vec<String> = symbols.iter().map(|s| (*s).to_string()).collect();
         let fee_overrides_owned = fee_overrides.cloned();
 
-        for (_, mut ws_rx) in receivers {
-            let tx_fwd = tx_prices.clone();
-            tokio::spawn(async move {
-                while let Some(price) = ws_rx.recv().await {
-                    let _ = tx_fwd.send(price).await;
-                }
-            });
-        }
-        drop(tx_prices);
-
-        tokio::spawn(async move {
-            let mut cache: HashMap<(Exchange, String), CexPrice> = HashMap::new();
+        symbol_registry::spawn(async move {
+            let mut cex_cache: HashMap<(Exchange, String), CexPrice> = HashMap::new();
+            let mut dex_cache: HashMap<(Exchange, String), DexPrice> = HashMap::new();
             let symbols_set: Vec<String> = symbols_vec;
 
             while let Some(price) = rx_prices.recv().await {
-                // Geçersiz fiyatları atla; 0 gelen güncelleme önceki geçerli fiyatı üzerine yazmasın
-                if price.mid_price <= 0.0 || price.bid_price <= 0.0 || price.ask_price <= 0.0 {
-                    continue;
-                }
-                let symbol = price.symbol.clone();
-                let ex = price.exchange.clone();
-                cache.insert((ex.clone(), symbol.clone()), price);
-
-                let mut all_opps = Vec::new();
-                for symbol in &symbols_set {
-                    let prices: Vec<CexPrice> = cache
-                        .values()
-                        .filter(|p| p.symbol == *symbol)
-                        .cloned()
-                        .collect();
-                    if prices.len() >= 2 {
-                        let opps = ArbitrageScanner::opportunities_from_prices(
-                            &prices,
-                            &[],
-                            fee_overrides_owned.as_ref(),
+                let mut trace = None;
+                match price {
+                    PriceData::Cex(price) => {
+                        // Geçersiz fiyatları atla; 0 gelen güncelleme önceki geçerli fiyatı üzerine yazmasın
+                        if price.mid_price <= 0.0
+                            || price.bid_price <= 0.0
+                            || price.ask_price <= 0.0
+                        {
+                            continue;
+                        }
+                        let stamp = take_frame_stamp(&price);
+                        cex_cache.insert(
+                            (price.exchange.clone(), price.symbol.clone()),
+                            price.clone(),
                         );
-                        all_opps.extend(opps);
+                        trace = stamp.map(|stamp| (price, stamp, Instant::now()));
+                    }
+                    PriceData::Dex(price) => {
+                        if price.mid_price <= 0.0
+                            || price.bid_price <= 0.0
+                            || price.ask_price <= 0.0
+                        {
+                            continue;
+                        }
+                        dex_cache.insert((price.exchange.clone(), price.symbol.clone()), price);
                     }
                 }
-                all_opps.sort_by(|a, b| {
-                    b.spread_percentage
-                        .partial_cmp(&a.spread_percentage)
-                        .unwrap_or(std::cmp::Ordering::Equal)
-                });
+
+                let mut all_opps = ArbitrageScanner::snapshot_from_prices(
+                    cex_cache.values(),
+                    dex_cache.values(),
+                    &symbols_set,
+                    fee_overrides_owned.as_ref(),
+                    maintenance.as_ref(),
+                    None,
+                );
+                if let Some((price, stamp, inserted_at)) = trace {
+                    let matched_at = Instant::now();
+                    LatencyTrace::new(&price, &stamp, inserted_at, matched_at, Instant::now())
+                        .attach(&price, &mut all_opps);
+                }
                 if tx.send(all_opps).await.is_err() {
                     return;
                 }
@@ -169,7 +415,92 @@ impl ArbitrageScanner {
         Ok(rx)
     }
 
-    fn exchange_supports_websocket(ex: &CexExchange) -> bool {
+    /// Builds a sorted opportunity snapshot from the latest cached price per (exchange, symbol).
+    /// Prices are grouped by logical market so venue spellings (e.g. Bitfinex BTCUST) join the group.
+    pub(crate) fn snapshot_from_cache<'a>(
+        cache: impl Iterator<Item = &'a CexPrice>,
+        symbols: &[String],
+        fee_overrides: Option<&FeeOverrides>,
+        maintenance: Option<&MaintenanceMonitor>,
+        metrics: Option<&mut MatchMetrics>,
+    ) -> Vec<ArbitrageOpportunity> {
+        Self::snapshot_from_prices(
+            cache,
+            std::iter::empty(),
+            symbols,
+            fee_overrides,
+            maintenance,
+            metrics,
+        )
+    }
+
+    /// Same as [ArbitrageScanner::snapshot_from_cache], with DEX prices matched alongside.
+    /// With `metrics`, counts the snapshot, its matching and the cached prices per symbol.
+    pub(crate) fn snapshot_from_prices<'a>(
+        cex_cache: impl Iterator<Item = &'a CexPrice>,
+        dex_cache: impl Iterator<Item = &'a DexPrice>,
+        symbols: &[String],
+        fee_overrides: Option<&FeeOverrides>,
+        maintenance: Option<&MaintenanceMonitor>,
+        mut metrics: Option<&mut MatchMetrics>,
+    ) -> Vec<ArbitrageOpportunity> {
+        let cex_cache: Vec<&CexPrice> = cex_cache.collect();
+        let dex_cache: Vec<&DexPrice> = dex_cache.collect();
+        let mut all_opps = Vec::new();
+        if let Some(metrics) = metrics.as_deref_mut() {
+            metrics.snapshots += 1;
+            metrics.cache_sizes.clear();
+            metrics.updated_at = Some(Timestamp::now());
+        }
+        for symbol in symbols {
+            let market = MarketKey::from_symbol(symbol);
+            let cached: Vec<&CexPrice> = cex_cache
+                .iter()
+                .copied()
+                .filter(|p| logical_market_key(&p.symbol, &p.exchange) == market)
+                .collect();
+            let prices: Vec<CexPrice> = cached
+                .iter()
+                .copied()
+                .filter(|p| match (maintenance, &p.exchange) {
+                    (Some(m), Exchange::Cex(cex)) => !m.is_in_maintenance(cex),
+                    _ => true,
+                })
+                .cloned()
+                .collect();
+            let dex_prices: Vec<DexPrice> = dex_cache
+                .iter()
+                .copied()
+                .filter(|p| logical_market_key(&p.symbol, &p.exchange) == market)
+                .cloned()
+                .collect();
+            if let Some(metrics) = metrics.as_deref_mut() {
+                metrics
+                    .cache_sizes
+                    .insert(symbol.clone(), cached.len() + dex_prices.len());
+                metrics.reject(
+                    RejectReason::Maintenance,
+                    (cached.len() - prices.len()) as u64,
+                );
+            }
+            if prices.len() + dex_prices.len() >= 2 {
+                all_opps.extend(Self::find_opportunities(
+                    &prices,
+                    &dex_prices,
+                    fee_overrides,
+                    metrics.as_deref_mut(),
+                ));
+            }
+        }
+        all_opps.sort_by(|a, b| {
+            b.spread_percentage
+                .partial_cmp(&a.spread_percentage)
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+        all_opps
+    }
+
+    pub(crate) fn exchange_supports_websocket(ex: &CexExchange) -> bool {
         match ex {
             CexExchange::Binance => Binance::new().supports_websocket(),
             CexExchange::Bybit => Bybit::new().supports_websocket(),
@@ -188,7 +519,45 @@ impl ArbitrageScanner {
         }
     }
 
-    async fn stream_cex_prices_websocket(
+    pub(crate) fn cex_capabilities(ex: &CexExchange) -> Capabilities {
+        match ex {
+            CexExchange::Binance => Binance::new().capabilities(),
+            CexExchange::Bybit => Bybit::new().capabilities(),
+            CexExchange::MEXC => Mexc::new().capabilities(),
+            CexExchange::OKX => OKX::new().capabilities(),
+            CexExchange::Gateio => Gateio::new().capabilities(),
+            CexExchange::Kucoin => Kucoin::new().capabilities(),
+            CexExchange::Bitget => Bitget::new().capabilities(),
+            CexExchange::Btcturk => Btcturk::new().capabilities(),
+            CexExchange::Htx => Htx::new().capabilities(),
+            CexExchange::Coinbase => Coinbase::new().capabilities(),
+            CexExchange::Kraken => Kraken::new().capabilities(),
+            CexExchange::Bitfinex => Bitfinex::new().capabilities(),
+            CexExchange::Upbit => Upbit::new().capabilities(),
+            CexExchange::Cryptocom => Cryptocom::new().capabilities(),
+        }
+    }
+
+    pub(crate) async fn cex_health_check(ex: &CexExchange) -> Result<(), MarketScannerError> {
+        match ex {
+            CexExchange::Binance => Binance::new().health_check().await,
+            CexExchange::Bybit => Bybit::new().health_check().await,
+            CexExchange::MEXC => Mexc::new().health_check().await,
+            CexExchange::OKX => OKX::new().health_check().await,
+            CexExchange::Gateio => Gateio::new().health_check().await,
+            CexExchange::Kucoin => Kucoin::new().health_check().await,
+            CexExchange::Bitget => Bitget::new().health_check().await,
+            CexExchange::Btcturk => Btcturk::new().health_check().await,
+            CexExchange::Htx => Htx::new().health_check().await,
+            CexExchange::Coinbase => Coinbase::new().health_check().await,
+            CexExchange::Kraken => Kraken::new().health_check().await,
+            CexExchange::Bitfinex => Bitfinex::new().health_check().await,
+            CexExchange::Upbit => Upbit::new().health_check().await,
+            CexExchange::Cryptocom => Cryptocom::new().health_check().await,
+        }
+    }
+
+    pub(crate) async fn stream_cex_prices_websocket(
         exchange: &CexExchange,
         symbols: &[&str],
         reconnect_attempts: u32,
@@ -268,20 +637,40 @@ impl ArbitrageScanner {
         }
     }
 
-    /// Fetches CEX prices in parallel
+    /// `exchanges` ordered by their recorded REST response time, slowest first; venues
+    /// without recorded requests come first. Ties keep their order.
+    pub fn order_by_rest_latency(exchanges: &[CexExchange]) -> Vec<CexExchange> {
+        let mut ordered: Vec<(CexExchange, f64)> = exchanges
+            .iter()
+            .map(|exchange| {
+                let latency = rest_connection_stats(exchange.rest_venue_name())
+                    .filter(|stats| stats.requests > 0)
+                    .map_or(f64::INFINITY, |stats| stats.avg_response_ms());
+                (exchange.clone(), latency)
+            })
+            .collect();
+        ordered.sort_by(|a, b| b.1.total_cmp(&a.1));
+        ordered.into_iter().map(|(exchange, _)| exchange).collect()
+    }
+
+    /// Fetches CEX prices in parallel. Requests are started slowest venue first (see
+    /// [Self::order_by_rest_latency]) so slow venues get their rate-limit slot and
+    /// connection first; prices keep the order of `exchanges`.
     async fn fetch_cex_prices(
         exchanges: &[CexExchange],
         symbol: &str,
     ) -> Result<Vec<CexPrice>, MarketScannerError> {
-        let futures: Vec<_> = exchanges
+        let ordered = Self::order_by_rest_latency(exchanges);
+        let futures: Vec<_> = ordered
             .iter()
             .map(|exchange| Self::get_cex_price(exchange, symbol))
             .collect();
 
-        let results = join_all(futures).await;
+        let mut results: Vec<_> = ordered.iter().zip(join_all(futures).await).collect();
+        results.sort_by_key(|(exchange, _)| exchanges.iter().position(|e| e == *exchange));
         let mut prices = Vec::new();
 
-        for (exchange, result) in exchanges.iter().zip(results) {
+        for (exchange, result) in results {
             match result {
                 Ok(price) => prices.push(price),
                 Err(e) => {
@@ -293,12 +682,41 @@ impl ArbitrageScanner {
         Ok(prices)
     }
 
+    /// Fetches order book snapshots (up to `depth` levels per side) in parallel.
+    /// Exchanges that fail or do not expose depth are skipped with a warning.
+    pub async fn fetch_order_books(
+        exchanges: &[CexExchange],
+        symbol: &str,
+        depth: usize,
+    ) -> Vec<OrderBook> {
+        let futures: Vec<_> = exchanges
+            .iter()
+            .map(|exchange| Self::get_cex_order_book(exchange, symbol, depth))
+            .collect();
+
+        let results = join_all(futures).await;
+        let mut books = Vec::new();
+        for (exchange, result) in exchanges.iter().zip(results) {
+            match result {
+                Ok(book) => books.push(book),
+                Err(e) => {
+                    eprintln!(
+                        "Warning: Failed to get order book from {:?}: {:?}",
+                        exchange, e
+                    );
+                }
+            }
+        }
+        books
+    }
+
     /// Fetches DEX prices in parallel
     async fn fetch_dex_prices(
         exchanges: Option<&[DexAggregator]>,
         base_token: Option<&Token>,
         quote_token: Option<&Token>,
         quote_amount: Option<f64>,
+        sources: &DexSourceFilter,
     ) -> Result<Vec<DexPrice>, MarketScannerError> {
         let mut prices = Vec::new();
 
@@ -307,13 +725,23 @@ impl ArbitrageScanner {
             {
                 let futures: Vec<_> = dex_list
                     .iter()
-                    .map(|exchange| Self::get_dex_price(exchange, base, quote, amount))
+                    .map(|exchange| {
+                        Self::get_dex_price_with_sources(
+                            exchange,
+                            base,
+                            quote,
+                            AmountSpec::Quote(amount),
+                            sources,
+                        )
+                    })
                     .collect();
 
                 let results = join_all(futures).await;
                 for (exchange, result) in dex_list.iter().zip(results) {
                     match result {
                         Ok(price) => prices.push(price),
+                        // Chain known to be unroutable: skipped, not a failure
+                        Err(MarketScannerError::UnsupportedChain(_)) => {}
                         Err(e) => {
                             eprintln!("Warning: Failed to get price from {:?}: {:?}", exchange, e);
                         }
@@ -330,12 +758,25 @@ impl ArbitrageScanner {
         cex_prices: &[CexPrice],
         dex_prices: &[DexPrice],
         fee_overrides: Option<&FeeOverrides>,
+        mut metrics: Option<&mut MatchMetrics>,
     ) -> Vec<ArbitrageOpportunity> {
         let mut opportunities = Vec::new();
 
         // Create buy candidates: effective ask = ask × (1 + fee), sorted lowest first
         let mut buy_candidates = Vec::new();
-        for cex_price in cex_prices {
+        // Halted / auction / post-only markets cannot take part in an opportunity
+        let tradable: Vec<&CexPrice> = cex_prices
+            .iter()
+            .filter(|p| p.trading_status.is_trading())
+            .collect();
+        if let Some(metrics) = metrics.as_deref_mut() {
+            metrics.reject(
+                RejectReason::NotTrading,
+                (cex_prices.len() - tradable.len()) as u64,
+            );
+        }
+        let cex_prices = tradable;
+        for &cex_price in &cex_prices {
             let effective = effective_price_with_overrides(
                 cex_price.ask_price,
                 &cex_price.exchange,
@@ -346,6 +787,7 @@ impl ArbitrageScanner {
                 effective,
                 PriceData::Cex(cex_price.clone()),
                 Self::exchange_name(&cex_price.exchange),
+                logical_market_key(&cex_price.symbol, &cex_price.exchange),
             ));
         }
         for dex_price in dex_prices {
@@ -359,13 +801,14 @@ impl ArbitrageScanner {
                 effective,
                 PriceData::Dex(dex_price.clone()),
                 Self::exchange_name(&dex_price.exchange),
+                logical_market_key(&dex_price.symbol, &dex_price.exchange),
             ));
         }
         buy_candidates.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(std::cmp::Ordering::Equal));
 
         // Create sell candidates: effective bid = bid × (1 − fee), sorted highest first
         let mut sell_candidates = Vec::new();
-        for cex_price in cex_prices {
+        for &cex_price in &cex_prices {
             let effective = effective_price_with_overrides(
                 cex_price.bid_price,
                 &cex_price.exchange,
@@ -376,6 +819,7 @@ impl ArbitrageScanner {
                 effective,
                 PriceData::Cex(cex_price.clone()),
                 Self::exchange_name(&cex_price.exchange),
+                logical_market_key(&cex_price.symbol, &cex_price.exchange),
             ));
         }
         for dex_price in dex_prices {
@@ -389,21 +833,34 @@ impl ArbitrageScanner {
                 effective,
                 PriceData::Dex(dex_price.clone()),
                 Self::exchange_name(&dex_price.exchange),
+                logical_market_key(&dex_price.symbol, &dex_price.exchange),
             ));
         }
         sell_candidates.sort_by(|a, b| b.0.partial_cmp(&a.0).unwrap_or(std::cmp::Ordering::Equal));
 
-        // Match buy and sell candidates
-        for (effective_ask, source_data, source_exchange) in &buy_candidates {
-            for (effective_bid, dest_data, dest_exchange) in &sell_candidates {
-                if source_exchange == dest_exchange || *effective_bid <= *effective_ask {
-                    continue;
-                }
-
+        // Match buy and sell candidates; legs must trade the same logical market
+        for (effective_ask, source_data, source_exchange, source_market) in &buy_candidates {
+            for (effective_bid, dest_data, dest_exchange, dest_market) in &sell_candidates {
                 let spread = effective_bid - effective_ask;
                 let spread_percentage = (spread / effective_ask) * 100.0;
-
-                if spread_percentage < 0.01 {
+                let rejection = if source_exchange == dest_exchange {
+                    Some(RejectReason::SameVenue)
+                } else if source_market != dest_market {
+                    Some(RejectReason::MarketMismatch)
+                } else if *effective_bid <= *effective_ask {
+                    Some(RejectReason::NoSpread)
+                } else if spread_percentage < 0.01 {
+                    Some(RejectReason::BelowMinSpread)
+                } else {
+                    None
+                };
+                if let Some(metrics) = metrics.as_deref_mut() {
+                    metrics.candidates_evaluated += 1;
+                    if let Some(reason) = rejection {
+                        metrics.reject(reason, 1);
+                    }
+                }
+                if rejection.is_some() {
                     continue;
                 }
 
@@ -419,7 +876,8 @@ impl ArbitrageScanner {
                     *effective_bid * executable_quantity * (dest_comm_rate / 100.0);
                 let total_commission_quote = source_commission_quote + destination_commission_quote;
 
-                opportunities.push(ArbitrageOpportunity {
+                let mut opportunity = ArbitrageOpportunity {
+                    schema_version: OPPORTUNITY_SCHEMA_VERSION,
                     source_exchange: source_exchange.clone(),
                     destination_exchange: dest_exchange.clone(),
                     symbol,
@@ -433,7 +891,17 @@ impl ArbitrageScanner {
                     total_commission_quote,
                     source_leg: source_data.clone(),
                     destination_leg: dest_data.clone(),
-                });
+                    viability: None,
+                    mev_risk: None,
+                    simulated_amount_out: None,
+                    latency: None,
+                    fiat_restriction: None,
+                    notional_floor: None,
+                    spread_breakdown: None,
+                    id: None,
+                };
+                opportunity.spread_breakdown = Some(SpreadBreakdown::from_fees(&opportunity));
+                opportunities.push(opportunity);
             }
         }
 
@@ -498,6 +966,75 @@ impl ArbitrageScanner {
         }
     }
 
+    /// Gets an order book snapshot from a CEX exchange
+    async fn get_cex_order_book(
+        exchange: &CexExchange,
+        symbol: &str,
+        depth: usize,
+    ) -> Result<OrderBook, MarketScannerError> {
+        match exchange {
+            CexExchange::Binance => Binance::new().get_order_book(symbol, depth).await,
+            CexExchange::Bybit => Bybit::new().get_order_book(symbol, depth).await,
+            CexExchange::MEXC => Mexc::new().get_order_book(symbol, depth).await,
+            CexExchange::OKX => OKX::new().get_order_book(symbol, depth).await,
+            CexExchange::Gateio => Gateio::new().get_order_book(symbol, depth).await,
+            CexExchange::Kucoin => Kucoin::new().get_order_book(symbol, depth).await,
+            CexExchange::Bitget => Bitget::new().get_order_book(symbol, depth).await,
+            CexExchange::Btcturk => Btcturk::new().get_order_book(symbol, depth).await,
+            CexExchange::Htx => Htx::new().get_order_book(symbol, depth).await,
+            CexExchange::Coinbase => Coinbase::new().get_order_book(symbol, depth).await,
+            CexExchange::Kraken => Kraken::new().get_order_book(symbol, depth).await,
+            CexExchange::Bitfinex => Bitfinex::new().get_order_book(symbol, depth).await,
+            CexExchange::Upbit => Upbit::new().get_order_book(symbol, depth).await,
+            CexExchange::Cryptocom => Cryptocom::new().get_order_book(symbol, depth).await,
+        }
+    }
+
+    /// Gets the minimum order sizes of a CEX market
+    async fn get_cex_order_limits(
+        exchange: &CexExchange,
+        symbol: &str,
+    ) -> Result<OrderLimits, MarketScannerError> {
+        match exchange {
+            CexExchange::Binance => Binance::new().get_order_limits(symbol).await,
+            CexExchange::Bybit => Bybit::new().get_order_limits(symbol).await,
+            CexExchange::MEXC => Mexc::new().get_order_limits(symbol).await,
+            CexExchange::OKX => OKX::new().get_order_limits(symbol).await,
+            CexExchange::Gateio => Gateio::new().get_order_limits(symbol).await,
+            CexExchange::Kucoin => Kucoin::new().get_order_limits(symbol).await,
+            CexExchange::Bitget => Bitget::new().get_order_limits(symbol).await,
+            CexExchange::Btcturk => Btcturk::new().get_order_limits(symbol).await,
+            CexExchange::Htx => Htx::new().get_order_limits(symbol).await,
+            CexExchange::Coinbase => Coinbase::new().get_order_limits(symbol).await,
+            CexExchange::Kraken => Kraken::new().get_order_limits(symbol).await,
+            CexExchange::Bitfinex => Bitfinex::new().get_order_limits(symbol).await,
+            CexExchange::Upbit => Upbit::new().get_order_limits(symbol).await,
+            CexExchange::Cryptocom => Cryptocom::new().get_order_limits(symbol).await,
+        }
+    }
+
+    /// Gets the instrument ids listed by a CEX
+    async fn get_cex_instrument_ids(
+        exchange: &CexExchange,
+    ) -> Result<HashMap<String, String>, MarketScannerError> {
+        match exchange {
+            CexExchange::Binance => Binance::new().get_instrument_ids().await,
+            CexExchange::Bybit => Bybit::new().get_instrument_ids().await,
+            CexExchange::MEXC => Mexc::new().get_instrument_ids().await,
+            CexExchange::OKX => OKX::new().get_instrument_ids().await,
+            CexExchange::Gateio => Gateio::new().get_instrument_ids().await,
+            CexExchange::Kucoin => Kucoin::new().get_instrument_ids().await,
+            CexExchange::Bitget => Bitget::new().get_instrument_ids().await,
+            CexExchange::Btcturk => Btcturk::new().get_instrument_ids().await,
+            CexExchange::Htx => Htx::new().get_instrument_ids().await,
+            CexExchange::Coinbase => Coinbase::new().get_instrument_ids().await,
+            CexExchange::Kraken => Kraken::new().get_instrument_ids().await,
+            CexExchange::Bitfinex => Bitfinex::new().get_instrument_ids().await,
+            CexExchange::Upbit => Upbit::new().get_instrument_ids().await,
+            CexExchange::Cryptocom => Cryptocom::new().get_instrument_ids().await,
+        }
+    }
+
     /// Gets price from a DEX exchange
     async fn get_dex_price(
         exchange: &DexAggregator,
@@ -514,8 +1051,25 @@ impl ArbitrageScanner {
         }
     }
 
+    /// Gets a price from a DEX exchange routed through the sources `sources` allows
+    async fn get_dex_price_with_sources(
+        exchange: &DexAggregator,
+        base_token: &Token,
+        quote_token: &Token,
+        amount: AmountSpec,
+        sources: &DexSourceFilter,
+    ) -> Result<DexPrice, MarketScannerError> {
+        match exchange {
+            DexAggregator::KyberSwap => {
+                KyberSwap::new()
+                    .get_price_with_sources(base_token, quote_token, amount, sources)
+                    .await
+            }
+        }
+    }
+
     /// Gets exchange name from Exchange enum
-    fn exchange_name(exchange: &crate::common::Exchange) -> String {
+    pub(crate) fn exchange_name(exchange: &crate::common::Exchange) -> String {
         match exchange {
             crate::common::Exchange::Cex(cex) => match cex {
                 CexExchange::Binance => "Binance",
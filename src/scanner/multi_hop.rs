@@ -0,0 +1,305 @@
+//! Multi-hop CEX arbitrage across venue chains.
+//!
+//! When no two venues quote the same market far enough apart, a chain through a third venue
+//! can still pay: buy BTC with USDT on A, sell it for EUR on B and convert the EUR back to
+//! USDT on C. [ArbitrageScanner::multi_hop_opportunities_from_prices] treats every quote as
+//! two conversion edges between assets (buy: quote → base at the effective ask, sell: base
+//! → quote at the effective bid) and finds cycles of 3 up to [MultiHopOptions::with_max_hops]
+//! hops whose product of rates exceeds 1. Like direct opportunities, chains assume the
+//! inventory of every hop is already on its venue; transfers are not priced.
+
+use crate::common::{
+    AmountSide, CexExchange, CexPrice, FeeOverrides, MarketScannerError,
+    effective_price_with_overrides, logical_market_key,
+};
+use crate::portfolio::TradeSide;
+use crate::scanner::ArbitrageScanner;
+use futures::future::join_all;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashSet};
+
+/// Longest chain searched by default.
+pub const DEFAULT_MAX_HOPS: usize = 3;
+
+/// Limits of the multi-hop search.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MultiHopOptions {
+    max_hops: usize,
+    min_profit_percent: f64,
+    min_venues: usize,
+    direct_threshold_percent: Option<f64>,
+}
+
+impl Default for MultiHopOptions {
+    fn default() -> Self {
+        Self {
+            max_hops: DEFAULT_MAX_HOPS,
+            min_profit_percent: 0.0,
+            min_venues: 2,
+            direct_threshold_percent: None,
+        }
+    }
+}
+
+impl MultiHopOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Longest chain (default [DEFAULT_MAX_HOPS]). The search grows quickly with the length;
+    /// fails below 3, which is a direct opportunity.
+    pub fn with_max_hops(mut self, max_hops: usize) -> Result<Self, MarketScannerError> {
+        if max_hops < 3 {
+            return Err(MarketScannerError::InvalidConfig(format!(
+                "Multi-hop chains need at least 3 hops, got {}",
+                max_hops
+            )));
+        }
+        self.max_hops = max_hops;
+        Ok(self)
+    }
+
+    /// Minimum profit of a chain after fees, in percent (default 0).
+    pub fn with_min_profit_percent(mut self, min_profit_percent: f64) -> Self {
+        self.min_profit_percent = min_profit_percent;
+        self
+    }
+
+    /// Minimum number of distinct venues in a chain (default 2, so single-venue triangles
+    /// are left out).
+    pub fn with_min_venues(mut self, min_venues: usize) -> Self {
+        self.min_venues = min_venues;
+        self
+    }
+
+    /// Searches chains only while no direct opportunity among the prices reaches
+    /// `spread_percent` (net of fees).
+    pub fn with_direct_threshold_percent(mut self, spread_percent: f64) -> Self {
+        self.direct_threshold_percent = Some(spread_percent);
+        self
+    }
+
+    pub fn max_hops(&self) -> usize {
+        self.max_hops
+    }
+}
+
+/// One conversion of a [MultiHopOpportunity].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Hop {
+    pub exchange: String,
+    pub symbol: String,
+    /// Buy the base with the quote, or sell the base for the quote
+    pub side: TradeSide,
+    /// Quoted ask (buy) or bid (sell)
+    pub price: f64,
+    pub from_asset: String,
+    pub to_asset: String,
+    /// Units of `to_asset` received per unit of `from_asset`, after fees
+    pub rate: f64,
+}
+
+/// Profitable cycle of conversions starting and ending in `start_asset`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MultiHopOpportunity {
+    pub start_asset: String,
+    pub hops: Vec<Hop>,
+    /// Product of the hop rates; above 1 when profitable
+    pub gross_rate: f64,
+    /// `(gross_rate − 1) × 100`
+    pub profit_percentage: f64,
+    /// Largest `start_asset` amount the top-of-book quantities of every hop can take
+    pub executable_amount: f64,
+}
+
+impl MultiHopOpportunity {
+    /// Profit of the executable amount, in `start_asset`.
+    pub fn total_profit(&self) -> f64 {
+        self.executable_amount * (self.gross_rate - 1.0)
+    }
+
+    /// Distinct venues of the chain.
+    pub fn venues(&self) -> Vec<&str> {
+        let mut venues: Vec<&str> = Vec::new();
+        for hop in &self.hops {
+            if !venues.contains(&hop.exchange.as_str()) {
+                venues.push(&hop.exchange);
+            }
+        }
+        venues
+    }
+}
+
+/// Conversion edge with the `from_asset` amount the top of book can take.
+struct Edge {
+    hop: Hop,
+    capacity: f64,
+}
+
+fn edges(prices: &[CexPrice], fee_overrides: Option<&FeeOverrides>) -> Vec<Edge> {
+    let mut edges = Vec::new();
+    for price in prices {
+        let market = logical_market_key(&price.symbol, &price.exchange);
+        let exchange = ArbitrageScanner::exchange_name(&price.exchange);
+        if price.ask_price > 0.0 && price.ask_qty > 0.0 {
+            let effective_ask = effective_price_with_overrides(
+                price.ask_price,
+                &price.exchange,
+                AmountSide::Buy,
+                fee_overrides,
+            );
+            edges.push(Edge {
+                hop: Hop {
+                    exchange: exchange.clone(),
+                    symbol: price.symbol.clone(),
+                    side: TradeSide::Buy,
+                    price: price.ask_price,
+                    from_asset: market.quote.clone(),
+                    to_asset: market.base.clone(),
+                    rate: 1.0 / effective_ask,
+                },
+                capacity: price.ask_qty * effective_ask,
+            });
+        }
+        if price.bid_price > 0.0 && price.bid_qty > 0.0 {
+            edges.push(Edge {
+                hop: Hop {
+                    exchange,
+                    symbol: price.symbol.clone(),
+                    side: TradeSide::Sell,
+                    price: price.bid_price,
+                    from_asset: market.base,
+                    to_asset: market.quote,
+                    rate: effective_price_with_overrides(
+                        price.bid_price,
+                        &price.exchange,
+                        AmountSide::Sell,
+                        fee_overrides,
+                    ),
+                },
+                capacity: price.bid_qty,
+            });
+        }
+    }
+    edges
+}
+
+struct Search<'a> {
+    edges: &'a [Edge],
+    options: &'a MultiHopOptions,
+    seen: HashSet<BTreeSet<usize>>,
+    found: Vec<MultiHopOpportunity>,
+}
+
+impl Search<'_> {
+    /// Extends `path` (edge indices from `start`) by one hop, recording closed cycles.
+    fn extend(&mut self, start: &str, path: &mut Vec<usize>) {
+        let last = &self.edges[*path.last().expect("non-empty path")].hop;
+        for (idx, edge) in self.edges.iter().enumerate() {
+            if edge.hop.from_asset != last.to_asset || path.contains(&idx) {
+                continue;
+            }
+            path.push(idx);
+            if edge.hop.to_asset == start {
+                if path.len() >= 3 {
+                    self.record(start, path);
+                }
+            } else if path.len() < self.options.max_hops
+                && !path
+                    .iter()
+                    .any(|&i| self.edges[i].hop.from_asset == edge.hop.to_asset)
+            {
+                self.extend(start, path);
+            }
+            path.pop();
+        }
+    }
+
+    fn record(&mut self, start: &str, path: &[usize]) {
+        let mut gross_rate = 1.0;
+        let mut executable_amount = f64::INFINITY;
+        for &idx in path {
+            let edge = &self.edges[idx];
+            executable_amount = executable_amount.min(edge.capacity / gross_rate);
+            gross_rate *= edge.hop.rate;
+        }
+        let profit_percentage = (gross_rate - 1.0) * 100.0;
+        if profit_percentage <= self.options.min_profit_percent {
+            return;
+        }
+        let opportunity = MultiHopOpportunity {
+            start_asset: start.to_string(),
+            hops: path.iter().map(|&i| self.edges[i].hop.clone()).collect(),
+            gross_rate,
+            profit_percentage,
+            executable_amount,
+        };
+        if opportunity.venues().len() < self.options.min_venues {
+            return;
+        }
+        // Rotations of a cycle are the same trade
+        if self.seen.insert(path.iter().copied().collect()) {
+            self.found.push(opportunity);
+        }
+    }
+}
+
+impl ArbitrageScanner {
+    /// Fetches every symbol of `symbols` (e.g. "BTCUSDT", "BTCEUR", "EURUSDT") from
+    /// `exchanges` and searches the quotes for chains (see
+    /// [ArbitrageScanner::multi_hop_opportunities_from_prices]).
+    pub async fn scan_multi_hop(
+        exchanges: &[CexExchange],
+        symbols: &[&str],
+        options: &MultiHopOptions,
+        fee_overrides: Option<&FeeOverrides>,
+    ) -> Result<Vec<MultiHopOpportunity>, MarketScannerError> {
+        let results = join_all(
+            symbols
+                .iter()
+                .map(|symbol| Self::fetch_cex_prices(exchanges, symbol)),
+        )
+        .await;
+        let mut prices = Vec::new();
+        for result in results {
+            prices.extend(result?);
+        }
+        Ok(Self::multi_hop_opportunities_from_prices(
+            &prices,
+            options,
+            fee_overrides,
+        ))
+    }
+
+    /// Profitable chains of 3 to `options.max_hops()` conversions across the markets and
+    /// venues of `prices`, most profitable first. Chains start with a buy, so they are
+    /// expressed in a quote asset.
+    pub fn multi_hop_opportunities_from_prices(
+        prices: &[CexPrice],
+        options: &MultiHopOptions,
+        fee_overrides: Option<&FeeOverrides>,
+    ) -> Vec<MultiHopOpportunity> {
+        if let Some(threshold) = options.direct_threshold_percent {
+            let direct = Self::opportunities_from_prices(prices, &[], fee_overrides);
+            if direct.iter().any(|o| o.spread_percentage >= threshold) {
+                return Vec::new();
+            }
+        }
+        let edges = edges(prices, fee_overrides);
+        let mut search = Search {
+            edges: &edges,
+            options,
+            seen: HashSet::new(),
+            found: Vec::new(),
+        };
+        for (idx, edge) in edges.iter().enumerate() {
+            if edge.hop.side == TradeSide::Buy {
+                let start = edge.hop.from_asset.clone();
+                search.extend(&start, &mut vec![idx]);
+            }
+        }
+        let mut found = search.found;
+        found.sort_by(|a, b| b.profit_percentage.total_cmp(&a.profit_percentage));
+        found
+    }
+}
@@ -1,4 +1,10 @@
-use crate::common::{CexPrice, DexPrice};
+use crate::common::{BaseQty, CexPrice, DexPrice, Exchange, QuoteQty};
+use crate::scanner::fiat_calendar::FiatRestriction;
+use crate::scanner::funding::OpportunityViability;
+use crate::scanner::latency::LatencyTrace;
+use crate::scanner::mev::MevRisk;
+use crate::scanner::min_notional::NotionalFloor;
+use crate::scanner::spread_breakdown::SpreadBreakdown;
 use serde::{Deserialize, Serialize};
 
 /// Price data enum - can contain either CEX or DEX price data
@@ -11,6 +17,32 @@ pub enum PriceData {
     Dex(DexPrice),
 }
 
+impl PriceData {
+    /// Venue of the leg and the price it trades at: the ask when buying, the bid when selling.
+    pub(crate) fn exchange_and_price(&self, buy: bool) -> (Exchange, f64) {
+        match self {
+            PriceData::Cex(p) => (
+                p.exchange.clone(),
+                if buy { p.ask_price } else { p.bid_price },
+            ),
+            PriceData::Dex(p) => (
+                p.exchange.clone(),
+                if buy { p.ask_price } else { p.bid_price },
+            ),
+        }
+    }
+}
+
+/// Current serialized schema version of [ArbitrageOpportunity].
+///
+/// Bumped whenever fields are added or change meaning. Version 1 is the original layout
+/// (`buy_*` / `sell_*` names) and is assumed for payloads without a `schema_version`.
+pub const OPPORTUNITY_SCHEMA_VERSION: u32 = 9;
+
+fn legacy_schema_version() -> u32 {
+    1
+}
+
 /// Arbitrage opportunity: buy from one exchange (source), sell on another (destination).
 ///
 /// Uses standard arbitrage terminology:
@@ -18,6 +50,9 @@ pub enum PriceData {
 /// - **Destination leg**: where we dispose of the asset (receive effective bid)
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ArbitrageOpportunity {
+    /// Schema version of the serialized form (see [OPPORTUNITY_SCHEMA_VERSION])
+    #[serde(default = "legacy_schema_version")]
+    pub schema_version: u32,
     /// Source exchange: where we buy (acquire) the asset
     #[serde(alias = "buy_exchange")]
     pub source_exchange: String,
@@ -38,7 +73,7 @@ pub struct ArbitrageOpportunity {
     /// Spread as percentage ((spread / effective_ask) × 100), net of fees
     #[serde(alias = "profit_percentage")]
     pub spread_percentage: f64,
-    /// Maximum executable quantity (min of available depth on both legs)
+    /// Maximum executable quantity in base units (min of available depth on both legs)
     #[serde(alias = "buy_quantity", alias = "sell_quantity")]
     pub executable_quantity: f64,
     /// Source leg commission rate in percent (e.g. 0.1 = 0.1%)
@@ -53,6 +88,37 @@ pub struct ArbitrageOpportunity {
     /// Full price data for the destination leg (dispose side)
     #[serde(alias = "sell_price_data")]
     pub destination_leg: PriceData,
+    /// Funding classification; set when a [crate::scanner::FundingModel] is applied
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub viability: Option<OpportunityViability>,
+    /// Sandwich/MEV exposure of the DEX legs; set when a [crate::scanner::MevRiskModel] is applied
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mev_risk: Option<MevRisk>,
+    /// Output amount (base units of the output token) of the DEX swap simulated against
+    /// current chain state; set by [crate::dex::execution::SwapSimulation::annotate]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub simulated_amount_out: Option<String>,
+    /// Stage timings of the price update behind this opportunity; set by the WebSocket
+    /// scanners while latency tracing is enabled
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub latency: Option<LatencyTrace>,
+    /// Flagged fiat period (banking holiday, closed banking hours) of a leg's quote
+    /// currency; set when a [crate::scanner::FiatCalendar] is applied
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fiat_restriction: Option<FiatRestriction>,
+    /// Minimum tradable notional of the legs; set when a [crate::scanner::MinNotionalGuard]
+    /// is applied
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub notional_floor: Option<NotionalFloor>,
+    /// Per-unit raw spread, fees, slippage and transfer cost; set by scans (raw spread and
+    /// fees) and completed by a [crate::scanner::SpreadCostModel]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub spread_breakdown: Option<SpreadBreakdown>,
+    /// Episode id, e.g. "Binance->OKX:BTCUSDT@1700000000000"; set by
+    /// [crate::scanner::OpportunityLifecycle] when the opportunity opens and used to
+    /// acknowledge its execution ([crate::scanner::ExecutionAck])
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
 }
 
 impl ArbitrageOpportunity {
@@ -60,4 +126,83 @@ impl ArbitrageOpportunity {
     pub fn total_profit(&self) -> f64 {
         self.spread * self.executable_quantity
     }
+
+    /// [ArbitrageOpportunity::executable_quantity] as a base quantity.
+    pub fn executable_size(&self) -> BaseQty {
+        BaseQty(self.executable_quantity)
+    }
+
+    /// Quote cost of buying the executable quantity at the effective ask.
+    pub fn executable_notional(&self) -> QuoteQty {
+        self.executable_size().to_quote(self.effective_ask)
+    }
+
+    /// [ArbitrageOpportunity::total_profit] as a quote quantity.
+    pub fn total_profit_quote(&self) -> QuoteQty {
+        QuoteQty(self.total_profit())
+    }
+
+    /// [ArbitrageOpportunity::total_commission_quote] as a quote quantity.
+    pub fn total_commission(&self) -> QuoteQty {
+        QuoteQty(self.total_commission_quote)
+    }
+
+    /// Identity of the venue pair and symbol, e.g. "Binance->OKX:BTCUSDT"; stable across
+    /// scans and restarts.
+    pub fn key(&self) -> String {
+        format!(
+            "{}->{}:{}",
+            self.source_exchange, self.destination_exchange, self.symbol
+        )
+    }
+}
+
+/// Part of a [MultiLegOpportunity] sold on one destination venue.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SellTranche {
+    /// Destination exchange for this tranche
+    pub destination_exchange: String,
+    /// Quantity sold on this venue
+    pub quantity: f64,
+    /// Share of the total quantity (0.0–1.0)
+    pub fraction: f64,
+    /// Volume-weighted effective bid across the consumed levels (bid × (1 − fee))
+    pub average_effective_bid: f64,
+    /// Quote proceeds after fees
+    pub proceeds_quote: f64,
+}
+
+/// Arbitrage opportunity sized against order book depth: buy on one venue and split the
+/// sale across several destination venues (e.g. sell 60% on B and 40% on C).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MultiLegOpportunity {
+    /// Source exchange: where we buy (acquire) the asset
+    pub source_exchange: String,
+    /// Trading pair symbol (e.g. "BTCUSDT")
+    pub symbol: String,
+    /// Total quantity bought and sold
+    pub quantity: f64,
+    /// Volume-weighted effective ask across the consumed levels (ask × (1 + fee))
+    pub average_effective_ask: f64,
+    /// Quote cost of the buy leg including fees
+    pub cost_quote: f64,
+    /// Sell tranches, largest first
+    pub tranches: Vec<SellTranche>,
+    /// Quote proceeds of all tranches after fees
+    pub proceeds_quote: f64,
+}
+
+impl MultiLegOpportunity {
+    /// Total profit in quote currency (proceeds − cost), net of fees
+    pub fn total_profit(&self) -> f64 {
+        self.proceeds_quote - self.cost_quote
+    }
+
+    /// Profit as percentage of the buy cost
+    pub fn profit_percentage(&self) -> f64 {
+        if self.cost_quote <= 0.0 {
+            return 0.0;
+        }
+        self.total_profit() / self.cost_quote * 100.0
+    }
 }
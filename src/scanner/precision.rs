@@ -0,0 +1,191 @@
+//! Decimal precision of exported and alerted numbers.
+//!
+//! Serialized `f64`s carry binary noise (`0.30000000000000004`). An [OutputPrecision] rounds
+//! numbers per [FieldClass] (prices and quote amounts, quantities, percents) when writing
+//! JSON or CSV. Fields are classified by name (see [FieldClass::of]); other numbers, and
+//! classes without a configured precision, are written unchanged. Used by
+//! [crate::scanner::AlertTemplate::with_precision], [OutputPrecision::json_lines_sink] and
+//! the [crate::portfolio::ProfitAttribution] exports.
+
+use crate::common::MarketScannerError;
+use crate::scanner::ArbitrageOpportunity;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+
+/// Kind of number a field holds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FieldClass {
+    /// Prices and amounts in quote currency (spreads, profits, fees)
+    Price,
+    /// Base asset quantities and token amounts
+    Quantity,
+    /// Percentages
+    Percent,
+}
+
+impl FieldClass {
+    /// Class of a field by its name: names containing `percent` are percents; `qty`,
+    /// `quantity` or an `amount_` prefix make a quantity; names ending in `price`, `_ask`,
+    /// `_bid` or `_quote`, containing `profit` or `pnl`, and `spread`, `spread_captured`,
+    /// `fees`, `slippage` and `transfer_costs` are prices.
+    pub fn of(field: &str) -> Option<Self> {
+        if field.contains("percent") {
+            Some(FieldClass::Percent)
+        } else if field.contains("qty")
+            || field.contains("quantity")
+            || field.starts_with("amount_")
+        {
+            Some(FieldClass::Quantity)
+        } else if field.ends_with("price")
+            || field.ends_with("_ask")
+            || field.ends_with("_bid")
+            || field.ends_with("_quote")
+            || field.contains("profit")
+            || field.contains("pnl")
+            || matches!(
+                field,
+                "spread" | "spread_captured" | "fees" | "slippage" | "transfer_costs"
+            )
+        {
+            Some(FieldClass::Price)
+        } else {
+            None
+        }
+    }
+}
+
+/// Decimal places per [FieldClass]; unset classes keep full precision.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct OutputPrecision {
+    price_decimals: Option<u32>,
+    quantity_decimals: Option<u32>,
+    percent_decimals: Option<u32>,
+    /// Classes of fields the naming rules get wrong or miss
+    field_classes: HashMap<String, FieldClass>,
+}
+
+impl OutputPrecision {
+    /// Full precision everywhere.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_price_decimals(mut self, decimals: u32) -> Self {
+        self.price_decimals = Some(decimals);
+        self
+    }
+
+    pub fn with_quantity_decimals(mut self, decimals: u32) -> Self {
+        self.quantity_decimals = Some(decimals);
+        self
+    }
+
+    pub fn with_percent_decimals(mut self, decimals: u32) -> Self {
+        self.percent_decimals = Some(decimals);
+        self
+    }
+
+    /// Classifies `field` as `class`, overriding [FieldClass::of].
+    pub fn with_field_class(mut self, field: &str, class: FieldClass) -> Self {
+        self.field_classes.insert(field.to_string(), class);
+        self
+    }
+
+    /// Decimal places of `class`, if limited.
+    pub fn decimals(&self, class: FieldClass) -> Option<u32> {
+        match class {
+            FieldClass::Price => self.price_decimals,
+            FieldClass::Quantity => self.quantity_decimals,
+            FieldClass::Percent => self.percent_decimals,
+        }
+    }
+
+    /// Class of `field`: the configured one, else [FieldClass::of].
+    pub fn class_of(&self, field: &str) -> Option<FieldClass> {
+        self.field_classes
+            .get(field)
+            .copied()
+            .or_else(|| FieldClass::of(field))
+    }
+
+    /// `value` rounded to the decimals of `class`; non-finite values are kept.
+    pub fn round(&self, value: f64, class: FieldClass) -> f64 {
+        let Some(decimals) = self.decimals(class) else {
+            return value;
+        };
+        let factor = 10f64.powi(decimals.min(i32::MAX as u32) as i32);
+        let rounded = (value * factor).round() / factor;
+        if rounded.is_finite() { rounded } else { value }
+    }
+
+    /// `value` rounded for `class` and formatted without trailing zeros (e.g. CSV cells).
+    pub fn format(&self, value: f64, class: FieldClass) -> String {
+        self.round(value, class).to_string()
+    }
+
+    /// Rounds every classified number in `value`, recursing into objects and arrays.
+    pub fn apply(&self, value: &mut Value) {
+        self.apply_field(None, value);
+    }
+
+    /// `value` serialized to JSON with its numbers rounded.
+    pub fn to_value<T: Serialize>(&self, value: &T) -> Result<Value, MarketScannerError> {
+        let mut value = serde_json::to_value(value)?;
+        self.apply(&mut value);
+        Ok(value)
+    }
+
+    /// `value` as pretty-printed JSON with its numbers rounded.
+    pub fn to_json<T: Serialize>(&self, value: &T) -> Result<String, MarketScannerError> {
+        Ok(serde_json::to_string_pretty(&self.to_value(value)?)?)
+    }
+
+    /// Sink for [crate::scanner::ScannerContext::with_sink] calling `write` with one JSON
+    /// line per opportunity, numbers rounded.
+    pub fn json_lines_sink<F>(
+        self,
+        write: F,
+    ) -> impl Fn(&str, &[ArbitrageOpportunity]) + Send + Sync + 'static
+    where
+        F: Fn(String) + Send + Sync + 'static,
+    {
+        move |_context, opportunities| {
+            for opportunity in opportunities {
+                if let Ok(value) = self.to_value(opportunity) {
+                    write(value.to_string());
+                }
+            }
+        }
+    }
+
+    fn apply_field(&self, class: Option<FieldClass>, value: &mut Value) {
+        match value {
+            Value::Number(number) => {
+                // Integers (timestamps, counts) are exact already
+                let Some(class) = class.filter(|_| number.is_f64()) else {
+                    return;
+                };
+                if let Some(rounded) = number
+                    .as_f64()
+                    .and_then(|n| serde_json::Number::from_f64(self.round(n, class)))
+                {
+                    *number = rounded;
+                }
+            }
+            Value::Array(items) => {
+                for item in items {
+                    self.apply_field(class, item);
+                }
+            }
+            Value::Object(fields) => {
+                for (name, field) in fields.iter_mut() {
+                    self.apply_field(self.class_of(name), field);
+                }
+            }
+            Value::Null | Value::Bool(_) | Value::String(_) => {}
+        }
+    }
+}
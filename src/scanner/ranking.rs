@@ -0,0 +1,139 @@
+//! Ordering of scan results.
+//!
+//! Scans sort by spread percentage. When capital is constrained, a small opportunity with a
+//! high yield on the capital it locks can be worth more than a big low-yield one.
+//! [CapitalEfficiencyModel] scores opportunities by net profit per unit of capital locked,
+//! `profit / (notional + transfer buffer)`, using the balances of a [FundingModel] and the
+//! transfer fees of a [TransferCostModel].
+
+use crate::common::{Exchange, MarketKey, logical_market_key};
+use crate::scanner::funding::FundingModel;
+use crate::scanner::transfer_cost::TransferCostModel;
+use crate::scanner::{ArbitrageOpportunity, PriceData};
+use serde::{Deserialize, Serialize};
+
+/// How opportunities are ordered, best first.
+#[derive(Debug, Clone, Default)]
+pub enum OpportunityRanking {
+    /// Highest spread percentage (net of fees) first
+    #[default]
+    SpreadPercent,
+    /// Highest total profit (spread × executable quantity) first
+    TotalProfit,
+    /// Highest net profit per unit of capital locked first (see [CapitalEfficiencyModel])
+    CapitalEfficiency(Box<CapitalEfficiencyModel>),
+}
+
+impl OpportunityRanking {
+    /// Sorts `opportunities` best first. Opportunities without a capital efficiency (no
+    /// common transfer network) go last.
+    pub fn sort(&self, opportunities: &mut [ArbitrageOpportunity]) {
+        match self {
+            OpportunityRanking::SpreadPercent => {
+                opportunities.sort_by(|a, b| b.spread_percentage.total_cmp(&a.spread_percentage))
+            }
+            OpportunityRanking::TotalProfit => {
+                opportunities.sort_by(|a, b| b.total_profit().total_cmp(&a.total_profit()))
+            }
+            OpportunityRanking::CapitalEfficiency(model) => model.rank(opportunities),
+        }
+    }
+}
+
+impl From<CapitalEfficiencyModel> for OpportunityRanking {
+    fn from(model: CapitalEfficiencyModel) -> Self {
+        OpportunityRanking::CapitalEfficiency(Box::new(model))
+    }
+}
+
+/// Capital an opportunity locks and what it earns on it.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct CapitalEfficiency {
+    /// Total profit minus rebalancing transfer fees (quote currency)
+    pub net_profit: f64,
+    /// Quote spent on the buy leg (quantity × effective ask)
+    pub notional: f64,
+    /// Inventory that must be transferred in before trading (valued in quote) plus the
+    /// rebalancing transfer fees
+    pub transfer_buffer: f64,
+    /// `net_profit / (notional + transfer_buffer)`
+    pub ratio: f64,
+}
+
+/// Scores opportunities by net profit per unit of capital locked. Without a funding model
+/// no inventory has to be transferred in; without a transfer cost model no fees are
+/// deducted.
+#[derive(Debug, Clone, Default)]
+pub struct CapitalEfficiencyModel {
+    funding: Option<FundingModel>,
+    transfer_costs: Option<TransferCostModel>,
+}
+
+impl CapitalEfficiencyModel {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Balances per venue: shortfalls of the legs count into the transfer buffer.
+    pub fn with_funding_model(mut self, funding: FundingModel) -> Self {
+        self.funding = Some(funding);
+        self
+    }
+
+    /// Rebalancing transfer fees are deducted from the profit and count into the buffer.
+    pub fn with_transfer_costs(mut self, transfer_costs: TransferCostModel) -> Self {
+        self.transfer_costs = Some(transfer_costs);
+        self
+    }
+
+    /// Capital efficiency of `opportunity` for its executable quantity; `None` if the
+    /// transfer cost model finds no common network for an asset.
+    pub fn evaluate(&self, opportunity: &ArbitrageOpportunity) -> Option<CapitalEfficiency> {
+        let quantity = opportunity.executable_quantity;
+        let notional = quantity * opportunity.effective_ask;
+        let fees = match &self.transfer_costs {
+            Some(model) => model.transfer_costs(opportunity)?.total_quote(),
+            None => 0.0,
+        };
+
+        let mut transfer_buffer = fees;
+        if let Some(funding) = &self.funding {
+            let (source, symbol) = leg_venue(&opportunity.source_leg);
+            let (destination, _) = leg_venue(&opportunity.destination_leg);
+            let MarketKey { base, quote } = logical_market_key(symbol, source);
+            // Buy leg needs quote on the source venue, sell leg needs base on the destination
+            let quote_shortfall = (notional - funding.balance(source, &quote)).max(0.0);
+            let base_shortfall = (quantity - funding.balance(destination, &base)).max(0.0);
+            transfer_buffer += quote_shortfall + base_shortfall * opportunity.effective_ask;
+        }
+
+        let net_profit = opportunity.total_profit() - fees;
+        let capital = notional + transfer_buffer;
+        Some(CapitalEfficiency {
+            net_profit,
+            notional,
+            transfer_buffer,
+            ratio: if capital > 0.0 {
+                net_profit / capital
+            } else {
+                0.0
+            },
+        })
+    }
+
+    /// Sorts `opportunities` by capital efficiency, best first; those without one go last.
+    pub fn rank(&self, opportunities: &mut [ArbitrageOpportunity]) {
+        let ratio = |o: &ArbitrageOpportunity| {
+            self.evaluate(o)
+                .map_or(f64::NEG_INFINITY, |efficiency| efficiency.ratio)
+        };
+        opportunities.sort_by(|a, b| ratio(b).total_cmp(&ratio(a)));
+    }
+}
+
+fn leg_venue(leg: &PriceData) -> (&Exchange, &str) {
+    match leg {
+        PriceData::Cex(p) => (&p.exchange, p.symbol.as_str()),
+        PriceData::Dex(p) => (&p.exchange, p.symbol.as_str()),
+    }
+}
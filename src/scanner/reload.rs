@@ -0,0 +1,271 @@
+//! Hot reload of thresholds, filters and fees.
+//!
+//! [ScannerSettings] are the tunables an operator changes while a scanner runs: the alert
+//! threshold, symbol and venue filters and taker fee overrides, kept in a JSON file. A
+//! [SettingsHandle] holds the current settings; [ScannerContext::with_settings] and
+//! [LiveScanOptions::with_settings] read them for every snapshot, so a reload applies on the
+//! next price update without restarting streams. Reloads swap the whole settings value at
+//! once: a snapshot never mixes old fees with new filters. A file that fails to parse or
+//! validate is rejected and the previous settings stay in place.
+//!
+//! Reloads are triggered by [SettingsHandle::reload_from], a file watch task
+//! ([SettingsHandle::watch_file]) or `SIGHUP` ([SettingsHandle::reload_on_sighup], Unix).
+//!
+//! [ScannerContext::with_settings]: crate::scanner::ScannerContext::with_settings
+//! [LiveScanOptions::with_settings]: crate::scanner::LiveScanOptions::with_settings
+
+use crate::common::{CexExchange, DexAggregator, FeeOverrides, MarketScannerError, Timestamp};
+use crate::scanner::ArbitrageOpportunity;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, SystemTime};
+use tokio::sync::mpsc;
+
+/// Reloadable thresholds, filters and fees.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ScannerSettings {
+    /// Alert threshold: opportunities below this spread (percent, net of fees) are dropped
+    pub min_spread_percent: Option<f64>,
+    /// Opportunities executable for less than this base quantity are dropped
+    pub min_executable_quantity: Option<f64>,
+    /// Only these symbols are kept (all when empty)
+    pub symbols: Vec<String>,
+    /// Opportunities with a leg on these venues are dropped (display names, e.g. `Binance`)
+    pub excluded_exchanges: Vec<String>,
+    /// Taker fee overrides per CEX (decimals, e.g. `0.001`)
+    pub cex_taker_fees: HashMap<CexExchange, f64>,
+    /// Taker fee overrides per DEX aggregator (decimals)
+    pub dex_taker_fees: HashMap<DexAggregator, f64>,
+}
+
+impl ScannerSettings {
+    pub fn from_json(json: &str) -> Result<Self, MarketScannerError> {
+        let settings: Self = serde_json::from_str(json)?;
+        settings.validate()?;
+        Ok(settings)
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, MarketScannerError> {
+        let path = path.as_ref();
+        let json = std::fs::read_to_string(path).map_err(|e| {
+            MarketScannerError::InvalidConfig(format!(
+                "Cannot read settings {}: {}",
+                path.display(),
+                e
+            ))
+        })?;
+        Self::from_json(&json)
+    }
+
+    pub fn validate(&self) -> Result<(), MarketScannerError> {
+        if let Some(min) = self.min_spread_percent {
+            if !min.is_finite() {
+                return Err(MarketScannerError::InvalidConfig(format!(
+                    "min_spread_percent must be finite, got {}",
+                    min
+                )));
+            }
+        }
+        if let Some(min) = self.min_executable_quantity {
+            if !min.is_finite() || min < 0.0 {
+                return Err(MarketScannerError::InvalidConfig(format!(
+                    "min_executable_quantity must be non-negative, got {}",
+                    min
+                )));
+            }
+        }
+        let mut fees = self
+            .cex_taker_fees
+            .values()
+            .chain(self.dex_taker_fees.values());
+        if let Some(fee) = fees.find(|fee| !(0.0..1.0).contains(*fee)) {
+            return Err(MarketScannerError::InvalidConfig(format!(
+                "Taker fees must be in [0, 1), got {}",
+                fee
+            )));
+        }
+        Ok(())
+    }
+
+    /// Fee overrides of these settings; `None` without any fee.
+    pub fn fee_overrides(&self) -> Option<FeeOverrides> {
+        if self.cex_taker_fees.is_empty() && self.dex_taker_fees.is_empty() {
+            return None;
+        }
+        Some(FeeOverrides {
+            cex_taker: self.cex_taker_fees.clone(),
+            dex_taker: self.dex_taker_fees.clone(),
+        })
+    }
+
+    /// Whether `opportunity` passes the threshold and filters of these settings.
+    pub fn allows(&self, opportunity: &ArbitrageOpportunity) -> bool {
+        self.min_spread_percent
+            .is_none_or(|min| opportunity.spread_percentage >= min)
+            && self
+                .min_executable_quantity
+                .is_none_or(|min| opportunity.executable_quantity >= min)
+            && (self.symbols.is_empty()
+                || self
+                    .symbols
+                    .iter()
+                    .any(|s| s.eq_ignore_ascii_case(&opportunity.symbol)))
+            && !self.excluded_exchanges.iter().any(|excluded| {
+                excluded.eq_ignore_ascii_case(&opportunity.source_exchange)
+                    || excluded.eq_ignore_ascii_case(&opportunity.destination_exchange)
+            })
+    }
+}
+
+/// Outcome of a reload by [SettingsHandle::watch_file] or [SettingsHandle::reload_on_sighup].
+#[derive(Debug, Clone, PartialEq)]
+pub struct SettingsReload {
+    pub path: PathBuf,
+    pub at: Timestamp,
+    /// Settings version after the reload (unchanged when it failed)
+    pub version: u64,
+    /// Why the file was rejected; the previous settings stay in place
+    pub error: Option<String>,
+}
+
+/// Current [ScannerSettings], shared between the scanner and whatever reloads them.
+/// Clones share the settings.
+#[derive(Clone, Default)]
+pub struct SettingsHandle {
+    current: Arc<RwLock<Arc<ScannerSettings>>>,
+    version: Arc<AtomicU64>,
+}
+
+impl fmt::Debug for SettingsHandle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SettingsHandle")
+            .field("current", &self.current())
+            .field("version", &self.version())
+            .finish()
+    }
+}
+
+impl SettingsHandle {
+    pub fn new(settings: ScannerSettings) -> Self {
+        let handle = Self::default();
+        *handle.current.write().unwrap_or_else(|e| e.into_inner()) = Arc::new(settings);
+        handle
+    }
+
+    /// Handle with the settings of the file at `path`.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, MarketScannerError> {
+        Ok(Self::new(ScannerSettings::load(path)?))
+    }
+
+    /// Settings in effect; hold the returned value for the duration of one snapshot.
+    pub fn current(&self) -> Arc<ScannerSettings> {
+        self.current
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+    }
+
+    /// Number of times the settings were replaced.
+    pub fn version(&self) -> u64 {
+        self.version.load(Ordering::SeqCst)
+    }
+
+    /// Replaces the settings (after validating them) and returns the new version.
+    pub fn replace(&self, settings: ScannerSettings) -> Result<u64, MarketScannerError> {
+        settings.validate()?;
+        let mut current = self.current.write().unwrap_or_else(|e| e.into_inner());
+        *current = Arc::new(settings);
+        Ok(self.version.fetch_add(1, Ordering::SeqCst) + 1)
+    }
+
+    /// Replaces the settings with those of the file at `path`; on error the current
+    /// settings are kept.
+    pub fn reload_from(&self, path: impl AsRef<Path>) -> Result<u64, MarketScannerError> {
+        self.replace(ScannerSettings::load(path)?)
+    }
+
+    /// Reloads the file at `path` whenever its modification time changes, checking every
+    /// `poll_interval`. Every reload attempt is reported on the returned channel; the
+    /// watch ends when the receiver is dropped.
+    pub fn watch_file(
+        &self,
+        path: impl Into<PathBuf>,
+        poll_interval: Duration,
+    ) -> mpsc::Receiver<SettingsReload> {
+        let path = path.into();
+        let handle = self.clone();
+        let (tx, rx) = mpsc::channel(16);
+        tokio::spawn(async move {
+            let modified = |path: &Path| -> Option<SystemTime> {
+                std::fs::metadata(path).and_then(|m| m.modified()).ok()
+            };
+            let mut last_modified = modified(&path);
+            let mut interval = tokio::time::interval(poll_interval.max(Duration::from_millis(1)));
+            interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            interval.tick().await;
+            loop {
+                tokio::select! {
+                    _ = interval.tick() => {}
+                    _ = tx.closed() => return,
+                }
+                let current = modified(&path);
+                if current.is_none() || current == last_modified {
+                    continue;
+                }
+                last_modified = current;
+                if tx.send(handle.reload_report(&path)).await.is_err() {
+                    return;
+                }
+            }
+        });
+        rx
+    }
+
+    /// Reloads the file at `path` on every `SIGHUP`, reporting each attempt on the
+    /// returned channel; stops listening when the receiver is dropped.
+    #[cfg(unix)]
+    pub fn reload_on_sighup(
+        &self,
+        path: impl Into<PathBuf>,
+    ) -> Result<mpsc::Receiver<SettingsReload>, MarketScannerError> {
+        use tokio::signal::unix::{SignalKind, signal};
+
+        let mut hangups = signal(SignalKind::hangup()).map_err(|e| {
+            MarketScannerError::InvalidConfig(format!("Cannot listen for SIGHUP: {}", e))
+        })?;
+        let path = path.into();
+        let handle = self.clone();
+        let (tx, rx) = mpsc::channel(16);
+        tokio::spawn(async move {
+            loop {
+                tokio::select! {
+                    received = hangups.recv() => {
+                        if received.is_none() {
+                            return;
+                        }
+                    }
+                    _ = tx.closed() => return,
+                }
+                if tx.send(handle.reload_report(&path)).await.is_err() {
+                    return;
+                }
+            }
+        });
+        Ok(rx)
+    }
+
+    fn reload_report(&self, path: &Path) -> SettingsReload {
+        let result = self.reload_from(path);
+        SettingsReload {
+            path: path.to_path_buf(),
+            at: Timestamp::now(),
+            version: self.version(),
+            error: result.err().map(|e| e.to_string()),
+        }
+    }
+}
@@ -0,0 +1,382 @@
+//! Windowed opportunity aggregation.
+//!
+//! [OpportunityAggregator] folds opportunities into per (source, destination, symbol)
+//! statistics over a fixed window and closes the window into an [OpportunityReport], so
+//! strategy research can work from summaries instead of every tick.
+//! [aggregate_opportunity_stream] does the same for a live snapshot stream and emits one
+//! report per window. [OpportunitySeasonality] buckets opportunities by UTC hour of day and
+//! day of week per venue pair, to find recurring patterns (funding, settlement).
+
+use crate::common::Timestamp;
+use crate::scanner::ArbitrageOpportunity;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::time::Duration;
+use tokio::sync::mpsc;
+
+/// Window used when 0 is configured (1 hour).
+const DEFAULT_WINDOW_MS: u64 = 60 * 60 * 1000;
+
+/// Statistics of one venue pair and symbol within a report window.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OpportunitySummary {
+    pub source_exchange: String,
+    pub destination_exchange: String,
+    pub symbol: String,
+    /// Number of recorded opportunities
+    pub count: u64,
+    /// Mean spread percentage (net of fees)
+    pub avg_spread_percentage: f64,
+    /// Largest spread percentage seen
+    pub max_spread_percentage: f64,
+    /// Sum of [ArbitrageOpportunity::total_profit] in quote currency
+    pub cumulative_profit_quote: f64,
+}
+
+/// Summary of all opportunities recorded in one window.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OpportunityReport {
+    pub window_start: Timestamp,
+    pub window_end: Timestamp,
+    /// Number of recorded opportunities across all entries
+    pub total_count: u64,
+    /// One entry per (source, destination, symbol), highest cumulative profit first
+    pub entries: Vec<OpportunitySummary>,
+}
+
+#[derive(Debug, Clone, Default)]
+struct Accumulator {
+    count: u64,
+    spread_percentage_sum: f64,
+    max_spread_percentage: f64,
+    profit_sum: f64,
+}
+
+impl Accumulator {
+    fn add(&mut self, spread_percentage: f64, profit: f64) {
+        self.count += 1;
+        self.spread_percentage_sum += spread_percentage;
+        self.max_spread_percentage = if self.count == 1 {
+            spread_percentage
+        } else {
+            self.max_spread_percentage.max(spread_percentage)
+        };
+        self.profit_sum += profit;
+    }
+
+    fn merge(&mut self, other: &Accumulator) {
+        if other.count == 0 {
+            return;
+        }
+        self.max_spread_percentage = if self.count == 0 {
+            other.max_spread_percentage
+        } else {
+            self.max_spread_percentage.max(other.max_spread_percentage)
+        };
+        self.count += other.count;
+        self.spread_percentage_sum += other.spread_percentage_sum;
+        self.profit_sum += other.profit_sum;
+    }
+}
+
+/// Aggregates opportunities over consecutive windows. Time is passed in explicitly so
+/// windows are deterministic.
+///
+/// Snapshot streams repeat an opportunity on every update for as long as it persists, so
+/// `count` measures observations rather than distinct opportunities.
+#[derive(Debug, Clone)]
+pub struct OpportunityAggregator {
+    window: Duration,
+    window_start: Timestamp,
+    stats: HashMap<(String, String, String), Accumulator>,
+}
+
+impl OpportunityAggregator {
+    /// Starts the first window at `start`; `window_ms` of 0 → 1 hour.
+    pub fn new(window_ms: u64, start: Timestamp) -> Self {
+        let window_ms = if window_ms == 0 {
+            DEFAULT_WINDOW_MS
+        } else {
+            window_ms
+        };
+        Self {
+            window: Duration::from_millis(window_ms),
+            window_start: start,
+            stats: HashMap::new(),
+        }
+    }
+
+    pub fn window(&self) -> Duration {
+        self.window
+    }
+
+    pub fn window_start(&self) -> Timestamp {
+        self.window_start
+    }
+
+    /// End of the current window.
+    pub fn window_end(&self) -> Timestamp {
+        self.window_start.saturating_add(self.window)
+    }
+
+    /// Adds one opportunity to the current window.
+    pub fn record(&mut self, opportunity: &ArbitrageOpportunity) {
+        let acc = self
+            .stats
+            .entry((
+                opportunity.source_exchange.clone(),
+                opportunity.destination_exchange.clone(),
+                opportunity.symbol.clone(),
+            ))
+            .or_default();
+        acc.add(opportunity.spread_percentage, opportunity.total_profit());
+    }
+
+    pub fn record_all(&mut self, opportunities: &[ArbitrageOpportunity]) {
+        for opportunity in opportunities {
+            self.record(opportunity);
+        }
+    }
+
+    /// Closes the current window once `now` has reached its end and returns its report.
+    /// The next window starts where the closed one ended.
+    pub fn poll(&mut self, now: Timestamp) -> Option<OpportunityReport> {
+        if now < self.window_end() {
+            return None;
+        }
+        let end = self.window_end();
+        Some(self.close(end))
+    }
+
+    /// Closes the current window early at `now` (e.g. on shutdown) and returns its report.
+    pub fn flush(&mut self, now: Timestamp) -> OpportunityReport {
+        self.close(now)
+    }
+
+    fn close(&mut self, end: Timestamp) -> OpportunityReport {
+        let mut entries: Vec<OpportunitySummary> = self
+            .stats
+            .drain()
+            .map(|((source, destination, symbol), acc)| OpportunitySummary {
+                source_exchange: source,
+                destination_exchange: destination,
+                symbol,
+                count: acc.count,
+                avg_spread_percentage: acc.spread_percentage_sum / acc.count as f64,
+                max_spread_percentage: acc.max_spread_percentage,
+                cumulative_profit_quote: acc.profit_sum,
+            })
+            .collect();
+        entries.sort_by(|a, b| {
+            b.cumulative_profit_quote
+                .partial_cmp(&a.cumulative_profit_quote)
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+
+        let report = OpportunityReport {
+            window_start: self.window_start,
+            window_end: end,
+            total_count: entries.iter().map(|e| e.count).sum(),
+            entries,
+        };
+        self.window_start = end;
+        report
+    }
+}
+
+/// Statistics of opportunities seen in one UTC hour of day and/or day of week.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SeasonalityBucket {
+    /// Day of week, 0 = Monday … 6 = Sunday; `None` when summed over all days
+    pub weekday: Option<u8>,
+    /// Hour of day (UTC, 0–23); `None` when summed over all hours
+    pub hour: Option<u8>,
+    pub count: u64,
+    pub avg_spread_percentage: f64,
+    pub max_spread_percentage: f64,
+    pub cumulative_profit_quote: f64,
+}
+
+impl SeasonalityBucket {
+    fn new(weekday: Option<u8>, hour: Option<u8>, acc: &Accumulator) -> Self {
+        Self {
+            weekday,
+            hour,
+            count: acc.count,
+            avg_spread_percentage: acc.spread_percentage_sum / acc.count.max(1) as f64,
+            max_spread_percentage: acc.max_spread_percentage,
+            cumulative_profit_quote: acc.profit_sum,
+        }
+    }
+}
+
+/// When one venue pair's opportunities occur.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct VenuePairSeasonality {
+    pub source_exchange: String,
+    pub destination_exchange: String,
+    pub total_count: u64,
+    /// One bucket per (weekday, hour) with opportunities, in week order
+    pub buckets: Vec<SeasonalityBucket>,
+    /// Buckets summed over all days, one per hour with opportunities
+    pub by_hour: Vec<SeasonalityBucket>,
+    /// Buckets summed over all hours, one per weekday with opportunities
+    pub by_weekday: Vec<SeasonalityBucket>,
+}
+
+impl VenuePairSeasonality {
+    /// Hour of day with the highest cumulative profit.
+    pub fn peak_hour(&self) -> Option<&SeasonalityBucket> {
+        self.by_hour.iter().max_by(|a, b| {
+            a.cumulative_profit_quote
+                .total_cmp(&b.cumulative_profit_quote)
+        })
+    }
+
+    /// Day of week with the highest cumulative profit.
+    pub fn peak_weekday(&self) -> Option<&SeasonalityBucket> {
+        self.by_weekday.iter().max_by(|a, b| {
+            a.cumulative_profit_quote
+                .total_cmp(&b.cumulative_profit_quote)
+        })
+    }
+}
+
+/// Buckets opportunities per venue pair by UTC hour of day and day of week. Time is passed
+/// in explicitly, so recorded or replayed opportunities can be bucketed by when they were
+/// seen.
+#[derive(Debug, Clone, Default)]
+pub struct OpportunitySeasonality {
+    stats: HashMap<(String, String), HashMap<(u8, u8), Accumulator>>,
+}
+
+/// (weekday with 0 = Monday, hour) of `at` in UTC.
+fn weekday_and_hour(at: Timestamp) -> (u8, u8) {
+    let secs = at.as_secs();
+    let days = secs / 86_400;
+    // 1970-01-01 was a Thursday
+    (((days + 3) % 7) as u8, ((secs % 86_400) / 3_600) as u8)
+}
+
+impl OpportunitySeasonality {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds one opportunity seen at `at`.
+    pub fn record(&mut self, opportunity: &ArbitrageOpportunity, at: Timestamp) {
+        self.stats
+            .entry((
+                opportunity.source_exchange.clone(),
+                opportunity.destination_exchange.clone(),
+            ))
+            .or_default()
+            .entry(weekday_and_hour(at))
+            .or_default()
+            .add(opportunity.spread_percentage, opportunity.total_profit());
+    }
+
+    pub fn record_all(&mut self, opportunities: &[ArbitrageOpportunity], at: Timestamp) {
+        for opportunity in opportunities {
+            self.record(opportunity, at);
+        }
+    }
+
+    /// Seasonality of `source` → `destination`.
+    pub fn venue_pair(&self, source: &str, destination: &str) -> Option<VenuePairSeasonality> {
+        let key = (source.to_string(), destination.to_string());
+        self.stats
+            .get(&key)
+            .map(|buckets| Self::summarize(&key, buckets))
+    }
+
+    /// Every venue pair, highest cumulative profit first.
+    pub fn report(&self) -> Vec<VenuePairSeasonality> {
+        let mut pairs: Vec<VenuePairSeasonality> = self
+            .stats
+            .iter()
+            .map(|(key, buckets)| Self::summarize(key, buckets))
+            .collect();
+        let profit = |pair: &VenuePairSeasonality| -> f64 {
+            pair.by_weekday
+                .iter()
+                .map(|b| b.cumulative_profit_quote)
+                .sum()
+        };
+        pairs.sort_by(|a, b| profit(b).total_cmp(&profit(a)));
+        pairs
+    }
+
+    fn summarize(
+        (source, destination): &(String, String),
+        buckets: &HashMap<(u8, u8), Accumulator>,
+    ) -> VenuePairSeasonality {
+        let mut hours: BTreeMap<u8, Accumulator> = BTreeMap::new();
+        let mut weekdays: BTreeMap<u8, Accumulator> = BTreeMap::new();
+        let mut keys: Vec<&(u8, u8)> = buckets.keys().collect();
+        keys.sort();
+        for &&(weekday, hour) in &keys {
+            let acc = &buckets[&(weekday, hour)];
+            hours.entry(hour).or_default().merge(acc);
+            weekdays.entry(weekday).or_default().merge(acc);
+        }
+
+        VenuePairSeasonality {
+            source_exchange: source.clone(),
+            destination_exchange: destination.clone(),
+            total_count: buckets.values().map(|acc| acc.count).sum(),
+            buckets: keys
+                .into_iter()
+                .map(|&(weekday, hour)| {
+                    SeasonalityBucket::new(Some(weekday), Some(hour), &buckets[&(weekday, hour)])
+                })
+                .collect(),
+            by_hour: hours
+                .iter()
+                .map(|(hour, acc)| SeasonalityBucket::new(None, Some(*hour), acc))
+                .collect(),
+            by_weekday: weekdays
+                .iter()
+                .map(|(weekday, acc)| SeasonalityBucket::new(Some(*weekday), None, acc))
+                .collect(),
+        }
+    }
+}
+
+/// Aggregates a snapshot stream (e.g. from
+/// [crate::scanner::ArbitrageScanner::scan_arbitrage_from_websockets]) into one
+/// [OpportunityReport] every `window_ms` (0 → 1 hour). When the input closes, the partial
+/// window is flushed as a final report.
+pub fn aggregate_opportunity_stream(
+    mut snapshots: mpsc::Receiver<Vec<ArbitrageOpportunity>>,
+    window_ms: u64,
+) -> mpsc::Receiver<OpportunityReport> {
+    let (tx, rx) = mpsc::channel(16);
+    let mut aggregator = OpportunityAggregator::new(window_ms, Timestamp::now());
+
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(aggregator.window());
+        // The first tick completes immediately
+        ticker.tick().await;
+
+        loop {
+            tokio::select! {
+                snapshot = snapshots.recv() => match snapshot {
+                    Some(opportunities) => aggregator.record_all(&opportunities),
+                    None => {
+                        let _ = tx.send(aggregator.flush(Timestamp::now())).await;
+                        return;
+                    }
+                },
+                _ = ticker.tick() => {
+                    let report = aggregator.flush(Timestamp::now());
+                    if tx.send(report).await.is_err() {
+                        return;
+                    }
+                }
+            }
+        }
+    });
+
+    rx
+}
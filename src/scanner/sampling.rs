@@ -0,0 +1,144 @@
+//! Per-consumer sampling of price and opportunity streams.
+//!
+//! WebSocket streams emit on every book change, far more often than a dashboard can
+//! render. [sample_stream] passes at most one item per key (venue and symbol for prices,
+//! the whole snapshot for opportunity snapshots) per interval: the first item of a window
+//! goes out at once, later ones replace each other and the latest is sent when the window
+//! ends, so a sampled consumer never misses the final state. With
+//! [StreamTee::subscribe_sampled] a UI subscriber is sampled while an execution subscriber
+//! of the same tee keeps the full rate.
+
+use crate::common::{CexPrice, DexPrice};
+use crate::scanner::{ArbitrageOpportunity, ArbitrageScanner, BackpressurePolicy, StreamTee};
+use std::collections::HashMap;
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tokio::time::Instant;
+
+/// Items of a sampled stream with the same key replace each other within a window.
+pub trait SampleKey {
+    fn sample_key(&self) -> String;
+}
+
+impl SampleKey for CexPrice {
+    /// Venue and symbol, e.g. "Binance:BTCUSDT"
+    fn sample_key(&self) -> String {
+        format!(
+            "{}:{}",
+            ArbitrageScanner::exchange_name(&self.exchange),
+            self.symbol
+        )
+    }
+}
+
+impl SampleKey for DexPrice {
+    /// Venue and symbol, e.g. "KyberSwap:ETHUSDT"
+    fn sample_key(&self) -> String {
+        format!(
+            "{}:{}",
+            ArbitrageScanner::exchange_name(&self.exchange),
+            self.symbol
+        )
+    }
+}
+
+impl SampleKey for ArbitrageOpportunity {
+    /// [ArbitrageOpportunity::key]
+    fn sample_key(&self) -> String {
+        self.key()
+    }
+}
+
+impl SampleKey for Vec<ArbitrageOpportunity> {
+    /// One key for every snapshot: at most one snapshot per window
+    fn sample_key(&self) -> String {
+        String::new()
+    }
+}
+
+/// Items of `source` with at most one per key every `every` (latest wins). Pending items
+/// are flushed when `source` ends; sampling stops when the returned receiver is dropped.
+pub fn sample_stream<T>(mut source: mpsc::Receiver<T>, every: Duration) -> mpsc::Receiver<T>
+where
+    T: SampleKey + Send + 'static,
+{
+    let (tx, rx) = mpsc::channel(64);
+    tokio::spawn(async move {
+        // Key -> end of its current window and the latest item received within it
+        let mut windows: HashMap<String, (Instant, Option<T>)> = HashMap::new();
+        loop {
+            let next_due = windows
+                .values()
+                .filter(|(_, pending)| pending.is_some())
+                .map(|(end, _)| *end)
+                .min();
+            tokio::select! {
+                item = source.recv() => {
+                    let Some(item) = item else {
+                        break;
+                    };
+                    let now = Instant::now();
+                    let key = item.sample_key();
+                    match windows.get_mut(&key) {
+                        Some((end, pending)) if now < *end => *pending = Some(item),
+                        _ => {
+                            windows.insert(key, (now + every, None));
+                            if tx.send(item).await.is_err() {
+                                return;
+                            }
+                        }
+                    }
+                }
+                _ = tokio::time::sleep_until(next_due.unwrap_or_else(Instant::now)),
+                    if next_due.is_some() =>
+                {
+                    let now = Instant::now();
+                    let mut due: Vec<(Instant, T)> = Vec::new();
+                    for (end, pending) in windows.values_mut() {
+                        if *end <= now {
+                            if let Some(item) = pending.take() {
+                                due.push((*end, item));
+                                *end = now + every;
+                            }
+                        }
+                    }
+                    due.sort_by_key(|(end, _)| *end);
+                    for (_, item) in due {
+                        if tx.send(item).await.is_err() {
+                            return;
+                        }
+                    }
+                }
+            }
+            let now = Instant::now();
+            windows.retain(|_, (end, pending)| pending.is_some() || *end > now);
+        }
+
+        let mut pending: Vec<(Instant, T)> = windows
+            .into_values()
+            .filter_map(|(end, pending)| Some((end, pending?)))
+            .collect();
+        pending.sort_by_key(|(end, _)| *end);
+        for (_, item) in pending {
+            if tx.send(item).await.is_err() {
+                return;
+            }
+        }
+    });
+    rx
+}
+
+impl<T: Clone + Send + SampleKey + 'static> StreamTee<T> {
+    /// [StreamTee::subscribe] sampled to at most one item per key every `every` (see
+    /// [sample_stream]). `capacity` and `policy` apply to the full-rate channel between
+    /// the tee and the sampler, which keeps up unless the consumer stalls.
+    pub fn subscribe_sampled(
+        &self,
+        name: &str,
+        capacity: usize,
+        policy: BackpressurePolicy,
+        every: Duration,
+    ) -> mpsc::Receiver<T> {
+        sample_stream(self.subscribe(name, capacity, policy), every)
+    }
+}
@@ -0,0 +1,220 @@
+//! Periodic REST scans with a cadence per symbol.
+//!
+//! A [ScanSchedule] assigns every symbol its own scan interval (BTC every 500 ms, long-tail
+//! pairs every 10 s), so a venue's rate limit is spent on the markets that actually produce
+//! opportunities. [ArbitrageScanner::scan_periodic] runs the schedule: symbols that are due
+//! at the same time are scanned concurrently and every scan is emitted as a
+//! [ScheduledScan]. A scan that takes longer than its interval delays that symbol only.
+//! With [ScanSchedule::with_jitter] every interval is randomly stretched or shortened so
+//! symbols sharing a cadence do not hit the venues in bursts; [ScanSchedule::with_rng]
+//! makes the jitter reproducible.
+
+use crate::common::symbol_registry;
+use crate::common::{CexExchange, FeeOverrides, MarketScannerError, SeededRng, Timestamp};
+use crate::scanner::{ArbitrageOpportunity, ArbitrageScanner, ScannerHandle};
+use futures::future::join_all;
+use std::time::Duration;
+use tokio::sync::mpsc;
+
+#[derive(Debug, Clone)]
+struct ScheduledSymbol {
+    symbol: String,
+    interval: Duration,
+    next_due: Option<Timestamp>,
+}
+
+/// Scan interval of every symbol of [ArbitrageScanner::scan_periodic].
+#[derive(Debug, Clone)]
+pub struct ScanSchedule {
+    default_interval: Duration,
+    symbols: Vec<ScheduledSymbol>,
+    handle: Option<ScannerHandle>,
+    jitter: f64,
+    rng: SeededRng,
+}
+
+impl ScanSchedule {
+    /// Empty schedule; symbols added with [Self::with_symbols] are scanned every
+    /// `default_interval_ms`.
+    pub fn new(default_interval_ms: u64) -> Self {
+        Self {
+            default_interval: Duration::from_millis(default_interval_ms),
+            symbols: Vec::new(),
+            handle: None,
+            jitter: 0.0,
+            rng: SeededRng::from_entropy(),
+        }
+    }
+
+    /// Scans `symbol` every `interval_ms`; replaces its interval if it is scheduled already.
+    pub fn with_symbol(mut self, symbol: &str, interval_ms: u64) -> Self {
+        self.set_interval(symbol, Duration::from_millis(interval_ms));
+        self
+    }
+
+    /// Scans `symbols` at the default interval.
+    pub fn with_symbols(mut self, symbols: &[&str]) -> Self {
+        for symbol in symbols {
+            self.set_interval(symbol, self.default_interval);
+        }
+        self
+    }
+
+    /// Stops [ArbitrageScanner::scan_periodic] when `handle` is stopped; scans are skipped
+    /// while it is paused.
+    pub fn with_handle(mut self, handle: ScannerHandle) -> Self {
+        self.handle = Some(handle);
+        self
+    }
+
+    /// Randomizes every interval by up to ± `fraction` of it (0.1 = ±10%).
+    pub fn with_jitter(mut self, fraction: f64) -> Self {
+        self.jitter = fraction;
+        self
+    }
+
+    /// Draws the jitter from `rng` (seeded from the clock by default).
+    pub fn with_rng(mut self, rng: SeededRng) -> Self {
+        self.rng = rng;
+        self
+    }
+
+    fn set_interval(&mut self, symbol: &str, interval: Duration) {
+        match self.symbols.iter_mut().find(|s| s.symbol == symbol) {
+            Some(scheduled) => scheduled.interval = interval,
+            None => self.symbols.push(ScheduledSymbol {
+                symbol: symbol.to_string(),
+                interval,
+                next_due: None,
+            }),
+        }
+    }
+
+    /// Scheduled symbols, in the order they were added.
+    pub fn symbols(&self) -> Vec<&str> {
+        self.symbols.iter().map(|s| s.symbol.as_str()).collect()
+    }
+
+    pub fn interval(&self, symbol: &str) -> Option<Duration> {
+        self.symbols
+            .iter()
+            .find(|s| s.symbol == symbol)
+            .map(|s| s.interval)
+    }
+
+    pub fn validate(&self) -> Result<(), MarketScannerError> {
+        if self.symbols.is_empty() {
+            return Err(MarketScannerError::InvalidConfig(
+                "Scan schedule has no symbols".to_string(),
+            ));
+        }
+        if let Some(scheduled) = self.symbols.iter().find(|s| s.interval.is_zero()) {
+            return Err(MarketScannerError::InvalidConfig(format!(
+                "Scan interval of {} must be positive",
+                scheduled.symbol
+            )));
+        }
+        if !(0.0..1.0).contains(&self.jitter) {
+            return Err(MarketScannerError::InvalidConfig(format!(
+                "Scan jitter must be in [0, 1), got {}",
+                self.jitter
+            )));
+        }
+        Ok(())
+    }
+
+    /// Symbols due at `now` (every symbol on the first call), which are then scheduled one
+    /// (jittered) interval later.
+    pub fn take_due(&mut self, now: Timestamp) -> Vec<String> {
+        let mut due = Vec::new();
+        for scheduled in &mut self.symbols {
+            if scheduled.next_due.is_none_or(|next| next <= now) {
+                // Skips missed slots instead of bursting to catch up
+                let mut next = scheduled.next_due.unwrap_or(now);
+                while next <= now {
+                    let interval = self.rng.jitter(scheduled.interval, self.jitter);
+                    next = next.saturating_add(interval.max(Duration::from_millis(1)));
+                }
+                scheduled.next_due = Some(next);
+                due.push(scheduled.symbol.clone());
+            }
+        }
+        due
+    }
+
+    /// Earliest time a symbol is due (`None` before the first [Self::take_due]).
+    pub fn next_due(&self) -> Option<Timestamp> {
+        self.symbols.iter().filter_map(|s| s.next_due).min()
+    }
+}
+
+/// One scan of [ArbitrageScanner::scan_periodic].
+#[derive(Debug)]
+pub struct ScheduledScan {
+    pub symbol: String,
+    pub scanned_at: Timestamp,
+    /// Opportunities sorted by profitability, or why the scan failed
+    pub result: Result<Vec<ArbitrageOpportunity>, MarketScannerError>,
+}
+
+impl ArbitrageScanner {
+    /// REST scans ([ArbitrageScanner::scan_arbitrage_opportunities]) of every symbol of
+    /// `schedule` on `cex_exchanges`, each at its own interval, until the receiver is
+    /// dropped or the schedule's handle is stopped.
+    pub async fn scan_periodic(
+        mut schedule: ScanSchedule,
+        cex_exchanges: &[CexExchange],
+        fee_overrides: Option<&FeeOverrides>,
+    ) -> Result<mpsc::Receiver<ScheduledScan>, MarketScannerError> {
+        schedule.validate()?;
+        let cex_exchanges = cex_exchanges.to_vec();
+        let fee_overrides = fee_overrides.cloned();
+        let handle = schedule.handle.clone().unwrap_or_default();
+        let (tx, rx) = mpsc::channel(64);
+
+        symbol_registry::spawn(async move {
+            loop {
+                let due = schedule.take_due(Timestamp::now());
+                if !due.is_empty() && !handle.is_paused() {
+                    let scans = due.iter().map(|symbol| {
+                        let cex_exchanges = &cex_exchanges;
+                        let fee_overrides = fee_overrides.as_ref();
+                        async move {
+                            let result = Self::scan_arbitrage_opportunities(
+                                symbol,
+                                cex_exchanges,
+                                None,
+                                None,
+                                None,
+                                None,
+                                fee_overrides,
+                            )
+                            .await;
+                            ScheduledScan {
+                                symbol: symbol.clone(),
+                                scanned_at: Timestamp::now(),
+                                result,
+                            }
+                        }
+                    });
+                    for scan in join_all(scans).await {
+                        if tx.send(scan).await.is_err() {
+                            return;
+                        }
+                    }
+                }
+
+                let wait = schedule
+                    .next_due()
+                    .map(|next| next.duration_since(Timestamp::now()))
+                    .unwrap_or_default();
+                tokio::select! {
+                    _ = tokio::time::sleep(wait) => {}
+                    _ = handle.stopped() => return,
+                    _ = tx.closed() => return,
+                }
+            }
+        });
+        Ok(rx)
+    }
+}
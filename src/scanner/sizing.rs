@@ -0,0 +1,174 @@
+//! Opportunities with zero-size legs.
+//!
+//! During auctions and on locked books some venues quote a top-of-book quantity of 0, which
+//! makes [ArbitrageOpportunity::executable_quantity] 0 although the spread is real.
+//! [ArbitrageOpportunity::has_zero_size_leg] flags these; a [ZeroSizePolicy] keeps them,
+//! drops them or sizes them from deeper order book levels instead.
+
+use crate::common::{
+    AmountSide, CexExchange, Exchange, FeeOverrides, OrderBook, effective_price_with_overrides,
+    logical_market_key,
+};
+use crate::scanner::{ArbitrageOpportunity, ArbitrageScanner, PriceData};
+use futures::future::join_all;
+use serde::{Deserialize, Serialize};
+
+/// What to do with opportunities that have a zero-size leg.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ZeroSizePolicy {
+    /// Emit them unchanged (executable quantity 0)
+    #[default]
+    Keep,
+    /// Leave them out
+    Drop,
+    /// Fetch the order books of both legs (up to `depth` levels) and size them from the
+    /// levels that are still profitable; left out if that finds no size either
+    DepthFallback { depth: usize },
+}
+
+impl ArbitrageOpportunity {
+    /// Whether a leg quoted no quantity, leaving no executable quantity (false once the
+    /// opportunity was sized from depth).
+    pub fn has_zero_size_leg(&self) -> bool {
+        self.executable_quantity.is_nan() || self.executable_quantity <= 0.0
+    }
+}
+
+fn cex_venue(leg: &PriceData) -> Option<(CexExchange, &str)> {
+    match leg {
+        PriceData::Cex(price) => match &price.exchange {
+            Exchange::Cex(exchange) => Some((exchange.clone(), price.symbol.as_str())),
+            Exchange::Dex(_) => None,
+        },
+        PriceData::Dex(_) => None,
+    }
+}
+
+fn find_book<'a>(books: &'a [OrderBook], leg: &PriceData) -> Option<&'a OrderBook> {
+    let (exchange, symbol) = cex_venue(leg)?;
+    let venue = Exchange::Cex(exchange);
+    let market = logical_market_key(symbol, &venue);
+    books.iter().find(|book| {
+        book.exchange == venue && logical_market_key(&book.symbol, &book.exchange) == market
+    })
+}
+
+/// Quantity that can be bought on `source` and sold on `destination` while the effective
+/// bid stays above the effective ask, skipping empty levels.
+fn profitable_depth(
+    source: &OrderBook,
+    destination: &OrderBook,
+    fee_overrides: Option<&FeeOverrides>,
+) -> f64 {
+    let levels = |book: &OrderBook, side: AmountSide| -> Vec<(f64, f64)> {
+        let levels = match side {
+            AmountSide::Buy => &book.asks,
+            AmountSide::Sell => &book.bids,
+        };
+        levels
+            .iter()
+            .filter(|l| l.price > 0.0 && l.quantity > 0.0)
+            .map(|l| {
+                let effective =
+                    effective_price_with_overrides(l.price, &book.exchange, side, fee_overrides);
+                (effective, l.quantity)
+            })
+            .collect()
+    };
+    let asks = levels(source, AmountSide::Buy);
+    let bids = levels(destination, AmountSide::Sell);
+
+    let (mut ask_idx, mut bid_idx) = (0, 0);
+    let (mut ask_left, mut bid_left) = (
+        asks.first().map_or(0.0, |l| l.1),
+        bids.first().map_or(0.0, |l| l.1),
+    );
+    let mut quantity = 0.0;
+    while ask_idx < asks.len() && bid_idx < bids.len() && bids[bid_idx].0 > asks[ask_idx].0 {
+        let take = ask_left.min(bid_left);
+        quantity += take;
+        ask_left -= take;
+        bid_left -= take;
+        if ask_left <= 0.0 {
+            ask_idx += 1;
+            ask_left = asks.get(ask_idx).map_or(0.0, |l| l.1);
+        }
+        if bid_left <= 0.0 {
+            bid_idx += 1;
+            bid_left = bids.get(bid_idx).map_or(0.0, |l| l.1);
+        }
+    }
+    quantity
+}
+
+impl ArbitrageScanner {
+    /// Sizes opportunities with a zero-size leg from `books` (see
+    /// [ZeroSizePolicy::DepthFallback]) and leaves out those that still have no size.
+    /// Prices stay those of the quote; commissions are recomputed for the new quantity.
+    /// Other opportunities are returned unchanged.
+    pub fn resize_from_books(
+        opportunities: Vec<ArbitrageOpportunity>,
+        books: &[OrderBook],
+        fee_overrides: Option<&FeeOverrides>,
+    ) -> Vec<ArbitrageOpportunity> {
+        opportunities
+            .into_iter()
+            .filter_map(|mut opportunity| {
+                if !opportunity.has_zero_size_leg() {
+                    return Some(opportunity);
+                }
+                let source = find_book(books, &opportunity.source_leg)?;
+                let destination = find_book(books, &opportunity.destination_leg)?;
+                let quantity = profitable_depth(source, destination, fee_overrides);
+                if quantity <= 0.0 {
+                    return None;
+                }
+                opportunity.executable_quantity = quantity;
+                opportunity.total_commission_quote = quantity
+                    * (opportunity.effective_ask * opportunity.source_commission_percent
+                        + opportunity.effective_bid * opportunity.destination_commission_percent)
+                    / 100.0;
+                Some(opportunity)
+            })
+            .collect()
+    }
+
+    /// Applies `policy` to `opportunities`. [ZeroSizePolicy::DepthFallback] fetches the
+    /// order books of the CEX legs of zero-size opportunities only; DEX legs cannot be
+    /// resized, so such opportunities are left out.
+    pub async fn apply_zero_size_policy(
+        opportunities: Vec<ArbitrageOpportunity>,
+        policy: ZeroSizePolicy,
+        fee_overrides: Option<&FeeOverrides>,
+    ) -> Vec<ArbitrageOpportunity> {
+        match policy {
+            ZeroSizePolicy::Keep => opportunities,
+            ZeroSizePolicy::Drop => opportunities
+                .into_iter()
+                .filter(|o| !o.has_zero_size_leg())
+                .collect(),
+            ZeroSizePolicy::DepthFallback { depth } => {
+                let mut venues: Vec<(CexExchange, String)> = Vec::new();
+                for opportunity in opportunities.iter().filter(|o| o.has_zero_size_leg()) {
+                    for leg in [&opportunity.source_leg, &opportunity.destination_leg] {
+                        if let Some((exchange, symbol)) = cex_venue(leg) {
+                            let venue = (exchange, symbol.to_string());
+                            if !venues.contains(&venue) {
+                                venues.push(venue);
+                            }
+                        }
+                    }
+                }
+                let books: Vec<OrderBook> =
+                    join_all(venues.iter().map(|(exchange, symbol)| {
+                        Self::get_cex_order_book(exchange, symbol, depth)
+                    }))
+                    .await
+                    .into_iter()
+                    .filter_map(Result::ok)
+                    .collect();
+                Self::resize_from_books(opportunities, &books, fee_overrides)
+            }
+        }
+    }
+}
@@ -0,0 +1,378 @@
+//! Long-running WebSocket stability (soak) test.
+//!
+//! [ArbitrageScanner::run_ws_soak] keeps the price streams of the given venues open for
+//! hours and reports how they held up: messages, reconnects (see
+//! [VenueHealth::ws_connections]), message gaps longer than a threshold and the process's
+//! resident memory over time. [WsSoakRecorder] holds the bookkeeping so a custom harness
+//! can feed it its own streams.
+//!
+//! [VenueHealth::ws_connections]: crate::common::VenueHealth::ws_connections
+
+use crate::common::{
+    CexExchange, MarketScannerError, Timestamp, merge_labeled_price_streams, venue_health,
+};
+use crate::scanner::ArbitrageScanner;
+use futures::future::join_all;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::time::{Instant, MissedTickBehavior};
+
+/// Progress callback of [WsSoakOptions::with_progress].
+pub type SoakProgress = Arc<dyn Fn(&WsStabilityReport) + Send + Sync>;
+
+/// Settings of [ArbitrageScanner::run_ws_soak].
+#[derive(Clone)]
+pub struct WsSoakOptions {
+    duration: Duration,
+    sample_interval: Duration,
+    gap_threshold: Duration,
+    progress: Option<SoakProgress>,
+}
+
+impl fmt::Debug for WsSoakOptions {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WsSoakOptions")
+            .field("duration", &self.duration)
+            .field("sample_interval", &self.sample_interval)
+            .field("gap_threshold", &self.gap_threshold)
+            .field("progress", &self.progress.is_some())
+            .finish()
+    }
+}
+
+impl WsSoakOptions {
+    /// Soak for `duration`, sampling memory every minute and counting silences over 30 s
+    /// as gaps.
+    pub fn new(duration: Duration) -> Self {
+        Self {
+            duration,
+            sample_interval: Duration::from_secs(60),
+            gap_threshold: Duration::from_secs(30),
+            progress: None,
+        }
+    }
+
+    pub fn with_sample_interval(mut self, interval: Duration) -> Self {
+        self.sample_interval = interval;
+        self
+    }
+
+    /// Silence of a venue longer than `threshold` counts as a message gap. Quiet markets
+    /// need a higher threshold.
+    pub fn with_gap_threshold(mut self, threshold: Duration) -> Self {
+        self.gap_threshold = threshold;
+        self
+    }
+
+    /// Calls `progress` with the report so far at every sample.
+    pub fn with_progress<F>(mut self, progress: F) -> Self
+    where
+        F: Fn(&WsStabilityReport) + Send + Sync + 'static,
+    {
+        self.progress = Some(Arc::new(progress));
+        self
+    }
+
+    fn validate(&self) -> Result<(), MarketScannerError> {
+        if self.duration.is_zero() || self.sample_interval.is_zero() {
+            return Err(MarketScannerError::InvalidConfig(
+                "Soak duration and sample interval must be positive".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Stability of one venue during a soak.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct VenueStability {
+    pub exchange: CexExchange,
+    pub messages: u64,
+    /// Stream connections made during the soak (the initial one included)
+    pub connections: u64,
+    /// Silences longer than the gap threshold (a trailing one included)
+    pub gaps: u64,
+    /// Longest silence between two messages (or since the last one)
+    pub max_gap_ms: u64,
+    pub first_message_at: Option<Timestamp>,
+    pub last_message_at: Option<Timestamp>,
+    /// Why the stream could not be opened, or that it ended
+    pub error: Option<String>,
+}
+
+impl VenueStability {
+    pub fn reconnects(&self) -> u64 {
+        self.connections.saturating_sub(1)
+    }
+}
+
+/// Resident memory of the process at one point of a soak.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct MemorySample {
+    pub at: Timestamp,
+    /// `None` where the platform does not expose it (only Linux does)
+    pub resident_bytes: Option<u64>,
+}
+
+/// Outcome of a soak.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WsStabilityReport {
+    pub started_at: Timestamp,
+    pub ended_at: Timestamp,
+    pub venues: Vec<VenueStability>,
+    pub memory: Vec<MemorySample>,
+}
+
+impl WsStabilityReport {
+    /// Resident memory of the last sample minus the first.
+    pub fn memory_growth_bytes(&self) -> Option<i64> {
+        let mut known = self.memory.iter().filter_map(|s| s.resident_bytes);
+        let first = known.next()?;
+        Some(known.next_back().unwrap_or(first) as i64 - first as i64)
+    }
+
+    pub fn venue(&self, exchange: &CexExchange) -> Option<&VenueStability> {
+        self.venues.iter().find(|v| v.exchange == *exchange)
+    }
+}
+
+impl fmt::Display for WsStabilityReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let hours = self.ended_at.duration_since(self.started_at).as_secs_f64() / 3600.0;
+        writeln!(f, "WebSocket stability over {:.2} h", hours)?;
+        writeln!(
+            f,
+            "{:<10} {:>10} {:>10} {:>6} {:>12}  status",
+            "venue", "messages", "reconnects", "gaps", "max gap (s)"
+        )?;
+        for venue in &self.venues {
+            writeln!(
+                f,
+                "{:<10} {:>10} {:>10} {:>6} {:>12.1}  {}",
+                format!("{:?}", venue.exchange),
+                venue.messages,
+                venue.reconnects(),
+                venue.gaps,
+                venue.max_gap_ms as f64 / 1000.0,
+                venue.error.as_deref().unwrap_or("ok")
+            )?;
+        }
+        let mb = |bytes: u64| bytes as f64 / (1024.0 * 1024.0);
+        let known: Vec<u64> = self
+            .memory
+            .iter()
+            .filter_map(|s| s.resident_bytes)
+            .collect();
+        match (
+            known.first(),
+            known.iter().max(),
+            self.memory_growth_bytes(),
+        ) {
+            (Some(first), Some(peak), Some(growth)) => write!(
+                f,
+                "memory: {:.1} MiB at start, {:.1} MiB peak, {:+.1} MiB growth over {} samples",
+                mb(*first),
+                mb(*peak),
+                growth as f64 / (1024.0 * 1024.0),
+                known.len()
+            ),
+            _ => write!(f, "memory: not available on this platform"),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct VenueState {
+    stability: VenueStability,
+    connections_before: u64,
+}
+
+/// Bookkeeping of a soak: messages, gaps, connection counts and memory samples.
+#[derive(Debug, Clone)]
+pub struct WsSoakRecorder {
+    started_at: Timestamp,
+    gap_threshold: Duration,
+    venues: Vec<VenueState>,
+    memory: Vec<MemorySample>,
+}
+
+impl WsSoakRecorder {
+    /// Starts tracking `exchanges` at `started_at`; their connection counts so far are the
+    /// baseline.
+    pub fn new(exchanges: &[CexExchange], gap_threshold: Duration, started_at: Timestamp) -> Self {
+        Self {
+            started_at,
+            gap_threshold,
+            venues: exchanges
+                .iter()
+                .map(|exchange| VenueState {
+                    stability: VenueStability {
+                        exchange: exchange.clone(),
+                        messages: 0,
+                        connections: 0,
+                        gaps: 0,
+                        max_gap_ms: 0,
+                        first_message_at: None,
+                        last_message_at: None,
+                        error: None,
+                    },
+                    connections_before: venue_health(exchange).ws_connections,
+                })
+                .collect(),
+            memory: Vec::new(),
+        }
+    }
+
+    /// Records one message of `exchange` received at `at`.
+    pub fn record_message(&mut self, exchange: &CexExchange, at: Timestamp) {
+        let gap_threshold = self.gap_threshold;
+        let Some(venue) = self.venue_mut(exchange) else {
+            return;
+        };
+        // Connecting is not a gap, only silence once messages flowed
+        if let Some(last) = venue.last_message_at {
+            let silence = at.duration_since(last);
+            venue.max_gap_ms = venue.max_gap_ms.max(silence.as_millis() as u64);
+            if silence > gap_threshold {
+                venue.gaps += 1;
+            }
+        }
+        venue.messages += 1;
+        venue.first_message_at.get_or_insert(at);
+        venue.last_message_at = Some(at);
+    }
+
+    /// Records that the stream of `exchange` failed to open or ended.
+    pub fn record_error(&mut self, exchange: &CexExchange, error: impl Into<String>) {
+        if let Some(venue) = self.venue_mut(exchange) {
+            venue.error = Some(error.into());
+        }
+    }
+
+    /// Records the resident memory of the process at `at`.
+    pub fn sample_memory(&mut self, at: Timestamp) {
+        self.memory.push(MemorySample {
+            at,
+            resident_bytes: resident_memory_bytes(),
+        });
+    }
+
+    /// Report as of `at`; a venue silent since longer than the gap threshold gets a
+    /// trailing gap.
+    pub fn report(&self, at: Timestamp) -> WsStabilityReport {
+        let venues = self
+            .venues
+            .iter()
+            .map(|state| {
+                let mut venue = state.stability.clone();
+                venue.connections = venue_health(&venue.exchange)
+                    .ws_connections
+                    .saturating_sub(state.connections_before);
+                if let Some(last) = venue.last_message_at {
+                    let silence = at.duration_since(last);
+                    if silence > self.gap_threshold {
+                        venue.gaps += 1;
+                        venue.max_gap_ms = venue.max_gap_ms.max(silence.as_millis() as u64);
+                    }
+                }
+                venue
+            })
+            .collect();
+        WsStabilityReport {
+            started_at: self.started_at,
+            ended_at: at,
+            venues,
+            memory: self.memory.clone(),
+        }
+    }
+
+    fn venue_mut(&mut self, exchange: &CexExchange) -> Option<&mut VenueStability> {
+        self.venues
+            .iter_mut()
+            .map(|state| &mut state.stability)
+            .find(|venue| venue.exchange == *exchange)
+    }
+}
+
+/// Resident memory of this process (Linux `/proc/self/status`); `None` elsewhere.
+pub fn resident_memory_bytes() -> Option<u64> {
+    let status = std::fs::read_to_string("/proc/self/status").ok()?;
+    let line = status.lines().find(|line| line.starts_with("VmRSS:"))?;
+    let kib: u64 = line.split_whitespace().nth(1)?.parse().ok()?;
+    Some(kib * 1024)
+}
+
+impl ArbitrageScanner {
+    /// Streams `symbols` from every WebSocket venue of `exchanges` for `options`'s
+    /// duration, reconnecting without limit, and reports their stability. Venues without
+    /// WebSocket support or whose stream cannot be opened are reported with an error.
+    pub async fn run_ws_soak(
+        exchanges: &[CexExchange],
+        symbols: &[&str],
+        options: WsSoakOptions,
+    ) -> Result<WsStabilityReport, MarketScannerError> {
+        options.validate()?;
+        if symbols.is_empty() {
+            return Err(MarketScannerError::InvalidSymbol(
+                "At least one symbol required".to_string(),
+            ));
+        }
+        let mut recorder = WsSoakRecorder::new(exchanges, options.gap_threshold, Timestamp::now());
+        recorder.sample_memory(Timestamp::now());
+
+        let streams = join_all(exchanges.iter().map(|exchange| async move {
+            if !Self::exchange_supports_websocket(exchange) {
+                return (exchange.clone(), Err("no WebSocket support".to_string()));
+            }
+            let stream = Self::stream_cex_prices_websocket(exchange, symbols, u32::MAX, 1000)
+                .await
+                .map_err(|e| e.to_string());
+            (exchange.clone(), stream)
+        }))
+        .await;
+        let mut receivers = Vec::new();
+        for (exchange, stream) in streams {
+            match stream {
+                Ok(rx) => receivers.push((exchange, rx)),
+                Err(error) => recorder.record_error(&exchange, error),
+            }
+        }
+
+        let streaming: Vec<CexExchange> = receivers.iter().map(|(e, _)| e.clone()).collect();
+        let mut rx = merge_labeled_price_streams(receivers, false);
+        let deadline = tokio::time::sleep(options.duration);
+        tokio::pin!(deadline);
+        let mut samples = tokio::time::interval_at(
+            Instant::now() + options.sample_interval,
+            options.sample_interval,
+        );
+        samples.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+        loop {
+            tokio::select! {
+                _ = &mut deadline => break,
+                _ = samples.tick() => {
+                    recorder.sample_memory(Timestamp::now());
+                    if let Some(progress) = &options.progress {
+                        progress(&recorder.report(Timestamp::now()));
+                    }
+                }
+                update = rx.recv() => match update {
+                    Some(update) => recorder.record_message(&update.source, Timestamp::now()),
+                    None => {
+                        // Every stream gave up before the deadline
+                        for exchange in &streaming {
+                            recorder.record_error(exchange, "stream ended");
+                        }
+                        break;
+                    }
+                },
+            }
+        }
+
+        recorder.sample_memory(Timestamp::now());
+        Ok(recorder.report(Timestamp::now()))
+    }
+}
@@ -0,0 +1,378 @@
+//! Pluggable price sources.
+//!
+//! A [PriceSource] is anything that produces a stream of [PriceData]: a CEX WebSocket feed
+//! ([CexWebSocketSource]), a REST ticker poller ([RestPollingSource]), an on-chain pool
+//! listener ([PoolListenerSource]), a DEX aggregator poller ([AggregatorPollingSource]) or a
+//! test double ([MockPriceSource]). [ArbitrageScanner::scan_sources] starts any mix of them
+//! and matches their prices in one engine, so a new kind of feed only has to implement the
+//! trait.
+//!
+//! [ArbitrageScanner::scan_sources]: crate::scanner::ArbitrageScanner::scan_sources
+
+use crate::common::symbol_registry;
+use crate::common::{
+    AmountSpec, CexExchange, DexAggregator, DexPrice, DexSourceFilter, Exchange, MarketScannerError,
+};
+use crate::dex::chains::Token;
+use crate::dex::{PoolListenerConfig, stream_pool_prices};
+use crate::scanner::{ArbitrageScanner, PriceData};
+use async_trait::async_trait;
+use std::time::Duration;
+use tokio::sync::mpsc;
+
+const SOURCE_CHANNEL_CAPACITY: usize = 64;
+
+/// A stream of CEX or DEX prices the scanner can match.
+#[async_trait]
+pub trait PriceSource: Send {
+    /// Label for logs and errors (e.g. `ws:Binance`).
+    fn name(&self) -> String;
+
+    /// Starts the source. The stream ends when the source is exhausted or gives up; the
+    /// source stops once the receiver is dropped.
+    async fn start(self: Box<Self>) -> Result<mpsc::Receiver<PriceData>, MarketScannerError>;
+}
+
+/// Prices from a CEX WebSocket stream.
+#[derive(Debug, Clone)]
+pub struct CexWebSocketSource {
+    exchange: CexExchange,
+    symbols: Vec<String>,
+    reconnect_attempts: u32,
+    reconnect_delay_ms: u64,
+}
+
+impl CexWebSocketSource {
+    /// Source for `symbols` on `exchange`, without reconnects.
+    pub fn new(exchange: CexExchange, symbols: &[&str]) -> Self {
+        Self {
+            exchange,
+            symbols: symbols.iter().map(|s| s.to_string()).collect(),
+            reconnect_attempts: 0,
+            reconnect_delay_ms: 0,
+        }
+    }
+
+    /// Reconnects up to `attempts` times, waiting `delay_ms` before each attempt.
+    pub fn with_reconnect(mut self, attempts: u32, delay_ms: u64) -> Self {
+        self.reconnect_attempts = attempts;
+        self.reconnect_delay_ms = delay_ms;
+        self
+    }
+}
+
+#[async_trait]
+impl PriceSource for CexWebSocketSource {
+    fn name(&self) -> String {
+        format!(
+            "ws:{}",
+            ArbitrageScanner::exchange_name(&Exchange::Cex(self.exchange.clone()))
+        )
+    }
+
+    async fn start(self: Box<Self>) -> Result<mpsc::Receiver<PriceData>, MarketScannerError> {
+        if !ArbitrageScanner::exchange_supports_websocket(&self.exchange) {
+            return Err(MarketScannerError::InvalidConfig(format!(
+                "{} has no WebSocket stream",
+                self.name()
+            )));
+        }
+        let symbols: Vec<&str> = self.symbols.iter().map(String::as_str).collect();
+        let mut prices = ArbitrageScanner::stream_cex_prices_websocket(
+            &self.exchange,
+            &symbols,
+            self.reconnect_attempts,
+            self.reconnect_delay_ms,
+        )
+        .await?;
+        let (tx, rx) = mpsc::channel(SOURCE_CHANNEL_CAPACITY);
+        symbol_registry::spawn(async move {
+            while let Some(price) = prices.recv().await {
+                if tx.send(PriceData::Cex(price)).await.is_err() {
+                    return;
+                }
+            }
+        });
+        Ok(rx)
+    }
+}
+
+/// Prices from polling a CEX REST ticker every `interval`.
+///
+/// Failed polls are skipped; a permanent error ([MarketScannerError::is_retryable] false)
+/// ends the source.
+#[derive(Debug, Clone)]
+pub struct RestPollingSource {
+    exchange: CexExchange,
+    symbols: Vec<String>,
+    interval: Duration,
+}
+
+impl RestPollingSource {
+    pub fn new(exchange: CexExchange, symbols: &[&str], interval: Duration) -> Self {
+        Self {
+            exchange,
+            symbols: symbols.iter().map(|s| s.to_string()).collect(),
+            interval,
+        }
+    }
+}
+
+#[async_trait]
+impl PriceSource for RestPollingSource {
+    fn name(&self) -> String {
+        format!(
+            "rest:{}",
+            ArbitrageScanner::exchange_name(&Exchange::Cex(self.exchange.clone()))
+        )
+    }
+
+    async fn start(self: Box<Self>) -> Result<mpsc::Receiver<PriceData>, MarketScannerError> {
+        validate_interval(&self.name(), self.interval)?;
+        let (tx, rx) = mpsc::channel(SOURCE_CHANNEL_CAPACITY);
+        symbol_registry::spawn(async move {
+            let mut interval = tokio::time::interval(self.interval);
+            interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            loop {
+                tokio::select! {
+                    _ = interval.tick() => {}
+                    _ = tx.closed() => return,
+                }
+                for symbol in &self.symbols {
+                    match ArbitrageScanner::get_cex_price(&self.exchange, symbol).await {
+                        Ok(price) => {
+                            if tx.send(PriceData::Cex(price)).await.is_err() {
+                                return;
+                            }
+                        }
+                        Err(e) if !e.is_retryable() => return,
+                        Err(_) => {}
+                    }
+                }
+            }
+        });
+        Ok(rx)
+    }
+}
+
+/// Prices from polling a DEX aggregator quote for `quote_amount` every `interval`.
+///
+/// Failed polls are skipped; a permanent error ends the source.
+#[derive(Debug, Clone)]
+pub struct AggregatorPollingSource {
+    aggregator: DexAggregator,
+    base_token: Token,
+    quote_token: Token,
+    quote_amount: f64,
+    interval: Duration,
+    sources: DexSourceFilter,
+}
+
+impl AggregatorPollingSource {
+    pub fn new(
+        aggregator: DexAggregator,
+        base_token: Token,
+        quote_token: Token,
+        quote_amount: f64,
+        interval: Duration,
+    ) -> Self {
+        Self {
+            aggregator,
+            base_token,
+            quote_token,
+            quote_amount,
+            interval,
+            sources: DexSourceFilter::default(),
+        }
+    }
+
+    /// Routes the quotes only through the liquidity sources `sources` allows.
+    pub fn with_dex_sources(mut self, sources: DexSourceFilter) -> Self {
+        self.sources = sources;
+        self
+    }
+}
+
+#[async_trait]
+impl PriceSource for AggregatorPollingSource {
+    fn name(&self) -> String {
+        format!(
+            "aggregator:{}:{}{}",
+            ArbitrageScanner::exchange_name(&Exchange::Dex(self.aggregator.clone())),
+            self.base_token.symbol,
+            self.quote_token.symbol
+        )
+    }
+
+    async fn start(self: Box<Self>) -> Result<mpsc::Receiver<PriceData>, MarketScannerError> {
+        validate_interval(&self.name(), self.interval)?;
+        if !self.quote_amount.is_finite() || self.quote_amount <= 0.0 {
+            return Err(MarketScannerError::InvalidConfig(format!(
+                "{}: quote amount must be positive, got {}",
+                self.name(),
+                self.quote_amount
+            )));
+        }
+        let (tx, rx) = mpsc::channel(SOURCE_CHANNEL_CAPACITY);
+        symbol_registry::spawn(async move {
+            let mut interval = tokio::time::interval(self.interval);
+            interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            loop {
+                tokio::select! {
+                    _ = interval.tick() => {}
+                    _ = tx.closed() => return,
+                }
+                match ArbitrageScanner::get_dex_price_with_sources(
+                    &self.aggregator,
+                    &self.base_token,
+                    &self.quote_token,
+                    AmountSpec::Quote(self.quote_amount),
+                    &self.sources,
+                )
+                .await
+                {
+                    Ok(price) => {
+                        if tx.send(PriceData::Dex(price)).await.is_err() {
+                            return;
+                        }
+                    }
+                    Err(e) if !e.is_retryable() => return,
+                    Err(_) => {}
+                }
+            }
+        });
+        Ok(rx)
+    }
+}
+
+/// Spot prices of one on-chain pool (see [stream_pool_prices]).
+///
+/// Pool prices carry no depth or route: each update becomes a [DexPrice] with bid = ask =
+/// the pool price and zero quantities, reported under `venue` so the scanner applies that
+/// venue's taker fee. The config must name the pair (`symbol`) and quote it as quote per
+/// base (`base_token` / `quote_token`, or a matching `price_direction`).
+#[derive(Debug, Clone)]
+pub struct PoolListenerSource {
+    config: PoolListenerConfig,
+    venue: DexAggregator,
+}
+
+impl PoolListenerSource {
+    pub fn new(config: PoolListenerConfig, venue: DexAggregator) -> Self {
+        Self { config, venue }
+    }
+}
+
+#[async_trait]
+impl PriceSource for PoolListenerSource {
+    fn name(&self) -> String {
+        format!("pool:{}:{}", self.config.chain_id, self.config.pool_address)
+    }
+
+    async fn start(self: Box<Self>) -> Result<mpsc::Receiver<PriceData>, MarketScannerError> {
+        let Some(symbol) = self.config.symbol.clone() else {
+            return Err(MarketScannerError::InvalidConfig(format!(
+                "{}: pool listener config needs a symbol",
+                self.name()
+            )));
+        };
+        let exchange = Exchange::Dex(self.venue);
+        let mut updates = stream_pool_prices(self.config).await?;
+        let (tx, rx) = mpsc::channel(SOURCE_CHANNEL_CAPACITY);
+        symbol_registry::spawn(async move {
+            while let Some(update) = updates.recv().await {
+                let price = DexPrice {
+                    symbol: symbol.clone(),
+                    mid_price: update.price,
+                    bid_price: update.price,
+                    ask_price: update.price,
+                    bid_qty: 0.0,
+                    ask_qty: 0.0,
+                    timestamp: update.timestamp,
+                    exchange: exchange.clone(),
+                    bid_route_summary: None,
+                    ask_route_summary: None,
+                    bid_route_data: None,
+                    ask_route_data: None,
+                    transfer_tax_bps: None,
+                };
+                if tx.send(PriceData::Dex(price)).await.is_err() {
+                    return;
+                }
+            }
+        });
+        Ok(rx)
+    }
+}
+
+/// Source fed by the caller, for tests and replays.
+#[derive(Debug)]
+pub struct MockPriceSource {
+    name: String,
+    prices: mpsc::Receiver<PriceData>,
+}
+
+impl MockPriceSource {
+    /// Emits `prices` in order, then ends.
+    pub fn new(name: &str, prices: Vec<PriceData>) -> Self {
+        let (tx, rx) = mpsc::channel(prices.len().max(1));
+        for price in prices {
+            let _ = tx.try_send(price);
+        }
+        Self {
+            name: name.to_string(),
+            prices: rx,
+        }
+    }
+
+    /// Emits whatever is sent on the returned sender; ends when the sender is dropped.
+    pub fn channel(name: &str, capacity: usize) -> (Self, mpsc::Sender<PriceData>) {
+        let (tx, rx) = mpsc::channel(capacity.max(1));
+        (
+            Self {
+                name: name.to_string(),
+                prices: rx,
+            },
+            tx,
+        )
+    }
+}
+
+#[async_trait]
+impl PriceSource for MockPriceSource {
+    fn name(&self) -> String {
+        self.name.clone()
+    }
+
+    async fn start(self: Box<Self>) -> Result<mpsc::Receiver<PriceData>, MarketScannerError> {
+        Ok(self.prices)
+    }
+}
+
+fn validate_interval(name: &str, interval: Duration) -> Result<(), MarketScannerError> {
+    if interval.is_zero() {
+        return Err(MarketScannerError::InvalidConfig(format!(
+            "{}: poll interval must be positive",
+            name
+        )));
+    }
+    Ok(())
+}
+
+/// Merges the streams of started sources; ends once every stream has.
+pub(crate) fn merge_sources(
+    receivers: Vec<mpsc::Receiver<PriceData>>,
+) -> mpsc::Receiver<PriceData> {
+    let (tx, rx) = mpsc::channel(SOURCE_CHANNEL_CAPACITY);
+    for mut source in receivers {
+        let tx = tx.clone();
+        symbol_registry::spawn(async move {
+            while let Some(price) = source.recv().await {
+                if tx.send(price).await.is_err() {
+                    return;
+                }
+            }
+        });
+    }
+    rx
+}
@@ -0,0 +1,224 @@
+//! Decomposition of an opportunity's spread into its cost layers.
+//!
+//! [ArbitrageOpportunity::spread] is already net of taker fees, which hides how much of
+//! the raw price gap each cost takes. A [SpreadBreakdown] lists the layers per unit of
+//! base asset: the raw gap between destination bid and source ask, the buy and sell fees,
+//! the estimated slippage and the gas / rebalancing transfer cost, so it is plain which
+//! layer an opportunity stops surviving ([SpreadBreakdown::first_losing_layer]). Scans set
+//! the raw gap and fees of every opportunity; a [SpreadCostModel] adds slippage and
+//! transfer costs.
+
+use crate::common::Exchange;
+use crate::scanner::{ArbitrageOpportunity, PriceData, TransferCostModel};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Cost layer of a [SpreadBreakdown], in the order they are deducted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum CostLayer {
+    /// The price gap itself
+    RawSpread,
+    /// Buy and sell taker fees
+    Fees,
+    Slippage,
+    /// Gas of DEX legs and rebalancing transfers
+    Transfer,
+}
+
+/// Per-unit components of an opportunity's spread, in quote currency.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct SpreadBreakdown {
+    /// Destination bid − source ask, before any cost
+    pub raw_spread: f64,
+    /// Taker fee of the source leg (effective ask − ask)
+    pub buy_fee: f64,
+    /// Taker fee of the destination leg (bid − effective bid)
+    pub sell_fee: f64,
+    /// Estimated slippage of both legs against their quotes
+    pub slippage: f64,
+    /// Gas of DEX legs plus rebalancing transfers, spread over the executable quantity
+    pub transfer_cost: f64,
+    /// What is left after every layer
+    pub net_spread: f64,
+}
+
+impl SpreadBreakdown {
+    /// Raw gap and fees of `opportunity`, without slippage or transfer costs.
+    pub fn from_fees(opportunity: &ArbitrageOpportunity) -> Self {
+        let ask = leg_price(&opportunity.source_leg, true);
+        let bid = leg_price(&opportunity.destination_leg, false);
+        Self {
+            raw_spread: bid - ask,
+            buy_fee: opportunity.effective_ask - ask,
+            sell_fee: bid - opportunity.effective_bid,
+            slippage: 0.0,
+            transfer_cost: 0.0,
+            net_spread: opportunity.spread,
+        }
+    }
+
+    /// Spread left after fees (the opportunity's `spread`).
+    pub fn after_fees(&self) -> f64 {
+        self.raw_spread - self.buy_fee - self.sell_fee
+    }
+
+    /// Spread left after fees and slippage.
+    pub fn after_slippage(&self) -> f64 {
+        self.after_fees() - self.slippage
+    }
+
+    /// Spread left after `layer` and the layers before it.
+    pub fn after(&self, layer: CostLayer) -> f64 {
+        match layer {
+            CostLayer::RawSpread => self.raw_spread,
+            CostLayer::Fees => self.after_fees(),
+            CostLayer::Slippage => self.after_slippage(),
+            CostLayer::Transfer => self.net_spread,
+        }
+    }
+
+    /// First layer after which nothing is left; `None` if the opportunity survives all.
+    pub fn first_losing_layer(&self) -> Option<CostLayer> {
+        [
+            CostLayer::RawSpread,
+            CostLayer::Fees,
+            CostLayer::Slippage,
+            CostLayer::Transfer,
+        ]
+        .into_iter()
+        .find(|layer| self.after(*layer) <= 0.0)
+    }
+}
+
+/// Slippage and transfer cost estimates completing the [SpreadBreakdown] of opportunities.
+#[derive(Debug, Clone)]
+pub struct SpreadCostModel {
+    cex_slippage_bps: f64,
+    dex_slippage_bps: f64,
+    venue_slippage_bps: HashMap<Exchange, f64>,
+    include_gas: bool,
+    transfer_costs: Option<TransferCostModel>,
+}
+
+impl Default for SpreadCostModel {
+    fn default() -> Self {
+        Self {
+            cex_slippage_bps: 0.0,
+            dex_slippage_bps: 0.0,
+            venue_slippage_bps: HashMap::new(),
+            include_gas: true,
+            transfer_costs: None,
+        }
+    }
+}
+
+impl SpreadCostModel {
+    /// No slippage, gas of DEX legs from their route summaries, no transfers.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Expected slippage of CEX legs, in basis points of the quoted price.
+    pub fn with_cex_slippage_bps(mut self, bps: f64) -> Self {
+        self.cex_slippage_bps = bps;
+        self
+    }
+
+    /// Expected slippage of DEX legs, in basis points of the quoted price.
+    pub fn with_dex_slippage_bps(mut self, bps: f64) -> Self {
+        self.dex_slippage_bps = bps;
+        self
+    }
+
+    /// Expected slippage of legs on `exchange`, replacing the CEX / DEX default.
+    pub fn with_venue_slippage_bps(mut self, exchange: Exchange, bps: f64) -> Self {
+        self.venue_slippage_bps.insert(exchange, bps);
+        self
+    }
+
+    /// Leaves the gas of DEX legs out of the transfer cost.
+    pub fn without_gas(mut self) -> Self {
+        self.include_gas = false;
+        self
+    }
+
+    /// Adds the cheapest rebalancing transfers of each opportunity to the transfer cost.
+    pub fn with_transfer_costs(mut self, model: TransferCostModel) -> Self {
+        self.transfer_costs = Some(model);
+        self
+    }
+
+    /// Full breakdown of `opportunity`. Gas (route `gas_usd`, taken as quote currency) and
+    /// transfer fees are spread over the executable quantity and left out when it is 0;
+    /// transfers are left out when an asset has no common network.
+    pub fn breakdown(&self, opportunity: &ArbitrageOpportunity) -> SpreadBreakdown {
+        let mut breakdown = SpreadBreakdown::from_fees(opportunity);
+        let ask = leg_price(&opportunity.source_leg, true);
+        let bid = leg_price(&opportunity.destination_leg, false);
+        breakdown.slippage = ask * self.slippage_bps(&opportunity.source_leg) / 10_000.0
+            + bid * self.slippage_bps(&opportunity.destination_leg) / 10_000.0;
+
+        let quantity = opportunity.executable_quantity;
+        if quantity > 0.0 {
+            let mut total_quote = 0.0;
+            if self.include_gas {
+                total_quote += gas_quote(&opportunity.source_leg, true)
+                    + gas_quote(&opportunity.destination_leg, false);
+            }
+            if let Some(costs) = self
+                .transfer_costs
+                .as_ref()
+                .and_then(|model| model.transfer_costs(opportunity))
+            {
+                total_quote += costs.total_quote();
+            }
+            breakdown.transfer_cost = total_quote / quantity;
+        }
+        breakdown.net_spread = breakdown.after_slippage() - breakdown.transfer_cost;
+        breakdown
+    }
+
+    /// Sets [ArbitrageOpportunity::spread_breakdown] of `opportunity`.
+    pub fn annotate(&self, opportunity: &mut ArbitrageOpportunity) {
+        opportunity.spread_breakdown = Some(self.breakdown(opportunity));
+    }
+
+    pub fn annotate_all(&self, opportunities: &mut [ArbitrageOpportunity]) {
+        for opportunity in opportunities {
+            self.annotate(opportunity);
+        }
+    }
+
+    fn slippage_bps(&self, leg: &PriceData) -> f64 {
+        let (exchange, default) = match leg {
+            PriceData::Cex(p) => (&p.exchange, self.cex_slippage_bps),
+            PriceData::Dex(p) => (&p.exchange, self.dex_slippage_bps),
+        };
+        self.venue_slippage_bps
+            .get(exchange)
+            .copied()
+            .unwrap_or(default)
+    }
+}
+
+fn leg_price(leg: &PriceData, ask: bool) -> f64 {
+    match (leg, ask) {
+        (PriceData::Cex(p), true) => p.ask_price,
+        (PriceData::Cex(p), false) => p.bid_price,
+        (PriceData::Dex(p), true) => p.ask_price,
+        (PriceData::Dex(p), false) => p.bid_price,
+    }
+}
+
+/// Gas of the route a DEX leg trades through (the ask route when buying).
+fn gas_quote(leg: &PriceData, buy: bool) -> f64 {
+    let PriceData::Dex(price) = leg else {
+        return 0.0;
+    };
+    let route = if buy {
+        price.ask_route_summary.as_ref()
+    } else {
+        price.bid_route_summary.as_ref()
+    };
+    route.and_then(|r| r.gas_usd).unwrap_or(0.0)
+}
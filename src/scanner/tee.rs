@@ -0,0 +1,180 @@
+//! Fan-out of one scanner stream to several consumers.
+//!
+//! Scanner streams ([ArbitrageScanner::scan_arbitrage_from_websockets],
+//! [ArbitrageScanner::scan_live], ...) are `mpsc` receivers with a single consumer. A
+//! [StreamTee] takes one over and copies every item to any number of subscribers (alerting,
+//! persistence, execution), each with its own channel capacity and [BackpressurePolicy]: a
+//! slow database writer can drop snapshots without delaying the execution engine. Subscribers
+//! can join while the stream runs and receive the items that arrive after they subscribed.
+//!
+//! [ArbitrageScanner::scan_arbitrage_from_websockets]: crate::scanner::ArbitrageScanner::scan_arbitrage_from_websockets
+//! [ArbitrageScanner::scan_live]: crate::scanner::ArbitrageScanner::scan_live
+
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, Mutex};
+use tokio::sync::mpsc;
+use tokio::sync::mpsc::error::TrySendError;
+
+/// What a subscriber's channel does when it is full.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BackpressurePolicy {
+    /// Wait for room; a slow subscriber slows the whole tee (and every other subscriber)
+    #[default]
+    Block,
+    /// Skip the item for this subscriber only and count it as dropped
+    DropNewest,
+    /// Unsubscribe the subscriber; its receiver ends after the items already queued
+    Disconnect,
+}
+
+/// Delivery counters of one subscriber of a [StreamTee].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SubscriberStats {
+    pub name: String,
+    pub policy: BackpressurePolicy,
+    pub delivered: u64,
+    /// Items skipped under [BackpressurePolicy::DropNewest]
+    pub dropped: u64,
+    /// The receiver was dropped or the subscriber was disconnected for falling behind
+    pub disconnected: bool,
+}
+
+struct Subscriber<T> {
+    id: u64,
+    tx: Option<mpsc::Sender<T>>,
+    stats: SubscriberStats,
+}
+
+struct TeeState<T> {
+    subscribers: Vec<Subscriber<T>>,
+    next_id: u64,
+    /// The source ended; new subscribers get a closed receiver
+    finished: bool,
+}
+
+/// Copies the items of one `mpsc` stream to every subscriber. Clones share the tee.
+pub struct StreamTee<T> {
+    state: Arc<Mutex<TeeState<T>>>,
+}
+
+impl<T> Clone for StreamTee<T> {
+    fn clone(&self) -> Self {
+        Self {
+            state: self.state.clone(),
+        }
+    }
+}
+
+enum Delivery {
+    Delivered,
+    Dropped,
+    Disconnected,
+}
+
+impl<T: Clone + Send + 'static> StreamTee<T> {
+    /// Starts copying `source` to the subscribers. Items that arrive while there are no
+    /// subscribers are discarded; every subscriber's receiver ends when `source` does.
+    pub fn spawn(mut source: mpsc::Receiver<T>) -> Self {
+        let tee = Self {
+            state: Arc::new(Mutex::new(TeeState {
+                subscribers: Vec::new(),
+                next_id: 0,
+                finished: false,
+            })),
+        };
+        let state = tee.state.clone();
+        tokio::spawn(async move {
+            while let Some(item) = source.recv().await {
+                let targets: Vec<(u64, mpsc::Sender<T>, BackpressurePolicy)> = state
+                    .lock()
+                    .unwrap_or_else(|e| e.into_inner())
+                    .subscribers
+                    .iter()
+                    .filter_map(|s| Some((s.id, s.tx.clone()?, s.stats.policy)))
+                    .collect();
+                let mut outcomes = Vec::with_capacity(targets.len());
+                for (id, tx, policy) in targets {
+                    outcomes.push((id, deliver(&tx, item.clone(), policy).await));
+                }
+
+                let mut state = state.lock().unwrap_or_else(|e| e.into_inner());
+                for (id, outcome) in outcomes {
+                    let Some(subscriber) = state.subscribers.iter_mut().find(|s| s.id == id) else {
+                        continue;
+                    };
+                    match outcome {
+                        Delivery::Delivered => subscriber.stats.delivered += 1,
+                        Delivery::Dropped => subscriber.stats.dropped += 1,
+                        Delivery::Disconnected => {
+                            subscriber.tx = None;
+                            subscriber.stats.disconnected = true;
+                        }
+                    }
+                }
+            }
+            let mut state = state.lock().unwrap_or_else(|e| e.into_inner());
+            state.finished = true;
+            for subscriber in &mut state.subscribers {
+                subscriber.tx = None;
+            }
+        });
+        tee
+    }
+
+    /// New subscriber named `name` with a channel of `capacity` items (at least 1),
+    /// handled by `policy` when full.
+    pub fn subscribe(
+        &self,
+        name: &str,
+        capacity: usize,
+        policy: BackpressurePolicy,
+    ) -> mpsc::Receiver<T> {
+        let (tx, rx) = mpsc::channel(capacity.max(1));
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        let id = state.next_id;
+        state.next_id += 1;
+        let finished = state.finished;
+        state.subscribers.push(Subscriber {
+            id,
+            tx: (!finished).then_some(tx),
+            stats: SubscriberStats {
+                name: name.to_string(),
+                policy,
+                delivered: 0,
+                dropped: 0,
+                disconnected: finished,
+            },
+        });
+        rx
+    }
+
+    /// Counters of every subscriber, in subscription order.
+    pub fn stats(&self) -> Vec<SubscriberStats> {
+        let state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        state.subscribers.iter().map(|s| s.stats.clone()).collect()
+    }
+
+    /// Subscribers still receiving items.
+    pub fn active_subscribers(&self) -> usize {
+        let state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        state.subscribers.iter().filter(|s| s.tx.is_some()).count()
+    }
+}
+
+async fn deliver<T>(tx: &mpsc::Sender<T>, item: T, policy: BackpressurePolicy) -> Delivery {
+    match policy {
+        BackpressurePolicy::Block => match tx.send(item).await {
+            Ok(()) => Delivery::Delivered,
+            Err(_) => Delivery::Disconnected,
+        },
+        BackpressurePolicy::DropNewest => match tx.try_send(item) {
+            Ok(()) => Delivery::Delivered,
+            Err(TrySendError::Full(_)) => Delivery::Dropped,
+            Err(TrySendError::Closed(_)) => Delivery::Disconnected,
+        },
+        BackpressurePolicy::Disconnect => match tx.try_send(item) {
+            Ok(()) => Delivery::Delivered,
+            Err(_) => Delivery::Disconnected,
+        },
+    }
+}
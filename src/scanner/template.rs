@@ -0,0 +1,231 @@
+//! Text templates for opportunity alerts.
+//!
+//! An [AlertTemplate] is a message with handlebars-style placeholders for the fields of an
+//! [ArbitrageOpportunity] (`{{symbol}}`, `{{spread_percentage}}`, `{{source_leg.ask_price}}`,
+//! ...), so Telegram, Discord or webhook messages can be changed in configuration instead
+//! of code. Placeholders name fields of the serialized opportunity, plus `total_profit` and,
+//! in sinks, `context` (the [crate::scanner::ScannerContext] name). Missing fields render
+//! empty. Helpers: `{{round <field> <decimals>}}`, `{{upper <field>}}` and
+//! `{{lower <field>}}`; `\{{` writes a literal `{{`. With
+//! [AlertTemplate::with_precision], numbers are rounded per field class before rendering.
+
+use crate::common::MarketScannerError;
+use crate::scanner::{ArbitrageOpportunity, OutputPrecision};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fmt;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, PartialEq)]
+enum Helper {
+    Raw,
+    Round(usize),
+    Upper,
+    Lower,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Segment {
+    Text(String),
+    Field { path: String, helper: Helper },
+}
+
+/// Parsed alert message template; (de)serializes as its source text (without the
+/// precision).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct AlertTemplate {
+    source: String,
+    segments: Vec<Segment>,
+    precision: Option<OutputPrecision>,
+}
+
+impl AlertTemplate {
+    /// Parses `source`; fails on unclosed or empty placeholders and unknown helpers.
+    pub fn parse(source: &str) -> Result<Self, MarketScannerError> {
+        let mut segments = Vec::new();
+        let mut text = String::new();
+        let mut rest = source;
+        while let Some(start) = rest.find("{{") {
+            if rest[..start].ends_with('\\') {
+                text.push_str(&rest[..start - 1]);
+                text.push_str("{{");
+                rest = &rest[start + 2..];
+                continue;
+            }
+            text.push_str(&rest[..start]);
+            let end = rest[start..].find("}}").ok_or_else(|| {
+                MarketScannerError::InvalidConfig(format!(
+                    "Unclosed placeholder in alert template: {}",
+                    &rest[start..]
+                ))
+            })?;
+            let placeholder = &rest[start + 2..start + end];
+            if !text.is_empty() {
+                segments.push(Segment::Text(std::mem::take(&mut text)));
+            }
+            segments.push(parse_placeholder(placeholder)?);
+            rest = &rest[start + end + 2..];
+        }
+        text.push_str(rest);
+        if !text.is_empty() {
+            segments.push(Segment::Text(text));
+        }
+        Ok(Self {
+            source: source.to_string(),
+            segments,
+            precision: None,
+        })
+    }
+
+    /// Rounds numbers of rendered opportunities with `precision`; `{{round ...}}` still
+    /// applies on top.
+    pub fn with_precision(mut self, precision: OutputPrecision) -> Self {
+        self.precision = Some(precision);
+        self
+    }
+
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    /// Field paths referenced by the template, in order.
+    pub fn fields(&self) -> impl Iterator<Item = &str> {
+        self.segments.iter().filter_map(|segment| match segment {
+            Segment::Field { path, .. } => Some(path.as_str()),
+            Segment::Text(_) => None,
+        })
+    }
+
+    /// Renders the template for `opportunity`.
+    pub fn render(&self, opportunity: &ArbitrageOpportunity) -> String {
+        self.render_value(&self.value(opportunity, None))
+    }
+
+    /// Sink for [crate::scanner::ScannerContext::with_sink] calling `send` with one rendered
+    /// message per opportunity; `{{context}}` is the context name.
+    pub fn sink<F>(self, send: F) -> impl Fn(&str, &[ArbitrageOpportunity]) + Send + Sync + 'static
+    where
+        F: Fn(String) + Send + Sync + 'static,
+    {
+        move |context, opportunities| {
+            for opportunity in opportunities {
+                send(self.render_value(&self.value(opportunity, Some(context))));
+            }
+        }
+    }
+
+    fn value(&self, opportunity: &ArbitrageOpportunity, context: Option<&str>) -> Value {
+        let mut value = opportunity_value(opportunity, context);
+        if let Some(precision) = &self.precision {
+            precision.apply(&mut value);
+        }
+        value
+    }
+
+    fn render_value(&self, value: &Value) -> String {
+        let mut out = String::new();
+        for segment in &self.segments {
+            match segment {
+                Segment::Text(text) => out.push_str(text),
+                Segment::Field { path, helper } => {
+                    let field = path
+                        .split('.')
+                        .try_fold(value, |value, key| value.get(key))
+                        .unwrap_or(&Value::Null);
+                    out.push_str(&format_field(field, helper));
+                }
+            }
+        }
+        out
+    }
+}
+
+impl FromStr for AlertTemplate {
+    type Err = MarketScannerError;
+
+    fn from_str(source: &str) -> Result<Self, Self::Err> {
+        Self::parse(source)
+    }
+}
+
+impl TryFrom<String> for AlertTemplate {
+    type Error = MarketScannerError;
+
+    fn try_from(source: String) -> Result<Self, Self::Error> {
+        Self::parse(&source)
+    }
+}
+
+impl From<AlertTemplate> for String {
+    fn from(template: AlertTemplate) -> Self {
+        template.source
+    }
+}
+
+impl fmt::Display for AlertTemplate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.source)
+    }
+}
+
+fn parse_placeholder(placeholder: &str) -> Result<Segment, MarketScannerError> {
+    let invalid = || {
+        MarketScannerError::InvalidConfig(format!(
+            "Invalid alert template placeholder: {{{{{}}}}}",
+            placeholder
+        ))
+    };
+    let words: Vec<&str> = placeholder.split_whitespace().collect();
+    let (path, helper) = match words.as_slice() {
+        [path] => (*path, Helper::Raw),
+        ["upper", path] => (*path, Helper::Upper),
+        ["lower", path] => (*path, Helper::Lower),
+        ["round", path, decimals] => (
+            *path,
+            Helper::Round(decimals.parse().map_err(|_| invalid())?),
+        ),
+        _ => return Err(invalid()),
+    };
+    Ok(Segment::Field {
+        path: path.to_string(),
+        helper,
+    })
+}
+
+fn opportunity_value(opportunity: &ArbitrageOpportunity, context: Option<&str>) -> Value {
+    let mut value = serde_json::to_value(opportunity).unwrap_or(Value::Null);
+    if let Value::Object(fields) = &mut value {
+        fields.insert(
+            "total_profit".to_string(),
+            opportunity.total_profit().into(),
+        );
+        if let Some(context) = context {
+            fields.insert("context".to_string(), context.into());
+        }
+    }
+    value
+}
+
+fn format_field(value: &Value, helper: &Helper) -> String {
+    let text = match value {
+        Value::Null => String::new(),
+        Value::String(s) => s.clone(),
+        // `f64` display: 100 rather than JSON's 100.0
+        Value::Number(n) => n
+            .as_i64()
+            .map(|n| n.to_string())
+            .or_else(|| n.as_f64().map(|n| n.to_string()))
+            .unwrap_or_else(|| n.to_string()),
+        other => other.to_string(),
+    };
+    match helper {
+        Helper::Raw => text,
+        Helper::Upper => text.to_uppercase(),
+        Helper::Lower => text.to_lowercase(),
+        Helper::Round(decimals) => match text.parse::<f64>() {
+            Ok(number) => format!("{:.*}", decimals, number),
+            Err(_) => text,
+        },
+    }
+}
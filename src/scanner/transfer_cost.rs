@@ -0,0 +1,251 @@
+//! Per-network transfer costs used to compute net opportunity economics.
+//!
+//! The same asset usually moves over several networks with very different fees and arrival
+//! times (USDT on TRC20 vs ERC20 vs BEP20). [TransferCostModel] holds a table of networks
+//! per asset and which networks each venue supports, picks the cheapest network both venues
+//! of a transfer have in common, and prices the rebalancing transfers of an opportunity
+//! (base from the source to the destination venue, quote back) so profit can be compared
+//! net of them.
+
+use crate::common::{Exchange, MarketKey, logical_market_key};
+use crate::scanner::transfer_status::TransferStatusMonitor;
+use crate::scanner::{ArbitrageOpportunity, PriceData};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Typical withdrawal fee (in units of the asset) and arrival time in seconds per network.
+const DEFAULT_NETWORKS: &[(&str, &str, f64, u64)] = &[
+    ("USDT", "TRC20", 1.0, 180),
+    ("USDT", "ERC20", 3.0, 600),
+    ("USDT", "BEP20", 0.5, 120),
+    ("USDT", "SOL", 1.0, 60),
+    ("USDT", "ARBITRUM", 0.8, 300),
+    ("USDC", "ERC20", 3.0, 600),
+    ("USDC", "BEP20", 0.5, 120),
+    ("USDC", "SOL", 1.0, 60),
+    ("USDC", "ARBITRUM", 0.8, 300),
+    ("BTC", "BTC", 0.0002, 3600),
+    ("ETH", "ERC20", 0.001, 600),
+    ("ETH", "ARBITRUM", 0.0001, 300),
+    ("ETH", "BASE", 0.0001, 300),
+    ("BNB", "BEP20", 0.0005, 120),
+    ("SOL", "SOL", 0.01, 60),
+    ("XRP", "XRP", 0.2, 120),
+];
+
+/// One network an asset can be transferred over.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TransferNetwork {
+    /// Network name, uppercase (e.g. "TRC20")
+    pub network: String,
+    /// Withdrawal fee in units of the asset
+    pub fee: f64,
+    /// Typical time until the transfer is credited
+    pub transfer_secs: u64,
+}
+
+/// Cheapest transfer of an asset between two venues.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TransferRoute {
+    pub asset: String,
+    pub network: String,
+    /// Withdrawal fee in units of the asset
+    pub fee: f64,
+    /// Withdrawal fee in the quote currency of the opportunity
+    pub fee_quote: f64,
+    pub transfer_secs: u64,
+}
+
+/// Rebalancing transfers of an opportunity and their cost.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TransferCosts {
+    /// Base asset from the source venue (where it was bought) to the destination venue
+    pub base: TransferRoute,
+    /// Quote asset from the destination venue (where it was received) to the source venue
+    pub quote: TransferRoute,
+}
+
+impl TransferCosts {
+    /// Fees of both transfers in the quote currency.
+    pub fn total_quote(&self) -> f64 {
+        self.base.fee_quote + self.quote.fee_quote
+    }
+
+    /// Time until both transfers are credited.
+    pub fn transfer_secs(&self) -> u64 {
+        self.base.transfer_secs.max(self.quote.transfer_secs)
+    }
+}
+
+/// Networks per asset plus the networks each venue supports. Venues without declared
+/// networks are assumed to support all of them; DEX venues always do.
+#[derive(Debug, Clone)]
+pub struct TransferCostModel {
+    networks: HashMap<String, Vec<TransferNetwork>>,
+    venue_networks: HashMap<(Exchange, String), Vec<String>>,
+    transfer_status: Option<TransferStatusMonitor>,
+}
+
+impl Default for TransferCostModel {
+    fn default() -> Self {
+        let mut model = Self {
+            networks: HashMap::new(),
+            venue_networks: HashMap::new(),
+            transfer_status: None,
+        };
+        for (asset, network, fee, secs) in DEFAULT_NETWORKS {
+            model = model.with_network(asset, network, *fee, *secs);
+        }
+        model
+    }
+}
+
+impl TransferCostModel {
+    /// Adds (or replaces) `network` of `asset` with its withdrawal `fee` (in units of the
+    /// asset) and typical arrival time.
+    pub fn with_network(
+        mut self,
+        asset: &str,
+        network: &str,
+        fee: f64,
+        transfer_secs: u64,
+    ) -> Self {
+        let network = network.to_uppercase();
+        let networks = self.networks.entry(asset.to_uppercase()).or_default();
+        networks.retain(|n| n.network != network);
+        networks.push(TransferNetwork {
+            network,
+            fee,
+            transfer_secs,
+        });
+        self
+    }
+
+    /// Declares the networks `exchange` supports for `asset`.
+    pub fn with_venue_networks(
+        mut self,
+        exchange: Exchange,
+        asset: &str,
+        networks: &[&str],
+    ) -> Self {
+        self.venue_networks.insert(
+            (exchange, asset.to_uppercase()),
+            networks.iter().map(|n| n.to_uppercase()).collect(),
+        );
+        self
+    }
+
+    /// Skips networks whose withdrawals (sending venue) or deposits (receiving venue) are
+    /// suspended according to `monitor`.
+    pub fn with_transfer_status(mut self, monitor: TransferStatusMonitor) -> Self {
+        self.transfer_status = Some(monitor);
+        self
+    }
+
+    /// Known networks of `asset`.
+    pub fn networks(&self, asset: &str) -> &[TransferNetwork] {
+        self.networks
+            .get(&asset.to_uppercase())
+            .map_or(&[], Vec::as_slice)
+    }
+
+    /// Cheapest network (lowest fee, then fastest) that both `from` and `to` support for
+    /// `asset` and that is not suspended. `None` if they have no network in common.
+    pub fn cheapest_network(
+        &self,
+        asset: &str,
+        from: &Exchange,
+        to: &Exchange,
+    ) -> Option<&TransferNetwork> {
+        self.networks(asset)
+            .iter()
+            .filter(|n| {
+                self.supports(from, asset, &n.network) && self.supports(to, asset, &n.network)
+            })
+            .filter(|n| self.network_open(asset, &n.network, from, to))
+            .min_by(|a, b| {
+                a.fee
+                    .total_cmp(&b.fee)
+                    .then(a.transfer_secs.cmp(&b.transfer_secs))
+            })
+    }
+
+    /// Cheapest rebalancing transfers of `opportunity`: base from the source to the
+    /// destination venue, quote back. `None` if an asset has no common network.
+    pub fn transfer_costs(&self, opportunity: &ArbitrageOpportunity) -> Option<TransferCosts> {
+        let (source, symbol) = leg_venue(&opportunity.source_leg);
+        let (destination, _) = leg_venue(&opportunity.destination_leg);
+        let MarketKey { base, quote } = logical_market_key(symbol, source);
+
+        let base_network = self.cheapest_network(&base, source, destination)?;
+        let quote_network = self.cheapest_network(&quote, destination, source)?;
+        Some(TransferCosts {
+            base: TransferRoute {
+                fee_quote: base_network.fee * opportunity.effective_ask,
+                asset: base,
+                network: base_network.network.clone(),
+                fee: base_network.fee,
+                transfer_secs: base_network.transfer_secs,
+            },
+            quote: TransferRoute {
+                fee_quote: quote_network.fee,
+                asset: quote,
+                network: quote_network.network.clone(),
+                fee: quote_network.fee,
+                transfer_secs: quote_network.transfer_secs,
+            },
+        })
+    }
+
+    /// Total profit of `opportunity` minus its cheapest rebalancing transfers; `None` if
+    /// an asset has no common network.
+    pub fn net_profit(&self, opportunity: &ArbitrageOpportunity) -> Option<f64> {
+        self.transfer_costs(opportunity)
+            .map(|costs| opportunity.total_profit() - costs.total_quote())
+    }
+
+    fn supports(&self, exchange: &Exchange, asset: &str, network: &str) -> bool {
+        match exchange {
+            Exchange::Cex(_) => self
+                .venue_networks
+                .get(&(exchange.clone(), asset.to_uppercase()))
+                .is_none_or(|networks| networks.iter().any(|n| n == network)),
+            Exchange::Dex(_) => true,
+        }
+    }
+
+    /// Whether withdrawals on `from` and deposits on `to` are open for `network`. Uses the
+    /// per-network status when the venue reports the network, the asset status otherwise.
+    fn network_open(&self, asset: &str, network: &str, from: &Exchange, to: &Exchange) -> bool {
+        let Some(monitor) = &self.transfer_status else {
+            return true;
+        };
+        let open = |exchange: &Exchange, withdraw: bool| -> bool {
+            let Exchange::Cex(cex) = exchange else {
+                return true;
+            };
+            let Some(status) = monitor.status(cex, asset) else {
+                return true;
+            };
+            // Venues name networks differently ("TRC20", "trc20usdt")
+            let reported = status
+                .networks
+                .iter()
+                .find(|n| n.network.to_uppercase().contains(network));
+            match (reported, withdraw) {
+                (Some(n), true) => n.withdrawals_enabled,
+                (Some(n), false) => n.deposits_enabled,
+                (None, true) => status.withdrawals_enabled,
+                (None, false) => status.deposits_enabled,
+            }
+        };
+        open(from, true) && open(to, false)
+    }
+}
+
+fn leg_venue(leg: &PriceData) -> (&Exchange, &str) {
+    match leg {
+        PriceData::Cex(p) => (&p.exchange, p.symbol.as_str()),
+        PriceData::Dex(p) => (&p.exchange, p.symbol.as_str()),
+    }
+}
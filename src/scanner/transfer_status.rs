@@ -0,0 +1,241 @@
+//! Deposit / withdrawal status per asset and venue.
+//!
+//! Venues suspend deposits or withdrawals of single assets (wallet maintenance, network
+//! upgrades, delistings), which makes an opportunity that needs inventory moved unviable.
+//! [TransferStatusMonitor] keeps the last known status per (venue, asset), refreshed from
+//! the public currency endpoints of the venues that publish one (KuCoin, Gate.io, HTX,
+//! Bitget) or fed by the user, e.g. from authenticated endpoints. A
+//! [crate::scanner::FundingModel] with a monitor classifies such opportunities as
+//! [crate::scanner::OpportunityViability::TransfersSuspended].
+
+use crate::common::{CexExchange, Exchange, MarketScannerError, Timestamp, create_http_client};
+use futures::future::join_all;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+
+const KUCOIN_CURRENCY_URL: &str = "https://api.kucoin.com/api/v3/currencies";
+const GATEIO_CURRENCY_URL: &str = "https://api.gateio.ws/api/v4/spot/currencies";
+const HTX_CURRENCY_URL: &str = "https://api.huobi.pro/v2/reference/currencies";
+const BITGET_COINS_URL: &str = "https://api.bitget.com/api/v2/spot/public/coins";
+
+/// Deposit / withdrawal status of an asset on one network of a venue.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NetworkTransferStatus {
+    /// Network name as reported by the venue (e.g. "TRC20", "trc20usdt")
+    pub network: String,
+    pub deposits_enabled: bool,
+    pub withdrawals_enabled: bool,
+}
+
+/// Deposit / withdrawal status of an asset on a venue.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AssetTransferStatus {
+    /// Deposits are enabled on at least one network
+    pub deposits_enabled: bool,
+    /// Withdrawals are enabled on at least one network
+    pub withdrawals_enabled: bool,
+    /// Per-network status, when the venue reports it
+    pub networks: Vec<NetworkTransferStatus>,
+    pub checked_at: Timestamp,
+}
+
+impl AssetTransferStatus {
+    pub fn new(deposits_enabled: bool, withdrawals_enabled: bool) -> Self {
+        Self {
+            deposits_enabled,
+            withdrawals_enabled,
+            networks: Vec::new(),
+            checked_at: Timestamp::now(),
+        }
+    }
+
+    /// Status from per-network statuses: a direction is enabled if any network allows it.
+    pub fn from_networks(networks: Vec<NetworkTransferStatus>) -> Self {
+        Self {
+            deposits_enabled: networks.iter().any(|n| n.deposits_enabled),
+            withdrawals_enabled: networks.iter().any(|n| n.withdrawals_enabled),
+            networks,
+            checked_at: Timestamp::now(),
+        }
+    }
+}
+
+/// Tracks deposit / withdrawal status per (venue, asset). Cheap to clone; clones share the
+/// same state. Unknown statuses (never checked, no public endpoint, DEX venues) count as
+/// enabled.
+#[derive(Debug, Clone, Default)]
+pub struct TransferStatusMonitor {
+    statuses: Arc<RwLock<HashMap<(CexExchange, String), AssetTransferStatus>>>,
+}
+
+impl TransferStatusMonitor {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records `status` of `asset` on `exchange`, e.g. from an authenticated endpoint.
+    pub fn set_status(&self, exchange: &CexExchange, asset: &str, status: AssetTransferStatus) {
+        self.statuses
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert((exchange.clone(), asset.to_uppercase()), status);
+    }
+
+    /// Last known status of `asset` on `exchange`.
+    pub fn status(&self, exchange: &CexExchange, asset: &str) -> Option<AssetTransferStatus> {
+        self.statuses
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(&(exchange.clone(), asset.to_uppercase()))
+            .cloned()
+    }
+
+    /// Whether `asset` can currently be deposited on `exchange` (true when unknown).
+    pub fn deposits_enabled(&self, exchange: &Exchange, asset: &str) -> bool {
+        match exchange {
+            Exchange::Cex(cex) => self
+                .status(cex, asset)
+                .is_none_or(|status| status.deposits_enabled),
+            Exchange::Dex(_) => true,
+        }
+    }
+
+    /// Whether `asset` can currently be withdrawn from `exchange` (true when unknown).
+    pub fn withdrawals_enabled(&self, exchange: &Exchange, asset: &str) -> bool {
+        match exchange {
+            Exchange::Cex(cex) => self
+                .status(cex, asset)
+                .is_none_or(|status| status.withdrawals_enabled),
+            Exchange::Dex(_) => true,
+        }
+    }
+
+    /// Fetches the status of every asset of `assets` on every venue of `exchanges`
+    /// concurrently and records it. Venues without a public currency endpoint are skipped;
+    /// failed fetches keep the previous status and are returned with their error.
+    pub async fn refresh(
+        &self,
+        exchanges: &[CexExchange],
+        assets: &[&str],
+    ) -> Vec<(CexExchange, String, String)> {
+        let client = create_http_client();
+        let pairs: Vec<(&CexExchange, &str)> = exchanges
+            .iter()
+            .flat_map(|exchange| assets.iter().map(move |asset| (exchange, *asset)))
+            .collect();
+        let results = join_all(pairs.into_iter().map(|(exchange, asset)| {
+            let client = &client;
+            async move {
+                let result = fetch_asset_transfer_status(client, exchange, asset).await;
+                (exchange, asset, result)
+            }
+        }))
+        .await;
+
+        let mut failures = Vec::new();
+        for (exchange, asset, result) in results {
+            match result {
+                Ok(Some(status)) => self.set_status(exchange, asset, status),
+                Ok(None) => {}
+                Err(e) => failures.push((exchange.clone(), asset.to_uppercase(), e.to_string())),
+            }
+        }
+        failures
+    }
+}
+
+/// Fetches the deposit / withdrawal status of `asset` on `exchange` from its public
+/// currency endpoint. `None` for venues without one.
+pub async fn fetch_asset_transfer_status(
+    client: &reqwest::Client,
+    exchange: &CexExchange,
+    asset: &str,
+) -> Result<Option<AssetTransferStatus>, MarketScannerError> {
+    let asset = asset.to_uppercase();
+    let url = match exchange {
+        CexExchange::Kucoin => format!("{}/{}", KUCOIN_CURRENCY_URL, asset),
+        CexExchange::Gateio => format!("{}/{}", GATEIO_CURRENCY_URL, asset),
+        CexExchange::Htx => format!("{}?currency={}", HTX_CURRENCY_URL, asset.to_lowercase()),
+        CexExchange::Bitget => format!("{}?coin={}", BITGET_COINS_URL, asset),
+        _ => return Ok(None),
+    };
+    let response: serde_json::Value = client.get(&url).send().await?.json().await?;
+    parse_asset_transfer_status(exchange, &response)
+}
+
+/// Flags are booleans on some venues and "true" / "false" strings on others.
+fn flag(value: &serde_json::Value) -> Option<bool> {
+    match value {
+        serde_json::Value::Bool(b) => Some(*b),
+        serde_json::Value::String(s) => s.parse().ok(),
+        _ => None,
+    }
+}
+
+/// Per-network statuses of a `chains` array; `None` if it is missing or a flag is.
+fn networks(
+    chains: &serde_json::Value,
+    network_key: &str,
+    deposits: impl Fn(&serde_json::Value) -> Option<bool>,
+    withdrawals: impl Fn(&serde_json::Value) -> Option<bool>,
+) -> Option<Vec<NetworkTransferStatus>> {
+    chains
+        .as_array()?
+        .iter()
+        .map(|chain| {
+            Some(NetworkTransferStatus {
+                network: chain[network_key].as_str().unwrap_or_default().to_string(),
+                deposits_enabled: deposits(chain)?,
+                withdrawals_enabled: withdrawals(chain)?,
+            })
+        })
+        .collect()
+}
+
+/// Parses the currency endpoint response of `exchange` (see
+/// [fetch_asset_transfer_status]). `None` for venues without a public endpoint.
+pub fn parse_asset_transfer_status(
+    exchange: &CexExchange,
+    response: &serde_json::Value,
+) -> Result<Option<AssetTransferStatus>, MarketScannerError> {
+    let status = match exchange {
+        // {"code": "200000", "data": {"chains": [{"chainName": "TRC20", "isDepositEnabled": true, "isWithdrawEnabled": true}]}}
+        CexExchange::Kucoin => networks(
+            &response["data"]["chains"],
+            "chainName",
+            |c| flag(&c["isDepositEnabled"]),
+            |c| flag(&c["isWithdrawEnabled"]),
+        )
+        .map(AssetTransferStatus::from_networks),
+        // {"currency": "USDT", "deposit_disabled": false, "withdraw_disabled": false}
+        CexExchange::Gateio => flag(&response["deposit_disabled"])
+            .zip(flag(&response["withdraw_disabled"]))
+            .map(|(deposit_disabled, withdraw_disabled)| {
+                AssetTransferStatus::new(!deposit_disabled, !withdraw_disabled)
+            }),
+        // {"code": 200, "data": [{"chains": [{"chain": "trc20usdt", "depositStatus": "allowed", "withdrawStatus": "allowed"}]}]}
+        CexExchange::Htx => networks(
+            &response["data"][0]["chains"],
+            "chain",
+            |c| c["depositStatus"].as_str().map(|s| s == "allowed"),
+            |c| c["withdrawStatus"].as_str().map(|s| s == "allowed"),
+        )
+        .map(AssetTransferStatus::from_networks),
+        // {"code": "00000", "data": [{"coin": "USDT", "chains": [{"chain": "TRC20", "rechargeable": "true", "withdrawable": "true"}]}]}
+        CexExchange::Bitget => networks(
+            &response["data"][0]["chains"],
+            "chain",
+            |c| flag(&c["rechargeable"]),
+            |c| flag(&c["withdrawable"]),
+        )
+        .map(AssetTransferStatus::from_networks),
+        _ => return Ok(None),
+    };
+    status.map(Some).ok_or_else(|| {
+        MarketScannerError::ApiError(format!(
+            "{:?} currency status missing deposit / withdrawal flags: {}",
+            exchange, response
+        ))
+    })
+}
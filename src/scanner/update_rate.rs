@@ -0,0 +1,198 @@
+//! Update frequency of each venue and symbol feed.
+//!
+//! A venue can stay connected while one of its symbols stops updating (delisted pair,
+//! dropped subscription, illiquid market), and that feed's price silently goes stale. An
+//! [UpdateRateTracker] counts the price updates per venue and market and reports their
+//! rate and the age of the last one as [FeedUpdateStats]. The live scanner keeps one in its
+//! [crate::scanner::ScannerHandle] (see [crate::scanner::ScannerHandle::update_stats]),
+//! including the feeds that have not delivered anything yet, so dormant feeds can be
+//! excluded or moved to REST.
+
+use crate::common::{CexPrice, Exchange, MarketKey, Timestamp, logical_market_key};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, Instant};
+
+/// Window over which [FeedUpdateStats::updates_per_sec] is measured.
+pub const UPDATE_RATE_WINDOW: Duration = Duration::from_secs(10);
+
+/// Update statistics of one venue's feed for one market.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FeedUpdateStats {
+    pub exchange: Exchange,
+    /// Symbol as requested, or as first received
+    pub symbol: String,
+    /// Updates received since tracking started
+    pub updates: u64,
+    /// Updates per second over roughly the last [UPDATE_RATE_WINDOW]
+    pub updates_per_sec: f64,
+    /// When the last update was received; `None` if none was
+    pub last_update: Option<Timestamp>,
+    /// Time since the last update, or since tracking started if none was received
+    /// (milliseconds)
+    pub last_update_age_ms: u64,
+}
+
+impl FeedUpdateStats {
+    /// Whether nothing was received for `max_age`.
+    pub fn is_dormant(&self, max_age: Duration) -> bool {
+        self.last_update_age_ms >= max_age.as_millis() as u64
+    }
+}
+
+#[derive(Debug, Clone)]
+struct FeedCounter {
+    symbol: String,
+    since: Instant,
+    updates: u64,
+    last_update: Option<(Instant, Timestamp)>,
+    window_start: Instant,
+    window_updates: u64,
+    /// Updates of the window before `window_start`, if it directly preceded it
+    previous_window_updates: Option<u64>,
+}
+
+impl FeedCounter {
+    fn new(symbol: &str, now: Instant) -> Self {
+        Self {
+            symbol: symbol.to_string(),
+            since: now,
+            updates: 0,
+            last_update: None,
+            window_start: now,
+            window_updates: 0,
+            previous_window_updates: None,
+        }
+    }
+
+    /// Starts new windows until `now` falls into the current one.
+    fn roll(&mut self, now: Instant) {
+        let elapsed = now.saturating_duration_since(self.window_start);
+        if elapsed < UPDATE_RATE_WINDOW {
+            return;
+        }
+        self.previous_window_updates = if elapsed < UPDATE_RATE_WINDOW * 2 {
+            Some(self.window_updates)
+        } else {
+            Some(0)
+        };
+        let windows = elapsed.as_nanos() / UPDATE_RATE_WINDOW.as_nanos();
+        self.window_start += UPDATE_RATE_WINDOW * windows as u32;
+        self.window_updates = 0;
+    }
+
+    fn record(&mut self, now: Instant) {
+        self.roll(now);
+        self.updates += 1;
+        self.window_updates += 1;
+        self.last_update = Some((now, Timestamp::now()));
+    }
+
+    fn stats(&self, exchange: &Exchange, now: Instant) -> FeedUpdateStats {
+        let mut counter = self.clone();
+        counter.roll(now);
+        let current = now.saturating_duration_since(counter.window_start);
+        let (updates, span) = match counter.previous_window_updates {
+            Some(previous) => (
+                previous + counter.window_updates,
+                UPDATE_RATE_WINDOW + current,
+            ),
+            None => (
+                counter.window_updates,
+                now.saturating_duration_since(counter.since),
+            ),
+        };
+        let updates_per_sec = if span.is_zero() {
+            0.0
+        } else {
+            updates as f64 / span.as_secs_f64()
+        };
+        let last = self.last_update.map_or(self.since, |(at, _)| at);
+        FeedUpdateStats {
+            exchange: exchange.clone(),
+            symbol: self.symbol.clone(),
+            updates: self.updates,
+            updates_per_sec,
+            last_update: self.last_update.map(|(_, ts)| ts),
+            last_update_age_ms: now.saturating_duration_since(last).as_millis() as u64,
+        }
+    }
+}
+
+/// Update counters per venue and market. Cheap to clone; clones share the counters.
+#[derive(Debug, Clone, Default)]
+pub struct UpdateRateTracker {
+    feeds: Arc<RwLock<HashMap<(Exchange, MarketKey), FeedCounter>>>,
+}
+
+impl UpdateRateTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Starts tracking `symbol` on `exchange` before its first update, so a feed that
+    /// never delivers shows up with 0 updates. No-op if already tracked.
+    pub fn expect(&self, exchange: &Exchange, symbol: &str) {
+        let key = (exchange.clone(), logical_market_key(symbol, exchange));
+        let mut feeds = self.feeds.write().unwrap_or_else(|e| e.into_inner());
+        feeds
+            .entry(key)
+            .or_insert_with(|| FeedCounter::new(symbol, Instant::now()));
+    }
+
+    /// Counts an update of `price`'s venue and market.
+    pub fn record(&self, price: &CexPrice) {
+        let now = Instant::now();
+        let key = (
+            price.exchange.clone(),
+            logical_market_key(&price.symbol, &price.exchange),
+        );
+        let mut feeds = self.feeds.write().unwrap_or_else(|e| e.into_inner());
+        feeds
+            .entry(key)
+            .or_insert_with(|| FeedCounter::new(&price.symbol, now))
+            .record(now);
+    }
+
+    /// Statistics of every tracked feed, sorted by venue and symbol.
+    pub fn stats(&self) -> Vec<FeedUpdateStats> {
+        let now = Instant::now();
+        let feeds = self.feeds.read().unwrap_or_else(|e| e.into_inner());
+        let mut stats: Vec<FeedUpdateStats> = feeds
+            .iter()
+            .map(|((exchange, _), counter)| counter.stats(exchange, now))
+            .collect();
+        stats.sort_by(|a, b| {
+            format!("{:?}", a.exchange)
+                .cmp(&format!("{:?}", b.exchange))
+                .then_with(|| a.symbol.cmp(&b.symbol))
+        });
+        stats
+    }
+
+    /// Statistics of `symbol` (matched through asset aliases) on `exchange`.
+    pub fn stats_for(&self, exchange: &Exchange, symbol: &str) -> Option<FeedUpdateStats> {
+        let key = (exchange.clone(), logical_market_key(symbol, exchange));
+        let feeds = self.feeds.read().unwrap_or_else(|e| e.into_inner());
+        feeds
+            .get(&key)
+            .map(|counter| counter.stats(exchange, Instant::now()))
+    }
+
+    /// Feeds without an update for `max_age` (including those that never delivered).
+    pub fn dormant(&self, max_age: Duration) -> Vec<FeedUpdateStats> {
+        self.stats()
+            .into_iter()
+            .filter(|stats| stats.is_dormant(max_age))
+            .collect()
+    }
+
+    /// Forgets every feed.
+    pub fn clear(&self) {
+        self.feeds
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .clear();
+    }
+}
@@ -0,0 +1,278 @@
+//! Venue scoring and automatic selection of the scan set.
+//!
+//! Scanning every venue for every symbol wastes requests on venues that are unhealthy, slow,
+//! thin or never part of an opportunity. [VenueScorer] rates each venue for a symbol from
+//! four components, each normalized to 0..=1:
+//!
+//! - **health**: share of WebSocket messages decoded successfully (see
+//!   [crate::common::venue_health]); 0 while a [MaintenanceMonitor] reports maintenance
+//! - **latency**: `1 / (1 + avg_ms / reference_ms)` of the WebSocket ping latency
+//! - **liquidity**: top-of-book depth in quote (`mid × min(bid_qty, ask_qty)`) relative to
+//!   the deepest candidate
+//! - **opportunity**: cumulative profit of the venue's opportunities on the symbol in the
+//!   recorded [OpportunityReport]s, relative to the best candidate
+//!
+//! The score is the weighted mean of the components. [ArbitrageScanner::scan_auto] fetches
+//! prices from the candidates, keeps the top-K venues and scans only those.
+
+use crate::common::{
+    CexExchange, CexPrice, Exchange, FeeOverrides, MarketScannerError, logical_market_key,
+    normalize_symbol, venue_health,
+};
+use crate::scanner::{
+    ArbitrageOpportunity, ArbitrageScanner, MaintenanceMonitor, OpportunityReport,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Latency score when a venue has no ping samples yet.
+const UNKNOWN_LATENCY_SCORE: f64 = 0.5;
+
+/// Weights of the score components (relative; they need not sum to 1).
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct VenueScoreWeights {
+    pub health: f64,
+    pub latency: f64,
+    pub liquidity: f64,
+    pub opportunity: f64,
+}
+
+impl Default for VenueScoreWeights {
+    fn default() -> Self {
+        Self {
+            health: 1.0,
+            latency: 1.0,
+            liquidity: 1.0,
+            opportunity: 1.0,
+        }
+    }
+}
+
+/// Score of a venue for one symbol; components and total are in 0..=1.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct VenueScore {
+    pub exchange: CexExchange,
+    pub health: f64,
+    pub latency: f64,
+    pub liquidity: f64,
+    pub opportunity: f64,
+    /// Weighted mean of the components
+    pub total: f64,
+}
+
+/// Venues selected by [ArbitrageScanner::scan_auto] and the opportunities among them.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AutoScan {
+    /// Scores of every candidate that returned a price, best first
+    pub scores: Vec<VenueScore>,
+    /// Top-K venues that were scanned
+    pub selected: Vec<CexExchange>,
+    /// Opportunities among the selected venues, sorted by profitability
+    pub opportunities: Vec<ArbitrageOpportunity>,
+}
+
+/// Rates venues per symbol from health, latency, liquidity and past opportunities.
+#[derive(Debug, Clone)]
+pub struct VenueScorer {
+    weights: VenueScoreWeights,
+    latency_reference_ms: f64,
+    maintenance: Option<MaintenanceMonitor>,
+    /// Cumulative profit per (venue name, normalized symbol)
+    profit: HashMap<(String, String), f64>,
+}
+
+impl Default for VenueScorer {
+    fn default() -> Self {
+        Self {
+            weights: VenueScoreWeights::default(),
+            latency_reference_ms: 100.0,
+            maintenance: None,
+            profit: HashMap::new(),
+        }
+    }
+}
+
+impl VenueScorer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_weights(mut self, weights: VenueScoreWeights) -> Self {
+        self.weights = weights;
+        self
+    }
+
+    /// Ping latency that scores 0.5 (default 100 ms).
+    pub fn with_latency_reference_ms(mut self, reference_ms: f64) -> Self {
+        self.latency_reference_ms = reference_ms.max(f64::EPSILON);
+        self
+    }
+
+    /// Venues under maintenance get a health of 0.
+    pub fn with_maintenance_monitor(mut self, monitor: MaintenanceMonitor) -> Self {
+        self.maintenance = Some(monitor);
+        self
+    }
+
+    /// Adds the opportunities of `report` to the history: each entry counts for both its
+    /// source and destination venue.
+    pub fn with_report(mut self, report: &OpportunityReport) -> Self {
+        self.record_report(report);
+        self
+    }
+
+    /// Adds the opportunities of `report` to the history.
+    pub fn record_report(&mut self, report: &OpportunityReport) {
+        for entry in &report.entries {
+            let symbol = normalize_symbol(&entry.symbol);
+            for venue in [&entry.source_exchange, &entry.destination_exchange] {
+                *self
+                    .profit
+                    .entry((venue.clone(), symbol.clone()))
+                    .or_default() += entry.cumulative_profit_quote.max(0.0);
+            }
+        }
+    }
+
+    /// Scores every venue with a price of `symbol` in `prices`, best first.
+    pub fn score(&self, symbol: &str, prices: &[CexPrice]) -> Vec<VenueScore> {
+        let venues: Vec<(CexExchange, f64, f64)> = prices
+            .iter()
+            .filter_map(|price| {
+                let Exchange::Cex(exchange) = &price.exchange else {
+                    return None;
+                };
+                if logical_market_key(&price.symbol, &price.exchange)
+                    != logical_market_key(symbol, &price.exchange)
+                {
+                    return None;
+                }
+                let depth = price.mid_price * price.bid_qty.min(price.ask_qty);
+                let name = ArbitrageScanner::exchange_name(&price.exchange);
+                let profit = self.profit.get(&(name, normalize_symbol(symbol))).copied();
+                Some((exchange.clone(), depth, profit.unwrap_or(0.0)))
+            })
+            .collect();
+        let max_depth = venues.iter().map(|v| v.1).fold(0.0, f64::max);
+        let max_profit = venues.iter().map(|v| v.2).fold(0.0, f64::max);
+
+        let mut scores: Vec<VenueScore> = venues
+            .into_iter()
+            .map(|(exchange, depth, profit)| {
+                let (health, latency) = self.health_and_latency(&exchange);
+                let liquidity = ratio(depth, max_depth);
+                let opportunity = ratio(profit, max_profit);
+                let w = &self.weights;
+                let weight_sum = w.health + w.latency + w.liquidity + w.opportunity;
+                let total = if weight_sum > 0.0 {
+                    (w.health * health
+                        + w.latency * latency
+                        + w.liquidity * liquidity
+                        + w.opportunity * opportunity)
+                        / weight_sum
+                } else {
+                    0.0
+                };
+                VenueScore {
+                    exchange,
+                    health,
+                    latency,
+                    liquidity,
+                    opportunity,
+                    total,
+                }
+            })
+            .collect();
+        scores.sort_by(|a, b| b.total.total_cmp(&a.total));
+        scores
+    }
+
+    /// The `k` best venues for `symbol` among those with a price in `prices`. Venues
+    /// under maintenance are never selected.
+    pub fn select(&self, symbol: &str, prices: &[CexPrice], k: usize) -> Vec<CexExchange> {
+        self.score(symbol, prices)
+            .into_iter()
+            .filter(|score| {
+                self.maintenance
+                    .as_ref()
+                    .is_none_or(|m| !m.is_in_maintenance(&score.exchange))
+            })
+            .take(k)
+            .map(|score| score.exchange)
+            .collect()
+    }
+
+    fn health_and_latency(&self, exchange: &CexExchange) -> (f64, f64) {
+        if self
+            .maintenance
+            .as_ref()
+            .is_some_and(|m| m.is_in_maintenance(exchange))
+        {
+            return (0.0, 0.0);
+        }
+        let health = venue_health(exchange);
+        let decode = health
+            .decode
+            .map_or(1.0, |decode| 1.0 - decode.error_rate.clamp(0.0, 1.0));
+        let latency = health.ws_latency.map_or(UNKNOWN_LATENCY_SCORE, |gauge| {
+            1.0 / (1.0 + gauge.avg_ms.max(0.0) / self.latency_reference_ms)
+        });
+        (decode, latency)
+    }
+}
+
+fn ratio(value: f64, max: f64) -> f64 {
+    if max > 0.0 { value / max } else { 0.0 }
+}
+
+impl ArbitrageScanner {
+    /// Scans `symbol` on the `top_k` best venues (see [VenueScorer]) instead of a static
+    /// list: fetches prices from `candidates` (every venue when empty), scores them and
+    /// matches only the prices of the selected venues.
+    pub async fn scan_auto(
+        symbol: &str,
+        candidates: &[CexExchange],
+        top_k: usize,
+        scorer: &VenueScorer,
+        fee_overrides: Option<&FeeOverrides>,
+    ) -> Result<AutoScan, MarketScannerError> {
+        let candidates = if candidates.is_empty() {
+            &CexExchange::ALL[..]
+        } else {
+            candidates
+        };
+        let prices = Self::fetch_cex_prices(candidates, symbol).await?;
+        Ok(Self::auto_scan_from_prices(
+            symbol,
+            &prices,
+            top_k,
+            scorer,
+            fee_overrides,
+        ))
+    }
+
+    /// [ArbitrageScanner::scan_auto] on already fetched prices.
+    pub fn auto_scan_from_prices(
+        symbol: &str,
+        prices: &[CexPrice],
+        top_k: usize,
+        scorer: &VenueScorer,
+        fee_overrides: Option<&FeeOverrides>,
+    ) -> AutoScan {
+        let scores = scorer.score(symbol, prices);
+        let selected = scorer.select(symbol, prices, top_k);
+        let selected_prices: Vec<CexPrice> = prices
+            .iter()
+            .filter(|p| matches!(&p.exchange, Exchange::Cex(e) if selected.contains(e)))
+            .cloned()
+            .collect();
+        let mut opportunities =
+            Self::opportunities_from_prices(&selected_prices, &[], fee_overrides);
+        opportunities.sort_by(|a, b| b.spread_percentage.total_cmp(&a.spread_percentage));
+        AutoScan {
+            scores,
+            selected,
+            opportunities,
+        }
+    }
+}
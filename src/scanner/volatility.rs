@@ -0,0 +1,154 @@
+//! Volatility guard for opportunity emission.
+//!
+//! In fast markets prices move between detection and fill, so a spread that is fine in a
+//! calm market is mostly execution risk. [VolatilityGuard] keeps the recent mid prices of
+//! every venue per logical market and computes realized volatility (standard deviation of
+//! tick-to-tick log returns, in percent). While a market's volatility exceeds the
+//! configured threshold, its opportunities need a larger minimum spread to be emitted.
+
+use crate::common::{CexPrice, Exchange, MarketKey, Timestamp, logical_market_key};
+use crate::scanner::{ArbitrageOpportunity, PriceData};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use std::time::Duration;
+
+/// Volatility guard thresholds.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VolatilityGuardConfig {
+    /// Ticks older than this (relative to the newest tick of a venue) are dropped (milliseconds)
+    pub window_ms: u64,
+    /// Returns needed before a venue's volatility is computed
+    pub min_samples: usize,
+    /// Realized volatility (percent) above which a market counts as volatile
+    pub threshold_percent: f64,
+    /// Minimum spread percentage of opportunities in volatile markets
+    pub min_spread_percent: f64,
+}
+
+impl Default for VolatilityGuardConfig {
+    /// 0.2% tick volatility over one minute requires a 0.5% spread.
+    fn default() -> Self {
+        Self {
+            window_ms: 60 * 1000,
+            min_samples: 10,
+            threshold_percent: 0.2,
+            min_spread_percent: 0.5,
+        }
+    }
+}
+
+impl VolatilityGuardConfig {
+    pub fn with_window_ms(mut self, window_ms: u64) -> Self {
+        self.window_ms = window_ms;
+        self
+    }
+
+    pub fn with_min_samples(mut self, min_samples: usize) -> Self {
+        self.min_samples = min_samples;
+        self
+    }
+
+    pub fn with_threshold_percent(mut self, threshold_percent: f64) -> Self {
+        self.threshold_percent = threshold_percent;
+        self
+    }
+
+    pub fn with_min_spread_percent(mut self, min_spread_percent: f64) -> Self {
+        self.min_spread_percent = min_spread_percent;
+        self
+    }
+}
+
+/// Recent mid prices per (venue, logical market) and the resulting realized volatility.
+#[derive(Debug, Clone)]
+pub struct VolatilityGuard {
+    config: VolatilityGuardConfig,
+    ticks: HashMap<(Exchange, MarketKey), VecDeque<(Timestamp, f64)>>,
+}
+
+impl VolatilityGuard {
+    pub fn new(config: VolatilityGuardConfig) -> Self {
+        Self {
+            config,
+            ticks: HashMap::new(),
+        }
+    }
+
+    pub fn config(&self) -> &VolatilityGuardConfig {
+        &self.config
+    }
+
+    /// Records the mid price of `price`. Non-positive prices are ignored.
+    pub fn record(&mut self, price: &CexPrice) {
+        if price.mid_price <= 0.0 {
+            return;
+        }
+        let key = (
+            price.exchange.clone(),
+            logical_market_key(&price.symbol, &price.exchange),
+        );
+        let series = self.ticks.entry(key).or_default();
+        series.push_back((price.timestamp, price.mid_price));
+
+        let cutoff = price
+            .timestamp
+            .saturating_sub(Duration::from_millis(self.config.window_ms));
+        while series.front().is_some_and(|(ts, _)| *ts < cutoff) {
+            series.pop_front();
+        }
+    }
+
+    /// Realized volatility of `market` in percent: the highest per-venue standard deviation
+    /// of log returns. `None` until a venue has `min_samples` returns in the window.
+    pub fn realized_volatility(&self, market: &MarketKey) -> Option<f64> {
+        self.ticks
+            .iter()
+            .filter(|((_, key), _)| key == market)
+            .filter_map(|(_, series)| self.series_volatility(series))
+            .max_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal))
+    }
+
+    pub fn is_volatile(&self, market: &MarketKey) -> bool {
+        self.realized_volatility(market)
+            .is_some_and(|vol| vol > self.config.threshold_percent)
+    }
+
+    /// Minimum spread percentage an opportunity on `market` needs right now
+    /// (0 while the market is calm).
+    pub fn min_spread_percent(&self, market: &MarketKey) -> f64 {
+        if self.is_volatile(market) {
+            self.config.min_spread_percent
+        } else {
+            0.0
+        }
+    }
+
+    /// Whether `opportunity` clears the minimum spread of its market.
+    pub fn allows(&self, opportunity: &ArbitrageOpportunity) -> bool {
+        let market = match &opportunity.source_leg {
+            PriceData::Cex(p) => logical_market_key(&p.symbol, &p.exchange),
+            PriceData::Dex(p) => logical_market_key(&p.symbol, &p.exchange),
+        };
+        opportunity.spread_percentage >= self.min_spread_percent(&market)
+    }
+
+    /// Drops opportunities that do not clear the minimum spread of their market.
+    pub fn filter(&self, opportunities: &mut Vec<ArbitrageOpportunity>) {
+        opportunities.retain(|o| self.allows(o));
+    }
+
+    fn series_volatility(&self, series: &VecDeque<(Timestamp, f64)>) -> Option<f64> {
+        let returns: Vec<f64> = series
+            .iter()
+            .zip(series.iter().skip(1))
+            .map(|((_, prev), (_, next))| (next / prev).ln())
+            .collect();
+        if returns.len() < self.config.min_samples.max(2) {
+            return None;
+        }
+        let n = returns.len() as f64;
+        let mean = returns.iter().sum::<f64>() / n;
+        let variance = returns.iter().map(|r| (r - mean).powi(2)).sum::<f64>() / (n - 1.0);
+        Some(variance.sqrt() * 100.0)
+    }
+}
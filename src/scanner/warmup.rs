@@ -0,0 +1,306 @@
+//! Startup warm-up and readiness reporting.
+//!
+//! The first scan after process start pays for DNS lookups, TLS handshakes, WebSocket
+//! connects and the semi-static REST endpoints (exchange info, asset pairs, product
+//! details) that later scans read from the HTTP cache. [ArbitrageScanner::warm_up] does
+//! that work up front, per venue and in parallel, and returns a [ReadinessReport].
+
+use crate::common::{
+    CexExchange, DexAggregator, Exchange, ExchangeTrait, MarketScannerError, Timestamp,
+};
+use crate::dex::chains::Token;
+use crate::scanner::ArbitrageScanner;
+use crate::{
+    Binance, Bitfinex, Bitget, Btcturk, Bybit, Coinbase, Cryptocom, Gateio, Htx, Kraken, Kucoin,
+    KyberSwap, Mexc, OKX, Upbit,
+};
+use futures::future::join_all;
+use serde::{Deserialize, Serialize};
+use std::future::Future;
+use std::time::{Duration, Instant};
+
+/// Options for [ArbitrageScanner::warm_up].
+#[derive(Debug, Clone, Default)]
+pub struct WarmUpOptions {
+    /// Also connect every WebSocket venue and wait for its first price
+    pub open_websockets: bool,
+    /// Timeout of each warm-up step (0 → 5000 ms)
+    pub timeout_ms: u64,
+    /// DEX aggregators warmed with one quote of `dex_quote`
+    pub dex_exchanges: Vec<DexAggregator>,
+    /// Base token, quote token and quote amount of the DEX warm-up quote
+    pub dex_quote: Option<(Token, Token, f64)>,
+}
+
+impl WarmUpOptions {
+    pub fn with_websockets(mut self) -> Self {
+        self.open_websockets = true;
+        self
+    }
+
+    pub fn with_timeout_ms(mut self, timeout_ms: u64) -> Self {
+        self.timeout_ms = timeout_ms;
+        self
+    }
+
+    pub fn with_dex_quote(
+        mut self,
+        dex_exchanges: &[DexAggregator],
+        base_token: Token,
+        quote_token: Token,
+        quote_amount: f64,
+    ) -> Self {
+        self.dex_exchanges = dex_exchanges.to_vec();
+        self.dex_quote = Some((base_token, quote_token, quote_amount));
+        self
+    }
+}
+
+/// Outcome of one warm-up step of a venue.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum WarmUpStep {
+    /// Step does not apply to the venue or was not requested
+    Skipped,
+    /// Step succeeded after `elapsed_ms`
+    Ready { elapsed_ms: u64 },
+    /// Step failed or timed out
+    Failed { error: String },
+}
+
+impl WarmUpStep {
+    pub fn is_ready(&self) -> bool {
+        matches!(self, WarmUpStep::Ready { .. })
+    }
+
+    pub fn is_failed(&self) -> bool {
+        matches!(self, WarmUpStep::Failed { .. })
+    }
+}
+
+/// Warm-up result of one venue.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct VenueReadiness {
+    pub exchange: Exchange,
+    /// Resolution of the REST API host
+    pub dns: WarmUpStep,
+    /// REST price of every symbol (fills the HTTP cache with exchange info / listings)
+    pub rest: WarmUpStep,
+    /// First WebSocket price; `Skipped` unless requested and supported
+    pub websocket: WarmUpStep,
+}
+
+impl VenueReadiness {
+    /// REST prices were fetched and no step failed.
+    pub fn is_ready(&self) -> bool {
+        self.rest.is_ready() && !self.dns.is_failed() && !self.websocket.is_failed()
+    }
+}
+
+/// Readiness of every venue passed to [ArbitrageScanner::warm_up].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReadinessReport {
+    pub venues: Vec<VenueReadiness>,
+    /// Wall-clock duration of the whole warm-up
+    pub elapsed_ms: u64,
+    pub completed_at: Timestamp,
+}
+
+impl ReadinessReport {
+    /// Every venue is ready.
+    pub fn is_ready(&self) -> bool {
+        self.venues.iter().all(VenueReadiness::is_ready)
+    }
+
+    pub fn ready_venues(&self) -> Vec<Exchange> {
+        self.venues
+            .iter()
+            .filter(|v| v.is_ready())
+            .map(|v| v.exchange.clone())
+            .collect()
+    }
+
+    pub fn not_ready_venues(&self) -> Vec<Exchange> {
+        self.venues
+            .iter()
+            .filter(|v| !v.is_ready())
+            .map(|v| v.exchange.clone())
+            .collect()
+    }
+
+    pub fn venue(&self, exchange: &Exchange) -> Option<&VenueReadiness> {
+        self.venues.iter().find(|v| v.exchange == *exchange)
+    }
+}
+
+impl ArbitrageScanner {
+    /// Warms up the given venues before the first scan: resolves their REST hosts, fetches
+    /// the REST price of every symbol (which also caches exchange info, asset pairs and
+    /// product details), optionally connects each WebSocket venue until its first price,
+    /// and quotes `options.dex_quote` on the configured aggregators.
+    ///
+    /// Failures do not abort the warm-up; they are reported per venue and step.
+    pub async fn warm_up(
+        symbols: &[&str],
+        cex_exchanges: &[CexExchange],
+        options: WarmUpOptions,
+    ) -> Result<ReadinessReport, MarketScannerError> {
+        if symbols.is_empty() {
+            return Err(MarketScannerError::InvalidSymbol(
+                "At least one symbol required".to_string(),
+            ));
+        }
+        let timeout = Duration::from_millis(if options.timeout_ms == 0 {
+            5000
+        } else {
+            options.timeout_ms
+        });
+        let started = Instant::now();
+
+        let cex_futures = cex_exchanges
+            .iter()
+            .map(|exchange| warm_up_cex(exchange, symbols, options.open_websockets, timeout));
+        let dex_futures = options
+            .dex_exchanges
+            .iter()
+            .map(|exchange| warm_up_dex(exchange, options.dex_quote.as_ref(), timeout));
+        let (mut venues, dex_venues) = tokio::join!(join_all(cex_futures), join_all(dex_futures));
+        venues.extend(dex_venues);
+
+        Ok(ReadinessReport {
+            venues,
+            elapsed_ms: started.elapsed().as_millis() as u64,
+            completed_at: Timestamp::now(),
+        })
+    }
+
+    fn cex_api_base(exchange: &CexExchange) -> String {
+        match exchange {
+            CexExchange::Binance => Binance::new().api_base().to_string(),
+            CexExchange::Bybit => Bybit::new().api_base().to_string(),
+            CexExchange::MEXC => Mexc::new().api_base().to_string(),
+            CexExchange::OKX => OKX::new().api_base().to_string(),
+            CexExchange::Gateio => Gateio::new().api_base().to_string(),
+            CexExchange::Kucoin => Kucoin::new().api_base().to_string(),
+            CexExchange::Bitget => Bitget::new().api_base().to_string(),
+            CexExchange::Btcturk => Btcturk::new().api_base().to_string(),
+            CexExchange::Htx => Htx::new().api_base().to_string(),
+            CexExchange::Coinbase => Coinbase::new().api_base().to_string(),
+            CexExchange::Kraken => Kraken::new().api_base().to_string(),
+            CexExchange::Bitfinex => Bitfinex::new().api_base().to_string(),
+            CexExchange::Upbit => Upbit::new().api_base().to_string(),
+            CexExchange::Cryptocom => Cryptocom::new().api_base().to_string(),
+        }
+    }
+}
+
+async fn warm_up_cex(
+    exchange: &CexExchange,
+    symbols: &[&str],
+    open_websocket: bool,
+    timeout: Duration,
+) -> VenueReadiness {
+    let dns = timed_step(
+        timeout,
+        resolve_host(&ArbitrageScanner::cex_api_base(exchange)),
+    )
+    .await;
+
+    let rest = timed_step(timeout, async {
+        let prices = join_all(
+            symbols
+                .iter()
+                .map(|symbol| ArbitrageScanner::get_cex_price(exchange, symbol)),
+        )
+        .await;
+        prices.into_iter().try_for_each(|p| p.map(|_| ()))
+    });
+    let websocket = async {
+        if !open_websocket || !ArbitrageScanner::exchange_supports_websocket(exchange) {
+            return WarmUpStep::Skipped;
+        }
+        timed_step(timeout, async {
+            // The stream closes once the receiver is dropped after the first price
+            let mut rx =
+                ArbitrageScanner::stream_cex_prices_websocket(exchange, symbols, 0, 0).await?;
+            rx.recv().await.map(|_| ()).ok_or_else(|| {
+                MarketScannerError::ApiError("WebSocket closed before the first price".to_string())
+            })
+        })
+        .await
+    };
+    let (rest, websocket) = tokio::join!(rest, websocket);
+
+    VenueReadiness {
+        exchange: Exchange::Cex(exchange.clone()),
+        dns,
+        rest,
+        websocket,
+    }
+}
+
+async fn warm_up_dex(
+    exchange: &DexAggregator,
+    quote: Option<&(Token, Token, f64)>,
+    timeout: Duration,
+) -> VenueReadiness {
+    let api_base = match exchange {
+        DexAggregator::KyberSwap => KyberSwap::new().api_base().to_string(),
+    };
+    let dns = timed_step(timeout, resolve_host(&api_base)).await;
+    let rest = match quote {
+        Some((base, quote, amount)) => {
+            timed_step(timeout, async {
+                ArbitrageScanner::get_dex_price(exchange, base, quote, *amount)
+                    .await
+                    .map(|_| ())
+            })
+            .await
+        }
+        None => WarmUpStep::Failed {
+            error: "No DEX quote configured".to_string(),
+        },
+    };
+
+    VenueReadiness {
+        exchange: Exchange::Dex(exchange.clone()),
+        dns,
+        rest,
+        websocket: WarmUpStep::Skipped,
+    }
+}
+
+/// Runs `step` with a timeout and measures how long it took.
+async fn timed_step<F>(timeout: Duration, step: F) -> WarmUpStep
+where
+    F: Future<Output = Result<(), MarketScannerError>>,
+{
+    let started = Instant::now();
+    match tokio::time::timeout(timeout, step).await {
+        Ok(Ok(())) => WarmUpStep::Ready {
+            elapsed_ms: started.elapsed().as_millis() as u64,
+        },
+        Ok(Err(e)) => WarmUpStep::Failed {
+            error: e.to_string(),
+        },
+        Err(_) => WarmUpStep::Failed {
+            error: format!("Timed out after {} ms", timeout.as_millis()),
+        },
+    }
+}
+
+/// Resolves the host of `url` so later connections hit a warm resolver cache.
+async fn resolve_host(url: &str) -> Result<(), MarketScannerError> {
+    let parsed = reqwest::Url::parse(url)
+        .map_err(|e| MarketScannerError::ApiError(format!("Invalid URL {}: {}", url, e)))?;
+    let host = parsed
+        .host_str()
+        .ok_or_else(|| MarketScannerError::ApiError(format!("URL without host: {}", url)))?;
+    let port = parsed.port_or_known_default().unwrap_or(443);
+    let mut addrs = tokio::net::lookup_host((host, port)).await.map_err(|e| {
+        MarketScannerError::ApiError(format!("DNS lookup of {} failed: {}", host, e))
+    })?;
+    addrs
+        .next()
+        .map(|_| ())
+        .ok_or_else(|| MarketScannerError::ApiError(format!("No addresses for {}", host)))
+}
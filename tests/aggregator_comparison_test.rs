@@ -0,0 +1,124 @@
+use aeon_market_scanner_rs::dex::chains::{ChainId, Token};
+use aeon_market_scanner_rs::{
+    AggregatorComparison, ArbitrageScanner, DexAggregator, DexPrice, DexRouteSummary, Exchange,
+    FeeOverrides, MarketScannerError, Timestamp,
+};
+use serde_json::json;
+
+fn summary(gas_usd: f64) -> DexRouteSummary {
+    DexRouteSummary {
+        token_in: "0xin".to_string(),
+        token_out: "0xout".to_string(),
+        amount_in: 0.0,
+        amount_out: 0.0,
+        amount_in_wei: "0".to_string(),
+        amount_out_wei: "0".to_string(),
+        gas: None,
+        gas_price: None,
+        gas_usd: Some(gas_usd),
+    }
+}
+
+/// Quote of 2 ETH at `ask` / `bid` with `gas_usd` per swap and a route of `paths` paths
+/// with two swaps each.
+fn quote(ask: f64, bid: f64, gas_usd: f64, paths: usize) -> DexPrice {
+    let route_data = json!({
+        "routeSummary": {
+            "route": vec![vec![json!({"pool": "0xa"}), json!({"pool": "0xb"})]; paths],
+        }
+    });
+    DexPrice {
+        symbol: "ETHUSDT".to_string(),
+        mid_price: (ask + bid) / 2.0,
+        bid_price: bid,
+        ask_price: ask,
+        bid_qty: 2.0,
+        ask_qty: 2.0,
+        timestamp: Timestamp::now(),
+        exchange: Exchange::Dex(DexAggregator::KyberSwap),
+        bid_route_summary: Some(summary(gas_usd)),
+        ask_route_summary: Some(summary(gas_usd)),
+        bid_route_data: Some(route_data.clone()),
+        ask_route_data: Some(route_data),
+        transfer_tax_bps: None,
+    }
+}
+
+fn no_fees() -> FeeOverrides {
+    FeeOverrides::default().with_dex_taker_fee(DexAggregator::KyberSwap, 0.0)
+}
+
+#[test]
+fn quotes_are_lined_up_by_effective_price() {
+    let comparison = AggregatorComparison::from_results(
+        4_000.0,
+        vec![
+            // Cheaper quote, but gas makes it the worse one
+            (
+                DexAggregator::KyberSwap,
+                Ok(quote(1_999.0, 1_998.0, 10.0, 2)),
+            ),
+            (
+                DexAggregator::KyberSwap,
+                Ok(quote(2_000.0, 1_999.0, 0.5, 1)),
+            ),
+            (
+                DexAggregator::KyberSwap,
+                Err(MarketScannerError::ApiError("no route".to_string())),
+            ),
+        ],
+        Some(&no_fees()),
+    );
+
+    assert_eq!(comparison.quotes.len(), 2);
+    assert_eq!(comparison.failures.len(), 1);
+    assert!(comparison.failures[0].1.contains("no route"));
+
+    let best_ask = comparison.best_ask().unwrap();
+    assert_eq!(best_ask.ask_price, 2_000.0);
+    assert!((best_ask.effective_ask - 2_000.25).abs() < 1e-9);
+    assert_eq!(best_ask.ask_gas_usd, Some(0.5));
+    assert_eq!(best_ask.ask_route_hops, Some(2));
+
+    let best_bid = comparison.best_bid().unwrap();
+    assert_eq!(best_bid.bid_price, 1_999.0);
+    assert!((best_bid.effective_bid - 1_998.75).abs() < 1e-9);
+
+    let worst = &comparison.quotes[1];
+    assert!((worst.effective_ask - 2_004.0).abs() < 1e-9);
+    assert_eq!(worst.bid_route_hops, Some(4));
+}
+
+#[test]
+fn quotes_without_route_data_have_no_hops() {
+    let mut price = quote(2_000.0, 1_999.0, 0.0, 1);
+    price.ask_route_data = None;
+    price.bid_route_summary = None;
+    let comparison = AggregatorComparison::from_results(
+        4_000.0,
+        vec![(DexAggregator::KyberSwap, Ok(price))],
+        Some(&no_fees()),
+    );
+    let quote = &comparison.quotes[0];
+    assert_eq!(quote.ask_route_hops, None);
+    assert_eq!(quote.bid_gas_usd, None);
+    assert_eq!(quote.effective_bid, 1_999.0);
+}
+
+#[tokio::test]
+async fn comparing_no_aggregators_is_empty() {
+    let token = |symbol: &str| {
+        Token::new(
+            format!("0x{}", symbol),
+            symbol.to_string(),
+            symbol.to_string(),
+            18,
+            ChainId::ETHEREUM,
+        )
+    };
+    let comparison =
+        ArbitrageScanner::compare_aggregators(&token("ETH"), &token("USDT"), 1_000.0, &[], None)
+            .await;
+    assert!(comparison.quotes.is_empty());
+    assert!(comparison.best_ask().is_none() && comparison.best_bid().is_none());
+}
@@ -0,0 +1,121 @@
+mod scanner_common;
+
+use aeon_market_scanner_rs::common::{OrderBook, OrderBookLevel, Timestamp};
+use aeon_market_scanner_rs::{
+    CexExchange, DepthSnapshot, Exchange, PriceHistoryFrame, ScannerContext,
+};
+use scanner_common::{binance_okx_fees, price};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Binance ask 100, OKX bid `okx_bid`.
+fn frame(at_ms: u64, okx_bid: f64) -> PriceHistoryFrame {
+    PriceHistoryFrame::new(
+        Timestamp::from_millis(at_ms),
+        vec![
+            price(CexExchange::Binance, 99.9, 100.0),
+            price(CexExchange::OKX, okx_bid, okx_bid + 0.1),
+        ],
+        Vec::new(),
+    )
+}
+
+fn context() -> ScannerContext {
+    let fees = binance_okx_fees();
+    ScannerContext::new("backfill").with_fee_overrides(fees)
+}
+
+#[test]
+fn test_backfill_applies_filters_in_time_order() {
+    // Out of order on purpose: spreads of 0.5%, 2% and 1%
+    let history = vec![
+        frame(3_000, 101.0),
+        frame(1_000, 100.5),
+        frame(2_000, 102.0),
+    ];
+    let report = context()
+        .with_min_spread_percent(0.8)
+        .backfill_alerts(&history, None);
+
+    assert_eq!(report.frames, 3);
+    assert_eq!(report.start, Some(Timestamp::from_millis(1_000)));
+    assert_eq!(report.end, Some(Timestamp::from_millis(3_000)));
+    assert_eq!(report.candidates, 3);
+    assert_eq!(report.matched, 2);
+    let times: Vec<u64> = report.alerts.iter().map(|a| a.at.as_millis()).collect();
+    assert_eq!(times, vec![2_000, 3_000]);
+    assert_eq!(report.alerts[0].opportunity.source_exchange, "Binance");
+    assert_eq!(
+        report.alerts_per_key(),
+        vec![("Binance->OKX:BTCUSDT".to_string(), 2)]
+    );
+}
+
+#[test]
+fn test_backfill_dedups_and_skips_sinks() {
+    let calls = Arc::new(AtomicUsize::new(0));
+    let sink_calls = calls.clone();
+    let context = context().with_sink(move |_, _| {
+        sink_calls.fetch_add(1, Ordering::SeqCst);
+    });
+    // Seen at 0-2 s, gone, back at 10 s
+    let history = vec![
+        frame(0, 102.0),
+        frame(1_000, 102.0),
+        frame(2_000, 102.0),
+        frame(5_000, 99.95),
+        frame(10_000, 102.0),
+    ];
+
+    let report = context.backfill_alerts(&history, Some(3_000));
+    assert_eq!(report.matched, 4);
+    let times: Vec<u64> = report.alerts.iter().map(|a| a.at.as_millis()).collect();
+    assert_eq!(times, vec![0, 10_000]);
+    assert_eq!(calls.load(Ordering::SeqCst), 0);
+
+    // A longer expiry covers the gap
+    let report = context.backfill_alerts(&history, Some(60_000));
+    assert_eq!(report.alerts.len(), 1);
+}
+
+#[test]
+fn test_frames_from_depth_snapshots() {
+    let book = |exchange: CexExchange, bid: f64, ask: f64| OrderBook {
+        symbol: "BTCUSDT".to_string(),
+        bids: vec![OrderBookLevel {
+            price: bid,
+            quantity: 2.0,
+        }],
+        asks: vec![OrderBookLevel {
+            price: ask,
+            quantity: 3.0,
+        }],
+        timestamp: Timestamp::from_millis(1),
+        exchange: Exchange::Cex(exchange),
+    };
+    let snapshot = |at_ms: u64, book: OrderBook| DepthSnapshot {
+        captured_at: Timestamp::from_millis(at_ms),
+        book,
+    };
+    let snapshots = vec![
+        snapshot(1_010, book(CexExchange::Binance, 99.9, 100.0)),
+        snapshot(1_020, book(CexExchange::OKX, 102.0, 102.1)),
+        snapshot(1_030, book(CexExchange::OKX, 101.0, 101.1)),
+        snapshot(2_005, book(CexExchange::Binance, 99.9, 100.0)),
+    ];
+
+    let frames = PriceHistoryFrame::from_depth_snapshots(&snapshots, 1_000);
+    assert_eq!(frames.len(), 2);
+    assert_eq!(frames[0].at, Timestamp::from_millis(1_030));
+    assert_eq!(frames[0].cex_prices.len(), 2);
+    let okx = frames[0]
+        .cex_prices
+        .iter()
+        .find(|p| p.exchange == Exchange::Cex(CexExchange::OKX))
+        .unwrap();
+    assert_eq!(okx.bid_price, 101.0);
+    assert_eq!(okx.ask_qty, 3.0);
+
+    let report = context().backfill_alerts(&frames, None);
+    assert_eq!(report.alerts.len(), 1);
+}
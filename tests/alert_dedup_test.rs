@@ -0,0 +1,83 @@
+mod scanner_common;
+
+use aeon_market_scanner_rs::common::Timestamp;
+use aeon_market_scanner_rs::{AlertDeduplicator, ScannerContext};
+use scanner_common::binance_to_okx;
+use std::sync::{Arc, Mutex};
+
+#[test]
+fn long_lived_spreads_alert_once_until_they_expire() {
+    let btc = binance_to_okx("BTCUSDT", 100.0, 110.0);
+    let eth = binance_to_okx("ETHUSDT", 100.0, 110.0);
+    assert_eq!(btc.key(), "Binance->OKX:BTCUSDT");
+
+    let dedup = AlertDeduplicator::new(10_000);
+    let at = Timestamp::from_millis;
+    assert_eq!(dedup.filter_new(std::slice::from_ref(&btc), at(0)).len(), 1);
+    // Still there: suppressed, and the record keeps being refreshed
+    assert!(
+        dedup
+            .filter_new(std::slice::from_ref(&btc), at(8_000))
+            .is_empty()
+    );
+    let fresh = dedup.filter_new(&[btc.clone(), eth.clone()], at(16_000));
+    assert_eq!(fresh.len(), 1);
+    assert_eq!(fresh[0].symbol, "ETHUSDT");
+
+    let record = dedup.record(&btc.key()).unwrap();
+    assert_eq!(record.alerted_at, at(0));
+    assert_eq!(record.last_seen_at, at(16_000));
+
+    // Gone for longer than the expiry: alerts again
+    assert!(
+        dedup
+            .filter_new(std::slice::from_ref(&eth), at(20_000))
+            .is_empty()
+    );
+    assert_eq!(
+        dedup
+            .filter_new(std::slice::from_ref(&btc), at(26_001))
+            .len(),
+        1
+    );
+}
+
+#[test]
+fn records_survive_a_restart_through_the_file_store() {
+    let path = std::env::temp_dir().join(format!("aeon_alerts_{}.json", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+    let btc = binance_to_okx("BTCUSDT", 100.0, 110.0);
+    let now = Timestamp::now();
+
+    let first = AlertDeduplicator::new(60_000).with_file(&path).unwrap();
+    assert_eq!(first.filter_new(std::slice::from_ref(&btc), now).len(), 1);
+    first.save().unwrap();
+    drop(first);
+
+    let restarted = AlertDeduplicator::new(60_000).with_file(&path).unwrap();
+    assert_eq!(restarted.record(&btc.key()).unwrap().alerted_at, now);
+    assert!(
+        restarted
+            .filter_new(std::slice::from_ref(&btc), now)
+            .is_empty()
+    );
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn wrapped_sinks_only_see_new_opportunities() {
+    let received = Arc::new(Mutex::new(Vec::new()));
+    let sink_received = received.clone();
+    let dedup = AlertDeduplicator::new(60_000);
+    let context = ScannerContext::new("desk").with_sink(dedup.sink(move |_, opportunities| {
+        sink_received.lock().unwrap().push(opportunities.len());
+    }));
+
+    context.process(vec![binance_to_okx("BTCUSDT", 100.0, 110.0)]);
+    context.process(vec![binance_to_okx("BTCUSDT", 100.0, 110.0)]);
+    context.process(vec![
+        binance_to_okx("BTCUSDT", 100.0, 110.0),
+        binance_to_okx("ETHUSDT", 100.0, 110.0),
+    ]);
+    assert_eq!(*received.lock().unwrap(), vec![1, 1]);
+}
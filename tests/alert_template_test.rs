@@ -0,0 +1,118 @@
+mod scanner_common;
+
+use aeon_market_scanner_rs::{
+    AlertTemplate, CexExchange, FeeOverrides, MarketScannerError, ScannerContext,
+};
+use scanner_common::{opportunity, two_venue_prices, zero_taker_fees};
+use std::sync::{Arc, Mutex};
+
+fn fees() -> FeeOverrides {
+    zero_taker_fees(&[CexExchange::Bybit, CexExchange::OKX])
+}
+
+#[test]
+fn placeholders_and_helpers_are_rendered() {
+    let template = AlertTemplate::parse(
+        "{{symbol}}: buy {{lower source_exchange}} @ {{source_leg.ask_price}}, \
+         sell {{upper destination_exchange}} @ {{destination_leg.bid_price}} \
+         ({{round spread_percentage 2}}%, profit {{round total_profit 1}}){{ missing }}",
+    )
+    .unwrap();
+    assert_eq!(
+        template.render(&opportunity(
+            "BTCUSDT",
+            CexExchange::Bybit,
+            100.0,
+            CexExchange::OKX,
+            103.0,
+            2.0
+        )),
+        "BTCUSDT: buy bybit @ 100, sell OKX @ 103 (3.00%, profit 6.0)"
+    );
+    assert_eq!(
+        template.fields().collect::<Vec<_>>(),
+        [
+            "symbol",
+            "source_exchange",
+            "source_leg.ask_price",
+            "destination_exchange",
+            "destination_leg.bid_price",
+            "spread_percentage",
+            "total_profit",
+            "missing",
+        ]
+    );
+}
+
+#[test]
+fn invalid_templates_are_rejected() {
+    for source in [
+        "{{symbol",
+        "{{}}",
+        "{{round spread}}",
+        "{{bold symbol}}",
+        "{{round spread x}}",
+    ] {
+        assert!(
+            matches!(
+                AlertTemplate::parse(source),
+                Err(MarketScannerError::InvalidConfig(_))
+            ),
+            "{}",
+            source
+        );
+    }
+    // Escaped braces are literal
+    let template: AlertTemplate = r"\{{symbol}} {{symbol}}".parse().unwrap();
+    assert_eq!(
+        template.render(&opportunity(
+            "BTCUSDT",
+            CexExchange::Bybit,
+            100.0,
+            CexExchange::OKX,
+            103.0,
+            2.0
+        )),
+        "{{symbol}} BTCUSDT"
+    );
+}
+
+#[test]
+fn templates_load_from_config_and_feed_sinks() {
+    #[derive(serde::Deserialize)]
+    struct AlertConfig {
+        telegram: AlertTemplate,
+    }
+    let config: AlertConfig =
+        serde_json::from_str(r#"{"telegram": "[{{context}}] {{symbol}} {{source_exchange}}"}"#)
+            .unwrap();
+    assert_eq!(
+        serde_json::to_value(&config.telegram).unwrap(),
+        "[{{context}}] {{symbol}} {{source_exchange}}"
+    );
+    assert!(serde_json::from_str::<AlertTemplate>(r#""{{symbol""#).is_err());
+
+    let sent = Arc::new(Mutex::new(Vec::new()));
+    let messages = sent.clone();
+    let context = ScannerContext::new("desk-1")
+        .with_fee_overrides(fees())
+        .with_sink(
+            config
+                .telegram
+                .sink(move |message| messages.lock().unwrap().push(message)),
+        );
+    let opportunities = context.opportunities_from_prices(
+        &two_venue_prices(
+            "BTCUSDT",
+            CexExchange::Bybit,
+            100.0,
+            CexExchange::OKX,
+            103.0,
+            2.0,
+        ),
+        &[],
+    );
+    let sent = sent.lock().unwrap();
+    assert_eq!(sent.len(), opportunities.len());
+    assert!(sent.contains(&"[desk-1] BTCUSDT Bybit".to_string()));
+}
@@ -0,0 +1,81 @@
+use aeon_market_scanner_rs::dex::execution::{ApprovalCache, approval_calldata, build_approval_tx};
+use ethers::core::types::{Address, Bytes, U256};
+use ethers::providers::Provider;
+use std::time::Duration;
+
+fn addr(last: u8) -> Address {
+    Address::from_low_u64_be(last as u64)
+}
+
+#[test]
+fn test_approval_calldata() {
+    let data = approval_calldata(addr(0xab), U256::from(1000u64));
+    assert_eq!(data.len(), 4 + 32 + 32);
+    assert_eq!(&data[..4], &[0x09, 0x5e, 0xa7, 0xb3]);
+    assert_eq!(data[35], 0xab);
+    assert_eq!(U256::from_big_endian(&data[36..68]), U256::from(1000u64));
+}
+
+#[test]
+fn test_build_approval_tx() {
+    let tx = build_approval_tx(1, addr(1), addr(2), addr(3), U256::MAX);
+    assert_eq!(tx.chain_id().map(|c| c.as_u64()), Some(1));
+    assert_eq!(tx.from(), Some(&addr(2)));
+    assert_eq!(tx.to_addr(), Some(&addr(1)));
+    assert_eq!(tx.data(), Some(&approval_calldata(addr(3), U256::MAX)));
+}
+
+#[test]
+fn test_approval_cache_record_spend_invalidate() {
+    let (wallet, token, router) = (addr(1), addr(2), addr(3));
+    let mut cache = ApprovalCache::default();
+    assert_eq!(cache.cached(wallet, token, router), None);
+
+    cache.record(wallet, token, router, U256::from(500u64));
+    assert_eq!(
+        cache.cached(wallet, token, router),
+        Some(U256::from(500u64))
+    );
+    // Keyed per router
+    assert_eq!(cache.cached(wallet, token, addr(4)), None);
+
+    cache.record_spend(wallet, token, router, U256::from(200u64));
+    assert_eq!(
+        cache.cached(wallet, token, router),
+        Some(U256::from(300u64))
+    );
+    cache.record_spend(wallet, token, router, U256::from(1000u64));
+    assert_eq!(cache.cached(wallet, token, router), Some(U256::zero()));
+
+    cache.invalidate(wallet, token, router);
+    assert_eq!(cache.cached(wallet, token, router), None);
+
+    let mut expired = ApprovalCache::default().with_ttl(Duration::ZERO);
+    expired.record(wallet, token, router, U256::one());
+    std::thread::sleep(Duration::from_millis(5));
+    assert_eq!(expired.cached(wallet, token, router), None);
+}
+
+#[tokio::test]
+async fn test_approval_cache_queries_chain_once() {
+    let (provider, mock) = Provider::mocked();
+    let mut word = [0u8; 32];
+    U256::from(750u64).to_big_endian(&mut word);
+    mock.push::<Bytes, _>(Bytes::from(word.to_vec())).unwrap();
+
+    let (wallet, token, router) = (addr(1), addr(2), addr(3));
+    let mut cache = ApprovalCache::default();
+    assert!(
+        !cache
+            .needs_approval(&provider, wallet, token, router, U256::from(700u64))
+            .await
+            .unwrap()
+    );
+    // Served from cache: the mock has no second response
+    assert!(
+        cache
+            .needs_approval(&provider, wallet, token, router, U256::from(800u64))
+            .await
+            .unwrap()
+    );
+}
@@ -0,0 +1,134 @@
+#![cfg(feature = "arrow")]
+
+mod scanner_common;
+
+use aeon_market_scanner_rs::arrow::{
+    opportunities_to_record_batch, opportunity_schema, price_schema, prices_to_record_batch,
+    write_prices_parquet,
+};
+use aeon_market_scanner_rs::common::TradingStatus;
+use aeon_market_scanner_rs::{ArbitrageScanner, CexExchange};
+use arrow::array::{Array, Float64Array, StringArray, TimestampMillisecondArray};
+use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+use scanner_common::{CexPriceBuilder, binance_okx_fees};
+
+#[test]
+fn prices_become_record_batch_rows() {
+    let prices = [
+        CexPriceBuilder::around(CexExchange::Binance, 100.0, 0.5)
+            .bid_qty(2.0)
+            .ask_qty(3.0)
+            .at(1_000)
+            .status(TradingStatus::Trading)
+            .build(),
+        CexPriceBuilder::around(CexExchange::Htx, 101.0, 0.5)
+            .bid_qty(2.0)
+            .ask_qty(3.0)
+            .at(2_000)
+            .status(TradingStatus::Trading)
+            .build(),
+    ];
+    let batch = prices_to_record_batch(&prices).unwrap();
+    assert_eq!(batch.schema(), price_schema());
+    assert_eq!(batch.num_rows(), 2);
+
+    let exchange = batch
+        .column_by_name("exchange")
+        .unwrap()
+        .as_any()
+        .downcast_ref::<StringArray>()
+        .unwrap();
+    assert_eq!(exchange.value(0), "Binance");
+    assert_eq!(exchange.value(1), "HTX");
+
+    let ts = batch
+        .column_by_name("timestamp")
+        .unwrap()
+        .as_any()
+        .downcast_ref::<TimestampMillisecondArray>()
+        .unwrap();
+    assert_eq!(ts.value(1), 2_000);
+    assert_eq!(ts.timezone(), Some("UTC"));
+
+    let status = batch
+        .column_by_name("trading_status")
+        .unwrap()
+        .as_any()
+        .downcast_ref::<StringArray>()
+        .unwrap();
+    assert_eq!(status.value(0), "Trading");
+}
+
+#[test]
+fn opportunities_become_record_batch_rows() {
+    let fees = binance_okx_fees();
+    let prices = [
+        CexPriceBuilder::around(CexExchange::Binance, 100.0, 0.5)
+            .bid_qty(2.0)
+            .ask_qty(3.0)
+            .at(5_000)
+            .status(TradingStatus::Trading)
+            .build(),
+        CexPriceBuilder::around(CexExchange::OKX, 102.0, 0.5)
+            .bid_qty(2.0)
+            .ask_qty(3.0)
+            .at(6_000)
+            .status(TradingStatus::Trading)
+            .build(),
+    ];
+    let opps = ArbitrageScanner::opportunities_from_prices(&prices, &[], Some(&fees));
+    assert_eq!(opps.len(), 1);
+
+    let batch = opportunities_to_record_batch(&opps).unwrap();
+    assert_eq!(batch.schema(), opportunity_schema());
+    assert_eq!(batch.num_rows(), 1);
+
+    let profit = batch
+        .column_by_name("total_profit")
+        .unwrap()
+        .as_any()
+        .downcast_ref::<Float64Array>()
+        .unwrap();
+    assert!((profit.value(0) - opps[0].total_profit()).abs() < 1e-9);
+
+    let dest_ts = batch
+        .column_by_name("destination_timestamp")
+        .unwrap()
+        .as_any()
+        .downcast_ref::<TimestampMillisecondArray>()
+        .unwrap();
+    assert_eq!(dest_ts.value(0), 6_000);
+}
+
+#[test]
+fn prices_round_trip_through_parquet() {
+    let prices: Vec<_> = (0..100)
+        .map(|i| {
+            CexPriceBuilder::around(CexExchange::Kraken, 100.0 + i as f64, 0.5)
+                .bid_qty(2.0)
+                .ask_qty(3.0)
+                .at(i * 1000)
+                .status(TradingStatus::Trading)
+                .build()
+        })
+        .collect();
+    let path = std::env::temp_dir().join(format!("aeon_prices_{}.parquet", std::process::id()));
+    write_prices_parquet(std::fs::File::create(&path).unwrap(), &prices).unwrap();
+
+    let reader = ParquetRecordBatchReaderBuilder::try_new(std::fs::File::open(&path).unwrap())
+        .unwrap()
+        .build()
+        .unwrap();
+    let batches: Vec<_> = reader.map(|b| b.unwrap()).collect();
+    std::fs::remove_file(&path).ok();
+
+    assert_eq!(batches.iter().map(|b| b.num_rows()).sum::<usize>(), 100);
+    assert_eq!(batches[0].schema(), price_schema());
+    let mid = batches[0]
+        .column_by_name("mid_price")
+        .unwrap()
+        .as_any()
+        .downcast_ref::<Float64Array>()
+        .unwrap();
+    assert_eq!(mid.value(99), 199.0);
+}
@@ -1,5 +1,5 @@
 mod common;
-use aeon_market_scanner_rs::{Binance, CexExchange, Exchange};
+use aeon_market_scanner_rs::{Binance, CEXTrait, CexExchange, Exchange, TradingStatus};
 use common::{
     test_get_price_common, test_get_price_empty_symbol_common,
     test_get_price_invalid_symbol_common, test_health_check_common,
@@ -30,3 +30,24 @@ async fn test_binance_invalid_symbol() {
 async fn test_binance_empty_symbol() {
     test_get_price_empty_symbol_common(&Binance::new(), "Binance").await;
 }
+
+#[tokio::test]
+async fn test_binance_get_price_reports_trading_status() {
+    let price = Binance::new().get_price("BTCUSDT").await.unwrap();
+    assert_eq!(price.trading_status, TradingStatus::Trading);
+}
+
+#[tokio::test]
+async fn test_binance_get_order_book() {
+    let book = Binance::new()
+        .get_order_book("BTCUSDT", 5)
+        .await
+        .expect("Should be able to get Binance order book");
+
+    assert_eq!(book.exchange, Exchange::Cex(CexExchange::Binance));
+    assert!(!book.bids.is_empty() && book.bids.len() <= 5);
+    assert!(!book.asks.is_empty() && book.asks.len() <= 5);
+    assert!(book.bids.windows(2).all(|w| w[0].price >= w[1].price));
+    assert!(book.asks.windows(2).all(|w| w[0].price <= w[1].price));
+    assert!(book.bids[0].price <= book.asks[0].price);
+}
@@ -38,3 +38,30 @@ async fn binance_ws_stream_multi_symbol() {
     );
     println!("\nReceived {} prices from {:?}.", count, seen);
 }
+
+#[tokio::test]
+async fn binance_ws_stream_order_book() {
+    println!("\n=== Binance order book stream (BTCUSDT, snapshot + deltas) ===\n");
+
+    let exchange = Binance::new();
+    let mut rx = exchange
+        .stream_order_book("BTCUSDT", 5, 5, 5000)
+        .await
+        .expect("Order book stream");
+
+    let mut count = 0u32;
+    while let Some(book) = rx.recv().await {
+        assert!(!book.bids.is_empty() && book.bids.len() <= 5);
+        assert!(!book.asks.is_empty() && book.asks.len() <= 5);
+        assert!(book.bids[0].price < book.asks[0].price);
+        println!(
+            "{}  best bid: {:>12}  best ask: {:>12}",
+            book.symbol, book.bids[0].price, book.asks[0].price
+        );
+        count += 1;
+        if count >= 5 {
+            break;
+        }
+    }
+    assert!(count >= 5, "Expected 5 books; got {}", count);
+}
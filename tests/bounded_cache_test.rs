@@ -0,0 +1,84 @@
+mod scanner_common;
+
+use aeon_market_scanner_rs::common::BoundedCache;
+use aeon_market_scanner_rs::{CacheLimits, CexExchange, Exchange, LivePriceCache, LiveScanOptions};
+use scanner_common::mid_price;
+use std::time::Duration;
+
+#[test]
+fn test_capacity_evicts_least_recently_updated() {
+    let mut cache = BoundedCache::new(CacheLimits::new().with_max_entries(2));
+    cache.insert("a", 1);
+    cache.insert("b", 2);
+    // Updating "a" makes "b" the least recently updated
+    cache.insert("a", 3);
+    cache.insert("c", 4);
+
+    assert_eq!(cache.len(), 2);
+    assert_eq!(cache.get(&"a"), Some(&3));
+    assert_eq!(cache.get(&"b"), None);
+    assert_eq!(cache.get(&"c"), Some(&4));
+    let stats = cache.stats();
+    assert_eq!(stats.entries, 2);
+    assert_eq!(stats.max_entries, Some(2));
+    assert_eq!(stats.capacity_evictions, 1);
+    assert_eq!(stats.ttl_evictions, 0);
+
+    // Removals are not evictions; shrinking the limit is
+    cache.retain(|key, _| *key != "c");
+    cache.set_limits(CacheLimits::new().with_max_entries(0));
+    assert!(cache.is_empty());
+    assert_eq!(cache.stats().evictions(), 2);
+}
+
+#[test]
+fn test_ttl_expires_stale_entries() {
+    let mut cache = BoundedCache::new(CacheLimits::new().with_ttl(Duration::from_millis(40)));
+    cache.insert("stale", 1);
+    std::thread::sleep(Duration::from_millis(60));
+    // Expired entries are hidden before they are purged
+    assert_eq!(cache.get(&"stale"), None);
+    assert_eq!(cache.values().count(), 0);
+
+    cache.insert("fresh", 2);
+    assert_eq!(cache.len(), 1);
+    assert_eq!(cache.get(&"fresh"), Some(&2));
+    assert_eq!(cache.stats().ttl_evictions, 1);
+}
+
+#[test]
+fn test_unbounded_by_default() {
+    let mut cache = BoundedCache::new(CacheLimits::default());
+    for i in 0..1000 {
+        cache.insert(i, i);
+    }
+    assert_eq!(cache.len(), 1000);
+    assert_eq!(cache.stats().evictions(), 0);
+}
+
+#[test]
+fn test_live_price_cache_limits() {
+    let cache = LivePriceCache::with_limits(CacheLimits::new().with_max_entries(2));
+    cache.insert(mid_price(CexExchange::Binance, "BTCUSDT", 100.0));
+    cache.insert(mid_price(CexExchange::OKX, "BTCUSDT", 100.0));
+    cache.insert(mid_price(CexExchange::Bybit, "BTCUSDT", 100.0));
+
+    let prices = cache.prices("BTCUSDT");
+    assert_eq!(prices.len(), 2);
+    assert!(
+        prices
+            .iter()
+            .all(|p| p.exchange != Exchange::Cex(CexExchange::Binance))
+    );
+    assert_eq!(cache.stats().capacity_evictions, 1);
+
+    // Clones share the limits
+    let clone = cache.clone();
+    clone.set_limits(CacheLimits::new().with_max_entries(1));
+    assert_eq!(cache.prices("BTCUSDT").len(), 1);
+    assert_eq!(cache.stats().entries, 1);
+
+    let options =
+        LiveScanOptions::default().with_cache_limits(CacheLimits::new().with_max_entries(500));
+    assert_eq!(options.cache_limits.max_entries, Some(500));
+}
@@ -33,3 +33,35 @@ async fn bybit_ws_stream_multi_symbol() {
     }
     println!("\nReceived {} prices.", count);
 }
+
+#[tokio::test]
+async fn bybit_ws_stream_order_book_depth_50() {
+    println!("\n=== Bybit order book stream (BTCUSDT, orderbook.50, 50 levels) ===\n");
+
+    let exchange = Bybit::new();
+    let mut rx = exchange
+        .stream_order_book("BTCUSDT", 50, 5, 5000)
+        .await
+        .expect("Order book stream");
+
+    let mut count = 0u32;
+    while let Some(book) = rx.recv().await {
+        assert!(!book.bids.is_empty() && book.bids.len() <= 50);
+        assert!(!book.asks.is_empty() && book.asks.len() <= 50);
+        assert!(book.bids[0].price < book.asks[0].price);
+        assert!(book.bids.windows(2).all(|w| w[0].price > w[1].price));
+        println!(
+            "{}  levels: {}/{}  best bid: {:>12}  best ask: {:>12}",
+            book.symbol,
+            book.bids.len(),
+            book.asks.len(),
+            book.bids[0].price,
+            book.asks[0].price
+        );
+        count += 1;
+        if count >= 5 {
+            break;
+        }
+    }
+    assert!(count >= 5, "Expected 5 books; got {}", count);
+}
@@ -0,0 +1,112 @@
+use aeon_market_scanner_rs::{
+    Binance, Btcturk, CEXTrait, CapabilityReport, CexExchange, OKX, VenueCapability,
+};
+use std::time::Duration;
+
+#[test]
+fn venues_declare_what_they_implement() {
+    let binance = Binance::new().capabilities();
+    for capability in [
+        VenueCapability::Websocket,
+        VenueCapability::OrderBook,
+        VenueCapability::OrderBookStream,
+        VenueCapability::UserData,
+        VenueCapability::OrderLimits,
+    ] {
+        assert!(binance.supports(capability), "{capability:?}");
+    }
+    assert!(!binance.supports(VenueCapability::Execution));
+
+    let okx = OKX::new().capabilities();
+    assert!(okx.supports(VenueCapability::Execution));
+    assert!(okx.supports(VenueCapability::UserData));
+
+    let btcturk = Btcturk::new().capabilities();
+    assert!(!btcturk.supports(VenueCapability::Websocket));
+    assert!(btcturk.supports(VenueCapability::OrderBook));
+}
+
+#[test]
+fn report_covers_every_venue_and_agrees_with_websocket_support() {
+    let report = CapabilityReport::new();
+    assert_eq!(report.venues.len(), CexExchange::ALL.len());
+    assert_eq!(
+        report.venues_with(VenueCapability::Execution),
+        vec![CexExchange::Bybit, CexExchange::OKX, CexExchange::Bitget]
+    );
+    // Every venue exposes its clock; no venue streams trades or futures yet
+    assert_eq!(
+        report.venues_with(VenueCapability::ServerTime).len(),
+        CexExchange::ALL.len()
+    );
+    assert!(report.venues_with(VenueCapability::Trades).is_empty());
+    assert!(report.venues_with(VenueCapability::Futures).is_empty());
+    let websocket = report.venues_with(VenueCapability::Websocket);
+    assert!(!websocket.contains(&CexExchange::Htx));
+    assert_eq!(websocket.len(), 12);
+}
+
+#[test]
+fn report_renders_markdown_and_csv() {
+    let report = CapabilityReport::for_venues(&[CexExchange::Binance, CexExchange::Htx]);
+
+    let markdown = report.to_markdown();
+    let lines: Vec<&str> = markdown.lines().collect();
+    assert_eq!(lines.len(), 5);
+    assert!(lines[0].starts_with("| venue | websocket | order_book |"));
+    assert!(!lines[0].contains("health"));
+    assert!(lines[2].starts_with("| Binance | yes | yes | yes | - |"));
+    assert!(lines[3].starts_with("| Htx | - | yes | - |"));
+    assert!(lines[4].starts_with("| **total** | 1 | 2 | 1 | 0 |"));
+
+    let csv = report.to_csv();
+    let mut rows = csv.lines();
+    assert_eq!(
+        rows.next().unwrap(),
+        "venue,websocket,order_book,order_book_stream,trades,futures,execution,user_data,\
+         order_limits,instrument_ids,server_time"
+    );
+    assert_eq!(
+        rows.next().unwrap(),
+        "Binance,true,true,true,false,false,false,true,true,false,true"
+    );
+
+    let json = serde_json::to_value(&report).unwrap();
+    assert_eq!(json["venues"][1]["exchange"], "Htx");
+}
+
+#[tokio::test]
+async fn health_checks_run_concurrently_and_time_out() {
+    let report = CapabilityReport::for_venues(&[CexExchange::Binance, CexExchange::Kraken])
+        .with_health_checks(Duration::from_millis(1))
+        .await;
+    // No venue answers within 1 ms
+    assert_eq!(report.unhealthy().len(), 2);
+    let health = report
+        .venue(&CexExchange::Kraken)
+        .unwrap()
+        .health
+        .as_ref()
+        .unwrap();
+    assert!(!health.healthy);
+    assert!(health.error.is_some());
+
+    assert!(
+        report
+            .to_markdown()
+            .lines()
+            .next()
+            .unwrap()
+            .ends_with(" health |")
+    );
+    assert!(report.to_markdown().contains("| 0 ok |"));
+    assert!(report.to_csv().starts_with("venue,websocket"));
+    assert!(
+        report
+            .to_csv()
+            .lines()
+            .next()
+            .unwrap()
+            .ends_with(",healthy,health_latency_ms")
+    );
+}
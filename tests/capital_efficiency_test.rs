@@ -0,0 +1,83 @@
+mod scanner_common;
+
+use aeon_market_scanner_rs::{
+    ArbitrageOpportunity, ArbitrageScanner, CapitalEfficiencyModel, CexExchange, Exchange,
+    FundingModel, OpportunityRanking, ScannerContext, TransferCostModel,
+};
+use scanner_common::{binance_okx_fees, sized_price};
+
+/// Big ETH opportunity (10 @ 100 -> 101, profit 10) and small SOL one (1 @ 100 -> 105,
+/// profit 5), both Binance -> OKX.
+fn opportunities() -> Vec<ArbitrageOpportunity> {
+    let prices = [
+        sized_price(CexExchange::Binance, "ETHUSDT", 99.0, 100.0, 10.0),
+        sized_price(CexExchange::OKX, "ETHUSDT", 101.0, 102.0, 10.0),
+        sized_price(CexExchange::Binance, "SOLUSDT", 99.0, 100.0, 1.0),
+        sized_price(CexExchange::OKX, "SOLUSDT", 105.0, 106.0, 1.0),
+    ];
+    ArbitrageScanner::opportunities_from_prices(&prices, &[], Some(&binance_okx_fees()))
+        .into_iter()
+        .filter(|o| o.source_exchange == "Binance")
+        .collect()
+}
+
+fn symbols(opportunities: &[ArbitrageOpportunity]) -> Vec<&str> {
+    opportunities.iter().map(|o| o.symbol.as_str()).collect()
+}
+
+#[test]
+fn small_high_yield_opportunities_outrank_big_ones() {
+    let mut opportunities = opportunities();
+    OpportunityRanking::TotalProfit.sort(&mut opportunities);
+    assert_eq!(symbols(&opportunities), ["ETHUSDT", "SOLUSDT"]);
+
+    let model = CapitalEfficiencyModel::new();
+    let eth = model.evaluate(&opportunities[0]).unwrap();
+    assert!((eth.notional - 1_000.0).abs() < 1e-9);
+    assert_eq!(eth.transfer_buffer, 0.0);
+    assert!((eth.ratio - 0.01).abs() < 1e-9);
+
+    OpportunityRanking::from(model).sort(&mut opportunities);
+    assert_eq!(symbols(&opportunities), ["SOLUSDT", "ETHUSDT"]);
+}
+
+#[test]
+fn shortfalls_and_transfer_fees_lock_extra_capital() {
+    let opportunities = opportunities();
+    let sol = opportunities
+        .iter()
+        .find(|o| o.symbol == "SOLUSDT")
+        .unwrap();
+    let binance = Exchange::Cex(CexExchange::Binance);
+    let okx = Exchange::Cex(CexExchange::OKX);
+
+    // 60 USDT on Binance, no SOL on OKX: 40 USDT and 1 SOL must be moved in first
+    let model = CapitalEfficiencyModel::new()
+        .with_funding_model(FundingModel::default().with_balance(binance.clone(), "USDT", 60.0))
+        .with_transfer_costs(
+            TransferCostModel::default()
+                .with_network("SOL", "SOL", 0.01, 60)
+                .with_venue_networks(okx.clone(), "USDT", &["TRC20"]),
+        );
+    let efficiency = model.evaluate(sol).unwrap();
+    // Rebalancing fees: 0.01 SOL (1 USDT) + 1 USDT over TRC20
+    assert!((efficiency.net_profit - 3.0).abs() < 1e-9);
+    assert!((efficiency.transfer_buffer - (40.0 + 100.0 + 2.0)).abs() < 1e-9);
+    assert!((efficiency.ratio - 3.0 / 242.0).abs() < 1e-9);
+
+    // No network in common: ranked last
+    let unroutable = CapitalEfficiencyModel::new().with_transfer_costs(
+        TransferCostModel::default().with_venue_networks(okx, "USDT", &["SOMECHAIN"]),
+    );
+    assert!(unroutable.evaluate(sol).is_none());
+}
+
+#[test]
+fn contexts_apply_their_ranking() {
+    let context = ScannerContext::new("constrained")
+        .with_fee_overrides(binance_okx_fees())
+        .with_ranking(CapitalEfficiencyModel::new().into())
+        .with_filter(|o| o.source_exchange == "Binance");
+    let ranked = context.process(opportunities());
+    assert_eq!(symbols(&ranked), ["SOLUSDT", "ETHUSDT"]);
+}
@@ -0,0 +1,49 @@
+use aeon_market_scanner_rs::CexExchange;
+use aeon_market_scanner_rs::common::ccxt::{
+    ccxt_id, cex_from_ccxt_id, symbol_from_ccxt, symbol_to_ccxt,
+};
+
+#[test]
+fn exchange_ids_round_trip() {
+    for exchange in [
+        CexExchange::Binance,
+        CexExchange::Bybit,
+        CexExchange::MEXC,
+        CexExchange::OKX,
+        CexExchange::Gateio,
+        CexExchange::Kucoin,
+        CexExchange::Bitget,
+        CexExchange::Btcturk,
+        CexExchange::Htx,
+        CexExchange::Coinbase,
+        CexExchange::Kraken,
+        CexExchange::Bitfinex,
+        CexExchange::Upbit,
+        CexExchange::Cryptocom,
+    ] {
+        assert_eq!(cex_from_ccxt_id(ccxt_id(&exchange)), Some(exchange));
+    }
+
+    // Legacy ids and case
+    assert_eq!(cex_from_ccxt_id("huobi"), Some(CexExchange::Htx));
+    assert_eq!(cex_from_ccxt_id("OKX"), Some(CexExchange::OKX));
+    assert_eq!(cex_from_ccxt_id("gate"), Some(CexExchange::Gateio));
+    assert_eq!(cex_from_ccxt_id("deribit"), None);
+}
+
+#[test]
+fn symbols_round_trip() {
+    assert_eq!(symbol_from_ccxt("BTC/USDT").unwrap(), "BTCUSDT");
+    assert_eq!(symbol_from_ccxt("eth/btc").unwrap(), "ETHBTC");
+    assert_eq!(symbol_to_ccxt("BTCUSDT").unwrap(), "BTC/USDT");
+    assert_eq!(symbol_to_ccxt("ETH-USDC").unwrap(), "ETH/USDC");
+    assert_eq!(symbol_to_ccxt("BTCKRW").unwrap(), "BTC/KRW");
+
+    // Venue tickers map to ccxt common currency codes
+    assert_eq!(symbol_to_ccxt("XBTEUR").unwrap(), "BTC/EUR");
+    assert_eq!(symbol_to_ccxt("BTCUST").unwrap(), "BTC/USDT");
+
+    assert!(symbol_from_ccxt("BTC/USDT:USDT").is_err());
+    assert!(symbol_from_ccxt("BTCUSDT").is_err());
+    assert!(symbol_from_ccxt("/USDT").is_err());
+}
@@ -0,0 +1,444 @@
+mod scanner_common;
+
+use aeon_market_scanner_rs::cex::bybit::BYBIT_DEMO_API_BASE;
+use aeon_market_scanner_rs::cex::execution::{
+    CexOrder, CexOrderAck, CexOrderExecution, OrderStatus, OrderType, TradingMode,
+    hmac_sha256_base64, hmac_sha256_hex,
+};
+use aeon_market_scanner_rs::common::{Credentials, OrderLimits, Timestamp};
+use aeon_market_scanner_rs::{
+    ArbitrageScanner, Bitget, Bybit, CexExchange, MarketScannerError, OKX, TradeSide,
+};
+use reqwest::Method;
+use scanner_common::{price, zero_taker_fees};
+use serde_json::{Value, json};
+
+const TIMESTAMP_MS: u64 = 1_700_000_000_123;
+
+fn credentials() -> Credentials {
+    Credentials::new("test-key", "test-secret").with_passphrase("test-pass")
+}
+
+fn body(request_body: &str) -> Value {
+    serde_json::from_str(request_body).unwrap()
+}
+
+#[test]
+fn hmac_matches_known_vector() {
+    let message = "The quick brown fox jumps over the lazy dog";
+    assert_eq!(
+        hmac_sha256_hex("key", message),
+        "f7bc83f430538424b13298e6aa6fb143ef4d59a14946175997479dbc2d1a3cd8"
+    );
+    assert_eq!(
+        hmac_sha256_base64("key", message),
+        "97yD9DBThCSxMpjmqm+xQ+9NWaFJRhdZl0edvC0aPNg="
+    );
+}
+
+#[test]
+fn okx_demo_orders_set_simulated_trading_header() {
+    let order =
+        CexOrder::limit("BTCUSDT", TradeSide::Buy, 0.01, 50_000.5).with_client_order_id("a1");
+    let okx = OKX::new();
+    let request = okx
+        .order_request(&credentials(), &order, TradingMode::Demo, TIMESTAMP_MS)
+        .unwrap();
+
+    assert_eq!(request.url, "https://www.okx.com/api/v5/trade/order");
+    assert_eq!(request.header("x-simulated-trading"), Some("1"));
+    assert_eq!(
+        request.header("OK-ACCESS-TIMESTAMP"),
+        Some("2023-11-14T22:13:20.123Z")
+    );
+    assert_eq!(request.header("OK-ACCESS-PASSPHRASE"), Some("test-pass"));
+    let expected = hmac_sha256_base64(
+        "test-secret",
+        &format!(
+            "2023-11-14T22:13:20.123ZPOST/api/v5/trade/order{}",
+            request.body
+        ),
+    );
+    assert_eq!(request.header("OK-ACCESS-SIGN"), Some(expected.as_str()));
+
+    let body = body(&request.body);
+    assert_eq!(body["instId"], "BTC-USDT");
+    assert_eq!(body["side"], "buy");
+    assert_eq!(body["ordType"], "limit");
+    assert_eq!(body["sz"], "0.01");
+    assert_eq!(body["px"], "50000.5");
+    assert_eq!(body["clOrdId"], "a1");
+
+    let live = okx
+        .order_request(&credentials(), &order, TradingMode::Live, TIMESTAMP_MS)
+        .unwrap();
+    assert!(live.header("x-simulated-trading").is_none());
+    // Signing material stays out of Debug output
+    assert!(!format!("{:?}", live).contains("test-key"));
+}
+
+#[test]
+fn okx_market_orders_are_sized_in_base() {
+    let order = CexOrder::market("ETHUSDT", TradeSide::Buy, 1.5);
+    let request = OKX::new()
+        .order_request(&credentials(), &order, TradingMode::Live, TIMESTAMP_MS)
+        .unwrap();
+    let body = body(&request.body);
+    assert_eq!(body["ordType"], "market");
+    assert_eq!(body["tgtCcy"], "base_ccy");
+    assert!(body.get("px").is_none());
+}
+
+#[test]
+fn order_amounts_are_rounded_to_venue_increments() {
+    // Float noise never reaches the venue, even without known increments
+    let noisy = CexOrder::limit("BTCUSDT", TradeSide::Buy, 0.1 + 0.2, 50_000.1 + 0.2);
+    let request = OKX::new()
+        .order_request(&credentials(), &noisy, TradingMode::Live, TIMESTAMP_MS)
+        .unwrap();
+    let okx_body = body(&request.body);
+    assert_eq!(okx_body["sz"], "0.3");
+    assert_eq!(okx_body["px"], "50000.3");
+
+    let limits = OrderLimits {
+        quantity_step: Some(0.001),
+        price_tick: Some(0.5),
+        ..OrderLimits::default()
+    };
+    let order = CexOrder::limit("BTCUSDT", TradeSide::Sell, 0.12345, 60_000.3).with_limits(&limits);
+    let request = Bybit::new()
+        .order_request(&credentials(), &order, TradingMode::Live, TIMESTAMP_MS)
+        .unwrap();
+    let body = body(&request.body);
+    assert_eq!(body["qty"], "0.123");
+    assert_eq!(body["price"], "60000.5");
+}
+
+#[test]
+fn rounding_never_exceeds_the_planned_order() {
+    let limits = OrderLimits {
+        quantity_step: Some(0.001),
+        price_tick: Some(0.5),
+        ..OrderLimits::default()
+    };
+    // Quantities round down, buy prices down and sell prices up
+    let buy = CexOrder::limit("BTCUSDT", TradeSide::Buy, 0.0019, 60_000.3).with_limits(&limits);
+    let rounded = buy.rounded().unwrap();
+    assert!((rounded.quantity - 0.001).abs() < 1e-12);
+    assert_eq!(rounded.price, Some(60_000.0));
+    let request = OKX::new()
+        .order_request(&credentials(), &buy, TradingMode::Live, TIMESTAMP_MS)
+        .unwrap();
+    let okx_body = body(&request.body);
+    assert_eq!(okx_body["sz"], "0.001");
+    assert_eq!(okx_body["px"], "60000");
+
+    // Amounts already on the step are kept despite float noise
+    let exact = CexOrder::limit("BTCUSDT", TradeSide::Sell, 0.003, 60_000.5).with_limits(&limits);
+    let rounded = exact.rounded().unwrap();
+    assert_eq!(rounded.quantity, 0.003);
+    assert_eq!(rounded.price, Some(60_000.5));
+
+    // The ack holds the amounts that were sent
+    let response = json!({"code": "0", "data": [{"ordId": "1", "clOrdId": ""}]});
+    let ack = OKX::new()
+        .parse_order_response(&response, &buy, TradingMode::Live)
+        .unwrap();
+    assert!((ack.quantity - 0.001).abs() < 1e-12);
+    assert_eq!(ack.price, Some(60_000.0));
+
+    // Below one step nothing is sent
+    let dust = CexOrder::market("BTCUSDT", TradeSide::Sell, 0.0004).with_limits(&limits);
+    assert!(matches!(
+        Bybit::new().order_request(&credentials(), &dust, TradingMode::Live, TIMESTAMP_MS),
+        Err(MarketScannerError::InvalidConfig(_))
+    ));
+}
+
+#[test]
+fn invalid_amounts_are_config_errors() {
+    for order in [
+        CexOrder::limit("BTCUSDT", TradeSide::Buy, 0.0, 50_000.0),
+        CexOrder::limit("BTCUSDT", TradeSide::Buy, f64::INFINITY, 50_000.0),
+        CexOrder::limit("BTCUSDT", TradeSide::Buy, 0.01, -1.0),
+        CexOrder::limit("BTCUSDT", TradeSide::Buy, 0.01, f64::NAN),
+    ] {
+        assert!(matches!(
+            OKX::new().order_request(&credentials(), &order, TradingMode::Live, TIMESTAMP_MS),
+            Err(MarketScannerError::InvalidConfig(_))
+        ));
+    }
+}
+
+#[test]
+fn bybit_demo_orders_use_demo_host() {
+    let order = CexOrder::market("BTCUSDT", TradeSide::Sell, 0.002);
+    let bybit = Bybit::new();
+    let request = bybit
+        .order_request(&credentials(), &order, TradingMode::Demo, TIMESTAMP_MS)
+        .unwrap();
+
+    assert_eq!(request.url, format!("{}/order/create", BYBIT_DEMO_API_BASE));
+    assert_eq!(request.header("X-BAPI-TIMESTAMP"), Some("1700000000123"));
+    let expected = hmac_sha256_hex(
+        "test-secret",
+        &format!("1700000000123test-key5000{}", request.body),
+    );
+    assert_eq!(request.header("X-BAPI-SIGN"), Some(expected.as_str()));
+    let body = body(&request.body);
+    assert_eq!(body["category"], "spot");
+    assert_eq!(body["side"], "Sell");
+    assert_eq!(body["orderType"], "Market");
+    assert_eq!(body["qty"], "0.002");
+
+    let live = bybit
+        .order_request(&credentials(), &order, TradingMode::Live, TIMESTAMP_MS)
+        .unwrap();
+    assert_eq!(live.url, "https://api.bybit.com/v5/order/create");
+}
+
+#[test]
+fn bitget_demo_orders_set_paper_trading_header() {
+    let order = CexOrder::limit("BTCUSDT", TradeSide::Sell, 0.5, 60_000.0);
+    let bitget = Bitget::new();
+    let request = bitget
+        .order_request(&credentials(), &order, TradingMode::Demo, TIMESTAMP_MS)
+        .unwrap();
+
+    assert_eq!(request.header("paptrading"), Some("1"));
+    let expected = hmac_sha256_base64(
+        "test-secret",
+        &format!(
+            "1700000000123POST/api/v2/spot/trade/place-order{}",
+            request.body
+        ),
+    );
+    assert_eq!(request.header("ACCESS-SIGN"), Some(expected.as_str()));
+    assert_eq!(body(&request.body)["price"], "60000");
+
+    // Market buys would need a quote amount
+    let market_buy = CexOrder::market("BTCUSDT", TradeSide::Buy, 0.5);
+    assert!(
+        bitget
+            .order_request(&credentials(), &market_buy, TradingMode::Demo, TIMESTAMP_MS)
+            .is_err()
+    );
+}
+
+#[test]
+fn invalid_orders_and_credentials_are_rejected() {
+    let okx = OKX::new();
+    let no_passphrase = Credentials::new("k", "s");
+    let order = CexOrder::limit("BTCUSDT", TradeSide::Buy, 1.0, 100.0);
+    assert!(matches!(
+        okx.order_request(&no_passphrase, &order, TradingMode::Demo, TIMESTAMP_MS),
+        Err(MarketScannerError::InvalidConfig(_))
+    ));
+
+    let mut no_price = order.clone();
+    no_price.price = None;
+    assert!(
+        okx.order_request(&credentials(), &no_price, TradingMode::Demo, TIMESTAMP_MS)
+            .is_err()
+    );
+    let zero_quantity = CexOrder::market("BTCUSDT", TradeSide::Sell, 0.0);
+    assert!(
+        Bybit::new()
+            .order_request(
+                &credentials(),
+                &zero_quantity,
+                TradingMode::Demo,
+                TIMESTAMP_MS
+            )
+            .is_err()
+    );
+}
+
+#[test]
+fn order_responses_are_parsed() {
+    let order = CexOrder::limit("BTCUSDT", TradeSide::Buy, 0.01, 50_000.0);
+
+    let ack = OKX::new()
+        .parse_order_response(
+            &json!({"code": "0", "msg": "", "data": [{"ordId": "123", "clOrdId": "a1", "sCode": "0", "sMsg": ""}]}),
+            &order,
+            TradingMode::Demo,
+        )
+        .unwrap();
+    assert_eq!(ack.order_id, "123");
+    assert_eq!(ack.client_order_id.as_deref(), Some("a1"));
+    assert_eq!(ack.exchange, CexExchange::OKX);
+    assert_eq!(ack.mode, TradingMode::Demo);
+
+    let rejected = OKX::new().parse_order_response(
+        &json!({"code": "1", "msg": "All operations failed", "data": [{"ordId": "", "sCode": "51008", "sMsg": "Insufficient balance"}]}),
+        &order,
+        TradingMode::Demo,
+    );
+    assert!(
+        matches!(rejected, Err(MarketScannerError::ApiError(e)) if e.contains("Insufficient balance"))
+    );
+
+    let ack = Bybit::new()
+        .parse_order_response(
+            &json!({"retCode": 0, "retMsg": "OK", "result": {"orderId": "b-1", "orderLinkId": ""}}),
+            &order,
+            TradingMode::Live,
+        )
+        .unwrap();
+    assert_eq!(ack.order_id, "b-1");
+    assert!(ack.client_order_id.is_none());
+    assert!(
+        Bybit::new()
+            .parse_order_response(
+                &json!({"retCode": 10001, "retMsg": "params error", "result": {}}),
+                &order,
+                TradingMode::Live,
+            )
+            .is_err()
+    );
+
+    let ack = Bitget::new()
+        .parse_order_response(
+            &json!({"code": "00000", "msg": "success", "data": {"orderId": "g-1", "clientOid": "c-1"}}),
+            &order,
+            TradingMode::Demo,
+        )
+        .unwrap();
+    assert_eq!(ack.order_id, "g-1");
+    assert_eq!(ack.client_order_id.as_deref(), Some("c-1"));
+}
+
+#[test]
+fn opportunity_legs_become_limit_orders() {
+    let fees = zero_taker_fees(&[CexExchange::Bybit, CexExchange::OKX]);
+    let prices = [
+        price(CexExchange::Bybit, 99.0, 100.0),
+        price(CexExchange::OKX, 110.0, 111.0),
+    ];
+    let opportunity = ArbitrageScanner::opportunities_from_prices(&prices, &[], Some(&fees))
+        .into_iter()
+        .find(|o| o.source_exchange == "Bybit")
+        .unwrap();
+
+    // Capped at the executable quantity
+    let (buy, sell) = CexOrder::from_opportunity(&opportunity, 5.0).unwrap();
+    assert_eq!(buy.side, TradeSide::Buy);
+    assert_eq!(buy.order_type, OrderType::Limit);
+    assert_eq!(buy.price, Some(100.0));
+    assert_eq!(buy.quantity, 1.0);
+    assert_eq!(sell.side, TradeSide::Sell);
+    assert_eq!(sell.price, Some(110.0));
+}
+
+fn ack(exchange: CexExchange, mode: TradingMode) -> CexOrderAck {
+    CexOrderAck {
+        exchange,
+        symbol: "BTCUSDT".to_string(),
+        order_id: "42".to_string(),
+        client_order_id: None,
+        side: TradeSide::Buy,
+        quantity: 0.5,
+        price: Some(60_000.0),
+        mode,
+        timestamp: Timestamp::from_millis(1),
+    }
+}
+
+#[test]
+fn status_and_cancel_requests_are_signed() {
+    let okx_ack = ack(CexExchange::OKX, TradingMode::Demo);
+    let request = OKX::new()
+        .order_status_request(&credentials(), &okx_ack, TIMESTAMP_MS)
+        .unwrap();
+    assert_eq!(request.method, Method::GET);
+    assert_eq!(
+        request.url,
+        "https://www.okx.com/api/v5/trade/order?instId=BTC-USDT&ordId=42"
+    );
+    assert!(request.body.is_empty());
+    assert_eq!(request.header("x-simulated-trading"), Some("1"));
+    let expected = hmac_sha256_base64(
+        "test-secret",
+        "2023-11-14T22:13:20.123ZGET/api/v5/trade/order?instId=BTC-USDT&ordId=42",
+    );
+    assert_eq!(request.header("OK-ACCESS-SIGN"), Some(expected.as_str()));
+
+    let bybit_ack = ack(CexExchange::Bybit, TradingMode::Demo);
+    let request = Bybit::new()
+        .order_status_request(&credentials(), &bybit_ack, TIMESTAMP_MS)
+        .unwrap();
+    assert_eq!(
+        request.url,
+        format!(
+            "{}/order/realtime?category=spot&symbol=BTCUSDT&orderId=42",
+            BYBIT_DEMO_API_BASE
+        )
+    );
+    let expected = hmac_sha256_hex(
+        "test-secret",
+        "1700000000123test-key5000category=spot&symbol=BTCUSDT&orderId=42",
+    );
+    assert_eq!(request.header("X-BAPI-SIGN"), Some(expected.as_str()));
+    let cancel = Bybit::new()
+        .cancel_request(&credentials(), &bybit_ack, TIMESTAMP_MS)
+        .unwrap();
+    assert_eq!(cancel.method, Method::POST);
+    assert_eq!(body(&cancel.body)["orderId"], "42");
+
+    let bitget_ack = ack(CexExchange::Bitget, TradingMode::Live);
+    let cancel = Bitget::new()
+        .cancel_request(&credentials(), &bitget_ack, TIMESTAMP_MS)
+        .unwrap();
+    assert_eq!(
+        cancel.url,
+        "https://api.bitget.com/api/v2/spot/trade/cancel-order"
+    );
+    assert!(cancel.header("paptrading").is_none());
+}
+
+#[test]
+fn order_status_responses_are_parsed() {
+    let update = OKX::new()
+        .parse_order_status(
+            &json!({"code": "0", "msg": "", "data": [{
+                "instId": "BTC-USDT", "ordId": "42", "clOrdId": "", "side": "buy",
+                "state": "partially_filled", "px": "60000", "sz": "0.5", "accFillSz": "0.2",
+                "avgPx": "59990", "uTime": "1700000000000"
+            }]}),
+            &ack(CexExchange::OKX, TradingMode::Live),
+        )
+        .unwrap();
+    assert_eq!(update.status, OrderStatus::PartiallyFilled);
+    assert_eq!(update.filled_quantity, 0.2);
+
+    let update = Bitget::new()
+        .parse_order_status(
+            &json!({"code": "00000", "msg": "success", "data": [{
+                "symbol": "BTCUSDT", "orderId": "42", "clientOid": "c-1", "side": "buy",
+                "status": "cancelled", "price": "60000", "size": "0.5", "baseVolume": "0.1",
+                "priceAvg": "60000", "uTime": "1700000000000"
+            }]}),
+            &ack(CexExchange::Bitget, TradingMode::Live),
+        )
+        .unwrap();
+    assert_eq!(update.status, OrderStatus::Canceled);
+    assert_eq!(update.client_order_id.as_deref(), Some("c-1"));
+
+    assert!(
+        Bybit::new()
+            .parse_order_status(
+                &json!({"retCode": 0, "retMsg": "OK", "result": {"list": []}}),
+                &ack(CexExchange::Bybit, TradingMode::Live),
+            )
+            .is_err()
+    );
+    assert!(
+        Bybit::new()
+            .parse_cancel_response(
+                &json!({"retCode": 170213, "retMsg": "Order does not exist.", "result": {}}),
+                &ack(CexExchange::Bybit, TradingMode::Live),
+            )
+            .is_err()
+    );
+}
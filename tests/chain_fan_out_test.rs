@@ -0,0 +1,83 @@
+use aeon_market_scanner_rs::common::remove_rate_limit;
+use aeon_market_scanner_rs::dex::chains::{ChainId, Token};
+use aeon_market_scanner_rs::{ArbitrageScanner, ChainFanOut, DexAggregator};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+
+/// Job that records the highest number of jobs of its chain running at once.
+async fn job(running: Arc<AtomicUsize>, peak: Arc<AtomicUsize>) {
+    let now = running.fetch_add(1, Ordering::SeqCst) + 1;
+    peak.fetch_max(now, Ordering::SeqCst);
+    tokio::time::sleep(Duration::from_millis(20)).await;
+    running.fetch_sub(1, Ordering::SeqCst);
+}
+
+#[tokio::test]
+async fn jobs_are_limited_per_chain() {
+    let fan_out = ChainFanOut::new()
+        .with_default_concurrency(3)
+        .with_chain_concurrency(ChainId::ETHEREUM, 2);
+    assert_eq!(fan_out.concurrency(&ChainId::ETHEREUM), 2);
+    assert_eq!(fan_out.concurrency(&ChainId::BSC), 3);
+
+    let (eth_running, eth_peak) = (Arc::new(AtomicUsize::new(0)), Arc::new(AtomicUsize::new(0)));
+    let (bsc_running, bsc_peak) = (Arc::new(AtomicUsize::new(0)), Arc::new(AtomicUsize::new(0)));
+    let mut jobs = Vec::new();
+    for _ in 0..6 {
+        jobs.push((
+            ChainId::ETHEREUM,
+            job(eth_running.clone(), eth_peak.clone()),
+        ));
+        jobs.push((ChainId::BSC, job(bsc_running.clone(), bsc_peak.clone())));
+    }
+    assert_eq!(fan_out.run(jobs).await.len(), 12);
+
+    assert_eq!(eth_peak.load(Ordering::SeqCst), 2);
+    assert_eq!(bsc_peak.load(Ordering::SeqCst), 3);
+}
+
+#[tokio::test]
+async fn outputs_keep_input_order() {
+    let jobs = (0..5u64)
+        .map(|i| {
+            (ChainId::ARBITRUM, async move {
+                // Later jobs finish first
+                tokio::time::sleep(Duration::from_millis(50 - i * 10)).await;
+                i
+            })
+        })
+        .collect();
+    let outputs = ChainFanOut::new()
+        .with_default_concurrency(0)
+        .run(jobs)
+        .await;
+    assert_eq!(outputs, [0, 1, 2, 3, 4]);
+}
+
+#[tokio::test]
+async fn aggregator_budgets_become_venue_rate_limits() {
+    ChainFanOut::new()
+        .with_aggregator_budget(DexAggregator::KyberSwap, 10, Duration::from_secs(1))
+        .apply_aggregator_budgets();
+    assert_eq!(
+        remove_rate_limit("KyberSwap"),
+        Some(Duration::from_millis(100))
+    );
+
+    let token = Token::new(
+        "0x1".to_string(),
+        "ETH".to_string(),
+        "ETH".to_string(),
+        18,
+        ChainId::ETHEREUM,
+    );
+    let quotes = ArbitrageScanner::fetch_dex_prices_across_chains(
+        &[(token.clone(), token)],
+        &[],
+        1_000.0,
+        &ChainFanOut::new(),
+    )
+    .await;
+    assert!(quotes.is_empty());
+}
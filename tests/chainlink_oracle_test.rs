@@ -0,0 +1,131 @@
+mod scanner_common;
+
+use aeon_market_scanner_rs::CexExchange;
+use aeon_market_scanner_rs::PriceData;
+use aeon_market_scanner_rs::common::Timestamp;
+use aeon_market_scanner_rs::dex::chains::ChainId;
+use aeon_market_scanner_rs::dex::oracles::{
+    ChainlinkOracle, OraclePrice, OracleSource, chainlink_usd_feed, decode_latest_round_data,
+};
+use ethers::core::types::{Bytes, I256, U256};
+use ethers::providers::Provider;
+use scanner_common::CexPriceBuilder;
+use std::time::Duration;
+
+const ETH_USD_FEED: &str = "0x5f4eC3Df9cbd43714FE2740f5E3616155c5b8419";
+
+fn round_data(answer: I256, updated_at: u64) -> Bytes {
+    let words = [
+        U256::from(42u64),
+        answer.into_raw(),
+        U256::from(updated_at),
+        U256::from(updated_at),
+        U256::from(42u64),
+    ];
+    let mut out = vec![0u8; 5 * 32];
+    for (i, word) in words.iter().enumerate() {
+        word.to_big_endian(&mut out[i * 32..(i + 1) * 32]);
+    }
+    Bytes::from(out)
+}
+
+fn decimals(decimals: u8) -> Bytes {
+    let mut out = vec![0u8; 32];
+    out[31] = decimals;
+    Bytes::from(out)
+}
+
+fn cex(symbol: &str, mid: f64) -> PriceData {
+    PriceData::Cex(
+        CexPriceBuilder::around(CexExchange::Binance, mid, 0.0)
+            .symbol(symbol)
+            .now()
+            .build(),
+    )
+}
+
+#[test]
+fn test_decode_latest_round_data() {
+    let round =
+        decode_latest_round_data(&round_data(I256::from(250_012_345_678i64), 1_700_000_000))
+            .unwrap();
+    assert_eq!(round.round_id, 42);
+    assert_eq!(round.answer, 250_012_345_678);
+    assert_eq!(round.updated_at, 1_700_000_000);
+
+    let negative = decode_latest_round_data(&round_data(I256::from(-5i64), 1)).unwrap();
+    assert_eq!(negative.answer, -5);
+
+    assert!(decode_latest_round_data(&[0u8; 64]).is_err());
+}
+
+#[test]
+fn test_known_feeds() {
+    assert_eq!(
+        chainlink_usd_feed(&ChainId::ETHEREUM, "eth"),
+        Some(ETH_USD_FEED)
+    );
+    assert_eq!(
+        chainlink_usd_feed(&ChainId::ETHEREUM, "WETH"),
+        chainlink_usd_feed(&ChainId::ETHEREUM, "ETH")
+    );
+    assert_eq!(chainlink_usd_feed(&ChainId::RONIN, "ETH"), None);
+}
+
+#[tokio::test]
+async fn test_get_price_scales_answer_and_caches_decimals() {
+    let (provider, mock) = Provider::mocked();
+    let now = Timestamp::now().as_secs();
+    // Responses are served last-in first-out: decimals, then two rounds
+    mock.push::<Bytes, _>(round_data(I256::from(251_000_000_000i64), now))
+        .unwrap();
+    mock.push::<Bytes, _>(round_data(I256::from(250_000_000_000i64), now))
+        .unwrap();
+    mock.push::<Bytes, _>(decimals(8)).unwrap();
+
+    let oracle = ChainlinkOracle::new(provider).with_feed("ETH-USD", ETH_USD_FEED);
+    let price = oracle.get_price("ethusd").await.unwrap();
+    assert_eq!(price.symbol, "ETHUSD");
+    assert_eq!(price.source, OracleSource::Chainlink);
+    assert_eq!(price.feed, ETH_USD_FEED);
+    assert!((price.price - 2500.0).abs() < 1e-9);
+
+    // decimals() is not queried again
+    let price = oracle.get_price("ETHUSD").await.unwrap();
+    assert!((price.price - 2510.0).abs() < 1e-9);
+}
+
+#[tokio::test]
+async fn test_get_price_rejects_stale_and_unknown() {
+    let (provider, mock) = Provider::mocked();
+    let two_hours_ago = Timestamp::now().as_secs() - 7200;
+    mock.push::<Bytes, _>(round_data(I256::from(250_000_000_000i64), two_hours_ago))
+        .unwrap();
+    mock.push::<Bytes, _>(decimals(8)).unwrap();
+
+    let oracle = ChainlinkOracle::new(provider)
+        .with_feed("ETHUSD", ETH_USD_FEED)
+        .with_max_staleness(Duration::from_secs(3600));
+    assert!(oracle.get_price("ETHUSD").await.is_err());
+    assert!(oracle.get_price("BTCUSD").await.is_err());
+}
+
+#[test]
+fn test_oracle_price_as_reference() {
+    let oracle = OraclePrice {
+        symbol: "ETHUSDT".to_string(),
+        price: 2000.0,
+        source: OracleSource::Chainlink,
+        feed: ETH_USD_FEED.to_string(),
+        updated_at: Timestamp::now(),
+        confidence: None,
+    };
+    assert!((oracle.deviation(2010.0) - 0.005).abs() < 1e-12);
+    assert!((oracle.deviation_from(&cex("ETHUSDT", 1990.0)) + 0.005).abs() < 1e-12);
+
+    assert!(oracle.confirms(&cex("ETH-USDT", 2010.0), 1.0));
+    assert!(!oracle.confirms(&cex("ETHUSDT", 2100.0), 1.0));
+    assert!(!oracle.confirms(&cex("BTCUSDT", 2000.0), 1.0));
+
+    assert!(!oracle.is_stale(Duration::from_secs(60)));
+}
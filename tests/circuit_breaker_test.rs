@@ -0,0 +1,92 @@
+use aeon_market_scanner_rs::scanner::CircuitBreaker;
+use aeon_market_scanner_rs::{CircuitBreakerConfig, CircuitState, LiveScanOptions, VenueFeed};
+
+fn config() -> CircuitBreakerConfig {
+    CircuitBreakerConfig::default()
+        .with_failure_threshold(3)
+        .with_failure_window_ms(60_000)
+        .with_open_duration_ms(10_000)
+}
+
+#[test]
+fn breaker_opens_after_threshold_within_window() {
+    let mut breaker = CircuitBreaker::new(config());
+    assert_eq!(breaker.record_failure(0), None);
+    assert_eq!(breaker.record_failure(1_000), None);
+    assert_eq!(breaker.record_failure(2_000), Some(CircuitState::Open));
+    assert_eq!(breaker.state(), CircuitState::Open);
+    assert_eq!(breaker.remaining_open_ms(5_000), 7_000);
+}
+
+#[test]
+fn failures_outside_window_do_not_count() {
+    let mut breaker = CircuitBreaker::new(config());
+    breaker.record_failure(0);
+    breaker.record_failure(1_000);
+    // First two failures have aged out of the 60 s window
+    assert_eq!(breaker.record_failure(70_000), None);
+    assert_eq!(breaker.state(), CircuitState::Closed);
+}
+
+#[test]
+fn half_open_probe_closes_or_reopens() {
+    let mut breaker = CircuitBreaker::new(config());
+    for t in [0, 1, 2] {
+        breaker.record_failure(t);
+    }
+    assert_eq!(breaker.poll(5_000), None, "Still within open duration");
+    assert_eq!(breaker.poll(10_002), Some(CircuitState::HalfOpen));
+
+    // Failed probe opens again
+    assert_eq!(breaker.record_failure(10_500), Some(CircuitState::Open));
+    assert_eq!(breaker.poll(20_500), Some(CircuitState::HalfOpen));
+
+    // Successful probe closes and resets the failure count
+    assert_eq!(breaker.record_success(20_600), Some(CircuitState::Closed));
+    assert_eq!(breaker.record_success(20_700), None);
+    assert_eq!(breaker.record_failure(21_000), None);
+}
+
+#[test]
+fn healthy_session_clears_failures() {
+    let mut breaker = CircuitBreaker::new(config().with_healthy_reset_ms(30_000));
+    breaker.record_failure(0);
+    breaker.record_failure(1_000);
+    // Healthy for less than 30 s: the two failures still count
+    breaker.record_success(2_000);
+    breaker.record_success(20_000);
+    assert_eq!(breaker.record_failure(21_000), Some(CircuitState::Open));
+
+    let mut breaker = CircuitBreaker::new(config().with_healthy_reset_ms(30_000));
+    breaker.record_failure(0);
+    breaker.record_failure(1_000);
+    breaker.record_success(2_000);
+    breaker.record_success(32_000);
+    assert_eq!(breaker.record_failure(33_000), None);
+    assert_eq!(breaker.state(), CircuitState::Closed);
+}
+
+#[test]
+fn zero_threshold_disables_breaker() {
+    let mut breaker = CircuitBreaker::new(config().with_failure_threshold(0));
+    for t in 0..10 {
+        assert_eq!(breaker.record_failure(t), None);
+    }
+    assert_eq!(breaker.state(), CircuitState::Closed);
+}
+
+#[test]
+fn rest_fallback_is_opt_in() {
+    assert_eq!(LiveScanOptions::default().rest_fallback_interval_ms, 0);
+    let options = LiveScanOptions::default()
+        .with_circuit_breaker(config())
+        .with_rest_fallback(2_000);
+    assert_eq!(options.rest_fallback_interval_ms, 2_000);
+
+    let feed = serde_json::to_string(&VenueFeed::RestPolling).unwrap();
+    assert_eq!(feed, "\"RestPolling\"");
+    assert_eq!(
+        serde_json::from_str::<VenueFeed>("\"WebSocket\"").unwrap(),
+        VenueFeed::WebSocket
+    );
+}
@@ -0,0 +1,151 @@
+mod scanner_common;
+
+use aeon_market_scanner_rs::common::{MarketKey, Timestamp};
+use aeon_market_scanner_rs::dex::oracles::{OraclePrice, OracleSource};
+use aeon_market_scanner_rs::{
+    ArbitrageScanner, CexExchange, CompositeQuoteConfig, CompositeQuoteSource, QuoteSourceKind,
+};
+use scanner_common::{CexPriceBuilder, binance_okx_fees};
+
+fn oracle(price: f64, millis: u64) -> OraclePrice {
+    OraclePrice {
+        symbol: "BTCUSD".to_string(),
+        price,
+        source: OracleSource::Chainlink,
+        feed: "0xfeed".to_string(),
+        updated_at: Timestamp::from_millis(millis),
+        confidence: None,
+    }
+}
+
+const NOW: Timestamp = Timestamp::from_millis(20_000);
+
+#[test]
+fn primary_source_is_emitted_and_agreement_is_reported() {
+    let mut quotes = CompositeQuoteSource::default();
+    quotes.record_websocket(
+        &CexPriceBuilder::around(CexExchange::Binance, 100.0, 0.05)
+            .symbol("BTCUSDT")
+            .at(19_000)
+            .build(),
+    );
+    quotes.record_rest(
+        &CexPriceBuilder::around(CexExchange::Binance, 100.2, 0.05)
+            .symbol("BTCUSDT")
+            .at(18_000)
+            .build(),
+    );
+
+    let quote = quotes.quote(&CexExchange::Binance, "BTCUSDT", NOW).unwrap();
+    assert_eq!(quote.primary, QuoteSourceKind::WebSocket);
+    assert_eq!(quote.price.mid_price, 100.0);
+    assert_eq!(quote.sources.len(), 2);
+    assert!((quote.max_divergence_percent - 0.2).abs() < 1e-9);
+    assert!(!quote.diverged);
+}
+
+#[test]
+fn diverging_sources_are_flagged() {
+    let mut quotes = CompositeQuoteSource::default();
+    // Frozen WebSocket feed: REST has moved 2% away
+    quotes.record_websocket(
+        &CexPriceBuilder::around(CexExchange::OKX, 100.0, 0.05)
+            .symbol("BTC-USDT")
+            .at(19_000)
+            .build(),
+    );
+    quotes.record_rest(
+        &CexPriceBuilder::around(CexExchange::OKX, 102.0, 0.05)
+            .symbol("BTC-USDT")
+            .at(19_500)
+            .build(),
+    );
+
+    let quote = quotes.quote(&CexExchange::OKX, "BTC-USDT", NOW).unwrap();
+    assert_eq!(quote.price.mid_price, 100.0);
+    assert!(quote.diverged);
+}
+
+#[test]
+fn stale_primary_falls_back_to_next_source() {
+    let mut quotes =
+        CompositeQuoteSource::new(CompositeQuoteConfig::default().with_max_age_ms(5_000));
+    quotes.record_websocket(
+        &CexPriceBuilder::around(CexExchange::Binance, 100.0, 0.05)
+            .symbol("BTCUSDT")
+            .at(1_000)
+            .build(),
+    );
+    quotes.record_rest(
+        &CexPriceBuilder::around(CexExchange::Binance, 101.0, 0.05)
+            .symbol("BTCUSDT")
+            .at(19_000)
+            .build(),
+    );
+
+    let quote = quotes.quote(&CexExchange::Binance, "BTCUSDT", NOW).unwrap();
+    assert_eq!(quote.primary, QuoteSourceKind::Rest);
+    assert_eq!(quote.price.mid_price, 101.0);
+    assert_eq!(quote.sources.len(), 1);
+
+    assert!(quotes.quote(&CexExchange::Bybit, "BTCUSDT", NOW).is_none());
+}
+
+#[test]
+fn oracle_only_checks_agreement() {
+    let config = CompositeQuoteConfig::default()
+        .with_priority(vec![QuoteSourceKind::Oracle, QuoteSourceKind::WebSocket])
+        .with_disagreement_threshold_percent(1.0);
+    let mut quotes = CompositeQuoteSource::new(config);
+    quotes.record_oracle(&MarketKey::from_symbol("BTCUSDT"), &oracle(97.0, 19_000));
+
+    // Oracle alone is never emitted
+    assert!(
+        quotes
+            .quote(&CexExchange::Binance, "BTCUSDT", NOW)
+            .is_none()
+    );
+
+    quotes.record_websocket(
+        &CexPriceBuilder::around(CexExchange::Binance, 100.0, 0.05)
+            .symbol("BTCUSDT")
+            .at(19_000)
+            .build(),
+    );
+    let quote = quotes.quote(&CexExchange::Binance, "BTCUSDT", NOW).unwrap();
+    assert_eq!(quote.primary, QuoteSourceKind::WebSocket);
+    assert!((quote.max_divergence_percent - 3.0).abs() < 1e-9);
+    assert!(quote.diverged);
+}
+
+#[test]
+fn legs_agree_checks_both_cex_legs() {
+    let binance = CexPriceBuilder::around(CexExchange::Binance, 100.0, 0.05)
+        .symbol("BTCUSDT")
+        .at(19_000)
+        .build();
+    let okx = CexPriceBuilder::around(CexExchange::OKX, 101.0, 0.05)
+        .symbol("BTC-USDT")
+        .at(19_000)
+        .build();
+    let fees = binance_okx_fees();
+    let opportunities = ArbitrageScanner::opportunities_from_prices(
+        &[binance.clone(), okx.clone()],
+        &[],
+        Some(&fees),
+    );
+    let opportunity = &opportunities[0];
+
+    let mut quotes = CompositeQuoteSource::default();
+    quotes.record_websocket(&binance);
+    quotes.record_websocket(&okx);
+    assert!(quotes.legs_agree(opportunity, NOW));
+
+    quotes.record_rest(
+        &CexPriceBuilder::around(CexExchange::OKX, 99.0, 0.05)
+            .symbol("BTC-USDT")
+            .at(19_500)
+            .build(),
+    );
+    assert!(!quotes.legs_agree(opportunity, NOW));
+}
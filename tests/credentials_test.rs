@@ -0,0 +1,146 @@
+use aeon_market_scanner_rs::common::{
+    CredentialProvider, CredentialStore, Credentials, EnvCredentials, FileCredentials,
+    MemoryCredentials,
+};
+use aeon_market_scanner_rs::{CexExchange, MarketScannerError};
+use async_trait::async_trait;
+
+const CREDENTIALS_JSON: &str = r#"{
+    "Binance": { "api_key": "file-key", "api_secret": "file-secret" },
+    "okx": { "api_key": "okx-key", "api_secret": "okx-secret", "passphrase": "okx-pass" }
+}"#;
+
+/// Custom provider (e.g. a vault) that only knows Kraken.
+struct VaultProvider;
+
+#[async_trait]
+impl CredentialProvider for VaultProvider {
+    async fn credentials(
+        &self,
+        exchange: &CexExchange,
+    ) -> Result<Option<Credentials>, MarketScannerError> {
+        Ok((*exchange == CexExchange::Kraken)
+            .then(|| Credentials::new("vault-key", "vault-secret")))
+    }
+}
+
+#[test]
+fn debug_redacts_secrets() {
+    let credentials = Credentials::new("my-key", "my-secret").with_passphrase("my-pass");
+    let debug = format!("{:?}", credentials);
+    assert!(!debug.contains("my-key"));
+    assert!(!debug.contains("my-secret"));
+    assert!(!debug.contains("my-pass"));
+    assert_eq!(credentials.passphrase(), Some("my-pass"));
+}
+
+#[tokio::test]
+async fn env_credentials_per_exchange() {
+    let env = EnvCredentials::with_prefix("AEON_TEST_");
+    assert_eq!(
+        env.var_name(&CexExchange::Gateio, "API_KEY"),
+        "AEON_TEST_GATEIO_API_KEY"
+    );
+    unsafe {
+        std::env::set_var("AEON_TEST_OKX_API_KEY", "env-key");
+        std::env::set_var("AEON_TEST_OKX_API_SECRET", "env-secret");
+        std::env::set_var("AEON_TEST_OKX_API_PASSPHRASE", "env-pass");
+        std::env::set_var("AEON_TEST_BYBIT_API_KEY", "only-key");
+    }
+
+    let okx = env.credentials(&CexExchange::OKX).await.unwrap().unwrap();
+    assert_eq!(okx.api_key(), "env-key");
+    assert_eq!(okx.api_secret(), "env-secret");
+    assert_eq!(okx.passphrase(), Some("env-pass"));
+
+    assert!(env.credentials(&CexExchange::MEXC).await.unwrap().is_none());
+    let result = env.credentials(&CexExchange::Bybit).await;
+    assert!(matches!(result, Err(MarketScannerError::InvalidConfig(e)) if !e.contains("only-key")));
+}
+
+#[tokio::test]
+async fn file_credentials_are_case_insensitive() {
+    let path = std::env::temp_dir().join(format!("aeon_credentials_{}.json", std::process::id()));
+    std::fs::write(&path, CREDENTIALS_JSON).unwrap();
+    let file = FileCredentials::load(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    let binance = file
+        .credentials(&CexExchange::Binance)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(binance.api_key(), "file-key");
+    assert!(binance.passphrase().is_none());
+    let okx = file.credentials(&CexExchange::OKX).await.unwrap().unwrap();
+    assert_eq!(okx.passphrase(), Some("okx-pass"));
+    assert!(
+        file.credentials(&CexExchange::Kraken)
+            .await
+            .unwrap()
+            .is_none()
+    );
+
+    assert!(FileCredentials::load(&path).is_err());
+}
+
+#[test]
+fn invalid_file_does_not_echo_values() {
+    let result = FileCredentials::from_json(r#"{ "binance": "leaked-secret" }"#);
+    assert!(
+        matches!(result, Err(MarketScannerError::InvalidConfig(e)) if !e.contains("leaked-secret"))
+    );
+}
+
+#[tokio::test]
+async fn store_asks_providers_in_order() {
+    let memory = MemoryCredentials::new();
+    memory.insert(
+        CexExchange::Binance,
+        Credentials::new("memory-key", "memory-secret"),
+    );
+
+    let store = CredentialStore::new()
+        .with_provider(memory)
+        .with_provider(FileCredentials::from_json(CREDENTIALS_JSON).unwrap())
+        .with_provider(VaultProvider);
+
+    // Memory comes before the file
+    let binance = store.get(&CexExchange::Binance).await.unwrap();
+    assert_eq!(binance.api_key(), "memory-key");
+    let okx = store.get(&CexExchange::OKX).await.unwrap();
+    assert_eq!(okx.api_key(), "okx-key");
+    let kraken = store.get(&CexExchange::Kraken).await.unwrap();
+    assert_eq!(kraken.api_key(), "vault-key");
+
+    assert!(store.lookup(&CexExchange::Htx).await.unwrap().is_none());
+    assert!(matches!(
+        store.get(&CexExchange::Htx).await,
+        Err(MarketScannerError::InvalidConfig(_))
+    ));
+}
+
+#[tokio::test]
+async fn memory_credentials_can_be_removed() {
+    let memory = MemoryCredentials::new();
+    memory.insert(
+        CexExchange::Kucoin,
+        Credentials::new("k", "s").with_passphrase("p"),
+    );
+    assert!(
+        memory
+            .credentials(&CexExchange::Kucoin)
+            .await
+            .unwrap()
+            .is_some()
+    );
+    assert!(memory.remove(&CexExchange::Kucoin));
+    assert!(!memory.remove(&CexExchange::Kucoin));
+    assert!(
+        memory
+            .credentials(&CexExchange::Kucoin)
+            .await
+            .unwrap()
+            .is_none()
+    );
+}
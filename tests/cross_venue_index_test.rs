@@ -0,0 +1,151 @@
+mod scanner_common;
+
+use aeon_market_scanner_rs::common::CexPrice;
+use aeon_market_scanner_rs::{
+    ArbitrageScanner, CexExchange, ExclusionReason, IndexCalculator, IndexConfig, IndexWeighting,
+    Timestamp,
+};
+use scanner_common::{CexPriceBuilder, zero_taker_fees};
+use std::collections::HashMap;
+use std::time::Duration;
+use tokio::sync::mpsc;
+
+fn prices() -> Vec<CexPrice> {
+    vec![
+        CexPriceBuilder::around(CexExchange::Binance, 100.0, 0.5)
+            .qty(3.0)
+            .now()
+            .build(),
+        CexPriceBuilder::around(CexExchange::OKX, 101.0, 0.5)
+            .qty(1.0)
+            .now()
+            .build(),
+        CexPriceBuilder::around(CexExchange::Bybit, 100.5, 0.5)
+            .qty(0.0)
+            .now()
+            .build(),
+        // Outlier: 20% above the median
+        CexPriceBuilder::around(CexExchange::Kraken, 121.0, 0.5)
+            .qty(10.0)
+            .now()
+            .build(),
+    ]
+}
+
+#[test]
+fn liquidity_weighted_index_excludes_outliers() {
+    let index = IndexCalculator::default()
+        .compute("BTC-USDT", &prices())
+        .unwrap();
+    // Weights 300 and 101 (Bybit has no depth)
+    let expected = (100.0 * 300.0 + 101.0 * 101.0) / 401.0;
+    assert!((index.price - expected).abs() < 1e-9);
+    assert_eq!(index.median, 100.75);
+    assert_eq!(index.constituents.len(), 2);
+    let weights: f64 = index.constituents.iter().map(|c| c.weight).sum();
+    assert!((weights - 1.0).abs() < 1e-9);
+
+    let reason = |exchange: &str| {
+        index
+            .excluded
+            .iter()
+            .find(|e| e.exchange == exchange)
+            .map(|e| e.reason)
+    };
+    assert_eq!(reason("Kraken"), Some(ExclusionReason::Outlier));
+    assert_eq!(reason("Bybit"), Some(ExclusionReason::NoWeight));
+}
+
+#[test]
+fn weighting_and_venue_minimum_are_configurable() {
+    let equal = IndexCalculator::new(IndexConfig::new().with_weighting(IndexWeighting::Equal))
+        .compute("BTCUSDT", &prices())
+        .unwrap();
+    assert!((equal.price - 100.5).abs() < 1e-9);
+
+    let fixed = IndexCalculator::new(IndexConfig::new().with_weighting(IndexWeighting::Fixed(
+        HashMap::from([(CexExchange::OKX, 1.0)]),
+    )));
+    assert!(fixed.compute("BTCUSDT", &prices()).is_none());
+    let fixed = IndexCalculator::new(fixed.config().clone().with_min_venues(1));
+    assert_eq!(fixed.compute("BTCUSDT", &prices()).unwrap().price, 101.0);
+
+    let mut stale = prices();
+    stale[0].timestamp = Timestamp::from_millis(1);
+    let index = IndexCalculator::new(IndexConfig::new().with_max_age(Duration::from_secs(5)))
+        .compute("BTCUSDT", &stale);
+    assert!(index.is_none(), "only OKX left with depth");
+
+    assert!(
+        IndexCalculator::default()
+            .compute("ETHUSDT", &prices())
+            .is_none()
+    );
+}
+
+#[test]
+fn index_is_a_fair_value_reference() {
+    let index = IndexCalculator::new(IndexConfig::new().with_max_deviation_percent(30.0))
+        .compute("BTCUSDT", &prices())
+        .unwrap();
+    assert!(index.deviation_percent(index.price * 1.02) - 2.0 < 1e-9);
+
+    let fees = zero_taker_fees(&[CexExchange::Binance, CexExchange::Kraken]);
+    let opportunities = ArbitrageScanner::opportunities_from_prices(
+        &[
+            CexPriceBuilder::around(CexExchange::Binance, 100.0, 0.5)
+                .qty(3.0)
+                .now()
+                .build(),
+            CexPriceBuilder::around(CexExchange::Kraken, 121.0, 0.5)
+                .qty(10.0)
+                .now()
+                .build(),
+        ],
+        &[],
+        Some(&fees),
+    );
+    let opportunity = opportunities
+        .iter()
+        .find(|o| o.source_exchange == "Binance")
+        .unwrap();
+    assert!(!index.is_sane(opportunity, 5.0));
+    assert!(index.is_sane(opportunity, 25.0));
+}
+
+#[tokio::test]
+async fn stream_emits_index_updates() {
+    let (tx, rx) = mpsc::channel(8);
+    let mut updates = IndexCalculator::default().stream(&["BTC-USDT"], rx);
+    tx.send(
+        CexPriceBuilder::around(CexExchange::Binance, 100.0, 0.5)
+            .qty(1.0)
+            .now()
+            .build(),
+    )
+    .await
+    .unwrap();
+    tx.send(
+        CexPriceBuilder::around(CexExchange::OKX, 102.0, 0.5)
+            .qty(1.0)
+            .now()
+            .build(),
+    )
+    .await
+    .unwrap();
+    tx.send(
+        CexPriceBuilder::around(CexExchange::OKX, 100.0, 0.5)
+            .qty(1.0)
+            .now()
+            .build(),
+    )
+    .await
+    .unwrap();
+    drop(tx);
+
+    let first = updates.recv().await.unwrap();
+    assert_eq!(first.symbol, "BTC-USDT");
+    assert!((first.price - (100.0 * 100.0 + 102.0 * 102.0) / 202.0).abs() < 1e-9);
+    assert_eq!(updates.recv().await.unwrap().price, 100.0);
+    assert!(updates.recv().await.is_none());
+}
@@ -0,0 +1,53 @@
+use aeon_market_scanner_rs::CexExchange;
+use aeon_market_scanner_rs::common::{
+    DEFAULT_DECODE_WARNING_THRESHOLD, decode_warning_threshold, decode_ws_json, record_ws_decode,
+    subscribe_decode_warnings, venue_health,
+};
+
+#[test]
+fn decode_ws_json_counts_successes_and_failures() {
+    assert!(venue_health(&CexExchange::Btcturk).decode.is_none());
+
+    let ok: Option<serde_json::Value> = decode_ws_json(&CexExchange::Btcturk, r#"{"a":1}"#);
+    assert!(ok.is_some());
+    let bad: Option<serde_json::Value> = decode_ws_json(&CexExchange::Btcturk, "{not json");
+    assert!(bad.is_none());
+
+    let stats = venue_health(&CexExchange::Btcturk)
+        .decode
+        .expect("decode stats should be recorded");
+    assert_eq!(stats.decoded, 1);
+    assert_eq!(stats.failures, 1);
+    assert_eq!(stats.messages(), 2);
+    assert!(stats.error_rate > 0.0 && stats.error_rate < 1.0);
+}
+
+#[test]
+fn rising_error_rate_emits_one_warning() {
+    assert_eq!(decode_warning_threshold(), DEFAULT_DECODE_WARNING_THRESHOLD);
+    let mut warnings = subscribe_decode_warnings();
+
+    // Healthy traffic first
+    for _ in 0..30 {
+        record_ws_decode(&CexExchange::Htx, true);
+    }
+    assert!(!venue_health(&CexExchange::Htx).decode.unwrap().warning);
+
+    // Protocol change: every message fails from here on
+    for _ in 0..30 {
+        record_ws_decode(&CexExchange::Htx, false);
+    }
+    let stats = venue_health(&CexExchange::Htx).decode.unwrap();
+    assert!(stats.warning);
+    assert!(stats.error_rate > DEFAULT_DECODE_WARNING_THRESHOLD);
+
+    let mut htx_warnings = Vec::new();
+    while let Ok(warning) = warnings.try_recv() {
+        if warning.exchange == CexExchange::Htx {
+            htx_warnings.push(warning);
+        }
+    }
+    assert_eq!(htx_warnings.len(), 1, "warning fires once per crossing");
+    assert!(htx_warnings[0].failures > 0);
+    assert!(htx_warnings[0].messages >= 20);
+}
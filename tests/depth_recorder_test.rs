@@ -0,0 +1,119 @@
+use aeon_market_scanner_rs::common::{OrderBook, OrderBookLevel};
+use aeon_market_scanner_rs::scanner::{DepthSnapshotWriter, depth_recording_files};
+use aeon_market_scanner_rs::{
+    ArbitrageScanner, CexExchange, DepthRecorderOptions, DepthSnapshot, DepthSnapshotReader,
+    Exchange, Timestamp,
+};
+use std::path::PathBuf;
+
+fn snapshot(millis: u64, levels: usize) -> DepthSnapshot {
+    let level = |i: usize, price: f64| OrderBookLevel {
+        price,
+        quantity: 1.0 + i as f64,
+    };
+    DepthSnapshot {
+        captured_at: Timestamp::from_millis(millis + 5),
+        book: OrderBook {
+            symbol: "BTCUSDT".to_string(),
+            bids: (0..levels).map(|i| level(i, 100.0 - i as f64)).collect(),
+            asks: (0..levels).map(|i| level(i, 101.0 + i as f64)).collect(),
+            timestamp: Timestamp::from_millis(millis),
+            exchange: Exchange::Cex(CexExchange::Binance),
+        },
+    }
+}
+
+fn temp_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("aeon_depth_{}_{}", name, std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+#[test]
+fn snapshots_round_trip_through_compressed_files() {
+    let dir = temp_dir("round_trip");
+    let path = dir.join("depth-1-00000.jsonl.gz");
+    let mut writer = DepthSnapshotWriter::create(&path).unwrap();
+    for millis in 0..100 {
+        writer.write(&snapshot(millis, 50)).unwrap();
+    }
+    assert_eq!(writer.len(), 100);
+    assert_eq!(writer.finish().unwrap(), path);
+
+    let snapshots = DepthSnapshotReader::read_all(&path).unwrap();
+    assert_eq!(snapshots.len(), 100);
+    assert_eq!(snapshots[7].book.timestamp, Timestamp::from_millis(7));
+    assert_eq!(snapshots[7].captured_at, Timestamp::from_millis(12));
+    assert_eq!(snapshots[7].book.bids.len(), 50);
+    assert_eq!(snapshots[7].book.asks[49].price, 150.0);
+
+    // Repetitive books compress well below their JSON size
+    let json_size = serde_json::to_vec(&snapshots).unwrap().len() as u64;
+    assert!(std::fs::metadata(&path).unwrap().len() * 5 < json_size);
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn flushed_files_are_readable_before_they_are_finished() {
+    let dir = temp_dir("flushed");
+    let path = dir.join("depth-1-00000.jsonl.gz");
+    let mut writer = DepthSnapshotWriter::create(&path).unwrap();
+    writer.write(&snapshot(1, 5)).unwrap();
+    writer.write(&snapshot(2, 5)).unwrap();
+    writer.flush().unwrap();
+
+    let mut reader = DepthSnapshotReader::open(&path).unwrap();
+    assert_eq!(
+        reader.next().unwrap().unwrap().book.timestamp,
+        Timestamp::from_millis(1)
+    );
+    assert_eq!(
+        reader.next().unwrap().unwrap().book.timestamp,
+        Timestamp::from_millis(2)
+    );
+    // The gzip trailer is missing until the writer finishes
+    assert!(reader.next().is_some_and(|last| last.is_err()));
+    drop(writer);
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn recording_files_are_listed_in_order() {
+    let dir = temp_dir("listing");
+    for index in [2, 0, 1] {
+        let path = dir.join(format!("depth-1700000000000-{:05}.jsonl.gz", index));
+        let mut writer = DepthSnapshotWriter::create(path).unwrap();
+        writer.write(&snapshot(index, 1)).unwrap();
+        writer.finish().unwrap();
+    }
+    std::fs::write(dir.join("notes.txt"), "not a recording").unwrap();
+
+    let files = depth_recording_files(&dir).unwrap();
+    assert_eq!(files.len(), 3);
+    let timestamps: Vec<u64> = files
+        .iter()
+        .flat_map(|file| DepthSnapshotReader::read_all(file).unwrap())
+        .map(|s| s.book.timestamp.as_millis())
+        .collect();
+    assert_eq!(timestamps, [0, 1, 2]);
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[tokio::test]
+async fn recording_options_are_validated() {
+    let dir = temp_dir("invalid");
+    let no_symbols = DepthRecorderOptions::new(&[CexExchange::Binance], &[]);
+    assert!(
+        ArbitrageScanner::record_depth_snapshots(no_symbols, &dir)
+            .await
+            .is_err()
+    );
+    let no_depth = DepthRecorderOptions::new(&[CexExchange::Binance], &["BTCUSDT"]).with_depth(0);
+    assert!(
+        ArbitrageScanner::record_depth_snapshots(no_depth, &dir)
+            .await
+            .is_err()
+    );
+    std::fs::remove_dir_all(&dir).unwrap();
+}
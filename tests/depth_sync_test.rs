@@ -0,0 +1,138 @@
+use aeon_market_scanner_rs::common::{
+    DepthDelta, DepthSnapshot, DepthSyncStatus, OrderBookSynchronizer,
+};
+use aeon_market_scanner_rs::{CexExchange, Exchange, OrderBookLevel};
+
+fn level(price: f64, quantity: f64) -> OrderBookLevel {
+    OrderBookLevel { price, quantity }
+}
+
+fn delta(first: u64, last: u64, bids: &[(f64, f64)], asks: &[(f64, f64)]) -> DepthDelta {
+    DepthDelta {
+        first_update_id: first,
+        final_update_id: last,
+        bids: bids.iter().map(|(p, q)| level(*p, *q)).collect(),
+        asks: asks.iter().map(|(p, q)| level(*p, *q)).collect(),
+    }
+}
+
+fn snapshot(last_update_id: u64) -> DepthSnapshot {
+    DepthSnapshot {
+        last_update_id,
+        bids: vec![level(100.0, 1.0), level(99.0, 2.0)],
+        asks: vec![level(101.0, 1.0), level(102.0, 2.0)],
+    }
+}
+
+fn synchronizer() -> OrderBookSynchronizer {
+    OrderBookSynchronizer::new("BTCUSDT", Exchange::Cex(CexExchange::Binance))
+}
+
+#[test]
+fn deltas_are_buffered_until_snapshot() {
+    let mut sync = synchronizer();
+    assert_eq!(
+        sync.push_delta(delta(5, 8, &[(100.0, 3.0)], &[])),
+        DepthSyncStatus::Buffered
+    );
+    assert_eq!(
+        sync.push_delta(delta(9, 10, &[], &[(101.0, 0.0)])),
+        DepthSyncStatus::Buffered
+    );
+    assert!(sync.book(5).is_none());
+    assert_eq!(sync.buffered_deltas(), 2);
+
+    // Snapshot at 6: first delta straddles it, second continues it
+    assert_eq!(sync.apply_snapshot(snapshot(6)), DepthSyncStatus::Applied);
+    assert_eq!(sync.last_update_id(), Some(10));
+    assert_eq!(sync.buffered_deltas(), 0);
+
+    let book = sync.book(5).unwrap();
+    assert_eq!(book.bids, vec![level(100.0, 3.0), level(99.0, 2.0)]);
+    assert_eq!(book.asks, vec![level(102.0, 2.0)]);
+}
+
+#[test]
+fn deltas_covered_by_snapshot_are_dropped() {
+    let mut sync = synchronizer();
+    sync.push_delta(delta(1, 4, &[(100.0, 9.0)], &[]));
+    sync.push_delta(delta(5, 7, &[(98.0, 1.0)], &[]));
+
+    assert_eq!(sync.apply_snapshot(snapshot(4)), DepthSyncStatus::Applied);
+    let book = sync.book(5).unwrap();
+    // The first delta is already part of the snapshot
+    assert_eq!(book.bids[0], level(100.0, 1.0));
+    assert_eq!(book.bids[2], level(98.0, 1.0));
+    assert_eq!(
+        sync.push_delta(delta(3, 6, &[(100.0, 5.0)], &[])),
+        DepthSyncStatus::Stale
+    );
+}
+
+#[test]
+fn snapshot_older_than_buffer_needs_refetch() {
+    let mut sync = synchronizer();
+    sync.push_delta(delta(10, 12, &[], &[]));
+
+    assert_eq!(sync.apply_snapshot(snapshot(5)), DepthSyncStatus::Gap);
+    assert!(!sync.is_synced());
+    assert_eq!(sync.buffered_deltas(), 1);
+
+    assert_eq!(sync.apply_snapshot(snapshot(11)), DepthSyncStatus::Applied);
+    assert_eq!(sync.last_update_id(), Some(12));
+}
+
+#[test]
+fn gap_resets_the_book() {
+    let mut sync = synchronizer();
+    assert_eq!(sync.apply_snapshot(snapshot(10)), DepthSyncStatus::Applied);
+    assert_eq!(
+        sync.push_delta(delta(11, 11, &[(99.5, 1.0)], &[])),
+        DepthSyncStatus::Applied
+    );
+    assert_eq!(sync.book(1).unwrap().bids, vec![level(100.0, 1.0)]);
+
+    assert_eq!(
+        sync.push_delta(delta(13, 14, &[], &[])),
+        DepthSyncStatus::Gap
+    );
+    assert!(sync.book(5).is_none());
+    // The delta after the gap starts the new buffer
+    assert_eq!(sync.buffered_deltas(), 1);
+    assert_eq!(sync.apply_snapshot(snapshot(13)), DepthSyncStatus::Applied);
+    assert_eq!(sync.last_update_id(), Some(14));
+}
+
+#[test]
+fn book_is_sorted_and_truncated() {
+    let mut sync = synchronizer();
+    sync.apply_snapshot(DepthSnapshot {
+        last_update_id: 1,
+        bids: vec![level(98.0, 1.0), level(100.0, 1.0), level(99.0, 1.0)],
+        asks: vec![level(103.0, 1.0), level(101.0, 1.0), level(102.0, 1.0)],
+    });
+
+    let book = sync.book(2).unwrap();
+    assert_eq!(book.symbol, "BTCUSDT");
+    assert_eq!(book.bids, vec![level(100.0, 1.0), level(99.0, 1.0)]);
+    assert_eq!(book.asks, vec![level(101.0, 1.0), level(102.0, 1.0)]);
+}
+
+#[test]
+fn gap_during_replay_keeps_the_remaining_deltas() {
+    let mut sync = synchronizer();
+    sync.push_delta(delta(10, 11, &[], &[]));
+    // 12 is missing
+    sync.push_delta(delta(13, 14, &[], &[]));
+    sync.push_delta(delta(15, 16, &[(99.5, 3.0)], &[]));
+
+    assert_eq!(sync.apply_snapshot(snapshot(10)), DepthSyncStatus::Gap);
+    assert!(!sync.is_synced());
+    assert_eq!(sync.buffered_deltas(), 2);
+
+    // A snapshot past the gap resumes from the kept deltas
+    assert_eq!(sync.apply_snapshot(snapshot(13)), DepthSyncStatus::Applied);
+    assert_eq!(sync.last_update_id(), Some(16));
+    assert_eq!(sync.book(1).unwrap().bids, vec![level(100.0, 1.0)]);
+    assert_eq!(sync.book(2).unwrap().bids[1], level(99.5, 3.0));
+}
@@ -0,0 +1,76 @@
+use aeon_market_scanner_rs::dex::{
+    f64_to_raw, format_units, parse_raw_amount, parse_units, raw_ratio, raw_to_f64, u256_to_f64,
+};
+use ethers::core::types::U256;
+
+#[test]
+fn large_supply_amounts_survive_round_trips() {
+    // 10^15 tokens with 18 decimals: beyond the 96-bit mantissa of Decimal
+    let raw = parse_raw_amount("1000000000000000123456789012345678").unwrap();
+    assert_eq!(format_units(raw, 18), "1000000000000000.123456789012345678");
+    assert_eq!(
+        parse_units("1000000000000000.123456789012345678", 18).unwrap(),
+        raw
+    );
+    assert!((raw_to_f64(raw, 18) - 1e15).abs() < 1.0);
+
+    // Beyond u128
+    let huge = U256::MAX;
+    assert!(u256_to_f64(huge) > 1.15e77);
+    assert!(raw_to_f64(huge, 18) > 1.15e59);
+    assert_eq!(parse_units(&format_units(huge, 18), 18).unwrap(), huge);
+}
+
+#[test]
+fn units_parse_exactly_and_reject_bad_input() {
+    assert_eq!(
+        parse_units("1.5", 18).unwrap(),
+        U256::from(1_500_000_000_000_000_000u64)
+    );
+    assert_eq!(parse_units("0.000001", 6).unwrap(), U256::one());
+    assert_eq!(parse_units(".50", 2).unwrap(), U256::from(50));
+    assert_eq!(parse_units("0", 18).unwrap(), U256::zero());
+    assert_eq!(format_units(U256::from(5), 6), "0.000005");
+    assert_eq!(format_units(U256::from(5_000_000), 6), "5");
+
+    assert!(parse_units("0.0000001", 6).is_err());
+    assert!(parse_units("-1", 18).is_err());
+    assert!(parse_units("1e18", 0).is_err());
+    assert!(parse_units("", 18).is_err());
+    assert!(parse_units(&format!("{}0", U256::MAX), 0).is_err());
+    assert!(parse_raw_amount("12.5").is_err());
+}
+
+#[test]
+fn f64_amounts_convert_without_binary_noise() {
+    // "{:.12}" formatting of this value would append digits the caller never gave
+    assert_eq!(
+        f64_to_raw(123456789.123, 18).unwrap(),
+        parse_units("123456789.123", 18).unwrap()
+    );
+    assert_eq!(
+        f64_to_raw(0.1, 18).unwrap(),
+        U256::from(100_000_000_000_000_000u64)
+    );
+    assert_eq!(f64_to_raw(0.5, 6).unwrap(), U256::from(500_000));
+    // Digits below the token precision are truncated
+    assert_eq!(f64_to_raw(1.23456789, 2).unwrap(), U256::from(123));
+    assert_eq!(
+        f64_to_raw(1e21, 18).unwrap(),
+        parse_units("1000000000000000000000", 18).unwrap()
+    );
+    assert!(f64_to_raw(-1.0, 18).is_err());
+    assert!(f64_to_raw(f64::NAN, 18).is_err());
+}
+
+#[test]
+fn ratios_scale_by_decimals() {
+    // 3000 USDC (6 decimals) for 1 WETH (18 decimals)
+    let usdc = parse_units("3000", 6).unwrap();
+    let weth = parse_units("1", 18).unwrap();
+    assert!((raw_ratio(usdc, 6, weth, 18).unwrap() - 3000.0).abs() < 1e-9);
+    // Large-supply token: 2 * 10^14 tokens for 1 WETH
+    let meme = parse_units("200000000000000", 18).unwrap();
+    assert!((raw_ratio(meme, 18, weth, 18).unwrap() - 2e14).abs() < 1.0);
+    assert!(raw_ratio(usdc, 6, U256::zero(), 18).is_err());
+}
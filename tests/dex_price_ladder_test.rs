@@ -0,0 +1,180 @@
+use aeon_market_scanner_rs::common::{DEFAULT_LADDER_SIZES, DexLadderPoint};
+use aeon_market_scanner_rs::dex::chains::{ChainId, Token};
+use aeon_market_scanner_rs::{
+    AmountSpec, DEXTrait, DexAggregator, DexPrice, DexPriceLadder, Exchange, ExchangeTrait,
+    MarketScannerError, Timestamp,
+};
+use async_trait::async_trait;
+
+/// Quotes 2000 ± 1% per 100k of size; sizes from 1M up find no route.
+struct ImpactDex {
+    client: reqwest::Client,
+}
+
+fn quote(quote_amount: f64) -> DexPrice {
+    let impact = quote_amount / 100_000.0 * 0.01;
+    let ask_price = 2000.0 * (1.0 + impact);
+    let bid_price = 2000.0 * (1.0 - impact);
+    DexPrice {
+        symbol: "ETHUSDT".to_string(),
+        mid_price: 2000.0,
+        bid_price,
+        ask_price,
+        bid_qty: quote_amount / 2000.0,
+        ask_qty: quote_amount / ask_price,
+        timestamp: Timestamp::now(),
+        exchange: Exchange::Dex(DexAggregator::KyberSwap),
+        bid_route_summary: None,
+        ask_route_summary: None,
+        bid_route_data: None,
+        ask_route_data: None,
+        transfer_tax_bps: None,
+    }
+}
+
+#[async_trait]
+impl ExchangeTrait for ImpactDex {
+    fn api_base(&self) -> &str {
+        "http://localhost"
+    }
+
+    fn client(&self) -> &reqwest::Client {
+        &self.client
+    }
+
+    fn exchange_name(&self) -> &str {
+        "ImpactDex"
+    }
+
+    async fn health_check(&self) -> Result<(), MarketScannerError> {
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl DEXTrait for ImpactDex {
+    async fn get_price(
+        &self,
+        _base_token: &Token,
+        _quote_token: &Token,
+        quote_amount: f64,
+    ) -> Result<DexPrice, MarketScannerError> {
+        if quote_amount >= 1_000_000.0 {
+            return Err(MarketScannerError::ApiError("no route".to_string()));
+        }
+        Ok(quote(quote_amount))
+    }
+}
+
+fn token(symbol: &str) -> Token {
+    Token::new(
+        format!("0x{}", symbol),
+        symbol.to_string(),
+        symbol.to_string(),
+        18,
+        ChainId::ETHEREUM,
+    )
+}
+
+fn dex() -> ImpactDex {
+    ImpactDex {
+        client: reqwest::Client::new(),
+    }
+}
+
+#[tokio::test]
+async fn ladder_quotes_every_size_sorted() {
+    let sizes = [10_000.0, 500.0, 50_000.0, 2_500.0];
+    let ladder = dex()
+        .get_price_ladder(&token("WETH"), &token("USDT"), &sizes)
+        .await
+        .unwrap();
+
+    let amounts: Vec<f64> = ladder.points.iter().map(|p| p.quote_amount).collect();
+    assert_eq!(amounts, DEFAULT_LADDER_SIZES.to_vec());
+    assert_eq!(ladder.symbol, "ETHUSDT");
+    assert_eq!(ladder.best().unwrap().ask_price, quote(500.0).ask_price);
+
+    let asks = ladder.ask_curve();
+    assert!(asks.windows(2).all(|w| w[0].1 < w[1].1));
+    let bids = ladder.bid_curve();
+    assert!(bids.windows(2).all(|w| w[0].1 > w[1].1));
+    let expected = (1.005 - 1.00005) / 1.00005 * 100.0;
+    assert!((ladder.ask_slippage_percent() - expected).abs() < 1e-9);
+}
+
+#[tokio::test]
+async fn ladder_skips_failed_sizes() {
+    let ladder = dex()
+        .get_price_ladder(&token("WETH"), &token("USDT"), &[500.0, 2_000_000.0])
+        .await
+        .unwrap();
+    assert_eq!(ladder.points.len(), 1);
+
+    let result = dex()
+        .get_price_ladder(&token("WETH"), &token("USDT"), &[1_000_000.0])
+        .await;
+    assert!(matches!(result, Err(MarketScannerError::ApiError(e)) if e == "no route"));
+    let result = dex()
+        .get_price_ladder(&token("WETH"), &token("USDT"), &[])
+        .await;
+    assert!(result.is_err());
+}
+
+#[test]
+fn order_book_reproduces_average_prices() {
+    let points = DEFAULT_LADDER_SIZES
+        .iter()
+        .map(|&quote_amount| DexLadderPoint {
+            quote_amount,
+            price: quote(quote_amount),
+        })
+        .collect();
+    let ladder = DexPriceLadder::new(points).unwrap();
+    let book = ladder.to_order_book();
+
+    assert_eq!(book.asks.len(), 4);
+    assert_eq!(book.bids.len(), 4);
+    assert!(book.asks.windows(2).all(|w| w[0].price < w[1].price));
+    assert!(book.bids.windows(2).all(|w| w[0].price > w[1].price));
+
+    // Walking every level costs what the largest quote costs on average
+    for (levels, avg_price, qty) in [
+        (
+            &book.asks,
+            quote(50_000.0).ask_price,
+            quote(50_000.0).ask_qty,
+        ),
+        (
+            &book.bids,
+            quote(50_000.0).bid_price,
+            quote(50_000.0).bid_qty,
+        ),
+    ] {
+        let total_qty: f64 = levels.iter().map(|l| l.quantity).sum();
+        let notional: f64 = levels.iter().map(|l| l.price * l.quantity).sum();
+        assert!((total_qty - qty).abs() < 1e-9);
+        assert!((notional / total_qty - avg_price).abs() < 1e-6);
+    }
+}
+
+#[test]
+fn empty_ladder_is_none() {
+    assert!(DexPriceLadder::new(Vec::new()).is_none());
+}
+
+#[tokio::test]
+async fn default_amount_spec_quotes_by_quote_amount_only() {
+    let price = dex()
+        .get_price_for_amount(&token("WETH"), &token("USDT"), AmountSpec::Quote(2_500.0))
+        .await
+        .unwrap();
+    assert_eq!(price.ask_price, quote(2_500.0).ask_price);
+
+    let err = dex()
+        .get_price_for_amount(&token("WETH"), &token("USDT"), AmountSpec::Base(2.0))
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("ImpactDex"));
+    assert_eq!(AmountSpec::Base(2.0).amount(), 2.0);
+}
@@ -0,0 +1,73 @@
+use aeon_market_scanner_rs::common::DEFAULT_EXCLUDED_DEX_SOURCES;
+use aeon_market_scanner_rs::dex::kyberswap::source_params;
+use aeon_market_scanner_rs::{DexSourceFilter, MarketScannerError};
+
+#[test]
+fn default_filter_keeps_rfq_sources_out() {
+    let filter = DexSourceFilter::default();
+    assert_eq!(filter.excluded, DEFAULT_EXCLUDED_DEX_SOURCES);
+    assert!(!filter.allows("bebop"));
+    assert!(filter.allows("uniswap-v3"));
+    assert_eq!(
+        source_params(&filter),
+        "&excludedSources=bebop,smardex,dodo"
+    );
+    assert_eq!(source_params(&DexSourceFilter::unfiltered()), "");
+}
+
+#[test]
+fn filters_include_and_exclude_per_request() {
+    let filter = DexSourceFilter::default()
+        .exclude("hashflow-v3")
+        .exclude("bebop")
+        .with_included_sources(["uniswap-v3", "curve", "hashflow-v3"]);
+    assert!(filter.allows("curve"));
+    assert!(!filter.allows("balancer-v2"), "not included");
+    assert!(!filter.allows("hashflow-v3"), "excluded wins");
+    assert_eq!(
+        source_params(&filter),
+        "&includedSources=uniswap-v3,curve,hashflow-v3&excludedSources=bebop,smardex,dodo,hashflow-v3"
+    );
+
+    let only_excluded =
+        DexSourceFilter::unfiltered().with_excluded_sources(vec!["dodo".to_string()]);
+    assert_eq!(source_params(&only_excluded), "&excludedSources=dodo");
+}
+
+#[test]
+fn filters_deserialize_from_config() {
+    let filter: DexSourceFilter = serde_json::from_str(r#"{"excluded":["bebop"]}"#).unwrap();
+    assert!(filter.included.is_empty());
+    assert!(!filter.allows("bebop"));
+    assert!(filter.allows("dodo"));
+}
+
+#[tokio::test]
+async fn polling_source_accepts_a_filter() {
+    use aeon_market_scanner_rs::dex::chains::{ChainId, Token};
+    use aeon_market_scanner_rs::{AggregatorPollingSource, DexAggregator, PriceSource};
+    use std::time::Duration;
+
+    let token = |symbol: &str| {
+        Token::new(
+            "0x0".to_string(),
+            symbol.to_string(),
+            symbol.to_string(),
+            18,
+            ChainId::ETHEREUM,
+        )
+    };
+    let source = AggregatorPollingSource::new(
+        DexAggregator::KyberSwap,
+        token("WETH"),
+        token("USDC"),
+        0.0,
+        Duration::from_secs(1),
+    )
+    .with_dex_sources(DexSourceFilter::unfiltered());
+    // Configuration is still validated first
+    assert!(matches!(
+        Box::new(source).start().await,
+        Err(MarketScannerError::InvalidConfig(_))
+    ));
+}
@@ -0,0 +1,123 @@
+use aeon_market_scanner_rs::cex::execution::{
+    CexOrder, CexOrderExecution, OrderStatus, TradingMode,
+};
+use aeon_market_scanner_rs::common::{
+    Credentials, DryRunAction, DryRunRecord, dry_run_enabled, dry_run_log, is_dry_run_id,
+    set_dry_run,
+};
+use aeon_market_scanner_rs::dex::execution::{ExecutionWallet, PrivateRelay, PrivateRelayConfig};
+use aeon_market_scanner_rs::{CexExchange, OKX, TradeSide};
+use ethers::core::types::transaction::eip2718::TypedTransaction;
+use ethers::core::types::{Address, Bytes, Eip1559TransactionRequest, H256, U256};
+use ethers::core::utils::keccak256;
+use ethers::providers::Provider;
+use ethers::signers::LocalWallet;
+
+// Well-known test key (anvil account #0)
+const TEST_KEY: &str = "ac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80";
+
+// Dry-run mode is global; every test in this binary runs with it enabled.
+fn enable_dry_run() {
+    set_dry_run(true);
+    assert!(dry_run_enabled());
+}
+
+fn record(id: &str) -> DryRunRecord {
+    dry_run_log()
+        .into_iter()
+        .find(|record| record.id == id)
+        .expect("request recorded")
+}
+
+fn swap_tx() -> TypedTransaction {
+    Eip1559TransactionRequest::new()
+        .to(Address::from_low_u64_be(0x1234))
+        .data(vec![0xde, 0xad])
+        .gas(200_000u64)
+        .max_fee_per_gas(30_000_000_000u64)
+        .max_priority_fee_per_gas(1_000_000_000u64)
+        .into()
+}
+
+#[tokio::test]
+async fn cex_orders_are_signed_and_logged_but_not_sent() {
+    enable_dry_run();
+    let okx = OKX::new();
+    let credentials = Credentials::new("test-key", "test-secret").with_passphrase("test-pass");
+    let mut order = CexOrder::limit("BTCUSDT", TradeSide::Buy, 0.01, 50_000.0);
+    order.client_order_id = Some("scan1".to_string());
+
+    let ack = okx
+        .place_order(&credentials, &order, TradingMode::Live)
+        .await
+        .unwrap();
+    assert!(is_dry_run_id(&ack.order_id));
+    assert_eq!(ack.exchange, CexExchange::OKX);
+    assert_eq!(ack.client_order_id.as_deref(), Some("scan1"));
+    assert_eq!(ack.price, Some(50_000.0));
+
+    let placed = record(&ack.order_id);
+    assert_eq!(placed.action, DryRunAction::PlaceOrder);
+    assert_eq!(placed.target, "OKX");
+    assert!(
+        placed
+            .request
+            .starts_with("POST https://www.okx.com/api/v5/trade/order")
+    );
+    assert!(placed.request.contains("\"clOrdId\":\"scan1\""));
+    // Signed headers are never logged
+    assert!(!placed.request.contains("test-key"));
+
+    let status = okx.order_status(&credentials, &ack).await.unwrap();
+    assert_eq!(status.status, OrderStatus::New);
+    assert_eq!(status.filled_quantity, 0.0);
+
+    okx.cancel_order(&credentials, &ack).await.unwrap();
+    let cancel = dry_run_log()
+        .into_iter()
+        .rev()
+        .find(|record| record.action == DryRunAction::CancelOrder)
+        .expect("cancel recorded");
+    assert!(is_dry_run_id(&cancel.id));
+    assert!(cancel.request.contains("cancel-order"));
+}
+
+#[tokio::test]
+async fn wallet_signs_and_returns_the_hash_without_broadcasting() {
+    enable_dry_run();
+    let (provider, mock) = Provider::mocked();
+    // Only the nonce is served; a broadcast would find no response
+    mock.push(U256::from(5u64)).unwrap();
+    let wallet = ExecutionWallet::new(provider, TEST_KEY.parse::<LocalWallet>().unwrap(), 1);
+
+    let hash = wallet.send(swap_tx()).await.unwrap();
+    let sent = record(&format!("{:?}", hash));
+    assert_eq!(sent.action, DryRunAction::SendTransaction);
+    assert_eq!(sent.target, "chain 1");
+    let raw: Bytes = sent.request.parse().unwrap();
+    assert_eq!(H256::from(keccak256(&raw)), hash);
+
+    // The nonce stays used, as after a real send
+    assert_eq!(wallet.next_nonce().await.unwrap(), U256::from(6u64));
+}
+
+#[tokio::test]
+async fn relay_submissions_are_logged_but_not_sent() {
+    enable_dry_run();
+    let relay = PrivateRelay::new(PrivateRelayConfig::default())
+        .unwrap()
+        .with_auth_signer(TEST_KEY.parse().unwrap());
+    let signed = Bytes::from(vec![0x02, 0xf8, 0x01]);
+
+    let hash = relay.send_transaction(&signed).await.unwrap();
+    assert_eq!(hash, H256::from(keccak256(&signed)));
+    assert_eq!(
+        record(&format!("{:?}", hash)).request,
+        "0x02f801".to_string()
+    );
+
+    let bundle_hash = relay.send_bundle(&[signed], 100).await.unwrap();
+    let bundle = record(&format!("{:?}", bundle_hash));
+    assert_eq!(bundle.action, DryRunAction::SendBundle);
+    assert!(bundle.request.contains("mev_sendBundle"));
+}
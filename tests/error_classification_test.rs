@@ -0,0 +1,92 @@
+use aeon_market_scanner_rs::common::RestRetryPolicy;
+use aeon_market_scanner_rs::{CexExchange, MarketScannerError, VenueIncident};
+use std::time::Duration;
+
+fn api_error(message: &str) -> MarketScannerError {
+    MarketScannerError::ApiError(message.to_string())
+}
+
+#[test]
+fn status_is_read_from_venue_error_messages() {
+    let limited = api_error("Binance API error: 429 Too Many Requests - {}");
+    assert_eq!(limited.http_status(), Some(429));
+    let unavailable = api_error("OKX API error: 503 Service Unavailable - ");
+    assert_eq!(unavailable.http_status(), Some(503));
+    assert_eq!(
+        api_error("Kraken API error: EQuery:Unknown asset pair").http_status(),
+        None
+    );
+    assert_eq!(
+        api_error("Bybit API error: 10001 params error").http_status(),
+        None
+    );
+    assert_eq!(MarketScannerError::HealthCheckFailed.http_status(), None);
+}
+
+#[test]
+fn rate_limits_server_errors_and_timeouts_are_retryable() {
+    for message in [
+        "Binance API error: 429 Too Many Requests - ",
+        "Binance API error: 408 Request Timeout - ",
+        "Gateio API error: 500 Internal Server Error - ",
+        "Coinbase API error: 502 Bad Gateway - ",
+        "Kucoin request timed out",
+        "MEXC rate limit exceeded",
+    ] {
+        assert!(api_error(message).is_retryable(), "{message}");
+    }
+    assert!(MarketScannerError::WsRpcError("connection reset".to_string()).is_retryable());
+    assert!(MarketScannerError::HealthCheckFailed.is_retryable());
+}
+
+#[test]
+fn client_errors_and_bad_input_are_permanent() {
+    for message in [
+        "Binance API error: 400 Bad Request - {\"code\":-1121,\"msg\":\"Invalid symbol.\"}",
+        "OKX API error: 404 Not Found - ",
+        "Kraken API error: EQuery:Unknown asset pair",
+    ] {
+        assert!(!api_error(message).is_retryable(), "{message}");
+    }
+    assert!(!MarketScannerError::InvalidSymbol("FOO".to_string()).is_retryable());
+    assert!(!MarketScannerError::InvalidConfig("bad".to_string()).is_retryable());
+    assert!(!MarketScannerError::UnsupportedChain("x".to_string()).is_retryable());
+    let parse = serde_json::from_str::<u32>("nope").unwrap_err();
+    assert!(!MarketScannerError::from(parse).is_retryable());
+}
+
+#[test]
+fn retry_policy_backs_off_on_retryable_errors_only() {
+    let policy = RestRetryPolicy::new(3)
+        .with_initial_delay(Duration::from_millis(100))
+        .with_max_delay(Duration::from_millis(300));
+    assert_eq!(policy.delay(1), Duration::from_millis(100));
+    assert_eq!(policy.delay(2), Duration::from_millis(200));
+    assert_eq!(policy.delay(3), Duration::from_millis(300));
+
+    let limited = api_error("Binance API error: 429 Too Many Requests - ");
+    assert!(policy.should_retry(&limited, 0));
+    assert!(policy.should_retry(&limited, 2));
+    assert!(!policy.should_retry(&limited, 3));
+    let not_found = api_error("Binance API error: 404 Not Found - ");
+    assert!(!policy.should_retry(&not_found, 0));
+    assert!(!RestRetryPolicy::default().should_retry(&limited, 0));
+}
+
+#[test]
+fn rest_incidents_name_the_error_class() {
+    let failed: Result<(), _> = Err(api_error("HTX API error: 503 Service Unavailable - "));
+    let incident = VenueIncident::from_rest_result(&CexExchange::Htx, &failed);
+    assert!(
+        incident.summary.contains("transient"),
+        "{}",
+        incident.summary
+    );
+    let failed: Result<(), _> = Err(MarketScannerError::InvalidSymbol("FOO".to_string()));
+    let incident = VenueIncident::from_rest_result(&CexExchange::Htx, &failed);
+    assert!(
+        incident.summary.contains("permanent"),
+        "{}",
+        incident.summary
+    );
+}
@@ -0,0 +1,143 @@
+mod scanner_common;
+
+use aeon_market_scanner_rs::common::Timestamp;
+use aeon_market_scanner_rs::scanner::serve_ack_webhook;
+use aeon_market_scanner_rs::{
+    AckOutcome, ExecutionAck, LifecycleConfig, LifecycleEvent, OpportunityLifecycle,
+};
+use scanner_common::binance_to_okx;
+
+fn opened_id(events: &[LifecycleEvent]) -> String {
+    events
+        .iter()
+        .find_map(|event| match event {
+            LifecycleEvent::Opened { opportunity, .. } => opportunity.id.clone(),
+            LifecycleEvent::Closed { .. } => None,
+        })
+        .expect("opened opportunity with an id")
+}
+
+#[test]
+fn acks_are_linked_to_their_episode() {
+    let mut lifecycle = OpportunityLifecycle::new(LifecycleConfig::new(0.5, 0.2)).unwrap();
+    let events = lifecycle.update(
+        &[binance_to_okx("BTCUSDT", 100.0, 102.0)],
+        Timestamp::from_millis(1_000),
+    );
+    let id = opened_id(&events);
+    let key = binance_to_okx("BTCUSDT", 100.0, 102.0).key();
+    assert_eq!(id, format!("{}@1000", key));
+    assert_eq!(lifecycle.open_id(&key), Some(id.as_str()));
+
+    // Later observations keep the episode id
+    lifecycle.update(
+        &[binance_to_okx("BTCUSDT", 100.0, 103.0)],
+        Timestamp::from_millis(2_000),
+    );
+    assert_eq!(
+        lifecycle.open_opportunities()[0].id.as_deref(),
+        Some(id.as_str())
+    );
+
+    let mut ack = ExecutionAck::new(
+        id.clone(),
+        AckOutcome::Filled {
+            realized_profit_quote: 1.5,
+        },
+    );
+    ack.at = Timestamp::from_millis(2_500);
+    let report = lifecycle.ack(&ack).unwrap();
+    assert_eq!(report.ack_delay_ms, 1_500);
+    assert!(report.closed_at.is_none());
+    assert_eq!(
+        report.opportunity.spread_percentage,
+        binance_to_okx("BTCUSDT", 100.0, 103.0).spread_percentage
+    );
+
+    // Acknowledging twice or an unknown id fails
+    assert!(lifecycle.ack(&ack).is_err());
+    assert!(
+        lifecycle
+            .ack(&ExecutionAck::new("unknown", AckOutcome::Missed))
+            .is_err()
+    );
+
+    let stats = lifecycle.execution_stats();
+    assert_eq!(stats.detected, 1);
+    assert_eq!(stats.acknowledged, 1);
+    assert_eq!(stats.filled, 1);
+    assert_eq!(stats.realized_profit_quote, 1.5);
+    assert_eq!(stats.ack_rate(), 1.0);
+    assert_eq!(stats.fill_rate(), 1.0);
+    assert_eq!(stats.avg_ack_delay_ms(), 1_500.0);
+}
+
+#[test]
+fn closed_episodes_can_still_be_acknowledged() {
+    let mut lifecycle = OpportunityLifecycle::new(LifecycleConfig::new(0.5, 0.2)).unwrap();
+    let first = opened_id(&lifecycle.update(
+        &[binance_to_okx("BTCUSDT", 100.0, 102.0)],
+        Timestamp::from_millis(0),
+    ));
+    lifecycle.update(&[], Timestamp::from_millis(1_000));
+    let second = opened_id(&lifecycle.update(
+        &[binance_to_okx("BTCUSDT", 100.0, 102.0)],
+        Timestamp::from_millis(2_000),
+    ));
+    assert_ne!(first, second);
+
+    let report = lifecycle
+        .ack(&ExecutionAck::new(
+            first,
+            AckOutcome::Rejected {
+                reason: "balance".to_string(),
+            },
+        ))
+        .unwrap();
+    assert_eq!(report.closed_at, Some(Timestamp::from_millis(1_000)));
+
+    let stats = lifecycle.execution_stats();
+    assert_eq!(stats.detected, 2);
+    assert_eq!(stats.rejected, 1);
+    assert_eq!(stats.ack_rate(), 0.5);
+    assert_eq!(stats.fill_rate(), 0.0);
+}
+
+#[test]
+fn outcomes_use_a_status_tag() {
+    let ack: ExecutionAck = serde_json::from_str(
+        r#"{"id": "a@1", "outcome": {"status": "partially_filled", "filled_quantity": 0.5, "realized_profit_quote": 2.0}}"#,
+    )
+    .unwrap();
+    assert_eq!(
+        ack.outcome,
+        AckOutcome::PartiallyFilled {
+            filled_quantity: 0.5,
+            realized_profit_quote: 2.0
+        }
+    );
+    assert_eq!(ack.outcome.realized_profit_quote(), 2.0);
+}
+
+#[tokio::test]
+async fn webhook_forwards_posted_acks() {
+    let (addr, mut acks) = serve_ack_webhook("127.0.0.1:0").await.unwrap();
+    let client = reqwest::Client::new();
+    let url = format!("http://{}/acks", addr);
+
+    let response = client
+        .post(&url)
+        .body(r#"{"id": "a@1", "outcome": {"status": "missed"}}"#)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status().as_u16(), 202);
+    let ack = acks.recv().await.unwrap();
+    assert_eq!(ack.id, "a@1");
+    assert_eq!(ack.outcome, AckOutcome::Missed);
+
+    let response = client.post(&url).body("not json").send().await.unwrap();
+    assert_eq!(response.status().as_u16(), 400);
+    let response = client.get(&url).send().await.unwrap();
+    assert_eq!(response.status().as_u16(), 405);
+}
@@ -0,0 +1,133 @@
+use aeon_market_scanner_rs::MarketScannerError;
+use aeon_market_scanner_rs::dex::execution::{
+    CallbackSigner, ExecutionWallet, TransactionSigner, local_signer_from_env,
+};
+use ethers::core::types::transaction::eip2718::TypedTransaction;
+use ethers::core::types::{Address, Bytes, Eip1559TransactionRequest, H256, U256};
+use ethers::core::utils::rlp::Rlp;
+use ethers::providers::Provider;
+use ethers::signers::{LocalWallet, Signer};
+use futures::FutureExt;
+
+// Well-known test key (anvil account #0)
+const TEST_KEY: &str = "ac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80";
+
+fn test_wallet() -> LocalWallet {
+    TEST_KEY.parse().unwrap()
+}
+
+fn swap_tx() -> TypedTransaction {
+    Eip1559TransactionRequest::new()
+        .to(Address::from_low_u64_be(0x1234))
+        .data(vec![0xde, 0xad])
+        .gas(200_000u64)
+        .max_fee_per_gas(30_000_000_000u64)
+        .max_priority_fee_per_gas(1_000_000_000u64)
+        .into()
+}
+
+#[test]
+fn test_local_signer_from_env() {
+    unsafe { std::env::set_var("AEON_TEST_WALLET_KEY", format!("0x{}", TEST_KEY)) };
+    let signer = local_signer_from_env(Some("AEON_TEST_WALLET_KEY")).unwrap();
+    assert_eq!(signer.address(), test_wallet().address());
+
+    unsafe { std::env::set_var("AEON_TEST_WALLET_BAD_KEY", "not-a-key") };
+    assert!(local_signer_from_env(Some("AEON_TEST_WALLET_BAD_KEY")).is_err());
+    assert!(local_signer_from_env(Some("AEON_TEST_WALLET_MISSING_KEY")).is_err());
+}
+
+#[tokio::test]
+async fn test_nonce_fetched_once_then_counted_locally() {
+    let (provider, mock) = Provider::mocked();
+    mock.push(U256::from(7u64)).unwrap();
+    let wallet = ExecutionWallet::new(provider, test_wallet(), 1);
+
+    assert_eq!(wallet.next_nonce().await.unwrap(), U256::from(7u64));
+    // Served locally: the mock has no second response
+    assert_eq!(wallet.next_nonce().await.unwrap(), U256::from(8u64));
+    assert_eq!(wallet.next_nonce().await.unwrap(), U256::from(9u64));
+
+    wallet.reset_nonce();
+    assert!(wallet.next_nonce().await.is_err());
+}
+
+#[tokio::test]
+async fn test_sign_fills_fields_and_recovers_to_wallet() {
+    let (provider, mock) = Provider::mocked();
+    mock.push(U256::from(3u64)).unwrap();
+    let signer = test_wallet();
+    let wallet = ExecutionWallet::new(provider, signer.clone(), 8453);
+
+    let raw = wallet.sign(swap_tx()).await.unwrap();
+    let (decoded, signature) = TypedTransaction::decode_signed(&Rlp::new(&raw)).unwrap();
+    assert_eq!(decoded.nonce(), Some(&U256::from(3u64)));
+    assert_eq!(decoded.chain_id().map(|c| c.as_u64()), Some(8453));
+    assert_eq!(decoded.gas(), Some(&U256::from(200_000u64)));
+    assert_eq!(
+        signature.recover(decoded.sighash()).unwrap(),
+        signer.address()
+    );
+}
+
+#[tokio::test]
+async fn test_send_returns_hash_and_resets_nonce_on_failure() {
+    let (provider, mock) = Provider::mocked();
+    let hash = H256::from_low_u64_be(0xabc);
+    // Responses are served last-in first-out
+    mock.push(hash).unwrap();
+    mock.push(U256::from(5u64)).unwrap();
+    let wallet = ExecutionWallet::new(provider, test_wallet(), 1);
+
+    assert_eq!(wallet.send(swap_tx()).await.unwrap(), hash);
+
+    // Broadcast fails (no response left): the nonce is dropped and refetched next time
+    mock.push(U256::from(6u64)).unwrap();
+    assert!(wallet.send(swap_tx()).await.is_err());
+    mock.push(U256::from(6u64)).unwrap();
+    assert_eq!(wallet.next_nonce().await.unwrap(), U256::from(6u64));
+}
+
+#[tokio::test]
+async fn test_balances() {
+    let (provider, mock) = Provider::mocked();
+    let mut word = [0u8; 32];
+    U256::from(1_500_000u64).to_big_endian(&mut word);
+    mock.push::<Bytes, _>(Bytes::from(word.to_vec())).unwrap();
+    mock.push(U256::exp10(18)).unwrap();
+    let wallet = ExecutionWallet::new(provider, test_wallet(), 1);
+
+    assert_eq!(wallet.native_balance().await.unwrap(), U256::exp10(18));
+    assert_eq!(
+        wallet
+            .token_balance(Address::from_low_u64_be(0x55))
+            .await
+            .unwrap(),
+        U256::from(1_500_000u64)
+    );
+}
+
+#[tokio::test]
+async fn test_callback_signer() {
+    let external = test_wallet();
+    let address = external.address();
+    let callback = CallbackSigner::new(address, move |tx: TypedTransaction| {
+        let external = external.clone();
+        async move {
+            external
+                .sign_transaction(&tx)
+                .await
+                .map_err(|e| MarketScannerError::ApiError(e.to_string()))
+        }
+        .boxed()
+    });
+    assert_eq!(callback.signer_address(), address);
+
+    let (provider, _mock) = Provider::mocked();
+    let wallet = ExecutionWallet::new(provider, callback, 1);
+    let mut tx = swap_tx();
+    tx.set_nonce(0u64);
+    let raw = wallet.sign(tx).await.unwrap();
+    let (decoded, signature) = TypedTransaction::decode_signed(&Rlp::new(&raw)).unwrap();
+    assert_eq!(signature.recover(decoded.sighash()).unwrap(), address);
+}
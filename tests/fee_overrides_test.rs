@@ -1,32 +1,19 @@
-use aeon_market_scanner_rs::common::CexPrice;
+mod scanner_common;
+
 use aeon_market_scanner_rs::scanner::ArbitrageScanner;
-use aeon_market_scanner_rs::{CexExchange, Exchange, FeeOverrides};
+use aeon_market_scanner_rs::{CexExchange, FeeOverrides};
+use scanner_common::CexPriceBuilder;
 
 #[test]
 fn fee_overrides_change_effective_prices_and_commission_percents() {
     // Deterministic/offline test: provide price snapshots directly.
     // Buy on Binance, sell on OKX.
-    let buy = CexPrice {
-        symbol: "BTCUSDT".to_string(),
-        mid_price: 100.0,
-        bid_price: 99.0,
-        ask_price: 100.0,
-        bid_qty: 1.0,
-        ask_qty: 1.0,
-        timestamp: 1,
-        exchange: Exchange::Cex(CexExchange::Binance),
-    };
-
-    let sell = CexPrice {
-        symbol: "BTCUSDT".to_string(),
-        mid_price: 110.0,
-        bid_price: 110.0,
-        ask_price: 111.0,
-        bid_qty: 1.0,
-        ask_qty: 1.0,
-        timestamp: 1,
-        exchange: Exchange::Cex(CexExchange::OKX),
-    };
+    let buy = CexPriceBuilder::new(CexExchange::Binance, 99.0, 100.0)
+        .mid(100.0)
+        .build();
+    let sell = CexPriceBuilder::new(CexExchange::OKX, 110.0, 111.0)
+        .mid(110.0)
+        .build();
 
     let base_opps =
         ArbitrageScanner::opportunities_from_prices(&[buy.clone(), sell.clone()], &[], None);
@@ -0,0 +1,99 @@
+mod scanner_common;
+
+use aeon_market_scanner_rs::common::Timestamp;
+use aeon_market_scanner_rs::scanner::FeedDiffRecorder;
+use aeon_market_scanner_rs::{CexExchange, FeedDiffOptions, VenueFeed};
+use scanner_common::quote_at;
+
+fn at(ms: u64) -> Timestamp {
+    Timestamp::from_millis(1_000_000 + ms)
+}
+
+#[test]
+fn rest_quotes_are_compared_with_the_latest_websocket_quote() {
+    let mut recorder = FeedDiffRecorder::new(
+        CexExchange::OKX,
+        "BTCUSDT",
+        FeedDiffOptions::default()
+            .with_divergence_threshold_bps(5.0)
+            .with_samples(true),
+    );
+    // No WebSocket quote yet: counted but not compared
+    assert!(
+        recorder
+            .record_rest(quote_at(CexExchange::OKX, 99.0, 101.0, 0), at(0), at(40))
+            .is_none()
+    );
+
+    recorder.record_websocket(quote_at(CexExchange::OKX, 99.0, 101.0, 1_000_100), at(100));
+    let sample = recorder
+        .record_rest(
+            quote_at(CexExchange::OKX, 99.0, 101.0, 1_000_150),
+            at(150),
+            at(200),
+        )
+        .unwrap();
+    assert_eq!(sample.mid_diff_bps, 0.0);
+    assert_eq!(sample.websocket_age_ms, 100);
+    assert_eq!(sample.timestamp_lag_ms, 50);
+    assert_eq!(sample.rest_latency_ms, 50);
+
+    // REST mid 100.1 vs WebSocket 100: 10 bps, above the threshold
+    recorder.record_websocket(quote_at(CexExchange::OKX, 99.0, 101.0, 1_000_250), at(250));
+    let sample = recorder
+        .record_rest(
+            quote_at(CexExchange::OKX, 99.1, 101.1, 1_000_300),
+            at(300),
+            at(330),
+        )
+        .unwrap();
+    assert!((sample.mid_diff_bps - 10.0).abs() < 1e-9);
+    recorder.record_rest_error();
+
+    let report = recorder.report();
+    assert_eq!(report.websocket_updates, 2);
+    assert_eq!(report.rest_samples, 3);
+    assert_eq!(report.rest_errors, 1);
+    assert_eq!(report.compared, 2);
+    assert_eq!(report.diverged, 1);
+    assert_eq!(report.divergence_rate(), 0.5);
+    assert!((report.mean_abs_mid_diff_bps - 5.0).abs() < 1e-9);
+    assert!((report.max_abs_mid_diff_bps - 10.0).abs() < 1e-9);
+    assert_eq!(report.max_websocket_age_ms, 100);
+    assert_eq!(report.mean_websocket_age_ms, 90.0);
+    assert_eq!(report.mean_timestamp_lag_ms, 50.0);
+    assert_eq!(report.mean_rest_latency_ms, 40.0);
+    assert_eq!(report.samples.len(), 2);
+    // REST was 50 ms newer on average, but a REST round trip takes 40 ms
+    assert_eq!(report.preferred_feed(), Some(VenueFeed::RestPolling));
+
+    let json = serde_json::to_value(&report).unwrap();
+    assert_eq!(json["diverged"], 1);
+    assert_eq!(json["samples"].as_array().unwrap().len(), 2);
+}
+
+#[test]
+fn preferred_feed_follows_freshness() {
+    let options = FeedDiffOptions::default();
+    let mut recorder = FeedDiffRecorder::new(CexExchange::Bybit, "BTCUSDT", options.clone());
+    assert_eq!(recorder.report().preferred_feed(), None);
+    recorder.record_websocket(quote_at(CexExchange::OKX, 99.0, 101.0, 500), at(0));
+    assert_eq!(
+        recorder.report().preferred_feed(),
+        Some(VenueFeed::WebSocket)
+    );
+
+    // REST lags the stream
+    recorder.record_rest(quote_at(CexExchange::OKX, 99.0, 101.0, 400), at(10), at(60));
+    let report = recorder.report();
+    assert_eq!(report.preferred_feed(), Some(VenueFeed::WebSocket));
+    assert!(report.samples.is_empty());
+
+    // A silent WebSocket loses to REST
+    let mut recorder = FeedDiffRecorder::new(CexExchange::Bybit, "BTCUSDT", options);
+    recorder.record_rest(quote_at(CexExchange::OKX, 99.0, 101.0, 400), at(10), at(60));
+    assert_eq!(
+        recorder.report().preferred_feed(),
+        Some(VenueFeed::RestPolling)
+    );
+}
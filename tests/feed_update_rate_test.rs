@@ -0,0 +1,127 @@
+mod scanner_common;
+
+use aeon_market_scanner_rs::{
+    ArbitrageScanner, CexExchange, Exchange, LiveScanOptions, ScannerHandle, UpdateRateTracker,
+    clear_ws_endpoint_override, set_ws_endpoint_override,
+};
+use futures::SinkExt;
+use scanner_common::{CexPriceBuilder, poll_until};
+use std::time::Duration;
+use tokio::net::TcpListener;
+use tokio_tungstenite::tungstenite::Message;
+
+/// Local WebSocket server; sends `frame` every 10 ms when set, stays silent otherwise.
+async fn ws_server(frame: Option<&'static str>) -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("ws://{}", listener.local_addr().unwrap());
+    tokio::spawn(async move {
+        while let Ok((stream, _)) = listener.accept().await {
+            tokio::spawn(async move {
+                let Ok(mut ws) = tokio_tungstenite::accept_async(stream).await else {
+                    return;
+                };
+                loop {
+                    if let Some(frame) = frame {
+                        if ws.send(Message::Text(frame.to_string())).await.is_err() {
+                            return;
+                        }
+                    }
+                    tokio::time::sleep(Duration::from_millis(10)).await;
+                }
+            });
+        }
+    });
+    url
+}
+
+#[tokio::test]
+async fn tracker_counts_updates_and_reports_silent_feeds() {
+    let tracker = UpdateRateTracker::new();
+    let binance = Exchange::Cex(CexExchange::Binance);
+    let okx = Exchange::Cex(CexExchange::OKX);
+    tracker.expect(&binance, "BTCUSDT");
+    tracker.expect(&okx, "BTCUSDT");
+
+    for _ in 0..20 {
+        tracker.record(
+            &CexPriceBuilder::around(CexExchange::Binance, 100.05, 0.05)
+                .symbol("BTCUSDT")
+                .now()
+                .build(),
+        );
+        tokio::time::sleep(Duration::from_millis(5)).await;
+    }
+    // Same market under another spelling
+    tracker.record(
+        &CexPriceBuilder::around(CexExchange::Binance, 100.05, 0.05)
+            .symbol("BTC-USDT")
+            .now()
+            .build(),
+    );
+
+    let active = tracker.stats_for(&binance, "btc_usdt").unwrap();
+    assert_eq!(active.updates, 21);
+    assert!(active.updates_per_sec > 10.0);
+    assert!(active.last_update.is_some());
+
+    let silent = tracker.stats_for(&okx, "BTCUSDT").unwrap();
+    assert_eq!(silent.updates, 0);
+    assert_eq!(silent.updates_per_sec, 0.0);
+    assert!(silent.last_update.is_none());
+    assert!(silent.last_update_age_ms >= 100);
+
+    let dormant = tracker.dormant(Duration::from_millis(100));
+    assert_eq!(dormant.len(), 1);
+    assert_eq!(dormant[0].exchange, okx);
+    assert!(tracker.dormant(Duration::from_secs(3600)).is_empty());
+
+    assert_eq!(tracker.stats().len(), 2);
+    tracker.clear();
+    assert!(tracker.stats().is_empty());
+}
+
+#[tokio::test]
+async fn live_scanner_exposes_per_venue_symbol_rates() {
+    let binance = ws_server(Some(
+        r#"{"u":1,"s":"BTCUSDT","b":"100.0","B":"1.0","a":"100.1","A":"1.0"}"#,
+    ))
+    .await;
+    let okx = ws_server(None).await;
+    set_ws_endpoint_override(&CexExchange::Binance, &binance);
+    set_ws_endpoint_override(&CexExchange::OKX, &okx);
+
+    let handle = ScannerHandle::new();
+    let options = LiveScanOptions::default().with_handle(handle.clone());
+    let mut events = ArbitrageScanner::scan_live(
+        &["BTCUSDT"],
+        &[CexExchange::Binance, CexExchange::OKX],
+        options,
+    )
+    .await
+    .unwrap();
+    tokio::spawn(async move { while events.recv().await.is_some() {} });
+
+    let binance = poll_until(Duration::from_secs(10), || {
+        handle
+            .feed_update_stats(&CexExchange::Binance, "BTCUSDT")
+            .filter(|stats| stats.updates > 10 && stats.updates_per_sec > 10.0)
+    })
+    .await
+    .unwrap_or_else(|| panic!("{:?}", handle.update_stats()));
+    assert!(binance.last_update_age_ms < 300, "{binance:?}");
+    assert_eq!(handle.update_stats().len(), 2);
+
+    // OKX never sends anything, so it turns dormant once 300 ms have passed
+    let dormant = poll_until(Duration::from_secs(10), || {
+        Some(handle.dormant_feeds(Duration::from_millis(300))).filter(|d| !d.is_empty())
+    })
+    .await
+    .unwrap_or_else(|| panic!("{:?}", handle.update_stats()));
+    assert_eq!(dormant.len(), 1);
+    assert_eq!(dormant[0].exchange, Exchange::Cex(CexExchange::OKX));
+    assert_eq!(dormant[0].updates, 0);
+
+    handle.stop();
+    clear_ws_endpoint_override(&CexExchange::Binance);
+    clear_ws_endpoint_override(&CexExchange::OKX);
+}
@@ -0,0 +1,115 @@
+mod scanner_common;
+
+use aeon_market_scanner_rs::common::Timestamp;
+use aeon_market_scanner_rs::{
+    ArbitrageOpportunity, ArbitrageScanner, CexExchange, FiatCalendar, ScannerContext,
+};
+use scanner_common::{sized_price, zero_taker_fees};
+
+/// Monday 2024-01-01 00:00 UTC
+const MONDAY: u64 = 1_704_067_200_000;
+const HOUR: u64 = 3_600_000;
+
+/// Buy BTC/TRY on Binance, sell on BTCTurk.
+fn binance_to_btcturk() -> Vec<ArbitrageOpportunity> {
+    let fees = zero_taker_fees(&[CexExchange::Binance, CexExchange::Btcturk]);
+    let prices = [
+        sized_price(CexExchange::Binance, "BTCTRY", 99.0, 100.0, 2.0),
+        sized_price(CexExchange::Btcturk, "BTCTRY", 102.0, 103.0, 2.0),
+    ];
+    ArbitrageScanner::opportunities_from_prices(&prices, &[], Some(&fees))
+        .into_iter()
+        .filter(|o| o.source_exchange == "Binance")
+        .collect()
+}
+
+#[test]
+fn holidays_and_banking_hours_flag_fiat_quotes() {
+    let calendar = FiatCalendar::new()
+        .with_venue_holiday(
+            CexExchange::Btcturk,
+            "try",
+            Timestamp::from_millis(MONDAY),
+            Timestamp::from_millis(MONDAY + 24 * HOUR),
+            "New Year's Day",
+        )
+        // Istanbul (UTC+3), 09:00-17:00
+        .with_banking_hours("TRY", 180, 9 * 60, 17 * 60);
+
+    let holiday = Timestamp::from_millis(MONDAY + 10 * HOUR);
+    assert_eq!(
+        calendar.restriction_reason(&CexExchange::Btcturk, "TRY", holiday),
+        Some("New Year's Day".to_string())
+    );
+
+    // Tuesday 10:00 UTC = 13:00 in Istanbul: open everywhere
+    let tuesday_noon = Timestamp::from_millis(MONDAY + 34 * HOUR);
+    assert_eq!(
+        calendar.restriction_reason(&CexExchange::Btcturk, "TRY", tuesday_noon),
+        None
+    );
+    // Tuesday 15:00 UTC = 18:00 in Istanbul: closed
+    let tuesday_evening = Timestamp::from_millis(MONDAY + 39 * HOUR);
+    assert!(
+        calendar
+            .restriction_reason(&CexExchange::Binance, "TRY", tuesday_evening)
+            .is_some()
+    );
+    // Saturday noon: closed
+    let saturday = Timestamp::from_millis(MONDAY + 5 * 24 * HOUR + 10 * HOUR);
+    assert!(
+        calendar
+            .restriction_reason(&CexExchange::Binance, "TRY", saturday)
+            .is_some()
+    );
+    assert_eq!(
+        calendar.restriction_reason(&CexExchange::Binance, "USDT", saturday),
+        None
+    );
+}
+
+#[test]
+fn restricted_opportunities_are_annotated_and_dampened() {
+    let calendar = FiatCalendar::new()
+        .with_hook(|exchange, quote, _| {
+            (*exchange == CexExchange::Btcturk && quote == "TRY")
+                .then(|| "TRY withdrawals throttled".to_string())
+        })
+        .with_dampening(0.25);
+
+    let mut opportunities = binance_to_btcturk();
+    assert_eq!(opportunities.len(), 1);
+    assert_eq!(opportunities[0].executable_quantity, 2.0);
+    calendar.annotate_all(&mut opportunities, Timestamp::from_millis(MONDAY));
+
+    let opportunity = &opportunities[0];
+    let restriction = opportunity.fiat_restriction.as_ref().unwrap();
+    assert_eq!(restriction.exchange, CexExchange::Btcturk);
+    assert_eq!(restriction.quote, "TRY");
+    assert_eq!(restriction.reason, "TRY withdrawals throttled");
+    assert_eq!(opportunity.executable_quantity, 0.5);
+    // Prices and spread are unchanged
+    assert_eq!(opportunity.spread, 2.0);
+
+    let json = serde_json::to_value(opportunity).unwrap();
+    assert_eq!(json["fiat_restriction"]["quote"], "TRY");
+}
+
+#[test]
+fn contexts_apply_their_calendar() {
+    let context = ScannerContext::new("try").with_fiat_calendar(FiatCalendar::new().with_holiday(
+        "TRY",
+        Timestamp::from_millis(0),
+        Timestamp::from_millis(u64::MAX),
+        "always",
+    ));
+    let opportunities = context.process(binance_to_btcturk());
+    assert_eq!(
+        opportunities[0].fiat_restriction.as_ref().unwrap().reason,
+        "always"
+    );
+    assert_eq!(opportunities[0].executable_quantity, 2.0);
+
+    let plain = ScannerContext::new("plain").process(binance_to_btcturk());
+    assert!(plain[0].fiat_restriction.is_none());
+}
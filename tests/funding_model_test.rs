@@ -0,0 +1,115 @@
+mod scanner_common;
+
+use aeon_market_scanner_rs::{
+    AssetTransferStatus, CexExchange, Exchange, FundingModel, OpportunityViability,
+    TransferStatusMonitor,
+};
+use scanner_common::binance_to_okx;
+
+#[test]
+fn prefunded_legs_are_instant() {
+    let model = FundingModel::default()
+        .with_balance(Exchange::Cex(CexExchange::Binance), "USDT", 1_000.0)
+        .with_balance(Exchange::Cex(CexExchange::OKX), "BTC", 1.0);
+    assert_eq!(
+        model.classify(&binance_to_okx("BTCUSDT", 100.0, 110.0)),
+        OpportunityViability::Instant
+    );
+}
+
+#[test]
+fn missing_inventory_held_elsewhere_requires_transfer() {
+    let model = FundingModel::default()
+        .with_balance(Exchange::Cex(CexExchange::Binance), "USDT", 1_000.0)
+        .with_balance(Exchange::Cex(CexExchange::Bybit), "BTC", 2.0)
+        .with_transfer_time_secs("BTC", 600);
+    assert_eq!(
+        model.classify(&binance_to_okx("BTCUSDT", 100.0, 110.0)),
+        OpportunityViability::TransferRequired {
+            assets: vec!["BTC".to_string()],
+            estimated_secs: 600,
+        }
+    );
+}
+
+#[test]
+fn slow_or_unavailable_transfers_are_not_viable() {
+    // BTC held elsewhere but the transfer takes longer than the allowed horizon
+    let slow = FundingModel::default()
+        .with_balance(Exchange::Cex(CexExchange::Binance), "USDT", 1_000.0)
+        .with_balance(Exchange::Cex(CexExchange::Bybit), "BTC", 2.0)
+        .with_max_transfer_secs(300);
+    assert_eq!(
+        slow.classify(&binance_to_okx("BTCUSDT", 100.0, 110.0)),
+        OpportunityViability::NotViable
+    );
+
+    // No BTC anywhere
+    let mut opps = vec![binance_to_okx("BTCUSDT", 100.0, 110.0)];
+    FundingModel::default()
+        .with_balance(Exchange::Cex(CexExchange::Binance), "USDT", 1_000.0)
+        .classify_all(&mut opps);
+    assert_eq!(opps[0].viability, Some(OpportunityViability::NotViable));
+}
+
+#[test]
+fn suspended_deposits_or_withdrawals_block_transfers() {
+    let monitor = TransferStatusMonitor::new();
+    let model = FundingModel::default()
+        .with_balance(Exchange::Cex(CexExchange::Binance), "USDT", 1_000.0)
+        .with_balance(Exchange::Cex(CexExchange::Bybit), "BTC", 2.0)
+        .with_transfer_time_secs("BTC", 600)
+        .with_transfer_status(monitor.clone());
+    let suspended = OpportunityViability::TransfersSuspended {
+        assets: vec!["BTC".to_string()],
+    };
+
+    // OKX does not accept BTC deposits
+    monitor.set_status(
+        &CexExchange::OKX,
+        "btc",
+        AssetTransferStatus::new(false, true),
+    );
+    assert_eq!(
+        model.classify(&binance_to_okx("BTCUSDT", 100.0, 110.0)),
+        suspended
+    );
+
+    // Deposits are back, but Bybit, the only BTC holder, halted withdrawals
+    monitor.set_status(
+        &CexExchange::OKX,
+        "BTC",
+        AssetTransferStatus::new(true, true),
+    );
+    monitor.set_status(
+        &CexExchange::Bybit,
+        "BTC",
+        AssetTransferStatus::new(true, false),
+    );
+    assert_eq!(
+        model.classify(&binance_to_okx("BTCUSDT", 100.0, 110.0)),
+        suspended
+    );
+
+    monitor.set_status(
+        &CexExchange::Bybit,
+        "BTC",
+        AssetTransferStatus::new(true, true),
+    );
+    assert!(matches!(
+        model.classify(&binance_to_okx("BTCUSDT", 100.0, 110.0)),
+        OpportunityViability::TransferRequired { .. }
+    ));
+
+    // Pre-funded legs need no transfer
+    let prefunded = model.with_balance(Exchange::Cex(CexExchange::OKX), "BTC", 1.0);
+    monitor.set_status(
+        &CexExchange::OKX,
+        "BTC",
+        AssetTransferStatus::new(false, false),
+    );
+    assert_eq!(
+        prefunded.classify(&binance_to_okx("BTCUSDT", 100.0, 110.0)),
+        OpportunityViability::Instant
+    );
+}
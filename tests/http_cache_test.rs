@@ -0,0 +1,118 @@
+use aeon_market_scanner_rs::common::{
+    DEFAULT_HTTP_CACHE_TTL, cached_get, create_http_client, http_cache_ttl, remove_http_cache_ttl,
+    set_http_cache_ttl,
+};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+
+/// Requests seen by the test server: (total, answered with 304)
+#[derive(Default)]
+struct Hits {
+    total: AtomicUsize,
+    not_modified: AtomicUsize,
+}
+
+/// Minimal HTTP server serving `{"v":1}` with `ETag: "v1"`, answering 304 to a matching
+/// `If-None-Match`. Returns its base URL.
+async fn serve(hits: Arc<Hits>) -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move {
+        loop {
+            let Ok((mut socket, _)) = listener.accept().await else {
+                return;
+            };
+            let hits = hits.clone();
+            tokio::spawn(async move {
+                let mut buf = vec![0u8; 4096];
+                let n = socket.read(&mut buf).await.unwrap_or(0);
+                let request = String::from_utf8_lossy(&buf[..n]).to_lowercase();
+                hits.total.fetch_add(1, Ordering::SeqCst);
+                let response = if request.contains("if-none-match: \"v1\"") {
+                    hits.not_modified.fetch_add(1, Ordering::SeqCst);
+                    "HTTP/1.1 304 Not Modified\r\nETag: \"v1\"\r\nConnection: close\r\n\r\n"
+                        .to_string()
+                } else {
+                    let body = "{\"v\":1}";
+                    format!(
+                        "HTTP/1.1 200 OK\r\nETag: \"v1\"\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                        body.len(),
+                        body
+                    )
+                };
+                let _ = socket.write_all(response.as_bytes()).await;
+                let _ = socket.shutdown().await;
+            });
+        }
+    });
+    format!("http://{}", addr)
+}
+
+#[tokio::test]
+async fn test_fresh_entry_is_served_without_request() {
+    let hits = Arc::new(Hits::default());
+    let url = format!("{}/fresh", serve(hits.clone()).await);
+    let client = create_http_client();
+
+    let ttl = Duration::from_secs(60);
+    assert_eq!(
+        cached_get(&client, &url, ttl, "Test").await.unwrap(),
+        "{\"v\":1}"
+    );
+    assert_eq!(
+        cached_get(&client, &url, ttl, "Test").await.unwrap(),
+        "{\"v\":1}"
+    );
+    assert_eq!(hits.total.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test]
+async fn test_expired_entry_is_revalidated_with_etag() {
+    let hits = Arc::new(Hits::default());
+    let url = format!("{}/revalidate", serve(hits.clone()).await);
+    let client = create_http_client();
+
+    assert_eq!(
+        cached_get(&client, &url, Duration::ZERO, "Test")
+            .await
+            .unwrap(),
+        "{\"v\":1}"
+    );
+    tokio::time::sleep(Duration::from_millis(5)).await;
+    // 304: the cached body is returned
+    assert_eq!(
+        cached_get(&client, &url, Duration::ZERO, "Test")
+            .await
+            .unwrap(),
+        "{\"v\":1}"
+    );
+    assert_eq!(hits.total.load(Ordering::SeqCst), 2);
+    assert_eq!(hits.not_modified.load(Ordering::SeqCst), 1);
+}
+
+#[test]
+fn test_ttl_longest_prefix_wins() {
+    assert_eq!(http_cache_ttl("cachetest/info"), DEFAULT_HTTP_CACHE_TTL);
+
+    set_http_cache_ttl("cachetest/", Duration::from_secs(600));
+    set_http_cache_ttl("cachetest/status", Duration::from_secs(5));
+    assert_eq!(http_cache_ttl("cachetest/info"), Duration::from_secs(600));
+    assert_eq!(
+        http_cache_ttl("cachetest/status?symbol=BTC"),
+        Duration::from_secs(5)
+    );
+
+    assert_eq!(
+        remove_http_cache_ttl("cachetest/status"),
+        Some(Duration::from_secs(5))
+    );
+    assert_eq!(
+        http_cache_ttl("cachetest/status?symbol=BTC"),
+        Duration::from_secs(600)
+    );
+    remove_http_cache_ttl("cachetest/");
+    assert_eq!(http_cache_ttl("cachetest/info"), DEFAULT_HTTP_CACHE_TTL);
+}
@@ -0,0 +1,95 @@
+use aeon_market_scanner_rs::common::HttpClientConfig;
+use aeon_market_scanner_rs::{Binance, Coinbase, ExchangeTrait, KyberSwap, MarketScannerError};
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+
+#[test]
+fn headers_replace_case_insensitively() {
+    let config = HttpClientConfig::new()
+        .with_user_agent("first")
+        .with_header("user-agent", "second")
+        .with_header("Accept", "application/json");
+    assert_eq!(config.user_agent(), Some("second"));
+    assert_eq!(config.header("ACCEPT"), Some("application/json"));
+    assert_eq!(config.timeout(), Duration::from_secs(5));
+}
+
+#[test]
+fn merge_applies_overrides_on_top() {
+    let defaults = HttpClientConfig::new()
+        .with_user_agent("default-agent")
+        .with_header("Accept", "application/json")
+        .with_timeout(Duration::from_secs(2));
+    let merged = defaults.merge(
+        HttpClientConfig::new()
+            .with_user_agent("my-bot/1.0")
+            .with_api_key("X-API-KEY", "secret"),
+    );
+    assert_eq!(merged.user_agent(), Some("my-bot/1.0"));
+    assert_eq!(merged.header("Accept"), Some("application/json"));
+    assert_eq!(merged.header("x-api-key"), Some("secret"));
+    // Unset override timeout keeps the default
+    assert_eq!(merged.timeout(), Duration::from_secs(2));
+}
+
+#[test]
+fn api_keys_are_redacted_in_debug() {
+    let config = HttpClientConfig::new()
+        .with_user_agent("my-bot/1.0")
+        .with_api_key("X-API-KEY", "secret");
+    let debug = format!("{:?}", config);
+    assert!(debug.contains("my-bot/1.0"));
+    assert!(!debug.contains("secret"));
+}
+
+#[test]
+fn venue_defaults() {
+    assert_eq!(
+        Coinbase::default_http_config().user_agent(),
+        Some("aeon-market-scanner-rs")
+    );
+    assert_eq!(
+        KyberSwap::default_http_config().header("X-Client-Id"),
+        Some("wc-arbitrage-bot")
+    );
+    assert!(Binance::default_http_config().user_agent().is_none());
+}
+
+#[test]
+fn invalid_headers_are_rejected() {
+    let result = Binance::with_http_config(HttpClientConfig::new().with_header("bad header", "x"));
+    assert!(matches!(result, Err(MarketScannerError::InvalidConfig(_))));
+    let result = Binance::with_http_config(HttpClientConfig::new().with_user_agent("line\nbreak"));
+    assert!(matches!(result, Err(MarketScannerError::InvalidConfig(_))));
+}
+
+#[tokio::test]
+async fn configured_headers_are_sent() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let server = tokio::spawn(async move {
+        let (mut socket, _) = listener.accept().await.unwrap();
+        let mut request = vec![0u8; 4096];
+        let n = socket.read(&mut request).await.unwrap();
+        socket
+            .write_all(b"HTTP/1.1 200 OK\r\ncontent-length: 0\r\nconnection: close\r\n\r\n")
+            .await
+            .unwrap();
+        String::from_utf8_lossy(&request[..n]).to_lowercase()
+    });
+
+    let coinbase =
+        Coinbase::with_http_config(HttpClientConfig::new().with_api_key("X-API-KEY", "secret"))
+            .unwrap();
+    coinbase
+        .client()
+        .get(format!("http://{}/time", addr))
+        .send()
+        .await
+        .unwrap();
+
+    let request = server.await.unwrap();
+    assert!(request.contains("user-agent: aeon-market-scanner-rs"));
+    assert!(request.contains("x-api-key: secret"));
+}
@@ -1,6 +1,6 @@
 mod common;
 
-use aeon_market_scanner_rs::{CexExchange, Exchange, Htx};
+use aeon_market_scanner_rs::{CEXTrait, CexExchange, Exchange, Htx};
 use common::{
     test_get_price_common, test_get_price_empty_symbol_common,
     test_get_price_invalid_symbol_common, test_health_check_common,
@@ -31,3 +31,25 @@ async fn test_htx_invalid_symbol() {
 async fn test_htx_empty_symbol() {
     test_get_price_empty_symbol_common(&Htx::new(), "HTX").await;
 }
+
+#[tokio::test]
+async fn test_htx_get_order_book() {
+    let book = Htx::new()
+        .get_order_book("BTCUSDT", 5)
+        .await
+        .expect("Should be able to get HTX order book");
+
+    assert_eq!(book.exchange, Exchange::Cex(CexExchange::Htx));
+    assert!(!book.bids.is_empty() && book.bids.len() <= 5);
+    assert!(!book.asks.is_empty() && book.asks.len() <= 5);
+    // Bids descending, asks ascending, best bid below best ask
+    assert!(book.bids.windows(2).all(|w| w[0].price >= w[1].price));
+    assert!(book.asks.windows(2).all(|w| w[0].price <= w[1].price));
+    assert!(book.bids[0].price <= book.asks[0].price);
+    println!(
+        "HTX {} levels: {} bids / {} asks",
+        book.symbol,
+        book.bids.len(),
+        book.asks.len()
+    );
+}
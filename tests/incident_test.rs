@@ -0,0 +1,191 @@
+use aeon_market_scanner_rs::common::{DecodeWarning, MarketScannerError, Timestamp};
+use aeon_market_scanner_rs::scanner::{
+    CircuitState, IncidentAction, IncidentKind, IncidentSeverity, VenueFeed, VenueStatus,
+    VenueStatusEvent,
+};
+use aeon_market_scanner_rs::{
+    CexExchange, IncidentRouter, IncidentSink, OpsgenieSink, PagerDutySink, ScannerEvent,
+    VenueIncident,
+};
+use async_trait::async_trait;
+use std::sync::{Arc, Mutex};
+
+#[derive(Clone, Default)]
+struct RecordingSink {
+    sent: Arc<Mutex<Vec<VenueIncident>>>,
+    fail: bool,
+}
+
+#[async_trait]
+impl IncidentSink for RecordingSink {
+    async fn send(&self, incident: &VenueIncident) -> Result<(), MarketScannerError> {
+        if self.fail {
+            return Err(MarketScannerError::ApiError("sink down".into()));
+        }
+        self.sent.lock().unwrap().push(incident.clone());
+        Ok(())
+    }
+}
+
+fn circuit(state: CircuitState) -> ScannerEvent {
+    ScannerEvent::CircuitStateChanged {
+        exchange: CexExchange::OKX,
+        state,
+        timestamp: Timestamp::from_millis(1_700_000_000_000),
+    }
+}
+
+#[test]
+fn health_changes_map_to_incidents() {
+    let opened = VenueIncident::from_scanner_event(&circuit(CircuitState::Open)).unwrap();
+    assert_eq!(opened.kind, IncidentKind::CircuitOpen);
+    assert_eq!(opened.action, IncidentAction::Trigger);
+    assert_eq!(opened.severity, IncidentSeverity::Critical);
+    assert!(VenueIncident::from_scanner_event(&circuit(CircuitState::HalfOpen)).is_none());
+    let closed = VenueIncident::from_scanner_event(&circuit(CircuitState::Closed)).unwrap();
+    assert_eq!(closed.action, IncidentAction::Resolve);
+    assert_eq!(closed.dedup_key(), opened.dedup_key());
+
+    let ws_down = VenueIncident::from_scanner_event(&ScannerEvent::FeedChanged {
+        exchange: CexExchange::Bybit,
+        feed: VenueFeed::RestPolling,
+        timestamp: Timestamp::from_millis(1),
+    })
+    .unwrap();
+    assert_eq!(ws_down.kind, IncidentKind::WebSocketDown);
+    assert!(ws_down.summary.contains("REST polling"));
+    assert!(VenueIncident::from_scanner_event(&ScannerEvent::Opportunities(vec![])).is_none());
+
+    let maintenance = VenueStatusEvent {
+        exchange: CexExchange::Binance,
+        previous: VenueStatus::Operational,
+        status: VenueStatus::Maintenance,
+        timestamp: Timestamp::from_millis(1),
+    };
+    let incident = VenueIncident::from_venue_status(&maintenance).unwrap();
+    assert_eq!(
+        (incident.kind, incident.severity),
+        (IncidentKind::Maintenance, IncidentSeverity::Warning)
+    );
+    let unknown = VenueStatusEvent {
+        previous: VenueStatus::Unknown,
+        status: VenueStatus::Operational,
+        ..maintenance
+    };
+    assert!(VenueIncident::from_venue_status(&unknown).is_none());
+
+    let decode = VenueIncident::from_decode_warning(&DecodeWarning {
+        exchange: CexExchange::Kraken,
+        error_rate: 0.35,
+        failures: 7,
+        messages: 20,
+        timestamp: Timestamp::from_millis(1),
+    });
+    assert_eq!(
+        decode.summary,
+        "Kraken: 35% of WebSocket messages fail to decode (7 of 20)"
+    );
+
+    let failed: Result<(), _> = Err(MarketScannerError::ApiError("502".into()));
+    let rest = VenueIncident::from_rest_result(&CexExchange::Htx, &failed);
+    assert_eq!(
+        (rest.kind, rest.action),
+        (IncidentKind::RestFailing, IncidentAction::Trigger)
+    );
+    let recovered = VenueIncident::from_rest_result(&CexExchange::Htx, &Ok(()));
+    assert_eq!(recovered.action, IncidentAction::Resolve);
+}
+
+#[tokio::test]
+async fn router_only_forwards_state_changes() {
+    let sink = RecordingSink::default();
+    let router = IncidentRouter::new().with_sink(sink.clone());
+
+    assert!(
+        router
+            .handle_scanner_event(&circuit(CircuitState::Open))
+            .await
+            .unwrap()
+    );
+    // Repeated trigger while open, and a half-open probe: nothing sent
+    assert!(
+        !router
+            .handle_scanner_event(&circuit(CircuitState::Open))
+            .await
+            .unwrap()
+    );
+    assert!(
+        !router
+            .handle_scanner_event(&circuit(CircuitState::HalfOpen))
+            .await
+            .unwrap()
+    );
+    assert_eq!(router.open_incidents().len(), 1);
+
+    assert!(
+        router
+            .handle_scanner_event(&circuit(CircuitState::Closed))
+            .await
+            .unwrap()
+    );
+    assert!(
+        !router
+            .handle_scanner_event(&circuit(CircuitState::Closed))
+            .await
+            .unwrap()
+    );
+    assert!(router.open_incidents().is_empty());
+
+    let sent = sink.sent.lock().unwrap();
+    assert_eq!(sent.len(), 2);
+    assert_eq!(sent[1].action, IncidentAction::Resolve);
+}
+
+#[tokio::test]
+async fn failing_sinks_do_not_block_the_others() {
+    let healthy = RecordingSink::default();
+    let router = IncidentRouter::new()
+        .with_sink(RecordingSink {
+            fail: true,
+            ..RecordingSink::default()
+        })
+        .with_sink(healthy.clone());
+    let incident = VenueIncident::trigger(CexExchange::OKX, IncidentKind::RestFailing, "down");
+    let error = router.report(incident).await.unwrap_err();
+    assert!(error.to_string().contains("sink down"));
+    assert_eq!(healthy.sent.lock().unwrap().len(), 1);
+    // The incident still counts as open
+    assert_eq!(router.open_incidents().len(), 1);
+}
+
+#[test]
+fn pagerduty_and_opsgenie_payloads() {
+    let opened = VenueIncident::from_scanner_event(&circuit(CircuitState::Open)).unwrap();
+    let closed = VenueIncident::from_scanner_event(&circuit(CircuitState::Closed)).unwrap();
+
+    let pagerduty = PagerDutySink::new("routing-key").with_source("scanner-eu-1");
+    let event = pagerduty.event(&opened);
+    assert_eq!(event["routing_key"], "routing-key");
+    assert_eq!(event["event_action"], "trigger");
+    assert_eq!(event["dedup_key"], "aeon-market-scanner-OKX-CircuitOpen");
+    assert_eq!(event["payload"]["severity"], "critical");
+    assert_eq!(event["payload"]["source"], "scanner-eu-1");
+    assert_eq!(event["payload"]["component"], "OKX");
+    assert_eq!(event["payload"]["timestamp"], "2023-11-14T22:13:20.000Z");
+    let event = pagerduty.event(&closed);
+    assert_eq!(event["event_action"], "resolve");
+    assert_eq!(event["dedup_key"], "aeon-market-scanner-OKX-CircuitOpen");
+    assert!(event.get("payload").is_none());
+
+    let opsgenie = OpsgenieSink::new("genie-key").with_base_url("https://api.eu.opsgenie.com/");
+    let (url, body) = opsgenie.request(&opened);
+    assert_eq!(url, "https://api.eu.opsgenie.com/v2/alerts");
+    assert_eq!(body["alias"], "aeon-market-scanner-OKX-CircuitOpen");
+    assert_eq!(body["priority"], "P1");
+    assert_eq!(body["tags"][2], "CircuitOpen");
+    let (url, _) = opsgenie.request(&closed);
+    assert_eq!(
+        url,
+        "https://api.eu.opsgenie.com/v2/alerts/aeon-market-scanner-OKX-CircuitOpen/close?identifierType=alias"
+    );
+}
@@ -0,0 +1,93 @@
+use aeon_market_scanner_rs::common::{
+    clear_instrument_ids, format_symbol_for_exchange, instrument_id, instrument_ids_refreshed_at,
+    load_instrument_ids, record_instrument_ids, save_instrument_ids,
+    standard_symbol_for_cex_ws_response,
+};
+use aeon_market_scanner_rs::{
+    ArbitrageScanner, CexExchange, register_symbol_override, remove_symbol_override,
+};
+use std::collections::HashMap;
+use std::time::Duration;
+
+fn ids(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+    pairs
+        .iter()
+        .map(|(standard, id)| (standard.to_string(), id.to_string()))
+        .collect()
+}
+
+#[test]
+fn cached_ids_replace_the_heuristics() {
+    // Heuristic: Bitfinex has no way to know DOGE trades as DOGE:USD
+    assert_eq!(
+        format_symbol_for_exchange("DOGEUSD", &CexExchange::Bitfinex).unwrap(),
+        "tDOGEUSD"
+    );
+    record_instrument_ids(
+        &CexExchange::Bitfinex,
+        ids(&[("DOGEUSD", "tDOGE:USD"), ("btc-usdt", "tBTCUST")]),
+    );
+    assert!(instrument_ids_refreshed_at(&CexExchange::Bitfinex).is_some());
+    assert_eq!(
+        format_symbol_for_exchange("DOGE-USD", &CexExchange::Bitfinex).unwrap(),
+        "tDOGE:USD"
+    );
+    assert_eq!(
+        instrument_id(&CexExchange::Bitfinex, "BTCUSDT").as_deref(),
+        Some("tBTCUST")
+    );
+    assert_eq!(
+        standard_symbol_for_cex_ws_response("tDOGE:USD", &CexExchange::Bitfinex),
+        "DOGEUSD"
+    );
+
+    // User overrides still win
+    register_symbol_override(&CexExchange::Bitfinex, "DOGEUSD", "tDOGEF0:USTF0");
+    assert_eq!(
+        format_symbol_for_exchange("DOGEUSD", &CexExchange::Bitfinex).unwrap(),
+        "tDOGEF0:USTF0"
+    );
+    remove_symbol_override(&CexExchange::Bitfinex, "DOGEUSD");
+
+    clear_instrument_ids(&CexExchange::Bitfinex);
+    assert!(instrument_ids_refreshed_at(&CexExchange::Bitfinex).is_none());
+    assert_eq!(
+        format_symbol_for_exchange("DOGEUSD", &CexExchange::Bitfinex).unwrap(),
+        "tDOGEUSD"
+    );
+}
+
+#[test]
+fn cache_survives_a_restart_through_a_file() {
+    let path =
+        std::env::temp_dir().join(format!("aeon_instrument_ids_{}.json", std::process::id()));
+    record_instrument_ids(&CexExchange::Upbit, ids(&[("BTCKRW", "KRW-BTC")]));
+    save_instrument_ids(&path).unwrap();
+    clear_instrument_ids(&CexExchange::Upbit);
+    assert!(instrument_id(&CexExchange::Upbit, "BTCKRW").is_none());
+
+    assert_eq!(load_instrument_ids(&path).unwrap(), 1);
+    assert_eq!(
+        format_symbol_for_exchange("BTCKRW", &CexExchange::Upbit).unwrap(),
+        "KRW-BTC"
+    );
+    assert!(load_instrument_ids(path.with_extension("missing")).is_err());
+    std::fs::remove_file(&path).ok();
+}
+
+#[tokio::test]
+async fn venues_without_a_listing_report_an_error() {
+    let refreshes = ArbitrageScanner::refresh_instrument_ids(&[CexExchange::Binance]).await;
+    assert_eq!(refreshes.len(), 1);
+    assert_eq!(refreshes[0].instruments, 0);
+    assert!(refreshes[0].error.is_some());
+
+    assert!(
+        ArbitrageScanner::refresh_instrument_ids_every(
+            &[CexExchange::Kraken],
+            Duration::ZERO,
+            None
+        )
+        .is_err()
+    );
+}
@@ -37,3 +37,11 @@ async fn test_kraken_invalid_symbol() {
 async fn test_kraken_empty_symbol() {
     test_get_price_empty_symbol_common(&Kraken::new(), "Kraken").await;
 }
+
+#[tokio::test]
+async fn test_kraken_get_price_resolves_pair_alias() {
+    // Kraken lists DOGE as XDG (XDGUSDT)
+    let price = Kraken::new().get_price("DOGEUSDT").await.unwrap();
+    assert_eq!(price.symbol, "DOGEUSDT");
+    assert!(price.bid_price > 0.0 && price.ask_price >= price.bid_price);
+}
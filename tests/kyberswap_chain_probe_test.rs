@@ -0,0 +1,56 @@
+use aeon_market_scanner_rs::common::Timestamp;
+use aeon_market_scanner_rs::dex::chains::{ChainId, Token};
+use aeon_market_scanner_rs::dex::kyberswap::{
+    CHAIN_PROBE_TTL, ChainProbe, chain_probe, clear_chain_probes, is_chain_skipped,
+    record_chain_probe,
+};
+use aeon_market_scanner_rs::{DEXTrait, KyberSwap, MarketScannerError};
+use std::time::Duration;
+
+fn failed_probe(chain: ChainId, checked_at: Timestamp) -> ChainProbe {
+    ChainProbe {
+        chain,
+        routable: false,
+        checked_at,
+        error: Some("KyberSwap API error: status 404 Not Found".to_string()),
+    }
+}
+
+#[tokio::test]
+async fn failed_chain_is_skipped_until_probe_expires_or_is_cleared() {
+    assert!(!is_chain_skipped(&ChainId::MANTLE));
+
+    record_chain_probe(failed_probe(ChainId::MANTLE, Timestamp::now()));
+    assert!(is_chain_skipped(&ChainId::MANTLE));
+    assert!(!chain_probe(&ChainId::MANTLE).unwrap().routable);
+
+    // Quotes fail fast without a request
+    let base = Token::create("0x1", "Wrapped Mantle", "WMNT", 18, ChainId::MANTLE);
+    let quote = Token::create("0x2", "USD Coin", "USDC", 6, ChainId::MANTLE);
+    let result = KyberSwap::new().get_price(&base, &quote, 100.0).await;
+    assert!(matches!(
+        result,
+        Err(MarketScannerError::UnsupportedChain(_))
+    ));
+
+    let expired = Timestamp::now().saturating_sub(CHAIN_PROBE_TTL + Duration::from_secs(1));
+    record_chain_probe(failed_probe(ChainId::MANTLE, expired));
+    assert!(!is_chain_skipped(&ChainId::MANTLE));
+
+    record_chain_probe(failed_probe(ChainId::RONIN, Timestamp::now()));
+    assert!(is_chain_skipped(&ChainId::RONIN));
+    clear_chain_probes();
+    assert!(chain_probe(&ChainId::RONIN).is_none());
+    assert!(!is_chain_skipped(&ChainId::RONIN));
+}
+
+#[test]
+fn routable_chain_is_not_skipped() {
+    record_chain_probe(ChainProbe {
+        chain: ChainId::SONIC,
+        routable: true,
+        checked_at: Timestamp::now(),
+        error: None,
+    });
+    assert!(!is_chain_skipped(&ChainId::SONIC));
+}
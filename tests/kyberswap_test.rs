@@ -1,7 +1,9 @@
 mod scanner_common;
 
+use aeon_market_scanner_rs::dex::chains::ChainId;
+use aeon_market_scanner_rs::dex::kyberswap::chain_probe;
 use aeon_market_scanner_rs::{
-    DEXTrait, DexAggregator, DexRouteSummary, Exchange, ExchangeTrait, KyberSwap,
+    AmountSpec, DEXTrait, DexAggregator, DexRouteSummary, Exchange, ExchangeTrait, KyberSwap,
 };
 use scanner_common::{
     create_base_eth, create_base_usdc, create_bsc_bnb, create_bsc_usdt, create_eth_eth,
@@ -64,6 +66,27 @@ async fn test_kyberswap_exchange_name() {
     );
 }
 
+#[tokio::test]
+async fn test_kyberswap_get_price_by_base_amount() {
+    tokio::time::sleep(DELAY_BETWEEN_TESTS).await;
+    let exchange = KyberSwap::new();
+    let result = exchange
+        .get_price_for_amount(&create_eth_eth(), &create_eth_usdt(), AmountSpec::Base(0.5))
+        .await;
+    assert!(
+        result.is_ok(),
+        "Should be able to quote 0.5 ETH on Ethereum: {:?}",
+        result.err()
+    );
+    let price = result.unwrap();
+
+    // The ask route sells exactly the requested base amount
+    assert!((price.ask_qty - 0.5).abs() < 1e-9);
+    assert!(price.bid_price > 0.0 && price.ask_price > 0.0);
+    let ask_route = price.ask_route_summary.expect("ask route summary");
+    assert_eq!(ask_route.amount_in_wei, "500000000000000000");
+}
+
 #[tokio::test]
 async fn test_kyberswap_get_price_ethereum() {
     tokio::time::sleep(DELAY_BETWEEN_TESTS).await;
@@ -277,3 +300,17 @@ async fn test_kyberswap_get_price_different_chains() {
         println!("Got expected error: {:?}", e);
     }
 }
+
+#[tokio::test]
+async fn test_kyberswap_supported_chains() {
+    tokio::time::sleep(DELAY_BETWEEN_TESTS).await;
+    let chains = KyberSwap::new().supported_chains().await;
+    println!("Routable KyberSwap chains: {:?}", chains);
+
+    assert!(
+        chains.contains(&ChainId::ETHEREUM),
+        "Ethereum should be routable"
+    );
+    let probe = chain_probe(&ChainId::ETHEREUM).expect("probe recorded");
+    assert!(probe.routable && probe.error.is_none());
+}
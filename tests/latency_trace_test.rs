@@ -0,0 +1,98 @@
+mod scanner_common;
+
+use aeon_market_scanner_rs::common::{
+    CexPrice, FrameStamp, WsSymbolFilter, mark_ws_frame_received, set_latency_tracing_enabled,
+    take_frame_stamp,
+};
+use aeon_market_scanner_rs::{ArbitrageScanner, CexExchange, FeeOverrides, LatencyTrace};
+use scanner_common::price_at;
+use std::time::{Duration, Instant};
+
+#[test]
+fn parsed_prices_are_stamped_while_tracing_is_enabled() {
+    let filter = WsSymbolFilter::new(&CexExchange::Kucoin, &["BTCUSDT"]);
+
+    // Disabled by default: nothing is stamped
+    mark_ws_frame_received();
+    let untraced = price_at(CexExchange::Kucoin, 100.0, 1);
+    assert!(filter.allows(&untraced));
+    assert!(take_frame_stamp(&untraced).is_none());
+
+    set_latency_tracing_enabled(true);
+    mark_ws_frame_received();
+    let first = price_at(CexExchange::Kucoin, 100.0, 2);
+    let second = price_at(CexExchange::Kucoin, 101.0, 3);
+    assert!(filter.allows(&first));
+    assert!(filter.allows(&second));
+
+    let stamp = take_frame_stamp(&second).expect("stamped");
+    assert!(stamp.parsed_at >= stamp.received_at);
+    // Taken once; the older, never-taken stamp is dropped with it
+    assert!(take_frame_stamp(&second).is_none());
+    assert!(take_frame_stamp(&first).is_none());
+
+    // Prices the filter rejects are not stamped
+    let other = CexPrice {
+        symbol: "ETHUSDT".to_string(),
+        ..price_at(CexExchange::Kucoin, 10.0, 4)
+    };
+    assert!(!filter.allows(&other));
+    assert!(take_frame_stamp(&other).is_none());
+    set_latency_tracing_enabled(false);
+}
+
+#[test]
+fn trace_is_attached_to_opportunities_of_the_trigger_price() {
+    let fees = FeeOverrides::default()
+        .with_cex_taker_fee(CexExchange::Binance, 0.0)
+        .with_cex_taker_fee(CexExchange::OKX, 0.0)
+        .with_cex_taker_fee(CexExchange::Kraken, 0.0);
+    let trigger = price_at(CexExchange::Binance, 100.0, 10);
+    let prices = [
+        trigger.clone(),
+        price_at(CexExchange::OKX, 102.0, 10),
+        price_at(CexExchange::Kraken, 104.0, 10),
+    ];
+    let mut opps = ArbitrageScanner::opportunities_from_prices(&prices, &[], Some(&fees));
+    assert_eq!(opps.len(), 3);
+
+    let received_at = Instant::now();
+    let stamp = FrameStamp {
+        correlation_id: 7,
+        received_at,
+        parsed_at: received_at + Duration::from_micros(50),
+    };
+    let trace = LatencyTrace::new(
+        &trigger,
+        &stamp,
+        received_at + Duration::from_micros(150),
+        received_at + Duration::from_micros(400),
+        received_at + Duration::from_micros(450),
+    );
+    assert_eq!(trace.parse_us, 50);
+    assert_eq!(trace.cache_insert_us, 100);
+    assert_eq!(trace.match_us, 250);
+    assert_eq!(trace.emit_us, 50);
+    assert_eq!(trace.total_us, 450);
+    assert_eq!(trace.exchange, "Binance");
+
+    trace.attach(&trigger, &mut opps);
+    for opp in &opps {
+        let involves_binance =
+            opp.source_exchange == "Binance" || opp.destination_exchange == "Binance";
+        assert_eq!(opp.latency.is_some(), involves_binance);
+    }
+
+    let traced = opps.iter().find(|o| o.latency.is_some()).unwrap();
+    assert_eq!(
+        serde_json::to_value(traced).unwrap()["latency"]["correlation_id"],
+        7
+    );
+    let untraced = opps.iter().find(|o| o.latency.is_none()).unwrap();
+    assert!(
+        serde_json::to_value(untraced)
+            .unwrap()
+            .get("latency")
+            .is_none()
+    );
+}
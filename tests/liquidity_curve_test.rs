@@ -0,0 +1,138 @@
+mod scanner_common;
+
+use aeon_market_scanner_rs::common::{AmountSide, DexLadderPoint, OrderBook, OrderBookLevel};
+use aeon_market_scanner_rs::{
+    ArbitrageOpportunity, ArbitrageScanner, CexExchange, DexAggregator, DexPrice, DexPriceLadder,
+    Exchange, FeeOverrides, LiquidityCurve, LiquiditySource, Timestamp,
+};
+use scanner_common::CexPriceBuilder;
+
+fn fees() -> FeeOverrides {
+    FeeOverrides::default()
+        .with_cex_taker_fee(CexExchange::Binance, 0.0)
+        .with_dex_taker_fee(DexAggregator::KyberSwap, 0.0)
+}
+
+fn level(price: f64, quantity: f64) -> OrderBookLevel {
+    OrderBookLevel { price, quantity }
+}
+
+fn book() -> OrderBook {
+    OrderBook {
+        symbol: "ETHUSDT".to_string(),
+        bids: vec![level(1990.0, 1.0)],
+        asks: vec![level(2000.0, 0.5), level(2005.0, 1.0), level(2010.0, 2.0)],
+        timestamp: Timestamp::from_millis(1),
+        exchange: Exchange::Cex(CexExchange::Binance),
+    }
+}
+
+fn dex_price(bid: f64, bid_qty: f64, ask: f64, ask_qty: f64) -> DexPrice {
+    DexPrice {
+        symbol: "ETHUSDT".to_string(),
+        mid_price: (bid + ask) / 2.0,
+        bid_price: bid,
+        ask_price: ask,
+        bid_qty,
+        ask_qty,
+        timestamp: Timestamp::from_millis(1),
+        exchange: Exchange::Dex(DexAggregator::KyberSwap),
+        bid_route_summary: None,
+        ask_route_summary: None,
+        bid_route_data: None,
+        ask_route_data: None,
+        transfer_tax_bps: None,
+    }
+}
+
+/// Bids: 1 @ 2030, then 2 more @ 2000 (3 @ 2010 on average); asks: 1 @ 2040, then 2 more @
+/// 2055.
+fn ladder() -> DexPriceLadder {
+    DexPriceLadder::new(vec![
+        DexLadderPoint {
+            quote_amount: 2_000.0,
+            price: dex_price(2030.0, 1.0, 2040.0, 1.0),
+        },
+        DexLadderPoint {
+            quote_amount: 6_000.0,
+            price: dex_price(2010.0, 3.0, 2050.0, 3.0),
+        },
+    ])
+    .unwrap()
+}
+
+#[test]
+fn book_and_ladder_merge_into_one_curve_per_side() {
+    let curve = LiquidityCurve::new(&book(), &ladder(), Some(&fees()));
+    assert_eq!(curve.symbol, "ETHUSDT");
+
+    let asks: Vec<(LiquiditySource, f64, f64)> = curve
+        .asks
+        .iter()
+        .map(|p| (p.source, p.price, p.cumulative_quantity))
+        .collect();
+    assert_eq!(
+        asks,
+        [
+            (LiquiditySource::Cex, 2000.0, 0.5),
+            (LiquiditySource::Cex, 2005.0, 1.5),
+            (LiquiditySource::Cex, 2010.0, 3.5),
+            (LiquiditySource::Dex, 2040.0, 4.5),
+            (LiquiditySource::Dex, 2055.0, 6.5),
+        ]
+    );
+    let bids: Vec<(LiquiditySource, f64)> =
+        curve.bids.iter().map(|p| (p.source, p.price)).collect();
+    assert_eq!(
+        bids,
+        [
+            (LiquiditySource::Dex, 2030.0),
+            (LiquiditySource::Dex, 2000.0),
+            (LiquiditySource::Cex, 1990.0),
+        ]
+    );
+    assert_eq!(curve.asks[1].average_price, (1000.0 + 2005.0) / 1.5);
+
+    assert_eq!(curve.depth(AmountSide::Buy), 6.5);
+    assert_eq!(curve.depth(AmountSide::Sell), 4.0);
+    assert_eq!(
+        curve.average_price(AmountSide::Buy, 1.0),
+        Some((1000.0 + 1002.5) / 1.0)
+    );
+    assert_eq!(curve.average_price(AmountSide::Buy, 7.0), None);
+
+    let fees = fees().with_cex_taker_fee(CexExchange::Binance, 0.001);
+    let with_fees = LiquidityCurve::new(&book(), &ladder(), Some(&fees));
+    assert!((with_fees.asks[0].price - 2002.0).abs() < 1e-9);
+}
+
+#[test]
+fn cex_dex_opportunities_are_sized_from_the_curve() {
+    let curve = LiquidityCurve::new(&book(), &ladder(), Some(&fees()));
+    // 0.5 @ 2000 and 0.5 @ 2005 sell into the DEX's first 1 @ 2030
+    assert_eq!(curve.optimal_quantity(LiquiditySource::Cex), 1.0);
+    assert_eq!(curve.optimal_quantity(LiquiditySource::Dex), 0.0);
+
+    let cex = CexPriceBuilder::new(CexExchange::Binance, 1990.0, 2000.0)
+        .symbol("ETHUSDT")
+        .ask_qty(0.5)
+        .build();
+    let opportunity: ArbitrageOpportunity = ArbitrageScanner::opportunities_from_prices(
+        &[cex],
+        &[dex_price(2030.0, 1.0, 2040.0, 1.0)],
+        Some(&fees()),
+    )
+    .into_iter()
+    .find(|o| o.source_exchange == "Binance")
+    .unwrap();
+    assert_eq!(opportunity.executable_quantity, 0.5);
+
+    let sized = ArbitrageScanner::size_from_liquidity_curve(opportunity.clone(), &curve).unwrap();
+    assert_eq!(sized.executable_quantity, 1.0);
+    assert_eq!(sized.effective_ask, opportunity.effective_ask);
+
+    let mut other_venue = book();
+    other_venue.exchange = Exchange::Cex(CexExchange::OKX);
+    let other_curve = LiquidityCurve::new(&other_venue, &ladder(), Some(&fees()));
+    assert!(ArbitrageScanner::size_from_liquidity_curve(opportunity, &other_curve).is_none());
+}
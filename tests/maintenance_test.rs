@@ -0,0 +1,64 @@
+use aeon_market_scanner_rs::scanner::maintenance::okx_window_affects_spot;
+use aeon_market_scanner_rs::{CexExchange, MaintenanceMonitor, VenueStatus};
+use serde_json::json;
+
+#[test]
+fn maintenance_monitor_tracks_status_changes() {
+    let monitor = MaintenanceMonitor::new();
+    assert_eq!(monitor.status(&CexExchange::Binance), VenueStatus::Unknown);
+
+    let event = monitor
+        .set_status(&CexExchange::Binance, VenueStatus::Maintenance)
+        .expect("Unknown -> Maintenance should emit an event");
+    assert_eq!(event.exchange, CexExchange::Binance);
+    assert_eq!(event.previous, VenueStatus::Unknown);
+    assert_eq!(event.status, VenueStatus::Maintenance);
+
+    // Same status again is not a change
+    assert!(
+        monitor
+            .set_status(&CexExchange::Binance, VenueStatus::Maintenance)
+            .is_none()
+    );
+
+    // Clones share state
+    let clone = monitor.clone();
+    assert!(clone.is_in_maintenance(&CexExchange::Binance));
+
+    let venues = [CexExchange::Binance, CexExchange::OKX, CexExchange::Kraken];
+    assert_eq!(
+        monitor.available(&venues),
+        vec![CexExchange::OKX, CexExchange::Kraken]
+    );
+
+    let event = monitor
+        .set_status(&CexExchange::Binance, VenueStatus::Operational)
+        .expect("Maintenance -> Operational should emit an event");
+    assert_eq!(event.previous, VenueStatus::Maintenance);
+    assert_eq!(monitor.available(&venues).len(), 3);
+}
+
+#[test]
+fn okx_maintenance_only_counts_spot_trading_and_funding_windows() {
+    // Trading service windows without product lines cover spot
+    assert!(okx_window_affects_spot(
+        &json!({"serviceType": "5", "env": "1"})
+    ));
+    assert!(okx_window_affects_spot(&json!({"serviceType": "9"})));
+    assert!(okx_window_affects_spot(
+        &json!({"serviceType": "5", "productLine": "SPOT,FUTURES"})
+    ));
+    assert!(okx_window_affects_spot(
+        &json!({"serviceType": "99", "productLine": ["funding"]})
+    ));
+
+    // Unrelated services, product lines and demo trading leave the venue up
+    assert!(!okx_window_affects_spot(&json!({"serviceType": "7"})));
+    assert!(!okx_window_affects_spot(&json!({"serviceType": "11"})));
+    assert!(!okx_window_affects_spot(
+        &json!({"serviceType": "5", "productLine": "FUTURES,SWAP"})
+    ));
+    assert!(!okx_window_affects_spot(
+        &json!({"serviceType": "5", "env": "2"})
+    ));
+}
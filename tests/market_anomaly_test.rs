@@ -0,0 +1,129 @@
+mod scanner_common;
+
+use aeon_market_scanner_rs::{
+    AnomalyDetector, AnomalyDetectorConfig, AnomalyKind, CexExchange, Exchange, MarketKey,
+    detect_market_anomalies,
+};
+use scanner_common::quote_at;
+use tokio::sync::mpsc;
+
+#[test]
+fn single_venue_books_are_reported_once_per_episode() {
+    let mut detector = AnomalyDetector::new(AnomalyDetectorConfig::default());
+    assert!(
+        detector
+            .update(&quote_at(CexExchange::Binance, 100.0, 100.1, 0))
+            .is_empty()
+    );
+
+    let locked = detector.update(&quote_at(CexExchange::Binance, 100.0, 100.0, 1));
+    assert_eq!(locked.len(), 1);
+    assert_eq!(locked[0].kind, AnomalyKind::Locked);
+    assert!(!locked[0].is_cross_venue());
+    assert_eq!(locked[0].market, MarketKey::from_symbol("BTCUSDT"));
+
+    // Locked -> crossed is reported, a crossed book staying crossed is not
+    let crossed = detector.update(&quote_at(CexExchange::Binance, 100.2, 100.0, 2));
+    assert_eq!(crossed.len(), 1);
+    assert_eq!(crossed[0].kind, AnomalyKind::Crossed);
+    assert!((crossed[0].cross_bps() - 20.0).abs() < 1e-9);
+    assert!(
+        detector
+            .update(&quote_at(CexExchange::Binance, 100.3, 100.0, 3))
+            .is_empty()
+    );
+    assert_eq!(detector.active().len(), 1);
+
+    // Cleared, then crossed again: a new episode
+    assert!(
+        detector
+            .update(&quote_at(CexExchange::Binance, 100.0, 100.1, 4))
+            .is_empty()
+    );
+    assert!(detector.active().is_empty());
+    assert_eq!(
+        detector
+            .update(&quote_at(CexExchange::Binance, 100.2, 100.0, 5))
+            .len(),
+        1
+    );
+}
+
+#[test]
+fn venue_pairs_are_compared_before_fees() {
+    let config = AnomalyDetectorConfig::default().with_min_cross_venue_bps(50.0);
+    let mut detector = AnomalyDetector::new(config);
+    detector.update(&quote_at(CexExchange::Binance, 99.9, 100.0, 0));
+
+    // 20 bps above Binance's ask: below the threshold
+    assert!(
+        detector
+            .update(&quote_at(CexExchange::OKX, 100.2, 100.3, 100))
+            .is_empty()
+    );
+
+    // 100 bps above Binance's ask
+    let anomalies = detector.update(&quote_at(CexExchange::OKX, 101.0, 101.1, 200));
+    assert_eq!(anomalies.len(), 1);
+    let anomaly = &anomalies[0];
+    assert!(anomaly.is_cross_venue());
+    assert_eq!(anomaly.kind, AnomalyKind::Crossed);
+    assert_eq!(anomaly.bid_exchange, Exchange::Cex(CexExchange::OKX));
+    assert_eq!(anomaly.ask_exchange, Exchange::Cex(CexExchange::Binance));
+    assert_eq!(anomaly.ask_price, 100.0);
+
+    // Binance catches up: the pair clears
+    detector.update(&quote_at(CexExchange::Binance, 100.9, 101.0, 300));
+    assert!(detector.active().is_empty());
+}
+
+#[test]
+fn stale_quotes_and_disabled_cross_venue_checks_report_nothing() {
+    let mut detector = AnomalyDetector::new(AnomalyDetectorConfig::default());
+    detector.update(&quote_at(CexExchange::Binance, 99.9, 100.0, 0));
+    // Binance's quote is 10 s older
+    assert!(
+        detector
+            .update(&quote_at(CexExchange::OKX, 101.0, 101.1, 10_000))
+            .is_empty()
+    );
+
+    let config = AnomalyDetectorConfig::default().with_cross_venue(false);
+    let mut detector = AnomalyDetector::new(config);
+    detector.update(&quote_at(CexExchange::Binance, 99.9, 100.0, 0));
+    assert!(
+        detector
+            .update(&quote_at(CexExchange::OKX, 101.0, 101.1, 10))
+            .is_empty()
+    );
+    // Empty sides are skipped
+    assert!(
+        detector
+            .update(&quote_at(CexExchange::OKX, 0.0, 0.0, 20))
+            .is_empty()
+    );
+}
+
+#[tokio::test]
+async fn anomalies_stream_next_to_unchanged_prices() {
+    let (tx, rx) = mpsc::channel(16);
+    let (mut prices, mut anomalies) =
+        detect_market_anomalies(rx, AnomalyDetectorConfig::default().with_cross_venue(false));
+
+    tx.send(quote_at(CexExchange::Kraken, 100.0, 100.1, 0))
+        .await
+        .unwrap();
+    tx.send(quote_at(CexExchange::Kraken, 100.2, 100.1, 1))
+        .await
+        .unwrap();
+    drop(tx);
+
+    assert_eq!(prices.recv().await.unwrap().bid_price, 100.0);
+    assert_eq!(prices.recv().await.unwrap().bid_price, 100.2);
+    assert!(prices.recv().await.is_none());
+
+    let anomaly = anomalies.recv().await.unwrap();
+    assert_eq!(anomaly.kind, AnomalyKind::Crossed);
+    assert_eq!(anomaly.bid_exchange, Exchange::Cex(CexExchange::Kraken));
+    assert!(anomalies.recv().await.is_none());
+}
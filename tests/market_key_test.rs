@@ -0,0 +1,81 @@
+mod scanner_common;
+
+use aeon_market_scanner_rs::scanner::ArbitrageScanner;
+use aeon_market_scanner_rs::{CexExchange, Exchange, MarketKey, logical_market_key};
+use scanner_common::symbol_price;
+
+#[test]
+fn market_key_resolves_venue_spellings() {
+    let usdt = MarketKey::from_symbol("BTCUSDT");
+    assert_eq!(usdt.base, "BTC");
+    assert_eq!(usdt.quote, "USDT");
+
+    // Bitfinex UST and Kraken XBT resolve to the same logical market
+    assert_eq!(
+        logical_market_key("BTCUST", &Exchange::Cex(CexExchange::Bitfinex)),
+        usdt
+    );
+    assert_eq!(
+        logical_market_key("XBTUSDT", &Exchange::Cex(CexExchange::Kraken)),
+        usdt
+    );
+
+    // Upbit serves USD requests from its KRW market
+    let upbit = logical_market_key("BTCUSD", &Exchange::Cex(CexExchange::Upbit));
+    assert_eq!(upbit.quote, "KRW");
+    assert_ne!(upbit, MarketKey::from_symbol("BTCUSD"));
+}
+
+#[test]
+fn opportunities_only_pair_same_logical_market() {
+    // Bitfinex BTCUST is the same market as Binance BTCUSDT
+    let binance = symbol_price(CexExchange::Binance, "BTCUSDT", 99.0, 100.0);
+    let bitfinex = symbol_price(CexExchange::Bitfinex, "BTCUST", 110.0, 111.0);
+    let opps = ArbitrageScanner::opportunities_from_prices(&[binance, bitfinex], &[], None);
+    assert!(
+        opps.iter()
+            .any(|o| o.source_exchange == "Binance" && o.destination_exchange == "Bitfinex"),
+        "Expected Binance -> Bitfinex across USDT/UST spellings"
+    );
+
+    // A KRW-quoted Upbit price must never be matched against a USDT leg
+    let binance = symbol_price(CexExchange::Binance, "BTCUSDT", 99.0, 100.0);
+    let upbit = symbol_price(CexExchange::Upbit, "BTCUSD", 140_000_000.0, 140_100_000.0);
+    let opps = ArbitrageScanner::opportunities_from_prices(&[binance, upbit], &[], None);
+    assert!(opps.is_empty(), "KRW and USDT legs must not be paired");
+
+    // Different base assets are never paired
+    let eth = symbol_price(CexExchange::Binance, "ETHUSDT", 99.0, 100.0);
+    let btc = symbol_price(CexExchange::OKX, "BTCUSDT", 110.0, 111.0);
+    let opps = ArbitrageScanner::opportunities_from_prices(&[eth, btc], &[], None);
+    assert!(opps.is_empty(), "ETH and BTC legs must not be paired");
+}
+
+#[test]
+fn renamed_tickers_match_through_alias_map() {
+    use aeon_market_scanner_rs::{canonical_asset, register_asset_alias, remove_asset_alias};
+
+    // Default rename
+    assert_eq!(
+        MarketKey::from_symbol("MATICUSDT"),
+        MarketKey::from_symbol("POLUSDT")
+    );
+
+    // Custom rename (test-only ticker so parallel tests are unaffected)
+    assert_ne!(
+        MarketKey::from_symbol("OLDTKNUSDT"),
+        MarketKey::from_symbol("NEWTKNUSDT")
+    );
+    register_asset_alias("oldtkn", "NEWTKN");
+    assert_eq!(canonical_asset("OLDTKN"), "NEWTKN");
+    let old = symbol_price(CexExchange::Binance, "OLDTKNUSDT", 1.0, 1.01);
+    let new = symbol_price(CexExchange::OKX, "NEWTKNUSDT", 1.1, 1.11);
+    let opps = ArbitrageScanner::opportunities_from_prices(&[old, new], &[], None);
+    assert!(
+        !opps.is_empty(),
+        "Renamed ticker should pair with its new name"
+    );
+
+    assert_eq!(remove_asset_alias("OLDTKN").as_deref(), Some("NEWTKN"));
+    assert_eq!(canonical_asset("OLDTKN"), "OLDTKN");
+}
@@ -0,0 +1,131 @@
+mod scanner_common;
+
+use aeon_market_scanner_rs::common::TradingStatus;
+use aeon_market_scanner_rs::{
+    ArbitrageScanner, CexExchange, LiveScanOptions, MatchMetrics, RejectReason, ScannerHandle,
+    ScannerSettings, SettingsHandle, clear_ws_endpoint_override, set_ws_endpoint_override,
+};
+use futures::SinkExt;
+use scanner_common::{CexPriceBuilder, binance_okx_fees, poll_until};
+use std::time::Duration;
+use tokio::net::TcpListener;
+use tokio_tungstenite::tungstenite::Message;
+
+/// Local WebSocket server sending `frame` every 10 ms.
+async fn ws_server(frame: &'static str) -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("ws://{}", listener.local_addr().unwrap());
+    tokio::spawn(async move {
+        while let Ok((stream, _)) = listener.accept().await {
+            tokio::spawn(async move {
+                let Ok(mut ws) = tokio_tungstenite::accept_async(stream).await else {
+                    return;
+                };
+                while ws.send(Message::Text(frame.to_string())).await.is_ok() {
+                    tokio::time::sleep(Duration::from_millis(10)).await;
+                }
+            });
+        }
+    });
+    url
+}
+
+#[test]
+fn matching_counts_pairs_and_rejections() {
+    let fees = binance_okx_fees();
+    let prices = [
+        CexPriceBuilder::new(CexExchange::Binance, 99.0, 100.0)
+            .now()
+            .status(TradingStatus::Trading)
+            .build(),
+        CexPriceBuilder::new(CexExchange::OKX, 101.0, 102.0)
+            .now()
+            .status(TradingStatus::Trading)
+            .build(),
+        CexPriceBuilder::new(CexExchange::Bybit, 150.0, 151.0)
+            .now()
+            .status(TradingStatus::Halted)
+            .build(),
+    ];
+    let mut metrics = MatchMetrics::new();
+    let opportunities = ArbitrageScanner::opportunities_from_prices_with_metrics(
+        &prices,
+        &[],
+        Some(&fees),
+        &mut metrics,
+    );
+
+    assert_eq!(opportunities.len(), 1);
+    // Binance / OKX as buy × sell legs; the halted Bybit price is left out
+    assert_eq!(metrics.candidates_evaluated, 4);
+    assert_eq!(metrics.rejected(RejectReason::NotTrading), 1);
+    assert_eq!(metrics.rejected(RejectReason::SameVenue), 2);
+    assert_eq!(metrics.rejected(RejectReason::NoSpread), 1);
+    assert_eq!(metrics.rejected(RejectReason::BelowMinSpread), 0);
+    assert_eq!(metrics.total_rejected(), 4);
+    assert_eq!(metrics.opportunities_emitted, 1);
+    assert_eq!(metrics.emit_ratio(), 0.25);
+
+    let json = serde_json::to_value(&metrics).unwrap();
+    assert_eq!(json["rejected"]["SameVenue"], 2);
+}
+
+#[tokio::test]
+async fn live_scanner_accumulates_match_metrics() {
+    // Combined stream frame, as two symbols are subscribed
+    let binance = ws_server(
+        r#"{"stream":"btcusdt@bookTicker","data":{"u":1,"s":"BTCUSDT","b":"100.0","B":"1.0","a":"100.1","A":"1.0"}}"#,
+    )
+    .await;
+    let okx = ws_server(
+        r#"{"arg":{"channel":"books5","instId":"BTC-USDT"},"data":[{"bids":[["101.5","1"]],"asks":[["101.6","1"]]}]}"#,
+    )
+    .await;
+    set_ws_endpoint_override(&CexExchange::Binance, &binance);
+    set_ws_endpoint_override(&CexExchange::OKX, &okx);
+
+    // The Binance -> OKX spread (~1.3%) is matched, then dropped by the settings threshold
+    let settings = SettingsHandle::new(ScannerSettings {
+        min_spread_percent: Some(50.0),
+        ..ScannerSettings::default()
+    });
+    let handle = ScannerHandle::new();
+    let options = LiveScanOptions::default()
+        .with_handle(handle.clone())
+        .with_settings(settings);
+    let mut events = ArbitrageScanner::scan_live(
+        &["BTCUSDT", "ETHUSDT"],
+        &[CexExchange::Binance, CexExchange::OKX],
+        options,
+    )
+    .await
+    .unwrap();
+    tokio::spawn(async move { while events.recv().await.is_some() {} });
+
+    let metrics = poll_until(Duration::from_secs(10), || {
+        Some(handle.match_metrics()).filter(|metrics| {
+            metrics.snapshots > 10
+                && metrics.rejected(RejectReason::SameVenue) > 0
+                && metrics.rejected(RejectReason::SettingsFilter) > 0
+        })
+    })
+    .await
+    .unwrap_or_else(|| panic!("{:?}", handle.match_metrics()));
+    assert!(metrics.candidates_evaluated > 0);
+    assert_eq!(metrics.opportunities_emitted, 0);
+    assert_eq!(metrics.cache_sizes["BTCUSDT"], 2);
+    assert_eq!(metrics.cache_sizes["ETHUSDT"], 0);
+    assert!(metrics.updated_at.is_some());
+
+    // Pause first so a snapshot already being matched cannot land after the reset
+    handle.pause();
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    handle.reset_match_metrics();
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    // Nothing is matched while paused
+    assert_eq!(handle.match_metrics().snapshots, 0);
+
+    handle.stop();
+    clear_ws_endpoint_override(&CexExchange::Binance);
+    clear_ws_endpoint_override(&CexExchange::OKX);
+}
@@ -0,0 +1,109 @@
+mod scanner_common;
+
+use aeon_market_scanner_rs::common::{CexPrice, DexPrice, DexRouteSummary, Timestamp};
+use aeon_market_scanner_rs::{
+    ArbitrageOpportunity, ArbitrageScanner, CexExchange, DexAggregator, Exchange, FeeOverrides,
+    MevRisk, MevRiskModel,
+};
+use scanner_common::CexPriceBuilder;
+
+fn cex(bid: f64, ask: f64) -> CexPrice {
+    CexPriceBuilder::new(CexExchange::Binance, bid, ask)
+        .symbol("ETHUSDT")
+        .qty(10.0)
+        .build()
+}
+
+fn dex(bid: f64, ask: f64, gas_price_wei: Option<&str>) -> DexPrice {
+    let route = DexRouteSummary {
+        token_in: "USDT".to_string(),
+        token_out: "ETH".to_string(),
+        amount_in: ask,
+        amount_out: 1.0,
+        amount_in_wei: "0".to_string(),
+        amount_out_wei: "0".to_string(),
+        gas: None,
+        gas_price: gas_price_wei.map(str::to_string),
+        gas_usd: None,
+    };
+    DexPrice {
+        symbol: "ETHUSDT".to_string(),
+        mid_price: (bid + ask) / 2.0,
+        bid_price: bid,
+        ask_price: ask,
+        bid_qty: 10.0,
+        ask_qty: 10.0,
+        timestamp: Timestamp::from_millis(1),
+        exchange: Exchange::Dex(DexAggregator::KyberSwap),
+        bid_route_summary: None,
+        ask_route_summary: Some(route),
+        bid_route_data: None,
+        ask_route_data: None,
+        transfer_tax_bps: None,
+    }
+}
+
+/// Buy on KyberSwap at `ask`, sell on Binance at 2100.
+fn dex_to_cex(dex_price: DexPrice) -> ArbitrageOpportunity {
+    let fees = FeeOverrides::default()
+        .with_cex_taker_fee(CexExchange::Binance, 0.0)
+        .with_dex_taker_fee(DexAggregator::KyberSwap, 0.0);
+    ArbitrageScanner::opportunities_from_prices(&[cex(2100.0, 2101.0)], &[dex_price], Some(&fees))
+        .into_iter()
+        .find(|o| o.source_exchange == "KyberSwap")
+        .expect("KyberSwap -> Binance opportunity")
+}
+
+#[test]
+fn cex_only_opportunities_are_not_graded() {
+    let binance = cex(2000.0, 2001.0);
+    let mut okx = cex(2100.0, 2101.0);
+    okx.exchange = Exchange::Cex(CexExchange::OKX);
+    let opp = ArbitrageScanner::opportunities_from_prices(&[binance, okx], &[], None)
+        .into_iter()
+        .next()
+        .expect("opportunity");
+    assert_eq!(MevRiskModel::default().classify(&opp), None);
+}
+
+#[test]
+fn tight_quote_in_deep_pool_is_low_risk() {
+    let opp = dex_to_cex(dex(1999.0, 2000.0, None));
+    let model = MevRiskModel::default().with_liquidity(
+        Exchange::Dex(DexAggregator::KyberSwap),
+        "ETH-USDT",
+        100_000_000.0,
+    );
+    assert_eq!(model.classify(&opp), Some(MevRisk::Low));
+}
+
+#[test]
+fn large_trade_against_shallow_pool_is_high_risk() {
+    // 10 ETH at 2000 = 20k against a 500k pool (4%)
+    let opp = dex_to_cex(dex(1999.0, 2000.0, None));
+    let model = MevRiskModel::default().with_liquidity(
+        Exchange::Dex(DexAggregator::KyberSwap),
+        "ETHUSDT",
+        500_000.0,
+    );
+    assert_eq!(model.classify(&opp), Some(MevRisk::High));
+}
+
+#[test]
+fn price_impact_and_congestion_combine() {
+    // ~0.5% impact alone is Medium
+    let opp = dex_to_cex(dex(1980.0, 2000.0, Some("20000000000")));
+    assert_eq!(
+        MevRiskModel::default().classify(&opp),
+        Some(MevRisk::Medium)
+    );
+
+    // 20 gwei route on a congested mempool (>= 15 gwei) adds a second Medium signal
+    let model = MevRiskModel::default().with_congested_gas_gwei(15.0);
+    let mut opps = vec![opp];
+    model.classify_all(&mut opps);
+    assert_eq!(opps[0].mev_risk, Some(MevRisk::High));
+
+    let json = serde_json::to_value(&opps[0]).unwrap();
+    assert_eq!(json["mev_risk"], "High");
+}
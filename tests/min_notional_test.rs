@@ -0,0 +1,115 @@
+mod scanner_common;
+
+use aeon_market_scanner_rs::{CexExchange, MinNotionalGuard, OrderLimits, ScannerContext};
+use scanner_common::{binance_okx_fees, binance_to_okx_with_depth, two_venue_prices};
+
+fn guard() -> MinNotionalGuard {
+    MinNotionalGuard::new()
+        .with_limits(
+            CexExchange::Binance,
+            "BTCUSDT",
+            OrderLimits {
+                min_quantity: None,
+                min_notional: Some(5.0),
+                ..OrderLimits::default()
+            },
+        )
+        .with_venue_default(
+            CexExchange::OKX,
+            OrderLimits {
+                min_quantity: Some(0.1),
+                min_notional: Some(1.0),
+                ..OrderLimits::default()
+            },
+        )
+}
+
+#[test]
+fn min_notional_takes_the_larger_of_quantity_and_value() {
+    let limits = OrderLimits {
+        min_quantity: Some(0.1),
+        min_notional: Some(5.0),
+        ..OrderLimits::default()
+    };
+    assert_eq!(limits.min_notional_at(20.0), 5.0);
+    assert_eq!(limits.min_notional_at(100.0), 10.0);
+    assert_eq!(OrderLimits::default().min_notional_at(100.0), 0.0);
+}
+
+#[test]
+fn floor_is_the_larger_leg_minimum() {
+    let guard = guard();
+    assert_eq!(
+        guard
+            .limits(&CexExchange::OKX, "ETHUSDT")
+            .unwrap()
+            .min_quantity,
+        Some(0.1)
+    );
+    assert!(guard.limits(&CexExchange::Bybit, "BTCUSDT").is_none());
+
+    let mut opportunity = binance_to_okx_with_depth(1.0);
+    guard.annotate(&mut opportunity);
+    let floor = opportunity.notional_floor.as_ref().unwrap();
+    // OKX: 0.1 × 110 = 11 beats Binance's 5
+    assert_eq!(floor.min_notional, 11.0);
+    assert_eq!(floor.binding_exchange, "OKX");
+    assert_eq!(floor.executable_notional, 100.0);
+    assert!(!opportunity.is_below_min_notional());
+}
+
+#[test]
+fn opportunities_below_the_floor_are_marked_not_executable() {
+    let mut opportunity = binance_to_okx_with_depth(0.05);
+    guard().annotate(&mut opportunity);
+    assert!(opportunity.is_below_min_notional());
+
+    let json = serde_json::to_value(&opportunity).unwrap();
+    assert_eq!(json["notional_floor"]["binding_exchange"], "OKX");
+
+    let unchecked = binance_to_okx_with_depth(0.05);
+    assert!(!unchecked.is_below_min_notional());
+    assert!(
+        serde_json::to_value(&unchecked).unwrap()["notional_floor"].is_null(),
+        "no floor without a guard"
+    );
+}
+
+#[test]
+fn context_applies_the_guard_before_filters() {
+    let context = ScannerContext::new("desk")
+        .with_fee_overrides(binance_okx_fees())
+        .with_min_notional_guard(guard())
+        .with_filter(|o| !o.is_below_min_notional());
+    assert!(
+        context
+            .opportunities_from_prices(
+                &two_venue_prices(
+                    "BTCUSDT",
+                    CexExchange::Binance,
+                    100.0,
+                    CexExchange::OKX,
+                    110.0,
+                    0.05
+                ),
+                &[]
+            )
+            .is_empty()
+    );
+    assert_eq!(
+        context
+            .opportunities_from_prices(
+                &two_venue_prices(
+                    "BTCUSDT",
+                    CexExchange::Binance,
+                    100.0,
+                    CexExchange::OKX,
+                    110.0,
+                    1.0
+                ),
+                &[]
+            )
+            .len(),
+        1
+    );
+}
@@ -0,0 +1,121 @@
+mod scanner_common;
+
+use aeon_market_scanner_rs::common::CexPrice;
+use aeon_market_scanner_rs::portfolio::TradeSide;
+use aeon_market_scanner_rs::{
+    ArbitrageScanner, CexExchange, Exchange, FeeOverrides, MarketScannerError, MultiHopOptions,
+};
+use scanner_common::{sized_price, zero_taker_fees};
+
+fn fees() -> FeeOverrides {
+    zero_taker_fees(&[
+        CexExchange::Binance,
+        CexExchange::Kraken,
+        CexExchange::OKX,
+        CexExchange::Bybit,
+    ])
+}
+
+/// Buy BTC for USDT on Binance, sell it for EUR on Kraken, sell the EUR on OKX.
+fn prices() -> Vec<CexPrice> {
+    vec![
+        sized_price(CexExchange::Binance, "BTCUSDT", 49_990.0, 50_000.0, 1.0),
+        sized_price(CexExchange::Kraken, "BTCEUR", 47_000.0, 47_100.0, 0.5),
+        sized_price(CexExchange::OKX, "EURUSDT", 1.07, 1.071, 100_000.0),
+    ]
+}
+
+#[test]
+fn three_venue_chains_are_found() {
+    let chains = ArbitrageScanner::multi_hop_opportunities_from_prices(
+        &prices(),
+        &MultiHopOptions::new(),
+        Some(&fees()),
+    );
+    assert_eq!(chains.len(), 1);
+    let chain = &chains[0];
+    assert_eq!(chain.start_asset, "USDT");
+    assert_eq!(chain.venues(), ["Binance", "Kraken", "OKX"]);
+    let sides: Vec<TradeSide> = chain.hops.iter().map(|h| h.side).collect();
+    assert_eq!(sides, [TradeSide::Buy, TradeSide::Sell, TradeSide::Sell]);
+    assert_eq!(chain.hops[1].from_asset, "BTC");
+    assert_eq!(chain.hops[1].to_asset, "EUR");
+
+    let expected_rate = 47_000.0 * 1.07 / 50_000.0;
+    assert!((chain.gross_rate - expected_rate).abs() < 1e-12);
+    assert!((chain.profit_percentage - 0.58).abs() < 1e-9);
+    // Kraken's 0.5 BTC limits the chain to 25k USDT
+    assert!((chain.executable_amount - 25_000.0).abs() < 1e-6);
+    assert!((chain.total_profit() - 145.0).abs() < 1e-6);
+
+    let strict = MultiHopOptions::new().with_min_profit_percent(1.0);
+    assert!(
+        ArbitrageScanner::multi_hop_opportunities_from_prices(&prices(), &strict, Some(&fees()))
+            .is_empty()
+    );
+    // Fees eat the spread
+    assert!(
+        ArbitrageScanner::multi_hop_opportunities_from_prices(
+            &prices(),
+            &MultiHopOptions::new(),
+            Some(&fees().with_cex_taker_fee(CexExchange::Kraken, 0.01)),
+        )
+        .is_empty()
+    );
+}
+
+#[test]
+fn options_bound_the_search() {
+    assert!(matches!(
+        MultiHopOptions::new().with_max_hops(2),
+        Err(MarketScannerError::InvalidConfig(_))
+    ));
+
+    // A single-venue triangle is left out unless one venue is enough
+    let triangle: Vec<CexPrice> = prices()
+        .into_iter()
+        .map(|mut p| {
+            p.exchange = Exchange::Cex(CexExchange::Binance);
+            p
+        })
+        .collect();
+    let options = MultiHopOptions::new();
+    assert!(
+        ArbitrageScanner::multi_hop_opportunities_from_prices(&triangle, &options, Some(&fees()))
+            .is_empty()
+    );
+    let options = MultiHopOptions::new().with_min_venues(1);
+    assert_eq!(
+        ArbitrageScanner::multi_hop_opportunities_from_prices(&triangle, &options, Some(&fees()))
+            .len(),
+        1
+    );
+
+    // A direct 1% spread above the threshold skips the chain search
+    let mut with_direct = prices();
+    with_direct.push(sized_price(
+        CexExchange::Bybit,
+        "BTCUSDT",
+        50_500.0,
+        50_600.0,
+        1.0,
+    ));
+    let options = MultiHopOptions::new().with_direct_threshold_percent(0.5);
+    assert!(
+        ArbitrageScanner::multi_hop_opportunities_from_prices(
+            &with_direct,
+            &options,
+            Some(&fees())
+        )
+        .is_empty()
+    );
+    let options = MultiHopOptions::new().with_direct_threshold_percent(2.0);
+    assert!(
+        !ArbitrageScanner::multi_hop_opportunities_from_prices(
+            &with_direct,
+            &options,
+            Some(&fees())
+        )
+        .is_empty()
+    );
+}
@@ -0,0 +1,88 @@
+use aeon_market_scanner_rs::{
+    ArbitrageScanner, CexExchange, Exchange, FeeOverrides, OrderBook, OrderBookLevel, Timestamp,
+};
+
+fn level(price: f64, quantity: f64) -> OrderBookLevel {
+    OrderBookLevel { price, quantity }
+}
+
+fn book(exchange: CexExchange, bids: Vec<OrderBookLevel>, asks: Vec<OrderBookLevel>) -> OrderBook {
+    OrderBook {
+        symbol: "BTCUSDT".to_string(),
+        bids,
+        asks,
+        timestamp: Timestamp::from_millis(1),
+        exchange: Exchange::Cex(exchange),
+    }
+}
+
+fn zero_fees() -> FeeOverrides {
+    FeeOverrides::default()
+        .with_cex_taker_fee(CexExchange::Binance, 0.0)
+        .with_cex_taker_fee(CexExchange::OKX, 0.0)
+        .with_cex_taker_fee(CexExchange::Bybit, 0.0)
+}
+
+#[test]
+fn splits_large_buy_across_sell_venues() {
+    let books = vec![
+        // Cheap venue with deep asks
+        book(
+            CexExchange::Binance,
+            vec![level(99.0, 10.0)],
+            vec![level(100.0, 10.0)],
+        ),
+        // Two richer venues with limited bid depth
+        book(
+            CexExchange::OKX,
+            vec![level(103.0, 6.0)],
+            vec![level(104.0, 1.0)],
+        ),
+        book(
+            CexExchange::Bybit,
+            vec![level(102.0, 4.0)],
+            vec![level(105.0, 1.0)],
+        ),
+    ];
+
+    let fees = zero_fees();
+    let opps = ArbitrageScanner::multi_leg_opportunities_from_books(&books, Some(&fees));
+    let best = opps.first().expect("Expected a multi-leg opportunity");
+
+    assert_eq!(best.source_exchange, "Binance");
+    assert!((best.quantity - 10.0).abs() < 1e-9);
+    assert_eq!(best.tranches.len(), 2);
+    assert_eq!(best.tranches[0].destination_exchange, "OKX");
+    assert!((best.tranches[0].fraction - 0.6).abs() < 1e-9);
+    assert_eq!(best.tranches[1].destination_exchange, "Bybit");
+    assert!((best.tranches[1].fraction - 0.4).abs() < 1e-9);
+
+    // 6 × 3 + 4 × 2 = 26 quote profit
+    assert!((best.total_profit() - 26.0).abs() < 1e-9);
+}
+
+#[test]
+fn stops_when_levels_are_no_longer_profitable() {
+    let books = vec![
+        book(
+            CexExchange::Binance,
+            vec![level(99.0, 10.0)],
+            vec![level(100.0, 2.0), level(110.0, 10.0)],
+        ),
+        book(
+            CexExchange::OKX,
+            vec![level(105.0, 10.0)],
+            vec![level(106.0, 1.0)],
+        ),
+    ];
+
+    let fees = zero_fees();
+    let opps = ArbitrageScanner::multi_leg_opportunities_from_books(&books, Some(&fees));
+    let binance = opps
+        .iter()
+        .find(|o| o.source_exchange == "Binance")
+        .expect("Expected Binance as source");
+    // Second ask level (110) exceeds the best bid (105), so only 2 units match
+    assert!((binance.quantity - 2.0).abs() < 1e-9);
+    assert_eq!(binance.tranches.len(), 1);
+}
@@ -0,0 +1,101 @@
+mod scanner_common;
+
+use aeon_market_scanner_rs::common::CexPrice;
+use aeon_market_scanner_rs::{
+    ArbitrageOpportunity, ArbitrageScanner, CexExchange, OpportunityClosed, OpportunityExpiry,
+    ScannerContext, Timestamp,
+};
+use scanner_common::{binance_okx_fees, price};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// Binance ask 100 vs OKX bid `okx_bid`.
+fn prices(okx_bid: f64) -> Vec<CexPrice> {
+    vec![
+        price(CexExchange::Binance, 99.0, 100.0),
+        price(CexExchange::OKX, okx_bid, okx_bid + 1.0),
+    ]
+}
+
+fn opportunities(okx_bid: f64) -> Vec<ArbitrageOpportunity> {
+    ArbitrageScanner::opportunities_from_prices(&prices(okx_bid), &[], Some(&binance_okx_fees()))
+        .into_iter()
+        .filter(|o| o.source_exchange == "Binance")
+        .collect()
+}
+
+#[test]
+fn expiry_closes_opportunities_unseen_for_the_grace_period() {
+    let expiry = OpportunityExpiry::new(Duration::from_millis(500));
+    let at = Timestamp::from_millis;
+    assert!(expiry.observe(&opportunities(105.0), at(1_000)).is_empty());
+    assert!(expiry.observe(&opportunities(110.0), at(1_200)).is_empty());
+    assert!(expiry.observe(&opportunities(102.0), at(1_400)).is_empty());
+    assert_eq!(expiry.open_count(), 1);
+
+    // Gone, but still within the expiry
+    assert!(expiry.observe(&[], at(1_800)).is_empty());
+    let closed = expiry.observe(&[], at(1_900));
+    assert_eq!(closed.len(), 1);
+    let closed = &closed[0];
+    assert_eq!(closed.opportunity.key(), "Binance->OKX:BTCUSDT");
+    assert_eq!(closed.opened_at, at(1_000));
+    assert_eq!(closed.last_seen_at, at(1_400));
+    assert_eq!(closed.closed_at, at(1_900));
+    assert_eq!(closed.duration_ms, 400);
+    assert!((closed.max_spread_percentage - 10.0).abs() < 1e-9);
+    assert!((closed.opportunity.spread_percentage - 2.0).abs() < 1e-9);
+    assert_eq!(expiry.open_count(), 0);
+
+    let json = serde_json::to_value(closed).unwrap();
+    assert_eq!(json["duration_ms"], 400);
+}
+
+type Received = Arc<Mutex<Vec<(String, Vec<OpportunityClosed>)>>>;
+
+#[test]
+fn context_hands_closes_to_close_sinks() {
+    let received: Received = Arc::default();
+    let sink = received.clone();
+    let context = ScannerContext::new("desk")
+        .with_fee_overrides(binance_okx_fees())
+        .with_min_spread_percent(1.0)
+        .with_expiry(Duration::ZERO)
+        .with_close_sink(move |name, closed| {
+            sink.lock()
+                .unwrap()
+                .push((name.to_string(), closed.to_vec()))
+        });
+
+    let raw = |okx_bid| {
+        ArbitrageScanner::opportunities_from_prices(
+            &prices(okx_bid),
+            &[],
+            Some(&binance_okx_fees()),
+        )
+    };
+    let (open, closed) = context.process_at(raw(105.0), Timestamp::from_millis(1_000));
+    assert_eq!(open.len(), 1);
+    assert!(closed.is_empty());
+    assert!(received.lock().unwrap().is_empty());
+
+    // The spread collapses below the context's minimum
+    let (open, closed) = context.process_at(raw(100.5), Timestamp::from_millis(2_000));
+    assert!(open.is_empty());
+    assert_eq!(closed.len(), 1);
+    let received = received.lock().unwrap();
+    assert_eq!(received.len(), 1);
+    assert_eq!(received[0].0, "desk");
+    assert_eq!(received[0].1[0].duration_ms, 0);
+    assert_eq!(received[0].1[0].closed_at, Timestamp::from_millis(2_000));
+}
+
+#[test]
+fn contexts_without_expiry_report_no_closes() {
+    let context = ScannerContext::new("desk")
+        .with_fee_overrides(binance_okx_fees())
+        .with_close_sink(|_, _| panic!("no expiry configured"));
+    context.opportunities_from_prices(&prices(105.0), &[]);
+    let (_, closed) = context.process_at(Vec::new(), Timestamp::now());
+    assert!(closed.is_empty());
+}
@@ -0,0 +1,197 @@
+mod scanner_common;
+
+use aeon_market_scanner_rs::common::Timestamp;
+use aeon_market_scanner_rs::scanner::CloseReason;
+use aeon_market_scanner_rs::{LifecycleConfig, LifecycleEvent, OpportunityLifecycle};
+use scanner_common::binance_to_okx;
+
+fn kinds(events: &[LifecycleEvent]) -> Vec<&'static str> {
+    events
+        .iter()
+        .map(|event| match event {
+            LifecycleEvent::Opened { .. } => "opened",
+            LifecycleEvent::Closed {
+                reason: CloseReason::BelowExit,
+                ..
+            } => "closed",
+            LifecycleEvent::Closed {
+                reason: CloseReason::Gone,
+                ..
+            } => "gone",
+        })
+        .collect()
+}
+
+#[test]
+fn hysteresis_keeps_flapping_spreads_open() {
+    let mut lifecycle = OpportunityLifecycle::new(LifecycleConfig::new(0.5, 0.2)).unwrap();
+    let mut step = |spread: f64, millis: u64| {
+        kinds(&lifecycle.update(
+            &[binance_to_okx("BTCUSDT", 100.0, 100.0 + spread)],
+            Timestamp::from_millis(millis),
+        ))
+    };
+
+    assert!(step(0.4, 1).is_empty());
+    assert_eq!(step(0.6, 2), ["opened"]);
+    // Flaps under the enter threshold but stays above the exit threshold
+    assert!(step(0.45, 3).is_empty());
+    assert!(step(0.7, 4).is_empty());
+    assert!(step(0.3, 5).is_empty());
+    assert_eq!(step(0.2, 6), ["closed"]);
+    assert!(step(0.45, 7).is_empty());
+}
+
+#[test]
+fn epsilon_absorbs_floating_point_noise_at_thresholds() {
+    let opportunity = binance_to_okx("BTCUSDT", 100.0, 100.3);
+    let spread = opportunity.spread_percentage;
+    let config = LifecycleConfig::new(spread + 1e-12, 0.1);
+
+    let mut strict = OpportunityLifecycle::new(config.with_epsilon(0.0)).unwrap();
+    assert!(
+        strict
+            .update(
+                std::slice::from_ref(&opportunity),
+                Timestamp::from_millis(1)
+            )
+            .is_empty()
+    );
+    let mut tolerant = OpportunityLifecycle::new(config).unwrap();
+    assert_eq!(
+        kinds(&tolerant.update(
+            std::slice::from_ref(&opportunity),
+            Timestamp::from_millis(1)
+        )),
+        ["opened"]
+    );
+    assert!(tolerant.is_open(&opportunity.key()));
+}
+
+#[test]
+fn vanished_opportunities_close_with_their_peak() {
+    let mut lifecycle = OpportunityLifecycle::new(LifecycleConfig::new(0.5, 0.2)).unwrap();
+    lifecycle.update(
+        &[
+            binance_to_okx("BTCUSDT", 100.0, 100.6),
+            binance_to_okx("ETHUSDT", 100.0, 100.6),
+        ],
+        Timestamp::from_millis(1),
+    );
+    lifecycle.update(
+        &[
+            binance_to_okx("BTCUSDT", 100.0, 100.9),
+            binance_to_okx("ETHUSDT", 100.0, 100.6),
+        ],
+        Timestamp::from_millis(2),
+    );
+    let events = lifecycle.update(
+        &[binance_to_okx("ETHUSDT", 100.0, 100.6)],
+        Timestamp::from_millis(3),
+    );
+    assert_eq!(kinds(&events), ["gone"]);
+    match &events[0] {
+        LifecycleEvent::Closed {
+            opportunity,
+            opened_at,
+            closed_at,
+            peak_spread_percentage,
+            ..
+        } => {
+            assert_eq!(opportunity.symbol, "BTCUSDT");
+            assert_eq!(*opened_at, Timestamp::from_millis(1));
+            assert_eq!(*closed_at, Timestamp::from_millis(3));
+            assert!(*peak_spread_percentage > 0.85);
+        }
+        other => panic!("unexpected event {other:?}"),
+    }
+    assert_eq!(lifecycle.open_opportunities().len(), 1);
+}
+
+#[test]
+fn exit_above_enter_is_rejected() {
+    assert!(OpportunityLifecycle::new(LifecycleConfig::new(0.2, 0.5)).is_err());
+    assert!(OpportunityLifecycle::new(LifecycleConfig::new(0.5, 0.2).with_epsilon(-1.0)).is_err());
+}
+
+#[test]
+fn one_tick_artifacts_do_not_open() {
+    let config = LifecycleConfig::new(0.5, 0.2).with_min_ticks(3);
+    let mut lifecycle = OpportunityLifecycle::new(config).unwrap();
+    let mut step = |spread: f64, millis: u64| {
+        kinds(&lifecycle.update(
+            &[binance_to_okx("BTCUSDT", 100.0, 100.0 + spread)],
+            Timestamp::from_millis(millis),
+        ))
+    };
+
+    // A single tick at the threshold is discarded
+    assert!(step(0.9, 1).is_empty());
+    assert!(step(0.4, 2).is_empty());
+    // The count restarts and opens on the third consecutive tick
+    assert!(step(0.6, 3).is_empty());
+    assert!(step(0.8, 4).is_empty());
+    assert_eq!(step(0.6, 5), ["opened"]);
+    assert_eq!(step(0.1, 6), ["closed"]);
+}
+
+#[test]
+fn candidates_must_persist_for_the_minimum_duration() {
+    let config = LifecycleConfig::new(0.5, 0.2).with_min_duration_ms(100);
+    let mut lifecycle = OpportunityLifecycle::new(config).unwrap();
+    let key = binance_to_okx("BTCUSDT", 100.0, 100.6).key();
+
+    assert!(
+        lifecycle
+            .update(
+                &[binance_to_okx("BTCUSDT", 100.0, 100.9)],
+                Timestamp::from_millis(1_000)
+            )
+            .is_empty()
+    );
+    assert!(lifecycle.is_pending(&key));
+    assert!(!lifecycle.is_open(&key));
+    // Vanishing discards the candidate
+    assert!(
+        lifecycle
+            .update(&[], Timestamp::from_millis(1_050))
+            .is_empty()
+    );
+    assert!(!lifecycle.is_pending(&key));
+
+    let opportunity = [binance_to_okx("BTCUSDT", 100.0, 100.6)];
+    assert!(
+        lifecycle
+            .update(&opportunity, Timestamp::from_millis(1_100))
+            .is_empty()
+    );
+    assert!(
+        lifecycle
+            .update(&opportunity, Timestamp::from_millis(1_150))
+            .is_empty()
+    );
+    let events = lifecycle.update(&opportunity, Timestamp::from_millis(1_200));
+    assert_eq!(kinds(&events), ["opened"]);
+    match &events[0] {
+        LifecycleEvent::Opened { opportunity, at } => {
+            assert_eq!(*at, Timestamp::from_millis(1_200));
+            assert_eq!(
+                opportunity.id.as_deref(),
+                Some(format!("{key}@1200").as_str())
+            );
+        }
+        other => panic!("unexpected event {other:?}"),
+    }
+    assert!(lifecycle.is_open(&key));
+    assert!(!lifecycle.is_pending(&key));
+    assert_eq!(lifecycle.execution_stats().detected, 1);
+}
+
+#[test]
+fn persistence_settings_default_when_missing() {
+    let config: LifecycleConfig = serde_json::from_str(
+        r#"{"enter_spread_percent":0.5,"exit_spread_percent":0.2,"epsilon":1e-9}"#,
+    )
+    .unwrap();
+    assert_eq!(config, LifecycleConfig::new(0.5, 0.2));
+}
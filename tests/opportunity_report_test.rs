@@ -0,0 +1,108 @@
+mod scanner_common;
+
+use aeon_market_scanner_rs::common::Timestamp;
+use aeon_market_scanner_rs::scanner::aggregate_opportunity_stream;
+use aeon_market_scanner_rs::{OpportunityAggregator, OpportunitySeasonality};
+use scanner_common::binance_to_okx;
+use tokio::sync::mpsc;
+
+#[test]
+fn aggregator_summarizes_per_venue_pair_and_symbol() {
+    let mut aggregator = OpportunityAggregator::new(60_000, Timestamp::from_millis(0));
+    aggregator.record(&binance_to_okx("BTCUSDT", 100.0, 101.0)); // 1% spread, profit 1
+    aggregator.record(&binance_to_okx("BTCUSDT", 100.0, 103.0)); // 3% spread, profit 3
+
+    let mut reversed = binance_to_okx("BTCUSDT", 100.0, 102.0);
+    reversed.source_exchange = "OKX".to_string();
+    reversed.destination_exchange = "Binance".to_string();
+    aggregator.record(&reversed);
+
+    // Window still open
+    assert!(aggregator.poll(Timestamp::from_millis(59_999)).is_none());
+
+    let report = aggregator
+        .poll(Timestamp::from_millis(60_500))
+        .expect("window should close");
+    assert_eq!(report.window_start, Timestamp::from_millis(0));
+    assert_eq!(report.window_end, Timestamp::from_millis(60_000));
+    assert_eq!(report.total_count, 3);
+    assert_eq!(report.entries.len(), 2);
+
+    let top = &report.entries[0];
+    assert_eq!(top.source_exchange, "Binance");
+    assert_eq!(top.destination_exchange, "OKX");
+    assert_eq!(top.count, 2);
+    assert!((top.avg_spread_percentage - 2.0).abs() < 1e-9);
+    assert!((top.max_spread_percentage - 3.0).abs() < 1e-9);
+    assert!((top.cumulative_profit_quote - 4.0).abs() < 1e-9);
+
+    // Next window starts where the previous one ended and is empty
+    assert_eq!(aggregator.window_start(), Timestamp::from_millis(60_000));
+    let next = aggregator.flush(Timestamp::from_millis(70_000));
+    assert_eq!(next.total_count, 0);
+    assert!(next.entries.is_empty());
+}
+
+#[test]
+fn seasonality_buckets_by_utc_hour_and_weekday() {
+    // Monday 2024-01-01 00:00 UTC
+    let monday = 1_704_067_200;
+    let at = |day: u64, hour: u64, minute: u64| {
+        Timestamp::from_secs(monday + day * 86_400 + hour * 3_600 + minute * 60)
+    };
+    let mut seasonality = OpportunitySeasonality::new();
+    seasonality.record(&binance_to_okx("BTCUSDT", 100.0, 101.0), at(0, 8, 0)); // Mon 08:00, profit 1
+    seasonality.record(&binance_to_okx("BTCUSDT", 100.0, 103.0), at(0, 8, 59)); // Mon 08:59, profit 3
+    seasonality.record(&binance_to_okx("BTCUSDT", 100.0, 102.0), at(7, 8, 30)); // next Mon 08:30, profit 2
+    seasonality.record(&binance_to_okx("BTCUSDT", 100.0, 101.0), at(6, 23, 0)); // Sun 23:00, profit 1
+
+    let mut reversed = binance_to_okx("BTCUSDT", 100.0, 110.0);
+    reversed.source_exchange = "OKX".to_string();
+    reversed.destination_exchange = "Binance".to_string();
+    seasonality.record(&reversed, at(2, 16, 0)); // Wed 16:00, profit 10
+
+    let report = seasonality.report();
+    assert_eq!(report.len(), 2);
+    assert_eq!(report[0].source_exchange, "OKX");
+
+    let pair = seasonality.venue_pair("Binance", "OKX").unwrap();
+    assert_eq!(pair.total_count, 4);
+    assert_eq!(pair.buckets.len(), 2);
+    let monday_8 = &pair.buckets[0];
+    assert_eq!((monday_8.weekday, monday_8.hour), (Some(0), Some(8)));
+    assert_eq!(monday_8.count, 3);
+    assert!((monday_8.avg_spread_percentage - 2.0).abs() < 1e-9);
+    assert!((monday_8.max_spread_percentage - 3.0).abs() < 1e-9);
+    assert_eq!(
+        (pair.buckets[1].weekday, pair.buckets[1].hour),
+        (Some(6), Some(23))
+    );
+
+    assert_eq!(pair.by_hour.len(), 2);
+    assert_eq!(pair.by_hour[0].weekday, None);
+    let peak = pair.peak_hour().unwrap();
+    assert_eq!(peak.hour, Some(8));
+    assert!((peak.cumulative_profit_quote - 6.0).abs() < 1e-9);
+    assert_eq!(pair.peak_weekday().unwrap().weekday, Some(0));
+    assert_eq!(pair.by_weekday[1].hour, None);
+    assert!(seasonality.venue_pair("Binance", "Bybit").is_none());
+}
+
+#[tokio::test]
+async fn stream_flushes_partial_window_on_close() {
+    let (tx, rx) = mpsc::channel(4);
+    let mut reports = aggregate_opportunity_stream(rx, 0);
+
+    tx.send(vec![binance_to_okx("BTCUSDT", 100.0, 101.0)])
+        .await
+        .unwrap();
+    tx.send(vec![binance_to_okx("BTCUSDT", 100.0, 102.0)])
+        .await
+        .unwrap();
+    drop(tx);
+
+    let report = reports.recv().await.expect("final report");
+    assert_eq!(report.total_count, 2);
+    assert_eq!(report.entries[0].count, 2);
+    assert!(reports.recv().await.is_none());
+}
@@ -0,0 +1,44 @@
+mod scanner_common;
+
+use aeon_market_scanner_rs::{ArbitrageOpportunity, OPPORTUNITY_SCHEMA_VERSION};
+use scanner_common::binance_to_okx;
+
+#[test]
+fn serialized_opportunity_carries_current_schema_version() {
+    let opp = binance_to_okx("BTCUSDT", 100.0, 110.0);
+    assert_eq!(opp.schema_version, OPPORTUNITY_SCHEMA_VERSION);
+
+    let json = serde_json::to_value(&opp).unwrap();
+    assert_eq!(json["schema_version"], OPPORTUNITY_SCHEMA_VERSION);
+
+    let back: ArbitrageOpportunity = serde_json::from_value(json).unwrap();
+    assert_eq!(back.schema_version, OPPORTUNITY_SCHEMA_VERSION);
+    assert_eq!(back.source_exchange, opp.source_exchange);
+}
+
+#[test]
+fn legacy_payload_without_version_deserializes_as_v1() {
+    let mut json = serde_json::to_value(binance_to_okx("BTCUSDT", 100.0, 110.0)).unwrap();
+    let obj = json.as_object_mut().unwrap();
+    obj.remove("schema_version");
+    // Original field names
+    for (new, old) in [
+        ("source_exchange", "buy_exchange"),
+        ("destination_exchange", "sell_exchange"),
+        ("effective_ask", "buy_price"),
+        ("effective_bid", "sell_price"),
+        ("spread", "profit"),
+        ("spread_percentage", "profit_percentage"),
+        ("executable_quantity", "buy_quantity"),
+        ("source_leg", "buy_price_data"),
+        ("destination_leg", "sell_price_data"),
+    ] {
+        let value = obj.remove(new).unwrap();
+        obj.insert(old.to_string(), value);
+    }
+
+    let legacy: ArbitrageOpportunity = serde_json::from_value(json).unwrap();
+    assert_eq!(legacy.schema_version, 1);
+    assert_eq!(legacy.source_exchange, "Binance");
+    assert!(legacy.viability.is_none());
+}
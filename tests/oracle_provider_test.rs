@@ -0,0 +1,130 @@
+use aeon_market_scanner_rs::MarketScannerError;
+use aeon_market_scanner_rs::common::Timestamp;
+use aeon_market_scanner_rs::dex::oracles::{
+    ChainlinkOracle, OraclePrice, OracleProvider, OracleSource, PythOracle, PythPriceFeed,
+    RedstoneOracle, RedstonePrice, pyth_usd_feed_id, reference_price,
+};
+use async_trait::async_trait;
+use ethers::providers::Provider;
+
+const HERMES_FEED: &str = r#"{
+    "id": "ff61491a931112ddf1bd8147cd1b641375f79f5825126d665480874634fd0ace",
+    "price": {"price": "250012345678", "conf": "150000000", "expo": -8, "publish_time": 1700000000},
+    "ema_price": {"price": "249900000000", "conf": "140000000", "expo": -8, "publish_time": 1700000000},
+    "metadata": {"slot": 1, "proof_available_time": 1700000001, "prev_publish_time": 1699999999}
+}"#;
+
+const REDSTONE_PRICE: &str = r#"{
+    "id": "abc",
+    "symbol": "ETH",
+    "provider": "I-5rWUehEv-MjdK9gFw09RxfSLQX9DIHxG614Wf8qo0",
+    "value": 2499.87,
+    "timestamp": 1700000000000,
+    "version": "0.4"
+}"#;
+
+/// Fixed answer for fallback tests.
+struct StaticOracle {
+    symbol: &'static str,
+    price: Option<f64>,
+}
+
+#[async_trait]
+impl OracleProvider for StaticOracle {
+    fn source(&self) -> OracleSource {
+        OracleSource::Redstone
+    }
+
+    fn supports(&self, symbol: &str) -> bool {
+        symbol == self.symbol
+    }
+
+    async fn get_price(&self, symbol: &str) -> Result<OraclePrice, MarketScannerError> {
+        let price = self
+            .price
+            .ok_or_else(|| MarketScannerError::ApiError("feed down".to_string()))?;
+        Ok(OraclePrice {
+            symbol: symbol.to_string(),
+            price,
+            source: OracleSource::Redstone,
+            feed: symbol.to_string(),
+            updated_at: Timestamp::now(),
+            confidence: None,
+        })
+    }
+}
+
+#[test]
+fn test_pyth_feed_to_oracle_price() {
+    let feed: PythPriceFeed = serde_json::from_str(HERMES_FEED).unwrap();
+    let price = feed.to_oracle_price("eth-usd").unwrap();
+    assert_eq!(price.symbol, "ETHUSD");
+    assert_eq!(price.source, OracleSource::Pyth);
+    assert_eq!(price.feed, pyth_usd_feed_id("ETH").unwrap());
+    assert!((price.price - 2500.12345678).abs() < 1e-6);
+    assert!((price.confidence.unwrap() - 1.5).abs() < 1e-9);
+    assert_eq!(price.updated_at, Timestamp::from_secs(1_700_000_000));
+    assert!((feed.ema_price.unwrap().value().unwrap() - 2499.0).abs() < 1e-6);
+
+    let mut bad = serde_json::from_str::<PythPriceFeed>(HERMES_FEED).unwrap();
+    bad.price.price = "0".to_string();
+    assert!(bad.to_oracle_price("ETHUSD").is_err());
+}
+
+#[test]
+fn test_redstone_price_to_oracle_price() {
+    let price: RedstonePrice = serde_json::from_str(REDSTONE_PRICE).unwrap();
+    let oracle_price = price.to_oracle_price("ETHUSD").unwrap();
+    assert_eq!(oracle_price.source, OracleSource::Redstone);
+    assert_eq!(oracle_price.feed, "ETH");
+    assert_eq!(oracle_price.price, 2499.87);
+    assert_eq!(
+        oracle_price.updated_at,
+        Timestamp::from_millis(1_700_000_000_000)
+    );
+    assert_eq!(oracle_price.confidence, None);
+}
+
+#[test]
+fn test_providers_report_configured_symbols() {
+    let pyth = PythOracle::new().with_feed("ETHUSD", pyth_usd_feed_id("ETH").unwrap());
+    assert!(pyth.supports("ETH-USD"));
+    assert!(!pyth.supports("BTCUSD"));
+    assert_eq!(OracleProvider::source(&pyth), OracleSource::Pyth);
+
+    let redstone = RedstoneOracle::new().with_feed("ETHUSD", "ETH");
+    assert!(redstone.supports("ethusd"));
+
+    let (provider, _mock) = Provider::mocked();
+    let chainlink = ChainlinkOracle::new(provider)
+        .with_feed("BTCUSD", "0xF4030086522a5bEEa4988F8cA5B36dbC97BeE88c");
+    assert!(chainlink.supports("BTCUSD"));
+    assert_eq!(OracleProvider::source(&chainlink), OracleSource::Chainlink);
+}
+
+#[tokio::test]
+async fn test_reference_price_falls_back_in_order() {
+    let down = StaticOracle {
+        symbol: "ETHUSD",
+        price: None,
+    };
+    let other_pair = StaticOracle {
+        symbol: "BTCUSD",
+        price: Some(60_000.0),
+    };
+    let up = StaticOracle {
+        symbol: "ETHUSD",
+        price: Some(2500.0),
+    };
+
+    let price = reference_price(&[&other_pair, &down, &up], "ETHUSD")
+        .await
+        .unwrap();
+    assert_eq!(price.price, 2500.0);
+
+    // Only failing providers: their error is returned
+    let err = reference_price(&[&down], "ETHUSD").await.unwrap_err();
+    assert!(err.to_string().contains("feed down"));
+    // No provider has the symbol
+    assert!(reference_price(&[&up], "SOLUSD").await.is_err());
+}
@@ -0,0 +1,144 @@
+mod scanner_common;
+
+use aeon_market_scanner_rs::common::Timestamp;
+use aeon_market_scanner_rs::{
+    AlertTemplate, AttributionGrouping, CexExchange, FieldClass, OutputPrecision,
+    ProfitAttribution, ScannerContext, TradeAttribution,
+};
+use scanner_common::opportunity;
+use std::sync::{Arc, Mutex};
+
+fn precision() -> OutputPrecision {
+    OutputPrecision::new()
+        .with_price_decimals(2)
+        .with_quantity_decimals(4)
+        .with_percent_decimals(3)
+}
+
+#[test]
+fn fields_are_classified_by_name() {
+    assert_eq!(FieldClass::of("ask_price"), Some(FieldClass::Price));
+    assert_eq!(FieldClass::of("effective_bid"), Some(FieldClass::Price));
+    assert_eq!(
+        FieldClass::of("total_commission_quote"),
+        Some(FieldClass::Price)
+    );
+    assert_eq!(FieldClass::of("realized_pnl"), Some(FieldClass::Price));
+    assert_eq!(FieldClass::of("bid_qty"), Some(FieldClass::Quantity));
+    assert_eq!(FieldClass::of("amount_out"), Some(FieldClass::Quantity));
+    assert_eq!(
+        FieldClass::of("spread_percentage"),
+        Some(FieldClass::Percent)
+    );
+    assert_eq!(FieldClass::of("timestamp"), None);
+
+    let precision = precision().with_field_class("dampening", FieldClass::Percent);
+    assert_eq!(precision.class_of("dampening"), Some(FieldClass::Percent));
+    assert_eq!(precision.round(0.1 + 0.2, FieldClass::Quantity), 0.3);
+    assert_eq!(precision.format(2.0 / 3.0, FieldClass::Price), "0.67");
+    assert_eq!(precision.format(5.0, FieldClass::Price), "5");
+    assert_eq!(
+        OutputPrecision::new().round(0.1 + 0.2, FieldClass::Price),
+        0.1 + 0.2
+    );
+}
+
+#[test]
+fn json_numbers_are_rounded_per_class() {
+    let value = precision()
+        .to_value(&opportunity(
+            "BTCUSDT",
+            CexExchange::Bybit,
+            100.123456,
+            CexExchange::OKX,
+            103.987654,
+            0.1 + 0.2,
+        ))
+        .unwrap();
+    assert_eq!(value["effective_ask"], 100.12);
+    assert_eq!(value["effective_bid"], 103.99);
+    assert_eq!(value["executable_quantity"], 0.3);
+    assert_eq!(value["source_leg"]["ask_qty"], 0.3);
+    assert_eq!(value["source_leg"]["timestamp"], 1);
+    let spread_percentage = value["spread_percentage"].as_f64().unwrap();
+    assert_eq!(
+        spread_percentage,
+        (spread_percentage * 1000.0).round() / 1000.0
+    );
+    // Symbols and venues are untouched
+    assert_eq!(value["symbol"], "BTCUSDT");
+
+    let lines = Arc::new(Mutex::new(Vec::new()));
+    let sink_lines = lines.clone();
+    let context = ScannerContext::new("export")
+        .with_sink(precision().json_lines_sink(move |line| sink_lines.lock().unwrap().push(line)));
+    context.process(vec![opportunity(
+        "BTCUSDT",
+        CexExchange::Bybit,
+        100.123456,
+        CexExchange::OKX,
+        103.987654,
+        0.1 + 0.2,
+    )]);
+    let lines = lines.lock().unwrap();
+    assert_eq!(lines.len(), 1);
+    assert!(lines[0].contains(r#""executable_quantity":0.3,"#));
+    assert!(!lines[0].contains("0.30000000000000004"));
+}
+
+#[test]
+fn alert_templates_render_rounded_numbers() {
+    let template =
+        AlertTemplate::parse("{{symbol}} buy {{effective_ask}} x {{executable_quantity}}").unwrap();
+    assert!(
+        template
+            .render(&opportunity(
+                "BTCUSDT",
+                CexExchange::Bybit,
+                100.123456,
+                CexExchange::OKX,
+                103.987654,
+                0.1 + 0.2
+            ))
+            .ends_with("x 0.30000000000000004")
+    );
+    assert_eq!(
+        template.with_precision(precision()).render(&opportunity(
+            "BTCUSDT",
+            CexExchange::Bybit,
+            100.123456,
+            CexExchange::OKX,
+            103.987654,
+            0.1 + 0.2
+        )),
+        "BTCUSDT buy 100.12 x 0.3"
+    );
+}
+
+#[test]
+fn attribution_exports_use_the_precision() {
+    let mut attribution = ProfitAttribution::new();
+    attribution.record(TradeAttribution {
+        buy_exchange: "Bybit".to_string(),
+        sell_exchange: "OKX".to_string(),
+        symbol: "BTCUSDT".to_string(),
+        matched_quantity: 0.1 + 0.2,
+        spread_captured: 1.0 / 3.0,
+        fees: 0.01,
+        slippage: 0.0,
+        transfer_costs: 0.0,
+        realized_pnl: 1.0 / 3.0 - 0.01,
+        timestamp: Timestamp::from_millis(1),
+    });
+
+    assert!(attribution.to_csv().contains("0.30000000000000004"));
+    let csv = attribution.to_csv_with_precision(&precision());
+    assert!(csv.ends_with(",Bybit,OKX,BTCUSDT,0.3,0.33,0.01,0,0,0.32\n"));
+    let summary = attribution.summary_csv_with_precision(AttributionGrouping::Symbol, &precision());
+    assert!(summary.ends_with("BTCUSDT,1,0.3,0.33,0.01,0,0,0.32\n"));
+
+    let json: serde_json::Value =
+        serde_json::from_str(&attribution.to_json_with_precision(&precision()).unwrap()).unwrap();
+    assert_eq!(json["total"]["realized_pnl"], 0.32);
+    assert_eq!(json["trades"][0]["matched_quantity"], 0.3);
+}
@@ -0,0 +1,86 @@
+use aeon_market_scanner_rs::MarketScannerError;
+use aeon_market_scanner_rs::common::Paginator;
+use serde_json::{Value, json};
+use std::sync::Mutex;
+
+/// Fetches pages from `pages` by endpoint and records the requested endpoints.
+async fn fetch_all(
+    paginator: &Paginator,
+    endpoint: &str,
+    pages: impl Fn(&str) -> Value,
+) -> (Result<Vec<Value>, MarketScannerError>, Vec<String>) {
+    let requested = Mutex::new(Vec::new());
+    let result = paginator
+        .fetch_all(endpoint, |page| {
+            requested.lock().unwrap().push(page.clone());
+            let body = pages(&page);
+            async move { Ok(body) }
+        })
+        .await;
+    (result, requested.into_inner().unwrap())
+}
+
+#[tokio::test]
+async fn page_numbers_stop_at_a_short_page() {
+    let paginator = Paginator::page_number("page", 1).with_page_size("count", 2);
+    let (items, requested) = fetch_all(&paginator, "market/all", |endpoint| {
+        match endpoint.rsplit('=').next() {
+            Some("1") => json!([{"market": "KRW-BTC"}, {"market": "KRW-ETH"}]),
+            Some("2") => json!([{"market": "KRW-SOL"}]),
+            _ => json!([]),
+        }
+    })
+    .await;
+    assert_eq!(items.unwrap().len(), 3);
+    assert_eq!(
+        requested,
+        ["market/all?count=2&page=1", "market/all?count=2&page=2"]
+    );
+}
+
+#[tokio::test]
+async fn cursors_are_followed_until_absent() {
+    let paginator = Paginator::cursor("after", "/pagination/after").with_items_at("/products");
+    let (items, requested) = fetch_all(&paginator, "products?type=SPOT", |endpoint| {
+        if endpoint.ends_with("after=p2") {
+            json!({"products": [{"id": "SOL-USD"}], "pagination": {"after": ""}})
+        } else {
+            json!({
+                "products": [{"id": "BTC-USD"}, {"id": "ETH-USD"}],
+                "pagination": {"after": "p2"}
+            })
+        }
+    })
+    .await;
+    let ids: Vec<String> = items
+        .unwrap()
+        .iter()
+        .map(|p| p["id"].as_str().unwrap().to_string())
+        .collect();
+    assert_eq!(ids, ["BTC-USD", "ETH-USD", "SOL-USD"]);
+    assert_eq!(
+        requested,
+        ["products?type=SPOT", "products?type=SPOT&after=p2"]
+    );
+}
+
+#[tokio::test]
+async fn ignored_page_parameter_ends_after_one_repeat() {
+    let paginator = Paginator::page_number("page", 0);
+    let listing = json!([{"id": "BTC-USD"}, {"id": "ETH-USD"}]);
+    let (items, requested) = fetch_all(&paginator, "products", |_| listing.clone()).await;
+    assert_eq!(items.unwrap().len(), 2);
+    assert_eq!(requested.len(), 2);
+}
+
+#[tokio::test]
+async fn endless_listings_hit_the_page_limit() {
+    let paginator = Paginator::page_number("page", 1).with_max_pages(3);
+    let (result, requested) = fetch_all(&paginator, "markets", |endpoint| json!([endpoint])).await;
+    assert!(result.is_err());
+    assert_eq!(requested.len(), 3);
+
+    let paginator = Paginator::page_number("page", 1).with_items_at("/data");
+    let (result, _) = fetch_all(&paginator, "markets", |_| json!({"data": "oops"})).await;
+    assert!(result.is_err());
+}
@@ -0,0 +1,102 @@
+mod scanner_common;
+
+use aeon_market_scanner_rs::portfolio::{SimulatedOutcome, SimulationStats};
+use aeon_market_scanner_rs::{AdverseSelectionModel, PaperTrader, SeededRng, SlippageModel};
+use scanner_common::binance_to_okx;
+
+fn run(model: AdverseSelectionModel, trades: usize) -> SimulationStats {
+    let opportunity = binance_to_okx("BTCUSDT", 100.0, 110.0);
+    let mut trader = PaperTrader::new(model);
+    for _ in 0..trades {
+        trader.execute(&opportunity, 1.0);
+    }
+    trader.stats().clone()
+}
+
+#[test]
+fn default_model_captures_the_quoted_spread() {
+    let mut trader = PaperTrader::new(AdverseSelectionModel::default());
+    let execution = trader.execute(&binance_to_okx("BTCUSDT", 100.0, 110.0), 1.0);
+
+    assert_eq!(execution.outcome, SimulatedOutcome::Filled);
+    assert_eq!(execution.fills.len(), 2);
+    assert!((execution.expected_profit - 10.0).abs() < 1e-9);
+    assert!((execution.realized_profit - 10.0).abs() < 1e-9);
+    assert!((trader.portfolio().realized_pnl() - 10.0).abs() < 1e-9);
+    assert!((trader.stats().capture_ratio() - 1.0).abs() < 1e-9);
+}
+
+#[test]
+fn fixed_slippage_moves_both_legs_against_the_trader() {
+    let model = AdverseSelectionModel::default().with_slippage(SlippageModel::Fixed { bps: 100.0 });
+    let mut trader = PaperTrader::new(model);
+    let execution = trader.execute(&binance_to_okx("BTCUSDT", 100.0, 110.0), 1.0);
+
+    assert!((execution.fills[0].price - 101.0).abs() < 1e-9);
+    assert!((execution.fills[1].price - 108.9).abs() < 1e-9);
+    assert!((execution.realized_profit - 7.9).abs() < 1e-9);
+}
+
+#[test]
+fn vanishing_quotes_miss_and_leg_trades() {
+    let model = AdverseSelectionModel::default()
+        .with_latency_ms(100)
+        .with_quote_half_life_ms(100.0)
+        .with_unwind_cost_bps(50.0)
+        .with_seed(7);
+    assert!((model.vanish_probability() - 0.5).abs() < 1e-12);
+
+    let stats = run(model.clone(), 1_000);
+    assert_eq!(stats.attempted, 1_000);
+    assert_eq!(stats.filled + stats.missed + stats.legged, 1_000);
+    // Each leg survives half the time: ~25% filled, ~25% missed, ~50% legged
+    assert!((200..300).contains(&stats.filled), "{stats:?}");
+    assert!((200..300).contains(&stats.missed), "{stats:?}");
+    assert!((stats.expected_profit - 10_000.0).abs() < 1e-6);
+    assert!(stats.capture_ratio() < 0.3, "{stats:?}");
+
+    // Same seed, same run
+    assert_eq!(run(model, 1_000), stats);
+}
+
+#[test]
+fn injected_rng_replaces_the_model_seed() {
+    let model = AdverseSelectionModel::default().with_slippage(SlippageModel::Uniform {
+        min_bps: 0.0,
+        max_bps: 20.0,
+    });
+    let opportunity = binance_to_okx("BTCUSDT", 100.0, 110.0);
+    let realized = |mut trader: PaperTrader| {
+        (0..50)
+            .map(|_| trader.execute(&opportunity, 1.0).realized_profit)
+            .collect::<Vec<f64>>()
+    };
+    let seeded = realized(PaperTrader::new(model.clone().with_seed(9)));
+    let injected = realized(PaperTrader::new(model.clone()).with_rng(SeededRng::new(9)));
+    assert_eq!(seeded, injected);
+    assert_ne!(
+        realized(PaperTrader::new(model).with_rng(SeededRng::new(10))),
+        seeded
+    );
+}
+
+#[test]
+fn legged_trade_pays_the_unwind_cost() {
+    let model = AdverseSelectionModel::default()
+        .with_latency_ms(1_000)
+        .with_quote_half_life_ms(1_000.0)
+        .with_unwind_cost_bps(100.0)
+        .with_seed(1);
+    let opportunity = binance_to_okx("BTCUSDT", 100.0, 110.0);
+    let mut trader = PaperTrader::new(model);
+    let execution = (0..100)
+        .map(|_| trader.execute(&opportunity, 1.0))
+        .find(|e| e.outcome == SimulatedOutcome::Legged)
+        .expect("a legged trade");
+
+    assert_eq!(execution.fills.len(), 2);
+    assert_eq!(execution.fills[0].exchange, execution.fills[1].exchange);
+    assert_ne!(execution.fills[0].side, execution.fills[1].side);
+    assert!(execution.realized_profit < 0.0);
+    assert!((execution.realized_profit + execution.fills[0].price * 0.01).abs() < 1e-9);
+}
@@ -0,0 +1,49 @@
+use aeon_market_scanner_rs::dex::{
+    ListenMode, PoolKind, PoolListenerConfig, PriceDirection, resolve_direction, stream_pool_prices,
+};
+use ethers::types::Address;
+
+fn addr(byte: u8) -> Address {
+    Address::from([byte; 20])
+}
+
+#[test]
+fn direction_quotes_quote_per_base() {
+    let (token0, token1) = (addr(1), addr(2));
+    assert_eq!(
+        resolve_direction(token0, token1, token0, token1).unwrap(),
+        PriceDirection::Token1PerToken0
+    );
+    assert_eq!(
+        resolve_direction(token0, token1, token1, token0).unwrap(),
+        PriceDirection::Token0PerToken1
+    );
+    assert!(resolve_direction(token0, token1, token0, addr(3)).is_err());
+}
+
+#[tokio::test]
+async fn base_and_quote_must_be_set_together() {
+    let config = PoolListenerConfig {
+        rpc_ws_url: "ws://127.0.0.1:1".to_string(),
+        rpc_ws_fallback_urls: Vec::new(),
+        chain_id: 1,
+        pool_address: format!("{:?}", addr(9)),
+        pool_kind: PoolKind::V2,
+        listen_mode: ListenMode::EveryBlock,
+        price_direction: PriceDirection::Token1PerToken0,
+        base_token: Some(format!("{:?}", addr(1))),
+        quote_token: None,
+        twap_window_secs: None,
+        symbol: None,
+        reconnect_attempts: 0,
+        reconnect_delay_ms: 0,
+    };
+    assert!(stream_pool_prices(config.clone()).await.is_err());
+
+    let bad_address = PoolListenerConfig {
+        quote_token: Some("not-an-address".to_string()),
+        twap_window_secs: None,
+        ..config
+    };
+    assert!(stream_pool_prices(bad_address).await.is_err());
+}
@@ -0,0 +1,43 @@
+use aeon_market_scanner_rs::dex::chains::ChainId;
+use aeon_market_scanner_rs::dex::{
+    DiscoveredPool, PoolFactories, PoolKind, UNISWAP_V3_FEE_TIERS, uniswap_v2_factory,
+    uniswap_v3_factory,
+};
+
+const WETH: &str = "0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2";
+const USDC: &str = "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48";
+
+#[test]
+fn test_uniswap_factories_per_chain() {
+    assert!(uniswap_v2_factory(&ChainId::ETHEREUM).is_some());
+    assert!(uniswap_v3_factory(&ChainId::ETHEREUM).is_some());
+    assert!(uniswap_v3_factory(&ChainId::BASE).is_some());
+    assert!(uniswap_v2_factory(&ChainId::RONIN).is_none());
+
+    let factories = PoolFactories::uniswap(&ChainId::ETHEREUM);
+    assert_eq!(factories.v3_fee_tiers, UNISWAP_V3_FEE_TIERS.to_vec());
+
+    let custom = PoolFactories::default()
+        .with_v3_factory("0x0BFbCF9fa4f9C56B0F40a671Ad40E0805A091865")
+        .with_v3_fee_tiers(vec![100, 500, 2500, 10000]);
+    assert!(custom.v2_factory.is_none());
+    assert_eq!(custom.v3_fee_tiers.len(), 4);
+}
+
+#[test]
+fn test_discovered_pool_listener_config() {
+    let pool = DiscoveredPool {
+        chain_id: 1,
+        pool_address: "0x88e6a0c2ddd26feeb64f039a2c41296fcb3f5640".to_string(),
+        pool_kind: PoolKind::V3,
+        fee: Some(500),
+        token_a: WETH.to_string(),
+        token_b: USDC.to_string(),
+    };
+    let config = pool.listener_config("wss://example.invalid");
+    assert_eq!(config.chain_id, 1);
+    assert_eq!(config.pool_address, pool.pool_address);
+    assert_eq!(config.pool_kind, PoolKind::V3);
+    assert_eq!(config.base_token.as_deref(), Some(WETH));
+    assert_eq!(config.quote_token.as_deref(), Some(USDC));
+}
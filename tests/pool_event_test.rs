@@ -0,0 +1,62 @@
+use aeon_market_scanner_rs::Timestamp;
+use aeon_market_scanner_rs::dex::{PoolEvent, PoolKind, PoolPriceUpdate, PriceDirection};
+use ethers::types::U256;
+
+#[test]
+fn test_pool_event_serde_roundtrip() {
+    let events = vec![
+        PoolEvent::Reorged { from_block: 100 },
+        PoolEvent::Price(PoolPriceUpdate {
+            chain_id: 1,
+            pool_address: "0x0000000000000000000000000000000000000001".to_string(),
+            pool_kind: PoolKind::V2,
+            price: 2500.0,
+            direction: PriceDirection::Token1PerToken0,
+            reserve0: Some(1.0),
+            reserve1: Some(2500.0),
+            sqrt_price_x96: None,
+            block_number: 101,
+            timestamp: Timestamp::from_millis(1),
+            symbol: None,
+            twap_price: None,
+        }),
+    ];
+    let json = serde_json::to_string(&events).unwrap();
+    let back: Vec<PoolEvent> = serde_json::from_str(&json).unwrap();
+    assert!(matches!(back[0], PoolEvent::Reorged { from_block: 100 }));
+    match &back[1] {
+        PoolEvent::Price(update) => assert_eq!(update.block_number, 101),
+        other => panic!("unexpected event {other:?}"),
+    }
+}
+
+#[test]
+fn test_sqrt_price_serializes_as_a_json_number() {
+    let update = |sqrt_price_x96| PoolPriceUpdate {
+        chain_id: 1,
+        pool_address: "0x0000000000000000000000000000000000000002".to_string(),
+        pool_kind: PoolKind::V3,
+        price: 1.0,
+        direction: PriceDirection::Token1PerToken0,
+        reserve0: None,
+        reserve1: None,
+        sqrt_price_x96,
+        block_number: 1,
+        timestamp: Timestamp::from_millis(1),
+        symbol: None,
+        twap_price: None,
+    };
+    // 2^96 (price 1.0): above u64, exact as a number
+    let q96 = U256::from(2u8).pow(U256::from(96u8));
+    let text = serde_json::to_string(&update(Some(q96))).unwrap();
+    assert!(text.contains("\"sqrt_price_x96\":79228162514264337593543950336"));
+    let back: PoolPriceUpdate = serde_json::from_str(&text).unwrap();
+    assert_eq!(back.sqrt_price_x96, Some(q96));
+
+    // Values beyond u128 are left out; older payloads without the field still parse
+    let huge = U256::from(u128::MAX) + 1;
+    let text = serde_json::to_string(&update(Some(huge))).unwrap();
+    assert!(!text.contains("sqrt_price_x96"));
+    let back: PoolPriceUpdate = serde_json::from_str(&text).unwrap();
+    assert_eq!(back.sqrt_price_x96, None);
+}
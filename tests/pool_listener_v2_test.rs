@@ -35,11 +35,15 @@ async fn run_listener(listen_mode: ListenMode, timeout_secs: u64) -> Option<u32>
 
     let config = PoolListenerConfig {
         rpc_ws_url: rpc_ws.clone(),
+        rpc_ws_fallback_urls: Vec::new(),
         chain_id: CHAIN_ID,
         pool_address: POOL_ADDRESS.to_string(),
         pool_kind: PoolKind::V2,
         listen_mode,
         price_direction: PriceDirection::Token0PerToken1,
+        base_token: None,
+        quote_token: None,
+        twap_window_secs: None,
         symbol: Some("BNBUSDT".to_string()),
         reconnect_attempts: 0,
         reconnect_delay_ms: 5000,
@@ -6,6 +6,7 @@
 //!
 //! Pool address and chain are fixed in this file (edit if needed).
 
+use aeon_market_scanner_rs::dex::get_twap;
 use aeon_market_scanner_rs::{
     ListenMode, PoolKind, PoolListenerConfig, PoolPriceUpdate, PriceDirection, load_dotenv,
     stream_pool_prices,
@@ -35,11 +36,15 @@ async fn run_listener(listen_mode: ListenMode, timeout_secs: u64) -> Option<u32>
 
     let config = PoolListenerConfig {
         rpc_ws_url: rpc_ws.clone(),
+        rpc_ws_fallback_urls: Vec::new(),
         chain_id: CHAIN_ID,
         pool_address: POOL_ADDRESS.to_string(),
         pool_kind: PoolKind::V3,
         listen_mode,
         price_direction: PriceDirection::Token0PerToken1,
+        base_token: None,
+        quote_token: None,
+        twap_window_secs: None,
         symbol: Some("BNBUSDT".to_string()),
         reconnect_attempts: 0,
         reconnect_delay_ms: 5000,
@@ -97,3 +102,17 @@ async fn pool_listener_v3_every_block() {
         count
     );
 }
+
+#[tokio::test]
+async fn pool_listener_v3_twap() {
+    println!("\n=== Pool V3 — 5 minute TWAP ===\n");
+    let Some(rpc_ws) = rpc_ws() else {
+        println!("Skipping: set POOL_LISTENER_RPC_WS");
+        return;
+    };
+    let twap = get_twap(&rpc_ws, POOL_ADDRESS, 300, PriceDirection::Token0PerToken1)
+        .await
+        .expect("get_twap");
+    println!("TWAP (USDT per BNB): {}", twap);
+    assert!(twap > 0.0);
+}
@@ -0,0 +1,132 @@
+use aeon_market_scanner_rs::dex::chains::ChainId;
+use aeon_market_scanner_rs::{
+    ChainPreset, ListenMode, MarketScannerError, PoolKind, PoolListenerConfig, PriceDirection,
+    stream_pool_prices,
+};
+use std::time::{Duration, Instant};
+use tokio::net::TcpListener;
+
+const DEAD_RPC: &str = "ws://127.0.0.1:1";
+
+fn config(
+    rpc_ws_url: &str,
+    reconnect_attempts: u32,
+    reconnect_delay_ms: u64,
+) -> PoolListenerConfig {
+    PoolListenerConfig {
+        rpc_ws_url: rpc_ws_url.to_string(),
+        rpc_ws_fallback_urls: Vec::new(),
+        chain_id: 1,
+        pool_address: "0x0000000000000000000000000000000000000001".to_string(),
+        pool_kind: PoolKind::V2,
+        listen_mode: ListenMode::EveryBlock,
+        price_direction: PriceDirection::Token1PerToken0,
+        base_token: None,
+        quote_token: None,
+        twap_window_secs: None,
+        symbol: None,
+        reconnect_attempts,
+        reconnect_delay_ms,
+    }
+}
+
+#[test]
+fn presets_cover_every_chain() {
+    let presets = ChainPreset::all();
+    assert_eq!(presets.len(), ChainId::ALL.len());
+    assert!(
+        presets
+            .iter()
+            .all(|p| p.block_time_ms > 0 && !p.rpc_ws_env_vars.is_empty())
+    );
+
+    let ethereum = ChainPreset::for_chain_id(1).unwrap();
+    assert_eq!(ethereum.chain, ChainId::ETHEREUM);
+    assert_eq!(ethereum.block_time(), Duration::from_secs(12));
+    assert_eq!(ChainPreset::for_chain(&ChainId::BSC).chain_id, 56);
+    assert!(ChainPreset::for_chain_id(999_999_999).is_none());
+}
+
+#[test]
+fn preset_reads_rpc_pool_from_env() {
+    let preset = ChainPreset::for_chain(&ChainId::MANTLE);
+    let [primary, secondary, ..] = preset.rpc_ws_env_vars else {
+        panic!("expected two env vars");
+    };
+    unsafe {
+        std::env::remove_var(primary);
+        std::env::remove_var(secondary);
+    }
+    assert!(preset.rpc_ws_urls().is_empty());
+    assert!(matches!(
+        preset.listener_config("0xpool", PoolKind::V3),
+        Err(MarketScannerError::InvalidConfig(_))
+    ));
+
+    unsafe {
+        std::env::set_var(primary, "wss://a.example, wss://b.example,");
+        std::env::set_var(secondary, "wss://b.example,wss://c.example");
+    }
+    assert_eq!(
+        preset.rpc_ws_urls(),
+        vec!["wss://a.example", "wss://b.example", "wss://c.example"]
+    );
+    let config = preset.listener_config("0xpool", PoolKind::V3).unwrap();
+    assert_eq!(config.chain_id, 5000);
+    assert_eq!(config.rpc_ws_url, "wss://a.example");
+    assert_eq!(
+        config.rpc_ws_fallback_urls,
+        vec!["wss://b.example", "wss://c.example"]
+    );
+    assert!(config.reconnect_attempts > 0);
+    unsafe {
+        std::env::remove_var(primary);
+        std::env::remove_var(secondary);
+    }
+}
+
+#[test]
+fn rpc_pool_drops_empty_and_duplicate_urls() {
+    let config = config("wss://a.example", 0, 0).with_fallback_rpc_ws_urls(&[
+        "",
+        "wss://a.example",
+        "wss://b.example",
+    ]);
+    assert_eq!(
+        config.rpc_ws_urls(),
+        vec!["wss://a.example", "wss://b.example"]
+    );
+}
+
+#[tokio::test]
+async fn empty_rpc_pool_is_rejected() {
+    assert!(matches!(
+        stream_pool_prices(config(" ", 0, 0)).await,
+        Err(MarketScannerError::InvalidConfig(_))
+    ));
+}
+
+#[tokio::test]
+async fn listener_fails_over_to_the_next_rpc() {
+    let backup = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let backup_url = format!("ws://{}", backup.local_addr().unwrap());
+    let config = config(DEAD_RPC, 0, 0).with_fallback_rpc_ws_urls(&[&backup_url]);
+
+    let _prices = stream_pool_prices(config).await.unwrap();
+    let accepted = tokio::time::timeout(Duration::from_secs(5), backup.accept()).await;
+    assert!(accepted.is_ok(), "backup RPC was never tried");
+}
+
+#[tokio::test]
+async fn failover_within_a_pass_does_not_wait_or_count_as_reconnect() {
+    let config = config(DEAD_RPC, 1, 400)
+        .with_fallback_rpc_ws_urls(&["ws://127.0.0.1:2", "ws://127.0.0.1:3"]);
+
+    let started = Instant::now();
+    let mut prices = stream_pool_prices(config).await.unwrap();
+    // Two passes over the three URLs, one reconnect delay between them
+    assert!(prices.recv().await.is_none());
+    let elapsed = started.elapsed();
+    assert!(elapsed >= Duration::from_millis(400), "{elapsed:?}");
+    assert!(elapsed < Duration::from_millis(1_200), "{elapsed:?}");
+}
@@ -0,0 +1,49 @@
+use aeon_market_scanner_rs::Timestamp;
+use aeon_market_scanner_rs::dex::{
+    PoolKind, PoolPriceUpdate, PriceDirection, twap_from_tick_cumulatives,
+};
+use ethers::core::types::U256;
+
+#[test]
+fn test_twap_from_tick_cumulatives() {
+    // Mean tick 0 with equal decimals is a price of 1
+    let flat = twap_from_tick_cumulatives(1_000, 1_000, 60, 18, 18);
+    assert!((flat - 1.0).abs() < 1e-12);
+
+    // Tick 200_000 held for 600s: 1.0001^200000 scaled from (18, 6) decimals
+    let twap = twap_from_tick_cumulatives(-4_000_000, 116_000_000, 600, 18, 6);
+    let expected = 1.0001f64.powi(200_000) * 1e12;
+    assert!((twap / expected - 1.0).abs() < 1e-9);
+
+    // Negative ticks
+    let negative = twap_from_tick_cumulatives(0, -6_000, 60, 18, 18);
+    assert!((negative - 1.0001f64.powi(-100)).abs() < 1e-12);
+}
+
+#[test]
+fn test_twap_deviation() {
+    let mut update = PoolPriceUpdate {
+        chain_id: 1,
+        pool_address: "0x0000000000000000000000000000000000000001".to_string(),
+        pool_kind: PoolKind::V3,
+        price: 2020.0,
+        direction: PriceDirection::Token1PerToken0,
+        reserve0: None,
+        reserve1: None,
+        sqrt_price_x96: Some(U256::one()),
+        block_number: 1,
+        timestamp: Timestamp::from_millis(1),
+        symbol: None,
+        twap_price: None,
+    };
+    assert_eq!(update.twap_deviation(), None);
+
+    update.twap_price = Some(2000.0);
+    assert!((update.twap_deviation().unwrap() - 0.01).abs() < 1e-12);
+
+    // Missing field deserializes as None
+    let mut json = serde_json::to_value(&update).unwrap();
+    json.as_object_mut().unwrap().remove("twap_price");
+    let back: PoolPriceUpdate = serde_json::from_value(json).unwrap();
+    assert_eq!(back.twap_price, None);
+}
@@ -0,0 +1,85 @@
+use aeon_market_scanner_rs::{
+    CexExchange, Exchange, Fill, MarketKey, Portfolio, Timestamp, TradeSide,
+};
+
+fn fill(exchange: CexExchange, side: TradeSide, quantity: f64, price: f64, fee: f64) -> Fill {
+    Fill {
+        exchange: Exchange::Cex(exchange),
+        symbol: "BTCUSDT".to_string(),
+        side,
+        quantity,
+        price,
+        fee_quote: fee,
+        timestamp: Timestamp::from_millis(1),
+    }
+}
+
+#[test]
+fn cross_venue_round_trip_realizes_spread() {
+    let mut portfolio = Portfolio::new();
+    portfolio.apply_fill(&fill(CexExchange::Binance, TradeSide::Buy, 1.0, 100.0, 0.1));
+    portfolio.apply_fill(&fill(CexExchange::OKX, TradeSide::Sell, 1.0, 105.0, 0.1));
+
+    let binance = Exchange::Cex(CexExchange::Binance);
+    let okx = Exchange::Cex(CexExchange::OKX);
+    assert!((portfolio.balance(&binance, "BTC") - 1.0).abs() < 1e-9);
+    assert!((portfolio.balance(&binance, "USDT") + 100.1).abs() < 1e-9);
+    assert!((portfolio.balance(&okx, "BTC") + 1.0).abs() < 1e-9);
+    assert!((portfolio.balance(&okx, "USDT") - 104.9).abs() < 1e-9);
+
+    // 5 spread − 0.2 fees
+    assert!((portfolio.realized_pnl() - 4.8).abs() < 1e-9);
+    let position = portfolio
+        .position(&MarketKey::from_symbol("BTCUSDT"))
+        .expect("BTC/USDT position");
+    assert_eq!(position.quantity, 0.0);
+
+    let history = portfolio.pnl_history();
+    assert_eq!(history.len(), 2);
+    assert!((history[0].realized_pnl + 0.1).abs() < 1e-9);
+    assert!((history[1].realized_pnl - 4.8).abs() < 1e-9);
+}
+
+#[test]
+fn average_cost_and_partial_close() {
+    let mut portfolio = Portfolio::new();
+    portfolio.apply_fill(&fill(CexExchange::Binance, TradeSide::Buy, 1.0, 100.0, 0.0));
+    portfolio.apply_fill(&fill(CexExchange::Bybit, TradeSide::Buy, 1.0, 110.0, 0.0));
+    portfolio.apply_fill(&fill(CexExchange::OKX, TradeSide::Sell, 0.5, 120.0, 0.0));
+
+    let position = portfolio
+        .position(&MarketKey::from_symbol("BTCUSDT"))
+        .unwrap();
+    assert!((position.average_price - 105.0).abs() < 1e-9);
+    assert!((position.quantity - 1.5).abs() < 1e-9);
+    assert!((portfolio.realized_pnl() - 7.5).abs() < 1e-9);
+    assert_eq!(portfolio.exposures().len(), 6);
+}
+
+#[test]
+fn empty_fill_keeps_flat_position_finite() {
+    let mut portfolio = Portfolio::new();
+    portfolio.apply_fill(&fill(CexExchange::Binance, TradeSide::Buy, 0.0, 100.0, 0.0));
+
+    let position = portfolio
+        .position(&MarketKey::from_symbol("BTCUSDT"))
+        .unwrap();
+    assert_eq!(position.quantity, 0.0);
+    assert_eq!(position.average_price, 0.0);
+
+    portfolio.apply_fill(&fill(CexExchange::Binance, TradeSide::Buy, 1.0, 100.0, 0.0));
+    let position = portfolio
+        .position(&MarketKey::from_symbol("BTCUSDT"))
+        .unwrap();
+    assert!((position.average_price - 100.0).abs() < 1e-9);
+}
+
+#[test]
+fn balance_resolves_asset_aliases() {
+    let mut portfolio = Portfolio::new();
+    portfolio.apply_fill(&fill(CexExchange::Kraken, TradeSide::Buy, 2.0, 100.0, 0.0));
+
+    let kraken = Exchange::Cex(CexExchange::Kraken);
+    assert!((portfolio.balance(&kraken, "XBT") - 2.0).abs() < 1e-9);
+    assert!((portfolio.balance(&kraken, "btc") - 2.0).abs() < 1e-9);
+}
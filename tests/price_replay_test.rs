@@ -0,0 +1,132 @@
+mod scanner_common;
+
+use aeon_market_scanner_rs::common::Timestamp;
+use aeon_market_scanner_rs::{
+    CexExchange, PriceHistoryFrame, PriceReplay, ReplayCheckpoint, ReplayProgress, ScannerContext,
+    ScannerHandle,
+};
+use scanner_common::{binance_okx_fees, price};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// Binance ask 100, OKX bid `okx_bid`.
+fn frame(at_ms: u64, okx_bid: f64) -> PriceHistoryFrame {
+    PriceHistoryFrame::new(
+        Timestamp::from_millis(at_ms),
+        vec![
+            price(CexExchange::Binance, 99.9, 100.0),
+            price(CexExchange::OKX, okx_bid, okx_bid + 0.1),
+        ],
+        Vec::new(),
+    )
+}
+
+/// One frame per second; spreads of 2% except a 0.5% dip at 3 s.
+fn history() -> Vec<PriceHistoryFrame> {
+    (1..=6)
+        .map(|s| frame(s * 1_000, if s == 3 { 100.5 } else { 102.0 }))
+        .collect()
+}
+
+fn replay() -> PriceReplay {
+    let fees = binance_okx_fees();
+    let context = ScannerContext::new("replay")
+        .with_fee_overrides(fees)
+        .with_min_spread_percent(1.0);
+    PriceReplay::new(context, history()).with_dedup_expiry_ms(1_500)
+}
+
+#[test]
+fn stepping_matches_a_straight_backfill() {
+    let mut stepped = replay();
+    assert_eq!(stepped.step().unwrap().len(), 1);
+    assert!(stepped.run_until(Timestamp::from_millis(3_000)).is_empty());
+    assert_eq!(stepped.next_frame_at(), Some(Timestamp::from_millis(4_000)));
+    stepped.run_to_end();
+    assert!(stepped.is_finished());
+    assert!(stepped.step().is_none());
+
+    let mut straight = replay();
+    let report = straight.run_to_end();
+    assert_eq!(stepped.report().matched, report.matched);
+    // Alerts at 1 s, then again at 4 s after the dip expired the record
+    let times: Vec<u64> = report.alerts.iter().map(|a| a.at.as_millis()).collect();
+    assert_eq!(times, vec![1_000, 4_000]);
+}
+
+#[test]
+fn checkpoints_restore_the_exact_state() {
+    let mut replay = replay();
+    replay.run_until(Timestamp::from_millis(2_000));
+    let checkpoint = replay.checkpoint();
+    let json = serde_json::to_string(&checkpoint).unwrap();
+    let rest: Vec<u64> = replay
+        .run_to_end()
+        .alerts
+        .iter()
+        .map(|a| a.at.as_millis())
+        .collect();
+
+    // Seeking skips frames without replaying them
+    replay.seek(Timestamp::from_millis(2_500));
+    assert_eq!(replay.position(), 2);
+    replay.seek_to_frame(100);
+    assert!(replay.is_finished());
+
+    let checkpoint: ReplayCheckpoint = serde_json::from_str(&json).unwrap();
+    replay.restore(&checkpoint).unwrap();
+    assert_eq!(replay.position(), 2);
+    assert_eq!(replay.report().alerts.len(), 1);
+    let again: Vec<u64> = replay
+        .run_to_end()
+        .alerts
+        .iter()
+        .map(|a| a.at.as_millis())
+        .collect();
+    assert_eq!(again, rest);
+
+    let mut other = PriceReplay::new(ScannerContext::new("other"), vec![frame(9_000, 102.0)]);
+    assert!(other.restore(&checkpoint).is_err());
+}
+
+#[test]
+fn progress_is_reported_every_n_frames_and_at_the_end() {
+    let seen: Arc<Mutex<Vec<ReplayProgress>>> = Arc::default();
+    let sink = seen.clone();
+    let mut replay = PriceReplay::new(ScannerContext::new("progress"), history())
+        .with_progress(4, move |progress| sink.lock().unwrap().push(*progress));
+    replay.run_to_end();
+
+    let seen = seen.lock().unwrap();
+    let positions: Vec<usize> = seen.iter().map(|p| p.position).collect();
+    assert_eq!(positions, vec![4, 6]);
+    assert_eq!(seen[1].at, Some(Timestamp::from_millis(6_000)));
+    assert_eq!(seen[1].fraction(), 1.0);
+}
+
+#[tokio::test]
+async fn play_is_paced_and_controlled_by_the_handle() {
+    let handle = ScannerHandle::new();
+    let mut replay = replay();
+
+    // 5 s of recording at 100x: 50 ms
+    let started = std::time::Instant::now();
+    let alerts = replay.play(100.0, &handle).await;
+    assert_eq!(alerts.len(), 2);
+    assert!(started.elapsed() >= Duration::from_millis(50));
+
+    let mut replay = self::replay();
+    handle.pause();
+    let control = handle.clone();
+    tokio::spawn(async move {
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        control.stop();
+    });
+    let alerts = replay.play(0.0, &handle).await;
+    assert!(alerts.is_empty());
+    assert_eq!(
+        replay.position(),
+        0,
+        "paused, then stopped before the first frame"
+    );
+}
@@ -0,0 +1,66 @@
+mod scanner_common;
+
+use aeon_market_scanner_rs::common::{MidPriceSmoother, smooth_price_stream};
+use aeon_market_scanner_rs::{CexExchange, Exchange};
+use scanner_common::price_at;
+use std::time::Duration;
+use tokio::sync::mpsc;
+
+#[test]
+fn average_moves_by_elapsed_half_lives() {
+    let mut smoother = MidPriceSmoother::new(Duration::from_secs(1));
+    assert_eq!(
+        smoother.update(&price_at(CexExchange::Binance, 100.0, 0)),
+        100.0
+    );
+
+    // One half-life: halfway to the new mid
+    assert!((smoother.update(&price_at(CexExchange::Binance, 200.0, 1_000)) - 150.0).abs() < 1e-9);
+    // Two more half-lives: three quarters of the remaining distance
+    assert!((smoother.update(&price_at(CexExchange::Binance, 150.0, 3_000)) - 150.0).abs() < 1e-9);
+    assert!((smoother.update(&price_at(CexExchange::Binance, 250.0, 5_000)) - 225.0).abs() < 1e-9);
+
+    // Stale or duplicate ticks leave the average alone
+    assert!((smoother.update(&price_at(CexExchange::Binance, 0.0, 5_000)) - 225.0).abs() < 1e-9);
+
+    // Venues are averaged separately
+    assert_eq!(
+        smoother.update(&price_at(CexExchange::OKX, 90.0, 5_000)),
+        90.0
+    );
+    let binance = Exchange::Cex(CexExchange::Binance);
+    assert!((smoother.smoothed_mid(&binance, "BTCUSDT").unwrap() - 225.0).abs() < 1e-9);
+
+    smoother.reset();
+    assert_eq!(smoother.smoothed_mid(&binance, "BTCUSDT"), None);
+}
+
+#[test]
+fn zero_half_life_follows_the_mid() {
+    let mut smoother = MidPriceSmoother::new(Duration::ZERO);
+    smoother.update(&price_at(CexExchange::Binance, 100.0, 0));
+    assert_eq!(
+        smoother.update(&price_at(CexExchange::Binance, 120.0, 1)),
+        120.0
+    );
+}
+
+#[tokio::test]
+async fn smoothed_streams_carry_the_average() {
+    let (tx, rx) = mpsc::channel(8);
+    let mut smoothed = smooth_price_stream(rx, Duration::from_secs(1));
+    tx.send(price_at(CexExchange::Binance, 100.0, 0))
+        .await
+        .unwrap();
+    tx.send(price_at(CexExchange::Binance, 200.0, 1_000))
+        .await
+        .unwrap();
+    drop(tx);
+
+    let first = smoothed.recv().await.unwrap();
+    assert_eq!(first.smoothed_mid, 100.0);
+    let second = smoothed.recv().await.unwrap();
+    assert_eq!(second.price.mid_price, 200.0);
+    assert!((second.smoothed_mid - 150.0).abs() < 1e-9);
+    assert!(smoothed.recv().await.is_none());
+}
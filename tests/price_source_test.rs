@@ -0,0 +1,119 @@
+mod scanner_common;
+
+use aeon_market_scanner_rs::common::{DexPrice, FeeOverrides, Timestamp};
+use aeon_market_scanner_rs::{
+    ArbitrageScanner, CexExchange, DexAggregator, Exchange, MockPriceSource, PriceData,
+    PriceSource, RestPollingSource,
+};
+use scanner_common::CexPriceBuilder;
+use std::collections::HashMap;
+use std::time::Duration;
+
+fn cex(exchange: CexExchange, mid: f64) -> PriceData {
+    PriceData::Cex(
+        CexPriceBuilder::around(exchange, mid, 0.5)
+            .symbol("ETHUSDT")
+            .qty(10.0)
+            .build(),
+    )
+}
+
+fn dex(mid: f64) -> PriceData {
+    PriceData::Dex(DexPrice {
+        symbol: "ETHUSDT".to_string(),
+        mid_price: mid,
+        bid_price: mid - 0.5,
+        ask_price: mid + 0.5,
+        bid_qty: 10.0,
+        ask_qty: 10.0,
+        timestamp: Timestamp::from_millis(1),
+        exchange: Exchange::Dex(DexAggregator::KyberSwap),
+        bid_route_summary: None,
+        ask_route_summary: None,
+        bid_route_data: None,
+        ask_route_data: None,
+        transfer_tax_bps: None,
+    })
+}
+
+fn zero_fees() -> FeeOverrides {
+    FeeOverrides {
+        cex_taker: HashMap::from([(CexExchange::Binance, 0.0), (CexExchange::OKX, 0.0)]),
+        dex_taker: HashMap::from([(DexAggregator::KyberSwap, 0.0)]),
+    }
+}
+
+#[tokio::test]
+async fn cex_and_dex_sources_share_one_matching_engine() {
+    let sources: Vec<Box<dyn PriceSource>> = vec![
+        Box::new(MockPriceSource::new(
+            "binance",
+            vec![cex(CexExchange::Binance, 2000.0)],
+        )),
+        Box::new(MockPriceSource::new("kyber", vec![dex(2020.0)])),
+    ];
+    let mut rx = ArbitrageScanner::scan_sources(sources, &["ETHUSDT"], Some(&zero_fees()))
+        .await
+        .unwrap();
+
+    let mut last = Vec::new();
+    while let Some(snapshot) = rx.recv().await {
+        last = snapshot;
+    }
+    assert_eq!(last.len(), 1);
+    assert_eq!(last[0].source_exchange, "Binance");
+    assert_eq!(last[0].destination_exchange, "KyberSwap");
+    assert!(last[0].spread_percentage > 0.0);
+}
+
+#[tokio::test]
+async fn latest_price_per_venue_is_matched() {
+    let (okx, okx_tx) = MockPriceSource::channel("okx", 8);
+    let sources: Vec<Box<dyn PriceSource>> = vec![
+        Box::new(MockPriceSource::new(
+            "binance",
+            vec![cex(CexExchange::Binance, 2000.0)],
+        )),
+        Box::new(okx),
+    ];
+    let mut rx = ArbitrageScanner::scan_sources(sources, &["ETHUSDT"], Some(&zero_fees()))
+        .await
+        .unwrap();
+    // Binance alone cannot be matched
+    assert!(rx.recv().await.unwrap().is_empty());
+
+    okx_tx.send(cex(CexExchange::OKX, 2010.0)).await.unwrap();
+    let first = rx.recv().await.unwrap();
+    assert_eq!(first[0].destination_exchange, "OKX");
+
+    // A zero price does not overwrite the previous one
+    okx_tx.send(cex(CexExchange::OKX, 0.0)).await.unwrap();
+    okx_tx.send(cex(CexExchange::OKX, 1990.0)).await.unwrap();
+    let second = rx.recv().await.unwrap();
+    assert_eq!(second[0].source_exchange, "OKX");
+    assert_eq!(second[0].destination_exchange, "Binance");
+
+    drop(okx_tx);
+    assert!(rx.recv().await.is_none());
+}
+
+#[tokio::test]
+async fn sources_must_be_given_and_start() {
+    assert!(
+        ArbitrageScanner::scan_sources(Vec::new(), &["ETHUSDT"], None)
+            .await
+            .is_err()
+    );
+
+    let invalid = RestPollingSource::new(CexExchange::Binance, &["ETHUSDT"], Duration::ZERO);
+    assert_eq!(invalid.name(), "rest:Binance");
+    let sources: Vec<Box<dyn PriceSource>> = vec![
+        Box::new(MockPriceSource::new("binance", Vec::new())),
+        Box::new(invalid),
+    ];
+    assert!(
+        ArbitrageScanner::scan_sources(sources, &["ETHUSDT"], None)
+            .await
+            .is_err()
+    );
+}
@@ -0,0 +1,53 @@
+use aeon_market_scanner_rs::dex::execution::{
+    FLASHBOTS_PROTECT_RPC_URL, PrivateRelay, PrivateRelayConfig, RelayHint, bundle_params,
+};
+use ethers::core::types::Bytes;
+
+#[test]
+fn test_rpc_url_with_hints() {
+    let config = PrivateRelayConfig::default();
+    assert_eq!(config.rpc_url_with_hints(), FLASHBOTS_PROTECT_RPC_URL);
+
+    let config = config.with_hints(vec![RelayHint::Hash, RelayHint::Logs]);
+    assert_eq!(
+        config.rpc_url_with_hints(),
+        "https://rpc.flashbots.net?hint=hash&hint=logs"
+    );
+
+    let config = config.with_rpc_url("https://rpc.flashbots.net/fast?originId=scanner");
+    assert_eq!(
+        config.rpc_url_with_hints(),
+        "https://rpc.flashbots.net/fast?originId=scanner&hint=hash&hint=logs"
+    );
+    assert!(PrivateRelay::new(config).is_ok());
+}
+
+#[test]
+fn test_bundle_params() {
+    let config = PrivateRelayConfig::default()
+        .with_max_block_offset(3)
+        .with_hints(vec![RelayHint::Calldata]);
+    let txs = [Bytes::from(vec![0x02, 0xf8]), Bytes::from(vec![0x02, 0xf9])];
+    let params = bundle_params(&txs, 100, &config);
+
+    assert_eq!(params["version"], "v0.1");
+    assert_eq!(params["inclusion"]["block"], "0x64");
+    assert_eq!(params["inclusion"]["maxBlock"], "0x67");
+    assert_eq!(params["body"].as_array().unwrap().len(), 2);
+    assert_eq!(params["body"][0]["tx"], "0x02f8");
+    assert_eq!(params["body"][0]["canRevert"], false);
+    assert_eq!(params["privacy"]["hints"][0], "calldata");
+
+    let no_hints = bundle_params(&txs, 100, &PrivateRelayConfig::default());
+    assert!(no_hints.get("privacy").is_none());
+}
+
+#[tokio::test]
+async fn test_send_bundle_requires_auth_signer() {
+    let relay = PrivateRelay::new(PrivateRelayConfig::default()).unwrap();
+    let err = relay
+        .send_bundle(&[Bytes::from(vec![0x02])], 1)
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("auth signer"));
+}
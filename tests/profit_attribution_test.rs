@@ -0,0 +1,207 @@
+mod scanner_common;
+
+use aeon_market_scanner_rs::cex::execution::{
+    CexOrder, ExecutionOutcome, ExecutionStatus, LegOutcome, OrderStatus, OrderUpdate,
+};
+use aeon_market_scanner_rs::common::Timestamp;
+use aeon_market_scanner_rs::{
+    AdverseSelectionModel, AttributionGrouping, CexExchange, Exchange, Fill, PaperTrader,
+    ProfitAttribution, SlippageModel, TradeAttribution, TradeSide,
+};
+use scanner_common::opportunity;
+
+fn fill(exchange: CexExchange, side: TradeSide, quantity: f64, price: f64, fee: f64) -> Fill {
+    Fill {
+        exchange: Exchange::Cex(exchange),
+        symbol: "BTCUSDT".to_string(),
+        side,
+        quantity,
+        price,
+        fee_quote: fee,
+        timestamp: Timestamp::from_millis(5),
+    }
+}
+
+#[test]
+fn components_add_up_to_realized_pnl() {
+    let opp = opportunity(
+        "BTCUSDT",
+        CexExchange::Binance,
+        100.0,
+        CexExchange::OKX,
+        110.0,
+        1.0,
+    );
+    let trade = TradeAttribution::from_fills(
+        &opp,
+        &[
+            fill(CexExchange::Binance, TradeSide::Buy, 1.0, 100.5, 0.1),
+            fill(CexExchange::OKX, TradeSide::Sell, 1.0, 109.0, 0.1),
+        ],
+    )
+    .with_transfer_costs(0.3);
+
+    assert_eq!(trade.venue_pair(), "Binance->OKX");
+    assert!((trade.matched_quantity - 1.0).abs() < 1e-9);
+    assert!((trade.spread_captured - 10.0).abs() < 1e-9);
+    assert!((trade.fees - 0.2).abs() < 1e-9);
+    assert!((trade.slippage - 1.5).abs() < 1e-9);
+    assert!((trade.transfer_costs - 0.3).abs() < 1e-9);
+    assert!((trade.realized_pnl - 8.0).abs() < 1e-9);
+    assert_eq!(trade.timestamp, Timestamp::from_millis(5));
+}
+
+#[test]
+fn unwound_quantity_counts_as_slippage() {
+    let opp = opportunity(
+        "BTCUSDT",
+        CexExchange::Binance,
+        100.0,
+        CexExchange::OKX,
+        110.0,
+        1.0,
+    );
+    let trade = TradeAttribution::from_fills(
+        &opp,
+        &[
+            fill(CexExchange::Binance, TradeSide::Buy, 1.0, 100.0, 0.0),
+            fill(CexExchange::OKX, TradeSide::Sell, 0.5, 110.0, 0.0),
+            fill(CexExchange::Binance, TradeSide::Sell, 0.5, 98.0, 0.0),
+        ],
+    );
+    assert!((trade.matched_quantity - 0.5).abs() < 1e-9);
+    assert!((trade.spread_captured - 5.0).abs() < 1e-9);
+    assert!((trade.slippage - 1.0).abs() < 1e-9);
+    assert!((trade.realized_pnl - 4.0).abs() < 1e-9);
+}
+
+#[test]
+fn execution_outcomes_are_attributed_from_their_fills() {
+    let opp = opportunity(
+        "BTCUSDT",
+        CexExchange::Binance,
+        100.0,
+        CexExchange::OKX,
+        110.0,
+        1.0,
+    );
+    let leg = |exchange: CexExchange, side: TradeSide, filled: f64, average: f64| LegOutcome {
+        exchange: exchange.clone(),
+        order: CexOrder::market("BTCUSDT", side, 1.0),
+        ack: None,
+        last_update: Some(OrderUpdate {
+            exchange,
+            symbol: "BTCUSDT".to_string(),
+            order_id: "1".to_string(),
+            client_order_id: None,
+            side,
+            status: OrderStatus::Filled,
+            price: None,
+            quantity: 1.0,
+            filled_quantity: filled,
+            average_fill_price: Some(average),
+            timestamp: Timestamp::from_millis(3),
+        }),
+        errors: Vec::new(),
+        replacements: Vec::new(),
+    };
+    let outcome = ExecutionOutcome {
+        status: ExecutionStatus::Hedged,
+        buy: leg(CexExchange::Binance, TradeSide::Buy, 1.0, 100.0),
+        sell: leg(CexExchange::OKX, TradeSide::Sell, 0.6, 110.0),
+        correction: Some(leg(CexExchange::OKX, TradeSide::Sell, 0.4, 109.0)),
+        residual_quantity: 0.0,
+        started_at: Timestamp::from_millis(1),
+        finished_at: Timestamp::from_millis(9),
+    };
+
+    assert_eq!(outcome.fills(&opp).len(), 3);
+    let trade = TradeAttribution::from_execution(&opp, &outcome);
+    assert!((trade.matched_quantity - 1.0).abs() < 1e-9);
+    assert!((trade.slippage - 0.4).abs() < 1e-9);
+    assert!((trade.realized_pnl - 9.6).abs() < 1e-9);
+    assert_eq!(trade.timestamp, Timestamp::from_millis(9));
+}
+
+#[test]
+fn paper_trades_are_grouped_and_exported() {
+    let model = AdverseSelectionModel::default().with_slippage(SlippageModel::Fixed { bps: 100.0 });
+    let mut trader = PaperTrader::new(model);
+    trader.execute(
+        &opportunity(
+            "BTCUSDT",
+            CexExchange::Binance,
+            100.0,
+            CexExchange::OKX,
+            110.0,
+            1.0,
+        ),
+        1.0,
+    );
+    trader.execute(
+        &opportunity(
+            "ETHUSDT",
+            CexExchange::Binance,
+            100.0,
+            CexExchange::OKX,
+            110.0,
+            1.0,
+        ),
+        1.0,
+    );
+    trader.execute(
+        &opportunity(
+            "BTCUSDT",
+            CexExchange::Bybit,
+            100.0,
+            CexExchange::OKX,
+            110.0,
+            1.0,
+        ),
+        1.0,
+    );
+    let attribution = trader.attribution();
+
+    let total = attribution.total();
+    assert_eq!(total.trades, 3);
+    assert!((total.spread_captured - 30.0).abs() < 1e-9);
+    assert!((total.slippage - 6.3).abs() < 1e-9);
+    assert!((total.realized_pnl - trader.stats().realized_profit).abs() < 1e-9);
+
+    let pairs = attribution.summary(AttributionGrouping::VenuePair);
+    assert_eq!(
+        pairs.iter().map(|s| s.group.as_str()).collect::<Vec<_>>(),
+        ["Binance->OKX", "Bybit->OKX"]
+    );
+    assert_eq!(pairs[0].trades, 2);
+    let symbols = attribution.summary(AttributionGrouping::Symbol);
+    assert_eq!(symbols[0].group, "BTCUSDT");
+    assert_eq!(symbols[0].trades, 2);
+
+    let csv = attribution.to_csv();
+    assert_eq!(csv.lines().count(), 4);
+    assert!(csv.starts_with("timestamp,buy_exchange,sell_exchange,symbol,"));
+    assert!(
+        csv.lines()
+            .nth(2)
+            .unwrap()
+            .contains(",Binance,OKX,ETHUSDT,1,10,0,")
+    );
+    let summary_csv = attribution.summary_csv(AttributionGrouping::Symbol);
+    assert!(
+        summary_csv
+            .lines()
+            .nth(2)
+            .unwrap()
+            .starts_with("ETHUSDT,1,")
+    );
+
+    let json: serde_json::Value = serde_json::from_str(&attribution.to_json().unwrap()).unwrap();
+    assert_eq!(json["by_venue_pair"][1]["group"], "Bybit->OKX");
+    assert_eq!(json["trades"].as_array().unwrap().len(), 3);
+    let parsed: ProfitAttribution = serde_json::from_value(serde_json::json!({
+        "trades": json["trades"].clone()
+    }))
+    .unwrap();
+    assert_eq!(&parsed, attribution);
+}
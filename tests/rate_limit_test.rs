@@ -0,0 +1,94 @@
+use aeon_market_scanner_rs::common::{
+    RequestPriority, acquire_request_slot, queued_requests, remove_rate_limit, set_rate_limit,
+};
+use std::time::Duration;
+use tokio::sync::mpsc;
+
+#[tokio::test]
+async fn unlimited_venue_is_not_delayed() {
+    tokio::time::timeout(Duration::from_millis(100), async {
+        for _ in 0..100 {
+            acquire_request_slot("NoLimitVenue", RequestPriority::Metadata).await;
+        }
+    })
+    .await
+    .expect("venues without a limit should not wait");
+}
+
+#[tokio::test]
+async fn queued_requests_are_served_by_priority() {
+    let venue = "PriorityVenue";
+    set_rate_limit(venue, 1, Duration::from_millis(100));
+    // Takes the first slot so the following requests queue up
+    acquire_request_slot(venue, RequestPriority::Price).await;
+
+    let (tx, mut rx) = mpsc::unbounded_channel();
+    for priority in [
+        RequestPriority::Metadata,
+        RequestPriority::OrderBook,
+        RequestPriority::Price,
+    ] {
+        let tx = tx.clone();
+        tokio::spawn(async move {
+            acquire_request_slot(venue, priority).await;
+            tx.send(priority).unwrap();
+        });
+        tokio::time::sleep(Duration::from_millis(5)).await;
+    }
+    drop(tx);
+    assert_eq!(queued_requests(venue), 3);
+
+    let mut order = Vec::new();
+    while let Some(priority) = rx.recv().await {
+        order.push(priority);
+    }
+    assert_eq!(
+        order,
+        [
+            RequestPriority::Price,
+            RequestPriority::OrderBook,
+            RequestPriority::Metadata
+        ]
+    );
+    assert_eq!(remove_rate_limit(venue), Some(Duration::from_millis(100)));
+}
+
+#[tokio::test]
+async fn cancelled_request_leaves_the_queue() {
+    let venue = "CancelVenue";
+    set_rate_limit(venue, 1, Duration::from_millis(200));
+    acquire_request_slot(venue, RequestPriority::Price).await;
+
+    let cancelled = tokio::time::timeout(
+        Duration::from_millis(20),
+        acquire_request_slot(venue, RequestPriority::Price),
+    )
+    .await;
+    assert!(cancelled.is_err());
+    assert_eq!(queued_requests(venue), 0);
+
+    tokio::time::timeout(
+        Duration::from_secs(1),
+        acquire_request_slot(venue, RequestPriority::Metadata),
+    )
+    .await
+    .expect("a cancelled request should not block the queue");
+    remove_rate_limit(venue);
+}
+
+#[tokio::test]
+async fn removing_the_limit_releases_waiters() {
+    let venue = "RemoveVenue";
+    set_rate_limit(venue, 1, Duration::from_secs(60));
+    acquire_request_slot(venue, RequestPriority::Price).await;
+
+    let waiter = tokio::spawn(acquire_request_slot(venue, RequestPriority::Metadata));
+    tokio::time::sleep(Duration::from_millis(20)).await;
+    assert_eq!(queued_requests(venue), 1);
+    remove_rate_limit(venue);
+
+    tokio::time::timeout(Duration::from_secs(1), waiter)
+        .await
+        .expect("waiters should be released")
+        .unwrap();
+}
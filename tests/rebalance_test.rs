@@ -0,0 +1,137 @@
+use aeon_market_scanner_rs::common::Timestamp;
+use aeon_market_scanner_rs::{
+    CexExchange, Exchange, Fill, OpportunityReport, OpportunitySummary, Portfolio,
+    RebalancePlanner, TradeSide, TransferCostModel,
+};
+
+fn entry(source: &str, destination: &str, symbol: &str, profit: f64) -> OpportunitySummary {
+    OpportunitySummary {
+        source_exchange: source.to_string(),
+        destination_exchange: destination.to_string(),
+        symbol: symbol.to_string(),
+        count: 1,
+        avg_spread_percentage: 0.5,
+        max_spread_percentage: 0.5,
+        cumulative_profit_quote: profit,
+    }
+}
+
+fn report(entries: Vec<OpportunitySummary>) -> OpportunityReport {
+    OpportunityReport {
+        window_start: Timestamp::from_millis(0),
+        window_end: Timestamp::from_millis(60_000),
+        total_count: entries.len() as u64,
+        entries,
+    }
+}
+
+fn cex(exchange: CexExchange) -> Exchange {
+    Exchange::Cex(exchange)
+}
+
+#[test]
+fn test_moves_quote_to_buying_venues_by_profit() {
+    let balances = vec![
+        (cex(CexExchange::Binance), "USDT".to_string(), 10_000.0),
+        (cex(CexExchange::OKX), "USDT".to_string(), 0.0),
+    ];
+    // OKX buys in three quarters of the profit, Binance in one quarter
+    let report = report(vec![
+        entry("OKX", "Bybit", "BTCUSDT", 30.0),
+        entry("Binance", "Bybit", "BTCUSDT", 10.0),
+    ]);
+
+    let plans = RebalancePlanner::new().plan_balances(&balances, &report);
+    let usdt: Vec<_> = plans.iter().filter(|p| p.asset == "USDT").collect();
+    assert_eq!(usdt.len(), 1);
+    assert_eq!(usdt[0].from_exchange, cex(CexExchange::Binance));
+    assert_eq!(usdt[0].to_exchange, cex(CexExchange::OKX));
+    assert!((usdt[0].amount - 7_500.0).abs() < 1e-9);
+    assert!((usdt[0].demand_share - 0.75).abs() < 1e-9);
+    assert!(usdt[0].network.is_none());
+}
+
+#[test]
+fn test_base_goes_to_selling_venues() {
+    let balances = vec![(cex(CexExchange::Binance), "BTC".to_string(), 2.0)];
+    let report = report(vec![entry("Binance", "Kraken", "BTCUSDT", 50.0)]);
+
+    let plans = RebalancePlanner::new().plan_balances(&balances, &report);
+    assert_eq!(plans.len(), 1);
+    assert_eq!(plans[0].asset, "BTC");
+    assert_eq!(plans[0].to_exchange, cex(CexExchange::Kraken));
+    assert!((plans[0].amount - 2.0).abs() < 1e-9);
+}
+
+#[test]
+fn test_no_demand_or_small_transfers_are_skipped() {
+    let balances = vec![
+        (cex(CexExchange::Binance), "USDT".to_string(), 1_000.0),
+        (cex(CexExchange::OKX), "USDT".to_string(), 980.0),
+    ];
+    let balanced = report(vec![
+        entry("Binance", "Bybit", "BTCUSDT", 10.0),
+        entry("OKX", "Bybit", "BTCUSDT", 10.0),
+    ]);
+    let planner = RebalancePlanner::new().with_min_transfer("usdt", 50.0);
+    assert!(planner.plan_balances(&balances, &balanced).is_empty());
+
+    // Unprofitable flow and unknown venues carry no demand
+    let empty = report(vec![
+        entry("Binance", "Bybit", "BTCUSDT", -5.0),
+        entry("Nowhere", "Bybit", "ETHUSDT", 10.0),
+    ]);
+    assert!(
+        RebalancePlanner::new()
+            .plan_balances(&balances, &empty)
+            .iter()
+            .all(|p| p.asset != "USDT")
+    );
+}
+
+#[test]
+fn test_transfer_costs_pick_common_network() {
+    let balances = vec![(cex(CexExchange::Binance), "USDT".to_string(), 1_000.0)];
+    let report = report(vec![entry("OKX", "Bybit", "BTCUSDT", 10.0)]);
+    let model = TransferCostModel::default()
+        .with_network("USDT", "TRC20", 1.0, 120)
+        .with_network("USDT", "ERC20", 5.0, 300)
+        .with_venue_networks(cex(CexExchange::Binance), "USDT", &["TRC20", "ERC20"])
+        .with_venue_networks(cex(CexExchange::OKX), "USDT", &["ERC20"]);
+
+    let plans = RebalancePlanner::new()
+        .with_transfer_costs(model.clone())
+        .plan_balances(&balances, &report);
+    assert_eq!(plans.len(), 1);
+    assert_eq!(plans[0].network.as_ref().unwrap().network, "ERC20");
+
+    // No network in common: nothing to suggest
+    let model = model.with_venue_networks(cex(CexExchange::OKX), "USDT", &["SOL"]);
+    assert!(
+        RebalancePlanner::new()
+            .with_transfer_costs(model)
+            .plan_balances(&balances, &report)
+            .is_empty()
+    );
+}
+
+#[test]
+fn test_plan_uses_portfolio_balances() {
+    let mut portfolio = Portfolio::new();
+    portfolio.apply_fill(&Fill {
+        exchange: cex(CexExchange::Binance),
+        symbol: "BTCUSDT".to_string(),
+        side: TradeSide::Sell,
+        quantity: 0.1,
+        price: 50_000.0,
+        fee_quote: 0.0,
+        timestamp: Timestamp::from_millis(1),
+    });
+    let report = report(vec![entry("OKX", "Binance", "BTCUSDT", 20.0)]);
+
+    let plans = RebalancePlanner::new().plan(&portfolio, &report);
+    assert_eq!(plans.len(), 1);
+    assert_eq!(plans[0].asset, "USDT");
+    assert_eq!(plans[0].to_exchange, cex(CexExchange::OKX));
+    assert!((plans[0].amount - 5_000.0).abs() < 1e-6);
+}
@@ -0,0 +1,152 @@
+use aeon_market_scanner_rs::common::{
+    RestRequestSample, clear_rest_request_hook, record_rest_request, rest_connection_stats,
+    set_rest_request_hook,
+};
+use aeon_market_scanner_rs::{ArbitrageScanner, CexExchange, ExchangeTrait, MarketScannerError};
+use async_trait::async_trait;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+
+/// Venue served by a local keep-alive HTTP server.
+struct LocalVenue {
+    client: reqwest::Client,
+    api_base: String,
+}
+
+#[async_trait]
+impl ExchangeTrait for LocalVenue {
+    fn api_base(&self) -> &str {
+        &self.api_base
+    }
+
+    fn client(&self) -> &reqwest::Client {
+        &self.client
+    }
+
+    fn exchange_name(&self) -> &str {
+        "LocalKeepAliveVenue"
+    }
+
+    async fn health_check(&self) -> Result<(), MarketScannerError> {
+        Ok(())
+    }
+}
+
+/// Answers every request with `{}` on persistent connections; returns the base URL and
+/// the number of accepted connections.
+async fn keep_alive_server() -> (String, Arc<AtomicUsize>) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let connections = Arc::new(AtomicUsize::new(0));
+    let accepted = connections.clone();
+    tokio::spawn(async move {
+        while let Ok((mut stream, _)) = listener.accept().await {
+            accepted.fetch_add(1, Ordering::SeqCst);
+            tokio::spawn(async move {
+                let mut buf = Vec::new();
+                let mut chunk = [0u8; 1024];
+                loop {
+                    while let Some(end) = buf.windows(4).position(|w| w == b"\r\n\r\n") {
+                        buf.drain(..end + 4);
+                        let response = "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: 2\r\n\r\n{}";
+                        if stream.write_all(response.as_bytes()).await.is_err() {
+                            return;
+                        }
+                    }
+                    match stream.read(&mut chunk).await {
+                        Ok(0) | Err(_) => return,
+                        Ok(n) => buf.extend_from_slice(&chunk[..n]),
+                    }
+                }
+            });
+        }
+    });
+    (format!("http://{}", addr), connections)
+}
+
+#[test]
+fn slow_requests_count_as_new_connections() {
+    let venue = "TimingVenue";
+    let ms = Duration::from_millis;
+    // First request of a venue: new connection
+    assert!(record_rest_request(venue, ms(0), ms(100), 200).likely_new_connection);
+    // Within twice the fastest time to headers: reused
+    assert!(!record_rest_request(venue, ms(10), ms(40), 200).likely_new_connection);
+    assert!(!record_rest_request(venue, ms(0), ms(70), 200).likely_new_connection);
+    // Handshake-sized slowdown: new connection
+    assert!(record_rest_request(venue, ms(20), ms(150), 200).likely_new_connection);
+
+    let stats = rest_connection_stats(venue).unwrap();
+    assert_eq!(stats.requests, 4);
+    assert_eq!(stats.likely_new_connections, 2);
+    assert_eq!(stats.reuse_rate(), 0.5);
+    assert_eq!(stats.min_time_to_headers_ms, 40.0);
+    assert_eq!(stats.avg_new_connection_ms, 125.0);
+    assert_eq!(stats.avg_reused_connection_ms, 55.0);
+    assert_eq!(stats.avg_queue_wait_ms, 7.5);
+    assert_eq!(stats.estimated_handshake_ms(), Some(70.0));
+    assert_eq!(stats.avg_response_ms(), 97.5);
+    assert!(rest_connection_stats("NeverRequested").is_none());
+}
+
+#[tokio::test]
+async fn requests_are_recorded_and_reuse_the_pool() {
+    let (api_base, connections) = keep_alive_server().await;
+    let samples: Arc<Mutex<Vec<RestRequestSample>>> = Arc::new(Mutex::new(Vec::new()));
+    let hook_samples = samples.clone();
+    set_rest_request_hook(move |sample| {
+        if sample.venue == "LocalKeepAliveVenue" {
+            hook_samples.lock().unwrap().push(sample.clone());
+        }
+    });
+
+    let venue = LocalVenue {
+        client: reqwest::Client::new(),
+        api_base,
+    };
+    for _ in 0..3 {
+        let _: serde_json::Value = venue.get("ping").await.unwrap();
+    }
+    clear_rest_request_hook();
+
+    assert_eq!(connections.load(Ordering::SeqCst), 1);
+    let stats = rest_connection_stats("LocalKeepAliveVenue").unwrap();
+    assert_eq!(stats.requests, 3);
+    assert!(stats.likely_new_connections >= 1);
+    let samples = samples.lock().unwrap();
+    assert_eq!(samples.len(), 3);
+    assert!(samples[0].likely_new_connection);
+    assert!(samples.iter().all(|s| s.status == 200));
+}
+
+#[test]
+fn slow_venues_are_requested_first() {
+    let ms = Duration::from_millis;
+    record_rest_request(CexExchange::Binance.rest_venue_name(), ms(0), ms(20), 200);
+    record_rest_request(CexExchange::Gateio.rest_venue_name(), ms(0), ms(300), 200);
+    record_rest_request(CexExchange::OKX.rest_venue_name(), ms(0), ms(60), 200);
+    // Rate-limit waits count too
+    record_rest_request(CexExchange::Kucoin.rest_venue_name(), ms(200), ms(50), 200);
+
+    let ordered = ArbitrageScanner::order_by_rest_latency(&[
+        CexExchange::Binance,
+        CexExchange::OKX,
+        CexExchange::Kucoin,
+        CexExchange::Gateio,
+        CexExchange::Kraken,
+    ]);
+    // Kraken has no recorded requests yet, so it goes first
+    assert_eq!(
+        ordered,
+        vec![
+            CexExchange::Kraken,
+            CexExchange::Gateio,
+            CexExchange::Kucoin,
+            CexExchange::OKX,
+            CexExchange::Binance,
+        ]
+    );
+}
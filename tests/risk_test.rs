@@ -0,0 +1,224 @@
+mod scanner_common;
+
+use aeon_market_scanner_rs::cex::execution::{
+    CexOrder, CexOrderExecution, OrderStatus, OrderUpdate, TradingMode,
+};
+use aeon_market_scanner_rs::common::{Credentials, Timestamp};
+use aeon_market_scanner_rs::{
+    CexExchange, Exchange, Exposure, MarketScannerError, OKX, RiskLimits, RiskManager,
+    RiskViolation, TradeSide,
+};
+use scanner_common::opportunity;
+
+fn violation(result: Result<impl std::fmt::Debug, MarketScannerError>) -> RiskViolation {
+    match result {
+        Err(MarketScannerError::RiskLimitExceeded(violation)) => violation,
+        other => panic!("expected a risk violation, got {:?}", other),
+    }
+}
+
+#[test]
+fn opportunity_notional_is_capped() {
+    let risk = RiskManager::new(RiskLimits::new().with_max_notional_per_opportunity(500.0));
+    let opportunity = opportunity(
+        "BTCUSDT",
+        CexExchange::Bybit,
+        100.0,
+        CexExchange::OKX,
+        110.0,
+        10.0,
+    );
+
+    assert!(matches!(
+        violation(risk.reserve_opportunity(&opportunity, 6.0)),
+        RiskViolation::OpportunityNotional { notional, limit } if notional == 600.0 && limit == 500.0
+    ));
+    // Nothing was recorded by the rejected reservation
+    assert!(risk.exposures().is_empty());
+    assert_eq!(risk.orders_last_minute(), 0);
+
+    let [buy, sell] = risk.reserve_opportunity(&opportunity, 5.0).unwrap();
+    assert_eq!(buy.exchange, Exchange::Cex(CexExchange::Bybit));
+    assert_eq!(buy.notional, 500.0);
+    assert_eq!(sell.notional, 550.0);
+    assert_eq!(risk.asset_exposure("BTC"), 1050.0);
+    assert_eq!(risk.orders_last_minute(), 2);
+}
+
+#[test]
+fn asset_and_venue_exposure_limits() {
+    let okx = Exchange::Cex(CexExchange::OKX);
+    let risk = RiskManager::new(
+        RiskLimits::new()
+            .with_max_exposure_per_venue(1_000.0)
+            .with_asset_exposure_limit("XBT", 1_500.0),
+    );
+    let order = CexOrder::limit("BTCUSDT", TradeSide::Buy, 8.0, 100.0);
+    let first = risk.reserve_order(&CexExchange::OKX, &order, None).unwrap();
+    assert_eq!(risk.exposure(&okx, "BTC"), 800.0);
+
+    // Venue limit: 800 + 300 > 1000
+    let eth = CexOrder::limit("ETHUSDT", TradeSide::Buy, 3.0, 100.0);
+    assert!(matches!(
+        violation(risk.reserve_order(&CexExchange::OKX, &eth, None)),
+        RiskViolation::VenueExposure { exposure, .. } if exposure == 1_100.0
+    ));
+    // Another venue, but the BTC limit (set via its XBT alias) is 1500
+    assert!(matches!(
+        violation(risk.reserve_order(&CexExchange::Bybit, &order, None)),
+        RiskViolation::AssetExposure { asset, limit, .. } if asset == "BTC" && limit == 1_500.0
+    ));
+    // Other assets only count against the venue
+    let eth = CexOrder::limit("ETHUSDT", TradeSide::Buy, 1.0, 150.0);
+    risk.reserve_order(&CexExchange::OKX, &eth, None).unwrap();
+    assert_eq!(risk.venue_exposure(&okx), 950.0);
+
+    risk.release(&first);
+    assert_eq!(risk.exposure(&okx, "BTC"), 0.0);
+    risk.reserve_order(&CexExchange::Bybit, &order, None)
+        .unwrap();
+}
+
+#[test]
+fn asset_limit_applies_to_four_letter_assets() {
+    let risk = RiskManager::new(RiskLimits::new().with_asset_exposure_limit("DOGE", 500.0));
+    assert_eq!(risk.limits().asset_limit("DOGE"), Some(500.0));
+    assert_eq!(risk.limits().asset_limit("XDG"), Some(500.0));
+
+    let order = CexOrder::limit("DOGEUSDT", TradeSide::Buy, 10.0, 100.0);
+    assert!(matches!(
+        violation(risk.reserve_order(&CexExchange::OKX, &order, None)),
+        RiskViolation::AssetExposure { asset, exposure, limit }
+            if asset == "DOGE" && exposure == 1_000.0 && limit == 500.0
+    ));
+    let order = CexOrder::limit("DOGEUSDT", TradeSide::Buy, 5.0, 100.0);
+    risk.reserve_order(&CexExchange::OKX, &order, None).unwrap();
+}
+
+#[test]
+fn exposure_queries_resolve_asset_aliases() {
+    let risk = RiskManager::new(RiskLimits::new());
+    let kraken = Exchange::Cex(CexExchange::Kraken);
+    let order = CexOrder::limit("BTCUSDT", TradeSide::Buy, 1.0, 100.0);
+    risk.reserve_order(&CexExchange::Kraken, &order, None)
+        .unwrap();
+    let order = CexOrder::limit("ETHUSDT", TradeSide::Buy, 2.0, 10.0);
+    let eth = risk
+        .reserve_order(&CexExchange::Kraken, &order, None)
+        .unwrap();
+
+    assert_eq!(risk.asset_exposure("XBT"), 100.0);
+    assert_eq!(risk.asset_exposure("xbt"), 100.0);
+    assert_eq!(risk.exposure(&kraken, "WETH"), 20.0);
+
+    risk.release(&Exposure {
+        asset: "WETH".to_string(),
+        ..eth
+    });
+    assert_eq!(risk.exposure(&kraken, "ETH"), 0.0);
+}
+
+#[test]
+fn non_positive_quantities_are_rejected() {
+    let risk = RiskManager::new(RiskLimits::new());
+    for quantity in [0.0, -1.0, f64::NAN, f64::INFINITY] {
+        let order = CexOrder::limit("BTCUSDT", TradeSide::Buy, quantity, 100.0);
+        assert!(matches!(
+            risk.reserve_order(&CexExchange::OKX, &order, None),
+            Err(MarketScannerError::InvalidConfig(_))
+        ));
+        assert!(matches!(
+            risk.reserve_opportunity(
+                &opportunity(
+                    "BTCUSDT",
+                    CexExchange::Bybit,
+                    100.0,
+                    CexExchange::OKX,
+                    110.0,
+                    10.0
+                ),
+                quantity
+            ),
+            Err(MarketScannerError::InvalidConfig(_))
+        ));
+    }
+    assert!(risk.exposures().is_empty());
+    assert_eq!(risk.orders_last_minute(), 0);
+}
+
+#[test]
+fn orders_per_minute_and_runtime_limits() {
+    let risk = RiskManager::new(RiskLimits::new().with_max_orders_per_minute(3));
+    let order = CexOrder::market("ETHUSDT", TradeSide::Sell, 1.0);
+
+    // Market orders are valued at a reference price
+    assert!(matches!(
+        risk.reserve_order(&CexExchange::Bybit, &order, None),
+        Err(MarketScannerError::InvalidConfig(_))
+    ));
+    for _ in 0..3 {
+        risk.reserve_order(&CexExchange::Bybit, &order, Some(2_000.0))
+            .unwrap();
+    }
+    assert!(matches!(
+        violation(risk.reserve_order(&CexExchange::Bybit, &order, Some(2_000.0))),
+        RiskViolation::OrderRate {
+            orders: 4,
+            limit: 3
+        }
+    ));
+
+    // Clones share state; raising the limit applies immediately
+    let shared = risk.clone();
+    shared.set_limits(risk.limits().with_max_orders_per_minute(10));
+    assert_eq!(risk.limits().max_orders_per_minute, Some(10));
+    risk.reserve_order(&CexExchange::Bybit, &order, Some(2_000.0))
+        .unwrap();
+    assert_eq!(shared.orders_last_minute(), 4);
+    assert_eq!(shared.asset_exposure("ETH"), 8_000.0);
+}
+
+#[test]
+fn final_order_updates_release_the_unfilled_part() {
+    let risk = RiskManager::new(RiskLimits::new());
+    let order = CexOrder::limit("BTCUSDT", TradeSide::Buy, 2.0, 100.0);
+    risk.reserve_order(&CexExchange::OKX, &order, None).unwrap();
+
+    let mut update = OrderUpdate {
+        exchange: CexExchange::OKX,
+        symbol: "BTCUSDT".to_string(),
+        order_id: "1".to_string(),
+        client_order_id: None,
+        side: TradeSide::Buy,
+        status: OrderStatus::PartiallyFilled,
+        price: Some(100.0),
+        quantity: 2.0,
+        filled_quantity: 0.5,
+        average_fill_price: Some(100.0),
+        timestamp: Timestamp::from_millis(1),
+    };
+    risk.apply_order_update(&update);
+    assert_eq!(risk.asset_exposure("BTC"), 200.0);
+
+    update.status = OrderStatus::Canceled;
+    risk.apply_order_update(&update);
+    assert_eq!(risk.asset_exposure("BTC"), 50.0);
+}
+
+#[tokio::test]
+async fn rejected_orders_are_never_sent() {
+    let risk = RiskManager::new(RiskLimits::new().with_max_exposure_per_asset(10.0));
+    let order = CexOrder::limit("BTCUSDT", TradeSide::Buy, 1.0, 50_000.0);
+    let result = OKX::new()
+        .place_order_with_risk(
+            &risk,
+            &Credentials::new("k", "s").with_passphrase("p"),
+            &order,
+            TradingMode::Demo,
+            None,
+        )
+        .await;
+    let violation = violation(result);
+    assert!(violation.to_string().contains("BTC exposure 50000"));
+    assert!(risk.exposures().is_empty());
+}
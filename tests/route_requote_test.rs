@@ -0,0 +1,83 @@
+use aeon_market_scanner_rs::dex::chains::ChainId;
+use aeon_market_scanner_rs::dex::kyberswap::{
+    cache_route, cached_route, clear_route_cache, diff_routes,
+};
+use aeon_market_scanner_rs::{KyberSwap, MarketScannerError};
+use serde_json::{Value, json};
+
+const USDT: &str = "0xdAC17F958D2ee523a2206206994597C13D831ec7";
+const WETH: &str = "0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2";
+
+fn route(amount_out: &str, pools: &[&str]) -> Value {
+    let swaps: Vec<Value> = pools
+        .iter()
+        .map(|pool| json!({ "pool": pool, "exchange": "uniswapv3" }))
+        .collect();
+    json!({
+        "routeSummary": {
+            "tokenIn": USDT,
+            "tokenOut": WETH,
+            "amountIn": "1000000000",
+            "amountOut": amount_out,
+            "route": [swaps],
+        },
+        "routerAddress": "0x6131B5fae19EA4f9D964eAc0408E4408b66337b5",
+    })
+}
+
+#[test]
+fn diff_reports_amount_out_change() {
+    let previous = route("400000000000000000", &["0xPoolA", "0xPoolB"]);
+    let current = route("404000000000000000", &["0xpoola", "0xpoolb"]);
+
+    let requote = diff_routes(&previous, &current).unwrap();
+    assert_eq!(requote.amount_in_wei, "1000000000");
+    assert_eq!(requote.previous_amount_out_wei, "400000000000000000");
+    assert_eq!(requote.amount_out_wei, "404000000000000000");
+    assert_eq!(requote.amount_out_change_wei, "4000000000000000");
+    assert!((requote.amount_out_change_percent - 1.0).abs() < 1e-9);
+    // Pool addresses compare case-insensitively
+    assert!(requote.same_path);
+    assert_eq!(requote.route_data, current);
+}
+
+#[test]
+fn diff_detects_path_change_and_decrease() {
+    let previous = route("400000000000000000", &["0xPoolA"]);
+    let current = route("398000000000000000", &["0xPoolC"]);
+
+    let requote = diff_routes(&previous, &current).unwrap();
+    assert_eq!(requote.amount_out_change_wei, "-2000000000000000");
+    assert!((requote.amount_out_change_percent + 0.5).abs() < 1e-9);
+    assert!(!requote.same_path);
+}
+
+#[test]
+fn diff_rejects_incomplete_route_data() {
+    let previous = route("1", &[]);
+    let result = diff_routes(&previous, &json!({ "routeSummary": {} }));
+    assert!(matches!(result, Err(MarketScannerError::ApiError(_))));
+}
+
+#[tokio::test]
+async fn routes_are_cached_per_chain_and_pair() {
+    let data = route("400000000000000000", &["0xPoolA"]);
+    cache_route(&ChainId::ARBITRUM, &data);
+
+    // Token addresses match case-insensitively
+    let cached = cached_route(
+        &ChainId::ARBITRUM,
+        &USDT.to_lowercase(),
+        &WETH.to_uppercase(),
+    );
+    assert_eq!(cached, Some(data));
+    assert!(cached_route(&ChainId::BASE, USDT, WETH).is_none());
+    assert!(cached_route(&ChainId::ARBITRUM, WETH, USDT).is_none());
+
+    clear_route_cache();
+    assert!(cached_route(&ChainId::ARBITRUM, USDT, WETH).is_none());
+    let result = KyberSwap::new()
+        .requote_cached(&ChainId::ARBITRUM, USDT, WETH)
+        .await;
+    assert!(result.is_err());
+}
@@ -0,0 +1,106 @@
+use aeon_market_scanner_rs::{ArbitrageScanner, CexExchange, ScanSchedule, SeededRng, Timestamp};
+use std::time::Duration;
+
+fn schedule() -> ScanSchedule {
+    ScanSchedule::new(10_000)
+        .with_symbol("BTCUSDT", 500)
+        .with_symbols(&["ETHUSDT", "PEPEUSDT"])
+        .with_symbol("ETHUSDT", 2_000)
+}
+
+#[test]
+fn symbols_keep_their_own_interval() {
+    let schedule = schedule();
+    assert_eq!(schedule.symbols(), ["BTCUSDT", "ETHUSDT", "PEPEUSDT"]);
+    assert_eq!(
+        schedule.interval("BTCUSDT"),
+        Some(Duration::from_millis(500))
+    );
+    assert_eq!(
+        schedule.interval("ETHUSDT"),
+        Some(Duration::from_millis(2_000))
+    );
+    assert_eq!(
+        schedule.interval("PEPEUSDT"),
+        Some(Duration::from_millis(10_000))
+    );
+    assert_eq!(schedule.interval("SOLUSDT"), None);
+}
+
+#[test]
+fn due_symbols_follow_their_cadence() {
+    let mut schedule = schedule();
+    assert_eq!(schedule.next_due(), None);
+    assert_eq!(
+        schedule.take_due(Timestamp::from_millis(0)),
+        ["BTCUSDT", "ETHUSDT", "PEPEUSDT"]
+    );
+    assert_eq!(schedule.next_due(), Some(Timestamp::from_millis(500)));
+
+    let mut scans = std::collections::HashMap::<String, usize>::new();
+    for millis in (100..=10_000).step_by(100) {
+        for symbol in schedule.take_due(Timestamp::from_millis(millis)) {
+            *scans.entry(symbol).or_default() += 1;
+        }
+    }
+    assert_eq!(scans["BTCUSDT"], 20);
+    assert_eq!(scans["ETHUSDT"], 5);
+    assert_eq!(scans["PEPEUSDT"], 1);
+}
+
+#[test]
+fn missed_slots_are_skipped_instead_of_burst() {
+    let mut schedule = ScanSchedule::new(1_000).with_symbols(&["BTCUSDT"]);
+    schedule.take_due(Timestamp::from_millis(0));
+    // The scanner was busy for 3.5 intervals: one scan, then back on the grid
+    assert_eq!(
+        schedule.take_due(Timestamp::from_millis(3_500)),
+        ["BTCUSDT"]
+    );
+    assert!(schedule.take_due(Timestamp::from_millis(3_600)).is_empty());
+    assert_eq!(schedule.next_due(), Some(Timestamp::from_millis(4_000)));
+}
+
+#[test]
+fn seeded_jitter_is_reproducible() {
+    let due_times = |seed: u64| {
+        let mut schedule = ScanSchedule::new(1_000)
+            .with_symbols(&["BTCUSDT"])
+            .with_jitter(0.2)
+            .with_rng(SeededRng::new(seed));
+        let mut now = Timestamp::from_millis(0);
+        let mut times = Vec::new();
+        for _ in 0..20 {
+            schedule.take_due(now);
+            now = schedule.next_due().expect("scheduled");
+            times.push(now.as_millis());
+        }
+        times
+    };
+    let times = due_times(42);
+    assert_eq!(due_times(42), times);
+    assert_ne!(due_times(43), times);
+    let gaps: Vec<u64> = std::iter::once(times[0])
+        .chain(times.windows(2).map(|w| w[1] - w[0]))
+        .collect();
+    assert!(
+        gaps.iter().all(|gap| (800..=1_200).contains(gap)),
+        "{gaps:?}"
+    );
+    assert!(gaps.iter().any(|gap| *gap != 1_000), "{gaps:?}");
+}
+
+#[tokio::test]
+async fn invalid_schedules_are_rejected() {
+    let empty = ScanSchedule::new(1_000);
+    assert!(
+        ArbitrageScanner::scan_periodic(empty, &[CexExchange::Binance], None)
+            .await
+            .is_err()
+    );
+    let zero = ScanSchedule::new(1_000).with_symbol("BTCUSDT", 0);
+    assert!(zero.validate().is_err());
+    let jitter = schedule().with_jitter(1.5);
+    assert!(jitter.validate().is_err());
+    assert!(schedule().validate().is_ok());
+}
@@ -1,5 +1,6 @@
-use aeon_market_scanner_rs::CexExchange;
+use aeon_market_scanner_rs::common::{CexPrice, Exchange, Timestamp, TradingStatus};
 use aeon_market_scanner_rs::dex::chains::{ChainId, Token};
+use aeon_market_scanner_rs::{ArbitrageOpportunity, ArbitrageScanner, CexExchange, FeeOverrides};
 
 // Allow dead code warnings since different test files use different items from this module
 #[allow(dead_code)]
@@ -116,3 +117,234 @@ pub fn create_base_usdc() -> Token {
         ChainId::BASE,
     )
 }
+
+/// Builder for CexPrice fixtures; defaults to a BTCUSDT quote of size 1.0 on both sides,
+/// stamped at 1 ms with an unknown trading status
+#[allow(dead_code)]
+pub struct CexPriceBuilder {
+    price: CexPrice,
+}
+
+#[allow(dead_code)]
+impl CexPriceBuilder {
+    pub fn new(exchange: CexExchange, bid: f64, ask: f64) -> Self {
+        Self {
+            price: CexPrice {
+                symbol: "BTCUSDT".to_string(),
+                mid_price: (bid + ask) / 2.0,
+                bid_price: bid,
+                ask_price: ask,
+                bid_qty: 1.0,
+                ask_qty: 1.0,
+                timestamp: Timestamp::from_millis(1),
+                exchange: Exchange::Cex(exchange),
+                trading_status: TradingStatus::Unknown,
+            },
+        }
+    }
+
+    /// Quote `half_spread` either side of `mid`, keeping `mid` exact
+    pub fn around(exchange: CexExchange, mid: f64, half_spread: f64) -> Self {
+        Self::new(exchange, mid - half_spread, mid + half_spread).mid(mid)
+    }
+
+    pub fn symbol(mut self, symbol: &str) -> Self {
+        self.price.symbol = symbol.to_string();
+        self
+    }
+
+    pub fn mid(mut self, mid: f64) -> Self {
+        self.price.mid_price = mid;
+        self
+    }
+
+    /// Sets both bid and ask size
+    pub fn qty(self, qty: f64) -> Self {
+        self.bid_qty(qty).ask_qty(qty)
+    }
+
+    pub fn bid_qty(mut self, qty: f64) -> Self {
+        self.price.bid_qty = qty;
+        self
+    }
+
+    pub fn ask_qty(mut self, qty: f64) -> Self {
+        self.price.ask_qty = qty;
+        self
+    }
+
+    pub fn at(mut self, millis: u64) -> Self {
+        self.price.timestamp = Timestamp::from_millis(millis);
+        self
+    }
+
+    pub fn now(mut self) -> Self {
+        self.price.timestamp = Timestamp::now();
+        self
+    }
+
+    pub fn status(mut self, status: TradingStatus) -> Self {
+        self.price.trading_status = status;
+        self
+    }
+
+    pub fn build(self) -> CexPrice {
+        self.price
+    }
+}
+
+/// BTCUSDT price with the builder defaults
+#[allow(dead_code)]
+pub fn price(exchange: CexExchange, bid: f64, ask: f64) -> CexPrice {
+    CexPriceBuilder::new(exchange, bid, ask).build()
+}
+
+/// `symbol` price with the builder defaults
+#[allow(dead_code)]
+pub fn symbol_price(exchange: CexExchange, symbol: &str, bid: f64, ask: f64) -> CexPrice {
+    CexPriceBuilder::new(exchange, bid, ask)
+        .symbol(symbol)
+        .build()
+}
+
+/// `symbol` price with `qty` on both sides
+#[allow(dead_code)]
+pub fn sized_price(exchange: CexExchange, symbol: &str, bid: f64, ask: f64, qty: f64) -> CexPrice {
+    CexPriceBuilder::new(exchange, bid, ask)
+        .symbol(symbol)
+        .qty(qty)
+        .build()
+}
+
+/// `symbol` price quoted 0.05 either side of `mid`
+#[allow(dead_code)]
+pub fn mid_price(exchange: CexExchange, symbol: &str, mid: f64) -> CexPrice {
+    CexPriceBuilder::around(exchange, mid, 0.05)
+        .symbol(symbol)
+        .build()
+}
+
+/// BTCUSDT price quoted 0.5 either side of `mid`, timestamped `millis`
+#[allow(dead_code)]
+pub fn price_at(exchange: CexExchange, mid: f64, millis: u64) -> CexPrice {
+    CexPriceBuilder::around(exchange, mid, 0.5)
+        .at(millis)
+        .build()
+}
+
+/// BTCUSDT price timestamped `millis`
+#[allow(dead_code)]
+pub fn quote_at(exchange: CexExchange, bid: f64, ask: f64, millis: u64) -> CexPrice {
+    CexPriceBuilder::new(exchange, bid, ask).at(millis).build()
+}
+
+/// Fee overrides with a zero taker fee on each of `exchanges`
+#[allow(dead_code)]
+pub fn zero_taker_fees(exchanges: &[CexExchange]) -> FeeOverrides {
+    exchanges
+        .iter()
+        .fold(FeeOverrides::default(), |fees, exchange| {
+            fees.with_cex_taker_fee(exchange.clone(), 0.0)
+        })
+}
+
+/// Zero taker fees on Binance and OKX, the venue pair most fixtures trade between
+#[allow(dead_code)]
+pub fn binance_okx_fees() -> FeeOverrides {
+    zero_taker_fees(&[CexExchange::Binance, CexExchange::OKX])
+}
+
+/// The opportunity buying on `source` and selling on `destination`; panics if there is none
+#[allow(dead_code)]
+pub fn find_opportunity(
+    prices: &[CexPrice],
+    fees: &FeeOverrides,
+    source: &str,
+    destination: &str,
+) -> ArbitrageOpportunity {
+    ArbitrageScanner::opportunities_from_prices(prices, &[], Some(fees))
+        .into_iter()
+        .find(|o| o.source_exchange == source && o.destination_exchange == destination)
+        .unwrap_or_else(|| panic!("{} -> {} opportunity", source, destination))
+}
+
+/// Polls `check` every 10 ms until it returns a value; `None` once `timeout` has passed
+#[allow(dead_code)]
+pub async fn poll_until<T>(
+    timeout: std::time::Duration,
+    mut check: impl FnMut() -> Option<T>,
+) -> Option<T> {
+    let deadline = tokio::time::Instant::now() + timeout;
+    loop {
+        if let Some(value) = check() {
+            return Some(value);
+        }
+        if tokio::time::Instant::now() >= deadline {
+            return None;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+    }
+}
+
+/// Buy on `buy` at `ask` (bid one below) and sell on `sell` at `bid` (ask one above), with
+/// `qty` on every level
+#[allow(dead_code)]
+pub fn two_venue_prices(
+    symbol: &str,
+    buy: CexExchange,
+    ask: f64,
+    sell: CexExchange,
+    bid: f64,
+    qty: f64,
+) -> [CexPrice; 2] {
+    [
+        sized_price(buy, symbol, ask - 1.0, ask, qty),
+        sized_price(sell, symbol, bid, bid + 1.0, qty),
+    ]
+}
+
+/// The `buy` -> `sell` opportunity of [two_venue_prices] without taker fees
+#[allow(dead_code)]
+pub fn opportunity(
+    symbol: &str,
+    buy: CexExchange,
+    ask: f64,
+    sell: CexExchange,
+    bid: f64,
+    qty: f64,
+) -> ArbitrageOpportunity {
+    let fees = zero_taker_fees(&[buy.clone(), sell.clone()]);
+    let prices = two_venue_prices(symbol, buy.clone(), ask, sell.clone(), bid, qty);
+    find_opportunity(
+        &prices,
+        &fees,
+        &format!("{:?}", buy),
+        &format!("{:?}", sell),
+    )
+}
+
+/// Buy one unit of `symbol` on Binance at `ask`, sell it on OKX at `bid`, without fees
+#[allow(dead_code)]
+pub fn binance_to_okx(symbol: &str, ask: f64, bid: f64) -> ArbitrageOpportunity {
+    opportunity(
+        symbol,
+        CexExchange::Binance,
+        ask,
+        CexExchange::OKX,
+        bid,
+        1.0,
+    )
+}
+
+/// Binance -> OKX on BTCUSDT at 100 -> 110, `qty` deep on both legs, without fees
+#[allow(dead_code)]
+pub fn binance_to_okx_with_depth(qty: f64) -> ArbitrageOpportunity {
+    opportunity(
+        "BTCUSDT",
+        CexExchange::Binance,
+        100.0,
+        CexExchange::OKX,
+        110.0,
+        qty,
+    )
+}
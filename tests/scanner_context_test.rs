@@ -0,0 +1,158 @@
+mod scanner_common;
+
+use aeon_market_scanner_rs::common::{
+    CexPrice, format_symbol_for_exchange, standard_symbol_for_cex_ws_response,
+};
+use aeon_market_scanner_rs::{
+    ArbitrageOpportunity, ArbitrageScanner, CexExchange, FeeOverrides, ScannerContext,
+    SymbolRegistry, canonical_asset,
+};
+use scanner_common::{binance_okx_fees, mid_price};
+use std::sync::{Arc, Mutex};
+
+fn prices() -> Vec<CexPrice> {
+    vec![
+        mid_price(CexExchange::Binance, "BTCUSDT", 100.0),
+        mid_price(CexExchange::OKX, "BTCUSDT", 100.5),
+    ]
+}
+
+type Received = Arc<Mutex<Vec<(String, usize)>>>;
+
+fn recording_sink(received: &Received) -> impl Fn(&str, &[ArbitrageOpportunity]) + use<> {
+    let received = received.clone();
+    move |name, opps| {
+        received
+            .lock()
+            .unwrap()
+            .push((name.to_string(), opps.len()))
+    }
+}
+
+#[test]
+fn contexts_use_their_own_fee_overrides() {
+    // VIP tenant trades fee-free; the retail tenant pays 0.3% per leg
+    let vip = ScannerContext::new("vip").with_fee_overrides(binance_okx_fees());
+    let retail = ScannerContext::new("retail").with_fee_overrides(
+        FeeOverrides::default()
+            .with_cex_taker_fee(CexExchange::Binance, 0.003)
+            .with_cex_taker_fee(CexExchange::OKX, 0.003),
+    );
+
+    assert_eq!(vip.opportunities_from_prices(&prices(), &[]).len(), 1);
+    assert!(retail.opportunities_from_prices(&prices(), &[]).is_empty());
+
+    // The stateless scanner keeps its defaults
+    let default = ArbitrageScanner::opportunities_from_prices(&prices(), &[], None);
+    let vip_opp = &vip.opportunities_from_prices(&prices(), &[])[0];
+    assert!(default.iter().all(|o| o.spread < vip_opp.spread));
+}
+
+#[test]
+fn filters_and_sinks_are_isolated() {
+    let received_a: Received = Arc::default();
+    let received_b: Received = Arc::default();
+
+    let a = ScannerContext::new("a")
+        .with_fee_overrides(binance_okx_fees())
+        .with_filter(|o| o.destination_exchange != "OKX")
+        .with_sink(recording_sink(&received_a));
+    let b = ScannerContext::new("b")
+        .with_fee_overrides(binance_okx_fees())
+        .with_min_spread_percent(0.1)
+        .with_sink(recording_sink(&received_b));
+
+    assert!(a.opportunities_from_prices(&prices(), &[]).is_empty());
+    assert_eq!(b.opportunities_from_prices(&prices(), &[]).len(), 1);
+    assert_eq!(b.opportunities_from_prices(&prices(), &[]).len(), 1);
+
+    assert_eq!(*received_a.lock().unwrap(), vec![("a".to_string(), 0)]);
+    assert_eq!(
+        *received_b.lock().unwrap(),
+        vec![("b".to_string(), 1), ("b".to_string(), 1)]
+    );
+
+    // Clones share sinks but adding a filter to one does not change the other
+    let strict = b.clone().with_min_spread_percent(5.0);
+    assert!(strict.opportunities_from_prices(&prices(), &[]).is_empty());
+    assert_eq!(b.opportunities_from_prices(&prices(), &[]).len(), 1);
+}
+
+#[test]
+fn contexts_run_concurrently() {
+    let handles: Vec<_> = (0..8)
+        .map(|i| {
+            let received: Received = Arc::default();
+            let context = ScannerContext::new(format!("tenant-{i}"))
+                .with_fee_overrides(binance_okx_fees())
+                .with_min_spread_percent(if i % 2 == 0 { 0.0 } else { 10.0 })
+                .with_sink(recording_sink(&received));
+            std::thread::spawn(move || {
+                for _ in 0..100 {
+                    context.opportunities_from_prices(&prices(), &[]);
+                }
+                received.lock().unwrap().clone()
+            })
+        })
+        .collect();
+
+    for (i, handle) in handles.into_iter().enumerate() {
+        let received = handle.join().unwrap();
+        assert_eq!(received.len(), 100);
+        let expected = if i % 2 == 0 { 1 } else { 0 };
+        assert!(
+            received
+                .iter()
+                .all(|(name, n)| *name == format!("tenant-{i}") && *n == expected)
+        );
+    }
+}
+
+#[test]
+fn contexts_resolve_their_own_asset_aliases() {
+    // One venue still lists the renamed ticker
+    let prices = [
+        mid_price(CexExchange::Binance, "NEWTKNUSDT", 100.0),
+        mid_price(CexExchange::OKX, "OLDTKNUSDT", 100.5),
+    ];
+    let renamed = ScannerContext::new("renamed")
+        .with_fee_overrides(binance_okx_fees())
+        .with_symbol_registry(SymbolRegistry::new().with_asset_alias("oldtkn", "newtkn"));
+    let other = ScannerContext::new("other").with_fee_overrides(binance_okx_fees());
+
+    assert_eq!(renamed.opportunities_from_prices(&prices, &[]).len(), 1);
+    assert!(other.opportunities_from_prices(&prices, &[]).is_empty());
+    assert_eq!(canonical_asset("OLDTKN"), "OLDTKN");
+}
+
+#[tokio::test]
+async fn symbol_registries_apply_within_their_scope() {
+    let registry = SymbolRegistry::new()
+        .with_symbol_override(&CexExchange::Binance, "TKNAUSDT", "TKNAFDUSD")
+        .with_symbol_formatter(&CexExchange::OKX, |symbol| Some(format!("{symbol}-SPOT")))
+        .with_asset_alias("TKNB", "TKNA");
+
+    registry.scope_sync(|| {
+        assert_eq!(
+            format_symbol_for_exchange("TKNA-USDT", &CexExchange::Binance).unwrap(),
+            "TKNAFDUSD"
+        );
+        assert_eq!(
+            standard_symbol_for_cex_ws_response("TKNAFDUSD", &CexExchange::Binance),
+            "TKNAUSDT"
+        );
+        assert_eq!(
+            format_symbol_for_exchange("TKNAUSDT", &CexExchange::OKX).unwrap(),
+            "TKNAUSDT-SPOT"
+        );
+    });
+    let canonical = registry.scope(async { canonical_asset("tknb") }).await;
+    assert_eq!(canonical, "TKNA");
+
+    // Outside the scope the process-wide formatting applies
+    assert_eq!(
+        format_symbol_for_exchange("TKNAUSDT", &CexExchange::Binance).unwrap(),
+        "TKNAUSDT"
+    );
+    assert_eq!(canonical_asset("TKNB"), "TKNB");
+}
@@ -0,0 +1,116 @@
+use aeon_market_scanner_rs::{
+    ArbitrageScanner, CexExchange, LiveScanOptions, ScannerEvent, ScannerHandle, ScannerRunState,
+};
+use std::time::Duration;
+use tokio::sync::mpsc;
+
+#[test]
+fn handle_transitions() {
+    let handle = ScannerHandle::new();
+    assert_eq!(handle.state(), ScannerRunState::Running);
+
+    let clone = handle.clone();
+    clone.pause();
+    assert!(handle.is_paused());
+    handle.resume();
+    assert_eq!(clone.state(), ScannerRunState::Running);
+
+    handle.stop();
+    assert!(clone.is_stopped());
+    // Stopping is final
+    clone.resume();
+    clone.pause();
+    assert_eq!(handle.state(), ScannerRunState::Stopped);
+}
+
+#[tokio::test]
+async fn stopped_resolves_on_stop() {
+    let handle = ScannerHandle::new();
+    let waiter = tokio::spawn({
+        let handle = handle.clone();
+        async move { handle.stopped().await }
+    });
+    handle.pause();
+    handle.stop();
+    tokio::time::timeout(Duration::from_secs(1), waiter)
+        .await
+        .expect("stopped() should resolve")
+        .unwrap();
+}
+
+async fn next_state(rx: &mut mpsc::Receiver<ScannerEvent>) -> Option<ScannerRunState> {
+    loop {
+        match rx.recv().await? {
+            ScannerEvent::StateChanged { state, .. } => return Some(state),
+            _ => continue,
+        }
+    }
+}
+
+#[tokio::test]
+async fn live_scanner_reports_state_and_ends_on_stop() {
+    let handle = ScannerHandle::new();
+    let options = LiveScanOptions::default()
+        .with_reconnect_delay_ms(50)
+        .with_handle(handle.clone());
+    let mut rx = ArbitrageScanner::scan_live(&["BTCUSDT"], &[CexExchange::Binance], options)
+        .await
+        .unwrap();
+
+    let run = async {
+        handle.pause();
+        assert_eq!(next_state(&mut rx).await, Some(ScannerRunState::Paused));
+        handle.resume();
+        assert_eq!(next_state(&mut rx).await, Some(ScannerRunState::Running));
+        handle.stop();
+        assert_eq!(next_state(&mut rx).await, Some(ScannerRunState::Stopped));
+        assert!(rx.recv().await.is_none());
+    };
+    tokio::time::timeout(Duration::from_secs(10), run)
+        .await
+        .expect("scanner should follow its handle");
+}
+
+#[test]
+fn fresh_handle_has_no_latest_state() {
+    let handle = ScannerHandle::new();
+    assert!(
+        handle
+            .latest_price(&CexExchange::Binance, "BTCUSDT")
+            .is_none()
+    );
+    assert!(handle.latest_opportunities("BTCUSDT").is_empty());
+}
+
+#[tokio::test]
+async fn handle_answers_latest_state_queries() {
+    let handle = ScannerHandle::new();
+    let venues = [CexExchange::Binance, CexExchange::OKX];
+    let options = LiveScanOptions::default().with_handle(handle.clone());
+    let mut rx = ArbitrageScanner::scan_live(&["BTCUSDT"], &venues, options)
+        .await
+        .unwrap();
+
+    let first_snapshot = async {
+        loop {
+            match rx.recv().await? {
+                ScannerEvent::Opportunities(opportunities) => return Some(opportunities),
+                _ => continue,
+            }
+        }
+    };
+    // Needs network access; nothing to check without it
+    let Ok(Some(snapshot)) = tokio::time::timeout(Duration::from_secs(15), first_snapshot).await
+    else {
+        handle.stop();
+        return;
+    };
+    assert!(
+        venues
+            .iter()
+            .any(|venue| handle.latest_price(venue, "BTC/USDT").is_some())
+    );
+    assert!(snapshot.len() <= 2);
+    assert!(handle.latest_opportunities("ETHUSDT").is_empty());
+    handle.stop();
+}
@@ -0,0 +1,127 @@
+use aeon_market_scanner_rs::{
+    ClockOffset, ExchangeTrait, MarketScannerError, Timestamp, measure_clock_offset,
+};
+use async_trait::async_trait;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Clock `ahead_ms` ahead of the local one; the first `failures` requests fail.
+struct SkewedVenue {
+    client: reqwest::Client,
+    ahead_ms: u64,
+    failures: usize,
+    requests: AtomicUsize,
+}
+
+impl SkewedVenue {
+    fn new(ahead_ms: u64, failures: usize) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            ahead_ms,
+            failures,
+            requests: AtomicUsize::new(0),
+        }
+    }
+}
+
+#[async_trait]
+impl ExchangeTrait for SkewedVenue {
+    fn api_base(&self) -> &str {
+        "http://localhost"
+    }
+
+    fn client(&self) -> &reqwest::Client {
+        &self.client
+    }
+
+    fn exchange_name(&self) -> &str {
+        "SkewedVenue"
+    }
+
+    async fn get_server_time(&self) -> Result<Timestamp, MarketScannerError> {
+        if self.requests.fetch_add(1, Ordering::SeqCst) < self.failures {
+            return Err(MarketScannerError::ApiError("timeout".to_string()));
+        }
+        Ok(Timestamp::from_millis(
+            Timestamp::now().as_millis() + self.ahead_ms,
+        ))
+    }
+
+    async fn health_check(&self) -> Result<(), MarketScannerError> {
+        Ok(())
+    }
+}
+
+#[test]
+fn offset_is_measured_from_the_round_trip_midpoint() {
+    let offset = ClockOffset::from_sample(
+        Timestamp::from_millis(1_000),
+        Timestamp::from_millis(1_550),
+        Timestamp::from_millis(1_100),
+    );
+    assert_eq!(offset.offset_ms, 500);
+    assert_eq!(offset.round_trip_ms, 100);
+    assert_eq!(offset.measured_at, Timestamp::from_millis(1_100));
+
+    assert_eq!(
+        offset.to_local(Timestamp::from_millis(10_500)),
+        Timestamp::from_millis(10_000)
+    );
+    assert_eq!(
+        offset.to_venue(Timestamp::from_millis(10_000)),
+        Timestamp::from_millis(10_500)
+    );
+
+    let behind = ClockOffset::from_sample(
+        Timestamp::from_millis(1_000),
+        Timestamp::from_millis(800),
+        Timestamp::from_millis(1_000),
+    );
+    assert_eq!(behind.offset_ms, -200);
+    assert_eq!(
+        behind.to_local(Timestamp::from_millis(800)),
+        Timestamp::from_millis(1_000)
+    );
+}
+
+#[tokio::test]
+async fn clock_offset_of_a_skewed_venue() {
+    let venue = SkewedVenue::new(5_000, 2);
+    let offset = measure_clock_offset(&venue, 5).await.unwrap();
+    assert_eq!(venue.requests.load(Ordering::SeqCst), 5);
+    let error = offset.offset_ms - 5_000;
+    assert!(
+        error.unsigned_abs() <= offset.round_trip_ms / 2 + 1,
+        "{offset:?}"
+    );
+
+    let failing = SkewedVenue::new(0, 3);
+    assert!(measure_clock_offset(&failing, 3).await.is_err());
+}
+
+#[tokio::test]
+async fn venues_without_server_time_report_an_error() {
+    struct NoClock(reqwest::Client);
+
+    #[async_trait]
+    impl ExchangeTrait for NoClock {
+        fn api_base(&self) -> &str {
+            "http://localhost"
+        }
+
+        fn client(&self) -> &reqwest::Client {
+            &self.0
+        }
+
+        fn exchange_name(&self) -> &str {
+            "NoClock"
+        }
+
+        async fn health_check(&self) -> Result<(), MarketScannerError> {
+            Ok(())
+        }
+    }
+
+    let venue = NoClock(reqwest::Client::new());
+    assert!(venue.get_server_time().await.is_err());
+    assert!(measure_clock_offset(&venue, 0).await.is_err());
+}
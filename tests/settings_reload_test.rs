@@ -0,0 +1,137 @@
+mod scanner_common;
+
+use aeon_market_scanner_rs::common::CexPrice;
+use aeon_market_scanner_rs::scanner::SettingsReload;
+use aeon_market_scanner_rs::{CexExchange, ScannerContext, ScannerSettings, SettingsHandle};
+use scanner_common::mid_price;
+use std::path::PathBuf;
+use std::time::Duration;
+
+fn prices() -> Vec<CexPrice> {
+    vec![
+        mid_price(CexExchange::Binance, "BTCUSDT", 100.0),
+        mid_price(CexExchange::OKX, "BTCUSDT", 100.5),
+    ]
+}
+
+const ZERO_FEES: &str = r#"{
+    "min_spread_percent": 0.3,
+    "cex_taker_fees": {"Binance": 0.0, "OKX": 0.0}
+}"#;
+
+fn settings_file(name: &str, json: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!(
+        "aeon_settings_{}_{}.json",
+        name,
+        std::process::id()
+    ));
+    std::fs::write(&path, json).unwrap();
+    path
+}
+
+#[test]
+fn settings_parse_and_validate() {
+    let settings = ScannerSettings::from_json(ZERO_FEES).unwrap();
+    assert_eq!(settings.min_spread_percent, Some(0.3));
+    let fees = settings.fee_overrides().expect("fees");
+    assert_eq!(fees.cex_taker[&CexExchange::OKX], 0.0);
+    assert!(ScannerSettings::default().fee_overrides().is_none());
+
+    assert!(ScannerSettings::from_json(r#"{"cex_taker_fees": {"Binance": 1.5}}"#).is_err());
+    assert!(ScannerSettings::from_json(r#"{"min_executable_quantity": -1}"#).is_err());
+    assert!(ScannerSettings::from_json("{not json").is_err());
+}
+
+#[test]
+fn reloads_retune_a_context_without_rebuilding_it() {
+    let handle = SettingsHandle::new(ScannerSettings::from_json(ZERO_FEES).unwrap());
+    let context = ScannerContext::new("desk").with_settings(handle.clone());
+    let symbols = |context: &ScannerContext| -> Vec<String> {
+        context
+            .opportunities_from_prices(&prices(), &[])
+            .into_iter()
+            .map(|o| {
+                format!(
+                    "{} {}->{}",
+                    o.symbol, o.source_exchange, o.destination_exchange
+                )
+            })
+            .collect()
+    };
+    assert_eq!(symbols(&context), ["BTCUSDT Binance->OKX"]);
+
+    // Symbol filter, then a venue exclusion
+    let version = handle
+        .replace(ScannerSettings {
+            symbols: vec!["ethusdt".to_string()],
+            ..ScannerSettings::from_json(ZERO_FEES).unwrap()
+        })
+        .unwrap();
+    assert_eq!(version, 1);
+    assert!(symbols(&context).is_empty());
+    handle
+        .replace(ScannerSettings {
+            symbols: vec!["btcusdt".to_string()],
+            excluded_exchanges: vec!["okx".to_string()],
+            ..ScannerSettings::from_json(ZERO_FEES).unwrap()
+        })
+        .unwrap();
+    assert!(symbols(&context).is_empty());
+    handle
+        .replace(ScannerSettings::from_json(ZERO_FEES).unwrap())
+        .unwrap();
+    assert_eq!(symbols(&context), ["BTCUSDT Binance->OKX"]);
+
+    // Fees high enough to eat the spread
+    handle
+        .replace(
+            ScannerSettings::from_json(
+                r#"{"min_spread_percent": 0.3, "cex_taker_fees": {"Binance": 0.002, "OKX": 0.002}}"#,
+            )
+            .unwrap(),
+        )
+        .unwrap();
+    assert!(symbols(&context).is_empty());
+    assert_eq!(handle.version(), 4);
+}
+
+#[test]
+fn invalid_files_keep_the_previous_settings() {
+    let path = settings_file("invalid", r#"{"min_spread_percent": "high"}"#);
+    let handle = SettingsHandle::new(ScannerSettings::from_json(ZERO_FEES).unwrap());
+    assert!(handle.reload_from(&path).is_err());
+    assert_eq!(handle.version(), 0);
+    assert_eq!(handle.current().min_spread_percent, Some(0.3));
+    assert!(SettingsHandle::load(path.with_extension("missing")).is_err());
+    std::fs::remove_file(path).ok();
+}
+
+#[tokio::test]
+async fn watched_files_are_reloaded_on_change() {
+    let path = settings_file("watch", ZERO_FEES);
+    let handle = SettingsHandle::load(&path).unwrap();
+    let mut reloads = handle.watch_file(&path, Duration::from_millis(20));
+
+    // Modification times can have a coarse resolution
+    tokio::time::sleep(Duration::from_millis(1_100)).await;
+    std::fs::write(&path, r#"{"min_spread_percent": 1.0}"#).unwrap();
+    let reload: SettingsReload = tokio::time::timeout(Duration::from_secs(5), reloads.recv())
+        .await
+        .expect("reload reported")
+        .unwrap();
+    assert_eq!(reload.error, None);
+    assert_eq!(reload.version, 1);
+    assert_eq!(handle.current().min_spread_percent, Some(1.0));
+    assert!(handle.current().fee_overrides().is_none());
+
+    tokio::time::sleep(Duration::from_millis(1_100)).await;
+    std::fs::write(&path, "{broken").unwrap();
+    let reload = tokio::time::timeout(Duration::from_secs(5), reloads.recv())
+        .await
+        .expect("reload reported")
+        .unwrap();
+    assert!(reload.error.is_some());
+    assert_eq!(reload.version, 1);
+    assert_eq!(handle.current().min_spread_percent, Some(1.0));
+    std::fs::remove_file(path).ok();
+}
@@ -0,0 +1,157 @@
+mod scanner_common;
+
+use aeon_market_scanner_rs::common::{DexPrice, DexRouteSummary, Timestamp};
+use aeon_market_scanner_rs::{
+    ArbitrageScanner, CexExchange, CostLayer, DexAggregator, Exchange, FeeOverrides,
+    ScannerContext, SpreadCostModel, TransferCostModel,
+};
+use scanner_common::{find_opportunity, sized_price, two_venue_prices};
+
+fn fees() -> FeeOverrides {
+    FeeOverrides::default()
+        .with_cex_taker_fee(CexExchange::Binance, 0.001)
+        .with_cex_taker_fee(CexExchange::OKX, 0.001)
+        .with_dex_taker_fee(DexAggregator::KyberSwap, 0.0)
+}
+
+fn close(a: f64, b: f64) -> bool {
+    (a - b).abs() < 1e-9
+}
+
+#[test]
+fn scans_split_the_spread_into_raw_gap_and_fees() {
+    let opp = find_opportunity(
+        &two_venue_prices(
+            "ETHUSDT",
+            CexExchange::Binance,
+            2_000.0,
+            CexExchange::OKX,
+            2_010.0,
+            2.0,
+        ),
+        &fees(),
+        "Binance",
+        "OKX",
+    );
+    let breakdown = opp.spread_breakdown.expect("set by the scan");
+    assert!(close(breakdown.raw_spread, 10.0));
+    assert!(close(breakdown.buy_fee, 2.0));
+    assert!(close(breakdown.sell_fee, 2.01));
+    assert!(close(breakdown.after_fees(), opp.spread));
+    assert_eq!(breakdown.slippage, 0.0);
+    assert_eq!(breakdown.transfer_cost, 0.0);
+    assert!(close(breakdown.net_spread, opp.spread));
+    assert_eq!(breakdown.first_losing_layer(), None);
+
+    let json = serde_json::to_value(&opp).unwrap();
+    assert!(close(
+        json["spread_breakdown"]["raw_spread"].as_f64().unwrap(),
+        10.0
+    ));
+}
+
+#[test]
+fn cost_model_adds_slippage_and_transfers() {
+    let opp = find_opportunity(
+        &two_venue_prices(
+            "ETHUSDT",
+            CexExchange::Binance,
+            2_000.0,
+            CexExchange::OKX,
+            2_010.0,
+            2.0,
+        ),
+        &fees(),
+        "Binance",
+        "OKX",
+    );
+    let model = SpreadCostModel::new()
+        .with_cex_slippage_bps(10.0)
+        .with_transfer_costs(TransferCostModel::default());
+    let breakdown = model.breakdown(&opp);
+    // 10 bps of the 2000 ask and of the 2010 bid
+    assert!(close(breakdown.slippage, 4.01));
+    assert!(close(breakdown.after_slippage(), 1.98));
+    // 0.0001 ETH (Arbitrum) at the 2002 effective ask + 0.5 USDT (BEP20), over 2 ETH
+    assert!(close(breakdown.transfer_cost, 0.3501));
+    assert!(close(breakdown.net_spread, 1.6299));
+    assert_eq!(breakdown.first_losing_layer(), None);
+
+    let thin = model.with_venue_slippage_bps(Exchange::Cex(CexExchange::OKX), 30.0);
+    let breakdown = thin.breakdown(&opp);
+    assert!(close(breakdown.slippage, 8.03));
+    assert!(breakdown.after_fees() > 0.0);
+    assert_eq!(breakdown.first_losing_layer(), Some(CostLayer::Slippage));
+    assert!(breakdown.after(CostLayer::Transfer) < 0.0);
+}
+
+#[test]
+fn gas_of_dex_legs_counts_as_transfer_cost() {
+    let route = DexRouteSummary {
+        token_in: "USDT".to_string(),
+        token_out: "ETH".to_string(),
+        amount_in: 3_990.0,
+        amount_out: 2.0,
+        amount_in_wei: "0".to_string(),
+        amount_out_wei: "0".to_string(),
+        gas: None,
+        gas_price: None,
+        gas_usd: Some(12.0),
+    };
+    let dex = DexPrice {
+        symbol: "ETHUSDT".to_string(),
+        mid_price: 1_994.0,
+        bid_price: 1_993.0,
+        ask_price: 1_995.0,
+        bid_qty: 2.0,
+        ask_qty: 2.0,
+        timestamp: Timestamp::from_millis(1),
+        exchange: Exchange::Dex(DexAggregator::KyberSwap),
+        bid_route_summary: None,
+        ask_route_summary: Some(route),
+        bid_route_data: None,
+        ask_route_data: None,
+        transfer_tax_bps: None,
+    };
+    let opp = ArbitrageScanner::opportunities_from_prices(
+        &[sized_price(
+            CexExchange::OKX,
+            "ETHUSDT",
+            2_000.0,
+            2_001.0,
+            2.0,
+        )],
+        &[dex],
+        Some(&fees()),
+    )
+    .into_iter()
+    .find(|o| o.source_exchange == "KyberSwap")
+    .expect("KyberSwap -> OKX opportunity");
+
+    let breakdown = SpreadCostModel::new().breakdown(&opp);
+    assert!(close(breakdown.transfer_cost, 6.0));
+    assert_eq!(breakdown.first_losing_layer(), Some(CostLayer::Transfer));
+    assert_eq!(
+        SpreadCostModel::new()
+            .without_gas()
+            .breakdown(&opp)
+            .transfer_cost,
+        0.0
+    );
+}
+
+#[test]
+fn context_applies_the_cost_model_before_filters() {
+    let prices = [
+        sized_price(CexExchange::Binance, "ETHUSDT", 1_999.0, 2_000.0, 2.0),
+        sized_price(CexExchange::OKX, "ETHUSDT", 2_010.0, 2_011.0, 2.0),
+    ];
+    let context = ScannerContext::new("net")
+        .with_fee_overrides(fees())
+        .with_spread_cost_model(SpreadCostModel::new().with_cex_slippage_bps(20.0))
+        .with_filter(|o| {
+            o.spread_breakdown
+                .is_some_and(|b| b.first_losing_layer().is_none())
+        });
+    assert!(context.opportunities_from_prices(&prices, &[]).is_empty());
+}
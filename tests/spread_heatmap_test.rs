@@ -0,0 +1,120 @@
+mod scanner_common;
+
+use aeon_market_scanner_rs::common::Timestamp;
+use aeon_market_scanner_rs::{
+    CexExchange, Exchange, FeeOverrides, LivePriceCache, LiveScanOptions, SpreadHeatmap,
+};
+use scanner_common::CexPriceBuilder;
+
+fn no_fees() -> FeeOverrides {
+    FeeOverrides::default()
+        .with_cex_taker_fee(CexExchange::Binance, 0.0)
+        .with_cex_taker_fee(CexExchange::Bybit, 0.0)
+        .with_cex_taker_fee(CexExchange::OKX, 0.0)
+}
+
+#[test]
+fn matrix_covers_every_venue_pair() {
+    let prices = [
+        CexPriceBuilder::new(CexExchange::OKX, 103.0, 104.0)
+            .symbol("BTCUSDT")
+            .at(3)
+            .build(),
+        CexPriceBuilder::new(CexExchange::Bybit, 99.0, 100.0)
+            .symbol("BTCUSDT")
+            .at(2)
+            .build(),
+        CexPriceBuilder::new(CexExchange::Binance, 100.0, 101.0)
+            .symbol("BTCUSDT")
+            .at(1)
+            .build(),
+        // Older quote of the same venue and another market are ignored
+        CexPriceBuilder::new(CexExchange::Bybit, 1.0, 2.0)
+            .symbol("BTCUSDT")
+            .at(1)
+            .build(),
+        CexPriceBuilder::new(CexExchange::OKX, 3000.0, 3001.0)
+            .symbol("ETHUSDT")
+            .at(3)
+            .build(),
+    ];
+    let heatmap = SpreadHeatmap::from_prices("BTCUSDT", &prices, Some(&no_fees()));
+
+    let binance = Exchange::Cex(CexExchange::Binance);
+    let bybit = Exchange::Cex(CexExchange::Bybit);
+    let okx = Exchange::Cex(CexExchange::OKX);
+    assert_eq!(
+        heatmap.venues,
+        [binance.clone(), bybit.clone(), okx.clone()]
+    );
+    assert_eq!(heatmap.spreads.len(), 3);
+    assert!(heatmap.spreads.iter().all(|row| row.len() == 3));
+    assert!((0..3).all(|i| heatmap.spreads[i][i].is_none()));
+
+    // Buy Bybit at 100, sell OKX at 103
+    assert!((heatmap.spread(&bybit, &okx).unwrap() - 3.0).abs() < 1e-9);
+    // The reverse direction is negative but still reported
+    assert!((heatmap.spread(&okx, &bybit).unwrap() - (99.0 - 104.0) / 104.0 * 100.0).abs() < 1e-9);
+    assert_eq!(heatmap.best().map(|(b, s, _)| (b, s)), Some((&bybit, &okx)));
+    assert_eq!(heatmap.oldest_price, Timestamp::from_millis(1));
+
+    let json = serde_json::to_value(&heatmap).unwrap();
+    assert_eq!(json["spreads"][0][0], serde_json::Value::Null);
+    assert_eq!(json["venues"].as_array().unwrap().len(), 3);
+    let parsed: SpreadHeatmap = serde_json::from_value(json).unwrap();
+    assert_eq!(parsed, heatmap);
+}
+
+#[test]
+fn fees_are_applied_to_both_legs() {
+    let fees = FeeOverrides::default()
+        .with_cex_taker_fee(CexExchange::Bybit, 0.001)
+        .with_cex_taker_fee(CexExchange::OKX, 0.002);
+    let prices = [
+        CexPriceBuilder::new(CexExchange::Bybit, 99.0, 100.0)
+            .symbol("BTCUSDT")
+            .at(1)
+            .build(),
+        CexPriceBuilder::new(CexExchange::OKX, 103.0, 104.0)
+            .symbol("BTCUSDT")
+            .at(1)
+            .build(),
+    ];
+    let heatmap = SpreadHeatmap::from_prices("BTCUSDT", &prices, Some(&fees));
+    let expected = (103.0 * 0.998 - 100.0 * 1.001) / (100.0 * 1.001) * 100.0;
+    let spread = heatmap
+        .spread(
+            &Exchange::Cex(CexExchange::Bybit),
+            &Exchange::Cex(CexExchange::OKX),
+        )
+        .unwrap();
+    assert!((spread - expected).abs() < 1e-9);
+}
+
+#[test]
+fn live_cache_is_shared_between_clones() {
+    let cache = LivePriceCache::new();
+    let options = LiveScanOptions::default().with_price_cache(cache.clone());
+    let scanner_side = options.price_cache.clone().unwrap();
+
+    scanner_side.insert(
+        CexPriceBuilder::new(CexExchange::Bybit, 99.0, 100.0)
+            .symbol("BTCUSDT")
+            .at(1)
+            .build(),
+    );
+    scanner_side.insert(
+        CexPriceBuilder::new(CexExchange::OKX, 103.0, 104.0)
+            .symbol("BTCUSDT")
+            .at(1)
+            .build(),
+    );
+    assert_eq!(cache.prices("BTCUSDT").len(), 2);
+    let heatmap = cache.spread_heatmap("BTCUSDT", Some(&no_fees()));
+    assert_eq!(heatmap.venues.len(), 2);
+
+    scanner_side.remove_venue(&Exchange::Cex(CexExchange::OKX));
+    let heatmap = cache.spread_heatmap("BTCUSDT", None);
+    assert_eq!(heatmap.venues.len(), 1);
+    assert!(heatmap.best().is_none());
+}
@@ -0,0 +1,71 @@
+mod scanner_common;
+
+use aeon_market_scanner_rs::common::{merge_labeled_price_streams, merge_price_streams};
+use aeon_market_scanner_rs::{CexExchange, Exchange};
+use scanner_common::symbol_price;
+use tokio::sync::mpsc;
+
+#[tokio::test]
+async fn merge_price_streams_forwards_all_sources_until_closed() {
+    let (tx_a, rx_a) = mpsc::channel(8);
+    let (tx_b, rx_b) = mpsc::channel(8);
+    let mut merged = merge_price_streams(vec![rx_a, rx_b]);
+
+    tx_a.send(symbol_price(
+        CexExchange::Binance,
+        "BTCUSDT",
+        100.0,
+        100.0 + 1.0,
+    ))
+    .await
+    .unwrap();
+    tx_b.send(symbol_price(
+        CexExchange::OKX,
+        "BTCUSDT",
+        101.0,
+        101.0 + 1.0,
+    ))
+    .await
+    .unwrap();
+    drop(tx_a);
+    drop(tx_b);
+
+    let mut received = Vec::new();
+    while let Some(p) = merged.recv().await {
+        received.push(p.exchange);
+    }
+    assert_eq!(received.len(), 2);
+    assert!(received.contains(&Exchange::Cex(CexExchange::Binance)));
+    assert!(received.contains(&Exchange::Cex(CexExchange::OKX)));
+}
+
+#[tokio::test]
+async fn merge_labeled_price_streams_coalesces_to_latest() {
+    let (tx, rx) = mpsc::channel(8);
+    // Fill the source before the merge starts so all updates are buffered together
+    for bid in [100.0, 101.0, 102.0] {
+        tx.send(symbol_price(
+            CexExchange::Binance,
+            "BTCUSDT",
+            bid,
+            bid + 1.0,
+        ))
+        .await
+        .unwrap();
+    }
+    drop(tx);
+
+    let mut merged = merge_labeled_price_streams(vec![("binance-spot", rx)], true);
+    let mut received = Vec::new();
+    while let Some(update) = merged.recv().await {
+        assert_eq!(update.source, "binance-spot");
+        received.push(update.price.bid_price);
+    }
+    assert!(!received.is_empty());
+    assert_eq!(
+        *received.last().unwrap(),
+        102.0,
+        "Latest price must survive"
+    );
+    assert!(received.len() <= 3);
+}
@@ -0,0 +1,118 @@
+mod scanner_common;
+
+use aeon_market_scanner_rs::common::CexPrice;
+use aeon_market_scanner_rs::scanner::sample_stream;
+use aeon_market_scanner_rs::{BackpressurePolicy, CexExchange, StreamTee};
+use scanner_common::CexPriceBuilder;
+use std::time::Duration;
+use tokio::sync::mpsc;
+
+async fn drain(rx: &mut mpsc::Receiver<CexPrice>) -> Vec<(String, f64)> {
+    let mut items = Vec::new();
+    while let Ok(Some(p)) = tokio::time::timeout(Duration::from_secs(2), rx.recv()).await {
+        items.push((format!("{:?}:{}", p.exchange, p.symbol), p.bid_price));
+    }
+    items
+}
+
+#[tokio::test]
+async fn one_update_per_symbol_per_window_latest_wins() {
+    let (tx, rx) = mpsc::channel(64);
+    let mut sampled = sample_stream(rx, Duration::from_millis(300));
+
+    for bid in [100.0, 101.0, 102.0] {
+        tx.send(
+            CexPriceBuilder::new(CexExchange::Binance, bid, bid + 1.0)
+                .symbol("BTCUSDT")
+                .now()
+                .build(),
+        )
+        .await
+        .unwrap();
+    }
+    tx.send(
+        CexPriceBuilder::new(CexExchange::OKX, 200.0, 200.0 + 1.0)
+            .symbol("BTCUSDT")
+            .now()
+            .build(),
+    )
+    .await
+    .unwrap();
+    // The first update of each key goes out at once
+    let first = tokio::time::timeout(Duration::from_millis(100), sampled.recv())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(first.bid_price, 100.0);
+    let second = tokio::time::timeout(Duration::from_millis(100), sampled.recv())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(second.bid_price, 200.0);
+    // 101 was replaced by 102 and waits for the end of the window
+    assert!(
+        tokio::time::timeout(Duration::from_millis(100), sampled.recv())
+            .await
+            .is_err()
+    );
+    let trailing = tokio::time::timeout(Duration::from_millis(500), sampled.recv())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(trailing.bid_price, 102.0);
+
+    // Pending updates are flushed when the source ends
+    tx.send(
+        CexPriceBuilder::new(CexExchange::Binance, 103.0, 103.0 + 1.0)
+            .symbol("BTCUSDT")
+            .now()
+            .build(),
+    )
+    .await
+    .unwrap();
+    tx.send(
+        CexPriceBuilder::new(CexExchange::Binance, 104.0, 104.0 + 1.0)
+            .symbol("BTCUSDT")
+            .now()
+            .build(),
+    )
+    .await
+    .unwrap();
+    drop(tx);
+    let rest: Vec<f64> = drain(&mut sampled)
+        .await
+        .into_iter()
+        .map(|(_, bid)| bid)
+        .collect();
+    assert_eq!(rest, vec![104.0]);
+}
+
+#[tokio::test]
+async fn sampled_subscribers_do_not_slow_full_rate_ones() {
+    let (tx, rx) = mpsc::channel(64);
+    let tee = StreamTee::spawn(rx);
+    let mut execution = tee.subscribe("execution", 64, BackpressurePolicy::Block);
+    let mut ui = tee.subscribe_sampled(
+        "ui",
+        64,
+        BackpressurePolicy::DropNewest,
+        Duration::from_secs(10),
+    );
+    for bid in 0..20 {
+        tx.send(
+            CexPriceBuilder::new(CexExchange::Binance, bid as f64, bid as f64 + 1.0)
+                .symbol("ETHUSDT")
+                .now()
+                .build(),
+        )
+        .await
+        .unwrap();
+    }
+    drop(tx);
+
+    assert_eq!(drain(&mut execution).await.len(), 20);
+    // First and last update only
+    let ui_items: Vec<f64> = drain(&mut ui).await.into_iter().map(|(_, b)| b).collect();
+    assert_eq!(ui_items, vec![0.0, 19.0]);
+    assert!(tee.stats().iter().all(|s| s.delivered == 20));
+}
@@ -0,0 +1,79 @@
+use aeon_market_scanner_rs::{BackpressurePolicy, StreamTee};
+use std::time::Duration;
+use tokio::sync::mpsc;
+
+async fn drain(rx: &mut mpsc::Receiver<u32>) -> Vec<u32> {
+    let mut items = Vec::new();
+    while let Ok(Some(item)) = tokio::time::timeout(Duration::from_secs(2), rx.recv()).await {
+        items.push(item);
+    }
+    items
+}
+
+#[tokio::test]
+async fn every_subscriber_gets_every_item() {
+    let (tx, rx) = mpsc::channel(8);
+    let tee = StreamTee::spawn(rx);
+    let mut alerts = tee.subscribe("alerts", 16, BackpressurePolicy::Block);
+    let mut storage = tee.subscribe("storage", 16, BackpressurePolicy::Block);
+    for item in 0..10 {
+        tx.send(item).await.unwrap();
+    }
+    drop(tx);
+
+    let expected: Vec<u32> = (0..10).collect();
+    assert_eq!(drain(&mut alerts).await, expected);
+    assert_eq!(drain(&mut storage).await, expected);
+    let stats = tee.stats();
+    assert_eq!(stats[0].name, "alerts");
+    assert!(stats.iter().all(|s| s.delivered == 10 && s.dropped == 0));
+    assert_eq!(tee.active_subscribers(), 0);
+}
+
+#[tokio::test]
+async fn slow_subscribers_drop_without_delaying_others() {
+    let (tx, rx) = mpsc::channel(64);
+    let tee = StreamTee::spawn(rx);
+    let mut execution = tee.subscribe("execution", 64, BackpressurePolicy::Block);
+    // Never read until the end: keeps 2 items, drops the rest
+    let mut persistence = tee.subscribe("persistence", 2, BackpressurePolicy::DropNewest);
+    // Never read: disconnected once full
+    let mut laggard = tee.subscribe("laggard", 1, BackpressurePolicy::Disconnect);
+    for item in 0..10 {
+        tx.send(item).await.unwrap();
+    }
+    drop(tx);
+
+    assert_eq!(drain(&mut execution).await, (0..10).collect::<Vec<u32>>());
+    assert_eq!(drain(&mut persistence).await, [0, 1]);
+    assert_eq!(drain(&mut laggard).await, [0]);
+
+    let stats = tee.stats();
+    assert_eq!((stats[1].delivered, stats[1].dropped), (2, 8));
+    assert!(!stats[1].disconnected);
+    assert_eq!(stats[2].delivered, 1);
+    assert!(stats[2].disconnected);
+}
+
+#[tokio::test]
+async fn late_and_departing_subscribers() {
+    let (tx, rx) = mpsc::channel(8);
+    let tee = StreamTee::spawn(rx);
+    let early = tee.subscribe("early", 8, BackpressurePolicy::Block);
+    drop(early);
+    tx.send(1).await.unwrap();
+    // Item 1 finds the early receiver gone
+    while !tee.stats()[0].disconnected {
+        tokio::time::sleep(Duration::from_millis(5)).await;
+    }
+
+    let mut late = tee.subscribe("late", 8, BackpressurePolicy::Block);
+    tx.send(2).await.unwrap();
+    assert_eq!(late.recv().await, Some(2));
+    drop(tx);
+    assert_eq!(late.recv().await, None);
+
+    // After the source ended, subscribers get a closed receiver
+    let mut after = tee.subscribe("after", 8, BackpressurePolicy::Block);
+    assert_eq!(after.recv().await, None);
+}
@@ -0,0 +1,99 @@
+mod scanner_common;
+
+use aeon_market_scanner_rs::dex::KyberSwapBuiltRoute;
+use aeon_market_scanner_rs::dex::execution::{SwapSimulation, simulate_route};
+use aeon_market_scanner_rs::{
+    ArbitrageOpportunity, ArbitrageScanner, CexExchange, OPPORTUNITY_SCHEMA_VERSION,
+};
+use ethers::core::types::{Address, Bytes, U256};
+use ethers::providers::Provider;
+use scanner_common::symbol_price;
+
+const BUILD_RESPONSE: &str = r#"{
+    "amountIn": "1000000000",
+    "amountOut": "400000000000000000",
+    "gas": "250000",
+    "data": "0xe21fd0e9000000000000000000000000000000000000000000000000000000000000002a",
+    "routerAddress": "0x6131B5fae19EA4f9D964eAc0408E4408b66337b5",
+    "transactionValue": "0"
+}"#;
+
+fn router_output(amount_out: U256, gas_used: U256) -> Bytes {
+    let mut out = [0u8; 64];
+    amount_out.to_big_endian(&mut out[..32]);
+    gas_used.to_big_endian(&mut out[32..]);
+    Bytes::from(out.to_vec())
+}
+
+fn simulation(expected: u64, simulated: u64) -> SwapSimulation {
+    SwapSimulation {
+        expected_amount_out: U256::from(expected),
+        simulated_amount_out: U256::from(simulated),
+        gas_used: None,
+    }
+}
+
+#[test]
+fn test_shortfall_and_tolerance() {
+    assert_eq!(simulation(10_000, 10_000).shortfall_bps(), 0.0);
+    assert_eq!(simulation(10_000, 10_500).shortfall_bps(), 0.0);
+    assert!((simulation(10_000, 9_970).shortfall_bps() - 30.0).abs() < 1e-9);
+    assert!(simulation(10_000, 9_970).within_tolerance(30));
+    assert!(!simulation(10_000, 9_970).within_tolerance(29));
+    assert_eq!(simulation(0, 0).shortfall_bps(), 0.0);
+}
+
+#[tokio::test]
+async fn test_simulate_route_decodes_router_output() {
+    let route: KyberSwapBuiltRoute = serde_json::from_str(BUILD_RESPONSE).unwrap();
+    let (provider, mock) = Provider::mocked();
+    let simulated = U256::from(399_000_000_000_000_000u64);
+    mock.push::<Bytes, _>(router_output(simulated, U256::from(180_000u64)))
+        .unwrap();
+
+    let sim = simulate_route(&provider, &route, Address::from_low_u64_be(7))
+        .await
+        .unwrap();
+    assert_eq!(
+        sim.expected_amount_out,
+        U256::from(400_000_000_000_000_000u64)
+    );
+    assert_eq!(sim.simulated_amount_out, simulated);
+    assert_eq!(sim.gas_used, Some(U256::from(180_000u64)));
+    assert!((sim.shortfall_bps() - 25.0).abs() < 1e-6);
+    assert!(sim.within_tolerance(50));
+}
+
+#[tokio::test]
+async fn test_simulate_route_rejects_empty_output() {
+    let route: KyberSwapBuiltRoute = serde_json::from_str(BUILD_RESPONSE).unwrap();
+    let (provider, mock) = Provider::mocked();
+    mock.push::<Bytes, _>(Bytes::new()).unwrap();
+    assert!(
+        simulate_route(&provider, &route, Address::from_low_u64_be(7))
+            .await
+            .is_err()
+    );
+}
+
+#[test]
+fn test_annotate_opportunity() {
+    let prices = [
+        symbol_price(CexExchange::Binance, "ETHUSDT", 99.0, 100.0),
+        symbol_price(CexExchange::OKX, "ETHUSDT", 110.0, 111.0),
+    ];
+    let mut opportunity = ArbitrageScanner::opportunities_from_prices(&prices, &[], None)
+        .into_iter()
+        .next()
+        .expect("opportunity");
+    assert_eq!(opportunity.simulated_amount_out, None);
+
+    simulation(10_000, 9_990).annotate(&mut opportunity);
+    assert_eq!(opportunity.simulated_amount_out.as_deref(), Some("9990"));
+
+    let json = serde_json::to_value(&opportunity).unwrap();
+    assert_eq!(json["simulated_amount_out"], "9990");
+    assert_eq!(json["schema_version"], OPPORTUNITY_SCHEMA_VERSION);
+    let back: ArbitrageOpportunity = serde_json::from_value(json).unwrap();
+    assert_eq!(back.simulated_amount_out.as_deref(), Some("9990"));
+}
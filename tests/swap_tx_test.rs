@@ -0,0 +1,96 @@
+use aeon_market_scanner_rs::common::{AmountSide, DexPrice, Timestamp};
+use aeon_market_scanner_rs::dex::KyberSwapBuiltRoute;
+use aeon_market_scanner_rs::dex::chains::ChainId;
+use aeon_market_scanner_rs::dex::execution::{GasFees, build_swap_tx, prepare_swap_tx};
+use aeon_market_scanner_rs::{DexAggregator, Exchange, KyberSwap};
+use ethers::core::types::{Address, U256};
+use ethers::providers::Provider;
+
+const BUILD_RESPONSE: &str = r#"{
+    "amountIn": "1000000000",
+    "amountInUsd": "1000.1",
+    "amountOut": "400000000000000000",
+    "amountOutUsd": "999.2",
+    "gas": "250000",
+    "gasUsd": "3.1",
+    "data": "0xe21fd0e9000000000000000000000000000000000000000000000000000000000000002a",
+    "routerAddress": "0x6131B5fae19EA4f9D964eAc0408E4408b66337b5",
+    "transactionValue": "0"
+}"#;
+
+fn fees() -> GasFees {
+    GasFees {
+        max_fee_per_gas: U256::from(30_000_000_000u64),
+        max_priority_fee_per_gas: U256::from(1_000_000_000u64),
+    }
+}
+
+#[test]
+fn test_build_swap_tx_from_built_route() {
+    let route: KyberSwapBuiltRoute = serde_json::from_str(BUILD_RESPONSE).unwrap();
+    let sender = Address::from_low_u64_be(7);
+    let tx = build_swap_tx(1, sender, &route, &fees()).unwrap();
+
+    let tx = tx.as_eip1559_ref().expect("EIP-1559 transaction");
+    assert_eq!(tx.chain_id.map(|c| c.as_u64()), Some(1));
+    assert_eq!(tx.from, Some(sender));
+    assert_eq!(
+        tx.to.as_ref().and_then(|to| to.as_address()).copied(),
+        Some(route.router_address.parse().unwrap())
+    );
+    assert_eq!(tx.value, Some(U256::zero()));
+    // 250k estimate + 20% buffer
+    assert_eq!(tx.gas, Some(U256::from(300_000u64)));
+    assert_eq!(tx.max_fee_per_gas, Some(fees().max_fee_per_gas));
+    assert_eq!(
+        tx.max_priority_fee_per_gas,
+        Some(fees().max_priority_fee_per_gas)
+    );
+    assert_eq!(tx.data.as_ref().map(|d| d.len()), Some(36));
+}
+
+#[test]
+fn test_build_swap_tx_native_value_and_invalid_router() {
+    let mut route: KyberSwapBuiltRoute = serde_json::from_str(BUILD_RESPONSE).unwrap();
+    route.transaction_value = Some("1000000000000000000".to_string());
+    route.gas = None;
+    let tx = build_swap_tx(1, Address::zero(), &route, &fees()).unwrap();
+    let tx = tx.as_eip1559_ref().unwrap();
+    assert_eq!(tx.value, Some(U256::exp10(18)));
+    assert_eq!(tx.gas, None);
+
+    route.router_address = "not-an-address".to_string();
+    assert!(build_swap_tx(1, Address::zero(), &route, &fees()).is_err());
+}
+
+#[tokio::test]
+async fn test_prepare_swap_tx_requires_route_data() {
+    let (provider, _mock) = Provider::mocked();
+    let price = DexPrice {
+        symbol: "ETHUSDT".to_string(),
+        mid_price: 2000.0,
+        bid_price: 1999.0,
+        ask_price: 2001.0,
+        bid_qty: 1.0,
+        ask_qty: 1.0,
+        timestamp: Timestamp::from_millis(1),
+        exchange: Exchange::Dex(DexAggregator::KyberSwap),
+        bid_route_summary: None,
+        ask_route_summary: None,
+        bid_route_data: None,
+        ask_route_data: None,
+        transfer_tax_bps: None,
+    };
+    let err = prepare_swap_tx(
+        &provider,
+        &KyberSwap::new(),
+        &ChainId::ETHEREUM,
+        &price,
+        AmountSide::Buy,
+        Address::zero(),
+        50,
+    )
+    .await
+    .unwrap_err();
+    assert!(err.to_string().contains("no route data"));
+}
@@ -0,0 +1,73 @@
+use aeon_market_scanner_rs::common::{
+    format_symbol_for_exchange, format_symbol_for_exchange_ws, standard_symbol_for_cex_ws_response,
+};
+use aeon_market_scanner_rs::{
+    CexExchange, clear_symbol_formatter, register_symbol_override, remove_symbol_override,
+    set_symbol_formatter,
+};
+
+#[test]
+fn registered_override_wins_over_heuristics() {
+    // Heuristics would split this as 1000PEPE-USDT
+    register_symbol_override(&CexExchange::OKX, "1000PEPE-USDT", "PEPE-USDT");
+    assert_eq!(
+        format_symbol_for_exchange("1000PEPEUSDT", &CexExchange::OKX).unwrap(),
+        "PEPE-USDT"
+    );
+    assert_eq!(
+        format_symbol_for_exchange_ws("1000pepe_usdt", &CexExchange::OKX).unwrap(),
+        "PEPE-USDT"
+    );
+    // Responses map back to the standard symbol
+    assert_eq!(
+        standard_symbol_for_cex_ws_response("PEPE-USDT", &CexExchange::OKX),
+        "1000PEPEUSDT"
+    );
+    // Other exchanges are unaffected
+    assert_eq!(
+        format_symbol_for_exchange("1000PEPEUSDT", &CexExchange::Kucoin).unwrap(),
+        "1000PEPE-USDT"
+    );
+
+    assert_eq!(
+        remove_symbol_override(&CexExchange::OKX, "1000PEPEUSDT").as_deref(),
+        Some("PEPE-USDT")
+    );
+    assert_eq!(
+        format_symbol_for_exchange("1000PEPEUSDT", &CexExchange::OKX).unwrap(),
+        "1000PEPE-USDT"
+    );
+}
+
+#[test]
+fn formatter_closure_falls_back_to_default() {
+    set_symbol_formatter(&CexExchange::Gateio, |symbol| {
+        symbol
+            .strip_suffix("EURC")
+            .map(|base| format!("{}_EURC", base))
+    });
+    assert_eq!(
+        format_symbol_for_exchange("BTCEURC", &CexExchange::Gateio).unwrap(),
+        "BTC_EURC"
+    );
+    // Closure returns None: built-in formatting applies
+    assert_eq!(
+        format_symbol_for_exchange("BTCUSDT", &CexExchange::Gateio).unwrap(),
+        "BTC_USDT"
+    );
+
+    // Exact mappings take precedence over the closure
+    register_symbol_override(&CexExchange::Gateio, "ETHEURC", "ETH_EURC_X");
+    assert_eq!(
+        format_symbol_for_exchange("ETHEURC", &CexExchange::Gateio).unwrap(),
+        "ETH_EURC_X"
+    );
+    remove_symbol_override(&CexExchange::Gateio, "ETHEURC");
+
+    // Without the closure the heuristics split at the last 3 characters
+    clear_symbol_formatter(&CexExchange::Gateio);
+    assert_eq!(
+        format_symbol_for_exchange("BTCEURC", &CexExchange::Gateio).unwrap(),
+        "BTCE_URC"
+    );
+}
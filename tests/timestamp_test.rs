@@ -0,0 +1,53 @@
+use aeon_market_scanner_rs::Timestamp;
+use std::time::Duration;
+
+#[test]
+fn unit_conversions_normalize_to_millis() {
+    let ms = 1_700_000_000_123u64;
+    assert_eq!(Timestamp::from_millis(ms).as_millis(), ms);
+    assert_eq!(
+        Timestamp::from_secs(1_700_000_000).as_millis(),
+        1_700_000_000_000
+    );
+    assert_eq!(Timestamp::from_micros(ms * 1000).as_millis(), ms);
+    assert_eq!(Timestamp::from_nanos(ms * 1_000_000).as_millis(), ms);
+    assert_eq!(Timestamp::from_millis(ms).as_secs(), 1_700_000_000);
+
+    // Venue values of unknown unit
+    for value in [
+        1_700_000_000,
+        1_700_000_000_000,
+        1_700_000_000_000_000,
+        1_700_000_000_000_000_000,
+    ] {
+        assert_eq!(Timestamp::from_epoch_guess(value).as_secs(), 1_700_000_000);
+    }
+}
+
+#[test]
+fn durations_and_age() {
+    let earlier = Timestamp::from_millis(1_000);
+    let later = earlier.saturating_add(Duration::from_millis(250));
+    assert_eq!(later.duration_since(earlier), Duration::from_millis(250));
+    assert_eq!(earlier.duration_since(later), Duration::ZERO);
+    assert_eq!(
+        later.saturating_sub(Duration::from_secs(10)),
+        Timestamp::from_millis(0)
+    );
+
+    let now = Timestamp::now();
+    assert!(now > 0);
+    assert!(now.age() < Duration::from_secs(5));
+    assert!(earlier.is_older_than(Duration::from_secs(60)));
+    assert!(!now.is_older_than(Duration::from_secs(60)));
+}
+
+#[test]
+fn serializes_as_plain_millis() {
+    let ts = Timestamp::from_millis(1_700_000_000_123);
+    assert_eq!(serde_json::to_string(&ts).unwrap(), "1700000000123");
+    let back: Timestamp = serde_json::from_str("1700000000123").unwrap();
+    assert_eq!(back, ts);
+    assert_eq!(u64::from(back), 1_700_000_000_123);
+    assert_eq!(Timestamp::from(5u64), 5);
+}
@@ -0,0 +1,47 @@
+mod scanner_common;
+
+use aeon_market_scanner_rs::CexExchange;
+use aeon_market_scanner_rs::common::{CexPrice, TradingStatus};
+use aeon_market_scanner_rs::scanner::ArbitrageScanner;
+use scanner_common::CexPriceBuilder;
+
+#[test]
+fn non_trading_venues_are_excluded_from_opportunities() {
+    let binance = CexPriceBuilder::new(CexExchange::Binance, 99.0, 100.0)
+        .status(TradingStatus::Trading)
+        .build();
+    let okx = CexPriceBuilder::new(CexExchange::OKX, 110.0, 111.0)
+        .status(TradingStatus::Unknown)
+        .build();
+    let opps =
+        ArbitrageScanner::opportunities_from_prices(&[binance.clone(), okx.clone()], &[], None);
+    assert!(!opps.is_empty(), "Trading and Unknown venues should pair");
+
+    for status in [
+        TradingStatus::Halted,
+        TradingStatus::Auction,
+        TradingStatus::PostOnly,
+        TradingStatus::CancelOnly,
+    ] {
+        assert!(!status.is_trading());
+        let halted_okx = CexPriceBuilder::new(CexExchange::OKX, 110.0, 111.0)
+            .status(status)
+            .build();
+        let opps =
+            ArbitrageScanner::opportunities_from_prices(&[binance.clone(), halted_okx], &[], None);
+        assert!(
+            opps.is_empty(),
+            "{:?} venue must not form an opportunity",
+            status
+        );
+    }
+}
+
+#[test]
+fn trading_status_defaults_to_unknown_when_missing() {
+    let json = r#"{"symbol":"BTCUSDT","mid_price":1.0,"bid_price":1.0,"ask_price":1.0,
+        "bid_qty":1.0,"ask_qty":1.0,"timestamp":1,"exchange":{"Cex":"Binance"}}"#;
+    let price: CexPrice = serde_json::from_str(json).expect("old payload should deserialize");
+    assert_eq!(price.trading_status, TradingStatus::Unknown);
+    assert!(price.trading_status.is_trading());
+}
@@ -0,0 +1,117 @@
+mod scanner_common;
+
+use aeon_market_scanner_rs::scanner::NetworkTransferStatus;
+use aeon_market_scanner_rs::{
+    AssetTransferStatus, CexExchange, Exchange, TransferCostModel, TransferStatusMonitor,
+};
+use scanner_common::opportunity;
+
+fn binance() -> Exchange {
+    Exchange::Cex(CexExchange::Binance)
+}
+
+fn okx() -> Exchange {
+    Exchange::Cex(CexExchange::OKX)
+}
+
+#[test]
+fn cheapest_network_common_to_both_venues_is_used() {
+    let model = TransferCostModel::default()
+        .with_venue_networks(binance(), "usdt", &["trc20", "ERC20", "BEP20"])
+        .with_venue_networks(okx(), "USDT", &["TRC20", "ERC20"]);
+    // BEP20 is cheaper but OKX does not support it
+    let network = model.cheapest_network("USDT", &okx(), &binance()).unwrap();
+    assert_eq!(network.network, "TRC20");
+
+    let only_erc20 = model.with_venue_networks(okx(), "USDT", &["ERC20", "POLYGON"]);
+    assert_eq!(
+        only_erc20
+            .cheapest_network("USDT", &okx(), &binance())
+            .unwrap()
+            .network,
+        "ERC20"
+    );
+    let disjoint = only_erc20.with_venue_networks(okx(), "USDT", &["POLYGON"]);
+    assert!(
+        disjoint
+            .cheapest_network("USDT", &okx(), &binance())
+            .is_none()
+    );
+    assert!(
+        disjoint
+            .cheapest_network("PEPE", &okx(), &binance())
+            .is_none()
+    );
+}
+
+#[test]
+fn net_profit_deducts_rebalancing_transfers() {
+    let opportunity = opportunity(
+        "ETHUSDT",
+        CexExchange::Binance,
+        2_000.0,
+        CexExchange::OKX,
+        2_010.0,
+        2.0,
+    );
+    assert!((opportunity.total_profit() - 20.0).abs() < 1e-9);
+
+    let model = TransferCostModel::default()
+        .with_network("ETH", "ARBITRUM", 0.0002, 300)
+        .with_venue_networks(okx(), "ETH", &["ERC20", "ARBITRUM"])
+        .with_venue_networks(okx(), "USDT", &["TRC20"]);
+    let costs = model.transfer_costs(&opportunity).unwrap();
+    assert_eq!(costs.base.asset, "ETH");
+    assert_eq!(costs.base.network, "ARBITRUM");
+    assert!((costs.base.fee_quote - 0.4).abs() < 1e-9);
+    assert_eq!(costs.quote.asset, "USDT");
+    assert_eq!(costs.quote.network, "TRC20");
+    assert_eq!(costs.quote.fee_quote, 1.0);
+    assert_eq!(costs.transfer_secs(), 300);
+    assert!((model.net_profit(&opportunity).unwrap() - 18.6).abs() < 1e-9);
+
+    let unroutable = model.with_venue_networks(okx(), "ETH", &["SOMECHAIN"]);
+    assert!(unroutable.net_profit(&opportunity).is_none());
+}
+
+#[test]
+fn suspended_networks_are_skipped() {
+    let monitor = TransferStatusMonitor::new();
+    let model = TransferCostModel::default().with_transfer_status(monitor.clone());
+    assert_eq!(
+        model
+            .cheapest_network("USDT", &okx(), &binance())
+            .unwrap()
+            .network,
+        "BEP20"
+    );
+
+    // OKX halted BEP20 withdrawals only
+    monitor.set_status(
+        &CexExchange::OKX,
+        "USDT",
+        AssetTransferStatus::from_networks(vec![
+            NetworkTransferStatus {
+                network: "USDT-BEP20".to_string(),
+                deposits_enabled: true,
+                withdrawals_enabled: false,
+            },
+            NetworkTransferStatus {
+                network: "USDT-TRC20".to_string(),
+                deposits_enabled: true,
+                withdrawals_enabled: true,
+            },
+        ]),
+    );
+    let network = model.cheapest_network("USDT", &okx(), &binance()).unwrap();
+    // Networks the venue does not report fall back to the asset status
+    assert_eq!(network.network, "ARBITRUM");
+    // Deposits on OKX over BEP20 are still open
+    assert_eq!(
+        model
+            .cheapest_network("USDT", &binance(), &okx())
+            .unwrap()
+            .network,
+        "BEP20"
+    );
+}
@@ -0,0 +1,75 @@
+use aeon_market_scanner_rs::common::Exchange;
+use aeon_market_scanner_rs::scanner::parse_asset_transfer_status;
+use aeon_market_scanner_rs::{
+    AssetTransferStatus, CexExchange, DexAggregator, TransferStatusMonitor,
+};
+use serde_json::json;
+
+#[test]
+fn currency_responses_are_parsed_per_venue() {
+    let kucoin = json!({"code": "200000", "data": {"currency": "USDT", "chains": [
+        {"chainName": "ERC20", "isDepositEnabled": true, "isWithdrawEnabled": false},
+        {"chainName": "TRC20", "isDepositEnabled": false, "isWithdrawEnabled": false}
+    ]}});
+    let status = parse_asset_transfer_status(&CexExchange::Kucoin, &kucoin)
+        .unwrap()
+        .unwrap();
+    assert!(status.deposits_enabled && !status.withdrawals_enabled);
+    assert_eq!(status.networks.len(), 2);
+    assert_eq!(status.networks[1].network, "TRC20");
+
+    let gateio = json!({"currency": "USDT", "deposit_disabled": false, "withdraw_disabled": true});
+    let status = parse_asset_transfer_status(&CexExchange::Gateio, &gateio)
+        .unwrap()
+        .unwrap();
+    assert!(status.deposits_enabled && !status.withdrawals_enabled);
+    assert!(status.networks.is_empty());
+
+    let htx = json!({"code": 200, "data": [{"currency": "usdt", "chains": [
+        {"chain": "trc20usdt", "depositStatus": "prohibited", "withdrawStatus": "allowed"}
+    ]}]});
+    let status = parse_asset_transfer_status(&CexExchange::Htx, &htx)
+        .unwrap()
+        .unwrap();
+    assert!(!status.deposits_enabled && status.withdrawals_enabled);
+
+    let bitget = json!({"code": "00000", "data": [{"coin": "USDT", "chains": [
+        {"chain": "TRC20", "rechargeable": "true", "withdrawable": "true"}
+    ]}]});
+    let status = parse_asset_transfer_status(&CexExchange::Bitget, &bitget)
+        .unwrap()
+        .unwrap();
+    assert!(status.deposits_enabled && status.withdrawals_enabled);
+
+    // No public endpoint
+    assert!(
+        parse_asset_transfer_status(&CexExchange::Binance, &json!({}))
+            .unwrap()
+            .is_none()
+    );
+}
+
+#[test]
+fn malformed_responses_are_errors() {
+    let error = json!({"code": "400100", "msg": "Invalid currency"});
+    assert!(parse_asset_transfer_status(&CexExchange::Kucoin, &error).is_err());
+    assert!(parse_asset_transfer_status(&CexExchange::Gateio, &error).is_err());
+}
+
+#[test]
+fn unknown_statuses_count_as_enabled() {
+    let monitor = TransferStatusMonitor::new();
+    let okx = Exchange::Cex(CexExchange::OKX);
+    assert!(monitor.deposits_enabled(&okx, "BTC"));
+    assert!(monitor.withdrawals_enabled(&okx, "BTC"));
+
+    monitor.set_status(
+        &CexExchange::OKX,
+        "btc",
+        AssetTransferStatus::new(true, false),
+    );
+    assert!(monitor.deposits_enabled(&okx, "BTC"));
+    assert!(!monitor.withdrawals_enabled(&okx, "BTC"));
+    assert!(monitor.status(&CexExchange::OKX, "BTC").is_some());
+    assert!(monitor.withdrawals_enabled(&Exchange::Dex(DexAggregator::KyberSwap), "BTC"));
+}
@@ -0,0 +1,107 @@
+use aeon_market_scanner_rs::dex::chains::{
+    ChainId, Token, TransferTax, clear_transfer_taxes, pair_transfer_tax_bps,
+    simulate_transfer_tax, transfer_tax,
+};
+use aeon_market_scanner_rs::{DexAggregator, DexPrice, Exchange, Timestamp};
+use ethers::core::types::{Address, Bytes, U256};
+use ethers::providers::Provider;
+
+fn token(address: &str, symbol: &str) -> Token {
+    Token::create(address, symbol, symbol, 18, ChainId::ETHEREUM)
+}
+
+fn amount_word(amount: u64) -> Bytes {
+    let mut word = [0u8; 32];
+    U256::from(amount).to_big_endian(&mut word);
+    Bytes::from(word.to_vec())
+}
+
+#[test]
+fn tax_is_share_of_transfer_kept() {
+    let tax = |sent: u64, received: u64| TransferTax {
+        chain: ChainId::ETHEREUM,
+        token: "0x01".to_string(),
+        sent: U256::from(sent),
+        received: U256::from(received),
+        checked_at: Timestamp::now(),
+    };
+    assert!((tax(10_000, 9_700).tax_bps() - 300.0).abs() < 1e-9);
+    assert!(tax(10_000, 9_700).is_taxed());
+    assert_eq!(tax(10_000, 10_000).tax_bps(), 0.0);
+    assert!(!tax(10_000, 10_000).is_taxed());
+    assert_eq!(tax(0, 0).tax_bps(), 0.0);
+}
+
+#[tokio::test]
+async fn simulated_transfers_flag_tax_tokens() {
+    let taxed = token("0x00000000000000000000000000000000000000aA", "TAX");
+    let plain = token("0x00000000000000000000000000000000000000bb", "USDT");
+    let holder = Address::from_low_u64_be(0x9001);
+
+    let (provider, mock) = Provider::mocked();
+    // Mocked responses are served last pushed first
+    mock.push::<Bytes, _>(amount_word(1_000_000)).unwrap();
+    mock.push::<Bytes, _>(amount_word(950_000)).unwrap();
+
+    let tax = simulate_transfer_tax(&provider, &taxed, holder, U256::from(1_000_000u64))
+        .await
+        .unwrap();
+    assert!((tax.tax_bps() - 500.0).abs() < 1e-9);
+    let clean = simulate_transfer_tax(&provider, &plain, holder, U256::from(1_000_000u64))
+        .await
+        .unwrap();
+    assert!(!clean.is_taxed());
+
+    // Looked up per chain and address, case-insensitively
+    let lowercase = token(&taxed.address.to_lowercase(), "TAX");
+    assert_eq!(transfer_tax(&lowercase), Some(tax));
+    let on_base = Token::create(&taxed.address, "TAX", "TAX", 18, ChainId::BASE);
+    assert_eq!(transfer_tax(&on_base), None);
+
+    assert_eq!(pair_transfer_tax_bps(&taxed, &plain), Some(500.0));
+    assert_eq!(pair_transfer_tax_bps(&plain, &plain), None);
+
+    clear_transfer_taxes();
+    assert_eq!(transfer_tax(&taxed), None);
+}
+
+#[tokio::test]
+async fn simulation_without_amount_fails() {
+    let (provider, mock) = Provider::mocked();
+    mock.push::<Bytes, _>(Bytes::new()).unwrap();
+    let result = simulate_transfer_tax(
+        &provider,
+        &token("0x00000000000000000000000000000000000000cc", "TAX"),
+        Address::from_low_u64_be(0x9001),
+        U256::from(1u64),
+    )
+    .await;
+    assert!(result.is_err());
+}
+
+#[test]
+fn dex_prices_carry_transfer_tax() {
+    let mut price = DexPrice {
+        symbol: "TAXUSDT".to_string(),
+        mid_price: 1.0,
+        bid_price: 1.0,
+        ask_price: 1.0,
+        bid_qty: 1.0,
+        ask_qty: 1.0,
+        timestamp: Timestamp::now(),
+        exchange: Exchange::Dex(DexAggregator::KyberSwap),
+        bid_route_summary: None,
+        ask_route_summary: None,
+        bid_route_data: None,
+        ask_route_data: None,
+        transfer_tax_bps: None,
+    };
+    assert!(!price.has_transfer_tax());
+    let json = serde_json::to_value(&price).unwrap();
+    assert!(json.get("transfer_tax_bps").is_none());
+
+    price.transfer_tax_bps = Some(500.0);
+    assert!(price.has_transfer_tax());
+    let parsed: DexPrice = serde_json::from_value(serde_json::to_value(&price).unwrap()).unwrap();
+    assert_eq!(parsed.transfer_tax_bps, Some(500.0));
+}
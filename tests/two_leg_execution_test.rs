@@ -0,0 +1,520 @@
+mod scanner_common;
+
+use aeon_market_scanner_rs::cex::execution::{
+    CexOrder, CexOrderAck, ChaseConfig, ExecutionConfig, ExecutionStatus, ExecutionVenue,
+    OrderStatus, OrderType, OrderUpdate, PartialFillPolicy, TradingMode, TwoLegExecutor,
+};
+use aeon_market_scanner_rs::common::{CexPrice, OrderLimits, Timestamp};
+use aeon_market_scanner_rs::{
+    CexExchange, Exchange, MarketScannerError, RiskLimits, RiskManager, TradeSide,
+};
+use async_trait::async_trait;
+use scanner_common::CexPriceBuilder;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+#[derive(Default)]
+struct Book {
+    next_id: u32,
+    /// order id -> (order, cancelled)
+    orders: HashMap<String, (CexOrder, bool)>,
+    placed: Vec<CexOrder>,
+    cancelled: Vec<String>,
+}
+
+/// Venue filling `fill_fraction` of limit orders (market orders fill completely). With a
+/// quoted `top` (bid, ask), limit orders crossing it fill completely unless `stale_quotes`.
+#[derive(Clone)]
+struct MockVenue {
+    exchange: CexExchange,
+    fill_fraction: f64,
+    reject: bool,
+    top: Option<(f64, f64)>,
+    stale_quotes: bool,
+    book: Arc<Mutex<Book>>,
+}
+
+impl MockVenue {
+    fn new(exchange: CexExchange, fill_fraction: f64) -> Self {
+        Self {
+            exchange,
+            fill_fraction,
+            reject: false,
+            top: None,
+            stale_quotes: false,
+            book: Arc::default(),
+        }
+    }
+
+    fn quoting(exchange: CexExchange, fill_fraction: f64, bid: f64, ask: f64) -> Self {
+        Self {
+            top: Some((bid, ask)),
+            ..Self::new(exchange, fill_fraction)
+        }
+    }
+
+    fn rejecting(exchange: CexExchange) -> Self {
+        Self {
+            reject: true,
+            ..Self::new(exchange, 0.0)
+        }
+    }
+
+    fn placed(&self) -> Vec<CexOrder> {
+        self.book.lock().unwrap().placed.clone()
+    }
+
+    fn cancelled(&self) -> Vec<String> {
+        self.book.lock().unwrap().cancelled.clone()
+    }
+}
+
+#[async_trait]
+impl ExecutionVenue for MockVenue {
+    fn exchange(&self) -> CexExchange {
+        self.exchange.clone()
+    }
+
+    async fn place_order(&self, order: &CexOrder) -> Result<CexOrderAck, MarketScannerError> {
+        if self.reject {
+            return Err(MarketScannerError::ApiError("insufficient balance".into()));
+        }
+        let mut book = self.book.lock().unwrap();
+        book.next_id += 1;
+        let order_id = book.next_id.to_string();
+        book.orders.insert(order_id.clone(), (order.clone(), false));
+        book.placed.push(order.clone());
+        Ok(CexOrderAck {
+            exchange: self.exchange.clone(),
+            symbol: order.symbol.clone(),
+            order_id,
+            client_order_id: None,
+            side: order.side,
+            quantity: order.quantity,
+            price: order.price,
+            mode: TradingMode::Demo,
+            timestamp: Timestamp::now(),
+        })
+    }
+
+    async fn order_status(&self, ack: &CexOrderAck) -> Result<OrderUpdate, MarketScannerError> {
+        let book = self.book.lock().unwrap();
+        let (order, cancelled) = &book.orders[&ack.order_id];
+        let crosses = match (self.top, order.price) {
+            (Some((bid, ask)), Some(price)) if !self.stale_quotes => match order.side {
+                TradeSide::Buy => price >= ask,
+                TradeSide::Sell => price <= bid,
+            },
+            _ => false,
+        };
+        let fraction = match order.order_type {
+            OrderType::Market => 1.0,
+            OrderType::Limit if crosses => 1.0,
+            OrderType::Limit => self.fill_fraction,
+        };
+        let filled_quantity = order.quantity * fraction;
+        let status = if fraction >= 1.0 {
+            OrderStatus::Filled
+        } else if *cancelled {
+            OrderStatus::Canceled
+        } else if fraction > 0.0 {
+            OrderStatus::PartiallyFilled
+        } else {
+            OrderStatus::New
+        };
+        Ok(OrderUpdate {
+            exchange: self.exchange.clone(),
+            symbol: order.symbol.clone(),
+            order_id: ack.order_id.clone(),
+            client_order_id: None,
+            side: order.side,
+            status,
+            price: order.price,
+            quantity: order.quantity,
+            filled_quantity,
+            average_fill_price: (filled_quantity > 0.0).then_some(order.price.unwrap_or(100.0)),
+            timestamp: Timestamp::now(),
+        })
+    }
+
+    async fn cancel_order(&self, ack: &CexOrderAck) -> Result<(), MarketScannerError> {
+        let mut book = self.book.lock().unwrap();
+        book.orders.get_mut(&ack.order_id).unwrap().1 = true;
+        book.cancelled.push(ack.order_id.clone());
+        Ok(())
+    }
+
+    async fn quote(&self, symbol: &str) -> Result<CexPrice, MarketScannerError> {
+        let (bid, ask) = self
+            .top
+            .ok_or_else(|| MarketScannerError::ApiError("no quotes".into()))?;
+        Ok(CexPriceBuilder::new(self.exchange.clone(), bid, ask)
+            .symbol(symbol)
+            .qty(10.0)
+            .now()
+            .build())
+    }
+}
+
+fn config(policy: PartialFillPolicy) -> ExecutionConfig {
+    ExecutionConfig::default()
+        .with_policy(policy)
+        .with_fill_timeout_ms(50)
+        .with_poll_interval_ms(5)
+}
+
+fn legs() -> (CexOrder, CexOrder) {
+    (
+        CexOrder::limit("BTCUSDT", TradeSide::Buy, 1.0, 100.0),
+        CexOrder::limit("BTCUSDT", TradeSide::Sell, 1.0, 110.0),
+    )
+}
+
+#[tokio::test]
+async fn both_legs_filled() {
+    let buy_venue = MockVenue::new(CexExchange::Bybit, 1.0);
+    let sell_venue = MockVenue::new(CexExchange::OKX, 1.0);
+    let executor = TwoLegExecutor::new(
+        buy_venue.clone(),
+        sell_venue.clone(),
+        config(PartialFillPolicy::Unwind),
+    );
+    let (buy, sell) = legs();
+    let outcome = executor.execute(buy, sell).await.unwrap();
+
+    assert_eq!(outcome.status, ExecutionStatus::Completed);
+    assert_eq!(outcome.matched_quantity(), 1.0);
+    assert_eq!(outcome.residual_quantity, 0.0);
+    assert!(outcome.correction.is_none());
+    assert_eq!(outcome.sell.average_fill_price(), Some(110.0));
+    assert!(buy_venue.cancelled().is_empty());
+}
+
+#[tokio::test]
+async fn partial_fill_is_unwound_on_the_filled_venue() {
+    let buy_venue = MockVenue::new(CexExchange::Bybit, 1.0);
+    let sell_venue = MockVenue::new(CexExchange::OKX, 0.4);
+    let executor = TwoLegExecutor::new(
+        buy_venue.clone(),
+        sell_venue.clone(),
+        config(PartialFillPolicy::Unwind),
+    );
+    let (buy, sell) = legs();
+    let outcome = executor.execute(buy, sell).await.unwrap();
+
+    // The sell leg timed out and was cancelled at 40%
+    assert_eq!(sell_venue.cancelled(), vec!["1".to_string()]);
+    assert_eq!(
+        outcome.sell.last_update.as_ref().unwrap().status,
+        OrderStatus::Canceled
+    );
+    // The extra 0.6 bought on Bybit is sold back there
+    let correction = outcome.correction.as_ref().unwrap();
+    assert_eq!(correction.exchange, CexExchange::Bybit);
+    assert_eq!(correction.order.side, TradeSide::Sell);
+    assert_eq!(correction.order.order_type, OrderType::Market);
+    assert!((correction.order.quantity - 0.6).abs() < 1e-12);
+    assert_eq!(outcome.status, ExecutionStatus::Unwound);
+    assert!(outcome.residual_quantity.abs() < 1e-12);
+    assert!((outcome.matched_quantity() - 0.4).abs() < 1e-12);
+    assert_eq!(buy_venue.placed().len(), 2);
+}
+
+#[tokio::test]
+async fn partial_fill_is_hedged_on_the_lagging_venue() {
+    let buy_venue = MockVenue::new(CexExchange::Bybit, 0.25);
+    let sell_venue = MockVenue::new(CexExchange::OKX, 1.0);
+    let executor = TwoLegExecutor::new(
+        buy_venue.clone(),
+        sell_venue.clone(),
+        config(PartialFillPolicy::Hedge),
+    );
+    let (buy, sell) = legs();
+    let outcome = executor.execute(buy, sell).await.unwrap();
+
+    // Sold 1.0 but bought 0.25: buy the rest on the buy venue
+    let correction = outcome.correction.as_ref().unwrap();
+    assert_eq!(correction.exchange, CexExchange::Bybit);
+    assert_eq!(correction.order.side, TradeSide::Buy);
+    assert_eq!(correction.order.quantity, 0.75);
+    assert_eq!(outcome.status, ExecutionStatus::Hedged);
+    assert_eq!(outcome.residual_quantity, 0.0);
+    assert!(sell_venue.cancelled().is_empty());
+}
+
+#[tokio::test]
+async fn hold_policy_reports_the_imbalance() {
+    let executor = TwoLegExecutor::new(
+        MockVenue::new(CexExchange::Bybit, 1.0),
+        MockVenue::new(CexExchange::OKX, 0.5),
+        config(PartialFillPolicy::Hold),
+    );
+    let (buy, sell) = legs();
+    let outcome = executor.execute(buy, sell).await.unwrap();
+    assert_eq!(outcome.status, ExecutionStatus::Unbalanced);
+    assert!(outcome.correction.is_none());
+    assert_eq!(outcome.residual_quantity, 0.5);
+}
+
+#[tokio::test]
+async fn rejected_leg_cancels_the_other_right_away() {
+    let buy_venue = MockVenue::new(CexExchange::Bybit, 0.0);
+    let executor = TwoLegExecutor::new(
+        buy_venue.clone(),
+        MockVenue::rejecting(CexExchange::OKX),
+        // Without the rejection the buy would wait a minute
+        config(PartialFillPolicy::Unwind).with_fill_timeout_ms(60_000),
+    );
+    let (buy, sell) = legs();
+    let outcome = tokio::time::timeout(
+        std::time::Duration::from_secs(5),
+        executor.execute(buy, sell),
+    )
+    .await
+    .unwrap()
+    .unwrap();
+
+    assert_eq!(outcome.status, ExecutionStatus::NotFilled);
+    assert!(outcome.sell.ack.is_none());
+    assert!(outcome.sell.errors[0].contains("insufficient balance"));
+    assert_eq!(buy_venue.cancelled(), vec!["1".to_string()]);
+}
+
+#[tokio::test]
+async fn risk_limits_are_checked_before_sending() {
+    let buy_venue = MockVenue::new(CexExchange::Bybit, 1.0);
+    let sell_venue = MockVenue::new(CexExchange::OKX, 0.4);
+    let risk = RiskManager::new(RiskLimits::new().with_max_orders_per_minute(2));
+    let executor = TwoLegExecutor::new(
+        buy_venue.clone(),
+        sell_venue.clone(),
+        config(PartialFillPolicy::Hold),
+    )
+    .with_risk(risk.clone());
+
+    let (buy, sell) = legs();
+    executor.execute(buy.clone(), sell.clone()).await.unwrap();
+    // Unfilled parts are released: 1.0 * 100 bought, 0.4 * 110 sold
+    assert_eq!(
+        risk.venue_exposure(&Exchange::Cex(CexExchange::Bybit)),
+        100.0
+    );
+    assert!((risk.venue_exposure(&Exchange::Cex(CexExchange::OKX)) - 44.0).abs() < 1e-9);
+
+    let result = executor.execute(buy, sell).await;
+    assert!(matches!(
+        result,
+        Err(MarketScannerError::RiskLimitExceeded(_))
+    ));
+    assert_eq!(buy_venue.placed().len(), 1);
+    assert_eq!(sell_venue.placed().len(), 1);
+}
+
+#[tokio::test]
+async fn correction_orders_are_reserved() {
+    let buy_venue = MockVenue::new(CexExchange::Bybit, 1.0);
+    let risk = RiskManager::new(RiskLimits::new());
+    let executor = TwoLegExecutor::new(
+        buy_venue.clone(),
+        MockVenue::new(CexExchange::OKX, 0.4),
+        config(PartialFillPolicy::Unwind),
+    )
+    .with_risk(risk.clone());
+    let (buy, sell) = legs();
+    let outcome = executor.execute(buy.clone(), sell.clone()).await.unwrap();
+    assert_eq!(outcome.status, ExecutionStatus::Unwound);
+    // The 0.6 sold back is reserved at the buy leg's fill price of 100
+    assert!((risk.venue_exposure(&Exchange::Cex(CexExchange::Bybit)) - 160.0).abs() < 1e-9);
+    assert_eq!(risk.orders_last_minute(), 3);
+
+    // Without room for a third order the correction is not sent
+    let buy_venue = MockVenue::new(CexExchange::Bybit, 1.0);
+    let executor = TwoLegExecutor::new(
+        buy_venue.clone(),
+        MockVenue::new(CexExchange::OKX, 0.4),
+        config(PartialFillPolicy::Unwind),
+    )
+    .with_risk(RiskManager::new(
+        RiskLimits::new().with_max_orders_per_minute(2),
+    ));
+    let outcome = executor.execute(buy, sell).await.unwrap();
+    assert_eq!(outcome.status, ExecutionStatus::Unbalanced);
+    let correction = outcome.correction.as_ref().unwrap();
+    assert!(correction.ack.is_none());
+    assert!(correction.errors[0].contains("orders"));
+    assert_eq!(buy_venue.placed().len(), 1);
+}
+
+#[tokio::test]
+async fn corrections_use_the_leg_quantity_step() {
+    let limits = OrderLimits {
+        quantity_step: Some(0.1),
+        ..OrderLimits::default()
+    };
+    let buy_venue = MockVenue::new(CexExchange::Bybit, 1.0);
+    let executor = TwoLegExecutor::new(
+        buy_venue.clone(),
+        MockVenue::new(CexExchange::OKX, 0.4),
+        config(PartialFillPolicy::Unwind),
+    );
+    let (buy, sell) = legs();
+    let outcome = executor
+        .execute(buy.with_limits(&limits), sell)
+        .await
+        .unwrap();
+    let correction = outcome.correction.as_ref().unwrap();
+    assert_eq!(correction.order.quantity_step, Some(0.1));
+
+    // An imbalance below one step is left alone
+    let executor = TwoLegExecutor::new(
+        MockVenue::new(CexExchange::Bybit, 1.0),
+        MockVenue::new(CexExchange::OKX, 0.95),
+        config(PartialFillPolicy::Unwind),
+    );
+    let (buy, sell) = legs();
+    let outcome = executor
+        .execute(buy.with_limits(&limits), sell)
+        .await
+        .unwrap();
+    assert_eq!(outcome.status, ExecutionStatus::Unbalanced);
+    assert!(outcome.correction.is_none());
+}
+
+#[tokio::test]
+async fn non_positive_quantities_are_rejected() {
+    let buy_venue = MockVenue::new(CexExchange::Bybit, 1.0);
+    let executor = TwoLegExecutor::new(
+        buy_venue.clone(),
+        MockVenue::new(CexExchange::OKX, 1.0),
+        config(PartialFillPolicy::Unwind),
+    )
+    .with_risk(RiskManager::new(RiskLimits::new()));
+    for quantity in [0.0, -1.0, f64::NAN, f64::INFINITY] {
+        let (mut buy, sell) = legs();
+        buy.quantity = quantity;
+        assert!(matches!(
+            executor.execute(buy, sell).await,
+            Err(MarketScannerError::InvalidConfig(_))
+        ));
+    }
+    assert!(buy_venue.placed().is_empty());
+}
+
+#[tokio::test]
+async fn legs_must_be_a_buy_and_a_sell() {
+    let executor = TwoLegExecutor::new(
+        MockVenue::new(CexExchange::Bybit, 1.0),
+        MockVenue::new(CexExchange::OKX, 1.0),
+        ExecutionConfig::default(),
+    );
+    let (buy, _) = legs();
+    assert!(executor.execute(buy.clone(), buy).await.is_err());
+}
+
+fn chasing(policy: PartialFillPolicy, max_attempts: u32, max_slippage_bps: f64) -> ExecutionConfig {
+    config(policy)
+        .with_chase(ChaseConfig::new(max_attempts, max_slippage_bps).with_attempt_timeout_ms(20))
+}
+
+#[tokio::test]
+async fn missed_leg_is_chased_at_the_current_price() {
+    // The ask moved to 101: half of the buy at 100 fills, the rest is re-placed at 101
+    let buy_venue = MockVenue::quoting(CexExchange::Bybit, 0.5, 99.0, 101.0);
+    let executor = TwoLegExecutor::new(
+        buy_venue.clone(),
+        MockVenue::new(CexExchange::OKX, 1.0),
+        chasing(PartialFillPolicy::Unwind, 3, 200.0),
+    );
+    let (buy, sell) = legs();
+    let outcome = executor.execute(buy, sell).await.unwrap();
+
+    assert_eq!(outcome.status, ExecutionStatus::Completed);
+    assert_eq!(outcome.buy.replacements.len(), 1);
+    let replacement = &outcome.buy.replacements[0];
+    assert_eq!(replacement.order.price, Some(101.0));
+    assert_eq!(replacement.order.quantity, 0.5);
+    assert_eq!(outcome.buy.attempts().count(), 2);
+    assert_eq!(outcome.buy.filled_quantity(), 1.0);
+    assert_eq!(outcome.buy.average_fill_price(), Some(100.5));
+    assert!(outcome.correction.is_none());
+    assert!(outcome.sell.replacements.is_empty());
+    assert_eq!(buy_venue.cancelled(), vec!["1".to_string()]);
+}
+
+#[tokio::test]
+async fn chased_orders_keep_the_leg_settings_and_are_reserved() {
+    let risk = RiskManager::new(RiskLimits::new());
+    let executor = TwoLegExecutor::new(
+        MockVenue::quoting(CexExchange::Bybit, 0.5, 99.0, 101.0),
+        MockVenue::new(CexExchange::OKX, 1.0),
+        chasing(PartialFillPolicy::Unwind, 3, 200.0),
+    )
+    .with_risk(risk.clone());
+    let (buy, sell) = legs();
+    let buy = buy.with_client_order_id("c1").with_limits(&OrderLimits {
+        quantity_step: Some(0.001),
+        ..OrderLimits::default()
+    });
+    let outcome = executor.execute(buy.clone(), sell.clone()).await.unwrap();
+    let replacement = &outcome.buy.replacements[0];
+    assert_eq!(replacement.order.quantity_step, Some(0.001));
+    assert_eq!(replacement.order.client_order_id.as_deref(), Some("c1"));
+    // 0.5 filled at 100 and 0.5 at 101 stay open
+    assert!((risk.venue_exposure(&Exchange::Cex(CexExchange::Bybit)) - 100.5).abs() < 1e-9);
+    assert_eq!(risk.orders_last_minute(), 3);
+
+    // Without room for the replacement the chase stops and the imbalance is held
+    let buy_venue = MockVenue::quoting(CexExchange::Bybit, 0.5, 99.0, 101.0);
+    let executor = TwoLegExecutor::new(
+        buy_venue.clone(),
+        MockVenue::new(CexExchange::OKX, 1.0),
+        chasing(PartialFillPolicy::Hold, 3, 200.0),
+    )
+    .with_risk(RiskManager::new(
+        RiskLimits::new().with_max_orders_per_minute(2),
+    ));
+    let outcome = executor.execute(buy, sell).await.unwrap();
+    assert!(outcome.buy.replacements.is_empty());
+    assert!(outcome.buy.errors[0].starts_with("Chase stopped"));
+    assert_eq!(outcome.status, ExecutionStatus::Unbalanced);
+    assert_eq!(buy_venue.placed().len(), 1);
+}
+
+#[tokio::test]
+async fn chase_is_bounded_by_attempts_and_slippage() {
+    // Quotes never fill: every attempt is used, then the imbalance is unwound
+    let buy_venue = MockVenue {
+        stale_quotes: true,
+        ..MockVenue::quoting(CexExchange::Bybit, 0.0, 99.0, 101.0)
+    };
+    let sell_venue = MockVenue::new(CexExchange::OKX, 1.0);
+    let executor = TwoLegExecutor::new(
+        buy_venue.clone(),
+        sell_venue.clone(),
+        chasing(PartialFillPolicy::Unwind, 2, 200.0),
+    );
+    let (buy, sell) = legs();
+    let outcome = executor.execute(buy, sell).await.unwrap();
+    assert_eq!(outcome.buy.replacements.len(), 2);
+    assert_eq!(buy_venue.placed().len(), 3);
+    assert_eq!(outcome.status, ExecutionStatus::Unwound);
+    assert_eq!(
+        outcome.correction.as_ref().unwrap().exchange,
+        CexExchange::OKX
+    );
+
+    // The ask is 5% away, beyond the 2% bound: nothing is re-placed
+    let buy_venue = MockVenue::quoting(CexExchange::Bybit, 0.0, 99.0, 105.0);
+    let executor = TwoLegExecutor::new(
+        buy_venue.clone(),
+        MockVenue::new(CexExchange::OKX, 1.0),
+        chasing(PartialFillPolicy::Hold, 2, 200.0),
+    );
+    let (buy, sell) = legs();
+    let outcome = executor.execute(buy, sell).await.unwrap();
+    assert!(outcome.buy.replacements.is_empty());
+    assert!(outcome.buy.errors[0].contains("Chase stopped"));
+    assert_eq!(buy_venue.placed().len(), 1);
+    assert_eq!(outcome.status, ExecutionStatus::Unbalanced);
+    assert_eq!(outcome.residual_quantity, -1.0);
+}
@@ -0,0 +1,45 @@
+mod scanner_common;
+
+use aeon_market_scanner_rs::{AmountSpec, BaseQty, CexExchange, QuoteQty};
+use scanner_common::{binance_okx_fees, find_opportunity, sized_price};
+
+#[test]
+fn conversions_take_a_price() {
+    assert_eq!(BaseQty(2.0).to_quote(2_000.0), QuoteQty(4_000.0));
+    assert_eq!(QuoteQty(4_000.0).to_base(2_000.0), BaseQty(2.0));
+    assert_eq!(QuoteQty(4_000.0).to_base(0.0), BaseQty(0.0));
+    assert_eq!(BaseQty(1.5) + BaseQty(0.5) - BaseQty(1.0), BaseQty(1.0));
+    let total: QuoteQty = [QuoteQty(1.0), QuoteQty(2.5)].into_iter().sum();
+    assert_eq!(total, QuoteQty(3.5));
+
+    assert_eq!(AmountSpec::from(BaseQty(2.0)), AmountSpec::Base(2.0));
+    assert_eq!(AmountSpec::from(QuoteQty(500.0)), AmountSpec::Quote(500.0));
+    assert_eq!(AmountSpec::Quote(500.0).to_base(2_000.0), BaseQty(0.25));
+    assert_eq!(AmountSpec::Base(0.25).to_quote(2_000.0), QuoteQty(500.0));
+}
+
+#[test]
+fn quantities_serialize_as_plain_numbers() {
+    assert_eq!(serde_json::to_string(&BaseQty(0.5)).unwrap(), "0.5");
+    let qty: QuoteQty = serde_json::from_str("1250.0").unwrap();
+    assert_eq!(qty, QuoteQty(1_250.0));
+}
+
+#[test]
+fn typed_accessors_match_the_raw_fields() {
+    let binance = sized_price(CexExchange::Binance, "ETHUSDT", 1_999.0, 2_000.0, 3.0);
+    assert_eq!(binance.ask_size(), BaseQty(3.0));
+    assert_eq!(binance.ask_notional(), QuoteQty(6_000.0));
+    assert_eq!(binance.bid_notional(), QuoteQty(5_997.0));
+
+    let fees = binance_okx_fees();
+    let prices = [
+        binance,
+        sized_price(CexExchange::OKX, "ETHUSDT", 2_010.0, 2_011.0, 2.0),
+    ];
+    let opp = find_opportunity(&prices, &fees, "Binance", "OKX");
+    assert_eq!(opp.executable_size(), BaseQty(2.0));
+    assert_eq!(opp.executable_notional(), QuoteQty(4_000.0));
+    assert_eq!(opp.total_profit_quote(), QuoteQty(20.0));
+    assert_eq!(opp.total_commission(), QuoteQty(0.0));
+}
@@ -57,3 +57,25 @@ async fn test_upbit_invalid_symbol() {
 async fn test_upbit_empty_symbol() {
     test_get_price_empty_symbol_common(&Upbit::new(), "Upbit").await;
 }
+
+#[tokio::test]
+async fn test_upbit_get_order_book() {
+    let book = Upbit::new()
+        .get_order_book("BTCUSD", 5)
+        .await
+        .expect("Should be able to get Upbit order book");
+
+    assert_eq!(book.exchange, Exchange::Cex(CexExchange::Upbit));
+    assert!(!book.bids.is_empty() && book.bids.len() <= 5);
+    assert!(!book.asks.is_empty() && book.asks.len() <= 5);
+    // Bids descending, asks ascending, best bid below best ask
+    assert!(book.bids.windows(2).all(|w| w[0].price >= w[1].price));
+    assert!(book.asks.windows(2).all(|w| w[0].price <= w[1].price));
+    assert!(book.bids[0].price <= book.asks[0].price);
+    println!(
+        "Upbit {} levels: {} bids / {} asks",
+        book.symbol,
+        book.bids.len(),
+        book.asks.len()
+    );
+}
@@ -0,0 +1,157 @@
+use aeon_market_scanner_rs::cex::execution::{
+    OrderStatus, TradingMode, UserDataEvent, UserDataStream,
+};
+use aeon_market_scanner_rs::common::{Credentials, Timestamp};
+use aeon_market_scanner_rs::{Binance, Bybit, CexExchange, MarketScannerError, OKX, TradeSide};
+use serde_json::json;
+
+#[test]
+fn binance_execution_report_becomes_order_update() {
+    let message = json!({
+        "e": "executionReport", "E": 1_700_000_000_123u64, "s": "ETHUSDT", "c": "my-1",
+        "S": "BUY", "o": "LIMIT", "q": "2.00000000", "p": "2000.00000000",
+        "X": "PARTIALLY_FILLED", "i": 4293153, "z": "0.50000000", "Z": "999.50000000"
+    });
+    let events = Binance::new().parse_user_data(&message);
+    let [UserDataEvent::Order(update)] = events.as_slice() else {
+        panic!("expected one order update, got {:?}", events);
+    };
+    assert_eq!(update.exchange, CexExchange::Binance);
+    assert_eq!(update.symbol, "ETHUSDT");
+    assert_eq!(update.order_id, "4293153");
+    assert_eq!(update.client_order_id.as_deref(), Some("my-1"));
+    assert_eq!(update.side, TradeSide::Buy);
+    assert_eq!(update.status, OrderStatus::PartiallyFilled);
+    assert!(!update.status.is_final());
+    assert_eq!(update.price, Some(2000.0));
+    assert_eq!(update.quantity, 2.0);
+    assert_eq!(update.filled_quantity, 0.5);
+    assert_eq!(update.average_fill_price, Some(1999.0));
+    assert_eq!(update.timestamp, Timestamp::from_millis(1_700_000_000_123));
+}
+
+#[test]
+fn binance_account_position_becomes_balance_updates() {
+    let message = json!({
+        "e": "outboundAccountPosition", "E": 1_700_000_000_000u64, "u": 1_700_000_000_000u64,
+        "B": [
+            {"a": "ETH", "f": "10.00000000", "l": "0.00000000"},
+            {"a": "USDT", "f": "500.00000000", "l": "1000.00000000"}
+        ]
+    });
+    let events = Binance::new().parse_user_data(&message);
+    assert_eq!(events.len(), 2);
+    let UserDataEvent::Balance(usdt) = &events[1] else {
+        panic!("expected balance update");
+    };
+    assert_eq!(usdt.asset, "USDT");
+    assert_eq!(usdt.free, 500.0);
+    assert_eq!(usdt.locked, 1000.0);
+
+    // Other events (e.g. listenKeyExpired) carry no updates
+    assert!(
+        Binance::new()
+            .parse_user_data(&json!({"e": "listenKeyExpired", "E": 1}))
+            .is_empty()
+    );
+}
+
+#[test]
+fn bybit_order_and_wallet_topics() {
+    let bybit = Bybit::new();
+    let order = json!({
+        "topic": "order", "creationTime": 1_700_000_000_000u64,
+        "data": [{
+            "category": "spot", "symbol": "BTCUSDT", "orderId": "b-1", "orderLinkId": "",
+            "side": "Sell", "orderType": "Market", "orderStatus": "Filled", "price": "0",
+            "qty": "0.01", "cumExecQty": "0.01", "avgPrice": "60000", "updatedTime": "1700000000500"
+        }]
+    });
+    let events = bybit.parse_user_data(&order);
+    let [UserDataEvent::Order(update)] = events.as_slice() else {
+        panic!("expected one order update, got {:?}", events);
+    };
+    assert_eq!(update.side, TradeSide::Sell);
+    assert_eq!(update.status, OrderStatus::Filled);
+    assert!(update.status.is_final());
+    assert!(update.price.is_none());
+    assert!(update.client_order_id.is_none());
+    assert_eq!(update.average_fill_price, Some(60000.0));
+    assert_eq!(update.timestamp, Timestamp::from_millis(1_700_000_000_500));
+
+    let wallet = json!({
+        "topic": "wallet", "creationTime": 1_700_000_000_000u64,
+        "data": [{"accountType": "UNIFIED", "coin": [
+            {"coin": "USDT", "walletBalance": "1500", "locked": "200"}
+        ]}]
+    });
+    let events = bybit.parse_user_data(&wallet);
+    let [UserDataEvent::Balance(balance)] = events.as_slice() else {
+        panic!("expected one balance update, got {:?}", events);
+    };
+    assert_eq!(balance.exchange, CexExchange::Bybit);
+    assert_eq!(balance.free, 1300.0);
+    assert_eq!(balance.locked, 200.0);
+
+    let cancelled = json!({"topic": "order", "data": [{
+        "symbol": "BTCUSDT", "orderId": "b-2", "side": "Buy",
+        "orderStatus": "PartiallyFilledCanceled", "price": "59000", "qty": "1", "cumExecQty": "0.2"
+    }]});
+    let UserDataEvent::Order(update) = &bybit.parse_user_data(&cancelled)[0] else {
+        panic!("expected order update");
+    };
+    assert_eq!(update.status, OrderStatus::Canceled);
+    assert_eq!(update.filled_quantity, 0.2);
+}
+
+#[test]
+fn okx_orders_and_account_channels() {
+    let okx = OKX::new();
+    let order = json!({
+        "arg": {"channel": "orders", "instType": "SPOT", "uid": "1"},
+        "data": [{
+            "instId": "BTC-USDT", "ordId": "312269865356374016", "clOrdId": "a1",
+            "side": "buy", "ordType": "market", "state": "filled", "px": "",
+            "sz": "0.01", "accFillSz": "0.01", "avgPx": "60100.5", "uTime": "1700000000900"
+        }]
+    });
+    let events = okx.parse_user_data(&order);
+    let [UserDataEvent::Order(update)] = events.as_slice() else {
+        panic!("expected one order update, got {:?}", events);
+    };
+    assert_eq!(update.symbol, "BTCUSDT");
+    assert_eq!(update.order_id, "312269865356374016");
+    assert_eq!(update.status, OrderStatus::Filled);
+    assert!(update.price.is_none());
+    assert_eq!(update.average_fill_price, Some(60100.5));
+
+    let account = json!({
+        "arg": {"channel": "account", "uid": "1"},
+        "data": [{"uTime": "1700000000000", "details": [
+            {"ccy": "BTC", "availBal": "0.51", "frozenBal": "0.1", "uTime": "1700000000001"},
+            {"ccy": "USDT", "availBal": "120.5", "frozenBal": "0", "uTime": "1700000000002"}
+        ]}]
+    });
+    let events = okx.parse_user_data(&account);
+    assert_eq!(events.len(), 2);
+    let UserDataEvent::Balance(btc) = &events[0] else {
+        panic!("expected balance update");
+    };
+    assert_eq!(btc.asset, "BTC");
+    assert_eq!(btc.free, 0.51);
+    assert_eq!(btc.locked, 0.1);
+
+    // Login and subscription acks carry no updates
+    assert!(
+        okx.parse_user_data(&json!({"event": "login", "code": "0", "msg": ""}))
+            .is_empty()
+    );
+}
+
+#[tokio::test]
+async fn okx_stream_needs_a_passphrase() {
+    let result = OKX::new()
+        .stream_user_data(&Credentials::new("k", "s"), TradingMode::Demo, 0, 0)
+        .await;
+    assert!(matches!(result, Err(MarketScannerError::InvalidConfig(_))));
+}
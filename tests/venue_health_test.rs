@@ -0,0 +1,27 @@
+use aeon_market_scanner_rs::common::record_ws_latency;
+use aeon_market_scanner_rs::{CexExchange, all_venue_health, venue_health};
+use std::time::Duration;
+
+#[test]
+fn ws_latency_gauge_tracks_samples() {
+    assert!(venue_health(&CexExchange::OKX).ws_latency.is_none());
+
+    record_ws_latency(&CexExchange::OKX, Duration::from_millis(100));
+    record_ws_latency(&CexExchange::OKX, Duration::from_millis(200));
+
+    let health = venue_health(&CexExchange::OKX);
+    let gauge = health.ws_latency.expect("OKX latency should be recorded");
+    assert_eq!(gauge.samples, 2);
+    assert_eq!(gauge.last_ms, 200.0);
+    assert_eq!(gauge.min_ms, 100.0);
+    assert_eq!(gauge.max_ms, 200.0);
+    // EWMA moves towards the newest sample without jumping to it
+    assert!(gauge.avg_ms > 100.0 && gauge.avg_ms < 200.0);
+    assert!(health.updated_at > 0);
+
+    assert!(
+        all_venue_health()
+            .iter()
+            .any(|h| h.exchange == CexExchange::OKX)
+    );
+}
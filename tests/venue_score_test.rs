@@ -0,0 +1,130 @@
+mod scanner_common;
+
+use aeon_market_scanner_rs::common::{CexPrice, Timestamp, record_ws_latency};
+use aeon_market_scanner_rs::{
+    ArbitrageScanner, CexExchange, FeeOverrides, MaintenanceMonitor, OpportunityReport,
+    OpportunitySummary, VenueScoreWeights, VenueScorer, VenueStatus,
+};
+use scanner_common::sized_price;
+use std::time::Duration;
+
+fn prices() -> Vec<CexPrice> {
+    vec![
+        sized_price(CexExchange::Binance, "BTCUSDT", 99.0, 100.0, 10.0),
+        sized_price(CexExchange::OKX, "BTCUSDT", 102.0, 103.0, 5.0),
+        sized_price(CexExchange::Kraken, "BTCUSDT", 101.0, 102.0, 1.0),
+        // Other symbols are ignored
+        sized_price(CexExchange::Bybit, "ETHUSDT", 9.0, 10.0, 1_000.0),
+    ]
+}
+
+fn report(source: &str, destination: &str, profit: f64) -> OpportunityReport {
+    OpportunityReport {
+        window_start: Timestamp::from_millis(0),
+        window_end: Timestamp::from_millis(60_000),
+        total_count: 1,
+        entries: vec![OpportunitySummary {
+            source_exchange: source.to_string(),
+            destination_exchange: destination.to_string(),
+            symbol: "BTCUSDT".to_string(),
+            count: 1,
+            avg_spread_percentage: 1.0,
+            max_spread_percentage: 1.0,
+            cumulative_profit_quote: profit,
+        }],
+    }
+}
+
+fn only(weights: fn(&mut VenueScoreWeights)) -> VenueScoreWeights {
+    let mut w = VenueScoreWeights {
+        health: 0.0,
+        latency: 0.0,
+        liquidity: 0.0,
+        opportunity: 0.0,
+    };
+    weights(&mut w);
+    w
+}
+
+#[test]
+fn liquidity_and_history_are_relative_to_the_best_candidate() {
+    let scorer = VenueScorer::new()
+        .with_report(&report("Kraken", "OKX", 40.0))
+        .with_report(&report("OKX", "Binance", 10.0));
+
+    let scores = scorer.score("BTCUSDT", &prices());
+    assert_eq!(scores.len(), 3);
+    let binance = scores
+        .iter()
+        .find(|s| s.exchange == CexExchange::Binance)
+        .unwrap();
+    assert_eq!(binance.liquidity, 1.0);
+    assert!((binance.opportunity - 0.2).abs() < 1e-9);
+    let okx = scores
+        .iter()
+        .find(|s| s.exchange == CexExchange::OKX)
+        .unwrap();
+    assert!((okx.liquidity - 5.0 * 102.5 / 995.0).abs() < 1e-9);
+    assert_eq!(okx.opportunity, 1.0);
+
+    let by_history = scorer.clone().with_weights(only(|w| w.opportunity = 1.0));
+    assert_eq!(
+        by_history.select("BTCUSDT", &prices(), 2),
+        [CexExchange::OKX, CexExchange::Kraken]
+    );
+    let by_liquidity = scorer.with_weights(only(|w| w.liquidity = 1.0));
+    assert_eq!(
+        by_liquidity.select("BTCUSDT", &prices(), 2),
+        [CexExchange::Binance, CexExchange::OKX]
+    );
+}
+
+#[test]
+fn latency_and_maintenance_lower_the_score() {
+    record_ws_latency(&CexExchange::Kraken, Duration::from_millis(100));
+    let scorer = VenueScorer::new().with_weights(only(|w| w.latency = 1.0));
+    let scores = scorer.score("BTCUSDT", &prices());
+    let kraken = scores
+        .iter()
+        .find(|s| s.exchange == CexExchange::Kraken)
+        .unwrap();
+    assert!((kraken.latency - 0.5).abs() < 1e-9);
+    assert_eq!(kraken.health, 1.0);
+
+    let monitor = MaintenanceMonitor::new();
+    monitor.set_status(&CexExchange::Binance, VenueStatus::Maintenance);
+    let scorer = VenueScorer::new().with_maintenance_monitor(monitor);
+    let scores = scorer.score("BTCUSDT", &prices());
+    let binance = scores
+        .iter()
+        .find(|s| s.exchange == CexExchange::Binance)
+        .unwrap();
+    assert_eq!(binance.health, 0.0);
+    assert!(
+        !scorer
+            .select("BTCUSDT", &prices(), 3)
+            .contains(&CexExchange::Binance)
+    );
+}
+
+#[test]
+fn auto_scan_only_matches_selected_venues() {
+    let fees = FeeOverrides::default()
+        .with_cex_taker_fee(CexExchange::Binance, 0.0)
+        .with_cex_taker_fee(CexExchange::OKX, 0.0)
+        .with_cex_taker_fee(CexExchange::Kraken, 0.0);
+    let scorer = VenueScorer::new().with_weights(only(|w| w.liquidity = 1.0));
+
+    let scan =
+        ArbitrageScanner::auto_scan_from_prices("BTCUSDT", &prices(), 2, &scorer, Some(&fees));
+    assert_eq!(scan.scores.len(), 3);
+    assert_eq!(scan.selected, [CexExchange::Binance, CexExchange::OKX]);
+    assert!(!scan.opportunities.is_empty());
+    assert!(
+        scan.opportunities
+            .iter()
+            .all(|o| o.source_exchange != "Kraken" && o.destination_exchange != "Kraken")
+    );
+    assert_eq!(scan.opportunities[0].source_exchange, "Binance");
+    assert_eq!(scan.opportunities[0].destination_exchange, "OKX");
+}
@@ -0,0 +1,75 @@
+mod scanner_common;
+
+use aeon_market_scanner_rs::{
+    ArbitrageScanner, CexExchange, MarketKey, VolatilityGuard, VolatilityGuardConfig,
+};
+use scanner_common::{binance_okx_fees, price_at};
+
+fn config() -> VolatilityGuardConfig {
+    VolatilityGuardConfig::default()
+        .with_window_ms(10_000)
+        .with_min_samples(4)
+        .with_threshold_percent(0.2)
+        .with_min_spread_percent(2.0)
+}
+
+fn btc() -> MarketKey {
+    MarketKey::from_symbol("BTCUSDT")
+}
+
+#[test]
+fn calm_market_keeps_default_spread() {
+    let mut guard = VolatilityGuard::new(config());
+    for (i, mid) in [100.0, 100.01, 100.0, 100.01, 100.0].iter().enumerate() {
+        guard.record(&price_at(CexExchange::Binance, *mid, i as u64 * 1000));
+    }
+    let vol = guard.realized_volatility(&btc()).expect("enough samples");
+    assert!(vol < 0.2);
+    assert!(!guard.is_volatile(&btc()));
+    assert_eq!(guard.min_spread_percent(&btc()), 0.0);
+}
+
+#[test]
+fn volatile_market_requires_larger_spread() {
+    let mut guard = VolatilityGuard::new(config());
+    // Not enough returns yet
+    guard.record(&price_at(CexExchange::Binance, 100.0, 0));
+    guard.record(&price_at(CexExchange::Binance, 101.0, 1000));
+    assert!(guard.realized_volatility(&btc()).is_none());
+
+    for (i, mid) in [99.0, 101.5, 98.5].iter().enumerate() {
+        guard.record(&price_at(
+            CexExchange::Binance,
+            *mid,
+            2000 + i as u64 * 1000,
+        ));
+    }
+    assert!(guard.is_volatile(&btc()));
+    assert_eq!(guard.min_spread_percent(&btc()), 2.0);
+
+    // A 1% opportunity is dropped while the market is volatile
+    let fees = binance_okx_fees();
+    let prices = [
+        price_at(CexExchange::Binance, 100.0, 5000),
+        price_at(CexExchange::OKX, 102.0, 5000),
+    ];
+    let mut opps = ArbitrageScanner::opportunities_from_prices(&prices, &[], Some(&fees));
+    assert_eq!(opps.len(), 1);
+    guard.filter(&mut opps);
+    assert!(opps.is_empty());
+}
+
+#[test]
+fn old_ticks_leave_the_window() {
+    let mut guard = VolatilityGuard::new(config());
+    for (i, mid) in [100.0, 103.0, 97.0, 103.0, 97.0].iter().enumerate() {
+        guard.record(&price_at(CexExchange::Binance, *mid, i as u64 * 1000));
+    }
+    assert!(guard.is_volatile(&btc()));
+
+    // 20 s later only calm ticks remain in the 10 s window
+    for i in 0..5 {
+        guard.record(&price_at(CexExchange::Binance, 100.0, 24_000 + i * 1000));
+    }
+    assert!(!guard.is_volatile(&btc()));
+}
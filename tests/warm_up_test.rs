@@ -0,0 +1,104 @@
+use aeon_market_scanner_rs::{
+    ArbitrageScanner, CexExchange, DexAggregator, Exchange, MarketScannerError, ReadinessReport,
+    Timestamp, VenueReadiness, WarmUpOptions, WarmUpStep,
+};
+
+fn venue(exchange: CexExchange, rest: WarmUpStep, websocket: WarmUpStep) -> VenueReadiness {
+    VenueReadiness {
+        exchange: Exchange::Cex(exchange),
+        dns: WarmUpStep::Ready { elapsed_ms: 1 },
+        rest,
+        websocket,
+    }
+}
+
+#[test]
+fn test_readiness_report_requires_rest_and_no_failed_step() {
+    let report = ReadinessReport {
+        venues: vec![
+            venue(
+                CexExchange::Binance,
+                WarmUpStep::Ready { elapsed_ms: 40 },
+                WarmUpStep::Skipped,
+            ),
+            venue(
+                CexExchange::OKX,
+                WarmUpStep::Ready { elapsed_ms: 55 },
+                WarmUpStep::Failed {
+                    error: "timed out".to_string(),
+                },
+            ),
+            venue(
+                CexExchange::Kraken,
+                WarmUpStep::Skipped,
+                WarmUpStep::Skipped,
+            ),
+        ],
+        elapsed_ms: 60,
+        completed_at: Timestamp::now(),
+    };
+
+    assert!(!report.is_ready());
+    assert_eq!(
+        report.ready_venues(),
+        vec![Exchange::Cex(CexExchange::Binance)]
+    );
+    assert_eq!(
+        report.not_ready_venues(),
+        vec![
+            Exchange::Cex(CexExchange::OKX),
+            Exchange::Cex(CexExchange::Kraken)
+        ]
+    );
+    assert!(
+        report
+            .venue(&Exchange::Cex(CexExchange::OKX))
+            .unwrap()
+            .websocket
+            .is_failed()
+    );
+
+    let json = serde_json::to_string(&report).unwrap();
+    let back: ReadinessReport = serde_json::from_str(&json).unwrap();
+    assert_eq!(back, report);
+}
+
+#[tokio::test]
+async fn test_warm_up_requires_symbols() {
+    let result =
+        ArbitrageScanner::warm_up(&[], &[CexExchange::Binance], WarmUpOptions::default()).await;
+    assert!(matches!(result, Err(MarketScannerError::InvalidSymbol(_))));
+}
+
+#[tokio::test]
+async fn test_warm_up_reports_every_venue() {
+    let options = WarmUpOptions {
+        dex_exchanges: vec![DexAggregator::KyberSwap],
+        ..WarmUpOptions::default()
+    }
+    .with_timeout_ms(3000);
+    let report = ArbitrageScanner::warm_up(
+        &["BTCUSDT"],
+        &[CexExchange::Binance, CexExchange::OKX],
+        options,
+    )
+    .await
+    .expect("warm-up should always return a report");
+
+    assert_eq!(report.venues.len(), 3);
+    for exchange in [CexExchange::Binance, CexExchange::OKX] {
+        let readiness = report
+            .venue(&Exchange::Cex(exchange))
+            .expect("every CEX should be reported");
+        // WebSockets were not requested
+        assert_eq!(readiness.websocket, WarmUpStep::Skipped);
+        assert_ne!(readiness.rest, WarmUpStep::Skipped);
+    }
+
+    // DEX venue without a configured quote cannot be ready
+    let kyber = report
+        .venue(&Exchange::Dex(DexAggregator::KyberSwap))
+        .unwrap();
+    assert!(kyber.rest.is_failed());
+    assert!(!report.is_ready());
+}
@@ -0,0 +1,72 @@
+use aeon_market_scanner_rs::CexExchange;
+use aeon_market_scanner_rs::common::{
+    RawWsFrame, RawWsPayload, Timestamp, WsCaptureFile, clear_ws_capture_hook, decode_ws_json,
+    read_ws_capture, set_ws_capture_hook, ws_capture_enabled,
+};
+use std::sync::{Arc, Mutex};
+
+/// The capture hook is process-wide; tests that set it run one at a time.
+static HOOK: Mutex<()> = Mutex::new(());
+
+#[test]
+fn hook_receives_raw_frames_before_decoding() {
+    let _guard = HOOK.lock().unwrap_or_else(|e| e.into_inner());
+    let captured: Arc<Mutex<Vec<RawWsFrame>>> = Arc::default();
+    let sink = captured.clone();
+    set_ws_capture_hook(move |frame| sink.lock().unwrap().push(frame.clone()));
+    assert!(ws_capture_enabled());
+
+    let decoded: Option<serde_json::Value> = decode_ws_json(&CexExchange::OKX, r#"{"a":1}"#);
+    assert!(decoded.is_some());
+    // Frames that fail to decode are captured too: those are the interesting ones
+    let broken: Option<serde_json::Value> = decode_ws_json(&CexExchange::Kraken, "{oops");
+    assert!(broken.is_none());
+
+    clear_ws_capture_hook();
+    assert!(!ws_capture_enabled());
+    let _: Option<serde_json::Value> = decode_ws_json(&CexExchange::OKX, "{}");
+
+    let captured = captured.lock().unwrap();
+    assert_eq!(captured.len(), 2);
+    assert_eq!(captured[0].exchange, CexExchange::OKX);
+    assert_eq!(captured[0].text(), Some(r#"{"a":1}"#));
+    assert_eq!(captured[1].text(), Some("{oops"));
+}
+
+#[test]
+fn file_sink_round_trips_text_and_binary_frames() {
+    let _guard = HOOK.lock().unwrap_or_else(|e| e.into_inner());
+    let path = std::env::temp_dir().join(format!("aeon_ws_capture_{}.jsonl", std::process::id()));
+    let file = WsCaptureFile::create(&path)
+        .unwrap()
+        .with_venues(&[CexExchange::MEXC, CexExchange::Binance]);
+    let binary = RawWsFrame {
+        exchange: CexExchange::MEXC,
+        received_at: Timestamp::from_millis(1_700_000_000_000),
+        payload: RawWsPayload::Binary(vec![0, 159, 255]),
+    };
+    file.write(&binary).unwrap();
+    let line = std::fs::read_to_string(&path).unwrap();
+    assert!(line.contains(r#""binary":"AJ//""#), "{line}");
+
+    file.install();
+    let _: Option<serde_json::Value> = decode_ws_json(&CexExchange::Binance, r#"{"b":"1"}"#);
+    // Not a captured venue
+    let _: Option<serde_json::Value> = decode_ws_json(&CexExchange::OKX, "{}");
+    clear_ws_capture_hook();
+
+    let frames = read_ws_capture(&path).unwrap();
+    assert_eq!(frames.len(), 2);
+    assert_eq!(frames[0], binary);
+    assert_eq!(frames[1].exchange, CexExchange::Binance);
+    assert_eq!(frames[1].text(), Some(r#"{"b":"1"}"#));
+
+    // Appending keeps earlier frames
+    WsCaptureFile::append(&path)
+        .unwrap()
+        .write(&binary)
+        .unwrap();
+    assert_eq!(read_ws_capture(&path).unwrap().len(), 3);
+    std::fs::remove_file(&path).ok();
+    assert!(read_ws_capture(&path).is_err());
+}
@@ -0,0 +1,112 @@
+//! Reconnect behavior of every WebSocket venue against a local server that accepts the
+//! handshake and closes each connection at once.
+
+use aeon_market_scanner_rs::{
+    CexExchange, CexWebSocketSource, PriceSource, ReconnectPolicy, clear_ws_endpoint_override,
+    set_ws_endpoint_override,
+};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::net::TcpListener;
+
+const WS_VENUES: [CexExchange; 12] = [
+    CexExchange::Binance,
+    CexExchange::Bybit,
+    CexExchange::MEXC,
+    CexExchange::OKX,
+    CexExchange::Gateio,
+    CexExchange::Kucoin,
+    CexExchange::Bitget,
+    CexExchange::Coinbase,
+    CexExchange::Kraken,
+    CexExchange::Bitfinex,
+    CexExchange::Upbit,
+    CexExchange::Cryptocom,
+];
+
+/// Local WebSocket server dropping every connection; returns its URL and connection times.
+async fn dropping_server() -> (String, Arc<Mutex<Vec<Instant>>>) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("ws://{}", listener.local_addr().unwrap());
+    let connections = Arc::new(Mutex::new(Vec::new()));
+    let seen = connections.clone();
+    tokio::spawn(async move {
+        while let Ok((stream, _)) = listener.accept().await {
+            let seen = seen.clone();
+            tokio::spawn(async move {
+                if let Ok(mut ws) = tokio_tungstenite::accept_async(stream).await {
+                    seen.lock().unwrap().push(Instant::now());
+                    let _ = ws.close(None).await;
+                }
+            });
+        }
+    });
+    (url, connections)
+}
+
+/// Streams `exchange` against a dropping server until the stream gives up; returns the
+/// connection times.
+async fn run_until_given_up(exchange: CexExchange, attempts: u32, delay_ms: u64) -> Vec<Instant> {
+    let (url, connections) = dropping_server().await;
+    set_ws_endpoint_override(&exchange, &url);
+    let source =
+        CexWebSocketSource::new(exchange.clone(), &["BTCUSDT"]).with_reconnect(attempts, delay_ms);
+    let mut rx = Box::new(source).start().await.unwrap();
+    let ended = tokio::time::timeout(Duration::from_secs(20), rx.recv()).await;
+    clear_ws_endpoint_override(&exchange);
+    assert!(
+        matches!(ended, Ok(None)),
+        "{exchange:?}: stream should end after its reconnects"
+    );
+    connections.lock().unwrap().clone()
+}
+
+#[test]
+fn policy_counts_the_initial_run_plus_reconnects() {
+    let policy = ReconnectPolicy::new(2, 0);
+    assert_eq!(policy.delay(), Duration::from_millis(1000));
+    assert!(policy.should_reconnect(1));
+    assert!(policy.should_reconnect(2));
+    assert!(!policy.should_reconnect(3));
+    assert_eq!(policy.max_connections(), 3);
+
+    let none = ReconnectPolicy::new(0, 250);
+    assert_eq!(none.delay(), Duration::from_millis(250));
+    assert!(!none.should_reconnect(1));
+    assert_eq!(none.max_connections(), 1);
+}
+
+#[tokio::test]
+async fn venues_count_attempts_and_wait_identically_under_forced_disconnects() {
+    let delay_ms = 150;
+    let runs = futures::future::join_all(
+        WS_VENUES
+            .iter()
+            .map(|exchange| run_until_given_up(exchange.clone(), 2, delay_ms)),
+    )
+    .await;
+    for (exchange, times) in WS_VENUES.iter().zip(runs) {
+        assert_eq!(
+            times.len(),
+            3,
+            "{exchange:?}: initial connection + 2 reconnects"
+        );
+        for pair in times.windows(2) {
+            assert!(
+                pair[1] - pair[0] >= Duration::from_millis(delay_ms),
+                "{exchange:?}: reconnected before the delay"
+            );
+        }
+    }
+
+    // Overrides are global, so both runs share one test
+    let runs = futures::future::join_all(
+        WS_VENUES
+            .iter()
+            .map(|exchange| run_until_given_up(exchange.clone(), 0, 50)),
+    )
+    .await;
+    for (exchange, times) in WS_VENUES.iter().zip(runs) {
+        assert_eq!(times.len(), 1, "{exchange:?}: 0 attempts = no reconnect");
+    }
+}
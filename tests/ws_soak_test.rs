@@ -0,0 +1,106 @@
+use aeon_market_scanner_rs::common::{Timestamp, record_ws_connect, venue_health};
+use aeon_market_scanner_rs::scanner::{
+    MemorySample, WsSoakRecorder, WsStabilityReport, resident_memory_bytes,
+};
+use aeon_market_scanner_rs::{ArbitrageScanner, CexExchange, MarketScannerError, WsSoakOptions};
+use std::time::Duration;
+
+fn at(secs: u64) -> Timestamp {
+    Timestamp::from_millis(secs * 1000)
+}
+
+#[test]
+fn test_recorder_counts_messages_gaps_and_reconnects() {
+    // Connections before the soak are the baseline
+    record_ws_connect(&CexExchange::Bitfinex);
+    let mut recorder = WsSoakRecorder::new(
+        &[CexExchange::Bitfinex, CexExchange::Kraken],
+        Duration::from_secs(30),
+        at(0),
+    );
+    record_ws_connect(&CexExchange::Bitfinex);
+    for secs in [50, 60, 70, 150, 160] {
+        recorder.record_message(&CexExchange::Bitfinex, at(secs));
+    }
+    record_ws_connect(&CexExchange::Bitfinex);
+    recorder.record_error(&CexExchange::Kraken, "no WebSocket support");
+
+    let report = recorder.report(at(170));
+    let bitfinex = report.venue(&CexExchange::Bitfinex).unwrap();
+    assert_eq!(bitfinex.messages, 5);
+    // Silence while connecting is not a gap; 70 → 150 is
+    assert_eq!(bitfinex.gaps, 1);
+    assert_eq!(bitfinex.max_gap_ms, 80_000);
+    assert_eq!(bitfinex.connections, 2);
+    assert_eq!(bitfinex.reconnects(), 1);
+    assert_eq!(bitfinex.first_message_at, Some(at(50)));
+    assert_eq!(bitfinex.last_message_at, Some(at(160)));
+    assert!(bitfinex.error.is_none());
+    let kraken = report.venue(&CexExchange::Kraken).unwrap();
+    assert_eq!(kraken.messages, 0);
+    assert_eq!(kraken.error.as_deref(), Some("no WebSocket support"));
+
+    // A venue silent at the end gets a trailing gap
+    let report = recorder.report(at(400));
+    let bitfinex = report.venue(&CexExchange::Bitfinex).unwrap();
+    assert_eq!(bitfinex.gaps, 2);
+    assert_eq!(bitfinex.max_gap_ms, 240_000);
+    assert!(venue_health(&CexExchange::Bitfinex).ws_connections >= 3);
+}
+
+#[test]
+fn test_report_memory_and_text() {
+    let report = WsStabilityReport {
+        started_at: at(0),
+        ended_at: at(7200),
+        venues: Vec::new(),
+        memory: vec![
+            MemorySample {
+                at: at(0),
+                resident_bytes: Some(10 * 1024 * 1024),
+            },
+            MemorySample {
+                at: at(3600),
+                resident_bytes: None,
+            },
+            MemorySample {
+                at: at(7200),
+                resident_bytes: Some(12 * 1024 * 1024),
+            },
+        ],
+    };
+    assert_eq!(report.memory_growth_bytes(), Some(2 * 1024 * 1024));
+    let text = report.to_string();
+    assert!(text.contains("over 2.00 h"));
+    assert!(text.contains("+2.0 MiB growth over 2 samples"));
+
+    let mut recorder = WsSoakRecorder::new(&[], Duration::from_secs(30), at(0));
+    recorder.sample_memory(at(1));
+    let sample = recorder.report(at(2)).memory[0];
+    assert_eq!(
+        sample.resident_bytes.is_some(),
+        resident_memory_bytes().is_some()
+    );
+}
+
+#[tokio::test]
+async fn test_run_ws_soak_validates_options() {
+    let result = ArbitrageScanner::run_ws_soak(
+        &[CexExchange::Binance],
+        &["BTCUSDT"],
+        WsSoakOptions::new(Duration::ZERO),
+    )
+    .await;
+    assert!(matches!(result, Err(MarketScannerError::InvalidConfig(_))));
+
+    // Venues without WebSocket support are reported, not fatal
+    let report = ArbitrageScanner::run_ws_soak(
+        &[CexExchange::Btcturk],
+        &["BTCUSDT"],
+        WsSoakOptions::new(Duration::from_millis(50)),
+    )
+    .await
+    .unwrap();
+    assert!(report.venues[0].error.is_some());
+    assert!(report.memory.len() >= 2);
+}
@@ -0,0 +1,27 @@
+mod scanner_common;
+
+use aeon_market_scanner_rs::CexExchange;
+use aeon_market_scanner_rs::common::{WsSymbolFilter, set_ws_symbol_filter_enabled};
+use scanner_common::mid_price;
+
+#[test]
+fn filter_allows_only_subscribed_markets() {
+    let filter = WsSymbolFilter::new(&CexExchange::Bitfinex, &["BTCUSDT", "ETHUSDT"]);
+    // Bitfinex spelling of a subscribed market
+    assert!(filter.allows(&mid_price(CexExchange::Bitfinex, "BTCUST", 100.0)));
+    assert!(filter.allows(&mid_price(CexExchange::Bitfinex, "ETHUSDT", 100.0)));
+    // Pushed after a resubscribe but never requested
+    assert!(!filter.allows(&mid_price(CexExchange::Bitfinex, "SOLUST", 100.0)));
+    assert!(!filter.allows(&mid_price(CexExchange::Bitfinex, "BTCUSD", 100.0)));
+
+    // Upbit USD requests are served by the KRW market
+    let upbit = WsSymbolFilter::new(&CexExchange::Upbit, &["BTCUSD"]);
+    assert!(upbit.allows(&mid_price(CexExchange::Upbit, "BTCKRW", 100.0)));
+    assert!(!upbit.allows(&mid_price(CexExchange::Upbit, "ETHKRW", 100.0)));
+
+    // Disabling the filter lets everything through
+    set_ws_symbol_filter_enabled(false);
+    assert!(upbit.allows(&mid_price(CexExchange::Upbit, "ETHKRW", 100.0)));
+    set_ws_symbol_filter_enabled(true);
+    assert!(!upbit.allows(&mid_price(CexExchange::Upbit, "ETHKRW", 100.0)));
+}
@@ -0,0 +1,84 @@
+mod scanner_common;
+
+use aeon_market_scanner_rs::common::{OrderBook, OrderBookLevel};
+use aeon_market_scanner_rs::{ArbitrageScanner, CexExchange, Exchange, Timestamp, ZeroSizePolicy};
+use scanner_common::{binance_okx_fees, binance_to_okx_with_depth};
+
+fn book(exchange: CexExchange, bids: &[(f64, f64)], asks: &[(f64, f64)]) -> OrderBook {
+    let levels = |levels: &[(f64, f64)]| {
+        levels
+            .iter()
+            .map(|&(price, quantity)| OrderBookLevel { price, quantity })
+            .collect()
+    };
+    OrderBook {
+        symbol: "BTCUSDT".to_string(),
+        bids: levels(bids),
+        asks: levels(asks),
+        timestamp: Timestamp::from_millis(1),
+        exchange: Exchange::Cex(exchange),
+    }
+}
+
+#[test]
+fn zero_size_legs_are_flagged() {
+    let locked = binance_to_okx_with_depth(0.0);
+    assert_eq!(locked.executable_quantity, 0.0);
+    assert!(locked.has_zero_size_leg());
+    assert!(!binance_to_okx_with_depth(0.5).has_zero_size_leg());
+}
+
+#[tokio::test]
+async fn drop_policy_leaves_out_zero_size_opportunities() {
+    let opportunities = vec![
+        binance_to_okx_with_depth(0.0),
+        binance_to_okx_with_depth(0.5),
+    ];
+    let kept =
+        ArbitrageScanner::apply_zero_size_policy(opportunities.clone(), ZeroSizePolicy::Keep, None)
+            .await;
+    assert_eq!(kept.len(), 2);
+    let dropped =
+        ArbitrageScanner::apply_zero_size_policy(opportunities, ZeroSizePolicy::Drop, None).await;
+    assert_eq!(dropped.len(), 1);
+    assert_eq!(dropped[0].executable_quantity, 0.5);
+}
+
+#[test]
+fn zero_size_opportunities_are_sized_from_profitable_depth() {
+    let books = [
+        // Empty top level, then 0.4 @ 101 and 5 @ 112 (no longer profitable)
+        book(
+            CexExchange::Binance,
+            &[(99.0, 1.0)],
+            &[(100.0, 0.0), (101.0, 0.4), (112.0, 5.0)],
+        ),
+        book(
+            CexExchange::OKX,
+            &[(110.0, 0.3), (109.0, 1.0)],
+            &[(111.0, 2.0)],
+        ),
+    ];
+    let sized = ArbitrageScanner::resize_from_books(
+        vec![
+            binance_to_okx_with_depth(0.0),
+            binance_to_okx_with_depth(0.5),
+        ],
+        &books,
+        Some(&binance_okx_fees()),
+    );
+    assert_eq!(sized.len(), 2);
+    assert!((sized[0].executable_quantity - 0.4).abs() < 1e-12);
+    assert!(!sized[0].has_zero_size_leg());
+    assert_eq!(sized[1].executable_quantity, 0.5);
+
+    // Without a book of both legs there is nothing to size from
+    assert!(
+        ArbitrageScanner::resize_from_books(
+            vec![binance_to_okx_with_depth(0.0)],
+            &books[..1],
+            None
+        )
+        .is_empty()
+    );
+}